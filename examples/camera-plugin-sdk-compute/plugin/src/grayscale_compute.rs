@@ -192,6 +192,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
             color_info: frame.color_info.clone(),
             mastering_display: frame.mastering_display.clone(),
             content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
         };
         self.outputs.write("video_out", &output_frame)?;
         self.frame_count.fetch_add(1, Ordering::Relaxed);
@@ -718,6 +718,7 @@ fn compose_one_frame(
         color_info: output_color_info,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     };
     outputs.write("video_out", &output_frame)?;
 
@@ -1016,6 +1017,7 @@ fn slot_videoframe(surface_id: &str, width: u32, height: u32) -> VideoFrame {
         color_info: None,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     }
 }
 
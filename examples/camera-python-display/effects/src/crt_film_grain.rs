@@ -237,6 +237,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for CrtFilmGrainPr
             color_info: frame.color_info.clone(),
             mastering_display: frame.mastering_display.clone(),
             content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
         };
         self.outputs.write("video_out", &output_frame)?;
         self.frame_count.fetch_add(1, Ordering::Relaxed);
@@ -365,5 +366,6 @@ fn synth_slot_videoframe(surface_id: &str, width: u32, height: u32) -> VideoFram
         color_info: None,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     }
 }
@@ -9,23 +9,19 @@
 //!
 //! This example is intentionally a no-op at HEAD. Its real implementation
 //! (preserved in git history before the registry-only migration) cannot yet
-//! be a standalone example: `@tatolab/screen-capture` is
-//! **Apple-only** (macOS/iOS via ScreenCaptureKit) — there is no Linux
-//! screen-capture backend — and the pipeline still uses the deprecated
+//! be a standalone example: the pipeline still uses the deprecated
 //! compile-time typed-struct API rather than the runtime graph API
-//! (`add_processor` + `processor_type_ref!`).
+//! (`add_processor` + `processor_type_ref!`). `@tatolab/screen-capture`
+//! itself runs on Linux (xdg-desktop-portal + PipeWire).
 //!
-//! When a Linux screen-capture backend lands, restore the
-//! `ScreenCapture → Mp4Writer` pipeline from git history and reference each
-//! processor with `processor_type_ref!` (no version, no load call); each
-//! provider's package resolves from this app's `streamlib_modules/` folder,
-//! populated by `./setup.sh`.
+//! To restore it, bring the `ScreenCapture → Mp4Writer` pipeline back from
+//! git history and reference each processor with `processor_type_ref!` (no
+//! version, no load call); each provider's package resolves from this app's
+//! `streamlib_modules/` folder, populated by `./setup.sh`.
 
 fn main() {
     eprintln!(
-        "screen-recorder is deferred and currently a no-op — screen capture is \
-         macOS/iOS-only (ScreenCaptureKit) with no Linux backend yet, and the \
-         example has no registry-only path. See the module-level note in \
-         src/main.rs."
+        "screen-recorder is deferred and currently a no-op — the example has \
+         no registry-only path yet. See the module-level note in src/main.rs."
     );
 }
@@ -0,0 +1,6 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+fn main() {
+    streamlib_jtd_codegen::build_rs::run_for_rust_crate();
+}
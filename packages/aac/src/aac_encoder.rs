@@ -0,0 +1,451 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AAC audio encoder — platform codec backend + reactive processor wrapper.
+//!
+//! AudioToolbox encodes on Apple platforms, Fraunhofer FDK AAC on Linux.
+//! Both hand back raw access units; [`AacEncoder`] frames them (ADTS or
+//! raw) and stamps each with the timestamp of the audio it starts with.
+
+use std::collections::VecDeque;
+
+use crate::_generated_::tatolab__aac::aac_encoder_config::{Profile, Transport};
+use crate::_generated_::{AudioFrame, EncodedAudioFrame};
+use crate::adts::{ADTS_HEADER_LEN, adts_header, sampling_frequency_index};
+use serde::{Deserialize, Serialize};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+// ============================================================================
+// AAC ENCODING CONFIGURATION
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AacProfile {
+    /// AAC-LC — the broadcast / MP4 baseline.
+    LowComplexity,
+    /// HE-AAC (AAC-LC + spectral band replication) for low bitrates.
+    HighEfficiency,
+    /// HE-AAC v2 (HE-AAC + parametric stereo); stereo input only.
+    HighEfficiencyV2,
+}
+
+impl AacProfile {
+    fn default_bitrate_bps(self) -> u32 {
+        match self {
+            AacProfile::LowComplexity => 128_000,
+            AacProfile::HighEfficiency => 64_000,
+            AacProfile::HighEfficiencyV2 => 32_000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AacTransport {
+    /// Each access unit behind a 7-byte ADTS header — self-describing, what
+    /// MPEG-TS / HLS segments carry.
+    Adts,
+    /// Bare access units, for muxers that signal the codec configuration
+    /// out of band (MP4 `esds`).
+    Raw,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AacEncoderSettings {
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub bitrate_bps: u32,
+    pub profile: AacProfile,
+    pub transport: AacTransport,
+}
+
+// ============================================================================
+// AUDIO ENCODER TRAIT
+// ============================================================================
+
+pub trait AudioEncoderAac: Send {
+    /// Encode one frame; returns the access units it completed (none while
+    /// the codec is still filling a frame).
+    fn encode(&mut self, frame: &AudioFrame) -> Result<Vec<EncodedAudioFrame>>;
+    /// Drain the codec at end of stream.
+    fn flush(&mut self) -> Result<Vec<EncodedAudioFrame>>;
+    fn config(&self) -> &AacEncoderSettings;
+}
+
+/// A platform AAC codec producing raw access units from interleaved `f32`
+/// samples.
+pub(crate) trait AacBackend: Send {
+    /// Samples per channel in one access unit (1024, 2048 with SBR).
+    fn frame_length(&self) -> u32;
+    /// Codec delay: decoded output lags the input by this many samples.
+    fn priming_samples(&self) -> u32;
+    fn encode(&mut self, samples: &[f32]) -> Result<Vec<Vec<u8>>>;
+    fn flush(&mut self) -> Result<Vec<Vec<u8>>>;
+}
+
+#[cfg(target_os = "linux")]
+fn create_backend(settings: &AacEncoderSettings) -> Result<Box<dyn AacBackend>> {
+    Ok(Box::new(crate::fdk::FdkAacEncoder::new(settings)?))
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn create_backend(settings: &AacEncoderSettings) -> Result<Box<dyn AacBackend>> {
+    Ok(Box::new(crate::audio_toolbox::AudioToolboxAacEncoder::new(
+        settings,
+    )?))
+}
+
+// ============================================================================
+// PACKET TIMESTAMPS
+// ============================================================================
+
+/// Access unit `k` decodes to the input that started `priming_samples`
+/// before sample `k × frame_length`; its timestamp is extrapolated from the
+/// latest input frame starting at or before that sample, so gaps and clock
+/// drift in the input carry through.
+#[derive(Debug, Clone)]
+pub(crate) struct PacketClock {
+    sample_rate: u32,
+    frame_length: u32,
+    priming_samples: u32,
+    /// `(input sample position, timestamp_ns)` of recent input frames,
+    /// oldest first.
+    anchors: VecDeque<(u64, i64)>,
+    input_samples: u64,
+    packets: u64,
+}
+
+impl PacketClock {
+    pub(crate) fn new(sample_rate: u32, frame_length: u32, priming_samples: u32) -> Self {
+        Self {
+            sample_rate,
+            frame_length,
+            priming_samples,
+            anchors: VecDeque::new(),
+            input_samples: 0,
+            packets: 0,
+        }
+    }
+
+    pub(crate) fn push_input(&mut self, timestamp_ns: i64, samples_per_channel: usize) {
+        self.anchors.push_back((self.input_samples, timestamp_ns));
+        self.input_samples += samples_per_channel as u64;
+    }
+
+    pub(crate) fn next_packet_timestamp(&mut self) -> i64 {
+        let position =
+            (self.packets * self.frame_length as u64) as i64 - self.priming_samples as i64;
+        self.packets += 1;
+        while self.anchors.len() > 1 && self.anchors[1].0 as i64 <= position {
+            self.anchors.pop_front();
+        }
+        let (start, timestamp_ns) = self.anchors.front().copied().unwrap_or((0, 0));
+        let offset_ns =
+            (position - start as i64) as i128 * 1_000_000_000 / self.sample_rate as i128;
+        timestamp_ns + offset_ns as i64
+    }
+}
+
+// ============================================================================
+// AAC ENCODER
+// ============================================================================
+
+/// AAC encoder for MP4 / HLS delivery.
+///
+/// # Requirements
+/// - Mono or stereo `AudioFrame`s (HE-AAC v2: stereo only)
+/// - A sample rate AAC can signal (8–96 kHz; with HE-AAC, twice one of
+///   those, e.g. 44.1 / 48 kHz)
+///
+/// Frames can be any length — the codec buffers input into 1024-sample
+/// (2048 with SBR) access units itself, so no rechunker is needed upstream.
+pub struct AacEncoder {
+    settings: AacEncoderSettings,
+    backend: Box<dyn AacBackend>,
+    clock: PacketClock,
+}
+
+impl AacEncoder {
+    pub fn new(settings: AacEncoderSettings) -> Result<Self> {
+        if !(1..=2).contains(&settings.channels) {
+            return Err(Error::Configuration(format!(
+                "AAC encoder supports mono or stereo, got {} channels",
+                settings.channels
+            )));
+        }
+        if settings.profile == AacProfile::HighEfficiencyV2 && settings.channels != 2 {
+            return Err(Error::Configuration(
+                "HE-AAC v2 (parametric stereo) needs stereo input".into(),
+            ));
+        }
+        let core_rate = match settings.profile {
+            AacProfile::LowComplexity => settings.sample_rate,
+            AacProfile::HighEfficiency | AacProfile::HighEfficiencyV2 => settings.sample_rate / 2,
+        };
+        let core_rate_exact = match settings.profile {
+            AacProfile::LowComplexity => true,
+            _ => core_rate * 2 == settings.sample_rate,
+        };
+        if !core_rate_exact || sampling_frequency_index(core_rate).is_err() {
+            return Err(Error::Configuration(format!(
+                "AAC {:?} cannot encode {}Hz. Use AudioResamplerProcessor upstream to convert to 48kHz.",
+                settings.profile, settings.sample_rate
+            )));
+        }
+
+        let backend = create_backend(&settings)?;
+        let clock = PacketClock::new(
+            settings.sample_rate,
+            backend.frame_length(),
+            backend.priming_samples(),
+        );
+
+        tracing::info!(
+            "AacEncoder initialized: {:?}, {}Hz, {} channels, {} kbps, {:?}, {} samples per frame, {} priming samples",
+            settings.profile,
+            settings.sample_rate,
+            settings.channels,
+            settings.bitrate_bps / 1000,
+            settings.transport,
+            backend.frame_length(),
+            backend.priming_samples()
+        );
+
+        Ok(Self {
+            settings,
+            backend,
+            clock,
+        })
+    }
+
+    /// Codec delay in samples per channel — what an MP4 edit list trims.
+    pub fn priming_samples(&self) -> u32 {
+        self.backend.priming_samples()
+    }
+
+    fn packetize(&mut self, packets: Vec<Vec<u8>>) -> Result<Vec<EncodedAudioFrame>> {
+        packets
+            .into_iter()
+            .map(|packet| {
+                let data = match self.settings.transport {
+                    AacTransport::Adts => {
+                        let header = adts_header(
+                            self.settings.profile,
+                            self.settings.sample_rate,
+                            self.settings.channels,
+                            packet.len(),
+                        )?;
+                        let mut data = Vec::with_capacity(ADTS_HEADER_LEN + packet.len());
+                        data.extend_from_slice(&header);
+                        data.extend_from_slice(&packet);
+                        data
+                    }
+                    AacTransport::Raw => packet,
+                };
+                Ok(EncodedAudioFrame {
+                    data,
+                    timestamp_ns: self.clock.next_packet_timestamp().to_string(),
+                    sample_count: self.backend.frame_length(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl AudioEncoderAac for AacEncoder {
+    fn encode(&mut self, frame: &AudioFrame) -> Result<Vec<EncodedAudioFrame>> {
+        if frame.sample_rate != self.settings.sample_rate
+            || frame.channels != self.settings.channels
+        {
+            return Err(Error::Configuration(format!(
+                "AacEncoder configured for {}Hz / {} channels, got {}Hz / {} channels",
+                self.settings.sample_rate,
+                self.settings.channels,
+                frame.sample_rate,
+                frame.channels
+            )));
+        }
+        let timestamp_ns: i64 = frame.timestamp_ns.parse().map_err(|_| {
+            Error::Runtime(format!(
+                "AudioFrame timestamp_ns '{}' is not an integer",
+                frame.timestamp_ns
+            ))
+        })?;
+
+        self.clock
+            .push_input(timestamp_ns, frame.samples.len() / frame.channels as usize);
+        let packets = self.backend.encode(&frame.samples)?;
+        self.packetize(packets)
+    }
+
+    fn flush(&mut self) -> Result<Vec<EncodedAudioFrame>> {
+        let packets = self.backend.flush()?;
+        self.packetize(packets)
+    }
+
+    fn config(&self) -> &AacEncoderSettings {
+        &self.settings
+    }
+}
+
+// ============================================================================
+// PROCESSOR
+// ============================================================================
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/aac/AacEncoder",
+    description = "Encodes AudioFrame to EncodedAudioFrame (AAC-LC / HE-AAC / HE-AAC v2, ADTS or raw access units)",
+    execution = reactive,
+    scheduling = realtime,
+    config = crate::_generated_::AacEncoderConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "Raw mono or stereo audio frames to encode"),
+    output("encoded_audio_out", "@tatolab/core/EncodedAudioFrame", description = "AAC encoded access units"),
+)]
+pub struct AacEncoderProcessor {
+    /// AAC encoder; created on the first frame, which fixes the format.
+    aac_encoder: Option<AacEncoder>,
+
+    /// Access units written.
+    frames_encoded: u64,
+}
+
+impl AacEncoderProcessor::Processor {
+    fn settings_for(&self, sample_rate: u32, channels: u8) -> AacEncoderSettings {
+        let profile = match &self.config.profile {
+            None | Some(Profile::LowComplexity) => AacProfile::LowComplexity,
+            Some(Profile::HighEfficiency) => AacProfile::HighEfficiency,
+            Some(Profile::HighEfficiencyV2) => AacProfile::HighEfficiencyV2,
+        };
+        let transport = match &self.config.transport {
+            None | Some(Transport::Adts) => AacTransport::Adts,
+            Some(Transport::Raw) => AacTransport::Raw,
+        };
+        AacEncoderSettings {
+            sample_rate,
+            channels,
+            bitrate_bps: self
+                .config
+                .bitrate_bps
+                .unwrap_or_else(|| profile.default_bitrate_bps()),
+            profile,
+            transport,
+        }
+    }
+
+    fn write_all(&mut self, encoded: Vec<EncodedAudioFrame>) -> Result<()> {
+        for frame in &encoded {
+            self.outputs.write("encoded_audio_out", frame)?;
+            self.frames_encoded += 1;
+            if self.frames_encoded == 1 {
+                tracing::info!("[AacEncoder] First frame encoded");
+            } else if self.frames_encoded % 500 == 0 {
+                tracing::info!(frames = self.frames_encoded, "[AacEncoder] Encode progress");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AacEncoderProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[AacEncoder] setup() - profile: {:?}, transport: {:?}, bitrate: {:?}",
+            self.config.profile,
+            self.config.transport,
+            self.config.bitrate_bps
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(mut encoder) = self.aac_encoder.take() {
+            let tail = encoder.flush()?;
+            if let Err(e) = self.write_all(tail) {
+                tracing::warn!("[AacEncoder] Dropped final frames at shutdown: {}", e);
+            }
+        }
+        tracing::info!(
+            frames_encoded = self.frames_encoded,
+            "[AacEncoder] Shutting down"
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("audio_in") {
+            return Ok(());
+        }
+        let frame: AudioFrame = self.inputs.read("audio_in")?;
+
+        let format_changed = self.aac_encoder.as_ref().is_some_and(|encoder| {
+            encoder.config().sample_rate != frame.sample_rate
+                || encoder.config().channels != frame.channels
+        });
+        if format_changed && let Some(mut encoder) = self.aac_encoder.take() {
+            tracing::info!(
+                "[AacEncoder] Input changed to {}Hz / {} channels, restarting encoder",
+                frame.sample_rate,
+                frame.channels
+            );
+            let tail = encoder.flush()?;
+            self.write_all(tail)?;
+        }
+        if self.aac_encoder.is_none() {
+            let settings = self.settings_for(frame.sample_rate, frame.channels);
+            self.aac_encoder = Some(AacEncoder::new(settings)?);
+        }
+
+        let encoder = self
+            .aac_encoder
+            .as_mut()
+            .ok_or_else(|| Error::Runtime("AAC encoder not initialized".into()))?;
+        let encoded = encoder.encode(&frame)?;
+        self.write_all(encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packets_are_stamped_from_the_audio_they_start_with() {
+        // 48 kHz, 1024-sample frames, no priming; input in 480-sample
+        // (10 ms) frames.
+        let mut clock = PacketClock::new(48_000, 1024, 0);
+        for i in 0..5 {
+            clock.push_input(1_000_000_000 + i * 10_000_000, 480);
+        }
+        assert_eq!(clock.next_packet_timestamp(), 1_000_000_000);
+        // Sample 1024 is 64 samples into the third input frame.
+        assert_eq!(
+            clock.next_packet_timestamp(),
+            1_020_000_000 + 64 * 1_000_000_000 / 48_000
+        );
+    }
+
+    #[test]
+    fn priming_moves_packets_earlier() {
+        let mut clock = PacketClock::new(48_000, 1024, 2048);
+        clock.push_input(1_000_000_000, 4096);
+        assert_eq!(
+            clock.next_packet_timestamp(),
+            1_000_000_000 - 2048 * 1_000_000_000 / 48_000
+        );
+        clock.next_packet_timestamp();
+        assert_eq!(clock.next_packet_timestamp(), 1_000_000_000);
+    }
+
+    #[test]
+    fn input_gaps_carry_into_packet_timestamps() {
+        let mut clock = PacketClock::new(48_000, 1024, 0);
+        clock.push_input(0, 1024);
+        // 1 s gap before the next frame.
+        clock.push_input(1_000_000_000 + 1024 * 1_000_000_000 / 48_000, 1024);
+        clock.next_packet_timestamp();
+        assert_eq!(
+            clock.next_packet_timestamp(),
+            1_000_000_000 + 1024 * 1_000_000_000 / 48_000
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! ADTS framing — the 7-byte header that makes each raw AAC access unit
+//! self-describing (profile, sample rate, channels, length), as MPEG-TS /
+//! HLS segments and `.aac` files carry it.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::aac_encoder::AacProfile;
+
+pub const ADTS_HEADER_LEN: usize = 7;
+
+/// Largest frame (header included) the 13-bit length field can describe.
+const MAX_ADTS_FRAME_LEN: usize = 0x1FFF;
+
+/// Sampling frequencies with an ADTS / AudioSpecificConfig index.
+const SAMPLING_FREQUENCIES: [u32; 13] = [
+    96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025, 8_000,
+    7_350,
+];
+
+pub fn sampling_frequency_index(sample_rate: u32) -> Result<u8> {
+    SAMPLING_FREQUENCIES
+        .iter()
+        .position(|&rate| rate == sample_rate)
+        .map(|index| index as u8)
+        .ok_or_else(|| {
+            Error::Configuration(format!(
+                "AAC has no sampling frequency index for {}Hz",
+                sample_rate
+            ))
+        })
+}
+
+/// Header for one access unit of `payload_len` bytes. HE-AAC streams are
+/// signalled implicitly, the way decoders without SBR / PS expect them: an
+/// AAC-LC header at the core (half) sample rate, mono core for HE-AAC v2.
+pub fn adts_header(
+    profile: AacProfile,
+    sample_rate: u32,
+    channels: u8,
+    payload_len: usize,
+) -> Result<[u8; ADTS_HEADER_LEN]> {
+    let (core_rate, core_channels) = match profile {
+        AacProfile::LowComplexity => (sample_rate, channels),
+        AacProfile::HighEfficiency => (sample_rate / 2, channels),
+        AacProfile::HighEfficiencyV2 => (sample_rate / 2, 1),
+    };
+    let frequency_index = sampling_frequency_index(core_rate)?;
+    if !(1..=7).contains(&core_channels) {
+        return Err(Error::Configuration(format!(
+            "ADTS cannot describe {} channels",
+            core_channels
+        )));
+    }
+    let frame_len = payload_len + ADTS_HEADER_LEN;
+    if frame_len > MAX_ADTS_FRAME_LEN {
+        return Err(Error::Runtime(format!(
+            "AAC access unit of {} bytes is too large for ADTS",
+            payload_len
+        )));
+    }
+
+    // AAC-LC object type (2) minus one.
+    let profile_bits = 1u8;
+    let frame_len = frame_len as u16;
+    Ok([
+        0xFF,
+        // Sync word low nibble, MPEG-4, layer 0, no CRC.
+        0xF1,
+        (profile_bits << 6) | (frequency_index << 2) | (core_channels >> 2),
+        ((core_channels & 0x3) << 6) | (frame_len >> 11) as u8,
+        (frame_len >> 3) as u8,
+        ((frame_len & 0x7) as u8) << 5 | 0x1F,
+        // Buffer fullness 0x7FF (variable rate), one raw data block.
+        0xFC,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lc_header_carries_rate_channels_and_length() {
+        let header = adts_header(AacProfile::LowComplexity, 48_000, 2, 371).unwrap();
+        assert_eq!(header, [0xFF, 0xF1, 0x4C, 0x80, 0x2F, 0x5F, 0xFC]);
+
+        let frame_len = ((header[3] as usize & 0x3) << 11)
+            | (header[4] as usize) << 3
+            | (header[5] as usize) >> 5;
+        assert_eq!(frame_len, 371 + ADTS_HEADER_LEN);
+    }
+
+    #[test]
+    fn he_headers_describe_the_core_stream() {
+        let he = adts_header(AacProfile::HighEfficiency, 48_000, 2, 100).unwrap();
+        assert_eq!(he[2] >> 6, 1);
+        assert_eq!(
+            (he[2] >> 2) & 0xF,
+            sampling_frequency_index(24_000).unwrap()
+        );
+        assert_eq!(he[3] >> 6, 2);
+
+        let he_v2 = adts_header(AacProfile::HighEfficiencyV2, 44_100, 2, 100).unwrap();
+        assert_eq!(
+            (he_v2[2] >> 2) & 0xF,
+            sampling_frequency_index(22_050).unwrap()
+        );
+        assert_eq!(he_v2[3] >> 6, 1);
+    }
+
+    #[test]
+    fn unrepresentable_streams_are_rejected() {
+        assert!(adts_header(AacProfile::LowComplexity, 47_999, 2, 100).is_err());
+        assert!(adts_header(AacProfile::LowComplexity, 48_000, 8, 100).is_err());
+        assert!(adts_header(AacProfile::LowComplexity, 48_000, 2, 8_185).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AudioToolbox AAC encoder backend (macOS / iOS) — an `AudioConverter`
+//! from interleaved float PCM to AAC packets.
+
+use std::ffi::c_void;
+use std::ptr::{self, NonNull};
+
+use objc2_audio_toolbox::{
+    AudioConverterDispose, AudioConverterFillComplexBuffer, AudioConverterGetProperty,
+    AudioConverterNew, AudioConverterPrimeInfo, AudioConverterRef, AudioConverterSetProperty,
+    kAudioConverterEncodeBitRate, kAudioConverterPrimeInfo,
+    kAudioConverterPropertyMaximumOutputPacketSize,
+};
+use objc2_core_audio_types::{
+    AudioBuffer, AudioBufferList, AudioStreamBasicDescription, AudioStreamPacketDescription,
+    kAudioFormatFlagIsFloat, kAudioFormatFlagIsPacked, kAudioFormatLinearPCM, kAudioFormatMPEG4AAC,
+    kAudioFormatMPEG4AAC_HE, kAudioFormatMPEG4AAC_HE_V2,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::aac_encoder::{AacBackend, AacEncoderSettings, AacProfile};
+
+/// Returned by the input callback when it has no more samples for now:
+/// the converter stops and keeps its partial packet for the next call.
+const STATUS_NEEDS_INPUT: i32 = 0x6e656564; // 'need'
+
+/// Samples the converter pulls through the input callback.
+struct InputFeed {
+    channels: usize,
+    /// Interleaved samples not yet handed to the converter.
+    pending: Vec<f32>,
+    /// The slice last handed over; the converter may read it until the
+    /// callback runs again, so it stays put until then.
+    in_flight: Vec<f32>,
+    /// Drain: report end of stream instead of asking for more input.
+    end_of_stream: bool,
+}
+
+pub(crate) struct AudioToolboxAacEncoder {
+    converter: AudioConverterRef,
+    channels: u32,
+    frame_length: u32,
+    priming_samples: u32,
+    output: Vec<u8>,
+    /// Boxed so the callback's user-data pointer stays valid.
+    feed: Box<InputFeed>,
+}
+
+// SAFETY: the converter is only used through `&mut self`.
+unsafe impl Send for AudioToolboxAacEncoder {}
+
+fn check(what: &str, status: i32) -> Result<()> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(Error::Configuration(format!(
+            "AudioConverter {} failed (OSStatus {})",
+            what, status
+        )))
+    }
+}
+
+impl AudioToolboxAacEncoder {
+    pub(crate) fn new(settings: &AacEncoderSettings) -> Result<Self> {
+        let channels = settings.channels as u32;
+        let bytes_per_frame = channels * size_of::<f32>() as u32;
+        let mut input_format = AudioStreamBasicDescription {
+            mSampleRate: settings.sample_rate as f64,
+            mFormatID: kAudioFormatLinearPCM,
+            mFormatFlags: kAudioFormatFlagIsFloat | kAudioFormatFlagIsPacked,
+            mBytesPerPacket: bytes_per_frame,
+            mFramesPerPacket: 1,
+            mBytesPerFrame: bytes_per_frame,
+            mChannelsPerFrame: channels,
+            mBitsPerChannel: 32,
+            mReserved: 0,
+        };
+        let (format_id, frame_length) = match settings.profile {
+            AacProfile::LowComplexity => (kAudioFormatMPEG4AAC, 1024),
+            AacProfile::HighEfficiency => (kAudioFormatMPEG4AAC_HE, 2048),
+            AacProfile::HighEfficiencyV2 => (kAudioFormatMPEG4AAC_HE_V2, 2048),
+        };
+        let mut output_format = AudioStreamBasicDescription {
+            mSampleRate: settings.sample_rate as f64,
+            mFormatID: format_id,
+            mFormatFlags: 0,
+            mBytesPerPacket: 0,
+            mFramesPerPacket: frame_length,
+            mBytesPerFrame: 0,
+            mChannelsPerFrame: channels,
+            mBitsPerChannel: 0,
+            mReserved: 0,
+        };
+
+        let mut converter: AudioConverterRef = ptr::null_mut();
+        // SAFETY: the formats and the out pointer are locals.
+        check("creation", unsafe {
+            AudioConverterNew(
+                NonNull::from(&mut input_format),
+                NonNull::from(&mut output_format),
+                NonNull::from(&mut converter),
+            )
+        })?;
+        // From here `Drop` disposes the converter on every error path.
+        let mut encoder = Self {
+            converter,
+            channels,
+            frame_length,
+            priming_samples: 0,
+            output: Vec::new(),
+            feed: Box::new(InputFeed {
+                channels: channels as usize,
+                pending: Vec::new(),
+                in_flight: Vec::new(),
+                end_of_stream: false,
+            }),
+        };
+
+        let mut bitrate = settings.bitrate_bps;
+        // SAFETY: `bitrate` is a u32 property value.
+        check("bitrate", unsafe {
+            AudioConverterSetProperty(
+                encoder.converter,
+                kAudioConverterEncodeBitRate,
+                size_of::<u32>() as u32,
+                NonNull::from(&mut bitrate).cast(),
+            )
+        })?;
+
+        let mut max_packet_size = 0u32;
+        let mut size = size_of::<u32>() as u32;
+        // SAFETY: as above.
+        check("maximum packet size", unsafe {
+            AudioConverterGetProperty(
+                encoder.converter,
+                kAudioConverterPropertyMaximumOutputPacketSize,
+                NonNull::from(&mut size),
+                NonNull::from(&mut max_packet_size).cast(),
+            )
+        })?;
+        encoder.output = vec![0; max_packet_size.max(1536 * channels) as usize];
+
+        let mut prime_info = AudioConverterPrimeInfo {
+            leadingFrames: 0,
+            trailingFrames: 0,
+        };
+        let mut size = size_of::<AudioConverterPrimeInfo>() as u32;
+        // SAFETY: as above. Not every encoder reports priming; keep 0.
+        if unsafe {
+            AudioConverterGetProperty(
+                encoder.converter,
+                kAudioConverterPrimeInfo,
+                NonNull::from(&mut size),
+                NonNull::from(&mut prime_info).cast(),
+            )
+        } == 0
+        {
+            encoder.priming_samples = prime_info.leadingFrames;
+        }
+
+        Ok(encoder)
+    }
+
+    /// Pull packets until the converter runs out of input (or, draining,
+    /// out of packets).
+    fn drain_packets(&mut self) -> Result<Vec<Vec<u8>>> {
+        let mut packets = Vec::new();
+        loop {
+            let mut buffer_list = AudioBufferList {
+                mNumberBuffers: 1,
+                mBuffers: [AudioBuffer {
+                    mNumberChannels: self.channels,
+                    mDataByteSize: self.output.len() as u32,
+                    mData: self.output.as_mut_ptr().cast(),
+                }],
+            };
+            let mut packet_count = 1u32;
+            let mut description = AudioStreamPacketDescription {
+                mStartOffset: 0,
+                mVariableFramesInPacket: 0,
+                mDataByteSize: 0,
+            };
+            // SAFETY: the buffer list points at `self.output`; `feed` is
+            // boxed and outlives the call.
+            let status = unsafe {
+                AudioConverterFillComplexBuffer(
+                    self.converter,
+                    Some(supply_input),
+                    (&mut *self.feed as *mut InputFeed).cast(),
+                    NonNull::from(&mut packet_count),
+                    NonNull::from(&mut buffer_list),
+                    &mut description,
+                )
+            };
+            if status != 0 && status != STATUS_NEEDS_INPUT {
+                return Err(Error::Runtime(format!(
+                    "AAC encoding failed (OSStatus {})",
+                    status
+                )));
+            }
+            if packet_count > 0 {
+                let bytes = buffer_list.mBuffers[0].mDataByteSize as usize;
+                packets.push(self.output[..bytes].to_vec());
+            }
+            if status == STATUS_NEEDS_INPUT || packet_count == 0 {
+                return Ok(packets);
+            }
+        }
+    }
+}
+
+/// `AudioConverterComplexInputDataProc`: hand over everything pending.
+unsafe extern "C-unwind" fn supply_input(
+    _converter: AudioConverterRef,
+    io_number_data_packets: NonNull<u32>,
+    io_data: NonNull<AudioBufferList>,
+    _out_packet_descriptions: *mut *mut AudioStreamPacketDescription,
+    user_data: *mut c_void,
+) -> i32 {
+    // SAFETY: `user_data` is the `InputFeed` passed to
+    // AudioConverterFillComplexBuffer, exclusively ours for the call.
+    let feed = unsafe { &mut *user_data.cast::<InputFeed>() };
+    // SAFETY: the converter passes valid in/out pointers.
+    let (requested, buffers) = unsafe {
+        (
+            &mut *io_number_data_packets.as_ptr(),
+            &mut *io_data.as_ptr(),
+        )
+    };
+
+    let available = feed.pending.len() / feed.channels;
+    let frames = available.min(*requested as usize);
+    if frames == 0 {
+        *requested = 0;
+        buffers.mBuffers[0].mDataByteSize = 0;
+        buffers.mBuffers[0].mData = ptr::null_mut();
+        // End of stream is signalled by supplying nothing without error.
+        return if feed.end_of_stream {
+            0
+        } else {
+            STATUS_NEEDS_INPUT
+        };
+    }
+
+    let samples = frames * feed.channels;
+    feed.in_flight.clear();
+    feed.in_flight.extend(feed.pending.drain(..samples));
+    *requested = frames as u32;
+    buffers.mBuffers[0].mNumberChannels = feed.channels as u32;
+    buffers.mBuffers[0].mDataByteSize = (samples * size_of::<f32>()) as u32;
+    buffers.mBuffers[0].mData = feed.in_flight.as_mut_ptr().cast();
+    0
+}
+
+impl AacBackend for AudioToolboxAacEncoder {
+    fn frame_length(&self) -> u32 {
+        self.frame_length
+    }
+
+    fn priming_samples(&self) -> u32 {
+        self.priming_samples
+    }
+
+    fn encode(&mut self, samples: &[f32]) -> Result<Vec<Vec<u8>>> {
+        self.feed.pending.extend_from_slice(samples);
+        self.drain_packets()
+    }
+
+    fn flush(&mut self) -> Result<Vec<Vec<u8>>> {
+        self.feed.end_of_stream = true;
+        self.drain_packets()
+    }
+}
+
+impl Drop for AudioToolboxAacEncoder {
+    fn drop(&mut self) {
+        // SAFETY: `converter` came from AudioConverterNew and is disposed
+        // once.
+        unsafe {
+            AudioConverterDispose(self.converter);
+        }
+    }
+}
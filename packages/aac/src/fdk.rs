@@ -0,0 +1,261 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Fraunhofer FDK AAC encoder backend (Linux).
+
+use std::ffi::{c_int, c_void};
+use std::ops::Range;
+use std::ptr;
+
+use fdk_aac_sys as sys;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::aac_encoder::{AacBackend, AacEncoderSettings, AacProfile};
+
+/// Raw access units, no transport framing — ADTS is added by
+/// [`crate::adts`] so both backends frame identically.
+const TRANSMUX_RAW: u32 = 0;
+
+/// Interleave channels L R C LFE Ls Rs, as `AudioFrame` does.
+const CHANNEL_ORDER_WAV: u32 = 1;
+
+const BITRATE_MODE_CBR: u32 = 0;
+
+pub(crate) struct FdkAacEncoder {
+    handle: sys::HANDLE_AACENCODER,
+    channels: usize,
+    frame_length: u32,
+    priming_samples: u32,
+    /// Converted samples not yet encoded. The encoder is handed exactly
+    /// one frame per call — its parametric-stereo path crashes on partial
+    /// frames.
+    pending: Vec<i16>,
+    output: Vec<u8>,
+}
+
+// SAFETY: the encoder handle is only used through `&mut self`.
+unsafe impl Send for FdkAacEncoder {}
+
+fn check(what: &str, status: sys::AACENC_ERROR) -> Result<()> {
+    if status == sys::AACENC_ERROR_AACENC_OK {
+        Ok(())
+    } else {
+        Err(Error::Configuration(format!(
+            "fdk-aac {} failed (AACENC_ERROR {:#x})",
+            what, status
+        )))
+    }
+}
+
+impl FdkAacEncoder {
+    pub(crate) fn new(settings: &AacEncoderSettings) -> Result<Self> {
+        let mut handle: sys::HANDLE_AACENCODER = ptr::null_mut();
+        // SAFETY: `handle` is an out pointer to a local.
+        check("aacEncOpen", unsafe {
+            sys::aacEncOpen(&mut handle, 0, settings.channels as u32)
+        })?;
+        // From here `Drop` closes the handle on every error path.
+        let mut encoder = Self {
+            handle,
+            channels: settings.channels as usize,
+            frame_length: 0,
+            priming_samples: 0,
+            pending: Vec::new(),
+            output: Vec::new(),
+        };
+
+        let object_type = match settings.profile {
+            AacProfile::LowComplexity => sys::AUDIO_OBJECT_TYPE_AOT_AAC_LC,
+            AacProfile::HighEfficiency => sys::AUDIO_OBJECT_TYPE_AOT_SBR,
+            AacProfile::HighEfficiencyV2 => sys::AUDIO_OBJECT_TYPE_AOT_PS,
+        };
+        let params = [
+            ("AOT", sys::AACENC_PARAM_AACENC_AOT, object_type as u32),
+            (
+                "sample rate",
+                sys::AACENC_PARAM_AACENC_SAMPLERATE,
+                settings.sample_rate,
+            ),
+            (
+                "channel mode",
+                sys::AACENC_PARAM_AACENC_CHANNELMODE,
+                settings.channels as u32,
+            ),
+            (
+                "channel order",
+                sys::AACENC_PARAM_AACENC_CHANNELORDER,
+                CHANNEL_ORDER_WAV,
+            ),
+            (
+                "bitrate mode",
+                sys::AACENC_PARAM_AACENC_BITRATEMODE,
+                BITRATE_MODE_CBR,
+            ),
+            (
+                "bitrate",
+                sys::AACENC_PARAM_AACENC_BITRATE,
+                settings.bitrate_bps,
+            ),
+            ("transport", sys::AACENC_PARAM_AACENC_TRANSMUX, TRANSMUX_RAW),
+            ("afterburner", sys::AACENC_PARAM_AACENC_AFTERBURNER, 1),
+        ];
+        for (what, param, value) in params {
+            // SAFETY: `handle` is open.
+            check(what, unsafe {
+                sys::aacEncoder_SetParam(encoder.handle, param, value)
+            })?;
+        }
+
+        // SAFETY: all-null buffers initialise the encoder with the
+        // parameters above.
+        check("initialization", unsafe {
+            sys::aacEncEncode(
+                encoder.handle,
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                ptr::null_mut(),
+            )
+        })?;
+
+        // SAFETY: `aacEncInfo` fills the whole struct on success.
+        let info = unsafe {
+            let mut info = std::mem::zeroed::<sys::AACENC_InfoStruct>();
+            check("aacEncInfo", sys::aacEncInfo(encoder.handle, &mut info))?;
+            info
+        };
+        encoder.frame_length = info.frameLength;
+        encoder.priming_samples = info.nDelay;
+        encoder.output = vec![0; info.maxOutBufBytes.max(8192) as usize];
+        Ok(encoder)
+    }
+
+    /// One `aacEncEncode` call on `pending[input]`, or with `None` to drain
+    /// at end of stream. Returns the input samples consumed and the bytes
+    /// of the access unit produced (zero while the encoder is still
+    /// buffering); `None` once a drain has emptied the encoder.
+    fn encode_call(&mut self, input: Option<Range<usize>>) -> Result<Option<(usize, usize)>> {
+        let input = input.map(|range| &self.pending[range]);
+        let (mut input_ptr, num_in_samples) = match input {
+            Some(input) => (input.as_ptr() as *mut c_void, input.len() as c_int),
+            None => (ptr::null_mut(), -1),
+        };
+        let mut input_id = sys::AACENC_BufferIdentifier_IN_AUDIO_DATA as c_int;
+        let mut input_size = (input.map_or(0, <[i16]>::len) * size_of::<i16>()) as c_int;
+        let mut input_element_size = size_of::<i16>() as c_int;
+        let input_desc = sys::AACENC_BufDesc {
+            numBufs: 1,
+            bufs: &mut input_ptr,
+            bufferIdentifiers: &mut input_id,
+            bufSizes: &mut input_size,
+            bufElSizes: &mut input_element_size,
+        };
+
+        let mut output_ptr = self.output.as_mut_ptr() as *mut c_void;
+        let mut output_id = sys::AACENC_BufferIdentifier_OUT_BITSTREAM_DATA as c_int;
+        let mut output_size = self.output.len() as c_int;
+        let mut output_element_size = 1 as c_int;
+        let output_desc = sys::AACENC_BufDesc {
+            numBufs: 1,
+            bufs: &mut output_ptr,
+            bufferIdentifiers: &mut output_id,
+            bufSizes: &mut output_size,
+            bufElSizes: &mut output_element_size,
+        };
+
+        let in_args = sys::AACENC_InArgs {
+            numInSamples: num_in_samples,
+            numAncBytes: 0,
+        };
+        // SAFETY: plain-data out struct.
+        let mut out_args = unsafe { std::mem::zeroed::<sys::AACENC_OutArgs>() };
+
+        // SAFETY: the descriptors point at locals and at `input` /
+        // `self.output`, all of which outlive the call.
+        let status = unsafe {
+            sys::aacEncEncode(
+                self.handle,
+                &input_desc,
+                &output_desc,
+                &in_args,
+                &mut out_args,
+            )
+        };
+        if status == sys::AACENC_ERROR_AACENC_ENCODE_EOF {
+            return Ok(None);
+        }
+        if status != sys::AACENC_ERROR_AACENC_OK {
+            return Err(Error::Runtime(format!(
+                "fdk-aac encoding failed (AACENC_ERROR {:#x})",
+                status
+            )));
+        }
+        Ok(Some((
+            out_args.numInSamples.max(0) as usize,
+            out_args.numOutBytes.max(0) as usize,
+        )))
+    }
+}
+
+impl AacBackend for FdkAacEncoder {
+    fn frame_length(&self) -> u32 {
+        self.frame_length
+    }
+
+    fn priming_samples(&self) -> u32 {
+        self.priming_samples
+    }
+
+    fn encode(&mut self, samples: &[f32]) -> Result<Vec<Vec<u8>>> {
+        self.pending.extend(
+            samples
+                .iter()
+                .map(|&sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+        );
+
+        let frame_samples = self.frame_length as usize * self.channels;
+        let mut packets = Vec::new();
+        let mut offset = 0;
+        while self.pending.len() - offset >= frame_samples {
+            let Some((consumed, bytes)) = self.encode_call(Some(offset..offset + frame_samples))?
+            else {
+                break;
+            };
+            offset += consumed;
+            if bytes > 0 {
+                packets.push(self.output[..bytes].to_vec());
+            } else if consumed == 0 {
+                break;
+            }
+        }
+        self.pending.drain(..offset);
+        Ok(packets)
+    }
+
+    fn flush(&mut self) -> Result<Vec<Vec<u8>>> {
+        // Pad the last partial frame with silence so it goes in whole.
+        let mut packets = Vec::new();
+        if !self.pending.is_empty() {
+            let frame_samples = self.frame_length as usize * self.channels;
+            let padded = self.pending.len().next_multiple_of(frame_samples);
+            self.pending.resize(padded, 0);
+            packets = self.encode(&[])?;
+        }
+        while let Some((_, bytes)) = self.encode_call(None)? {
+            if bytes == 0 {
+                break;
+            }
+            packets.push(self.output[..bytes].to_vec());
+        }
+        Ok(packets)
+    }
+}
+
+impl Drop for FdkAacEncoder {
+    fn drop(&mut self) {
+        // SAFETY: `handle` came from `aacEncOpen` and is closed once.
+        unsafe {
+            sys::aacEncClose(&mut self.handle);
+        }
+    }
+}
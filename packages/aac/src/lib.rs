@@ -0,0 +1,35 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/aac` — AAC-LC / HE-AAC audio encoder processor for MP4 and
+//! HLS outputs.
+//!
+//! AudioToolbox on macOS / iOS, Fraunhofer FDK AAC on Linux; no Windows
+//! backend yet, so the package compiles to an empty surface there.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "linux"))]
+pub mod aac_encoder;
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "linux"))]
+pub mod adts;
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod audio_toolbox;
+
+#[cfg(target_os = "linux")]
+mod fdk;
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "linux"))]
+pub use aac_encoder::{
+    AacEncoder, AacEncoderProcessor, AacEncoderSettings, AacProfile, AacTransport, AudioEncoderAac,
+};
+
+pub use _generated_::AacEncoderConfig;
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "linux"))]
+streamlib_plugin_abi::export_plugin!(crate::AacEncoderProcessor::Processor);
@@ -0,0 +1,13 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+#![allow(clippy::disallowed_macros)] // build.rs uses println! for `cargo:` directives
+
+//! Codegen for the adaptive-encode package: generates the typed configs,
+//! the QualityScore wire type, and the imported `@tatolab/core`,
+//! `@tatolab/scale` and `@tatolab/scene-detect` wire types consumed by the
+//! controllers.
+
+fn main() {
+    streamlib_jtd_codegen::build_rs::run_for_rust_crate();
+}
@@ -0,0 +1,202 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Adaptive encode controller — feeds quality scores, scene cuts and the
+//! encoder's own output into an [`AdaptiveController`] and forwards its
+//! decisions to the encoder's `control` input as `EncoderControl`
+//! messages.
+//!
+//! Every decision is logged and published on [`ADAPTIVE_ENCODE_DECISION_TOPIC`]
+//! with the measurements behind it, so an encode can be audited after the
+//! fact: which setting was on air when, and why.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+use crate::_generated_::tatolab__adaptive_encode::adaptive_encode_config::Mode;
+use crate::_generated_::{EncodedVideoFrame, EncoderControl, QualityScore, SceneChange};
+use crate::controller::{AdaptiveController, ControllerSettings, Decision, RateMode, RateSetting};
+
+/// Custom-event topic carrying `{processor_id, metric, decision}` for
+/// every decision, including the initial setting.
+pub const ADAPTIVE_ENCODE_DECISION_TOPIC: &str = "adaptive_encode:decision";
+
+const DEFAULT_QUALITY_TOLERANCE: f32 = 1.0;
+const DEFAULT_SETTLE_SAMPLES: u32 = 30;
+const DEFAULT_INITIAL_BITRATE_BPS: u32 = 2_000_000;
+const DEFAULT_MIN_BITRATE_BPS: u32 = 500_000;
+const DEFAULT_MAX_BITRATE_BPS: u32 = 8_000_000;
+const DEFAULT_BITRATE_STEP_PERCENT: u32 = 15;
+const DEFAULT_INITIAL_QP: u32 = 26;
+const DEFAULT_MIN_QP: u32 = 18;
+const DEFAULT_MAX_QP: u32 = 40;
+/// Frame rate assumed for encoded frames that don't carry one — the
+/// encoders' own default.
+const DEFAULT_FPS: u32 = 60;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/adaptive-encode/AdaptiveEncodeController",
+    description = "Steers a video encoder's bitrate or QP toward a target quality score within configured bounds. Averages QualityScore frames into per-window decisions, measures the encoded bitrate as a content-complexity signal (capping QP mode at max_bitrate_bps), starts a fresh window and optionally forces a keyframe at each SceneChange, and sends the result to the encoder's control input. Every decision is logged and published on the adaptive_encode:decision topic.",
+    execution = reactive,
+    config = crate::_generated_::AdaptiveEncodeConfig,
+    input("quality", "@tatolab/adaptive-encode/QualityScore", description = "Quality measurements of the encoded output"),
+    input("scene_change", "@tatolab/scene-detect/SceneChange", optional = true, description = "Scene cuts from a SceneChange detector"),
+    input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", optional = true, description = "The encoder's output, tapped to measure its bitrate"),
+    output("encoder_control", "@tatolab/core/EncoderControl", description = "Rate-control changes and keyframe requests for the encoder"),
+)]
+pub struct AdaptiveEncodeControllerProcessor {
+    processor_id: Option<String>,
+    controller: Option<AdaptiveController>,
+    /// Whether the controller's initial setting has gone out; reset when a
+    /// config update rebuilds the controller.
+    initial_sent: bool,
+    /// `metric` of the latest QualityScore, carried into decision events.
+    metric: Option<String>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for AdaptiveEncodeControllerProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        let settings = self.settings()?;
+        tracing::info!(
+            "AdaptiveEncodeController: {:?} mode, target quality {} ± {}",
+            settings.mode,
+            settings.target_quality,
+            settings.quality_tolerance
+        );
+        self.controller = Some(AdaptiveController::new(settings));
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.controller = Some(AdaptiveController::new(self.settings()?));
+        self.initial_sent = false;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let Some(controller) = &mut self.controller else {
+            return Ok(());
+        };
+        let mut decisions = Vec::new();
+        if !self.initial_sent {
+            decisions.push(controller.initial_decision());
+            self.initial_sent = true;
+        }
+        // Cuts first, so this call's measurements land in the new scene's
+        // windows.
+        while self.inputs.has_data("scene_change") {
+            let _cut: SceneChange = self.inputs.read("scene_change")?;
+            decisions.extend(controller.on_scene_change());
+        }
+        while self.inputs.has_data("encoded_video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("encoded_video_in")?;
+            let fps = frame.fps.unwrap_or(DEFAULT_FPS);
+            decisions.extend(controller.on_encoded_frame(frame.data.len(), fps));
+        }
+        while self.inputs.has_data("quality") {
+            let score: QualityScore = self.inputs.read("quality")?;
+            if score.metric.is_some() {
+                self.metric = score.metric;
+            }
+            decisions.extend(controller.on_quality(score.score));
+        }
+
+        for decision in decisions {
+            self.outputs
+                .write("encoder_control", &encoder_control(&decision))?;
+            self.audit(&decision);
+        }
+        Ok(())
+    }
+}
+
+impl AdaptiveEncodeControllerProcessor::Processor {
+    /// Controller settings from the config, with defaults filled in.
+    fn settings(&self) -> Result<ControllerSettings> {
+        let config = &self.config;
+        let settings = ControllerSettings {
+            mode: match config.mode {
+                Some(Mode::Qp) => RateMode::Qp,
+                Some(Mode::Bitrate) | None => RateMode::Bitrate,
+            },
+            target_quality: config.target_quality,
+            quality_tolerance: config
+                .quality_tolerance
+                .unwrap_or(DEFAULT_QUALITY_TOLERANCE),
+            settle_samples: config.settle_samples.unwrap_or(DEFAULT_SETTLE_SAMPLES),
+            initial_bitrate_bps: config
+                .initial_bitrate_bps
+                .unwrap_or(DEFAULT_INITIAL_BITRATE_BPS),
+            min_bitrate_bps: config.min_bitrate_bps.unwrap_or(DEFAULT_MIN_BITRATE_BPS),
+            max_bitrate_bps: config.max_bitrate_bps.unwrap_or(DEFAULT_MAX_BITRATE_BPS),
+            bitrate_step_percent: config
+                .bitrate_step_percent
+                .unwrap_or(DEFAULT_BITRATE_STEP_PERCENT),
+            initial_qp: config.initial_qp.unwrap_or(DEFAULT_INITIAL_QP),
+            min_qp: config.min_qp.unwrap_or(DEFAULT_MIN_QP),
+            max_qp: config.max_qp.unwrap_or(DEFAULT_MAX_QP),
+            keyframe_on_scene_change: config.keyframe_on_scene_change.unwrap_or(true),
+        };
+        if !settings.target_quality.is_finite() {
+            return Err(Error::Configuration(
+                "AdaptiveEncodeController: target_quality must be finite".into(),
+            ));
+        }
+        if settings.min_bitrate_bps == 0 || settings.min_bitrate_bps > settings.max_bitrate_bps {
+            return Err(Error::Configuration(
+                "AdaptiveEncodeController: need 0 < min_bitrate_bps <= max_bitrate_bps".into(),
+            ));
+        }
+        if settings.min_qp > settings.max_qp {
+            return Err(Error::Configuration(
+                "AdaptiveEncodeController: min_qp must not exceed max_qp".into(),
+            ));
+        }
+        Ok(settings)
+    }
+
+    fn audit(&self, decision: &Decision) {
+        tracing::info!(
+            "AdaptiveEncodeController: {:?} → {:?}{} (mean quality {:?}, measured {:?} bps)",
+            decision.reason,
+            decision.setting,
+            if decision.force_keyframe {
+                ", keyframe"
+            } else {
+                ""
+            },
+            decision.mean_quality,
+            decision.measured_bitrate_bps,
+        );
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "metric": self.metric,
+            "decision": decision,
+        });
+        if let Err(e) = publish_custom_event(ADAPTIVE_ENCODE_DECISION_TOPIC, &payload) {
+            tracing::debug!("AdaptiveEncodeController: decision not published: {}", e);
+        }
+    }
+}
+
+/// The control message for `decision`. The current setting rides along
+/// with every message; encoders ignore a setting that is already in effect.
+fn encoder_control(decision: &Decision) -> EncoderControl {
+    let (bitrate_bps, qp) = match decision.setting {
+        RateSetting::BitrateBps(bps) => (Some(bps), None),
+        RateSetting::Qp(qp) => (None, Some(qp)),
+    };
+    EncoderControl {
+        bitrate_bps,
+        qp,
+        force_keyframe: decision.force_keyframe.then_some(true),
+    }
+}
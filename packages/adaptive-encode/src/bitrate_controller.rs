@@ -0,0 +1,226 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Bitrate controller — feeds a transport's congestion reports into a
+//! [`BitrateController`] and drives the encoder's bitrate through its
+//! `control` input and, with a resolution ladder configured, a Scale
+//! processor's output size through its own.
+//!
+//! Any sender that writes `CongestionReport`s can drive it: RtpSender
+//! from RTCP receiver reports, WebrtcWhip from WebRTC stats. Every
+//! decision is logged and published on [`BITRATE_CONTROLLER_DECISION_TOPIC`].
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+use crate::_generated_::{CongestionReport, EncoderControl, ScaleControl};
+use crate::congestion::{
+    BitrateController, CongestionDecision, CongestionSettings, CongestionSignal, Rendition,
+};
+
+/// Custom-event topic carrying `{processor_id, source, decision}` for
+/// every decision, including the initial setting.
+pub const BITRATE_CONTROLLER_DECISION_TOPIC: &str = "bitrate_controller:decision";
+
+const DEFAULT_INITIAL_BITRATE_BPS: u32 = 2_000_000;
+const DEFAULT_MIN_BITRATE_BPS: u32 = 300_000;
+const DEFAULT_MAX_BITRATE_BPS: u32 = 8_000_000;
+const DEFAULT_LOSS_LOW: f32 = 0.02;
+const DEFAULT_LOSS_HIGH: f32 = 0.10;
+const DEFAULT_INCREASE_PERCENT: u32 = 8;
+const DEFAULT_INCREASE_INTERVAL_MS: u32 = 4_000;
+const DEFAULT_DECREASE_INTERVAL_MS: u32 = 1_000;
+const DEFAULT_MIN_CHANGE_PERCENT: u32 = 5;
+const DEFAULT_AVAILABLE_BITRATE_PERCENT: u32 = 90;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/adaptive-encode/BitrateController",
+    description = "Adapts a video encoder's bitrate to network congestion. Cuts on packet loss or a high round-trip time, probes upward while the path is clean, caps at a transport's bandwidth estimate, and with a resolution ladder configured steps a Scale processor's output size to match. Decisions are paced for encoder re-mints, logged, and published on the bitrate_controller:decision topic.",
+    execution = reactive,
+    config = crate::_generated_::BitrateControllerConfig,
+    input("congestion", "@tatolab/core/CongestionReport", description = "Congestion feedback from the sending transport"),
+    output("encoder_control", "@tatolab/core/EncoderControl", description = "Bitrate changes for the encoder"),
+    output("scale_control", "@tatolab/scale/ScaleControl", description = "Resolution ladder steps for a Scale processor ahead of the encoder"),
+)]
+pub struct BitrateControllerProcessor {
+    processor_id: Option<String>,
+    controller: Option<BitrateController>,
+    /// Whether the controller's initial setting has gone out; reset when a
+    /// config update rebuilds the controller.
+    initial_sent: bool,
+    /// `source` of the latest report, carried into decision events.
+    source: Option<String>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for BitrateControllerProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        let settings = self.settings()?;
+        tracing::info!(
+            "BitrateController: {}..{} bps, {} renditions",
+            settings.min_bitrate_bps,
+            settings.max_bitrate_bps,
+            settings.renditions.len()
+        );
+        self.controller = Some(BitrateController::new(settings));
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.controller = Some(BitrateController::new(self.settings()?));
+        self.initial_sent = false;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let Some(controller) = &mut self.controller else {
+            return Ok(());
+        };
+        let mut decisions = Vec::new();
+        if !self.initial_sent {
+            decisions.push(controller.initial_decision());
+            self.initial_sent = true;
+        }
+        while self.inputs.has_data("congestion") {
+            let report: CongestionReport = self.inputs.read("congestion")?;
+            if report.source.is_some() {
+                self.source = report.source.clone();
+            }
+            let now_ms = (MediaClock::now().as_nanos() / 1_000_000) as u64;
+            decisions.extend(controller.on_report(signal(&report), now_ms));
+        }
+
+        for decision in decisions {
+            self.outputs.write(
+                "encoder_control",
+                &EncoderControl {
+                    bitrate_bps: Some(decision.bitrate_bps),
+                    qp: None,
+                    force_keyframe: None,
+                },
+            )?;
+            if let Some(rendition) = decision.rendition
+                && self.outputs.has_port("scale_control")
+            {
+                self.outputs
+                    .write("scale_control", &scale_control(&rendition))?;
+            }
+            self.audit(&decision);
+        }
+        Ok(())
+    }
+}
+
+impl BitrateControllerProcessor::Processor {
+    /// Controller settings from the config, with defaults filled in.
+    fn settings(&self) -> Result<CongestionSettings> {
+        let config = &self.config;
+        let settings = CongestionSettings {
+            initial_bitrate_bps: config
+                .initial_bitrate_bps
+                .unwrap_or(DEFAULT_INITIAL_BITRATE_BPS),
+            min_bitrate_bps: config.min_bitrate_bps.unwrap_or(DEFAULT_MIN_BITRATE_BPS),
+            max_bitrate_bps: config.max_bitrate_bps.unwrap_or(DEFAULT_MAX_BITRATE_BPS),
+            loss_low: config.loss_low.unwrap_or(DEFAULT_LOSS_LOW),
+            loss_high: config.loss_high.unwrap_or(DEFAULT_LOSS_HIGH),
+            rtt_limit_ms: config.rtt_limit_ms,
+            increase_percent: config.increase_percent.unwrap_or(DEFAULT_INCREASE_PERCENT),
+            increase_interval_ms: u64::from(
+                config
+                    .increase_interval_ms
+                    .unwrap_or(DEFAULT_INCREASE_INTERVAL_MS),
+            ),
+            decrease_interval_ms: u64::from(
+                config
+                    .decrease_interval_ms
+                    .unwrap_or(DEFAULT_DECREASE_INTERVAL_MS),
+            ),
+            min_change_percent: config
+                .min_change_percent
+                .unwrap_or(DEFAULT_MIN_CHANGE_PERCENT),
+            available_bitrate_percent: config
+                .available_bitrate_percent
+                .unwrap_or(DEFAULT_AVAILABLE_BITRATE_PERCENT),
+            renditions: config
+                .renditions
+                .iter()
+                .flatten()
+                .map(|rendition| Rendition {
+                    width: rendition.width,
+                    height: rendition.height,
+                    min_bitrate_bps: rendition.min_bitrate_bps,
+                })
+                .collect(),
+        };
+        if settings.min_bitrate_bps == 0 || settings.min_bitrate_bps > settings.max_bitrate_bps {
+            return Err(Error::Configuration(
+                "BitrateController: need 0 < min_bitrate_bps <= max_bitrate_bps".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&settings.loss_low)
+            || !(0.0..=1.0).contains(&settings.loss_high)
+            || settings.loss_low > settings.loss_high
+        {
+            return Err(Error::Configuration(
+                "BitrateController: need 0 <= loss_low <= loss_high <= 1".into(),
+            ));
+        }
+        if settings
+            .renditions
+            .iter()
+            .any(|rendition| rendition.height == 0 || rendition.width == Some(0))
+        {
+            return Err(Error::Configuration(
+                "BitrateController: rendition sizes must be nonzero".into(),
+            ));
+        }
+        Ok(settings)
+    }
+
+    fn audit(&self, decision: &CongestionDecision) {
+        tracing::info!(
+            "BitrateController: {:?} → {} bps{} (loss {:?}, rtt {:?} ms, available {:?} bps)",
+            decision.reason,
+            decision.bitrate_bps,
+            decision
+                .rendition
+                .map(|r| format!(", {}p", r.height))
+                .unwrap_or_default(),
+            decision.fraction_lost,
+            decision.rtt_ms,
+            decision.available_bitrate_bps,
+        );
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "source": self.source,
+            "decision": decision,
+        });
+        if let Err(e) = publish_custom_event(BITRATE_CONTROLLER_DECISION_TOPIC, &payload) {
+            tracing::debug!("BitrateController: decision not published: {}", e);
+        }
+    }
+}
+
+fn signal(report: &CongestionReport) -> CongestionSignal {
+    CongestionSignal {
+        fraction_lost: report.fraction_lost,
+        rtt_ms: report.rtt_ms,
+        available_bitrate_bps: report.available_bitrate_bps,
+        send_bitrate_bps: report.send_bitrate_bps,
+    }
+}
+
+fn scale_control(rendition: &Rendition) -> ScaleControl {
+    ScaleControl {
+        width: rendition.width,
+        height: Some(rendition.height),
+    }
+}
@@ -0,0 +1,416 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The congestion-driven bitrate decision, separate from port I/O so it
+//! can be driven with synthetic reports and a synthetic clock.
+//!
+//! Each report moves the bitrate from the one in effect, in the manner of
+//! the loss-based half of Google Congestion Control: loss above
+//! `loss_high` cuts it by half the loss fraction, a round-trip time over
+//! `rtt_limit_ms` cuts it by a fixed step, and loss below `loss_low`
+//! probes upward by `increase_percent` — but not past half again the rate
+//! the transport actually sent, since an encoder undershooting its target
+//! says nothing about the path. A transport's own bandwidth estimate caps
+//! the result outright. Encoders rebuild their session on every rate
+//! change, so cuts are spaced by `decrease_interval_ms`, probes by the
+//! longer `increase_interval_ms`, and changes under `min_change_percent`
+//! are not sent unless they land on a bound.
+//!
+//! With a resolution ladder configured, the bitrate also picks a rung: the
+//! largest rendition whose `min_bitrate_bps` it covers. Stepping back up
+//! needs [`RENDITION_UPGRADE_MARGIN`] over the rung's floor, so a bitrate
+//! hovering at a boundary doesn't flip the resolution every report.
+
+use serde::Serialize;
+
+/// Headroom over a larger rendition's `min_bitrate_bps` needed to switch
+/// up to it.
+pub const RENDITION_UPGRADE_MARGIN: f64 = 1.2;
+
+/// Multiplier applied when the round-trip time is over its limit.
+const RTT_DECREASE_FACTOR: f64 = 0.85;
+
+/// Probes stop at this multiple of the measured send bitrate.
+const PROBE_SEND_RATE_CAP: f64 = 1.5;
+
+/// One rung of the resolution ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Rendition {
+    /// Omitted to follow the source aspect ratio.
+    pub width: Option<u32>,
+    pub height: u32,
+    pub min_bitrate_bps: u32,
+}
+
+/// Bounds and pacing, resolved from the config with defaults filled in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CongestionSettings {
+    pub initial_bitrate_bps: u32,
+    pub min_bitrate_bps: u32,
+    pub max_bitrate_bps: u32,
+    pub loss_low: f32,
+    pub loss_high: f32,
+    pub rtt_limit_ms: Option<f32>,
+    pub increase_percent: u32,
+    pub increase_interval_ms: u64,
+    pub decrease_interval_ms: u64,
+    pub min_change_percent: u32,
+    pub available_bitrate_percent: u32,
+    /// Any order; the controller sorts them largest floor first.
+    pub renditions: Vec<Rendition>,
+}
+
+/// The measurements of one congestion report the controller uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CongestionSignal {
+    pub fraction_lost: Option<f32>,
+    pub rtt_ms: Option<f32>,
+    pub available_bitrate_bps: Option<u32>,
+    pub send_bitrate_bps: Option<u32>,
+}
+
+/// Why a decision was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CongestionReason {
+    /// The starting bitrate, sent before any report.
+    Initial,
+    /// Loss above `loss_high`.
+    LossHigh,
+    /// Round-trip time above `rtt_limit_ms`.
+    RttHigh,
+    /// The transport's bandwidth estimate capped the bitrate.
+    AvailableBitrate,
+    /// Loss below `loss_low`; probing for more bandwidth.
+    Probe,
+}
+
+/// One controller output: the bitrate to run at, and the rendition when it
+/// changes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CongestionDecision {
+    pub reason: CongestionReason,
+    pub bitrate_bps: u32,
+    /// Set when the decision moves to another rung of the ladder.
+    pub rendition: Option<Rendition>,
+    /// The report behind the decision.
+    pub fraction_lost: Option<f32>,
+    pub rtt_ms: Option<f32>,
+    pub available_bitrate_bps: Option<u32>,
+    pub send_bitrate_bps: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct BitrateController {
+    settings: CongestionSettings,
+    bitrate_bps: u32,
+    rendition: Option<usize>,
+    last_change_ms: Option<u64>,
+}
+
+impl BitrateController {
+    pub fn new(mut settings: CongestionSettings) -> Self {
+        settings
+            .renditions
+            .sort_by_key(|rendition| std::cmp::Reverse(rendition.min_bitrate_bps));
+        let bitrate_bps = settings
+            .initial_bitrate_bps
+            .clamp(settings.min_bitrate_bps, settings.max_bitrate_bps);
+        let mut controller = Self {
+            settings,
+            bitrate_bps,
+            rendition: None,
+            last_change_ms: None,
+        };
+        controller.rendition = controller.rendition_for(bitrate_bps);
+        controller
+    }
+
+    pub fn bitrate_bps(&self) -> u32 {
+        self.bitrate_bps
+    }
+
+    /// The starting bitrate and rendition, to send before any report.
+    pub fn initial_decision(&self) -> CongestionDecision {
+        CongestionDecision {
+            reason: CongestionReason::Initial,
+            bitrate_bps: self.bitrate_bps,
+            rendition: self.rendition.map(|index| self.settings.renditions[index]),
+            fraction_lost: None,
+            rtt_ms: None,
+            available_bitrate_bps: None,
+            send_bitrate_bps: None,
+        }
+    }
+
+    /// Take one report received at `now_ms`. Returns a decision when it
+    /// calls for a different bitrate and the pacing allows one.
+    pub fn on_report(
+        &mut self,
+        signal: CongestionSignal,
+        now_ms: u64,
+    ) -> Option<CongestionDecision> {
+        let current = f64::from(self.bitrate_bps);
+        let loss = signal
+            .fraction_lost
+            .filter(|loss| loss.is_finite())
+            .map(|loss| f64::from(loss.clamp(0.0, 1.0)));
+        let rtt_high = match (signal.rtt_ms, self.settings.rtt_limit_ms) {
+            (Some(rtt), Some(limit)) => rtt > limit,
+            _ => false,
+        };
+
+        let (mut reason, mut next) = match loss {
+            Some(loss) if loss > f64::from(self.settings.loss_high) => (
+                Some(CongestionReason::LossHigh),
+                current * (1.0 - 0.5 * loss),
+            ),
+            _ if rtt_high => (
+                Some(CongestionReason::RttHigh),
+                current * RTT_DECREASE_FACTOR,
+            ),
+            Some(loss) if loss < f64::from(self.settings.loss_low) => {
+                let mut probe = current * (1.0 + f64::from(self.settings.increase_percent) / 100.0);
+                if let Some(sent) = signal.send_bitrate_bps {
+                    probe = probe
+                        .min(f64::from(sent) * PROBE_SEND_RATE_CAP)
+                        .max(current);
+                }
+                (Some(CongestionReason::Probe), probe)
+            }
+            _ => (None, current),
+        };
+        if let Some(available) = signal.available_bitrate_bps {
+            let cap =
+                f64::from(available) * f64::from(self.settings.available_bitrate_percent) / 100.0;
+            if next > cap {
+                reason = Some(CongestionReason::AvailableBitrate);
+                next = cap;
+            }
+        }
+        let reason = reason?;
+        let next = (next.round() as u32)
+            .clamp(self.settings.min_bitrate_bps, self.settings.max_bitrate_bps);
+
+        // A small step is still worth taking to land on a bound.
+        let change = (f64::from(next) - current).abs() / current * 100.0;
+        let at_bound =
+            next == self.settings.min_bitrate_bps || next == self.settings.max_bitrate_bps;
+        if next == self.bitrate_bps
+            || (change < f64::from(self.settings.min_change_percent) && !at_bound)
+        {
+            return None;
+        }
+        let spacing = if next < self.bitrate_bps {
+            self.settings.decrease_interval_ms
+        } else {
+            self.settings.increase_interval_ms
+        };
+        if self
+            .last_change_ms
+            .is_some_and(|last| now_ms.saturating_sub(last) < spacing)
+        {
+            return None;
+        }
+
+        self.bitrate_bps = next;
+        self.last_change_ms = Some(now_ms);
+        let rendition = self.rendition_for(next);
+        let rendition_changed = rendition != self.rendition;
+        self.rendition = rendition;
+        Some(CongestionDecision {
+            reason,
+            bitrate_bps: next,
+            rendition: rendition
+                .filter(|_| rendition_changed)
+                .map(|index| self.settings.renditions[index]),
+            fraction_lost: signal.fraction_lost,
+            rtt_ms: signal.rtt_ms,
+            available_bitrate_bps: signal.available_bitrate_bps,
+            send_bitrate_bps: signal.send_bitrate_bps,
+        })
+    }
+
+    /// The ladder rung for `bitrate_bps`, staying on the current one until
+    /// the bitrate clears a larger rung's floor by the upgrade margin.
+    fn rendition_for(&self, bitrate_bps: u32) -> Option<usize> {
+        let renditions = &self.settings.renditions;
+        if renditions.is_empty() {
+            return None;
+        }
+        let fits = |index: usize| {
+            let floor = f64::from(renditions[index].min_bitrate_bps);
+            let upgrading = self.rendition.is_some_and(|current| index < current);
+            let needed = if upgrading {
+                floor * RENDITION_UPGRADE_MARGIN
+            } else {
+                floor
+            };
+            f64::from(bitrate_bps) >= needed
+        };
+        Some(
+            (0..renditions.len())
+                .find(|index| fits(*index))
+                .unwrap_or(renditions.len() - 1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> CongestionSettings {
+        CongestionSettings {
+            initial_bitrate_bps: 2_000_000,
+            min_bitrate_bps: 300_000,
+            max_bitrate_bps: 4_000_000,
+            loss_low: 0.02,
+            loss_high: 0.10,
+            rtt_limit_ms: Some(400.0),
+            increase_percent: 10,
+            increase_interval_ms: 4_000,
+            decrease_interval_ms: 1_000,
+            min_change_percent: 5,
+            available_bitrate_percent: 90,
+            renditions: Vec::new(),
+        }
+    }
+
+    fn loss(fraction_lost: f32) -> CongestionSignal {
+        CongestionSignal {
+            fraction_lost: Some(fraction_lost),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn heavy_loss_cuts_by_half_the_loss_and_moderate_loss_holds() {
+        let mut controller = BitrateController::new(settings());
+        let cut = controller
+            .on_report(loss(0.2), 0)
+            .expect("loss above loss_high");
+        assert_eq!(cut.reason, CongestionReason::LossHigh);
+        assert_eq!(cut.bitrate_bps, 1_800_000);
+        assert_eq!(cut.fraction_lost, Some(0.2));
+
+        // Between the thresholds: hold.
+        assert_eq!(controller.on_report(loss(0.05), 5_000), None);
+        assert_eq!(controller.bitrate_bps(), 1_800_000);
+    }
+
+    #[test]
+    fn changes_are_paced_and_small_ones_dropped() {
+        let mut controller = BitrateController::new(settings());
+        assert!(controller.on_report(loss(0.5), 0).is_some());
+        // Another cut inside decrease_interval_ms waits.
+        assert_eq!(controller.on_report(loss(0.5), 500), None);
+        assert_eq!(
+            controller
+                .on_report(loss(0.5), 1_000)
+                .map(|d| d.bitrate_bps),
+            Some(1_125_000)
+        );
+
+        // A probe waits the longer increase_interval_ms.
+        assert_eq!(controller.on_report(loss(0.0), 2_000), None);
+        assert_eq!(
+            controller.on_report(loss(0.0), 5_000).map(|d| d.reason),
+            Some(CongestionReason::Probe)
+        );
+
+        // 2% loss cuts only 1%, under min_change_percent.
+        let mut gentle = BitrateController::new(CongestionSettings {
+            loss_low: 0.005,
+            loss_high: 0.01,
+            ..settings()
+        });
+        assert_eq!(gentle.on_report(loss(0.02), 0), None);
+    }
+
+    #[test]
+    fn probes_follow_the_send_rate_and_respect_bounds() {
+        let mut controller = BitrateController::new(settings());
+        // The encoder is only sending 1.2 Mb/s of its 2 Mb/s target.
+        let undershoot = CongestionSignal {
+            send_bitrate_bps: Some(1_200_000),
+            ..loss(0.0)
+        };
+        assert_eq!(controller.on_report(undershoot, 0), None);
+
+        for now_ms in (0..10).map(|i| i * 4_000) {
+            controller.on_report(loss(0.0), now_ms);
+        }
+        assert_eq!(controller.bitrate_bps(), 4_000_000, "pinned at max");
+    }
+
+    #[test]
+    fn rtt_and_available_bitrate_cut() {
+        let mut controller = BitrateController::new(settings());
+        let slow = CongestionSignal {
+            rtt_ms: Some(600.0),
+            ..loss(0.0)
+        };
+        let cut = controller.on_report(slow, 0).unwrap();
+        assert_eq!(cut.reason, CongestionReason::RttHigh);
+        assert_eq!(cut.bitrate_bps, 1_700_000);
+
+        let estimated = CongestionSignal {
+            available_bitrate_bps: Some(1_000_000),
+            ..loss(0.0)
+        };
+        let capped = controller.on_report(estimated, 1_000).unwrap();
+        assert_eq!(capped.reason, CongestionReason::AvailableBitrate);
+        assert_eq!(capped.bitrate_bps, 900_000);
+    }
+
+    #[test]
+    fn the_ladder_steps_down_at_a_floor_and_up_past_the_margin() {
+        let ladder = vec![
+            Rendition {
+                width: None,
+                height: 360,
+                min_bitrate_bps: 0,
+            },
+            Rendition {
+                width: Some(1920),
+                height: 1080,
+                min_bitrate_bps: 1_900_000,
+            },
+            Rendition {
+                width: Some(1280),
+                height: 720,
+                min_bitrate_bps: 1_000_000,
+            },
+        ];
+        let mut controller = BitrateController::new(CongestionSettings {
+            renditions: ladder,
+            ..settings()
+        });
+        let initial = controller.initial_decision();
+        assert_eq!(initial.rendition.map(|r| r.height), Some(1080));
+
+        // 1.8 Mb/s is under the 1080p floor.
+        let down = controller.on_report(loss(0.2), 0).unwrap();
+        assert_eq!(down.rendition.map(|r| r.height), Some(720));
+
+        // 1.98 Mb/s clears the floor but not the margin: still 720p.
+        let probe = controller.on_report(loss(0.0), 4_000).unwrap();
+        assert_eq!(probe.bitrate_bps, 1_980_000);
+        assert_eq!(probe.rendition, None);
+
+        // 2.178 Mb/s is still short of 1.9 Mb/s x 1.2; 2.396 Mb/s is not.
+        assert_eq!(
+            controller.on_report(loss(0.0), 8_000).unwrap().rendition,
+            None
+        );
+        let up = controller.on_report(loss(0.0), 12_000).unwrap();
+        assert_eq!(up.rendition.map(|r| r.height), Some(1080));
+
+        // Total loss halves the bitrate twice, down to the floor rung.
+        let halved = controller.on_report(loss(1.0), 13_000).unwrap();
+        assert_eq!(halved.rendition.map(|r| r.height), Some(720));
+        let floor = controller.on_report(loss(1.0), 14_000).unwrap();
+        assert_eq!(floor.rendition.map(|r| r.height), Some(360));
+    }
+}
@@ -0,0 +1,382 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The rate-control decision, separate from port I/O so it can be driven
+//! with synthetic scores and packet sizes.
+//!
+//! Quality scores are averaged over a window of `settle_samples`; a window
+//! whose mean falls below the target's dead band buys quality (more
+//! bitrate, or a lower QP), one above it saves bits. One step per window
+//! keeps the loop slow enough for an encoder re-mint to show its effect
+//! before the next decision. The encoded bitrate stands in for content
+//! complexity: at a constant QP it rises with detail and motion, so in QP
+//! mode a measured second above `max_bitrate_bps` raises the QP whatever
+//! the quality says, and blocks quality-driven QP cuts until it drops back.
+//! A scene change starts both windows over, since the old scene's numbers
+//! say nothing about the new one.
+
+use serde::Serialize;
+
+/// Encoder knob the controller turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateMode {
+    Bitrate,
+    Qp,
+}
+
+/// Bounds and pacing, resolved from the config with defaults filled in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControllerSettings {
+    pub mode: RateMode,
+    pub target_quality: f32,
+    pub quality_tolerance: f32,
+    pub settle_samples: u32,
+    pub initial_bitrate_bps: u32,
+    pub min_bitrate_bps: u32,
+    pub max_bitrate_bps: u32,
+    pub bitrate_step_percent: u32,
+    pub initial_qp: u32,
+    pub min_qp: u32,
+    pub max_qp: u32,
+    pub keyframe_on_scene_change: bool,
+}
+
+/// Encoder setting in effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateSetting {
+    BitrateBps(u32),
+    Qp(u32),
+}
+
+/// Why a decision was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionReason {
+    /// The starting setting, sent before any measurement.
+    Initial,
+    /// The window's mean score fell below the dead band.
+    QualityLow,
+    /// The window's mean score rose above the dead band.
+    QualityHigh,
+    /// The measured bitrate exceeded `max_bitrate_bps` (QP mode).
+    BitrateCeiling,
+    /// A scene cut; the setting is unchanged and a keyframe is requested.
+    SceneChange,
+}
+
+/// One controller output: what to send to the encoder and why.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Decision {
+    pub reason: DecisionReason,
+    pub setting: RateSetting,
+    pub force_keyframe: bool,
+    /// Mean score of the window that triggered the decision.
+    pub mean_quality: Option<f32>,
+    /// Encoded bitrate over the last measured second.
+    pub measured_bitrate_bps: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct AdaptiveController {
+    settings: ControllerSettings,
+    setting: RateSetting,
+    quality_sum: f64,
+    quality_samples: u32,
+    encoded_bits: u64,
+    encoded_frames: u32,
+    measured_bitrate_bps: Option<u64>,
+}
+
+impl AdaptiveController {
+    pub fn new(settings: ControllerSettings) -> Self {
+        let setting = match settings.mode {
+            RateMode::Bitrate => RateSetting::BitrateBps(
+                settings
+                    .initial_bitrate_bps
+                    .clamp(settings.min_bitrate_bps, settings.max_bitrate_bps),
+            ),
+            RateMode::Qp => {
+                RateSetting::Qp(settings.initial_qp.clamp(settings.min_qp, settings.max_qp))
+            }
+        };
+        Self {
+            settings,
+            setting,
+            quality_sum: 0.0,
+            quality_samples: 0,
+            encoded_bits: 0,
+            encoded_frames: 0,
+            measured_bitrate_bps: None,
+        }
+    }
+
+    /// The starting setting, to send before any measurement arrives.
+    pub fn initial_decision(&self) -> Decision {
+        self.decision(DecisionReason::Initial, false, None)
+    }
+
+    /// Record one quality score. Returns a decision when it closes a
+    /// window whose mean calls for a different setting.
+    pub fn on_quality(&mut self, score: f32) -> Option<Decision> {
+        if !score.is_finite() {
+            return None;
+        }
+        self.quality_sum += f64::from(score);
+        self.quality_samples += 1;
+        if self.quality_samples < self.settings.settle_samples.max(1) {
+            return None;
+        }
+        let mean = (self.quality_sum / f64::from(self.quality_samples)) as f32;
+        self.quality_sum = 0.0;
+        self.quality_samples = 0;
+
+        let target = self.settings.target_quality;
+        let tolerance = self.settings.quality_tolerance.max(0.0);
+        let (reason, next) = if mean < target - tolerance {
+            (DecisionReason::QualityLow, self.richer())
+        } else if mean > target + tolerance {
+            (DecisionReason::QualityHigh, self.cheaper())
+        } else {
+            return None;
+        };
+        self.change(next, reason, Some(mean))
+    }
+
+    /// Record one encoded frame of `bytes` at `fps`. Every second of frames
+    /// updates the measured bitrate; in QP mode a second above
+    /// `max_bitrate_bps` raises the QP.
+    pub fn on_encoded_frame(&mut self, bytes: usize, fps: u32) -> Option<Decision> {
+        let fps = fps.max(1);
+        self.encoded_bits += bytes as u64 * 8;
+        self.encoded_frames += 1;
+        if self.encoded_frames < fps {
+            return None;
+        }
+        let measured = self.encoded_bits * u64::from(fps) / u64::from(self.encoded_frames);
+        self.encoded_bits = 0;
+        self.encoded_frames = 0;
+        self.measured_bitrate_bps = Some(measured);
+
+        if !self.over_ceiling() {
+            return None;
+        }
+        match self.setting {
+            RateSetting::Qp(_) => self.change(self.cheaper(), DecisionReason::BitrateCeiling, None),
+            RateSetting::BitrateBps(_) => None,
+        }
+    }
+
+    /// Start both windows over at a scene cut. Returns a keyframe request
+    /// when `keyframe_on_scene_change` is set.
+    pub fn on_scene_change(&mut self) -> Option<Decision> {
+        self.quality_sum = 0.0;
+        self.quality_samples = 0;
+        self.encoded_bits = 0;
+        self.encoded_frames = 0;
+        self.measured_bitrate_bps = None;
+        self.settings
+            .keyframe_on_scene_change
+            .then(|| self.decision(DecisionReason::SceneChange, true, None))
+    }
+
+    fn over_ceiling(&self) -> bool {
+        self.measured_bitrate_bps
+            .is_some_and(|measured| measured > u64::from(self.settings.max_bitrate_bps))
+    }
+
+    /// One step toward better quality, within bounds.
+    fn richer(&self) -> RateSetting {
+        match self.setting {
+            RateSetting::BitrateBps(bps) => {
+                let step = self.bitrate_step(bps);
+                RateSetting::BitrateBps(
+                    bps.saturating_add(step)
+                        .clamp(self.settings.min_bitrate_bps, self.settings.max_bitrate_bps),
+                )
+            }
+            // A lower QP means more bits; hold while over the ceiling.
+            RateSetting::Qp(qp) if self.over_ceiling() => RateSetting::Qp(qp),
+            RateSetting::Qp(qp) => RateSetting::Qp(
+                qp.saturating_sub(1)
+                    .clamp(self.settings.min_qp, self.settings.max_qp),
+            ),
+        }
+    }
+
+    /// One step toward fewer bits, within bounds.
+    fn cheaper(&self) -> RateSetting {
+        match self.setting {
+            RateSetting::BitrateBps(bps) => {
+                let step = self.bitrate_step(bps);
+                RateSetting::BitrateBps(
+                    bps.saturating_sub(step)
+                        .clamp(self.settings.min_bitrate_bps, self.settings.max_bitrate_bps),
+                )
+            }
+            RateSetting::Qp(qp) => {
+                RateSetting::Qp((qp + 1).clamp(self.settings.min_qp, self.settings.max_qp))
+            }
+        }
+    }
+
+    fn bitrate_step(&self, bps: u32) -> u32 {
+        let step = u64::from(bps) * u64::from(self.settings.bitrate_step_percent) / 100;
+        (step as u32).max(1)
+    }
+
+    fn change(
+        &mut self,
+        next: RateSetting,
+        reason: DecisionReason,
+        mean_quality: Option<f32>,
+    ) -> Option<Decision> {
+        if next == self.setting {
+            return None;
+        }
+        self.setting = next;
+        Some(self.decision(reason, false, mean_quality))
+    }
+
+    fn decision(
+        &self,
+        reason: DecisionReason,
+        force_keyframe: bool,
+        mean_quality: Option<f32>,
+    ) -> Decision {
+        Decision {
+            reason,
+            setting: self.setting,
+            force_keyframe,
+            mean_quality,
+            measured_bitrate_bps: self.measured_bitrate_bps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(mode: RateMode) -> ControllerSettings {
+        ControllerSettings {
+            mode,
+            target_quality: 40.0,
+            quality_tolerance: 1.0,
+            settle_samples: 3,
+            initial_bitrate_bps: 2_000_000,
+            min_bitrate_bps: 1_000_000,
+            max_bitrate_bps: 2_500_000,
+            bitrate_step_percent: 20,
+            initial_qp: 20,
+            min_qp: 19,
+            max_qp: 22,
+            keyframe_on_scene_change: true,
+        }
+    }
+
+    fn feed(controller: &mut AdaptiveController, score: f32) -> Option<Decision> {
+        (0..3).filter_map(|_| controller.on_quality(score)).last()
+    }
+
+    #[test]
+    fn bitrate_mode_steps_toward_target_within_bounds() {
+        let mut controller = AdaptiveController::new(settings(RateMode::Bitrate));
+        assert_eq!(
+            controller.initial_decision().setting,
+            RateSetting::BitrateBps(2_000_000)
+        );
+
+        // Inside the dead band: no change.
+        assert_eq!(feed(&mut controller, 40.5), None);
+
+        let low = feed(&mut controller, 35.0).expect("quality below target");
+        assert_eq!(low.reason, DecisionReason::QualityLow);
+        assert_eq!(low.setting, RateSetting::BitrateBps(2_400_000));
+        assert_eq!(low.mean_quality, Some(35.0));
+        // Clamped to the ceiling, then pinned there.
+        assert_eq!(
+            feed(&mut controller, 35.0).map(|d| d.setting),
+            Some(RateSetting::BitrateBps(2_500_000))
+        );
+        assert_eq!(feed(&mut controller, 35.0), None);
+
+        let high = feed(&mut controller, 45.0).expect("quality above target");
+        assert_eq!(high.reason, DecisionReason::QualityHigh);
+        assert_eq!(high.setting, RateSetting::BitrateBps(2_000_000));
+    }
+
+    #[test]
+    fn decisions_wait_for_a_full_window() {
+        let mut controller = AdaptiveController::new(settings(RateMode::Bitrate));
+        assert_eq!(controller.on_quality(10.0), None);
+        assert_eq!(controller.on_quality(f32::NAN), None);
+        assert_eq!(controller.on_quality(10.0), None);
+        assert!(controller.on_quality(10.0).is_some());
+    }
+
+    #[test]
+    fn qp_mode_steps_one_at_a_time_within_bounds() {
+        let mut controller = AdaptiveController::new(settings(RateMode::Qp));
+        assert_eq!(
+            feed(&mut controller, 35.0).map(|d| d.setting),
+            Some(RateSetting::Qp(19))
+        );
+        assert_eq!(feed(&mut controller, 35.0), None, "pinned at min_qp");
+        for expected in [20, 21, 22] {
+            assert_eq!(
+                feed(&mut controller, 45.0).map(|d| d.setting),
+                Some(RateSetting::Qp(expected))
+            );
+        }
+        assert_eq!(feed(&mut controller, 45.0), None, "pinned at max_qp");
+    }
+
+    #[test]
+    fn measured_bitrate_caps_qp_mode() {
+        let mut controller = AdaptiveController::new(settings(RateMode::Qp));
+        // 10 fps of 40 KB frames: 3.2 Mb/s, over the 2.5 Mb/s ceiling.
+        let decisions: Vec<Decision> = (0..10)
+            .filter_map(|_| controller.on_encoded_frame(40_000, 10))
+            .collect();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].reason, DecisionReason::BitrateCeiling);
+        assert_eq!(decisions[0].setting, RateSetting::Qp(21));
+        assert_eq!(decisions[0].measured_bitrate_bps, Some(3_200_000));
+
+        // Poor quality can't lower the QP while over the ceiling.
+        assert_eq!(feed(&mut controller, 30.0), None);
+
+        // Back under it, it can.
+        for _ in 0..10 {
+            assert_eq!(controller.on_encoded_frame(10_000, 10), None);
+        }
+        assert_eq!(
+            feed(&mut controller, 30.0).map(|d| d.setting),
+            Some(RateSetting::Qp(20))
+        );
+    }
+
+    #[test]
+    fn scene_change_restarts_windows_and_requests_a_keyframe() {
+        let mut controller = AdaptiveController::new(settings(RateMode::Bitrate));
+        controller.on_quality(10.0);
+        controller.on_quality(10.0);
+
+        let cut = controller.on_scene_change().expect("keyframe request");
+        assert_eq!(cut.reason, DecisionReason::SceneChange);
+        assert!(cut.force_keyframe);
+        assert_eq!(cut.setting, RateSetting::BitrateBps(2_000_000));
+
+        // The old scene's two low scores were dropped.
+        assert_eq!(controller.on_quality(40.0), None);
+        assert_eq!(controller.on_quality(40.0), None);
+        assert_eq!(controller.on_quality(40.0), None);
+
+        let mut quiet = AdaptiveController::new(ControllerSettings {
+            keyframe_on_scene_change: false,
+            ..settings(RateMode::Bitrate)
+        });
+        assert_eq!(quiet.on_scene_change(), None);
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/adaptive-encode` — encoder rate control. `AdaptiveEncodeController`
+//! watches quality scores, scene cuts and the encoded bitrate, and steers an
+//! encoder's bitrate or QP through its `control` input within configured
+//! bounds. `BitrateController` adapts the bitrate, and optionally the
+//! resolution, to a transport's congestion reports.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod adaptive_encode;
+pub mod bitrate_controller;
+pub mod congestion;
+pub mod controller;
+
+pub use adaptive_encode::{ADAPTIVE_ENCODE_DECISION_TOPIC, AdaptiveEncodeControllerProcessor};
+pub use bitrate_controller::{BITRATE_CONTROLLER_DECISION_TOPIC, BitrateControllerProcessor};
+pub use congestion::{BitrateController, CongestionDecision, CongestionReason, Rendition};
+pub use controller::{AdaptiveController, Decision, DecisionReason, RateSetting};
+
+streamlib_plugin_abi::export_plugin!(
+    crate::AdaptiveEncodeControllerProcessor::Processor,
+    crate::BitrateControllerProcessor::Processor,
+);
@@ -3,4 +3,15 @@
 
 fn main() {
     streamlib_jtd_codegen::build_rs::run_for_rust_crate();
+
+    // The `GrpcApi` processor's service stubs (server side only); needs
+    // `protoc` on PATH or in `$PROTOC`.
+    #[cfg(feature = "grpc")]
+    tonic_build::configure()
+        .build_client(false)
+        .compile_protos(
+            &["proto/streamlib/control/v1/runtime_control.proto"],
+            &["proto"],
+        )
+        .expect("failed to compile the RuntimeControl protobuf service");
 }
@@ -1,16 +1,21 @@
-// Copyright (c) 2025 Jonathan Fontanez
-// SPDX-License-Identifier: BUSL-1.1
-
-//! Bearer-token authentication for the api-server's mutating routes.
+//! Bearer-token authentication and per-route scopes for the api-server.
 //!
 //! An endpoint that mutates the runtime graph is remote code execution by
 //! design. Auth is an opt-in hardening layer for exposed / fleet deployments,
-//! not a default: a node runs locally with full permission, so the mutating
-//! routes are open unless the config sets `require_auth`. When opted in, every
-//! mutating route is gated behind a shared secret the client presents as
-//! `Authorization: Bearer <token>`; the token is auto-generated on first
-//! `setup()` and persisted at `0600` under the streamlib data dir, so it
-//! survives restarts without being re-issued.
+//! not a default: a node runs locally with full permission, so every route is
+//! open unless the config sets `require_auth` or configures a credential. When
+//! opted in, a client presents `Authorization: Bearer <token>`, and the token
+//! grants one of two [`AuthScope`]s:
+//!
+//! - **write** — every route, graph mutation included;
+//! - **read** — the read-only routes: the tap WebSocket always, and the GET
+//!   routes plus the event stream when `auth_protect_reads` is set.
+//!
+//! A token is one of: the auto-generated secret `require_auth` persists at
+//! `0600` under the streamlib data dir (write scope, survives restarts without
+//! being re-issued); a static token from the config's `auth_write_tokens` /
+//! `auth_read_tokens`; or an OIDC access token from the configured issuer,
+//! scoped by its `scope` claim (see [`crate::oidc`]).
 
 use std::io::Write;
 use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
@@ -27,6 +32,8 @@ use axum::{
 use serde::Serialize;
 use streamlib::sdk::error::{Error, Result};
 
+use crate::oidc::{OidcRejection, OidcValidator};
+
 /// Subdirectory (under the streamlib data dir) that holds the api-server's
 /// persisted control-plane secrets.
 const AUTH_TOKEN_SUBDIR: &str = "api-server";
@@ -38,9 +45,8 @@ const AUTH_TOKEN_FILE: &str = "auth-token";
 /// 64-character secret. 256 bits of entropy — brute-force-infeasible.
 const TOKEN_RANDOM_BYTES: usize = 32;
 
-/// The api-server's bearer token: the shared secret a client presents on
-/// every mutating route. Cheap to clone (the secret sits behind an [`Arc`]),
-/// so it doubles as the auth middleware's state.
+/// One static bearer secret: the persisted `require_auth` token or a token
+/// from the config. Cheap to clone (the secret sits behind an [`Arc`]).
 #[derive(Clone)]
 pub(crate) struct ApiServerBearerToken {
     secret: Arc<String>,
@@ -79,8 +85,7 @@ impl ApiServerBearerToken {
         })
     }
 
-    /// Construct from a known secret — test-only wiring for the middleware.
-    #[cfg(test)]
+    /// Construct from a known secret — a token from the config.
     pub(crate) fn from_secret(secret: impl Into<String>) -> Self {
         Self {
             secret: Arc::new(secret.into()),
@@ -95,6 +100,42 @@ impl ApiServerBearerToken {
     }
 }
 
+/// The static tokens a control-plane config names: the persisted
+/// `require_auth` secret (write scope) plus its `auth_write_tokens` /
+/// `auth_read_tokens`. `owner` prefixes the log line and errors.
+pub(crate) fn configured_tokens(
+    owner: &str,
+    require_auth: bool,
+    write_tokens: &[String],
+    read_tokens: &[String],
+) -> Result<Vec<(ApiServerBearerToken, AuthScope)>> {
+    let mut tokens = Vec::new();
+    if require_auth {
+        tokens.push((
+            ApiServerBearerToken::load_or_create_under_data_dir()?,
+            AuthScope::Write,
+        ));
+        tracing::info!(
+            "{owner} bearer token at {}",
+            ApiServerBearerToken::default_token_path().display()
+        );
+    }
+    for (secrets, scope) in [
+        (write_tokens, AuthScope::Write),
+        (read_tokens, AuthScope::Read),
+    ] {
+        for secret in secrets {
+            if secret.is_empty() {
+                return Err(Error::Configuration(format!(
+                    "{owner}: auth tokens must not be empty"
+                )));
+            }
+            tokens.push((ApiServerBearerToken::from_secret(secret.clone()), scope));
+        }
+    }
+    Ok(tokens)
+}
+
 /// Read an existing, non-empty token file. `Ok(None)` when the file is absent
 /// (first run) or empty (a truncated write); any other IO error propagates.
 fn read_persisted_token(path: &Path) -> Result<Option<String>> {
@@ -146,6 +187,160 @@ fn persist_token_0600(path: &Path, secret: &str) -> Result<()> {
     Ok(())
 }
 
+/// What a credential may reach. Write implies read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum AuthScope {
+    Read,
+    Write,
+}
+
+/// The api-server's auth state: its static tokens and optional OIDC
+/// validator. Cheap to clone; [`Self::gate`] binds it to the scope a route
+/// group requires for [`require_scope`].
+#[derive(Clone)]
+pub(crate) struct ApiServerAuth {
+    inner: Arc<ApiServerAuthInner>,
+}
+
+struct ApiServerAuthInner {
+    tokens: Vec<(ApiServerBearerToken, AuthScope)>,
+    oidc: Option<OidcValidator>,
+    protect_reads: bool,
+}
+
+/// Why [`ApiServerAuth::authorize`] turned a request away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AuthDenial {
+    /// No usable `Authorization: Bearer` credential.
+    MissingBearerToken,
+    /// Matches no static token and fails OIDC validation.
+    InvalidBearerToken,
+    /// Authentic, but short of the required scope.
+    InsufficientScope,
+}
+
+impl AuthDenial {
+    /// The typed error discriminator the wire bodies carry.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingBearerToken => "MissingBearerToken",
+            Self::InvalidBearerToken => "InvalidBearerToken",
+            Self::InsufficientScope => "InsufficientScope",
+        }
+    }
+}
+
+/// How a presented credential resolved.
+enum Credential {
+    /// Matches no static token and fails OIDC validation.
+    Unknown,
+    /// Authentic; `None` when it carries neither configured OIDC scope.
+    Granted(Option<AuthScope>),
+}
+
+impl ApiServerAuth {
+    pub(crate) fn new(
+        tokens: Vec<(ApiServerBearerToken, AuthScope)>,
+        oidc: Option<OidcValidator>,
+        protect_reads: bool,
+    ) -> Self {
+        Self {
+            inner: Arc::new(ApiServerAuthInner {
+                tokens,
+                oidc,
+                protect_reads,
+            }),
+        }
+    }
+
+    /// One write-scoped secret, reads open — test-only wiring for the
+    /// middleware.
+    #[cfg(test)]
+    pub(crate) fn from_secret(secret: impl Into<String>) -> Self {
+        Self::new(
+            vec![(ApiServerBearerToken::from_secret(secret), AuthScope::Write)],
+            None,
+            false,
+        )
+    }
+
+    /// Whether the read-only routes require a read-scoped credential.
+    pub(crate) fn protects_reads(&self) -> bool {
+        self.inner.protect_reads
+    }
+
+    /// Middleware state admitting credentials that grant `required`.
+    pub(crate) fn gate(&self, required: AuthScope) -> ScopeGate {
+        ScopeGate {
+            auth: self.clone(),
+            required,
+        }
+    }
+
+    /// Check an `Authorization` header value against `required` — the one
+    /// decision [`require_scope`] and the gRPC front end both render.
+    pub(crate) async fn authorize(
+        &self,
+        authorization: Option<&str>,
+        required: AuthScope,
+    ) -> std::result::Result<(), AuthDenial> {
+        let Some(token) = authorization.and_then(bearer_token_from_header) else {
+            return Err(AuthDenial::MissingBearerToken);
+        };
+        match self.resolve(token).await {
+            Credential::Granted(Some(scope)) if scope >= required => Ok(()),
+            Credential::Granted(_) => Err(AuthDenial::InsufficientScope),
+            Credential::Unknown => Err(AuthDenial::InvalidBearerToken),
+        }
+    }
+
+    async fn resolve(&self, presented: &str) -> Credential {
+        // Every static token is compared, so the match position doesn't leak
+        // through timing.
+        let matched = self
+            .inner
+            .tokens
+            .iter()
+            .filter(|(token, _)| token.matches(presented))
+            .map(|&(_, scope)| scope)
+            .max();
+        if let Some(scope) = matched {
+            return Credential::Granted(Some(scope));
+        }
+        let Some(oidc) = &self.inner.oidc else {
+            return Credential::Unknown;
+        };
+        let mut verdict = oidc.validate(presented);
+        if matches!(verdict, Err(OidcRejection::UnknownKey(_))) {
+            // The issuer may have rotated keys; refetch off the async workers.
+            let inner = Arc::clone(&self.inner);
+            let refreshed = tokio::task::spawn_blocking(move || {
+                inner.oidc.as_ref().is_some_and(|oidc| oidc.refresh_keys())
+            })
+            .await
+            .unwrap_or(false);
+            if refreshed {
+                verdict = oidc.validate(presented);
+            }
+        }
+        match verdict {
+            Ok(scope) => Credential::Granted(scope),
+            Err(rejection) => {
+                tracing::debug!("ApiServer rejected OIDC token: {:?}", rejection);
+                Credential::Unknown
+            }
+        }
+    }
+}
+
+/// [`require_scope`]'s state: the auth to check against and the scope the
+/// guarded routes need.
+#[derive(Clone)]
+pub(crate) struct ScopeGate {
+    auth: ApiServerAuth,
+    required: AuthScope,
+}
+
 /// Body for `401 Unauthorized` — no usable `Authorization: Bearer` header was
 /// presented. Mirrors the typed-discriminator shape of the graph error
 /// responses in [`crate::state`].
@@ -155,43 +350,43 @@ pub(crate) struct UnauthorizedResponse {
     pub error: &'static str,
 }
 
-/// Body for `403 Forbidden` — a bearer token was presented but did not match
-/// the server's secret.
+/// Body for `403 Forbidden` — a bearer token was presented but is not one
+/// the server accepts, or does not grant the route's scope.
 #[derive(Serialize, utoipa::ToSchema)]
 pub(crate) struct ForbiddenResponse {
-    /// Typed error discriminator: always `"InvalidBearerToken"`.
+    /// Typed error discriminator: `"InvalidBearerToken"` or
+    /// `"InsufficientScope"`.
     pub error: &'static str,
 }
 
-/// Auth middleware gating the mutating routes: rejects a missing / malformed
-/// `Authorization` header with `401`, a wrong token with `403`, and otherwise
-/// runs the inner handler. Its state (the expected token) is supplied by
-/// [`axum::middleware::from_fn_with_state`], independent of the router's
-/// `AppState`.
-pub(crate) async fn require_bearer_token(
-    State(expected): State<ApiServerBearerToken>,
+/// Auth middleware gating a route group: rejects a missing / malformed
+/// `Authorization` header with `401`, an unknown token with `403
+/// InvalidBearerToken`, a token short of the gate's scope with `403
+/// InsufficientScope`, and otherwise runs the inner handler. Its state is
+/// supplied by [`axum::middleware::from_fn_with_state`] with
+/// [`ApiServerAuth::gate`], independent of the router's `AppState`.
+pub(crate) async fn require_scope(
+    State(gate): State<ScopeGate>,
     request: Request,
     next: Next,
 ) -> Response {
-    let presented = request
+    let authorization = request
         .headers()
         .get(AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .and_then(bearer_token_from_header);
-
-    match presented {
-        None => (
+        .and_then(|value| value.to_str().ok());
+    match gate.auth.authorize(authorization, gate.required).await {
+        Ok(()) => next.run(request).await,
+        Err(AuthDenial::MissingBearerToken) => (
             StatusCode::UNAUTHORIZED,
             Json(UnauthorizedResponse {
-                error: "MissingBearerToken",
+                error: AuthDenial::MissingBearerToken.as_str(),
             }),
         )
             .into_response(),
-        Some(token) if expected.matches(token) => next.run(request).await,
-        Some(_) => (
+        Err(denial) => (
             StatusCode::FORBIDDEN,
             Json(ForbiddenResponse {
-                error: "InvalidBearerToken",
+                error: denial.as_str(),
             }),
         )
             .into_response(),
@@ -220,17 +415,25 @@ mod tests {
     use axum::{Router, body::Body, http::Request, routing::post};
     use tower::ServiceExt;
 
-    fn protected_test_router(token: ApiServerBearerToken) -> Router {
+    fn protected_test_router(auth: ApiServerAuth) -> Router {
         Router::new()
             .route("/mutate", post(|| async { StatusCode::OK }))
             .route_layer(axum::middleware::from_fn_with_state(
-                token,
-                require_bearer_token,
+                auth.gate(AuthScope::Write),
+                require_scope,
             ))
+            .merge(
+                Router::new()
+                    .route("/read", axum::routing::get(|| async { StatusCode::OK }))
+                    .route_layer(axum::middleware::from_fn_with_state(
+                        auth.gate(AuthScope::Read),
+                        require_scope,
+                    )),
+            )
     }
 
     async fn status_for_auth_header(auth: Option<&str>) -> StatusCode {
-        let router = protected_test_router(ApiServerBearerToken::from_secret("correct-horse"));
+        let router = protected_test_router(ApiServerAuth::from_secret("correct-horse"));
         let mut builder = Request::builder().method("POST").uri("/mutate");
         if let Some(value) = auth {
             builder = builder.header(AUTHORIZATION, value);
@@ -282,6 +485,54 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn read_tokens_reach_read_routes_but_not_mutating_ones() {
+        let auth = ApiServerAuth::new(
+            vec![
+                (
+                    ApiServerBearerToken::from_secret("writer"),
+                    AuthScope::Write,
+                ),
+                (ApiServerBearerToken::from_secret("reader"), AuthScope::Read),
+            ],
+            None,
+            true,
+        );
+        let request = |method: &str, uri: &str, token: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let send = |request: Request<Body>| protected_test_router(auth.clone()).oneshot(request);
+
+        let denied = send(request("POST", "/mutate", "reader")).await.unwrap();
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(denied.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["error"],
+            "InsufficientScope"
+        );
+
+        for (method, uri, token) in [
+            ("GET", "/read", "reader"),
+            ("GET", "/read", "writer"),
+            ("POST", "/mutate", "writer"),
+        ] {
+            let status = send(request(method, uri, token)).await.unwrap().status();
+            assert_eq!(status, StatusCode::OK, "{method} {uri} with {token}");
+        }
+        let status = send(request("GET", "/read", "stranger"))
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
     #[test]
     fn token_is_generated_persisted_0600_and_reused() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,115 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Latest camera control descriptors per processor, cached from the custom
+//! events `@tatolab/camera` publishes so `GET
+//! /api/processors/{id}/camera-controls` can answer without a device round
+//! trip.
+
+use std::collections::HashMap;
+
+use streamlib::sdk::error::Result;
+use streamlib::sdk::pubsub::{Event, EventListener, RuntimeEvent};
+
+/// Topic the camera processor publishes its control descriptors on, after
+/// start and after every config update.
+pub(crate) const CAMERA_CONTROLS_TOPIC: &str = "camera:controls";
+
+/// Last `camera:controls` payload seen per processor id. Subscribed to the
+/// camera topic and to runtime-global events, so a removed processor's
+/// entry is evicted.
+#[derive(Default)]
+pub(crate) struct CameraControlsCache {
+    by_processor: HashMap<String, serde_json::Value>,
+}
+
+impl CameraControlsCache {
+    pub(crate) fn get(&self, processor_id: &str) -> Option<serde_json::Value> {
+        self.by_processor.get(processor_id).cloned()
+    }
+}
+
+impl EventListener for CameraControlsCache {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Custom { topic, data } if topic == CAMERA_CONTROLS_TOPIC => {
+                match data.get("processor_id").and_then(serde_json::Value::as_str) {
+                    Some(processor_id) => {
+                        self.by_processor
+                            .insert(processor_id.to_string(), data.clone());
+                    }
+                    None => tracing::debug!(
+                        "[api-server] Ignoring {} event without a processor_id",
+                        CAMERA_CONTROLS_TOPIC
+                    ),
+                }
+            }
+            Event::RuntimeGlobal(RuntimeEvent::RuntimeDidRemoveProcessor { processor_id }) => {
+                self.by_processor.remove(processor_id.as_str());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controls_event(processor_id: &str, gain: i64) -> Event {
+        Event::custom(
+            CAMERA_CONTROLS_TOPIC,
+            serde_json::json!({
+                "processor_id": processor_id,
+                "controls": [{ "control": "gain", "value": gain }],
+            }),
+        )
+    }
+
+    #[test]
+    fn keeps_the_latest_payload_per_processor() {
+        let mut cache = CameraControlsCache::default();
+
+        cache.on_event(&controls_event("cam-1", 4)).unwrap();
+        cache.on_event(&controls_event("cam-1", 8)).unwrap();
+        cache.on_event(&controls_event("cam-2", 1)).unwrap();
+
+        assert_eq!(cache.get("cam-1").unwrap()["controls"][0]["value"], 8);
+        assert_eq!(cache.get("cam-2").unwrap()["controls"][0]["value"], 1);
+        assert!(cache.get("cam-3").is_none());
+    }
+
+    #[test]
+    fn ignores_other_topics_and_payloads_without_a_processor_id() {
+        let mut cache = CameraControlsCache::default();
+
+        cache
+            .on_event(&Event::custom(
+                "other:topic",
+                serde_json::json!({ "processor_id": "cam-1" }),
+            ))
+            .unwrap();
+        cache
+            .on_event(&Event::custom(CAMERA_CONTROLS_TOPIC, serde_json::json!({})))
+            .unwrap();
+
+        assert!(cache.by_processor.is_empty());
+    }
+
+    #[test]
+    fn removing_the_processor_evicts_its_entry() {
+        let mut cache = CameraControlsCache::default();
+        cache.on_event(&controls_event("cam-1", 4)).unwrap();
+
+        cache
+            .on_event(&Event::RuntimeGlobal(
+                RuntimeEvent::RuntimeDidRemoveProcessor {
+                    processor_id: "cam-1".into(),
+                },
+            ))
+            .unwrap();
+
+        assert!(cache.get("cam-1").is_none());
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `ControllerAgent` processor — remote management by a fleet
+//! controller.
+//!
+//! An agent thread keeps a long poll open to the controller, so every
+//! connection is outbound and a runtime behind NAT or a firewall needs no
+//! open port. Each command it receives is verified and applied as one
+//! all-or-nothing batch by [`crate::controller_command`]; the outcome is
+//! POSTed back to the controller and published on
+//! [`CONTROLLER_COMMAND_TOPIC`]. While the controller is unreachable the
+//! agent re-polls with exponential backoff.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, PUBSUB};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::controller_command::{
+    ControllerCommandReport, ControllerCommandStatus, ControllerPublicKey, ControllerSeqStore,
+    SignedControllerCommand, apply_controller_actions, command_rejection,
+};
+
+/// Custom-event topic each handled command's report is published on.
+pub const CONTROLLER_COMMAND_TOPIC: &str = "controller:command";
+
+/// Header identifying the polling runtime instance to the controller.
+pub const CONTROLLER_RUNTIME_ID_HEADER: &str = "X-Streamlib-Runtime-Id";
+
+/// State location under the streamlib data dir when `state_dir` is unset.
+const CONTROLLER_AGENT_SUBDIR: &str = "controller-agent";
+
+const DEFAULT_POLL_WAIT_MS: u32 = 20_000;
+const DEFAULT_RETRY_BACKOFF_MS: u32 = 1000;
+const DEFAULT_TIMEOUT_MS: u32 = 10_000;
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// HTTP(S) side of the agent.
+struct ControllerClient {
+    agent: ureq::Agent,
+    commands_url: String,
+    results_url: String,
+    token: Option<String>,
+    runtime_id: String,
+    poll_wait_ms: u32,
+}
+
+impl ControllerClient {
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self
+            .agent
+            .request(method, url)
+            .set(CONTROLLER_RUNTIME_ID_HEADER, &self.runtime_id);
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    /// The next command after `after`, or `None` when the poll window
+    /// closed without one (204).
+    fn poll(
+        &self,
+        after: Option<u64>,
+    ) -> std::result::Result<Option<SignedControllerCommand>, String> {
+        let mut request = self
+            .request("GET", &self.commands_url)
+            .query("wait_ms", &self.poll_wait_ms.to_string());
+        if let Some(after) = after {
+            request = request.query("after", &after.to_string());
+        }
+        match request.call() {
+            Ok(response) if response.status() == 204 => Ok(None),
+            Ok(response) => {
+                let body = response
+                    .into_string()
+                    .map_err(|e| format!("read command: {e}"))?;
+                serde_json::from_str(&body)
+                    .map(Some)
+                    .map_err(|e| format!("malformed signed command: {e}"))
+            }
+            Err(ureq::Error::Status(code, _)) => Err(format!("controller responded {code}")),
+            Err(ureq::Error::Transport(transport)) => Err(transport.to_string()),
+        }
+    }
+
+    fn report(&self, report: &ControllerCommandReport) -> std::result::Result<(), String> {
+        let body = serde_json::to_string(report).map_err(|e| e.to_string())?;
+        match self
+            .request("POST", &self.results_url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+        {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(code, _)) => Err(format!("controller responded {code}")),
+            Err(ureq::Error::Transport(transport)) => Err(transport.to_string()),
+        }
+    }
+}
+
+/// State owned by the agent thread.
+struct ControllerAgentWorker {
+    runtime: Arc<dyn RuntimeOperations>,
+    /// Drives the async runtime operations from this plain thread.
+    executor: tokio::runtime::Runtime,
+    client: ControllerClient,
+    public_key: ControllerPublicKey,
+    agent_id: String,
+    processor_id: Option<String>,
+    seq_store: ControllerSeqStore,
+    last_applied_seq: Option<u64>,
+    retry_backoff: Duration,
+}
+
+impl ControllerAgentWorker {
+    /// Poll until the stop sender is dropped. A poll in flight when the
+    /// agent stops is finished, but a command it returns is not applied.
+    fn run(mut self, stop: mpsc::Receiver<()>) {
+        let mut backoff = self.retry_backoff;
+        loop {
+            if !matches!(stop.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                return;
+            }
+            let poll = self.client.poll(self.last_applied_seq);
+            if !matches!(stop.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                return;
+            }
+            match poll {
+                Ok(Some(signed)) => {
+                    backoff = self.retry_backoff;
+                    self.handle(&signed);
+                }
+                Ok(None) => backoff = self.retry_backoff,
+                Err(error) => {
+                    tracing::info!(
+                        %error,
+                        retry_in_ms = backoff.as_millis() as u64,
+                        "ControllerAgent: controller unreachable"
+                    );
+                    if !matches!(
+                        stop.recv_timeout(backoff),
+                        Err(mpsc::RecvTimeoutError::Timeout)
+                    ) {
+                        return;
+                    }
+                    backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+                }
+            }
+        }
+    }
+
+    fn handle(&mut self, signed: &SignedControllerCommand) {
+        let mut report = ControllerCommandReport {
+            agent_id: self.agent_id.clone(),
+            runtime_id: self.client.runtime_id.clone(),
+            seq: None,
+            status: ControllerCommandStatus::Rejected,
+            created: Vec::new(),
+            failed_action: None,
+            error: None,
+            rollback_errors: Vec::new(),
+        };
+        match self.public_key.verify(signed) {
+            Err(error) => report.error = Some(error),
+            Ok(command) => {
+                report.seq = Some(command.seq);
+                if let Some(rejection) = command_rejection(
+                    &command,
+                    &self.agent_id,
+                    self.last_applied_seq,
+                    unix_millis(),
+                ) {
+                    // An expired command is still consumed, or the next
+                    // poll (`after` the last seq) would return it again.
+                    if command.agent_id == self.agent_id
+                        && self.last_applied_seq.is_none_or(|last| command.seq > last)
+                    {
+                        self.consume(command.seq);
+                    }
+                    report.error = Some(rejection);
+                } else {
+                    // Consumed whether it applies or rolls back, so a failing
+                    // command is reported once rather than retried forever.
+                    self.consume(command.seq);
+                    match self
+                        .executor
+                        .block_on(apply_controller_actions(&self.runtime, command.actions))
+                    {
+                        Ok(created) => {
+                            report.status = ControllerCommandStatus::Applied;
+                            report.created = created;
+                        }
+                        Err(failure) => {
+                            report.status = ControllerCommandStatus::Failed;
+                            report.created = failure.created;
+                            report.failed_action = Some(failure.index);
+                            report.error = Some(failure.error);
+                            report.rollback_errors = failure.rollback_errors;
+                        }
+                    }
+                }
+            }
+        }
+
+        match report.status {
+            ControllerCommandStatus::Applied => tracing::info!(
+                seq = report.seq,
+                actions = report.created.len(),
+                "ControllerAgent: command applied"
+            ),
+            ControllerCommandStatus::Failed => tracing::warn!(
+                seq = report.seq,
+                failed_action = report.failed_action,
+                error = report.error.as_deref().unwrap_or_default(),
+                rollback_errors = ?report.rollback_errors,
+                "ControllerAgent: command failed and was rolled back"
+            ),
+            ControllerCommandStatus::Rejected => tracing::warn!(
+                seq = report.seq,
+                error = report.error.as_deref().unwrap_or_default(),
+                "ControllerAgent: command rejected"
+            ),
+        }
+        if let Err(error) = self.client.report(&report) {
+            tracing::warn!(seq = report.seq, %error, "ControllerAgent: result not delivered");
+        }
+        PUBSUB.publish(
+            CONTROLLER_COMMAND_TOPIC,
+            &Event::custom(
+                CONTROLLER_COMMAND_TOPIC,
+                serde_json::json!({
+                    "processor_id": self.processor_id,
+                    "report": report,
+                }),
+            ),
+        );
+    }
+
+    fn consume(&mut self, seq: u64) {
+        self.last_applied_seq = Some(seq);
+        if let Err(e) = self.seq_store.store(seq) {
+            tracing::warn!(
+                seq,
+                error = %e,
+                "ControllerAgent: could not persist the applied sequence number"
+            );
+        }
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/ControllerAgent",
+    description = "Remote management agent: long-polls a fleet controller over an outbound connection, applies Ed25519-signed graph edits, config updates and start/stop commands all-or-nothing, and reports the results",
+    execution = manual,
+    config = crate::_generated_::ControllerAgentConfig,
+)]
+pub struct ControllerAgentProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    runtime_id: Option<String>,
+    processor_id: Option<String>,
+    public_key: Option<ControllerPublicKey>,
+    /// Dropped to stop the agent thread.
+    stop_tx: Option<mpsc::Sender<()>>,
+}
+
+impl ManualProcessor for ControllerAgentProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if !(self.config.url.starts_with("https://") || self.config.url.starts_with("http://")) {
+            return Err(Error::Config(format!(
+                "ControllerAgent: unsupported controller URL `{}` — expected https:// or http://",
+                self.config.url
+            )));
+        }
+        if self.config.agent_id.is_empty() {
+            return Err(Error::Config(
+                "ControllerAgent: `agent_id` must not be empty".into(),
+            ));
+        }
+        let public_key = ControllerPublicKey::from_hex(&self.config.public_key)
+            .map_err(|e| Error::Config(format!("ControllerAgent: `public_key`: {e}")))?;
+        self.public_key = Some(public_key);
+        self.runtime = Some(ctx.runtime());
+        self.runtime_id = Some(ctx.runtime_id().to_string());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let (Some(runtime), Some(public_key)) = (self.runtime.clone(), self.public_key.clone())
+        else {
+            return Err(Error::Runtime(
+                "ControllerAgent: started before setup".into(),
+            ));
+        };
+        let agent_id = self.config.agent_id.clone();
+        let state_dir = self.config.state_dir.as_ref().map_or_else(
+            || streamlib::sdk::home::get_streamlib_data_dir().join(CONTROLLER_AGENT_SUBDIR),
+            PathBuf::from,
+        );
+        let seq_store = ControllerSeqStore::open(&state_dir, &agent_id).map_err(|e| {
+            Error::Config(format!(
+                "ControllerAgent: open state dir {}: {e}",
+                state_dir.display()
+            ))
+        })?;
+        let last_applied_seq = seq_store.load().map_err(|e| {
+            Error::Config(format!(
+                "ControllerAgent: read applied sequence number in {}: {e}",
+                state_dir.display()
+            ))
+        })?;
+        let executor = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!(
+                    "ControllerAgent: failed to build tokio runtime: {e}"
+                ))
+            })?;
+
+        let poll_wait_ms = self.config.poll_wait_ms.unwrap_or(DEFAULT_POLL_WAIT_MS);
+        let timeout =
+            Duration::from_millis(self.config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS) as u64);
+        let base_url = self.config.url.trim_end_matches('/');
+        let client = ControllerClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout(timeout + Duration::from_millis(poll_wait_ms as u64))
+                .build(),
+            commands_url: format!("{base_url}/agents/{agent_id}/commands"),
+            results_url: format!("{base_url}/agents/{agent_id}/results"),
+            token: self.config.token.clone(),
+            runtime_id: self.runtime_id.clone().unwrap_or_default(),
+            poll_wait_ms,
+        };
+        let worker = ControllerAgentWorker {
+            runtime,
+            executor,
+            client,
+            public_key,
+            agent_id: agent_id.clone(),
+            processor_id: self.processor_id.clone(),
+            seq_store,
+            last_applied_seq,
+            retry_backoff: Duration::from_millis(
+                self.config
+                    .retry_backoff_ms
+                    .unwrap_or(DEFAULT_RETRY_BACKOFF_MS) as u64,
+            ),
+        };
+        let (stop_tx, stop_rx) = mpsc::channel();
+        // Not joined on stop: a long poll may be in flight for up to
+        // `poll_wait_ms`, and the thread exits on its own once it returns.
+        std::thread::Builder::new()
+            .name("controller-agent".into())
+            .spawn(move || worker.run(stop_rx))
+            .map_err(|e| Error::Runtime(format!("ControllerAgent: spawn agent thread: {e}")))?;
+        self.stop_tx = Some(stop_tx);
+
+        tracing::info!(
+            controller = %base_url,
+            %agent_id,
+            ?last_applied_seq,
+            "ControllerAgent polling for commands"
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop_tx.take();
+        tracing::info!("ControllerAgent stopped");
+        Ok(())
+    }
+}
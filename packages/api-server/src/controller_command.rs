@@ -0,0 +1,980 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Signed fleet-controller commands: wire types, verification, replay
+//! guard and all-or-nothing application.
+//!
+//! A controller signs the exact JSON text of a [`ControllerCommand`] with
+//! Ed25519 and ships it with the hex signature as a
+//! [`SignedControllerCommand`]. The agent verifies the signature over those
+//! bytes — no canonicalization — then checks the command is addressed to it,
+//! unexpired and newer than the last one it applied. Actions run in order
+//! through [`RuntimeOperations`]; if one fails, the actions already applied
+//! are undone newest-first, the same all-or-nothing contract as
+//! [`crate::ops::submit_processor_source`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use streamlib::sdk::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::graph::{InputLinkPortRef, LinkUniqueId, OutputLinkPortRef, ProcessorUniqueId};
+use streamlib::sdk::json_schema::{GraphResponse, SchemaIdentOutput};
+use streamlib::sdk::processors::ProcessorSpec;
+use streamlib::sdk::runtime::RuntimeOperations;
+
+/// Prefix marking a processor id as the alias of a processor added earlier
+/// in the same command (`"$camera"`).
+const PROCESSOR_ALIAS_PREFIX: char = '$';
+
+/// Processor state string a paused processor reports in the graph.
+const PAUSED_STATE: &str = "Paused";
+
+/// A command as delivered: the signed JSON text and its signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedControllerCommand {
+    /// JSON text of a [`ControllerCommand`], verified byte-for-byte.
+    pub command: String,
+    /// Hex Ed25519 signature of `command`'s UTF-8 bytes.
+    pub signature: String,
+}
+
+/// One batch of graph edits, applied all-or-nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControllerCommand {
+    /// Agent the command is addressed to.
+    pub agent_id: String,
+    /// Strictly increasing per agent; a command at or below the last
+    /// applied sequence number is a replay and is rejected.
+    pub seq: u64,
+    /// Unix-epoch milliseconds after which the command is rejected unapplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    pub actions: Vec<ControllerAction>,
+}
+
+/// A single graph edit. Processor ids may name a processor added earlier in
+/// the same command by its alias, as `$<alias>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ControllerAction {
+    AddProcessor {
+        processor_type: SchemaIdentOutput,
+        /// Processor config (default `{}`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        config: Option<serde_json::Value>,
+        /// Name later actions refer to this processor by, as `$<alias>`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        alias: Option<String>,
+    },
+    RemoveProcessor {
+        processor_id: String,
+    },
+    Connect {
+        from_processor: String,
+        from_port: String,
+        to_processor: String,
+        to_port: String,
+    },
+    Disconnect {
+        link_id: String,
+    },
+    /// Replace a processor's whole config.
+    UpdateConfig {
+        processor_id: String,
+        config: serde_json::Value,
+    },
+    /// Resume a stopped processor.
+    StartProcessor {
+        processor_id: String,
+    },
+    /// Pause a processor in place; it keeps its links and config.
+    StopProcessor {
+        processor_id: String,
+    },
+}
+
+/// How a command ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControllerCommandStatus {
+    /// Every action was applied.
+    Applied,
+    /// An action failed and the applied ones were rolled back.
+    Failed,
+    /// The command was refused before any action ran (bad signature, wrong
+    /// agent, replayed or expired).
+    Rejected,
+}
+
+/// Result the agent reports back to the controller.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControllerCommandReport {
+    pub agent_id: String,
+    pub runtime_id: String,
+    /// `None` when the command could not be verified, so its sequence number
+    /// is untrusted.
+    pub seq: Option<u64>,
+    pub status: ControllerCommandStatus,
+    /// Per applied action, the id it created (processor id for
+    /// `add_processor`, link id for `connect`), in action order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<Option<String>>,
+    /// Index of the failed action, for [`ControllerCommandStatus::Failed`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_action: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Inverse actions that failed during rollback; non-empty means the
+    /// graph was left partially edited.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollback_errors: Vec<String>,
+}
+
+/// Ed25519 public key commands are verified under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ControllerPublicKey([u8; 32]);
+
+impl ControllerPublicKey {
+    pub fn from_hex(hex_key: &str) -> std::result::Result<Self, String> {
+        let bytes = hex::decode(hex_key.trim()).map_err(|e| format!("not hex: {e}"))?;
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()))?;
+        Ok(Self(key))
+    }
+
+    /// Verify `signed`'s signature and decode the command it covers.
+    pub fn verify(
+        &self,
+        signed: &SignedControllerCommand,
+    ) -> std::result::Result<ControllerCommand, String> {
+        let signature = hex::decode(signed.signature.trim())
+            .map_err(|e| format!("signature is not hex: {e}"))?;
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, &self.0)
+            .verify(signed.command.as_bytes(), &signature)
+            .map_err(|_| "signature does not verify under the controller key".to_string())?;
+        serde_json::from_str(&signed.command).map_err(|e| format!("malformed command: {e}"))
+    }
+}
+
+/// Why a verified command is not applied.
+pub(crate) fn command_rejection(
+    command: &ControllerCommand,
+    agent_id: &str,
+    last_applied_seq: Option<u64>,
+    now_ms: u64,
+) -> Option<String> {
+    if command.agent_id != agent_id {
+        return Some(format!(
+            "command is addressed to agent `{}`, not `{agent_id}`",
+            command.agent_id
+        ));
+    }
+    if let Some(last) = last_applied_seq
+        && command.seq <= last
+    {
+        return Some(format!(
+            "replayed command: seq {} is not after the last applied seq {last}",
+            command.seq
+        ));
+    }
+    if let Some(expires_at_ms) = command.expires_at_ms
+        && expires_at_ms <= now_ms
+    {
+        return Some(format!("command expired at {expires_at_ms} ms"));
+    }
+    None
+}
+
+/// Last applied sequence number, persisted so a restart cannot be used to
+/// replay an old command.
+pub(crate) struct ControllerSeqStore {
+    path: PathBuf,
+}
+
+impl ControllerSeqStore {
+    /// Store for `agent_id` under `dir` (created if needed).
+    pub fn open(dir: &Path, agent_id: &str) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file_name: String = agent_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Ok(Self {
+            path: dir.join(format!("{file_name}.seq")),
+        })
+    }
+
+    pub fn load(&self) -> io::Result<Option<u64>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => contents
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write via a temp file + rename, so a crash never leaves a torn value.
+    pub fn store(&self, seq: u64) -> io::Result<()> {
+        let tmp = self.path.with_extension("seq.tmp");
+        fs::write(&tmp, seq.to_string())?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+/// Why [`apply_controller_actions`] stopped.
+#[derive(Debug)]
+pub(crate) struct ControllerActionFailure {
+    /// Index of the action that failed.
+    pub index: usize,
+    pub error: String,
+    /// Ids created by the actions before `index`; all of them were undone.
+    pub created: Vec<Option<String>>,
+    pub rollback_errors: Vec<String>,
+}
+
+/// An applied action and what undoing it needs.
+enum AppliedAction {
+    Added(ProcessorUniqueId),
+    Removed {
+        processor_id: String,
+        spec: ProcessorSpec,
+        links: Vec<(OutputLinkPortRef, InputLinkPortRef)>,
+    },
+    Connected(LinkUniqueId),
+    Disconnected {
+        from: OutputLinkPortRef,
+        to: InputLinkPortRef,
+    },
+    ConfigReplaced {
+        processor_id: ProcessorUniqueId,
+        previous: serde_json::Value,
+    },
+    PauseSet {
+        processor_id: ProcessorUniqueId,
+        was_paused: bool,
+    },
+}
+
+/// Apply `actions` in order. On the first failure, undo the applied ones
+/// newest-first and report which action failed.
+pub(crate) async fn apply_controller_actions(
+    runtime: &Arc<dyn RuntimeOperations>,
+    actions: Vec<ControllerAction>,
+) -> std::result::Result<Vec<Option<String>>, ControllerActionFailure> {
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut applied: Vec<AppliedAction> = Vec::with_capacity(actions.len());
+    let mut created: Vec<Option<String>> = Vec::with_capacity(actions.len());
+    for (index, action) in actions.into_iter().enumerate() {
+        match apply_action(runtime, action, &mut aliases).await {
+            Ok((undo, id)) => {
+                applied.push(undo);
+                created.push(id);
+            }
+            Err(error) => {
+                let mut rollback_errors = Vec::new();
+                for undo in applied.into_iter().rev() {
+                    if let Err(e) = rollback(runtime, undo).await {
+                        rollback_errors.push(e.to_string());
+                    }
+                }
+                return Err(ControllerActionFailure {
+                    index,
+                    error: error.to_string(),
+                    created,
+                    rollback_errors,
+                });
+            }
+        }
+    }
+    Ok(created)
+}
+
+async fn apply_action(
+    runtime: &Arc<dyn RuntimeOperations>,
+    action: ControllerAction,
+    aliases: &mut HashMap<String, String>,
+) -> Result<(AppliedAction, Option<String>)> {
+    match action {
+        ControllerAction::AddProcessor {
+            processor_type,
+            config,
+            alias,
+        } => {
+            let ident = schema_ident(&processor_type)?;
+            let id = runtime
+                .add_processor_async(ProcessorSpec::new(
+                    ident,
+                    config.unwrap_or_else(|| serde_json::json!({})),
+                ))
+                .await?;
+            if let Some(alias) = alias {
+                aliases.insert(alias, id.to_string());
+            }
+            Ok((AppliedAction::Added(id.clone()), Some(id.to_string())))
+        }
+        ControllerAction::RemoveProcessor { processor_id } => {
+            let processor_id = resolve_alias(aliases, &processor_id)?;
+            let graph = graph(runtime).await?;
+            let node = graph
+                .nodes
+                .iter()
+                .find(|node| node.id == processor_id)
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.clone()))?;
+            let spec = ProcessorSpec::new(
+                schema_ident(&node.processor_type)?,
+                node.config.clone().unwrap_or_else(|| serde_json::json!({})),
+            )
+            .with_display_name(node.display_name.clone());
+            let links = graph
+                .links
+                .iter()
+                .filter(|link| {
+                    link.source.processor_id == processor_id
+                        || link.target.processor_id == processor_id
+                })
+                .map(|link| {
+                    (
+                        OutputLinkPortRef::new(
+                            link.source.processor_id.clone(),
+                            link.source.port_name.clone(),
+                        ),
+                        InputLinkPortRef::new(
+                            link.target.processor_id.clone(),
+                            link.target.port_name.clone(),
+                        ),
+                    )
+                })
+                .collect();
+            runtime
+                .remove_processor_async(processor_id.clone().into())
+                .await?;
+            Ok((
+                AppliedAction::Removed {
+                    processor_id,
+                    spec,
+                    links,
+                },
+                None,
+            ))
+        }
+        ControllerAction::Connect {
+            from_processor,
+            from_port,
+            to_processor,
+            to_port,
+        } => {
+            let from = OutputLinkPortRef::new(resolve_alias(aliases, &from_processor)?, from_port);
+            let to = InputLinkPortRef::new(resolve_alias(aliases, &to_processor)?, to_port);
+            let link_id = runtime.connect_async(from, to).await?;
+            Ok((
+                AppliedAction::Connected(link_id.clone()),
+                Some(link_id.to_string()),
+            ))
+        }
+        ControllerAction::Disconnect { link_id } => {
+            let graph = graph(runtime).await?;
+            let link = graph
+                .links
+                .iter()
+                .find(|link| link.id == link_id)
+                .ok_or_else(|| Error::Runtime(format!("link `{link_id}` not found")))?;
+            let from = OutputLinkPortRef::new(
+                link.source.processor_id.clone(),
+                link.source.port_name.clone(),
+            );
+            let to = InputLinkPortRef::new(
+                link.target.processor_id.clone(),
+                link.target.port_name.clone(),
+            );
+            runtime.disconnect_async(link_id.into()).await?;
+            Ok((AppliedAction::Disconnected { from, to }, None))
+        }
+        ControllerAction::UpdateConfig {
+            processor_id,
+            config,
+        } => {
+            let processor_id = resolve_alias(aliases, &processor_id)?;
+            let previous = graph(runtime)
+                .await?
+                .nodes
+                .into_iter()
+                .find(|node| node.id == processor_id)
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.clone()))?
+                .config
+                .unwrap_or_else(|| serde_json::json!({}));
+            let processor_id = ProcessorUniqueId::from(processor_id);
+            runtime
+                .update_processor_config_async(processor_id.clone(), config)
+                .await?;
+            Ok((
+                AppliedAction::ConfigReplaced {
+                    processor_id,
+                    previous,
+                },
+                None,
+            ))
+        }
+        ControllerAction::StartProcessor { processor_id } => {
+            set_paused(runtime, aliases, &processor_id, false).await
+        }
+        ControllerAction::StopProcessor { processor_id } => {
+            set_paused(runtime, aliases, &processor_id, true).await
+        }
+    }
+}
+
+async fn set_paused(
+    runtime: &Arc<dyn RuntimeOperations>,
+    aliases: &HashMap<String, String>,
+    processor_id: &str,
+    paused: bool,
+) -> Result<(AppliedAction, Option<String>)> {
+    let processor_id = resolve_alias(aliases, processor_id)?;
+    let was_paused = graph(runtime)
+        .await?
+        .nodes
+        .iter()
+        .find(|node| node.id == processor_id)
+        .ok_or_else(|| Error::ProcessorNotFound(processor_id.clone()))?
+        .components
+        .get("state")
+        .and_then(|state| state.as_str())
+        == Some(PAUSED_STATE);
+    let processor_id = ProcessorUniqueId::from(processor_id);
+    runtime
+        .set_processor_paused_async(processor_id.clone(), paused)
+        .await?;
+    Ok((
+        AppliedAction::PauseSet {
+            processor_id,
+            was_paused,
+        },
+        None,
+    ))
+}
+
+async fn rollback(runtime: &Arc<dyn RuntimeOperations>, undo: AppliedAction) -> Result<()> {
+    match undo {
+        AppliedAction::Added(processor_id) => runtime.remove_processor_async(processor_id).await,
+        AppliedAction::Removed {
+            processor_id,
+            spec,
+            links,
+        } => {
+            // The processor comes back under a fresh id; re-point its links.
+            let restored = runtime.add_processor_async(spec).await?.to_string();
+            let rename = |id: &str| {
+                if id == processor_id {
+                    restored.clone()
+                } else {
+                    id.to_string()
+                }
+            };
+            for (from, to) in links {
+                runtime
+                    .connect_async(
+                        OutputLinkPortRef::new(rename(from.processor_id.as_str()), from.port_name),
+                        InputLinkPortRef::new(rename(to.processor_id.as_str()), to.port_name),
+                    )
+                    .await?;
+            }
+            Ok(())
+        }
+        AppliedAction::Connected(link_id) => runtime.disconnect_async(link_id).await,
+        AppliedAction::Disconnected { from, to } => {
+            runtime.connect_async(from, to).await.map(|_| ())
+        }
+        AppliedAction::ConfigReplaced {
+            processor_id,
+            previous,
+        } => {
+            runtime
+                .update_processor_config_async(processor_id, previous)
+                .await
+        }
+        AppliedAction::PauseSet {
+            processor_id,
+            was_paused,
+        } => {
+            runtime
+                .set_processor_paused_async(processor_id, was_paused)
+                .await
+        }
+    }
+}
+
+pub(crate) async fn graph(runtime: &Arc<dyn RuntimeOperations>) -> Result<GraphResponse> {
+    serde_json::from_value(runtime.to_json_async().await?)
+        .map_err(|e| Error::Runtime(format!("parse graph: {e}")))
+}
+
+fn resolve_alias(aliases: &HashMap<String, String>, processor_id: &str) -> Result<String> {
+    match processor_id.strip_prefix(PROCESSOR_ALIAS_PREFIX) {
+        None => Ok(processor_id.to_string()),
+        Some(alias) => aliases.get(alias).cloned().ok_or_else(|| {
+            Error::Runtime(format!(
+                "`{processor_id}` names no processor added earlier in this command"
+            ))
+        }),
+    }
+}
+
+/// Typed conversion through the segment validators, as `POST /api/processor`
+/// does it.
+fn schema_ident(processor_type: &SchemaIdentOutput) -> Result<SchemaIdent> {
+    match (
+        Org::new(processor_type.org.clone()),
+        Package::new(processor_type.package.clone()),
+        TypeName::new(processor_type.type_name.clone()),
+    ) {
+        (Ok(org), Ok(package), Ok(type_name)) => Ok(SchemaIdent::new(
+            org,
+            package,
+            type_name,
+            SemVer::new(
+                processor_type.version.major,
+                processor_type.version.minor,
+                processor_type.version.patch,
+            ),
+        )),
+        _ => Err(Error::Runtime(format!(
+            "malformed processor type `@{}/{}/{}`",
+            processor_type.org, processor_type.package, processor_type.type_name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use streamlib::sdk::graph_edit_history::GraphEdit;
+    use streamlib::sdk::midi_mapping::MidiControlBinding;
+    use streamlib::sdk::parameter_automation::ParameterChange;
+    use streamlib::sdk::preset_morph::PresetMorph;
+    use streamlib::sdk::runtime::{
+        BoxFuture, RegisterProcessorReceipt, ReplaceProcessorFromSource, SubmittedProcessorSource,
+    };
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn sign(key_pair: &Ed25519KeyPair, command: &str) -> SignedControllerCommand {
+        SignedControllerCommand {
+            command: command.to_string(),
+            signature: hex::encode(key_pair.sign(command.as_bytes())),
+        }
+    }
+
+    fn public_key_hex(key_pair: &Ed25519KeyPair) -> String {
+        hex::encode(key_pair.public_key().as_ref())
+    }
+
+    fn command(agent_id: &str, seq: u64, expires_at_ms: Option<u64>) -> ControllerCommand {
+        ControllerCommand {
+            agent_id: agent_id.to_string(),
+            seq,
+            expires_at_ms,
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn verify_accepts_the_signed_bytes_and_nothing_else() {
+        let key_pair = key_pair();
+        let public_key = ControllerPublicKey::from_hex(&public_key_hex(&key_pair)).unwrap();
+        let text = r#"{"agent_id":"edge-1","seq":7,"actions":[{"op":"stop_processor","processor_id":"cam"}]}"#;
+
+        let command = public_key.verify(&sign(&key_pair, text)).unwrap();
+        assert_eq!(command.agent_id, "edge-1");
+        assert_eq!(command.seq, 7);
+        assert!(matches!(
+            command.actions.as_slice(),
+            [ControllerAction::StopProcessor { processor_id }] if processor_id == "cam"
+        ));
+
+        // Re-serialized with different whitespace: same command, but not the
+        // bytes that were signed.
+        let mut reformatted = sign(&key_pair, text);
+        reformatted.command = text.replace(",\"seq\"", ", \"seq\"");
+        assert!(public_key.verify(&reformatted).is_err());
+
+        let mut tampered = sign(&key_pair, text);
+        tampered.command = text.replace("\"seq\":7", "\"seq\":8");
+        assert!(public_key.verify(&tampered).is_err());
+
+        let wrong_key = ControllerPublicKey::from_hex(&public_key_hex(&self::key_pair())).unwrap();
+        assert!(wrong_key.verify(&sign(&key_pair, text)).is_err());
+    }
+
+    #[test]
+    fn public_key_must_be_32_hex_bytes() {
+        assert!(ControllerPublicKey::from_hex(&"ab".repeat(32)).is_ok());
+        assert!(ControllerPublicKey::from_hex(&"ab".repeat(31)).is_err());
+        assert!(ControllerPublicKey::from_hex("not-hex").is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_agent_replays_and_expired_commands() {
+        assert!(command_rejection(&command("edge-1", 1, None), "edge-1", None, 0).is_none());
+        assert!(
+            command_rejection(&command("edge-1", 5, Some(2000)), "edge-1", Some(4), 1000).is_none()
+        );
+
+        let wrong_agent = command_rejection(&command("edge-2", 1, None), "edge-1", None, 0);
+        assert!(wrong_agent.unwrap().contains("edge-2"));
+        assert!(command_rejection(&command("edge-1", 4, None), "edge-1", Some(4), 0).is_some());
+        assert!(command_rejection(&command("edge-1", 3, None), "edge-1", Some(4), 0).is_some());
+        assert!(
+            command_rejection(&command("edge-1", 5, Some(1000)), "edge-1", Some(4), 1000).is_some()
+        );
+    }
+
+    #[test]
+    fn seq_store_round_trips_and_sanitizes_the_agent_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ControllerSeqStore::open(&dir.path().join("state"), "../edge 1").unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        store.store(41).unwrap();
+        store.store(42).unwrap();
+        assert_eq!(store.load().unwrap(), Some(42));
+        assert!(dir.path().join("state").join("___edge_1.seq").exists());
+        assert_eq!(
+            ControllerSeqStore::open(&dir.path().join("state"), "../edge 1")
+                .unwrap()
+                .load()
+                .unwrap(),
+            Some(42)
+        );
+    }
+
+    /// Stub runtime over a fixed two-processor graph (`cam.video -> enc.video`
+    /// as link `L1`, `cam` running with config `{"fps": 30}`). Every call is
+    /// recorded in order; a `connect` to port `missing` fails.
+    struct RecordingStubRuntime {
+        calls: Mutex<Vec<String>>,
+        added: std::sync::atomic::AtomicUsize,
+    }
+
+    impl RecordingStubRuntime {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                calls: Mutex::new(Vec::new()),
+                added: std::sync::atomic::AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().clone()
+        }
+
+        fn record(&self, call: String) {
+            self.calls.lock().push(call);
+        }
+
+        fn graph_json() -> serde_json::Value {
+            let node = |id: &str, type_name: &str, state: &str| {
+                serde_json::json!({
+                    "id": id,
+                    "type": {
+                        "org": "tatolab",
+                        "package": "core",
+                        "type": type_name,
+                        "version": { "major": 1, "minor": 0, "patch": 0 },
+                    },
+                    "display_name": type_name,
+                    "config": { "fps": 30 },
+                    "ports": { "inputs": [], "outputs": [] },
+                    "components": { "state": state },
+                })
+            };
+            serde_json::json!({
+                "nodes": [node("cam", "Camera", "Running"), node("enc", "Encoder", "Running")],
+                "links": [{
+                    "id": "L1",
+                    "source": { "processor_id": "cam", "port_name": "video" },
+                    "target": { "processor_id": "enc", "port_name": "video" },
+                    "components": {},
+                }],
+            })
+        }
+    }
+
+    impl RuntimeOperations for RecordingStubRuntime {
+        fn add_processor_async(
+            &self,
+            spec: ProcessorSpec,
+        ) -> BoxFuture<'_, Result<ProcessorUniqueId>> {
+            let n = self
+                .added
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let id = format!("new-{n}");
+            self.record(format!("add {} as {id}", spec.name));
+            Box::pin(async move { Ok(ProcessorUniqueId::from(id)) })
+        }
+        fn remove_processor_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+        ) -> BoxFuture<'_, Result<()>> {
+            self.record(format!("remove {processor_id}"));
+            Box::pin(async { Ok(()) })
+        }
+        fn connect_async(
+            &self,
+            from: OutputLinkPortRef,
+            to: InputLinkPortRef,
+        ) -> BoxFuture<'_, Result<LinkUniqueId>> {
+            let call = format!(
+                "connect {}.{} -> {}.{}",
+                from.processor_id, from.port_name, to.processor_id, to.port_name
+            );
+            self.record(call.clone());
+            if to.port_name == "missing" {
+                return Box::pin(
+                    async move { Err(Error::Runtime(format!("{call}: no such port"))) },
+                );
+            }
+            Box::pin(async { Ok(LinkUniqueId::from("L-new")) })
+        }
+        fn disconnect_async(&self, link_id: LinkUniqueId) -> BoxFuture<'_, Result<()>> {
+            self.record(format!("disconnect {link_id}"));
+            Box::pin(async { Ok(()) })
+        }
+        fn to_json_async(&self) -> BoxFuture<'_, Result<serde_json::Value>> {
+            Box::pin(async { Ok(Self::graph_json()) })
+        }
+        fn register_processor_source_async(
+            &self,
+            _request: SubmittedProcessorSource,
+        ) -> BoxFuture<'_, Result<RegisterProcessorReceipt>> {
+            Box::pin(async { Err(Error::NotSupported("stub runtime".to_string())) })
+        }
+        fn replace_processor_async(
+            &self,
+            _request: ReplaceProcessorFromSource,
+        ) -> BoxFuture<'_, Result<RegisterProcessorReceipt>> {
+            Box::pin(async { Err(Error::NotSupported("stub runtime".to_string())) })
+        }
+        fn tap_async(
+            &self,
+            channel: String,
+            _count: Option<usize>,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::TapSubscription>> {
+            Box::pin(async move { Err(Error::TapChannelNotFound(channel)) })
+        }
+        fn preview_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _port: String,
+            _options: streamlib::sdk::frame_snapshot::PreviewOptions,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::PortPreview>> {
+            unreachable!("the controller has no preview action")
+        }
+        fn update_processor_config_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            config: serde_json::Value,
+        ) -> BoxFuture<'_, Result<()>> {
+            self.record(format!("config {processor_id} {config}"));
+            Box::pin(async { Ok(()) })
+        }
+        fn set_processor_paused_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            paused: bool,
+        ) -> BoxFuture<'_, Result<()>> {
+            self.record(format!("paused {processor_id} {paused}"));
+            Box::pin(async { Ok(()) })
+        }
+        fn save_graph_snapshot_async(
+            &self,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_snapshot::GraphSnapshot>> {
+            Box::pin(async { Err(Error::NotSupported("stub runtime".to_string())) })
+        }
+        fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async { Ok(None) })
+        }
+        fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async { Ok(None) })
+        }
+        fn apply_graph_patch_async(
+            &self,
+            _patch: streamlib::sdk::graph_patch::GraphPatch,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_patch::GraphPatchReceipt>> {
+            Box::pin(async { Ok(Default::default()) })
+        }
+        fn morph_processor_configs_async(&self, _morph: PresetMorph) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+        fn set_midi_mappings_async(
+            &self,
+            _bindings: Vec<MidiControlBinding>,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+        fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+        fn set_parameter_async(&self, _change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+        fn inject_chaos_fault_async(
+            &self,
+            _fault: streamlib::sdk::chaos::ChaosFault,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::chaos::ChaosFaultRecord>> {
+            unreachable!("the controller has no chaos action")
+        }
+        fn chaos_fault_log_async(
+            &self,
+        ) -> BoxFuture<'_, Result<Vec<streamlib::sdk::chaos::ChaosFaultRecord>>> {
+            unreachable!("the controller has no chaos action")
+        }
+        fn snapshot_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _port: String,
+            _path: std::path::PathBuf,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::frame_snapshot::FrameSnapshot>> {
+            unreachable!("the controller has no snapshot action")
+        }
+        fn add_processor(&self, _spec: ProcessorSpec) -> Result<ProcessorUniqueId> {
+            unreachable!("the controller applies actions through the async API")
+        }
+        fn remove_processor(&self, _processor_id: &ProcessorUniqueId) -> Result<()> {
+            unreachable!("the controller applies actions through the async API")
+        }
+        fn connect(&self, _from: OutputLinkPortRef, _to: InputLinkPortRef) -> Result<LinkUniqueId> {
+            unreachable!("the controller applies actions through the async API")
+        }
+        fn disconnect(&self, _link_id: &LinkUniqueId) -> Result<()> {
+            unreachable!("the controller applies actions through the async API")
+        }
+        fn to_json(&self) -> Result<serde_json::Value> {
+            unreachable!("the controller applies actions through the async API")
+        }
+    }
+
+    fn actions(json: serde_json::Value) -> Vec<ControllerAction> {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn applies_actions_in_order_resolving_aliases() {
+        let stub = RecordingStubRuntime::new();
+        let runtime: Arc<dyn RuntimeOperations> = stub.clone();
+        let created = apply_controller_actions(
+            &runtime,
+            actions(serde_json::json!([
+                {
+                    "op": "add_processor",
+                    "processor_type": {
+                        "org": "tatolab", "package": "core", "type": "Display",
+                        "version": { "major": 1, "minor": 0, "patch": 0 },
+                    },
+                    "alias": "screen",
+                },
+                { "op": "connect", "from_processor": "cam", "from_port": "video",
+                  "to_processor": "$screen", "to_port": "video" },
+                { "op": "update_config", "processor_id": "cam", "config": { "fps": 60 } },
+                { "op": "stop_processor", "processor_id": "enc" },
+            ])),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            created,
+            vec![
+                Some("new-0".to_string()),
+                Some("L-new".to_string()),
+                None,
+                None
+            ]
+        );
+        let calls = stub.calls();
+        assert_eq!(calls[0], "add @tatolab/core/Display@1.0.0 as new-0");
+        assert_eq!(calls[1], "connect cam.video -> new-0.video");
+        assert_eq!(calls[2], r#"config cam {"fps":60}"#);
+        assert_eq!(calls[3], "paused enc true");
+    }
+
+    #[tokio::test]
+    async fn a_failing_action_rolls_back_the_applied_ones_newest_first() {
+        let stub = RecordingStubRuntime::new();
+        let runtime: Arc<dyn RuntimeOperations> = stub.clone();
+        let failure = apply_controller_actions(
+            &runtime,
+            actions(serde_json::json!([
+                { "op": "stop_processor", "processor_id": "cam" },
+                { "op": "update_config", "processor_id": "cam", "config": { "fps": 60 } },
+                { "op": "disconnect", "link_id": "L1" },
+                { "op": "connect", "from_processor": "cam", "from_port": "video",
+                  "to_processor": "enc", "to_port": "missing" },
+            ])),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(failure.index, 3);
+        assert!(failure.error.contains("no such port"), "{}", failure.error);
+        assert!(failure.rollback_errors.is_empty());
+        assert_eq!(
+            stub.calls(),
+            vec![
+                "paused cam true",
+                r#"config cam {"fps":60}"#,
+                "disconnect L1",
+                "connect cam.video -> enc.missing",
+                // Rollback: re-link, previous config, previous pause state.
+                "connect cam.video -> enc.video",
+                r#"config cam {"fps":30}"#,
+                "paused cam false",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rolling_back_a_removal_restores_the_processor_and_its_links() {
+        let stub = RecordingStubRuntime::new();
+        let runtime: Arc<dyn RuntimeOperations> = stub.clone();
+        let failure = apply_controller_actions(
+            &runtime,
+            actions(serde_json::json!([
+                { "op": "remove_processor", "processor_id": "enc" },
+                { "op": "start_processor", "processor_id": "$undeclared" },
+            ])),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(failure.index, 1);
+        assert_eq!(failure.created, vec![None]);
+        let calls = stub.calls();
+        assert_eq!(calls[0], "remove enc");
+        assert_eq!(
+            &calls[1..],
+            [
+                "add @tatolab/core/Encoder@1.0.0 as new-0",
+                "connect cam.video -> new-0.video",
+            ]
+        );
+    }
+}
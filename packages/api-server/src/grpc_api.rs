@@ -0,0 +1,527 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `GrpcApi` processor — the runtime control plane as the versioned
+//! `streamlib.control.v1.RuntimeControl` protobuf service
+//! (`proto/streamlib/control/v1/runtime_control.proto`), for orchestration
+//! layers that generate gRPC clients rather than wrap REST.
+//!
+//! It serves the same operations as the `ApiServer` REST routes — graph
+//! read, processor add / remove / config / pause, connect / disconnect,
+//! scheduled parameter changes — through the same [`RuntimeOperations`],
+//! and streams pubsub events the way `/ws/events` does. Auth reuses
+//! [`ApiServerAuth`]: the bearer credential travels as `authorization`
+//! metadata and maps onto UNAUTHENTICATED / PERMISSION_DENIED.
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_util::Stream;
+use parking_lot::Mutex;
+use serde_json::Value;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef};
+use streamlib::sdk::parameter_automation::{ParameterChange, ParameterInterpolation};
+use streamlib::sdk::processors::{ManualProcessor, ProcessorSpec};
+use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+use streamlib::sdk::runtime::RuntimeOperations;
+use tonic::{Request, Response, Status};
+
+use crate::auth::{ApiServerAuth, AuthDenial, AuthScope, configured_tokens};
+
+/// Generated messages and server stubs for `streamlib.control.v1`.
+#[allow(clippy::all)]
+pub(crate) mod proto {
+    tonic::include_proto!("streamlib.control.v1");
+}
+
+use proto::runtime_control_server::{RuntimeControl, RuntimeControlServer};
+
+const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:50051";
+
+/// The `RuntimeControl` implementation: a thin projection of each RPC onto
+/// [`RuntimeOperations`].
+struct RuntimeControlService {
+    runtime: Arc<dyn RuntimeOperations>,
+    auth: Option<ApiServerAuth>,
+}
+
+impl RuntimeControlService {
+    /// Admit `request` if it may reach an RPC needing `required`. Reads are
+    /// open unless the auth protects them.
+    async fn authorize<T>(
+        &self,
+        request: &Request<T>,
+        required: AuthScope,
+    ) -> std::result::Result<(), Status> {
+        let Some(auth) = &self.auth else {
+            return Ok(());
+        };
+        if required == AuthScope::Read && !auth.protects_reads() {
+            return Ok(());
+        }
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        auth.authorize(authorization, required)
+            .await
+            .map_err(denial_status)
+    }
+}
+
+/// Map an auth refusal onto its gRPC status, carrying the same typed
+/// discriminator the REST bodies do.
+fn denial_status(denial: AuthDenial) -> Status {
+    match denial {
+        AuthDenial::MissingBearerToken => Status::unauthenticated(denial.as_str()),
+        AuthDenial::InvalidBearerToken | AuthDenial::InsufficientScope => {
+            Status::permission_denied(denial.as_str())
+        }
+    }
+}
+
+/// Map a runtime [`Error`] onto a gRPC status — the counterpart of the REST
+/// handlers' status mapping: a missing processor / port / link is
+/// NOT_FOUND, an unregistered processor type FAILED_PRECONDITION, a runtime
+/// fault INTERNAL, anything else a rejected argument.
+fn error_status(error: Error) -> Status {
+    let message = error.to_string();
+    match error {
+        Error::ProcessorNotFound(_)
+        | Error::ProcessorPortNotFound { .. }
+        | Error::LinkNotFound(_)
+        | Error::NotFound(_) => Status::not_found(message),
+        Error::UnknownProcessorType { .. } => Status::failed_precondition(message),
+        Error::Runtime(_) | Error::Io(_) | Error::Other(_) => Status::internal(message),
+        _ => Status::invalid_argument(message),
+    }
+}
+
+/// Parse a JSON-typed request field; `empty` stands in for an empty string.
+fn parse_json_field(field: &str, text: &str, empty: Value) -> std::result::Result<Value, Status> {
+    if text.trim().is_empty() {
+        return Ok(empty);
+    }
+    serde_json::from_str(text)
+        .map_err(|e| Status::invalid_argument(format!("{field} is not valid JSON: {e}")))
+}
+
+/// Validate a wire processor type into a [`SchemaIdent`].
+fn schema_ident(
+    processor_type: Option<proto::ProcessorType>,
+) -> std::result::Result<SchemaIdent, Status> {
+    let processor_type =
+        processor_type.ok_or_else(|| Status::invalid_argument("processor_type is required"))?;
+    let version = processor_type.version.unwrap_or_default();
+    match (
+        Org::new(processor_type.org),
+        Package::new(processor_type.package),
+        TypeName::new(processor_type.type_name),
+    ) {
+        (Ok(org), Ok(package), Ok(type_name)) => Ok(SchemaIdent::new(
+            org,
+            package,
+            type_name,
+            SemVer::new(version.major, version.minor, version.patch),
+        )),
+        _ => Err(Status::invalid_argument(
+            "Malformed processor identifier — one of org / package / type failed validation",
+        )),
+    }
+}
+
+/// Build the runtime's [`ParameterChange`] from its wire form.
+fn parameter_change(
+    request: proto::SetParameterRequest,
+) -> std::result::Result<ParameterChange, Status> {
+    let interpolation = match request.interpolation() {
+        proto::Interpolation::Step => ParameterInterpolation::Step,
+        proto::Interpolation::Linear => ParameterInterpolation::Linear,
+        proto::Interpolation::Exponential => ParameterInterpolation::Exponential,
+    };
+    if request.parameter.is_empty() {
+        return Err(Status::invalid_argument("parameter is required"));
+    }
+    let value = parse_json_field("value_json", &request.value_json, Value::Null)?;
+    let mut change = ParameterChange::new(request.processor_id, request.parameter, value);
+    change.at_ns = request.at_ns;
+    change.interpolation = interpolation;
+    Ok(change)
+}
+
+/// Project a pubsub event onto the wire.
+fn runtime_event(event: &Event) -> Option<proto::RuntimeEvent> {
+    match serde_json::to_string(event) {
+        Ok(event_json) => Some(proto::RuntimeEvent {
+            topic: event.topic(),
+            event_json,
+        }),
+        Err(e) => {
+            tracing::warn!("GrpcApi: failed to serialize event: {}", e);
+            None
+        }
+    }
+}
+
+/// Bridges the synchronous pubsub listener onto an event stream.
+struct GrpcEventForwarder {
+    tx: tokio::sync::mpsc::UnboundedSender<proto::RuntimeEvent>,
+}
+
+impl EventListener for GrpcEventForwarder {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        if let Some(event) = runtime_event(event) {
+            let _ = self.tx.send(event);
+        }
+        Ok(())
+    }
+}
+
+type EventStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<proto::RuntimeEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl RuntimeControl for RuntimeControlService {
+    type StreamEventsStream = EventStream;
+
+    async fn get_graph(
+        &self,
+        request: Request<proto::GetGraphRequest>,
+    ) -> std::result::Result<Response<proto::GetGraphResponse>, Status> {
+        self.authorize(&request, AuthScope::Read).await?;
+        let graph = self.runtime.to_json_async().await.map_err(error_status)?;
+        Ok(Response::new(proto::GetGraphResponse {
+            graph_json: graph.to_string(),
+        }))
+    }
+
+    async fn add_processor(
+        &self,
+        request: Request<proto::AddProcessorRequest>,
+    ) -> std::result::Result<Response<proto::AddProcessorResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        let request = request.into_inner();
+        let ident = schema_ident(request.processor_type)?;
+        let config = parse_json_field(
+            "config_json",
+            &request.config_json,
+            Value::Object(Default::default()),
+        )?;
+        let id = self
+            .runtime
+            .add_processor_async(ProcessorSpec::new(ident, config))
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::AddProcessorResponse {
+            processor_id: id.to_string(),
+        }))
+    }
+
+    async fn remove_processor(
+        &self,
+        request: Request<proto::RemoveProcessorRequest>,
+    ) -> std::result::Result<Response<proto::RemoveProcessorResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        self.runtime
+            .remove_processor_async(request.into_inner().processor_id.into())
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::RemoveProcessorResponse {}))
+    }
+
+    async fn update_processor_config(
+        &self,
+        request: Request<proto::UpdateProcessorConfigRequest>,
+    ) -> std::result::Result<Response<proto::UpdateProcessorConfigResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        let request = request.into_inner();
+        let config = parse_json_field(
+            "config_json",
+            &request.config_json,
+            Value::Object(Default::default()),
+        )?;
+        self.runtime
+            .update_processor_config_async(request.processor_id.into(), config)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::UpdateProcessorConfigResponse {}))
+    }
+
+    async fn set_processor_paused(
+        &self,
+        request: Request<proto::SetProcessorPausedRequest>,
+    ) -> std::result::Result<Response<proto::SetProcessorPausedResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        let request = request.into_inner();
+        self.runtime
+            .set_processor_paused_async(request.processor_id.into(), request.paused)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::SetProcessorPausedResponse {}))
+    }
+
+    async fn connect(
+        &self,
+        request: Request<proto::ConnectRequest>,
+    ) -> std::result::Result<Response<proto::ConnectResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        let request = request.into_inner();
+        let from = OutputLinkPortRef::new(request.from_processor, request.from_port);
+        let to = InputLinkPortRef::new(request.to_processor, request.to_port);
+        let link_id = self
+            .runtime
+            .connect_async(from, to)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::ConnectResponse {
+            link_id: link_id.to_string(),
+        }))
+    }
+
+    async fn disconnect(
+        &self,
+        request: Request<proto::DisconnectRequest>,
+    ) -> std::result::Result<Response<proto::DisconnectResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        self.runtime
+            .disconnect_async(request.into_inner().link_id.into())
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::DisconnectResponse {}))
+    }
+
+    async fn set_parameter(
+        &self,
+        request: Request<proto::SetParameterRequest>,
+    ) -> std::result::Result<Response<proto::SetParameterResponse>, Status> {
+        self.authorize(&request, AuthScope::Write).await?;
+        let change = parameter_change(request.into_inner())?;
+        self.runtime
+            .set_parameter_async(change)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(proto::SetParameterResponse {}))
+    }
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> std::result::Result<Response<Self::StreamEventsStream>, Status> {
+        self.authorize(&request, AuthScope::Read).await?;
+        let mut requested = request.into_inner().topics;
+        requested.sort();
+        requested.dedup();
+        if requested.is_empty() {
+            requested.push(topics::ALL.to_string());
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        // The bus holds listeners weakly; the stream owns the strong ref, so
+        // dropping the stream (client cancel) unsubscribes it.
+        let listener = Arc::new(Mutex::new(GrpcEventForwarder { tx }));
+        for topic in &requested {
+            PUBSUB.subscribe(topic, listener.clone());
+        }
+        let stream = futures_util::stream::unfold((rx, listener), |(mut rx, listener)| async {
+            let event = rx.recv().await?;
+            Some((Ok(event), (rx, listener)))
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/GrpcApi",
+    description = "gRPC variant of the runtime control API: graph CRUD, parameter updates and an event stream as the versioned streamlib.control.v1.RuntimeControl protobuf service",
+    execution = manual,
+    config = crate::_generated_::GrpcApiConfig,
+)]
+pub struct GrpcApiProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    auth: Option<ApiServerAuth>,
+    /// Processor-owned tokio runtime the tonic server runs on; built in
+    /// `start()`, dropped in `stop()`.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+    shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl ManualProcessor for GrpcApiProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let tokens = configured_tokens(
+            "GrpcApi",
+            self.config.require_auth == Some(true),
+            self.config.auth_write_tokens.as_deref().unwrap_or_default(),
+            self.config.auth_read_tokens.as_deref().unwrap_or_default(),
+        )?;
+        self.auth = (!tokens.is_empty()).then(|| {
+            ApiServerAuth::new(tokens, None, self.config.auth_protect_reads == Some(true))
+        });
+        self.runtime = Some(ctx.runtime());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| Error::Runtime("GrpcApi: started before setup".into()))?;
+        let bind_address = self
+            .config
+            .bind_address
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BIND_ADDRESS.to_string());
+        let address: SocketAddr = bind_address.parse().map_err(|e| {
+            Error::Configuration(format!("GrpcApi: bind_address '{bind_address}': {e}"))
+        })?;
+        if self.auth.is_none() && !address.ip().is_loopback() {
+            tracing::warn!(
+                "GrpcApi bound to {} with auth off: anyone who can reach it can mutate the graph",
+                address
+            );
+        }
+
+        let executor = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .map_err(|e| Error::Runtime(format!("GrpcApi: failed to build tokio runtime: {e}")))?;
+        // Bind here so a taken port fails `start` instead of the server task.
+        let incoming = executor.block_on(async {
+            let listener = tokio::net::TcpListener::bind(address)
+                .await
+                .map_err(|e| Error::Config(format!("GrpcApi: bind {address}: {e}")))?;
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None)
+                .map_err(|e| Error::Runtime(format!("GrpcApi: listen on {address}: {e}")))
+        })?;
+
+        let service = RuntimeControlService {
+            runtime,
+            auth: self.auth.clone(),
+        };
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        executor.spawn(async move {
+            let served = tonic::transport::Server::builder()
+                .add_service(RuntimeControlServer::new(service))
+                .serve_with_incoming_shutdown(incoming, async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+            if let Err(error) = served {
+                tracing::error!(%error, "GrpcApi server exited");
+            }
+        });
+        self.shutdown_tx = Some(shutdown_tx);
+        self.tokio_runtime = Some(executor);
+
+        tracing::info!(address = %address, "GrpcApi serving streamlib.control.v1.RuntimeControl");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        // Dropping the runtime waits for the server task to wind down.
+        self.tokio_runtime.take();
+        tracing::info!("GrpcApi stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn runtime_errors_map_onto_grpc_codes() {
+        let code = |error| error_status(error).code();
+        assert_eq!(
+            code(Error::ProcessorNotFound("cam".into())),
+            tonic::Code::NotFound
+        );
+        assert_eq!(
+            code(Error::LinkNotFound("l1".into())),
+            tonic::Code::NotFound
+        );
+        assert_eq!(code(Error::Runtime("boom".into())), tonic::Code::Internal);
+        assert_eq!(
+            code(Error::InvalidLink("no".into())),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            denial_status(AuthDenial::MissingBearerToken).code(),
+            tonic::Code::Unauthenticated
+        );
+        assert_eq!(
+            denial_status(AuthDenial::InsufficientScope).code(),
+            tonic::Code::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn set_parameter_requests_become_parameter_changes() {
+        let change = parameter_change(proto::SetParameterRequest {
+            processor_id: "mixer".into(),
+            parameter: "/inputs/0/gain_db".into(),
+            value_json: "-6.5".into(),
+            at_ns: Some(1_000),
+            interpolation: proto::Interpolation::Exponential as i32,
+        })
+        .unwrap();
+        assert_eq!(change.processor_id.as_str(), "mixer");
+        assert_eq!(change.value, json!(-6.5));
+        assert_eq!(change.at_ns, Some(1_000));
+        assert_eq!(change.interpolation, ParameterInterpolation::Exponential);
+
+        let bad = parameter_change(proto::SetParameterRequest {
+            processor_id: "mixer".into(),
+            parameter: "gain".into(),
+            value_json: "{not json".into(),
+            ..Default::default()
+        });
+        assert_eq!(bad.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn processor_types_are_validated_segment_by_segment() {
+        let processor_type = |org: &str| proto::ProcessorType {
+            org: org.into(),
+            package: "core".into(),
+            type_name: "Scale".into(),
+            version: Some(proto::SemanticVersion {
+                major: 1,
+                minor: 2,
+                patch: 0,
+            }),
+        };
+        let ident = schema_ident(Some(processor_type("tatolab"))).unwrap();
+        assert_eq!(ident.version, SemVer::new(1, 2, 0));
+        assert_eq!(
+            schema_ident(Some(processor_type("not an org!")))
+                .unwrap_err()
+                .code(),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            schema_ident(None).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+}
@@ -17,27 +17,39 @@ use serde::Deserialize;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use std::sync::Arc;
-use streamlib::sdk::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+use streamlib::sdk::chaos::{ChaosFault, ChaosFaultRecord};
+use streamlib::sdk::descriptors::SchemaIdent;
 use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::frame_snapshot::{FrameSnapshot, PreviewFormat, PreviewOptions};
 use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef};
+use streamlib::sdk::graph_patch::{
+    GraphPatch, GraphPatchConfig, GraphPatchLink, GraphPatchProcessor,
+};
 use streamlib::sdk::json_schema::{
-    ProcessorDescriptorOutput, RegistryResponse, SchemaDescriptorOutput, SchemaIdentOutput,
-    SemanticVersionOutput,
+    CreateConnectionRequest, CreateProcessorRequest, ErrorResponse, GraphResponse, IdResponse,
+    ProbeResponse, ProcessorDescriptorOutput, RegistryResponse, SchemaDescriptorOutput,
+    SchemaIdentOutput, SemanticVersionOutput, UpdateProcessorConfigRequest,
 };
+use streamlib::sdk::midi_mapping::MidiControlBinding;
+use streamlib::sdk::parameter_automation::ParameterChange;
+use streamlib::sdk::preset_morph::PresetMorph;
 use streamlib::sdk::processors::PROCESSOR_REGISTRY;
 use streamlib::sdk::processors::ProcessorSpec;
-use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+use streamlib::sdk::pubsub::{Event, EventListener, JournalFollow, PUBSUB, topics};
 use streamlib::sdk::runtime::{RuntimeOperations, SubmittedProcessorSource};
 use tower_http::trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer};
 use tracing::Level;
 use utoipa::OpenApi;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::auth::{ApiServerBearerToken, ForbiddenResponse, UnauthorizedResponse};
+use crate::auth::{ApiServerAuth, AuthScope, ForbiddenResponse, UnauthorizedResponse};
+use crate::camera_controls::{CAMERA_CONTROLS_TOPIC, CameraControlsCache};
+use crate::probes::{self, Probes};
 use crate::state::{
-    ApiDoc, AppState, CreateConnectionRequest, CreateProcessorRequest, ErrorResponse, IdResponse,
+    ApiDoc, AppState, EventReplayResponse, PatchGraphRequest, PatchGraphResponse,
     ProcessorNotFoundResponse, ProcessorPortNotFoundResponse, RegisterProcessorSourceResponse,
-    ReplaceProcessorSourceRequest, SubmittedProcessorSourceRequest, UnknownProcessorTypeResponse,
+    ReplaceProcessorSourceRequest, SnapshotPortRequest, SubmittedProcessorSourceRequest,
+    UnknownProcessorTypeResponse,
 };
 
 /// The relative WebSocket URL carrying this runtime's live event stream — the
@@ -52,58 +64,42 @@ const RUNTIME_EVENTS_URL: &str = "/ws/events";
 /// Build the full router with shared state and trace layer attached.
 ///
 /// The mutating routes (`POST /api/processor`, `POST /api/processor/source`,
-/// `POST /api/processor/source/replace`, `DELETE /api/processors/{id}`, `POST
-/// /api/connections`, `DELETE /api/connections/{id}`) sit behind the
-/// bearer-token auth middleware only when `auth_token` is `Some` (auth opted
-/// in); with `None` — the zero-ceremony default — they are open like every
-/// other route. The two source-submit routes are RCE-capable (they execute
-/// submitted source), so they join this gated group. The GET routes, health
-/// check, WebSocket event stream, and OpenAPI spec are always open.
+/// `POST /api/processor/source/replace`, `DELETE /api/processors/{id}`, `PUT
+/// /api/processors/{id}/config`, `POST /api/processors/{id}/pause`, `POST
+/// /api/processors/{id}/resume`, `POST /api/presets/morph`, `PUT
+/// /api/midi/mappings`, `POST /api/parameters`, `POST /api/graph/undo`, `POST
+/// /api/graph/redo`, `POST /api/chaos/faults`, `POST /api/connections`,
+/// `DELETE /api/connections/{id}`, `PATCH /api/graph`) and the MCP endpoint
+/// require the write scope only when `auth` is `Some` (auth opted in); with
+/// `None` — the zero-ceremony default — they are open like every other
+/// route. The two source-submit routes are RCE-capable (they execute
+/// submitted source), so they join this gated group. The tap and preview
+/// WebSockets and the WHEP routes (feature `whep`) require the read scope
+/// whenever auth is on; the GET routes and the WebSocket event stream
+/// require it only when [`ApiServerAuth::protects_reads`]. The health check,
+/// readiness and liveness probes, the OpenAPI spec and the graph JSON Schema
+/// are always open.
 /// `route_layer` binds the auth layer to exactly the routes already on the
-/// protected sub-router, so a later `merge` leaves the open routes ungated.
+/// gated sub-router, so a later `merge` leaves the open routes ungated.
 pub(crate) fn build_router(
     runtime: Arc<dyn RuntimeOperations>,
-    auth_token: Option<ApiServerBearerToken>,
+    auth: Option<ApiServerAuth>,
+    probes: Probes,
     #[cfg(feature = "moq")] runtime_id: String,
 ) -> Router {
-    // The read-only tap WebSocket is gated exactly like the mutating routes WHEN
-    // auth is opted in — same bearer middleware, same route_layer binding; the
-    // default (auth off) leaves it open like every other route. This is
-    // mechanism parity, not a trust boundary the tap itself imposes. Clone the
-    // token before it is moved into the mutating-route middleware below.
-    let tap_auth_token = auth_token.clone();
-    // The MCP endpoint exposes the same mutating ops as tools, so it is gated
-    // exactly like the mutating routes when auth is opted in.
-    let mcp_auth_token = auth_token.clone();
+    let (router, openapi) = documented_routes(auth.clone()).split_for_parts();
 
-    let mut protected = OpenApiRouter::new()
-        .routes(routes!(create_processor))
-        .routes(routes!(create_processor_source))
-        .routes(routes!(replace_processor_source))
-        .routes(routes!(delete_processor))
-        .routes(routes!(create_connection))
-        .routes(routes!(delete_connection));
-    if let Some(auth_token) = auth_token {
-        protected = protected.route_layer(axum::middleware::from_fn_with_state(
-            auth_token,
-            crate::auth::require_bearer_token,
-        ));
-    }
-
-    let (router, openapi) = OpenApiRouter::with_openapi(ApiDoc::openapi())
-        .routes(routes!(health))
-        .routes(routes!(get_graph))
-        .routes(routes!(get_registry))
-        .routes(routes!(list_schema_definitions))
-        .routes(routes!(get_schema_definition))
-        .merge(protected)
-        .split_for_parts();
+    let camera_controls = Arc::new(Mutex::new(CameraControlsCache::default()));
+    PUBSUB.subscribe(CAMERA_CONTROLS_TOPIC, camera_controls.clone());
+    PUBSUB.subscribe(topics::RUNTIME_GLOBAL, camera_controls.clone());
 
     let state = AppState {
         runtime,
         #[cfg(feature = "moq")]
         runtime_id,
         openapi,
+        camera_controls,
+        probes,
     };
 
     // TraceLayer logs all HTTP requests with method, path, status, and latency.
@@ -112,25 +108,58 @@ pub(crate) fn build_router(
         .on_request(DefaultOnRequest::new().level(Level::INFO))
         .on_response(DefaultOnResponse::new().level(Level::INFO));
 
-    let mut tap_router = Router::new().route("/ws/tap/{channel}", get(tap_websocket_handler));
-    if let Some(tap_auth_token) = tap_auth_token {
+    // The read-only tap and preview WebSockets are gated whenever auth is
+    // opted in, even with the other reads open: they stream the frames
+    // themselves.
+    let mut tap_router = Router::new()
+        .route("/ws/tap/{channel}", get(tap_websocket_handler))
+        .route(
+            "/ws/preview/{processor_id}/{port}",
+            get(preview_websocket_handler),
+        );
+    // WHEP sessions stream full-rate video of any output, so they are gated
+    // like the tap.
+    #[cfg(feature = "whep")]
+    {
+        tap_router = tap_router
+            .route("/whep/{processor_id}/{port}", post(whep_offer))
+            .route(
+                "/whep/sessions/{session_id}",
+                axum::routing::delete(whep_session_delete),
+            );
+    }
+    if let Some(auth) = &auth {
         tap_router = tap_router.route_layer(axum::middleware::from_fn_with_state(
-            tap_auth_token,
-            crate::auth::require_bearer_token,
+            auth.gate(AuthScope::Read),
+            crate::auth::require_scope,
         ));
     }
 
-    let mut mcp_router = Router::new().route("/mcp", post(crate::mcp::mcp_endpoint));
-    if let Some(mcp_auth_token) = mcp_auth_token {
+    // The MCP endpoint exposes the same mutating ops as tools, so it is gated
+    // exactly like the mutating routes when auth is opted in.
+    let mut mcp_router = Router::new().route(
+        "/mcp",
+        post(crate::mcp::mcp_endpoint).get(crate::mcp::mcp_notifications),
+    );
+    if let Some(auth) = &auth {
         mcp_router = mcp_router.route_layer(axum::middleware::from_fn_with_state(
-            mcp_auth_token,
-            crate::auth::require_bearer_token,
+            auth.gate(AuthScope::Write),
+            crate::auth::require_scope,
+        ));
+    }
+
+    let mut events_router = Router::new().route("/ws/events", get(websocket_handler));
+    if let Some(auth) = auth.as_ref().filter(|auth| auth.protects_reads()) {
+        events_router = events_router.route_layer(axum::middleware::from_fn_with_state(
+            auth.gate(AuthScope::Read),
+            crate::auth::require_scope,
         ));
     }
 
     let router = router
-        .route("/ws/events", get(websocket_handler))
         .route("/api/openapi.json", get(get_openapi_spec))
+        .route("/api/graph/schema", get(get_graph_json_schema))
+        .merge(events_router)
         .merge(tap_router)
         .merge(mcp_router);
 
@@ -140,6 +169,77 @@ pub(crate) fn build_router(
     router.layer(trace_layer).with_state(state)
 }
 
+/// The route document behind `/api/openapi.json`, built from the same route
+/// table as [`build_router`] so it cannot drift from the routes. The served
+/// document also carries the registered processors' config schemas (see
+/// [`crate::schema_export`]).
+pub fn openapi_spec() -> utoipa::openapi::OpenApi {
+    documented_routes(None).split_for_parts().1
+}
+
+/// The JSON Schema for graph payloads `/api/graph/schema` serves, over the
+/// processors registered in this process.
+pub fn graph_json_schema() -> serde_json::Value {
+    crate::schema_export::graph_json_schema(
+        &openapi_spec(),
+        &crate::schema_export::registered_config_schemas(),
+    )
+}
+
+/// Every route documented through `utoipa`: the mutating ones behind the
+/// write-scope middleware when `auth` is `Some`, the reads behind the
+/// read-scope middleware when it also protects reads.
+fn documented_routes(auth: Option<ApiServerAuth>) -> OpenApiRouter<AppState> {
+    let mut protected = OpenApiRouter::new()
+        .routes(routes!(create_processor))
+        .routes(routes!(create_processor_source))
+        .routes(routes!(replace_processor_source))
+        .routes(routes!(delete_processor))
+        .routes(routes!(update_processor_config))
+        .routes(routes!(pause_processor))
+        .routes(routes!(resume_processor))
+        .routes(routes!(snapshot_port))
+        .routes(routes!(morph_presets))
+        .routes(routes!(set_midi_mappings))
+        .routes(routes!(set_parameter))
+        .routes(routes!(create_connection))
+        .routes(routes!(delete_connection))
+        .routes(routes!(patch_graph))
+        .routes(routes!(undo_graph_edit))
+        .routes(routes!(redo_graph_edit))
+        .routes(routes!(inject_chaos_fault));
+    if let Some(auth) = &auth {
+        protected = protected.route_layer(axum::middleware::from_fn_with_state(
+            auth.gate(AuthScope::Write),
+            crate::auth::require_scope,
+        ));
+    }
+
+    let mut reads = OpenApiRouter::new()
+        .routes(routes!(get_graph))
+        .routes(routes!(get_graph_snapshot))
+        .routes(routes!(get_camera_controls))
+        .routes(routes!(get_registry))
+        .routes(routes!(list_schema_definitions))
+        .routes(routes!(get_schema_definition))
+        .routes(routes!(get_chaos_faults))
+        .routes(routes!(get_midi_mappings))
+        .routes(routes!(get_event_replay));
+    if let Some(auth) = auth.as_ref().filter(|auth| auth.protects_reads()) {
+        reads = reads.route_layer(axum::middleware::from_fn_with_state(
+            auth.gate(AuthScope::Read),
+            crate::auth::require_scope,
+        ));
+    }
+
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(health))
+        .routes(routes!(ready))
+        .routes(routes!(live))
+        .merge(reads)
+        .merge(protected)
+}
+
 // ============================================================================
 // API Handlers
 // ============================================================================
@@ -156,12 +256,58 @@ pub(crate) async fn health() -> &'static str {
     "ok"
 }
 
+/// A probe result as `200` when it passed, `503` when it did not.
+fn probe_response(report: ProbeResponse) -> axum::response::Response {
+    let status = if report.ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "graph",
+    responses(
+        (status = 200, description = "Every processor is running and every critical link has delivered frames", body = ProbeResponse),
+        (status = 503, description = "Not ready; the failing checks say why", body = ProbeResponse)
+    )
+)]
+pub(crate) async fn ready(State(state): State<AppState>) -> axum::response::Response {
+    let report = match state.runtime.to_json_async().await.and_then(|value| {
+        serde_json::from_value::<GraphResponse>(value)
+            .map_err(|e| Error::Runtime(format!("parse graph: {e}")))
+    }) {
+        Ok(graph) => probes::readiness(&graph, &state.probes.criteria),
+        Err(error) => probes::unreadable_graph(&error),
+    };
+    probe_response(report)
+}
+
+#[utoipa::path(
+    get,
+    path = "/live",
+    tag = "graph",
+    responses(
+        (status = 200, description = "The runtime answered the watchdog recently", body = ProbeResponse),
+        (status = 503, description = "The runtime has stopped answering; restart it", body = ProbeResponse)
+    )
+)]
+pub(crate) async fn live(State(state): State<AppState>) -> axum::response::Response {
+    probe_response(probes::liveness(
+        &state.probes.heartbeat,
+        &state.probes.criteria,
+    ))
+}
+
 #[utoipa::path(
     get,
     path = "/api/graph",
     tag = "graph",
     responses(
-        (status = 200, description = "Current graph state as JSON"),
+        (status = 200, description = "Current graph state. Node `components` carry `state` and `metrics` (ProcessorMetricsOutput); link `components` carry `frame_drops` (LinkFrameDropsOutput); the graph's own `components` carry `gpu_pool` (GpuPoolStatsOutput) once the runtime has started", body = GraphResponse),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -176,6 +322,57 @@ pub(crate) async fn get_graph(
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/snapshot",
+    tag = "graph",
+    responses(
+        (status = 200, description = "Current graph as a GraphSnapshot document — the shape `streamlib-runtime --snapshot` loads"),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn get_graph_snapshot(State(state): State<AppState>) -> axum::response::Response {
+    match state.runtime.save_graph_snapshot_async().await {
+        Ok(snapshot) => (StatusCode::OK, Json(snapshot)).into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/processors/{id}/camera-controls",
+    tag = "processors",
+    params(
+        ("id" = String, Path, description = "Camera processor ID")
+    ),
+    responses(
+        (status = 200, description = "Latest control descriptors the camera published: its device path plus, for each supported exposure / gain / white balance / focus control, the range, default and current value. Set them through the `controls` field of `PUT /api/processors/{id}/config`."),
+        (status = 404, description = "No camera controls published for this processor", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn get_camera_controls(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> axum::response::Response {
+    match state.camera_controls.lock().get(&id) {
+        Some(controls) => (StatusCode::OK, Json(controls)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ProcessorNotFoundResponse {
+                error: "ProcessorNotFound",
+                processor_id: id,
+            }),
+        )
+            .into_response(),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/processor",
@@ -185,7 +382,7 @@ pub(crate) async fn get_graph(
         (status = 200, description = "Processor created successfully", body = IdResponse),
         (status = 400, description = "Malformed request (invalid org / package / type / version segment)", body = ErrorResponse),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 422, description = "Processor type is structurally valid but not registered in the runtime; the failed node is left in the graph in `Error` state", body = UnknownProcessorTypeResponse)
     )
 )]
@@ -193,35 +390,9 @@ pub(crate) async fn create_processor(
     State(state): State<AppState>,
     Json(body): Json<CreateProcessorRequest>,
 ) -> axum::response::Response {
-    // Convert SchemaIdentOutput → SchemaIdent through the typed segment
-    // validators (Org::new / Package::new / TypeName::new / SemVer::new).
-    // This is typed conversion, not parsing — there is no `SchemaIdent::parse`.
-    let SchemaIdentOutput {
-        org,
-        package,
-        type_name,
-        version,
-    } = body.processor_type.clone();
-    let ident = match (
-        Org::new(org),
-        Package::new(package),
-        TypeName::new(type_name),
-    ) {
-        (Ok(org), Ok(package), Ok(type_name)) => SchemaIdent::new(
-            org,
-            package,
-            type_name,
-            SemVer::new(version.major, version.minor, version.patch),
-        ),
-        _ => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse {
-                    error: "Malformed processor identifier — one of org / package / type failed validation".into(),
-                }),
-            )
-                .into_response();
-        }
+    let ident = match processor_ident(body.processor_type.clone()) {
+        Ok(ident) => ident,
+        Err(response) => return response,
     };
     let spec = ProcessorSpec::new(ident, body.config);
 
@@ -245,6 +416,24 @@ pub(crate) async fn create_processor(
     }
 }
 
+/// Convert SchemaIdentOutput → SchemaIdent via [`crate::ops::processor_ident`].
+/// A segment that fails validation yields the `400` response to return.
+fn processor_ident(
+    processor_type: SchemaIdentOutput,
+) -> std::result::Result<SchemaIdent, axum::response::Response> {
+    crate::ops::processor_ident(processor_type).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error:
+                    "Malformed processor identifier — one of org / package / type failed validation"
+                        .into(),
+            }),
+        )
+            .into_response()
+    })
+}
+
 /// Map a register/replace-from-source [`Error`] onto an HTTP response. The
 /// source-submit refusals (unsupported language, missing name, un-mintable
 /// name, build failure, replace-target mismatch) surface as
@@ -318,7 +507,7 @@ fn connect_error_response(error: Error) -> axum::response::Response {
         (status = 200, description = "Source registered, first discovered processor instantiated, and optional connections wired; body carries the minted registration ident, discovered ports, instance id, and connection ids", body = RegisterProcessorSourceResponse),
         (status = 400, description = "A `connect` wiring failed for a generic graph reason (neither a missing peer processor nor a missing peer port). On any wiring failure the whole submit is rolled back — the instantiated processor and any links created earlier in the call are removed", body = ErrorResponse),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 404, description = "A `connect` wiring references a peer processor not in the graph (same shape as POST /api/connections)", body = ProcessorNotFoundResponse),
         (status = 422, description = "The submitted source could not be registered or instantiated (unsupported language, missing name, build failure, unknown processor type); OR a `connect` wiring references a port that doesn't exist on an existing peer processor — the latter carries a ProcessorPortNotFoundResponse body, same shape as POST /api/connections", body = ErrorResponse),
         (status = 500, description = "Runtime failure while registering the source", body = ErrorResponse)
@@ -390,7 +579,7 @@ fn submit_source_error_response(error: crate::ops::SubmitSourceError) -> axum::r
         (status = 200, description = "Prior `@session/<name>` registration replaced; body carries the new registration ident and discovered ports (type-level replacement — running graph instances are not swapped)", body = RegisterProcessorSourceResponse),
         (status = 400, description = "`target_session_module` is not a valid `@org/name@<range>` module ident", body = ErrorResponse),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 422, description = "The replacement source could not be registered, or its name does not resolve to the target's `@session/<name>`", body = ErrorResponse),
         (status = 500, description = "Runtime failure while replacing (including a replacement that failed and could not restore the prior registration)", body = ErrorResponse)
     )
@@ -433,7 +622,7 @@ pub(crate) async fn replace_processor_source(
     responses(
         (status = 204, description = "Processor deleted successfully"),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 404, description = "Processor not found")
     )
 )]
@@ -450,6 +639,158 @@ pub(crate) async fn delete_processor(
         .map_err(|_| axum::http::StatusCode::NOT_FOUND)
 }
 
+/// Map a per-processor control error (config update, pause, resume) to a
+/// response: an unknown id is `404` with the typed body, anything else `400`.
+fn processor_control_error_response(error: Error) -> axum::response::Response {
+    match error {
+        Error::ProcessorNotFound(processor_id) => (
+            StatusCode::NOT_FOUND,
+            Json(ProcessorNotFoundResponse {
+                error: "ProcessorNotFound",
+                processor_id,
+            }),
+        )
+            .into_response(),
+        other => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: other.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/processors/{id}/config",
+    tag = "processors",
+    params(
+        ("id" = String, Path, description = "Processor ID whose config is replaced")
+    ),
+    request_body = UpdateProcessorConfigRequest,
+    responses(
+        (status = 204, description = "Config replaced; the running processor picks it up on the next commit"),
+        (status = 400, description = "Config rejected", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "Processor not found", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn update_processor_config(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<UpdateProcessorConfigRequest>,
+) -> axum::response::Response {
+    match state
+        .runtime
+        .update_processor_config_async(id.into(), body.config)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/processors/{id}/pause",
+    tag = "processors",
+    params(
+        ("id" = String, Path, description = "Processor ID to pause")
+    ),
+    responses(
+        (status = 204, description = "Processor paused"),
+        (status = 400, description = "Processor can't be paused", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "Processor not found", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn pause_processor(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> axum::response::Response {
+    match state
+        .runtime
+        .set_processor_paused_async(id.into(), true)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/processors/{id}/resume",
+    tag = "processors",
+    params(
+        ("id" = String, Path, description = "Processor ID to resume")
+    ),
+    responses(
+        (status = 204, description = "Processor resumed"),
+        (status = 400, description = "Processor can't be resumed", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "Processor not found", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn resume_processor(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> axum::response::Response {
+    match state
+        .runtime
+        .set_processor_paused_async(id.into(), false)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/processors/{id}/outputs/{port}/snapshot",
+    tag = "processors",
+    params(
+        ("id" = String, Path, description = "Processor ID whose output is captured"),
+        ("port" = String, Path, description = "Video output port name")
+    ),
+    request_body = SnapshotPortRequest,
+    responses(
+        (status = 200, description = "The next frame on the port was written to `path` as PNG", body = FrameSnapshot),
+        (status = 400, description = "Not a video port, unsupported path or surface, or no frame arrived", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "Processor not found", body = ProcessorNotFoundResponse),
+        (status = 409, description = "The port isn't wired, or its channel is already tapped", body = ErrorResponse),
+        (status = 422, description = "The processor has no such output port", body = ProcessorPortNotFoundResponse)
+    )
+)]
+pub(crate) async fn snapshot_port(
+    State(state): State<AppState>,
+    Path((id, port)): Path<(String, String)>,
+    Json(body): Json<SnapshotPortRequest>,
+) -> axum::response::Response {
+    match state
+        .runtime
+        .snapshot_async(id.into(), port, body.path.into())
+        .await
+    {
+        Ok(snapshot) => (StatusCode::OK, Json(snapshot)).into_response(),
+        Err(error @ (Error::TapChannelNotFound(_) | Error::TapSlotOccupied(_))) => (
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+            .into_response(),
+        Err(error) => connect_error_response(error),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/connections",
@@ -459,7 +800,7 @@ pub(crate) async fn delete_processor(
         (status = 200, description = "Connection created successfully", body = IdResponse),
         (status = 400, description = "Malformed request or generic graph error", body = ErrorResponse),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 404, description = "One of the referenced processors isn't in the graph", body = ProcessorNotFoundResponse),
         (status = 422, description = "Referenced processor exists but has no port with that name and direction", body = ProcessorPortNotFoundResponse)
     )
@@ -487,22 +828,310 @@ pub(crate) async fn create_connection(
     responses(
         (status = 204, description = "Connection deleted successfully"),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
         (status = 404, description = "Connection not found")
     )
 )]
-pub(crate) async fn delete_connection(
-    State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> std::result::Result<axum::http::StatusCode, axum::http::StatusCode> {
-    let link_id = id.into();
-
-    state
-        .runtime
-        .disconnect_async(link_id)
-        .await
-        .map(|_| axum::http::StatusCode::NO_CONTENT)
-        .map_err(|_| axum::http::StatusCode::NOT_FOUND)
+pub(crate) async fn delete_connection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> std::result::Result<axum::http::StatusCode, axum::http::StatusCode> {
+    let link_id = id.into();
+
+    state
+        .runtime
+        .disconnect_async(link_id)
+        .await
+        .map(|_| axum::http::StatusCode::NO_CONTENT)
+        .map_err(|_| axum::http::StatusCode::NOT_FOUND)
+}
+
+/// Map an undo / redo outcome onto an HTTP response: the applied edit as
+/// JSON, or 409 when the history has nothing to apply.
+fn graph_edit_response(
+    result: Result<Option<streamlib::sdk::graph_edit_history::GraphEdit>>,
+    nothing_to_apply: &str,
+) -> axum::response::Response {
+    match result {
+        Ok(Some(edit)) => (StatusCode::OK, Json(edit)).into_response(),
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: nothing_to_apply.to_string(),
+            }),
+        )
+            .into_response(),
+        Err(error) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/graph",
+    tag = "graph",
+    request_body = PatchGraphRequest,
+    responses(
+        (status = 200, description = "Every step applied and a single `GraphDidChange` was published; body maps each alias to its processor id and lists the created link ids", body = PatchGraphResponse),
+        (status = 400, description = "Malformed patch (duplicate alias, malformed identifier) or a rejected step (unknown link, a link closing a cycle); nothing was applied", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "A step names a processor that isn't in the graph or the patch; nothing was applied", body = ProcessorNotFoundResponse),
+        (status = 422, description = "An added processor type isn't registered (`UnknownProcessorTypeResponse`) or a link names a port the processor doesn't have; nothing was applied", body = ProcessorPortNotFoundResponse)
+    )
+)]
+pub(crate) async fn patch_graph(
+    State(state): State<AppState>,
+    Json(body): Json<PatchGraphRequest>,
+) -> axum::response::Response {
+    let mut add_processors = Vec::with_capacity(body.add_processors.len());
+    for processor in body.add_processors {
+        let ident = match processor_ident(processor.processor_type) {
+            Ok(ident) => ident,
+            Err(response) => return response,
+        };
+        add_processors.push(GraphPatchProcessor {
+            alias: processor.alias,
+            spec: ProcessorSpec::new(ident, processor.config),
+        });
+    }
+    let patch = GraphPatch {
+        disconnect: body.disconnect.into_iter().map(Into::into).collect(),
+        remove_processors: body.remove_processors.into_iter().map(Into::into).collect(),
+        add_processors,
+        update_configs: body
+            .update_configs
+            .into_iter()
+            .map(|update| GraphPatchConfig {
+                processor_id: update.processor_id.into(),
+                config: update.config,
+            })
+            .collect(),
+        connect: body
+            .connect
+            .into_iter()
+            .map(|link| GraphPatchLink {
+                from: OutputLinkPortRef::new(link.from_processor, link.from_port),
+                to: InputLinkPortRef::new(link.to_processor, link.to_port),
+            })
+            .collect(),
+    };
+
+    match state.runtime.apply_graph_patch_async(patch).await {
+        Ok(receipt) => (
+            StatusCode::OK,
+            Json(PatchGraphResponse {
+                processors: receipt
+                    .processors
+                    .into_iter()
+                    .map(|(alias, id)| (alias, id.to_string()))
+                    .collect(),
+                connections: receipt.links.iter().map(ToString::to_string).collect(),
+            }),
+        )
+            .into_response(),
+        Err(Error::UnknownProcessorType { ident }) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(UnknownProcessorTypeResponse {
+                error: "UnknownProcessorType",
+                ident: SchemaIdentOutput::from(&ident),
+            }),
+        )
+            .into_response(),
+        Err(error) => connect_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/graph/undo",
+    tag = "graph",
+    responses(
+        (status = 200, description = "Undid the newest graph edit; body is the edit that was undone"),
+        (status = 400, description = "The edit's inverse could not be applied; it stays undoable", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 409, description = "Nothing to undo", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn undo_graph_edit(State(state): State<AppState>) -> axum::response::Response {
+    graph_edit_response(
+        state.runtime.undo_graph_edit_async().await,
+        "nothing to undo",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/graph/redo",
+    tag = "graph",
+    responses(
+        (status = 200, description = "Redid the most recently undone graph edit; body is the edit that was redone"),
+        (status = 400, description = "The edit could not be re-applied; it stays redoable", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 409, description = "Nothing to redo", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn redo_graph_edit(State(state): State<AppState>) -> axum::response::Response {
+    graph_edit_response(
+        state.runtime.redo_graph_edit_async().await,
+        "nothing to redo",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/presets/morph",
+    tag = "processors",
+    request_body = PresetMorph,
+    responses(
+        (status = 202, description = "Morph started; it runs for `duration_ms` after the response"),
+        (status = 400, description = "Empty morph or a processor named twice", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "A target processor isn't in the graph", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn morph_presets(
+    State(state): State<AppState>,
+    Json(morph): Json<PresetMorph>,
+) -> axum::response::Response {
+    match state.runtime.morph_processor_configs_async(morph).await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/midi/mappings",
+    tag = "processors",
+    request_body = Vec<MidiControlBinding>,
+    responses(
+        (status = 204, description = "Bindings replaced; control changes drive them from now on"),
+        (status = 400, description = "A binding has an out-of-range MIDI number or a bad parameter pointer", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse)
+    )
+)]
+pub(crate) async fn set_midi_mappings(
+    State(state): State<AppState>,
+    Json(bindings): Json<Vec<MidiControlBinding>>,
+) -> axum::response::Response {
+    match state.runtime.set_midi_mappings_async(bindings).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/parameters",
+    tag = "processors",
+    request_body = ParameterChange,
+    responses(
+        (status = 202, description = "Change scheduled; the processor applies it at `at_ns`"),
+        (status = 400, description = "No parameter named, or the processor isn't running", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "Processor not found", body = ProcessorNotFoundResponse)
+    )
+)]
+pub(crate) async fn set_parameter(
+    State(state): State<AppState>,
+    Json(change): Json<ParameterChange>,
+) -> axum::response::Response {
+    match state.runtime.set_parameter_async(change).await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(error) => processor_control_error_response(error),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/midi/mappings",
+    tag = "processors",
+    responses(
+        (status = 200, description = "Current MIDI control bindings", body = Vec<MidiControlBinding>),
+        (status = 500, description = "Failed to read the bindings", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn get_midi_mappings(State(state): State<AppState>) -> axum::response::Response {
+    match state.runtime.midi_mappings_async().await {
+        Ok(bindings) => (StatusCode::OK, Json(bindings)).into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/chaos/faults",
+    tag = "graph",
+    request_body = ChaosFault,
+    responses(
+        (status = 200, description = "Fault injected; body is its log entry", body = ChaosFaultRecord),
+        (status = 400, description = "Invalid fault, or it could not be applied", body = ErrorResponse),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse),
+        (status = 404, description = "The target processor or link isn't in the graph", body = ErrorResponse),
+        (status = 409, description = "Chaos mode is off, or the link has no in-process destination", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn inject_chaos_fault(
+    State(state): State<AppState>,
+    Json(fault): Json<ChaosFault>,
+) -> axum::response::Response {
+    match state.runtime.inject_chaos_fault_async(fault).await {
+        Ok(record) => (StatusCode::OK, Json(record)).into_response(),
+        Err(error) => {
+            let status = match &error {
+                Error::ProcessorNotFound(_) | Error::LinkNotFound(_) => StatusCode::NOT_FOUND,
+                Error::NotSupported(_) => StatusCode::CONFLICT,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (
+                status,
+                Json(ErrorResponse {
+                    error: error.to_string(),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/chaos/faults",
+    tag = "graph",
+    responses(
+        (status = 200, description = "Chaos faults injected so far, oldest first", body = Vec<ChaosFaultRecord>),
+        (status = 500, description = "Failed to read the fault log", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn get_chaos_faults(State(state): State<AppState>) -> axum::response::Response {
+    match state.runtime.chaos_fault_log_async().await {
+        Ok(log) => (StatusCode::OK, Json(log)).into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+            .into_response(),
+    }
 }
 
 #[utoipa::path(
@@ -574,10 +1203,24 @@ pub(crate) async fn get_schema_definition(
         .ok_or(axum::http::StatusCode::NOT_FOUND)
 }
 
-pub(crate) async fn get_openapi_spec(
+/// `GET /api/openapi.json` — the OpenAPI 3.1 route document, plus one
+/// component per registered processor config schema.
+pub(crate) async fn get_openapi_spec(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(crate::schema_export::openapi_document(
+        &state.openapi,
+        &crate::schema_export::registered_config_schemas(),
+    ))
+}
+
+/// `GET /api/graph/schema` — a JSON Schema 2020-12 document for graph
+/// payloads, checking each node's `config` against its processor type.
+pub(crate) async fn get_graph_json_schema(
     State(state): State<AppState>,
-) -> Json<utoipa::openapi::OpenApi> {
-    Json(state.openapi)
+) -> Json<serde_json::Value> {
+    Json(crate::schema_export::graph_json_schema(
+        &state.openapi,
+        &crate::schema_export::registered_config_schemas(),
+    ))
 }
 
 /// MoQ broadcast catalog with currently-published tracks.
@@ -598,12 +1241,224 @@ pub(crate) async fn get_moq_catalog(
     Json(catalog)
 }
 
+/// `POST /whep/{processor_id}/{port}` — open a WHEP playback session on a
+/// video output.
+///
+/// The body is the browser's `application/sdp` offer; the `201` answer
+/// carries every ICE candidate (no trickle) and a `Location` to `DELETE` to
+/// end the session. Raw `VideoFrame` outputs get an H.264 encoder of their
+/// own; `EncodedVideoFrame` outputs must already be H.264. Not in the
+/// OpenAPI document: the bodies are SDP, not JSON.
+#[cfg(feature = "whep")]
+pub(crate) async fn whep_offer(
+    State(state): State<AppState>,
+    Path((processor_id, port)): Path<(String, String)>,
+    headers: axum::http::HeaderMap,
+    offer: String,
+) -> axum::response::Response {
+    use axum::http::header::{CONTENT_TYPE, LOCATION};
+
+    let is_sdp = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/sdp"));
+    if !is_sdp {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(ErrorResponse {
+                error: "WHEP offers must be sent as application/sdp".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    match crate::whep::open_session(state.runtime.clone(), &processor_id, &port, offer).await {
+        Ok(answer) => (
+            StatusCode::CREATED,
+            [
+                (CONTENT_TYPE, "application/sdp".to_string()),
+                (LOCATION, format!("/whep/sessions/{}", answer.session_id)),
+            ],
+            answer.sdp,
+        )
+            .into_response(),
+        Err(Error::NotSupported(reason)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse { error: reason }),
+        )
+            .into_response(),
+        Err(Error::Runtime(reason)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: reason }),
+        )
+            .into_response(),
+        Err(other) => connect_error_response(other),
+    }
+}
+
+/// `DELETE /whep/sessions/{session_id}` — end a WHEP session and remove the
+/// processors it added.
+#[cfg(feature = "whep")]
+pub(crate) async fn whep_session_delete(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> axum::response::Response {
+    match crate::whep::close_session(state.runtime.clone(), &session_id).await {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(Error::NotFound(reason)) => {
+            (StatusCode::NOT_FOUND, Json(ErrorResponse { error: reason })).into_response()
+        }
+        Err(other) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: other.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
 // ============================================================================
 // WebSocket Event Streaming
 // ============================================================================
 
-pub(crate) async fn websocket_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_websocket)
+/// Query for the event replay routes.
+#[derive(Deserialize)]
+pub(crate) struct EventReplayQuery {
+    /// Replay journaled events published after this wall-clock time,
+    /// nanoseconds since the Unix epoch.
+    since_ns: Option<i64>,
+}
+
+/// Close code sent on a replaying `/ws/events` when no journal is enabled.
+const EVENT_JOURNAL_DISABLED_CLOSE_CODE: u16 = 4404;
+
+/// Why a replay was refused: the runtime keeps no event journal.
+pub(crate) const EVENT_JOURNAL_DISABLED: &str =
+    "the event journal is not enabled; start the runtime with --event-journal";
+
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    params(
+        ("since_ns" = Option<i64>, Query, description = "Only events published after this wall-clock time, nanoseconds since the Unix epoch; absent returns the whole journal")
+    ),
+    responses(
+        (status = 200, description = "Journaled events, oldest first. Pass the last `timestamp_ns` back as `since_ns` to catch up after a reconnect", body = EventReplayResponse),
+        (status = 404, description = "The runtime keeps no event journal", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn get_event_replay(
+    Query(query): Query<EventReplayQuery>,
+) -> axum::response::Response {
+    match PUBSUB.journal_since(query.since_ns.unwrap_or(i64::MIN)) {
+        Some(events) => (StatusCode::OK, Json(EventReplayResponse { events })).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: EVENT_JOURNAL_DISABLED.to_string(),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// `GET /ws/events` — stream every pubsub event as a JSON text frame.
+///
+/// With `since_ns`, the stream comes from the event journal instead: the
+/// events published after `since_ns`, then every new one, each as a
+/// `JournaledEvent` so the client can resume from its `timestamp_ns`.
+#[utoipa::path(
+    get,
+    path = "/ws/events",
+    tag = "events",
+    params(
+        ("since_ns" = Option<i64>, Query, description = "Replay the journal from this wall-clock time (nanoseconds since the Unix epoch) before streaming live events")
+    ),
+    responses(
+        (status = 101, description = "WebSocket upgraded. Without `since_ns`, every runtime, processor and custom-topic event is sent as one JSON text frame in the Event shape, starting from the moment of connection. With `since_ns`, each frame is a JournaledEvent: first the journaled events after `since_ns`, then live ones, none lost or repeated in between; the socket closes with 4404 when the runtime keeps no journal. Client messages other than Close are ignored.", body = Event)
+    )
+)]
+pub(crate) async fn websocket_handler(
+    ws: WebSocketUpgrade,
+    Query(query): Query<EventReplayQuery>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        match query.since_ns {
+            Some(since_ns) => handle_replay_websocket(socket, since_ns).await,
+            None => handle_websocket(socket).await,
+        }
+    })
+}
+
+async fn handle_replay_websocket(socket: WebSocket, since_ns: i64) {
+    let (mut sender, mut receiver) = socket.split();
+
+    let Some(JournalFollow { backlog, mut live }) = PUBSUB.follow_journal(since_ns) else {
+        let _ = sender
+            .send(Message::Close(Some(axum::extract::ws::CloseFrame {
+                code: EVENT_JOURNAL_DISABLED_CLOSE_CODE,
+                reason: EVENT_JOURNAL_DISABLED.into(),
+            })))
+            .await;
+        return;
+    };
+    tracing::info!(
+        since_ns,
+        replayed = backlog.len(),
+        "WebSocket client connected, replaying the event journal"
+    );
+
+    for entry in backlog {
+        if send_json(&mut sender, &entry).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        tokio::select! {
+            entry = live.recv() => match entry {
+                Some(entry) => {
+                    if send_json(&mut sender, &entry).await.is_err() {
+                        break;
+                    }
+                }
+                // The journal was replaced, or this client fell too far
+                // behind and was dropped; it resumes with a new `since_ns`.
+                None => {
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Err(e)) => {
+                    tracing::warn!("WebSocket error: {}", e);
+                    break;
+                }
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    tracing::info!("WebSocket client disconnected");
+}
+
+/// Send `value` as one JSON text frame. A value that fails to serialize is
+/// logged and skipped; only a dead socket is an error.
+async fn send_json<S>(sender: &mut S, value: &impl serde::Serialize) -> std::result::Result<(), ()>
+where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    match serde_json::to_string(value) {
+        Ok(json) => sender
+            .send(Message::Text(json.into()))
+            .await
+            .map_err(|_| ()),
+        Err(e) => {
+            tracing::warn!("Failed to serialize event: {}", e);
+            Ok(())
+        }
+    }
 }
 
 async fn handle_websocket(socket: WebSocket) {
@@ -698,7 +1553,7 @@ pub(crate) struct TapQuery {
     responses(
         (status = 101, description = "WebSocket upgraded. Read-only observability tap: each channel bag is forwarded verbatim (FrameHeader-framed) as a binary WS frame with no encode, containerize, or transcode — decoding is the client's concern. To observe a viewable video feed, tap an encoded (h264/h265/jpeg) or container (CMAF/fMP4) channel; a raw video channel carries zero-copy DMA-BUF/VkImage frame descriptors (meaningless off-host), not pixels, and this is not a realtime-video transport (use the WebRTC/MoQ/display processors)."),
         (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
-        (status = 403, description = "Invalid bearer token", body = ForbiddenResponse)
+        (status = 403, description = "Invalid bearer token, or one without the write scope", body = ForbiddenResponse)
     )
 )]
 pub(crate) async fn tap_websocket_handler(
@@ -812,6 +1667,161 @@ fn truncate_on_char_boundary(mut text: String, max_bytes: usize) -> String {
     text
 }
 
+// ============================================================================
+// Port Preview WebSocket (low-rate video thumbnails)
+// ============================================================================
+
+/// Query parameters for the preview WebSocket; unset ones take the
+/// [`PreviewOptions`] defaults (2 fps, 320 px wide, JPEG at quality 70).
+#[derive(Deserialize)]
+pub(crate) struct PreviewQuery {
+    /// Thumbnails per second, clamped to 2–5.
+    fps: Option<f32>,
+    /// Widest thumbnail in pixels; frames are never upscaled.
+    max_width: Option<u32>,
+    /// `jpeg` or `webp` (lossless).
+    format: Option<PreviewFormat>,
+    /// JPEG quality, 1–100.
+    quality: Option<u8>,
+}
+
+/// `GET /ws/preview/{processor_id}/{port}` — stream downscaled thumbnails of a
+/// video output as binary WebSocket frames.
+///
+/// Each frame is one complete JPEG or WebP image, ready to show through a
+/// `Blob` URL, so a web UI gets a confidence monitor without negotiating
+/// WebRTC. The preview reads back one frame per interval and skips the rest;
+/// it holds the port's tap slot, so it can't share a port with a tap or a
+/// snapshot.
+#[utoipa::path(
+    get,
+    path = "/ws/preview/{processor_id}/{port}",
+    tag = "events",
+    params(
+        ("processor_id" = String, Path, description = "Processor whose output is previewed"),
+        ("port" = String, Path, description = "Video output port name"),
+        ("fps" = Option<f32>, Query, description = "Thumbnails per second, clamped to 2–5 (default 2)"),
+        ("max_width" = Option<u32>, Query, description = "Widest thumbnail in pixels, aspect ratio kept, never upscaled (default 320)"),
+        ("format" = Option<PreviewFormat>, Query, description = "Thumbnail encoding (default `jpeg`)"),
+        ("quality" = Option<u8>, Query, description = "JPEG quality 1–100 (default 70); ignored for WebP")
+    ),
+    responses(
+        (status = 101, description = "WebSocket upgraded. Each binary frame is one encoded thumbnail. The socket closes with 4404 when the processor, port or link doesn't exist, 4409 when the port is already tapped, and 4415 when the port doesn't carry 8-bit video frames."),
+        (status = 401, description = "Missing or malformed bearer token", body = UnauthorizedResponse),
+        (status = 403, description = "Invalid bearer token, or one without the read scope", body = ForbiddenResponse)
+    )
+)]
+pub(crate) async fn preview_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Path((processor_id, port)): Path<(String, String)>,
+    Query(query): Query<PreviewQuery>,
+) -> impl IntoResponse {
+    let defaults = PreviewOptions::default();
+    let options = PreviewOptions {
+        fps: query.fps.unwrap_or(defaults.fps),
+        max_width: query.max_width.unwrap_or(defaults.max_width),
+        format: query.format.unwrap_or(defaults.format),
+        quality: query.quality.unwrap_or(defaults.quality),
+    };
+    ws.on_upgrade(move |socket| {
+        handle_preview_websocket(socket, state.runtime, processor_id, port, options)
+    })
+}
+
+async fn handle_preview_websocket(
+    socket: WebSocket,
+    runtime: Arc<dyn RuntimeOperations>,
+    processor_id: String,
+    port: String,
+    options: PreviewOptions,
+) {
+    let (mut sender, mut receiver) = socket.split();
+    let close = |error: &Error| {
+        let (code, reason) = preview_error_close_frame(error);
+        Message::Close(Some(axum::extract::ws::CloseFrame {
+            code,
+            reason: reason.into(),
+        }))
+    };
+
+    let mut preview = match runtime
+        .preview_async(processor_id.clone().into(), port.clone(), options)
+        .await
+    {
+        Ok(preview) => preview,
+        Err(e) => {
+            tracing::info!(processor_id = %processor_id, port = %port, "preview rejected: {e}");
+            let _ = sender.send(close(&e)).await;
+            return;
+        }
+    };
+
+    tracing::info!(processor_id = %processor_id, port = %port, "preview client attached");
+
+    loop {
+        tokio::select! {
+            next = preview.next_frame() => match next {
+                Some(Ok(thumbnail)) => {
+                    if sender.send(Message::Binary(thumbnail.bytes.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // Not a video port, or frames previews can't encode: every
+                // later frame would fail the same way.
+                Some(Err(e @ Error::NotSupported(_))) => {
+                    let _ = sender.send(close(&e)).await;
+                    break;
+                }
+                Some(Err(e)) => {
+                    tracing::debug!(
+                        processor_id = %processor_id,
+                        port = %port,
+                        "preview frame skipped: {e}"
+                    );
+                }
+                None => {
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            maybe_msg = receiver.next() => match maybe_msg {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+
+    // Detach off the async worker, as the tap handler does.
+    let tap = preview.into_tap();
+    if let Err(join_error) = tokio::task::spawn_blocking(move || drop(tap)).await {
+        tracing::warn!(
+            processor_id = %processor_id,
+            port = %port,
+            "preview detach task failed to join: {join_error}"
+        );
+    }
+
+    tracing::info!(processor_id = %processor_id, port = %port, "preview client detached");
+}
+
+/// [`tap_error_close_frame`] plus the failures only a preview has: a missing
+/// processor or port (4404, like a missing channel) and frames it can't
+/// thumbnail (4415).
+fn preview_error_close_frame(error: &Error) -> (u16, String) {
+    let (code, reason) = match error {
+        Error::ProcessorNotFound(_) | Error::ProcessorPortNotFound { .. } => {
+            (4404, format!("preview target not found: {error}"))
+        }
+        Error::NotSupported(_) => (4415, format!("preview not supported: {error}")),
+        other => return tap_error_close_frame(other),
+    };
+    (
+        code,
+        truncate_on_char_boundary(reason, MAX_WS_CLOSE_REASON_BYTES),
+    )
+}
+
 #[cfg(test)]
 mod router_auth_gate_tests {
     use super::*;
@@ -820,8 +1830,10 @@ mod router_auth_gate_tests {
         Request, StatusCode,
         header::{AUTHORIZATION, CONTENT_TYPE},
     };
-    use streamlib::sdk::descriptors::{ModuleIdent, SemVerRange};
+    use streamlib::sdk::descriptors::{ModuleIdent, Org, Package, SemVer, SemVerRange, TypeName};
     use streamlib::sdk::graph::{LinkUniqueId, ProcessorUniqueId};
+    use streamlib::sdk::graph_edit_history::GraphEdit;
+    use streamlib::sdk::graph_patch::GraphPatchReceipt;
     use streamlib::sdk::processors::PortSchemaSpec;
     use streamlib::sdk::runtime::{
         BoxFuture, RegisterProcessorReceipt, RegisteredPortReceipt, RegisteredProcessorReceipt,
@@ -884,6 +1896,119 @@ mod router_auth_gate_tests {
         ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::TapSubscription>> {
             Box::pin(async move { Err(Error::TapChannelNotFound(channel)) })
         }
+        fn preview_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            port: String,
+            _options: streamlib::sdk::frame_snapshot::PreviewOptions,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::PortPreview>> {
+            Box::pin(async move { Err(Error::TapChannelNotFound(port)) })
+        }
+        fn update_processor_config_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _config: serde_json::Value,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn set_processor_paused_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _paused: bool,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn save_graph_snapshot_async(
+            &self,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_snapshot::GraphSnapshot>> {
+            Box::pin(async move {
+                Ok(streamlib::sdk::graph_snapshot::GraphSnapshot {
+                    name: None,
+                    processors: Vec::new(),
+                    connections: Vec::new(),
+                    schedules: Vec::new(),
+                    auto_convert: false,
+                    edit_history: Default::default(),
+                })
+            })
+        }
+        fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async move {
+                Ok(Some(GraphEdit::UpdateConfig {
+                    processor_id: "some-id".into(),
+                    previous: serde_json::json!({ "gain": 1.0 }),
+                    config: serde_json::json!({ "gain": 0.5 }),
+                }))
+            })
+        }
+        fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async move { Ok(None) })
+        }
+        fn apply_graph_patch_async(
+            &self,
+            patch: GraphPatch,
+        ) -> BoxFuture<'_, Result<GraphPatchReceipt>> {
+            Box::pin(async move {
+                Ok(GraphPatchReceipt {
+                    processors: patch
+                        .add_processors
+                        .into_iter()
+                        .map(|processor| (processor.alias, ProcessorUniqueId::new()))
+                        .collect(),
+                    links: patch.connect.iter().map(|_| LinkUniqueId::new()).collect(),
+                })
+            })
+        }
+        fn morph_processor_configs_async(&self, _morph: PresetMorph) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn set_midi_mappings_async(
+            &self,
+            _bindings: Vec<MidiControlBinding>,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn set_parameter_async(&self, _change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn inject_chaos_fault_async(
+            &self,
+            fault: ChaosFault,
+        ) -> BoxFuture<'_, Result<ChaosFaultRecord>> {
+            Box::pin(async move {
+                Ok(ChaosFaultRecord {
+                    fault_id: 1,
+                    fault,
+                    source: streamlib::sdk::chaos::ChaosFaultSource::Requested,
+                    injected_at_ms: 0,
+                    cleared_at_ms: None,
+                    error: None,
+                    restored_processor_id: None,
+                })
+            })
+        }
+        fn chaos_fault_log_async(&self) -> BoxFuture<'_, Result<Vec<ChaosFaultRecord>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn snapshot_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _port: String,
+            path: std::path::PathBuf,
+        ) -> BoxFuture<'_, Result<FrameSnapshot>> {
+            Box::pin(async move {
+                Ok(FrameSnapshot {
+                    path,
+                    width: 64,
+                    height: 48,
+                    timestamp_ns: 0,
+                    bytes: 0,
+                })
+            })
+        }
         fn add_processor(&self, _spec: ProcessorSpec) -> Result<ProcessorUniqueId> {
             Ok(ProcessorUniqueId::new())
         }
@@ -970,6 +2095,99 @@ mod router_auth_gate_tests {
         ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::TapSubscription>> {
             Box::pin(async move { Err(Error::TapChannelNotFound(channel)) })
         }
+        fn preview_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            port: String,
+            _options: streamlib::sdk::frame_snapshot::PreviewOptions,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::PortPreview>> {
+            Box::pin(async move { Err(Error::TapChannelNotFound(port)) })
+        }
+        fn update_processor_config_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _config: serde_json::Value,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Err(Error::ProcessorNotFound(processor_id.to_string())) })
+        }
+        fn set_processor_paused_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _paused: bool,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Err(Error::ProcessorNotFound(processor_id.to_string())) })
+        }
+        fn save_graph_snapshot_async(
+            &self,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_snapshot::GraphSnapshot>> {
+            Box::pin(async move {
+                Err(Error::NotSupported(
+                    "stub runtime has no graph to snapshot".to_string(),
+                ))
+            })
+        }
+        fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async move { Ok(None) })
+        }
+        fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+            Box::pin(async move { Ok(None) })
+        }
+        fn apply_graph_patch_async(
+            &self,
+            _patch: GraphPatch,
+        ) -> BoxFuture<'_, Result<GraphPatchReceipt>> {
+            Box::pin(async move { Ok(GraphPatchReceipt::default()) })
+        }
+        fn morph_processor_configs_async(&self, _morph: PresetMorph) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn set_midi_mappings_async(
+            &self,
+            _bindings: Vec<MidiControlBinding>,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn set_parameter_async(&self, _change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn inject_chaos_fault_async(
+            &self,
+            fault: ChaosFault,
+        ) -> BoxFuture<'_, Result<ChaosFaultRecord>> {
+            Box::pin(async move {
+                Ok(ChaosFaultRecord {
+                    fault_id: 1,
+                    fault,
+                    source: streamlib::sdk::chaos::ChaosFaultSource::Requested,
+                    injected_at_ms: 0,
+                    cleared_at_ms: None,
+                    error: None,
+                    restored_processor_id: None,
+                })
+            })
+        }
+        fn chaos_fault_log_async(&self) -> BoxFuture<'_, Result<Vec<ChaosFaultRecord>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn snapshot_async(
+            &self,
+            _processor_id: ProcessorUniqueId,
+            _port: String,
+            path: std::path::PathBuf,
+        ) -> BoxFuture<'_, Result<FrameSnapshot>> {
+            Box::pin(async move {
+                Ok(FrameSnapshot {
+                    path,
+                    width: 64,
+                    height: 48,
+                    timestamp_ns: 0,
+                    bytes: 0,
+                })
+            })
+        }
         fn add_processor(&self, _spec: ProcessorSpec) -> Result<ProcessorUniqueId> {
             Ok(self.instance_id.clone())
         }
@@ -1028,7 +2246,8 @@ mod router_auth_gate_tests {
     fn auth_enabled_router() -> Router {
         build_router(
             Arc::new(AlwaysOkStubRuntime),
-            Some(ApiServerBearerToken::from_secret(TEST_TOKEN)),
+            Some(ApiServerAuth::from_secret(TEST_TOKEN)),
+            Probes::default(),
             #[cfg(feature = "moq")]
             "test-runtime-id".to_string(),
         )
@@ -1040,6 +2259,7 @@ mod router_auth_gate_tests {
         build_router(
             Arc::new(AlwaysOkStubRuntime),
             None,
+            Probes::default(),
             #[cfg(feature = "moq")]
             "test-runtime-id".to_string(),
         )
@@ -1251,6 +2471,7 @@ mod router_auth_gate_tests {
         let router = build_router(
             runtime,
             None,
+            Probes::default(),
             #[cfg(feature = "moq")]
             "test-runtime-id".to_string(),
         );
@@ -1373,6 +2594,61 @@ mod router_auth_gate_tests {
         );
     }
 
+    #[tokio::test]
+    async fn graph_json_schema_is_open_and_roots_at_the_graph_response() {
+        let request = Request::builder()
+            .method("GET")
+            .uri("/api/graph/schema")
+            .body(Body::empty())
+            .unwrap();
+        let schema = json_body_on(auth_enabled_router(), request).await;
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(schema["$ref"], "#/$defs/GraphResponse");
+        assert!(
+            schema["$defs"]["GraphResponse"].is_object(),
+            "the graph schema must carry GraphResponse in $defs"
+        );
+    }
+
+    #[tokio::test]
+    async fn event_stream_and_metrics_are_documented_in_the_openapi_spec() {
+        let request = Request::builder()
+            .method("GET")
+            .uri("/api/openapi.json")
+            .body(Body::empty())
+            .unwrap();
+        let spec = json_body_on(auth_enabled_router(), request).await;
+        assert!(
+            spec["paths"]["/ws/events"]["get"]["responses"]["101"].is_object(),
+            "GET /ws/events must appear in the OpenAPI spec"
+        );
+        assert!(
+            spec["paths"]["/api/graph"]["get"]["responses"]["200"]["content"].is_object(),
+            "GET /api/graph must document its GraphResponse body"
+        );
+        let schemas = &spec["components"]["schemas"];
+        for name in [
+            "Event",
+            "RuntimeEvent",
+            "ProcessorEvent",
+            "GraphResponse",
+            "ProcessorMetricsOutput",
+            "LinkFrameDropsOutput",
+            "GpuPoolStatsOutput",
+        ] {
+            assert!(
+                schemas[name].is_object(),
+                "`{name}` must be a documented schema"
+            );
+        }
+
+        // The generator binary emits exactly what the server serves.
+        assert_eq!(spec, serde_json::to_value(openapi_spec()).unwrap());
+    }
+
     #[tokio::test]
     async fn create_connection_with_token_is_200() {
         let request = Request::builder()
@@ -1385,6 +2661,78 @@ mod router_auth_gate_tests {
         assert_eq!(status_of(request).await, StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn patch_graph_requires_a_token_and_maps_aliases_to_ids() {
+        let patch = serde_json::json!({
+            "disconnect": ["l1"],
+            "add_processors": [{
+                "alias": "blur",
+                "processor_type": {
+                    "org": "tatolab",
+                    "package": "debug-utilities",
+                    "type": "SimplePassthroughProcessor",
+                    "version": { "major": 1, "minor": 0, "patch": 0 }
+                },
+                "config": {}
+            }],
+            "connect": [
+                { "from_processor": "camera", "from_port": "video",
+                  "to_processor": "blur", "to_port": "input" },
+                { "from_processor": "blur", "from_port": "output",
+                  "to_processor": "display", "to_port": "video" }
+            ]
+        })
+        .to_string();
+        let request = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("PATCH")
+                .uri("/api/graph")
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                builder = builder.header(AUTHORIZATION, bearer(token));
+            }
+            builder.body(Body::from(patch.clone())).unwrap()
+        };
+
+        assert_eq!(status_of(request(None)).await, StatusCode::UNAUTHORIZED);
+        let body = json_body_on(auth_enabled_router(), request(Some(TEST_TOKEN))).await;
+        assert!(body["processors"]["blur"].is_string(), "{body}");
+        assert_eq!(body["connections"].as_array().unwrap().len(), 2);
+
+        // PATCH shares its path with the open `GET /api/graph`.
+        let read = Request::builder()
+            .uri("/api/graph")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(read).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn patch_graph_rejects_a_malformed_identifier_with_400() {
+        let request = Request::builder()
+            .method("PATCH")
+            .uri("/api/graph")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "add_processors": [{
+                        "alias": "bad",
+                        "processor_type": {
+                            "org": "Not An Org",
+                            "package": "x",
+                            "type": "Y",
+                            "version": { "major": 1, "minor": 0, "patch": 0 }
+                        },
+                        "config": {}
+                    }]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn delete_processor_with_token_is_204() {
         let request = Request::builder()
@@ -1396,6 +2744,217 @@ mod router_auth_gate_tests {
         assert_eq!(status_of(request).await, StatusCode::NO_CONTENT);
     }
 
+    #[tokio::test]
+    async fn processor_control_routes_require_a_token() {
+        let requests = [
+            ("PUT", "/api/processors/some-id/config"),
+            ("POST", "/api/processors/some-id/pause"),
+            ("POST", "/api/processors/some-id/resume"),
+        ];
+        for (method, uri) in requests {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"config":{}}"#))
+                .unwrap();
+            assert_eq!(
+                status_of(request).await,
+                StatusCode::UNAUTHORIZED,
+                "{method} {uri} must be gated when auth is on"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn processor_control_routes_with_token_are_204() {
+        let requests = [
+            ("PUT", "/api/processors/some-id/config"),
+            ("POST", "/api/processors/some-id/pause"),
+            ("POST", "/api/processors/some-id/resume"),
+        ];
+        for (method, uri) in requests {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(AUTHORIZATION, bearer(TEST_TOKEN))
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"config":{"gain":0.5}}"#))
+                .unwrap();
+            assert_eq!(
+                status_of(request).await,
+                StatusCode::NO_CONTENT,
+                "{method} {uri}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn graph_edit_routes_require_a_token() {
+        for uri in ["/api/graph/undo", "/api/graph/redo"] {
+            let request = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            assert_eq!(
+                status_of(request).await,
+                StatusCode::UNAUTHORIZED,
+                "POST {uri} must be gated when auth is on"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn morph_presets_requires_a_token_and_is_202_with_one() {
+        let morph = r#"{"duration_ms":500,"targets":[{"processor_id":"blur","to":{"radius":4}}]}"#;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/presets/morph")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(morph))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/presets/morph")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(morph))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn midi_mappings_require_a_token_to_set_and_are_open_to_read() {
+        let bindings = r#"[{"controller":7,"processor_id":"mixer","parameter":"/gain_db","min":-60,"max":0}]"#;
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/api/midi/mappings")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(bindings))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/api/midi/mappings")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(bindings))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::NO_CONTENT);
+
+        let request = Request::builder()
+            .uri("/api/midi/mappings")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn parameter_changes_require_a_token() {
+        let change = r#"{"processor_id":"blur","parameter":"radius","value":12.0,"at_ns":5000000000,"interpolation":"linear"}"#;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/parameters")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(change))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/parameters")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(change))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn chaos_fault_injection_requires_a_token_and_the_log_is_open() {
+        let fault = r#"{"kind":"stall_link","link_id":"L1","duration_ms":500}"#;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/chaos/faults")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(fault))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/chaos/faults")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(fault))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::OK);
+
+        let request = Request::builder()
+            .uri("/api/chaos/faults")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn port_snapshot_requires_a_token_and_returns_the_written_file() {
+        let body = r#"{"path":"/tmp/preview.png"}"#;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/processors/camera/outputs/video_out/snapshot")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/processors/camera/outputs/video_out/snapshot")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = auth_enabled_router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let snapshot: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(snapshot["path"], "/tmp/preview.png");
+        assert_eq!(snapshot["width"], 64);
+    }
+
+    #[tokio::test]
+    async fn undo_returns_the_undone_edit_and_an_empty_redo_is_409() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/graph/undo")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .body(Body::empty())
+            .unwrap();
+        let response = auth_enabled_router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let edit: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(edit["op"], "update_config");
+        assert_eq!(edit["processor_id"], "some-id");
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/graph/redo")
+            .header(AUTHORIZATION, bearer(TEST_TOKEN))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn delete_connection_with_token_is_204() {
         let request = Request::builder()
@@ -1407,9 +2966,40 @@ mod router_auth_gate_tests {
         assert_eq!(status_of(request).await, StatusCode::NO_CONTENT);
     }
 
+    #[tokio::test]
+    async fn probes_are_open_and_fail_until_the_runtime_answers() {
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        // The stub's `{}` graph is unreadable, so readiness can't be shown,
+        // and no watchdog has beaten the heartbeat.
+        assert_eq!(
+            status_of(get("/ready")).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status_of(get("/live")).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let probes = Probes::default();
+        probes.heartbeat.beat();
+        let router = build_router(
+            Arc::new(AlwaysOkStubRuntime),
+            Some(ApiServerAuth::from_secret(TEST_TOKEN)),
+            probes,
+            #[cfg(feature = "moq")]
+            "test-runtime-id".to_string(),
+        );
+        assert_eq!(status_on(router, get("/live")).await, StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn open_routes_need_no_authorization_header() {
-        let open = ["/health", "/api/registry", "/api/openapi.json"];
+        let open = [
+            "/health",
+            "/api/registry",
+            "/api/snapshot",
+            "/api/openapi.json",
+        ];
         for uri in open {
             let request = Request::builder()
                 .method("GET")
@@ -1424,6 +3014,84 @@ mod router_auth_gate_tests {
         }
     }
 
+    #[tokio::test]
+    async fn protected_reads_need_a_read_token_and_it_cannot_mutate() {
+        use crate::auth::{ApiServerBearerToken, AuthScope};
+        const READ_TOKEN: &str = "test-read-secret";
+        let router = || {
+            build_router(
+                Arc::new(AlwaysOkStubRuntime),
+                Some(ApiServerAuth::new(
+                    vec![
+                        (
+                            ApiServerBearerToken::from_secret(TEST_TOKEN),
+                            AuthScope::Write,
+                        ),
+                        (
+                            ApiServerBearerToken::from_secret(READ_TOKEN),
+                            AuthScope::Read,
+                        ),
+                    ],
+                    None,
+                    true,
+                )),
+                Probes::default(),
+                #[cfg(feature = "moq")]
+                "test-runtime-id".to_string(),
+            )
+        };
+        let get = |uri: &str, token: Option<&str>| {
+            let mut builder = Request::builder().method("GET").uri(uri);
+            if let Some(token) = token {
+                builder = builder.header(AUTHORIZATION, bearer(token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        // Reads now need a credential; the probes and the spec stay open.
+        for uri in ["/api/registry", "/api/snapshot"] {
+            assert_eq!(
+                status_on(router(), get(uri, None)).await,
+                StatusCode::UNAUTHORIZED
+            );
+            assert_eq!(
+                status_on(router(), get(uri, Some(READ_TOKEN))).await,
+                StatusCode::OK
+            );
+            assert_eq!(
+                status_on(router(), get(uri, Some(TEST_TOKEN))).await,
+                StatusCode::OK
+            );
+        }
+        for uri in ["/health", "/api/openapi.json"] {
+            assert_eq!(status_on(router(), get(uri, None)).await, StatusCode::OK);
+        }
+        assert_eq!(
+            status_on(router(), get("/ws/events", None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let mutate = Request::builder()
+            .method("POST")
+            .uri("/api/connections")
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, bearer(READ_TOKEN))
+            .body(create_connection_body())
+            .unwrap();
+        let body = json_body_on(router(), mutate).await;
+        assert_eq!(body["error"], "InsufficientScope");
+    }
+
+    #[tokio::test]
+    async fn camera_controls_route_is_open_and_404s_before_any_publish() {
+        let request = Request::builder()
+            .method("GET")
+            .uri("/api/processors/no-such-camera/camera-controls")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(request).await, StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn auth_off_lets_create_routes_through_without_a_token() {
         // The zero-ceremony default: with auth off, the mutating POST routes
@@ -1504,4 +3172,58 @@ mod router_auth_gate_tests {
             "GET /ws/tap/{{channel}} must be reachable with auth off (no token)"
         );
     }
+
+    #[tokio::test]
+    async fn event_replay_404s_without_a_journal() {
+        let request = || {
+            Request::builder()
+                .uri("/api/events?since_ns=0")
+                .body(Body::empty())
+                .unwrap()
+        };
+        // No runtime here enables the journal, so the replay is refused with
+        // a body that says how to turn it on — and, reads being open by
+        // default, auth on or off makes no difference.
+        let body = json_body_on(auth_disabled_router(), request()).await;
+        assert!(
+            body["error"].as_str().unwrap().contains("--event-journal"),
+            "{body}"
+        );
+        assert_eq!(
+            status_on(auth_disabled_router(), request()).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(status_of(request()).await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn preview_ws_is_gated_like_the_tap() {
+        let preview_ws_request = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("GET")
+                .uri("/ws/preview/camera/video?fps=4&format=webp");
+            if let Some(token) = token {
+                builder = builder.header(AUTHORIZATION, bearer(token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+        assert_eq!(
+            status_of(preview_ws_request(None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_ne!(
+            status_of(preview_ws_request(Some(TEST_TOKEN))).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_ne!(
+            status_on(auth_disabled_router(), preview_ws_request(None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let spec = serde_json::to_value(openapi_spec()).unwrap();
+        assert!(
+            spec["paths"]["/ws/preview/{processor_id}/{port}"]["get"].is_object(),
+            "the preview WebSocket must appear in the OpenAPI spec"
+        );
+    }
 }
@@ -7,17 +7,82 @@ pub mod _generated_ {
 }
 
 mod auth;
+mod camera_controls;
+mod controller_agent;
+mod controller_command;
+#[cfg(feature = "grpc")]
+mod grpc_api;
 mod handlers;
 mod mcp;
+mod mcp_prompts;
+mod mqtt;
 pub mod node_registry;
+mod oidc;
 mod ops;
+mod osc;
+mod osc_sender;
+mod osc_server;
+mod probes;
 mod processor;
+mod schema_export;
 mod state;
+mod telemetry_spool;
+mod telemetry_uplink;
+mod timeline;
+mod tls;
+mod webhook_notifier;
+#[cfg(feature = "whep")]
+mod whep;
 
-pub use _generated_::ApiServerConfig;
+pub use _generated_::{
+    ApiServerConfig, ControllerAgentConfig, GrpcApiConfig, OscSenderConfig, OscServerConfig,
+    TelemetryUplinkConfig, TimelineConfig, WebhookNotifierConfig, WhepSinkConfig,
+};
+pub use controller_agent::{
+    CONTROLLER_COMMAND_TOPIC, CONTROLLER_RUNTIME_ID_HEADER, ControllerAgentProcessor,
+};
+pub use controller_command::{
+    ControllerAction, ControllerCommand, ControllerCommandReport, ControllerCommandStatus,
+    SignedControllerCommand,
+};
+#[cfg(feature = "grpc")]
+pub use grpc_api::GrpcApiProcessor;
 pub use mcp::serve_stdio_jsonrpc;
 pub use node_registry::{
     NODE_REGISTRY_SCHEMA_VERSION, NodeRegistryEntry, NodeRegistryError, read_entry, registry_dir,
     remove_entry, scan_entries, write_entry,
 };
+pub use handlers::{graph_json_schema, openapi_spec};
+pub use osc_sender::OscSenderProcessor;
+pub use osc_server::{OSC_ACTION_TOPIC, OscServerProcessor};
 pub use processor::ApiServerProcessor;
+pub use telemetry_uplink::{
+    EventSample, LinkSample, ProcessorSample, TELEMETRY_ENVELOPE_VERSION, TELEMETRY_UPLOAD_TOPIC,
+    TelemetryEnvelope, TelemetryUplinkProcessor, decode_telemetry_envelope,
+    encode_telemetry_envelope,
+};
+pub use timeline::{TIMELINE_FINISHED_TOPIC, TimelineProcessor};
+pub use webhook_notifier::{
+    WEBHOOK_DELIVERY_TOPIC, WEBHOOK_SIGNATURE_HEADER, WebhookDeliveryMetrics,
+    WebhookNotifierProcessor, sign_webhook_body, webhook_event_name,
+};
+
+use streamlib::sdk::processors::PROCESSOR_REGISTRY;
+
+/// Register every processor type this package provides on the shared
+/// `PROCESSOR_REGISTRY` — what `streamlib-runtime` seeds at boot, and what a
+/// tool reading that runtime's saved graphs (`streamlib validate`) needs to
+/// know the same types.
+pub fn register_processors() {
+    PROCESSOR_REGISTRY.register::<ApiServerProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<WebhookNotifierProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<TelemetryUplinkProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<ControllerAgentProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<OscServerProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<OscSenderProcessor::Processor>();
+    PROCESSOR_REGISTRY.register::<TimelineProcessor::Processor>();
+    #[cfg(feature = "grpc")]
+    PROCESSOR_REGISTRY.register::<GrpcApiProcessor::Processor>();
+    #[cfg(feature = "whep")]
+    PROCESSOR_REGISTRY.register::<whep::WhepSinkProcessor::Processor>();
+}
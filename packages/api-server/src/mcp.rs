@@ -6,8 +6,9 @@
 //! The MCP dispatch is transport-free: [`dispatch_jsonrpc`] answers one parsed
 //! JSON-RPC 2.0 message against an `Arc<dyn RuntimeOperations>` and knows
 //! nothing about how the bytes arrived. Two transports drive that one surface —
-//! the Streamable-HTTP endpoint (`POST /mcp`, [`mcp_endpoint`]) on the existing
-//! axum stack with its [`crate::auth`] bearer middleware, and the
+//! the Streamable-HTTP endpoint (`POST /mcp`, [`mcp_endpoint`], plus the
+//! `GET /mcp` server-notification stream, [`mcp_notifications`]) on the
+//! existing axum stack with its [`crate::auth`] bearer middleware, and the
 //! newline-delimited stdio server ([`serve_stdio_jsonrpc`]) an MCP host spawns
 //! over a pipe. Sharing the one dispatch means the two transports can never
 //! diverge. It exposes the runtime graph as MCP *tools* so an LLM agent can
@@ -15,12 +16,20 @@
 //! handlers call the shared [`crate::ops`] layer, so the MCP surface and the REST
 //! surface can never drift.
 //!
+//! Alongside the tools it serves read-only *resources* an agent can pull
+//! while diagnosing a pipeline: the graph JSON, per-processor and per-link
+//! metrics, and the last runtime errors (see [`resource_definitions`]). Its
+//! *prompts* are pipeline recipes resolved against the registry
+//! ([`crate::mcp_prompts`]), so an agent assembling one gets the exact types
+//! and port names instead of guessing them.
+//!
 //! Two of the tools (`tap`, `logs`) front WebSocket *streams* in the REST API.
 //! MCP tools are request/response, so each bridges its stream to a **bounded
 //! sample** — both by a count AND a monotonic sample window (a quiet channel /
 //! idle event stream returns the partial sample rather than blocking the tool
 //! call) — and returns the collected sample as the tool result.
 
+use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -28,21 +37,25 @@ use axum::{
     Json,
     extract::State,
     http::StatusCode,
-    response::{IntoResponse, Response},
+    response::{
+        IntoResponse, Response,
+        sse::{Event as SseEvent, KeepAlive, Sse},
+    },
 };
+use futures_util::{Stream, StreamExt, stream};
 use parking_lot::Mutex;
 use serde::Deserialize;
 use serde_json::{Value, json};
 use streamlib::sdk::error::Result;
 use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef};
-use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+use streamlib::sdk::parameter_automation::ParameterChange;
+use streamlib::sdk::processors::ProcessorSpec;
+use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, RuntimeEvent, topics};
+use streamlib::sdk::json_schema::{CreateConnectionRequest, SchemaIdentOutput};
 use streamlib::sdk::runtime::{RuntimeOperations, SubmittedProcessorSource};
 
 use crate::ops::{ReplaceSourceError, SubmitSourceError, SubmittedSourceOutcome};
-use crate::state::{
-    AppState, CreateConnectionRequest, ReplaceProcessorSourceRequest,
-    SubmittedProcessorSourceRequest,
-};
+use crate::state::{AppState, ReplaceProcessorSourceRequest, SubmittedProcessorSourceRequest};
 
 /// MCP protocol revision this server implements (the date-stamped spec version
 /// echoed back on `initialize`). Advertised verbatim; a client that requested a
@@ -60,6 +73,14 @@ const MCP_SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
 const DEFAULT_TAP_SAMPLE_COUNT: usize = 8;
 const DEFAULT_LOGS_SAMPLE_COUNT: usize = 16;
 
+/// Resources served by `resources/read`.
+const GRAPH_RESOURCE_URI: &str = "streamlib://graph";
+const METRICS_RESOURCE_URI: &str = "streamlib://metrics";
+const ERRORS_RESOURCE_URI: &str = "streamlib://errors";
+
+/// Errors the errors resource returns when its URI pins no `limit`.
+const DEFAULT_RECENT_ERROR_COUNT: usize = 20;
+
 /// Hard ceiling on a requested sample `count`, so a tool call cannot pin an
 /// unbounded collection loop.
 const MAX_SAMPLE_COUNT: usize = 1024;
@@ -101,7 +122,8 @@ pub(crate) struct JsonRpcRequest {
     params: Option<Value>,
 }
 
-/// A JSON-RPC error (method-not-found / invalid-params). Tool-execution
+/// A JSON-RPC error (method-not-found / invalid-params / resource-not-found,
+/// or an internal error reading a resource). Tool-execution
 /// failures are NOT these — they surface as a successful `tools/call` result
 /// with `isError: true`, per the MCP tool-error convention.
 struct RpcError {
@@ -122,12 +144,25 @@ impl RpcError {
             message: message.into(),
         }
     }
+    fn internal_error(message: impl Into<String>) -> Self {
+        Self {
+            code: -32603,
+            message: message.into(),
+        }
+    }
+    fn resource_not_found(uri: &str) -> Self {
+        Self {
+            code: -32002,
+            message: format!("resource not found: {uri}"),
+        }
+    }
 }
 
 /// `POST /mcp` — the MCP Streamable-HTTP endpoint. Dispatches one JSON-RPC
 /// message through the transport-free [`dispatch_jsonrpc`] and answers with a
 /// single `application/json` response (this server's tools are all
-/// request/response, so it never opens an SSE stream); a notification is acked
+/// request/response, so a POST never opens an SSE stream — server-initiated
+/// messages go out on [`mcp_notifications`]); a notification is acked
 /// `202 Accepted` with no body.
 #[tracing::instrument(skip_all, fields(mcp_method = %request.method))]
 pub(crate) async fn mcp_endpoint(
@@ -140,6 +175,56 @@ pub(crate) async fn mcp_endpoint(
     }
 }
 
+/// `GET /mcp` — the Streamable-HTTP server-notification stream. An SSE stream
+/// of JSON-RPC notifications, one per `message` event, for as long as the
+/// client keeps it open: `notifications/resources/updated` for the graph
+/// resource when the graph changes, and for the errors resource when a
+/// runtime error is published. The transport is sessionless, so every open
+/// stream carries every update; `resources/subscribe` is acknowledged but
+/// filters nothing.
+pub(crate) async fn mcp_notifications()
+-> Sse<impl Stream<Item = std::result::Result<SseEvent, Infallible>>> {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
+    let listener = Arc::new(Mutex::new(McpEventForwarder { tx }));
+    PUBSUB.subscribe(topics::ALL, listener.clone());
+
+    // The stream owns the listener, so the subscription lives exactly as long
+    // as the client's connection (Weak-ref cleanup on the next publish).
+    let notifications = stream::unfold((rx, listener), |(mut rx, listener)| async move {
+        let event = rx.recv().await?;
+        Some((resource_update_notifications(&event), (rx, listener)))
+    })
+    .flat_map(|notifications| {
+        stream::iter(notifications.into_iter().map(|notification| {
+            Ok(SseEvent::default()
+                .event("message")
+                .data(notification.to_string()))
+        }))
+    });
+    Sse::new(notifications).keep_alive(KeepAlive::default())
+}
+
+/// The notifications `event` is announced by on the [`mcp_notifications`]
+/// stream: which resources it changed. Empty for events no resource reflects.
+fn resource_update_notifications(event: &Event) -> Vec<Value> {
+    let mut uris = Vec::new();
+    if matches!(event, Event::RuntimeGlobal(RuntimeEvent::GraphDidChange)) {
+        uris.push(GRAPH_RESOURCE_URI);
+    }
+    if event.error_message().is_some() {
+        uris.push(ERRORS_RESOURCE_URI);
+    }
+    uris.into_iter()
+        .map(|uri| {
+            json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/resources/updated",
+                "params": { "uri": uri },
+            })
+        })
+        .collect()
+}
+
 /// Dispatch one parsed MCP JSON-RPC 2.0 message against `runtime`, transport-free.
 ///
 /// Returns the full JSON-RPC response envelope (`result` or `error`) for a
@@ -239,6 +324,16 @@ async fn dispatch(
         "ping" => Ok(json!({})),
         "tools/list" => Ok(json!({ "tools": tool_definitions() })),
         "tools/call" => tools_call(runtime, params).await,
+        "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+        "resources/templates/list" => {
+            Ok(json!({ "resourceTemplates": resource_template_definitions() }))
+        }
+        "resources/read" => resources_read(runtime, params).await,
+        // Every notification stream carries every resource update, so a
+        // subscription has nothing to register.
+        "resources/subscribe" | "resources/unsubscribe" => Ok(json!({})),
+        "prompts/list" => Ok(json!({ "prompts": prompt_definitions() })),
+        "prompts/get" => prompts_get(params),
         other => Err(RpcError::method_not_found(other)),
     }
 }
@@ -246,9 +341,13 @@ async fn dispatch(
 fn initialize_result() -> Value {
     json!({
         "protocolVersion": MCP_PROTOCOL_VERSION,
-        "capabilities": { "tools": { "listChanged": false } },
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": true, "listChanged": false },
+            "prompts": { "listChanged": false },
+        },
         "serverInfo": { "name": MCP_SERVER_NAME, "version": MCP_SERVER_VERSION },
-        "instructions": "StreamLib runtime control plane. Tools inspect and mutate the live processor graph and observe its channels and event stream.",
+        "instructions": "StreamLib runtime control plane. Tools inspect and mutate the live processor graph and observe its channels and event stream; resources expose the graph, its metrics and recent runtime errors.",
     })
 }
 
@@ -310,6 +409,36 @@ fn tool_definitions() -> Vec<Value> {
                 "required": ["target_session_module", "language", "source"]
             },
         }),
+        json!({
+            "name": "add_processor",
+            "description": "Instantiate a registered processor type in the running graph. The type is the structured `@org/package/Type@version` identity, as listed by the registry. Returns the new processor's id for `connect`, `set_parameter` and `remove_processor`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "processor_type": {
+                        "type": "object",
+                        "properties": {
+                            "org": { "type": "string", "description": "Org segment, e.g. `tatolab`." },
+                            "package": { "type": "string", "description": "Package segment, e.g. `h264`." },
+                            "type": { "type": "string", "description": "PascalCase type name, e.g. `H264Encoder`." },
+                            "version": {
+                                "type": "object",
+                                "properties": {
+                                    "major": { "type": "integer", "minimum": 0 },
+                                    "minor": { "type": "integer", "minimum": 0 },
+                                    "patch": { "type": "integer", "minimum": 0 }
+                                },
+                                "required": ["major", "minor", "patch"]
+                            }
+                        },
+                        "required": ["org", "package", "type", "version"]
+                    },
+                    "config": { "type": "object", "description": "Processor config. Defaults to {}." }
+                },
+                "required": ["processor_type"],
+                "additionalProperties": false
+            },
+        }),
         json!({
             "name": "remove_processor",
             "description": "Remove a processor instance from the graph by id.",
@@ -335,6 +464,22 @@ fn tool_definitions() -> Vec<Value> {
                 "additionalProperties": false
             },
         }),
+        json!({
+            "name": "set_parameter",
+            "description": "Change one config parameter of a running processor without replacing its whole config, optionally at a media-clock time and ramped from the current value.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "processor_id": { "type": "string" },
+                    "parameter": { "type": "string", "description": "Top-level config field (`radius`) or a JSON pointer into the config (`/inputs/0/gain_db`)." },
+                    "value": { "description": "The new value." },
+                    "at_ns": { "type": "integer", "description": "Media-clock time in nanoseconds the value takes effect. Omit for the processor's next tick." },
+                    "interpolation": { "type": "string", "enum": ["step", "linear", "exponential"], "description": "How the value moves to the new one by `at_ns`. Defaults to `step`." }
+                },
+                "required": ["processor_id", "parameter", "value"],
+                "additionalProperties": false
+            },
+        }),
         json!({
             "name": "tap",
             "description": "Attach a read-only tap to a channel and collect a bounded sample of raw bags (FrameHeader-framed bytes; a hex preview plus byte length per bag).",
@@ -359,9 +504,150 @@ fn tool_definitions() -> Vec<Value> {
                 "additionalProperties": false
             },
         }),
+        json!({
+            "name": "replay_events",
+            "description": "Replay the runtime's event journal: every event published after a timestamp, oldest first, with the topic and publish time of each. Use it to rebuild state after reconnecting instead of sampling the live stream. Fails when the runtime keeps no journal.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "since_ns": { "type": "integer", "description": "Only events published after this wall-clock time, nanoseconds since the Unix epoch (the `timestamp_ns` of the last event seen). Omit to replay the whole journal." }
+                },
+                "additionalProperties": false
+            },
+        }),
     ]
 }
 
+// ============================================================================
+// Resources
+// ============================================================================
+
+/// The MCP resource catalog returned by `resources/list`. Every resource is a
+/// JSON snapshot taken when it is read.
+fn resource_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "uri": GRAPH_RESOURCE_URI,
+            "name": "graph",
+            "description": "The current runtime graph (processors, links, states, metrics) as JSON — the same document as the `graph` tool.",
+            "mimeType": "application/json",
+        }),
+        json!({
+            "uri": METRICS_RESOURCE_URI,
+            "name": "metrics",
+            "description": "Per-processor state and metrics (throughput, latency, frames processed and dropped) and per-link delivery and drop counters.",
+            "mimeType": "application/json",
+        }),
+        json!({
+            "uri": ERRORS_RESOURCE_URI,
+            "name": "errors",
+            "description": "The most recent runtime errors — failed compiles, processor errors, runtime lifecycle failures — oldest first, with the topic and publish time of each.",
+            "mimeType": "application/json",
+        }),
+    ]
+}
+
+/// Parameterised resources returned by `resources/templates/list`.
+fn resource_template_definitions() -> Vec<Value> {
+    vec![json!({
+        "uriTemplate": format!("{ERRORS_RESOURCE_URI}{{?limit}}"),
+        "name": "errors",
+        "description": "The last `limit` runtime errors, oldest first.",
+        "mimeType": "application/json",
+    })]
+}
+
+async fn resources_read(
+    runtime: &Arc<dyn RuntimeOperations>,
+    params: Value,
+) -> std::result::Result<Value, RpcError> {
+    #[derive(Deserialize)]
+    struct ReadParams {
+        uri: String,
+    }
+    let ReadParams { uri } = serde_json::from_value(params)
+        .map_err(|e| RpcError::invalid_params(format!("malformed resources/read params: {e}")))?;
+    let (resource, query) = uri.split_once('?').unwrap_or((uri.as_str(), ""));
+
+    let contents = match resource {
+        GRAPH_RESOURCE_URI => runtime
+            .to_json_async()
+            .await
+            .map_err(|e| RpcError::internal_error(format!("graph export failed: {e}")))?,
+        METRICS_RESOURCE_URI => {
+            let graph = crate::controller_command::graph(runtime)
+                .await
+                .map_err(|e| RpcError::internal_error(format!("graph export failed: {e}")))?;
+            let (processors, links) = crate::telemetry_uplink::sample_graph(&graph);
+            json!({ "processors": processors, "links": links })
+        }
+        ERRORS_RESOURCE_URI => {
+            let limit = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("limit="));
+            let limit = match limit {
+                Some(limit) => limit.parse().map_err(|_| {
+                    RpcError::invalid_params(format!("`limit` must be a count, got `{limit}`"))
+                })?,
+                None => DEFAULT_RECENT_ERROR_COUNT,
+            };
+            json!({ "errors": PUBSUB.recent_errors(limit) })
+        }
+        _ => return Err(RpcError::resource_not_found(&uri)),
+    };
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": contents.to_string(),
+        }]
+    }))
+}
+
+// ============================================================================
+// Prompts
+// ============================================================================
+
+/// The MCP prompt catalog returned by `prompts/list`: the pipeline recipes the
+/// registered processors can build right now.
+fn prompt_definitions() -> Vec<Value> {
+    crate::mcp_prompts::registered_pipeline_prompts()
+        .into_iter()
+        .map(|prompt| {
+            json!({
+                "name": prompt.name,
+                "title": prompt.title,
+                "description": prompt.description,
+                "arguments": [],
+            })
+        })
+        .collect()
+}
+
+fn prompts_get(params: Value) -> std::result::Result<Value, RpcError> {
+    #[derive(Deserialize)]
+    struct GetParams {
+        name: String,
+    }
+    let GetParams { name } = serde_json::from_value(params)
+        .map_err(|e| RpcError::invalid_params(format!("malformed prompts/get params: {e}")))?;
+    let prompt = crate::mcp_prompts::registered_pipeline_prompts()
+        .into_iter()
+        .find(|prompt| prompt.name == name)
+        .ok_or_else(|| {
+            RpcError::invalid_params(format!(
+                "unknown prompt `{name}`, or its processors are not registered"
+            ))
+        })?;
+    Ok(json!({
+        "description": prompt.description,
+        "messages": [{
+            "role": "user",
+            "content": { "type": "text", "text": prompt.text },
+        }],
+    }))
+}
+
 // ============================================================================
 // tools/call dispatch
 // ============================================================================
@@ -388,10 +674,13 @@ async fn tools_call(
         "graph" => call_graph(runtime).await,
         "submit_processor" => call_submit_processor(runtime, arguments).await,
         "replace_processor" => call_replace_processor(runtime, arguments).await,
+        "add_processor" => call_add_processor(runtime, arguments).await,
         "remove_processor" => call_remove_processor(runtime, arguments).await,
         "connect" => call_connect(runtime, arguments).await,
+        "set_parameter" => call_set_parameter(runtime, arguments).await,
         "tap" => call_tap(runtime, arguments).await,
         "logs" => call_logs(runtime, arguments).await,
+        "replay_events" => call_replay_events(arguments),
         other => tool_error(format!("unknown tool: {other}")),
     };
     Ok(result)
@@ -442,6 +731,30 @@ async fn call_replace_processor(runtime: &Arc<dyn RuntimeOperations>, arguments:
     }
 }
 
+async fn call_add_processor(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) -> Value {
+    #[derive(Deserialize)]
+    struct AddArgs {
+        processor_type: SchemaIdentOutput,
+        #[serde(default)]
+        config: Option<Value>,
+    }
+    let AddArgs {
+        processor_type,
+        config,
+    } = match serde_json::from_value(arguments) {
+        Ok(args) => args,
+        Err(e) => return tool_error(format!("add_processor arguments: {e}")),
+    };
+    let Some(ident) = crate::ops::processor_ident(processor_type) else {
+        return tool_error("add_processor: one of org / package / type failed validation");
+    };
+    let spec = ProcessorSpec::new(ident, config.unwrap_or_else(|| json!({})));
+    match runtime.add_processor_async(spec).await {
+        Ok(id) => tool_ok(json!({ "processor_id": id.to_string() })),
+        Err(e) => tool_error(format!("add_processor failed: {e}")),
+    }
+}
+
 async fn call_remove_processor(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) -> Value {
     #[derive(Deserialize)]
     struct RemoveArgs {
@@ -473,6 +786,19 @@ async fn call_connect(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) ->
     }
 }
 
+async fn call_set_parameter(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) -> Value {
+    let change: ParameterChange = match serde_json::from_value(arguments) {
+        Ok(change) => change,
+        Err(e) => return tool_error(format!("set_parameter arguments: {e}")),
+    };
+    let processor_id = change.processor_id.to_string();
+    let parameter = change.parameter.clone();
+    match runtime.set_parameter_async(change).await {
+        Ok(()) => tool_ok(json!({ "processor_id": processor_id, "parameter": parameter })),
+        Err(e) => tool_error(format!("set_parameter failed: {e}")),
+    }
+}
+
 async fn call_tap(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) -> Value {
     #[derive(Deserialize)]
     struct TapArgs {
@@ -555,6 +881,22 @@ async fn call_logs(runtime: &Arc<dyn RuntimeOperations>, arguments: Value) -> Va
     }))
 }
 
+fn call_replay_events(arguments: Value) -> Value {
+    #[derive(Deserialize)]
+    struct ReplayArgs {
+        #[serde(default)]
+        since_ns: Option<i64>,
+    }
+    let ReplayArgs { since_ns } = match serde_json::from_value(arguments) {
+        Ok(args) => args,
+        Err(e) => return tool_error(format!("replay_events arguments: {e}")),
+    };
+    match PUBSUB.journal_since(since_ns.unwrap_or(i64::MIN)) {
+        Some(events) => tool_ok(json!({ "events": events })),
+        None => tool_error(crate::handlers::EVENT_JOURNAL_DISABLED),
+    }
+}
+
 // ============================================================================
 // Result shaping
 // ============================================================================
@@ -632,8 +974,9 @@ fn hex_encode(bytes: &[u8]) -> String {
     hex
 }
 
-/// Forwards runtime events into the `logs` tool's bounded collection channel,
-/// mirroring the REST WebSocket event forwarder.
+/// Forwards runtime events into the `logs` tool's bounded collection channel
+/// or a [`mcp_notifications`] stream, mirroring the REST WebSocket event
+/// forwarder.
 struct McpEventForwarder {
     tx: tokio::sync::mpsc::UnboundedSender<Event>,
 }
@@ -665,7 +1008,8 @@ mod tests {
     use streamlib::sdk::graph::{
         InputLinkPortRef, LinkUniqueId, OutputLinkPortRef, ProcessorUniqueId,
     };
-    use streamlib::sdk::processors::{PortSchemaSpec, ProcessorSpec};
+    use streamlib::sdk::parameter_automation::ParameterInterpolation;
+    use streamlib::sdk::processors::PortSchemaSpec;
     use streamlib::sdk::runtime::{
         BoxFuture, RegisterProcessorReceipt, RegisteredPortReceipt, RegisteredProcessorReceipt,
         ReplaceProcessorFromSource, RuntimeOperations, SubmittedProcessorSource, TapSubscription,
@@ -703,6 +1047,8 @@ mod tests {
         recorded_removed_processors: Arc<Mutex<Vec<String>>>,
         recorded_connections: RecordedConnections,
         recorded_replaced_modules: Arc<Mutex<Vec<String>>>,
+        recorded_added_processors: Arc<Mutex<Vec<ProcessorSpec>>>,
+        recorded_parameter_changes: Arc<Mutex<Vec<ParameterChange>>>,
     }
 
     impl RecordingStubRuntime {
@@ -714,6 +1060,8 @@ mod tests {
                 recorded_removed_processors: Arc::new(Mutex::new(Vec::new())),
                 recorded_connections: Arc::new(Mutex::new(Vec::new())),
                 recorded_replaced_modules: Arc::new(Mutex::new(Vec::new())),
+                recorded_added_processors: Arc::new(Mutex::new(Vec::new())),
+                recorded_parameter_changes: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
@@ -779,8 +1127,9 @@ mod tests {
     impl RuntimeOperations for RecordingStubRuntime {
         fn add_processor_async(
             &self,
-            _spec: ProcessorSpec,
+            spec: ProcessorSpec,
         ) -> BoxFuture<'_, Result<ProcessorUniqueId>> {
+            self.recorded_added_processors.lock().push(spec);
             let id = self.instance_id.clone();
             Box::pin(async move { Ok(id) })
         }
@@ -847,6 +1196,105 @@ mod tests {
                 ))
             })
         }
+        fn update_processor_config_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _config: serde_json::Value,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Err(Error::ProcessorNotFound(processor_id.to_string())) })
+        }
+        fn set_processor_paused_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _paused: bool,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Err(Error::ProcessorNotFound(processor_id.to_string())) })
+        }
+        fn save_graph_snapshot_async(
+            &self,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_snapshot::GraphSnapshot>> {
+            Box::pin(async move {
+                Err(Error::NotSupported(
+                    "stub runtime has no graph to snapshot".to_string(),
+                ))
+            })
+        }
+        fn undo_graph_edit_async(
+            &self,
+        ) -> BoxFuture<'_, Result<Option<streamlib::sdk::graph_edit_history::GraphEdit>>> {
+            Box::pin(async move { Ok(None) })
+        }
+        fn redo_graph_edit_async(
+            &self,
+        ) -> BoxFuture<'_, Result<Option<streamlib::sdk::graph_edit_history::GraphEdit>>> {
+            Box::pin(async move { Ok(None) })
+        }
+        fn apply_graph_patch_async(
+            &self,
+            _patch: streamlib::sdk::graph_patch::GraphPatch,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::graph_patch::GraphPatchReceipt>> {
+            Box::pin(async move { Ok(Default::default()) })
+        }
+        fn morph_processor_configs_async(
+            &self,
+            _morph: streamlib::sdk::preset_morph::PresetMorph,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn set_midi_mappings_async(
+            &self,
+            _bindings: Vec<streamlib::sdk::midi_mapping::MidiControlBinding>,
+        ) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move { Ok(()) })
+        }
+        fn midi_mappings_async(
+            &self,
+        ) -> BoxFuture<'_, Result<Vec<streamlib::sdk::midi_mapping::MidiControlBinding>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn set_parameter_async(&self, change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+            self.recorded_parameter_changes.lock().push(change);
+            Box::pin(async move { Ok(()) })
+        }
+        fn inject_chaos_fault_async(
+            &self,
+            _fault: streamlib::sdk::chaos::ChaosFault,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::chaos::ChaosFaultRecord>> {
+            Box::pin(async move {
+                Err(streamlib::sdk::error::Error::NotSupported(
+                    "chaos mode is off".into(),
+                ))
+            })
+        }
+        fn chaos_fault_log_async(
+            &self,
+        ) -> BoxFuture<'_, Result<Vec<streamlib::sdk::chaos::ChaosFaultRecord>>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+        fn snapshot_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _port: String,
+            _path: std::path::PathBuf,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::frame_snapshot::FrameSnapshot>> {
+            Box::pin(async move {
+                Err(streamlib::sdk::error::Error::ProcessorNotFound(
+                    processor_id.to_string(),
+                ))
+            })
+        }
+        fn preview_async(
+            &self,
+            processor_id: ProcessorUniqueId,
+            _port: String,
+            _options: streamlib::sdk::frame_snapshot::PreviewOptions,
+        ) -> BoxFuture<'_, Result<streamlib::sdk::runtime::PortPreview>> {
+            Box::pin(async move {
+                Err(streamlib::sdk::error::Error::ProcessorNotFound(
+                    processor_id.to_string(),
+                ))
+            })
+        }
         fn add_processor(&self, _spec: ProcessorSpec) -> Result<ProcessorUniqueId> {
             Ok(self.instance_id.clone())
         }
@@ -868,6 +1316,7 @@ mod tests {
         crate::handlers::build_router(
             runtime,
             None,
+            crate::probes::Probes::default(),
             #[cfg(feature = "moq")]
             "test-runtime-id".to_string(),
         )
@@ -927,6 +1376,130 @@ mod tests {
         assert_eq!(body, Value::Null);
     }
 
+    #[tokio::test]
+    async fn resources_list_and_read_serve_graph_and_errors() {
+        let runtime: Arc<dyn RuntimeOperations> = Arc::new(RecordingStubRuntime::new());
+
+        let (status, body) = mcp_call(
+            runtime.clone(),
+            json!({ "jsonrpc": "2.0", "id": 30, "method": "resources/list" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let uris: Vec<&str> = body["result"]["resources"]
+            .as_array()
+            .expect("resources array")
+            .iter()
+            .filter_map(|resource| resource["uri"].as_str())
+            .collect();
+        assert_eq!(
+            uris,
+            [
+                "streamlib://graph",
+                "streamlib://metrics",
+                "streamlib://errors"
+            ]
+        );
+
+        let (_, body) = mcp_call(
+            runtime.clone(),
+            json!({
+                "jsonrpc": "2.0", "id": 31, "method": "resources/read",
+                "params": { "uri": "streamlib://graph" }
+            }),
+        )
+        .await;
+        let content = &body["result"]["contents"][0];
+        assert_eq!(content["mimeType"], "application/json");
+        let graph: Value = serde_json::from_str(content["text"].as_str().unwrap()).unwrap();
+        assert_eq!(graph, json!({ "processors": [], "links": [] }));
+
+        let (_, body) = mcp_call(
+            runtime.clone(),
+            json!({
+                "jsonrpc": "2.0", "id": 32, "method": "resources/read",
+                "params": { "uri": "streamlib://errors?limit=5" }
+            }),
+        )
+        .await;
+        let content = &body["result"]["contents"][0];
+        assert_eq!(content["uri"], "streamlib://errors?limit=5");
+        let errors: Value = serde_json::from_str(content["text"].as_str().unwrap()).unwrap();
+        assert!(errors["errors"].is_array(), "{errors}");
+
+        let (_, body) = mcp_call(
+            runtime,
+            json!({
+                "jsonrpc": "2.0", "id": 33, "method": "resources/read",
+                "params": { "uri": "streamlib://nope" }
+            }),
+        )
+        .await;
+        assert_eq!(body["error"]["code"], -32002, "body={body}");
+    }
+
+    #[tokio::test]
+    async fn get_mcp_opens_the_notification_stream() {
+        let response = mcp_router(Arc::new(RecordingStubRuntime::new()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/mcp")
+                    .header("accept", "text/event-stream")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/event-stream",
+            "GET /mcp must answer with an SSE stream"
+        );
+    }
+
+    #[test]
+    fn graph_changes_and_errors_announce_resource_updates() {
+        let uris = |event: Event| {
+            resource_update_notifications(&event)
+                .into_iter()
+                .map(|notification| {
+                    assert_eq!(notification["method"], "notifications/resources/updated");
+                    notification["params"]["uri"].as_str().unwrap().to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            uris(Event::RuntimeGlobal(RuntimeEvent::GraphDidChange)),
+            ["streamlib://graph"]
+        );
+        assert_eq!(
+            uris(Event::RuntimeGlobal(RuntimeEvent::CompilerDidFail {
+                error: "no such port".into()
+            })),
+            ["streamlib://errors"]
+        );
+        assert!(uris(Event::RuntimeGlobal(RuntimeEvent::RuntimeStarted)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn prompts_get_rejects_an_unknown_recipe() {
+        let (status, body) = mcp_call(
+            Arc::new(RecordingStubRuntime::new()),
+            json!({
+                "jsonrpc": "2.0", "id": 34, "method": "prompts/get",
+                "params": { "name": "does_not_exist" }
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["error"]["code"], -32602, "body={body}");
+    }
+
     #[tokio::test]
     async fn tools_list_advertises_every_veneer_tool() {
         let (status, body) = mcp_call(
@@ -945,10 +1518,13 @@ mod tests {
             "graph",
             "submit_processor",
             "replace_processor",
+            "add_processor",
             "remove_processor",
             "connect",
+            "set_parameter",
             "tap",
             "logs",
+            "replay_events",
         ] {
             assert!(
                 names.contains(&expected),
@@ -1046,7 +1622,8 @@ mod tests {
         let auth_router = || {
             crate::handlers::build_router(
                 Arc::new(RecordingStubRuntime::new()),
-                Some(crate::auth::ApiServerBearerToken::from_secret(TOKEN)),
+                Some(crate::auth::ApiServerAuth::from_secret(TOKEN)),
+                crate::probes::Probes::default(),
                 #[cfg(feature = "moq")]
                 "test-runtime-id".to_string(),
             )
@@ -1208,6 +1785,25 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn tools_call_replay_events_reports_a_missing_journal_in_band() {
+        // PUBSUB keeps no journal here; the failure is a tool error the agent
+        // can read, not a JSON-RPC error.
+        let (status, body) = mcp_call(
+            Arc::new(RecordingStubRuntime::new()),
+            json!({
+                "jsonrpc": "2.0", "id": 13, "method": "tools/call",
+                "params": { "name": "replay_events", "arguments": { "since_ns": 0 } }
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"]["isError"], true, "body={body}");
+        let text = body["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("event journal"), "{text}");
+    }
+
     #[tokio::test]
     async fn tools_call_remove_processor_reaches_the_runtime() {
         let runtime = Arc::new(RecordingStubRuntime::new());
@@ -1260,6 +1856,81 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn tools_call_add_processor_instantiates_the_structured_type() {
+        let runtime = Arc::new(RecordingStubRuntime::new());
+        let recorded_added = runtime.recorded_added_processors.clone();
+
+        let (status, body) = mcp_call(
+            runtime,
+            json!({
+                "jsonrpc": "2.0", "id": 17, "method": "tools/call",
+                "params": { "name": "add_processor", "arguments": {
+                    "processor_type": {
+                        "org": "tatolab", "package": "h264", "type": "H264Encoder",
+                        "version": { "major": 1, "minor": 0, "patch": 0 }
+                    },
+                    "config": { "profile": "baseline" }
+                } }
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"]["isError"], false, "body={body}");
+        let text = body["result"]["content"][0]["text"].as_str().unwrap();
+        let outcome: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(outcome["processor_id"], "mcp-instance");
+        let added = recorded_added.lock();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].config, json!({ "profile": "baseline" }));
+
+        let (_, body) = mcp_call(
+            Arc::new(RecordingStubRuntime::new()),
+            json!({
+                "jsonrpc": "2.0", "id": 18, "method": "tools/call",
+                "params": { "name": "add_processor", "arguments": {
+                    "processor_type": {
+                        "org": "Not An Org", "package": "h264", "type": "H264Encoder",
+                        "version": { "major": 1, "minor": 0, "patch": 0 }
+                    }
+                } }
+            }),
+        )
+        .await;
+        assert_eq!(body["result"]["isError"], true, "body={body}");
+    }
+
+    #[tokio::test]
+    async fn tools_call_set_parameter_schedules_the_change() {
+        let runtime = Arc::new(RecordingStubRuntime::new());
+        let recorded_changes = runtime.recorded_parameter_changes.clone();
+
+        let (status, body) = mcp_call(
+            runtime,
+            json!({
+                "jsonrpc": "2.0", "id": 19, "method": "tools/call",
+                "params": { "name": "set_parameter", "arguments": {
+                    "processor_id": "blur", "parameter": "radius", "value": 4.0,
+                    "at_ns": 1_000, "interpolation": "linear"
+                } }
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"]["isError"], false, "body={body}");
+        let changes = recorded_changes.lock();
+        assert_eq!(
+            *changes,
+            vec![
+                ParameterChange::new("blur", "radius", json!(4.0))
+                    .at_ns(1_000)
+                    .with_interpolation(ParameterInterpolation::Linear)
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn tools_call_replace_processor_reaches_the_runtime() {
         let runtime = Arc::new(RecordingStubRuntime::new());
@@ -1391,10 +2062,13 @@ mod tests {
             "graph",
             "submit_processor",
             "replace_processor",
+            "add_processor",
             "remove_processor",
             "connect",
+            "set_parameter",
             "tap",
             "logs",
+            "replay_events",
         ] {
             assert!(
                 names.contains(&expected),
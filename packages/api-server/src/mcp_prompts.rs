@@ -0,0 +1,366 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MCP prompts for common pipeline recipes, generated from the registry.
+//!
+//! A recipe names its stages by processor type and nothing more. Everything
+//! an agent would otherwise have to guess — the exact `@org/package/Type@version`,
+//! the port names, which output feeds which input, the config schema — is
+//! read from the registered [`ProcessorDescriptor`]s when the prompt is
+//! listed. A recipe whose stages are not all registered, or whose adjacent
+//! stages share no compatible port, is not offered at all, so a prompt never
+//! names a type the runtime lacks or a port that does not exist.
+
+use serde_json::json;
+use streamlib::sdk::descriptors::{PortDescriptor, ProcessorDescriptor};
+use streamlib::sdk::json_schema::SchemaIdentOutput;
+use streamlib::sdk::processors::{PROCESSOR_REGISTRY, PortSchemaSpec};
+
+/// One stage of a recipe: the processor type it instantiates, the alias the
+/// prompt refers to it by, and what it does in this pipeline.
+struct RecipeStage {
+    alias: &'static str,
+    org: &'static str,
+    package: &'static str,
+    type_name: &'static str,
+    role: &'static str,
+}
+
+/// A linear pipeline: each stage feeds the next.
+struct PipelineRecipe {
+    name: &'static str,
+    title: &'static str,
+    description: &'static str,
+    stages: &'static [RecipeStage],
+}
+
+const RECIPES: &[PipelineRecipe] = &[
+    PipelineRecipe {
+        name: "camera_preview",
+        title: "Camera → display",
+        description: "Preview a camera in a window.",
+        stages: &[
+            RecipeStage {
+                alias: "camera",
+                org: "tatolab",
+                package: "camera",
+                type_name: "Camera",
+                role: "captures the camera",
+            },
+            RecipeStage {
+                alias: "display",
+                org: "tatolab",
+                package: "display",
+                type_name: "Display",
+                role: "shows the frames in a window",
+            },
+        ],
+    },
+    PipelineRecipe {
+        name: "camera_chroma_key_whip",
+        title: "Camera → chroma key → compositor → WHIP",
+        description: "Key a camera over a composite, encode it and publish it to a WHIP \
+                      endpoint.",
+        stages: &[
+            RecipeStage {
+                alias: "camera",
+                org: "tatolab",
+                package: "camera",
+                type_name: "Camera",
+                role: "captures the presenter in front of the green screen",
+            },
+            RecipeStage {
+                alias: "chroma_key",
+                org: "tatolab",
+                package: "shader-effect",
+                type_name: "ShaderEffect",
+                role: "runs a chroma-key shader that makes the key colour transparent",
+            },
+            RecipeStage {
+                alias: "compositor",
+                org: "tatolab",
+                package: "compositor",
+                type_name: "Compositor",
+                role: "layers the keyed camera over the background layers",
+            },
+            RecipeStage {
+                alias: "encoder",
+                org: "tatolab",
+                package: "h264",
+                type_name: "H264Encoder",
+                role: "encodes the composite as H.264",
+            },
+            RecipeStage {
+                alias: "whip",
+                org: "tatolab",
+                package: "webrtc",
+                type_name: "WebrtcWhip",
+                role: "publishes the stream to the WHIP endpoint",
+            },
+        ],
+    },
+    PipelineRecipe {
+        name: "camera_rtmp",
+        title: "Camera → H.264 → RTMP",
+        description: "Stream a camera to an RTMP ingest.",
+        stages: &[
+            RecipeStage {
+                alias: "camera",
+                org: "tatolab",
+                package: "camera",
+                type_name: "Camera",
+                role: "captures the camera",
+            },
+            RecipeStage {
+                alias: "encoder",
+                org: "tatolab",
+                package: "h264",
+                type_name: "H264Encoder",
+                role: "encodes the frames as H.264",
+            },
+            RecipeStage {
+                alias: "rtmp",
+                org: "tatolab",
+                package: "rtmp",
+                type_name: "RtmpSink",
+                role: "pushes the stream to the RTMP ingest",
+            },
+        ],
+    },
+    PipelineRecipe {
+        name: "microphone_monitor",
+        title: "Microphone → speakers",
+        description: "Monitor a microphone on the default audio output.",
+        stages: &[
+            RecipeStage {
+                alias: "microphone",
+                org: "tatolab",
+                package: "audio",
+                type_name: "AudioCapture",
+                role: "captures the microphone",
+            },
+            RecipeStage {
+                alias: "speakers",
+                org: "tatolab",
+                package: "audio",
+                type_name: "AudioOutput",
+                role: "plays the audio",
+            },
+        ],
+    },
+];
+
+/// A recipe resolved against the registry, ready to serve as an MCP prompt.
+pub(crate) struct PipelinePrompt {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// The instructions, naming exact types, ports and config schemas.
+    pub text: String,
+}
+
+/// The recipes the runtime's registered processors can build.
+pub(crate) fn registered_pipeline_prompts() -> Vec<PipelinePrompt> {
+    pipeline_prompts(&PROCESSOR_REGISTRY.list_registered())
+}
+
+/// [`registered_pipeline_prompts`] over explicit descriptors.
+fn pipeline_prompts(descriptors: &[ProcessorDescriptor]) -> Vec<PipelinePrompt> {
+    RECIPES
+        .iter()
+        .filter_map(|recipe| resolve_recipe(recipe, descriptors))
+        .collect()
+}
+
+/// A wiring between two adjacent stages.
+struct RecipeLink<'a> {
+    from: &'static str,
+    from_port: &'a PortDescriptor,
+    to: &'static str,
+    to_port: &'a PortDescriptor,
+}
+
+fn resolve_recipe(
+    recipe: &PipelineRecipe,
+    descriptors: &[ProcessorDescriptor],
+) -> Option<PipelinePrompt> {
+    let stages = recipe
+        .stages
+        .iter()
+        .map(|stage| Some((stage, registered_descriptor(stage, descriptors)?)))
+        .collect::<Option<Vec<_>>>()?;
+    let links = stages
+        .windows(2)
+        .map(|pair| {
+            let ((from, from_descriptor), (to, to_descriptor)) = (pair[0], pair[1]);
+            let (from_port, to_port) = compatible_ports(from_descriptor, to_descriptor)?;
+            Some(RecipeLink {
+                from: from.alias,
+                from_port,
+                to: to.alias,
+                to_port,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(PipelinePrompt {
+        name: recipe.name,
+        title: recipe.title,
+        description: recipe.description,
+        text: render_recipe(recipe, &stages, &links),
+    })
+}
+
+/// The newest registered version of the stage's processor type.
+fn registered_descriptor<'a>(
+    stage: &RecipeStage,
+    descriptors: &'a [ProcessorDescriptor],
+) -> Option<&'a ProcessorDescriptor> {
+    descriptors
+        .iter()
+        .filter(|descriptor| {
+            descriptor.name.org.as_str() == stage.org
+                && descriptor.name.package.as_str() == stage.package
+                && descriptor.name.r#type.as_str() == stage.type_name
+        })
+        .max_by_key(|descriptor| descriptor.name.version)
+}
+
+/// The first output of `from` that an input of `to` accepts, in declaration
+/// order on both sides.
+fn compatible_ports<'a>(
+    from: &'a ProcessorDescriptor,
+    to: &'a ProcessorDescriptor,
+) -> Option<(&'a PortDescriptor, &'a PortDescriptor)> {
+    from.outputs.iter().find_map(|output| {
+        to.inputs
+            .iter()
+            .find(|input| schemas_compatible(&output.schema, &input.schema))
+            .map(|input| (output, input))
+    })
+}
+
+/// Whether frames of `output` schema can flow into an `input` port. Versions
+/// are the link compiler's business; here the type is what matters.
+fn schemas_compatible(output: &PortSchemaSpec, input: &PortSchemaSpec) -> bool {
+    match (output, input) {
+        (PortSchemaSpec::Any, _) | (_, PortSchemaSpec::Any) => true,
+        (PortSchemaSpec::Specific(output), PortSchemaSpec::Specific(input)) => {
+            output.org == input.org
+                && output.package == input.package
+                && output.r#type == input.r#type
+        }
+        (PortSchemaSpec::Named(name), PortSchemaSpec::Specific(ident))
+        | (PortSchemaSpec::Specific(ident), PortSchemaSpec::Named(name)) => *name == ident.r#type,
+        (PortSchemaSpec::Named(output), PortSchemaSpec::Named(input)) => output == input,
+    }
+}
+
+fn render_recipe(
+    recipe: &PipelineRecipe,
+    stages: &[(&RecipeStage, &ProcessorDescriptor)],
+    links: &[RecipeLink<'_>],
+) -> String {
+    let mut text = format!(
+        "Build this pipeline on the running StreamLib runtime: {}\n\n\
+         1. Add each processor with the `add_processor` tool, passing exactly this \
+         `processor_type`:\n",
+        recipe.description
+    );
+    for (stage, descriptor) in stages {
+        let processor_type = json!(SchemaIdentOutput::from(&descriptor.name));
+        text.push_str(&format!(
+            "   - `{}`: {} — {}.",
+            stage.alias, descriptor.name, stage.role
+        ));
+        if !descriptor.description.is_empty() {
+            let description = descriptor.description.trim_end_matches('.');
+            text.push_str(&format!(" {description}."));
+        }
+        match &descriptor.config_schema {
+            Some(schema) => text.push_str(&format!(" Config follows the `{schema}` schema.")),
+            None => text.push_str(" It takes no config."),
+        }
+        text.push_str(&format!("\n     processor_type: {processor_type}\n"));
+    }
+
+    text.push_str(
+        "2. Wire adjacent processors with the `connect` tool, using the processor ids \
+         `add_processor` returned in place of these aliases:\n",
+    );
+    for link in links {
+        text.push_str(&format!(
+            "   - from_processor `{}`, from_port `{}` → to_processor `{}`, to_port `{}` ({})\n",
+            link.from, link.from_port.name, link.to, link.to_port.name, link.from_port.schema
+        ));
+    }
+
+    text.push_str(
+        "3. Read the `streamlib://graph` resource to check every processor is running and \
+         every link is wired; if something is not, read `streamlib://errors`.\n",
+    );
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use streamlib::sdk::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+
+    use super::*;
+
+    fn ident(org: &str, package: &str, type_name: &str) -> SchemaIdent {
+        SchemaIdent::new(
+            Org::new(org).unwrap(),
+            Package::new(package).unwrap(),
+            TypeName::new(type_name).unwrap(),
+            SemVer::new(1, 0, 0),
+        )
+    }
+
+    fn port(name: &str, schema: &str) -> PortDescriptor {
+        PortDescriptor::new(
+            name,
+            "",
+            PortSchemaSpec::Specific(ident("tatolab", "core", schema)),
+            true,
+        )
+    }
+
+    fn camera() -> ProcessorDescriptor {
+        ProcessorDescriptor::new(ident("tatolab", "camera", "Camera"), "Captures a camera")
+            .with_output(port("video", "VideoFrame"))
+    }
+
+    fn display() -> ProcessorDescriptor {
+        ProcessorDescriptor::new(ident("tatolab", "display", "Display"), "")
+            .with_config_schema("@tatolab/display/DisplayConfig@1.0.0")
+            .with_input(port("video", "VideoFrame"))
+    }
+
+    #[test]
+    fn a_recipe_resolves_types_and_ports_from_the_descriptors() {
+        let prompts = pipeline_prompts(&[camera(), display()]);
+        let names: Vec<_> = prompts.iter().map(|prompt| prompt.name).collect();
+        assert_eq!(names, ["camera_preview"]);
+
+        let text = &prompts[0].text;
+        assert!(text.contains("@tatolab/camera/Camera@1.0.0"), "{text}");
+        assert!(text.contains(r#""type":"Display""#), "{text}");
+        assert!(
+            text.contains("from_port `video` → to_processor `display`, to_port `video`"),
+            "{text}"
+        );
+        assert!(
+            text.contains("`@tatolab/display/DisplayConfig@1.0.0`"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn a_recipe_without_compatible_ports_is_not_offered() {
+        let audio_display = ProcessorDescriptor::new(ident("tatolab", "display", "Display"), "")
+            .with_input(port("audio", "AudioFrame"));
+        assert!(pipeline_prompts(&[camera(), audio_display]).is_empty());
+        assert!(pipeline_prompts(&[camera()]).is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Minimal MQTT 3.1.1 publisher for the telemetry uplink: CONNECT, QoS 1
+//! PUBLISH and DISCONNECT over plain TCP. No subscriptions, no session
+//! state — every acknowledged publish has already been removed from the
+//! uplink's spool, and an unacknowledged one is resent from there.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const MQTT_DEFAULT_PORT: u16 = 1883;
+/// Largest value the variable-length "remaining length" field encodes.
+const MQTT_MAX_REMAINING_LENGTH: usize = 268_435_455;
+const MQTT_KEEP_ALIVE_SECS: u16 = 60;
+
+const PACKET_CONNECT: u8 = 0x10;
+const PACKET_CONNACK: u8 = 0x20;
+/// PUBLISH with QoS 1, no DUP, no RETAIN.
+const PACKET_PUBLISH_QOS1: u8 = 0x32;
+const PACKET_PUBACK: u8 = 0x40;
+const PACKET_DISCONNECT: u8 = 0xE0;
+
+const CONNECT_FLAG_CLEAN_SESSION: u8 = 0x02;
+const CONNECT_FLAG_PASSWORD: u8 = 0x40;
+const CONNECT_FLAG_USERNAME: u8 = 0x80;
+
+/// Broker address and credentials parsed from an `mqtt://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MqttBroker {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl MqttBroker {
+    /// Parse `mqtt://[user[:pass]@]host[:port]`. Credentials are taken
+    /// verbatim (no percent-decoding); a trailing `/` is ignored.
+    pub fn parse(url: &str) -> std::result::Result<Self, String> {
+        let rest = url
+            .strip_prefix("mqtt://")
+            .ok_or_else(|| format!("`{url}` is not an mqtt:// URL"))?
+            .trim_end_matches('/');
+        let (credentials, authority) = match rest.rsplit_once('@') {
+            Some((credentials, authority)) => (Some(credentials), authority),
+            None => (None, rest),
+        };
+        let (username, password) = match credentials {
+            Some(credentials) => match credentials.split_once(':') {
+                Some((user, pass)) => (Some(user.to_string()), Some(pass.to_string())),
+                None => (Some(credentials.to_string()), None),
+            },
+            None => (None, None),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            // `[::1]` alone has a colon but no port.
+            Some((host, port)) if !port.ends_with(']') => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("invalid MQTT port `{port}`"))?,
+            ),
+            _ => (authority, MQTT_DEFAULT_PORT),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(format!("`{url}` has no broker host"));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            username,
+            password,
+        })
+    }
+}
+
+/// An open broker connection.
+pub(crate) struct MqttSession {
+    stream: TcpStream,
+    next_packet_id: u16,
+}
+
+impl MqttSession {
+    /// Connect and complete the CONNECT / CONNACK handshake. `timeout`
+    /// bounds the TCP connect and every subsequent read and write.
+    pub fn connect(broker: &MqttBroker, client_id: &str, timeout: Duration) -> io::Result<Self> {
+        let addr = (broker.host.as_str(), broker.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no address for MQTT broker `{}`", broker.host),
+                )
+            })?;
+        let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        stream.write_all(&connect_packet(
+            client_id,
+            broker.username.as_deref(),
+            broker.password.as_deref(),
+        )?)?;
+        let (header, body) = read_packet(&mut stream)?;
+        if header != PACKET_CONNACK || body.len() != 2 {
+            return Err(protocol_error(format!(
+                "expected CONNACK, got packet 0x{header:02x}"
+            )));
+        }
+        if body[1] != 0 {
+            return Err(protocol_error(format!(
+                "broker refused the connection (return code {})",
+                body[1]
+            )));
+        }
+        Ok(Self {
+            stream,
+            next_packet_id: 1,
+        })
+    }
+
+    /// Publish `payload` on `topic` at QoS 1 and wait for the PUBACK.
+    pub fn publish(&mut self, topic: &str, payload: &[u8]) -> io::Result<()> {
+        let packet_id = self.next_packet_id;
+        // Packet identifiers are non-zero.
+        self.next_packet_id = self.next_packet_id.checked_add(1).unwrap_or(1);
+        self.stream
+            .write_all(&publish_packet(topic, packet_id, payload)?)?;
+        let (header, body) = read_packet(&mut self.stream)?;
+        if header != PACKET_PUBACK || body.len() != 2 {
+            return Err(protocol_error(format!(
+                "expected PUBACK, got packet 0x{header:02x}"
+            )));
+        }
+        let acked = u16::from_be_bytes([body[0], body[1]]);
+        if acked != packet_id {
+            return Err(protocol_error(format!(
+                "PUBACK for packet {acked}, expected {packet_id}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Send DISCONNECT and close. Best effort — the broker drops the
+    /// session either way.
+    pub fn disconnect(mut self) {
+        let _ = self.stream.write_all(&[PACKET_DISCONNECT, 0]);
+    }
+}
+
+fn protocol_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Append the variable-length "remaining length" encoding of `len`.
+fn encode_remaining_length(mut len: usize, out: &mut Vec<u8>) -> io::Result<()> {
+    if len > MQTT_MAX_REMAINING_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("MQTT packet of {len} bytes exceeds the protocol maximum"),
+        ));
+    }
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Append a length-prefixed UTF-8 string.
+fn write_str(s: &str, out: &mut Vec<u8>) -> io::Result<()> {
+    let len = u16::try_from(s.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "MQTT string longer than 65535 bytes",
+        )
+    })?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// Prefix `body` with the fixed header.
+fn framed(header: u8, body: &[u8]) -> io::Result<Vec<u8>> {
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    packet.push(header);
+    encode_remaining_length(body.len(), &mut packet)?;
+    packet.extend_from_slice(body);
+    Ok(packet)
+}
+
+fn connect_packet(
+    client_id: &str,
+    username: Option<&str>,
+    password: Option<&str>,
+) -> io::Result<Vec<u8>> {
+    let mut flags = CONNECT_FLAG_CLEAN_SESSION;
+    if username.is_some() {
+        flags |= CONNECT_FLAG_USERNAME;
+        // 3.1.1 only allows a password alongside a username.
+        if password.is_some() {
+            flags |= CONNECT_FLAG_PASSWORD;
+        }
+    }
+    let mut body = Vec::new();
+    write_str("MQTT", &mut body)?;
+    body.push(4); // protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&MQTT_KEEP_ALIVE_SECS.to_be_bytes());
+    write_str(client_id, &mut body)?;
+    if let Some(username) = username {
+        write_str(username, &mut body)?;
+        if let Some(password) = password {
+            write_str(password, &mut body)?;
+        }
+    }
+    framed(PACKET_CONNECT, &body)
+}
+
+fn publish_packet(topic: &str, packet_id: u16, payload: &[u8]) -> io::Result<Vec<u8>> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 4);
+    write_str(topic, &mut body)?;
+    body.extend_from_slice(&packet_id.to_be_bytes());
+    body.extend_from_slice(payload);
+    framed(PACKET_PUBLISH_QOS1, &body)
+}
+
+/// Read one packet: its fixed-header byte and body.
+fn read_packet(stream: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut byte = [0u8; 1];
+    stream.read_exact(&mut byte)?;
+    let header = byte[0];
+    let mut len = 0usize;
+    let mut multiplier = 1usize;
+    loop {
+        stream.read_exact(&mut byte)?;
+        len += (byte[0] & 0x7F) as usize * multiplier;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        multiplier *= 128;
+        if multiplier > 128 * 128 * 128 {
+            return Err(protocol_error("malformed remaining length".into()));
+        }
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    Ok((header, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn remaining_length_matches_spec_examples() {
+        for (len, encoded) in [
+            (0, vec![0x00]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xFF, 0x7F]),
+            (16_384, vec![0x80, 0x80, 0x01]),
+            (MQTT_MAX_REMAINING_LENGTH, vec![0xFF, 0xFF, 0xFF, 0x7F]),
+        ] {
+            let mut out = Vec::new();
+            encode_remaining_length(len, &mut out).unwrap();
+            assert_eq!(out, encoded, "length {len}");
+        }
+        assert!(encode_remaining_length(MQTT_MAX_REMAINING_LENGTH + 1, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn broker_urls_parse_host_port_and_credentials() {
+        assert_eq!(
+            MqttBroker::parse("mqtt://broker.local").unwrap(),
+            MqttBroker {
+                host: "broker.local".into(),
+                port: MQTT_DEFAULT_PORT,
+                username: None,
+                password: None,
+            }
+        );
+        assert_eq!(
+            MqttBroker::parse("mqtt://edge:s3cr@t@10.0.0.2:8883/").unwrap(),
+            MqttBroker {
+                host: "10.0.0.2".into(),
+                port: 8883,
+                username: Some("edge".into()),
+                password: Some("s3cr@t".into()),
+            }
+        );
+        assert_eq!(MqttBroker::parse("mqtt://[::1]").unwrap().host, "::1");
+        assert_eq!(MqttBroker::parse("mqtt://[::1]:1884").unwrap().port, 1884);
+        assert!(MqttBroker::parse("https://broker.local").is_err());
+        assert!(MqttBroker::parse("mqtt://broker.local:http").is_err());
+        assert!(MqttBroker::parse("mqtt://").is_err());
+    }
+
+    #[test]
+    fn publishes_against_a_loopback_broker() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (header, connect) = read_packet(&mut stream).unwrap();
+            assert_eq!(header, PACKET_CONNECT);
+            assert_eq!(&connect[..7], b"\x00\x04MQTT\x04");
+            assert_eq!(
+                connect[7],
+                CONNECT_FLAG_CLEAN_SESSION | CONNECT_FLAG_USERNAME | CONNECT_FLAG_PASSWORD
+            );
+            stream.write_all(&[PACKET_CONNACK, 2, 0, 0]).unwrap();
+
+            let (header, publish) = read_packet(&mut stream).unwrap();
+            assert_eq!(header, PACKET_PUBLISH_QOS1);
+            assert_eq!(&publish[..9], b"\x00\x07fleet/1");
+            let packet_id = [publish[9], publish[10]];
+            assert_eq!(&publish[11..], b"\xA1\x61v\x01");
+            stream
+                .write_all(&[PACKET_PUBACK, 2, packet_id[0], packet_id[1]])
+                .unwrap();
+
+            let (header, _) = read_packet(&mut stream).unwrap();
+            assert_eq!(header, PACKET_DISCONNECT);
+        });
+
+        let broker_addr = MqttBroker::parse(&format!("mqtt://edge:pw@127.0.0.1:{port}")).unwrap();
+        let mut session = MqttSession::connect(&broker_addr, "R1", Duration::from_secs(5)).unwrap();
+        session.publish("fleet/1", b"\xA1\x61v\x01").unwrap();
+        session.disconnect();
+        broker.join().unwrap();
+    }
+
+    #[test]
+    fn refused_connection_is_an_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_packet(&mut stream).unwrap();
+            // Return code 5: not authorized.
+            stream.write_all(&[PACKET_CONNACK, 2, 0, 5]).unwrap();
+        });
+        let broker_addr = MqttBroker::parse(&format!("mqtt://127.0.0.1:{port}")).unwrap();
+        let error = MqttSession::connect(&broker_addr, "R1", Duration::from_secs(5))
+            .err()
+            .expect("CONNACK return code 5 must fail the connect");
+        assert!(error.to_string().contains("return code 5"), "{error}");
+        broker.join().unwrap();
+    }
+}
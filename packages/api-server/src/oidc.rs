@@ -0,0 +1,590 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! OIDC access-token validation for the api-server's bearer auth.
+//!
+//! A token is a compact JWS: `header.claims.signature`, each part base64url.
+//! It is accepted when it is signed RS256 or ES256 by a key in the issuer's
+//! JWKS, its `iss` is the configured issuer, its `aud` names the configured
+//! audience, and `exp` / `nbf` hold (with [`CLOCK_LEEWAY_SECS`] of skew).
+//! The scope it grants comes from its `scope` (space-separated) or `scp`
+//! claim, mapped onto [`AuthScope`] through the configured scope names.
+//!
+//! Keys are fetched once at setup. A token naming a `kid` the set lacks
+//! triggers a refetch — the issuer rotated — at most once per
+//! [`JWKS_REFRESH_MIN_INTERVAL`].
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use parking_lot::{Mutex, RwLock};
+use ring::signature::{
+    ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256, RsaPublicKeyComponents, UnparsedPublicKey,
+};
+use serde::Deserialize;
+use streamlib::sdk::error::{Error, Result};
+
+use crate::auth::AuthScope;
+
+/// Scope name granting [`AuthScope::Read`] when none is configured.
+pub(crate) const DEFAULT_OIDC_READ_SCOPE: &str = "streamlib:read";
+
+/// Scope name granting [`AuthScope::Write`] when none is configured.
+pub(crate) const DEFAULT_OIDC_WRITE_SCOPE: &str = "streamlib:write";
+
+/// Clock skew tolerated on `exp` / `nbf`.
+const CLOCK_LEEWAY_SECS: u64 = 60;
+
+/// Shortest gap between two JWKS refetches triggered by unknown `kid`s, so a
+/// stream of forged tokens can't turn the server into a JWKS flooder.
+const JWKS_REFRESH_MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Timeout on each discovery / JWKS request.
+const JWKS_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How the validator is configured.
+#[derive(Debug, Clone)]
+pub(crate) struct OidcSettings {
+    pub issuer: String,
+    pub audience: String,
+    /// Skips discovery when set.
+    pub jwks_url: Option<String>,
+    pub read_scope: String,
+    pub write_scope: String,
+}
+
+/// Why a token was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OidcRejection {
+    /// Not a well-formed JWT.
+    Malformed(String),
+    /// Signed by a key the JWKS doesn't hold (yet).
+    UnknownKey(Option<String>),
+    /// Signature, issuer, audience or validity window failed.
+    Invalid(String),
+}
+
+/// One verification key from a JWKS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VerificationKey {
+    Rsa {
+        n: Vec<u8>,
+        e: Vec<u8>,
+    },
+    /// Uncompressed SEC1 point, `0x04 || x || y`.
+    EcP256 {
+        point: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Jwk {
+    kid: Option<String>,
+    key: VerificationKey,
+}
+
+#[derive(Deserialize)]
+struct JwkDocument {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default, rename = "use")]
+    key_use: Option<String>,
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwksDocument {
+    keys: Vec<JwkDocument>,
+}
+
+#[derive(Deserialize)]
+struct DiscoveryDocument {
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+/// `aud` may be one string or an array of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// `scp` may be an array or a space-separated string, like `scope`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScopeClaim {
+    Joined(String),
+    List(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    iss: String,
+    aud: Audience,
+    exp: u64,
+    #[serde(default)]
+    nbf: Option<u64>,
+    #[serde(default)]
+    scope: Option<ScopeClaim>,
+    #[serde(default)]
+    scp: Option<ScopeClaim>,
+}
+
+/// Validates OIDC access tokens against one issuer.
+pub(crate) struct OidcValidator {
+    settings: OidcSettings,
+    jwks_url: String,
+    keys: RwLock<Vec<Jwk>>,
+    last_refresh: Mutex<Instant>,
+}
+
+impl OidcValidator {
+    /// Resolve the JWKS (through discovery unless `jwks_url` is set) and load
+    /// its keys. Blocking — call off the async runtime.
+    pub(crate) fn discover(settings: OidcSettings) -> Result<Self> {
+        let agent = fetch_agent();
+        let jwks_url = match &settings.jwks_url {
+            Some(url) => url.clone(),
+            None => {
+                let discovery_url = format!(
+                    "{}/.well-known/openid-configuration",
+                    settings.issuer.trim_end_matches('/')
+                );
+                let body = fetch(&agent, &discovery_url)?;
+                serde_json::from_str::<DiscoveryDocument>(&body)
+                    .map_err(|e| {
+                        Error::Configuration(format!(
+                            "ApiServer: OIDC discovery document at {discovery_url} is \
+                             malformed: {e}"
+                        ))
+                    })?
+                    .jwks_uri
+            }
+        };
+        let keys = parse_jwks(&fetch(&agent, &jwks_url)?)?;
+        tracing::info!(
+            "ApiServer accepting OIDC tokens from {} ({} signing keys)",
+            settings.issuer,
+            keys.len()
+        );
+        Ok(Self::with_keys(settings, jwks_url, keys))
+    }
+
+    fn with_keys(settings: OidcSettings, jwks_url: String, keys: Vec<Jwk>) -> Self {
+        Self {
+            settings,
+            jwks_url,
+            keys: RwLock::new(keys),
+            last_refresh: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Refetch the JWKS after an unknown `kid`, unless one was fetched within
+    /// [`JWKS_REFRESH_MIN_INTERVAL`]. Returns whether the keys were reloaded.
+    /// Blocking.
+    pub(crate) fn refresh_keys(&self) -> bool {
+        {
+            let mut last = self.last_refresh.lock();
+            if last.elapsed() < JWKS_REFRESH_MIN_INTERVAL {
+                return false;
+            }
+            *last = Instant::now();
+        }
+        let fetched = fetch(&fetch_agent(), &self.jwks_url).and_then(|body| parse_jwks(&body));
+        match fetched {
+            Ok(keys) => {
+                *self.keys.write() = keys;
+                true
+            }
+            Err(e) => {
+                tracing::warn!("ApiServer: OIDC JWKS refresh failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// The scope `token` grants, `None` when it is valid but carries neither
+    /// configured scope.
+    pub(crate) fn validate(
+        &self,
+        token: &str,
+    ) -> std::result::Result<Option<AuthScope>, OidcRejection> {
+        self.validate_at(token, unix_now())
+    }
+
+    fn validate_at(
+        &self,
+        token: &str,
+        now: u64,
+    ) -> std::result::Result<Option<AuthScope>, OidcRejection> {
+        let mut parts = token.split('.');
+        let (Some(header_b64), Some(claims_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(OidcRejection::Malformed("not a three-part JWT".into()));
+        };
+        let header: JwtHeader = decode_json(header_b64)?;
+        let signature = decode_part(signature_b64)?;
+        let signed = &token[..header_b64.len() + 1 + claims_b64.len()];
+
+        // `none` and HMAC algorithms never verify against a public JWKS.
+        if !matches!(header.alg.as_str(), "RS256" | "ES256") {
+            return Err(OidcRejection::Invalid(format!(
+                "unsupported signing algorithm {}",
+                header.alg
+            )));
+        }
+        {
+            let keys = self.keys.read();
+            let candidates: Vec<&Jwk> = keys
+                .iter()
+                .filter(|jwk| header.kid.is_none() || jwk.kid == header.kid)
+                .filter(|jwk| key_fits_alg(&jwk.key, &header.alg))
+                .collect();
+            if candidates.is_empty() {
+                return Err(OidcRejection::UnknownKey(header.kid));
+            }
+            if !candidates
+                .iter()
+                .any(|jwk| verify(&jwk.key, signed.as_bytes(), &signature))
+            {
+                return Err(OidcRejection::Invalid("signature does not verify".into()));
+            }
+        }
+
+        let claims: Claims = decode_json(claims_b64)?;
+        if claims.iss != self.settings.issuer {
+            return Err(OidcRejection::Invalid(format!(
+                "issuer {} is not {}",
+                claims.iss, self.settings.issuer
+            )));
+        }
+        let audience_ok = match &claims.aud {
+            Audience::One(aud) => *aud == self.settings.audience,
+            Audience::Many(auds) => auds.contains(&self.settings.audience),
+        };
+        if !audience_ok {
+            return Err(OidcRejection::Invalid(format!(
+                "token is not for audience {}",
+                self.settings.audience
+            )));
+        }
+        if now > claims.exp + CLOCK_LEEWAY_SECS {
+            return Err(OidcRejection::Invalid("token expired".into()));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf > now + CLOCK_LEEWAY_SECS) {
+            return Err(OidcRejection::Invalid("token not yet valid".into()));
+        }
+
+        let scopes: Vec<String> = [claims.scope, claims.scp]
+            .into_iter()
+            .flatten()
+            .flat_map(|claim| match claim {
+                ScopeClaim::Joined(joined) => {
+                    joined.split_whitespace().map(str::to_string).collect()
+                }
+                ScopeClaim::List(list) => list,
+            })
+            .collect();
+        let has = |name: &str| scopes.iter().any(|scope| scope == name);
+        Ok(if has(&self.settings.write_scope) {
+            Some(AuthScope::Write)
+        } else if has(&self.settings.read_scope) {
+            Some(AuthScope::Read)
+        } else {
+            None
+        })
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn fetch_agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(JWKS_FETCH_TIMEOUT)
+        .build()
+}
+
+fn fetch(agent: &ureq::Agent, url: &str) -> Result<String> {
+    agent
+        .get(url)
+        .call()
+        .map_err(|e| Error::Runtime(format!("ApiServer: OIDC fetch {url} failed: {e}")))?
+        .into_string()
+        .map_err(|e| Error::Runtime(format!("ApiServer: OIDC fetch {url} failed: {e}")))
+}
+
+fn decode_part(part: &str) -> std::result::Result<Vec<u8>, OidcRejection> {
+    URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|e| OidcRejection::Malformed(format!("bad base64url: {e}")))
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(
+    part: &str,
+) -> std::result::Result<T, OidcRejection> {
+    serde_json::from_slice(&decode_part(part)?)
+        .map_err(|e| OidcRejection::Malformed(format!("bad JSON: {e}")))
+}
+
+/// Signing keys of a JWKS; encryption keys and key types other than RSA and
+/// P-256 are skipped.
+fn parse_jwks(body: &str) -> Result<Vec<Jwk>> {
+    let document: JwksDocument = serde_json::from_str(body)
+        .map_err(|e| Error::Configuration(format!("ApiServer: OIDC JWKS is malformed: {e}")))?;
+    let b64 = |value: &Option<String>| {
+        value
+            .as_deref()
+            .and_then(|v| URL_SAFE_NO_PAD.decode(v).ok())
+    };
+    Ok(document
+        .keys
+        .into_iter()
+        .filter(|key| key.key_use.as_deref().is_none_or(|u| u == "sig"))
+        .filter_map(|key| {
+            let parsed = match key.kty.as_str() {
+                "RSA" => VerificationKey::Rsa {
+                    n: b64(&key.n)?,
+                    e: b64(&key.e)?,
+                },
+                "EC" if key.crv.as_deref() == Some("P-256") => {
+                    let (x, y) = (b64(&key.x)?, b64(&key.y)?);
+                    if x.len() != 32 || y.len() != 32 {
+                        return None;
+                    }
+                    let mut point = Vec::with_capacity(65);
+                    point.push(0x04);
+                    point.extend_from_slice(&x);
+                    point.extend_from_slice(&y);
+                    VerificationKey::EcP256 { point }
+                }
+                _ => return None,
+            };
+            Some(Jwk {
+                kid: key.kid,
+                key: parsed,
+            })
+        })
+        .collect())
+}
+
+fn key_fits_alg(key: &VerificationKey, alg: &str) -> bool {
+    matches!(
+        (key, alg),
+        (VerificationKey::Rsa { .. }, "RS256") | (VerificationKey::EcP256 { .. }, "ES256")
+    )
+}
+
+fn verify(key: &VerificationKey, message: &[u8], signature: &[u8]) -> bool {
+    match key {
+        VerificationKey::Rsa { n, e } => RsaPublicKeyComponents { n, e }
+            .verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+            .is_ok(),
+        VerificationKey::EcP256 { point } => {
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point)
+                .verify(message, signature)
+                .is_ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+
+    const ISSUER: &str = "https://id.example.com";
+    const AUDIENCE: &str = "streamlib-node";
+    const NOW: u64 = 1_800_000_000;
+
+    struct Signer {
+        key_pair: EcdsaKeyPair,
+        kid: &'static str,
+    }
+
+    impl Signer {
+        fn new(kid: &'static str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+            let key_pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            Self { key_pair, kid }
+        }
+
+        fn jwk(&self) -> serde_json::Value {
+            let point = self.key_pair.public_key().as_ref();
+            serde_json::json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "sig",
+                "kid": self.kid,
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+            })
+        }
+
+        fn sign(&self, claims: serde_json::Value) -> String {
+            let header = serde_json::json!({ "alg": "ES256", "typ": "JWT", "kid": self.kid });
+            let signed = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let signature = self
+                .key_pair
+                .sign(&SystemRandom::new(), signed.as_bytes())
+                .unwrap();
+            format!("{signed}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    fn validator(signers: &[&Signer]) -> OidcValidator {
+        let jwks =
+            serde_json::json!({ "keys": signers.iter().map(|s| s.jwk()).collect::<Vec<_>>() });
+        OidcValidator::with_keys(
+            OidcSettings {
+                issuer: ISSUER.into(),
+                audience: AUDIENCE.into(),
+                jwks_url: None,
+                read_scope: DEFAULT_OIDC_READ_SCOPE.into(),
+                write_scope: DEFAULT_OIDC_WRITE_SCOPE.into(),
+            },
+            "https://id.example.com/jwks".into(),
+            parse_jwks(&jwks.to_string()).unwrap(),
+        )
+    }
+
+    fn claims(scope: &str) -> serde_json::Value {
+        serde_json::json!({
+            "iss": ISSUER,
+            "aud": [AUDIENCE, "other"],
+            "exp": NOW + 300,
+            "nbf": NOW - 10,
+            "scope": scope,
+        })
+    }
+
+    #[test]
+    fn a_signed_token_grants_its_mapped_scope() {
+        let signer = Signer::new("k1");
+        let validator = validator(&[&signer]);
+
+        let write = signer.sign(claims("openid streamlib:write"));
+        assert_eq!(
+            validator.validate_at(&write, NOW),
+            Ok(Some(AuthScope::Write))
+        );
+        let read = signer.sign(claims("streamlib:read"));
+        assert_eq!(validator.validate_at(&read, NOW), Ok(Some(AuthScope::Read)));
+        let neither = signer.sign(claims("openid profile"));
+        assert_eq!(validator.validate_at(&neither, NOW), Ok(None));
+
+        let mut scp = claims("");
+        scp["scp"] = serde_json::json!(["streamlib:read"]);
+        assert_eq!(
+            validator.validate_at(&signer.sign(scp), NOW),
+            Ok(Some(AuthScope::Read))
+        );
+    }
+
+    #[test]
+    fn forged_foreign_and_stale_tokens_are_rejected() {
+        let signer = Signer::new("k1");
+        let validator = validator(&[&signer]);
+
+        // Claims swapped after signing.
+        let token = signer.sign(claims("streamlib:read"));
+        let header = token.split('.').next().unwrap();
+        let signature = token.rsplit('.').next().unwrap();
+        let forged = format!(
+            "{header}.{}.{signature}",
+            URL_SAFE_NO_PAD.encode(claims("streamlib:write").to_string())
+        );
+        assert!(matches!(
+            validator.validate_at(&forged, NOW),
+            Err(OidcRejection::Invalid(_))
+        ));
+
+        // A key the JWKS doesn't hold.
+        let stranger = Signer::new("k2");
+        assert_eq!(
+            validator.validate_at(&stranger.sign(claims("streamlib:write")), NOW),
+            Err(OidcRejection::UnknownKey(Some("k2".into())))
+        );
+
+        let mut wrong_issuer = claims("streamlib:write");
+        wrong_issuer["iss"] = serde_json::json!("https://evil.example.com");
+        let mut wrong_audience = claims("streamlib:write");
+        wrong_audience["aud"] = serde_json::json!("someone-else");
+        for claims in [wrong_issuer, wrong_audience] {
+            assert!(matches!(
+                validator.validate_at(&signer.sign(claims), NOW),
+                Err(OidcRejection::Invalid(_))
+            ));
+        }
+
+        let token = signer.sign(claims("streamlib:write"));
+        assert!(
+            validator
+                .validate_at(&token, NOW + 300 + CLOCK_LEEWAY_SECS)
+                .is_ok()
+        );
+        assert!(
+            validator
+                .validate_at(&token, NOW + 301 + CLOCK_LEEWAY_SECS)
+                .is_err()
+        );
+        assert!(
+            validator
+                .validate_at(&token, NOW - 11 - CLOCK_LEEWAY_SECS)
+                .is_err()
+        );
+
+        assert!(matches!(
+            validator.validate_at("not-a-jwt", NOW),
+            Err(OidcRejection::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn jwks_parsing_keeps_only_usable_signing_keys() {
+        let jwks = serde_json::json!({ "keys": [
+            { "kty": "RSA", "kid": "rsa", "n": "AQAB", "e": "AQAB" },
+            { "kty": "RSA", "kid": "enc", "use": "enc", "n": "AQAB", "e": "AQAB" },
+            { "kty": "EC", "kid": "p384", "crv": "P-384", "x": "AA", "y": "AA" },
+            { "kty": "oct", "kid": "hmac", "k": "c2VjcmV0" },
+        ]});
+        let keys = parse_jwks(&jwks.to_string()).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].kid.as_deref(), Some("rsa"));
+        assert!(key_fits_alg(&keys[0].key, "RS256"));
+        assert!(!key_fits_alg(&keys[0].key, "ES256"));
+    }
+}
@@ -13,9 +13,12 @@
 
 use std::sync::Arc;
 
-use streamlib::sdk::descriptors::{ModuleIdent, SchemaIdent, SemVer, SemVerRange, TypeName};
+use streamlib::sdk::descriptors::{
+    ModuleIdent, Org, Package, SchemaIdent, SemVer, SemVerRange, TypeName,
+};
 use streamlib::sdk::error::Error;
 use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef};
+use streamlib::sdk::json_schema::SchemaIdentOutput;
 use streamlib::sdk::processors::ProcessorSpec;
 use streamlib::sdk::runtime::{
     RegisterProcessorReceipt, ReplaceProcessorFromSource, RuntimeOperations,
@@ -220,6 +223,25 @@ fn project_receipt_ports(
         .collect()
 }
 
+/// Convert a structured [`SchemaIdentOutput`] into a [`SchemaIdent`] through
+/// the typed segment validators (Org::new / Package::new / TypeName::new /
+/// SemVer::new). This is typed conversion, not parsing — there is no
+/// `SchemaIdent::parse`. `None` when a segment fails validation.
+pub(crate) fn processor_ident(processor_type: SchemaIdentOutput) -> Option<SchemaIdent> {
+    let SchemaIdentOutput {
+        org,
+        package,
+        type_name,
+        version,
+    } = processor_type;
+    Some(SchemaIdent::new(
+        Org::new(org).ok()?,
+        Package::new(package).ok()?,
+        TypeName::new(type_name).ok()?,
+        SemVer::new(version.major, version.minor, version.patch),
+    ))
+}
+
 /// The concrete [`SemVer`] a session-module range pins. Session registrations
 /// mint an `Exact` range, so the other range shapes fall back to their lower
 /// bound and only a wildcard `Any` (never minted for a session) yields `None`.
@@ -0,0 +1,300 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Minimal OSC 1.0 codec for the `OscServer` and `OscSender` processors:
+//! messages with `i f s b h d T F N` arguments, and bundles, whose time
+//! tags are ignored — every message is handled on arrival.
+
+use std::io;
+
+const BUNDLE_TAG: &[u8] = b"#bundle\0";
+/// `#bundle\0` plus the 8-byte time tag.
+const BUNDLE_HEADER_LEN: usize = 16;
+/// Time tag meaning "immediately".
+const TIME_TAG_IMMEDIATE: u64 = 1;
+
+/// One OSC argument.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum OscArg {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Blob(Vec<u8>),
+    Long(i64),
+    Double(f64),
+    Bool(bool),
+    Nil,
+}
+
+impl OscArg {
+    fn type_tag(&self) -> u8 {
+        match self {
+            Self::Int(_) => b'i',
+            Self::Float(_) => b'f',
+            Self::String(_) => b's',
+            Self::Blob(_) => b'b',
+            Self::Long(_) => b'h',
+            Self::Double(_) => b'd',
+            Self::Bool(true) => b'T',
+            Self::Bool(false) => b'F',
+            Self::Nil => b'N',
+        }
+    }
+
+    /// Numeric value of a number or boolean argument.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(f64::from(*value)),
+            Self::Float(value) => Some(f64::from(*value)),
+            Self::Long(value) => Some(*value as f64),
+            Self::Double(value) => Some(*value),
+            Self::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Self::String(_) | Self::Blob(_) | Self::Nil => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+}
+
+fn protocol_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Append `bytes` zero-padded to a multiple of four.
+fn write_padded(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(bytes);
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+/// Append an OSC string: the bytes, a NUL, then padding.
+fn write_string(s: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(s.as_bytes());
+    out.push(0);
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+/// Encode `message` as one OSC packet.
+pub(crate) fn encode_osc_message(message: &OscMessage) -> Vec<u8> {
+    let mut out = Vec::with_capacity(message.address.len() + 8 + message.args.len() * 8);
+    write_string(&message.address, &mut out);
+    let mut tags = String::with_capacity(message.args.len() + 1);
+    tags.push(',');
+    tags.extend(message.args.iter().map(|arg| arg.type_tag() as char));
+    write_string(&tags, &mut out);
+    for arg in &message.args {
+        match arg {
+            OscArg::Int(value) => out.extend_from_slice(&value.to_be_bytes()),
+            OscArg::Float(value) => out.extend_from_slice(&value.to_be_bytes()),
+            OscArg::String(value) => write_string(value, &mut out),
+            OscArg::Blob(bytes) => {
+                out.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                write_padded(bytes, &mut out);
+            }
+            OscArg::Long(value) => out.extend_from_slice(&value.to_be_bytes()),
+            OscArg::Double(value) => out.extend_from_slice(&value.to_be_bytes()),
+            OscArg::Bool(_) | OscArg::Nil => {}
+        }
+    }
+    out
+}
+
+/// Encode `messages` as one bundle to be handled immediately.
+pub(crate) fn encode_osc_bundle(messages: &[OscMessage]) -> Vec<u8> {
+    let mut out = Vec::from(BUNDLE_TAG);
+    out.extend_from_slice(&TIME_TAG_IMMEDIATE.to_be_bytes());
+    for message in messages {
+        let element = encode_osc_message(message);
+        out.extend_from_slice(&(element.len() as i32).to_be_bytes());
+        out.extend_from_slice(&element);
+    }
+    out
+}
+
+/// Decode a packet — a message, or a bundle flattened into its messages
+/// in order.
+pub(crate) fn decode_osc_packet(packet: &[u8]) -> io::Result<Vec<OscMessage>> {
+    let mut messages = Vec::new();
+    decode_into(packet, &mut messages)?;
+    Ok(messages)
+}
+
+fn decode_into(packet: &[u8], messages: &mut Vec<OscMessage>) -> io::Result<()> {
+    if !packet.starts_with(BUNDLE_TAG) {
+        messages.push(decode_message(packet)?);
+        return Ok(());
+    }
+    let mut reader = Reader {
+        bytes: packet,
+        pos: BUNDLE_HEADER_LEN.min(packet.len()),
+    };
+    while reader.pos < packet.len() {
+        let len = usize::try_from(reader.i32()?)
+            .map_err(|_| protocol_error("negative bundle element size".into()))?;
+        decode_into(reader.take(len)?, messages)?;
+    }
+    Ok(())
+}
+
+fn decode_message(packet: &[u8]) -> io::Result<OscMessage> {
+    let mut reader = Reader {
+        bytes: packet,
+        pos: 0,
+    };
+    let address = reader.string()?;
+    if !address.starts_with('/') {
+        return Err(protocol_error(format!(
+            "OSC address `{address}` does not start with `/`"
+        )));
+    }
+    // A message may omit its type tags entirely, meaning no arguments.
+    if reader.pos == packet.len() {
+        return Ok(OscMessage::new(address, Vec::new()));
+    }
+    let tags = reader.string()?;
+    let tags = tags
+        .strip_prefix(',')
+        .ok_or_else(|| protocol_error(format!("malformed type tags `{tags}`")))?;
+    let mut args = Vec::with_capacity(tags.len());
+    for tag in tags.bytes() {
+        args.push(match tag {
+            b'i' => OscArg::Int(reader.i32()?),
+            b'f' => OscArg::Float(f32::from_be_bytes(reader.array()?)),
+            b's' | b'S' => OscArg::String(reader.string()?),
+            b'b' => {
+                let len = usize::try_from(reader.i32()?)
+                    .map_err(|_| protocol_error("negative blob size".into()))?;
+                let blob = reader.take(len)?.to_vec();
+                reader.pos = reader.pos.next_multiple_of(4);
+                OscArg::Blob(blob)
+            }
+            b'h' => OscArg::Long(i64::from_be_bytes(reader.array()?)),
+            b'd' => OscArg::Double(f64::from_be_bytes(reader.array()?)),
+            b'T' => OscArg::Bool(true),
+            b'F' => OscArg::Bool(false),
+            b'N' | b'I' => OscArg::Nil,
+            other => {
+                return Err(protocol_error(format!(
+                    "unsupported OSC type tag `{}`",
+                    other as char
+                )));
+            }
+        });
+    }
+    Ok(OscMessage::new(address, args))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| protocol_error("truncated OSC packet".into()))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn i32(&mut self) -> io::Result<i32> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let rest = &self.bytes[self.pos.min(self.bytes.len())..];
+        let len = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| protocol_error("unterminated OSC string".into()))?;
+        let s = std::str::from_utf8(&rest[..len])
+            .map_err(|_| protocol_error("OSC string is not UTF-8".into()))?
+            .to_string();
+        self.take((len + 1).next_multiple_of(4))?;
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_encoding_matches_the_spec_example() {
+        // OSC 1.0 spec: "/oscillator/4/frequency" with the float 440.0.
+        let encoded = encode_osc_message(&OscMessage::new(
+            "/oscillator/4/frequency",
+            vec![OscArg::Float(440.0)],
+        ));
+        assert_eq!(
+            encoded,
+            b"/oscillator/4/frequency\0,f\0\0\x43\xdc\x00\x00".to_vec()
+        );
+    }
+
+    #[test]
+    fn every_argument_type_round_trips() {
+        let message = OscMessage::new(
+            "/streamlib/test",
+            vec![
+                OscArg::Int(-7),
+                OscArg::Float(0.5),
+                OscArg::String("camera".into()),
+                OscArg::Blob(vec![1, 2, 3, 4, 5]),
+                OscArg::Long(1 << 40),
+                OscArg::Double(0.25),
+                OscArg::Bool(true),
+                OscArg::Bool(false),
+                OscArg::Nil,
+            ],
+        );
+        let encoded = encode_osc_message(&message);
+        assert_eq!(encoded.len() % 4, 0);
+        assert_eq!(decode_osc_packet(&encoded).unwrap(), vec![message]);
+    }
+
+    #[test]
+    fn bundles_flatten_into_their_messages_in_order() {
+        let first = OscMessage::new("/a", vec![OscArg::Int(1)]);
+        let second = OscMessage::new("/b", vec![OscArg::String("x".into())]);
+        let inner = encode_osc_bundle(std::slice::from_ref(&second));
+        let mut outer = encode_osc_bundle(std::slice::from_ref(&first));
+        outer.extend_from_slice(&(inner.len() as i32).to_be_bytes());
+        outer.extend_from_slice(&inner);
+        assert_eq!(decode_osc_packet(&outer).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn malformed_packets_are_errors() {
+        assert!(decode_osc_packet(b"no-slash\0\0\0\0").is_err());
+        assert!(decode_osc_packet(b"/a\0\0,f\0\0\x43").is_err());
+        assert!(decode_osc_packet(b"/a\0\0,q\0\0").is_err());
+        // A message without type tags has no arguments.
+        assert_eq!(
+            decode_osc_packet(b"/go\0").unwrap(),
+            vec![OscMessage::new("/go", Vec::new())]
+        );
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `OscSender` processor — runtime telemetry out as Open Sound
+//! Control, so a lighting desk or show controller can follow the pipeline.
+//!
+//! Every `interval_ms` a sender thread samples the graph the way
+//! `TelemetryUplink` does and sends one datagram per processor and one
+//! bundle per link, so a link's counters arrive together:
+//!
+//! ```text
+//! <prefix>/processor/<id>/state             ,s  "Running"
+//! <prefix>/link/<id>/frames_delivered       ,h  3600
+//! <prefix>/link/<id>/frames_dropped         ,h  12
+//! <prefix>/link/<id>/drop_rate              ,f  0.25
+//! <prefix>/link/<id>/congested              ,T / ,F
+//! ```
+//!
+//! Selected runtime events go out as they happen, from a PUBSUB listener,
+//! on `<prefix>/event/<name>` with the event JSON as a string argument.
+
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::json_schema::GraphResponse;
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::osc::{OscArg, OscMessage, encode_osc_bundle, encode_osc_message};
+use crate::telemetry_uplink::{LinkSample, ProcessorSample, sample_graph};
+use crate::webhook_notifier::webhook_event_name;
+
+/// Event-name selector matching every event.
+const OSC_SELECT_ALL_EVENTS: &str = "*";
+
+const DEFAULT_ADDRESS_PREFIX: &str = "/streamlib";
+const DEFAULT_INTERVAL_MS: u32 = 1000;
+
+/// OSC packets for one graph sample: each processor's state, then each
+/// link's counters as a group sent in one bundle.
+fn sample_packets(
+    prefix: &str,
+    processors: &[ProcessorSample],
+    links: &[LinkSample],
+) -> Vec<Vec<OscMessage>> {
+    let mut packets = Vec::with_capacity(processors.len() + links.len());
+    for processor in processors {
+        packets.push(vec![OscMessage::new(
+            format!("{prefix}/processor/{}/state", processor.id),
+            vec![OscArg::String(processor.state.clone())],
+        )]);
+    }
+    for link in links {
+        let address = |leaf: &str| format!("{prefix}/link/{}/{leaf}", link.id);
+        packets.push(vec![
+            OscMessage::new(
+                address("frames_delivered"),
+                vec![OscArg::Long(link.frames_delivered as i64)],
+            ),
+            OscMessage::new(
+                address("frames_dropped"),
+                vec![OscArg::Long(link.frames_dropped as i64)],
+            ),
+            OscMessage::new(
+                address("drop_rate"),
+                vec![OscArg::Float(link.window_drop_rate as f32)],
+            ),
+            OscMessage::new(address("congested"), vec![OscArg::Bool(link.congested)]),
+        ]);
+    }
+    packets
+}
+
+/// Send `messages` as one datagram — a bare message when there is one, a
+/// bundle otherwise. A failed send is only logged; OSC over UDP carries no
+/// delivery guarantee anyway.
+fn send_packet(socket: &UdpSocket, messages: &[OscMessage]) {
+    let packet = match messages {
+        [message] => encode_osc_message(message),
+        messages => encode_osc_bundle(messages),
+    };
+    if let Err(e) = socket.send(&packet) {
+        tracing::debug!(
+            address = messages.first().map(|message| message.address.as_str()),
+            error = %e,
+            "OscSender: send failed"
+        );
+    }
+}
+
+/// PUBSUB listener that sends selected events as they happen.
+struct OscEventSender {
+    socket: Arc<UdpSocket>,
+    prefix: String,
+    selected_event_names: Vec<String>,
+}
+
+impl OscEventSender {
+    fn selects(&self, event_name: &str) -> bool {
+        self.selected_event_names
+            .iter()
+            .any(|name| name == OSC_SELECT_ALL_EVENTS || name == event_name)
+    }
+
+    fn event_message(&self, name: &str, event: &Event) -> Result<OscMessage> {
+        let event = serde_json::to_string(event)
+            .map_err(|e| Error::Runtime(format!("OscSender: serialize event: {e}")))?;
+        Ok(OscMessage::new(
+            format!("{}/event/{name}", self.prefix),
+            vec![OscArg::String(event)],
+        ))
+    }
+}
+
+impl EventListener for OscEventSender {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        let name = webhook_event_name(event);
+        if self.selects(&name) {
+            send_packet(&self.socket, &[self.event_message(&name, event)?]);
+        }
+        Ok(())
+    }
+}
+
+/// State owned by the sender thread.
+struct OscSenderWorker {
+    runtime: Arc<dyn RuntimeOperations>,
+    socket: Arc<UdpSocket>,
+    prefix: String,
+    interval: Duration,
+}
+
+impl OscSenderWorker {
+    /// Sample every interval until the stop sender is dropped.
+    fn run(self, stop: mpsc::Receiver<()>) {
+        while matches!(
+            stop.recv_timeout(self.interval),
+            Err(mpsc::RecvTimeoutError::Timeout)
+        ) {
+            match self.runtime.to_json().and_then(|value| {
+                serde_json::from_value::<GraphResponse>(value)
+                    .map_err(|e| Error::Runtime(format!("parse graph: {e}")))
+            }) {
+                Ok(graph) => {
+                    let (processors, links) = sample_graph(&graph);
+                    for packet in sample_packets(&self.prefix, &processors, &links) {
+                        send_packet(&self.socket, &packet);
+                    }
+                }
+                Err(e) => tracing::warn!(error = %e, "OscSender: graph sample failed"),
+            }
+        }
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/OscSender",
+    description = "Sends processor states, link frame counters and selected runtime events as Open Sound Control over UDP, for lighting and show-control systems",
+    execution = manual,
+    config = crate::_generated_::OscSenderConfig,
+)]
+pub struct OscSenderProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    /// Held so the PUBSUB subscription (a weak reference) stays live.
+    event_sender: Option<Arc<Mutex<dyn EventListener>>>,
+    /// Dropped to stop the sender thread.
+    stop_tx: Option<mpsc::Sender<()>>,
+    sender_thread: Option<JoinHandle<()>>,
+}
+
+impl ManualProcessor for OscSenderProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.interval_ms == Some(0) {
+            return Err(Error::Config(
+                "OscSender: `interval_ms` must be positive".into(),
+            ));
+        }
+        if let Some(prefix) = &self.config.address_prefix
+            && !prefix.is_empty()
+            && !prefix.starts_with('/')
+        {
+            return Err(Error::Config(format!(
+                "OscSender: `address_prefix` `{prefix}` must start with `/`"
+            )));
+        }
+        self.runtime = Some(ctx.runtime());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| Error::Runtime("OscSender: started before setup".into()))?;
+        let target = &self.config.target;
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .and_then(|socket| socket.connect(target).map(|()| socket))
+            .map_err(|e| Error::Config(format!("OscSender: target `{target}`: {e}")))?;
+        let socket = Arc::new(socket);
+        let prefix = self
+            .config
+            .address_prefix
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ADDRESS_PREFIX.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let worker = OscSenderWorker {
+            runtime,
+            socket: Arc::clone(&socket),
+            prefix: prefix.clone(),
+            interval: Duration::from_millis(
+                self.config.interval_ms.unwrap_or(DEFAULT_INTERVAL_MS) as u64
+            ),
+        };
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let sender_thread = std::thread::Builder::new()
+            .name("osc-sender".into())
+            .spawn(move || worker.run(stop_rx))
+            .map_err(|e| Error::Runtime(format!("OscSender: spawn sender thread: {e}")))?;
+
+        let events = self.config.events.clone().unwrap_or_default();
+        if !events.is_empty() {
+            let event_sender: Arc<Mutex<dyn EventListener>> =
+                Arc::new(Mutex::new(OscEventSender {
+                    socket,
+                    prefix: prefix.clone(),
+                    selected_event_names: events.clone(),
+                }));
+            PUBSUB.subscribe(topics::ALL, Arc::clone(&event_sender));
+            self.event_sender = Some(event_sender);
+        }
+        self.stop_tx = Some(stop_tx);
+        self.sender_thread = Some(sender_thread);
+
+        tracing::info!(%target, %prefix, ?events, "OscSender sending telemetry");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.event_sender.take();
+        self.stop_tx.take();
+        if let Some(thread) = self.sender_thread.take()
+            && thread.join().is_err()
+        {
+            tracing::warn!("OscSender: sender thread panicked");
+        }
+        tracing::info!("OscSender stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osc::decode_osc_packet;
+    use streamlib::sdk::pubsub::RuntimeEvent;
+
+    #[test]
+    fn samples_become_one_message_per_value() {
+        let processors = vec![ProcessorSample {
+            id: "camera".into(),
+            processor_type: "@tatolab/core/Camera".into(),
+            state: "Running".into(),
+            metrics: None,
+        }];
+        let links = vec![LinkSample {
+            id: "L1".into(),
+            source: "camera.video_out".into(),
+            target: "encoder.video_in".into(),
+            frames_delivered: 3_600,
+            frames_dropped: 12,
+            window_drop_rate: 0.25,
+            congested: true,
+        }];
+        assert_eq!(
+            sample_packets("/show", &processors, &links),
+            vec![
+                vec![OscMessage::new(
+                    "/show/processor/camera/state",
+                    vec![OscArg::String("Running".into())]
+                )],
+                vec![
+                    OscMessage::new("/show/link/L1/frames_delivered", vec![OscArg::Long(3_600)]),
+                    OscMessage::new("/show/link/L1/frames_dropped", vec![OscArg::Long(12)]),
+                    OscMessage::new("/show/link/L1/drop_rate", vec![OscArg::Float(0.25)]),
+                    OscMessage::new("/show/link/L1/congested", vec![OscArg::Bool(true)]),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn selected_events_are_sent_as_they_happen() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.connect(receiver.local_addr().unwrap()).unwrap();
+        let mut sender = OscEventSender {
+            socket: Arc::new(socket),
+            prefix: "/streamlib".into(),
+            selected_event_names: vec!["RuntimeStarted".into()],
+        };
+
+        sender
+            .on_event(&Event::RuntimeGlobal(RuntimeEvent::RuntimeStopped))
+            .unwrap();
+        sender
+            .on_event(&Event::RuntimeGlobal(RuntimeEvent::RuntimeStarted))
+            .unwrap();
+
+        let mut buf = [0u8; 1024];
+        let len = receiver.recv(&mut buf).unwrap();
+        let messages = decode_osc_packet(&buf[..len]).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].address, "/streamlib/event/RuntimeStarted");
+        assert!(matches!(messages[0].args.as_slice(), [OscArg::String(_)]));
+    }
+}
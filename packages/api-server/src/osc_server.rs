@@ -0,0 +1,624 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `OscServer` processor — Open Sound Control in, for lighting desks,
+//! show controllers and control surfaces such as TouchOSC.
+//!
+//! A server thread receives OSC over UDP and runs every mapping whose
+//! address matches. `SetParameter` mappings write the first argument into
+//! a processor config at a JSON pointer; a fader sweep sends hundreds of
+//! messages a second, so values are coalesced per parameter and written
+//! every [`OSC_FLUSH_INTERVAL`], the way the runtime's MIDI mappings are.
+//! The other mappings are triggers — start / stop a processor, connect or
+//! disconnect a link — applied through [`crate::controller_command`] and
+//! reported on [`OSC_ACTION_TOPIC`].
+
+use std::io;
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, PUBSUB};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::_generated_::tatolab__api_server::osc_server_config::{Action, Mapping};
+use crate::controller_command::{ControllerAction, apply_controller_actions, graph};
+use crate::osc::{OscArg, OscMessage, decode_osc_packet};
+
+/// Custom-event topic each triggered action's outcome is published on.
+pub const OSC_ACTION_TOPIC: &str = "osc:action";
+
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:9000";
+
+/// How often coalesced `SetParameter` values are written into configs;
+/// also the socket read timeout, so the stop channel is polled as often.
+const OSC_FLUSH_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Largest UDP payload.
+const MAX_DATAGRAM_LEN: usize = 65_507;
+
+/// A validated mapping.
+#[derive(Debug, Clone)]
+struct OscMapping {
+    address: String,
+    action: OscMappingAction,
+}
+
+#[derive(Debug, Clone)]
+enum OscMappingAction {
+    SetParameter {
+        processor_id: String,
+        parameter: String,
+        /// `(min, max)` a normalized 0..1 argument is scaled onto.
+        range: Option<(f64, f64)>,
+    },
+    Trigger(ControllerAction),
+}
+
+/// Check each config mapping has what its action needs.
+fn compile_mappings(mappings: &[Mapping]) -> Result<Vec<OscMapping>> {
+    mappings
+        .iter()
+        .map(|mapping| {
+            let field = |value: &Option<String>, name: &str| {
+                value.clone().ok_or_else(|| {
+                    Error::Config(format!(
+                        "OscServer: mapping `{}` ({:?}) needs `{name}`",
+                        mapping.address, mapping.action
+                    ))
+                })
+            };
+            if !mapping.address.starts_with('/') {
+                return Err(Error::Config(format!(
+                    "OscServer: mapping address `{}` must start with `/`",
+                    mapping.address
+                )));
+            }
+            let action = match mapping.action {
+                Action::SetParameter => {
+                    let parameter = field(&mapping.parameter, "parameter")?;
+                    if !parameter.starts_with('/') {
+                        return Err(Error::Config(format!(
+                            "OscServer: mapping `{}` parameter `{parameter}` must be a JSON pointer such as `/gain_db`",
+                            mapping.address
+                        )));
+                    }
+                    let range = match (mapping.min, mapping.max) {
+                        (None, None) => None,
+                        (min, Some(max))
+                            if min.unwrap_or_default().is_finite() && max.is_finite() =>
+                        {
+                            Some((min.unwrap_or_default(), max))
+                        }
+                        _ => {
+                            return Err(Error::Config(format!(
+                                "OscServer: mapping `{}` needs a finite `max` (and `min`, default 0) to scale onto",
+                                mapping.address
+                            )));
+                        }
+                    };
+                    OscMappingAction::SetParameter {
+                        processor_id: field(&mapping.processor_id, "processor_id")?,
+                        parameter,
+                        range,
+                    }
+                }
+                Action::StartProcessor => {
+                    OscMappingAction::Trigger(ControllerAction::StartProcessor {
+                        processor_id: field(&mapping.processor_id, "processor_id")?,
+                    })
+                }
+                Action::StopProcessor => {
+                    OscMappingAction::Trigger(ControllerAction::StopProcessor {
+                        processor_id: field(&mapping.processor_id, "processor_id")?,
+                    })
+                }
+                Action::Connect => OscMappingAction::Trigger(ControllerAction::Connect {
+                    from_processor: field(&mapping.from_processor, "from_processor")?,
+                    from_port: field(&mapping.from_port, "from_port")?,
+                    to_processor: field(&mapping.to_processor, "to_processor")?,
+                    to_port: field(&mapping.to_port, "to_port")?,
+                }),
+                Action::Disconnect => OscMappingAction::Trigger(ControllerAction::Disconnect {
+                    link_id: field(&mapping.link_id, "link_id")?,
+                }),
+            };
+            Ok(OscMapping {
+                address: mapping.address.clone(),
+                action,
+            })
+        })
+        .collect()
+}
+
+/// Whether a trigger fires: no argument, or a first argument that is true
+/// or non-zero. A button sends 1 on press and 0 on release.
+fn trigger_fires(args: &[OscArg]) -> bool {
+    match args.first() {
+        None | Some(OscArg::Nil) => true,
+        Some(arg) => arg.as_f64().is_none_or(|value| value != 0.0),
+    }
+}
+
+/// The value a `SetParameter` argument writes: a number scaled onto
+/// `range` when set, a string or boolean as sent.
+fn parameter_value(arg: &OscArg, range: Option<(f64, f64)>) -> Option<Value> {
+    if let OscArg::String(s) = arg {
+        return Some(Value::String(s.clone()));
+    }
+    if let OscArg::Bool(b) = arg
+        && range.is_none()
+    {
+        return Some(Value::Bool(*b));
+    }
+    let value = arg.as_f64()?;
+    let value = match range {
+        Some((min, max)) => min + (max - min) * value.clamp(0.0, 1.0),
+        None => value,
+    };
+    serde_json::Number::from_f64(value).map(Value::Number)
+}
+
+/// Write `value` into `config` at JSON pointer `parameter`, typed after
+/// what is already there: integers stay integers (rounded), booleans
+/// switch at 0.5. Missing objects along the way are created.
+fn write_parameter(config: &mut Value, parameter: &str, value: Value) {
+    let slot = pointer_slot(config, parameter);
+    let number = value.as_f64();
+    *slot = match (&*slot, number) {
+        (Value::Bool(_), Some(number)) => Value::Bool(number >= 0.5),
+        (Value::Number(existing), Some(number)) if existing.is_i64() => {
+            Value::from(number.round() as i64)
+        }
+        (Value::Number(existing), Some(number)) if existing.is_u64() => {
+            Value::from(number.round().max(0.0) as u64)
+        }
+        _ => value,
+    };
+}
+
+/// The value at `pointer`, creating objects (and `null` leaves) for
+/// missing members. Array indices past the end are appended as `null`.
+fn pointer_slot<'a>(config: &'a mut Value, pointer: &str) -> &'a mut Value {
+    let mut slot = config;
+    for token in pointer.split('/').skip(1) {
+        let key = token.replace("~1", "/").replace("~0", "~");
+        let index = key.parse::<usize>().ok().filter(|_| slot.is_array());
+        slot = match index {
+            Some(index) => {
+                if let Value::Array(items) = &mut *slot
+                    && index >= items.len()
+                {
+                    items.resize(index + 1, Value::Null);
+                }
+                &mut slot[index]
+            }
+            None => {
+                // Indexing `null` by a key makes it an object.
+                if !slot.is_object() {
+                    *slot = Value::Null;
+                }
+                &mut slot[key.as_str()]
+            }
+        };
+    }
+    slot
+}
+
+/// A `SetParameter` value waiting for the next flush.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingWrite {
+    processor_id: String,
+    parameter: String,
+    value: Value,
+}
+
+/// Latest value per processor / parameter, in first-arrival order.
+#[derive(Debug, Default)]
+struct PendingWrites(Vec<PendingWrite>);
+
+impl PendingWrites {
+    fn queue(&mut self, write: PendingWrite) {
+        match self.0.iter_mut().find(|pending| {
+            pending.processor_id == write.processor_id && pending.parameter == write.parameter
+        }) {
+            Some(pending) => pending.value = write.value,
+            None => self.0.push(write),
+        }
+    }
+
+    /// Drain the queued writes, grouped by processor.
+    fn take_by_processor(&mut self) -> Vec<(String, Vec<PendingWrite>)> {
+        let mut grouped: Vec<(String, Vec<PendingWrite>)> = Vec::new();
+        for write in std::mem::take(&mut self.0) {
+            match grouped
+                .iter_mut()
+                .find(|(processor_id, _)| *processor_id == write.processor_id)
+            {
+                Some((_, writes)) => writes.push(write),
+                None => grouped.push((write.processor_id.clone(), vec![write])),
+            }
+        }
+        grouped
+    }
+}
+
+/// State owned by the server thread.
+struct OscServerWorker {
+    runtime: Arc<dyn RuntimeOperations>,
+    /// Drives the async runtime operations from this plain thread.
+    executor: tokio::runtime::Runtime,
+    socket: UdpSocket,
+    mappings: Vec<OscMapping>,
+    pending: PendingWrites,
+    processor_id: Option<String>,
+}
+
+impl OscServerWorker {
+    /// Receive until the stop sender is dropped.
+    fn run(mut self, stop: mpsc::Receiver<()>) {
+        let mut buf = vec![0u8; MAX_DATAGRAM_LEN];
+        let mut last_flush = Instant::now();
+        loop {
+            if !matches!(stop.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                return;
+            }
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, from)) => match decode_osc_packet(&buf[..len]) {
+                    Ok(messages) => messages.iter().for_each(|message| self.handle(message)),
+                    Err(e) => tracing::debug!(%from, error = %e, "OscServer: malformed packet"),
+                },
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) => {}
+                Err(e) => tracing::warn!(error = %e, "OscServer: receive failed"),
+            }
+            if last_flush.elapsed() >= OSC_FLUSH_INTERVAL {
+                self.flush();
+                last_flush = Instant::now();
+            }
+        }
+    }
+
+    fn handle(&mut self, message: &OscMessage) {
+        for mapping in self
+            .mappings
+            .iter()
+            .filter(|mapping| mapping.address == message.address)
+        {
+            match &mapping.action {
+                OscMappingAction::SetParameter {
+                    processor_id,
+                    parameter,
+                    range,
+                } => match message
+                    .args
+                    .first()
+                    .and_then(|arg| parameter_value(arg, *range))
+                {
+                    Some(value) => self.pending.queue(PendingWrite {
+                        processor_id: processor_id.clone(),
+                        parameter: parameter.clone(),
+                        value,
+                    }),
+                    None => tracing::debug!(
+                        address = %message.address,
+                        "OscServer: message has no usable value"
+                    ),
+                },
+                OscMappingAction::Trigger(action) if trigger_fires(&message.args) => {
+                    let result = self.executor.block_on(apply_controller_actions(
+                        &self.runtime,
+                        vec![action.clone()],
+                    ));
+                    let error = result.err().map(|failure| failure.error);
+                    match &error {
+                        None => tracing::info!(
+                            address = %message.address,
+                            ?action,
+                            "OscServer: action applied"
+                        ),
+                        Some(error) => tracing::warn!(
+                            address = %message.address,
+                            ?action,
+                            %error,
+                            "OscServer: action failed"
+                        ),
+                    }
+                    PUBSUB.publish(
+                        OSC_ACTION_TOPIC,
+                        &Event::custom(
+                            OSC_ACTION_TOPIC,
+                            serde_json::json!({
+                                "processor_id": self.processor_id,
+                                "address": message.address,
+                                "action": action,
+                                "error": error,
+                            }),
+                        ),
+                    );
+                }
+                OscMappingAction::Trigger(_) => {}
+            }
+        }
+    }
+
+    /// Write the coalesced `SetParameter` values, one config update per
+    /// processor whose config changed.
+    fn flush(&mut self) {
+        let updates = self.pending.take_by_processor();
+        if updates.is_empty() {
+            return;
+        }
+        let runtime = &self.runtime;
+        self.executor.block_on(async {
+            let graph = match graph(runtime).await {
+                Ok(graph) => graph,
+                Err(e) => {
+                    tracing::warn!(error = %e, "OscServer: graph read failed");
+                    return;
+                }
+            };
+            for (processor_id, writes) in updates {
+                let Some(node) = graph.nodes.iter().find(|node| node.id == processor_id) else {
+                    tracing::debug!(%processor_id, "OscServer: processor isn't in the graph");
+                    continue;
+                };
+                let previous = node.config.clone().unwrap_or(Value::Null);
+                let mut config = previous.clone();
+                for write in writes {
+                    write_parameter(&mut config, &write.parameter, write.value);
+                }
+                if config == previous {
+                    continue;
+                }
+                if let Err(e) = runtime
+                    .update_processor_config_async(processor_id.clone().into(), config)
+                    .await
+                {
+                    tracing::warn!(%processor_id, error = %e, "OscServer: config update failed");
+                }
+            }
+        });
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/OscServer",
+    description = "Receives Open Sound Control over UDP and maps OSC addresses to processor config parameters and runtime actions: start / stop processors and connect / disconnect links",
+    execution = manual,
+    config = crate::_generated_::OscServerConfig,
+)]
+pub struct OscServerProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    processor_id: Option<String>,
+    mappings: Vec<OscMapping>,
+    /// Dropped to stop the server thread.
+    stop_tx: Option<mpsc::Sender<()>>,
+    server_thread: Option<JoinHandle<()>>,
+}
+
+impl ManualProcessor for OscServerProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.mappings = compile_mappings(&self.config.mappings)?;
+        self.runtime = Some(ctx.runtime());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| Error::Runtime("OscServer: started before setup".into()))?;
+        let bind_address = self
+            .config
+            .bind_address
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BIND_ADDRESS.to_string());
+        let socket = UdpSocket::bind(&bind_address)
+            .map_err(|e| Error::Config(format!("OscServer: bind {bind_address}: {e}")))?;
+        socket
+            .set_read_timeout(Some(OSC_FLUSH_INTERVAL))
+            .map_err(|e| Error::Runtime(format!("OscServer: set read timeout: {e}")))?;
+        let local_address = socket
+            .local_addr()
+            .map_or(bind_address, |address| address.to_string());
+        let executor = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("OscServer: failed to build tokio runtime: {e}"))
+            })?;
+
+        let worker = OscServerWorker {
+            runtime,
+            executor,
+            socket,
+            mappings: self.mappings.clone(),
+            pending: PendingWrites::default(),
+            processor_id: self.processor_id.clone(),
+        };
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let server_thread = std::thread::Builder::new()
+            .name("osc-server".into())
+            .spawn(move || worker.run(stop_rx))
+            .map_err(|e| Error::Runtime(format!("OscServer: spawn server thread: {e}")))?;
+        self.stop_tx = Some(stop_tx);
+        self.server_thread = Some(server_thread);
+
+        tracing::info!(
+            address = %local_address,
+            mappings = self.mappings.len(),
+            "OscServer listening for OSC"
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop_tx.take();
+        if let Some(thread) = self.server_thread.take()
+            && thread.join().is_err()
+        {
+            tracing::warn!("OscServer: server thread panicked");
+        }
+        tracing::info!("OscServer stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping(address: &str, action: Action) -> Mapping {
+        Mapping {
+            address: address.into(),
+            action,
+            ..Mapping::default()
+        }
+    }
+
+    #[test]
+    fn compile_checks_each_action_has_its_fields() {
+        let gain = Mapping {
+            processor_id: Some("mixer".into()),
+            parameter: Some("/inputs/0/gain_db".into()),
+            min: Some(-60.0),
+            max: Some(6.0),
+            ..mapping("/mixer/music/gain", Action::SetParameter)
+        };
+        let stop = Mapping {
+            processor_id: Some("camera".into()),
+            ..mapping("/camera/stop", Action::StopProcessor)
+        };
+        let compiled = compile_mappings(&[gain.clone(), stop]).unwrap();
+        assert!(matches!(
+            &compiled[0].action,
+            OscMappingAction::SetParameter { processor_id, parameter, range }
+                if processor_id == "mixer"
+                    && parameter == "/inputs/0/gain_db"
+                    && *range == Some((-60.0, 6.0))
+        ));
+        assert!(matches!(
+            &compiled[1].action,
+            OscMappingAction::Trigger(ControllerAction::StopProcessor { processor_id })
+                if processor_id == "camera"
+        ));
+
+        assert!(compile_mappings(&[mapping("/link/drop", Action::Disconnect)]).is_err());
+        assert!(
+            compile_mappings(&[Mapping {
+                address: "no-slash".into(),
+                ..gain.clone()
+            }])
+            .is_err()
+        );
+        assert!(
+            compile_mappings(&[Mapping {
+                parameter: Some("gain_db".into()),
+                ..gain.clone()
+            }])
+            .is_err()
+        );
+        assert!(compile_mappings(&[Mapping { max: None, ..gain }]).is_err());
+    }
+
+    #[test]
+    fn triggers_fire_on_press_but_not_release() {
+        assert!(trigger_fires(&[]));
+        assert!(trigger_fires(&[OscArg::Float(1.0)]));
+        assert!(trigger_fires(&[OscArg::Bool(true)]));
+        assert!(trigger_fires(&[OscArg::String("go".into())]));
+        assert!(!trigger_fires(&[OscArg::Int(0)]));
+        assert!(!trigger_fires(&[OscArg::Bool(false)]));
+    }
+
+    #[test]
+    fn parameter_values_scale_onto_the_range() {
+        assert_eq!(
+            parameter_value(&OscArg::Float(0.5), Some((-60.0, 0.0))),
+            Some(json!(-30.0))
+        );
+        // Out-of-range fader positions clamp.
+        assert_eq!(
+            parameter_value(&OscArg::Double(2.0), Some((0.0, 10.0))),
+            Some(json!(10.0))
+        );
+        assert_eq!(parameter_value(&OscArg::Int(42), None), Some(json!(42.0)));
+        assert_eq!(
+            parameter_value(&OscArg::String("Sum".into()), None),
+            Some(json!("Sum"))
+        );
+        assert_eq!(
+            parameter_value(&OscArg::Bool(true), None),
+            Some(json!(true))
+        );
+        assert_eq!(parameter_value(&OscArg::Nil, None), None);
+    }
+
+    #[test]
+    fn writes_keep_the_type_already_in_the_config_and_create_members() {
+        let mut config = json!({ "radius": 2, "bypass": false, "inputs": [{ "input": "left" }] });
+        write_parameter(&mut config, "/radius", json!(4.6));
+        write_parameter(&mut config, "/bypass", json!(1.0));
+        write_parameter(&mut config, "/inputs/0/gain_db", json!(-6.0));
+        write_parameter(&mut config, "/ducking/key", json!("right"));
+        assert_eq!(
+            config,
+            json!({
+                "radius": 5,
+                "bypass": true,
+                "inputs": [{ "input": "left", "gain_db": -6.0 }],
+                "ducking": { "key": "right" },
+            })
+        );
+    }
+
+    #[test]
+    fn pending_writes_keep_the_latest_value_per_parameter() {
+        let write = |processor_id: &str, parameter: &str, value: f64| PendingWrite {
+            processor_id: processor_id.into(),
+            parameter: parameter.into(),
+            value: json!(value),
+        };
+        let mut pending = PendingWrites::default();
+        pending.queue(write("mixer", "/gain", 0.1));
+        pending.queue(write("blur", "/radius", 3.0));
+        pending.queue(write("mixer", "/pan", -1.0));
+        pending.queue(write("mixer", "/gain", 0.9));
+
+        assert_eq!(
+            pending.take_by_processor(),
+            vec![
+                (
+                    "mixer".to_string(),
+                    vec![write("mixer", "/gain", 0.9), write("mixer", "/pan", -1.0)]
+                ),
+                ("blur".to_string(), vec![write("blur", "/radius", 3.0)]),
+            ]
+        );
+        assert!(pending.take_by_processor().is_empty());
+    }
+}
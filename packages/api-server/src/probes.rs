@@ -0,0 +1,378 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Readiness and liveness probes — `GET /ready` and `GET /live`.
+//!
+//! `/health` answers as soon as the HTTP server is up. `/ready` passes
+//! only once every processor has reached `Running` (a paused processor
+//! got there first, so it counts) and every critical link has delivered
+//! `ready_min_frames` frames. `/live` passes while the runtime answers:
+//! a [`Watchdog`] thread round-trips the graph every
+//! [`WATCHDOG_INTERVAL`], and the probe fails once the last answer is
+//! older than `live_stall_ms` — a wedged runtime that a restart fixes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::json_schema::{GraphResponse, LinkOutput, ProbeCheck, ProbeResponse};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::_generated_::ApiServerConfig;
+
+pub(crate) const DEFAULT_READY_MIN_FRAMES: u32 = 1;
+pub(crate) const DEFAULT_LIVE_STALL_MS: u32 = 5_000;
+
+/// How often the watchdog round-trips the graph.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Processor states that count as having reached `Running`.
+const READY_STATES: &[&str] = &["Running", "Paused"];
+
+/// What `/ready` and `/live` check, from the `ApiServer` config.
+#[derive(Debug, Clone)]
+pub(crate) struct ProbeCriteria {
+    /// Links that must deliver frames before `/ready` passes — a link id,
+    /// or either end as `processor.port` with the processor's id or
+    /// display name.
+    pub critical_links: Vec<String>,
+    pub min_frames: u64,
+    /// Processors, by id or display name, left out of the `Running` check.
+    pub ignored_processors: Vec<String>,
+    pub live_stall: Duration,
+}
+
+impl Default for ProbeCriteria {
+    fn default() -> Self {
+        Self {
+            critical_links: Vec::new(),
+            min_frames: u64::from(DEFAULT_READY_MIN_FRAMES),
+            ignored_processors: Vec::new(),
+            live_stall: Duration::from_millis(u64::from(DEFAULT_LIVE_STALL_MS)),
+        }
+    }
+}
+
+impl ProbeCriteria {
+    pub(crate) fn from_config(config: &ApiServerConfig) -> Self {
+        Self {
+            critical_links: config.ready_critical_links.clone().unwrap_or_default(),
+            min_frames: u64::from(config.ready_min_frames.unwrap_or(DEFAULT_READY_MIN_FRAMES)),
+            ignored_processors: config.ready_ignore_processors.clone().unwrap_or_default(),
+            live_stall: Duration::from_millis(u64::from(
+                config.live_stall_ms.unwrap_or(DEFAULT_LIVE_STALL_MS),
+            )),
+        }
+    }
+}
+
+/// A probe report: ok when every check passed.
+fn from_checks(checks: Vec<ProbeCheck>) -> ProbeResponse {
+    ProbeResponse {
+        ok: checks.iter().all(|check| check.ok),
+        checks,
+    }
+}
+
+fn pass(name: impl Into<String>) -> ProbeCheck {
+    ProbeCheck {
+        name: name.into(),
+        ok: true,
+        detail: None,
+    }
+}
+
+fn fail(name: impl Into<String>, detail: String) -> ProbeCheck {
+    ProbeCheck {
+        name: name.into(),
+        ok: false,
+        detail: Some(detail),
+    }
+}
+
+/// `/ready` when the graph could not be read.
+pub(crate) fn unreadable_graph(error: &Error) -> ProbeResponse {
+    from_checks(vec![fail("graph", error.to_string())])
+}
+
+/// Evaluate `/ready` against a graph snapshot.
+pub(crate) fn readiness(graph: &GraphResponse, criteria: &ProbeCriteria) -> ProbeResponse {
+    let display_names: HashMap<&str, &str> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node.display_name.as_str()))
+        .collect();
+    let is_named = |id: &str, name: &str| {
+        id == name
+            || display_names
+                .get(id)
+                .is_some_and(|display| *display == name)
+    };
+
+    let not_running: Vec<String> = graph
+        .nodes
+        .iter()
+        .filter(|node| {
+            !criteria
+                .ignored_processors
+                .iter()
+                .any(|ignored| is_named(&node.id, ignored))
+        })
+        .filter_map(|node| {
+            let state = node.state().unwrap_or("Pending");
+            (!READY_STATES.contains(&state)).then(|| format!("{} ({})", node.id, state))
+        })
+        .collect();
+    let mut checks = vec![if not_running.is_empty() {
+        pass("processors")
+    } else {
+        fail(
+            "processors",
+            format!("not running: {}", not_running.join(", ")),
+        )
+    }];
+
+    for designation in &criteria.critical_links {
+        let name = format!("link:{designation}");
+        let matches_end = |processor_id: &str, port_name: &str| {
+            designation
+                .strip_suffix(port_name)
+                .and_then(|processor| processor.strip_suffix('.'))
+                .is_some_and(|processor| is_named(processor_id, processor))
+        };
+        let links: Vec<&LinkOutput> = graph
+            .links
+            .iter()
+            .filter(|link| {
+                link.id == *designation
+                    || matches_end(&link.source.processor_id, &link.source.port_name)
+                    || matches_end(&link.target.processor_id, &link.target.port_name)
+            })
+            .collect();
+        if links.is_empty() {
+            checks.push(fail(name, "no such link".into()));
+            continue;
+        }
+        // Every matching link must deliver — a fanned-out output is only
+        // as ready as its slowest consumer.
+        let delivered = links
+            .iter()
+            .map(|link| {
+                link.frame_drops()
+                    .map_or(0, |drops| drops.frames_delivered)
+            })
+            .min()
+            .unwrap_or_default();
+        checks.push(if delivered >= criteria.min_frames {
+            pass(name)
+        } else {
+            fail(
+                name,
+                format!(
+                    "{} of {} frame(s) delivered",
+                    delivered, criteria.min_frames
+                ),
+            )
+        });
+    }
+    from_checks(checks)
+}
+
+/// When the [`Watchdog`] last got an answer from the runtime.
+#[derive(Clone, Default)]
+pub(crate) struct Heartbeat(Arc<Mutex<Option<Instant>>>);
+
+impl Heartbeat {
+    pub(crate) fn beat(&self) {
+        *self.0.lock() = Some(Instant::now());
+    }
+
+    fn age(&self) -> Option<Duration> {
+        self.0.lock().map(|at| at.elapsed())
+    }
+}
+
+/// Evaluate `/live`. Answering at all shows the HTTP event loop is
+/// responsive; the heartbeat shows the runtime is.
+pub(crate) fn liveness(heartbeat: &Heartbeat, criteria: &ProbeCriteria) -> ProbeResponse {
+    let check = match heartbeat.age() {
+        None => fail("watchdog", "the runtime has not answered yet".into()),
+        Some(age) if age > criteria.live_stall => fail(
+            "watchdog",
+            format!(
+                "the runtime last answered {} ms ago (limit {} ms)",
+                age.as_millis(),
+                criteria.live_stall.as_millis()
+            ),
+        ),
+        Some(_) => pass("watchdog"),
+    };
+    from_checks(vec![check])
+}
+
+/// Shared probe state handed to the router.
+#[derive(Clone, Default)]
+pub(crate) struct Probes {
+    pub criteria: Arc<ProbeCriteria>,
+    pub heartbeat: Heartbeat,
+}
+
+/// Thread that beats [`Heartbeat`] each time the runtime answers a graph
+/// round-trip. Stops when dropped.
+pub(crate) struct Watchdog {
+    _stop_tx: mpsc::Sender<()>,
+}
+
+impl Watchdog {
+    pub(crate) fn spawn(runtime: Arc<dyn RuntimeOperations>, heartbeat: Heartbeat) -> Result<Self> {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        // Detached rather than joined on drop: a runtime wedged inside
+        // `to_json` would otherwise hang the ApiServer's stop as well. The
+        // thread exits at its next wake-up.
+        std::thread::Builder::new()
+            .name("api-server-watchdog".into())
+            .spawn(move || {
+                loop {
+                    match runtime.to_json() {
+                        Ok(_) => heartbeat.beat(),
+                        Err(e) => tracing::debug!(
+                            error = %e,
+                            "ApiServer watchdog: graph round-trip failed"
+                        ),
+                    }
+                    if !matches!(
+                        stop_rx.recv_timeout(WATCHDOG_INTERVAL),
+                        Err(mpsc::RecvTimeoutError::Timeout)
+                    ) {
+                        return;
+                    }
+                }
+            })
+            .map_err(|e| Error::Runtime(format!("ApiServer: spawn watchdog thread: {e}")))?;
+        Ok(Self { _stop_tx: stop_tx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(links: serde_json::Value) -> GraphResponse {
+        let node = |id: &str, name: &str, state: &str| {
+            serde_json::json!({
+                "id": id,
+                "type": {
+                    "org": "tatolab",
+                    "package": "core",
+                    "type": name,
+                    "version": {"major": 1, "minor": 0, "patch": 0}
+                },
+                "display_name": name,
+                "ports": {"inputs": [], "outputs": []},
+                "components": {"state": state}
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "nodes": [
+                node("p1", "Camera", "Running"),
+                node("p2", "Encoder", "Paused"),
+                node("p3", "Preview", "Idle")
+            ],
+            "links": links
+        }))
+        .unwrap()
+    }
+
+    fn link(id: &str, delivered: u64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "source": {"processor_id": "p1", "port_name": "video_out"},
+            "target": {"processor_id": "p2", "port_name": "video_in"},
+            "components": {"frame_drops": {
+                "frames_delivered": delivered,
+                "frames_dropped": 0,
+                "window_secs": 5,
+                "window_frames_dropped": 0,
+                "window_drop_rate": 0.0,
+                "congested": false
+            }}
+        })
+    }
+
+    #[test]
+    fn every_processor_must_reach_running_unless_ignored() {
+        let graph = graph(serde_json::json!([]));
+
+        let report = readiness(&graph, &ProbeCriteria::default());
+        assert!(!report.ok);
+        assert_eq!(
+            report.checks[0].detail.as_deref(),
+            Some("not running: p3 (Idle)")
+        );
+
+        for ignored in ["p3", "Preview"] {
+            let criteria = ProbeCriteria {
+                ignored_processors: vec![ignored.into()],
+                ..ProbeCriteria::default()
+            };
+            assert!(readiness(&graph, &criteria).ok, "ignoring {ignored}");
+        }
+    }
+
+    #[test]
+    fn critical_links_must_deliver_frames() {
+        let criteria = |links: &[&str], min_frames| ProbeCriteria {
+            critical_links: links.iter().map(|link| link.to_string()).collect(),
+            min_frames,
+            ignored_processors: vec!["p3".into()],
+            ..ProbeCriteria::default()
+        };
+
+        let idle = graph(serde_json::json!([link("L1", 0)]));
+        let report = readiness(&idle, &criteria(&["L1"], 1));
+        assert!(!report.ok);
+        assert_eq!(report.checks[1].name, "link:L1");
+        assert_eq!(
+            report.checks[1].detail.as_deref(),
+            Some("0 of 1 frame(s) delivered")
+        );
+
+        let flowing = graph(serde_json::json!([link("L1", 30)]));
+        for designation in ["L1", "p1.video_out", "Camera.video_out", "Encoder.video_in"] {
+            assert!(
+                readiness(&flowing, &criteria(&[designation], 1)).ok,
+                "{designation}"
+            );
+        }
+        assert!(!readiness(&flowing, &criteria(&["L1"], 60)).ok);
+
+        let report = readiness(&flowing, &criteria(&["Camera.audio_out"], 1));
+        assert_eq!(report.checks[1].detail.as_deref(), Some("no such link"));
+    }
+
+    #[test]
+    fn liveness_follows_the_heartbeat_age() {
+        let heartbeat = Heartbeat::default();
+        let criteria = ProbeCriteria {
+            live_stall: Duration::from_millis(50),
+            ..ProbeCriteria::default()
+        };
+
+        assert!(!liveness(&heartbeat, &criteria).ok);
+        heartbeat.beat();
+        assert!(liveness(&heartbeat, &criteria).ok);
+        std::thread::sleep(Duration::from_millis(80));
+        let report = liveness(&heartbeat, &criteria);
+        assert!(!report.ok);
+        assert!(
+            report.checks[0]
+                .detail
+                .as_deref()
+                .unwrap()
+                .contains("limit 50 ms")
+        );
+    }
+}
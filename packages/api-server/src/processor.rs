@@ -11,6 +11,9 @@ use streamlib::sdk::error::{Error, Result};
 use streamlib::sdk::processors::ManualProcessor;
 use streamlib::sdk::runtime::RuntimeOperations;
 
+use crate::auth::{ApiServerAuth, configured_tokens};
+use crate::oidc::{DEFAULT_OIDC_READ_SCOPE, DEFAULT_OIDC_WRITE_SCOPE, OidcSettings, OidcValidator};
+
 /// Handles cloned from the setup-time context for use in start().
 /// The `tokio_handle` points at this processor's own tokio runtime
 /// (constructed in `setup()`) — the processor owns a dedicated runtime
@@ -20,9 +23,13 @@ struct StashedHandles {
     runtime: Arc<dyn RuntimeOperations>,
     tokio_handle: tokio::runtime::Handle,
     runtime_id: String,
-    /// `Some` only when the config opted into bearer auth; `None` leaves the
-    /// mutating routes open (the zero-ceremony default).
-    auth_token: Option<crate::auth::ApiServerBearerToken>,
+    /// `Some` only when the config opted into auth (a persisted or static
+    /// token, or an OIDC issuer); `None` leaves every route open (the
+    /// zero-ceremony default).
+    auth: Option<ApiServerAuth>,
+    /// `Some` when the config names a certificate and key to terminate TLS
+    /// with.
+    tls: Option<tokio_rustls::TlsAcceptor>,
 }
 
 /// Docker-style adjectives for runtime name generation.
@@ -106,6 +113,79 @@ fn generate_runtime_name() -> String {
     format!("{}-{}", adj, noun)
 }
 
+/// Assemble the auth layer from the config, or `None` when nothing asks for
+/// it. Bearer auth is opt-in (default off): a node runs locally with full
+/// permission, so every route stays open unless the config names a token or
+/// an OIDC issuer. `require_auth` auto-generates + 0600-persists a
+/// write-scoped secret on first setup (reused across restarts).
+fn build_auth(config: &crate::_generated_::ApiServerConfig) -> Result<Option<ApiServerAuth>> {
+    let tokens = configured_tokens(
+        "ApiServer",
+        config.require_auth == Some(true),
+        config.auth_write_tokens.as_deref().unwrap_or_default(),
+        config.auth_read_tokens.as_deref().unwrap_or_default(),
+    )?;
+
+    let oidc = match &config.oidc_issuer {
+        Some(issuer) => {
+            let audience = config.oidc_audience.clone().ok_or_else(|| {
+                Error::Configuration("ApiServer: oidc_issuer needs oidc_audience".into())
+            })?;
+            let validator = OidcValidator::discover(OidcSettings {
+                issuer: issuer.clone(),
+                audience,
+                jwks_url: config.oidc_jwks_url.clone(),
+                read_scope: config
+                    .oidc_read_scope
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_OIDC_READ_SCOPE.to_string()),
+                write_scope: config
+                    .oidc_write_scope
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_OIDC_WRITE_SCOPE.to_string()),
+            })?;
+            tracing::info!("ApiServer accepting OIDC tokens from {issuer}");
+            Some(validator)
+        }
+        None => None,
+    };
+
+    if tokens.is_empty() && oidc.is_none() {
+        return Ok(None);
+    }
+    let protect_reads = config.auth_protect_reads == Some(true);
+    Ok(Some(ApiServerAuth::new(tokens, oidc, protect_reads)))
+}
+
+/// Whether `host` only accepts connections from this machine.
+pub(crate) fn is_loopback_host(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Serve `app` on `listener` until `shutdown` fires.
+async fn serve_until<L>(
+    listener: L,
+    app: axum::Router,
+    shutdown: tokio::sync::oneshot::Receiver<()>,
+) where
+    L: axum::serve::Listener,
+    L::Addr: std::fmt::Debug,
+{
+    let served = axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = shutdown.await;
+        })
+        .await;
+    if let Err(error) = served {
+        tracing::error!(%error, "ApiServer HTTP server exited");
+    }
+}
+
 #[streamlib::sdk::processor(
     "@tatolab/api-server/ApiServer",
     description = "Runtime API server — HTTP + WebSocket control plane",
@@ -121,6 +201,8 @@ pub struct ApiServerProcessor {
     /// on the calling lifecycle thread already being inside a tokio runtime.
     tokio_runtime: Option<tokio::runtime::Runtime>,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
+    /// Beats the `/live` heartbeat while the server runs.
+    watchdog: Option<crate::probes::Watchdog>,
     runtime_id: Option<String>,
     resolved_name: Option<String>,
     actual_port: Option<u16>,
@@ -143,20 +225,33 @@ impl ManualProcessor for ApiServerProcessor::Processor {
         let tokio_handle = runtime.handle().clone();
         self.tokio_runtime = Some(runtime);
 
-        // Bearer auth is opt-in (default off): a node runs locally with full
-        // permission, so the mutating routes stay open unless the config asks
-        // for a token. When enabled, auto-generate + 0600-persist the secret on
-        // first setup (reused across restarts) and gate every mutating route.
-        let auth_token = if self.config.require_auth == Some(true) {
-            let token = crate::auth::ApiServerBearerToken::load_or_create_under_data_dir()?;
-            tracing::info!(
-                "ApiServer bearer token at {}",
-                crate::auth::ApiServerBearerToken::default_token_path().display()
-            );
-            Some(token)
-        } else {
-            None
+        let auth = build_auth(&self.config)?;
+        let tls = match (&self.config.tls_cert_path, &self.config.tls_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                Some(crate::tls::load_acceptor(cert_path, key_path)?)
+            }
+            (None, None) => None,
+            _ => {
+                return Err(Error::Configuration(
+                    "ApiServer: tls_cert_path and tls_key_path must be set together".into(),
+                ));
+            }
         };
+        if !is_loopback_host(&self.config.host) {
+            if auth.is_none() {
+                tracing::warn!(
+                    "ApiServer bound to {} with auth off: anyone who can reach it can \
+                     mutate the graph",
+                    self.config.host
+                );
+            } else if tls.is_none() {
+                tracing::warn!(
+                    "ApiServer bound to {} without TLS: bearer tokens cross the network \
+                     in the clear",
+                    self.config.host
+                );
+            }
+        }
 
         // Capture just the narrow handles the HTTP server task needs;
         // the long-lived task never holds a `RuntimeContext`.
@@ -164,7 +259,8 @@ impl ManualProcessor for ApiServerProcessor::Processor {
             runtime: ctx.runtime(),
             tokio_handle,
             runtime_id: ctx.runtime_id().to_string(),
-            auth_token,
+            auth,
+            tls,
         });
         Ok(())
     }
@@ -207,9 +303,15 @@ impl ManualProcessor for ApiServerProcessor::Processor {
         let config = self.config.clone();
         let host = config.host.clone();
 
+        let probes = crate::probes::Probes {
+            criteria: Arc::new(crate::probes::ProbeCriteria::from_config(&config)),
+            heartbeat: crate::probes::Heartbeat::default(),
+        };
+        let heartbeat = probes.heartbeat.clone();
         let app = crate::handlers::build_router(
             handles.runtime.clone(),
-            handles.auth_token.clone(),
+            handles.auth.clone(),
+            probes,
             #[cfg(feature = "moq")]
             handles.runtime_id.clone(),
         );
@@ -256,7 +358,12 @@ impl ManualProcessor for ApiServerProcessor::Processor {
         // The endpoint exists now (the port is bound), so the entry's existence
         // tracks the control endpoint's. A write failure is non-fatal — the node
         // starts regardless; it just won't be discoverable until the next run.
-        let control_url = format!("http://127.0.0.1:{}", actual_port);
+        let scheme = if handles.tls.is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        let control_url = format!("{scheme}://127.0.0.1:{actual_port}");
         let entry = crate::node_registry::NodeRegistryEntry::for_current_process(
             handles.runtime_id.clone(),
             control_url,
@@ -269,19 +376,29 @@ impl ManualProcessor for ApiServerProcessor::Processor {
         }
 
         tracing::info!(
-            "OpenAPI spec available at http://{}/api/openapi.json",
+            "OpenAPI spec available at {}://{}/api/openapi.json",
+            scheme,
             api_endpoint
         );
 
+        self.watchdog = Some(crate::probes::Watchdog::spawn(
+            handles.runtime.clone(),
+            heartbeat,
+        )?);
+
         // Spawn the HTTP server
-        tokio_handle.spawn(async move {
-            axum::serve(listener, app)
-                .with_graceful_shutdown(async {
-                    let _ = shutdown_rx.await;
-                })
-                .await
-                .unwrap();
-        });
+        match handles.tls.clone() {
+            Some(acceptor) => {
+                let listener = {
+                    let _guard = tokio_handle.enter();
+                    crate::tls::TlsListener::spawn(listener, acceptor)?
+                };
+                tokio_handle.spawn(serve_until(listener, app, shutdown_rx));
+            }
+            None => {
+                tokio_handle.spawn(serve_until(listener, app, shutdown_rx));
+            }
+        }
 
         Ok(())
     }
@@ -298,6 +415,7 @@ impl ManualProcessor for ApiServerProcessor::Processor {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(());
         }
+        self.watchdog.take();
         Ok(())
     }
 }
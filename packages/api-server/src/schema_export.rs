@@ -0,0 +1,483 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Machine-readable API descriptions derived from what the runtime actually
+//! serves and has registered, for client and SDK generators.
+//!
+//! The route document itself comes from the handlers' `utoipa` annotations
+//! (see [`crate::handlers::documented_routes`]). This module adds the part no
+//! handler signature can know: each registered processor's config schema.
+//! A processor's [`ProcessorDescriptor`] names its config schema; the schema
+//! body is JSON Type Definition (RFC 8927), converted here to JSON Schema
+//! 2020-12 — the dialect OpenAPI 3.1 embeds. Two documents result:
+//!
+//! - the OpenAPI document at `/api/openapi.json`, whose components gain one
+//!   schema per processor config;
+//! - a standalone JSON Schema for graph payloads at `/api/graph/schema`,
+//!   where each node's `config` is checked against its processor type's
+//!   config schema.
+//!
+//! Both are rebuilt per request: modules register processors at runtime.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+use streamlib::sdk::descriptors::ProcessorDescriptor;
+use streamlib::sdk::json_schema::SchemaIdentOutput;
+use streamlib::sdk::processors::PROCESSOR_REGISTRY;
+
+/// The JSON Schema dialect both documents use.
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `$id` prefix for a converted config schema; `#/$defs/...` refs inside it
+/// resolve against this, wherever the schema is embedded.
+const CONFIG_SCHEMA_ID_PREFIX: &str = "urn:streamlib:schema:";
+
+/// `$id` of the graph payload document.
+const GRAPH_SCHEMA_ID: &str = "urn:streamlib:api:graph";
+
+/// Extension keyword listing the processor types a config schema belongs to.
+const PROCESSOR_TYPES_EXTENSION: &str = "x-streamlib-processor-types";
+
+/// One processor config schema, converted.
+#[derive(Debug, Clone)]
+pub(crate) struct ProcessorConfigSchema {
+    /// OpenAPI component name (`org.package.Type`).
+    pub component: String,
+    /// The processor types configured by it.
+    pub processor_types: Vec<SchemaIdentOutput>,
+    /// The JSON Schema.
+    pub schema: Value,
+}
+
+/// Config schemas of every registered processor whose config schema
+/// definition the runtime holds, one per schema, ordered by component name.
+pub(crate) fn registered_config_schemas() -> Vec<ProcessorConfigSchema> {
+    config_schemas_for(
+        &PROCESSOR_REGISTRY.list_registered(),
+        streamlib::sdk::schemas::current_schema_definition,
+    )
+}
+
+/// [`registered_config_schemas`] over explicit descriptors and a schema
+/// lookup.
+fn config_schemas_for(
+    descriptors: &[ProcessorDescriptor],
+    lookup: impl Fn(&str) -> Option<std::sync::Arc<str>>,
+) -> Vec<ProcessorConfigSchema> {
+    let mut by_schema: BTreeMap<String, ProcessorConfigSchema> = BTreeMap::new();
+    for descriptor in descriptors {
+        let Some(schema_id) = descriptor.config_schema.as_deref() else {
+            continue;
+        };
+        let processor_type = SchemaIdentOutput::from(&descriptor.name);
+        if let Some(existing) = by_schema.get_mut(schema_id) {
+            existing.processor_types.push(processor_type);
+            continue;
+        }
+        let Some(definition) = lookup(schema_id) else {
+            continue;
+        };
+        let jtd: Value = match serde_yaml::from_str(&definition) {
+            Ok(jtd) => jtd,
+            Err(error) => {
+                tracing::warn!(%error, schema = schema_id, "config schema is not valid YAML");
+                continue;
+            }
+        };
+        let mut schema = jtd_to_json_schema(&jtd);
+        schema["$id"] = json!(format!("{CONFIG_SCHEMA_ID_PREFIX}{schema_id}"));
+        by_schema.insert(
+            schema_id.to_string(),
+            ProcessorConfigSchema {
+                component: component_name(schema_id),
+                processor_types: vec![processor_type],
+                schema,
+            },
+        );
+    }
+    let mut schemas: Vec<_> = by_schema.into_values().collect();
+    for schema in &mut schemas {
+        schema.schema[PROCESSOR_TYPES_EXTENSION] = json!(schema.processor_types);
+    }
+    schemas.sort_by(|a, b| a.component.cmp(&b.component));
+    schemas
+}
+
+/// An OpenAPI component name for a schema id: `@org/pkg/Type@1.0.0` becomes
+/// `org.pkg.Type`, within the `[A-Za-z0-9._-]` names OpenAPI allows.
+fn component_name(schema_id: &str) -> String {
+    let unversioned = schema_id.trim_start_matches('@');
+    let unversioned = unversioned
+        .rsplit_once('@')
+        .map_or(unversioned, |(name, _)| name);
+    unversioned
+        .chars()
+        .map(|c| match c {
+            '/' => '.',
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// The served OpenAPI document: the route document plus one component per
+/// processor config schema.
+pub(crate) fn openapi_document(
+    openapi: &utoipa::openapi::OpenApi,
+    configs: &[ProcessorConfigSchema],
+) -> Value {
+    let mut document = serde_json::to_value(openapi).unwrap_or_else(|_| json!({}));
+    let schemas = document
+        .as_object_mut()
+        .map(|root| root.entry("components").or_insert_with(|| json!({})))
+        .and_then(Value::as_object_mut)
+        .map(|components| components.entry("schemas").or_insert_with(|| json!({})))
+        .and_then(Value::as_object_mut);
+    if let Some(schemas) = schemas {
+        for config in configs {
+            schemas.insert(config.component.clone(), config.schema.clone());
+        }
+    }
+    document
+}
+
+/// A standalone JSON Schema for graph payloads (`GET /api/graph`): the graph
+/// DTOs from the route document's components, with each node's `config`
+/// checked against its processor type's config schema.
+pub(crate) fn graph_json_schema(
+    openapi: &utoipa::openapi::OpenApi,
+    configs: &[ProcessorConfigSchema],
+) -> Value {
+    let mut defs = Map::new();
+    if let Some(components) = openapi.components.as_ref() {
+        for (name, schema) in &components.schemas {
+            let mut schema = serde_json::to_value(schema).unwrap_or_else(|_| json!({}));
+            rewrite_component_refs(&mut schema);
+            defs.insert(name.clone(), schema);
+        }
+    }
+
+    // One `if type matches, then config conforms` rule per processor type.
+    let mut rules = Vec::new();
+    for config in configs {
+        defs.insert(config.component.clone(), config.schema.clone());
+        for processor_type in &config.processor_types {
+            rules.push(json!({
+                "if": {
+                    "properties": {
+                        "type": {
+                            "properties": {
+                                "org": { "const": processor_type.org },
+                                "package": { "const": processor_type.package },
+                                "type": { "const": processor_type.type_name },
+                            },
+                        },
+                    },
+                },
+                "then": {
+                    "properties": {
+                        "config": { "$ref": format!("#/$defs/{}", config.component) },
+                    },
+                },
+            }));
+        }
+    }
+    if let Some(node) = defs.get_mut("ProcessorNodeOutput")
+        && !rules.is_empty()
+    {
+        let node_schema = std::mem::take(node);
+        *node = json!({ "allOf": [node_schema], });
+        node["allOf"]
+            .as_array_mut()
+            .expect("just built")
+            .extend(rules);
+    }
+
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$id": GRAPH_SCHEMA_ID,
+        "title": "StreamLib graph",
+        "description": "The runtime graph as `GET /api/graph` returns it",
+        "$ref": "#/$defs/GraphResponse",
+        "$defs": defs,
+    })
+}
+
+/// Point `#/components/schemas/X` refs at `#/$defs/X`.
+fn rewrite_component_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key != "$ref" {
+                    rewrite_component_refs(child);
+                    continue;
+                }
+                let target = child
+                    .as_str()
+                    .and_then(|r| r.strip_prefix("#/components/schemas/"))
+                    .map(str::to_owned);
+                if let Some(target) = target {
+                    *child = json!(format!("#/$defs/{target}"));
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_component_refs),
+        _ => {}
+    }
+}
+
+/// Convert a root JTD schema to JSON Schema 2020-12. `definitions` become
+/// `$defs`; the root's `metadata.type` becomes its `title`.
+pub(crate) fn jtd_to_json_schema(jtd: &Value) -> Value {
+    let mut schema = convert_form(jtd);
+    let Value::Object(root) = &mut schema else {
+        return schema;
+    };
+    root.insert("$schema".into(), json!(JSON_SCHEMA_DIALECT));
+    if let Some(title) = jtd.pointer("/metadata/type").and_then(Value::as_str) {
+        root.insert("title".into(), json!(title));
+    }
+    if let Some(definitions) = jtd.get("definitions").and_then(Value::as_object) {
+        let defs: Map<String, Value> = definitions
+            .iter()
+            .map(|(name, form)| (name.clone(), convert_form(form)))
+            .collect();
+        root.insert("$defs".into(), Value::Object(defs));
+    }
+    schema
+}
+
+/// Convert one JTD form (RFC 8927 §2.2).
+fn convert_form(jtd: &Value) -> Value {
+    let mut schema = if let Some(name) = jtd.get("ref").and_then(Value::as_str) {
+        json!({ "$ref": format!("#/$defs/{name}") })
+    } else if let Some(type_name) = jtd.get("type").and_then(Value::as_str) {
+        convert_type(type_name)
+    } else if let Some(values) = jtd.get("enum") {
+        json!({ "type": "string", "enum": values })
+    } else if let Some(elements) = jtd.get("elements") {
+        json!({ "type": "array", "items": convert_form(elements) })
+    } else if let Some(values) = jtd.get("values") {
+        json!({ "type": "object", "additionalProperties": convert_form(values) })
+    } else if let Some(tag) = jtd.get("discriminator").and_then(Value::as_str) {
+        convert_discriminator(tag, jtd.get("mapping"))
+    } else if jtd.get("properties").is_some() || jtd.get("optionalProperties").is_some() {
+        convert_properties(jtd, None)
+    } else {
+        // The empty form accepts any value.
+        json!({})
+    };
+
+    if jtd.get("nullable").and_then(Value::as_bool) == Some(true) {
+        schema = json!({ "anyOf": [schema, { "type": "null" }] });
+    }
+    if let Some(description) = jtd.pointer("/metadata/description").and_then(Value::as_str)
+        && let Value::Object(map) = &mut schema
+    {
+        map.insert("description".into(), json!(description));
+    }
+    schema
+}
+
+/// JTD primitive types. Integer widths become `minimum` / `maximum`.
+fn convert_type(type_name: &str) -> Value {
+    let integer = |min: i64, max: i64| json!({ "type": "integer", "minimum": min, "maximum": max });
+    match type_name {
+        "boolean" => json!({ "type": "boolean" }),
+        "string" => json!({ "type": "string" }),
+        "timestamp" => json!({ "type": "string", "format": "date-time" }),
+        "float32" | "float64" => json!({ "type": "number" }),
+        "int8" => integer(i8::MIN.into(), i8::MAX.into()),
+        "uint8" => integer(0, u8::MAX.into()),
+        "int16" => integer(i16::MIN.into(), i16::MAX.into()),
+        "uint16" => integer(0, u16::MAX.into()),
+        "int32" => integer(i32::MIN.into(), i32::MAX.into()),
+        "uint32" => integer(0, u32::MAX.into()),
+        _ => json!({}),
+    }
+}
+
+/// The properties form; `tag` is the discriminator property a mapping
+/// variant carries, pinned to `tag.1`.
+fn convert_properties(jtd: &Value, tag: Option<(&str, &str)>) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    if let Some((name, value)) = tag {
+        properties.insert(name.into(), json!({ "const": value }));
+        required.push(json!(name));
+    }
+    if let Some(props) = jtd.get("properties").and_then(Value::as_object) {
+        for (name, form) in props {
+            properties.insert(name.clone(), convert_form(form));
+            required.push(json!(name));
+        }
+    }
+    if let Some(props) = jtd.get("optionalProperties").and_then(Value::as_object) {
+        for (name, form) in props {
+            properties.insert(name.clone(), convert_form(form));
+        }
+    }
+    let additional = jtd.get("additionalProperties").and_then(Value::as_bool) == Some(true);
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": additional,
+    });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// The discriminator form: one properties variant per tag value.
+fn convert_discriminator(tag: &str, mapping: Option<&Value>) -> Value {
+    let variants: Vec<Value> = mapping
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(value, form)| convert_properties(form, Some((tag, value))))
+        .collect();
+    json!({ "type": "object", "required": [tag], "oneOf": variants })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use streamlib::sdk::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+
+    const BLUR_CONFIG: &str = r#"
+metadata:
+  type: BlurConfig
+  description: "Blur settings"
+definitions:
+  Kernel:
+    enum: [box, gaussian]
+properties:
+  radius:
+    metadata:
+      description: "Radius in pixels"
+    type: uint16
+optionalProperties:
+  kernel:
+    ref: Kernel
+  label:
+    type: string
+    nullable: true
+  weights:
+    elements:
+      type: float32
+"#;
+
+    fn descriptor(type_name: &str, config_schema: Option<&str>) -> ProcessorDescriptor {
+        let ident = SchemaIdent::new(
+            Org::new("acme").unwrap(),
+            Package::new("fx").unwrap(),
+            TypeName::new(type_name).unwrap(),
+            SemVer::new(1, 0, 0),
+        );
+        let descriptor = ProcessorDescriptor::new(ident, "test");
+        match config_schema {
+            Some(schema) => descriptor.with_config_schema(schema),
+            None => descriptor,
+        }
+    }
+
+    #[test]
+    fn jtd_forms_convert_to_json_schema() {
+        let jtd: Value = serde_yaml::from_str(BLUR_CONFIG).unwrap();
+        let schema = jtd_to_json_schema(&jtd);
+
+        assert_eq!(schema["title"], "BlurConfig");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["radius"]));
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["properties"]["radius"],
+            json!({
+                "type": "integer",
+                "minimum": 0,
+                "maximum": 65535,
+                "description": "Radius in pixels",
+            })
+        );
+        assert_eq!(
+            schema["properties"]["kernel"],
+            json!({ "$ref": "#/$defs/Kernel" })
+        );
+        assert_eq!(
+            schema["$defs"]["Kernel"],
+            json!({ "type": "string", "enum": ["box", "gaussian"] })
+        );
+        assert_eq!(
+            schema["properties"]["label"],
+            json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            schema["properties"]["weights"]["items"],
+            json!({ "type": "number" })
+        );
+    }
+
+    #[test]
+    fn discriminators_become_tagged_one_of_variants() {
+        let jtd = json!({
+            "discriminator": "op",
+            "mapping": {
+                "resize": { "properties": { "width": { "type": "uint32" } } },
+                "reset": { "properties": {} },
+            },
+        });
+        let schema = convert_form(&jtd);
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 2);
+        let resize = variants
+            .iter()
+            .find(|v| v["properties"]["op"]["const"] == "resize")
+            .unwrap();
+        assert_eq!(resize["required"], json!(["op", "width"]));
+    }
+
+    #[test]
+    fn config_schemas_group_processors_and_constrain_graph_nodes() {
+        let descriptors = [
+            descriptor("Blur", Some("@acme/fx/BlurConfig@1.0.0")),
+            descriptor("FastBlur", Some("@acme/fx/BlurConfig@1.0.0")),
+            descriptor("Passthrough", None),
+            descriptor("Missing", Some("@acme/fx/MissingConfig@1.0.0")),
+        ];
+        let configs = config_schemas_for(&descriptors, |id| {
+            (id == "@acme/fx/BlurConfig@1.0.0").then(|| Arc::from(BLUR_CONFIG))
+        });
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].component, "acme.fx.BlurConfig");
+        assert_eq!(configs[0].processor_types.len(), 2);
+        assert_eq!(
+            configs[0].schema["$id"],
+            "urn:streamlib:schema:@acme/fx/BlurConfig@1.0.0"
+        );
+
+        let openapi = crate::handlers::openapi_spec();
+        let document = openapi_document(&openapi, &configs);
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(
+            document["components"]["schemas"]["acme.fx.BlurConfig"]["title"],
+            "BlurConfig"
+        );
+
+        let graph = graph_json_schema(&openapi, &configs);
+        assert_eq!(graph["$ref"], "#/$defs/GraphResponse");
+        let node_rules = graph["$defs"]["ProcessorNodeOutput"]["allOf"]
+            .as_array()
+            .unwrap();
+        // The node's own schema plus one rule per processor type.
+        assert_eq!(node_rules.len(), 3);
+        assert_eq!(
+            node_rules[1]["then"]["properties"]["config"]["$ref"],
+            "#/$defs/acme.fx.BlurConfig"
+        );
+        let graph_text = graph.to_string();
+        assert!(!graph_text.contains("#/components/schemas/"));
+    }
+}
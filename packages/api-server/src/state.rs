@@ -3,12 +3,22 @@
 
 //! Shared HTTP state, OpenAPI document, and request/response wire types.
 
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use streamlib::sdk::json_schema::SchemaIdentOutput;
+use streamlib::sdk::frame_snapshot::PreviewFormat;
+use streamlib::sdk::json_schema::{
+    CreateConnectionRequest, GpuPoolStatsOutput, LinkFrameDropsOutput, ProcessorMetricsOutput,
+    SchemaIdentOutput,
+};
+use streamlib::sdk::pubsub::{Event, JournaledEvent};
 use streamlib::sdk::runtime::{ProcessorLanguage, RuntimeOperations};
 use utoipa::OpenApi;
 
+use crate::camera_controls::CameraControlsCache;
+use crate::probes::Probes;
+
 /// Shared HTTP handler state.
 #[derive(Clone)]
 pub(crate) struct AppState {
@@ -18,47 +28,17 @@ pub(crate) struct AppState {
     #[cfg(feature = "moq")]
     pub runtime_id: String,
     pub openapi: utoipa::openapi::OpenApi,
+    /// Latest `camera:controls` descriptors per processor. Also the strong
+    /// reference that keeps the cache's PUBSUB subscription alive.
+    pub camera_controls: Arc<Mutex<CameraControlsCache>>,
+    /// Criteria and watchdog heartbeat behind `/ready` and `/live`.
+    pub probes: Probes,
 }
 
 // ============================================================================
 // Request/Response Types with OpenAPI Schema
 // ============================================================================
 
-#[derive(Deserialize, utoipa::ToSchema)]
-pub(crate) struct CreateProcessorRequest {
-    /// Structured processor identity — the four-field map form of
-    /// `@org/package/Type@version`. The structured-everywhere rule
-    /// applies on the HTTP API too — bare strings like
-    /// `"CameraProcessor"` are rejected at deserialize time.
-    pub processor_type: SchemaIdentOutput,
-    /// Processor-specific configuration as JSON
-    pub config: serde_json::Value,
-}
-
-#[derive(Deserialize, utoipa::ToSchema)]
-pub(crate) struct CreateConnectionRequest {
-    /// Source processor ID
-    pub from_processor: String,
-    /// Source output port name
-    pub from_port: String,
-    /// Destination processor ID
-    pub to_processor: String,
-    /// Destination input port name
-    pub to_port: String,
-}
-
-#[derive(Serialize, utoipa::ToSchema)]
-pub(crate) struct IdResponse {
-    /// The created resource ID
-    pub id: String,
-}
-
-#[derive(Serialize, utoipa::ToSchema)]
-pub(crate) struct ErrorResponse {
-    /// Error message
-    pub error: String,
-}
-
 /// Body returned alongside `422 Unprocessable Entity` when the caller
 /// supplies a structurally-valid `SchemaIdent` whose type isn't registered.
 /// The runtime is dynamic — types load and unload — so this is a normal
@@ -197,6 +177,13 @@ pub(crate) struct SubmittedProcessorSourceRequest {
     pub connect: Vec<SourceProcessorConnection>,
 }
 
+/// Body of `POST /api/processors/{id}/outputs/{port}/snapshot`.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(crate) struct SnapshotPortRequest {
+    /// Where the runtime host writes the PNG; must end in `.png`.
+    pub path: String,
+}
+
 /// Body of `POST /api/processor/source/replace`: swap a live
 /// `@session/<name>` source registration for a replacement, transactionally
 /// (a failed replacement restores the prior registration).
@@ -283,13 +270,91 @@ pub(crate) struct RegisterProcessorSourceResponse {
     pub events_url: &'static str,
 }
 
+/// A processor added by `PATCH /api/graph`.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(crate) struct PatchGraphProcessorRequest {
+    /// Patch-local name for the new processor. `connect` and
+    /// `update_configs` entries use it in place of a processor id, and the
+    /// response maps it to the id the processor was given.
+    pub alias: String,
+    /// Structured processor identity, as in `POST /api/processor`.
+    pub processor_type: SchemaIdentOutput,
+    /// Processor-specific configuration as JSON.
+    pub config: serde_json::Value,
+}
+
+/// A config replacement in `PATCH /api/graph`.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(crate) struct PatchGraphConfigRequest {
+    /// The processor id, or the alias of a processor the same patch adds.
+    pub processor_id: String,
+    /// The full replacement config as JSON.
+    pub config: serde_json::Value,
+}
+
+/// Body of `PATCH /api/graph`: a graph diff applied all or nothing. Steps
+/// run in a fixed order — `disconnect`, `remove_processors`,
+/// `add_processors`, `update_configs`, then `connect` — whatever order the
+/// fields appear in. Every field may be omitted.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(crate) struct PatchGraphRequest {
+    /// Link ids to remove.
+    #[serde(default)]
+    pub disconnect: Vec<String>,
+    /// Processor ids to remove, together with their links.
+    #[serde(default)]
+    pub remove_processors: Vec<String>,
+    /// Processors to add.
+    #[serde(default)]
+    pub add_processors: Vec<PatchGraphProcessorRequest>,
+    /// Configs to replace.
+    #[serde(default)]
+    pub update_configs: Vec<PatchGraphConfigRequest>,
+    /// Links to wire. Either end may name an added processor by its alias.
+    #[serde(default)]
+    pub connect: Vec<CreateConnectionRequest>,
+}
+
+/// Response to `PATCH /api/graph`.
+#[derive(Serialize, utoipa::ToSchema)]
+pub(crate) struct PatchGraphResponse {
+    /// The id each added processor's alias was given.
+    pub processors: BTreeMap<String, String>,
+    /// Link ids created by `connect`, in request order.
+    pub connections: Vec<String>,
+}
+
+/// Response to `GET /api/events`.
+#[derive(Serialize, utoipa::ToSchema)]
+pub(crate) struct EventReplayResponse {
+    /// Journaled events after `since_ns`, oldest first.
+    pub events: Vec<JournaledEvent>,
+}
+
 // ============================================================================
 // OpenAPI Documentation
 // ============================================================================
 
+/// Base document the router's `routes!` extend. Routes served outside the
+/// `OpenApiRouter` (the WebSocket upgrades) are listed here, as are schemas
+/// no handler signature mentions: the event stream's messages (live and
+/// replayed), the preview thumbnail formats and the metrics components
+/// embedded in the graph, its nodes and its links.
 #[derive(OpenApi)]
 #[openapi(
-    paths(crate::handlers::tap_websocket_handler),
+    paths(
+        crate::handlers::websocket_handler,
+        crate::handlers::tap_websocket_handler,
+        crate::handlers::preview_websocket_handler
+    ),
+    components(schemas(
+        Event,
+        JournaledEvent,
+        ProcessorMetricsOutput,
+        LinkFrameDropsOutput,
+        GpuPoolStatsOutput,
+        PreviewFormat
+    )),
     info(
         title = "StreamLib Runtime API",
         version = "0.1.0",
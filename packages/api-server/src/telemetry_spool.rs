@@ -0,0 +1,240 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Store-and-forward spool for the telemetry uplink.
+//!
+//! Every envelope is written to disk before it is uploaded and removed only
+//! once the collector acknowledges it, so envelopes built while the device
+//! is offline — or before a crash — go out on the next successful upload.
+//! Files are named by zero-padded sequence number, so directory order is
+//! upload order, and the sequence resumes past the newest file on reopen.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SPOOL_FILE_EXTENSION: &str = "cbor";
+
+/// Result of one [`TelemetrySpool::flush`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct SpoolFlush {
+    /// Envelopes the collector acknowledged (and that were removed).
+    pub uploaded: u64,
+    /// Envelopes still spooled.
+    pub pending: u64,
+    /// Why the flush stopped early, if it did.
+    pub error: Option<String>,
+}
+
+/// Directory of envelopes awaiting upload, capped at a byte budget.
+pub(crate) struct TelemetrySpool {
+    dir: PathBuf,
+    max_bytes: u64,
+    next_seq: u64,
+}
+
+impl TelemetrySpool {
+    /// Open (creating if needed) the spool at `dir`.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let mut spool = Self {
+            dir,
+            max_bytes,
+            next_seq: 0,
+        };
+        spool.next_seq = spool
+            .entries()?
+            .last()
+            .map_or(0, |entry| entry.seq.saturating_add(1));
+        Ok(spool)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Claim the sequence number for the next envelope.
+    pub fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    /// Persist envelope `seq`, then discard the oldest envelopes until the
+    /// spool fits its budget. The envelope just stored is never discarded.
+    /// Returns the number of envelopes discarded.
+    pub fn store(&mut self, seq: u64, envelope: &[u8]) -> io::Result<u64> {
+        // Write-then-rename, so a crash never leaves a truncated envelope
+        // under a name `flush` would upload.
+        let path = self.path_for(seq);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, envelope)?;
+        fs::rename(&tmp, &path)?;
+
+        let entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|entry| entry.len).sum();
+        let mut evicted = 0;
+        for entry in &entries {
+            if total <= self.max_bytes || entry.seq == seq {
+                break;
+            }
+            fs::remove_file(&entry.path)?;
+            total -= entry.len;
+            evicted += 1;
+        }
+        Ok(evicted)
+    }
+
+    /// Upload spooled envelopes oldest-first, removing each one `upload`
+    /// accepts. Stops at the first failure so envelopes stay in order.
+    pub fn flush(
+        &mut self,
+        mut upload: impl FnMut(&[u8]) -> std::result::Result<(), String>,
+    ) -> SpoolFlush {
+        let entries = match self.entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                return SpoolFlush {
+                    error: Some(format!("read spool {}: {e}", self.dir.display())),
+                    ..SpoolFlush::default()
+                };
+            }
+        };
+        let mut flush = SpoolFlush {
+            pending: entries.len() as u64,
+            ..SpoolFlush::default()
+        };
+        for entry in entries {
+            let result = fs::read(&entry.path)
+                .map_err(|e| format!("read {}: {e}", entry.path.display()))
+                .and_then(|envelope| upload(&envelope))
+                .and_then(|()| {
+                    fs::remove_file(&entry.path)
+                        .map_err(|e| format!("remove {}: {e}", entry.path.display()))
+                });
+            if let Err(error) = result {
+                flush.error = Some(error);
+                break;
+            }
+            flush.uploaded += 1;
+            flush.pending -= 1;
+        }
+        flush
+    }
+
+    fn path_for(&self, seq: u64) -> PathBuf {
+        self.dir.join(format!("{seq:020}.{SPOOL_FILE_EXTENSION}"))
+    }
+
+    /// Spooled envelopes in sequence order. Files that aren't spool
+    /// entries (including interrupted `.tmp` writes) are ignored.
+    fn entries(&self) -> io::Result<Vec<SpoolEntry>> {
+        let mut entries = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let dir_entry = dir_entry?;
+            let path = dir_entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SPOOL_FILE_EXTENSION) {
+                continue;
+            }
+            let Some(seq) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            entries.push(SpoolEntry {
+                seq,
+                len: dir_entry.metadata()?.len(),
+                path,
+            });
+        }
+        entries.sort_by_key(|entry| entry.seq);
+        Ok(entries)
+    }
+}
+
+struct SpoolEntry {
+    seq: u64,
+    len: u64,
+    path: PathBuf,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flush_uploads_oldest_first_and_keeps_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = TelemetrySpool::open(dir.path(), 1024).unwrap();
+        for payload in [b"a", b"b", b"c"] {
+            let seq = spool.next_seq();
+            spool.store(seq, payload).unwrap();
+        }
+
+        // Collector offline after the first envelope.
+        let mut sent = Vec::new();
+        let flush = spool.flush(|envelope| {
+            if sent.is_empty() {
+                sent.push(envelope.to_vec());
+                Ok(())
+            } else {
+                Err("connection refused".into())
+            }
+        });
+        assert_eq!(sent, [b"a".to_vec()]);
+        assert_eq!(
+            flush,
+            SpoolFlush {
+                uploaded: 1,
+                pending: 2,
+                error: Some("connection refused".into()),
+            }
+        );
+
+        let mut sent = Vec::new();
+        let flush = spool.flush(|envelope| {
+            sent.push(envelope.to_vec());
+            Ok(())
+        });
+        assert_eq!(sent, [b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!((flush.uploaded, flush.pending), (2, 0));
+    }
+
+    #[test]
+    fn budget_evicts_oldest_but_never_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = TelemetrySpool::open(dir.path(), 10).unwrap();
+        assert_eq!(spool.store(0, &[0; 4]).unwrap(), 0);
+        assert_eq!(spool.store(1, &[1; 4]).unwrap(), 0);
+        assert_eq!(spool.store(2, &[2; 4]).unwrap(), 1);
+        assert_eq!(spool.store(3, &[3; 32]).unwrap(), 2);
+
+        let mut sent = Vec::new();
+        spool.flush(|envelope| {
+            sent.push(envelope[0]);
+            Ok(())
+        });
+        assert_eq!(sent, [3]);
+    }
+
+    #[test]
+    fn sequence_resumes_after_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = TelemetrySpool::open(dir.path(), 1024).unwrap();
+        assert_eq!(spool.next_seq(), 0);
+        spool.store(0, b"x").unwrap();
+        assert_eq!(spool.next_seq(), 1);
+        spool.store(1, b"y").unwrap();
+        // A stray partial write and an unrelated file don't count.
+        fs::write(dir.path().join("00000000000000000009.tmp"), b"z").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"z").unwrap();
+        drop(spool);
+
+        let mut reopened = TelemetrySpool::open(dir.path(), 1024).unwrap();
+        assert_eq!(reopened.next_seq(), 2);
+        assert_eq!(reopened.flush(|_| Ok(())).uploaded, 2);
+    }
+}
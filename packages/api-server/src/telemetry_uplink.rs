@@ -0,0 +1,752 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `TelemetryUplink` processor — push telemetry for devices that can't
+//! be scraped.
+//!
+//! Every `interval_ms` an uplink thread samples the graph (processor
+//! states and metrics, per-link frame counters), drains the runtime events
+//! a PUBSUB listener batched since the last tick, and encodes both as one
+//! CBOR [`TelemetryEnvelope`]. The envelope is spooled to disk first and
+//! then everything spooled is pushed oldest-first to the collector — an
+//! HTTP(S) POST or an MQTT QoS 1 publish — so an offline device catches up
+//! once the link returns. Each upload round is reported on
+//! [`TELEMETRY_UPLOAD_TOPIC`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::json_schema::GraphResponse;
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::mqtt::{MqttBroker, MqttSession};
+use crate::telemetry_spool::{SpoolFlush, TelemetrySpool};
+use crate::webhook_notifier::webhook_event_name;
+
+/// Custom-event topic each upload round is published on. Never batched
+/// into an envelope, even under a `*` selection.
+pub const TELEMETRY_UPLOAD_TOPIC: &str = "telemetry:upload";
+
+/// Version stamped into every envelope's `v` key.
+pub const TELEMETRY_ENVELOPE_VERSION: u32 = 1;
+
+/// Event-name selector matching every event.
+const TELEMETRY_SELECT_ALL_EVENTS: &str = "*";
+
+/// Spool location under the streamlib data dir when `spool_dir` is unset.
+const TELEMETRY_SPOOL_SUBDIR: &str = "telemetry";
+
+const DEFAULT_INTERVAL_MS: u32 = 60_000;
+const DEFAULT_MAX_EVENTS_PER_BATCH: u32 = 256;
+const DEFAULT_MAX_SPOOL_BYTES: u32 = 16 * 1024 * 1024;
+const DEFAULT_TIMEOUT_MS: u32 = 10_000;
+
+/// One upload unit. Keys are single letters to keep the CBOR small on
+/// metered links; the collector decodes them with this schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryEnvelope {
+    /// [`TELEMETRY_ENVELOPE_VERSION`].
+    #[serde(rename = "v")]
+    pub version: u32,
+    #[serde(rename = "rid")]
+    pub runtime_id: String,
+    /// Spool sequence number; `(runtime_id, seq)` identifies an envelope,
+    /// so a collector can drop the duplicate of a re-sent upload.
+    pub seq: u64,
+    /// Unix-epoch milliseconds the batch window opened.
+    #[serde(rename = "t0")]
+    pub window_start_ms: u64,
+    /// Unix-epoch milliseconds the batch window closed (sample time).
+    #[serde(rename = "t1")]
+    pub window_end_ms: u64,
+    #[serde(rename = "p")]
+    pub processors: Vec<ProcessorSample>,
+    #[serde(rename = "l")]
+    pub links: Vec<LinkSample>,
+    #[serde(rename = "e")]
+    pub events: Vec<EventSample>,
+    /// Selected events past `max_events_per_batch` in this window.
+    #[serde(rename = "ed")]
+    pub events_dropped: u64,
+}
+
+/// A processor's state at sample time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessorSample {
+    #[serde(rename = "i")]
+    pub id: String,
+    /// `@org/package/Type`.
+    #[serde(rename = "t")]
+    pub processor_type: String,
+    /// Lifecycle state, e.g. `Running`.
+    #[serde(rename = "s")]
+    pub state: String,
+    /// The processor's `metrics` component, when it reports one.
+    #[serde(rename = "m", default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<serde_json::Value>,
+}
+
+/// A link's frame counters at sample time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkSample {
+    #[serde(rename = "i")]
+    pub id: String,
+    /// `processor_id.port_name` of the source output.
+    #[serde(rename = "s")]
+    pub source: String,
+    /// `processor_id.port_name` of the target input.
+    #[serde(rename = "d")]
+    pub target: String,
+    /// Cumulative frames delivered.
+    #[serde(rename = "fd")]
+    pub frames_delivered: u64,
+    /// Cumulative frames dropped.
+    #[serde(rename = "fx")]
+    pub frames_dropped: u64,
+    /// Drop rate over the link's sliding window.
+    #[serde(rename = "r")]
+    pub window_drop_rate: f64,
+    #[serde(rename = "c")]
+    pub congested: bool,
+}
+
+/// A selected runtime event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventSample {
+    /// Event name, as [`webhook_event_name`] reports it.
+    #[serde(rename = "n")]
+    pub name: String,
+    /// Unix-epoch milliseconds the event was observed.
+    #[serde(rename = "t")]
+    pub at_ms: u64,
+    #[serde(rename = "d")]
+    pub event: serde_json::Value,
+}
+
+/// Encode `envelope` as CBOR.
+pub fn encode_telemetry_envelope(envelope: &TelemetryEnvelope) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(envelope, &mut bytes)
+        .map_err(|e| Error::Runtime(format!("TelemetryUplink: encode envelope: {e}")))?;
+    Ok(bytes)
+}
+
+/// Decode a CBOR envelope — the collector-side counterpart of
+/// [`encode_telemetry_envelope`].
+pub fn decode_telemetry_envelope(bytes: &[u8]) -> Result<TelemetryEnvelope> {
+    ciborium::from_reader(bytes)
+        .map_err(|e| Error::Runtime(format!("TelemetryUplink: decode envelope: {e}")))
+}
+
+/// Processor and link samples from a `to_json()` graph snapshot.
+pub(crate) fn sample_graph(graph: &GraphResponse) -> (Vec<ProcessorSample>, Vec<LinkSample>) {
+    let processors = graph
+        .nodes
+        .iter()
+        .map(|node| ProcessorSample {
+            id: node.id.clone(),
+            processor_type: format!(
+                "@{}/{}/{}",
+                node.processor_type.org, node.processor_type.package, node.processor_type.type_name
+            ),
+            state: node
+                .components
+                .get("state")
+                .and_then(|state| state.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            metrics: node.components.get("metrics").cloned(),
+        })
+        .collect();
+    let links = graph
+        .links
+        .iter()
+        .map(|link| {
+            let drops = link.components.get("frame_drops");
+            let counter = |key: &str| {
+                drops
+                    .and_then(|drops| drops.get(key))
+                    .and_then(|value| value.as_u64())
+                    .unwrap_or_default()
+            };
+            LinkSample {
+                id: link.id.clone(),
+                source: format!("{}.{}", link.source.processor_id, link.source.port_name),
+                target: format!("{}.{}", link.target.processor_id, link.target.port_name),
+                frames_delivered: counter("frames_delivered"),
+                frames_dropped: counter("frames_dropped"),
+                window_drop_rate: drops
+                    .and_then(|drops| drops.get("window_drop_rate"))
+                    .and_then(|value| value.as_f64())
+                    .unwrap_or_default(),
+                congested: drops
+                    .and_then(|drops| drops.get("congested"))
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+    (processors, links)
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Selected events awaiting the next envelope.
+#[derive(Debug, Default)]
+struct TelemetryEventBatch {
+    events: Vec<EventSample>,
+    dropped: u64,
+}
+
+/// PUBSUB listener that filters by event name into the shared batch.
+struct TelemetryEventSelector {
+    selected_event_names: Vec<String>,
+    max_events_per_batch: usize,
+    batch: Arc<Mutex<TelemetryEventBatch>>,
+}
+
+impl TelemetryEventSelector {
+    fn selects(&self, event_name: &str) -> bool {
+        event_name != TELEMETRY_UPLOAD_TOPIC
+            && self
+                .selected_event_names
+                .iter()
+                .any(|name| name == TELEMETRY_SELECT_ALL_EVENTS || name == event_name)
+    }
+}
+
+impl EventListener for TelemetryEventSelector {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        let name = webhook_event_name(event);
+        if !self.selects(&name) {
+            return Ok(());
+        }
+        let mut batch = self.batch.lock();
+        if batch.events.len() >= self.max_events_per_batch {
+            batch.dropped += 1;
+            return Ok(());
+        }
+        let event = serde_json::to_value(event)
+            .map_err(|e| Error::Runtime(format!("TelemetryUplink: serialize event: {e}")))?;
+        batch.events.push(EventSample {
+            name,
+            at_ms: unix_millis(),
+            event,
+        });
+        Ok(())
+    }
+}
+
+/// Where envelopes go.
+enum TelemetryCollector {
+    Http {
+        agent: ureq::Agent,
+        url: String,
+    },
+    Mqtt {
+        broker: MqttBroker,
+        topic: String,
+        client_id: String,
+        timeout: Duration,
+        /// Open for the duration of one flush.
+        session: Option<MqttSession>,
+    },
+}
+
+impl TelemetryCollector {
+    fn from_url(
+        url: &str,
+        mqtt_topic: String,
+        runtime_id: &str,
+        timeout: Duration,
+    ) -> Result<Self> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Ok(Self::Http {
+                agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+                url: url.to_string(),
+            });
+        }
+        if url.starts_with("mqtt://") {
+            let broker = MqttBroker::parse(url)
+                .map_err(|e| Error::Config(format!("TelemetryUplink: {e}")))?;
+            return Ok(Self::Mqtt {
+                broker,
+                topic: mqtt_topic,
+                client_id: format!("streamlib-{runtime_id}"),
+                timeout,
+                session: None,
+            });
+        }
+        Err(Error::Config(format!(
+            "TelemetryUplink: unsupported collector URL `{url}` — expected http://, https:// or mqtt://"
+        )))
+    }
+
+    fn upload(&mut self, envelope: &[u8]) -> std::result::Result<(), String> {
+        match self {
+            Self::Http { agent, url } => match agent
+                .post(url)
+                .set("Content-Type", "application/cbor")
+                .send_bytes(envelope)
+            {
+                Ok(_) => Ok(()),
+                Err(ureq::Error::Status(code, _)) => Err(format!("collector responded {code}")),
+                Err(ureq::Error::Transport(transport)) => Err(transport.to_string()),
+            },
+            Self::Mqtt {
+                broker,
+                topic,
+                client_id,
+                timeout,
+                session,
+            } => {
+                if session.is_none() {
+                    *session = Some(
+                        MqttSession::connect(broker, client_id, *timeout)
+                            .map_err(|e| format!("MQTT connect: {e}"))?,
+                    );
+                }
+                let result = session
+                    .as_mut()
+                    .expect("session opened above")
+                    .publish(topic, envelope)
+                    .map_err(|e| format!("MQTT publish: {e}"));
+                if result.is_err() {
+                    session.take();
+                }
+                result
+            }
+        }
+    }
+
+    /// Close whatever a flush opened.
+    fn finish_flush(&mut self) {
+        if let Self::Mqtt { session, .. } = self
+            && let Some(session) = session.take()
+        {
+            session.disconnect();
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Http { url, .. } => url.clone(),
+            Self::Mqtt { broker, topic, .. } => {
+                format!("mqtt://{}:{} topic {topic}", broker.host, broker.port)
+            }
+        }
+    }
+}
+
+/// State owned by the uplink thread.
+struct TelemetryUplinkWorker {
+    runtime: Arc<dyn RuntimeOperations>,
+    runtime_id: String,
+    processor_id: Option<String>,
+    interval: Duration,
+    batch: Arc<Mutex<TelemetryEventBatch>>,
+    spool: TelemetrySpool,
+    collector: TelemetryCollector,
+    window_start_ms: u64,
+}
+
+impl TelemetryUplinkWorker {
+    /// Tick every interval until the stop sender is dropped, then send a
+    /// final envelope so a clean shutdown loses nothing.
+    fn run(mut self, stop: mpsc::Receiver<()>) {
+        loop {
+            let stopping = matches!(
+                stop.recv_timeout(self.interval),
+                Err(mpsc::RecvTimeoutError::Disconnected) | Ok(())
+            );
+            self.tick();
+            if stopping {
+                return;
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        let window_end_ms = unix_millis();
+        let (processors, links) = match self.runtime.to_json().and_then(|value| {
+            serde_json::from_value::<GraphResponse>(value)
+                .map_err(|e| Error::Runtime(format!("parse graph: {e}")))
+        }) {
+            Ok(graph) => sample_graph(&graph),
+            Err(e) => {
+                tracing::warn!(error = %e, "TelemetryUplink: graph sample failed");
+                (Vec::new(), Vec::new())
+            }
+        };
+        let batch = std::mem::take(&mut *self.batch.lock());
+        let envelope = TelemetryEnvelope {
+            version: TELEMETRY_ENVELOPE_VERSION,
+            runtime_id: self.runtime_id.clone(),
+            seq: self.spool.next_seq(),
+            window_start_ms: self.window_start_ms,
+            window_end_ms,
+            processors,
+            links,
+            events: batch.events,
+            events_dropped: batch.dropped,
+        };
+        self.window_start_ms = window_end_ms;
+
+        let mut evicted = 0;
+        match encode_telemetry_envelope(&envelope).and_then(|bytes| {
+            self.spool.store(envelope.seq, &bytes).map_err(|e| {
+                Error::Runtime(format!("spool to {}: {e}", self.spool.dir().display()))
+            })
+        }) {
+            Ok(count) => evicted = count,
+            Err(e) => {
+                tracing::warn!(seq = envelope.seq, error = %e, "TelemetryUplink: envelope lost")
+            }
+        }
+        if evicted > 0 {
+            tracing::warn!(
+                evicted,
+                "TelemetryUplink: spool full, discarded oldest envelopes"
+            );
+        }
+
+        let collector = &mut self.collector;
+        let flush = self.spool.flush(|bytes| collector.upload(bytes));
+        collector.finish_flush();
+        self.report(envelope.seq, evicted, &flush);
+    }
+
+    fn report(&self, seq: u64, evicted: u64, flush: &SpoolFlush) {
+        match &flush.error {
+            None => tracing::debug!(
+                seq,
+                uploaded = flush.uploaded,
+                "TelemetryUplink: envelopes uploaded"
+            ),
+            Some(error) => tracing::info!(
+                seq,
+                uploaded = flush.uploaded,
+                pending = flush.pending,
+                %error,
+                "TelemetryUplink: collector unreachable, envelopes spooled"
+            ),
+        }
+        PUBSUB.publish(
+            TELEMETRY_UPLOAD_TOPIC,
+            &Event::custom(
+                TELEMETRY_UPLOAD_TOPIC,
+                serde_json::json!({
+                    "processor_id": self.processor_id,
+                    "collector": self.collector.describe(),
+                    "seq": seq,
+                    "uploaded": flush.uploaded,
+                    "pending": flush.pending,
+                    "evicted": evicted,
+                    "error": flush.error,
+                }),
+            ),
+        );
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/TelemetryUplink",
+    description = "Pushes processor states, link frame counters and selected runtime events to a collector as compact CBOR envelopes over HTTP(S) or MQTT, spooling to disk while offline",
+    execution = manual,
+    config = crate::_generated_::TelemetryUplinkConfig,
+)]
+pub struct TelemetryUplinkProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    runtime_id: Option<String>,
+    processor_id: Option<String>,
+    /// Held so the PUBSUB subscription (a weak reference) stays live.
+    selector: Option<Arc<Mutex<dyn EventListener>>>,
+    /// Dropped to stop the uplink thread.
+    stop_tx: Option<mpsc::Sender<()>>,
+    uplink_thread: Option<JoinHandle<()>>,
+}
+
+impl ManualProcessor for TelemetryUplinkProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.url.is_empty() {
+            return Err(Error::Config(
+                "TelemetryUplink: `url` must not be empty".into(),
+            ));
+        }
+        if self.config.interval_ms == Some(0) {
+            return Err(Error::Config(
+                "TelemetryUplink: `interval_ms` must be positive".into(),
+            ));
+        }
+        self.runtime = Some(ctx.runtime());
+        self.runtime_id = Some(ctx.runtime_id().to_string());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| Error::Runtime("TelemetryUplink: started before setup".into()))?;
+        let runtime_id = self.runtime_id.clone().unwrap_or_default();
+        let timeout =
+            Duration::from_millis(self.config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS) as u64);
+        let mqtt_topic = self
+            .config
+            .mqtt_topic
+            .clone()
+            .unwrap_or_else(|| format!("streamlib/telemetry/{runtime_id}"));
+        let collector =
+            TelemetryCollector::from_url(&self.config.url, mqtt_topic, &runtime_id, timeout)?;
+        let spool_dir = self.config.spool_dir.as_ref().map_or_else(
+            || streamlib::sdk::home::get_streamlib_data_dir().join(TELEMETRY_SPOOL_SUBDIR),
+            PathBuf::from,
+        );
+        let spool = TelemetrySpool::open(
+            &spool_dir,
+            self.config
+                .max_spool_bytes
+                .unwrap_or(DEFAULT_MAX_SPOOL_BYTES) as u64,
+        )
+        .map_err(|e| {
+            Error::Config(format!(
+                "TelemetryUplink: open spool {}: {e}",
+                spool_dir.display()
+            ))
+        })?;
+
+        let batch = Arc::new(Mutex::new(TelemetryEventBatch::default()));
+        let collector_description = collector.describe();
+        let worker = TelemetryUplinkWorker {
+            runtime,
+            runtime_id,
+            processor_id: self.processor_id.clone(),
+            interval: Duration::from_millis(
+                self.config.interval_ms.unwrap_or(DEFAULT_INTERVAL_MS) as u64
+            ),
+            batch: Arc::clone(&batch),
+            spool,
+            collector,
+            window_start_ms: unix_millis(),
+        };
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let uplink_thread = std::thread::Builder::new()
+            .name("telemetry-uplink".into())
+            .spawn(move || worker.run(stop_rx))
+            .map_err(|e| Error::Runtime(format!("TelemetryUplink: spawn uplink thread: {e}")))?;
+
+        let events = self.config.events.clone().unwrap_or_default();
+        if !events.is_empty() {
+            let selector: Arc<Mutex<dyn EventListener>> =
+                Arc::new(Mutex::new(TelemetryEventSelector {
+                    selected_event_names: events.clone(),
+                    max_events_per_batch: self
+                        .config
+                        .max_events_per_batch
+                        .unwrap_or(DEFAULT_MAX_EVENTS_PER_BATCH)
+                        as usize,
+                    batch,
+                }));
+            PUBSUB.subscribe(topics::ALL, Arc::clone(&selector));
+            self.selector = Some(selector);
+        }
+        self.stop_tx = Some(stop_tx);
+        self.uplink_thread = Some(uplink_thread);
+
+        tracing::info!(
+            collector = %collector_description,
+            spool = %spool_dir.display(),
+            ?events,
+            "TelemetryUplink pushing runtime telemetry"
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.selector.take();
+        // Dropping the sender wakes the uplink thread for a final envelope.
+        self.stop_tx.take();
+        if let Some(thread) = self.uplink_thread.take()
+            && thread.join().is_err()
+        {
+            tracing::warn!("TelemetryUplink: uplink thread panicked");
+        }
+        tracing::info!("TelemetryUplink stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use streamlib::sdk::pubsub::RuntimeEvent;
+
+    fn selector(events: &[&str], max_events_per_batch: usize) -> TelemetryEventSelector {
+        TelemetryEventSelector {
+            selected_event_names: events.iter().map(|name| name.to_string()).collect(),
+            max_events_per_batch,
+            batch: Arc::default(),
+        }
+    }
+
+    #[test]
+    fn envelope_round_trips_through_cbor_with_short_keys() {
+        let envelope = TelemetryEnvelope {
+            version: TELEMETRY_ENVELOPE_VERSION,
+            runtime_id: "R1".into(),
+            seq: 7,
+            window_start_ms: 1_000,
+            window_end_ms: 61_000,
+            processors: vec![ProcessorSample {
+                id: "camera".into(),
+                processor_type: "@tatolab/core/Camera".into(),
+                state: "Running".into(),
+                metrics: None,
+            }],
+            links: vec![LinkSample {
+                id: "L1".into(),
+                source: "camera.video_out".into(),
+                target: "encoder.video_in".into(),
+                frames_delivered: 3_600,
+                frames_dropped: 12,
+                window_drop_rate: 0.25,
+                congested: false,
+            }],
+            events: vec![EventSample {
+                name: "RuntimeStarted".into(),
+                at_ms: 1_500,
+                event: serde_json::json!({"RuntimeGlobal": "RuntimeStarted"}),
+            }],
+            events_dropped: 2,
+        };
+        let bytes = encode_telemetry_envelope(&envelope).unwrap();
+        assert_eq!(decode_telemetry_envelope(&bytes).unwrap(), envelope);
+
+        let raw: ciborium::Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+        let keys: Vec<String> = raw
+            .as_map()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.as_text().unwrap().to_string())
+            .collect();
+        assert_eq!(keys, ["v", "rid", "seq", "t0", "t1", "p", "l", "e", "ed"]);
+        // Smaller than the same data as JSON.
+        assert!(bytes.len() < serde_json::to_vec(&envelope).unwrap().len());
+    }
+
+    #[test]
+    fn graph_sample_reads_states_and_frame_counters() {
+        let graph: GraphResponse = serde_json::from_value(serde_json::json!({
+            "nodes": [{
+                "id": "camera",
+                "type": {
+                    "org": "tatolab",
+                    "package": "core",
+                    "type": "Camera",
+                    "version": {"major": 1, "minor": 0, "patch": 0}
+                },
+                "display_name": "Camera",
+                "ports": {"inputs": [], "outputs": []},
+                "components": {"state": "Running"}
+            }],
+            "links": [{
+                "id": "L1",
+                "source": {"processor_id": "camera", "port_name": "video_out"},
+                "target": {"processor_id": "encoder", "port_name": "video_in"},
+                "components": {"frame_drops": {
+                    "frames_delivered": 100,
+                    "frames_dropped": 5,
+                    "window_secs": 5,
+                    "window_frames_dropped": 5,
+                    "window_drop_rate": 0.05,
+                    "congested": false
+                }}
+            }]
+        }))
+        .unwrap();
+        let (processors, links) = sample_graph(&graph);
+        assert_eq!(processors[0].processor_type, "@tatolab/core/Camera");
+        assert_eq!(processors[0].state, "Running");
+        assert_eq!(links[0].source, "camera.video_out");
+        assert_eq!(links[0].target, "encoder.video_in");
+        assert_eq!(
+            (links[0].frames_delivered, links[0].frames_dropped),
+            (100, 5)
+        );
+        assert_eq!(links[0].window_drop_rate, 0.05);
+    }
+
+    #[test]
+    fn selector_caps_the_batch_and_skips_its_own_reports() {
+        let mut selector = selector(&[TELEMETRY_SELECT_ALL_EVENTS], 2);
+        assert!(!selector.selects(TELEMETRY_UPLOAD_TOPIC));
+        for _ in 0..3 {
+            selector
+                .on_event(&Event::RuntimeGlobal(RuntimeEvent::RuntimeStarted))
+                .unwrap();
+        }
+        selector
+            .on_event(&Event::custom(
+                TELEMETRY_UPLOAD_TOPIC,
+                serde_json::json!({}),
+            ))
+            .unwrap();
+        let batch = selector.batch.lock();
+        assert_eq!(batch.events.len(), 2);
+        assert_eq!(batch.events[0].name, "RuntimeStarted");
+        assert_eq!(batch.dropped, 1);
+    }
+
+    #[test]
+    fn collector_url_scheme_picks_the_transport() {
+        let timeout = Duration::from_secs(1);
+        assert!(matches!(
+            TelemetryCollector::from_url(
+                "https://collector.example/ingest",
+                String::new(),
+                "R1",
+                timeout
+            ),
+            Ok(TelemetryCollector::Http { .. })
+        ));
+        let Ok(TelemetryCollector::Mqtt {
+            broker, client_id, ..
+        }) = TelemetryCollector::from_url("mqtt://broker:1884", "t".into(), "R1", timeout)
+        else {
+            panic!("mqtt:// must select the MQTT transport");
+        };
+        assert_eq!((broker.host.as_str(), broker.port), ("broker", 1884));
+        assert_eq!(client_id, "streamlib-R1");
+        assert!(matches!(
+            TelemetryCollector::from_url("mqtts://broker", String::new(), "R1", timeout),
+            Err(Error::Config(_))
+        ));
+    }
+}
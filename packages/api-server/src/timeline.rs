@@ -0,0 +1,598 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `Timeline` processor — keyframed parameter animation on the media
+//! clock, for stingers and lower thirds that would otherwise need a custom
+//! processor.
+//!
+//! Each track drives one processor config parameter through keyframes.
+//! Every `interval_ms` a timeline thread reads the media clock, samples
+//! each track at the timeline position and sends the values that changed
+//! through the runtime's parameter API
+//! ([`RuntimeOperations::set_parameter_async`]). As in the compositor's
+//! layer animations, a keyframe's interpolation shapes the segment
+//! *arriving* at it, and `repeat` decides what happens past the last
+//! keyframe of the longest track.
+//!
+//! Starting the processor plays the timeline from the top; pausing holds
+//! it where it is. A `once` timeline that reaches its end publishes
+//! [`TIMELINE_FINISHED_TOPIC`], so a stinger can chain the next cue.
+
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde_json::json;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::media_clock::MediaClock;
+use streamlib::sdk::parameter_automation::ParameterChange;
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, PUBSUB};
+use streamlib::sdk::runtime::RuntimeOperations;
+
+use crate::_generated_::TimelineConfig;
+use crate::_generated_::tatolab__api_server::timeline_config::{
+    Interpolation, Repeat as ConfigRepeat, Track,
+};
+
+/// Custom-event topic a finished `once` timeline is reported on.
+pub const TIMELINE_FINISHED_TOPIC: &str = "timeline:finished";
+
+const DEFAULT_INTERVAL_MS: u32 = 20;
+
+/// Bisection steps solving a bezier's x for time — 2^-40 is far below a
+/// nanosecond over any keyframe span.
+const BEZIER_SOLVE_STEPS: usize = 40;
+
+/// A CSS cubic-bezier timing function through `(0, 0)`, `(x1, y1)`,
+/// `(x2, y2)` and `(1, 1)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CubicBezier {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl CubicBezier {
+    /// CSS `ease`.
+    const EASE: Self = Self {
+        x1: 0.25,
+        y1: 0.1,
+        x2: 0.25,
+        y2: 1.0,
+    };
+
+    /// One coordinate of the curve at parameter `s`.
+    fn coordinate(p1: f64, p2: f64, s: f64) -> f64 {
+        let u = 1.0 - s;
+        3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s
+    }
+
+    /// Eased progress for linear progress `t` (0..1). With x1 and x2 in
+    /// 0..1 the curve's x is monotonic, so bisection finds the point.
+    fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..BEZIER_SOLVE_STEPS {
+            let mid = (lo + hi) / 2.0;
+            if Self::coordinate(self.x1, self.x2, mid) < t {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Self::coordinate(self.y1, self.y2, (lo + hi) / 2.0)
+    }
+}
+
+/// Shape of the segment arriving at a keyframe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    Linear,
+    Bezier(CubicBezier),
+    /// Keep the previous value, then jump at the keyframe.
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CurveKey {
+    at: Duration,
+    value: f64,
+    segment: Segment,
+}
+
+/// What the timeline does past its last keyframe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum TimelineRepeat {
+    #[default]
+    Once,
+    Loop,
+    PingPong,
+}
+
+/// One validated track.
+#[derive(Debug, Clone)]
+struct TimelineTrack {
+    processor_id: String,
+    parameter: String,
+    /// Sorted by time; never empty.
+    keys: Vec<CurveKey>,
+}
+
+impl TimelineTrack {
+    /// The track's value at timeline position `time`.
+    fn sample(&self, time: Duration) -> f64 {
+        let next = self.keys.partition_point(|key| key.at <= time);
+        if next == 0 {
+            return self.keys[0].value;
+        }
+        let from = &self.keys[next - 1];
+        let Some(to) = self.keys.get(next) else {
+            return from.value;
+        };
+        let progress = (time - from.at).as_secs_f64() / (to.at - from.at).as_secs_f64();
+        let eased = match to.segment {
+            Segment::Linear => progress,
+            Segment::Bezier(bezier) => bezier.apply(progress),
+            Segment::Hold => 0.0,
+        };
+        from.value + (to.value - from.value) * eased
+    }
+}
+
+/// The validated config: every track and how the timeline repeats.
+#[derive(Debug, Clone, Default)]
+struct Timeline {
+    tracks: Vec<TimelineTrack>,
+    repeat: TimelineRepeat,
+    /// Time of the last keyframe of any track.
+    duration: Duration,
+}
+
+impl Timeline {
+    /// Check each track and keyframe and sort the keyframes by time.
+    fn from_config(config: &TimelineConfig) -> Result<Self> {
+        if config.interval_ms == Some(0) {
+            return Err(Error::Config(
+                "Timeline: `interval_ms` must be positive".into(),
+            ));
+        }
+        let tracks = config
+            .tracks
+            .iter()
+            .map(compile_track)
+            .collect::<Result<Vec<_>>>()?;
+        let duration = tracks
+            .iter()
+            .filter_map(|track| track.keys.last())
+            .map(|key| key.at)
+            .max()
+            .unwrap_or(Duration::ZERO);
+        let repeat = match config.repeat {
+            Some(ConfigRepeat::Once) | None => TimelineRepeat::Once,
+            Some(ConfigRepeat::Loop) => TimelineRepeat::Loop,
+            Some(ConfigRepeat::PingPong) => TimelineRepeat::PingPong,
+        };
+        Ok(Self {
+            tracks,
+            repeat,
+            duration,
+        })
+    }
+
+    /// Timeline position `elapsed` after it started playing.
+    fn position(&self, elapsed: Duration) -> Duration {
+        if self.duration.is_zero() {
+            return Duration::ZERO;
+        }
+        let duration = self.duration.as_nanos();
+        let nanos = |nanos: u128| Duration::from_nanos(nanos as u64);
+        match self.repeat {
+            TimelineRepeat::Once => elapsed.min(self.duration),
+            TimelineRepeat::Loop => nanos(elapsed.as_nanos() % duration),
+            TimelineRepeat::PingPong => {
+                let phase = elapsed.as_nanos() % (duration * 2);
+                nanos(if phase > duration {
+                    duration * 2 - phase
+                } else {
+                    phase
+                })
+            }
+        }
+    }
+
+    /// Whether nothing changes after `elapsed`.
+    fn is_finished(&self, elapsed: Duration) -> bool {
+        self.repeat == TimelineRepeat::Once && elapsed >= self.duration
+    }
+}
+
+fn compile_track(track: &Track) -> Result<TimelineTrack> {
+    let name = || format!("{} {}", track.processor_id, track.parameter);
+    if track.processor_id.is_empty() || track.parameter.trim_start_matches('/').is_empty() {
+        return Err(Error::Config(format!(
+            "Timeline: track `{}` needs a processor_id and a parameter",
+            name()
+        )));
+    }
+    if track.keyframes.is_empty() {
+        return Err(Error::Config(format!(
+            "Timeline: track `{}` has no keyframes",
+            name()
+        )));
+    }
+    let mut keys = track
+        .keyframes
+        .iter()
+        .map(|keyframe| {
+            let segment = match keyframe.interpolation {
+                Some(Interpolation::Linear) | None => Segment::Linear,
+                Some(Interpolation::Hold) => Segment::Hold,
+                Some(Interpolation::Bezier) => match keyframe.bezier.as_deref() {
+                    None => Segment::Bezier(CubicBezier::EASE),
+                    Some(&[x1, y1, x2, y2])
+                        if (0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2) =>
+                    {
+                        Segment::Bezier(CubicBezier {
+                            x1: f64::from(x1),
+                            y1: f64::from(y1),
+                            x2: f64::from(x2),
+                            y2: f64::from(y2),
+                        })
+                    }
+                    Some(points) => {
+                        return Err(Error::Config(format!(
+                            "Timeline: track `{}` keyframe at {} ms: `bezier` must be \
+                             [x1, y1, x2, y2] with x1 and x2 in 0..1, got {points:?}",
+                            name(),
+                            keyframe.at_ms
+                        )));
+                    }
+                },
+            };
+            Ok(CurveKey {
+                at: Duration::from_millis(u64::from(keyframe.at_ms)),
+                value: keyframe.value,
+                segment,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    keys.sort_by_key(|key| key.at);
+    Ok(TimelineTrack {
+        processor_id: track.processor_id.clone(),
+        parameter: track.parameter.clone(),
+        keys,
+    })
+}
+
+/// State owned by the timeline thread.
+struct TimelineWorker {
+    runtime: Arc<dyn RuntimeOperations>,
+    /// Drives the async runtime operations from this plain thread.
+    executor: tokio::runtime::Runtime,
+    timeline: Timeline,
+    processor_id: Option<String>,
+    /// Media-clock time the timeline was at position zero.
+    origin: Duration,
+    interval: Duration,
+    /// Value each track last delivered.
+    sent: Vec<Option<f64>>,
+    /// Whether each track's last send failed, so a missing target is
+    /// logged once rather than every interval.
+    failing: Vec<bool>,
+}
+
+impl TimelineWorker {
+    fn elapsed(&self) -> Duration {
+        MediaClock::now().saturating_sub(self.origin)
+    }
+
+    /// Play until the stop sender is dropped, returning how far the
+    /// timeline got.
+    fn run(mut self, stop: mpsc::Receiver<()>) -> Duration {
+        loop {
+            let elapsed = self.elapsed();
+            self.send_values(self.timeline.position(elapsed));
+            if self.timeline.is_finished(elapsed) {
+                self.publish_finished();
+                // Hold the last values until stopped.
+                let _ = stop.recv();
+                return elapsed;
+            }
+            if !matches!(
+                stop.recv_timeout(self.interval),
+                Err(mpsc::RecvTimeoutError::Timeout)
+            ) {
+                return self.elapsed();
+            }
+        }
+    }
+
+    /// Send each track's value at `position` when it changed since the
+    /// last successful send.
+    fn send_values(&mut self, position: Duration) {
+        for (index, track) in self.timeline.tracks.iter().enumerate() {
+            let value = track.sample(position);
+            if self.sent[index] == Some(value) {
+                continue;
+            }
+            let change =
+                ParameterChange::new(track.processor_id.as_str(), &track.parameter, json!(value));
+            match self
+                .executor
+                .block_on(self.runtime.set_parameter_async(change))
+            {
+                Ok(()) => {
+                    self.sent[index] = Some(value);
+                    self.failing[index] = false;
+                }
+                Err(e) => {
+                    if !self.failing[index] {
+                        tracing::warn!(
+                            processor_id = %track.processor_id,
+                            parameter = %track.parameter,
+                            error = %e,
+                            "Timeline: parameter update failed"
+                        );
+                    }
+                    self.failing[index] = true;
+                }
+            }
+        }
+    }
+
+    fn publish_finished(&self) {
+        tracing::info!(
+            duration_ms = self.timeline.duration.as_millis(),
+            "Timeline: finished"
+        );
+        PUBSUB.publish(
+            TIMELINE_FINISHED_TOPIC,
+            &Event::custom(
+                TIMELINE_FINISHED_TOPIC,
+                json!({
+                    "processor_id": self.processor_id,
+                    "duration_ms": self.timeline.duration.as_millis() as u64,
+                }),
+            ),
+        );
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/Timeline",
+    description = "Plays keyframed curves (linear, bezier, hold) on the media clock into processor config parameters — pre-programmed stingers and lower-third animations without a custom processor",
+    execution = manual,
+    config = crate::_generated_::TimelineConfig,
+)]
+pub struct TimelineProcessor {
+    runtime: Option<Arc<dyn RuntimeOperations>>,
+    processor_id: Option<String>,
+    timeline: Timeline,
+    /// Timeline position to play from; where a pause left it.
+    position: Duration,
+    /// Dropped to stop the timeline thread.
+    stop_tx: Option<mpsc::Sender<()>>,
+    timeline_thread: Option<JoinHandle<Duration>>,
+}
+
+impl TimelineProcessor::Processor {
+    /// Play from `self.position` on a new timeline thread.
+    fn play(&mut self) -> Result<()> {
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| Error::Runtime("Timeline: started before setup".into()))?;
+        let executor = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| Error::Runtime(format!("Timeline: failed to build tokio runtime: {e}")))?;
+        let tracks = self.timeline.tracks.len();
+        let worker = TimelineWorker {
+            runtime,
+            executor,
+            timeline: self.timeline.clone(),
+            processor_id: self.processor_id.clone(),
+            origin: MediaClock::now().saturating_sub(self.position),
+            interval: Duration::from_millis(u64::from(
+                self.config.interval_ms.unwrap_or(DEFAULT_INTERVAL_MS),
+            )),
+            sent: vec![None; tracks],
+            failing: vec![false; tracks],
+        };
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let timeline_thread = std::thread::Builder::new()
+            .name("timeline".into())
+            .spawn(move || worker.run(stop_rx))
+            .map_err(|e| Error::Runtime(format!("Timeline: spawn timeline thread: {e}")))?;
+        self.stop_tx = Some(stop_tx);
+        self.timeline_thread = Some(timeline_thread);
+        Ok(())
+    }
+
+    /// Stop the timeline thread, keeping the position it reached.
+    fn halt(&mut self) {
+        self.stop_tx.take();
+        if let Some(thread) = self.timeline_thread.take() {
+            match thread.join() {
+                Ok(elapsed) => self.position = elapsed,
+                Err(_) => tracing::warn!("Timeline: timeline thread panicked"),
+            }
+        }
+    }
+}
+
+impl ManualProcessor for TimelineProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.timeline = Timeline::from_config(&self.config)?;
+        self.runtime = Some(ctx.runtime());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        self.halt();
+        tracing::info!(position_ms = self.position.as_millis(), "Timeline paused");
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if self.timeline.is_finished(self.position) {
+            return Ok(());
+        }
+        tracing::info!(position_ms = self.position.as_millis(), "Timeline resumed");
+        self.play()
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.position = Duration::ZERO;
+        self.play()?;
+        tracing::info!(
+            tracks = self.timeline.tracks.len(),
+            duration_ms = self.timeline.duration.as_millis(),
+            repeat = ?self.timeline.repeat,
+            "Timeline playing"
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.halt();
+        tracing::info!("Timeline stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_generated_::tatolab__api_server::timeline_config::TrackKeyframe;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn keyframe(at_ms: u32, value: f64, interpolation: Option<Interpolation>) -> TrackKeyframe {
+        TrackKeyframe {
+            at_ms,
+            value,
+            interpolation,
+            ..TrackKeyframe::default()
+        }
+    }
+
+    fn track(keyframes: Vec<TrackKeyframe>) -> Track {
+        Track {
+            processor_id: "lower_third".into(),
+            parameter: "/layers/1/x".into(),
+            keyframes,
+        }
+    }
+
+    fn timeline(tracks: Vec<Track>, repeat: Option<ConfigRepeat>) -> Result<Timeline> {
+        Timeline::from_config(&TimelineConfig {
+            tracks,
+            repeat,
+            ..TimelineConfig::default()
+        })
+    }
+
+    #[test]
+    fn linear_and_hold_segments() {
+        let timeline = timeline(
+            vec![track(vec![
+                keyframe(1000, 10.0, None),
+                keyframe(0, 0.0, None),
+                keyframe(2000, 20.0, Some(Interpolation::Hold)),
+            ])],
+            None,
+        )
+        .unwrap();
+        let track = &timeline.tracks[0];
+        assert_eq!(track.sample(ms(0)), 0.0);
+        assert_eq!(track.sample(ms(250)), 2.5);
+        // Hold keeps the previous value until its keyframe.
+        assert_eq!(track.sample(ms(1999)), 10.0);
+        assert_eq!(track.sample(ms(2000)), 20.0);
+        assert_eq!(track.sample(ms(9000)), 20.0);
+    }
+
+    #[test]
+    fn bezier_segments_ease() {
+        let ease = CubicBezier::EASE;
+        assert!(ease.apply(0.0).abs() < 1e-9);
+        assert!((ease.apply(1.0) - 1.0).abs() < 1e-9);
+        // CSS `ease` is at about 0.8024 half way through.
+        assert!((ease.apply(0.5) - 0.8024).abs() < 1e-3);
+        let linear = CubicBezier {
+            x1: 0.0,
+            y1: 0.0,
+            x2: 1.0,
+            y2: 1.0,
+        };
+        assert!((linear.apply(0.3) - 0.3).abs() < 1e-6);
+
+        let timeline = timeline(
+            vec![track(vec![
+                keyframe(0, 0.0, None),
+                keyframe(1000, 100.0, Some(Interpolation::Bezier)),
+            ])],
+            None,
+        )
+        .unwrap();
+        assert!((timeline.tracks[0].sample(ms(500)) - 80.24).abs() < 0.1);
+    }
+
+    #[test]
+    fn repeat_modes_map_elapsed_time_to_positions() {
+        let ramp = || track(vec![keyframe(0, 0.0, None), keyframe(1000, 1.0, None)]);
+        let once = timeline(vec![ramp()], None).unwrap();
+        assert_eq!(once.position(ms(1500)), ms(1000));
+        assert!(!once.is_finished(ms(999)));
+        assert!(once.is_finished(ms(1000)));
+
+        let looped = timeline(vec![ramp()], Some(ConfigRepeat::Loop)).unwrap();
+        assert_eq!(looped.position(ms(1250)), ms(250));
+        assert!(!looped.is_finished(ms(5000)));
+
+        let ping_pong = timeline(vec![ramp()], Some(ConfigRepeat::PingPong)).unwrap();
+        assert_eq!(ping_pong.position(ms(1250)), ms(750));
+        assert_eq!(ping_pong.position(ms(2250)), ms(250));
+    }
+
+    #[test]
+    fn the_longest_track_sets_the_duration() {
+        let short = track(vec![keyframe(0, 0.0, None), keyframe(500, 1.0, None)]);
+        let long = track(vec![keyframe(3000, 1.0, None)]);
+        let timeline = timeline(vec![short, long], Some(ConfigRepeat::Loop)).unwrap();
+        assert_eq!(timeline.duration, ms(3000));
+        // The short track holds until the timeline wraps.
+        assert_eq!(timeline.tracks[0].sample(timeline.position(ms(2000))), 1.0);
+        assert_eq!(timeline.tracks[0].sample(timeline.position(ms(3250))), 0.5);
+    }
+
+    #[test]
+    fn invalid_tracks_are_config_errors() {
+        assert!(timeline(vec![track(Vec::new())], None).is_err());
+        let mut unnamed = track(vec![keyframe(0, 0.0, None)]);
+        unnamed.parameter = "/".into();
+        assert!(timeline(vec![unnamed], None).is_err());
+        let mut bad_bezier = keyframe(1000, 1.0, Some(Interpolation::Bezier));
+        bad_bezier.bezier = Some(vec![1.5, 0.0, 0.5, 1.0]);
+        assert!(timeline(vec![track(vec![bad_bezier])], None).is_err());
+        assert!(
+            Timeline::from_config(&TimelineConfig {
+                interval_ms: Some(0),
+                ..TimelineConfig::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! TLS termination for the control plane.
+//!
+//! With `tls_cert_path` / `tls_key_path` set, the bound TCP listener is
+//! wrapped in a [`TlsListener`]: a background task accepts connections and
+//! runs each handshake on its own task (bounded by [`HANDSHAKE_TIMEOUT`]),
+//! so one stalled client can't hold up the accept loop. `axum::serve` only
+//! ever sees finished TLS streams.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use streamlib::sdk::error::{Error, Result};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+
+/// How long a client gets to finish its handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Finished handshakes waiting for `axum::serve` to pick them up.
+const ACCEPTED_QUEUE_DEPTH: usize = 64;
+
+/// Pause after a failed `accept()` (e.g. out of file descriptors) before
+/// trying again, instead of spinning.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Build the acceptor from a PEM certificate chain and private key.
+pub(crate) fn load_acceptor(cert_path: &str, key_path: &str) -> Result<TlsAcceptor> {
+    let chain = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| {
+            Error::Configuration(format!(
+                "ApiServer: can't read TLS certificate '{cert_path}': {e}"
+            ))
+        })?;
+    if chain.is_empty() {
+        return Err(Error::Configuration(format!(
+            "ApiServer: '{cert_path}' holds no certificate"
+        )));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|e| {
+        Error::Configuration(format!(
+            "ApiServer: can't read TLS private key '{key_path}': {e}"
+        ))
+    })?;
+    let mut config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| Error::Runtime(format!("ApiServer: TLS setup failed: {e}")))?
+    .with_no_client_auth()
+    .with_single_cert(chain, key)
+    .map_err(|e| {
+        Error::Configuration(format!(
+            "ApiServer: '{key_path}' doesn't match '{cert_path}': {e}"
+        ))
+    })?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// An `axum::serve` listener yielding TLS streams.
+pub(crate) struct TlsListener {
+    accepted: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+    local_addr: SocketAddr,
+}
+
+impl TlsListener {
+    /// Start accepting on `listener`. Must be called inside a tokio runtime;
+    /// the accept task exits once the listener is dropped.
+    pub(crate) fn spawn(listener: TcpListener, acceptor: TlsAcceptor) -> Result<Self> {
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| Error::Runtime(format!("ApiServer: listener address: {e}")))?;
+        let (tx, accepted) = mpsc::channel(ACCEPTED_QUEUE_DEPTH);
+        tokio::spawn(accept_loop(listener, acceptor, tx));
+        Ok(Self {
+            accepted,
+            local_addr,
+        })
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    tx: mpsc::Sender<(TlsStream<TcpStream>, SocketAddr)>,
+) {
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = tx.closed() => return,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(error) => {
+                    tracing::warn!(%error, "ApiServer: accept failed");
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            },
+        };
+        let acceptor = acceptor.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(tls)) => {
+                    let _ = tx.send((tls, peer)).await;
+                }
+                Ok(Err(error)) => {
+                    tracing::debug!(%error, %peer, "ApiServer: TLS handshake failed");
+                }
+                Err(_) => tracing::debug!(%peer, "ApiServer: TLS handshake timed out"),
+            }
+        });
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.accepted.recv().await {
+            Some(accepted) => accepted,
+            // The accept task only ends once this listener is gone, so this
+            // arm is unreachable in practice; park rather than spin.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
@@ -0,0 +1,504 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `WebhookNotifier` processor — forwards selected runtime events to an
+//! HTTP endpoint as signed JSON POSTs.
+//!
+//! A PUBSUB listener filters events by name and hands them to a dedicated
+//! delivery thread over a bounded queue, so a slow or unreachable endpoint
+//! never stalls the publisher; once [`WEBHOOK_QUEUE_CAPACITY`] events are
+//! waiting, the oldest is dropped. The delivery thread retries with
+//! exponential backoff and reports every outcome on
+//! [`WEBHOOK_DELIVERY_TOPIC`] alongside running totals. `stop()` abandons
+//! whatever is still queued or mid-retry rather than waiting it out.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use parking_lot::{Condvar, Mutex};
+use sha2::Sha256;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::processors::ManualProcessor;
+use streamlib::sdk::pubsub::{Event, EventListener, PUBSUB, topics};
+
+/// Custom-event topic each delivery outcome is published on. Never delivered
+/// to the webhook itself, even under a `*` selection.
+pub const WEBHOOK_DELIVERY_TOPIC: &str = "webhook:delivery";
+
+/// Header carrying the hex HMAC-SHA256 of the request body.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Streamlib-Signature";
+
+/// Event-name selector matching every event.
+const WEBHOOK_SELECT_ALL_EVENTS: &str = "*";
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_BACKOFF_MS: u32 = 500;
+const DEFAULT_TIMEOUT_MS: u32 = 5000;
+
+/// Events waiting for delivery before the oldest is dropped.
+pub const WEBHOOK_QUEUE_CAPACITY: usize = 256;
+
+/// Name an event is selected by: the `RuntimeEvent` / `ProcessorEvent`
+/// variant name, or the topic of a custom event.
+pub fn webhook_event_name(event: &Event) -> String {
+    fn variant_name(value: serde_json::Result<serde_json::Value>) -> String {
+        match value {
+            Ok(serde_json::Value::String(name)) => name,
+            Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+    match event {
+        Event::RuntimeGlobal(inner) => variant_name(serde_json::to_value(inner)),
+        Event::ProcessorEvent { event, .. } => variant_name(serde_json::to_value(event)),
+        Event::Custom { topic, .. } => topic.clone(),
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 signature of `body` under `secret`.
+pub fn sign_webhook_body(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC-SHA256 accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Running delivery totals, shared between the delivery thread and the
+/// processor so `stop()` can log a summary.
+#[derive(Debug, Default)]
+pub struct WebhookDeliveryMetrics {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    retries: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl WebhookDeliveryMetrics {
+    /// Events the endpoint accepted (2xx).
+    pub fn delivered(&self) -> u64 {
+        self.delivered.load(Ordering::Relaxed)
+    }
+
+    /// Events abandoned after exhausting every retry.
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Retry attempts across all events.
+    pub fn retries(&self) -> u64 {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Events dropped undelivered because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Resolved delivery settings, defaults applied.
+#[derive(Debug, Clone)]
+struct WebhookDeliverySettings {
+    url: String,
+    secret: Option<String>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    timeout: Duration,
+}
+
+/// One event queued for delivery.
+struct QueuedWebhookEvent {
+    event_name: String,
+    body: String,
+}
+
+/// Bounded drop-oldest hand-off from the selector to the delivery thread.
+struct WebhookDeliveryQueue {
+    state: Mutex<WebhookDeliveryQueueState>,
+    changed: Condvar,
+}
+
+struct WebhookDeliveryQueueState {
+    events: VecDeque<QueuedWebhookEvent>,
+    capacity: usize,
+    /// Set by `stop()`; nothing queued or mid-retry is delivered after it.
+    abandoned: bool,
+}
+
+impl WebhookDeliveryQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(WebhookDeliveryQueueState {
+                events: VecDeque::with_capacity(capacity),
+                capacity,
+                abandoned: false,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Queue `event`, dropping the oldest queued event when full. Returns
+    /// whether one was dropped.
+    fn push(&self, event: QueuedWebhookEvent) -> bool {
+        let mut state = self.state.lock();
+        if state.abandoned {
+            return false;
+        }
+        let dropped = state.events.len() >= state.capacity && state.events.pop_front().is_some();
+        state.events.push_back(event);
+        self.changed.notify_one();
+        dropped
+    }
+
+    /// Block for the next event; `None` once the queue is abandoned.
+    fn pop(&self) -> Option<QueuedWebhookEvent> {
+        let mut state = self.state.lock();
+        loop {
+            if state.abandoned {
+                return None;
+            }
+            if let Some(event) = state.events.pop_front() {
+                return Some(event);
+            }
+            self.changed.wait(&mut state);
+        }
+    }
+
+    /// Sleep for `duration`, cut short if the queue is abandoned meanwhile.
+    /// Returns whether it was.
+    fn sleep_unless_abandoned(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        let mut state = self.state.lock();
+        while !state.abandoned {
+            if self.changed.wait_until(&mut state, deadline).timed_out() {
+                break;
+            }
+        }
+        state.abandoned
+    }
+
+    fn is_abandoned(&self) -> bool {
+        self.state.lock().abandoned
+    }
+
+    /// Stop handing out events and wake the delivery thread. Returns how
+    /// many queued events were discarded.
+    fn abandon(&self) -> usize {
+        let mut state = self.state.lock();
+        state.abandoned = true;
+        let pending = state.events.len();
+        state.events.clear();
+        self.changed.notify_all();
+        pending
+    }
+}
+
+/// PUBSUB listener that filters by event name and queues matches.
+struct WebhookEventSelector {
+    selected_event_names: Vec<String>,
+    runtime_id: String,
+    processor_id: Option<String>,
+    queue: Arc<WebhookDeliveryQueue>,
+    metrics: Arc<WebhookDeliveryMetrics>,
+}
+
+impl WebhookEventSelector {
+    fn selects(&self, event_name: &str) -> bool {
+        event_name != WEBHOOK_DELIVERY_TOPIC
+            && self
+                .selected_event_names
+                .iter()
+                .any(|name| name == WEBHOOK_SELECT_ALL_EVENTS || name == event_name)
+    }
+}
+
+impl EventListener for WebhookEventSelector {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        let event_name = webhook_event_name(event);
+        if !self.selects(&event_name) {
+            return Ok(());
+        }
+        let emitted_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let body = serde_json::to_string(&serde_json::json!({
+            "runtime_id": self.runtime_id,
+            "processor_id": self.processor_id,
+            "event_name": event_name,
+            "emitted_at_ms": emitted_at_ms,
+            "event": event,
+        }))
+        .map_err(|e| Error::Runtime(format!("WebhookNotifier: serialize event: {e}")))?;
+        if self.queue.push(QueuedWebhookEvent { event_name, body }) {
+            self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+/// POST `body` with retries. Returns the number of attempts made and the
+/// final error, if every attempt failed or the queue was abandoned while
+/// backing off.
+fn deliver_with_retries(
+    agent: &ureq::Agent,
+    settings: &WebhookDeliverySettings,
+    body: &str,
+    queue: &WebhookDeliveryQueue,
+    metrics: &WebhookDeliveryMetrics,
+) -> (u32, Option<String>) {
+    let signature = settings
+        .secret
+        .as_deref()
+        .map(|secret| sign_webhook_body(secret, body.as_bytes()));
+    let mut backoff = settings.retry_backoff;
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        let mut request = agent
+            .post(&settings.url)
+            .set("Content-Type", "application/json");
+        if let Some(signature) = &signature {
+            request = request.set(WEBHOOK_SIGNATURE_HEADER, signature);
+        }
+        let error = match request.send_string(body) {
+            Ok(_) => return (attempt, None),
+            Err(ureq::Error::Status(code, _)) => format!("endpoint responded {code}"),
+            Err(ureq::Error::Transport(transport)) => transport.to_string(),
+        };
+        if attempt > settings.max_retries {
+            return (attempt, Some(error));
+        }
+        tracing::debug!(attempt, %error, "WebhookNotifier: delivery failed, retrying");
+        metrics.retries.fetch_add(1, Ordering::Relaxed);
+        if queue.sleep_unless_abandoned(backoff) {
+            return (attempt, Some(error));
+        }
+        backoff = backoff.saturating_mul(2);
+    }
+}
+
+/// Deliver queued events until the queue is abandoned.
+fn run_delivery_loop(
+    queue: Arc<WebhookDeliveryQueue>,
+    settings: WebhookDeliverySettings,
+    metrics: Arc<WebhookDeliveryMetrics>,
+) {
+    let agent = ureq::AgentBuilder::new().timeout(settings.timeout).build();
+    while let Some(queued) = queue.pop() {
+        let (attempts, error) =
+            deliver_with_retries(&agent, &settings, &queued.body, &queue, &metrics);
+        if error.is_some() && queue.is_abandoned() {
+            break;
+        }
+        let status = match &error {
+            None => {
+                metrics.delivered.fetch_add(1, Ordering::Relaxed);
+                "delivered"
+            }
+            Some(error) => {
+                metrics.failed.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    event_name = %queued.event_name,
+                    attempts,
+                    %error,
+                    "WebhookNotifier: giving up on event"
+                );
+                "failed"
+            }
+        };
+        PUBSUB.publish(
+            WEBHOOK_DELIVERY_TOPIC,
+            &Event::custom(
+                WEBHOOK_DELIVERY_TOPIC,
+                serde_json::json!({
+                    "url": settings.url,
+                    "event_name": queued.event_name,
+                    "status": status,
+                    "attempts": attempts,
+                    "error": error,
+                    "delivered_total": metrics.delivered(),
+                    "failed_total": metrics.failed(),
+                    "retries_total": metrics.retries(),
+                    "dropped_total": metrics.dropped(),
+                }),
+            ),
+        );
+    }
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/WebhookNotifier",
+    description = "POSTs signed JSON payloads for selected runtime events to an HTTP endpoint, with retries",
+    execution = manual,
+    config = crate::_generated_::WebhookNotifierConfig,
+)]
+pub struct WebhookNotifierProcessor {
+    runtime_id: Option<String>,
+    processor_id: Option<String>,
+    /// Held so the PUBSUB subscription (a weak reference) stays live.
+    selector: Option<Arc<Mutex<dyn EventListener>>>,
+    delivery_queue: Option<Arc<WebhookDeliveryQueue>>,
+    delivery_thread: Option<JoinHandle<()>>,
+    metrics: Arc<WebhookDeliveryMetrics>,
+}
+
+impl ManualProcessor for WebhookNotifierProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.url.is_empty() {
+            return Err(Error::Config(
+                "WebhookNotifier: `url` must not be empty".into(),
+            ));
+        }
+        self.runtime_id = Some(ctx.runtime_id().to_string());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let settings = WebhookDeliverySettings {
+            url: self.config.url.clone(),
+            secret: self.config.secret.clone(),
+            max_retries: self.config.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+            retry_backoff: Duration::from_millis(
+                self.config
+                    .retry_backoff_ms
+                    .unwrap_or(DEFAULT_RETRY_BACKOFF_MS) as u64,
+            ),
+            timeout: Duration::from_millis(
+                self.config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS) as u64
+            ),
+        };
+        let queue = Arc::new(WebhookDeliveryQueue::new(WEBHOOK_QUEUE_CAPACITY));
+        let delivery_queue = Arc::clone(&queue);
+        let metrics = Arc::clone(&self.metrics);
+        let delivery_thread = std::thread::Builder::new()
+            .name("webhook-notifier".into())
+            .spawn(move || run_delivery_loop(delivery_queue, settings, metrics))
+            .map_err(|e| Error::Runtime(format!("WebhookNotifier: spawn delivery thread: {e}")))?;
+
+        let selector: Arc<Mutex<dyn EventListener>> = Arc::new(Mutex::new(WebhookEventSelector {
+            selected_event_names: self.config.events.clone(),
+            runtime_id: self.runtime_id.clone().unwrap_or_default(),
+            processor_id: self.processor_id.clone(),
+            queue: Arc::clone(&queue),
+            metrics: Arc::clone(&self.metrics),
+        }));
+        PUBSUB.subscribe(topics::ALL, Arc::clone(&selector));
+        self.selector = Some(selector);
+        self.delivery_queue = Some(queue);
+        self.delivery_thread = Some(delivery_thread);
+
+        tracing::info!(
+            url = %self.config.url,
+            events = ?self.config.events,
+            "WebhookNotifier delivering runtime events"
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        // Unsubscribe, then abandon the queue: the delivery thread wakes from
+        // any retry backoff and exits after at most one in-flight request.
+        self.selector.take();
+        let abandoned = self
+            .delivery_queue
+            .take()
+            .map(|queue| queue.abandon())
+            .unwrap_or_default();
+        if let Some(thread) = self.delivery_thread.take() {
+            if thread.join().is_err() {
+                tracing::warn!("WebhookNotifier: delivery thread panicked");
+            }
+        }
+        tracing::info!(
+            delivered = self.metrics.delivered(),
+            failed = self.metrics.failed(),
+            retries = self.metrics.retries(),
+            dropped = self.metrics.dropped(),
+            abandoned,
+            "WebhookNotifier stopped"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use streamlib::sdk::pubsub::RuntimeEvent;
+
+    #[test]
+    fn event_name_is_variant_name_or_custom_topic() {
+        assert_eq!(
+            webhook_event_name(&Event::RuntimeGlobal(RuntimeEvent::RuntimeStarted)),
+            "RuntimeStarted"
+        );
+        assert_eq!(
+            webhook_event_name(&Event::RuntimeGlobal(RuntimeEvent::RuntimeError {
+                error: "boom".into()
+            })),
+            "RuntimeError"
+        );
+        assert_eq!(
+            webhook_event_name(&Event::custom("recording:finished", serde_json::json!({}))),
+            "recording:finished"
+        );
+    }
+
+    #[test]
+    fn selector_never_forwards_its_own_delivery_reports() {
+        let selector = WebhookEventSelector {
+            selected_event_names: vec![WEBHOOK_SELECT_ALL_EVENTS.into()],
+            runtime_id: "R1".into(),
+            processor_id: None,
+            queue: Arc::new(WebhookDeliveryQueue::new(WEBHOOK_QUEUE_CAPACITY)),
+            metrics: Arc::default(),
+        };
+        assert!(selector.selects("RuntimeStarted"));
+        assert!(!selector.selects(WEBHOOK_DELIVERY_TOPIC));
+    }
+
+    #[test]
+    fn full_queue_drops_oldest_and_abandon_discards_the_rest() {
+        let queue = WebhookDeliveryQueue::new(2);
+        let event = |name: &str| QueuedWebhookEvent {
+            event_name: name.into(),
+            body: String::new(),
+        };
+        assert!(!queue.push(event("a")));
+        assert!(!queue.push(event("b")));
+        assert!(queue.push(event("c")));
+        assert_eq!(queue.pop().unwrap().event_name, "b");
+
+        assert_eq!(queue.abandon(), 1);
+        assert!(queue.pop().is_none());
+        assert!(!queue.push(event("d")));
+        assert!(queue.sleep_unless_abandoned(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn signature_matches_rfc_4231_vector() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            sign_webhook_body("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,519 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! WHEP monitoring — full-rate playback of any processor output in a
+//! browser, negotiated through the API server.
+//!
+//! `POST /whep/{processor_id}/{port}` carries the browser's SDP offer.
+//! Opening the session answers it from a send-only peer connection and
+//! patches the graph, all or nothing, so the output feeds a `WhepSink`:
+//! through a fresh `H264Encoder` when the port carries `VideoFrame`s, or
+//! directly when it already carries `EncodedVideoFrame`s (assumed H.264 —
+//! the only codec the session offers). The sink hands each access unit to
+//! the session's track over a bounded channel; a browser that falls behind
+//! loses frames rather than stalling the graph.
+//!
+//! `DELETE /whep/sessions/{id}` — or the peer connection failing or
+//! closing — removes the processors the session added and closes the peer.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef, ProcessorUniqueId};
+use streamlib::sdk::graph_patch::{GraphPatch, GraphPatchLink, GraphPatchProcessor};
+use streamlib::sdk::json_schema::GraphResponse;
+use streamlib::sdk::processor_type_ref;
+use streamlib::sdk::processors::ProcessorSpec;
+use streamlib::sdk::runtime::RuntimeOperations;
+use tokio::sync::mpsc;
+use webrtc::api::APIBuilder;
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::{MIME_TYPE_H264, MediaEngine};
+use webrtc::interceptor::registry::Registry;
+use webrtc::media::Sample;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use webrtc::track::track_local::TrackLocal;
+use webrtc::track::track_local::track_local_static_sample::TrackLocalStaticSample;
+
+use crate::_generated_::EncodedVideoFrame;
+
+/// Access units buffered between a sink and its session's track. About a
+/// quarter second at 30 fps; past that the sink drops.
+const SESSION_SAMPLE_CAPACITY: usize = 8;
+
+/// Sample duration when a frame carries no `fps`.
+const DEFAULT_FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / 30);
+
+/// Patch aliases for the processors a session adds.
+const ENCODER_ALIAS: &str = "encoder";
+const SINK_ALIAS: &str = "sink";
+
+/// Live sessions by id. The sinks look their channel up here, so a sink
+/// outliving its session finds nothing and drops its frames.
+static WHEP_SESSIONS: LazyLock<Mutex<HashMap<String, WhepSession>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+struct WhepSession {
+    samples: mpsc::Sender<Sample>,
+    peer_connection: Arc<RTCPeerConnection>,
+    /// The encoder (if any) and sink the session added to the graph.
+    processor_ids: Vec<ProcessorUniqueId>,
+}
+
+/// The answer to a WHEP offer.
+pub(crate) struct WhepAnswer {
+    pub session_id: String,
+    pub sdp: String,
+}
+
+/// What the monitored output carries, and so whether the session inserts an
+/// encoder in front of its sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WhepSource {
+    VideoFrame,
+    EncodedVideoFrame,
+}
+
+/// Classify `processor_id`'s `port` output from the graph document.
+fn classify_output(graph: &GraphResponse, processor_id: &str, port: &str) -> Result<WhepSource> {
+    let node = graph
+        .nodes
+        .iter()
+        .find(|node| node.id == processor_id)
+        .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+    let output = node
+        .ports
+        .outputs
+        .iter()
+        .find(|output| output.name == port)
+        .ok_or_else(|| Error::ProcessorPortNotFound {
+            processor_id: processor_id.to_string(),
+            port_name: port.to_string(),
+            direction: streamlib::sdk::error::PortDirection::Output,
+        })?;
+    let data_type = output
+        .data_type
+        .as_ref()
+        .filter(|data_type| data_type.org == "tatolab" && data_type.package == "core");
+    match data_type.map(|data_type| data_type.type_name.as_str()) {
+        Some("VideoFrame") => Ok(WhepSource::VideoFrame),
+        Some("EncodedVideoFrame") => Ok(WhepSource::EncodedVideoFrame),
+        _ => Err(Error::NotSupported(format!(
+            "WHEP: output '{port}' of '{processor_id}' carries neither VideoFrame nor \
+             EncodedVideoFrame"
+        ))),
+    }
+}
+
+/// The patch wiring `processor_id`'s `port` into a `WhepSink` for
+/// `session_id`, through an encoder when the output is raw video.
+fn session_patch(
+    source: WhepSource,
+    processor_id: &str,
+    port: &str,
+    session_id: &str,
+) -> GraphPatch {
+    let sink = GraphPatchProcessor {
+        alias: SINK_ALIAS.to_string(),
+        spec: ProcessorSpec::new(
+            processor_type_ref!("tatolab", "api-server", "WhepSink"),
+            serde_json::json!({ "session_id": session_id }),
+        ),
+    };
+    let sink_input = InputLinkPortRef::new(SINK_ALIAS, "encoded_video_in");
+    match source {
+        WhepSource::EncodedVideoFrame => GraphPatch {
+            add_processors: vec![sink],
+            connect: vec![GraphPatchLink {
+                from: OutputLinkPortRef::new(processor_id, port),
+                to: sink_input,
+            }],
+            ..GraphPatch::default()
+        },
+        WhepSource::VideoFrame => {
+            // Constrained baseline decodes in every browser; a short GOP
+            // gets the first picture up quickly.
+            let encoder = GraphPatchProcessor {
+                alias: ENCODER_ALIAS.to_string(),
+                spec: ProcessorSpec::new(
+                    processor_type_ref!("tatolab", "h264", "H264Encoder"),
+                    serde_json::json!({ "profile": "baseline", "keyframe_interval_seconds": 1.0 }),
+                ),
+            };
+            GraphPatch {
+                add_processors: vec![encoder, sink],
+                connect: vec![
+                    GraphPatchLink {
+                        from: OutputLinkPortRef::new(processor_id, port),
+                        to: InputLinkPortRef::new(ENCODER_ALIAS, "video_in"),
+                    },
+                    GraphPatchLink {
+                        from: OutputLinkPortRef::new(ENCODER_ALIAS, "encoded_video_out"),
+                        to: sink_input,
+                    },
+                ],
+                ..GraphPatch::default()
+            }
+        }
+    }
+}
+
+/// Answer `offer_sdp` and start streaming `processor_id`'s `port` to it.
+pub(crate) async fn open_session(
+    runtime: Arc<dyn RuntimeOperations>,
+    processor_id: &str,
+    port: &str,
+    offer_sdp: String,
+) -> Result<WhepAnswer> {
+    let graph = crate::controller_command::graph(&runtime).await?;
+    let source = classify_output(&graph, processor_id, port)?;
+    let offer = RTCSessionDescription::offer(offer_sdp)
+        .map_err(|e| Error::Configuration(format!("WHEP: malformed SDP offer: {e}")))?;
+
+    let session_id = generate_session_id()?;
+    let (peer_connection, track) = new_peer_connection().await?;
+    {
+        let runtime = Arc::clone(&runtime);
+        let session_id = session_id.clone();
+        peer_connection.on_peer_connection_state_change(Box::new(move |state| {
+            let runtime = Arc::clone(&runtime);
+            let session_id = session_id.clone();
+            Box::pin(async move {
+                if matches!(
+                    state,
+                    RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed
+                ) && let Err(e) = close_session(runtime, &session_id).await
+                    && !matches!(e, Error::NotFound(_))
+                {
+                    tracing::warn!(%session_id, "[WHEP] Tearing down session failed: {e}");
+                }
+            })
+        }));
+    }
+
+    let answer = match answer_offer(&peer_connection, offer).await {
+        Ok(answer) => answer,
+        Err(e) => {
+            let _ = peer_connection.close().await;
+            return Err(e);
+        }
+    };
+
+    let (samples, mut sample_rx) = mpsc::channel::<Sample>(SESSION_SAMPLE_CAPACITY);
+    WHEP_SESSIONS.lock().insert(
+        session_id.clone(),
+        WhepSession {
+            samples,
+            peer_connection: Arc::clone(&peer_connection),
+            processor_ids: Vec::new(),
+        },
+    );
+    tokio::spawn(async move {
+        while let Some(sample) = sample_rx.recv().await {
+            if let Err(e) = track.write_sample(&sample).await {
+                tracing::debug!("[WHEP] Dropped a sample: {e}");
+            }
+        }
+    });
+
+    let patch = session_patch(source, processor_id, port, &session_id);
+    let added: Vec<_> = match runtime.apply_graph_patch_async(patch).await {
+        Ok(receipt) => receipt.processors.into_values().collect(),
+        Err(e) => {
+            WHEP_SESSIONS.lock().remove(&session_id);
+            let _ = peer_connection.close().await;
+            return Err(e);
+        }
+    };
+    let orphaned = match WHEP_SESSIONS.lock().get_mut(&session_id) {
+        Some(session) => {
+            session.processor_ids = added;
+            None
+        }
+        None => Some(added),
+    };
+    // The peer went away while the patch applied, so its teardown found no
+    // processors to remove.
+    if let Some(orphaned) = orphaned {
+        runtime
+            .apply_graph_patch_async(GraphPatch {
+                remove_processors: orphaned,
+                ..GraphPatch::default()
+            })
+            .await?;
+        return Err(Error::Runtime(
+            "WHEP: the peer connection closed while the session opened".into(),
+        ));
+    }
+
+    tracing::info!(%session_id, processor_id, port, "[WHEP] Session opened");
+    Ok(WhepAnswer {
+        session_id,
+        sdp: answer,
+    })
+}
+
+/// End `session_id`: remove the processors it added and close its peer.
+pub(crate) async fn close_session(
+    runtime: Arc<dyn RuntimeOperations>,
+    session_id: &str,
+) -> Result<()> {
+    let session = WHEP_SESSIONS
+        .lock()
+        .remove(session_id)
+        .ok_or_else(|| Error::NotFound(format!("WHEP session '{session_id}'")))?;
+    let removed = if session.processor_ids.is_empty() {
+        Ok(())
+    } else {
+        runtime
+            .apply_graph_patch_async(GraphPatch {
+                remove_processors: session.processor_ids,
+                ..GraphPatch::default()
+            })
+            .await
+            .map(|_| ())
+    };
+    if let Err(e) = session.peer_connection.close().await {
+        tracing::debug!(session_id, "[WHEP] Closing the peer connection failed: {e}");
+    }
+    tracing::info!(session_id, "[WHEP] Session closed");
+    removed
+}
+
+/// A send-only peer connection carrying one H.264 track.
+async fn new_peer_connection() -> Result<(Arc<RTCPeerConnection>, Arc<TrackLocalStaticSample>)> {
+    let mut media_engine = MediaEngine::default();
+    media_engine
+        .register_default_codecs()
+        .map_err(|e| Error::Runtime(format!("WHEP: registering codecs failed: {e}")))?;
+    let registry = register_default_interceptors(Registry::new(), &mut media_engine)
+        .map_err(|e| Error::Runtime(format!("WHEP: registering interceptors failed: {e}")))?;
+    let api = APIBuilder::new()
+        .with_media_engine(media_engine)
+        .with_interceptor_registry(registry)
+        .build();
+    let peer_connection = Arc::new(
+        api.new_peer_connection(RTCConfiguration::default())
+            .await
+            .map_err(|e| {
+                Error::Runtime(format!("WHEP: creating the peer connection failed: {e}"))
+            })?,
+    );
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_H264.to_owned(),
+            clock_rate: 90_000,
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "streamlib-whep".to_owned(),
+    ));
+    let sender = peer_connection
+        .add_track(Arc::clone(&track) as Arc<dyn TrackLocal + Send + Sync>)
+        .await
+        .map_err(|e| Error::Runtime(format!("WHEP: adding the video track failed: {e}")))?;
+    // Incoming RTCP must be read for the interceptors to run; the loop ends
+    // with the connection.
+    tokio::spawn(async move {
+        let mut buf = vec![0u8; 1500];
+        while sender.read(&mut buf).await.is_ok() {}
+    });
+    Ok((peer_connection, track))
+}
+
+/// Apply the browser's offer and return the answer, candidates included —
+/// WHEP has no trickle channel back to the browser.
+async fn answer_offer(
+    peer_connection: &RTCPeerConnection,
+    offer: RTCSessionDescription,
+) -> Result<String> {
+    peer_connection
+        .set_remote_description(offer)
+        .await
+        .map_err(|e| Error::Configuration(format!("WHEP: offer rejected: {e}")))?;
+    let answer = peer_connection
+        .create_answer(None)
+        .await
+        .map_err(|e| Error::Runtime(format!("WHEP: creating the answer failed: {e}")))?;
+    let mut gathered = peer_connection.gathering_complete_promise().await;
+    peer_connection
+        .set_local_description(answer)
+        .await
+        .map_err(|e| Error::Runtime(format!("WHEP: setting the answer failed: {e}")))?;
+    let _ = gathered.recv().await;
+    peer_connection
+        .local_description()
+        .await
+        .map(|description| description.sdp)
+        .ok_or_else(|| Error::Runtime("WHEP: no local description after gathering".into()))
+}
+
+fn generate_session_id() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| Error::Runtime(format!("WHEP: OS RNG unavailable: {e}")))?;
+    Ok(hex::encode(bytes))
+}
+
+#[streamlib::sdk::processor(
+    "@tatolab/api-server/WhepSink",
+    description = "Delivers H.264 access units to a browser WHEP monitoring session opened through the API server",
+    execution = reactive,
+    config = crate::_generated_::WhepSinkConfig,
+    input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", description = "H.264 Annex-B access units, sent to the session's browser track"),
+)]
+pub struct WhepSinkProcessor {
+    samples: Option<mpsc::Sender<Sample>>,
+    frames_dropped: u64,
+}
+
+impl streamlib::sdk::processors::ReactiveProcessor for WhepSinkProcessor::Processor {
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let frame: EncodedVideoFrame = self.inputs.read("encoded_video_in")?;
+        if self.samples.is_none() {
+            self.samples = WHEP_SESSIONS
+                .lock()
+                .get(&self.config.session_id)
+                .map(|session| session.samples.clone());
+        }
+        let Some(samples) = &self.samples else {
+            self.frames_dropped += 1;
+            return Ok(());
+        };
+        let duration = frame
+            .fps
+            .filter(|fps| *fps > 0)
+            .map_or(DEFAULT_FRAME_DURATION, |fps| {
+                Duration::from_secs_f64(1.0 / f64::from(fps))
+            });
+        let sample = Sample {
+            data: bytes::Bytes::from(frame.data),
+            duration,
+            ..Default::default()
+        };
+        match samples.try_send(sample) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => self.frames_dropped += 1,
+            // The session ended; its teardown removes this sink.
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                self.samples = None;
+                self.frames_dropped += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            session_id = %self.config.session_id,
+            frames_dropped = self.frames_dropped,
+            "[WhepSink] Shutting down"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> GraphResponse {
+        let port = |name: &str, type_name: &str| {
+            serde_json::json!({
+                "name": name,
+                "data_type": {
+                    "org": "tatolab",
+                    "package": "core",
+                    "type": type_name,
+                    "version": { "major": 1, "minor": 0, "patch": 0 }
+                }
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "nodes": [{
+                "id": "camera",
+                "type": {
+                    "org": "tatolab",
+                    "package": "camera",
+                    "type": "Camera",
+                    "version": { "major": 1, "minor": 0, "patch": 0 }
+                },
+                "display_name": "Camera",
+                "ports": {
+                    "inputs": [],
+                    "outputs": [
+                        port("video", "VideoFrame"),
+                        port("encoded", "EncodedVideoFrame"),
+                        port("audio", "AudioFrame")
+                    ]
+                },
+                "components": {}
+            }],
+            "links": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn classify_output_picks_the_encoder_path_from_the_port_schema() {
+        let graph = graph();
+        assert_eq!(
+            classify_output(&graph, "camera", "video").unwrap(),
+            WhepSource::VideoFrame
+        );
+        assert_eq!(
+            classify_output(&graph, "camera", "encoded").unwrap(),
+            WhepSource::EncodedVideoFrame
+        );
+        assert!(matches!(
+            classify_output(&graph, "camera", "audio"),
+            Err(Error::NotSupported(_))
+        ));
+        assert!(matches!(
+            classify_output(&graph, "camera", "depth"),
+            Err(Error::ProcessorPortNotFound { .. })
+        ));
+        assert!(matches!(
+            classify_output(&graph, "mic", "video"),
+            Err(Error::ProcessorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn session_patch_encodes_raw_video_and_passes_encoded_video_through() {
+        let raw = session_patch(WhepSource::VideoFrame, "camera", "video", "s1");
+        let aliases: Vec<_> = raw
+            .add_processors
+            .iter()
+            .map(|p| p.alias.as_str())
+            .collect();
+        assert_eq!(aliases, [ENCODER_ALIAS, SINK_ALIAS]);
+        assert_eq!(
+            raw.connect[0].to,
+            InputLinkPortRef::new(ENCODER_ALIAS, "video_in")
+        );
+        assert_eq!(
+            raw.connect[1].to,
+            InputLinkPortRef::new(SINK_ALIAS, "encoded_video_in")
+        );
+        assert_eq!(raw.add_processors[1].spec.config["session_id"], "s1");
+        raw.check().unwrap();
+
+        let encoded = session_patch(WhepSource::EncodedVideoFrame, "camera", "encoded", "s2");
+        assert_eq!(encoded.add_processors.len(), 1);
+        assert_eq!(
+            encoded.connect[0].from,
+            OutputLinkPortRef::new("camera", "encoded")
+        );
+        encoded.check().unwrap();
+    }
+}
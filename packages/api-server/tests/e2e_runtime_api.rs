@@ -0,0 +1,225 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! API-server leg of the hardware-free end-to-end suite.
+//!
+//! Boots a real runtime with the in-process `ApiServer` processor and the
+//! staged test-fixtures package, then builds, edits and tears down the
+//! tone → probe graph purely over HTTP with `streamlib-client` — the same
+//! path the CLI and dashboards take. The probe's marker file (written by
+//! cdylib code) is the ground truth that each request really reached the
+//! running graph; `/ready`, the graph metrics and the `/ws/events` stream
+//! are checked against it.
+//!
+//! The engine-side legs (start/stop, live edits, checkpoints) and the
+//! shared harness live in `runtime/streamlib-engine/tests/`. Runs wherever
+//! `Runner::start` can open a Vulkan device — the rig, or a CI runner with
+//! a software rasterizer (`.github/workflows/e2e.yml`).
+
+#[path = "../../../runtime/streamlib-engine/tests/common/e2e_harness.rs"]
+mod e2e_harness;
+
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use e2e_harness::{
+    E2E_TIMEOUT, ProbeFile, audio_probe_ident, build_and_stage_test_fixtures,
+    runner_with_test_fixtures, tone_source_ident,
+};
+use serde_json::json;
+use serial_test::serial;
+use streamlib::sdk::processor_type_ref;
+use streamlib::sdk::processors::{PROCESSOR_REGISTRY, ProcessorSpec};
+use streamlib_client::{
+    BlockingClient, CreateConnectionRequest, CreateProcessorRequest, EventStreamItem,
+    SchemaIdentOutput, UpdateProcessorConfigRequest,
+};
+
+/// Grab an ephemeral port the OS reports free, then release it.
+fn free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
+
+/// Poll `/health` across the api-server's bind-retry window (it increments
+/// the port on collision) and return a client for the port that answered.
+fn wait_for_api(base_port: u16) -> BlockingClient {
+    let deadline = Instant::now() + E2E_TIMEOUT;
+    while Instant::now() < deadline {
+        for port in base_port..base_port + 10 {
+            let client = BlockingClient::new(&format!("http://127.0.0.1:{port}"))
+                .unwrap()
+                .with_timeout(Duration::from_secs(5));
+            if client.health().is_ok() {
+                return client;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    panic!("api-server never answered /health above port {base_port}");
+}
+
+fn poll(mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + E2E_TIMEOUT;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    done()
+}
+
+#[test]
+#[serial]
+fn graph_is_built_edited_and_observed_over_the_api() {
+    let (tmp, fixtures_dst) = build_and_stage_test_fixtures();
+    let probe = ProbeFile::new(tmp.path(), "api_probe");
+
+    // Registered in-process, exactly as `streamlib-runtime` does at boot.
+    PROCESSOR_REGISTRY.register::<streamlib_api_server::ApiServerProcessor::Processor>();
+    let runtime = runner_with_test_fixtures(&fixtures_dst);
+    let base_port = free_port();
+    runtime
+        .add_processor(ProcessorSpec::new(
+            processor_type_ref!("tatolab", "api-server", "ApiServer"),
+            json!({
+                "host": "127.0.0.1",
+                "port": base_port,
+                "ready_critical_links": ["AudioProbeTestProcessor.audio"],
+            }),
+        ))
+        .unwrap();
+    runtime.start().expect("runtime.start");
+
+    let client = wait_for_api(base_port);
+    assert!(client.live().unwrap().ok, "a responsive runtime is live");
+    assert!(
+        !client.ready().unwrap().ok,
+        "the critical link doesn't exist yet, so the runtime isn't ready"
+    );
+
+    // Drain the event stream on its own thread; the iterator blocks.
+    let (event_tx, event_rx) = mpsc::channel();
+    let events = client.events().expect("open /ws/events");
+    std::thread::spawn(move || {
+        for item in events {
+            if event_tx.send(item).is_err() {
+                break;
+            }
+        }
+    });
+
+    // ---- Build the graph over HTTP ------------------------------------------
+    let tone = client
+        .create_processor(&CreateProcessorRequest {
+            processor_type: SchemaIdentOutput::from(&tone_source_ident()),
+            config: json!({ "frequency_hz": 440.0, "amplitude": 0.5 }),
+        })
+        .unwrap();
+    let sink = client
+        .create_processor(&CreateProcessorRequest {
+            processor_type: SchemaIdentOutput::from(&audio_probe_ident()),
+            config: json!({
+                "output_path": probe.path().to_string_lossy(),
+                "max_frames": 1_000_000u32,
+            }),
+        })
+        .unwrap();
+    let link = client
+        .connect(&CreateConnectionRequest {
+            from_processor: tone.clone(),
+            from_port: "audio".into(),
+            to_processor: sink.clone(),
+            to_port: "audio".into(),
+        })
+        .unwrap();
+
+    assert!(
+        probe.wait_for_frames(10) >= 10,
+        "a graph built over the API must run:\n{}",
+        probe.dump()
+    );
+    assert!(
+        poll(|| client.ready().is_ok_and(|ready| ready.ok)),
+        "the runtime must turn ready once the critical link delivers: {:?}",
+        client.ready()
+    );
+    assert!(
+        poll(|| {
+            client.metrics().is_ok_and(|metrics| {
+                metrics
+                    .links
+                    .get(&link)
+                    .is_some_and(|drops| drops.frames_delivered > 0)
+            })
+        }),
+        "the graph metrics must report frames delivered on the new link"
+    );
+
+    // ---- Edit it live over HTTP ----------------------------------------------
+    client
+        .update_processor_config(
+            &tone,
+            &UpdateProcessorConfigRequest {
+                config: json!({ "frequency_hz": 440.0, "amplitude": 0.25 }),
+            },
+        )
+        .unwrap();
+    assert!(
+        probe.wait_for(|probe| {
+            probe
+                .peaks()
+                .last()
+                .is_some_and(|peak| (peak - 0.25).abs() < 0.01)
+        }),
+        "a config update over the API must reach the running tone:\n{}",
+        probe.dump()
+    );
+
+    client.disconnect(&link).unwrap();
+    client.remove_processor(&sink).unwrap();
+    assert!(
+        probe.wait_for(|probe| probe.count("TEARDOWN") == 1),
+        "removing the probe over the API must tear it down:\n{}",
+        probe.dump()
+    );
+    let graph = client.graph().unwrap();
+    assert!(graph.links.is_empty(), "{graph:?}");
+    assert!(graph.nodes.iter().any(|node| node.id == tone));
+    assert!(graph.nodes.iter().all(|node| node.id != sink));
+
+    // Every mutation above is a graph change the event stream announced.
+    let expected = [
+        "RuntimeDidAddProcessor",
+        "RuntimeDidConnect",
+        "RuntimeDidDisconnect",
+        "RuntimeDidRemoveProcessor",
+    ];
+    let mut graph_changes: Vec<String> = Vec::new();
+    let deadline = Instant::now() + E2E_TIMEOUT;
+    while !expected
+        .iter()
+        .all(|kind| graph_changes.iter().any(|seen| seen == kind))
+    {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match event_rx.recv_timeout(remaining) {
+            Ok(Ok(EventStreamItem::Event(event))) if event.is_graph_change() => {
+                graph_changes.push(event.kind().to_string());
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    runtime.stop().expect("runtime.stop");
+
+    for kind in expected {
+        assert!(
+            graph_changes.iter().any(|seen| seen == kind),
+            "/ws/events must carry {kind}; saw {graph_changes:?}"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AppleVision (macOS) — runs Vision requests on each analyzed frame's
+//! IOSurface-backed `CVPixelBuffer` and publishes the observations as a
+//! `VisionDetections` data frame. Vision reads the surface in place, so
+//! pixels never leave the GPU-shared allocation.
+//!
+//! Requests are built per analyzed frame: Vision objects are not `Send`,
+//! and a handful of request allocations is noise next to the inference.
+
+use objc2::AllocAnyThread;
+use objc2::rc::Retained;
+use objc2_core_foundation::CGRect;
+use objc2_core_video::CVPixelBuffer;
+use objc2_foundation::{NSArray, NSDictionary};
+use objc2_vision::{
+    VNDetectFaceLandmarksRequest, VNDetectFaceRectanglesRequest, VNDetectHumanBodyPoseRequest,
+    VNFaceLandmarkRegion2D, VNFaceObservation, VNHumanBodyPoseObservation,
+    VNHumanBodyPoseObservationJointsGroupNameAll, VNImageRequestHandler, VNRecognizeTextRequest,
+    VNRecognizedTextObservation, VNRequest, VNRequestTextRecognitionLevel,
+};
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__apple_vision::apple_vision_config::TextRecognitionLevel;
+use crate::_generated_::tatolab__apple_vision::vision_detections::{
+    VisionDetectionsBody, VisionDetectionsBodyJoint, VisionDetectionsFace,
+    VisionDetectionsFaceLandmark, VisionDetectionsTextRegion,
+};
+use crate::_generated_::{VideoFrame, VisionDetections};
+use crate::observations::{self, FrameRect};
+
+const DEFAULT_MIN_CONFIDENCE: f32 = 0.5;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/apple-vision/AppleVision",
+    description = "Runs Apple Vision requests (face rectangles and landmarks, human body pose, text recognition) directly on each frame's IOSurface and emits the results as VisionDetections data frames, so overlays can draw on top without a CPU readback. Forwards every frame unmodified. macOS only.",
+    execution = reactive,
+    config = crate::_generated_::AppleVisionConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to analyze"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames, unmodified"),
+    output("detections", "@tatolab/apple-vision/VisionDetections", description = "One data frame per analyzed frame"),
+)]
+pub struct AppleVisionProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    frames_seen: u64,
+    frames_analyzed: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AppleVisionProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let min_confidence = self.config.min_confidence.unwrap_or(DEFAULT_MIN_CONFIDENCE);
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(Error::Configuration(
+                "AppleVision: min_confidence must be within 0..1".into(),
+            ));
+        }
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!(
+            "[AppleVision] Setup (faces: {}, landmarks: {}, body pose: {}, text: {})",
+            self.faces_enabled(),
+            self.config.face_landmarks.unwrap_or(false),
+            self.config.body_pose.unwrap_or(false),
+            self.config.text.unwrap_or(false)
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[AppleVision] Teardown ({} of {} frames analyzed)",
+            self.frames_analyzed,
+            self.frames_seen
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let analyze =
+            observations::should_analyze(self.frames_seen, self.config.interval.unwrap_or(1));
+        self.frames_seen += 1;
+        if analyze {
+            let detections = self.analyze(&frame)?;
+            self.frames_analyzed += 1;
+            self.outputs.write("detections", &detections)?;
+        }
+        self.outputs.write("video_out", &frame)
+    }
+}
+
+impl AppleVisionProcessor::Processor {
+    fn faces_enabled(&self) -> bool {
+        self.config.faces.unwrap_or(true) || self.config.face_landmarks.unwrap_or(false)
+    }
+
+    /// Run the configured requests on `frame`'s pixel buffer.
+    fn analyze(&self, frame: &VideoFrame) -> Result<VisionDetections> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("AppleVision: GPU context not initialized".into())
+        })?;
+        let buffer = gpu.resolve_pixel_buffer_by_surface_id(&frame.surface_id)?;
+        // SAFETY: on macOS a resolved PixelBuffer wraps a live
+        // CVPixelBufferRef, retained by `buffer` for this whole call.
+        let pixel_buffer = unsafe { &*(buffer.as_ptr() as *const CVPixelBuffer) };
+        let min_confidence = self.config.min_confidence.unwrap_or(DEFAULT_MIN_CONFIDENCE);
+
+        let landmarks = self.config.face_landmarks.unwrap_or(false);
+        let face_rectangles = (self.faces_enabled() && !landmarks)
+            .then(|| unsafe { VNDetectFaceRectanglesRequest::new() });
+        let face_landmarks = landmarks.then(|| unsafe { VNDetectFaceLandmarksRequest::new() });
+        let body_pose = self
+            .config
+            .body_pose
+            .unwrap_or(false)
+            .then(|| unsafe { VNDetectHumanBodyPoseRequest::new() });
+        let text = self.config.text.unwrap_or(false).then(|| {
+            let request = unsafe { VNRecognizeTextRequest::new() };
+            let level = match self.config.text_recognition_level {
+                Some(TextRecognitionLevel::Accurate) => VNRequestTextRecognitionLevel::Accurate,
+                Some(TextRecognitionLevel::Fast) | None => VNRequestTextRecognitionLevel::Fast,
+            };
+            unsafe { request.setRecognitionLevel(level) };
+            request
+        });
+
+        let mut requests: Vec<&VNRequest> = Vec::new();
+        requests.extend(face_rectangles.as_deref().map(AsRef::as_ref));
+        requests.extend(face_landmarks.as_deref().map(AsRef::as_ref));
+        requests.extend(body_pose.as_deref().map(AsRef::as_ref));
+        requests.extend(text.as_deref().map(AsRef::as_ref));
+
+        let mut detections = VisionDetections {
+            timestamp_ns: frame.timestamp_ns.clone(),
+            width: frame.width,
+            height: frame.height,
+            faces: Vec::new(),
+            bodies: Vec::new(),
+            text_regions: Vec::new(),
+        };
+        if requests.is_empty() {
+            return Ok(detections);
+        }
+
+        let handler = unsafe {
+            VNImageRequestHandler::initWithCVPixelBuffer_options(
+                VNImageRequestHandler::alloc(),
+                pixel_buffer,
+                &NSDictionary::new(),
+            )
+        };
+        unsafe { handler.performRequests_error(&NSArray::from_slice(&requests)) }
+            .map_err(|e| Error::Runtime(format!("AppleVision: Vision request failed: {}", e)))?;
+
+        let face_results = face_landmarks
+            .as_deref()
+            .and_then(|request| unsafe { request.results() })
+            .or_else(|| {
+                face_rectangles
+                    .as_deref()
+                    .and_then(|request| unsafe { request.results() })
+            });
+        if let Some(faces) = face_results {
+            detections.faces = faces
+                .iter()
+                .filter(|face| unsafe { face.confidence() } >= min_confidence)
+                .map(|face| face_detection(&face, landmarks))
+                .collect();
+        }
+        if let Some(bodies) = body_pose
+            .as_deref()
+            .and_then(|request| unsafe { request.results() })
+        {
+            detections.bodies = bodies
+                .iter()
+                .filter(|body| unsafe { body.confidence() } >= min_confidence)
+                .map(|body| body_detection(&body, min_confidence))
+                .collect();
+        }
+        if let Some(lines) = text
+            .as_deref()
+            .and_then(|request| unsafe { request.results() })
+        {
+            detections.text_regions = lines
+                .iter()
+                .filter_map(|line| text_detection(&line, min_confidence))
+                .collect();
+        }
+        Ok(detections)
+    }
+}
+
+fn frame_rect(rect: CGRect) -> FrameRect {
+    FrameRect::from_vision(
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    )
+}
+
+fn face_detection(face: &VNFaceObservation, landmarks: bool) -> VisionDetectionsFace {
+    let rect = frame_rect(unsafe { face.boundingBox() });
+    let mut regions = Vec::new();
+    if let Some(found) = landmarks.then(|| unsafe { face.landmarks() }).flatten() {
+        let named: [(&str, Option<Retained<VNFaceLandmarkRegion2D>>); 12] = unsafe {
+            [
+                ("face_contour", found.faceContour()),
+                ("left_eye", found.leftEye()),
+                ("right_eye", found.rightEye()),
+                ("left_eyebrow", found.leftEyebrow()),
+                ("right_eyebrow", found.rightEyebrow()),
+                ("nose", found.nose()),
+                ("nose_crest", found.noseCrest()),
+                ("median_line", found.medianLine()),
+                ("outer_lips", found.outerLips()),
+                ("inner_lips", found.innerLips()),
+                ("left_pupil", found.leftPupil()),
+                ("right_pupil", found.rightPupil()),
+            ]
+        };
+        for (region, landmark) in named {
+            let Some(landmark) = landmark else {
+                continue;
+            };
+            // SAFETY: Vision owns `pointCount` points at `normalizedPoints`
+            // for as long as `landmark` is alive.
+            let points = unsafe {
+                std::slice::from_raw_parts(
+                    landmark.normalizedPoints().as_ptr(),
+                    landmark.pointCount(),
+                )
+            };
+            regions.push(VisionDetectionsFaceLandmark {
+                region: region.to_string(),
+                points: points
+                    .iter()
+                    .flat_map(|point| {
+                        let (x, y) = rect.landmark_point(point.x, point.y);
+                        [x, y]
+                    })
+                    .collect(),
+            });
+        }
+    }
+    VisionDetectionsFace {
+        x: rect.x,
+        y: rect.y,
+        width: rect.width,
+        height: rect.height,
+        confidence: unsafe { face.confidence() },
+        landmarks: regions,
+    }
+}
+
+fn body_detection(body: &VNHumanBodyPoseObservation, min_confidence: f32) -> VisionDetectionsBody {
+    let points = unsafe {
+        body.recognizedPointsForJointsGroupName_error(VNHumanBodyPoseObservationJointsGroupNameAll)
+    };
+    let mut joints = Vec::new();
+    match points {
+        Ok(points) => {
+            let (names, points) = points.to_vecs();
+            for (name, point) in names.iter().zip(points) {
+                let confidence = unsafe { point.confidence() };
+                if confidence < min_confidence {
+                    continue;
+                }
+                let location = unsafe { point.location() };
+                let (x, y) = observations::frame_point(location.x, location.y);
+                joints.push(VisionDetectionsBodyJoint {
+                    name: observations::joint_name(&name.to_string()).to_string(),
+                    x,
+                    y,
+                    confidence,
+                });
+            }
+        }
+        Err(e) => tracing::debug!("[AppleVision] Body joints unavailable: {}", e),
+    }
+    VisionDetectionsBody {
+        confidence: unsafe { body.confidence() },
+        joints,
+    }
+}
+
+fn text_detection(
+    line: &VNRecognizedTextObservation,
+    min_confidence: f32,
+) -> Option<VisionDetectionsTextRegion> {
+    let candidates = unsafe { line.topCandidates(1) };
+    let best = candidates.firstObject()?;
+    let confidence = unsafe { best.confidence() };
+    if confidence < min_confidence {
+        return None;
+    }
+    let rect = frame_rect(unsafe { line.boundingBox() });
+    Some(VisionDetectionsTextRegion {
+        text: unsafe { best.string() }.to_string(),
+        confidence,
+        x: rect.x,
+        y: rect.y,
+        width: rect.width,
+        height: rect.height,
+    })
+}
@@ -0,0 +1,6 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+pub mod apple_vision;
+
+pub use apple_vision::AppleVisionProcessor;
@@ -0,0 +1,30 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/apple-vision` — Apple Vision framework analysis. The
+//! `AppleVision` processor runs face rectangle / landmark, human body
+//! pose and text recognition requests directly on each frame's IOSurface
+//! and publishes `VisionDetections` data frames for overlays.
+//!
+//! The wire conventions (top-left origin, stable joint names) live in
+//! [`observations`] and build everywhere; the processor itself is parked
+//! under `_apple_impl_pending_`, so no target ships a live processor yet.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod observations;
+
+// The Vision processor resolves frames to IOSurface-backed CVPixelBuffers
+// through `PixelBuffer::as_ptr`, part of the engine-free Apple RHI surface
+// the plugin SDK exposes only under `cfg(linux)` today. Gated so it never
+// compiles on any target; unpark this module — add
+// `#[cfg(target_os = "macos")] export_plugin!(AppleVisionProcessor::Processor)`
+// below and re-add its Apple interop deps in Cargo.toml — once the plugin
+// SDK ships an engine-free Apple RHI surface.
+#[cfg(any())]
+mod _apple_impl_pending_;
+
+pub use _generated_::{AppleVisionConfig, VisionDetections};
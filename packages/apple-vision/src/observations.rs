@@ -0,0 +1,126 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Conversions from Vision's observation conventions to the
+//! `VisionDetections` wire conventions. Vision reports rectangles and
+//! points normalized to the image with the origin at the bottom-left,
+//! face landmarks normalized to the face's bounding box, and body joints
+//! under `VNHumanBodyPoseObservationJointName` raw values that name
+//! bones rather than joints (`left_forearm_joint` is the elbow). The
+//! wire format is top-left origin in frame coordinates with stable
+//! joint names.
+
+/// A rectangle normalized to the frame, origin at the top-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl FrameRect {
+    /// From a Vision `boundingBox` (a `CGRect`, origin bottom-left).
+    pub fn from_vision(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x: x as f32,
+            y: (1.0 - y - height) as f32,
+            width: width as f32,
+            height: height as f32,
+        }
+    }
+
+    /// Frame position of a landmark point Vision normalized to this face's
+    /// box (origin bottom-left within the box).
+    pub fn landmark_point(&self, x: f64, y: f64) -> (f32, f32) {
+        let (x, y) = (x as f32, y as f32);
+        (self.x + x * self.width, self.y + (1.0 - y) * self.height)
+    }
+}
+
+/// Frame position of a Vision image point (origin bottom-left).
+pub fn frame_point(x: f64, y: f64) -> (f32, f32) {
+    (x as f32, (1.0 - y) as f32)
+}
+
+/// Whether frame `frame_index` (0-based) is analyzed at `interval`.
+pub fn should_analyze(frame_index: u64, interval: u32) -> bool {
+    interval <= 1 || frame_index.is_multiple_of(u64::from(interval))
+}
+
+/// `VNHumanBodyPoseObservationJointName` raw values and the names
+/// published on the wire.
+const JOINT_NAMES: &[(&str, &str)] = &[
+    ("head_joint", "nose"),
+    ("left_eye_joint", "left_eye"),
+    ("right_eye_joint", "right_eye"),
+    ("left_ear_joint", "left_ear"),
+    ("right_ear_joint", "right_ear"),
+    ("neck_1_joint", "neck"),
+    ("left_shoulder_1_joint", "left_shoulder"),
+    ("right_shoulder_1_joint", "right_shoulder"),
+    ("left_forearm_joint", "left_elbow"),
+    ("right_forearm_joint", "right_elbow"),
+    ("left_hand_joint", "left_wrist"),
+    ("right_hand_joint", "right_wrist"),
+    ("root", "root"),
+    ("left_upLeg_joint", "left_hip"),
+    ("right_upLeg_joint", "right_hip"),
+    ("left_leg_joint", "left_knee"),
+    ("right_leg_joint", "right_knee"),
+    ("left_foot_joint", "left_ankle"),
+    ("right_foot_joint", "right_ankle"),
+];
+
+/// Wire name of a Vision body joint. Joints added by later OS releases
+/// pass through under their raw name.
+pub fn joint_name(vision_name: &str) -> &str {
+    JOINT_NAMES
+        .iter()
+        .find(|(raw, _)| *raw == vision_name)
+        .map_or(vision_name, |(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rects_and_points_flip_to_top_left_origin() {
+        let face = FrameRect::from_vision(0.25, 0.5, 0.25, 0.25);
+        assert_eq!(
+            face,
+            FrameRect {
+                x: 0.25,
+                y: 0.25,
+                width: 0.25,
+                height: 0.25
+            }
+        );
+        // Box corners: Vision's bottom-left is the frame rect's lower edge.
+        assert_eq!(face.landmark_point(0.0, 0.0), (0.25, 0.5));
+        assert_eq!(face.landmark_point(1.0, 1.0), (0.5, 0.25));
+        assert_eq!(frame_point(0.5, 0.75), (0.5, 0.25));
+    }
+
+    #[test]
+    fn interval_selects_every_nth_frame() {
+        for interval in [0, 1] {
+            assert!((0..4).all(|frame| should_analyze(frame, interval)));
+        }
+        let analyzed: Vec<u64> = (0..7).filter(|&frame| should_analyze(frame, 3)).collect();
+        assert_eq!(analyzed, [0, 3, 6]);
+    }
+
+    #[test]
+    fn joint_names_are_stable() {
+        assert_eq!(joint_name("head_joint"), "nose");
+        assert_eq!(joint_name("left_forearm_joint"), "left_elbow");
+        assert_eq!(joint_name("right_upLeg_joint"), "right_hip");
+        assert_eq!(joint_name("left_thumb_tip"), "left_thumb_tip");
+        let mut names: Vec<&str> = JOINT_NAMES.iter().map(|(_, name)| *name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), JOINT_NAMES.len());
+    }
+}
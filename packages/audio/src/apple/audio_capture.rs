@@ -176,6 +176,7 @@ impl AppleAudioCaptureProcessor::Processor {
                         sample_rate: sample_rate_clone,
                         timestamp_ns: timestamp_ns.to_string(),
                         frame_index: frame_number.to_string(),
+                        channel_layout: Some(crate::channel_layout::ChannelLayout::Mono),
                     };
 
                     if let Err(e) = outputs_clone.write("audio", &ipc_frame) {
@@ -3,6 +3,7 @@
 
 use crate::_generated_::tatolab__audio::audio_channel_converter_config::Mode;
 use crate::_generated_::AudioFrame;
+use crate::channel_layout::default_layout;
 use streamlib_plugin_sdk::sdk::error::{Result, Error};
 use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
 
@@ -82,6 +83,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioChannelCo
             sample_rate: input_frame.sample_rate,
             timestamp_ns: input_frame.timestamp_ns,
             frame_index: self.frame_counter.to_string(),
+            channel_layout: default_layout(output_channels),
         };
 
         self.outputs.write("audio_out", &output_frame)?;
@@ -0,0 +1,110 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AudioDownmix — folds surround or ambisonic audio down to 5.1, stereo or
+//! mono with the ITU-R BS.775 coefficients (see [`crate::channel_layout`]).
+
+use crate::_generated_::AudioFrame;
+use crate::_generated_::tatolab__audio::audio_downmix_config::TargetLayout;
+use crate::channel_layout::{ChannelLayout, DownmixMatrix, DownmixOptions, frame_layout};
+use crate::sidechain_ducker::db_to_linear;
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/audio/AudioDownmix",
+    description = "Downmixes 7.1, 5.1, stereo or ambisonic audio to 5.1, stereo or mono with ITU-R BS.775 coefficients",
+    execution = reactive,
+    scheduling = realtime,
+    config = crate::_generated_::AudioDownmixConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "Audio with a known channel layout (named, or 1/2/6/8 channels)"),
+    output("audio_out", "@tatolab/core/AudioFrame", description = "Audio in the target layout"),
+)]
+pub struct AudioDownmixProcessor {
+    /// Source layout and the matrix folding it to the target; rebuilt when
+    /// either changes.
+    matrix: Option<(ChannelLayout, DownmixMatrix)>,
+    frame_counter: u64,
+}
+
+impl AudioDownmixProcessor::Processor {
+    fn target_layout(&self) -> ChannelLayout {
+        match self.config.target_layout {
+            TargetLayout::Mono => ChannelLayout::Mono,
+            TargetLayout::Stereo => ChannelLayout::Stereo,
+            TargetLayout::Surround51 => ChannelLayout::Surround51,
+        }
+    }
+
+    fn options(&self) -> DownmixOptions {
+        DownmixOptions {
+            lfe_gain: self.config.lfe_gain_db.map_or(0.0, db_to_linear),
+            normalize: self.config.normalize.unwrap_or(false),
+        }
+    }
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioDownmixProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[AudioDownmix] setup() - target: {:?}, options: {:?}",
+            self.config.target_layout,
+            self.options()
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[AudioDownmix] Stopped (processed {} frames)",
+            self.frame_counter
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.matrix = None;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("audio_in") {
+            return Ok(());
+        }
+
+        let input_frame: AudioFrame = self.inputs.read("audio_in")?;
+        let Some(source) = frame_layout(&input_frame)? else {
+            return Err(Error::Configuration(format!(
+                "AudioDownmix: {} discrete channels have no speaker layout to downmix from; set AudioFrame.channel_layout upstream",
+                input_frame.channels
+            )));
+        };
+
+        let current = matches!(&self.matrix, Some((layout, _)) if *layout == source);
+        if !current {
+            let target = self.target_layout();
+            let matrix = DownmixMatrix::new(&source, &target, self.options())?;
+            tracing::info!("[AudioDownmix] Downmixing {:?} to {:?}", source, target);
+            self.matrix = Some((source, matrix));
+        }
+        let Some((_, matrix)) = &self.matrix else {
+            return Err(Error::Runtime(
+                "AudioDownmix: matrix not initialized".into(),
+            ));
+        };
+
+        let output_frame = AudioFrame {
+            samples: matrix.apply(&input_frame.samples),
+            channels: matrix.outputs() as u8,
+            sample_rate: input_frame.sample_rate,
+            timestamp_ns: input_frame.timestamp_ns,
+            frame_index: self.frame_counter.to_string(),
+            channel_layout: Some(self.target_layout()),
+        };
+
+        self.outputs.write("audio_out", &output_frame)?;
+        self.frame_counter += 1;
+
+        Ok(())
+    }
+}
@@ -1,25 +1,396 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
-use crate::_generated_::tatolab__audio::audio_mixer_config::Strategy;
-use crate::_generated_::AudioFrame;
-use streamlib_plugin_sdk::sdk::error::{Result, Error};
+//! AudioMixer — two inputs into one stereo output through per-input
+//! channel strips (gain, pan, mute, solo), optional sidechain ducking keyed
+//! by one input, and gain/pan automation on the media clock. Mono inputs
+//! pan with a constant-power law; stereo inputs balance; surround and
+//! ambisonic inputs are folded to stereo first. Config updates apply live:
+//! strip moves are smoothed over a few milliseconds so they don't click,
+//! and the ducker keeps its state.
+
+use std::f32::consts::FRAC_PI_4;
+
+use crate::_generated_::tatolab__audio::audio_mixer_config::{
+    Easing as ConfigEasing, Parameter, Strategy,
+};
+use crate::_generated_::{AudioFrame, AudioMixerConfig};
+use crate::channel_layout::{ChannelLayout, DownmixMatrix, DownmixOptions, frame_layout};
+use crate::mixer_automation::{AutomationLane, AutomationPoint, Easing};
+use crate::sidechain_ducker::{DuckingSettings, SidechainDucker, db_to_linear};
 use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+
+/// Input ports, indexed by strip.
+const INPUTS: [&str; 2] = ["left", "right"];
+
+/// Time constant strip gain and pan changes are smoothed over.
+const SMOOTHING_MS: f32 = 5.0;
+
+/// A strip's static settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StripSettings {
+    gain_db: f32,
+    /// `None`: mono `left` hard left and `right` hard right, stereo
+    /// centred.
+    pan: Option<f32>,
+    mute: bool,
+    solo: bool,
+}
+
+impl StripSettings {
+    /// Unity gain at the default pan.
+    fn unconfigured() -> Self {
+        Self {
+            gain_db: 0.0,
+            pan: None,
+            mute: false,
+            solo: false,
+        }
+    }
+}
+
+/// Constant-power pan law: (left, right) gains for `pan` in -1..1.
+fn pan_gains(pan: f32) -> (f32, f32) {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_4;
+    // cos(pi/2) rounds to a tiny negative; hard right must not leak left.
+    (angle.cos().max(0.0), angle.sin())
+}
+
+/// Balance law for stereo inputs: (left, right) gains for `pan` in -1..1,
+/// unity at centre, turning the opposite channel down towards the edges.
+fn balance_gains(pan: f32) -> (f32, f32) {
+    let pan = pan.clamp(-1.0, 1.0);
+    ((1.0 - pan).min(1.0), (1.0 + pan).min(1.0))
+}
+
+/// Config resolved once per setup or config update.
+#[derive(Debug, Clone)]
+struct MixSettings {
+    strategy: Strategy,
+    strips: [StripSettings; 2],
+    gain_lanes: [Option<AutomationLane>; 2],
+    pan_lanes: [Option<AutomationLane>; 2],
+    /// Key input and settings.
+    ducking: Option<(usize, DuckingSettings)>,
+}
+
+impl MixSettings {
+    /// Whether each input is heard, after mute and solo.
+    fn audible(&self) -> [bool; 2] {
+        let any_solo = self.strips.iter().any(|strip| strip.solo);
+        self.strips
+            .map(|strip| !strip.mute && (!any_solo || strip.solo))
+    }
+
+    /// Linear (left, right) gains of input `index` — mono, or stereo when
+    /// `stereo` — at media-clock time `timestamp_ns`, before ducking.
+    fn strip_gains(&self, index: usize, stereo: bool, timestamp_ns: i64) -> (f32, f32) {
+        let strip = &self.strips[index];
+        let gain_db = self.gain_lanes[index]
+            .as_ref()
+            .map_or(strip.gain_db, |lane| lane.value_at(timestamp_ns));
+        let default_pan = match (stereo, index) {
+            (true, _) => 0.0,
+            (false, 0) => -1.0,
+            (false, _) => 1.0,
+        };
+        let pan = self.pan_lanes[index]
+            .as_ref()
+            .map_or(strip.pan.unwrap_or(default_pan), |lane| {
+                lane.value_at(timestamp_ns)
+            });
+        let gain = db_to_linear(gain_db);
+        let (left, right) = if stereo {
+            balance_gains(pan)
+        } else {
+            pan_gains(pan)
+        };
+        (gain * left, gain * right)
+    }
+}
+
+fn input_index(processor_input: &str, field: &str) -> Result<usize> {
+    INPUTS
+        .iter()
+        .position(|input| *input == processor_input)
+        .ok_or_else(|| {
+            Error::Configuration(format!(
+                "AudioMixer: {} names unknown input {:?} (expected one of {:?})",
+                field, processor_input, INPUTS
+            ))
+        })
+}
+
+/// Resolve `config`; automation lanes without a `start_ns` start at
+/// `applied_at_ns`.
+fn resolve_config(config: &AudioMixerConfig, applied_at_ns: i64) -> Result<MixSettings> {
+    let mut strips = [StripSettings::unconfigured(); 2];
+    for entry in config.inputs.iter().flatten() {
+        let index = input_index(&entry.input, "inputs")?;
+        let defaults = StripSettings::unconfigured();
+        strips[index] = StripSettings {
+            gain_db: entry.gain_db.unwrap_or(defaults.gain_db),
+            pan: entry.pan.map(|pan| pan.clamp(-1.0, 1.0)),
+            mute: entry.mute.unwrap_or(defaults.mute),
+            solo: entry.solo.unwrap_or(defaults.solo),
+        };
+    }
+
+    let mut gain_lanes: [Option<AutomationLane>; 2] = [None, None];
+    let mut pan_lanes: [Option<AutomationLane>; 2] = [None, None];
+    let mut automated = [[false; 2]; 2];
+    for lane in config.automation.iter().flatten() {
+        let index = input_index(&lane.input, "automation")?;
+        let start_ns = match &lane.start_ns {
+            Some(start_ns) => start_ns.parse::<i64>().map_err(|_| {
+                Error::Configuration(format!(
+                    "AudioMixer: automation start_ns must be an integer, got {:?}",
+                    start_ns
+                ))
+            })?,
+            None => applied_at_ns,
+        };
+        let points = lane
+            .keyframes
+            .iter()
+            .map(|keyframe| AutomationPoint {
+                offset_ns: i64::from(keyframe.at_ms) * 1_000_000,
+                value: keyframe.value,
+                easing: match keyframe.easing {
+                    Some(ConfigEasing::Linear) | None => Easing::Linear,
+                    Some(ConfigEasing::EaseIn) => Easing::EaseIn,
+                    Some(ConfigEasing::EaseOut) => Easing::EaseOut,
+                    Some(ConfigEasing::EaseInOut) => Easing::EaseInOut,
+                    Some(ConfigEasing::Step) => Easing::Step,
+                },
+            })
+            .collect();
+        let (lanes, parameter) = match lane.parameter {
+            Parameter::GainDb => (&mut gain_lanes, 0),
+            Parameter::Pan => (&mut pan_lanes, 1),
+        };
+        if std::mem::replace(&mut automated[parameter][index], true) {
+            return Err(Error::Configuration(format!(
+                "AudioMixer: more than one {:?} automation lane for input {:?}",
+                lane.parameter, lane.input
+            )));
+        }
+        // A lane without keyframes leaves the strip's static value.
+        lanes[index] = AutomationLane::new(start_ns, points);
+    }
+
+    let ducking = match &config.ducking {
+        Some(ducking) => {
+            let defaults = DuckingSettings::default();
+            Some((
+                input_index(&ducking.key, "ducking.key")?,
+                DuckingSettings {
+                    threshold_db: ducking.threshold_db.unwrap_or(defaults.threshold_db),
+                    depth_db: ducking.depth_db.unwrap_or(defaults.depth_db),
+                    attack_ms: ducking.attack_ms.unwrap_or(defaults.attack_ms),
+                    hold_ms: ducking.hold_ms.unwrap_or(defaults.hold_ms),
+                    release_ms: ducking.release_ms.unwrap_or(defaults.release_ms),
+                },
+            ))
+        }
+        None => None,
+    };
+
+    Ok(MixSettings {
+        strategy: config.strategy.clone(),
+        strips,
+        gain_lanes,
+        pan_lanes,
+        ducking,
+    })
+}
+
+/// Mixing state carried across frames.
+#[derive(Debug, Clone)]
+struct Mixer {
+    settings: MixSettings,
+    /// Smoothed (left, right) gain of each input; `None` until the first
+    /// frame, which starts at its targets.
+    gains: Option<[(f32, f32); 2]>,
+    /// Key input, ducker and the sample rate it was built for.
+    ducker: Option<(usize, SidechainDucker, u32)>,
+}
+
+impl Mixer {
+    fn new(settings: MixSettings) -> Self {
+        Self {
+            settings,
+            gains: None,
+            ducker: None,
+        }
+    }
+
+    /// Swap in new settings, keeping the smoothed gains and, with the same
+    /// key, the ducker's state.
+    fn update(&mut self, settings: MixSettings) {
+        self.ducker = match (self.ducker.take(), settings.ducking) {
+            (Some((key, mut ducker, sample_rate)), Some((new_key, ducking))) if key == new_key => {
+                ducker.update(&ducking, sample_rate);
+                Some((key, ducker, sample_rate))
+            }
+            _ => None,
+        };
+        self.settings = settings;
+    }
+
+    /// Mix one buffer of each input into interleaved stereo. Each input is
+    /// mono or interleaved stereo, per `channels`. `timestamp_ns` is the
+    /// media-clock time of the first sample.
+    fn mix(
+        &mut self,
+        inputs: [&[f32]; 2],
+        channels: [usize; 2],
+        sample_rate: u32,
+        timestamp_ns: i64,
+    ) -> Vec<f32> {
+        let settings = &self.settings;
+        let stereo = channels.map(|channels| channels == 2);
+        let audible = settings.audible();
+        // A new key or sample rate starts a fresh ducker.
+        match settings.ducking {
+            Some((key, ducking)) => {
+                let current = matches!(
+                    &self.ducker,
+                    Some((ducker_key, _, rate)) if *ducker_key == key && *rate == sample_rate
+                );
+                if !current {
+                    self.ducker = Some((
+                        key,
+                        SidechainDucker::new(&ducking, sample_rate),
+                        sample_rate,
+                    ));
+                }
+            }
+            None => self.ducker = None,
+        }
+
+        let smoothing = 1.0 - (-1000.0 / (SMOOTHING_MS * sample_rate as f32)).exp();
+        let gains = self.gains.get_or_insert_with(|| {
+            [0, 1].map(|index| {
+                if audible[index] {
+                    settings.strip_gains(index, stereo[index], timestamp_ns)
+                } else {
+                    (0.0, 0.0)
+                }
+            })
+        });
+        let automated = settings
+            .gain_lanes
+            .iter()
+            .chain(&settings.pan_lanes)
+            .any(Option::is_some);
+        let mut targets =
+            [0, 1].map(|index| settings.strip_gains(index, stereo[index], timestamp_ns));
+
+        let frames = (inputs[0].len() / channels[0]).min(inputs[1].len() / channels[1]);
+        let mut mixed = Vec::with_capacity(frames * 2);
+        for i in 0..frames {
+            // (left, right) of each input; mono feeds both sides.
+            let frame = [0, 1].map(|index| {
+                let samples = &inputs[index][i * channels[index]..];
+                (samples[0], samples[channels[index] - 1])
+            });
+            if automated {
+                let sample_ns = timestamp_ns + (i as i64 * 1_000_000_000) / sample_rate as i64;
+                targets =
+                    [0, 1].map(|index| settings.strip_gains(index, stereo[index], sample_ns));
+            }
+            let duck = self.ducker.as_mut().map(|(key, ducker, _)| {
+                let (key_left, key_right) = if audible[*key] {
+                    frame[*key]
+                } else {
+                    (0.0, 0.0)
+                };
+                (*key, ducker.process(key_left.abs().max(key_right.abs())))
+            });
+
+            let (mut left, mut right) = (0.0f32, 0.0f32);
+            let (mut left_total, mut right_total) = (0.0f32, 0.0f32);
+            for index in 0..2 {
+                let (target_left, target_right) = if audible[index] {
+                    targets[index]
+                } else {
+                    (0.0, 0.0)
+                };
+                let gain = &mut gains[index];
+                gain.0 += (target_left - gain.0) * smoothing;
+                gain.1 += (target_right - gain.1) * smoothing;
+                let duck_gain = match duck {
+                    Some((key, duck_gain)) if key != index => duck_gain,
+                    _ => 1.0,
+                };
+                let (sample_left, sample_right) = frame[index];
+                left += sample_left * duck_gain * gain.0;
+                right += sample_right * duck_gain * gain.1;
+                left_total += gain.0 * duck_gain;
+                right_total += gain.1 * duck_gain;
+            }
+
+            let (left, right) = match settings.strategy {
+                Strategy::Sum => (left, right),
+                Strategy::SumNormalized => {
+                    (left / left_total.max(1.0), right / right_total.max(1.0))
+                }
+                Strategy::SumClipped => (left.clamp(-1.0, 1.0), right.clamp(-1.0, 1.0)),
+            };
+            mixed.push(left);
+            mixed.push(right);
+        }
+        mixed
+    }
+
+    /// Current ducking gain reduction in dB, when ducking is configured.
+    fn ducking_reduction_db(&self) -> Option<f32> {
+        self.ducker
+            .as_ref()
+            .map(|(_, ducker, _)| ducker.reduction_db())
+    }
+}
+
+/// Mono and stereo `input` frames as they are; any other layout folded to
+/// stereo.
+fn fold_to_stereo(frame: AudioFrame, input: &str) -> Result<AudioFrame> {
+    match frame_layout(&frame)? {
+        Some(ChannelLayout::Mono | ChannelLayout::Stereo) => Ok(frame),
+        Some(layout) => {
+            let matrix =
+                DownmixMatrix::new(&layout, &ChannelLayout::Stereo, DownmixOptions::default())?;
+            Ok(AudioFrame {
+                samples: matrix.apply(&frame.samples),
+                channels: 2,
+                channel_layout: Some(ChannelLayout::Stereo),
+                ..frame
+            })
+        }
+        None => Err(Error::Configuration(format!(
+            "AudioMixer: {} input has {} discrete channels with no layout to fold to stereo; set AudioFrame.channel_layout upstream",
+            input, frame.channels
+        ))),
+    }
+}
 
 #[streamlib_plugin_sdk::sdk::processor(
     "@tatolab/audio/AudioMixer",
-    description = "Mixes two mono audio signals into a single stereo signal",
+    description = "Mixes two inputs into stereo through per-input gain, pan, mute and solo, with optional sidechain ducking and gain/pan automation on the media clock. Mono inputs pan, stereo inputs balance, surround and ambisonic inputs are folded to stereo first",
     execution = reactive,
     scheduling = realtime,
     config = crate::_generated_::AudioMixerConfig,
-    input("left", "@tatolab/core/AudioFrame", description = "Left channel mono audio frame"),
-    input("right", "@tatolab/core/AudioFrame", description = "Right channel mono audio frame"),
+    input("left", "@tatolab/core/AudioFrame", description = "Mono input (panned hard left by default), or stereo / surround / ambisonic input (centred)"),
+    input("right", "@tatolab/core/AudioFrame", description = "Mono input (panned hard right by default), or stereo / surround / ambisonic input (centred)"),
     output("audio", "@tatolab/core/AudioFrame", description = "Mixed stereo audio frame"),
 )]
 pub struct AudioMixerProcessor {
     sample_rate: u32,
     buffer_size: usize,
     frame_counter: u64,
+    mixer: Option<Mixer>,
 }
 
 impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProcessor::Processor {
@@ -27,6 +398,8 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
         self.sample_rate = 0;
         self.buffer_size = 0;
         self.frame_counter = 0;
+        let settings = resolve_config(&self.config, MediaClock::now().as_nanos() as i64)?;
+        self.mixer = Some(Mixer::new(settings));
 
         tracing::info!(
             "AudioMixer: Starting (sample_rate and buffer_size will be inferred from first input, strategy: {:?})",
@@ -40,6 +413,27 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
         Ok(())
     }
 
+    fn on_config_update(&mut self) -> Result<()> {
+        let settings = resolve_config(&self.config, MediaClock::now().as_nanos() as i64)?;
+        tracing::info!(
+            "[AudioMixer] Config updated (strategy: {:?}, strips: {:?}, ducking: {:?}, {} automation lanes)",
+            settings.strategy,
+            settings.strips,
+            settings.ducking,
+            settings
+                .gain_lanes
+                .iter()
+                .chain(&settings.pan_lanes)
+                .filter(|lane| lane.is_some())
+                .count()
+        );
+        match self.mixer.as_mut() {
+            Some(mixer) => mixer.update(settings),
+            None => self.mixer = Some(Mixer::new(settings)),
+        }
+        Ok(())
+    }
+
     fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
         tracing::debug!("[AudioMixer] process() called");
 
@@ -48,25 +442,13 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
             return Ok(());
         }
 
-        let left_frame: AudioFrame = self.inputs.read("left")?;
-        let right_frame: AudioFrame = self.inputs.read("right")?;
-
-        if left_frame.channels != 1 {
-            return Err(Error::Configuration(format!(
-                "AudioMixer expects mono left input (1 channel), got {} channels",
-                left_frame.channels
-            )));
-        }
-        if right_frame.channels != 1 {
-            return Err(Error::Configuration(format!(
-                "AudioMixer expects mono right input (1 channel), got {} channels",
-                right_frame.channels
-            )));
-        }
+        let left_frame = fold_to_stereo(self.inputs.read("left")?, "left")?;
+        let right_frame = fold_to_stereo(self.inputs.read("right")?, "right")?;
+        let channels = [left_frame.channels as usize, right_frame.channels as usize];
 
         if self.sample_rate == 0 {
             self.sample_rate = left_frame.sample_rate;
-            self.buffer_size = left_frame.samples.len();
+            self.buffer_size = left_frame.samples.len() / channels[0];
             tracing::info!(
                 "[AudioMixer] Inferred config from first frame: {}Hz, {} samples",
                 self.sample_rate,
@@ -82,10 +464,10 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
             );
             return Ok(());
         }
-        if left_frame.samples.len() != self.buffer_size {
+        if left_frame.samples.len() / channels[0] != self.buffer_size {
             tracing::warn!(
                 "[AudioMixer] Dropping left frame with mismatched buffer_size {} (expected {})",
-                left_frame.samples.len(),
+                left_frame.samples.len() / channels[0],
                 self.buffer_size
             );
             return Ok(());
@@ -99,10 +481,10 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
             );
             return Ok(());
         }
-        if right_frame.samples.len() != self.buffer_size {
+        if right_frame.samples.len() / channels[1] != self.buffer_size {
             tracing::warn!(
                 "[AudioMixer] Dropping right frame with mismatched buffer_size {} (expected {})",
-                right_frame.samples.len(),
+                right_frame.samples.len() / channels[1],
                 self.buffer_size
             );
             return Ok(());
@@ -113,24 +495,22 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
         } else {
             right_frame.timestamp_ns.clone()
         };
+        let media_time_ns: i64 = timestamp_ns.parse().map_err(|_| {
+            Error::Runtime(format!(
+                "AudioMixer: timestamp_ns must be an integer, got {:?}",
+                timestamp_ns
+            ))
+        })?;
 
-        let mut stereo_samples = Vec::with_capacity(self.buffer_size * 2);
-
-        for i in 0..self.buffer_size {
-            let left_sample = left_frame.samples[i];
-            let right_sample = right_frame.samples[i];
-
-            let (final_left, final_right) = match self.config.strategy {
-                Strategy::Sum => (left_sample, right_sample),
-                Strategy::SumNormalized => (left_sample, right_sample),
-                Strategy::SumClipped => {
-                    (left_sample.clamp(-1.0, 1.0), right_sample.clamp(-1.0, 1.0))
-                }
-            };
-
-            stereo_samples.push(final_left);
-            stereo_samples.push(final_right);
-        }
+        let Some(mixer) = self.mixer.as_mut() else {
+            return Err(Error::Configuration("AudioMixer: not initialized".into()));
+        };
+        let stereo_samples = mixer.mix(
+            [&left_frame.samples, &right_frame.samples],
+            channels,
+            self.sample_rate,
+            media_time_ns,
+        );
 
         let output_frame = AudioFrame {
             samples: stereo_samples,
@@ -138,13 +518,266 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioMixerProc
             sample_rate: self.sample_rate,
             timestamp_ns,
             frame_index: self.frame_counter.to_string(),
+            channel_layout: Some(ChannelLayout::Stereo),
         };
 
         self.outputs.write("audio", &output_frame)?;
 
-        tracing::debug!("[AudioMixer] Wrote mixed stereo frame");
+        tracing::debug!(
+            "[AudioMixer] Wrote mixed stereo frame (ducking: {:?} dB)",
+            mixer.ducking_reduction_db()
+        );
         self.frame_counter += 1;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_generated_::tatolab__audio::audio_mixer_config::{
+        Automation, AutomationKeyframe, Ducking, Input,
+    };
+
+    const RATE: u32 = 48_000;
+
+    fn config(strategy: Strategy) -> AudioMixerConfig {
+        AudioMixerConfig {
+            strategy,
+            ..AudioMixerConfig::default()
+        }
+    }
+
+    fn strip(input: &str) -> Input {
+        Input {
+            input: input.to_string(),
+            ..Input::default()
+        }
+    }
+
+    fn mixer(config: &AudioMixerConfig) -> Mixer {
+        Mixer::new(resolve_config(config, 0).unwrap())
+    }
+
+    fn stereo(samples: &[f32]) -> Vec<(f32, f32)> {
+        samples.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+
+    #[test]
+    fn unconfigured_inputs_stay_hard_panned() {
+        let mut mixer = mixer(&config(Strategy::Sum));
+        let mixed = stereo(&mixer.mix([&[0.5; 4], &[-0.25; 4]], [1, 1], RATE, 0));
+        for (left, right) in mixed {
+            assert!((left - 0.5).abs() < 1e-6, "{left}");
+            assert!((right + 0.25).abs() < 1e-6, "{right}");
+        }
+    }
+
+    #[test]
+    fn gain_pan_and_normalization() {
+        let mut centered = AudioMixerConfig {
+            inputs: Some(vec![
+                Input {
+                    gain_db: Some(-6.0),
+                    pan: Some(0.0),
+                    ..strip("left")
+                },
+                Input {
+                    pan: Some(0.0),
+                    ..strip("right")
+                },
+            ]),
+            ..config(Strategy::Sum)
+        };
+        let mixed = stereo(&mixer(&centered).mix([&[1.0; 2], &[0.0; 2]], [1, 1], RATE, 0));
+        let expected = db_to_linear(-6.0) * std::f32::consts::FRAC_1_SQRT_2;
+        assert!((mixed[0].0 - expected).abs() < 1e-5, "{:?}", mixed[0]);
+        assert!((mixed[0].0 - mixed[0].1).abs() < 1e-6);
+
+        // Two full-scale inputs at center sum past unity; normalization
+        // scales by the gain feeding each channel.
+        centered.inputs.as_mut().unwrap()[0].gain_db = None;
+        centered.strategy = Strategy::SumNormalized;
+        let mixed = stereo(&mixer(&centered).mix([&[1.0; 2], &[1.0; 2]], [1, 1], RATE, 0));
+        assert!((mixed[0].0 - 1.0).abs() < 1e-5, "{:?}", mixed[0]);
+    }
+
+    #[test]
+    fn solo_and_mute_select_the_audible_inputs() {
+        let soloed = AudioMixerConfig {
+            inputs: Some(vec![Input {
+                solo: Some(true),
+                ..strip("right")
+            }]),
+            ..config(Strategy::Sum)
+        };
+        let mixed = stereo(&mixer(&soloed).mix([&[1.0; 2], &[1.0; 2]], [1, 1], RATE, 0));
+        assert_eq!(mixed[0], (0.0, 1.0));
+
+        let muted = AudioMixerConfig {
+            inputs: Some(vec![Input {
+                mute: Some(true),
+                ..strip("right")
+            }]),
+            ..config(Strategy::Sum)
+        };
+        let mixed = stereo(&mixer(&muted).mix([&[1.0; 2], &[1.0; 2]], [1, 1], RATE, 0));
+        assert_eq!(mixed[0], (1.0, 0.0));
+    }
+
+    #[test]
+    fn stereo_inputs_balance_and_surround_folds_to_stereo() {
+        // An unconfigured stereo input plays centred at unity.
+        let mixed = stereo(&mixer(&config(Strategy::Sum)).mix([&[0.5, -0.5, 0.5, -0.5], &[0.0; 2]], [2, 1], RATE, 0));
+        assert_eq!(mixed[0], (0.5, -0.5));
+
+        let balanced = AudioMixerConfig {
+            inputs: Some(vec![Input {
+                pan: Some(0.5),
+                ..strip("left")
+            }]),
+            ..config(Strategy::Sum)
+        };
+        let mixed = stereo(&mixer(&balanced).mix([&[1.0; 4], &[0.0; 2]], [2, 1], RATE, 0));
+        assert_eq!(mixed[0], (0.5, 1.0));
+
+        // Centre-only 5.1 lands in both sides at -3 dB.
+        let surround = AudioFrame {
+            samples: vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0],
+            channels: 6,
+            sample_rate: RATE,
+            timestamp_ns: "0".to_string(),
+            frame_index: "0".to_string(),
+            channel_layout: None,
+        };
+        let folded = fold_to_stereo(surround.clone(), "left").unwrap();
+        assert_eq!(folded.channels, 2);
+        assert!((folded.samples[0] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(folded.samples[0], folded.samples[1]);
+
+        let discrete = AudioFrame {
+            samples: vec![0.0; 3],
+            channels: 3,
+            ..surround
+        };
+        assert!(fold_to_stereo(discrete, "left").is_err());
+    }
+
+    #[test]
+    fn live_changes_are_smoothed() {
+        let mut mixer = mixer(&config(Strategy::Sum));
+        mixer.mix([&[1.0; 64], &[0.0; 64]], [1, 1], RATE, 0);
+        mixer.update(
+            resolve_config(
+                &AudioMixerConfig {
+                    inputs: Some(vec![Input {
+                        mute: Some(true),
+                        ..strip("left")
+                    }]),
+                    ..config(Strategy::Sum)
+                },
+                0,
+            )
+            .unwrap(),
+        );
+        let mixed = stereo(&mixer.mix([&[1.0; 4800], &[0.0; 4800]], [1, 1], RATE, 0));
+        // Fades over the smoothing time instead of cutting to silence.
+        assert!(mixed[0].0 > 0.9, "{:?}", mixed[0]);
+        assert!(mixed[4799].0 < 1e-6, "{:?}", mixed[4799]);
+    }
+
+    #[test]
+    fn ducking_turns_the_other_input_down_while_the_key_is_active() {
+        let ducked = AudioMixerConfig {
+            ducking: Some(Ducking {
+                key: "left".to_string(),
+                ..Ducking::default()
+            }),
+            ..config(Strategy::Sum)
+        };
+        let mut mixer = mixer(&ducked);
+        // Music alone plays at full level.
+        let quiet = stereo(&mixer.mix([&[0.0; 4800], &[0.5; 4800]], [1, 1], RATE, 0));
+        assert!((quiet[4799].1 - 0.5).abs() < 1e-6);
+        // Speech on the key pulls it down by the default 12 dB.
+        let speech = stereo(&mixer.mix([&[0.1; 4800], &[0.5; 4800]], [1, 1], RATE, 100_000_000));
+        let expected = 0.5 * db_to_linear(-12.0);
+        assert!(
+            (speech[4799].1 - expected).abs() < 1e-3,
+            "{:?}",
+            speech[4799]
+        );
+        // The key itself isn't ducked.
+        assert!((speech[4799].0 - 0.1).abs() < 1e-6);
+        assert!(mixer.ducking_reduction_db().unwrap() > 11.9);
+    }
+
+    #[test]
+    fn automation_follows_sample_timestamps() {
+        let start_ns: i64 = 1_000_000_000;
+        let fade = AudioMixerConfig {
+            automation: Some(vec![Automation {
+                input: "left".to_string(),
+                parameter: Parameter::GainDb,
+                keyframes: vec![
+                    AutomationKeyframe {
+                        at_ms: 0,
+                        value: 0.0,
+                        easing: None,
+                    },
+                    AutomationKeyframe {
+                        at_ms: 100,
+                        value: -60.0,
+                        easing: Some(ConfigEasing::Step),
+                    },
+                ],
+                start_ns: Some(start_ns.to_string()),
+            }]),
+            ..config(Strategy::Sum)
+        };
+        let mut mixer = mixer(&fade);
+        // The step lands 100 ms after start_ns: 4800 samples into a buffer
+        // that starts at start_ns, whatever the chunking.
+        let mixed = stereo(&mixer.mix([&[1.0; 9600], &[0.0; 9600]], [1, 1], RATE, start_ns));
+        assert_eq!(mixed[4799].0, 1.0);
+        assert!(mixed[4800].0 < 1.0);
+        assert!(mixed[9599].0 < 0.01, "{:?}", mixed[9599]);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let unknown_input = AudioMixerConfig {
+            inputs: Some(vec![strip("center")]),
+            ..config(Strategy::Sum)
+        };
+        assert!(resolve_config(&unknown_input, 0).is_err());
+
+        let lane = Automation {
+            input: "right".to_string(),
+            parameter: Parameter::Pan,
+            keyframes: Vec::new(),
+            start_ns: Some("soon".to_string()),
+        };
+        let bad_start = AudioMixerConfig {
+            automation: Some(vec![lane.clone()]),
+            ..config(Strategy::Sum)
+        };
+        assert!(resolve_config(&bad_start, 0).is_err());
+
+        let duplicated = AudioMixerConfig {
+            automation: Some(vec![
+                Automation {
+                    start_ns: None,
+                    ..lane.clone()
+                },
+                Automation {
+                    start_ns: None,
+                    ..lane
+                },
+            ]),
+            ..config(Strategy::Sum)
+        };
+        assert!(resolve_config(&duplicated, 0).is_err());
+    }
+}
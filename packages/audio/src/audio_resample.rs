@@ -7,6 +7,8 @@ use rubato::{
 use serde::{Deserialize, Serialize};
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 
+use crate::channel_layout::MAX_CHANNELS;
+
 /// Quality presets for audio resampling.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ResamplingQuality {
@@ -44,26 +46,17 @@ impl ResamplingQuality {
     }
 }
 
-/// Multi-channel audio resampler (1-8 channels).
+/// Multi-channel audio resampler (1-16 channels). Channels are resampled
+/// independently, so any layout — speakers or ambisonics — comes through
+/// intact.
 pub struct AudioResampler {
-    inner: ResamplerInner,
+    inner: SincFixedIn<f32>,
     source_sample_rate: u32,
     target_sample_rate: u32,
     channels: u8,
     quality: ResamplingQuality,
 }
 
-enum ResamplerInner {
-    One(SincFixedIn<f32>),
-    Two(SincFixedIn<f32>),
-    Three(SincFixedIn<f32>),
-    Four(SincFixedIn<f32>),
-    Five(SincFixedIn<f32>),
-    Six(SincFixedIn<f32>),
-    Seven(SincFixedIn<f32>),
-    Eight(SincFixedIn<f32>),
-}
-
 impl AudioResampler {
     /// Create a new multi-channel audio resampler.
     pub fn new(
@@ -73,98 +66,24 @@ impl AudioResampler {
         chunk_size: usize,
         quality: ResamplingQuality,
     ) -> Result<Self> {
+        if !(1..=MAX_CHANNELS).contains(&channels) {
+            return Err(Error::Configuration(format!(
+                "Unsupported channel count: {}. Must be 1-{}.",
+                channels, MAX_CHANNELS
+            )));
+        }
+
         let ratio = target_rate as f64 / source_rate as f64;
         let params = quality.to_parameters();
-
-        // Create channel-specific resampler
-        let inner = match channels {
-            1 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 1)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 1-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::One(resampler)
-            }
-            2 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 2)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 2-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Two(resampler)
-            }
-            3 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 3)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 3-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Three(resampler)
-            }
-            4 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 4)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 4-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Four(resampler)
-            }
-            5 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 5)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 5-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Five(resampler)
-            }
-            6 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 6)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 6-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Six(resampler)
-            }
-            7 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 7)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 7-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Seven(resampler)
-            }
-            8 => {
-                let resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, 8)
-                    .map_err(|e| {
-                        Error::Runtime(format!(
-                            "Failed to create 8-channel resampler: {:?}",
-                            e
-                        ))
-                    })?;
-                ResamplerInner::Eight(resampler)
-            }
-            _ => {
-                return Err(Error::Configuration(format!(
-                    "Unsupported channel count: {}. Must be 1-8.",
-                    channels
-                )))
-            }
-        };
+        let inner =
+            SincFixedIn::<f32>::new(ratio, 2.0, params, chunk_size, channels as usize).map_err(
+                |e| {
+                    Error::Runtime(format!(
+                        "Failed to create {}-channel resampler: {:?}",
+                        channels, e
+                    ))
+                },
+            )?;
 
         Ok(Self {
             inner,
@@ -189,18 +108,10 @@ impl AudioResampler {
             }
         }
 
-        // Resample using the appropriate inner resampler
-        let planar_output = match &mut self.inner {
-            ResamplerInner::One(r) => r.process(&planar_input, None),
-            ResamplerInner::Two(r) => r.process(&planar_input, None),
-            ResamplerInner::Three(r) => r.process(&planar_input, None),
-            ResamplerInner::Four(r) => r.process(&planar_input, None),
-            ResamplerInner::Five(r) => r.process(&planar_input, None),
-            ResamplerInner::Six(r) => r.process(&planar_input, None),
-            ResamplerInner::Seven(r) => r.process(&planar_input, None),
-            ResamplerInner::Eight(r) => r.process(&planar_input, None),
-        }
-        .map_err(|e| Error::Runtime(format!("Resampling failed: {:?}", e)))?;
+        let planar_output = self
+            .inner
+            .process(&planar_input, None)
+            .map_err(|e| Error::Runtime(format!("Resampling failed: {:?}", e)))?;
 
         // Convert back to interleaved
         let output_samples_per_channel = planar_output[0].len();
@@ -484,15 +395,27 @@ mod tests {
         );
         assert!(result.is_ok());
 
-        // Test with invalid channel count (9 channels, outside 1-8 range)
+        // Second-order ambisonics (9 channels) is supported
         let result = AudioResampler::new(
             48000,
             24000,
-            9, // invalid
+            9, // 9 channels
             960,
             ResamplingQuality::Medium,
         );
-        assert!(result.is_err());
+        assert!(result.is_ok());
+
+        // Test with invalid channel counts (outside 1-16 range)
+        for channels in [0, 17] {
+            let result = AudioResampler::new(
+                48000,
+                24000,
+                channels, // invalid
+                960,
+                ResamplingQuality::Medium,
+            );
+            assert!(result.is_err());
+        }
     }
 
     #[test]
@@ -17,7 +17,7 @@ fn quality_to_resampling_quality(quality: &Quality) -> ResamplingQuality {
 
 #[streamlib_plugin_sdk::sdk::processor(
     "@tatolab/audio/AudioResampler",
-    description = "Resamples audio between sample rates",
+    description = "Resamples audio between sample rates, keeping its channel layout (1-16 channels)",
     execution = reactive,
     scheduling = realtime,
     config = crate::_generated_::AudioResamplerConfig,
@@ -28,7 +28,9 @@ pub struct AudioResamplerProcessor {
     resampler: Option<AudioResampler>,
     output_sample_rate: u32,
     frame_counter: u64,
-    channels: u8,
+    /// Sample rate and channel count the resampler was built for; a change
+    /// mid-stream rebuilds it.
+    input_format: Option<(u32, u8)>,
 }
 
 impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioResamplerProcessor::Processor {
@@ -59,23 +61,22 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioResampler
 
         let input_frame: AudioFrame = self.inputs.read("audio_in")?;
 
-        if self.channels == 0 {
-            self.channels = input_frame.channels;
-        }
-
-        if self.resampler.is_none() {
+        let input_format = (input_frame.sample_rate, input_frame.channels);
+        if self.input_format != Some(input_format) {
             let input_sample_rate = input_frame.sample_rate;
+            self.resampler = None;
 
             if input_sample_rate != self.output_sample_rate {
                 let chunk_size = input_frame.samples.len() / input_frame.channels as usize;
                 let quality = quality_to_resampling_quality(&self.config.quality);
 
                 tracing::info!(
-                    "[AudioResampler] Initializing: {}Hz → {}Hz ({:?}, {} channels, chunk_size={})",
+                    "[AudioResampler] Initializing: {}Hz → {}Hz ({:?}, {} channels, layout {:?}, chunk_size={})",
                     input_sample_rate,
                     self.output_sample_rate,
                     self.config.quality,
                     input_frame.channels,
+                    input_frame.channel_layout,
                     chunk_size
                 );
 
@@ -94,6 +95,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioResampler
                     input_sample_rate
                 );
             }
+            self.input_format = Some(input_format);
         }
 
         let output_samples = if let Some(ref mut resampler) = self.resampler {
@@ -104,10 +106,11 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for AudioResampler
 
         let output_frame = AudioFrame {
             samples: output_samples,
-            channels: self.channels,
+            channels: input_frame.channels,
             sample_rate: self.output_sample_rate,
             timestamp_ns: input_frame.timestamp_ns.clone(),
             frame_index: self.frame_counter.to_string(),
+            channel_layout: input_frame.channel_layout.clone(),
         };
 
         self.outputs.write("audio_out", &output_frame)?;
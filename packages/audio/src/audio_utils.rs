@@ -3,9 +3,35 @@
 
 use crate::audio_resample::{AudioResampler, ResamplingQuality};
 use crate::_generated_::AudioFrame;
+use crate::channel_layout::{
+    ChannelLayout, DownmixMatrix, DownmixOptions, channel_count, default_layout,
+};
 use streamlib_plugin_sdk::sdk::error::Result;
 
+/// ITU downmix for surround and ambisonic sources whose target channel
+/// count names a layout they fold to; `None` when there is no such matrix.
+fn itu_downmix(frame: &AudioFrame, target_channels: u8) -> Option<AudioFrame> {
+    let source = frame.channel_layout.as_ref()?;
+    if channel_count(source) != frame.channels || frame.channels <= 2 {
+        return None;
+    }
+    let target = default_layout(target_channels)?;
+    let matrix = DownmixMatrix::new(source, &target, DownmixOptions::default()).ok()?;
+    Some(AudioFrame {
+        samples: matrix.apply(&frame.samples),
+        channels: target_channels,
+        timestamp_ns: frame.timestamp_ns.clone(),
+        frame_index: frame.frame_index.clone(),
+        sample_rate: frame.sample_rate,
+        channel_layout: Some(target),
+    })
+}
+
 /// Convert audio frame to a different channel count.
+///
+/// Surround and ambisonic frames with a `channel_layout` fold down with the
+/// ITU coefficients when the target is 5.1, stereo or mono; everything else
+/// is mapped channel by channel.
 pub fn convert_channels(frame: &AudioFrame, target_channels: u8) -> AudioFrame {
     let source_channels = frame.channels;
 
@@ -14,6 +40,10 @@ pub fn convert_channels(frame: &AudioFrame, target_channels: u8) -> AudioFrame {
         return frame.clone();
     }
 
+    if let Some(downmixed) = itu_downmix(frame, target_channels) {
+        return downmixed;
+    }
+
     let source_count = source_channels as usize;
     let target_count = target_channels as usize;
     let sample_count = frame.samples.len() / source_count;
@@ -110,6 +140,7 @@ pub fn convert_channels(frame: &AudioFrame, target_channels: u8) -> AudioFrame {
         timestamp_ns: frame.timestamp_ns.clone(),
         frame_index: frame.frame_index.clone(),
         sample_rate: frame.sample_rate,
+        channel_layout: default_layout(target_channels),
     }
 }
 
@@ -141,6 +172,7 @@ pub fn resample_frame(
         timestamp_ns: frame.timestamp_ns.clone(),
         frame_index: frame.frame_index.clone(),
         sample_rate: target_sample_rate,
+        channel_layout: frame.channel_layout.clone(),
     })
 }
 
@@ -164,6 +196,8 @@ pub struct AudioRechunker {
     target_sample_count: usize,
     buffer: Vec<f32>,
     next_frame_number: u64,
+    /// Layout of the most recent input, carried onto the output frames.
+    channel_layout: Option<ChannelLayout>,
 }
 
 impl AudioRechunker {
@@ -174,6 +208,7 @@ impl AudioRechunker {
             target_sample_count,
             buffer: Vec::new(),
             next_frame_number: 0,
+            channel_layout: None,
         }
     }
 
@@ -191,6 +226,7 @@ impl AudioRechunker {
 
         // Accumulate samples
         self.buffer.extend_from_slice(&input.samples);
+        self.channel_layout = input.channel_layout.clone();
 
         let channels_usize = self.channels as usize;
         let target_total_samples = self.target_sample_count * channels_usize;
@@ -206,6 +242,7 @@ impl AudioRechunker {
                 timestamp_ns: input.timestamp_ns.clone(),
                 frame_index: self.next_frame_number.to_string(),
                 sample_rate: input.sample_rate,
+                channel_layout: self.channel_layout.clone(),
             };
 
             self.next_frame_number += 1;
@@ -237,6 +274,7 @@ impl AudioRechunker {
             timestamp_ns: timestamp_ns.to_string(),
             frame_index: self.next_frame_number.to_string(),
             sample_rate,
+            channel_layout: self.channel_layout.clone(),
         };
 
         self.next_frame_number += 1;
@@ -262,6 +300,7 @@ mod tests {
             timestamp_ns: "0".to_string(),
             frame_index: "0".to_string(),
             sample_rate: 48000,
+            channel_layout: None,
         };
 
         let stereo = convert_channels(&frame, 2);
@@ -279,6 +318,7 @@ mod tests {
             timestamp_ns: "0".to_string(),
             frame_index: "0".to_string(),
             sample_rate: 48000,
+            channel_layout: None,
         };
 
         let mono = convert_channels(&frame, 1);
@@ -299,6 +339,7 @@ mod tests {
             timestamp_ns: "0".to_string(),
             frame_index: "0".to_string(),
             sample_rate: 48000,
+            channel_layout: None,
         };
 
         let output = rechunker.process(&frame).expect("Should output frame");
@@ -317,6 +358,7 @@ mod tests {
             timestamp_ns: "0".to_string(),
             frame_index: "0".to_string(),
             sample_rate: 48000,
+            channel_layout: None,
         };
         assert!(rechunker.process(&frame1).is_none());
 
@@ -327,10 +369,33 @@ mod tests {
             timestamp_ns: "100".to_string(),
             frame_index: "1".to_string(),
             sample_rate: 48000,
+            channel_layout: None,
         };
         let output = rechunker.process(&frame2).expect("Should output frame");
 
         assert_eq!(output.samples.len() / output.channels as usize, 4);
         assert_eq!(&output.samples, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
     }
+
+    #[test]
+    fn test_convert_surround_to_stereo_uses_itu_downmix() {
+        // One 5.1 sample: L R C LFE Ls Rs
+        let frame = AudioFrame {
+            samples: vec![0.5, 0.0, 0.2, 1.0, 0.4, 0.0],
+            channels: 6,
+            timestamp_ns: "0".to_string(),
+            frame_index: "0".to_string(),
+            sample_rate: 48000,
+            channel_layout: Some(ChannelLayout::Surround51),
+        };
+
+        let stereo = convert_channels(&frame, 2);
+
+        let k = std::f32::consts::FRAC_1_SQRT_2;
+        assert_eq!(stereo.channels, 2);
+        assert_eq!(stereo.channel_layout, Some(ChannelLayout::Stereo));
+        // L' = L + kC + kLs, R' = R + kC + kRs; the LFE is dropped
+        assert!((stereo.samples[0] - (0.5 + k * 0.2 + k * 0.4)).abs() < 1e-6);
+        assert!((stereo.samples[1] - k * 0.2).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,636 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Binaural rendering through a structural HRTF — the spherical-head model
+//! of Brown & Duda (1998) — and decoding of AmbiX ambisonics through
+//! virtual loudspeakers rendered the same way.
+//!
+//! Each source reaches each ear through the interaural delay of a sphere
+//! (Woodworth's formula), a one-pole/one-zero head-shadow filter that lifts
+//! the highs at the near ear and cuts them behind the head, and five pinna
+//! echoes whose delays shrink as the source rises — the main elevation cue.
+//! Directions are azimuth counter-clockwise from straight ahead (90° is
+//! hard left) and elevation up from the horizon. Filter parameters glide to
+//! each new position across a block, so moving sources don't click.
+
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use crate::channel_layout::{ChannelLayout, channel_count};
+
+/// Average adult head radius.
+pub const DEFAULT_HEAD_RADIUS_M: f32 = 0.0875;
+
+const SPEED_OF_SOUND_M_S: f32 = 343.0;
+
+/// Closest a source gets for distance attenuation (+12 dB at a 1 m
+/// reference), so sources passing through the head don't blow up.
+const MIN_DISTANCE_M: f32 = 0.25;
+
+/// Deepest head shadow and the angle from the ear it occurs at.
+const SHADOW_ALPHA_MIN: f32 = 0.1;
+const SHADOW_THETA_MIN_DEG: f32 = 150.0;
+
+/// Pinna echoes as (reflection coefficient, A, B, D): at azimuth θ and
+/// elevation φ an echo arrives `A·cos(θ/2)·sin(D·(90° − φ)) + B` samples
+/// (at 44.1 kHz) after the direct sound.
+const PINNA_ECHOES: [(f32, f32, f32, f32); 5] = [
+    (0.5, 1.0, 2.0, 1.0),
+    (-1.0, 5.0, 4.0, 0.5),
+    (0.5, 5.0, 7.0, 0.5),
+    (-0.25, 5.0, 11.0, 0.5),
+    (0.25, 5.0, 13.0, 0.5),
+];
+const PINNA_SAMPLE_RATE: f32 = 44_100.0;
+
+/// Where a source sits around the listener.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourcePosition {
+    pub azimuth_deg: f32,
+    pub elevation_deg: f32,
+    /// `None` keeps the source at the reference distance (unity gain).
+    pub distance_m: Option<f32>,
+}
+
+impl SourcePosition {
+    pub const FRONT: Self = Self::at(0.0, 0.0);
+
+    pub const fn at(azimuth_deg: f32, elevation_deg: f32) -> Self {
+        Self {
+            azimuth_deg,
+            elevation_deg,
+            distance_m: None,
+        }
+    }
+
+    /// Unit vector: x ahead, y left, z up.
+    fn direction(&self) -> [f32; 3] {
+        let (azimuth, elevation) = (
+            self.azimuth_deg.to_radians(),
+            self.elevation_deg.to_radians(),
+        );
+        [
+            elevation.cos() * azimuth.cos(),
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+        ]
+    }
+}
+
+/// Listener head orientation, applied yaw, then pitch, then roll. Zero
+/// faces azimuth 0; positive yaw turns left, positive pitch looks up and
+/// positive roll tilts the right ear down.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HeadOrientation {
+    pub yaw_deg: f32,
+    pub pitch_deg: f32,
+    pub roll_deg: f32,
+}
+
+impl HeadOrientation {
+    /// A room direction in head axes.
+    fn to_head(self, [x, y, z]: [f32; 3]) -> [f32; 3] {
+        let (sin, cos) = (-self.yaw_deg).to_radians().sin_cos();
+        let (x, y) = (x * cos - y * sin, x * sin + y * cos);
+        let (sin, cos) = self.pitch_deg.to_radians().sin_cos();
+        let (x, z) = (x * cos + z * sin, z * cos - x * sin);
+        let (sin, cos) = (-self.roll_deg).to_radians().sin_cos();
+        let (y, z) = (y * cos - z * sin, y * sin + z * cos);
+        [x, y, z]
+    }
+}
+
+/// Where each channel plays from until it is placed: loudspeaker layouts
+/// at their ITU-R BS.2051 angles, so surround mixes can be monitored on
+/// headphones, and everything else straight ahead.
+pub fn default_sources(channels: u8, layout: Option<&ChannelLayout>) -> Vec<SourcePosition> {
+    let azimuths: &[f32] = match layout {
+        Some(ChannelLayout::Stereo) => &[30.0, -30.0],
+        Some(ChannelLayout::Surround51) => &[30.0, -30.0, 0.0, 0.0, 110.0, -110.0],
+        Some(ChannelLayout::Surround71) => &[30.0, -30.0, 0.0, 0.0, 135.0, -135.0, 90.0, -90.0],
+        _ => &[],
+    };
+    (0..channels as usize)
+        .map(|channel| SourcePosition::at(azimuths.get(channel).copied().unwrap_or(0.0), 0.0))
+        .collect()
+}
+
+/// Ambisonic order of `layout`, if it is a sound field.
+pub fn ambisonic_order(layout: &ChannelLayout) -> Option<usize> {
+    match layout {
+        ChannelLayout::AmbisonicsOrder1 => Some(1),
+        ChannelLayout::AmbisonicsOrder2 => Some(2),
+        ChannelLayout::AmbisonicsOrder3 => Some(3),
+        _ => None,
+    }
+}
+
+/// Filter parameters for one ear at one direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EarParams {
+    /// Interaural delay, in samples.
+    delay: f32,
+    /// Head-shadow gain at high frequencies: 2 facing the ear, down to
+    /// `SHADOW_ALPHA_MIN` behind the head.
+    alpha: f32,
+    /// Pinna echo delays after the direct sound, in samples.
+    echoes: [f32; 5],
+}
+
+impl EarParams {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            delay: mix(self.delay, other.delay),
+            alpha: mix(self.alpha, other.alpha),
+            echoes: std::array::from_fn(|k| mix(self.echoes[k], other.echoes[k])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct VoiceParams {
+    /// Left, right.
+    ears: [EarParams; 2],
+    gain: f32,
+}
+
+impl VoiceParams {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            ears: [0, 1].map(|ear| self.ears[ear].lerp(&other.ears[ear], t)),
+            gain: self.gain + (other.gain - self.gain) * t,
+        }
+    }
+}
+
+/// The spherical-head HRTF at one sample rate and head size.
+#[derive(Debug, Clone)]
+pub struct SphericalHeadModel {
+    sample_rate: f32,
+    /// Head radius over the speed of sound, in seconds.
+    head_delay_s: f32,
+    /// Longest delay any tap reads, in samples.
+    max_delay: usize,
+}
+
+impl SphericalHeadModel {
+    pub fn new(sample_rate: u32, head_radius_m: f32) -> Self {
+        let sample_rate = sample_rate as f32;
+        let head_delay_s = head_radius_m / SPEED_OF_SOUND_M_S;
+        let interaural = head_delay_s * (1.0 + FRAC_PI_2) * sample_rate;
+        let echo = PINNA_ECHOES
+            .iter()
+            .map(|&(_, a, b, _)| a + b)
+            .fold(0.0, f32::max)
+            * sample_rate
+            / PINNA_SAMPLE_RATE;
+        Self {
+            sample_rate,
+            head_delay_s,
+            max_delay: (interaural + echo).ceil() as usize + 1,
+        }
+    }
+
+    /// Parameters for a source in head-relative unit `direction`.
+    fn params(&self, [x, y, z]: [f32; 3], gain: f32) -> VoiceParams {
+        let azimuth = y.atan2(x);
+        let elevation = z.clamp(-1.0, 1.0).asin();
+        let echo_scale = self.sample_rate / PINNA_SAMPLE_RATE;
+        let echoes = PINNA_ECHOES.map(|(_, a, b, d)| {
+            (a * (azimuth / 2.0).cos() * (d * (FRAC_PI_2 - elevation)).sin() + b) * echo_scale
+        });
+        // `side` is the ear's y: 1 left, -1 right.
+        let ear = |side: f32| {
+            let theta = (y * side).clamp(-1.0, 1.0).acos();
+            let path = if theta < FRAC_PI_2 {
+                1.0 - theta.cos()
+            } else {
+                1.0 + theta - FRAC_PI_2
+            };
+            let alpha = (1.0 + SHADOW_ALPHA_MIN / 2.0)
+                + (1.0 - SHADOW_ALPHA_MIN / 2.0) * (theta * 180.0 / SHADOW_THETA_MIN_DEG).cos();
+            EarParams {
+                delay: path * self.head_delay_s * self.sample_rate,
+                alpha,
+                echoes,
+            }
+        };
+        VoiceParams {
+            ears: [ear(1.0), ear(-1.0)],
+            gain,
+        }
+    }
+
+    /// Head-shadow coefficients `(b0, b1, a1)`: the bilinear transform of
+    /// `(1 + α·s/2ω₀) / (1 + s/2ω₀)` with ω₀ = c / a — unity at DC, α at
+    /// high frequencies.
+    fn shadow(&self, alpha: f32) -> (f32, f32, f32) {
+        let two_omega0 = 2.0 / self.head_delay_s;
+        let two_fs = 2.0 * self.sample_rate;
+        let norm = 1.0 / (two_omega0 + two_fs);
+        (
+            (two_omega0 + alpha * two_fs) * norm,
+            (two_omega0 - alpha * two_fs) * norm,
+            (two_omega0 - two_fs) * norm,
+        )
+    }
+}
+
+/// One source rendered to both ears, with the delay line and filter state
+/// that carry across blocks.
+#[derive(Debug, Clone)]
+struct BinauralVoice {
+    /// Power-of-two ring of recent input.
+    history: Vec<f32>,
+    /// Where the next input sample goes.
+    write: usize,
+    /// Head-shadow state per ear: last input, last output.
+    shadow_state: [(f32, f32); 2],
+    /// Where the last block ended; the next glides from here.
+    params: Option<VoiceParams>,
+}
+
+impl BinauralVoice {
+    fn new(model: &SphericalHeadModel) -> Self {
+        Self {
+            history: vec![0.0; (model.max_delay + 2).next_power_of_two()],
+            write: 0,
+            shadow_state: [(0.0, 0.0); 2],
+            params: None,
+        }
+    }
+
+    /// Input from `delay` samples ago, linearly interpolated.
+    fn tap(&self, delay: f32) -> f32 {
+        let mask = self.history.len() - 1;
+        let whole = delay as usize;
+        let newest = self.write.wrapping_sub(1);
+        let a = self.history[newest.wrapping_sub(whole) & mask];
+        let b = self.history[newest.wrapping_sub(whole + 1) & mask];
+        a + (b - a) * (delay - whole as f32)
+    }
+
+    /// Add `input` to interleaved stereo `output`, gliding from where the
+    /// last block ended to head-relative `direction` at `gain`.
+    fn render(
+        &mut self,
+        model: &SphericalHeadModel,
+        input: impl Iterator<Item = f32>,
+        output: &mut [f32],
+        direction: [f32; 3],
+        gain: f32,
+    ) {
+        let target = model.params(direction, gain);
+        let start = self.params.unwrap_or(target);
+        let frames = (output.len() / 2).max(1) as f32;
+        let mask = self.history.len() - 1;
+        for (n, (sample, out)) in input.zip(output.chunks_exact_mut(2)).enumerate() {
+            let params = start.lerp(&target, (n + 1) as f32 / frames);
+            self.history[self.write] = sample;
+            self.write = (self.write + 1) & mask;
+            for (ear, out) in out.iter_mut().enumerate() {
+                let ear_params = &params.ears[ear];
+                let mut x = self.tap(ear_params.delay);
+                for (&(reflection, ..), &echo) in PINNA_ECHOES.iter().zip(&ear_params.echoes) {
+                    x += reflection * self.tap(ear_params.delay + echo);
+                }
+                let (b0, b1, a1) = model.shadow(ear_params.alpha);
+                let (x1, y1) = self.shadow_state[ear];
+                let y = b0 * x + b1 * x1 - a1 * y1;
+                self.shadow_state[ear] = (x, y);
+                *out += y * params.gain;
+            }
+        }
+        self.params = Some(target);
+    }
+}
+
+/// Real spherical harmonics to third order at unit `direction`, in ACN
+/// order with SN3D normalization (AmbiX).
+fn spherical_harmonics([x, y, z]: [f32; 3]) -> [f32; 16] {
+    let sqrt3 = 3f32.sqrt();
+    let sqrt15 = 15f32.sqrt();
+    let sqrt3_8 = (3.0f32 / 8.0).sqrt();
+    let sqrt5_8 = (5.0f32 / 8.0).sqrt();
+    [
+        1.0,
+        y,
+        z,
+        x,
+        sqrt3 * x * y,
+        sqrt3 * y * z,
+        (3.0 * z * z - 1.0) / 2.0,
+        sqrt3 * x * z,
+        sqrt3 / 2.0 * (x * x - y * y),
+        sqrt5_8 * y * (3.0 * x * x - y * y),
+        sqrt15 * x * y * z,
+        sqrt3_8 * y * (5.0 * z * z - 1.0),
+        z * (5.0 * z * z - 3.0) / 2.0,
+        sqrt3_8 * x * (5.0 * z * z - 1.0),
+        sqrt15 / 2.0 * z * (x * x - y * y),
+        sqrt5_8 * x * (x * x - 3.0 * y * y),
+    ]
+}
+
+/// Legendre polynomial P_order(x), up to third order.
+fn legendre(order: usize, x: f32) -> f32 {
+    match order {
+        0 => 1.0,
+        1 => x,
+        2 => (3.0 * x * x - 1.0) / 2.0,
+        _ => (5.0 * x * x * x - 3.0 * x) / 2.0,
+    }
+}
+
+/// What the input channels carry.
+#[derive(Debug, Clone)]
+enum Scene {
+    /// One source per channel.
+    Sources,
+    /// An AmbiX sound field decoded to virtual loudspeakers: their room
+    /// directions, and per loudspeaker one gain per input channel.
+    Ambisonics {
+        speakers: Vec<[f32; 3]>,
+        decoder: Vec<f32>,
+    },
+}
+
+/// Virtual loudspeakers spread evenly over the sphere (a Fibonacci
+/// lattice), twice as many as the order has components, and the max-rE
+/// sampling decoder feeding them.
+fn ambisonic_scene(order: usize) -> Scene {
+    let components = (order + 1) * (order + 1);
+    let count = 2 * components;
+    let golden_angle = PI * (3.0 - 5f32.sqrt());
+    let speakers: Vec<[f32; 3]> = (0..count)
+        .map(|i| {
+            let z = 1.0 - (2 * i + 1) as f32 / count as f32;
+            let radius = (1.0 - z * z).sqrt();
+            let (sin, cos) = (i as f32 * golden_angle).sin_cos();
+            [radius * cos, radius * sin, z]
+        })
+        .collect();
+    // max-rE weights sharpen the decoded image.
+    let spread = (137.9f32).to_radians() / (order as f32 + 1.51);
+    let decoder = speakers
+        .iter()
+        .flat_map(|&speaker| {
+            let harmonics = spherical_harmonics(speaker);
+            (0..components).map(move |acn| {
+                let degree = (acn as f32).sqrt() as usize;
+                legendre(degree, spread.cos()) * (2 * degree + 1) as f32 * harmonics[acn]
+                    / count as f32
+            })
+        })
+        .collect();
+    Scene::Ambisonics { speakers, decoder }
+}
+
+/// Binaural renderer for one input format.
+#[derive(Debug, Clone)]
+pub struct BinauralRenderer {
+    model: SphericalHeadModel,
+    channels: usize,
+    scene: Scene,
+    voices: Vec<BinauralVoice>,
+    reference_distance_m: f32,
+}
+
+impl BinauralRenderer {
+    /// Renderer for `channels` interleaved channels in `layout`. Ambisonic
+    /// layouts decode as a sound field; anything else renders each channel
+    /// as a source.
+    pub fn new(
+        sample_rate: u32,
+        channels: u8,
+        layout: Option<&ChannelLayout>,
+        head_radius_m: f32,
+        reference_distance_m: f32,
+    ) -> Self {
+        let model = SphericalHeadModel::new(sample_rate, head_radius_m);
+        let scene = match layout.and_then(ambisonic_order) {
+            Some(order) if layout.map(channel_count) == Some(channels) => ambisonic_scene(order),
+            _ => Scene::Sources,
+        };
+        let voice_count = match &scene {
+            Scene::Sources => channels as usize,
+            Scene::Ambisonics { speakers, .. } => speakers.len(),
+        };
+        Self {
+            voices: (0..voice_count)
+                .map(|_| BinauralVoice::new(&model))
+                .collect(),
+            model,
+            channels: channels as usize,
+            scene,
+            reference_distance_m,
+        }
+    }
+
+    /// Whether the input is a sound field rather than sources.
+    pub fn is_ambisonic(&self) -> bool {
+        matches!(self.scene, Scene::Ambisonics { .. })
+    }
+
+    /// Render interleaved `input` to interleaved stereo. `sources` places
+    /// each channel — channels past its end play from straight ahead — and
+    /// is ignored for a sound field, which only turns with the head.
+    pub fn render(
+        &mut self,
+        input: &[f32],
+        sources: &[SourcePosition],
+        head: &HeadOrientation,
+    ) -> Vec<f32> {
+        let channels = self.channels;
+        let frames = input.len() / channels;
+        let mut output = vec![0.0; frames * 2];
+        match &self.scene {
+            Scene::Sources => {
+                for (channel, voice) in self.voices.iter_mut().enumerate() {
+                    let source = sources.get(channel).unwrap_or(&SourcePosition::FRONT);
+                    let distance = source
+                        .distance_m
+                        .unwrap_or(self.reference_distance_m)
+                        .max(MIN_DISTANCE_M);
+                    let samples = input[channel..].iter().step_by(channels).copied();
+                    voice.render(
+                        &self.model,
+                        samples.take(frames),
+                        &mut output,
+                        head.to_head(source.direction()),
+                        self.reference_distance_m / distance,
+                    );
+                }
+            }
+            Scene::Ambisonics { speakers, decoder } => {
+                for ((speaker, row), voice) in speakers
+                    .iter()
+                    .zip(decoder.chunks_exact(channels))
+                    .zip(&mut self.voices)
+                {
+                    let feed = input.chunks_exact(channels).map(|frame| {
+                        row.iter()
+                            .zip(frame)
+                            .map(|(gain, sample)| gain * sample)
+                            .sum()
+                    });
+                    voice.render(&self.model, feed, &mut output, head.to_head(*speaker), 1.0);
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    /// Per-ear energy and first arrival of an impulse from `source`.
+    fn impulse_response(
+        renderer: &mut BinauralRenderer,
+        input: &[f32],
+        sources: &[SourcePosition],
+        head: &HeadOrientation,
+    ) -> [(f32, usize); 2] {
+        let output = renderer.render(input, sources, head);
+        [0, 1].map(|ear| {
+            let samples: Vec<f32> = output.iter().skip(ear).step_by(2).copied().collect();
+            let energy = samples.iter().map(|s| s * s).sum();
+            let arrival = samples
+                .iter()
+                .position(|s| s.abs() > 1e-4)
+                .unwrap_or(usize::MAX);
+            (energy, arrival)
+        })
+    }
+
+    fn impulse(channels: usize, gains: &[f32]) -> Vec<f32> {
+        let mut input = vec![0.0; 256 * channels];
+        input[..channels].copy_from_slice(gains);
+        input
+    }
+
+    #[test]
+    fn harmonics_are_sn3d() {
+        // SN3D: each order's harmonics square-sum to 1 in every direction.
+        for source in [
+            SourcePosition::at(0.0, 0.0),
+            SourcePosition::at(37.0, 12.0),
+            SourcePosition::at(-120.0, -60.0),
+            SourcePosition::at(200.0, 80.0),
+        ] {
+            let harmonics = spherical_harmonics(source.direction());
+            for order in 0..=3 {
+                let sum: f32 = harmonics[order * order..(order + 1) * (order + 1)]
+                    .iter()
+                    .map(|h| h * h)
+                    .sum();
+                assert!((sum - 1.0).abs() < 1e-5, "order {order}: {sum}");
+            }
+        }
+    }
+
+    #[test]
+    fn a_source_on_the_left_reaches_the_left_ear_first_and_louder() {
+        let mut renderer = BinauralRenderer::new(RATE, 1, None, DEFAULT_HEAD_RADIUS_M, 1.0);
+        let [left, right] = impulse_response(
+            &mut renderer,
+            &impulse(1, &[1.0]),
+            &[SourcePosition::at(90.0, 0.0)],
+            &HeadOrientation::default(),
+        );
+        assert!(left.1 < right.1, "arrivals {} / {}", left.1, right.1);
+        // Woodworth: (a/c)(1 + π/2) between the ears, about 31 samples.
+        assert!(
+            (29..=33).contains(&(right.1 - left.1)),
+            "{}",
+            right.1 - left.1
+        );
+        assert!(left.0 > 2.0 * right.0, "energies {} / {}", left.0, right.0);
+    }
+
+    #[test]
+    fn turning_the_head_towards_a_source_centres_it() {
+        let source = SourcePosition::at(30.0, 20.0);
+        let head = HeadOrientation {
+            yaw_deg: 30.0,
+            pitch_deg: 20.0,
+            roll_deg: 0.0,
+        };
+        let ahead = head.to_head(source.direction());
+        assert!(
+            (ahead[0] - 1.0).abs() < 1e-5 && ahead[1].abs() < 1e-5,
+            "{ahead:?}"
+        );
+
+        let mut renderer = BinauralRenderer::new(RATE, 1, None, DEFAULT_HEAD_RADIUS_M, 1.0);
+        let output = renderer.render(&impulse(1, &[1.0]), &[source], &head);
+        for frame in output.chunks_exact(2) {
+            assert!((frame[0] - frame[1]).abs() < 1e-5);
+        }
+
+        // Rolling the right ear down brings a source overhead to the left.
+        let rolled = HeadOrientation {
+            roll_deg: 90.0,
+            ..HeadOrientation::default()
+        };
+        let overhead = rolled.to_head(SourcePosition::at(0.0, 90.0).direction());
+        assert!((overhead[1] - 1.0).abs() < 1e-5, "{overhead:?}");
+    }
+
+    #[test]
+    fn ambisonic_fields_decode_and_turn_with_the_head() {
+        // First-order plane wave from the left: W = 1, Y = 1.
+        let field = impulse(4, &[1.0, 1.0, 0.0, 0.0]);
+        let mut renderer = BinauralRenderer::new(
+            RATE,
+            4,
+            Some(&ChannelLayout::AmbisonicsOrder1),
+            DEFAULT_HEAD_RADIUS_M,
+            1.0,
+        );
+        assert!(renderer.is_ambisonic());
+        let [left, right] =
+            impulse_response(&mut renderer, &field, &[], &HeadOrientation::default());
+        assert!(left.0 > 2.0 * right.0, "energies {} / {}", left.0, right.0);
+
+        // Turned around, the same field arrives from the right.
+        let mut renderer = BinauralRenderer::new(
+            RATE,
+            4,
+            Some(&ChannelLayout::AmbisonicsOrder1),
+            DEFAULT_HEAD_RADIUS_M,
+            1.0,
+        );
+        let turned = HeadOrientation {
+            yaw_deg: 180.0,
+            ..HeadOrientation::default()
+        };
+        let [left, right] = impulse_response(&mut renderer, &field, &[], &turned);
+        assert!(right.0 > 2.0 * left.0, "energies {} / {}", left.0, right.0);
+    }
+
+    #[test]
+    fn distance_attenuates_and_surround_layouts_default_to_speaker_angles() {
+        let mut near = BinauralRenderer::new(RATE, 1, None, DEFAULT_HEAD_RADIUS_M, 1.0);
+        let mut far = near.clone();
+        let input = impulse(1, &[1.0]);
+        let head = HeadOrientation::default();
+        let [near_left, _] = impulse_response(&mut near, &input, &[SourcePosition::FRONT], &head);
+        let placed = SourcePosition {
+            distance_m: Some(2.0),
+            ..SourcePosition::FRONT
+        };
+        let [far_left, _] = impulse_response(&mut far, &input, &[placed], &head);
+        // Half the amplitude, a quarter of the energy.
+        assert!((far_left.0 / near_left.0 - 0.25).abs() < 1e-4);
+
+        let sources = default_sources(6, Some(&ChannelLayout::Surround51));
+        let azimuths: Vec<f32> = sources.iter().map(|source| source.azimuth_deg).collect();
+        assert_eq!(azimuths, [30.0, -30.0, 0.0, 0.0, 110.0, -110.0]);
+        assert_eq!(default_sources(3, None), [SourcePosition::FRONT; 3]);
+    }
+}
@@ -63,6 +63,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for BufferRechunke
                 sample_rate: self.sample_rate,
                 timestamp_ns: input_frame.timestamp_ns.clone(),
                 frame_index: self.frame_counter.to_string(),
+                channel_layout: input_frame.channel_layout.clone(),
             };
 
             self.outputs.write("audio_out", &output_frame)?;
@@ -0,0 +1,402 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Channel layouts and downmixing.
+//!
+//! `AudioFrame.channel_layout` names what the interleaved channels carry;
+//! frames without it get the conventional layout for their channel count.
+//! Downmixes fold one step at a time — 7.1 → 5.1 → stereo → mono, and
+//! ambisonics → stereo — with the ITU-R BS.775 coefficients, so every
+//! supported conversion is the product of those steps.
+
+use std::f32::consts::FRAC_1_SQRT_2;
+
+pub use crate::_generated_::tatolab__core::audio_frame::ChannelLayout;
+
+use crate::_generated_::AudioFrame;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// Most channels an `AudioFrame` carries (third-order ambisonics).
+pub const MAX_CHANNELS: u8 = 16;
+
+/// Channels `layout` interleaves.
+pub fn channel_count(layout: &ChannelLayout) -> u8 {
+    match layout {
+        ChannelLayout::Mono => 1,
+        ChannelLayout::Stereo => 2,
+        ChannelLayout::Surround51 => 6,
+        ChannelLayout::Surround71 => 8,
+        ChannelLayout::AmbisonicsOrder1 => 4,
+        ChannelLayout::AmbisonicsOrder2 => 9,
+        ChannelLayout::AmbisonicsOrder3 => 16,
+    }
+}
+
+/// Layout assumed for frames that don't name one. Other channel counts
+/// (including 4, which is as often quad as first-order ambisonics) are
+/// discrete channels.
+pub fn default_layout(channels: u8) -> Option<ChannelLayout> {
+    match channels {
+        1 => Some(ChannelLayout::Mono),
+        2 => Some(ChannelLayout::Stereo),
+        6 => Some(ChannelLayout::Surround51),
+        8 => Some(ChannelLayout::Surround71),
+        _ => None,
+    }
+}
+
+/// The frame's layout — its own, or the default for its channel count.
+/// Errors when a named layout disagrees with `channels`.
+pub fn frame_layout(frame: &AudioFrame) -> Result<Option<ChannelLayout>> {
+    match &frame.channel_layout {
+        Some(layout) if channel_count(layout) != frame.channels => {
+            Err(Error::Configuration(format!(
+                "AudioFrame channel_layout {:?} has {} channels, frame has {}",
+                layout,
+                channel_count(layout),
+                frame.channels
+            )))
+        }
+        Some(layout) => Ok(Some(layout.clone())),
+        None => Ok(default_layout(frame.channels)),
+    }
+}
+
+/// How a downmix treats what the ITU coefficients leave open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownmixOptions {
+    /// Linear gain the LFE is folded in at when the target has no LFE
+    /// channel. BS.775 drops it (0.0).
+    pub lfe_gain: f32,
+    /// Scale the matrix so no output can exceed the largest input peak —
+    /// full-scale content can't clip, at the cost of a quieter mix.
+    pub normalize: bool,
+}
+
+impl Default for DownmixOptions {
+    fn default() -> Self {
+        Self {
+            lfe_gain: 0.0,
+            normalize: false,
+        }
+    }
+}
+
+/// Output × input mixing coefficients between two layouts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownmixMatrix {
+    inputs: usize,
+    outputs: usize,
+    /// Row-major: `coefficients[output * inputs + input]`.
+    coefficients: Vec<f32>,
+}
+
+impl DownmixMatrix {
+    pub fn new(
+        source: &ChannelLayout,
+        target: &ChannelLayout,
+        options: DownmixOptions,
+    ) -> Result<Self> {
+        let mut matrix = Self::identity(channel_count(source) as usize);
+        let mut layout = source.clone();
+        while layout != *target {
+            let Some((next, step)) = fold_step(&layout, options) else {
+                return Err(Error::Configuration(format!(
+                    "Cannot downmix {:?} to {:?}",
+                    source, target
+                )));
+            };
+            matrix = step.compose(&matrix);
+            layout = next;
+        }
+        if options.normalize {
+            matrix.normalize();
+        }
+        Ok(matrix)
+    }
+
+    fn identity(channels: usize) -> Self {
+        let mut coefficients = vec![0.0; channels * channels];
+        for channel in 0..channels {
+            coefficients[channel * channels + channel] = 1.0;
+        }
+        Self {
+            inputs: channels,
+            outputs: channels,
+            coefficients,
+        }
+    }
+
+    fn from_rows<const INPUTS: usize>(rows: &[[f32; INPUTS]]) -> Self {
+        Self {
+            inputs: INPUTS,
+            outputs: rows.len(),
+            coefficients: rows.iter().flatten().copied().collect(),
+        }
+    }
+
+    /// `self` applied after `first`.
+    fn compose(&self, first: &Self) -> Self {
+        let mut coefficients = vec![0.0; self.outputs * first.inputs];
+        for output in 0..self.outputs {
+            for input in 0..first.inputs {
+                coefficients[output * first.inputs + input] = (0..self.inputs)
+                    .map(|middle| {
+                        self.coefficient(output, middle) * first.coefficient(middle, input)
+                    })
+                    .sum();
+            }
+        }
+        Self {
+            inputs: first.inputs,
+            outputs: self.outputs,
+            coefficients,
+        }
+    }
+
+    fn normalize(&mut self) {
+        let loudest = (0..self.outputs)
+            .map(|output| {
+                (0..self.inputs)
+                    .map(|input| self.coefficient(output, input).abs())
+                    .sum::<f32>()
+            })
+            .fold(0.0f32, f32::max);
+        if loudest > 1.0 {
+            for coefficient in &mut self.coefficients {
+                *coefficient /= loudest;
+            }
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn coefficient(&self, output: usize, input: usize) -> f32 {
+        self.coefficients[output * self.inputs + input]
+    }
+
+    /// Downmix interleaved samples; a trailing partial frame is dropped.
+    pub fn apply(&self, samples: &[f32]) -> Vec<f32> {
+        let mut mixed = Vec::with_capacity(samples.len() / self.inputs * self.outputs);
+        for frame in samples.chunks_exact(self.inputs) {
+            for row in self.coefficients.chunks_exact(self.inputs) {
+                mixed.push(row.iter().zip(frame).map(|(c, s)| c * s).sum());
+            }
+        }
+        mixed
+    }
+}
+
+/// The next smaller layout `layout` folds into, and the matrix that does it.
+fn fold_step(
+    layout: &ChannelLayout,
+    options: DownmixOptions,
+) -> Option<(ChannelLayout, DownmixMatrix)> {
+    const K: f32 = FRAC_1_SQRT_2;
+    let lfe = options.lfe_gain;
+    let step = match layout {
+        ChannelLayout::Mono => return None,
+        // BS.775: M = 0.707 L + 0.707 R.
+        ChannelLayout::Stereo => (ChannelLayout::Mono, DownmixMatrix::from_rows(&[[K, K]])),
+        // BS.775 3/2 → 2/0: centre and each surround at -3 dB into its
+        // side.
+        ChannelLayout::Surround51 => (
+            ChannelLayout::Stereo,
+            DownmixMatrix::from_rows(&[[1.0, 0.0, K, lfe, K, 0.0], [0.0, 1.0, K, lfe, 0.0, K]]),
+        ),
+        // Rear and side surrounds share the 5.1 surround at -3 dB each,
+        // which keeps the power of uncorrelated content.
+        ChannelLayout::Surround71 => (
+            ChannelLayout::Surround51,
+            DownmixMatrix::from_rows(&[
+                [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0, K, 0.0, K, 0.0],
+                [0.0, 0.0, 0.0, 0.0, 0.0, K, 0.0, K],
+            ]),
+        ),
+        // Two virtual cardioids facing hard left and right, from the
+        // first-order W and Y components (ACN 0 and 1) — higher orders
+        // only add spatial detail a stereo pair can't reproduce.
+        ChannelLayout::AmbisonicsOrder1
+        | ChannelLayout::AmbisonicsOrder2
+        | ChannelLayout::AmbisonicsOrder3 => {
+            let inputs = channel_count(layout) as usize;
+            let mut coefficients = vec![0.0; 2 * inputs];
+            coefficients[..2].copy_from_slice(&[0.5, 0.5]);
+            coefficients[inputs..inputs + 2].copy_from_slice(&[0.5, -0.5]);
+            let matrix = DownmixMatrix {
+                inputs,
+                outputs: 2,
+                coefficients,
+            };
+            (ChannelLayout::Stereo, matrix)
+        }
+    };
+    Some(step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K: f32 = FRAC_1_SQRT_2;
+
+    fn assert_rows(matrix: &DownmixMatrix, rows: &[&[f32]]) {
+        assert_eq!(matrix.outputs(), rows.len());
+        for (output, row) in rows.iter().enumerate() {
+            assert_eq!(matrix.inputs(), row.len());
+            for (input, expected) in row.iter().enumerate() {
+                let actual = matrix.coefficient(output, input);
+                assert!(
+                    (actual - expected).abs() < 1e-6,
+                    "[{output}][{input}] = {actual}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn surround_folds_with_itu_coefficients() {
+        let options = DownmixOptions::default();
+        let stereo =
+            DownmixMatrix::new(&ChannelLayout::Surround51, &ChannelLayout::Stereo, options)
+                .unwrap();
+        assert_rows(
+            &stereo,
+            &[&[1.0, 0.0, K, 0.0, K, 0.0], &[0.0, 1.0, K, 0.0, 0.0, K]],
+        );
+
+        // BS.775 3/2 → 1/0, reached through stereo.
+        let mono =
+            DownmixMatrix::new(&ChannelLayout::Surround51, &ChannelLayout::Mono, options).unwrap();
+        assert_rows(&mono, &[&[K, K, 1.0, 0.0, 0.5, 0.5]]);
+
+        let stereo =
+            DownmixMatrix::new(&ChannelLayout::Surround71, &ChannelLayout::Stereo, options)
+                .unwrap();
+        assert_rows(
+            &stereo,
+            &[
+                &[1.0, 0.0, K, 0.0, 0.5, 0.0, 0.5, 0.0],
+                &[0.0, 1.0, K, 0.0, 0.0, 0.5, 0.0, 0.5],
+            ],
+        );
+    }
+
+    #[test]
+    fn lfe_gain_and_normalization() {
+        let options = DownmixOptions {
+            lfe_gain: 0.5,
+            normalize: true,
+        };
+        let stereo =
+            DownmixMatrix::new(&ChannelLayout::Surround51, &ChannelLayout::Stereo, options)
+                .unwrap();
+        let row_sum = 1.0 + K + 0.5 + K;
+        assert_rows(
+            &stereo,
+            &[
+                &[
+                    1.0 / row_sum,
+                    0.0,
+                    K / row_sum,
+                    0.5 / row_sum,
+                    K / row_sum,
+                    0.0,
+                ],
+                &[
+                    0.0,
+                    1.0 / row_sum,
+                    K / row_sum,
+                    0.5 / row_sum,
+                    0.0,
+                    K / row_sum,
+                ],
+            ],
+        );
+        // Full scale everywhere stays within full scale.
+        let mixed = stereo.apply(&[1.0; 6]);
+        assert!(
+            mixed.iter().all(|sample| *sample <= 1.0 + 1e-6),
+            "{mixed:?}"
+        );
+    }
+
+    #[test]
+    fn ambisonics_decodes_left_and_right_to_their_sides() {
+        let stereo = DownmixMatrix::new(
+            &ChannelLayout::AmbisonicsOrder2,
+            &ChannelLayout::Stereo,
+            DownmixOptions::default(),
+        )
+        .unwrap();
+        // A plane wave from the left in SN3D: W = 1, Y = 1, X = Z = 0.
+        let mut left_source = vec![0.0; 9];
+        left_source[0] = 1.0;
+        left_source[1] = 1.0;
+        assert_eq!(stereo.apply(&left_source), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn apply_mixes_interleaved_frames() {
+        let mono = DownmixMatrix::new(
+            &ChannelLayout::Stereo,
+            &ChannelLayout::Mono,
+            DownmixOptions::default(),
+        )
+        .unwrap();
+        let mixed = mono.apply(&[1.0, 1.0, 0.5, -0.5, 0.25]);
+        assert_eq!(mixed.len(), 2);
+        assert!((mixed[0] - 2.0 * K).abs() < 1e-6);
+        assert!(mixed[1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn upmixes_and_mismatched_layouts_are_rejected() {
+        let options = DownmixOptions::default();
+        assert!(
+            DownmixMatrix::new(&ChannelLayout::Stereo, &ChannelLayout::Surround51, options)
+                .is_err()
+        );
+        assert!(
+            DownmixMatrix::new(
+                &ChannelLayout::AmbisonicsOrder1,
+                &ChannelLayout::Surround51,
+                options
+            )
+            .is_err()
+        );
+
+        let frame = AudioFrame {
+            samples: vec![0.0; 4],
+            channels: 4,
+            sample_rate: 48_000,
+            timestamp_ns: "0".to_string(),
+            frame_index: "0".to_string(),
+            channel_layout: Some(ChannelLayout::Surround51),
+        };
+        assert!(frame_layout(&frame).is_err());
+        let discrete = AudioFrame {
+            channel_layout: None,
+            ..frame.clone()
+        };
+        assert_eq!(frame_layout(&discrete).unwrap(), None);
+        let ambisonic = AudioFrame {
+            channel_layout: Some(ChannelLayout::AmbisonicsOrder1),
+            ..frame
+        };
+        assert_eq!(
+            frame_layout(&ambisonic).unwrap(),
+            Some(ChannelLayout::AmbisonicsOrder1)
+        );
+    }
+}
@@ -5,6 +5,7 @@ use parking_lot::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use crate::_generated_::AudioFrame;
+use crate::channel_layout::ChannelLayout;
 use streamlib_plugin_sdk::sdk::context::AudioTickContext;
 use streamlib_plugin_sdk::sdk::error::Result;
 use streamlib_plugin_sdk::sdk::context::RuntimeContextFullAccess;
@@ -141,6 +142,7 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for ChordGeneratorPr
                     sample_rate,
                     timestamp_ns: tick.timestamp_ns.to_string(),
                     frame_index: counter.to_string(),
+                    channel_layout: Some(ChannelLayout::Stereo),
                 };
 
                 if counter == 0 {
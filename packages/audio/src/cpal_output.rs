@@ -0,0 +1,398 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `cpal` output path behind both platform `AudioOutputProcessor`s.
+//!
+//! A `cpal::Stream` is `!Send`, so the stream — and the
+//! `ProcessorAudioConverter` that feeds it — are built and held entirely on
+//! one output thread, which also runs the input-poll loop. The thread
+//! rebuilds the stream when the device goes away or the default moves (see
+//! [`crate::output_device`]), renegotiating the converter to the new
+//! device's format and swapping a fresh ring producer into the processor's
+//! slot. The platform wrappers differ only in their [`CpalOutputBackend`].
+
+use crate::_generated_::AudioFrame;
+use crate::output_device::{
+    publish_device_changed, DeviceChoice, DeviceSnapshot, OutputDeviceFollower,
+    DEVICE_POLL_INTERVAL,
+};
+use crate::processor_audio_converter::{ProcessorAudioConverter, ProcessorAudioConverterTargetFormat};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::StreamConfig;
+use rtrb::{Producer, RingBuffer};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::InputMailboxes;
+
+/// Frames buffered between the poll loop and the device callback.
+const RING_CAPACITY: usize = 256;
+
+/// What differs between the host audio backends `cpal` drives.
+pub(crate) struct CpalOutputBackend {
+    /// Backend name, for logs.
+    pub name: &'static str,
+    /// Whether a stream error means the device is gone and the stream must
+    /// be reopened.
+    pub error_means_lost: fn(&cpal::StreamError) -> bool,
+}
+
+/// The device configuration the output thread resolved, reported back to the
+/// processor for its `current_device` summary.
+#[derive(Clone)]
+pub(crate) struct ResolvedOutputConfig {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub channels: u32,
+    pub buffer_size: usize,
+}
+
+/// What the output thread is handed by the processor — owned `Send` handles
+/// only, never a borrow of the processor.
+pub(crate) struct CpalOutputThreadArgs {
+    /// An `InputMailboxes` clone; its handle is refcounted through the
+    /// plugin ABI vtable.
+    pub inputs: InputMailboxes,
+    /// The processor's ring producer slot, replaced on every reopen.
+    pub frame_producer: Arc<Mutex<Option<Producer<AudioFrame>>>>,
+    /// Set by the processor's teardown; the thread drops the stream and exits.
+    pub stop: Arc<AtomicBool>,
+    /// Configured device index; `None` follows the default device.
+    pub device_id: Option<usize>,
+    pub processor_id: Option<String>,
+}
+
+/// Spawn the output thread, open the initial device on it and wait for the
+/// result. Fails if the thread can't be spawned or the device can't be
+/// opened.
+pub(crate) fn spawn_cpal_output_thread(
+    backend: &'static CpalOutputBackend,
+    args: CpalOutputThreadArgs,
+) -> Result<(thread::JoinHandle<()>, ResolvedOutputConfig)> {
+    let (ready_sender, ready_receiver) = mpsc::channel::<Result<ResolvedOutputConfig>>();
+    let output_thread = thread::Builder::new()
+        .name("audio-output".to_string())
+        .spawn(move || run_output_thread(backend, args, ready_sender))
+        .map_err(|e| Error::Configuration(format!("Failed to spawn audio output thread: {}", e)))?;
+    let resolved = ready_receiver.recv().map_err(|_| {
+        Error::Configuration("Audio output thread exited before reporting stream setup".into())
+    })??;
+    Ok((output_thread, resolved))
+}
+
+fn run_output_thread(
+    backend: &'static CpalOutputBackend,
+    args: CpalOutputThreadArgs,
+    ready_sender: mpsc::Sender<Result<ResolvedOutputConfig>>,
+) {
+    let CpalOutputThreadArgs {
+        inputs,
+        frame_producer,
+        stop,
+        device_id,
+        processor_id,
+    } = args;
+
+    let (producer, consumer) = RingBuffer::<AudioFrame>::new(RING_CAPACITY);
+    *frame_producer.lock().unwrap() = Some(producer);
+
+    let host = cpal::default_host();
+    let lost = Arc::new(AtomicBool::new(false));
+    let opened = select_output_device(&host, device_id)
+        .and_then(|device| build_output_stream(backend, device, consumer, Arc::clone(&lost)));
+    let (mut resolved, stream) = match opened {
+        Ok(built) => built,
+        Err(e) => {
+            let _ = ready_sender.send(Err(e));
+            return;
+        }
+    };
+    let mut stream = Some(stream);
+
+    let mut follower = if device_id.is_some() {
+        OutputDeviceFollower::pinned_to(resolved.device_name.clone())
+    } else {
+        let mut follower = OutputDeviceFollower::following_default();
+        follower.opened(Some(resolved.device_name.clone()));
+        follower
+    };
+
+    // Renegotiates when a device move changes the target format.
+    let mut audio = ProcessorAudioConverter::new();
+
+    if ready_sender.send(Ok(resolved.clone())).is_err() {
+        return;
+    }
+
+    tracing::info!("[AudioOutput] Polling loop started");
+    let mut last_device_check = Instant::now();
+    while !stop.load(Ordering::SeqCst) {
+        if inputs.has_data("audio") {
+            if let Ok(frame) = inputs.read::<AudioFrame>("audio") {
+                // Without a device, frames are drained and dropped so
+                // upstream never backs up.
+                if stream.is_some() {
+                    match audio.convert(&frame, &target_format(&resolved)) {
+                        Ok(converted_frames) => {
+                            let mut producer_guard = frame_producer.lock().unwrap();
+                            if let Some(ref mut producer) = *producer_guard {
+                                for converted in converted_frames {
+                                    if producer.push(converted).is_err() {
+                                        tracing::warn!(
+                                            "[AudioOutput] Ring buffer full, dropping frame"
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("[AudioOutput] Audio conversion failed: {}", e);
+                        }
+                    }
+                }
+            }
+        } else {
+            thread::sleep(std::time::Duration::from_micros(500));
+        }
+
+        if !lost.load(Ordering::SeqCst) && last_device_check.elapsed() < DEVICE_POLL_INTERVAL {
+            continue;
+        }
+        last_device_check = Instant::now();
+
+        let snapshot = device_snapshot(&host, &follower, &lost);
+        let Some(reason) = follower.check(&snapshot) else {
+            continue;
+        };
+        let choice = follower.choose(&snapshot);
+        let previous = follower.current().map(str::to_string);
+
+        // Release the old device before opening the next one — it may be the
+        // same device coming back.
+        drop(stream.take());
+        lost.store(false, Ordering::SeqCst);
+        let (producer, consumer) = RingBuffer::<AudioFrame>::new(RING_CAPACITY);
+        *frame_producer.lock().unwrap() = Some(producer);
+
+        match open_choice(&host, &choice)
+            .and_then(|device| build_output_stream(backend, device, consumer, Arc::clone(&lost)))
+        {
+            Ok((reopened, reopened_stream)) => {
+                tracing::info!(
+                    "[AudioOutput] Device {} ({}): now on {} ({}Hz, {} channels)",
+                    reason.as_str(),
+                    previous.as_deref().unwrap_or("none"),
+                    reopened.device_name,
+                    reopened.sample_rate,
+                    reopened.channels
+                );
+                follower.opened(Some(reopened.device_name.clone()));
+                publish_device_changed(
+                    processor_id.as_deref(),
+                    reason,
+                    previous.as_deref(),
+                    Some(&reopened.device_name),
+                    Some((reopened.sample_rate, reopened.channels)),
+                );
+                resolved = reopened;
+                stream = Some(reopened_stream);
+            }
+            Err(e) => {
+                follower.opened(None);
+                // Retried every poll; only the transition is news.
+                if previous.is_some() {
+                    tracing::warn!(
+                        "[AudioOutput] Device {} ({}), no output device to move to: {}",
+                        reason.as_str(),
+                        previous.as_deref().unwrap_or("none"),
+                        e
+                    );
+                    publish_device_changed(
+                        processor_id.as_deref(),
+                        reason,
+                        previous.as_deref(),
+                        None,
+                        None,
+                    );
+                } else {
+                    tracing::debug!("[AudioOutput] Still no output device: {}", e);
+                }
+            }
+        }
+    }
+
+    drop(stream);
+    tracing::info!("[AudioOutput] Polling loop stopped");
+}
+
+/// Format the converter feeds the device: its native rate and channel count,
+/// rechunked to its callback size.
+fn target_format(resolved: &ResolvedOutputConfig) -> ProcessorAudioConverterTargetFormat {
+    ProcessorAudioConverterTargetFormat {
+        sample_rate: Some(resolved.sample_rate),
+        channels: Some(resolved.channels as u8),
+        buffer_size: Some(resolved.buffer_size),
+    }
+}
+
+/// The configured device by index, or the default device.
+fn select_output_device(host: &cpal::Host, device_id: Option<usize>) -> Result<cpal::Device> {
+    if let Some(id) = device_id {
+        let devices: Vec<_> = host
+            .output_devices()
+            .map_err(|e| {
+                Error::Configuration(format!("Failed to enumerate audio devices: {}", e))
+            })?
+            .collect();
+        devices
+            .get(id)
+            .cloned()
+            .ok_or_else(|| Error::Configuration(format!("Audio device {} not found", id)))
+    } else {
+        host.default_output_device()
+            .ok_or_else(|| Error::Configuration("No default audio output device".into()))
+    }
+}
+
+/// The device a move goes to. Indices shift as devices come and go, so the
+/// configured device is found again by name.
+fn open_choice(host: &cpal::Host, choice: &DeviceChoice) -> Result<cpal::Device> {
+    match choice {
+        DeviceChoice::Pinned(name) => host
+            .output_devices()
+            .map_err(|e| {
+                Error::Configuration(format!("Failed to enumerate audio devices: {}", e))
+            })?
+            .find(|device| device.name().is_ok_and(|n| n == *name))
+            .ok_or_else(|| Error::Configuration(format!("Audio device {} not found", name))),
+        DeviceChoice::Default => select_output_device(host, None),
+    }
+}
+
+/// Look at the system's devices for the follower. Loss of the current device
+/// is only taken from the stream's error callback — ALSA keeps an open `hw`
+/// device out of enumeration.
+fn device_snapshot(
+    host: &cpal::Host,
+    follower: &OutputDeviceFollower,
+    lost: &AtomicBool,
+) -> DeviceSnapshot {
+    let pinned_present = match follower.pinned() {
+        Some(pinned) if follower.on_fallback() => host.output_devices().is_ok_and(|mut devices| {
+            devices.any(|device| device.name().is_ok_and(|n| n == pinned))
+        }),
+        _ => false,
+    };
+    DeviceSnapshot {
+        current_present: follower.current().is_some() && !lost.load(Ordering::SeqCst),
+        pinned_present,
+        default_device: host
+            .default_output_device()
+            .and_then(|device| device.name().ok()),
+    }
+}
+
+/// Build the `cpal` output stream on `device` fed by `consumer`, and start
+/// playback. Runs entirely on the output thread because a `cpal::Stream` is
+/// `!Send`; the resolved device config is returned so the processor can
+/// report it. Stream errors the backend treats as device loss set `lost`.
+fn build_output_stream(
+    backend: &'static CpalOutputBackend,
+    device: cpal::Device,
+    consumer: rtrb::Consumer<AudioFrame>,
+    lost: Arc<AtomicBool>,
+) -> Result<(ResolvedOutputConfig, cpal::Stream)> {
+    let device_config = device.default_output_config().map_err(|e| {
+        Error::Configuration(format!("Failed to get audio config: {}", e))
+    })?;
+
+    let device_sample_rate = device_config.sample_rate().0;
+    let device_channels = device_config.channels() as u32;
+
+    // 512 samples (~10ms at 48kHz) balances latency and reliability.
+    let device_buffer_size = match device_config.buffer_size() {
+        cpal::SupportedBufferSize::Range { min, max } => {
+            let preferred = 512u32;
+            preferred.clamp(*min, *max) as usize
+        }
+        cpal::SupportedBufferSize::Unknown => 512,
+    };
+
+    tracing::info!(
+        "AudioOutput: Queried device config - {}Hz, {} channels, {} buffer size ({} backend)",
+        device_sample_rate,
+        device_channels,
+        device_buffer_size,
+        backend.name
+    );
+
+    let consumer = Arc::new(Mutex::new(consumer));
+    let consumer_for_callback = Arc::clone(&consumer);
+    let mut sample_buffer: Vec<f32> = Vec::new();
+
+    let stream_config = StreamConfig {
+        channels: device_channels as u16,
+        sample_rate: cpal::SampleRate(device_sample_rate),
+        buffer_size: cpal::BufferSize::Fixed(device_buffer_size as u32),
+    };
+
+    let stream = device
+        .build_output_stream(
+            &stream_config,
+            move |data: &mut [f32], _info: &cpal::OutputCallbackInfo| {
+                let mut consumer_guard = consumer_for_callback.lock().unwrap();
+
+                while sample_buffer.len() < data.len() {
+                    if let Ok(audio_frame) = consumer_guard.pop() {
+                        sample_buffer.extend_from_slice(&audio_frame.samples);
+                    } else {
+                        break;
+                    }
+                }
+
+                if sample_buffer.len() >= data.len() {
+                    data.copy_from_slice(&sample_buffer[..data.len()]);
+                    sample_buffer.drain(..data.len());
+                } else if !sample_buffer.is_empty() {
+                    let copy_len = sample_buffer.len();
+                    data[..copy_len].copy_from_slice(&sample_buffer);
+                    data[copy_len..].fill(0.0);
+                    sample_buffer.clear();
+                } else {
+                    data.fill(0.0);
+                }
+            },
+            move |err| {
+                tracing::error!("Audio output stream error: {}", err);
+                if (backend.error_means_lost)(&err) {
+                    lost.store(true, Ordering::SeqCst);
+                }
+            },
+            None,
+        )
+        .map_err(|e| Error::Configuration(format!("Failed to build audio stream: {}", e)))?;
+
+    tracing::info!("AudioOutput: Starting cpal stream playback");
+    stream
+        .play()
+        .map_err(|e| Error::Configuration(format!("Failed to start stream: {}", e)))?;
+
+    tracing::info!("AudioOutput: cpal stream.play() succeeded");
+
+    let device_name = device
+        .name()
+        .unwrap_or_else(|_| "Unknown Device".to_string());
+
+    Ok((
+        ResolvedOutputConfig {
+            device_name,
+            sample_rate: device_sample_rate,
+            channels: device_channels,
+            buffer_size: device_buffer_size,
+        },
+        stream,
+    ))
+}
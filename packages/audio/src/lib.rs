@@ -9,29 +9,64 @@ pub mod _generated_ {
 }
 
 // Audio DSP utilities reach-for by processors that need conversion / resampling /
-// rechunking. Engine no longer hosts these; consumers (this package, packages/clap)
-// instantiate `ProcessorAudioConverter` directly as a struct field.
+// rechunking. Engine no longer hosts these; consumers (this package, packages/clap,
+// packages/vst3) instantiate `ProcessorAudioConverter` directly as a struct field.
 pub mod audio_resample;
 pub mod audio_utils;
 pub mod processor_audio_converter;
 
+// Plugin hosting surface shared by packages/clap, packages/vst3 and the macOS
+// AudioUnit host (`macos::audio_unit_host`) — plugin and parameter descriptions
+// plus the parameter automation scheduler.
+pub mod plugin_host;
+
+// AudioMixer building blocks — media-clock automation lanes and the
+// sidechain ducker.
+pub mod mixer_automation;
+pub mod sidechain_ducker;
+
+// AudioFrame channel layouts and the ITU downmix matrices behind
+// `AudioDownmixProcessor` and the mixer's surround inputs.
+pub mod channel_layout;
+
+// Spherical-head HRTF and ambisonic decoding behind `SpatialAudioProcessor`.
+pub mod binaural;
+
+// Device following behind `AudioOutputProcessor` — reopening on device loss
+// or default changes, and the device-changed custom event.
+pub mod output_device;
+
+// EBU R128 loudness meter and normalization gain behind `LoudnessProcessor`.
+pub mod loudness_meter;
+
 // Cross-platform processors
 pub mod audio_channel_converter;
+pub mod audio_downmix;
 pub mod audio_mixer;
 pub mod audio_resampler;
 pub mod buffer_rechunker;
 pub mod chord_generator;
+pub mod loudness;
+pub mod spatial_audio;
 
 // Cross-platform shims that re-export the per-platform impl under a unified name.
 pub mod audio_capture;
 pub mod audio_output;
 
+// The `cpal` output thread behind both platform `AudioOutputProcessor`s.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios"))]
+mod cpal_output;
+
 #[cfg(target_os = "linux")]
 pub mod linux;
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub mod apple;
 
+// AudioUnit effect hosting — macOS only, not iOS.
+#[cfg(target_os = "macos")]
+pub mod macos;
+
 // `_apple_impl_pending_` holds the cross-platform Apple-flavored audio
 // codec types parked out of the engine in #786. Gated so it never
 // compiles; re-enable + rewire imports once Apple support is activated.
@@ -40,24 +75,42 @@ mod _apple_impl_pending_;
 
 pub use audio_capture::{AudioCaptureProcessor, AudioInputDevice};
 pub use audio_channel_converter::AudioChannelConverterProcessor;
+pub use audio_downmix::AudioDownmixProcessor;
 pub use audio_mixer::AudioMixerProcessor;
 pub use audio_output::{AudioDevice, AudioOutputProcessor};
 pub use audio_resample::{AudioResampler, ResamplingQuality, StereoResampler};
 pub use audio_resampler::AudioResamplerProcessor;
 pub use audio_utils::{convert_audio_frame, convert_channels, resample_frame, AudioRechunker};
+pub use binaural::{BinauralRenderer, HeadOrientation, SourcePosition};
 pub use buffer_rechunker::BufferRechunkerProcessor;
+pub use channel_layout::{ChannelLayout, DownmixMatrix, DownmixOptions};
 pub use chord_generator::ChordGeneratorProcessor;
+pub use loudness::LoudnessProcessor;
+pub use loudness_meter::{LoudnessMeter, LoudnessNormalizer, LoudnessReading};
+pub use output_device::{DeviceChangeReason, AUDIO_OUTPUT_DEVICE_CHANGED_TOPIC};
 pub use processor_audio_converter::{
     ProcessorAudioConverter, ProcessorAudioConverterStatus, ProcessorAudioConverterTargetFormat,
 };
+pub use spatial_audio::SpatialAudioProcessor;
+
+#[cfg(target_os = "macos")]
+pub use macos::{
+    AudioUnitComponentId, AudioUnitEffectProcessor, AudioUnitHost, AudioUnitInfo,
+    AudioUnitInstantiation, AudioUnitScanner,
+};
 
 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios"))]
 streamlib_plugin_abi::export_plugin!(
     crate::AudioChannelConverterProcessor::Processor,
+    crate::AudioDownmixProcessor::Processor,
     crate::AudioMixerProcessor::Processor,
     crate::AudioResamplerProcessor::Processor,
     crate::BufferRechunkerProcessor::Processor,
     crate::ChordGeneratorProcessor::Processor,
+    crate::LoudnessProcessor::Processor,
+    crate::SpatialAudioProcessor::Processor,
     crate::AudioCaptureProcessor::Processor,
     crate::AudioOutputProcessor::Processor,
+    #[cfg(target_os = "macos")]
+    crate::AudioUnitEffectProcessor::Processor,
 );
@@ -0,0 +1,175 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Loudness — EBU R128 metering with optional normalization to a delivery
+//! target. Audio passes through (scaled when normalizing); readings go out
+//! as `LoudnessMeasurement` data frames and as the node's `loudness` graph
+//! component.
+
+use crate::_generated_::tatolab__audio::loudness_config::Normalization;
+use crate::_generated_::{AudioFrame, LoudnessMeasurement};
+use crate::loudness_meter::{
+    BROADCAST_TARGET_LUFS, LoudnessMeter, LoudnessNormalizer, LoudnessReading,
+    STREAMING_TARGET_LUFS,
+};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::Result;
+use streamlib_plugin_sdk::sdk::graph::report_processor_component;
+
+/// `components` key the latest reading is published under.
+const LOUDNESS_COMPONENT: &str = "loudness";
+
+const DEFAULT_TRUE_PEAK_CEILING_DBTP: f32 = -1.0;
+const DEFAULT_MAX_GAIN_DB: f32 = 12.0;
+const DEFAULT_REPORT_INTERVAL_MS: u32 = 100;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/audio/Loudness",
+    description = "Measures EBU R128 momentary, short-term and integrated loudness and true peak, reported as LoudnessMeasurement data frames and a 'loudness' graph component. Optionally normalizes the audio to -14 LUFS (streaming) or -23 LUFS (broadcast) under a true-peak ceiling.",
+    execution = reactive,
+    scheduling = realtime,
+    config = crate::_generated_::LoudnessConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "Audio to measure (1-8 channels, 5.1 as L R C LFE Ls Rs)"),
+    output("audio_out", "@tatolab/core/AudioFrame", description = "The same audio, normalized when a target is configured"),
+    output("loudness", "@tatolab/audio/LoudnessMeasurement", description = "Loudness readings at the report interval"),
+)]
+pub struct LoudnessProcessor {
+    meter: Option<LoudnessMeter>,
+    normalizer: Option<LoudnessNormalizer>,
+    processor_id: Option<String>,
+    /// Sample frames measured since the last report.
+    frames_since_report: u64,
+}
+
+impl LoudnessProcessor::Processor {
+    fn configure_normalizer(&mut self) {
+        let target = self
+            .config
+            .target_lufs
+            .or(match &self.config.normalization {
+                Some(Normalization::Streaming) => Some(STREAMING_TARGET_LUFS),
+                Some(Normalization::Broadcast) => Some(BROADCAST_TARGET_LUFS),
+                None => None,
+            });
+        let ceiling = self
+            .config
+            .true_peak_ceiling_dbtp
+            .unwrap_or(DEFAULT_TRUE_PEAK_CEILING_DBTP);
+        let max_gain_db = self.config.max_gain_db.unwrap_or(DEFAULT_MAX_GAIN_DB);
+        self.normalizer = match (self.normalizer.take(), target) {
+            (_, None) => None,
+            // Retarget in place so the ramp continues from the gain
+            // actually being applied.
+            (Some(mut normalizer), Some(target)) => {
+                normalizer.target_lufs = target;
+                normalizer.true_peak_ceiling_dbtp = ceiling;
+                normalizer.max_gain_db = max_gain_db.abs();
+                Some(normalizer)
+            }
+            (None, Some(target)) => Some(LoudnessNormalizer::new(target, ceiling, max_gain_db)),
+        };
+    }
+
+    fn report(&mut self, frame: &AudioFrame, reading: &LoudnessReading) -> Result<()> {
+        let measurement = LoudnessMeasurement {
+            timestamp_ns: frame.timestamp_ns.clone(),
+            gain_db: self
+                .normalizer
+                .map_or(0.0, |normalizer| normalizer.gain_db()),
+            momentary_lufs: reading.momentary_lufs,
+            short_term_lufs: reading.short_term_lufs,
+            integrated_lufs: reading.integrated_lufs,
+            true_peak_dbtp: reading.true_peak_dbtp,
+            target_lufs: self.normalizer.map(|normalizer| normalizer.target_lufs),
+        };
+        if let Some(processor_id) = &self.processor_id
+            && let Err(e) =
+                report_processor_component(processor_id, LOUDNESS_COMPONENT, &measurement)
+        {
+            tracing::debug!("[Loudness] Failed to report graph component: {}", e);
+        }
+        self.outputs.write("loudness", &measurement)
+    }
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for LoudnessProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        self.configure_normalizer();
+        tracing::info!(
+            "[Loudness] setup() - normalization: {:?}",
+            self.normalizer.map(|normalizer| normalizer.target_lufs)
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(meter) = &self.meter {
+            tracing::info!(
+                "[Loudness] Stopped (integrated {:?} LUFS, true peak {:?} dBTP)",
+                meter.integrated_lufs(),
+                meter.true_peak_dbtp()
+            );
+        }
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.configure_normalizer();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("audio_in") {
+            return Ok(());
+        }
+
+        let mut frame: AudioFrame = self.inputs.read("audio_in")?;
+        let channels = frame.channels.max(1) as usize;
+
+        // A new layout is a new programme — the integrated measurement
+        // can't span it.
+        let mut meter = match self.meter.take() {
+            Some(meter)
+                if meter.sample_rate() == frame.sample_rate && meter.channels() == channels =>
+            {
+                meter
+            }
+            previous => {
+                if previous.is_some() {
+                    tracing::info!(
+                        "[Loudness] Format changed to {} Hz / {} channels, restarting measurement",
+                        frame.sample_rate,
+                        channels
+                    );
+                }
+                LoudnessMeter::new(frame.sample_rate, channels)
+            }
+        };
+
+        meter.process_interleaved(&frame.samples);
+        let reading = meter.reading();
+        self.meter = Some(meter);
+        if let Some(normalizer) = &mut self.normalizer {
+            normalizer.apply(&mut frame.samples, channels, &reading);
+        }
+
+        self.frames_since_report += (frame.samples.len() / channels) as u64;
+        let interval_frames = u64::from(
+            self.config
+                .report_interval_ms
+                .unwrap_or(DEFAULT_REPORT_INTERVAL_MS),
+        ) * u64::from(frame.sample_rate)
+            / 1000;
+        let report_due = self.frames_since_report >= interval_frames.max(1);
+        if report_due {
+            self.frames_since_report = 0;
+        }
+
+        self.outputs.write("audio_out", &frame)?;
+        if report_due {
+            self.report(&frame, &reading)?;
+        }
+        Ok(())
+    }
+}
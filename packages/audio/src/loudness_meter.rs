@@ -0,0 +1,561 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! EBU R128 / ITU-R BS.1770-4 loudness metering and normalization gain.
+//!
+//! Samples are K-weighted (a high shelf modelling the head followed by a
+//! high-pass), squared, channel-weighted and summed into 100 ms sub-blocks.
+//! Momentary loudness is the mean over the last 400 ms, short-term over the
+//! last 3 s. Every 100 ms the 400 ms block also feeds the integrated
+//! measurement, which gates out blocks below −70 LUFS and then blocks more
+//! than 10 LU below the mean of the rest. True peak is the sample peak of
+//! the signal oversampled 4× (2× at 96 kHz and above).
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+/// Integrated loudness most streaming services normalize to.
+pub const STREAMING_TARGET_LUFS: f32 = -14.0;
+
+/// EBU R128 broadcast delivery target.
+pub const BROADCAST_TARGET_LUFS: f32 = -23.0;
+
+/// Sub-blocks per second; also the momentary / short-term update rate.
+const SUB_BLOCKS_PER_SECOND: u32 = 10;
+
+/// 400 ms momentary window, in sub-blocks.
+const MOMENTARY_SUB_BLOCKS: usize = 4;
+
+/// 3 s short-term window, in sub-blocks.
+const SHORT_TERM_SUB_BLOCKS: usize = 30;
+
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Gating blocks are binned by loudness so the integrated measurement runs
+/// in constant memory however long the stream is. Each bin keeps the exact
+/// energy sum of its blocks; only the relative gate is bin-quantized.
+const HISTOGRAM_MAX_LUFS: f64 = 30.0;
+const HISTOGRAM_BINS_PER_LU: f64 = 10.0;
+const HISTOGRAM_BINS: usize =
+    ((HISTOGRAM_MAX_LUFS - ABSOLUTE_GATE_LUFS) * HISTOGRAM_BINS_PER_LU) as usize;
+
+/// Taps per polyphase branch of the true-peak interpolator.
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// Mean square → LUFS. The −0.691 offset cancels the K-weighting's gain at
+/// 1 kHz.
+fn energy_to_lufs(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+/// A finite reading, or `None` for silence.
+fn finite(value: f64) -> Option<f32> {
+    value.is_finite().then_some(value as f32)
+}
+
+/// Transposed direct form II biquad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b0: b[0],
+            b1: b[1],
+            b2: b[2],
+            a1: a[0],
+            a2: a[1],
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+/// The two K-weighting stages for `sample_rate`. BS.1770 only tabulates
+/// 48 kHz coefficients; these come from the analog prototypes so every
+/// rate gets the same curve.
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let fs = sample_rate as f64;
+
+    let f0 = 1_681.974_450_955_533;
+    let gain_db = 3.999_843_853_973_347;
+    let q = 0.707_175_236_955_419_6;
+    let k = (PI * f0 / fs).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    let f0 = 38.135_470_876_024_44;
+    let q = 0.500_327_037_323_877_3;
+    let k = (PI * f0 / fs).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad::new(
+        [1.0, -2.0, 1.0],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    [shelf, high_pass]
+}
+
+/// BS.1770 channel weights, assuming the usual interleaving
+/// (L R C LFE Ls Rs for 5.1): surrounds count +1.5 dB and the LFE is left
+/// out. Every other layout weighs its channels equally.
+fn channel_weights(channels: usize) -> Vec<f64> {
+    match channels {
+        5 => vec![1.0, 1.0, 1.0, 1.41, 1.41],
+        6 => vec![1.0, 1.0, 1.0, 0.0, 1.41, 1.41],
+        _ => vec![1.0; channels],
+    }
+}
+
+/// Peak of the signal oversampled by a windowed-sinc polyphase
+/// interpolator.
+#[derive(Debug, Clone)]
+struct TruePeakMeter {
+    /// `phases[p][k]` weights input `t - k` for output phase `p`.
+    phases: Vec<[f64; TRUE_PEAK_TAPS]>,
+    /// Most recent inputs per channel, newest first.
+    history: Vec<[f64; TRUE_PEAK_TAPS]>,
+    peak: f64,
+}
+
+impl TruePeakMeter {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        let factor = match sample_rate {
+            0..96_000 => 4,
+            96_000..192_000 => 2,
+            _ => 1,
+        };
+        let length = (factor * TRUE_PEAK_TAPS) as f64;
+        let center = length / 2.0;
+        let phases = (0..factor)
+            .map(|phase| {
+                std::array::from_fn(|tap| {
+                    let n = (tap * factor + phase) as f64;
+                    let x = (n - center) / factor as f64;
+                    let sinc = if x == 0.0 {
+                        1.0
+                    } else {
+                        (PI * x).sin() / (PI * x)
+                    };
+                    let window = 0.5 * (1.0 - (2.0 * PI * n / length).cos());
+                    sinc * window
+                })
+            })
+            .collect();
+        Self {
+            phases,
+            history: vec![[0.0; TRUE_PEAK_TAPS]; channels],
+            peak: 0.0,
+        }
+    }
+
+    fn process(&mut self, channel: usize, sample: f64) {
+        let history = &mut self.history[channel];
+        history.copy_within(0..TRUE_PEAK_TAPS - 1, 1);
+        history[0] = sample;
+        for phase in &self.phases {
+            let value: f64 = phase.iter().zip(history.iter()).map(|(h, x)| h * x).sum();
+            self.peak = self.peak.max(value.abs());
+        }
+        self.peak = self.peak.max(sample.abs());
+    }
+}
+
+/// Gating blocks above the absolute gate, binned by loudness.
+#[derive(Debug, Clone)]
+struct GatingHistogram {
+    counts: Vec<u64>,
+    energies: Vec<f64>,
+}
+
+impl GatingHistogram {
+    fn new() -> Self {
+        Self {
+            counts: vec![0; HISTOGRAM_BINS],
+            energies: vec![0.0; HISTOGRAM_BINS],
+        }
+    }
+
+    fn bin(lufs: f64) -> usize {
+        let bin = ((lufs - ABSOLUTE_GATE_LUFS) * HISTOGRAM_BINS_PER_LU) as usize;
+        bin.min(HISTOGRAM_BINS - 1)
+    }
+
+    fn add(&mut self, energy: f64) {
+        let lufs = energy_to_lufs(energy);
+        if lufs <= ABSOLUTE_GATE_LUFS {
+            return;
+        }
+        let bin = Self::bin(lufs);
+        self.counts[bin] += 1;
+        self.energies[bin] += energy;
+    }
+
+    /// Gated mean loudness, or `None` before any block passes the
+    /// absolute gate.
+    fn integrated_lufs(&self) -> Option<f64> {
+        let (count, energy) = Self::sum(&self.counts, &self.energies);
+        if count == 0 {
+            return None;
+        }
+        let relative_gate = energy_to_lufs(energy / count as f64) + RELATIVE_GATE_LU;
+        let first = if relative_gate <= ABSOLUTE_GATE_LUFS {
+            0
+        } else {
+            Self::bin(relative_gate)
+        };
+        let (count, energy) = Self::sum(&self.counts[first..], &self.energies[first..]);
+        (count > 0).then(|| energy_to_lufs(energy / count as f64))
+    }
+
+    fn sum(counts: &[u64], energies: &[f64]) -> (u64, f64) {
+        (counts.iter().sum(), energies.iter().sum())
+    }
+}
+
+/// One reading of every meter. `None` until enough audio has been measured,
+/// and for digital silence.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoudnessReading {
+    pub momentary_lufs: Option<f32>,
+    pub short_term_lufs: Option<f32>,
+    pub integrated_lufs: Option<f32>,
+    pub true_peak_dbtp: Option<f32>,
+}
+
+/// Streaming EBU R128 meter over interleaved `f32` audio of a fixed
+/// layout.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    sample_rate: u32,
+    channels: usize,
+    filters: Vec<[Biquad; 2]>,
+    weights: Vec<f64>,
+    sub_block_frames: usize,
+    sub_block_position: usize,
+    sub_block_energy: f64,
+    /// Weighted energy of the most recent sub-blocks, newest last.
+    sub_blocks: VecDeque<f64>,
+    histogram: GatingHistogram,
+    true_peak: TruePeakMeter,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let sample_rate = sample_rate.max(SUB_BLOCKS_PER_SECOND);
+        let channels = channels.max(1);
+        Self {
+            sample_rate,
+            channels,
+            filters: vec![k_weighting(sample_rate); channels],
+            weights: channel_weights(channels),
+            sub_block_frames: (sample_rate / SUB_BLOCKS_PER_SECOND) as usize,
+            sub_block_position: 0,
+            sub_block_energy: 0.0,
+            sub_blocks: VecDeque::with_capacity(SHORT_TERM_SUB_BLOCKS),
+            histogram: GatingHistogram::new(),
+            true_peak: TruePeakMeter::new(sample_rate, channels),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Start a new measurement (a new programme).
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate, self.channels);
+    }
+
+    /// Measure interleaved samples. A trailing partial frame is ignored.
+    pub fn process_interleaved(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            let mut energy = 0.0;
+            for (channel, &sample) in frame.iter().enumerate() {
+                let sample = sample as f64;
+                self.true_peak.process(channel, sample);
+                let [shelf, high_pass] = &mut self.filters[channel];
+                let weighted = high_pass.process(shelf.process(sample));
+                energy += self.weights[channel] * weighted * weighted;
+            }
+            self.sub_block_energy += energy;
+            self.sub_block_position += 1;
+            if self.sub_block_position == self.sub_block_frames {
+                self.finish_sub_block();
+            }
+        }
+    }
+
+    fn finish_sub_block(&mut self) {
+        if self.sub_blocks.len() == SHORT_TERM_SUB_BLOCKS {
+            self.sub_blocks.pop_front();
+        }
+        self.sub_blocks.push_back(self.sub_block_energy);
+        self.sub_block_energy = 0.0;
+        self.sub_block_position = 0;
+
+        // Gating blocks are 400 ms long with 75% overlap — one per
+        // sub-block once the first window has filled.
+        if let Some(energy) = self.window_energy(MOMENTARY_SUB_BLOCKS) {
+            self.histogram.add(energy);
+        }
+    }
+
+    /// Mean square over the last `sub_blocks`, once that many exist.
+    fn window_energy(&self, sub_blocks: usize) -> Option<f64> {
+        if self.sub_blocks.len() < sub_blocks {
+            return None;
+        }
+        let energy: f64 = self.sub_blocks.iter().rev().take(sub_blocks).sum();
+        Some(energy / (sub_blocks * self.sub_block_frames) as f64)
+    }
+
+    pub fn momentary_lufs(&self) -> Option<f32> {
+        self.window_energy(MOMENTARY_SUB_BLOCKS)
+            .and_then(|energy| finite(energy_to_lufs(energy)))
+    }
+
+    pub fn short_term_lufs(&self) -> Option<f32> {
+        self.window_energy(SHORT_TERM_SUB_BLOCKS)
+            .and_then(|energy| finite(energy_to_lufs(energy)))
+    }
+
+    pub fn integrated_lufs(&self) -> Option<f32> {
+        self.histogram.integrated_lufs().and_then(finite)
+    }
+
+    /// Highest true peak since the measurement started, in dBTP.
+    pub fn true_peak_dbtp(&self) -> Option<f32> {
+        finite(20.0 * self.true_peak.peak.log10())
+    }
+
+    pub fn reading(&self) -> LoudnessReading {
+        LoudnessReading {
+            momentary_lufs: self.momentary_lufs(),
+            short_term_lufs: self.short_term_lufs(),
+            integrated_lufs: self.integrated_lufs(),
+            true_peak_dbtp: self.true_peak_dbtp(),
+        }
+    }
+}
+
+/// Gain that brings a programme's integrated loudness to a target without
+/// pushing its true peak over a ceiling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessNormalizer {
+    pub target_lufs: f32,
+    pub true_peak_ceiling_dbtp: f32,
+    /// Largest boost or cut applied, in dB.
+    pub max_gain_db: f32,
+    gain_db: f32,
+}
+
+impl LoudnessNormalizer {
+    pub fn new(target_lufs: f32, true_peak_ceiling_dbtp: f32, max_gain_db: f32) -> Self {
+        Self {
+            target_lufs,
+            true_peak_ceiling_dbtp,
+            max_gain_db: max_gain_db.abs(),
+            gain_db: 0.0,
+        }
+    }
+
+    /// Gain applied at the end of the last [`Self::apply`].
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// The gain `reading` calls for: unity until there is an integrated
+    /// measurement.
+    pub fn target_gain_db(&self, reading: &LoudnessReading) -> f32 {
+        let Some(integrated) = reading.integrated_lufs else {
+            return 0.0;
+        };
+        let mut gain = self.target_lufs - integrated;
+        if let Some(true_peak) = reading.true_peak_dbtp {
+            gain = gain.min(self.true_peak_ceiling_dbtp - true_peak);
+        }
+        gain.clamp(-self.max_gain_db, self.max_gain_db)
+    }
+
+    /// Scale interleaved `samples`, ramping linearly from the previous gain
+    /// to the one `reading` calls for across the buffer so gain changes
+    /// don't click.
+    pub fn apply(&mut self, samples: &mut [f32], channels: usize, reading: &LoudnessReading) {
+        let target = self.target_gain_db(reading);
+        let from = crate::sidechain_ducker::db_to_linear(self.gain_db);
+        let to = crate::sidechain_ducker::db_to_linear(target);
+        let frames = samples.len() / channels.max(1);
+        for (index, frame) in samples.chunks_mut(channels.max(1)).enumerate() {
+            let gain = from + (to - from) * (index + 1) as f32 / frames.max(1) as f32;
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+        self.gain_db = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Interleaved stereo sine with the same amplitude on both channels.
+    fn stereo_sine(frequency: f64, dbfs: f64, seconds: f64, sample_rate: u32) -> Vec<f32> {
+        let amplitude = 10f64.powf(dbfs / 20.0);
+        let frames = (seconds * sample_rate as f64) as usize;
+        (0..frames)
+            .flat_map(|i| {
+                let value = (amplitude
+                    * (2.0 * PI * frequency * i as f64 / sample_rate as f64).sin())
+                    as f32;
+                [value, value]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn k_weighting_matches_the_bs1770_48k_coefficients() {
+        let [shelf, high_pass] = k_weighting(48_000);
+        let expected_shelf = [
+            1.535_124_859_586_97,
+            -2.691_696_189_406_38,
+            1.198_392_810_852_85,
+            -1.690_659_293_182_41,
+            0.732_480_774_215_85,
+        ];
+        let actual_shelf = [shelf.b0, shelf.b1, shelf.b2, shelf.a1, shelf.a2];
+        for (actual, expected) in actual_shelf.iter().zip(expected_shelf) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+        assert!((high_pass.a1 - -1.990_047_454_833_98).abs() < 1e-9);
+        assert!((high_pass.a2 - 0.990_072_250_366_21).abs() < 1e-9);
+    }
+
+    /// EBU Tech 3341 cases 1 and 2: a stereo 1 kHz sine reads its level.
+    #[test]
+    fn stereo_sine_reads_its_level_on_every_meter() {
+        for (dbfs, sample_rate) in [(-23.0, 48_000), (-33.0, 48_000), (-23.0, 44_100)] {
+            let mut meter = LoudnessMeter::new(sample_rate, 2);
+            meter.process_interleaved(&stereo_sine(1000.0, dbfs, 20.0, sample_rate));
+
+            let reading = meter.reading();
+            for value in [
+                reading.momentary_lufs,
+                reading.short_term_lufs,
+                reading.integrated_lufs,
+            ] {
+                let value = value.expect("measured") as f64;
+                assert!((value - dbfs).abs() < 0.1, "{value} at {sample_rate} Hz");
+            }
+        }
+    }
+
+    /// EBU Tech 3341 case 3: quiet passages 13 LU down are gated out of the
+    /// integrated measurement.
+    #[test]
+    fn relative_gate_excludes_quiet_passages() {
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.process_interleaved(&stereo_sine(1000.0, -36.0, 10.0, 48_000));
+        meter.process_interleaved(&stereo_sine(1000.0, -23.0, 60.0, 48_000));
+        meter.process_interleaved(&stereo_sine(1000.0, -36.0, 10.0, 48_000));
+
+        let integrated = meter.integrated_lufs().unwrap();
+        assert!((integrated + 23.0).abs() < 0.1, "{integrated}");
+    }
+
+    #[test]
+    fn meters_stay_empty_until_their_window_fills() {
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.process_interleaved(&stereo_sine(1000.0, -23.0, 0.3, 48_000));
+        assert_eq!(meter.momentary_lufs(), None);
+        assert_eq!(meter.integrated_lufs(), None);
+
+        meter.process_interleaved(&stereo_sine(1000.0, -23.0, 0.1, 48_000));
+        assert!(meter.momentary_lufs().is_some());
+        assert_eq!(meter.short_term_lufs(), None);
+
+        let mut silent = LoudnessMeter::new(48_000, 2);
+        silent.process_interleaved(&vec![0.0; 48_000 * 2 * 4]);
+        assert_eq!(silent.reading(), LoudnessReading::default());
+    }
+
+    /// A full-scale sine at fs/4 sampled 45° off its peaks reads −3 dBFS
+    /// sample peak; the true peak is 0 dBTP.
+    #[test]
+    fn true_peak_finds_inter_sample_peaks() {
+        let samples: Vec<f32> = (0..4800)
+            .map(|i| (PI / 2.0 * i as f64 + PI / 4.0).sin() as f32)
+            .collect();
+        let sample_peak = samples.iter().fold(0f32, |peak, s| peak.max(s.abs()));
+        assert!((20.0 * sample_peak.log10() + 3.01).abs() < 0.01);
+
+        let mut meter = LoudnessMeter::new(48_000, 1);
+        meter.process_interleaved(&samples);
+        let true_peak = meter.true_peak_dbtp().unwrap();
+        assert!(true_peak > -0.3 && true_peak < 0.3, "{true_peak}");
+    }
+
+    #[test]
+    fn normalizer_targets_loudness_under_the_peak_ceiling() {
+        let normalizer = LoudnessNormalizer::new(STREAMING_TARGET_LUFS, -1.0, 20.0);
+        let reading = |integrated, true_peak| LoudnessReading {
+            integrated_lufs: Some(integrated),
+            true_peak_dbtp: Some(true_peak),
+            ..LoudnessReading::default()
+        };
+
+        assert_eq!(normalizer.target_gain_db(&LoudnessReading::default()), 0.0);
+        assert_eq!(normalizer.target_gain_db(&reading(-20.0, -10.0)), 6.0);
+        assert_eq!(normalizer.target_gain_db(&reading(-20.0, -4.0)), 3.0);
+        assert_eq!(normalizer.target_gain_db(&reading(-8.0, -0.5)), -6.0);
+        assert_eq!(normalizer.target_gain_db(&reading(-50.0, -30.0)), 20.0);
+    }
+
+    #[test]
+    fn normalizer_ramps_to_the_new_gain() {
+        let mut normalizer = LoudnessNormalizer::new(BROADCAST_TARGET_LUFS, -1.0, 20.0);
+        let reading = LoudnessReading {
+            integrated_lufs: Some(-17.0),
+            ..LoudnessReading::default()
+        };
+        let mut samples = vec![1.0f32; 8];
+        normalizer.apply(&mut samples, 2, &reading);
+
+        let target = 10f32.powf(-6.0 / 20.0);
+        assert!(samples[0] < 1.0 && samples[0] > target);
+        assert_eq!(samples[0], samples[1]);
+        assert!((samples[7] - target).abs() < 1e-6);
+        assert_eq!(normalizer.gain_db(), -6.0);
+    }
+}
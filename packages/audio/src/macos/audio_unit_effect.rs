@@ -0,0 +1,317 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AudioUnit effect processor — wraps `AudioUnitHost` with a streamlib
+//! processor lifecycle. Polls the input mailbox on a dedicated audio
+//! thread and dispatches converted stereo frames into the plugin.
+
+use crate::_generated_::AudioFrame;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use streamlib_plugin_sdk::sdk::context::RuntimeContextFullAccess;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::InputMailboxes;
+use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+
+use super::audio_unit_host::{AudioUnitHost, AudioUnitInstantiation};
+use super::audio_unit_scanner::AudioUnitComponentId;
+use crate::_generated_::tatolab__audio::audio_unit_effect_config::Instantiation;
+use crate::plugin_host::{ParameterInfo, PluginInfo, PluginParameterControl};
+use crate::processor_audio_converter::{
+    ProcessorAudioConverter, ProcessorAudioConverterTargetFormat,
+};
+
+/// Wrapper for InputMailboxes pointer that is Send.
+/// SAFETY: InputMailboxes is Send, and we ensure the pointed-to data outlives
+/// any thread that uses this pointer (polling thread is joined in teardown()).
+struct SendableInputsPtr(*const InputMailboxes);
+
+// SAFETY: InputMailboxes is Send, and we control the lifetime
+unsafe impl Send for SendableInputsPtr {}
+
+impl SendableInputsPtr {
+    /// SAFETY: Caller must ensure the pointed-to data is still valid.
+    unsafe fn get(&self) -> &InputMailboxes {
+        &*self.0
+    }
+}
+
+/// Wrapper for ProcessorAudioConverter pointer that is Send.
+/// SAFETY: We ensure the pointed-to data outlives any thread that uses this pointer,
+/// and only one thread accesses it.
+struct SendableAudioConverterPtr(*mut ProcessorAudioConverter);
+
+// SAFETY: Only one thread accesses it, and we join before drop
+unsafe impl Send for SendableAudioConverterPtr {}
+
+#[allow(clippy::mut_from_ref)]
+impl SendableAudioConverterPtr {
+    /// SAFETY: Caller must ensure the pointed-to data is still valid
+    /// and no other thread is accessing it.
+    unsafe fn get_mut(&self) -> &mut ProcessorAudioConverter {
+        &mut *self.0
+    }
+}
+
+/// Wrapper for AudioUnitHost pointer that is Send.
+/// SAFETY: We ensure the pointed-to data outlives any thread that uses this pointer,
+/// and only one thread accesses it between start() and teardown().
+struct SendableAudioUnitHostPtr(*mut Option<AudioUnitHost>);
+
+// SAFETY: Only one thread accesses it, and we join before drop
+unsafe impl Send for SendableAudioUnitHostPtr {}
+
+#[allow(clippy::mut_from_ref)]
+impl SendableAudioUnitHostPtr {
+    /// SAFETY: Caller must ensure the pointed-to data is still valid
+    /// and no other thread is accessing it.
+    unsafe fn get_mut(&self) -> &mut Option<AudioUnitHost> {
+        &mut *self.0
+    }
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/audio/AudioUnitEffect",
+    description = "AudioUnit (v2 / AUv3) effect processor with parameter control and automation (macOS)",
+    execution = manual,
+    config = crate::_generated_::AudioUnitEffectConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "Stereo audio frame to process through the AudioUnit (2 channels)"),
+    output("audio_out", "@tatolab/core/AudioFrame", description = "Processed stereo audio frame from the AudioUnit (2 channels)"),
+)]
+pub struct AudioUnitEffectProcessor {
+    host: Option<AudioUnitHost>,
+    buffer_size: usize,
+    polling_thread: Option<std::thread::JoinHandle<()>>,
+    stop_polling: Arc<AtomicBool>,
+    audio: Option<ProcessorAudioConverter>,
+}
+
+impl AudioUnitEffectProcessor::Processor {
+    pub fn plugin_info(&self) -> Result<&PluginInfo> {
+        self.host
+            .as_ref()
+            .map(|h| h.plugin_info())
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))
+    }
+
+    pub fn list_parameters(&self) -> Result<Vec<ParameterInfo>> {
+        self.host
+            .as_ref()
+            .map(|h| h.list_parameters())
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))
+    }
+
+    pub fn get_parameter(&self, id: u32) -> Result<f64> {
+        self.host
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .get_parameter(id)
+    }
+
+    pub fn set_parameter(&mut self, id: u32, value: f64) -> Result<()> {
+        self.host
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .set_parameter(id, value)
+    }
+
+    pub fn begin_edit(&mut self, id: u32) -> Result<()> {
+        self.host
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .begin_edit(id)
+    }
+
+    pub fn end_edit(&mut self, id: u32) -> Result<()> {
+        self.host
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .end_edit(id)
+    }
+
+    pub fn activate(&mut self, sample_rate: u32, max_frames: usize) -> Result<()> {
+        self.host
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .activate(sample_rate, max_frames)
+    }
+
+    pub fn deactivate(&mut self) -> Result<()> {
+        self.host
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("Plugin not initialized".into()))?
+            .deactivate()
+    }
+}
+
+impl ManualProcessor for AudioUnitEffectProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.buffer_size = self.config.buffer_size as usize;
+        self.audio = Some(ProcessorAudioConverter::new());
+
+        let instantiation = match self.config.instantiation {
+            Some(Instantiation::InProcess) => AudioUnitInstantiation::InProcess,
+            Some(Instantiation::OutOfProcess) => AudioUnitInstantiation::OutOfProcess,
+            None => AudioUnitInstantiation::Default,
+        };
+
+        // Instantiate with placeholder sample_rate — activate() will set the real rate
+        // when the first input frame arrives in the polling thread
+        let host = if let Some(component) = self.config.component.as_deref() {
+            let id = AudioUnitComponentId::parse(component)?;
+            AudioUnitHost::load_by_id(&id, instantiation, 48000, self.buffer_size)?
+        } else if let Some(name) = self.config.component_name.as_deref() {
+            AudioUnitHost::load_by_name(name, instantiation, 48000, self.buffer_size)?
+        } else {
+            return Err(Error::Configuration(
+                "AudioUnitEffect needs either `component` or `component_name`".into(),
+            ));
+        };
+
+        tracing::info!(
+            "[AudioUnitEffect] Loaded plugin '{}' (activation deferred to first input frame)",
+            host.plugin_info().name,
+        );
+        self.host = Some(host);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop_polling.store(true, Ordering::SeqCst);
+
+        if let Some(handle) = self.polling_thread.take() {
+            let _ = handle.join();
+        }
+
+        // Safe to access self.host now — polling thread is joined
+        if let Some(ref mut host) = self.host {
+            let name = host.plugin_info().name.clone();
+            match host.deactivate() {
+                Ok(()) => {
+                    tracing::info!("[AudioUnitEffect] Deactivated plugin '{}'", name);
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let stop_flag = Arc::clone(&self.stop_polling);
+        stop_flag.store(false, Ordering::SeqCst);
+
+        // SAFETY for all raw pointers:
+        // 1. The polling thread is stopped in teardown() before self is dropped
+        // 2. Only the polling thread accesses these after start() returns
+        // 3. In Manual mode, no other code touches self.inputs/self.audio/self.host
+        //    between start() and teardown()
+        let audio = self.audio.as_mut().ok_or_else(|| {
+            Error::Configuration(
+                "audio converter not initialized — setup() must run before start()".into(),
+            )
+        })?;
+        let inputs_ptr = SendableInputsPtr(&self.inputs as *const _);
+        let audio_ptr = SendableAudioConverterPtr(audio as *mut _);
+        let host_ptr = SendableAudioUnitHostPtr(&mut self.host as *mut _);
+        let outputs = self.outputs.clone();
+        let buffer_size = self.buffer_size;
+
+        let polling_thread = std::thread::spawn(move || {
+            let mut plugin_activated = false;
+            let mut frame_counter: u64 = 0;
+
+            let target = ProcessorAudioConverterTargetFormat {
+                sample_rate: None, // Don't resample — AudioOutput handles that
+                channels: Some(2), // Stereo for AudioUnit
+                buffer_size: Some(buffer_size),
+            };
+
+            while !stop_flag.load(Ordering::SeqCst) {
+                let inputs = unsafe { inputs_ptr.get() };
+
+                if !inputs.has_data("audio_in") {
+                    std::thread::sleep(std::time::Duration::from_micros(500));
+                    continue;
+                }
+
+                let input_frame: AudioFrame = match inputs.read("audio_in") {
+                    Ok(f) => f,
+                    Err(e) => {
+                        tracing::error!("[AudioUnitEffect] Read failed: {}", e);
+                        continue;
+                    }
+                };
+
+                // Deferred activation on first frame
+                if !plugin_activated {
+                    let host = unsafe { host_ptr.get_mut() };
+                    if let Some(ref mut h) = host {
+                        match h.activate(input_frame.sample_rate, buffer_size) {
+                            Ok(()) => {
+                                tracing::info!(
+                                    "[AudioUnitEffect] Activated plugin '{}' at {}Hz (from input)",
+                                    h.plugin_info().name,
+                                    input_frame.sample_rate,
+                                );
+                                plugin_activated = true;
+                            }
+                            Err(e) => {
+                                tracing::error!("[AudioUnitEffect] Activation failed: {}", e);
+                                continue;
+                            }
+                        }
+                    }
+                }
+
+                // Convert (channels + rechunk) and process through AudioUnit
+                let audio = unsafe { audio_ptr.get_mut() };
+                match audio.convert(&input_frame, &target) {
+                    Ok(frames) => {
+                        let host = unsafe { host_ptr.get_mut() };
+                        if let Some(ref mut h) = host {
+                            for frame in frames {
+                                match h.process_audio(&frame) {
+                                    Ok(output) => {
+                                        if let Err(e) = outputs.write("audio_out", &output) {
+                                            tracing::error!("[AudioUnitEffect] Write failed: {}", e);
+                                        }
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("[AudioUnitEffect] AudioUnit process failed: {}", e)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => tracing::error!("[AudioUnitEffect] Convert failed: {}", e),
+                }
+
+                frame_counter += 1;
+            }
+
+            tracing::info!(
+                "[AudioUnitEffect] Polling thread stopped after {} frames",
+                frame_counter
+            );
+        });
+
+        self.polling_thread = Some(polling_thread);
+        Ok(())
+    }
+}
+
+impl PluginParameterControl for AudioUnitEffectProcessor::Processor {
+    fn set_parameter(&mut self, id: u32, value: f64) -> Result<()> {
+        AudioUnitEffectProcessor::Processor::set_parameter(self, id, value)
+    }
+
+    fn begin_edit(&mut self, id: u32) -> Result<()> {
+        AudioUnitEffectProcessor::Processor::begin_edit(self, id)
+    }
+
+    fn end_edit(&mut self, id: u32) -> Result<()> {
+        AudioUnitEffectProcessor::Processor::end_edit(self, id)
+    }
+}
@@ -0,0 +1,848 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! In-process / out-of-process AudioUnit effect instance, driven through the
+//! v2 AudioUnit API. v3 units are bridged to that API by AudioToolbox, so one
+//! code path hosts both.
+
+use std::ffi::{CStr, c_void};
+use std::ptr::NonNull;
+use std::time::Duration;
+
+use block2::RcBlock;
+use objc2_audio_toolbox::{
+    AURenderCallbackStruct, AudioComponent, AudioComponentInstance, AudioComponentInstanceDispose,
+    AudioComponentInstantiate, AudioComponentInstantiationOptions, AudioUnit,
+    AudioUnitGetParameter, AudioUnitGetProperty, AudioUnitGetPropertyInfo, AudioUnitInitialize,
+    AudioUnitParameterInfo, AudioUnitParameterOptions, AudioUnitParameterUnit, AudioUnitRender,
+    AudioUnitRenderActionFlags, AudioUnitSetParameter, AudioUnitSetProperty, AudioUnitUninitialize,
+    kAudioUnitProperty_Latency, kAudioUnitProperty_MaximumFramesPerSlice,
+    kAudioUnitProperty_ParameterInfo, kAudioUnitProperty_ParameterList,
+    kAudioUnitProperty_SetRenderCallback, kAudioUnitProperty_StreamFormat, kAudioUnitScope_Global,
+    kAudioUnitScope_Input, kAudioUnitScope_Output,
+};
+use objc2_core_audio_types::{
+    AudioBuffer, AudioBufferList, AudioStreamBasicDescription, AudioTimeStamp, AudioTimeStampFlags,
+    kAudioFormatFlagIsFloat, kAudioFormatFlagIsNonInterleaved, kAudioFormatFlagIsPacked,
+    kAudioFormatLinearPCM,
+};
+use objc2_core_foundation::{CFRetained, CFString};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use super::audio_unit_scanner::{AudioUnitComponentId, AudioUnitInfo, AudioUnitScanner};
+use crate::_generated_::AudioFrame;
+use crate::plugin_host::{ParameterInfo, PluginInfo};
+
+/// How long to wait for an asynchronous instantiation — out-of-process
+/// units launch their extension process first.
+const INSTANTIATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where the unit's code runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioUnitInstantiation {
+    /// The system default: v2 units in-process, v3 units out-of-process.
+    #[default]
+    Default,
+    /// Load into the host process (v3 units must allow it).
+    InProcess,
+    /// Run in a separate extension process, isolating crashes.
+    OutOfProcess,
+}
+
+impl AudioUnitInstantiation {
+    fn options(self) -> AudioComponentInstantiationOptions {
+        match self {
+            Self::Default => AudioComponentInstantiationOptions(0),
+            Self::InProcess => AudioComponentInstantiationOptions::LoadInProcess,
+            Self::OutOfProcess => AudioComponentInstantiationOptions::LoadOutOfProcess,
+        }
+    }
+}
+
+/// Input audio for the unit's render callback. Written before each
+/// `AudioUnitRender`, read from inside it.
+struct RenderInput {
+    channels: Vec<Vec<f32>>,
+    frames: usize,
+}
+
+/// `AudioBufferList` with room for two channel buffers.
+#[repr(C)]
+struct StereoBufferList {
+    number_buffers: u32,
+    buffers: [AudioBuffer; 2],
+}
+
+/// A component instance handed back from the instantiation callback.
+struct SendableInstance(AudioComponentInstance);
+
+// SAFETY: the instance is only moved to the waiting thread, never shared.
+unsafe impl Send for SendableInstance {}
+
+pub struct AudioUnitHost {
+    unit: AudioUnit,
+
+    plugin_info: PluginInfo,
+
+    parameter_info: Vec<ParameterInfo>,
+
+    /// Display unit of each entry in `parameter_info`.
+    parameter_units: Vec<(AudioUnitParameterUnit, String)>,
+
+    is_initialized: bool,
+
+    sample_rate: u32,
+    buffer_size: usize,
+
+    input_channels: usize,
+    output_channels: usize,
+
+    /// Owned (leaked from a `Box`); the unit holds this pointer as the
+    /// render callback's context until it is disposed.
+    render_input: NonNull<RenderInput>,
+    output_buffers: Vec<Vec<f32>>,
+
+    /// Samples rendered since initialization, passed as the render time.
+    sample_time: f64,
+}
+
+// SAFETY: AudioUnits may be called from any one thread at a time; the host
+// is moved to the processor's audio thread and only used there between
+// start() and teardown().
+unsafe impl Send for AudioUnitHost {}
+
+impl AudioUnitHost {
+    /// Load an effect by name (`AUDelay` or `Apple: AUDelay`).
+    pub fn load_by_name(
+        name: &str,
+        instantiation: AudioUnitInstantiation,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        let (component, info) = AudioUnitScanner::find_by_name(name)?;
+        Self::load_component(component, info, instantiation, sample_rate, buffer_size)
+    }
+
+    /// Load the unit registered under this type / subtype / manufacturer.
+    pub fn load_by_id(
+        id: &AudioUnitComponentId,
+        instantiation: AudioUnitInstantiation,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        let (component, info) = AudioUnitScanner::find(id)?;
+        Self::load_component(component, info, instantiation, sample_rate, buffer_size)
+    }
+
+    fn load_component(
+        component: AudioComponent,
+        info: AudioUnitInfo,
+        instantiation: AudioUnitInstantiation,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        if instantiation == AudioUnitInstantiation::InProcess
+            && info.is_v3
+            && !info.can_load_in_process
+        {
+            return Err(Error::Configuration(format!(
+                "AudioUnit '{}' can only be loaded out-of-process",
+                info.name
+            )));
+        }
+
+        let unit = instantiate(component, instantiation.options())?;
+        let render_input = NonNull::from(Box::leak(Box::new(RenderInput {
+            channels: Vec::new(),
+            frames: 0,
+        })));
+
+        let mut host = Self {
+            unit,
+            plugin_info: PluginInfo {
+                name: info.name.clone(),
+                vendor: info.vendor.clone(),
+                version: info.version.clone(),
+                format: "AudioUnit".to_string(),
+                id: info.id.to_string(),
+                num_inputs: 0,
+                num_outputs: 0,
+            },
+            parameter_info: Vec::new(),
+            parameter_units: Vec::new(),
+            is_initialized: false,
+            sample_rate,
+            buffer_size,
+            input_channels: 0,
+            output_channels: 0,
+            render_input,
+            output_buffers: Vec::new(),
+            sample_time: 0.0,
+        };
+
+        host.plugin_info.num_inputs = host.stream_channels(kAudioUnitScope_Input);
+        host.plugin_info.num_outputs = host.stream_channels(kAudioUnitScope_Output);
+        host.discover_parameters()?;
+
+        tracing::info!(
+            "[AudioUnit] Instantiated '{}' by '{}' ({}, {:?}) with {} parameters",
+            info.name,
+            info.vendor,
+            info.id,
+            instantiation,
+            host.parameter_info.len()
+        );
+
+        Ok(host)
+    }
+
+    /// Channel count of the unit's current format on bus 0 of `scope`.
+    fn stream_channels(&self, scope: u32) -> u32 {
+        // SAFETY: AudioStreamBasicDescription is a plain C struct.
+        let mut format: AudioStreamBasicDescription = unsafe { std::mem::zeroed() };
+        if self.get_property(kAudioUnitProperty_StreamFormat, scope, 0, &mut format) == 0 {
+            format.mChannelsPerFrame
+        } else {
+            0
+        }
+    }
+
+    fn discover_parameters(&mut self) -> Result<()> {
+        for id in self.parameter_ids()? {
+            // SAFETY: AudioUnitParameterInfo is a plain C struct.
+            let mut info: AudioUnitParameterInfo = unsafe { std::mem::zeroed() };
+            if self.get_property(
+                kAudioUnitProperty_ParameterInfo,
+                kAudioUnitScope_Global,
+                id,
+                &mut info,
+            ) != 0
+            {
+                tracing::debug!("[AudioUnit] Skipping parameter {} without info", id);
+                continue;
+            }
+
+            let flags = info.flags;
+            let release = flags.contains(AudioUnitParameterOptions::Flag_CFNameRelease);
+            let name = if flags.contains(AudioUnitParameterOptions::Flag_HasCFNameString)
+                && !info.cfNameString.is_null()
+            {
+                // SAFETY: the unit filled in a valid CFString.
+                unsafe { cf_string(info.cfNameString, release) }
+            } else {
+                // SAFETY: `name` is a NUL-terminated C string when there is
+                // no CFString name.
+                unsafe { CStr::from_ptr(info.name.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            let custom_unit =
+                if info.unit == AudioUnitParameterUnit::CustomUnit && !info.unitName.is_null() {
+                    // SAFETY: as for the name.
+                    unsafe { cf_string(info.unitName, release) }
+                } else {
+                    String::new()
+                };
+
+            let writable = flags.contains(AudioUnitParameterOptions::Flag_IsWritable);
+            let value = self.read_parameter(id).unwrap_or(info.defaultValue as f64);
+            self.parameter_info.push(ParameterInfo {
+                id,
+                name,
+                value,
+                min: info.minValue as f64,
+                max: info.maxValue as f64,
+                default: info.defaultValue as f64,
+                is_automatable: writable
+                    && !flags.contains(AudioUnitParameterOptions::Flag_NonRealTime),
+                is_stepped: matches!(
+                    info.unit,
+                    AudioUnitParameterUnit::Indexed | AudioUnitParameterUnit::Boolean
+                ),
+                is_periodic: matches!(
+                    info.unit,
+                    AudioUnitParameterUnit::Phase | AudioUnitParameterUnit::Degrees
+                ),
+                is_hidden: false,
+                is_readonly: !writable,
+                is_bypass: false,
+                display: display_value(value, info.unit, &custom_unit),
+            });
+            self.parameter_units.push((info.unit, custom_unit));
+        }
+        Ok(())
+    }
+
+    /// Global-scope parameter IDs.
+    fn parameter_ids(&self) -> Result<Vec<u32>> {
+        let mut size = 0u32;
+        // SAFETY: `self.unit` is a live instance; out pointers are to locals.
+        let status = unsafe {
+            AudioUnitGetPropertyInfo(
+                self.unit,
+                kAudioUnitProperty_ParameterList,
+                kAudioUnitScope_Global,
+                0,
+                &mut size,
+                std::ptr::null_mut(),
+            )
+        };
+        if status != 0 || size == 0 {
+            // Units without parameters may not implement the property.
+            return Ok(Vec::new());
+        }
+
+        let mut ids = vec![0u32; size as usize / std::mem::size_of::<u32>()];
+        // SAFETY: `ids` holds `size` bytes.
+        check(
+            unsafe {
+                AudioUnitGetProperty(
+                    self.unit,
+                    kAudioUnitProperty_ParameterList,
+                    kAudioUnitScope_Global,
+                    0,
+                    NonNull::new_unchecked(ids.as_mut_ptr().cast()),
+                    NonNull::from(&mut size),
+                )
+            },
+            "parameter list",
+        )?;
+        ids.truncate(size as usize / std::mem::size_of::<u32>());
+        Ok(ids)
+    }
+
+    fn read_parameter(&self, id: u32) -> Result<f64> {
+        let mut value = 0f32;
+        // SAFETY: `self.unit` is a live instance.
+        check(
+            unsafe {
+                AudioUnitGetParameter(
+                    self.unit,
+                    id,
+                    kAudioUnitScope_Global,
+                    0,
+                    NonNull::from(&mut value),
+                )
+            },
+            "get parameter",
+        )?;
+        Ok(value as f64)
+    }
+
+    fn parameter_index(&self, id: u32) -> Result<usize> {
+        self.parameter_info
+            .iter()
+            .position(|info| info.id == id)
+            .ok_or_else(|| Error::Configuration(format!("Parameter ID {} not found", id)))
+    }
+
+    pub fn plugin_info(&self) -> &PluginInfo {
+        &self.plugin_info
+    }
+
+    pub fn list_parameters(&self) -> Vec<ParameterInfo> {
+        self.parameter_info
+            .iter()
+            .zip(&self.parameter_units)
+            .map(|(info, (unit, custom_unit))| {
+                let value = self.read_parameter(info.id).unwrap_or(info.value);
+                ParameterInfo {
+                    value,
+                    display: display_value(value, *unit, custom_unit),
+                    ..info.clone()
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_parameter(&self, id: u32) -> Result<f64> {
+        self.parameter_index(id)?;
+        self.read_parameter(id)
+    }
+
+    /// Set a parameter in its own units (clamped to its range). Takes effect
+    /// from the next rendered block.
+    pub fn set_parameter(&mut self, id: u32, value: f64) -> Result<()> {
+        let info = &self.parameter_info[self.parameter_index(id)?];
+        let value = value.clamp(info.min, info.max);
+
+        // SAFETY: `self.unit` is a live instance.
+        check(
+            unsafe {
+                AudioUnitSetParameter(self.unit, id, kAudioUnitScope_Global, 0, value as f32, 0)
+            },
+            "set parameter",
+        )?;
+
+        tracing::debug!("Parameter {} set to {}", id, value);
+        Ok(())
+    }
+
+    /// AudioUnit edit gestures only matter to parameter listeners (editors);
+    /// accepted for the shared automation surface.
+    pub fn begin_edit(&mut self, id: u32) -> Result<()> {
+        tracing::trace!("begin_edit({}) - no-op for AudioUnit", id);
+        Ok(())
+    }
+
+    pub fn end_edit(&mut self, id: u32) -> Result<()> {
+        tracing::trace!("end_edit({}) - no-op for AudioUnit", id);
+        Ok(())
+    }
+
+    /// Processing latency the unit reports, in samples.
+    pub fn latency_samples(&self) -> u32 {
+        let mut seconds = 0f64;
+        if self.get_property(
+            kAudioUnitProperty_Latency,
+            kAudioUnitScope_Global,
+            0,
+            &mut seconds,
+        ) == 0
+        {
+            (seconds * self.sample_rate as f64).round() as u32
+        } else {
+            0
+        }
+    }
+
+    pub fn activate(&mut self, sample_rate: u32, max_frames: usize) -> Result<()> {
+        if self.is_initialized {
+            self.deactivate()?;
+        }
+
+        let max_frames_u32 = max_frames as u32;
+        check(
+            self.set_property(
+                kAudioUnitProperty_MaximumFramesPerSlice,
+                kAudioUnitScope_Global,
+                0,
+                &max_frames_u32,
+            ),
+            "set maximum frames per slice",
+        )?;
+
+        // Prefer stereo on both sides; fall back to mono where the unit
+        // refuses it.
+        self.output_channels = self.negotiate_channels(kAudioUnitScope_Output, sample_rate)?;
+        self.input_channels = self.negotiate_channels(kAudioUnitScope_Input, sample_rate)?;
+
+        // SAFETY: no render is in flight while the unit is uninitialized.
+        let render_input = unsafe { &mut *self.render_input.as_ptr() };
+        render_input.channels = vec![vec![0.0; max_frames]; self.input_channels];
+        render_input.frames = 0;
+        self.output_buffers = vec![vec![0.0; max_frames]; self.output_channels];
+
+        let callback = AURenderCallbackStruct {
+            inputProc: Some(render_input_callback),
+            inputProcRefCon: self.render_input.as_ptr().cast(),
+        };
+        check(
+            self.set_property(
+                kAudioUnitProperty_SetRenderCallback,
+                kAudioUnitScope_Input,
+                0,
+                &callback,
+            ),
+            "set render callback",
+        )?;
+
+        // SAFETY: `self.unit` is a live instance.
+        check(unsafe { AudioUnitInitialize(self.unit) }, "initialize")?;
+
+        self.sample_rate = sample_rate;
+        self.buffer_size = max_frames;
+        self.sample_time = 0.0;
+        self.is_initialized = true;
+        self.plugin_info.num_inputs = self.input_channels as u32;
+        self.plugin_info.num_outputs = self.output_channels as u32;
+
+        tracing::info!(
+            "[AudioUnit] Initialized '{}' at {}Hz, {} frames, {} in / {} out channels",
+            self.plugin_info.name,
+            sample_rate,
+            max_frames,
+            self.input_channels,
+            self.output_channels
+        );
+        Ok(())
+    }
+
+    fn negotiate_channels(&self, scope: u32, sample_rate: u32) -> Result<usize> {
+        for channels in [2, 1] {
+            let format = AudioStreamBasicDescription {
+                mSampleRate: sample_rate as f64,
+                mFormatID: kAudioFormatLinearPCM,
+                mFormatFlags: kAudioFormatFlagIsFloat
+                    | kAudioFormatFlagIsPacked
+                    | kAudioFormatFlagIsNonInterleaved,
+                mBytesPerPacket: 4,
+                mFramesPerPacket: 1,
+                mBytesPerFrame: 4,
+                mChannelsPerFrame: channels,
+                mBitsPerChannel: 32,
+                mReserved: 0,
+            };
+            if self.set_property(kAudioUnitProperty_StreamFormat, scope, 0, &format) == 0 {
+                return Ok(channels as usize);
+            }
+        }
+        Err(Error::Configuration(format!(
+            "AudioUnit '{}' supports neither stereo nor mono 32-bit float {}",
+            self.plugin_info.name,
+            if scope == kAudioUnitScope_Input {
+                "input"
+            } else {
+                "output"
+            }
+        )))
+    }
+
+    pub fn deactivate(&mut self) -> Result<()> {
+        if !self.is_initialized {
+            return Ok(());
+        }
+        self.is_initialized = false;
+        // SAFETY: `self.unit` is a live instance.
+        check(unsafe { AudioUnitUninitialize(self.unit) }, "uninitialize")
+    }
+
+    pub fn process_audio(&mut self, input: &AudioFrame) -> Result<AudioFrame> {
+        if !self.is_initialized {
+            return Err(Error::Configuration("Plugin not activated".into()));
+        }
+
+        let num_samples = input.samples.len() / input.channels as usize;
+        if num_samples > self.buffer_size {
+            return Err(Error::Runtime(format!(
+                "Frame of {} samples exceeds the {} activated with",
+                num_samples, self.buffer_size
+            )));
+        }
+
+        {
+            // SAFETY: only the render callback reads this, and only inside
+            // the AudioUnitRender call below.
+            let render_input = unsafe { &mut *self.render_input.as_ptr() };
+            for i in 0..num_samples {
+                let base_idx = i * 2; // 2 channels (stereo)
+                let (left, right) = (input.samples[base_idx], input.samples[base_idx + 1]);
+                if self.input_channels == 2 {
+                    render_input.channels[0][i] = left;
+                    render_input.channels[1][i] = right;
+                } else {
+                    render_input.channels[0][i] = 0.5 * (left + right);
+                }
+            }
+            render_input.frames = num_samples;
+        }
+
+        let byte_size = (num_samples * std::mem::size_of::<f32>()) as u32;
+        let mut buffer_list = StereoBufferList {
+            number_buffers: self.output_channels as u32,
+            buffers: [AudioBuffer {
+                mNumberChannels: 1,
+                mDataByteSize: byte_size,
+                mData: std::ptr::null_mut(),
+            }; 2],
+        };
+        for (buffer, output) in buffer_list.buffers.iter_mut().zip(&mut self.output_buffers) {
+            buffer.mData = output.as_mut_ptr().cast();
+        }
+
+        // SAFETY: AudioTimeStamp is a plain C struct.
+        let mut time_stamp: AudioTimeStamp = unsafe { std::mem::zeroed() };
+        time_stamp.mSampleTime = self.sample_time;
+        time_stamp.mFlags = AudioTimeStampFlags::SampleTimeValid;
+        let mut action_flags = AudioUnitRenderActionFlags(0);
+
+        // SAFETY: the buffer list describes `output_channels` buffers of at
+        // least `num_samples` floats; StereoBufferList has AudioBufferList's
+        // layout.
+        check(
+            unsafe {
+                AudioUnitRender(
+                    self.unit,
+                    &mut action_flags,
+                    NonNull::from(&time_stamp),
+                    0,
+                    num_samples as u32,
+                    NonNull::from(&mut buffer_list).cast::<AudioBufferList>(),
+                )
+            },
+            "render",
+        )?;
+        self.sample_time += num_samples as f64;
+
+        // The unit may render in place and hand back its own buffers, so read
+        // through the returned pointers.
+        let output_len = num_samples * 2;
+        let mut output_samples = Vec::with_capacity(output_len);
+        let right_channel = self.output_channels - 1;
+        // SAFETY: each returned buffer holds at least `num_samples` floats.
+        let (left, right) = unsafe {
+            (
+                std::slice::from_raw_parts(buffer_list.buffers[0].mData as *const f32, num_samples),
+                std::slice::from_raw_parts(
+                    buffer_list.buffers[right_channel].mData as *const f32,
+                    num_samples,
+                ),
+            )
+        };
+        for i in 0..num_samples {
+            output_samples.push(left[i]);
+            output_samples.push(right[i]);
+        }
+
+        Ok(AudioFrame {
+            samples: output_samples,
+            channels: input.channels,
+            timestamp_ns: input.timestamp_ns.clone(),
+            frame_index: input.frame_index.clone(),
+            sample_rate: input.sample_rate,
+            channel_layout: input.channel_layout.clone(),
+        })
+    }
+
+    fn get_property<T>(&self, id: u32, scope: u32, element: u32, value: &mut T) -> i32 {
+        let mut size = std::mem::size_of::<T>() as u32;
+        // SAFETY: `value` is a `T`, the property's C type.
+        unsafe {
+            AudioUnitGetProperty(
+                self.unit,
+                id,
+                scope,
+                element,
+                NonNull::from(value).cast(),
+                NonNull::from(&mut size),
+            )
+        }
+    }
+
+    fn set_property<T>(&self, id: u32, scope: u32, element: u32, value: &T) -> i32 {
+        // SAFETY: `value` is a `T`, the property's C type.
+        unsafe {
+            AudioUnitSetProperty(
+                self.unit,
+                id,
+                scope,
+                element,
+                (value as *const T).cast(),
+                std::mem::size_of::<T>() as u32,
+            )
+        }
+    }
+}
+
+impl Drop for AudioUnitHost {
+    fn drop(&mut self) {
+        if let Err(e) = self.deactivate() {
+            tracing::warn!("[AudioUnit] Uninitialize failed during drop: {}", e);
+        }
+        // SAFETY: the instance is disposed once; after this nothing calls
+        // the render callback, so its context can be freed.
+        unsafe {
+            AudioComponentInstanceDispose(self.unit);
+            drop(Box::from_raw(self.render_input.as_ptr()));
+        }
+    }
+}
+
+/// Create an instance of `component`. Instantiation is asynchronous (v3 and
+/// out-of-process units require it); this blocks until it completes, so it
+/// must not run on the main thread.
+fn instantiate(
+    component: AudioComponent,
+    options: AudioComponentInstantiationOptions,
+) -> Result<AudioUnit> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let handler = RcBlock::new(move |instance: AudioComponentInstance, status: i32| {
+        let _ = sender.send((SendableInstance(instance), status));
+    });
+
+    // SAFETY: `component` came from the registry.
+    unsafe { AudioComponentInstantiate(component, options, &handler) };
+
+    let (instance, status) = receiver.recv_timeout(INSTANTIATE_TIMEOUT).map_err(|_| {
+        Error::Runtime(format!(
+            "AudioUnit instantiation did not complete within {:?}",
+            INSTANTIATE_TIMEOUT
+        ))
+    })?;
+    check(status, "instantiate")?;
+    if instance.0.is_null() {
+        return Err(Error::Runtime(
+            "AudioUnit instantiation returned no instance".into(),
+        ));
+    }
+    Ok(instance.0)
+}
+
+/// The render callback feeding the unit's input bus from [`RenderInput`].
+unsafe extern "C-unwind" fn render_input_callback(
+    ref_con: NonNull<c_void>,
+    _action_flags: NonNull<AudioUnitRenderActionFlags>,
+    _time_stamp: NonNull<AudioTimeStamp>,
+    _bus_number: u32,
+    number_frames: u32,
+    io_data: *mut AudioBufferList,
+) -> i32 {
+    // SAFETY: `ref_con` is the host's RenderInput, alive while the unit is.
+    let input = unsafe { ref_con.cast::<RenderInput>().as_ref() };
+    let Some(io_data) = NonNull::new(io_data) else {
+        return 0;
+    };
+    if input.channels.is_empty() {
+        return 0;
+    }
+
+    // SAFETY: the unit passes a list of `mNumberBuffers` buffers.
+    let buffers = unsafe {
+        let list = io_data.as_ptr();
+        std::slice::from_raw_parts_mut(
+            std::ptr::addr_of_mut!((*list).mBuffers).cast::<AudioBuffer>(),
+            (*list).mNumberBuffers as usize,
+        )
+    };
+    let frames = (number_frames as usize).min(input.frames);
+    for (channel, buffer) in buffers.iter_mut().enumerate() {
+        let source = &input.channels[channel.min(input.channels.len() - 1)];
+        if buffer.mData.is_null() {
+            // No buffer supplied — lend ours for the duration of the render.
+            buffer.mData = source.as_ptr().cast_mut().cast();
+            buffer.mDataByteSize = (frames * std::mem::size_of::<f32>()) as u32;
+            continue;
+        }
+        let capacity = buffer.mDataByteSize as usize / std::mem::size_of::<f32>();
+        // SAFETY: the unit's buffer holds `mDataByteSize` bytes.
+        let destination =
+            unsafe { std::slice::from_raw_parts_mut(buffer.mData.cast::<f32>(), capacity) };
+        let copied = frames.min(capacity);
+        destination[..copied].copy_from_slice(&source[..copied]);
+        destination[copied..].fill(0.0);
+    }
+    0
+}
+
+/// Read a CFString the unit returned, releasing it when the unit handed
+/// over ownership.
+unsafe fn cf_string(string: *const CFString, release: bool) -> String {
+    let Some(string) = NonNull::new(string.cast_mut()) else {
+        return String::new();
+    };
+    if release {
+        // SAFETY: ownership was transferred to us.
+        unsafe { CFRetained::from_raw(string) }.to_string()
+    } else {
+        // SAFETY: the unit keeps the string alive.
+        unsafe { string.as_ref() }.to_string()
+    }
+}
+
+fn check(status: i32, what: &str) -> Result<()> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(Error::Runtime(format!(
+            "AudioUnit {} failed (OSStatus {})",
+            what, status
+        )))
+    }
+}
+
+/// A parameter value with its unit, as a generic editor would show it.
+fn display_value(value: f64, unit: AudioUnitParameterUnit, custom_unit: &str) -> String {
+    let suffix = match unit {
+        AudioUnitParameterUnit::Boolean => {
+            return if value >= 0.5 { "On" } else { "Off" }.to_string();
+        }
+        AudioUnitParameterUnit::Indexed
+        | AudioUnitParameterUnit::MIDINoteNumber
+        | AudioUnitParameterUnit::MIDIController => return format!("{}", value.round() as i64),
+        AudioUnitParameterUnit::Percent => "%",
+        AudioUnitParameterUnit::Seconds => "s",
+        AudioUnitParameterUnit::Milliseconds => "ms",
+        AudioUnitParameterUnit::SampleFrames => "samples",
+        AudioUnitParameterUnit::Hertz => "Hz",
+        AudioUnitParameterUnit::Cents | AudioUnitParameterUnit::AbsoluteCents => "cents",
+        AudioUnitParameterUnit::RelativeSemiTones => "st",
+        AudioUnitParameterUnit::Decibels => "dB",
+        AudioUnitParameterUnit::Degrees => "°",
+        AudioUnitParameterUnit::Octaves => "oct",
+        AudioUnitParameterUnit::BPM => "BPM",
+        AudioUnitParameterUnit::Beats => "beats",
+        AudioUnitParameterUnit::Ratio => ":1",
+        AudioUnitParameterUnit::CustomUnit => custom_unit,
+        _ => "",
+    };
+    if suffix.is_empty() {
+        format!("{:.2}", value)
+    } else if suffix == ":1" {
+        format!("{:.2}:1", value)
+    } else {
+        format!("{:.2} {}", value, suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stereo_buffer_list_matches_audio_buffer_list_layout() {
+        assert_eq!(
+            std::mem::offset_of!(StereoBufferList, number_buffers),
+            std::mem::offset_of!(AudioBufferList, mNumberBuffers)
+        );
+        assert_eq!(
+            std::mem::offset_of!(StereoBufferList, buffers),
+            std::mem::offset_of!(AudioBufferList, mBuffers)
+        );
+        assert_eq!(
+            std::mem::size_of::<StereoBufferList>(),
+            std::mem::size_of::<AudioBufferList>() + std::mem::size_of::<AudioBuffer>()
+        );
+        assert_eq!(
+            std::mem::align_of::<StereoBufferList>(),
+            std::mem::align_of::<AudioBufferList>()
+        );
+    }
+
+    #[test]
+    fn parameter_values_display_with_their_unit() {
+        assert_eq!(
+            display_value(-6.0, AudioUnitParameterUnit::Decibels, ""),
+            "-6.00 dB"
+        );
+        assert_eq!(
+            display_value(1.0, AudioUnitParameterUnit::Boolean, ""),
+            "On"
+        );
+        assert_eq!(display_value(2.4, AudioUnitParameterUnit::Indexed, ""), "2");
+        assert_eq!(
+            display_value(4.0, AudioUnitParameterUnit::Ratio, ""),
+            "4.00:1"
+        );
+        assert_eq!(
+            display_value(3.0, AudioUnitParameterUnit::CustomUnit, "taps"),
+            "3.00 taps"
+        );
+        assert_eq!(
+            display_value(0.25, AudioUnitParameterUnit::Generic, ""),
+            "0.25"
+        );
+    }
+
+    #[test]
+    fn instantiation_maps_to_component_options() {
+        assert_eq!(AudioUnitInstantiation::Default.options().0, 0);
+        assert_eq!(
+            AudioUnitInstantiation::InProcess.options(),
+            AudioComponentInstantiationOptions::LoadInProcess
+        );
+        assert_eq!(
+            AudioUnitInstantiation::OutOfProcess.options(),
+            AudioComponentInstantiationOptions::LoadOutOfProcess
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Discovery of installed AudioUnit effects through the AudioComponent
+//! registry — v2 components and v3 app extensions alike.
+
+use std::ptr::NonNull;
+
+use objc2_audio_toolbox::{
+    AudioComponent, AudioComponentCopyName, AudioComponentDescription, AudioComponentFindNext,
+    AudioComponentFlags, AudioComponentGetDescription, AudioComponentGetVersion,
+    kAudioUnitType_Effect, kAudioUnitType_MusicEffect,
+};
+use objc2_core_foundation::{CFRetained, CFString};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// Component types hosted as effects: plain effects and MIDI-controlled
+/// effects (`aufx`, `aumf`).
+const EFFECT_TYPES: [u32; 2] = [kAudioUnitType_Effect, kAudioUnitType_MusicEffect];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioUnitComponentId {
+    pub component_type: u32,
+
+    pub subtype: u32,
+
+    pub manufacturer: u32,
+}
+
+impl AudioUnitComponentId {
+    /// Parse the `type subtype manufacturer` four-char-code triple AU tools
+    /// print (`auval -a`), e.g. `aufx dely appl`.
+    pub fn parse(codes: &str) -> Result<Self> {
+        let codes: Vec<&str> = codes.split_whitespace().collect();
+        let [component_type, subtype, manufacturer] = codes.as_slice() else {
+            return Err(Error::Configuration(format!(
+                "AudioUnit component must be 'type subtype manufacturer', got {} codes",
+                codes.len()
+            )));
+        };
+        Ok(Self {
+            component_type: four_char_code(component_type)?,
+            subtype: four_char_code(subtype)?,
+            manufacturer: four_char_code(manufacturer)?,
+        })
+    }
+
+    fn description(&self) -> AudioComponentDescription {
+        AudioComponentDescription {
+            componentType: self.component_type,
+            componentSubType: self.subtype,
+            componentManufacturer: self.manufacturer,
+            componentFlags: 0,
+            componentFlagsMask: 0,
+        }
+    }
+}
+
+impl std::fmt::Display for AudioUnitComponentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            four_char_string(self.component_type),
+            four_char_string(self.subtype),
+            four_char_string(self.manufacturer)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioUnitInfo {
+    pub id: AudioUnitComponentId,
+
+    pub name: String,
+
+    pub vendor: String,
+
+    pub version: String,
+
+    /// Implemented against the v3 (`AUAudioUnit`) API — an app extension.
+    pub is_v3: bool,
+
+    /// The component supports being loaded into the host process. v3 units
+    /// without this flag only run out-of-process.
+    pub can_load_in_process: bool,
+}
+
+pub struct AudioUnitScanner;
+
+impl AudioUnitScanner {
+    /// Every registered effect component, effects first then music effects,
+    /// each in registry order.
+    pub fn scan_effects() -> Vec<AudioUnitInfo> {
+        let mut plugins = Vec::new();
+        for component_type in EFFECT_TYPES {
+            let query = AudioComponentDescription {
+                componentType: component_type,
+                componentSubType: 0,
+                componentManufacturer: 0,
+                componentFlags: 0,
+                componentFlagsMask: 0,
+            };
+            for component in components_matching(&query) {
+                match Self::component_info(component) {
+                    Ok(info) => plugins.push(info),
+                    Err(e) => tracing::debug!("Failed to describe AudioUnit component: {}", e),
+                }
+            }
+        }
+        plugins
+    }
+
+    /// The registered component with this exact type, subtype and
+    /// manufacturer.
+    pub fn find(id: &AudioUnitComponentId) -> Result<(AudioComponent, AudioUnitInfo)> {
+        let component = components_matching(&id.description())
+            .next()
+            .ok_or_else(|| Error::Configuration(format!("AudioUnit '{}' is not installed", id)))?;
+        Ok((component, Self::component_info(component)?))
+    }
+
+    /// The first effect whose name matches `name` — either the bare unit
+    /// name or the registry's full `Vendor: Name` form.
+    pub fn find_by_name(name: &str) -> Result<(AudioComponent, AudioUnitInfo)> {
+        let mut available = Vec::new();
+        for component_type in EFFECT_TYPES {
+            let id = AudioUnitComponentId {
+                component_type,
+                subtype: 0,
+                manufacturer: 0,
+            };
+            for component in components_matching(&id.description()) {
+                let Ok(info) = Self::component_info(component) else {
+                    continue;
+                };
+                if info.name == name || format!("{}: {}", info.vendor, info.name) == name {
+                    return Ok((component, info));
+                }
+                available.push(info.name);
+            }
+        }
+
+        let available = if available.is_empty() {
+            "none found".to_string()
+        } else {
+            available.join(", ")
+        };
+        Err(Error::Configuration(format!(
+            "AudioUnit '{}' not found. Available effects: [{}]",
+            name, available
+        )))
+    }
+
+    pub(crate) fn component_info(component: AudioComponent) -> Result<AudioUnitInfo> {
+        let mut description = AudioComponentDescription {
+            componentType: 0,
+            componentSubType: 0,
+            componentManufacturer: 0,
+            componentFlags: 0,
+            componentFlagsMask: 0,
+        };
+        // SAFETY: `component` came from AudioComponentFindNext; the out
+        // pointers are to locals.
+        let status =
+            unsafe { AudioComponentGetDescription(component, NonNull::from(&mut description)) };
+        if status != 0 {
+            return Err(Error::Runtime(format!(
+                "AudioComponentGetDescription failed (OSStatus {})",
+                status
+            )));
+        }
+
+        let mut name_ref: *const CFString = std::ptr::null();
+        // SAFETY: as above; the copied name is owned by the caller.
+        let full_name =
+            if unsafe { AudioComponentCopyName(component, NonNull::from(&mut name_ref)) } == 0
+                && let Some(name_ref) = NonNull::new(name_ref.cast_mut())
+            {
+                // SAFETY: the Copy rule — we own this reference.
+                unsafe { CFRetained::from_raw(name_ref) }.to_string()
+            } else {
+                String::new()
+            };
+        let (vendor, name) = split_component_name(&full_name);
+
+        let mut version = 0u32;
+        // SAFETY: as above.
+        let version =
+            if unsafe { AudioComponentGetVersion(component, NonNull::from(&mut version)) } == 0 {
+                format_version(version)
+            } else {
+                String::new()
+            };
+
+        let flags = AudioComponentFlags(description.componentFlags);
+        Ok(AudioUnitInfo {
+            id: AudioUnitComponentId {
+                component_type: description.componentType,
+                subtype: description.componentSubType,
+                manufacturer: description.componentManufacturer,
+            },
+            name,
+            vendor,
+            version,
+            is_v3: flags.contains(AudioComponentFlags::IsV3AudioUnit),
+            can_load_in_process: flags.contains(AudioComponentFlags::CanLoadInProcess),
+        })
+    }
+}
+
+/// Walk the registry for components matching `query` (zero fields are
+/// wildcards).
+fn components_matching(query: &AudioComponentDescription) -> impl Iterator<Item = AudioComponent> {
+    let query = *query;
+    let mut previous: AudioComponent = std::ptr::null_mut();
+    std::iter::from_fn(move || {
+        let mut query = query;
+        // SAFETY: `previous` is null or a component the registry returned.
+        let next = unsafe { AudioComponentFindNext(previous, NonNull::from(&mut query)) };
+        if next.is_null() {
+            None
+        } else {
+            previous = next;
+            Some(next)
+        }
+    })
+}
+
+/// Registry names are `Vendor: Name`; names without a vendor prefix keep
+/// the whole string as the name.
+fn split_component_name(full_name: &str) -> (String, String) {
+    match full_name.split_once(": ") {
+        Some((vendor, name)) => (vendor.trim().to_string(), name.trim().to_string()),
+        None => (String::new(), full_name.trim().to_string()),
+    }
+}
+
+/// Component versions are packed `0xMMMMmmDD` (major, minor, dot).
+fn format_version(version: u32) -> String {
+    format!(
+        "{}.{}.{}",
+        version >> 16,
+        (version >> 8) & 0xff,
+        version & 0xff
+    )
+}
+
+fn four_char_code(code: &str) -> Result<u32> {
+    let bytes = code.as_bytes();
+    if bytes.len() != 4 || !bytes.iter().all(|byte| (0x20..0x7f).contains(byte)) {
+        return Err(Error::Configuration(format!(
+            "'{}' is not a four-character code",
+            code
+        )));
+    }
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn four_char_string(code: u32) -> String {
+    code.to_be_bytes()
+        .iter()
+        .map(|&byte| byte as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_ids_round_trip_through_four_char_codes() {
+        let id = AudioUnitComponentId::parse("aufx  dely appl").unwrap();
+        assert_eq!(id.component_type, kAudioUnitType_Effect);
+        assert_eq!(id.subtype, u32::from_be_bytes(*b"dely"));
+        assert_eq!(id.manufacturer, u32::from_be_bytes(*b"appl"));
+        assert_eq!(id.to_string(), "aufx dely appl");
+
+        assert!(AudioUnitComponentId::parse("aufx dely").is_err());
+        assert!(AudioUnitComponentId::parse("aufx delay appl").is_err());
+    }
+
+    #[test]
+    fn registry_names_split_into_vendor_and_name() {
+        assert_eq!(
+            split_component_name("Apple: AUDelay"),
+            ("Apple".to_string(), "AUDelay".to_string())
+        );
+        assert_eq!(
+            split_component_name("Unprefixed"),
+            (String::new(), "Unprefixed".to_string())
+        );
+    }
+
+    #[test]
+    fn packed_versions_format_as_major_minor_dot() {
+        assert_eq!(format_version(0x0001_0203), "1.2.3");
+        assert_eq!(format_version(0x000A_0000), "10.0.0");
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+pub mod audio_unit_effect;
+pub mod audio_unit_host;
+pub mod audio_unit_scanner;
+
+pub use audio_unit_effect::AudioUnitEffectProcessor;
+pub use audio_unit_host::{AudioUnitHost, AudioUnitInstantiation};
+pub use audio_unit_scanner::{AudioUnitComponentId, AudioUnitInfo, AudioUnitScanner};
@@ -0,0 +1,176 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Parameter automation for [`AudioMixerProcessor`](crate::AudioMixerProcessor),
+//! sampled on the media clock.
+//!
+//! A lane holds keyframes at offsets from its start time, a media-clock
+//! timestamp on the same clock as `AudioFrame::timestamp_ns`. The mixer
+//! samples each lane at every sample's own timestamp, so a fade lands on
+//! the same sample however the audio is chunked. Between two keyframes the
+//! value is interpolated with the *later* keyframe's [`Easing`]; before the
+//! first keyframe the first value holds, and after the last the last value
+//! holds.
+
+/// Timing function of one lane segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Cubic, starting slow.
+    EaseIn,
+    /// Cubic, ending slow.
+    EaseOut,
+    /// Cubic, slow at both ends.
+    EaseInOut,
+    /// Holds the previous value, then jumps at the keyframe.
+    Step,
+}
+
+impl Easing {
+    /// Eased progress for linear progress `t` (0..1).
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            Self::Step => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// A value the lane passes through `offset_ns` after its start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutomationPoint {
+    pub offset_ns: i64,
+    pub value: f32,
+    /// Timing of the segment arriving at this point.
+    pub easing: Easing,
+}
+
+/// One parameter's keyframes, anchored on the media clock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutomationLane {
+    start_ns: i64,
+    points: Vec<AutomationPoint>,
+}
+
+impl AutomationLane {
+    /// A lane through `points` (in any order) starting at media-clock time
+    /// `start_ns`. `None` without points.
+    pub fn new(start_ns: i64, mut points: Vec<AutomationPoint>) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        points.sort_by_key(|point| point.offset_ns);
+        Some(Self { start_ns, points })
+    }
+
+    /// Media-clock time after which the value no longer changes.
+    pub fn end_ns(&self) -> i64 {
+        self.start_ns
+            .saturating_add(self.points.last().map_or(0, |point| point.offset_ns))
+    }
+
+    /// The parameter's value at media-clock time `timestamp_ns`.
+    pub fn value_at(&self, timestamp_ns: i64) -> f32 {
+        let offset_ns = timestamp_ns.saturating_sub(self.start_ns);
+        let next = self
+            .points
+            .partition_point(|point| point.offset_ns <= offset_ns);
+        if next == 0 {
+            return self.points[0].value;
+        }
+        let from = &self.points[next - 1];
+        let Some(to) = self.points.get(next) else {
+            return from.value;
+        };
+        // Strictly before `to`, so a step there hasn't happened yet.
+        if to.easing == Easing::Step {
+            return from.value;
+        }
+        let progress = (offset_ns - from.offset_ns) as f64 / (to.offset_ns - from.offset_ns) as f64;
+        from.value + (to.value - from.value) * to.easing.apply(progress as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: i64 = 1_000_000;
+
+    fn point(at_ms: i64, value: f32, easing: Easing) -> AutomationPoint {
+        AutomationPoint {
+            offset_ns: at_ms * MS,
+            value,
+            easing,
+        }
+    }
+
+    #[test]
+    fn easings_hit_their_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Step,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::Step.apply(0.99), 0.0);
+    }
+
+    #[test]
+    fn lane_interpolates_on_the_media_clock_and_holds_at_both_ends() {
+        let start_ns = 5_000 * MS;
+        let fade = AutomationLane::new(
+            start_ns,
+            vec![
+                point(2000, -20.0, Easing::Linear),
+                point(0, 0.0, Easing::Linear),
+            ],
+        )
+        .unwrap();
+        assert_eq!(fade.end_ns(), 7_000 * MS);
+        assert_eq!(fade.value_at(0), 0.0);
+        assert_eq!(fade.value_at(start_ns), 0.0);
+        assert_eq!(fade.value_at(start_ns + 500 * MS), -5.0);
+        assert_eq!(fade.value_at(start_ns + 1000 * MS), -10.0);
+        assert_eq!(fade.value_at(start_ns + 60_000 * MS), -20.0);
+    }
+
+    #[test]
+    fn step_jumps_at_its_keyframe_and_empty_lanes_are_rejected() {
+        let lane = AutomationLane::new(
+            0,
+            vec![
+                point(100, -1.0, Easing::Linear),
+                point(200, 1.0, Easing::Step),
+            ],
+        )
+        .unwrap();
+        assert_eq!(lane.value_at(50 * MS), -1.0);
+        assert_eq!(lane.value_at(200 * MS - 1), -1.0);
+        assert_eq!(lane.value_at(200 * MS), 1.0);
+        assert!(AutomationLane::new(0, Vec::new()).is_none());
+    }
+}
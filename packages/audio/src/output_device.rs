@@ -0,0 +1,242 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Output device following behind `AudioOutputProcessor`.
+//!
+//! The output thread keeps playing across dock/undock cycles: when its
+//! device goes away it moves to the system default, it follows the default
+//! when that changes, and it returns to a configured device when it comes
+//! back. Each move rebuilds the stream at the new device's native format
+//! (the `ProcessorAudioConverter` feeding it renegotiates on the next frame)
+//! and is published on the [`AUDIO_OUTPUT_DEVICE_CHANGED_TOPIC`]
+//! custom-event topic.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+/// Custom-event topic carrying `{processor_id, reason, previous_device,
+/// device, sample_rate, channels}` every time the output moves device.
+pub const AUDIO_OUTPUT_DEVICE_CHANGED_TOPIC: &str = "audio_output:device_changed";
+
+/// How often the output thread looks at the system's devices.
+pub const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Why the output moved to another device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceChangeReason {
+    /// The device being played on went away (or nothing was open).
+    Lost,
+    /// The system default moved while the output was following it.
+    DefaultChanged,
+    /// The configured device came back after playing on a fallback, or a
+    /// device appeared while none was open.
+    Restored,
+}
+
+impl DeviceChangeReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lost => "lost",
+            Self::DefaultChanged => "default_changed",
+            Self::Restored => "restored",
+        }
+    }
+}
+
+/// Device to open next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceChoice {
+    /// The configured device, by name.
+    Pinned(String),
+    /// Whatever the system default is.
+    Default,
+}
+
+/// What the output thread saw on its latest look at the system's devices.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceSnapshot {
+    /// The device being played on is still there and its stream healthy.
+    pub current_present: bool,
+    /// The configured device is present. Only looked up while it isn't
+    /// the one being played on.
+    pub pinned_present: bool,
+    /// Name of the system default output device.
+    pub default_device: Option<String>,
+}
+
+/// Decides when the output stream has to move device, and where to.
+#[derive(Debug, Default)]
+pub struct OutputDeviceFollower {
+    /// Name of the configured device once first opened; `None` follows
+    /// the system default.
+    pinned: Option<String>,
+    /// Device being played on; `None` while nothing could be opened.
+    current: Option<String>,
+}
+
+impl OutputDeviceFollower {
+    /// Follow the system default.
+    pub fn following_default() -> Self {
+        Self::default()
+    }
+
+    /// Stay on `name` whenever it's present, the default otherwise.
+    pub fn pinned_to(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            current: Some(name.clone()),
+            pinned: Some(name),
+        }
+    }
+
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    pub fn pinned(&self) -> Option<&str> {
+        self.pinned.as_deref()
+    }
+
+    /// Whether the configured device is set but not the one playing — the
+    /// only time the thread needs to look it up.
+    pub fn on_fallback(&self) -> bool {
+        self.pinned.is_some() && self.pinned != self.current
+    }
+
+    /// Where to go on a move: the configured device when present, the
+    /// default otherwise.
+    pub fn choose(&self, snapshot: &DeviceSnapshot) -> DeviceChoice {
+        match &self.pinned {
+            Some(pinned) if snapshot.pinned_present => DeviceChoice::Pinned(pinned.clone()),
+            _ => DeviceChoice::Default,
+        }
+    }
+
+    /// Whether the stream has to move, given the latest snapshot.
+    pub fn check(&self, snapshot: &DeviceSnapshot) -> Option<DeviceChangeReason> {
+        let Some(current) = &self.current else {
+            let reappeared = snapshot.pinned_present || snapshot.default_device.is_some();
+            return reappeared.then_some(DeviceChangeReason::Restored);
+        };
+        if !snapshot.current_present {
+            return Some(DeviceChangeReason::Lost);
+        }
+        if self.on_fallback() && snapshot.pinned_present {
+            return Some(DeviceChangeReason::Restored);
+        }
+        let following_default = self.pinned.is_none() || self.on_fallback();
+        match &snapshot.default_device {
+            Some(default) if following_default && default != current => {
+                Some(DeviceChangeReason::DefaultChanged)
+            }
+            _ => None,
+        }
+    }
+
+    /// Record the device the stream now plays on (`None` when nothing
+    /// could be opened).
+    pub fn opened(&mut self, device: Option<String>) {
+        self.current = device;
+    }
+}
+
+/// Publish a move on [`AUDIO_OUTPUT_DEVICE_CHANGED_TOPIC`].
+pub fn publish_device_changed(
+    processor_id: Option<&str>,
+    reason: DeviceChangeReason,
+    previous_device: Option<&str>,
+    device: Option<&str>,
+    format: Option<(u32, u32)>,
+) {
+    let payload = serde_json::json!({
+        "processor_id": processor_id,
+        "reason": reason.as_str(),
+        "previous_device": previous_device,
+        "device": device,
+        "sample_rate": format.map(|(sample_rate, _)| sample_rate),
+        "channels": format.map(|(_, channels)| channels),
+    });
+    if let Err(e) = publish_custom_event(AUDIO_OUTPUT_DEVICE_CHANGED_TOPIC, &payload) {
+        tracing::debug!("[AudioOutput] Device change not published: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(
+        current_present: bool,
+        pinned_present: bool,
+        default: Option<&str>,
+    ) -> DeviceSnapshot {
+        DeviceSnapshot {
+            current_present,
+            pinned_present,
+            default_device: default.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn following_default_moves_when_the_default_changes() {
+        let mut follower = OutputDeviceFollower::following_default();
+        follower.opened(Some("Speakers".into()));
+
+        assert_eq!(
+            follower.check(&snapshot(true, false, Some("Speakers"))),
+            None
+        );
+        let moved = snapshot(true, false, Some("Dock"));
+        assert_eq!(
+            follower.check(&moved),
+            Some(DeviceChangeReason::DefaultChanged)
+        );
+        assert_eq!(follower.choose(&moved), DeviceChoice::Default);
+    }
+
+    #[test]
+    fn pinned_device_falls_back_to_default_and_returns() {
+        let mut follower = OutputDeviceFollower::pinned_to("Dock");
+        assert!(!follower.on_fallback());
+        // Default moves don't matter while on the configured device.
+        assert_eq!(
+            follower.check(&snapshot(true, false, Some("Speakers"))),
+            None
+        );
+
+        let undocked = snapshot(false, false, Some("Speakers"));
+        assert_eq!(follower.check(&undocked), Some(DeviceChangeReason::Lost));
+        assert_eq!(follower.choose(&undocked), DeviceChoice::Default);
+        follower.opened(Some("Speakers".into()));
+        assert!(follower.on_fallback());
+
+        // On the fallback the default is followed...
+        assert_eq!(
+            follower.check(&snapshot(true, false, Some("Headphones"))),
+            Some(DeviceChangeReason::DefaultChanged)
+        );
+
+        // ...until the configured device is back.
+        let docked = snapshot(true, true, Some("Speakers"));
+        assert_eq!(follower.check(&docked), Some(DeviceChangeReason::Restored));
+        assert_eq!(
+            follower.choose(&docked),
+            DeviceChoice::Pinned("Dock".into())
+        );
+        follower.opened(Some("Dock".into()));
+        assert!(!follower.on_fallback());
+    }
+
+    #[test]
+    fn nothing_open_retries_once_a_device_appears() {
+        let mut follower = OutputDeviceFollower::following_default();
+        follower.opened(None);
+
+        assert_eq!(follower.check(&snapshot(false, false, None)), None);
+        assert_eq!(
+            follower.check(&snapshot(false, false, Some("Speakers"))),
+            Some(DeviceChangeReason::Restored)
+        );
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Format-agnostic plugin hosting surface shared by the CLAP (`@tatolab/clap`),
+//! VST3 (`@tatolab/vst3`) and macOS AudioUnit hosts — plugin and parameter
+//! descriptions, and the parameter automation scheduler with its modulators.
+
+pub mod parameter_automation;
+pub mod parameter_modulation;
+pub mod plugin_info;
+
+pub use parameter_automation::{ParameterAutomation, PluginParameterControl};
+pub use parameter_modulation::{LfoWaveform, ParameterModulator};
+pub use plugin_info::{ParameterInfo, PluginInfo};
@@ -1,11 +1,13 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
-use crate::parameter_modulation::ParameterModulator;
+use super::parameter_modulation::ParameterModulator;
 use std::collections::HashMap;
 use streamlib_plugin_sdk::sdk::error::Result;
 
-pub trait ClapParameterControl {
+/// Parameter writes the automation scheduler drives — implemented by the
+/// CLAP, VST3 and AudioUnit effect processors.
+pub trait PluginParameterControl {
     fn set_parameter(&mut self, id: u32, value: f64) -> Result<()>;
 
     fn begin_edit(&mut self, id: u32) -> Result<()>;
@@ -86,7 +88,7 @@ impl ParameterAutomation {
 
     pub fn update<P>(&mut self, time: f64, processor: &mut P) -> Result<usize>
     where
-        P: ClapParameterControl,
+        P: PluginParameterControl,
     {
         let mut updates = 0;
 
@@ -107,10 +109,10 @@ impl ParameterAutomation {
                 continue;
             }
 
-            if let Some(end_time) = modulator_state.end_time {
-                if time >= end_time {
-                    continue;
-                }
+            if let Some(end_time) = modulator_state.end_time
+                && time >= end_time
+            {
+                continue;
             }
 
             let mod_value = modulator_state.modulator.sample(time);
@@ -169,7 +171,7 @@ impl Default for ParameterAutomation {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parameter_modulation::LfoWaveform;
+    use crate::plugin_host::parameter_modulation::LfoWaveform;
 
     #[allow(dead_code)]
     struct MockProcessor {
@@ -42,7 +42,7 @@ pub struct ProcessorAudioConverter {
 }
 
 /// Stored copy of target format for detecting changes.
-#[allow(dead_code)]
+#[derive(PartialEq)]
 struct StoredTargetFormat {
     sample_rate: Option<u32>,
     channels: Option<u8>,
@@ -80,11 +80,18 @@ impl ProcessorAudioConverter {
         let source_sample_rate = frame.sample_rate;
         let source_channels = frame.channels;
 
-        // Detect source format change — re-initialize everything
+        // Detect source or target format change (e.g. the output device
+        // moved to one with another native rate) — re-initialize everything
         let source_changed = self.last_source_sample_rate != Some(source_sample_rate)
             || self.last_source_channels != Some(source_channels);
-
-        if source_changed {
+        let target_changed = self.last_target.as_ref()
+            != Some(&StoredTargetFormat {
+                sample_rate: target.sample_rate,
+                channels: target.channels,
+                buffer_size: target.buffer_size,
+            });
+
+        if source_changed || target_changed {
             self.resampler = None;
             self.resampler_chunk_size = 0;
             self.pre_resample_buffer.clear();
@@ -172,6 +179,7 @@ impl ProcessorAudioConverter {
                     sample_rate: target_rate,
                     timestamp_ns: after_channels.timestamp_ns.clone(),
                     frame_index: after_channels.frame_index.clone(),
+                    channel_layout: after_channels.channel_layout.clone(),
                 });
             }
 
@@ -215,6 +223,7 @@ impl ProcessorAudioConverter {
                         sample_rate: resampled.sample_rate,
                         timestamp_ns: resampled.timestamp_ns.clone(),
                         frame_index: resampled.frame_index.clone(),
+                        channel_layout: resampled.channel_layout.clone(),
                     };
                     while let Some(f) = rechunker.process(&empty) {
                         frames.push(f);
@@ -256,3 +265,46 @@ impl Default for ProcessorAudioConverter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(sample_rate: u32, frames: usize) -> AudioFrame {
+        AudioFrame {
+            samples: vec![0.0; frames * 2],
+            channels: 2,
+            sample_rate,
+            timestamp_ns: "0".to_string(),
+            frame_index: "0".to_string(),
+            channel_layout: None,
+        }
+    }
+
+    fn target(sample_rate: u32) -> ProcessorAudioConverterTargetFormat {
+        ProcessorAudioConverterTargetFormat {
+            sample_rate: Some(sample_rate),
+            channels: Some(2),
+            buffer_size: Some(480),
+        }
+    }
+
+    #[test]
+    fn test_target_rate_change_renegotiates() {
+        let mut converter = ProcessorAudioConverter::new();
+        let mut out = Vec::new();
+        for _ in 0..8 {
+            out.extend(converter.convert(&frame(48000, 480), &target(48000)).unwrap());
+        }
+        assert!(out.iter().all(|f| f.sample_rate == 48000 && f.samples.len() == 960));
+
+        // The output device moved to a 44.1kHz one.
+        let mut out = Vec::new();
+        for _ in 0..8 {
+            out.extend(converter.convert(&frame(48000, 480), &target(44100)).unwrap());
+        }
+        assert!(!out.is_empty());
+        assert!(out.iter().all(|f| f.sample_rate == 44100 && f.samples.len() == 960));
+        assert!(converter.status_arc().lock().unwrap().is_resampling);
+    }
+}
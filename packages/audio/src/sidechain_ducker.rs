@@ -0,0 +1,182 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Sidechain ducking — turn a mix down while a key signal is active, the
+//! "music under the mic" effect.
+//!
+//! The key is tracked by a peak envelope. Ducking engages while the
+//! envelope is above the threshold and for `hold` after it falls below,
+//! which bridges the gaps between words; the duck gain moves towards its
+//! target with one-pole smoothing at the attack time going down and the
+//! release time coming back up.
+
+/// Decay time constant of the key's peak envelope.
+const ENVELOPE_DECAY_MS: f32 = 20.0;
+
+/// Resolved ducking settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuckingSettings {
+    /// Key peak level that engages ducking, in dBFS.
+    pub threshold_db: f32,
+    /// Gain reduction while ducked, in dB (positive).
+    pub depth_db: f32,
+    pub attack_ms: f32,
+    pub hold_ms: f32,
+    pub release_ms: f32,
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self {
+            threshold_db: -40.0,
+            depth_db: 12.0,
+            attack_ms: 10.0,
+            hold_ms: 300.0,
+            release_ms: 500.0,
+        }
+    }
+}
+
+/// Per-sample coefficient of a one-pole filter with time constant `ms`;
+/// 1 (no smoothing) for zero or negative times.
+fn one_pole_coefficient(ms: f32, sample_rate: u32) -> f32 {
+    let samples = ms * sample_rate as f32 / 1000.0;
+    if samples <= 1.0 {
+        1.0
+    } else {
+        1.0 - (-1.0 / samples).exp()
+    }
+}
+
+pub fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Gain computer for the ducked inputs, fed one key sample at a time.
+#[derive(Debug, Clone)]
+pub struct SidechainDucker {
+    threshold: f32,
+    ducked_gain: f32,
+    attack: f32,
+    release: f32,
+    envelope_decay: f32,
+    hold_samples: u32,
+    envelope: f32,
+    hold_remaining: u32,
+    gain: f32,
+}
+
+impl SidechainDucker {
+    pub fn new(settings: &DuckingSettings, sample_rate: u32) -> Self {
+        let mut ducker = Self {
+            threshold: 0.0,
+            ducked_gain: 1.0,
+            attack: 1.0,
+            release: 1.0,
+            envelope_decay: 1.0 - one_pole_coefficient(ENVELOPE_DECAY_MS, sample_rate),
+            hold_samples: 0,
+            envelope: 0.0,
+            hold_remaining: 0,
+            gain: 1.0,
+        };
+        ducker.update(settings, sample_rate);
+        ducker
+    }
+
+    /// Apply new settings, keeping the current envelope and gain so a live
+    /// change doesn't jump.
+    pub fn update(&mut self, settings: &DuckingSettings, sample_rate: u32) {
+        self.threshold = db_to_linear(settings.threshold_db);
+        self.ducked_gain = db_to_linear(-settings.depth_db.abs());
+        self.attack = one_pole_coefficient(settings.attack_ms, sample_rate);
+        self.release = one_pole_coefficient(settings.release_ms, sample_rate);
+        self.hold_samples = (settings.hold_ms.max(0.0) * sample_rate as f32 / 1000.0) as u32;
+    }
+
+    /// Advance by one key sample; returns the gain for the ducked inputs.
+    pub fn process(&mut self, key: f32) -> f32 {
+        self.envelope = key.abs().max(self.envelope * self.envelope_decay);
+        if self.envelope >= self.threshold {
+            self.hold_remaining = self.hold_samples;
+        }
+        let active = self.envelope >= self.threshold || self.hold_remaining > 0;
+        self.hold_remaining = self.hold_remaining.saturating_sub(1);
+
+        let target = if active { self.ducked_gain } else { 1.0 };
+        let coefficient = if target < self.gain {
+            self.attack
+        } else {
+            self.release
+        };
+        self.gain += (target - self.gain) * coefficient;
+        self.gain
+    }
+
+    /// Current gain reduction in dB (0 when not ducking).
+    pub fn reduction_db(&self) -> f32 {
+        -20.0 * self.gain.max(f32::MIN_POSITIVE).log10()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    fn run(ducker: &mut SidechainDucker, key: f32, ms: u32) -> f32 {
+        let mut gain = 1.0;
+        for _ in 0..ms * RATE / 1000 {
+            gain = ducker.process(key);
+        }
+        gain
+    }
+
+    #[test]
+    fn ducks_while_the_key_is_active_then_holds_and_releases() {
+        let settings = DuckingSettings::default();
+        let mut ducker = SidechainDucker::new(&settings, RATE);
+        // Silence leaves the mix alone.
+        assert_eq!(run(&mut ducker, 0.0, 100), 1.0);
+
+        // Speech at -20 dBFS: fully ducked well within 10 attack constants.
+        run(&mut ducker, 0.1, 100);
+        assert!(
+            (ducker.reduction_db() - 12.0).abs() < 0.1,
+            "{}",
+            ducker.reduction_db()
+        );
+
+        // A pause shorter than the hold keeps the duck.
+        run(&mut ducker, 0.0, 200);
+        assert!(ducker.reduction_db() > 11.9, "{}", ducker.reduction_db());
+
+        // After hold + several release constants it's back up.
+        run(&mut ducker, 0.0, 3000);
+        assert!(ducker.reduction_db() < 0.1, "{}", ducker.reduction_db());
+    }
+
+    #[test]
+    fn key_below_threshold_never_ducks() {
+        let mut ducker = SidechainDucker::new(&DuckingSettings::default(), RATE);
+        // -46 dBFS hum under a -40 dBFS threshold.
+        assert_eq!(run(&mut ducker, 0.005, 500), 1.0);
+    }
+
+    #[test]
+    fn live_updates_keep_the_current_gain() {
+        let mut ducker = SidechainDucker::new(&DuckingSettings::default(), RATE);
+        run(&mut ducker, 0.5, 200);
+        let before = ducker.reduction_db();
+        ducker.update(
+            &DuckingSettings {
+                depth_db: 24.0,
+                ..DuckingSettings::default()
+            },
+            RATE,
+        );
+        assert_eq!(ducker.reduction_db(), before);
+        run(&mut ducker, 0.5, 200);
+        assert!((ducker.reduction_db() - 24.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! SpatialAudio — binaural rendering of positioned sources or an ambisonic
+//! sound field for headphones (see [`crate::binaural`]). Source positions
+//! and head orientation arrive as `SpatialPositions` data frames and take
+//! effect at their media-clock time, not when they are received.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::_generated_::{AudioFrame, SpatialPositions};
+use crate::binaural::{
+    BinauralRenderer, DEFAULT_HEAD_RADIUS_M, HeadOrientation, SourcePosition, default_sources,
+};
+use crate::channel_layout::{ChannelLayout, frame_layout};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const DEFAULT_REFERENCE_DISTANCE_M: f32 = 1.0;
+
+/// Updates held for media-clock times not reached yet. Past this the oldest
+/// apply early rather than pile up while audio is stalled.
+const MAX_PENDING_UPDATES: usize = 256;
+
+/// Sample rate, channel count and layout a renderer was built for.
+type RenderFormat = (u32, u8, Option<ChannelLayout>);
+
+fn parse_timestamp(timestamp_ns: &str, what: &str) -> Result<i64> {
+    timestamp_ns.parse().map_err(|_| {
+        Error::Runtime(format!(
+            "SpatialAudio: {} timestamp_ns must be an integer, got {:?}",
+            what, timestamp_ns
+        ))
+    })
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/audio/SpatialAudio",
+    description = "Renders positioned sources or an ambisonic sound field to binaural stereo through a spherical-head HRTF, following source positions and head orientation from SpatialPositions frames on the media clock",
+    execution = reactive,
+    scheduling = realtime,
+    config = crate::_generated_::SpatialAudioConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "One source per channel (1-16; stereo and surround start at their speaker angles), or AmbiX ambisonics up to third order"),
+    input("positions", "@tatolab/audio/SpatialPositions", optional = true, description = "Source positions and head orientation, applied at their media-clock time"),
+    output("audio_out", "@tatolab/core/AudioFrame", description = "Binaural stereo for headphones"),
+)]
+pub struct SpatialAudioProcessor {
+    renderer: Option<(RenderFormat, BinauralRenderer)>,
+    /// Updates not due yet, in media-clock order.
+    pending: VecDeque<(i64, SpatialPositions)>,
+    /// Positions sources have been moved to, by channel.
+    placed: HashMap<u8, SourcePosition>,
+    head: HeadOrientation,
+    frame_counter: u64,
+}
+
+impl SpatialAudioProcessor::Processor {
+    fn queue(&mut self, update: SpatialPositions) -> Result<()> {
+        let at = parse_timestamp(&update.timestamp_ns, "SpatialPositions")?;
+        let index = self
+            .pending
+            .partition_point(|(pending_at, _)| *pending_at <= at);
+        self.pending.insert(index, (at, update));
+        while self.pending.len() > MAX_PENDING_UPDATES {
+            if let Some((_, update)) = self.pending.pop_front() {
+                self.apply(update);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, update: SpatialPositions) {
+        for source in update.sources.unwrap_or_default() {
+            self.placed.insert(
+                source.channel,
+                SourcePosition {
+                    azimuth_deg: source.azimuth_deg,
+                    elevation_deg: source.elevation_deg,
+                    distance_m: source.distance_m,
+                },
+            );
+        }
+        if let Some(listener) = update.listener {
+            self.head = HeadOrientation {
+                yaw_deg: listener.yaw_deg,
+                pitch_deg: listener.pitch_deg,
+                roll_deg: listener.roll_deg,
+            };
+        }
+    }
+
+    fn render(&mut self, frame: &AudioFrame) -> Result<AudioFrame> {
+        let layout = frame_layout(frame)?;
+        let frames = frame.samples.len() / frame.channels.max(1) as usize;
+
+        // Everything due before this frame ends; the renderer glides to it
+        // across the frame.
+        let start_ns = parse_timestamp(&frame.timestamp_ns, "AudioFrame")?;
+        let end_ns = start_ns + (frames as i64 * 1_000_000_000) / frame.sample_rate.max(1) as i64;
+        while self.pending.front().is_some_and(|(at, _)| *at < end_ns) {
+            if let Some((_, update)) = self.pending.pop_front() {
+                self.apply(update);
+            }
+        }
+
+        let format = (frame.sample_rate, frame.channels, layout.clone());
+        if !matches!(&self.renderer, Some((current, _)) if *current == format) {
+            let renderer = BinauralRenderer::new(
+                frame.sample_rate,
+                frame.channels,
+                layout.as_ref(),
+                self.config.head_radius_m.unwrap_or(DEFAULT_HEAD_RADIUS_M),
+                self.config
+                    .reference_distance_m
+                    .unwrap_or(DEFAULT_REFERENCE_DISTANCE_M),
+            );
+            tracing::info!(
+                "[SpatialAudio] Rendering {} channels ({:?}) at {}Hz as {}",
+                frame.channels,
+                layout,
+                frame.sample_rate,
+                if renderer.is_ambisonic() {
+                    "an ambisonic sound field"
+                } else {
+                    "sources"
+                }
+            );
+            self.renderer = Some((format, renderer));
+        }
+        let Some((_, renderer)) = self.renderer.as_mut() else {
+            return Err(Error::Runtime(
+                "SpatialAudio: renderer not initialized".into(),
+            ));
+        };
+
+        let mut sources = default_sources(frame.channels, layout.as_ref());
+        for (channel, position) in &self.placed {
+            if let Some(source) = sources.get_mut(*channel as usize) {
+                *source = *position;
+            }
+        }
+
+        Ok(AudioFrame {
+            samples: renderer.render(&frame.samples, &sources, &self.head),
+            channels: 2,
+            sample_rate: frame.sample_rate,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            frame_index: self.frame_counter.to_string(),
+            channel_layout: Some(ChannelLayout::Stereo),
+        })
+    }
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SpatialAudioProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[SpatialAudio] setup() - head radius {:?} m, reference distance {:?} m",
+            self.config.head_radius_m,
+            self.config.reference_distance_m
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[SpatialAudio] Stopped (rendered {} frames)",
+            self.frame_counter
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        // Rebuilt with the new head on the next frame.
+        self.renderer = None;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("positions") {
+            let update: SpatialPositions = self.inputs.read("positions")?;
+            self.queue(update)?;
+        }
+
+        if !self.inputs.has_data("audio_in") {
+            return Ok(());
+        }
+
+        let input_frame: AudioFrame = self.inputs.read("audio_in")?;
+        let output_frame = self.render(&input_frame)?;
+
+        self.outputs.write("audio_out", &output_frame)?;
+        self.frame_counter += 1;
+
+        Ok(())
+    }
+}
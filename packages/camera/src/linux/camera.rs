@@ -13,6 +13,7 @@ use streamlib_plugin_sdk::sdk::color::{
 use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContextFullAccess};
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
 use streamlib_plugin_sdk::sdk::rhi::{
     HostTimelineSemaphore, ImageCopyRegion, PixelFormat, RhiColorConverter, RhiCommandRecorder,
     SourceLayoutInfo, StorageBuffer, Texture, TextureFormat, VulkanAccess, VulkanLayout,
@@ -24,6 +25,8 @@ use v4l::io::traits::CaptureStream;
 use v4l::video::Capture;
 use v4l::FourCC;
 
+use super::controls::{CameraControlDevice, CAMERA_CONTROLS_TOPIC};
+
 /// Number of ring textures for GPU-resident pipeline (matches MAX_FRAMES_IN_FLIGHT).
 const RING_TEXTURE_COUNT: usize = 2;
 
@@ -50,11 +53,14 @@ pub struct LinuxCameraProcessor {
     is_capturing: Arc<AtomicBool>,
     frame_counter: Arc<AtomicU64>,
     capture_thread_handle: Option<std::thread::JoinHandle<()>>,
+    processor_id: Option<String>,
+    controls: Option<CameraControlDevice>,
 }
 
 impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for LinuxCameraProcessor::Processor {
     fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
         self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.processor_id = ctx.processor_id();
         tracing::info!("Camera: setup() complete");
         Ok(())
     }
@@ -346,6 +352,18 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for LinuxCameraProce
 
         self.capture_thread_handle = Some(handle);
 
+        // Image controls are best-effort at start: a camera without, say,
+        // manual focus still streams. Config updates surface the error.
+        match CameraControlDevice::open(&device_path) {
+            Ok(controls) => {
+                self.controls = Some(controls);
+                if let Err(e) = self.apply_controls() {
+                    tracing::warn!("Camera {}: {}", self.camera_name, e);
+                }
+            }
+            Err(e) => tracing::warn!("Camera {}: {}", self.camera_name, e),
+        }
+
         tracing::info!(
             "Camera {}: V4L2 capture started ({}x{} {:?}, {} mmap buffers)",
             self.camera_name,
@@ -357,8 +375,13 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for LinuxCameraProce
         Ok(())
     }
 
+    fn on_config_update(&mut self) -> Result<()> {
+        self.apply_controls()
+    }
+
     fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
         self.is_capturing.store(false, Ordering::Release);
+        self.controls = None;
 
         // Bounded wait — same shape as `LinuxDisplayProcessor::stop`. The
         // capture thread can be inside a long timeline wait or a V4L2 dequeue
@@ -469,17 +492,18 @@ fn capture_thread_loop(
         }
     };
 
-    // Query V4L2 format once at processor start. We need three things
+    // Query V4L2 format once at processor start. We need four things
     // from this: (1) the colorspace 4-tuple for `ColorInfo`,
     // (2) `bytesperline` for the source SSBO stride (vivid + some UVC
     // drivers report stride > width even for NV12), (3) `sizeimage`
     // for the SSBO allocation (must hold the full V4L2 frame including
-    // padding). V4L2 contract is that all three stay constant during
-    // streaming.
-    let (cached_color_info, v4l2_bytes_per_line, v4l2_size_image): (
+    // padding), (4) the field layout for `VideoFrame.field_order`. V4L2
+    // contract is that all four stay constant during streaming.
+    let (cached_color_info, v4l2_bytes_per_line, v4l2_size_image, cached_field_order): (
         crate::_generated_::ColorInfo,
         u32,
         u32,
+        Option<crate::_generated_::tatolab__core::video_frame::FieldOrder>,
     ) = unsafe {
         let mut v4l2_fmt: v4l::v4l_sys::v4l2_format = std::mem::zeroed();
         v4l2_fmt.type_ = v4l::buffer::Type::VideoCapture as u32;
@@ -508,7 +532,9 @@ fn capture_thread_loop(
                 pix.__bindgen_anon_1.ycbcr_enc,
                 pix.quantization,
             );
-            (color, pix.bytesperline, pix.sizeimage)
+            let field_order =
+                crate::linux::v4l2_field::v4l2_field_to_field_order(pix.field, pix.height);
+            (color, pix.bytesperline, pix.sizeimage, field_order)
         } else {
             // ioctl failed — emit "all unknown" colors and fall back
             // to tight-packed buffer sizing. `ColorInfo::default()` is
@@ -529,6 +555,7 @@ fn capture_thread_loop(
                 crate::_generated_::ColorInfo::default(),
                 tight_bytes_per_line,
                 tight_size_image,
+                None,
             )
         }
     };
@@ -1235,6 +1262,7 @@ fn capture_thread_loop(
             // populated by HDR-aware sources only.
             mastering_display: None,
             content_light: None,
+            field_order: cached_field_order.clone(),
         };
 
         if let Err(e) = outputs.write("video", &ipc_frame) {
@@ -1343,6 +1371,32 @@ impl LinuxCameraProcessor::Processor {
 
         Ok(devices)
     }
+
+    /// Apply `config.controls` to the open device, then publish the
+    /// resulting descriptors on [`CAMERA_CONTROLS_TOPIC`]. A no-op until
+    /// `start()` has opened the device — `start()` applies them itself.
+    fn apply_controls(&self) -> Result<()> {
+        let Some(device) = &self.controls else {
+            return Ok(());
+        };
+        let result = match &self.config.controls {
+            Some(controls) => device.apply(controls),
+            None => Ok(()),
+        };
+
+        // Published even after a failed apply: the descriptors show the
+        // values the device actually ended up with.
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "device": device.device_path(),
+            "camera_name": self.camera_name,
+            "controls": device.query(),
+        });
+        if let Err(e) = publish_custom_event(CAMERA_CONTROLS_TOPIC, &payload) {
+            tracing::debug!("Camera {}: controls not published: {}", self.camera_name, e);
+        }
+        result
+    }
 }
 
 /// Per-axis maps from this package's `_generated_::ColorInfo` enums to the
@@ -1439,6 +1493,7 @@ mod tests {
             max_fps: None,
             max_width: None,
             max_height: None,
+            controls: None,
         };
 
         let result = LinuxCameraProcessor::Processor::from_config(config);
@@ -0,0 +1,361 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! V4L2 image controls — exposure, gain, white balance and focus.
+//!
+//! Controls go through their own device handle: V4L2 accepts control
+//! ioctls on any open fd of the device, including while another fd is
+//! streaming, so config updates never touch the capture thread.
+
+use std::io;
+
+use serde::Serialize;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::CameraControls;
+
+/// Custom-event topic the camera publishes its control descriptors on,
+/// after start and after every config update.
+pub const CAMERA_CONTROLS_TOPIC: &str = "camera:controls";
+
+// Control ids and values from <linux/v4l2-controls.h>.
+const V4L2_CID_BASE: u32 = 0x0098_0900;
+const V4L2_CID_CAMERA_CLASS_BASE: u32 = 0x009a_0900;
+const V4L2_CID_AUTO_WHITE_BALANCE: u32 = V4L2_CID_BASE + 12;
+const V4L2_CID_GAIN: u32 = V4L2_CID_BASE + 19;
+const V4L2_CID_WHITE_BALANCE_TEMPERATURE: u32 = V4L2_CID_BASE + 26;
+const V4L2_CID_EXPOSURE_AUTO: u32 = V4L2_CID_CAMERA_CLASS_BASE + 1;
+const V4L2_CID_EXPOSURE_ABSOLUTE: u32 = V4L2_CID_CAMERA_CLASS_BASE + 2;
+const V4L2_CID_FOCUS_ABSOLUTE: u32 = V4L2_CID_CAMERA_CLASS_BASE + 10;
+const V4L2_CID_FOCUS_AUTO: u32 = V4L2_CID_CAMERA_CLASS_BASE + 12;
+
+const V4L2_EXPOSURE_AUTO: i32 = 0;
+const V4L2_EXPOSURE_MANUAL: i32 = 1;
+const V4L2_EXPOSURE_APERTURE_PRIORITY: i32 = 3;
+
+const V4L2_CTRL_FLAG_DISABLED: u32 = 0x0001;
+const V4L2_CTRL_FLAG_READ_ONLY: u32 = 0x0004;
+const V4L2_CTRL_FLAG_INACTIVE: u32 = 0x0010;
+
+/// V4L2 exposure is in 100 µs units; the config and descriptors use µs.
+const EXPOSURE_UNIT_US: i64 = 100;
+
+/// One settable control. Serializes as the matching [`CameraControls`]
+/// field name so a UI can map a descriptor straight onto the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CameraControl {
+    ExposureAuto,
+    ExposureTimeUs,
+    Gain,
+    WhiteBalanceAuto,
+    WhiteBalanceTemperature,
+    FocusAuto,
+    FocusAbsolute,
+}
+
+impl CameraControl {
+    /// Every control, each auto toggle ahead of the manual value it gates —
+    /// the order [`CameraControlDevice::apply`] sets them in.
+    pub const ALL: [Self; 7] = [
+        Self::ExposureAuto,
+        Self::ExposureTimeUs,
+        Self::Gain,
+        Self::WhiteBalanceAuto,
+        Self::WhiteBalanceTemperature,
+        Self::FocusAuto,
+        Self::FocusAbsolute,
+    ];
+
+    fn cid(self) -> u32 {
+        match self {
+            Self::ExposureAuto => V4L2_CID_EXPOSURE_AUTO,
+            Self::ExposureTimeUs => V4L2_CID_EXPOSURE_ABSOLUTE,
+            Self::Gain => V4L2_CID_GAIN,
+            Self::WhiteBalanceAuto => V4L2_CID_AUTO_WHITE_BALANCE,
+            Self::WhiteBalanceTemperature => V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+            Self::FocusAuto => V4L2_CID_FOCUS_AUTO,
+            Self::FocusAbsolute => V4L2_CID_FOCUS_ABSOLUTE,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ExposureAuto => "exposure_auto",
+            Self::ExposureTimeUs => "exposure_time_us",
+            Self::Gain => "gain",
+            Self::WhiteBalanceAuto => "white_balance_auto",
+            Self::WhiteBalanceTemperature => "white_balance_temperature",
+            Self::FocusAuto => "focus_auto",
+            Self::FocusAbsolute => "focus_absolute",
+        }
+    }
+
+    /// The value `controls` requests for this control, booleans as 0 / 1.
+    fn requested(self, controls: &CameraControls) -> Option<i64> {
+        match self {
+            Self::ExposureAuto => controls.exposure_auto.map(i64::from),
+            Self::ExposureTimeUs => controls.exposure_time_us.map(i64::from),
+            Self::Gain => controls.gain.map(i64::from),
+            Self::WhiteBalanceAuto => controls.white_balance_auto.map(i64::from),
+            Self::WhiteBalanceTemperature => controls.white_balance_temperature.map(i64::from),
+            Self::FocusAuto => controls.focus_auto.map(i64::from),
+            Self::FocusAbsolute => controls.focus_absolute.map(i64::from),
+        }
+    }
+
+    /// Convert a raw driver value into the units the config uses. The
+    /// exposure menu collapses to 0 (manual) / 1 (any automatic mode).
+    fn from_device(self, raw: i64) -> i64 {
+        match self {
+            Self::ExposureAuto => i64::from(raw != i64::from(V4L2_EXPOSURE_MANUAL)),
+            Self::ExposureTimeUs => raw * EXPOSURE_UNIT_US,
+            _ => raw,
+        }
+    }
+}
+
+/// A control the device supports, with its range and current value in
+/// config units.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CameraControlInfo {
+    pub control: CameraControl,
+    pub minimum: i64,
+    pub maximum: i64,
+    pub step: i64,
+    pub default: i64,
+    pub value: i64,
+    pub read_only: bool,
+    /// The driver ignores writes right now — typically a manual value
+    /// while its auto toggle is on.
+    pub inactive: bool,
+}
+
+/// Control handle on a V4L2 capture device.
+pub struct CameraControlDevice {
+    device: v4l::Device,
+    device_path: String,
+}
+
+impl CameraControlDevice {
+    pub fn open(device_path: &str) -> Result<Self> {
+        let device = v4l::Device::with_path(device_path).map_err(|e| {
+            Error::Configuration(format!(
+                "Failed to open V4L2 device '{}' for controls: {}",
+                device_path, e
+            ))
+        })?;
+        Ok(Self {
+            device,
+            device_path: device_path.to_string(),
+        })
+    }
+
+    pub fn device_path(&self) -> &str {
+        &self.device_path
+    }
+
+    /// Descriptors for every [`CameraControl`] the device supports.
+    pub fn query(&self) -> Vec<CameraControlInfo> {
+        CameraControl::ALL
+            .into_iter()
+            .filter_map(|control| {
+                let query = self.query_ctrl(control.cid()).ok()?;
+                if query.flags & V4L2_CTRL_FLAG_DISABLED != 0 {
+                    return None;
+                }
+                let value = self.get_ctrl(control.cid()).ok()?;
+                let (minimum, maximum, step) = match control {
+                    // Presented as a boolean, whatever menu entries the driver has.
+                    CameraControl::ExposureAuto => (0, 1, 1),
+                    _ => (
+                        control.from_device(query.minimum.into()),
+                        control.from_device(query.maximum.into()),
+                        control.from_device(query.step.into()),
+                    ),
+                };
+                Some(CameraControlInfo {
+                    control,
+                    minimum,
+                    maximum,
+                    step,
+                    default: control.from_device(query.default_value.into()),
+                    value: control.from_device(value.into()),
+                    read_only: query.flags & V4L2_CTRL_FLAG_READ_ONLY != 0,
+                    inactive: query.flags & V4L2_CTRL_FLAG_INACTIVE != 0,
+                })
+            })
+            .collect()
+    }
+
+    /// Set every control `controls` specifies, auto toggles first. A
+    /// control that fails doesn't stop the rest; the failures are
+    /// reported together.
+    pub fn apply(&self, controls: &CameraControls) -> Result<()> {
+        let failures: Vec<String> = CameraControl::ALL
+            .into_iter()
+            .filter_map(|control| Some((control, control.requested(controls)?)))
+            .filter_map(|(control, value)| {
+                self.set(control, value)
+                    .err()
+                    .map(|e| format!("{}: {}", control.name(), e))
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Configuration(format!(
+                "Failed to set camera controls on '{}': {}",
+                self.device_path,
+                failures.join("; ")
+            )))
+        }
+    }
+
+    fn set(&self, control: CameraControl, value: i64) -> io::Result<()> {
+        let cid = control.cid();
+        match control {
+            // UVC cameras usually offer only manual and aperture priority;
+            // fall back to full auto for drivers that don't.
+            CameraControl::ExposureAuto if value != 0 => self
+                .set_ctrl(cid, V4L2_EXPOSURE_APERTURE_PRIORITY)
+                .or_else(|_| self.set_ctrl(cid, V4L2_EXPOSURE_AUTO)),
+            CameraControl::ExposureAuto => self.set_ctrl(cid, V4L2_EXPOSURE_MANUAL),
+            _ => self.set_ctrl(cid, to_device(control, value)?),
+        }
+    }
+
+    fn query_ctrl(&self, id: u32) -> io::Result<v4l::v4l_sys::v4l2_queryctrl> {
+        // SAFETY: v4l2_queryctrl is a plain C struct; all-zero is valid.
+        let mut query: v4l::v4l_sys::v4l2_queryctrl = unsafe { std::mem::zeroed() };
+        query.id = id;
+        // SAFETY: the fd is owned by `self.device` and stays open for the
+        // call; VIDIOC_QUERYCTRL writes only into `query`.
+        let result = unsafe {
+            libc::ioctl(
+                self.device.handle().fd(),
+                v4l::v4l2::vidioc::VIDIOC_QUERYCTRL as libc::c_ulong,
+                &mut query,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(query)
+    }
+
+    fn get_ctrl(&self, id: u32) -> io::Result<i32> {
+        let mut ctrl = v4l::v4l_sys::v4l2_control { id, value: 0 };
+        // SAFETY: see `query_ctrl`; VIDIOC_G_CTRL writes only into `ctrl`.
+        let result = unsafe {
+            libc::ioctl(
+                self.device.handle().fd(),
+                v4l::v4l2::vidioc::VIDIOC_G_CTRL as libc::c_ulong,
+                &mut ctrl,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(ctrl.value)
+    }
+
+    fn set_ctrl(&self, id: u32, value: i32) -> io::Result<()> {
+        let mut ctrl = v4l::v4l_sys::v4l2_control { id, value };
+        // SAFETY: see `query_ctrl`; VIDIOC_S_CTRL reads `ctrl` and writes
+        // back the value the driver clamped it to.
+        let result = unsafe {
+            libc::ioctl(
+                self.device.handle().fd(),
+                v4l::v4l2::vidioc::VIDIOC_S_CTRL as libc::c_ulong,
+                &mut ctrl,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+/// Convert a config value into the driver's units, rounding exposure to
+/// the nearest 100 µs.
+fn to_device(control: CameraControl, value: i64) -> io::Result<i32> {
+    let raw = match control {
+        CameraControl::ExposureTimeUs => (value + EXPOSURE_UNIT_US / 2) / EXPOSURE_UNIT_US,
+        _ => value,
+    };
+    i32::try_from(raw).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is out of range", value),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_names_match_the_config_fields() {
+        for control in CameraControl::ALL {
+            assert_eq!(
+                serde_json::to_value(control).unwrap(),
+                serde_json::Value::from(control.name())
+            );
+        }
+    }
+
+    #[test]
+    fn exposure_converts_between_microseconds_and_100us_units() {
+        assert_eq!(
+            to_device(CameraControl::ExposureTimeUs, 10_000).unwrap(),
+            100
+        );
+        assert_eq!(to_device(CameraControl::ExposureTimeUs, 149).unwrap(), 1);
+        assert_eq!(to_device(CameraControl::ExposureTimeUs, 150).unwrap(), 2);
+        assert_eq!(CameraControl::ExposureTimeUs.from_device(100), 10_000);
+        assert_eq!(to_device(CameraControl::Gain, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn exposure_menu_reads_back_as_a_boolean() {
+        let auto = CameraControl::ExposureAuto;
+        assert_eq!(auto.from_device(V4L2_EXPOSURE_MANUAL.into()), 0);
+        assert_eq!(auto.from_device(V4L2_EXPOSURE_AUTO.into()), 1);
+        assert_eq!(auto.from_device(V4L2_EXPOSURE_APERTURE_PRIORITY.into()), 1);
+    }
+
+    #[test]
+    fn requested_controls_set_auto_toggles_before_manual_values() {
+        let controls = CameraControls {
+            exposure_time_us: Some(5_000),
+            exposure_auto: Some(false),
+            focus_absolute: Some(40),
+            focus_auto: Some(false),
+            ..Default::default()
+        };
+
+        let order: Vec<CameraControl> = CameraControl::ALL
+            .into_iter()
+            .filter(|control| control.requested(&controls).is_some())
+            .collect();
+
+        assert_eq!(
+            order,
+            [
+                CameraControl::ExposureAuto,
+                CameraControl::ExposureTimeUs,
+                CameraControl::FocusAuto,
+                CameraControl::FocusAbsolute,
+            ]
+        );
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected_before_the_ioctl() {
+        let error = to_device(CameraControl::Gain, i64::from(i32::MAX) + 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 pub mod camera;
+pub mod controls;
 pub mod v4l2_color;
+pub mod v4l2_field;
 
 pub use camera::{LinuxCameraDevice, LinuxCameraProcessor};
+pub use controls::{CAMERA_CONTROLS_TOPIC, CameraControl, CameraControlDevice, CameraControlInfo};
@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! V4L2 `field` → `VideoFrame.field_order` translation.
+//!
+//! Only layouts that deliver both fields woven into one buffer map to an
+//! interlaced order. Single-field, sequential and alternating layouts are
+//! not something downstream processors can deinterlace from one frame,
+//! so they — like `V4L2_FIELD_ANY` — propagate as `None` (unknown).
+
+use crate::_generated_::tatolab__core::video_frame::FieldOrder;
+
+// V4L2 `field` enumerants (from `<linux/videodev2.h>`).
+const V4L2_FIELD_NONE: u32 = 1;
+const V4L2_FIELD_INTERLACED: u32 = 4;
+const V4L2_FIELD_INTERLACED_TB: u32 = 8;
+const V4L2_FIELD_INTERLACED_BT: u32 = 9;
+
+/// Frame heights of the 525-line standards, whose `V4L2_FIELD_INTERLACED`
+/// buffers carry the bottom field first.
+const NTSC_HEIGHTS: [u32; 2] = [480, 486];
+
+/// Translate the negotiated `v4l2_pix_format.field`. Plain
+/// `V4L2_FIELD_INTERLACED` leaves the temporal order to the video
+/// standard — bottom first for NTSC, top first otherwise — which is
+/// resolved from the frame height.
+pub fn v4l2_field_to_field_order(field: u32, height: u32) -> Option<FieldOrder> {
+    match field {
+        V4L2_FIELD_NONE => Some(FieldOrder::Progressive),
+        V4L2_FIELD_INTERLACED_TB => Some(FieldOrder::TopFieldFirst),
+        V4L2_FIELD_INTERLACED_BT => Some(FieldOrder::BottomFieldFirst),
+        V4L2_FIELD_INTERLACED if NTSC_HEIGHTS.contains(&height) => {
+            Some(FieldOrder::BottomFieldFirst)
+        }
+        V4L2_FIELD_INTERLACED => Some(FieldOrder::TopFieldFirst),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progressive_and_explicit_orders() {
+        assert_eq!(
+            v4l2_field_to_field_order(V4L2_FIELD_NONE, 1080),
+            Some(FieldOrder::Progressive)
+        );
+        assert_eq!(
+            v4l2_field_to_field_order(V4L2_FIELD_INTERLACED_TB, 480),
+            Some(FieldOrder::TopFieldFirst)
+        );
+        assert_eq!(
+            v4l2_field_to_field_order(V4L2_FIELD_INTERLACED_BT, 576),
+            Some(FieldOrder::BottomFieldFirst)
+        );
+    }
+
+    #[test]
+    fn plain_interlaced_follows_the_standard() {
+        assert_eq!(
+            v4l2_field_to_field_order(V4L2_FIELD_INTERLACED, 480),
+            Some(FieldOrder::BottomFieldFirst)
+        );
+        assert_eq!(
+            v4l2_field_to_field_order(V4L2_FIELD_INTERLACED, 576),
+            Some(FieldOrder::TopFieldFirst)
+        );
+    }
+
+    #[test]
+    fn any_and_single_field_layouts_are_unknown() {
+        // V4L2_FIELD_ANY, V4L2_FIELD_TOP, V4L2_FIELD_ALTERNATE.
+        for field in [0, 2, 7] {
+            assert_eq!(v4l2_field_to_field_order(field, 1080), None);
+        }
+    }
+}
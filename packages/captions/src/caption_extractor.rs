@@ -0,0 +1,150 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Reads captions out of encoded video.
+//!
+//! Each access unit's A/53 `cc_data` is fed to a CEA-608 decoder for one
+//! field-1 data channel and a CEA-708 decoder for one service, and every
+//! caption they complete goes out on `captions` as text, stamped with the
+//! frame it completed in. 608 roll-up lines complete when the next line
+//! starts or the screen is cleared, so they trail the picture by a line.
+//! With `emit_cc_data` the raw triplets go out too, one message per frame
+//! that has any, for relaying captions into another stream.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__captions::caption_data::Standard;
+use crate::_generated_::tatolab__captions::caption_extractor_config::Codec as ConfigCodec;
+use crate::_generated_::{CaptionData, EncodedVideoFrame};
+use crate::cea608::Cea608Decoder;
+use crate::cea708::Cea708Decoder;
+use crate::sei::{self, CC_TYPE_608_FIELD_1, Codec};
+
+const DEFAULT_CHANNEL: u8 = 1;
+const DEFAULT_SERVICE: u8 = 1;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/captions/CaptionExtractor",
+    description = "Reads ATSC A/53 closed captions from H.264/H.265 SEI and writes them as CaptionData: CEA-608 (CC1 or CC2) and CEA-708 text, and optionally each frame's raw cc_data.",
+    execution = reactive,
+    config = crate::_generated_::CaptionExtractorConfig,
+    input("video_in", "@tatolab/core/EncodedVideoFrame", description = "Annex-B access units carrying caption SEI"),
+    output("captions", "@tatolab/captions/CaptionData", description = "Decoded caption text, and raw cc_data when enabled"),
+)]
+pub struct CaptionExtractorProcessor {
+    codec: Option<Codec>,
+    cea608: Option<Cea608Decoder>,
+    cea708: Option<Cea708Decoder>,
+    captions_written: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for CaptionExtractorProcessor::Processor
+{
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.configure()?;
+        tracing::info!(
+            "[CaptionExtractor] Setup ({:?}, 608 {}, 708 {})",
+            self.codec,
+            self.cea608.is_some(),
+            self.cea708.is_some()
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[CaptionExtractor] Teardown ({} captions)",
+            self.captions_written
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.configure()
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("video_in")?;
+            let Some(codec) = self.codec else {
+                continue;
+            };
+            let triplets = sei::extract_cc_data(codec, &frame.data);
+            if triplets.is_empty() {
+                continue;
+            }
+            if self.config.emit_cc_data == Some(true) {
+                self.write(CaptionData {
+                    timestamp_ns: frame.timestamp_ns.clone(),
+                    cc_data: Some(triplets.concat()),
+                    ..Default::default()
+                })?;
+            }
+
+            let mut captions = Vec::new();
+            if let Some(decoder) = &mut self.cea608 {
+                let channel = self.config.cea608_channel.unwrap_or(DEFAULT_CHANNEL);
+                captions.extend(
+                    triplets
+                        .iter()
+                        .filter(|triplet| {
+                            sei::is_valid(triplet) && sei::cc_type(triplet) == CC_TYPE_608_FIELD_1
+                        })
+                        .filter_map(|triplet| decoder.push([triplet[1], triplet[2]]))
+                        .map(|text| (text, Standard::Cea608, channel)),
+                );
+            }
+            if let Some(decoder) = &mut self.cea708 {
+                let service = self.config.cea708_service.unwrap_or(DEFAULT_SERVICE);
+                captions.extend(
+                    triplets
+                        .iter()
+                        .flat_map(|triplet| decoder.push(triplet))
+                        .map(|text| (text, Standard::Cea708, service)),
+                );
+            }
+            for (text, standard, channel) in captions {
+                self.write(CaptionData {
+                    timestamp_ns: frame.timestamp_ns.clone(),
+                    text: Some(text),
+                    standard: Some(standard),
+                    channel: Some(channel),
+                    cc_data: None,
+                })?;
+                self.captions_written += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CaptionExtractorProcessor::Processor {
+    fn configure(&mut self) -> Result<()> {
+        let channel = self.config.cea608_channel.unwrap_or(DEFAULT_CHANNEL);
+        if channel > 2 {
+            return Err(Error::Configuration(format!(
+                "CaptionExtractor: cea608_channel must be 0, 1 or 2, got {channel}"
+            )));
+        }
+        let service = self.config.cea708_service.unwrap_or(DEFAULT_SERVICE);
+        if service > 63 {
+            return Err(Error::Configuration(format!(
+                "CaptionExtractor: cea708_service must be 0 … 63, got {service}"
+            )));
+        }
+        self.codec = Some(match self.config.codec {
+            Some(ConfigCodec::H264) | None => Codec::H264,
+            Some(ConfigCodec::H265) => Codec::H265,
+        });
+        // A changed channel or service starts decoding afresh.
+        self.cea608 = (channel != 0).then(|| Cea608Decoder::new(channel));
+        self.cea708 = (service != 0).then(|| Cea708Decoder::new(service));
+        Ok(())
+    }
+
+    fn write(&mut self, caption: CaptionData) -> Result<()> {
+        self.outputs.write("captions", &caption)
+    }
+}
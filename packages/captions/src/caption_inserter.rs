@@ -0,0 +1,177 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Embeds captions in encoded video.
+//!
+//! Text from `captions_in` is encoded as CEA-608 on one data channel and
+//! as CEA-708 on one service, and raw `cc_data` is queued as-is; a
+//! [`CcScheduler`] then paces it all onto the video at the A/53 rates,
+//! one `cc_data` SEI in front of each access unit's first slice. Until
+//! the first caption arrives frames pass through untouched; after that
+//! every frame carries `cc_data`, padding included, as decoders expect.
+//!
+//! Captions go out as fast as the caption rate allows, not at their
+//! timestamps — write them when they should appear.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__captions::caption_data::Standard;
+use crate::_generated_::tatolab__captions::caption_inserter_config::{Codec as ConfigCodec, Mode};
+use crate::_generated_::{CaptionData, EncodedVideoFrame};
+use crate::cea608::{self, Cea608Encoder};
+use crate::cea708::Cea708Encoder;
+use crate::schedule::CcScheduler;
+use crate::sei::{self, Codec};
+
+const DEFAULT_FPS: u32 = 30;
+const DEFAULT_ROLL_UP_ROWS: u8 = 2;
+const DEFAULT_CHANNEL: u8 = 1;
+const DEFAULT_SERVICE: u8 = 1;
+
+/// Queued pairs and triplets past which new captions are dropped — about
+/// ten seconds of 608 — so a runaway writer can't grow the queue forever.
+const MAX_BACKLOG: usize = 300;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/captions/CaptionInserter",
+    description = "Embeds closed captions in H.264/H.265 video as ATSC A/53 cc_data SEI, so they travel inside the video samples through MP4, Matroska, FLV and RTP. Caption text is sent as CEA-608 (CC1/CC2, roll-up or pop-on) and CEA-708; raw cc_data is passed through as-is.",
+    execution = reactive,
+    config = crate::_generated_::CaptionInserterConfig,
+    input("video_in", "@tatolab/core/EncodedVideoFrame", description = "Annex-B access units to caption"),
+    input("captions_in", "@tatolab/captions/CaptionData", optional = true, description = "Caption text or raw cc_data to embed"),
+    output("video_out", "@tatolab/core/EncodedVideoFrame", description = "The same access units with caption SEI"),
+)]
+pub struct CaptionInserterProcessor {
+    codec: Option<Codec>,
+    cea608: Option<Cea608Encoder>,
+    cea708: Option<Cea708Encoder>,
+    scheduler: CcScheduler,
+    /// Set by the first caption; from then on every frame carries cc_data.
+    active: bool,
+    captions_received: u64,
+    captions_dropped: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for CaptionInserterProcessor::Processor
+{
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.configure()?;
+        tracing::info!(
+            "[CaptionInserter] Setup ({:?}, 608 {}, 708 {})",
+            self.codec,
+            self.cea608.is_some(),
+            self.cea708.is_some()
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "[CaptionInserter] Teardown ({} captions, {} dropped, {} unsent)",
+            self.captions_received,
+            self.captions_dropped,
+            self.scheduler.backlog()
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.configure()
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("captions_in") {
+            let caption: CaptionData = self.inputs.read("captions_in")?;
+            self.queue(&caption);
+        }
+        while self.inputs.has_data("video_in") {
+            let mut frame: EncodedVideoFrame = self.inputs.read("video_in")?;
+            if self.active
+                && let Some(codec) = self.codec
+            {
+                let fps = frame.fps.or(self.config.fps).unwrap_or(DEFAULT_FPS);
+                let sei = sei::cc_data_sei(codec, &self.scheduler.next_frame(fps));
+                frame.data = sei::insert_before_first_slice(codec, &frame.data, &sei);
+            }
+            self.outputs.write("video_out", &frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl CaptionInserterProcessor::Processor {
+    fn configure(&mut self) -> Result<()> {
+        let channel = self.config.cea608_channel.unwrap_or(DEFAULT_CHANNEL);
+        if channel > 2 {
+            return Err(Error::Configuration(format!(
+                "CaptionInserter: cea608_channel must be 0, 1 or 2, got {channel}"
+            )));
+        }
+        let service = self.config.cea708_service.unwrap_or(DEFAULT_SERVICE);
+        if service > 6 {
+            return Err(Error::Configuration(format!(
+                "CaptionInserter: cea708_service must be 0 … 6, got {service}"
+            )));
+        }
+        let rows = self.config.roll_up_rows.unwrap_or(DEFAULT_ROLL_UP_ROWS);
+        if !(2..=4).contains(&rows) {
+            return Err(Error::Configuration(format!(
+                "CaptionInserter: roll_up_rows must be 2 … 4, got {rows}"
+            )));
+        }
+        if self.config.fps == Some(0) {
+            return Err(Error::Configuration(
+                "CaptionInserter: fps must be positive".into(),
+            ));
+        }
+        let (mode, window_rows, roll_up) = match self.config.mode {
+            Some(Mode::RollUp) | None => (cea608::Mode::RollUp { rows }, rows, true),
+            Some(Mode::PopOn) => (cea608::Mode::PopOn, 4, false),
+        };
+        self.codec = Some(match self.config.codec {
+            Some(ConfigCodec::H264) | None => Codec::H264,
+            Some(ConfigCodec::H265) => Codec::H265,
+        });
+        self.cea608 = (channel != 0).then(|| Cea608Encoder::new(channel, mode));
+        self.cea708 = (service != 0).then(|| Cea708Encoder::new(service, window_rows, roll_up));
+        Ok(())
+    }
+
+    fn queue(&mut self, caption: &CaptionData) {
+        self.captions_received += 1;
+        if self.scheduler.backlog() > MAX_BACKLOG {
+            self.captions_dropped += 1;
+            tracing::warn!(
+                "[CaptionInserter] Caption backlog full ({} queued), dropping caption at {}",
+                self.scheduler.backlog(),
+                caption.timestamp_ns
+            );
+            return;
+        }
+        if let Some(cc_data) = &caption.cc_data {
+            self.scheduler.push_raw(
+                cc_data
+                    .chunks_exact(3)
+                    .map(|chunk| [chunk[0], chunk[1], chunk[2]]),
+            );
+        }
+        if let Some(text) = &caption.text {
+            if caption.standard != Some(Standard::Cea708)
+                && let Some(encoder) = &self.cea608
+            {
+                self.scheduler.push_608(encoder.encode(text));
+            }
+            if caption.standard != Some(Standard::Cea608)
+                && let Some(encoder) = &mut self.cea708
+            {
+                self.scheduler.push_708(encoder.encode(text));
+            }
+        }
+        if !self.active {
+            tracing::info!("[CaptionInserter] First caption, embedding cc_data from now on");
+            self.active = true;
+        }
+    }
+}
@@ -0,0 +1,447 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! CEA-608 line-21 captions: text to byte pairs and back.
+//!
+//! Each byte carries seven bits and odd parity. Pairs starting 0x10–0x1F
+//! are commands, preamble address codes (PACs, which place the cursor)
+//! and special characters, all sent twice so a dropped pair doesn't lose
+//! them; the receiver ignores the repeat. Anything else is one or two
+//! characters of the basic set — ASCII with a handful of code points
+//! swapped for accented letters. Bit 3 of a command's first byte selects
+//! data channel 1 or 2 (CC1/CC2 on field 1).
+//!
+//! Roll-up captions scroll each new line in on the bottom row; pop-on
+//! captions are built off screen and shown whole. Extended characters
+//! (0x12/0x13 pairs) aren't decoded and are left as the basic-set
+//! fallback sent before them.
+
+/// Columns on a caption row.
+pub const COLUMNS: usize = 32;
+
+/// Bottom row, where roll-up captions and the last pop-on row go.
+const BASE_ROW: u8 = 15;
+
+const RCL: u8 = 0x20;
+const BS: u8 = 0x21;
+const RU2: u8 = 0x25;
+const RDC: u8 = 0x29;
+const EDM: u8 = 0x2C;
+const CR: u8 = 0x2D;
+const ENM: u8 = 0x2E;
+const EOC: u8 = 0x2F;
+
+/// Characters the basic set swaps in for ASCII punctuation.
+const BASIC_SUBSTITUTIONS: [(u8, char); 10] = [
+    (0x2A, 'á'),
+    (0x5C, 'é'),
+    (0x5E, 'í'),
+    (0x5F, 'ó'),
+    (0x60, 'ú'),
+    (0x7B, 'ç'),
+    (0x7C, '÷'),
+    (0x7D, 'Ñ'),
+    (0x7E, 'ñ'),
+    (0x7F, '█'),
+];
+
+/// Special characters, second byte 0x30–0x3F after 0x11 (CC1) / 0x19
+/// (CC2). 0x39 is a transparent space.
+const SPECIAL: [char; 16] = [
+    '®', '°', '½', '¿', '™', '¢', '£', '♪', 'à', ' ', 'è', 'â', 'ê', 'î', 'ô', 'û',
+];
+
+/// One byte with its odd-parity bit set.
+pub fn with_parity(byte: u8) -> u8 {
+    let byte = byte & 0x7F;
+    if byte.count_ones().is_multiple_of(2) {
+        byte | 0x80
+    } else {
+        byte
+    }
+}
+
+fn strip_parity(byte: u8) -> Option<u8> {
+    (byte.count_ones() % 2 == 1).then_some(byte & 0x7F)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Lines scroll up from the bottom row, `rows` (2–4) at a time.
+    RollUp { rows: u8 },
+    /// Each caption replaces the last, shown whole.
+    PopOn,
+}
+
+/// How a character goes out: one basic-set byte or a special pair.
+enum Encoded {
+    Basic(u8),
+    Special(u8),
+}
+
+fn encode_char(c: char) -> Encoded {
+    if let Some(&(byte, _)) = BASIC_SUBSTITUTIONS.iter().find(|(_, sub)| *sub == c) {
+        return Encoded::Basic(byte);
+    }
+    if let Some(index) = SPECIAL.iter().position(|&special| special == c && c != ' ') {
+        return Encoded::Special(0x30 + index as u8);
+    }
+    match c {
+        // Taken by the substitutions above; nearest look-alikes.
+        '*' => Encoded::Basic(b'+'),
+        '\\' => Encoded::Basic(b'/'),
+        '^' | '`' => Encoded::Basic(b'\''),
+        '_' => Encoded::Basic(b'-'),
+        '{' => Encoded::Basic(b'('),
+        '}' => Encoded::Basic(b')'),
+        '|' => Encoded::Basic(b'!'),
+        '~' => Encoded::Basic(b'-'),
+        ' '..='\u{7E}' => Encoded::Basic(c as u8),
+        _ => Encoded::Basic(b'?'),
+    }
+}
+
+fn decode_basic(byte: u8) -> char {
+    BASIC_SUBSTITUTIONS
+        .iter()
+        .find(|(code, _)| *code == byte)
+        .map_or(char::from(byte), |&(_, c)| c)
+}
+
+/// `text` split into rows of at most [`COLUMNS`], on word boundaries
+/// where possible. Line breaks in `text` are kept.
+pub fn wrap(text: &str) -> Vec<String> {
+    let mut rows = Vec::new();
+    for paragraph in text.lines() {
+        let mut row = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            loop {
+                let row_len = row.chars().count();
+                let word_len = word.chars().count();
+                let needed = if row.is_empty() {
+                    word_len
+                } else {
+                    row_len + 1 + word_len
+                };
+                if needed <= COLUMNS {
+                    if !row.is_empty() {
+                        row.push(' ');
+                    }
+                    row.push_str(word);
+                    break;
+                }
+                if !row.is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                    continue;
+                }
+                // A word longer than a row is split.
+                let split = word
+                    .char_indices()
+                    .nth(COLUMNS)
+                    .map_or(word.len(), |(i, _)| i);
+                rows.push(word[..split].to_string());
+                word = &word[split..];
+                if word.is_empty() {
+                    break;
+                }
+            }
+        }
+        if !row.is_empty() {
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+/// Turns caption text into field-1 byte pairs for one data channel.
+#[derive(Debug, Clone)]
+pub struct Cea608Encoder {
+    channel: u8,
+    mode: Mode,
+}
+
+impl Cea608Encoder {
+    /// `channel` is 1 (CC1) or 2 (CC2).
+    pub fn new(channel: u8, mode: Mode) -> Self {
+        Self { channel, mode }
+    }
+
+    /// Byte pairs, with parity, that put `text` on screen.
+    pub fn encode(&self, text: &str) -> Vec<[u8; 2]> {
+        let rows = wrap(text);
+        if rows.is_empty() {
+            return Vec::new();
+        }
+        let mut pairs = Vec::new();
+        match self.mode {
+            Mode::RollUp { rows: depth } => {
+                self.command(&mut pairs, RU2 + depth.clamp(2, 4) - 2);
+                for row in &rows {
+                    self.command(&mut pairs, CR);
+                    self.pac(&mut pairs, BASE_ROW);
+                    self.characters(&mut pairs, row);
+                }
+            }
+            Mode::PopOn => {
+                // Four rows fit a pop-on caption; keep the last ones.
+                let rows = &rows[rows.len().saturating_sub(4)..];
+                self.command(&mut pairs, RCL);
+                self.command(&mut pairs, ENM);
+                let first_row = BASE_ROW + 1 - rows.len() as u8;
+                for (index, row) in rows.iter().enumerate() {
+                    self.pac(&mut pairs, first_row + index as u8);
+                    self.characters(&mut pairs, row);
+                }
+                self.command(&mut pairs, EDM);
+                self.command(&mut pairs, EOC);
+            }
+        }
+        pairs
+    }
+
+    fn channel_bit(&self) -> u8 {
+        if self.channel == 2 { 0x08 } else { 0 }
+    }
+
+    /// A control pair, sent twice.
+    fn control(&self, pairs: &mut Vec<[u8; 2]>, first: u8, second: u8) {
+        let pair = [with_parity(first | self.channel_bit()), with_parity(second)];
+        pairs.extend([pair, pair]);
+    }
+
+    fn command(&self, pairs: &mut Vec<[u8; 2]>, command: u8) {
+        self.control(pairs, 0x14, command);
+    }
+
+    /// Preamble address code: `row` (1–15), column 0, white.
+    fn pac(&self, pairs: &mut Vec<[u8; 2]>, row: u8) {
+        const FIRST_BYTES: [u8; 15] = [
+            0x11, 0x11, 0x12, 0x12, 0x15, 0x15, 0x16, 0x16, 0x17, 0x17, 0x10, 0x13, 0x13, 0x14,
+            0x14,
+        ];
+        let row = row.clamp(1, 15);
+        let first = FIRST_BYTES[usize::from(row - 1)];
+        // Row 11 stands alone; otherwise the second of each pair of rows
+        // sharing a first byte is offset by 0x20.
+        let lower = matches!(row, 2 | 4 | 6 | 8 | 10 | 13 | 15);
+        self.control(pairs, first, if lower { 0x60 } else { 0x40 });
+    }
+
+    fn characters(&self, pairs: &mut Vec<[u8; 2]>, row: &str) {
+        let mut pending: Option<u8> = None;
+        for c in row.chars() {
+            match encode_char(c) {
+                Encoded::Basic(byte) => match pending.take() {
+                    Some(first) => pairs.push([with_parity(first), with_parity(byte)]),
+                    None => pending = Some(byte),
+                },
+                Encoded::Special(second) => {
+                    if let Some(first) = pending.take() {
+                        pairs.push([with_parity(first), with_parity(0)]);
+                    }
+                    self.control(pairs, 0x11, second);
+                }
+            }
+        }
+        if let Some(first) = pending {
+            pairs.push([with_parity(first), with_parity(0)]);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecodeMode {
+    None,
+    RollUp,
+    PopOn,
+    PaintOn,
+}
+
+/// Recovers caption text for one data channel from field-1 byte pairs.
+#[derive(Debug, Clone)]
+pub struct Cea608Decoder {
+    channel: u8,
+    /// Channel the stream last addressed; characters belong to it.
+    active_channel: Option<u8>,
+    mode: DecodeMode,
+    /// The roll-up or paint-on line being written.
+    line: String,
+    /// Pop-on caption being built off screen.
+    buffer: String,
+    /// The last control pair, to drop its repeat.
+    last_control: Option<[u8; 2]>,
+}
+
+impl Cea608Decoder {
+    /// `channel` is 1 (CC1) or 2 (CC2).
+    pub fn new(channel: u8) -> Self {
+        Self {
+            channel,
+            active_channel: None,
+            mode: DecodeMode::None,
+            line: String::new(),
+            buffer: String::new(),
+            last_control: None,
+        }
+    }
+
+    /// Feed one byte pair. Returns a caption when one completes: a
+    /// roll-up or paint-on line when the next begins or the screen
+    /// clears, a pop-on caption, rows separated by `\n`, when shown.
+    pub fn push(&mut self, pair: [u8; 2]) -> Option<String> {
+        let (Some(first), Some(second)) = (strip_parity(pair[0]), strip_parity(pair[1])) else {
+            return None;
+        };
+        if first == 0 && second == 0 {
+            return None;
+        }
+        if !(0x10..=0x1F).contains(&first) {
+            self.last_control = None;
+            if self.active_channel != Some(self.channel) {
+                return None;
+            }
+            for byte in [first, second] {
+                if byte >= 0x20 {
+                    self.target()?.push(decode_basic(byte));
+                }
+            }
+            return None;
+        }
+
+        let control = [first, second];
+        if self.last_control == Some(control) {
+            self.last_control = None;
+            return None;
+        }
+        self.last_control = Some(control);
+        let channel = if first & 0x08 != 0 { 2 } else { 1 };
+        self.active_channel = Some(channel);
+        if channel != self.channel {
+            return None;
+        }
+        match (first & !0x08, second) {
+            // Miscellaneous commands (0x15 is their field-2 form).
+            (0x14 | 0x15, 0x20..=0x2F) => self.command(second),
+            (0x11, 0x30..=0x3F) => {
+                self.target()?.push(SPECIAL[usize::from(second - 0x30)]);
+                None
+            }
+            // Mid-row style codes show as a space.
+            (0x11, 0x20..=0x2F) => {
+                self.target()?.push(' ');
+                None
+            }
+            // A PAC moving to another row starts a new row of a caption.
+            (_, 0x40..=0x7F) => {
+                if self.mode != DecodeMode::RollUp {
+                    let target = self.target()?;
+                    if !target.is_empty() && !target.ends_with('\n') {
+                        target.push('\n');
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn target(&mut self) -> Option<&mut String> {
+        match self.mode {
+            DecodeMode::None => None,
+            DecodeMode::PopOn => Some(&mut self.buffer),
+            DecodeMode::RollUp | DecodeMode::PaintOn => Some(&mut self.line),
+        }
+    }
+
+    fn command(&mut self, command: u8) -> Option<String> {
+        match command {
+            RCL => {
+                self.mode = DecodeMode::PopOn;
+                None
+            }
+            BS => {
+                self.target()?.pop();
+                None
+            }
+            RU2..=0x27 => {
+                self.mode = DecodeMode::RollUp;
+                None
+            }
+            RDC => {
+                self.mode = DecodeMode::PaintOn;
+                None
+            }
+            CR | EDM if self.mode != DecodeMode::PopOn => take_caption(&mut self.line),
+            ENM => {
+                self.buffer.clear();
+                None
+            }
+            EOC => {
+                self.mode = DecodeMode::PopOn;
+                take_caption(&mut self.buffer)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn take_caption(text: &mut String) -> Option<String> {
+    let caption = text.trim().to_string();
+    text.clear();
+    (!caption.is_empty()).then_some(caption)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(decoder: &mut Cea608Decoder, pairs: &[[u8; 2]]) -> Vec<String> {
+        pairs
+            .iter()
+            .filter_map(|&pair| decoder.push(pair))
+            .collect()
+    }
+
+    #[test]
+    fn parity_is_odd() {
+        assert_eq!(with_parity(0x14), 0x94);
+        assert_eq!(with_parity(0x2C), 0x2C);
+        assert_eq!(with_parity(0x00), 0x80);
+    }
+
+    #[test]
+    fn wrap_breaks_on_words_and_splits_long_ones() {
+        assert_eq!(
+            wrap("the quick brown fox jumps over the lazy dog\nend"),
+            ["the quick brown fox jumps over", "the lazy dog", "end"]
+        );
+        assert_eq!(wrap(&"x".repeat(40)), ["x".repeat(32), "x".repeat(8)]);
+    }
+
+    #[test]
+    fn roll_up_round_trips_line_by_line() {
+        let encoder = Cea608Encoder::new(1, Mode::RollUp { rows: 2 });
+        let mut decoder = Cea608Decoder::new(1);
+        assert!(decode_all(&mut decoder, &encoder.encode("Café * ♪ ok")).is_empty());
+        assert_eq!(
+            decode_all(&mut decoder, &encoder.encode("second")),
+            ["Café + ♪ ok"]
+        );
+        // Another channel's text is ignored.
+        let other = Cea608Encoder::new(2, Mode::RollUp { rows: 2 });
+        assert!(decode_all(&mut decoder, &other.encode("elsewhere")).is_empty());
+        let erase = [with_parity(0x14), with_parity(EDM)];
+        assert_eq!(decode_all(&mut decoder, &[erase, erase]), ["second"]);
+    }
+
+    #[test]
+    fn pop_on_shows_whole_captions() {
+        let encoder = Cea608Encoder::new(2, Mode::PopOn);
+        let mut decoder = Cea608Decoder::new(2);
+        assert_eq!(
+            decode_all(&mut decoder, &encoder.encode("Hello\nworld")),
+            ["Hello\nworld"]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! CEA-708 DTV captions: text to DTVCC packets and back.
+//!
+//! A DTVCC packet is a header byte (two-bit sequence number, six-bit
+//! size in byte pairs) and up to 127 bytes of service blocks, each a
+//! header (service number, length) and up to 31 bytes of that service's
+//! commands and characters. Packets are cut into `cc_data` triplets, the
+//! first marked as a packet start.
+//!
+//! The encoder writes into one window, defined with every caption so a
+//! decoder joining mid-stream picks it up: roll-up captions go on a new
+//! row that scrolls the window, pop-on captions clear it first. Each
+//! caption ends with ETX so the decoder side can flush it at once. The
+//! decoder takes characters from the G0 and G1 sets and treats CR, ETX,
+//! form feed and window clears as the end of a caption line; pen and
+//! window styling is skipped.
+
+use crate::cea608::wrap;
+use crate::sei::{CC_TYPE_DTVCC_DATA, CC_TYPE_DTVCC_START, CcTriplet, cc_type, is_valid, triplet};
+
+const ETX: u8 = 0x03;
+const BS: u8 = 0x08;
+const FF: u8 = 0x0C;
+const CR: u8 = 0x0D;
+const HCR: u8 = 0x0E;
+const EXT1: u8 = 0x10;
+const CLW: u8 = 0x88;
+const HDW: u8 = 0x8A;
+const DLW: u8 = 0x8C;
+const DF0: u8 = 0x98;
+
+/// The musical note in G0's 0x7F position.
+const NOTE: u8 = 0x7F;
+
+const MAX_BLOCK: usize = 31;
+const MAX_PACKET_DATA: usize = 127;
+
+/// Parameter bytes following each C1 command, 0x80–0x9F.
+const C1_PARAMS: [usize; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, // CW0–CW7
+    1, 1, 1, 1, 1, 1, 0, 0, // CLW DSW HDW TGW DLW DLY DLC RST
+    2, 3, 2, 0, 0, 0, 0, 4, // SPA SPC SPL (reserved) SWA
+    6, 6, 6, 6, 6, 6, 6, 6, // DF0–DF7
+];
+
+fn encode_char(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '♪' => NOTE,
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        _ => b'?',
+    }
+}
+
+/// Turns caption text into DTVCC triplets for one service.
+#[derive(Debug, Clone)]
+pub struct Cea708Encoder {
+    service: u8,
+    /// Rows of the caption window.
+    rows: u8,
+    roll_up: bool,
+    sequence: u8,
+}
+
+impl Cea708Encoder {
+    /// `service` is 1–6. Roll-up captions scroll a `rows`-high window;
+    /// otherwise each caption replaces the window's contents.
+    pub fn new(service: u8, rows: u8, roll_up: bool) -> Self {
+        Self {
+            service: service.clamp(1, 6),
+            rows: rows.clamp(1, 4),
+            roll_up,
+            sequence: 0,
+        }
+    }
+
+    pub fn encode(&mut self, text: &str) -> Vec<CcTriplet> {
+        let rows = wrap(text);
+        if rows.is_empty() {
+            return Vec::new();
+        }
+        let mut data = self.define_window();
+        if self.roll_up {
+            for row in &rows {
+                data.push(CR);
+                data.extend(row.chars().map(encode_char));
+            }
+        } else {
+            let rows = &rows[rows.len().saturating_sub(usize::from(self.rows))..];
+            // Clear window 0.
+            data.extend([CLW, 0x01]);
+            for (index, row) in rows.iter().enumerate() {
+                if index > 0 {
+                    data.push(CR);
+                }
+                data.extend(row.chars().map(encode_char));
+            }
+        }
+        data.push(ETX);
+        self.packets(&data)
+    }
+
+    /// DF0: window 0, visible, locked to 32 columns × `rows`, anchored
+    /// bottom-center near the bottom of the safe area, popup window and
+    /// pen styles.
+    fn define_window(&self) -> Vec<u8> {
+        vec![
+            DF0,
+            0x38,
+            0x80 | 99,
+            50,
+            (7 << 4) | (self.rows - 1),
+            (crate::cea608::COLUMNS - 1) as u8,
+            (1 << 3) | 1,
+        ]
+    }
+
+    /// Service blocks of `data` in as many packets as it takes.
+    fn packets(&mut self, data: &[u8]) -> Vec<CcTriplet> {
+        let mut triplets = Vec::new();
+        let mut packet: Vec<u8> = Vec::new();
+        for block in split_commands(data) {
+            if packet.len() + 1 + block.len() > MAX_PACKET_DATA {
+                self.finish_packet(&mut packet, &mut triplets);
+            }
+            packet.push((self.service << 5) | block.len() as u8);
+            packet.extend_from_slice(block);
+        }
+        self.finish_packet(&mut packet, &mut triplets);
+        triplets
+    }
+
+    fn finish_packet(&mut self, packet: &mut Vec<u8>, triplets: &mut Vec<CcTriplet>) {
+        if packet.is_empty() {
+            return;
+        }
+        // Header plus data, padded to whole pairs with a null block header.
+        if packet.len().is_multiple_of(2) {
+            packet.push(0);
+        }
+        let pairs = packet.len().div_ceil(2);
+        let size_code = (pairs % 64) as u8;
+        let mut bytes = vec![(self.sequence << 6) | size_code];
+        bytes.append(packet);
+        self.sequence = (self.sequence + 1) % 4;
+        for (index, pair) in bytes.chunks_exact(2).enumerate() {
+            let cc_type = if index == 0 {
+                CC_TYPE_DTVCC_START
+            } else {
+                CC_TYPE_DTVCC_DATA
+            };
+            triplets.push(triplet(true, cc_type, [pair[0], pair[1]]));
+        }
+    }
+}
+
+/// `data` cut into service-block-sized pieces without splitting a
+/// command from its parameters.
+fn split_commands(data: &[u8]) -> Vec<&[u8]> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    let mut at = 0;
+    while at < data.len() {
+        let len = command_len(data[at]);
+        if at + len - start > MAX_BLOCK {
+            blocks.push(&data[start..at]);
+            start = at;
+        }
+        at += len;
+    }
+    if start < data.len() {
+        blocks.push(&data[start..]);
+    }
+    blocks
+}
+
+/// Bytes taken by the code starting with `byte`, itself included.
+fn command_len(byte: u8) -> usize {
+    match byte {
+        0x00..=0x0F => 1,
+        0x10..=0x17 => 2,
+        0x18..=0x1F => 3,
+        0x80..=0x9F => 1 + C1_PARAMS[usize::from(byte - 0x80)],
+        _ => 1,
+    }
+}
+
+/// Recovers caption lines for one service from DTVCC triplets.
+#[derive(Debug, Clone)]
+pub struct Cea708Decoder {
+    service: u8,
+    packet: Vec<u8>,
+    /// Bytes the current packet holds, header included.
+    expected: usize,
+    line: String,
+}
+
+impl Cea708Decoder {
+    pub fn new(service: u8) -> Self {
+        Self {
+            service,
+            packet: Vec::new(),
+            expected: 0,
+            line: String::new(),
+        }
+    }
+
+    /// Feed one triplet. Returns the caption lines it completed.
+    pub fn push(&mut self, triplet: &CcTriplet) -> Vec<String> {
+        if !is_valid(triplet) {
+            return Vec::new();
+        }
+        match cc_type(triplet) {
+            CC_TYPE_DTVCC_START => {
+                // A new packet abandons an unfinished one.
+                let size_code = usize::from(triplet[1] & 0x3F);
+                self.expected = if size_code == 0 { 128 } else { size_code * 2 };
+                self.packet = vec![triplet[1], triplet[2]];
+            }
+            CC_TYPE_DTVCC_DATA if !self.packet.is_empty() => {
+                self.packet.extend_from_slice(&triplet[1..]);
+            }
+            _ => return Vec::new(),
+        }
+        if self.packet.len() < self.expected {
+            return Vec::new();
+        }
+        let packet = std::mem::take(&mut self.packet);
+        self.service_blocks(&packet[1..self.expected])
+    }
+
+    fn service_blocks(&mut self, mut data: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let [header, rest @ ..] = data {
+            let mut service = header >> 5;
+            let size = usize::from(header & 0x1F);
+            data = rest;
+            if service == 7 {
+                let [extended, rest @ ..] = data else {
+                    break;
+                };
+                service = extended & 0x3F;
+                data = rest;
+            }
+            // A null block header pads out the packet.
+            if service == 0 || size == 0 {
+                break;
+            }
+            let (block, rest) = data.split_at(size.min(data.len()));
+            data = rest;
+            if service == self.service {
+                self.interpret(block, &mut lines);
+            }
+        }
+        lines
+    }
+
+    fn interpret(&mut self, block: &[u8], lines: &mut Vec<String>) {
+        let mut at = 0;
+        while at < block.len() {
+            let byte = block[at];
+            match byte {
+                ETX | FF | CR | CLW | HDW | DLW => self.flush(lines),
+                BS => {
+                    self.line.pop();
+                }
+                HCR => self.line.clear(),
+                // G2 transparent and non-breaking spaces; other extended
+                // characters are skipped.
+                EXT1 if matches!(block.get(at + 1), Some(0x20 | 0x21)) => self.line.push(' '),
+                NOTE => self.line.push('♪'),
+                0x20..=0x7E | 0xA0..=0xFF => self.line.push(char::from(byte)),
+                _ => {}
+            }
+            at += command_len(byte);
+        }
+    }
+
+    fn flush(&mut self, lines: &mut Vec<String>) {
+        let line = self.line.trim();
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+        self.line.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(decoder: &mut Cea708Decoder, triplets: &[CcTriplet]) -> Vec<String> {
+        triplets
+            .iter()
+            .flat_map(|triplet| decoder.push(triplet))
+            .collect()
+    }
+
+    #[test]
+    fn roll_up_round_trips_across_packets() {
+        let mut encoder = Cea708Encoder::new(1, 2, true);
+        let mut decoder = Cea708Decoder::new(1);
+        let long = "Señor ♪ ".repeat(30);
+        let triplets = encoder.encode(&long);
+        // Several packets, each starting with a start triplet.
+        assert!(
+            triplets
+                .iter()
+                .filter(|t| cc_type(t) == CC_TYPE_DTVCC_START)
+                .count()
+                > 1
+        );
+        let lines = decode_all(&mut decoder, &triplets);
+        assert_eq!(lines, wrap(&long));
+    }
+
+    #[test]
+    fn other_services_and_padding_are_ignored() {
+        let mut encoder = Cea708Encoder::new(2, 3, false);
+        let mut decoder = Cea708Decoder::new(1);
+        let mut triplets = encoder.encode("not for service one");
+        triplets.push(triplet(false, CC_TYPE_DTVCC_DATA, [0, 0]));
+        assert!(decode_all(&mut decoder, &triplets).is_empty());
+
+        let mut decoder = Cea708Decoder::new(2);
+        assert_eq!(
+            decode_all(&mut decoder, &encoder.encode("a\nb")),
+            ["a", "b"]
+        );
+    }
+}
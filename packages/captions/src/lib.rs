@@ -0,0 +1,29 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/captions` — closed captions in encoded video.
+//! `CaptionInserter` embeds CEA-608/708 captions in H.264/H.265 as ATSC
+//! A/53 SEI, where every muxer downstream carries them along with the
+//! video samples; `CaptionExtractor` reads them back out as text and raw
+//! `cc_data` on a data port.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod caption_extractor;
+pub mod caption_inserter;
+pub mod cea608;
+pub mod cea708;
+pub mod schedule;
+pub mod sei;
+
+pub use _generated_::{CaptionData, CaptionExtractorConfig, CaptionInserterConfig};
+pub use caption_extractor::CaptionExtractorProcessor;
+pub use caption_inserter::CaptionInserterProcessor;
+
+streamlib_plugin_abi::export_plugin!(
+    crate::CaptionInserterProcessor::Processor,
+    crate::CaptionExtractorProcessor::Processor,
+);
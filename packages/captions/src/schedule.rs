@@ -0,0 +1,121 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Pacing caption data onto video frames.
+//!
+//! A/53 sizes `cc_data` for a constant 9600 bit/s whatever the frame
+//! rate — 20 triplets a frame at 30 fps, 10 at 60 — and CEA-608 runs at
+//! one field-1 pair per 1/30 s, so above 30 fps it gets every other
+//! frame. Queued captions drain in order at those rates; slots with
+//! nothing to send carry padding, so the stream keeps its cadence
+//! between captions.
+
+use std::collections::VecDeque;
+
+use crate::sei::{
+    CC_TYPE_608_FIELD_1, CC_TYPE_608_FIELD_2, CC_TYPE_DTVCC_DATA, CcTriplet, MAX_CC_COUNT, cc_type,
+    is_valid, triplet,
+};
+
+/// CEA-608 null pair, parity set.
+const NULL_PAIR: [u8; 2] = [0x80, 0x80];
+
+/// Triplets per frame at `fps`.
+pub fn cc_count(fps: u32) -> usize {
+    (600 / fps.max(1) as usize).clamp(2, MAX_CC_COUNT)
+}
+
+#[derive(Debug, Default)]
+pub struct CcScheduler {
+    field_1: VecDeque<[u8; 2]>,
+    field_2: VecDeque<[u8; 2]>,
+    dtvcc: VecDeque<CcTriplet>,
+    frames: u64,
+}
+
+impl CcScheduler {
+    pub fn push_608(&mut self, pairs: impl IntoIterator<Item = [u8; 2]>) {
+        self.field_1.extend(pairs);
+    }
+
+    pub fn push_708(&mut self, triplets: impl IntoIterator<Item = CcTriplet>) {
+        self.dtvcc.extend(triplets);
+    }
+
+    /// Queue triplets received as-is, sorted by type. Padding is dropped.
+    pub fn push_raw(&mut self, triplets: impl IntoIterator<Item = CcTriplet>) {
+        for triplet in triplets.into_iter().filter(is_valid) {
+            let pair = [triplet[1], triplet[2]];
+            match cc_type(&triplet) {
+                CC_TYPE_608_FIELD_1 => self.field_1.push_back(pair),
+                CC_TYPE_608_FIELD_2 => self.field_2.push_back(pair),
+                _ => self.dtvcc.push_back(triplet),
+            }
+        }
+    }
+
+    /// Queued triplets and pairs not yet sent.
+    pub fn backlog(&self) -> usize {
+        self.field_1.len() + self.field_2.len() + self.dtvcc.len()
+    }
+
+    /// The `cc_data` for the next frame at `fps`.
+    pub fn next_frame(&mut self, fps: u32) -> Vec<CcTriplet> {
+        let count = cc_count(fps);
+        let frame = self.frames;
+        self.frames += 1;
+        let mut triplets = Vec::with_capacity(count);
+        if fps <= 30 || frame.is_multiple_of(2) {
+            let field_1 = self.field_1.pop_front().unwrap_or(NULL_PAIR);
+            triplets.push(triplet(true, CC_TYPE_608_FIELD_1, field_1));
+            let field_2 = self.field_2.pop_front();
+            triplets.push(triplet(
+                field_2.is_some(),
+                CC_TYPE_608_FIELD_2,
+                field_2.unwrap_or(NULL_PAIR),
+            ));
+        }
+        while triplets.len() < count {
+            // A packet's start triplet may follow another packet's data
+            // directly, so the queue drains as is.
+            triplets.push(
+                self.dtvcc
+                    .pop_front()
+                    .unwrap_or_else(|| triplet(false, CC_TYPE_DTVCC_DATA, [0, 0])),
+            );
+        }
+        triplets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sei::CC_TYPE_DTVCC_START;
+
+    #[test]
+    fn frames_carry_608_at_30_hz_and_fill_with_708() {
+        let mut scheduler = CcScheduler::default();
+        scheduler.push_608([[0x94, 0x2C], [0x94, 0x2C]]);
+        scheduler.push_708((0..25).map(|i| triplet(true, CC_TYPE_DTVCC_START, [i, 0])));
+
+        let first = scheduler.next_frame(60);
+        assert_eq!(first.len(), 10);
+        assert_eq!(first[0], triplet(true, CC_TYPE_608_FIELD_1, [0x94, 0x2C]));
+        assert!(!is_valid(&first[1]));
+        assert_eq!(first[2][1], 0);
+        // Odd frames above 30 fps skip 608.
+        let second = scheduler.next_frame(60);
+        assert_eq!(second[0][1], 8);
+        let third = scheduler.next_frame(60);
+        assert_eq!(third[0][1], 0x94);
+        assert_eq!(third[8][1], 24);
+        assert!(!is_valid(&third[9]));
+        assert_eq!(scheduler.backlog(), 0);
+
+        let padded = scheduler.next_frame(30);
+        assert_eq!(padded.len(), 20);
+        assert_eq!(padded[0], triplet(true, CC_TYPE_608_FIELD_1, NULL_PAIR));
+        assert!(!is_valid(&padded[19]));
+    }
+}
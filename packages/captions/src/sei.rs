@@ -0,0 +1,315 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! ATSC A/53 caption data in H.264 / H.265 SEI.
+//!
+//! Captions ride in a `user_data_registered_itu_t_t35` SEI message
+//! (payload type 4) with the `GA94` identifier and user data type 3,
+//! whose body is a list of `cc_data` triplets: one marker/valid/type byte
+//! and two data bytes. Types 0 and 1 are CEA-608 byte pairs for fields 1
+//! and 2; types 3 and 2 start and continue CEA-708 DTVCC packets. The
+//! SEI goes in front of the access unit's first slice, so every muxer
+//! that carries the H.264/H.265 samples — MP4, Matroska, FLV, RTP —
+//! carries the captions with them.
+
+/// One `cc_data` triplet as it appears on the wire.
+pub type CcTriplet = [u8; 3];
+
+/// `cc_type` of a triplet.
+pub const CC_TYPE_608_FIELD_1: u8 = 0;
+pub const CC_TYPE_608_FIELD_2: u8 = 1;
+pub const CC_TYPE_DTVCC_DATA: u8 = 2;
+pub const CC_TYPE_DTVCC_START: u8 = 3;
+
+/// Most triplets one `cc_data` can hold (`cc_count` is five bits).
+pub const MAX_CC_COUNT: usize = 31;
+
+const SEI_PAYLOAD_USER_DATA_REGISTERED: u8 = 4;
+const T35_COUNTRY_USA: u8 = 0xB5;
+const T35_PROVIDER_ATSC: [u8; 2] = [0x00, 0x31];
+const ATSC_IDENTIFIER: &[u8; 4] = b"GA94";
+const ATSC_CC_DATA: u8 = 0x03;
+
+/// A triplet: the five marker bits, `cc_valid` and `cc_type`, then data.
+pub fn triplet(valid: bool, cc_type: u8, data: [u8; 2]) -> CcTriplet {
+    [
+        0xF8 | (u8::from(valid) << 2) | (cc_type & 0x03),
+        data[0],
+        data[1],
+    ]
+}
+
+pub fn is_valid(triplet: &CcTriplet) -> bool {
+    triplet[0] & 0x04 != 0
+}
+
+pub fn cc_type(triplet: &CcTriplet) -> u8 {
+    triplet[0] & 0x03
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    H264,
+    H265,
+}
+
+impl Codec {
+    fn nal_type(self, nal: &[u8]) -> u8 {
+        match self {
+            Self::H264 => nal[0] & 0x1F,
+            Self::H265 => (nal[0] >> 1) & 0x3F,
+        }
+    }
+
+    /// Header bytes before the RBSP.
+    fn header_len(self) -> usize {
+        match self {
+            Self::H264 => 1,
+            Self::H265 => 2,
+        }
+    }
+
+    fn is_slice(self, nal: &[u8]) -> bool {
+        let nal_type = self.nal_type(nal);
+        match self {
+            Self::H264 => (1..=5).contains(&nal_type),
+            Self::H265 => nal_type < 32,
+        }
+    }
+
+    fn is_sei(self, nal: &[u8]) -> bool {
+        let nal_type = self.nal_type(nal);
+        match self {
+            Self::H264 => nal_type == 6,
+            // Prefix and suffix SEI.
+            Self::H265 => nal_type == 39 || nal_type == 40,
+        }
+    }
+
+    /// Prefix SEI header (H.265: layer 0, temporal id 0).
+    fn sei_header(self) -> &'static [u8] {
+        match self {
+            Self::H264 => &[0x06],
+            Self::H265 => &[39 << 1, 0x01],
+        }
+    }
+}
+
+/// The NAL units of an Annex-B access unit as `(offset, len)` ranges,
+/// start codes stripped.
+fn nal_ranges(data: &[u8]) -> Vec<(usize, usize)> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            starts.push(i + 3);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    let mut ranges = Vec::with_capacity(starts.len());
+    for (index, &start) in starts.iter().enumerate() {
+        let end = match starts.get(index + 1) {
+            // A 4-byte start code's leading zero belongs to it, not to the
+            // NAL unit before.
+            Some(&next) => {
+                let end = next - 3;
+                if end > start && data[end - 1] == 0 {
+                    end - 1
+                } else {
+                    end
+                }
+            }
+            None => data.len(),
+        };
+        if end > start {
+            ranges.push((start, end - start));
+        }
+    }
+    ranges
+}
+
+/// Strip emulation-prevention bytes (`00 00 03` → `00 00`).
+fn unescape(rbsp: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(rbsp.len());
+    let mut zeros = 0;
+    for &byte in rbsp {
+        if zeros >= 2 && byte == 0x03 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        out.push(byte);
+    }
+    out
+}
+
+/// Insert emulation-prevention bytes so no start code appears in `rbsp`.
+fn escape(rbsp: &[u8], out: &mut Vec<u8>) {
+    let mut zeros = 0;
+    for &byte in rbsp {
+        if zeros >= 2 && byte <= 0x03 {
+            out.push(0x03);
+            zeros = 0;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        out.push(byte);
+    }
+}
+
+/// A prefix SEI NAL unit, with a 4-byte start code, carrying `triplets`
+/// (at most [`MAX_CC_COUNT`]) as A/53 `cc_data`.
+pub fn cc_data_sei(codec: Codec, triplets: &[CcTriplet]) -> Vec<u8> {
+    let triplets = &triplets[..triplets.len().min(MAX_CC_COUNT)];
+    let mut payload = Vec::with_capacity(10 + triplets.len() * 3);
+    payload.push(T35_COUNTRY_USA);
+    payload.extend_from_slice(&T35_PROVIDER_ATSC);
+    payload.extend_from_slice(ATSC_IDENTIFIER);
+    payload.push(ATSC_CC_DATA);
+    // reserved, process_cc_data_flag, no additional data, cc_count.
+    payload.push(0xC0 | triplets.len() as u8);
+    // em_data, unused.
+    payload.push(0xFF);
+    payload.extend(triplets.iter().flatten());
+    // marker_bits.
+    payload.push(0xFF);
+
+    let mut rbsp = vec![SEI_PAYLOAD_USER_DATA_REGISTERED];
+    let mut size = payload.len();
+    while size >= 0xFF {
+        rbsp.push(0xFF);
+        size -= 0xFF;
+    }
+    rbsp.push(size as u8);
+    rbsp.extend_from_slice(&payload);
+    // rbsp_trailing_bits.
+    rbsp.push(0x80);
+
+    let mut nal = vec![0, 0, 0, 1];
+    nal.extend_from_slice(codec.sei_header());
+    escape(&rbsp, &mut nal);
+    nal
+}
+
+/// `access_unit` with `sei` inserted in front of its first slice, after
+/// any delimiter and parameter sets. Without a slice it goes at the end.
+pub fn insert_before_first_slice(codec: Codec, access_unit: &[u8], sei: &[u8]) -> Vec<u8> {
+    let at = nal_ranges(access_unit)
+        .into_iter()
+        .find(|&(offset, len)| codec.is_slice(&access_unit[offset..offset + len]))
+        .map_or(access_unit.len(), |(offset, _)| {
+            // Back up over the slice's start code.
+            let mut at = offset - 3;
+            if at > 0 && access_unit[at - 1] == 0 {
+                at -= 1;
+            }
+            at
+        });
+    let mut out = Vec::with_capacity(access_unit.len() + sei.len());
+    out.extend_from_slice(&access_unit[..at]);
+    out.extend_from_slice(sei);
+    out.extend_from_slice(&access_unit[at..]);
+    out
+}
+
+/// The A/53 `cc_data` triplets in `access_unit`'s SEI, in order.
+/// Messages that aren't ATSC caption data are skipped.
+pub fn extract_cc_data(codec: Codec, access_unit: &[u8]) -> Vec<CcTriplet> {
+    let mut triplets = Vec::new();
+    for (offset, len) in nal_ranges(access_unit) {
+        let nal = &access_unit[offset..offset + len];
+        if nal.len() <= codec.header_len() || !codec.is_sei(nal) {
+            continue;
+        }
+        let rbsp = unescape(&nal[codec.header_len()..]);
+        let mut rest = rbsp.as_slice();
+        // Stop at the trailing bits (a lone 0x80) or a truncated header.
+        while rest.len() > 2 {
+            let Some((payload_type, after_type)) = sei_varint(rest) else {
+                break;
+            };
+            let Some((size, after_size)) = sei_varint(after_type) else {
+                break;
+            };
+            let Some(payload) = after_size.get(..size) else {
+                break;
+            };
+            if payload_type == usize::from(SEI_PAYLOAD_USER_DATA_REGISTERED) {
+                parse_atsc_cc_data(payload, &mut triplets);
+            }
+            rest = &after_size[size..];
+        }
+    }
+    triplets
+}
+
+/// An SEI payload type or size: a run of `0xFF` bytes plus a final byte.
+fn sei_varint(data: &[u8]) -> Option<(usize, &[u8])> {
+    let mut value = 0;
+    for (index, &byte) in data.iter().enumerate() {
+        value += usize::from(byte);
+        if byte != 0xFF {
+            return Some((value, &data[index + 1..]));
+        }
+    }
+    None
+}
+
+fn parse_atsc_cc_data(payload: &[u8], triplets: &mut Vec<CcTriplet>) {
+    let Some(body) = payload
+        .strip_prefix(&[T35_COUNTRY_USA])
+        .and_then(|rest| rest.strip_prefix(&T35_PROVIDER_ATSC))
+        .and_then(|rest| rest.strip_prefix(ATSC_IDENTIFIER))
+        .and_then(|rest| rest.strip_prefix(&[ATSC_CC_DATA]))
+    else {
+        return;
+    };
+    let [flags, _em_data, cc_data @ ..] = body else {
+        return;
+    };
+    if flags & 0x40 == 0 {
+        return;
+    }
+    let count = usize::from(flags & 0x1F);
+    triplets.extend(
+        cc_data
+            .chunks_exact(3)
+            .take(count)
+            .map(|chunk| [chunk[0], chunk[1], chunk[2]]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cc_data_round_trips_through_an_access_unit() {
+        for codec in [Codec::H264, Codec::H265] {
+            let (parameter_set, slice): (&[u8], &[u8]) = match codec {
+                Codec::H264 => (&[0x67, 0x42, 0x00], &[0x65, 0x88, 0x84, 0x21]),
+                Codec::H265 => (&[0x40, 0x01, 0x0C], &[0x26, 0x01, 0xAF, 0x10]),
+            };
+            let mut access_unit = vec![0, 0, 0, 1];
+            access_unit.extend_from_slice(parameter_set);
+            access_unit.extend_from_slice(&[0, 0, 1]);
+            access_unit.extend_from_slice(slice);
+
+            // Zero data bytes force emulation prevention.
+            let triplets = [
+                triplet(true, CC_TYPE_608_FIELD_1, [0x94, 0x2C]),
+                triplet(true, CC_TYPE_DTVCC_START, [0x00, 0x00]),
+                triplet(false, CC_TYPE_DTVCC_DATA, [0x00, 0x00]),
+            ];
+            let sei = cc_data_sei(codec, &triplets);
+            let with_sei = insert_before_first_slice(codec, &access_unit, &sei);
+
+            assert_eq!(extract_cc_data(codec, &with_sei), triplets);
+            // Parameter set first, then the SEI, then the untouched slice.
+            assert!(with_sei.starts_with(&access_unit[..4 + parameter_set.len()]));
+            assert!(with_sei.ends_with(slice));
+            assert!(extract_cc_data(codec, &access_unit).is_empty());
+        }
+    }
+}
@@ -12,11 +12,10 @@ use streamlib_plugin_sdk::sdk::context::RuntimeContextFullAccess;
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 use streamlib_plugin_sdk::sdk::iceoryx2::InputMailboxes;
 use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+use streamlib_audio::plugin_host::{ParameterInfo, PluginInfo, PluginParameterControl};
 use streamlib_audio::{ProcessorAudioConverter, ProcessorAudioConverterTargetFormat};
 
 use crate::host::ClapPluginHost;
-use crate::parameter_automation::ClapParameterControl;
-use crate::plugin_info::{ParameterInfo, PluginInfo};
 
 /// Wrapper for InputMailboxes pointer that is Send.
 /// SAFETY: InputMailboxes is Send, and we ensure the pointed-to data outlives
@@ -300,7 +299,7 @@ impl ManualProcessor for ClapEffectProcessor::Processor {
     }
 }
 
-impl ClapParameterControl for ClapEffectProcessor::Processor {
+impl PluginParameterControl for ClapEffectProcessor::Processor {
     fn set_parameter(&mut self, id: u32, value: f64) -> Result<()> {
         ClapEffectProcessor::Processor::set_parameter(self, id, value)
     }
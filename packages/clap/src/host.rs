@@ -21,9 +21,9 @@ use parking_lot::Mutex as ParkingLotMutex;
 use std::path::Path;
 use std::sync::Arc;
 use crate::_generated_::AudioFrame;
+use streamlib_audio::plugin_host::{ParameterInfo, PluginInfo};
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 
-use crate::plugin_info::{ParameterInfo, PluginInfo};
 use crate::scanner::ClapScanner;
 
 struct SharedState {
@@ -524,6 +524,7 @@ impl ClapPluginHost {
             timestamp_ns: input.timestamp_ns.clone(),
             frame_index: input.frame_index.clone(),
             sample_rate: input.sample_rate,
+            channel_layout: input.channel_layout.clone(),
         })
     }
 
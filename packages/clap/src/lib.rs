@@ -15,12 +15,6 @@ pub mod clap_effect;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub mod host;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
-pub mod parameter_automation;
-#[cfg(any(target_os = "macos", target_os = "ios"))]
-pub mod parameter_modulation;
-#[cfg(any(target_os = "macos", target_os = "ios"))]
-pub mod plugin_info;
-#[cfg(any(target_os = "macos", target_os = "ios"))]
 pub mod scanner;
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -28,13 +22,14 @@ pub use clap_effect::ClapEffectProcessor;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub use host::ClapPluginHost;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
-pub use parameter_automation::{ClapParameterControl, ParameterAutomation};
-#[cfg(any(target_os = "macos", target_os = "ios"))]
-pub use parameter_modulation::{LfoWaveform, ParameterModulator};
-#[cfg(any(target_os = "macos", target_os = "ios"))]
-pub use plugin_info::{ParameterInfo, PluginInfo};
-#[cfg(any(target_os = "macos", target_os = "ios"))]
 pub use scanner::{ClapPluginInfo, ClapScanner};
+// The format-agnostic hosting surface lives in `streamlib-audio`, shared with
+// the VST3 host.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub use streamlib_audio::plugin_host::{
+    LfoWaveform, ParameterAutomation, ParameterInfo, ParameterModulator, PluginInfo,
+    PluginParameterControl,
+};
 
 pub use _generated_::ClapEffectConfig;
 
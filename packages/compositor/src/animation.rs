@@ -0,0 +1,249 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Keyframe animation curves sampled against elapsed runtime-clock time.
+//!
+//! A curve holds keyframes sorted by time. Between two keyframes the value
+//! is interpolated with the *later* keyframe's [`Easing`] ("arrive at this
+//! value, easing out"); before the first keyframe the first value holds,
+//! and past the last one [`Repeat`] decides whether the curve holds,
+//! wraps or plays back in reverse.
+
+use std::time::Duration;
+
+/// Linear interpolation between two values of an animated type.
+pub trait Lerp: Copy {
+    /// The value `t` (0..1) of the way from `from` to `to`.
+    fn lerp(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+/// Timing function of one curve segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Cubic, starting slow.
+    EaseIn,
+    /// Cubic, ending slow.
+    EaseOut,
+    /// Cubic, slow at both ends.
+    EaseInOut,
+    /// Holds the previous value, then jumps at the keyframe.
+    Step,
+}
+
+impl Easing {
+    /// Eased progress for linear progress `t` (0..1).
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            Self::Step => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// What a curve does past its last keyframe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Repeat {
+    /// Hold the last value.
+    #[default]
+    Once,
+    /// Start over from the first keyframe.
+    Loop,
+    /// Play backwards to the first keyframe, then forwards again.
+    PingPong,
+}
+
+/// A value the curve passes through at `at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<T> {
+    /// Offset from the start of the animation.
+    pub at: Duration,
+    pub value: T,
+    /// Timing of the segment arriving at this keyframe.
+    pub easing: Easing,
+}
+
+/// Keyframes sampled over time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationCurve<T> {
+    keyframes: Vec<Keyframe<T>>,
+    repeat: Repeat,
+}
+
+impl<T: Lerp> AnimationCurve<T> {
+    /// A curve through `keyframes` (in any order). `None` without
+    /// keyframes.
+    pub fn new(mut keyframes: Vec<Keyframe<T>>, repeat: Repeat) -> Option<Self> {
+        if keyframes.is_empty() {
+            return None;
+        }
+        keyframes.sort_by_key(|keyframe| keyframe.at);
+        Some(Self { keyframes, repeat })
+    }
+
+    /// Time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last().map_or(Duration::ZERO, |keyframe| keyframe.at)
+    }
+
+    /// Whether the value no longer changes after `elapsed`.
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        self.repeat == Repeat::Once && elapsed >= self.duration()
+    }
+
+    /// The curve's value `elapsed` after the animation started.
+    pub fn sample(&self, elapsed: Duration) -> T {
+        let duration = self.duration();
+        let time = if duration.is_zero() {
+            Duration::ZERO
+        } else {
+            match self.repeat {
+                Repeat::Once => elapsed.min(duration),
+                Repeat::Loop if elapsed >= duration => nanos(elapsed.as_nanos() % duration.as_nanos()),
+                Repeat::Loop => elapsed,
+                Repeat::PingPong => {
+                    let period = duration.as_nanos() * 2;
+                    let phase = elapsed.as_nanos() % period;
+                    nanos(if phase > duration.as_nanos() {
+                        period - phase
+                    } else {
+                        phase
+                    })
+                }
+            }
+        };
+
+        let next = self.keyframes.partition_point(|keyframe| keyframe.at <= time);
+        if next == 0 {
+            return self.keyframes[0].value;
+        }
+        let from = &self.keyframes[next - 1];
+        let Some(to) = self.keyframes.get(next) else {
+            return from.value;
+        };
+        let span = (to.at - from.at).as_secs_f32();
+        let progress = (time - from.at).as_secs_f32() / span;
+        T::lerp(from.value, to.value, to.easing.apply(progress))
+    }
+}
+
+fn nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn keyframe(at_ms: u64, value: f32, easing: Easing) -> Keyframe<f32> {
+        Keyframe {
+            at: ms(at_ms),
+            value,
+            easing,
+        }
+    }
+
+    fn ramp(repeat: Repeat) -> AnimationCurve<f32> {
+        AnimationCurve::new(
+            vec![
+                keyframe(1000, 10.0, Easing::Linear),
+                keyframe(0, 0.0, Easing::Linear),
+            ],
+            repeat,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn easings_hit_their_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Step,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(Easing::Step.apply(0.99), 0.0);
+    }
+
+    #[test]
+    fn once_interpolates_then_holds() {
+        let curve = ramp(Repeat::Once);
+        assert_eq!(curve.duration(), ms(1000));
+        assert_eq!(curve.sample(ms(0)), 0.0);
+        assert_eq!(curve.sample(ms(250)), 2.5);
+        assert_eq!(curve.sample(ms(5000)), 10.0);
+        assert!(!curve.is_finished(ms(999)));
+        assert!(curve.is_finished(ms(1000)));
+    }
+
+    #[test]
+    fn loop_wraps_and_ping_pong_reverses() {
+        let looped = ramp(Repeat::Loop);
+        assert_eq!(looped.sample(ms(1250)), 2.5);
+        assert!(!looped.is_finished(ms(5000)));
+
+        let ping_pong = ramp(Repeat::PingPong);
+        assert_eq!(ping_pong.sample(ms(1000)), 10.0);
+        assert_eq!(ping_pong.sample(ms(1250)), 7.5);
+        assert_eq!(ping_pong.sample(ms(2000)), 0.0);
+        assert_eq!(ping_pong.sample(ms(2250)), 2.5);
+    }
+
+    #[test]
+    fn first_value_holds_before_the_first_keyframe() {
+        let curve = AnimationCurve::new(
+            vec![
+                keyframe(500, 1.0, Easing::Linear),
+                keyframe(1500, 3.0, Easing::Step),
+            ],
+            Repeat::Once,
+        )
+        .unwrap();
+        assert_eq!(curve.sample(ms(100)), 1.0);
+        // Step holds the previous value until the keyframe itself.
+        assert_eq!(curve.sample(ms(1499)), 1.0);
+        assert_eq!(curve.sample(ms(1500)), 3.0);
+    }
+
+    #[test]
+    fn degenerate_curves() {
+        assert!(AnimationCurve::<f32>::new(Vec::new(), Repeat::Loop).is_none());
+        let single =
+            AnimationCurve::new(vec![keyframe(0, 4.0, Easing::Linear)], Repeat::Loop).unwrap();
+        assert_eq!(single.sample(ms(123)), 4.0);
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Compositor (Linux) — composites up to eight video inputs on the GPU.
+//!
+//! Every frame on `layer_0` produces an output frame; the other inputs
+//! contribute the latest frame they delivered, so they may run at any
+//! rate and be linked or unlinked while the graph runs. A layer whose
+//! input has gone quiet for `stale_after_ms` is hidden. Per-layer
+//! transforms are resolved on the CPU (see [`crate::layer_transform`]),
+//! sampled from their keyframe curves at the time elapsed on the runtime
+//! clock, and written to a host-mapped layer buffer; one compute dispatch
+//! then blends every visible layer in z-order into the next slot of an
+//! RGBA8 output ring.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRegistration, TextureRing, TextureUsages, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::tatolab__compositor::compositor_config::{
+    Blend, Easing as ConfigEasing, Layer, Repeat as ConfigRepeat,
+};
+use crate::_generated_::{CompositorConfig, VideoFrame};
+use crate::animation::{AnimationCurve, Easing, Keyframe, Repeat};
+use crate::layer_transform::{BlendMode, LayerPlacement, LayerTransform};
+
+const COMPOSITOR_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/compositor.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::storage_buffer(1),
+    ComputeBindingSpec::sampled_texture(2),
+    ComputeBindingSpec::sampled_texture(3),
+    ComputeBindingSpec::sampled_texture(4),
+    ComputeBindingSpec::sampled_texture(5),
+    ComputeBindingSpec::sampled_texture(6),
+    ComputeBindingSpec::sampled_texture(7),
+    ComputeBindingSpec::sampled_texture(8),
+    ComputeBindingSpec::sampled_texture(9),
+];
+
+/// Binding of the first layer texture; layer `i` in draw order is at
+/// `FIRST_LAYER_BINDING + i`.
+const FIRST_LAYER_BINDING: u32 = 2;
+
+/// Matches `compositor.comp`'s `MAX_LAYERS`.
+const MAX_LAYERS: usize = 8;
+
+/// Input ports, indexed by layer.
+const LAYER_INPUTS: [&str; MAX_LAYERS] = [
+    "layer_0", "layer_1", "layer_2", "layer_3", "layer_4", "layer_5", "layer_6", "layer_7",
+];
+
+/// Matches `compositor.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+const DEFAULT_STALE_AFTER_MS: u32 = 1000;
+
+/// Opaque black.
+const DEFAULT_BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// Push constants of `compositor.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CompositorPushConstants {
+    width: u32,
+    height: u32,
+    layer_count: u32,
+    _pad: u32,
+    background: [f32; 4],
+}
+
+/// A layer's resolved config.
+#[derive(Debug, Clone)]
+struct LayerSettings {
+    z: i32,
+    transform: LayerTransform,
+    blend: BlendMode,
+    animation: Option<AnimationCurve<LayerTransform>>,
+}
+
+impl LayerSettings {
+    /// Fitted, centered and stacked in input order.
+    fn unconfigured(index: usize) -> Self {
+        Self {
+            z: index as i32,
+            transform: LayerTransform::default(),
+            blend: BlendMode::Normal,
+            animation: None,
+        }
+    }
+
+    fn from_config(index: usize, layer: &Layer) -> Self {
+        let defaults = LayerTransform::default();
+        let transform = LayerTransform {
+            x: layer.x.unwrap_or(defaults.x),
+            y: layer.y.unwrap_or(defaults.y),
+            scale: layer.scale.unwrap_or(defaults.scale),
+            rotation_deg: layer.rotation_deg.unwrap_or(defaults.rotation_deg),
+            opacity: layer.opacity.unwrap_or(defaults.opacity),
+        };
+        let keyframes = layer
+            .keyframes
+            .iter()
+            .flatten()
+            .map(|keyframe| Keyframe {
+                at: Duration::from_millis(u64::from(keyframe.at_ms)),
+                value: LayerTransform {
+                    x: keyframe.x.unwrap_or(transform.x),
+                    y: keyframe.y.unwrap_or(transform.y),
+                    scale: keyframe.scale.unwrap_or(transform.scale),
+                    rotation_deg: keyframe.rotation_deg.unwrap_or(transform.rotation_deg),
+                    opacity: keyframe.opacity.unwrap_or(transform.opacity),
+                },
+                easing: match keyframe.easing {
+                    Some(ConfigEasing::Linear) | None => Easing::Linear,
+                    Some(ConfigEasing::EaseIn) => Easing::EaseIn,
+                    Some(ConfigEasing::EaseOut) => Easing::EaseOut,
+                    Some(ConfigEasing::EaseInOut) => Easing::EaseInOut,
+                    Some(ConfigEasing::Step) => Easing::Step,
+                },
+            })
+            .collect();
+        let repeat = match layer.repeat {
+            Some(ConfigRepeat::Once) | None => Repeat::Once,
+            Some(ConfigRepeat::Loop) => Repeat::Loop,
+            Some(ConfigRepeat::PingPong) => Repeat::PingPong,
+        };
+        Self {
+            z: layer.z.unwrap_or(index as i32),
+            transform,
+            blend: match layer.blend {
+                Some(Blend::Normal) | None => BlendMode::Normal,
+                Some(Blend::Add) => BlendMode::Add,
+                Some(Blend::Multiply) => BlendMode::Multiply,
+                Some(Blend::Screen) => BlendMode::Screen,
+            },
+            animation: AnimationCurve::new(keyframes, repeat),
+        }
+    }
+
+    fn transform_at(&self, elapsed: Duration) -> LayerTransform {
+        self.animation
+            .as_ref()
+            .map_or(self.transform, |curve| curve.sample(elapsed))
+    }
+}
+
+/// Config resolved once per setup or config update.
+#[derive(Debug, Clone)]
+struct ResolvedConfig {
+    layers: Vec<LayerSettings>,
+    background: [f32; 4],
+    stale_after: Option<Duration>,
+}
+
+fn resolve_config(config: &CompositorConfig) -> Result<ResolvedConfig> {
+    let mut layers: Vec<LayerSettings> = (0..MAX_LAYERS).map(LayerSettings::unconfigured).collect();
+    for layer in config.layers.iter().flatten() {
+        let index = LAYER_INPUTS
+            .iter()
+            .position(|input| *input == layer.input)
+            .ok_or_else(|| {
+                Error::Configuration(format!(
+                    "Compositor: unknown layer input '{}' (expected layer_0 … layer_{})",
+                    layer.input,
+                    MAX_LAYERS - 1
+                ))
+            })?;
+        layers[index] = LayerSettings::from_config(index, layer);
+    }
+    let background = match config.background.as_deref() {
+        None => DEFAULT_BACKGROUND,
+        Some(&[r, g, b, a]) => [r, g, b, a],
+        Some(other) => {
+            return Err(Error::Configuration(format!(
+                "Compositor: background must be [r, g, b, a], got {} values",
+                other.len()
+            )));
+        }
+    };
+    if config.width == Some(0) || config.height == Some(0) {
+        return Err(Error::Configuration(
+            "Compositor: width and height must be > 0".into(),
+        ));
+    }
+    let stale_after = match config.stale_after_ms.unwrap_or(DEFAULT_STALE_AFTER_MS) {
+        0 => None,
+        ms => Some(Duration::from_millis(u64::from(ms))),
+    };
+    Ok(ResolvedConfig {
+        layers,
+        background,
+        stale_after,
+    })
+}
+
+/// Latest frame delivered on a layer input and when it arrived on the
+/// runtime clock.
+struct LatestFrame {
+    frame: VideoFrame,
+    received_at: Duration,
+}
+
+/// A layer resolved for one output frame.
+struct DrawLayer {
+    surface_id: String,
+    registration: TextureRegistration,
+    placement: LayerPlacement,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/compositor/Compositor",
+    description = "Composites up to eight video inputs into one frame on the GPU. Each layer has a position, scale, rotation, opacity, blend mode (normal, add, multiply, screen) and z-order, optionally animated with keyframes on the runtime clock. Inputs can be linked and unlinked while running; layer_0 drives the output rate.",
+    execution = reactive,
+    config = crate::_generated_::CompositorConfig,
+    input("layer_0", "@tatolab/core/VideoFrame", description = "Base layer; each of its frames produces an output frame"),
+    input("layer_1", "@tatolab/core/VideoFrame", optional = true, description = "Layer 1"),
+    input("layer_2", "@tatolab/core/VideoFrame", optional = true, description = "Layer 2"),
+    input("layer_3", "@tatolab/core/VideoFrame", optional = true, description = "Layer 3"),
+    input("layer_4", "@tatolab/core/VideoFrame", optional = true, description = "Layer 4"),
+    input("layer_5", "@tatolab/core/VideoFrame", optional = true, description = "Layer 5"),
+    input("layer_6", "@tatolab/core/VideoFrame", optional = true, description = "Layer 6"),
+    input("layer_7", "@tatolab/core/VideoFrame", optional = true, description = "Layer 7"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Composited frames (RGBA8)"),
+)]
+pub struct CompositorProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-mapped `[LayerPlacement; MAX_LAYERS]`.
+    layer_buffer: Option<StorageBuffer>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    resolved: Option<ResolvedConfig>,
+    /// Latest frame per layer input; `layer_0`'s slot is unused.
+    latest: [Option<LatestFrame>; MAX_LAYERS],
+    /// Runtime-clock time the keyframe animations started at.
+    animation_epoch: Duration,
+    frames_composited: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for CompositorProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let resolved = resolve_config(&self.config)?;
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "compositor",
+            spv: COMPOSITOR_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<CompositorPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("compositor")?);
+        let layer_buffer = full
+            .acquire_storage_buffer(std::mem::size_of::<[LayerPlacement; MAX_LAYERS]>() as u64)?;
+        if layer_buffer.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Compositor: layer buffer is not host-mapped".into(),
+            ));
+        }
+        self.layer_buffer = Some(layer_buffer);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        let configured = self.config.layers.as_ref().map_or(0, Vec::len);
+        self.resolved = Some(resolved);
+        self.animation_epoch = MediaClock::now();
+        tracing::info!("[Compositor] Setup ({} configured layers)", configured);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.layer_buffer = None;
+        self.output_ring = None;
+        self.latest = Default::default();
+        tracing::info!(
+            "[Compositor] Teardown ({} frames composited)",
+            self.frames_composited
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.resolved = Some(resolve_config(&self.config)?);
+        self.animation_epoch = MediaClock::now();
+        tracing::info!("[Compositor] Config updated; animations restarted");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let now = MediaClock::now();
+        for (index, port) in LAYER_INPUTS.iter().enumerate().skip(1) {
+            while self.inputs.has_data(port) {
+                let frame: VideoFrame = self.inputs.read(port)?;
+                self.latest[index] = Some(LatestFrame {
+                    frame,
+                    received_at: now,
+                });
+            }
+        }
+        if !self.inputs.has_data(LAYER_INPUTS[0]) {
+            return Ok(());
+        }
+        let mut base: VideoFrame = self.inputs.read(LAYER_INPUTS[0])?;
+        // Composite only the newest base frame when several queued up.
+        while self.inputs.has_data(LAYER_INPUTS[0]) {
+            base = self.inputs.read(LAYER_INPUTS[0])?;
+        }
+        let output = self.composite(&base, now)?;
+        self.frames_composited += 1;
+        self.outputs.write("video_out", &output)
+    }
+}
+
+impl CompositorProcessor::Processor {
+    /// Composite `base` and the latest frame of every other layer into the
+    /// next output slot.
+    fn composite(&mut self, base: &VideoFrame, now: Duration) -> Result<VideoFrame> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("Compositor: GPU context not initialized".into())
+        })?;
+        let (Some(kernel), Some(recorder), Some(layer_buffer), Some(resolved)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.layer_buffer.as_ref(),
+            self.resolved.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "Compositor: kernel not initialized".into(),
+            ));
+        };
+        let width = self.config.width.unwrap_or(base.width);
+        let height = self.config.height.unwrap_or(base.height);
+        let elapsed = now.saturating_sub(self.animation_epoch);
+
+        // Visible layers, bottom first.
+        let mut order: Vec<usize> = (0..MAX_LAYERS)
+            .filter(|&index| {
+                index == 0
+                    || self.latest[index].as_ref().is_some_and(|latest| {
+                        resolved.stale_after.is_none_or(|stale_after| {
+                            now.saturating_sub(latest.received_at) < stale_after
+                        })
+                    })
+            })
+            .collect();
+        order.sort_by_key(|&index| (resolved.layers[index].z, index));
+
+        // The base frame must resolve; its texture also fills unused
+        // sampler bindings.
+        let base_registration = gpu.resolve_texture_registration_by_surface_id(
+            &base.surface_id,
+            base.texture_layout,
+            base.width,
+            base.height,
+        )?;
+        let mut draw: Vec<DrawLayer> = Vec::with_capacity(order.len());
+        for index in order {
+            let frame = match index {
+                0 => base,
+                _ => match self.latest[index].as_ref() {
+                    Some(latest) => &latest.frame,
+                    None => continue,
+                },
+            };
+            let settings = &resolved.layers[index];
+            let Some(placement) = settings.transform_at(elapsed).placement(
+                (frame.width, frame.height),
+                (width, height),
+                settings.blend,
+            ) else {
+                continue;
+            };
+            let registration = if index == 0 {
+                base_registration.clone()
+            } else {
+                match gpu.resolve_texture_registration_by_surface_id(
+                    &frame.surface_id,
+                    frame.texture_layout,
+                    frame.width,
+                    frame.height,
+                ) {
+                    Ok(registration) => registration,
+                    Err(e) => {
+                        // The producer released the surface, e.g. after its
+                        // link was removed; hide the layer until it sends
+                        // again.
+                        tracing::warn!(
+                            "[Compositor] Dropping {}: surface '{}' is unavailable: {}",
+                            LAYER_INPUTS[index],
+                            frame.surface_id,
+                            e
+                        );
+                        self.latest[index] = None;
+                        continue;
+                    }
+                }
+            };
+            draw.push(DrawLayer {
+                surface_id: frame.surface_id.clone(),
+                registration,
+                placement,
+            });
+        }
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        // SAFETY: `layer_buffer` is a persistently-mapped host-visible
+        // allocation of `MAX_LAYERS` placements (checked non-null in
+        // setup), and the previous submit has completed, so the GPU is not
+        // reading it. Host writes before the submit are visible to the
+        // kernel.
+        unsafe {
+            let placements = layer_buffer.mapped_ptr() as *mut LayerPlacement;
+            for (i, layer) in draw.iter().enumerate() {
+                placements.add(i).write(layer.placement);
+            }
+        }
+        kernel.set_storage_image(0, &slot.texture)?;
+        kernel.set_storage_buffer_storage(1, layer_buffer)?;
+        // Every sampler must be bound; unused ones repeat the base texture.
+        for i in 0..MAX_LAYERS {
+            let texture = draw.get(i).map_or(base_registration.texture(), |layer| {
+                layer.registration.texture()
+            });
+            kernel.set_sampled_texture(FIRST_LAYER_BINDING + i as u32, texture)?;
+        }
+        kernel.set_push_constants_value(&CompositorPushConstants {
+            width,
+            height,
+            layer_count: draw.len() as u32,
+            _pad: 0,
+            background: resolved.background,
+        })?;
+
+        recorder.begin()?;
+        // Every bound texture is sampled; the same surface may feed several
+        // layers, so transition each one once.
+        let mut sampled: Vec<(&str, &TextureRegistration)> =
+            vec![(base.surface_id.as_str(), &base_registration)];
+        for layer in &draw {
+            if !sampled
+                .iter()
+                .any(|(surface_id, _)| *surface_id == layer.surface_id)
+            {
+                sampled.push((layer.surface_id.as_str(), &layer.registration));
+            }
+        }
+        for (_, registration) in &sampled {
+            let current_layout = registration.current_layout();
+            if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+                recorder.record_image_barrier(
+                    registration.texture(),
+                    current_layout,
+                    VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                    VulkanStage::ALL_COMMANDS,
+                    VulkanStage::COMPUTE_SHADER,
+                    VulkanAccess::MEMORY_WRITE,
+                    VulkanAccess::SHADER_SAMPLED_READ,
+                )?;
+            }
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        let sampled: Vec<&TextureRegistration> = sampled
+            .iter()
+            .map(|(_, registration)| *registration)
+            .collect();
+        recorder.finish_compute_output(&slot_registration, &sampled)?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: base.timestamp_ns.clone(),
+            fps: base.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            // Layers are blended as encoded; the output keeps the base
+            // layer's signal description.
+            color_info: base.color_info.clone(),
+            mastering_display: None,
+            content_light: None,
+            // Layers are resampled onto the canvas, which blends fields.
+            field_order: None,
+        })
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Layer placement: where a layer lands in the output and how the
+//! compositor kernel maps output pixels back into the layer's texture.
+//!
+//! A layer is first fitted into the output preserving its aspect ratio
+//! (letterboxed, like `object-fit: contain`), then scaled and rotated
+//! clockwise about its center, which sits at `(x, y)` in output
+//! coordinates normalized to 0..1 with the origin at the top-left.
+
+use crate::animation::Lerp;
+
+/// How a layer combines with what is beneath it. Values match
+/// `compositor.comp`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BlendMode {
+    /// Source over.
+    #[default]
+    Normal = 0,
+    /// Additive, clamped.
+    Add = 1,
+    Multiply = 2,
+    Screen = 3,
+}
+
+/// A layer's animatable transform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerTransform {
+    /// Horizontal center, 0 (left edge) to 1 (right edge).
+    pub x: f32,
+    /// Vertical center, 0 (top edge) to 1 (bottom edge).
+    pub y: f32,
+    /// Size relative to the aspect-preserving fit.
+    pub scale: f32,
+    /// Clockwise rotation in degrees.
+    pub rotation_deg: f32,
+    /// 0 (transparent) to 1 (opaque); multiplies the layer's own alpha.
+    pub opacity: f32,
+}
+
+impl Default for LayerTransform {
+    /// Centered, fitted, upright and opaque.
+    fn default() -> Self {
+        Self {
+            x: 0.5,
+            y: 0.5,
+            scale: 1.0,
+            rotation_deg: 0.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl Lerp for LayerTransform {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        Self {
+            x: f32::lerp(from.x, to.x, t),
+            y: f32::lerp(from.y, to.y, t),
+            scale: f32::lerp(from.scale, to.scale, t),
+            rotation_deg: f32::lerp(from.rotation_deg, to.rotation_deg, t),
+            opacity: f32::lerp(from.opacity, to.opacity, t),
+        }
+    }
+}
+
+/// One layer's entry in the kernel's layer buffer (std430 `Layer` in
+/// `compositor.comp`). Texture UV for output pixel `p` is
+/// `(dot(uv_from_pixel.xy, p), dot(uv_from_pixel.zw, p)) + uv_offset`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerPlacement {
+    /// Row-major 2x2 matrix from output pixels to texture UV.
+    pub uv_from_pixel: [f32; 4],
+    pub uv_offset: [f32; 2],
+    pub opacity: f32,
+    pub blend: u32,
+}
+
+impl LayerTransform {
+    /// Kernel placement of a `source`-sized layer in an `output`-sized
+    /// frame. `None` when the layer covers nothing (transparent, zero or
+    /// negative scale, or an empty source).
+    pub fn placement(
+        &self,
+        source: (u32, u32),
+        output: (u32, u32),
+        blend: BlendMode,
+    ) -> Option<LayerPlacement> {
+        let opacity = self.opacity.clamp(0.0, 1.0);
+        if opacity <= 0.0 || self.scale <= 0.0 || source.0 == 0 || source.1 == 0 {
+            return None;
+        }
+        let (source_w, source_h) = (source.0 as f32, source.1 as f32);
+        let (output_w, output_h) = (output.0 as f32, output.1 as f32);
+        let fit = (output_w / source_w).min(output_h / source_h);
+        let layer_w = source_w * fit * self.scale;
+        let layer_h = source_h * fit * self.scale;
+        let center = (self.x * output_w, self.y * output_h);
+
+        // Undo the clockwise rotation (y-down), then normalize by the
+        // layer size: uv = S⁻¹ · R(-θ) · (p - center) + 0.5.
+        let (sin, cos) = self.rotation_deg.to_radians().sin_cos();
+        let m = [cos / layer_w, sin / layer_w, -sin / layer_h, cos / layer_h];
+        Some(LayerPlacement {
+            uv_from_pixel: m,
+            uv_offset: [
+                0.5 - (m[0] * center.0 + m[1] * center.1),
+                0.5 - (m[2] * center.0 + m[3] * center.1),
+            ],
+            opacity,
+            blend: blend as u32,
+        })
+    }
+}
+
+impl LayerPlacement {
+    /// Texture UV for output pixel coordinates `(px, py)`.
+    pub fn uv(&self, px: f32, py: f32) -> (f32, f32) {
+        let m = &self.uv_from_pixel;
+        (
+            m[0] * px + m[1] * py + self.uv_offset[0],
+            m[2] * px + m[3] * py + self.uv_offset[1],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_uv(placement: &LayerPlacement, pixel: (f32, f32), expected: (f32, f32)) {
+        let (u, v) = placement.uv(pixel.0, pixel.1);
+        assert!(
+            (u - expected.0).abs() < 1e-4 && (v - expected.1).abs() < 1e-4,
+            "pixel {pixel:?}: uv ({u}, {v}), expected {expected:?}"
+        );
+    }
+
+    #[test]
+    fn default_transform_fills_a_same_aspect_output() {
+        let placement = LayerTransform::default()
+            .placement((1280, 720), (1920, 1080), BlendMode::Normal)
+            .unwrap();
+        assert_uv(&placement, (0.0, 0.0), (0.0, 0.0));
+        assert_uv(&placement, (1920.0, 1080.0), (1.0, 1.0));
+        assert_uv(&placement, (960.0, 540.0), (0.5, 0.5));
+    }
+
+    #[test]
+    fn mismatched_aspect_is_letterboxed() {
+        // 4:3 in 16:9 — pillarboxed, 1440 px wide.
+        let placement = LayerTransform::default()
+            .placement((640, 480), (1920, 1080), BlendMode::Normal)
+            .unwrap();
+        assert_uv(&placement, (240.0, 0.0), (0.0, 0.0));
+        assert_uv(&placement, (1680.0, 1080.0), (1.0, 1.0));
+    }
+
+    #[test]
+    fn picture_in_picture_position_and_scale() {
+        let transform = LayerTransform {
+            x: 0.75,
+            y: 0.25,
+            scale: 0.25,
+            ..LayerTransform::default()
+        };
+        let placement = transform
+            .placement((1920, 1080), (1920, 1080), BlendMode::Screen)
+            .unwrap();
+        assert_eq!(placement.blend, BlendMode::Screen as u32);
+        // 480x270 centered at (1440, 270).
+        assert_uv(&placement, (1200.0, 135.0), (0.0, 0.0));
+        assert_uv(&placement, (1680.0, 405.0), (1.0, 1.0));
+    }
+
+    #[test]
+    fn rotation_is_clockwise_about_the_center() {
+        let transform = LayerTransform {
+            rotation_deg: 90.0,
+            ..LayerTransform::default()
+        };
+        let placement = transform
+            .placement((100, 100), (100, 100), BlendMode::Normal)
+            .unwrap();
+        // Turned clockwise, the layer's top edge faces right.
+        assert_uv(&placement, (100.0, 50.0), (0.5, 0.0));
+        assert_uv(&placement, (50.0, 100.0), (1.0, 0.5));
+    }
+
+    #[test]
+    fn invisible_layers_have_no_placement() {
+        let size = (16, 16);
+        for transform in [
+            LayerTransform {
+                opacity: 0.0,
+                ..LayerTransform::default()
+            },
+            LayerTransform {
+                scale: 0.0,
+                ..LayerTransform::default()
+            },
+        ] {
+            assert!(transform.placement(size, size, BlendMode::Normal).is_none());
+        }
+        assert!(
+            LayerTransform::default()
+                .placement((0, 16), size, BlendMode::Normal)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn placement_matches_the_kernel_layout() {
+        assert_eq!(std::mem::size_of::<LayerPlacement>(), 32);
+        assert_eq!(std::mem::offset_of!(LayerPlacement, uv_offset), 16);
+        assert_eq!(std::mem::offset_of!(LayerPlacement, opacity), 24);
+        assert_eq!(std::mem::offset_of!(LayerPlacement, blend), 28);
+    }
+}
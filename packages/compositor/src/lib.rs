@@ -0,0 +1,45 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/compositor` — multi-layer video compositing. `Compositor`
+//! places up to eight inputs with per-layer transforms, blend modes and
+//! z-order, animated by [`animation::AnimationCurve`]s on the runtime
+//! clock. `Multiview` tiles up to sixteen inputs into a labeled
+//! control-room monitor with audio meters and tally borders.
+//! `VideoSwitcher` cuts and transitions between program and preview
+//! buses and broadcasts the resulting tally.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod animation;
+pub mod layer_transform;
+pub mod multiview_label;
+pub mod multiview_layout;
+pub mod switcher_bus;
+
+// The three processors blend layers and tiles on the GPU through
+// `sdk::rhi`, which is Linux-only; animation, layout, label and bus
+// state stay portable.
+#[cfg(target_os = "linux")]
+pub mod compositor;
+#[cfg(target_os = "linux")]
+pub mod multiview;
+#[cfg(target_os = "linux")]
+pub mod video_switcher;
+
+#[cfg(target_os = "linux")]
+pub use compositor::CompositorProcessor;
+#[cfg(target_os = "linux")]
+pub use multiview::MultiviewProcessor;
+#[cfg(target_os = "linux")]
+pub use video_switcher::VideoSwitcherProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::CompositorProcessor::Processor,
+    crate::MultiviewProcessor::Processor,
+    crate::VideoSwitcherProcessor::Processor,
+);
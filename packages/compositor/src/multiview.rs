@@ -0,0 +1,795 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Multiview (Linux) — a control-room monitor of up to sixteen inputs.
+//!
+//! Each video input keeps its latest frame, and each audio input feeds
+//! the [`PeakMeter`] of the tile with the same index. Whenever an input
+//! delivers and at least one output interval has passed, one compute
+//! dispatch draws every tile — tally border, letterboxed picture, meter
+//! and label — into the next slot of an RGBA8 output ring. Tile geometry
+//! comes from [`crate::multiview_layout`]; labels are rasterized on the
+//! CPU once per layout (see [`crate::multiview_label`]) and uploaded to a
+//! host-mapped coverage buffer.
+//!
+//! A tile's border shows the higher of its configured tally and the live
+//! tally from the `tally` input, where a VideoSwitcher reports which of
+//! its inputs are on program and preview by the same index as `video_N`.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::context::{
+    FontFace, GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRegistration, TextureRing, TextureUsages, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::tatolab__compositor::multiview_config::Tally as ConfigTally;
+use crate::_generated_::{AudioFrame, MultiviewConfig, TallyState, VideoFrame};
+use crate::multiview_label::LabelRasterizer;
+use crate::multiview_layout::{
+    Grid, GridCell, MAX_TILES, PeakMeter, Rect, Tally, TileGeometry, tile_rects,
+};
+
+const MULTIVIEW_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/multiview.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::storage_buffer(1),
+    ComputeBindingSpec::storage_buffer(2),
+    ComputeBindingSpec::sampled_texture(3),
+    ComputeBindingSpec::sampled_texture(4),
+    ComputeBindingSpec::sampled_texture(5),
+    ComputeBindingSpec::sampled_texture(6),
+    ComputeBindingSpec::sampled_texture(7),
+    ComputeBindingSpec::sampled_texture(8),
+    ComputeBindingSpec::sampled_texture(9),
+    ComputeBindingSpec::sampled_texture(10),
+    ComputeBindingSpec::sampled_texture(11),
+    ComputeBindingSpec::sampled_texture(12),
+    ComputeBindingSpec::sampled_texture(13),
+    ComputeBindingSpec::sampled_texture(14),
+    ComputeBindingSpec::sampled_texture(15),
+    ComputeBindingSpec::sampled_texture(16),
+    ComputeBindingSpec::sampled_texture(17),
+    ComputeBindingSpec::sampled_texture(18),
+];
+
+/// Binding of tile 0's picture; tile `i` samples `FIRST_PICTURE_BINDING + i`.
+const FIRST_PICTURE_BINDING: u32 = 3;
+
+/// Input ports, indexed by tile input.
+const VIDEO_INPUTS: [&str; MAX_TILES] = [
+    "video_0", "video_1", "video_2", "video_3", "video_4", "video_5", "video_6", "video_7",
+    "video_8", "video_9", "video_10", "video_11", "video_12", "video_13", "video_14", "video_15",
+];
+const AUDIO_INPUTS: [&str; MAX_TILES] = [
+    "audio_0", "audio_1", "audio_2", "audio_3", "audio_4", "audio_5", "audio_6", "audio_7",
+    "audio_8", "audio_9", "audio_10", "audio_11", "audio_12", "audio_13", "audio_14", "audio_15",
+];
+
+/// `multiview.comp`'s `NO_TEXTURE`.
+const NO_TEXTURE: u32 = u32::MAX;
+
+/// Meter bars a tile's layout reserves, so labels don't move when a
+/// tile's audio appears or changes channel count.
+const METER_BARS: u32 = 2;
+
+/// Matches `multiview.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Side of the texture bound to picture slots with nothing to show.
+const PLACEHOLDER_SIZE: u32 = 16;
+
+const DEFAULT_WIDTH: u32 = 1920;
+const DEFAULT_HEIGHT: u32 = 1080;
+const DEFAULT_GRID: u32 = 4;
+const DEFAULT_FPS: u32 = 30;
+const DEFAULT_BORDER_PX: u32 = 4;
+const DEFAULT_LABEL_SIZE_PX: u32 = 20;
+const DEFAULT_FONT: &str = "sans";
+const DEFAULT_STALE_AFTER_MS: u32 = 1000;
+
+/// Opaque black.
+const DEFAULT_BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// Push constants of `multiview.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MultiviewPushConstants {
+    width: u32,
+    height: u32,
+    tile_count: u32,
+    _pad: u32,
+    background: [f32; 4],
+}
+
+/// `multiview.comp`'s `Tile`, std430.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct TileParams {
+    rect: [u32; 4],
+    inner: [u32; 4],
+    picture: [f32; 4],
+    label: [u32; 4],
+    meter: [f32; 2],
+    meter_channels: u32,
+    meter_bar_width: u32,
+    tally: u32,
+    texture_slot: u32,
+    label_offset: u32,
+    _pad: u32,
+}
+
+fn rect_words(rect: Rect) -> [u32; 4] {
+    [rect.x, rect.y, rect.width, rect.height]
+}
+
+/// A tile of the resolved layout.
+#[derive(Debug, Clone)]
+struct TileSettings {
+    /// Index of the video (and audio) input shown.
+    input: usize,
+    label: String,
+    rect: Rect,
+    tally: Tally,
+}
+
+/// Config resolved once per setup or config update.
+#[derive(Debug, Clone)]
+struct ResolvedLayout {
+    width: u32,
+    height: u32,
+    border: u32,
+    label_size_px: u32,
+    background: [f32; 4],
+    frame_interval: Duration,
+    stale_after: Option<Duration>,
+    tiles: Vec<TileSettings>,
+}
+
+impl ResolvedLayout {
+    fn label_height(&self) -> u32 {
+        self.label_size_px * 3 / 2
+    }
+
+    fn geometry(&self, tile: &TileSettings, picture: Option<(u32, u32)>) -> TileGeometry {
+        TileGeometry::new(
+            tile.rect,
+            self.border,
+            picture,
+            METER_BARS,
+            self.label_height(),
+        )
+    }
+}
+
+fn resolve_layout(config: &MultiviewConfig) -> Result<ResolvedLayout> {
+    let grid = Grid {
+        width: config.width.unwrap_or(DEFAULT_WIDTH),
+        height: config.height.unwrap_or(DEFAULT_HEIGHT),
+        columns: config.columns.unwrap_or(DEFAULT_GRID),
+        rows: config.rows.unwrap_or(DEFAULT_GRID),
+    };
+    if grid.width == 0 || grid.height == 0 || grid.columns == 0 || grid.rows == 0 {
+        return Err(Error::Configuration(
+            "Multiview: width, height, columns and rows must be > 0".into(),
+        ));
+    }
+    let fps = config.fps.unwrap_or(DEFAULT_FPS);
+    if fps == 0 {
+        return Err(Error::Configuration("Multiview: fps must be > 0".into()));
+    }
+
+    let mut tiles = Vec::new();
+    let mut cells = Vec::new();
+    match &config.tiles {
+        None => {
+            let count = grid.columns.saturating_mul(grid.rows).min(MAX_TILES as u32) as usize;
+            for input in 0..count {
+                cells.push(grid.default_cell(input));
+                tiles.push((input, VIDEO_INPUTS[input].to_string(), Tally::Off));
+            }
+        }
+        Some(configured) => {
+            if configured.len() > MAX_TILES {
+                return Err(Error::Configuration(format!(
+                    "Multiview: at most {MAX_TILES} tiles, got {}",
+                    configured.len()
+                )));
+            }
+            for (index, tile) in configured.iter().enumerate() {
+                let input = VIDEO_INPUTS
+                    .iter()
+                    .position(|port| *port == tile.input)
+                    .ok_or_else(|| {
+                        Error::Configuration(format!(
+                            "Multiview: unknown tile input '{}' (expected video_0 … video_{})",
+                            tile.input,
+                            MAX_TILES - 1
+                        ))
+                    })?;
+                let default = grid.default_cell(index);
+                cells.push(GridCell {
+                    column: tile.column.unwrap_or(default.column),
+                    row: tile.row.unwrap_or(default.row),
+                    column_span: tile.column_span.unwrap_or(1),
+                    row_span: tile.row_span.unwrap_or(1),
+                });
+                let tally = match tile.tally {
+                    Some(ConfigTally::Off) | None => Tally::Off,
+                    Some(ConfigTally::Preview) => Tally::Preview,
+                    Some(ConfigTally::Program) => Tally::Program,
+                };
+                let label = tile.label.clone().unwrap_or_else(|| tile.input.clone());
+                tiles.push((input, label, tally));
+            }
+        }
+    }
+    let rects =
+        tile_rects(&grid, &cells).map_err(|e| Error::Configuration(format!("Multiview: {e}")))?;
+
+    let background = match config.background.as_deref() {
+        None => DEFAULT_BACKGROUND,
+        Some(&[r, g, b, a]) => [r, g, b, a],
+        Some(other) => {
+            return Err(Error::Configuration(format!(
+                "Multiview: background must be [r, g, b, a], got {} values",
+                other.len()
+            )));
+        }
+    };
+    let stale_after = match config.stale_after_ms.unwrap_or(DEFAULT_STALE_AFTER_MS) {
+        0 => None,
+        ms => Some(Duration::from_millis(u64::from(ms))),
+    };
+    Ok(ResolvedLayout {
+        width: grid.width,
+        height: grid.height,
+        border: config.border_px.unwrap_or(DEFAULT_BORDER_PX),
+        label_size_px: config.label_size_px.unwrap_or(DEFAULT_LABEL_SIZE_PX).max(1),
+        background,
+        frame_interval: Duration::from_secs(1) / fps,
+        stale_after,
+        tiles: tiles
+            .into_iter()
+            .zip(rects)
+            .map(|((input, label, tally), rect)| TileSettings {
+                input,
+                label,
+                rect,
+                tally,
+            })
+            .collect(),
+    })
+}
+
+/// Every tile's label bitmap, concatenated, and where each starts.
+struct LabelBitmaps {
+    pixels: Vec<u8>,
+    offsets: Vec<u32>,
+}
+
+fn rasterize_labels(rasterizer: &LabelRasterizer, layout: &ResolvedLayout) -> LabelBitmaps {
+    let mut pixels = Vec::new();
+    let mut offsets = Vec::with_capacity(layout.tiles.len());
+    for tile in &layout.tiles {
+        let label = layout.geometry(tile, None).label;
+        offsets.push(pixels.len() as u32);
+        pixels.extend(rasterizer.rasterize(&tile.label, label.width, label.height));
+    }
+    LabelBitmaps { pixels, offsets }
+}
+
+/// Latest frame delivered on a video input and when it arrived on the
+/// runtime clock.
+struct LatestFrame {
+    frame: VideoFrame,
+    received_at: Duration,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/compositor/Multiview",
+    description = "Control-room monitor: composites up to sixteen video inputs into a labeled grid on the GPU, with a peak audio meter per tile from the matching audio input and a tally border (off, preview, program), configured or live from a VideoSwitcher. Tiles can span grid cells; the grid, tiles, labels and tally are reconfigured at runtime through the config.",
+    execution = reactive,
+    config = crate::_generated_::MultiviewConfig,
+    input("video_0", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 0"),
+    input("video_1", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 1"),
+    input("video_2", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 2"),
+    input("video_3", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 3"),
+    input("video_4", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 4"),
+    input("video_5", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 5"),
+    input("video_6", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 6"),
+    input("video_7", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 7"),
+    input("video_8", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 8"),
+    input("video_9", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 9"),
+    input("video_10", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 10"),
+    input("video_11", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 11"),
+    input("video_12", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 12"),
+    input("video_13", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 13"),
+    input("video_14", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 14"),
+    input("video_15", "@tatolab/core/VideoFrame", optional = true, description = "Tile video 15"),
+    input("audio_0", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_0"),
+    input("audio_1", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_1"),
+    input("audio_2", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_2"),
+    input("audio_3", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_3"),
+    input("audio_4", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_4"),
+    input("audio_5", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_5"),
+    input("audio_6", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_6"),
+    input("audio_7", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_7"),
+    input("audio_8", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_8"),
+    input("audio_9", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_9"),
+    input("audio_10", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_10"),
+    input("audio_11", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_11"),
+    input("audio_12", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_12"),
+    input("audio_13", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_13"),
+    input("audio_14", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_14"),
+    input("audio_15", "@tatolab/core/AudioFrame", optional = true, description = "Meter audio for video_15"),
+    input("tally", "@tatolab/compositor/TallyState", optional = true, description = "Live tally from a VideoSwitcher; raises the configured border of tiles whose video index is on program or preview"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Multiview frames (RGBA8)"),
+)]
+pub struct MultiviewProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-mapped `[TileParams; MAX_TILES]`.
+    tile_buffer: Option<StorageBuffer>,
+    /// Host-mapped label coverage and its size in bytes.
+    label_buffer: Option<(StorageBuffer, u64)>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    /// Bound to picture slots without a live frame.
+    placeholder: Option<TextureRing>,
+    font: String,
+    font_chain: Vec<FontFace>,
+    rasterizer: Option<LabelRasterizer>,
+    layout: Option<ResolvedLayout>,
+    /// Labels for the current layout, uploaded before the next dispatch.
+    pending_labels: Option<LabelBitmaps>,
+    label_offsets: Vec<u32>,
+    latest: [Option<LatestFrame>; MAX_TILES],
+    meters: [PeakMeter; MAX_TILES],
+    /// Live tally by video input, from the `tally` input.
+    live_tally: [Tally; MAX_TILES],
+    last_output: Option<Duration>,
+    frames_composited: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for MultiviewProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let layout = resolve_layout(&self.config)?;
+        self.font = self
+            .config
+            .font
+            .clone()
+            .unwrap_or_else(|| DEFAULT_FONT.to_string());
+        self.font_chain = ctx.fonts().resolve(&self.font)?;
+        let rasterizer = LabelRasterizer::new(&self.font_chain, layout.label_size_px as f32)?;
+        self.pending_labels = Some(rasterize_labels(&rasterizer, &layout));
+        self.rasterizer = Some(rasterizer);
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "multiview",
+            spv: MULTIVIEW_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<MultiviewPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("multiview")?);
+        let tile_buffer =
+            full.acquire_storage_buffer(std::mem::size_of::<[TileParams; MAX_TILES]>() as u64)?;
+        if tile_buffer.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Multiview: tile buffer is not host-mapped".into(),
+            ));
+        }
+        self.tile_buffer = Some(tile_buffer);
+        self.placeholder = Some(full.create_texture_ring(
+            PLACEHOLDER_SIZE,
+            PLACEHOLDER_SIZE,
+            TextureFormat::Rgba8Unorm,
+            TextureUsages::TEXTURE_BINDING,
+            1,
+        )?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!(
+            "[Multiview] Setup ({} tiles, {}x{}, font '{}')",
+            layout.tiles.len(),
+            layout.width,
+            layout.height,
+            self.font
+        );
+        self.layout = Some(layout);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.tile_buffer = None;
+        self.label_buffer = None;
+        self.output_ring = None;
+        self.placeholder = None;
+        self.latest = Default::default();
+        tracing::info!(
+            "[Multiview] Teardown ({} frames composited)",
+            self.frames_composited
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let font = self.config.font.as_deref().unwrap_or(DEFAULT_FONT);
+        if font != self.font {
+            tracing::warn!(
+                "[Multiview] font is fixed at setup ('{}'); re-add the processor to use '{}'",
+                self.font,
+                font
+            );
+        }
+        let layout = resolve_layout(&self.config)?;
+        let resize = self
+            .layout
+            .as_ref()
+            .is_none_or(|previous| previous.label_size_px != layout.label_size_px);
+        if resize {
+            self.rasterizer = Some(LabelRasterizer::new(
+                &self.font_chain,
+                layout.label_size_px as f32,
+            )?);
+        }
+        if let Some(rasterizer) = &self.rasterizer {
+            self.pending_labels = Some(rasterize_labels(rasterizer, &layout));
+        }
+        tracing::info!("[Multiview] Layout updated ({} tiles)", layout.tiles.len());
+        self.layout = Some(layout);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let now = MediaClock::now();
+        let mut delivered = false;
+        for index in 0..MAX_TILES {
+            while self.inputs.has_data(VIDEO_INPUTS[index]) {
+                let frame: VideoFrame = self.inputs.read(VIDEO_INPUTS[index])?;
+                self.latest[index] = Some(LatestFrame {
+                    frame,
+                    received_at: now,
+                });
+                delivered = true;
+            }
+            while self.inputs.has_data(AUDIO_INPUTS[index]) {
+                let audio: AudioFrame = self.inputs.read(AUDIO_INPUTS[index])?;
+                self.meters[index].on_samples(&audio.samples, usize::from(audio.channels), now);
+                delivered = true;
+            }
+        }
+        while self.inputs.has_data("tally") {
+            let state: TallyState = self.inputs.read("tally")?;
+            self.live_tally = live_tally(&state);
+            delivered = true;
+        }
+        let Some(layout) = self.layout.as_ref() else {
+            return Ok(());
+        };
+        let due = self
+            .last_output
+            .is_none_or(|last| now.saturating_sub(last) >= layout.frame_interval);
+        if !delivered || !due {
+            return Ok(());
+        }
+        let output = self.composite(now)?;
+        self.last_output = Some(now);
+        self.frames_composited += 1;
+        self.outputs.write("video_out", &output)
+    }
+}
+
+/// Per-input tally from a switcher's state. Inputs beyond the multiview's
+/// sixteen are ignored; program wins when an input is on both.
+fn live_tally(state: &TallyState) -> [Tally; MAX_TILES] {
+    let mut tally = [Tally::Off; MAX_TILES];
+    let buses = [
+        (&state.preview, Tally::Preview),
+        (&state.program, Tally::Program),
+    ];
+    for (inputs, bus) in buses {
+        for &input in inputs {
+            if let Some(slot) = tally.get_mut(input as usize) {
+                *slot = (*slot).max(bus);
+            }
+        }
+    }
+    tally
+}
+
+impl MultiviewProcessor::Processor {
+    /// Draw every tile into the next output slot.
+    fn composite(&mut self, now: Duration) -> Result<VideoFrame> {
+        let gpu = self
+            .gpu_context
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Multiview: GPU context not initialized".into()))?;
+        let (Some(kernel), Some(recorder), Some(tile_buffer), Some(placeholder), Some(layout)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.tile_buffer.as_ref(),
+            self.placeholder.as_ref(),
+            self.layout.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "Multiview: kernel not initialized".into(),
+            ));
+        };
+        let (width, height) = (layout.width, layout.height);
+
+        // The previous submit has completed, so the label buffer is free
+        // to replace or rewrite.
+        if let Some(labels) = self.pending_labels.take() {
+            let bytes = (labels.pixels.len() as u64).max(4);
+            if self
+                .label_buffer
+                .as_ref()
+                .is_none_or(|(_, capacity)| *capacity < bytes)
+            {
+                let capacity = bytes.next_power_of_two();
+                let buffer = gpu.escalate(|full| full.acquire_storage_buffer(capacity))??;
+                if buffer.mapped_ptr().is_null() {
+                    return Err(Error::Configuration(
+                        "Multiview: label buffer is not host-mapped".into(),
+                    ));
+                }
+                self.label_buffer = Some((buffer, capacity));
+            }
+            if let Some((buffer, _)) = &self.label_buffer {
+                // SAFETY: `buffer` is a persistently-mapped host-visible
+                // allocation of at least `labels.pixels.len()` bytes
+                // (checked non-null and sized above), and the GPU is not
+                // reading it between submits.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        labels.pixels.as_ptr(),
+                        buffer.mapped_ptr(),
+                        labels.pixels.len(),
+                    );
+                }
+            }
+            self.label_offsets = labels.offsets;
+        }
+        let Some((label_buffer, _)) = self.label_buffer.as_ref() else {
+            return Err(Error::Configuration(
+                "Multiview: label buffer not allocated".into(),
+            ));
+        };
+
+        let placeholder_slot = placeholder.acquire_next();
+        let placeholder_surface_id = placeholder_slot.surface_id().to_string();
+        let placeholder_registration = gpu.resolve_texture_registration_by_surface_id(
+            &placeholder_surface_id,
+            None,
+            PLACEHOLDER_SIZE,
+            PLACEHOLDER_SIZE,
+        )?;
+
+        // Per tile: its parameters and, when it shows a live frame, the
+        // frame's texture.
+        let mut params = [TileParams::default(); MAX_TILES];
+        let mut pictures: Vec<Option<(String, TextureRegistration)>> = Vec::new();
+        let mut unavailable = Vec::new();
+        for (slot, tile) in layout.tiles.iter().enumerate() {
+            let live = self.latest[tile.input].as_ref().filter(|latest| {
+                layout
+                    .stale_after
+                    .is_none_or(|stale_after| now.saturating_sub(latest.received_at) < stale_after)
+            });
+            let picture = match live {
+                Some(latest) => match gpu.resolve_texture_registration_by_surface_id(
+                    &latest.frame.surface_id,
+                    latest.frame.texture_layout,
+                    latest.frame.width,
+                    latest.frame.height,
+                ) {
+                    Ok(registration) => Some((latest, registration)),
+                    Err(e) => {
+                        // The producer released the surface, e.g. after its
+                        // link was removed; show no signal until it sends
+                        // again.
+                        tracing::warn!(
+                            "[Multiview] Dropping {}: surface '{}' is unavailable: {}",
+                            VIDEO_INPUTS[tile.input],
+                            latest.frame.surface_id,
+                            e
+                        );
+                        unavailable.push(tile.input);
+                        None
+                    }
+                },
+                None => None,
+            };
+            let geometry = layout.geometry(
+                tile,
+                picture
+                    .as_ref()
+                    .map(|(latest, _)| (latest.frame.width, latest.frame.height)),
+            );
+            let meter = &self.meters[tile.input];
+            params[slot] = TileParams {
+                rect: rect_words(geometry.tile),
+                inner: rect_words(geometry.inner),
+                picture: geometry.picture,
+                label: rect_words(geometry.label),
+                meter: meter.levels(now),
+                meter_channels: meter.channels(),
+                meter_bar_width: geometry.meter_bar_width,
+                tally: tile.tally.max(self.live_tally[tile.input]) as u32,
+                texture_slot: if picture.is_some() {
+                    slot as u32
+                } else {
+                    NO_TEXTURE
+                },
+                label_offset: self.label_offsets.get(slot).copied().unwrap_or(0),
+                _pad: 0,
+            };
+            pictures.push(
+                picture
+                    .map(|(latest, registration)| (latest.frame.surface_id.clone(), registration)),
+            );
+        }
+        for input in unavailable {
+            self.latest[input] = None;
+        }
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        // SAFETY: `tile_buffer` is a persistently-mapped host-visible
+        // allocation of `MAX_TILES` tile parameters (checked non-null in
+        // setup), and the previous submit has completed, so the GPU is not
+        // reading it. Host writes before the submit are visible to the
+        // kernel.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                params.as_ptr(),
+                tile_buffer.mapped_ptr() as *mut TileParams,
+                MAX_TILES,
+            );
+        }
+        kernel.set_storage_image(0, &slot.texture)?;
+        kernel.set_storage_buffer_storage(1, tile_buffer)?;
+        kernel.set_storage_buffer_storage(2, label_buffer)?;
+        // Every sampler must be bound; tiles without a picture get the
+        // placeholder.
+        for i in 0..MAX_TILES {
+            let texture = pictures
+                .get(i)
+                .and_then(Option::as_ref)
+                .map_or(placeholder_registration.texture(), |(_, registration)| {
+                    registration.texture()
+                });
+            kernel.set_sampled_texture(FIRST_PICTURE_BINDING + i as u32, texture)?;
+        }
+        kernel.set_push_constants_value(&MultiviewPushConstants {
+            width,
+            height,
+            tile_count: layout.tiles.len() as u32,
+            _pad: 0,
+            background: layout.background,
+        })?;
+
+        recorder.begin()?;
+        // Every bound texture is sampled; the same surface may feed several
+        // tiles, so transition each one once.
+        let mut sampled: Vec<(&str, &TextureRegistration)> =
+            vec![(placeholder_surface_id.as_str(), &placeholder_registration)];
+        for (surface_id, registration) in pictures.iter().flatten() {
+            if !sampled.iter().any(|(seen, _)| *seen == surface_id) {
+                sampled.push((surface_id.as_str(), registration));
+            }
+        }
+        for (_, registration) in &sampled {
+            let current_layout = registration.current_layout();
+            if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+                recorder.record_image_barrier(
+                    registration.texture(),
+                    current_layout,
+                    VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                    VulkanStage::ALL_COMMANDS,
+                    VulkanStage::COMPUTE_SHADER,
+                    VulkanAccess::MEMORY_WRITE,
+                    VulkanAccess::SHADER_SAMPLED_READ,
+                )?;
+            }
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        let sampled: Vec<&TextureRegistration> = sampled
+            .iter()
+            .map(|(_, registration)| *registration)
+            .collect();
+        recorder.finish_compute_output(&slot_registration, &sampled)?;
+
+        let fps = self.config.fps.unwrap_or(DEFAULT_FPS);
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: now.as_nanos().to_string(),
+            fps: Some(fps),
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: None,
+            mastering_display: None,
+            content_light: None,
+            field_order: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_params_match_the_std430_struct() {
+        assert_eq!(std::mem::size_of::<TileParams>(), 96);
+        assert_eq!(std::mem::size_of::<MultiviewPushConstants>(), 32);
+    }
+
+    #[test]
+    fn live_tally_maps_switcher_inputs_to_tiles() {
+        let state = TallyState {
+            program: vec![0, 2],
+            preview: vec![2, 3, 40],
+            ..Default::default()
+        };
+        let tally = live_tally(&state);
+        assert_eq!(
+            tally[..5],
+            [
+                Tally::Program,
+                Tally::Off,
+                Tally::Program,
+                Tally::Preview,
+                Tally::Off
+            ]
+        );
+    }
+}
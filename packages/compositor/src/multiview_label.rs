@@ -0,0 +1,100 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tile labels — one line of text rasterized on the CPU into an R8
+//! coverage bitmap per tile, which the multiview kernel blends over the
+//! label bar.
+//!
+//! Labels change only with the layout, so each is drawn once per config
+//! rather than kept in a glyph atlas like TextOverlay's. The host
+//! resolves the font name to files through `ctx.fonts()`; each character
+//! takes the first face in the fallback chain that has it.
+
+use ab_glyph::{Font, FontVec, GlyphId, PxScale, ScaleFont, point};
+use streamlib_plugin_sdk::sdk::context::FontFace;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+pub struct LabelRasterizer {
+    faces: Vec<FontVec>,
+    scale: PxScale,
+}
+
+impl LabelRasterizer {
+    /// Load every face of `chain` at `px_size`. Faces that fail to load
+    /// are skipped; an error is returned only when none do.
+    pub fn new(chain: &[FontFace], px_size: f32) -> Result<Self> {
+        let faces: Vec<FontVec> = chain
+            .iter()
+            .filter_map(|face| {
+                let data = std::fs::read(&face.path)
+                    .map_err(|e| {
+                        tracing::warn!("[Multiview] Cannot read {}: {}", face.path.display(), e)
+                    })
+                    .ok()?;
+                FontVec::try_from_vec_and_index(data, face.face_index)
+                    .map_err(|e| {
+                        tracing::warn!("[Multiview] Cannot parse {}: {}", face.path.display(), e)
+                    })
+                    .ok()
+            })
+            .collect();
+        if faces.is_empty() {
+            return Err(Error::FontNotFound(format!(
+                "none of {} fallback face(s) could be loaded",
+                chain.len()
+            )));
+        }
+        Ok(Self {
+            faces,
+            scale: PxScale::from(px_size),
+        })
+    }
+
+    /// `text` on one line, left-aligned with a small inset and centered
+    /// vertically in a `width × height` bitmap, clipped at its edges.
+    pub fn rasterize(&self, text: &str, width: u32, height: u32) -> Vec<u8> {
+        let mut pixels = vec![0u8; width as usize * height as usize];
+        let metrics = self.faces[0].as_scaled(self.scale);
+        let text_height = metrics.ascent() - metrics.descent();
+        let baseline = ((height as f32 - text_height) / 2.0 + metrics.ascent()).round();
+        let mut pen = (self.scale.y / 3.0).round();
+        let mut previous: Option<(usize, GlyphId)> = None;
+        for c in text.chars() {
+            if pen >= width as f32 {
+                break;
+            }
+            let Some((face, id)) = self
+                .faces
+                .iter()
+                .enumerate()
+                .map(|(index, font)| (index, font.glyph_id(c)))
+                .find(|(_, id)| *id != GlyphId(0))
+            else {
+                continue;
+            };
+            let font = self.faces[face].as_scaled(self.scale);
+            if let Some((previous_face, previous_id)) = previous
+                && previous_face == face
+            {
+                pen += font.kern(previous_id, id);
+            }
+            previous = Some((face, id));
+            if let Some(outline) =
+                font.outline_glyph(id.with_scale_and_position(self.scale, point(pen, baseline)))
+            {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    let x = bounds.min.x as i64 + i64::from(gx);
+                    let y = bounds.min.y as i64 + i64::from(gy);
+                    if (0..i64::from(width)).contains(&x) && (0..i64::from(height)).contains(&y) {
+                        let index = y as usize * width as usize + x as usize;
+                        let value = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                        pixels[index] = pixels[index].max(value);
+                    }
+                });
+            }
+            pen += font.h_advance(id);
+        }
+        pixels
+    }
+}
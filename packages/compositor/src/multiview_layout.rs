@@ -0,0 +1,376 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Multiview geometry and metering: where each tile, picture, meter and
+//! label sits on the canvas, and how audio peaks turn into meter heights.
+//!
+//! The canvas is split into a `columns` × `rows` grid; a tile covers one
+//! cell or a span of them. Inside a tile's tally border the picture is
+//! letterboxed, the audio meter runs up the right edge, and the label bar
+//! sits along the bottom over the picture.
+
+use std::time::Duration;
+
+/// Tiles, and video/audio input pairs. Matches `multiview.comp`'s
+/// `MAX_TILES`.
+pub const MAX_TILES: usize = 16;
+
+/// Meter scale floor; quieter peaks read as an empty meter.
+pub const METER_FLOOR_DB: f32 = -60.0;
+
+/// Peak meter fall-back rate, after IEC 60268-10 type I.
+const METER_DECAY_DB_PER_SECOND: f32 = 20.0;
+
+/// Tally state of a tile. Values match `multiview.comp`, and order by
+/// precedence so a tile's border is the `max` of its sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Tally {
+    #[default]
+    Off = 0,
+    /// Green border.
+    Preview = 1,
+    /// Red border.
+    Program = 2,
+}
+
+/// Pixel rectangle, origin at the top-left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// Shrunk by `by` on every side, down to nothing.
+    pub fn inset(self, by: u32) -> Self {
+        let by_x = by.min(self.width / 2);
+        let by_y = by.min(self.height / 2);
+        Self {
+            x: self.x + by_x,
+            y: self.y + by_y,
+            width: self.width - 2 * by_x,
+            height: self.height - 2 * by_y,
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// A tile's place in the grid, in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridCell {
+    pub column: u32,
+    pub row: u32,
+    pub column_span: u32,
+    pub row_span: u32,
+}
+
+/// The canvas and how it is divided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grid {
+    pub width: u32,
+    pub height: u32,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+impl Grid {
+    /// The pixels `cell` covers. Cell edges are rounded down, so adjacent
+    /// cells meet exactly and the last column and row reach the canvas
+    /// edge. `None` when the cell falls outside the grid.
+    pub fn cell_rect(&self, cell: GridCell) -> Option<Rect> {
+        if cell.column_span == 0
+            || cell.row_span == 0
+            || cell.column + cell.column_span > self.columns
+            || cell.row + cell.row_span > self.rows
+        {
+            return None;
+        }
+        let x_edge = |column: u32| {
+            (u64::from(self.width) * u64::from(column) / u64::from(self.columns)) as u32
+        };
+        let y_edge =
+            |row: u32| (u64::from(self.height) * u64::from(row) / u64::from(self.rows)) as u32;
+        let x = x_edge(cell.column);
+        let y = y_edge(cell.row);
+        Some(Rect {
+            x,
+            y,
+            width: x_edge(cell.column + cell.column_span) - x,
+            height: y_edge(cell.row + cell.row_span) - y,
+        })
+    }
+
+    /// Row-major placement of tile `index`, one cell each.
+    pub fn default_cell(&self, index: usize) -> GridCell {
+        let index = index as u32;
+        GridCell {
+            column: index % self.columns,
+            row: index / self.columns,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+}
+
+/// Pixel rects of every tile, in order; an error names the first tile
+/// outside the grid or overlapping an earlier one.
+pub fn tile_rects(grid: &Grid, cells: &[GridCell]) -> Result<Vec<Rect>, String> {
+    let mut rects: Vec<Rect> = Vec::with_capacity(cells.len());
+    for (index, cell) in cells.iter().enumerate() {
+        let rect = grid.cell_rect(*cell).ok_or_else(|| {
+            format!(
+                "tile {index} at column {} row {} spanning {}x{} is outside the {}x{} grid",
+                cell.column, cell.row, cell.column_span, cell.row_span, grid.columns, grid.rows
+            )
+        })?;
+        if let Some(other) = rects.iter().position(|other| other.overlaps(&rect)) {
+            return Err(format!("tile {index} overlaps tile {other}"));
+        }
+        rects.push(rect);
+    }
+    Ok(rects)
+}
+
+/// The sub-rectangles of one tile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileGeometry {
+    pub tile: Rect,
+    /// Inside the tally border.
+    pub inner: Rect,
+    /// The letterboxed picture as `[x, y, width, height]` in pixels.
+    pub picture: [f32; 4],
+    /// Width of each meter bar; the meter spans `channels` bars.
+    pub meter_bar_width: u32,
+    /// Label bar along the bottom of `inner`, left of the meter.
+    pub label: Rect,
+}
+
+impl TileGeometry {
+    /// Lay out a tile showing a `picture` of the given size (or none yet),
+    /// with `meter_channels` bars and a label `label_height` tall.
+    pub fn new(
+        tile: Rect,
+        border: u32,
+        picture: Option<(u32, u32)>,
+        meter_channels: u32,
+        label_height: u32,
+    ) -> Self {
+        let inner = tile.inset(border);
+        let meter_bar_width = (inner.width / 60).max(4);
+        let meter_width = (meter_bar_width * meter_channels).min(inner.width);
+        let label_height = label_height.min(inner.height);
+        Self {
+            tile,
+            inner,
+            picture: picture.map_or([0.0; 4], |size| fit(size, inner)),
+            meter_bar_width,
+            label: Rect {
+                x: inner.x,
+                y: inner.y + inner.height - label_height,
+                width: inner.width - meter_width,
+                height: label_height,
+            },
+        }
+    }
+}
+
+/// `size` scaled to fit `area` with its aspect ratio kept, centered.
+pub fn fit(size: (u32, u32), area: Rect) -> [f32; 4] {
+    let (width, height) = (size.0.max(1) as f32, size.1.max(1) as f32);
+    let scale = (area.width as f32 / width).min(area.height as f32 / height);
+    let (fitted_width, fitted_height) = (width * scale, height * scale);
+    [
+        area.x as f32 + (area.width as f32 - fitted_width) / 2.0,
+        area.y as f32 + (area.height as f32 - fitted_height) / 2.0,
+        fitted_width,
+        fitted_height,
+    ]
+}
+
+/// Sample peak of up to two channels with a falling hold: a new peak
+/// above the display jumps straight up, then the reading falls at
+/// [`METER_DECAY_DB_PER_SECOND`].
+#[derive(Debug, Clone, Default)]
+pub struct PeakMeter {
+    channels: u32,
+    /// Displayed peak per channel in dBFS and when it was set.
+    peaks: [(f32, Duration); 2],
+}
+
+impl PeakMeter {
+    /// Take a block of `channels`-interleaved samples received at `now`.
+    /// Channels past the first two are not metered.
+    pub fn on_samples(&mut self, samples: &[f32], channels: usize, now: Duration) {
+        if channels == 0 {
+            return;
+        }
+        let metered = channels.min(2);
+        for channel in 0..metered {
+            let peak = samples
+                .iter()
+                .skip(channel)
+                .step_by(channels)
+                .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+            let peak_db = 20.0 * peak.max(f32::MIN_POSITIVE).log10();
+            if peak_db >= self.level_db(channel, now) {
+                self.peaks[channel] = (peak_db, now);
+            }
+        }
+        self.channels = metered as u32;
+    }
+
+    /// Channels seen in the last block; 0 before any audio.
+    pub fn channels(&self) -> u32 {
+        self.channels
+    }
+
+    fn level_db(&self, channel: usize, now: Duration) -> f32 {
+        if channel as u32 >= self.channels {
+            return METER_FLOOR_DB;
+        }
+        let (peak_db, at) = self.peaks[channel];
+        peak_db - METER_DECAY_DB_PER_SECOND * now.saturating_sub(at).as_secs_f32()
+    }
+
+    /// Meter heights at `now`, 0 at [`METER_FLOOR_DB`] to 1 at full scale.
+    pub fn levels(&self, now: Duration) -> [f32; 2] {
+        [0, 1].map(|channel| {
+            ((self.level_db(channel, now) - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HD: Grid = Grid {
+        width: 1920,
+        height: 1080,
+        columns: 4,
+        rows: 4,
+    };
+
+    fn cell(column: u32, row: u32, column_span: u32, row_span: u32) -> GridCell {
+        GridCell {
+            column,
+            row,
+            column_span,
+            row_span,
+        }
+    }
+
+    #[test]
+    fn cells_tile_the_canvas_exactly() {
+        let grid = Grid {
+            width: 1000,
+            height: 1000,
+            columns: 3,
+            rows: 3,
+        };
+        let rects: Vec<Rect> = (0..9)
+            .map(|index| grid.cell_rect(grid.default_cell(index)).unwrap())
+            .collect();
+        assert_eq!(
+            rects[0],
+            Rect {
+                x: 0,
+                y: 0,
+                width: 333,
+                height: 333
+            }
+        );
+        assert_eq!(
+            rects[8],
+            Rect {
+                x: 666,
+                y: 666,
+                width: 334,
+                height: 334
+            }
+        );
+        assert_eq!(
+            rects.iter().map(|r| r.width * r.height).sum::<u32>(),
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn a_spanning_program_tile_rejects_overlaps_and_overflow() {
+        let rects = tile_rects(&HD, &[cell(0, 0, 2, 2), cell(2, 0, 1, 1)]).unwrap();
+        assert_eq!(
+            rects[0],
+            Rect {
+                x: 0,
+                y: 0,
+                width: 960,
+                height: 540
+            }
+        );
+
+        assert_eq!(
+            tile_rects(&HD, &[cell(0, 0, 2, 2), cell(1, 1, 1, 1)]).unwrap_err(),
+            "tile 1 overlaps tile 0"
+        );
+        assert!(tile_rects(&HD, &[cell(3, 0, 2, 1)]).is_err());
+    }
+
+    #[test]
+    fn the_picture_is_letterboxed_inside_the_border() {
+        let tile = Rect {
+            x: 0,
+            y: 0,
+            width: 480,
+            height: 400,
+        };
+        let geometry = TileGeometry::new(tile, 4, Some((1920, 1080)), 2, 24);
+        assert_eq!(
+            geometry.inner,
+            Rect {
+                x: 4,
+                y: 4,
+                width: 472,
+                height: 392
+            }
+        );
+        // 472 wide → 265.5 tall, centered in 392.
+        assert_eq!(geometry.picture, [4.0, 67.25, 472.0, 265.5]);
+        assert_eq!(geometry.meter_bar_width, 7);
+        assert_eq!(
+            geometry.label,
+            Rect {
+                x: 4,
+                y: 372,
+                width: 458,
+                height: 24
+            }
+        );
+    }
+
+    #[test]
+    fn the_meter_holds_a_peak_then_falls() {
+        let mut meter = PeakMeter::default();
+        assert_eq!(meter.levels(Duration::ZERO), [0.0, 0.0]);
+
+        // Left at full scale, right at -20 dBFS.
+        meter.on_samples(&[1.0, 0.1, -0.5, 0.0], 2, Duration::ZERO);
+        let [left, right] = meter.levels(Duration::ZERO);
+        assert_eq!(left, 1.0);
+        assert!((right - 40.0 / 60.0).abs() < 1e-4);
+
+        // Quieter blocks don't pull it down faster than the decay.
+        meter.on_samples(&[0.01, 0.01], 2, Duration::from_millis(500));
+        let [left, _] = meter.levels(Duration::from_millis(500));
+        assert!((left - 50.0 / 60.0).abs() < 1e-4);
+        assert_eq!(meter.levels(Duration::from_secs(10)), [0.0, 0.0]);
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Program/preview bus logic for the video switcher, separate from port
+//! I/O and the GPU so it can be driven with synthetic instants.
+//!
+//! Program is on air; preview is what goes on air next. A cut swaps them
+//! at once. An auto transition mixes program into preview over its
+//! duration and then swaps them, so the outgoing source lands on
+//! preview, ready to come back. Both sources of a running transition are
+//! on air for tally.
+
+use std::time::Duration;
+
+/// Inputs a switcher selects between.
+pub const MAX_INPUTS: usize = 8;
+
+/// How an auto transition mixes. Values match `transition.comp`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum TransitionKind {
+    /// Crossfade.
+    #[default]
+    Dissolve = 0,
+    /// A soft edge sweeping the incoming source across the outgoing one.
+    Wipe = 1,
+}
+
+/// Direction a wipe's edge travels. Values match `transition.comp`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WipeDirection {
+    #[default]
+    LeftToRight = 0,
+    RightToLeft = 1,
+    TopToBottom = 2,
+    BottomToTop = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionStyle {
+    pub kind: TransitionKind,
+    pub direction: WipeDirection,
+    /// Width of a wipe's edge as a fraction of the frame.
+    pub softness: f32,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transition {
+    to: usize,
+    started_at: Duration,
+    style: TransitionStyle,
+}
+
+/// What the program output shows at an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mix {
+    Single(usize),
+    /// `position` runs from 0 (all `from`) to 1 (all `to`).
+    Blend {
+        from: usize,
+        to: usize,
+        position: f32,
+        style: TransitionStyle,
+    },
+}
+
+/// Inputs on air and on preview, in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tally {
+    pub program: Vec<usize>,
+    pub preview: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Buses {
+    program: usize,
+    preview: usize,
+    transition: Option<Transition>,
+}
+
+impl Buses {
+    pub fn new(program: usize, preview: usize) -> Self {
+        Self {
+            program,
+            preview,
+            transition: None,
+        }
+    }
+
+    pub fn in_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Select the next source. Refused mid-transition, when preview is
+    /// already going on air.
+    pub fn set_preview(&mut self, input: usize) -> Result<(), String> {
+        if self.in_transition() {
+            return Err("preview can't change during a transition".into());
+        }
+        self.preview = input;
+        Ok(())
+    }
+
+    /// Hot-cut program straight to `input`, abandoning a transition.
+    pub fn set_program(&mut self, input: usize) {
+        self.transition = None;
+        self.program = input;
+    }
+
+    /// Swap program and preview. Mid-transition, finishes it at once.
+    pub fn cut(&mut self) {
+        if self.transition.is_some() {
+            self.finish();
+        } else {
+            std::mem::swap(&mut self.program, &mut self.preview);
+        }
+    }
+
+    /// Start mixing program into preview at `now`. A zero duration cuts.
+    pub fn auto(&mut self, now: Duration, style: TransitionStyle) -> Result<(), String> {
+        if self.in_transition() {
+            return Err("a transition is already running".into());
+        }
+        if style.duration.is_zero() {
+            self.cut();
+            return Ok(());
+        }
+        self.transition = Some(Transition {
+            to: self.preview,
+            started_at: now,
+            style,
+        });
+        Ok(())
+    }
+
+    /// Finish a transition whose time is up. Returns whether it did.
+    pub fn tick(&mut self, now: Duration) -> bool {
+        let done = self.transition.is_some_and(|transition| {
+            now.saturating_sub(transition.started_at) >= transition.style.duration
+        });
+        if done {
+            self.finish();
+        }
+        done
+    }
+
+    fn finish(&mut self) {
+        if let Some(transition) = self.transition.take() {
+            self.preview = self.program;
+            self.program = transition.to;
+        }
+    }
+
+    pub fn mix(&self, now: Duration) -> Mix {
+        match self.transition {
+            None => Mix::Single(self.program),
+            Some(transition) => Mix::Blend {
+                from: self.program,
+                to: transition.to,
+                position: (now.saturating_sub(transition.started_at).as_secs_f32()
+                    / transition.style.duration.as_secs_f32())
+                .min(1.0),
+                style: transition.style,
+            },
+        }
+    }
+
+    pub fn tally(&self) -> Tally {
+        match self.transition {
+            None => Tally {
+                program: vec![self.program],
+                preview: if self.preview == self.program {
+                    Vec::new()
+                } else {
+                    vec![self.preview]
+                },
+            },
+            Some(transition) => {
+                let mut program = vec![self.program, transition.to];
+                program.sort_unstable();
+                program.dedup();
+                Tally {
+                    program,
+                    preview: Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISSOLVE: TransitionStyle = TransitionStyle {
+        kind: TransitionKind::Dissolve,
+        direction: WipeDirection::LeftToRight,
+        softness: 0.0,
+        duration: Duration::from_secs(1),
+    };
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn cut_swaps_program_and_preview() {
+        let mut buses = Buses::new(0, 1);
+        buses.cut();
+        assert_eq!(
+            buses.tally(),
+            Tally {
+                program: vec![1],
+                preview: vec![0]
+            }
+        );
+        assert_eq!(buses.mix(ms(0)), Mix::Single(1));
+    }
+
+    #[test]
+    fn auto_mixes_then_swaps_with_both_sources_on_air() {
+        let mut buses = Buses::new(2, 0);
+        buses.auto(ms(1000), DISSOLVE).unwrap();
+        assert_eq!(
+            buses.tally(),
+            Tally {
+                program: vec![0, 2],
+                preview: vec![]
+            }
+        );
+        assert!(matches!(
+            buses.mix(ms(1250)),
+            Mix::Blend { from: 2, to: 0, position, .. } if position == 0.25
+        ));
+        assert!(buses.set_preview(3).is_err());
+        assert!(buses.auto(ms(1300), DISSOLVE).is_err());
+
+        assert!(!buses.tick(ms(1999)));
+        assert!(buses.tick(ms(2000)));
+        assert_eq!(
+            buses.tally(),
+            Tally {
+                program: vec![0],
+                preview: vec![2]
+            }
+        );
+    }
+
+    #[test]
+    fn cut_finishes_a_transition_and_hot_cut_abandons_it() {
+        let mut buses = Buses::new(0, 1);
+        buses.auto(ms(0), DISSOLVE).unwrap();
+        buses.cut();
+        assert_eq!(buses.mix(ms(100)), Mix::Single(1));
+        assert!(!buses.in_transition());
+
+        buses.auto(ms(200), DISSOLVE).unwrap();
+        buses.set_program(5);
+        assert_eq!(
+            buses.tally(),
+            Tally {
+                program: vec![5],
+                preview: vec![0]
+            }
+        );
+    }
+}
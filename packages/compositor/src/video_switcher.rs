@@ -0,0 +1,464 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Video switcher (Linux) — program/preview buses over eight inputs.
+//!
+//! Bus state lives in [`Buses`]; this processor feeds it operator actions
+//! from the `control` input and renders what it selects. Outside a
+//! transition program and preview frames are forwarded untouched, so a
+//! cut costs nothing. During an auto transition each frame from either
+//! source is mixed with the other's latest on the GPU (`transition.comp`)
+//! into an RGBA8 output ring at the outgoing source's size.
+//!
+//! Every tally change — a cut, the start and end of an auto, a bus
+//! selection — is written to the `tally` output for Multiview borders and
+//! published on [`SWITCHER_TALLY_TOPIC`] for camera tally lights and
+//! operator UIs.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel, VulkanLayout,
+    VulkanStage,
+};
+
+use crate::_generated_::tatolab__compositor::{switcher_control, video_switcher_config};
+use crate::_generated_::{SwitcherControl, TallyState, VideoFrame};
+use crate::switcher_bus::{Buses, MAX_INPUTS, Mix, TransitionKind, TransitionStyle, WipeDirection};
+
+/// Custom-event topic carrying `{processor_id, program, preview,
+/// in_transition}` on every tally change.
+pub const SWITCHER_TALLY_TOPIC: &str = "switcher:tally";
+
+const TRANSITION_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/transition.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::sampled_texture(1),
+    ComputeBindingSpec::sampled_texture(2),
+];
+
+/// Input ports, indexed by input number.
+const INPUTS: [&str; MAX_INPUTS] = [
+    "input_0", "input_1", "input_2", "input_3", "input_4", "input_5", "input_6", "input_7",
+];
+
+/// Matches `transition.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+const DEFAULT_PREVIEW: u32 = 1;
+const DEFAULT_TRANSITION_MS: u32 = 1000;
+const DEFAULT_WIPE_SOFTNESS: f32 = 0.02;
+
+/// Push constants of `transition.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct TransitionPushConstants {
+    width: u32,
+    height: u32,
+    kind: u32,
+    direction: u32,
+    position: f32,
+    softness: f32,
+}
+
+fn input_index(field: &str, input: u32) -> Result<usize> {
+    let index = input as usize;
+    if index >= MAX_INPUTS {
+        return Err(Error::Configuration(format!(
+            "VideoSwitcher: {field} must be 0 … {}, got {input}",
+            MAX_INPUTS - 1
+        )));
+    }
+    Ok(index)
+}
+
+fn transition_style(config: &crate::_generated_::VideoSwitcherConfig) -> Result<TransitionStyle> {
+    let softness = config.wipe_softness.unwrap_or(DEFAULT_WIPE_SOFTNESS);
+    if !(0.0..=1.0).contains(&softness) {
+        return Err(Error::Configuration(
+            "VideoSwitcher: wipe_softness must be 0 … 1".into(),
+        ));
+    }
+    Ok(TransitionStyle {
+        kind: match config.transition {
+            Some(video_switcher_config::Transition::Dissolve) | None => TransitionKind::Dissolve,
+            Some(video_switcher_config::Transition::Wipe) => TransitionKind::Wipe,
+        },
+        direction: match config.wipe_direction {
+            Some(video_switcher_config::WipeDirection::LeftToRight) | None => {
+                WipeDirection::LeftToRight
+            }
+            Some(video_switcher_config::WipeDirection::RightToLeft) => WipeDirection::RightToLeft,
+            Some(video_switcher_config::WipeDirection::TopToBottom) => WipeDirection::TopToBottom,
+            Some(video_switcher_config::WipeDirection::BottomToTop) => WipeDirection::BottomToTop,
+        },
+        softness,
+        duration: Duration::from_millis(u64::from(
+            config.transition_ms.unwrap_or(DEFAULT_TRANSITION_MS),
+        )),
+    })
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/compositor/VideoSwitcher",
+    description = "Program/preview video switcher over eight inputs. Cuts swap the buses instantly; auto transitions dissolve or wipe program into preview on the GPU. Operator actions arrive as SwitcherControl messages; tally changes go out as TallyState on the tally output and on the switcher:tally topic.",
+    execution = reactive,
+    config = crate::_generated_::VideoSwitcherConfig,
+    input("input_0", "@tatolab/core/VideoFrame", optional = true, description = "Source 0"),
+    input("input_1", "@tatolab/core/VideoFrame", optional = true, description = "Source 1"),
+    input("input_2", "@tatolab/core/VideoFrame", optional = true, description = "Source 2"),
+    input("input_3", "@tatolab/core/VideoFrame", optional = true, description = "Source 3"),
+    input("input_4", "@tatolab/core/VideoFrame", optional = true, description = "Source 4"),
+    input("input_5", "@tatolab/core/VideoFrame", optional = true, description = "Source 5"),
+    input("input_6", "@tatolab/core/VideoFrame", optional = true, description = "Source 6"),
+    input("input_7", "@tatolab/core/VideoFrame", optional = true, description = "Source 7"),
+    input("control", "@tatolab/compositor/SwitcherControl", optional = true, description = "Operator actions"),
+    output("program_out", "@tatolab/core/VideoFrame", description = "On-air output"),
+    output("preview_out", "@tatolab/core/VideoFrame", description = "The source on preview"),
+    output("tally", "@tatolab/compositor/TallyState", description = "Program and preview inputs, on every change"),
+)]
+pub struct VideoSwitcherProcessor {
+    processor_id: Option<String>,
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    buses: Option<Buses>,
+    style: Option<TransitionStyle>,
+    latest: [Option<VideoFrame>; MAX_INPUTS],
+    frames_mixed: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for VideoSwitcherProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        let program = input_index("program", self.config.program.unwrap_or(0))?;
+        let preview = input_index("preview", self.config.preview.unwrap_or(DEFAULT_PREVIEW))?;
+        self.style = Some(transition_style(&self.config)?);
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "video_switcher",
+            spv: TRANSITION_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<TransitionPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("video_switcher")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.buses = Some(Buses::new(program, preview));
+        tracing::info!(
+            "[VideoSwitcher] Setup (program {}, preview {})",
+            program,
+            preview
+        );
+        self.broadcast_tally()
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.output_ring = None;
+        self.latest = Default::default();
+        tracing::info!(
+            "[VideoSwitcher] Teardown ({} transition frames)",
+            self.frames_mixed
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.style = Some(transition_style(&self.config)?);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let now = MediaClock::now();
+        let mut tally_changed = false;
+        while self.inputs.has_data("control") {
+            let control: SwitcherControl = self.inputs.read("control")?;
+            tally_changed |= self.apply(&control, now);
+        }
+        let mut delivered = [false; MAX_INPUTS];
+        for (index, port) in INPUTS.iter().enumerate() {
+            while self.inputs.has_data(port) {
+                self.latest[index] = Some(self.inputs.read(port)?);
+                delivered[index] = true;
+            }
+        }
+        let Some(buses) = self.buses.as_mut() else {
+            return Ok(());
+        };
+        tally_changed |= buses.tick(now);
+        let mix = buses.mix(now);
+        let preview = buses.tally().preview.first().copied();
+        if tally_changed {
+            self.broadcast_tally()?;
+        }
+
+        if let Some(preview) = preview
+            && delivered[preview]
+            && self.outputs.has_port("preview_out")
+            && let Some(frame) = &self.latest[preview]
+        {
+            self.outputs.write("preview_out", frame)?;
+        }
+        match mix {
+            Mix::Single(input) => {
+                if delivered[input]
+                    && let Some(frame) = &self.latest[input]
+                {
+                    self.outputs.write("program_out", frame)?;
+                }
+            }
+            Mix::Blend {
+                from,
+                to,
+                position,
+                style,
+            } => {
+                if !(delivered[from] || delivered[to]) {
+                    return Ok(());
+                }
+                let output = match (&self.latest[from], &self.latest[to]) {
+                    (Some(_), Some(_)) => self.blend(from, to, position, style)?,
+                    // One side has never delivered; show the other.
+                    (Some(frame), None) | (None, Some(frame)) => frame.clone(),
+                    (None, None) => return Ok(()),
+                };
+                self.outputs.write("program_out", &output)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VideoSwitcherProcessor::Processor {
+    /// Apply one operator message. Returns whether the tally changed.
+    /// Invalid or refused actions are logged and skipped, leaving the
+    /// buses as they were.
+    fn apply(&mut self, control: &SwitcherControl, now: Duration) -> bool {
+        let (Some(buses), Some(style)) = (self.buses.as_mut(), self.style.as_mut()) else {
+            return false;
+        };
+        if let Some(transition) = &control.transition {
+            style.kind = match transition {
+                switcher_control::Transition::Dissolve => TransitionKind::Dissolve,
+                switcher_control::Transition::Wipe => TransitionKind::Wipe,
+            };
+        }
+        if let Some(ms) = control.transition_ms {
+            style.duration = Duration::from_millis(u64::from(ms));
+        }
+        if let Some(direction) = &control.wipe_direction {
+            style.direction = match direction {
+                switcher_control::WipeDirection::LeftToRight => WipeDirection::LeftToRight,
+                switcher_control::WipeDirection::RightToLeft => WipeDirection::RightToLeft,
+                switcher_control::WipeDirection::TopToBottom => WipeDirection::TopToBottom,
+                switcher_control::WipeDirection::BottomToTop => WipeDirection::BottomToTop,
+            };
+        }
+
+        let before = buses.tally();
+        if let Some(program) = control.program {
+            match input_index("program", program) {
+                Ok(program) => buses.set_program(program),
+                Err(e) => tracing::warn!("[VideoSwitcher] {}", e),
+            }
+        }
+        if let Some(preview) = control.preview {
+            let result = input_index("preview", preview)
+                .map_err(|e| e.to_string())
+                .and_then(|preview| buses.set_preview(preview));
+            if let Err(e) = result {
+                tracing::warn!("[VideoSwitcher] Preview {} refused: {}", preview, e);
+            }
+        }
+        if control.cut == Some(true) {
+            buses.cut();
+        } else if control.auto == Some(true)
+            && let Err(e) = buses.auto(now, *style)
+        {
+            tracing::warn!("[VideoSwitcher] Auto refused: {}", e);
+        }
+        buses.tally() != before
+    }
+
+    /// Write the current tally to the `tally` output and publish it.
+    fn broadcast_tally(&mut self) -> Result<()> {
+        let Some(buses) = self.buses.as_ref() else {
+            return Ok(());
+        };
+        let tally = buses.tally();
+        let in_transition = buses.in_transition();
+        tracing::info!(
+            "[VideoSwitcher] Tally: program {:?}, preview {:?}",
+            tally.program,
+            tally.preview
+        );
+        let to_wire = |inputs: &[usize]| inputs.iter().map(|&i| i as u32).collect::<Vec<_>>();
+        let state = TallyState {
+            timestamp_ns: MediaClock::now().as_nanos().to_string(),
+            program: to_wire(&tally.program),
+            preview: to_wire(&tally.preview),
+            in_transition: Some(in_transition),
+        };
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "program": state.program,
+            "preview": state.preview,
+            "in_transition": in_transition,
+        });
+        if let Err(e) = publish_custom_event(SWITCHER_TALLY_TOPIC, &payload) {
+            tracing::debug!("[VideoSwitcher] Tally not published: {}", e);
+        }
+        if self.outputs.has_port("tally") {
+            self.outputs.write("tally", &state)?;
+        }
+        Ok(())
+    }
+
+    /// Mix the latest frames of `from` and `to` into the next output slot.
+    fn blend(
+        &mut self,
+        from: usize,
+        to: usize,
+        position: f32,
+        style: TransitionStyle,
+    ) -> Result<VideoFrame> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("VideoSwitcher: GPU context not initialized".into())
+        })?;
+        let (Some(kernel), Some(recorder), Some(outgoing), Some(incoming)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.latest[from].as_ref(),
+            self.latest[to].as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "VideoSwitcher: kernel not initialized".into(),
+            ));
+        };
+        let (width, height) = (outgoing.width, outgoing.height);
+        let sources = [outgoing, incoming].map(|frame| {
+            gpu.resolve_texture_registration_by_surface_id(
+                &frame.surface_id,
+                frame.texture_layout,
+                frame.width,
+                frame.height,
+            )
+        });
+        let [outgoing_registration, incoming_registration] = sources;
+        let (outgoing_registration, incoming_registration) =
+            (outgoing_registration?, incoming_registration?);
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        kernel.set_storage_image(0, &slot.texture)?;
+        kernel.set_sampled_texture(1, outgoing_registration.texture())?;
+        kernel.set_sampled_texture(2, incoming_registration.texture())?;
+        kernel.set_push_constants_value(&TransitionPushConstants {
+            width,
+            height,
+            kind: style.kind as u32,
+            direction: style.direction as u32,
+            position,
+            softness: style.softness,
+        })?;
+
+        recorder.begin()?;
+        // The same surface can feed both sides; transition it once.
+        let mut sampled = vec![&outgoing_registration];
+        if incoming.surface_id != outgoing.surface_id {
+            sampled.push(&incoming_registration);
+        }
+        for registration in &sampled {
+            let current_layout = registration.current_layout();
+            if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+                recorder.record_image_barrier(
+                    registration.texture(),
+                    current_layout,
+                    VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                    VulkanStage::ALL_COMMANDS,
+                    VulkanStage::COMPUTE_SHADER,
+                    VulkanAccess::MEMORY_WRITE,
+                    VulkanAccess::SHADER_SAMPLED_READ,
+                )?;
+            }
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &sampled)?;
+        self.frames_mixed += 1;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: outgoing.timestamp_ns.clone(),
+            fps: outgoing.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            // Mixed as encoded; the output keeps the outgoing source's
+            // signal description.
+            color_info: outgoing.color_info.clone(),
+            mastering_display: None,
+            content_light: None,
+            field_order: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<TransitionPushConstants>(), 24);
+    }
+}
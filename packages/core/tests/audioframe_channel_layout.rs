@@ -0,0 +1,42 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `AudioFrame.channel_layout` is optional: producers that know their
+//! speaker layout (or ambisonics order) set it, everyone else leaves it
+//! out and consumers assume the conventional layout for the channel
+//! count. Lock the wire shape: snake_case values when set, absent when
+//! `None`, and frames without the field still deserialize.
+
+use streamlib_core_schema_tests::_generated_::AudioFrame;
+use streamlib_core_schema_tests::_generated_::tatolab__core::audio_frame::ChannelLayout;
+
+fn frame(channels: u8, channel_layout: Option<ChannelLayout>) -> AudioFrame {
+    AudioFrame {
+        samples: vec![0.0; channels as usize],
+        channels,
+        sample_rate: 48_000,
+        timestamp_ns: "0".to_string(),
+        frame_index: "0".to_string(),
+        channel_layout,
+    }
+}
+
+#[test]
+fn audioframe_channel_layout_round_trip() {
+    for (layout, wire, channels) in [
+        (ChannelLayout::Surround51, "surround_5_1", 6),
+        (ChannelLayout::Surround71, "surround_7_1", 8),
+        (ChannelLayout::AmbisonicsOrder1, "ambisonics_order_1", 4),
+        (ChannelLayout::AmbisonicsOrder3, "ambisonics_order_3", 16),
+    ] {
+        let json = serde_json::to_value(frame(channels, Some(layout.clone()))).expect("serialize");
+        assert_eq!(json["channel_layout"], wire);
+        let back: AudioFrame = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back.channel_layout, Some(layout));
+    }
+
+    let json_absent = serde_json::to_value(frame(2, None)).expect("serialize");
+    assert!(json_absent.get("channel_layout").is_none());
+    let back: AudioFrame = serde_json::from_value(json_absent).expect("deserialize");
+    assert_eq!(back.channel_layout, None);
+}
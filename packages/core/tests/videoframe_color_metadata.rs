@@ -50,6 +50,7 @@ fn videoframe_color_metadata_round_trip() {
             max_cll: 1000,
             max_fall: 400,
         }),
+        field_order: None,
     };
     let json = serde_json::to_value(&with_color).expect("serialize");
     assert!(json.get("color_info").is_some());
@@ -91,6 +92,7 @@ fn videoframe_color_metadata_round_trip() {
         color_info: None,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     };
     let json_absent = serde_json::to_value(&without_color).expect("serialize");
     assert!(json_absent.get("color_info").is_none());
@@ -25,6 +25,7 @@ fn videoframe_texture_layout_serialization_round_trip() {
         color_info: None,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     };
     let json = serde_json::to_value(&with_layout).expect("serialize");
     assert_eq!(
@@ -44,6 +45,7 @@ fn videoframe_texture_layout_serialization_round_trip() {
         color_info: None,
         mastering_display: None,
         content_light: None,
+        field_order: None,
     };
     let json_absent = serde_json::to_value(&absent).expect("serialize");
     assert!(
@@ -23,7 +23,7 @@ use std::sync::Arc;
     description = "Streams raw BGRA frames from a file as Videoframes",
     execution = manual,
     config = crate::_generated_::BgraFileSourceConfig,
-    output("video", "@tatolab/core/VideoFrame", description = "Video frames read from the BGRA file"),
+    output("video", "@tatolab/core/VideoFrame", format = "bgra8", description = "Video frames read from the BGRA file"),
 )]
 pub struct BgraFileSourceProcessor {
     gpu_context: Option<GpuContextLimitedAccess>,
@@ -194,6 +194,7 @@ fn source_thread_loop(
             color_info: None,
             mastering_display: None,
             content_light: None,
+            field_order: None,
         };
 
         if let Err(e) = outputs.write("video", &video_frame) {
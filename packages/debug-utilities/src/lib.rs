@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 //! `@tatolab/debug-utilities` — utility processors for development,
-//! demos, and rigorous-input testing (BgraFileSource, SimplePassthrough).
+//! demos, and rigorous-input testing (BgraFileSource, SimplePassthrough,
+//! TestSignal).
 
 #[allow(non_snake_case, unused_imports, clippy::all)]
 pub mod _generated_ {
@@ -11,6 +12,7 @@ pub mod _generated_ {
 
 pub mod live_video_frame_forwarder;
 pub mod simple_passthrough;
+pub mod test_pattern;
 pub mod video_frame_counter;
 
 #[cfg(test)]
@@ -22,6 +24,9 @@ pub mod bgra_file_source;
 #[cfg(target_os = "linux")]
 pub mod jpeg_bytes_source;
 
+#[cfg(target_os = "linux")]
+pub mod test_signal;
+
 pub use live_video_frame_forwarder::LiveVideoFrameForwarderProcessor;
 pub use simple_passthrough::SimplePassthroughProcessor;
 pub use video_frame_counter::VideoFrameCounterProcessor;
@@ -32,6 +37,9 @@ pub use bgra_file_source::BgraFileSourceProcessor;
 #[cfg(target_os = "linux")]
 pub use jpeg_bytes_source::JpegBytesSourceProcessor;
 
+#[cfg(target_os = "linux")]
+pub use test_signal::TestSignalProcessor;
+
 #[cfg(target_os = "linux")]
 streamlib_plugin_abi::export_plugin!(
     crate::LiveVideoFrameForwarderProcessor::Processor,
@@ -39,6 +47,7 @@ streamlib_plugin_abi::export_plugin!(
     crate::VideoFrameCounterProcessor::Processor,
     crate::BgraFileSourceProcessor::Processor,
     crate::JpegBytesSourceProcessor::Processor,
+    crate::TestSignalProcessor::Processor,
 );
 
 #[cfg(not(target_os = "linux"))]
@@ -118,3 +118,21 @@ fn jpeg_bytes_source_descriptor_loads() {
         "JpegBytesSource declares a single `encoded_jpeg` output port"
     );
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_signal_descriptor_loads() {
+    let descriptor = <crate::TestSignalProcessor::Processor as GeneratedProcessor>::descriptor()
+        .expect("TestSignal must expose a macro-generated descriptor");
+
+    assert_eq!(descriptor.name.r#type.as_str(), "TestSignal");
+    assert!(
+        descriptor.inputs.is_empty(),
+        "TestSignal is a source — it declares no input ports"
+    );
+    assert_eq!(
+        port_names(&descriptor.outputs),
+        vec!["video", "audio"],
+        "TestSignal declares `video` and `audio` output ports"
+    );
+}
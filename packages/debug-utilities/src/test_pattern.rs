@@ -0,0 +1,383 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// Test pattern rendering
+//
+// CPU rendering of the TestSignal patterns into RGBA8 frames, the burnt-in
+// frame counter / timecode, and the line-up tone. Kept free of the
+// processor plumbing so the pixel values can be checked directly.
+
+use std::f64::consts::{PI, TAU};
+
+/// Video pattern to render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// SMPTE color bars: 75% bars, the reverse-blue strip, and the
+    /// -I / white / +Q / PLUGE row.
+    Bars,
+    /// Horizontal 0–255 ramps in four bands: gray, red, green, blue.
+    Ramp,
+    /// Circular zone plate whose rings move outward one step per frame;
+    /// its frequency reaches Nyquist at the frame edge.
+    ZonePlate,
+}
+
+const BAR_WHITE: [u8; 3] = [191, 191, 191];
+const BAR_YELLOW: [u8; 3] = [191, 191, 0];
+const BAR_CYAN: [u8; 3] = [0, 191, 191];
+const BAR_GREEN: [u8; 3] = [0, 191, 0];
+const BAR_MAGENTA: [u8; 3] = [191, 0, 191];
+const BAR_RED: [u8; 3] = [191, 0, 0];
+const BAR_BLUE: [u8; 3] = [0, 0, 191];
+const BLACK: [u8; 3] = [0, 0, 0];
+const FULL_WHITE: [u8; 3] = [255, 255, 255];
+const MINUS_I: [u8; 3] = [0, 33, 76];
+const PLUS_Q: [u8; 3] = [50, 0, 106];
+/// PLUGE steps. Full-range RGB has nothing below black, so the
+/// "blacker than black" step sits at black and the others 2% / 4% above.
+const PLUGE: [[u8; 3]; 3] = [[0, 0, 0], [5, 5, 5], [10, 10, 10]];
+
+const TOP_ROW: [[u8; 3]; 7] = [
+    BAR_WHITE,
+    BAR_YELLOW,
+    BAR_CYAN,
+    BAR_GREEN,
+    BAR_MAGENTA,
+    BAR_RED,
+    BAR_BLUE,
+];
+const MIDDLE_ROW: [[u8; 3]; 7] = [
+    BAR_BLUE,
+    BLACK,
+    BAR_MAGENTA,
+    BLACK,
+    BAR_CYAN,
+    BLACK,
+    BAR_WHITE,
+];
+
+/// Phase steps per zone plate cycle; the rings move one step per frame.
+const ZONE_PLATE_STEPS: usize = 64;
+
+/// Renders frames of one pattern at one size. Bars and ramps are drawn
+/// once and copied; the zone plate keeps each pixel's phase so a frame is
+/// a table lookup per pixel rather than a cosine.
+pub struct PatternRenderer {
+    pattern: Pattern,
+    /// The still frame, for bars and ramps.
+    still: Vec<u8>,
+    /// Per-pixel zone plate phase, in `1 / ZONE_PLATE_STEPS` of a cycle.
+    zone_phase: Vec<u8>,
+    /// Gray level for each phase step.
+    zone_levels: [u8; ZONE_PLATE_STEPS],
+}
+
+impl PatternRenderer {
+    pub fn new(pattern: Pattern, width: u32, height: u32) -> Self {
+        let (width, height) = (width as usize, height as usize);
+        let mut still = Vec::new();
+        let mut zone_phase = Vec::new();
+        match pattern {
+            Pattern::Bars | Pattern::Ramp => {
+                still = vec![0; width * height * 4];
+                for (y, row) in still.chunks_exact_mut(width * 4).enumerate() {
+                    if pattern == Pattern::Bars {
+                        bars_row(row, width, y * 12 / height);
+                    } else {
+                        ramp_row(row, width, y * 4 / height);
+                    }
+                }
+            }
+            Pattern::ZonePlate => {
+                // Phase k·r² has spatial frequency k·r/π cycles per pixel,
+                // which is Nyquist (0.5) at r = half the larger dimension.
+                let k = PI / width.max(height) as f64;
+                zone_phase.reserve(width * height);
+                for y in 0..height {
+                    let cy = y as f64 - height as f64 / 2.0;
+                    for x in 0..width {
+                        let cx = x as f64 - width as f64 / 2.0;
+                        let cycles = k * (cx * cx + cy * cy) / TAU;
+                        let step = (cycles.fract() * ZONE_PLATE_STEPS as f64).round() as usize;
+                        zone_phase.push((step % ZONE_PLATE_STEPS) as u8);
+                    }
+                }
+            }
+        }
+        let zone_levels = std::array::from_fn(|step| {
+            (127.5 + 127.5 * (step as f64 * TAU / ZONE_PLATE_STEPS as f64).cos()) as u8
+        });
+        Self {
+            pattern,
+            still,
+            zone_phase,
+            zone_levels,
+        }
+    }
+
+    /// Render frame `frame_index` into `frame`, tightly packed RGBA8 of
+    /// `width * height * 4` bytes.
+    pub fn render(&self, frame_index: u64, frame: &mut [u8]) {
+        match self.pattern {
+            Pattern::Bars | Pattern::Ramp => {
+                let len = frame.len().min(self.still.len());
+                frame[..len].copy_from_slice(&self.still[..len]);
+            }
+            Pattern::ZonePlate => {
+                let motion = (frame_index % ZONE_PLATE_STEPS as u64) as usize;
+                for (pixel, phase) in frame.chunks_exact_mut(4).zip(&self.zone_phase) {
+                    let step = (usize::from(*phase) + ZONE_PLATE_STEPS - motion) % ZONE_PLATE_STEPS;
+                    let level = self.zone_levels[step];
+                    pixel.copy_from_slice(&[level, level, level, 255]);
+                }
+            }
+        }
+    }
+}
+
+/// `twelfth` is the row's vertical position in twelfths of the frame: the
+/// top two thirds are the bars, one twelfth the reverse strip, the last
+/// quarter the -I / +Q / PLUGE row.
+fn bars_row(row: &mut [u8], width: usize, twelfth: usize) {
+    for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+        let rgb = match twelfth {
+            0..=7 => TOP_ROW[x * 7 / width],
+            8 => MIDDLE_ROW[x * 7 / width],
+            _ => bottom_row(x * 28 / width),
+        };
+        pixel.copy_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+    }
+}
+
+/// The bottom row in 28ths of the width (a bar is 4/28): -I, white, +Q
+/// and black blocks 5/28 wide, then the three PLUGE steps a quarter bar
+/// each under the red bar, then black.
+fn bottom_row(x28: usize) -> [u8; 3] {
+    match x28 {
+        0..=4 => MINUS_I,
+        5..=9 => FULL_WHITE,
+        10..=14 => PLUS_Q,
+        15..=19 => BLACK,
+        20..=22 => PLUGE[x28 - 20],
+        _ => BLACK,
+    }
+}
+
+fn ramp_row(row: &mut [u8], width: usize, band: usize) {
+    let denominator = (width - 1).max(1);
+    for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+        let level = (x * 255 / denominator) as u8;
+        let rgb = match band {
+            0 => [level, level, level],
+            1 => [level, 0, 0],
+            2 => [0, level, 0],
+            _ => [0, 0, level],
+        };
+        pixel.copy_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+    }
+}
+
+/// `HH:MM:SS:FF` timecode for frame `frame_index` at a whole `fps`.
+pub fn timecode(frame_index: u64, fps: u32) -> String {
+    let fps = u64::from(fps.max(1));
+    let frames = frame_index % fps;
+    let seconds = frame_index / fps;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        frames
+    )
+}
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/// 5×7 glyphs, one row per byte, most significant of the low five bits
+/// leftmost. Covers what the burn-in prints.
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+/// Burn `lines` of text in white on a black box into the top-left of
+/// `frame` (RGBA8), scaled with the frame height so it stays legible
+/// after downscaling. Clipped at the frame edges.
+pub fn burn_in(frame: &mut [u8], width: u32, height: u32, lines: &[&str]) {
+    let (width, height) = (width as usize, height as usize);
+    let scale = (height / 180).max(1);
+    let advance = (GLYPH_WIDTH + 1) * scale;
+    let line_height = (GLYPH_HEIGHT + 2) * scale;
+    let margin = 2 * scale;
+    let columns = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let box_width = (margin * 2 + columns * advance).min(width);
+    let box_height = (margin * 2 + lines.len() * line_height).min(height);
+    for y in 0..box_height {
+        for x in 0..box_width {
+            set_pixel(frame, width, x, y, BLACK);
+        }
+    }
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let top = margin + line_index * line_height;
+        for (column, c) in line.chars().enumerate() {
+            let left = margin + column * advance;
+            for (glyph_y, bits) in glyph(c).iter().enumerate() {
+                for glyph_x in 0..GLYPH_WIDTH {
+                    if bits & (0x10 >> glyph_x) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let x = left + glyph_x * scale + dx;
+                            let y = top + glyph_y * scale + dy;
+                            if x < width && y < height {
+                                set_pixel(frame, width, x, y, FULL_WHITE);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn set_pixel(frame: &mut [u8], width: usize, x: usize, y: usize, rgb: [u8; 3]) {
+    let offset = (y * width + x) * 4;
+    if let Some(pixel) = frame.get_mut(offset..offset + 4) {
+        pixel.copy_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+    }
+}
+
+/// Continuous sine tone, interleaved across channels.
+#[derive(Debug, Clone)]
+pub struct ToneGenerator {
+    phase: f64,
+    phase_step: f64,
+    amplitude: f32,
+}
+
+impl ToneGenerator {
+    pub fn new(frequency_hz: f64, level_dbfs: f64, sample_rate: u32) -> Self {
+        Self {
+            phase: 0.0,
+            phase_step: TAU * frequency_hz / f64::from(sample_rate),
+            amplitude: 10f64.powf(level_dbfs / 20.0) as f32,
+        }
+    }
+
+    /// Fill `samples` with the next `samples.len() / channels` frames, the
+    /// same tone on every channel.
+    pub fn fill(&mut self, samples: &mut [f32], channels: usize) {
+        for frame in samples.chunks_exact_mut(channels.max(1)) {
+            frame.fill(self.amplitude * self.phase.sin() as f32);
+            self.phase = (self.phase + self.phase_step) % TAU;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(frame: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * width + x) * 4) as usize;
+        frame[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn bars_follow_the_smpte_layout() {
+        let (width, height) = (280, 120);
+        let mut frame = vec![0; (width * height * 4) as usize];
+        PatternRenderer::new(Pattern::Bars, width, height).render(0, &mut frame);
+
+        assert_eq!(pixel(&frame, width, 0, 0), [191, 191, 191, 255]);
+        assert_eq!(pixel(&frame, width, 90, 10), [0, 191, 191, 255]);
+        assert_eq!(pixel(&frame, width, 279, 79), [0, 0, 191, 255]);
+        // Reverse strip: blue under white.
+        assert_eq!(pixel(&frame, width, 0, 85), [0, 0, 191, 255]);
+        assert_eq!(pixel(&frame, width, 0, 119), [0, 33, 76, 255]);
+        assert_eq!(pixel(&frame, width, 225, 119), [10, 10, 10, 255]);
+    }
+
+    #[test]
+    fn ramps_span_the_full_range() {
+        let (width, height) = (256, 4);
+        let mut frame = vec![0; (width * height * 4) as usize];
+        PatternRenderer::new(Pattern::Ramp, width, height).render(0, &mut frame);
+
+        assert_eq!(pixel(&frame, width, 0, 0), [0, 0, 0, 255]);
+        assert_eq!(pixel(&frame, width, 255, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&frame, width, 128, 1), [128, 0, 0, 255]);
+        assert_eq!(pixel(&frame, width, 255, 3), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn zone_plate_moves_between_frames() {
+        let (width, height) = (64, 64);
+        let mut first = vec![0; (width * height * 4) as usize];
+        let mut second = first.clone();
+        let renderer = PatternRenderer::new(Pattern::ZonePlate, width, height);
+        renderer.render(0, &mut first);
+        renderer.render(1, &mut second);
+
+        // Bright at the center on frame 0.
+        assert_eq!(pixel(&first, width, 32, 32), [255, 255, 255, 255]);
+        assert_ne!(first, second);
+        // The rings come back round after a full cycle of steps.
+        renderer.render(ZONE_PLATE_STEPS as u64, &mut second);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn timecode_counts_frames_within_the_second() {
+        assert_eq!(timecode(0, 30), "00:00:00:00");
+        assert_eq!(timecode(29, 30), "00:00:00:29");
+        assert_eq!(timecode(30 * 3661 + 7, 30), "01:01:01:07");
+    }
+
+    #[test]
+    fn burn_in_draws_white_glyphs_on_black() {
+        let (width, height) = (64, 32);
+        let mut frame = vec![128; (width * height * 4) as usize];
+        burn_in(&mut frame, width, height, &["1"]);
+
+        // Box margin is black; the 1's top stroke starts at glyph column 2.
+        assert_eq!(pixel(&frame, width, 0, 0), [0, 0, 0, 255]);
+        assert_eq!(pixel(&frame, width, 4, 2), [255, 255, 255, 255]);
+        // Outside the box is untouched.
+        assert_eq!(pixel(&frame, width, 40, 20), [128, 128, 128, 128]);
+    }
+
+    #[test]
+    fn tone_is_at_the_requested_level_and_frequency() {
+        let mut tone = ToneGenerator::new(1000.0, -20.0, 48_000);
+        let mut samples = vec![0.0; 48 * 2];
+        tone.fill(&mut samples, 2);
+
+        // One 1 kHz cycle is 48 frames: quarter-cycle peak, same on both channels.
+        assert!((samples[12 * 2] - 0.1).abs() < 1e-6);
+        assert_eq!(samples[12 * 2], samples[12 * 2 + 1]);
+        let peak = samples.iter().fold(0f32, |peak, s| peak.max(s.abs()));
+        assert!((peak - 0.1).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// Test Signal Processor
+//
+// Generates SMPTE bars, ramps or a moving zone plate as VideoFrames, with
+// the frame counter, timecode and media clock timestamp burnt in, plus a
+// sine line-up tone (1 kHz by default) as AudioFrames. Both are paced in
+// real time off one start instant and stamped on the media clock, so
+// pipelines can be validated — levels, scaling, A/V sync, dropped frames —
+// without a physical camera or microphone.
+
+use crate::_generated_::tatolab__core::audio_frame::ChannelLayout;
+use crate::_generated_::tatolab__debug_utilities::test_signal_config;
+use crate::_generated_::{AudioFrame, VideoFrame};
+use crate::test_pattern::{Pattern, PatternRenderer, ToneGenerator, burn_in, timecode};
+use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContextFullAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+use streamlib_plugin_sdk::sdk::rhi::PixelFormat;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+const DEFAULT_TONE_FREQUENCY_HZ: f64 = 1000.0;
+const DEFAULT_TONE_LEVEL_DBFS: f64 = -20.0;
+
+const AUDIO_SAMPLE_RATE: u32 = 48_000;
+const AUDIO_CHANNELS: u8 = 2;
+/// Tone is emitted in 10 ms chunks.
+const AUDIO_CHUNK_FRAMES: usize = 480;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/debug-utilities/TestSignal",
+    description = "Generates SMPTE bars, ramps or a moving zone plate with burnt-in frame counter and timecode, plus a 1 kHz line-up tone, for validating pipelines without a camera",
+    execution = manual,
+    config = crate::_generated_::TestSignalConfig,
+    output("video", "@tatolab/core/VideoFrame", description = "Test pattern frames"),
+    output("audio", "@tatolab/core/AudioFrame", format = "48khz", description = "Sine line-up tone, 48 kHz stereo"),
+)]
+pub struct TestSignalProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    is_running: Arc<AtomicBool>,
+    frame_counter: Arc<AtomicU64>,
+    source_thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ManualProcessor for TestSignalProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.width == 0 || self.config.height == 0 || self.config.fps == 0 {
+            return Err(Error::Configuration(format!(
+                "TestSignal: width, height and fps must be non-zero (got {}x{}@{})",
+                self.config.width, self.config.height, self.config.fps
+            )));
+        }
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!(
+            "[TestSignal] Setup ({:?}, {}x{}@{}fps)",
+            self.config.pattern,
+            self.config.width,
+            self.config.height,
+            self.config.fps
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let frames = self.frame_counter.load(Ordering::Relaxed);
+        tracing::info!("[TestSignal] Teardown ({frames} frames generated)");
+        self.is_running.store(false, Ordering::Release);
+        if let Some(handle) = self.source_thread_handle.take() {
+            let _ = handle.join();
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let gpu_context = self
+            .gpu_context
+            .clone()
+            .ok_or_else(|| Error::Configuration("GPU context not initialized".into()))?;
+
+        let pattern = match self.config.pattern {
+            None | Some(test_signal_config::Pattern::Bars) => Pattern::Bars,
+            Some(test_signal_config::Pattern::Ramp) => Pattern::Ramp,
+            Some(test_signal_config::Pattern::ZonePlate) => Pattern::ZonePlate,
+        };
+        let signal = Signal {
+            width: self.config.width,
+            height: self.config.height,
+            fps: self.config.fps,
+            frame_count: self.config.frame_count.unwrap_or(0),
+            burn_in: self.config.burn_in.unwrap_or(true),
+            renderer: PatternRenderer::new(pattern, self.config.width, self.config.height),
+            tone: ToneGenerator::new(
+                self.config
+                    .tone_frequency_hz
+                    .unwrap_or(DEFAULT_TONE_FREQUENCY_HZ),
+                self.config
+                    .tone_level_dbfs
+                    .unwrap_or(DEFAULT_TONE_LEVEL_DBFS),
+                AUDIO_SAMPLE_RATE,
+            ),
+        };
+
+        self.is_running.store(true, Ordering::Release);
+
+        let is_running = Arc::clone(&self.is_running);
+        let frame_counter = Arc::clone(&self.frame_counter);
+        let outputs: OutputWriter = self.outputs.clone();
+
+        let handle = std::thread::Builder::new()
+            .name("test-signal".into())
+            .spawn(move || {
+                source_thread_loop(signal, is_running, frame_counter, outputs, gpu_context);
+            })
+            .map_err(|e| Error::Configuration(format!("Failed to spawn source thread: {e}")))?;
+
+        self.source_thread_handle = Some(handle);
+        tracing::info!("[TestSignal] Generating");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.is_running.store(false, Ordering::Release);
+        if let Some(handle) = self.source_thread_handle.take() {
+            let _ = handle.join();
+        }
+        tracing::info!("[TestSignal] Stopped");
+        Ok(())
+    }
+}
+
+/// What the source thread generates.
+struct Signal {
+    width: u32,
+    height: u32,
+    fps: u32,
+    /// 0 = unlimited.
+    frame_count: u32,
+    burn_in: bool,
+    renderer: PatternRenderer,
+    tone: ToneGenerator,
+}
+
+fn source_thread_loop(
+    mut signal: Signal,
+    is_running: Arc<AtomicBool>,
+    frame_counter: Arc<AtomicU64>,
+    outputs: OutputWriter,
+    gpu_context: GpuContextLimitedAccess,
+) {
+    let mut frame_buf = vec![0u8; (signal.width * signal.height * 4) as usize];
+    let mut samples = vec![0f32; AUDIO_CHUNK_FRAMES * AUDIO_CHANNELS as usize];
+    let frame_interval_ns = 1_000_000_000u64 / u64::from(signal.fps);
+    let chunk_interval_ns =
+        AUDIO_CHUNK_FRAMES as u64 * 1_000_000_000 / u64::from(AUDIO_SAMPLE_RATE);
+
+    // Frame and chunk N are due N intervals after the start, and stamped
+    // the same distance from the start's media clock time, so video and
+    // audio line up exactly whatever the scheduling jitter.
+    let clock_start = Instant::now();
+    let media_clock_start_ns = MediaClock::now().as_nanos() as u64;
+    let mut frame_idx: u64 = 0;
+    let mut chunk_idx: u64 = 0;
+
+    while is_running.load(Ordering::Acquire) {
+        if signal.frame_count != 0 && frame_idx >= u64::from(signal.frame_count) {
+            break;
+        }
+        let elapsed_ns = clock_start.elapsed().as_nanos() as u64;
+        let video_due_ns = frame_idx * frame_interval_ns;
+        let audio_due_ns = chunk_idx * chunk_interval_ns;
+
+        if video_due_ns <= elapsed_ns {
+            let timestamp_ns = media_clock_start_ns + video_due_ns;
+            if let Err(e) = publish_frame(
+                &signal,
+                frame_idx,
+                timestamp_ns,
+                &mut frame_buf,
+                &outputs,
+                &gpu_context,
+            ) {
+                tracing::error!("[TestSignal] Failed to publish frame: {e}");
+                break;
+            }
+            frame_idx += 1;
+            frame_counter.store(frame_idx, Ordering::Relaxed);
+            if frame_idx == 1 {
+                tracing::info!("[TestSignal] First frame published");
+            }
+            continue;
+        }
+
+        if audio_due_ns <= elapsed_ns {
+            signal.tone.fill(&mut samples, AUDIO_CHANNELS as usize);
+            let audio_frame = AudioFrame {
+                samples: samples.clone(),
+                channels: AUDIO_CHANNELS,
+                sample_rate: AUDIO_SAMPLE_RATE,
+                timestamp_ns: (media_clock_start_ns + audio_due_ns).to_string(),
+                frame_index: chunk_idx.to_string(),
+                channel_layout: Some(ChannelLayout::Stereo),
+            };
+            if let Err(e) = outputs.write("audio", &audio_frame) {
+                tracing::error!("[TestSignal] Failed to write audio: {e}");
+                break;
+            }
+            chunk_idx += 1;
+            continue;
+        }
+
+        let next_due_ns = video_due_ns.min(audio_due_ns);
+        std::thread::sleep(Duration::from_nanos(next_due_ns - elapsed_ns));
+    }
+
+    is_running.store(false, Ordering::Release);
+    tracing::info!(
+        "[TestSignal] Source thread done ({} frames)",
+        frame_counter.load(Ordering::Relaxed)
+    );
+}
+
+fn publish_frame(
+    signal: &Signal,
+    frame_idx: u64,
+    timestamp_ns: u64,
+    frame_buf: &mut [u8],
+    outputs: &OutputWriter,
+    gpu_context: &GpuContextLimitedAccess,
+) -> Result<()> {
+    signal.renderer.render(frame_idx, frame_buf);
+    if signal.burn_in {
+        let counter = format!("{frame_idx:08}");
+        let timecode = timecode(frame_idx, signal.fps);
+        let seconds = format!("{:.3}", timestamp_ns as f64 / 1e9);
+        burn_in(
+            frame_buf,
+            signal.width,
+            signal.height,
+            &[&counter, &timecode, &seconds],
+        );
+    }
+
+    // Same staging as BgraFileSource: the pool id is the output
+    // `surface_id`, and the pool's strong-count reuse gating keeps the slot
+    // intact until downstream has consumed it.
+    let (pool_id, pixel_buffer) =
+        gpu_context.acquire_pixel_buffer(signal.width, signal.height, PixelFormat::Rgba32)?;
+    let dst_ptr = pixel_buffer.plane_base_address(0);
+    if dst_ptr.is_null() {
+        return Err(Error::Runtime(
+            "Pixel buffer plane base address is null".into(),
+        ));
+    }
+    let copy_len = frame_buf.len().min(pixel_buffer.plane_size(0) as usize);
+    // SAFETY: `dst_ptr` is the mapped host-visible base of a pixel buffer
+    // sized (width, height, Rgba32); `copy_len` is clamped to both the
+    // rendered frame and the plane size, and the regions do not overlap.
+    unsafe {
+        std::ptr::copy_nonoverlapping(frame_buf.as_ptr(), dst_ptr, copy_len);
+    }
+
+    let video_frame = VideoFrame {
+        surface_id: pool_id.to_string(),
+        width: signal.width,
+        height: signal.height,
+        timestamp_ns: timestamp_ns.to_string(),
+        fps: Some(signal.fps),
+        texture_layout: None,
+        // Patterns are drawn as full-range sRGB values; left unknown like
+        // the other debug sources so consumers keep their defaults.
+        color_info: None,
+        mastering_display: None,
+        content_light: None,
+        field_order: None,
+    };
+    outputs.write("video", &video_frame)
+}
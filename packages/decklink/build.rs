@@ -0,0 +1,48 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+#![allow(clippy::disallowed_macros)] // build.rs uses println! for `cargo:` directives
+
+//! Build script: codegen, plus the C++ shim over the Blackmagic DeckLink
+//! SDK on Linux. The SDK headers aren't redistributable, so point
+//! `DECKLINK_SDK_DIR` at the SDK's `Linux/include` directory. The driver
+//! library itself (`libDeckLinkAPI.so`, from Desktop Video) is loaded at
+//! runtime by the SDK's dispatch code.
+
+fn main() {
+    streamlib_jtd_codegen::build_rs::run_for_rust_crate();
+    #[cfg(target_os = "linux")]
+    compile_shim();
+}
+
+#[cfg(target_os = "linux")]
+fn compile_shim() {
+    use std::path::PathBuf;
+
+    println!("cargo:rerun-if-env-changed=DECKLINK_SDK_DIR");
+    println!("cargo:rerun-if-changed=src/shim/decklink_shim.cpp");
+    println!("cargo:rerun-if-changed=src/shim/decklink_shim.h");
+
+    let sdk_include = PathBuf::from(std::env::var("DECKLINK_SDK_DIR").expect(
+        "DECKLINK_SDK_DIR not set. Download the Blackmagic DeckLink SDK and point it at Linux/include.",
+    ));
+    let dispatch = sdk_include.join("DeckLinkAPIDispatch.cpp");
+    assert!(
+        dispatch.exists(),
+        "{} not found; DECKLINK_SDK_DIR must be the SDK's Linux/include directory",
+        dispatch.display()
+    );
+
+    cc::Build::new()
+        .cpp(true)
+        .std("c++17")
+        .include(&sdk_include)
+        .file("src/shim/decklink_shim.cpp")
+        .file(&dispatch)
+        .warnings(false)
+        .compile("decklink_shim");
+
+    // The dispatch code dlopens the driver library.
+    println!("cargo:rustc-link-lib=dl");
+    println!("cargo:rustc-link-lib=pthread");
+}
@@ -0,0 +1,215 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! DeckLink SDI playout (Linux).
+//!
+//! Each incoming frame is read back from the GPU, converted to the card's
+//! 8-bit UYVY or 10-bit v210 layout and displayed synchronously, so the
+//! card paces the pipeline at the output frame rate. Incoming audio is
+//! embedded as 32-bit PCM. Frames must already match the output mode's
+//! resolution — scale upstream. The card's reference-input status is
+//! reported to the host clock subsystem while playing out; lock the card
+//! to house sync to genlock the output.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    TextureFormat, TextureReadback, TextureSourceLayout, VulkanLayout,
+};
+
+use crate::_generated_::{AudioFrame, VideoFrame};
+use crate::device::Device;
+use crate::display_mode::SignalFormat;
+use crate::genlock::ReferenceMonitor;
+use crate::pixel;
+
+/// Upper bound on one frame's GPU→CPU copy.
+const READBACK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Embedded audio is always played out at 48 kHz.
+const AUDIO_SAMPLE_RATE: u32 = 48_000;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/decklink/DecklinkSink",
+    description = "Plays video (8-bit or 10-bit YUV) and embedded audio out of a Blackmagic DeckLink card's SDI output, and reports the card's genlock status to the clock subsystem.",
+    execution = reactive,
+    config = crate::_generated_::DecklinkSinkConfig,
+    input("video", "@tatolab/core/VideoFrame", description = "Frames to play out, at the output mode's resolution"),
+    input("audio", "@tatolab/core/AudioFrame", format = "48khz", description = "Audio to embed, 48 kHz with the configured channel count"),
+)]
+pub struct DecklinkSinkProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    format: Option<SignalFormat>,
+    device: Option<Device>,
+    reference_monitor: Option<ReferenceMonitor>,
+    readback: Option<TextureReadback>,
+    /// Converted frame in the card's layout, reused across frames.
+    yuv: Vec<u8>,
+    /// Set once a mismatched input has been logged.
+    warned_video: bool,
+    warned_audio: bool,
+    frames_out: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for DecklinkSinkProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let format = SignalFormat::resolve(
+            &self.config.display_mode,
+            self.config.bit_depth,
+            self.config.audio_channels,
+        )?;
+        let device_index = self.config.device_index.unwrap_or(0);
+        let mut device = Device::open(device_index)?;
+        device.start_output(format.mode, format.depth, format.audio_channels)?;
+        tracing::info!(
+            "[DecklinkSink] Setup ({}, {} {:?}, {} audio channels)",
+            device.name(),
+            format.mode.name,
+            format.depth,
+            format.audio_channels
+        );
+        self.yuv =
+            vec![0u8; format.depth.row_bytes(format.mode.width) * format.mode.height as usize];
+        self.reference_monitor = Some(ReferenceMonitor::spawn(device_index)?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.format = Some(format);
+        self.device = Some(device);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(mut monitor) = self.reference_monitor.take() {
+            monitor.stop();
+        }
+        if let Some(mut device) = self.device.take() {
+            device.stop_output();
+        }
+        self.readback = None;
+        tracing::info!(
+            "[DecklinkSink] Teardown ({} frames played out)",
+            self.frames_out
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if self.inputs.has_data("audio") {
+            let audio: AudioFrame = self.inputs.read("audio")?;
+            self.play_audio(&audio)?;
+        }
+        if self.inputs.has_data("video") {
+            let frame: VideoFrame = self.inputs.read("video")?;
+            self.play_video(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl DecklinkSinkProcessor::Processor {
+    fn play_video(&mut self, frame: &VideoFrame) -> Result<()> {
+        let (Some(gpu), Some(format), Some(device)) =
+            (self.gpu_context.as_ref(), self.format, self.device.as_ref())
+        else {
+            return Err(Error::Configuration(
+                "DecklinkSink: device not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height, texture_format) = (texture.width(), texture.height(), texture.format());
+        let supported_format = matches!(
+            texture_format,
+            TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm
+        );
+        if !supported_format || (width, height) != (format.mode.width, format.mode.height) {
+            if !self.warned_video {
+                self.warned_video = true;
+                tracing::warn!(
+                    "[DecklinkSink] Dropping {}x{} {:?} frames: {} needs {}x{} RGBA8/BGRA8",
+                    width,
+                    height,
+                    texture_format,
+                    format.mode.name,
+                    format.mode.width,
+                    format.mode.height
+                );
+            }
+            return Ok(());
+        }
+
+        let readback = match self.readback.take() {
+            Some(readback) if readback.format() == texture_format => readback,
+            _ => gpu.escalate(|full| {
+                full.create_texture_readback("decklink-sink", width, height, texture_format)
+            })??,
+        };
+        let source_layout =
+            if registration.current_layout() == VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+                TextureSourceLayout::ShaderReadOnly
+            } else {
+                TextureSourceLayout::General
+            };
+        let ticket = readback.submit(&texture, source_layout)?;
+        let result = readback
+            .wait_and_read(ticket, READBACK_TIMEOUT.as_nanos() as u64)
+            .map(|pixels| {
+                if texture_format == TextureFormat::Bgra8Unorm {
+                    let mut rgba = pixels.to_vec();
+                    rgba.chunks_exact_mut(4).for_each(|px| px.swap(0, 2));
+                    pixel::rgba_to_yuv(&rgba, width, height, format.depth, &mut self.yuv);
+                } else {
+                    pixel::rgba_to_yuv(pixels, width, height, format.depth, &mut self.yuv);
+                }
+            });
+        self.readback = Some(readback);
+        result?;
+
+        device.output_frame(&self.yuv, format.depth.row_bytes(width))?;
+        self.frames_out += 1;
+        if self.frames_out == 1 {
+            tracing::info!("[DecklinkSink] First frame played out");
+        }
+        Ok(())
+    }
+
+    fn play_audio(&mut self, audio: &AudioFrame) -> Result<()> {
+        let (Some(format), Some(device)) = (self.format, self.device.as_ref()) else {
+            return Ok(());
+        };
+        if format.audio_channels == 0 {
+            return Ok(());
+        }
+        if audio.channels != format.audio_channels || audio.sample_rate != AUDIO_SAMPLE_RATE {
+            if !self.warned_audio {
+                self.warned_audio = true;
+                tracing::warn!(
+                    "[DecklinkSink] Dropping {} ch / {} Hz audio: output is {} ch / {} Hz (convert upstream)",
+                    audio.channels,
+                    audio.sample_rate,
+                    format.audio_channels,
+                    AUDIO_SAMPLE_RATE
+                );
+            }
+            return Ok(());
+        }
+        let samples = pixel::pcm_f32_to_i32(&audio.samples);
+        let sample_frames = samples.len() / usize::from(audio.channels);
+        let written = device.output_audio(&samples, audio.channels)?;
+        if written < sample_frames {
+            tracing::debug!(
+                "[DecklinkSink] Audio buffer full: dropped {} sample frames",
+                sample_frames - written
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! DeckLink SDI capture (Linux).
+//!
+//! The card delivers 8-bit UYVY or 10-bit v210 frames plus embedded audio
+//! on its own callback thread. Each frame is converted to RGBA8 straight
+//! into a pooled host-visible pixel buffer and published as a
+//! `VideoFrame`; embedded audio goes out as interleaved f32 `AudioFrame`s
+//! at 48 kHz. The card's reference-input status is reported to the host
+//! clock subsystem while capturing.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContextFullAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+use streamlib_plugin_sdk::sdk::rhi::PixelFormat;
+
+use crate::_generated_::tatolab__core::video_frame::FieldOrder;
+use crate::_generated_::{AudioFrame, VideoFrame};
+use crate::device::{CaptureHandler, Device};
+use crate::display_mode::{FieldDominance, SignalFormat};
+use crate::genlock::ReferenceMonitor;
+use crate::pixel::{self, BitDepth};
+
+/// Embedded audio is always captured at 48 kHz.
+const AUDIO_SAMPLE_RATE: u32 = 48_000;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/decklink/DecklinkSource",
+    description = "Captures SDI video (8-bit or 10-bit YUV) and embedded audio from a Blackmagic DeckLink card, and reports the card's genlock status to the clock subsystem.",
+    execution = manual,
+    config = crate::_generated_::DecklinkSourceConfig,
+    output("video", "@tatolab/core/VideoFrame", description = "Captured frames, converted to RGBA8"),
+    output("audio", "@tatolab/core/AudioFrame", format = "48khz", description = "Embedded audio, interleaved f32 at 48 kHz"),
+)]
+pub struct DecklinkSourceProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    format: Option<SignalFormat>,
+    device: Option<Device>,
+    reference_monitor: Option<ReferenceMonitor>,
+    frame_counter: Arc<AtomicU64>,
+}
+
+impl ManualProcessor for DecklinkSourceProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let format = SignalFormat::resolve(
+            &self.config.display_mode,
+            self.config.bit_depth,
+            self.config.audio_channels,
+        )?;
+        let device_index = self.config.device_index.unwrap_or(0);
+        let device = Device::open(device_index)?;
+        tracing::info!(
+            "[DecklinkSource] Setup ({}, {} {:?}, {} audio channels)",
+            device.name(),
+            format.mode.name,
+            format.depth,
+            format.audio_channels
+        );
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.format = Some(format);
+        self.device = Some(device);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop_capture();
+        self.device = None;
+        let frames = self.frame_counter.load(Ordering::Relaxed);
+        tracing::info!("[DecklinkSource] Teardown ({frames} frames captured)");
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let gpu_context = self
+            .gpu_context
+            .clone()
+            .ok_or_else(|| Error::Configuration("GPU context not initialized".into()))?;
+        let (Some(format), Some(device)) = (self.format, self.device.as_mut()) else {
+            return Err(Error::Configuration(
+                "DecklinkSource: device not initialized".into(),
+            ));
+        };
+        let handler = Capture {
+            outputs: self.outputs.clone(),
+            gpu_context,
+            depth: format.depth,
+            fps: (format.mode.frame_rate_num as f32 / format.mode.frame_rate_den as f32).round()
+                as u32,
+            field_order: match format.mode.field_dominance() {
+                FieldDominance::Progressive => FieldOrder::Progressive,
+                FieldDominance::UpperFirst => FieldOrder::TopFieldFirst,
+                FieldDominance::LowerFirst => FieldOrder::BottomFieldFirst,
+            },
+            frame_counter: Arc::clone(&self.frame_counter),
+            audio_frame_index: 0,
+            had_signal: None,
+        };
+        device.start_input(
+            format.mode,
+            format.depth,
+            format.audio_channels,
+            Box::new(handler),
+        )?;
+        self.reference_monitor = Some(ReferenceMonitor::spawn(
+            self.config.device_index.unwrap_or(0),
+        )?);
+        tracing::info!("[DecklinkSource] Capture started");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop_capture();
+        tracing::info!("[DecklinkSource] Stopped");
+        Ok(())
+    }
+}
+
+impl DecklinkSourceProcessor::Processor {
+    fn stop_capture(&mut self) {
+        if let Some(mut monitor) = self.reference_monitor.take() {
+            monitor.stop();
+        }
+        if let Some(device) = self.device.as_mut() {
+            device.stop_input();
+        }
+    }
+}
+
+/// Runs on the card's callback thread.
+struct Capture {
+    outputs: OutputWriter,
+    gpu_context: GpuContextLimitedAccess,
+    depth: BitDepth,
+    fps: u32,
+    field_order: FieldOrder,
+    frame_counter: Arc<AtomicU64>,
+    audio_frame_index: u64,
+    /// Last signal state logged, so loss/recovery is logged once per edge.
+    had_signal: Option<bool>,
+}
+
+impl Capture {
+    fn publish_video(&mut self, bytes: &[u8], row_bytes: usize, width: u32, height: u32) {
+        // Same pooled-upload path as BgraFileSource: the pool id is the
+        // surface id, and downstream resolution uploads the buffer.
+        let (pool_id, pixel_buffer) =
+            match self
+                .gpu_context
+                .acquire_pixel_buffer(width, height, PixelFormat::Rgba32)
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::warn!("[DecklinkSource] Failed to acquire pixel buffer: {e}");
+                    return;
+                }
+            };
+        let dst_ptr = pixel_buffer.plane_base_address(0);
+        let rgba_len = width as usize * height as usize * 4;
+        if dst_ptr.is_null() || (pixel_buffer.plane_size(0) as usize) < rgba_len {
+            tracing::warn!("[DecklinkSource] Pixel buffer is unmapped or too small");
+            return;
+        }
+        // SAFETY: `dst_ptr` is the mapped base of a (width, height, Rgba32)
+        // buffer, checked to hold `rgba_len` bytes, and nothing else
+        // references it until it is published below.
+        let dst = unsafe { std::slice::from_raw_parts_mut(dst_ptr, rgba_len) };
+        pixel::yuv_to_rgba(bytes, row_bytes, width, height, self.depth, dst);
+
+        let video_frame = VideoFrame {
+            surface_id: pool_id.to_string(),
+            width,
+            height,
+            timestamp_ns: MediaClock::now().as_nanos().to_string(),
+            fps: Some(self.fps),
+            texture_layout: None,
+            // The Y'CbCr → RGB conversion above already applied BT.709;
+            // the frame is plain RGB, like every other CPU-uploaded source.
+            color_info: None,
+            mastering_display: None,
+            content_light: None,
+            // Interlaced modes arrive as woven frames; mark them so a
+            // deinterlacer downstream knows the field order.
+            field_order: Some(self.field_order.clone()),
+        };
+        if let Err(e) = self.outputs.write("video", &video_frame) {
+            tracing::warn!("[DecklinkSource] Failed to write frame: {e}");
+            return;
+        }
+        let frames = self.frame_counter.fetch_add(1, Ordering::Relaxed) + 1;
+        if frames == 1 {
+            tracing::info!("[DecklinkSource] First frame published");
+        }
+    }
+}
+
+impl CaptureHandler for Capture {
+    fn video(&mut self, bytes: &[u8], row_bytes: usize, width: u32, height: u32, no_signal: bool) {
+        if self.had_signal != Some(!no_signal) {
+            if no_signal {
+                tracing::warn!("[DecklinkSource] No input signal");
+            } else {
+                tracing::info!("[DecklinkSource] Input signal present ({width}x{height})");
+            }
+            self.had_signal = Some(!no_signal);
+        }
+        if !no_signal {
+            self.publish_video(bytes, row_bytes, width, height);
+        }
+    }
+
+    fn audio(&mut self, samples: &[i32], channels: u8) {
+        if samples.is_empty() {
+            return;
+        }
+        let audio_frame = AudioFrame {
+            samples: pixel::pcm_i32_to_f32(samples),
+            channels,
+            sample_rate: AUDIO_SAMPLE_RATE,
+            timestamp_ns: MediaClock::now().as_nanos().to_string(),
+            frame_index: self.audio_frame_index.to_string(),
+            // Embedded SDI channels are discrete; their assignment is
+            // facility-specific.
+            channel_layout: None,
+        };
+        if let Err(e) = self.outputs.write("audio", &audio_frame) {
+            tracing::warn!("[DecklinkSource] Failed to write audio: {e}");
+            return;
+        }
+        self.audio_frame_index += 1;
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Safe wrapper over the C++ shim in `src/shim/` (Linux) — device
+//! enumeration, capture with callbacks, synchronous playout, and the
+//! reference-input status the genlock report is built from.
+
+use std::ffi::{CStr, c_char, c_void};
+
+use parking_lot::Mutex;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::display_mode::DisplayMode;
+use crate::pixel::BitDepth;
+
+#[repr(C)]
+struct DlShimDevice {
+    _opaque: [u8; 0],
+}
+
+type VideoCallback = unsafe extern "C" fn(
+    user: *mut c_void,
+    bytes: *const u8,
+    row_bytes: i32,
+    width: i32,
+    height: i32,
+    no_signal: i32,
+    stream_time_ns: i64,
+);
+
+type AudioCallback = unsafe extern "C" fn(
+    user: *mut c_void,
+    samples: *const i32,
+    sample_frames: i32,
+    channels: i32,
+    packet_time_ns: i64,
+);
+
+unsafe extern "C" {
+    fn dl_shim_device_count() -> i32;
+    fn dl_shim_device_open(index: i32) -> *mut DlShimDevice;
+    fn dl_shim_device_close(device: *mut DlShimDevice);
+    fn dl_shim_device_name(device: *mut DlShimDevice, buf: *mut c_char, len: usize) -> i32;
+    fn dl_shim_input_start(
+        device: *mut DlShimDevice,
+        display_mode: u32,
+        pixel_format: u32,
+        audio_channels: i32,
+        video_cb: VideoCallback,
+        audio_cb: AudioCallback,
+        user: *mut c_void,
+    ) -> i32;
+    fn dl_shim_input_stop(device: *mut DlShimDevice);
+    fn dl_shim_output_start(
+        device: *mut DlShimDevice,
+        display_mode: u32,
+        pixel_format: u32,
+        width: i32,
+        height: i32,
+        audio_channels: i32,
+    ) -> i32;
+    fn dl_shim_output_frame(device: *mut DlShimDevice, bytes: *const u8, row_bytes: i32) -> i32;
+    fn dl_shim_output_audio(
+        device: *mut DlShimDevice,
+        samples: *const i32,
+        sample_frames: i32,
+    ) -> i32;
+    fn dl_shim_output_stop(device: *mut DlShimDevice);
+    fn dl_shim_reference_status(device: *mut DlShimDevice, locked: *mut i32, mode: *mut u32)
+    -> i32;
+}
+
+/// Receives captured media on DeckLink's callback thread.
+pub trait CaptureHandler: Send {
+    /// One frame in the card's layout; `bytes` is only valid for the call.
+    fn video(&mut self, bytes: &[u8], row_bytes: usize, width: u32, height: u32, no_signal: bool);
+    /// Interleaved 32-bit embedded audio that arrived with the frame.
+    fn audio(&mut self, samples: &[i32], channels: u8);
+}
+
+type SharedHandler = Mutex<Box<dyn CaptureHandler>>;
+
+/// Reference-input lock state as the card reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceStatus {
+    pub locked: bool,
+    /// Detected `BMDDisplayMode` while locked.
+    pub mode: Option<u32>,
+}
+
+/// An open DeckLink device.
+pub struct Device {
+    raw: *mut DlShimDevice,
+    index: u32,
+    /// Boxed handler the shim's `user` pointer refers to while capturing.
+    capture: Option<Box<SharedHandler>>,
+}
+
+// SAFETY: the DeckLink interfaces behind `raw` are free-threaded, and the
+// shim serializes playout calls internally.
+unsafe impl Send for Device {}
+
+impl Device {
+    /// Number of DeckLink devices the driver reports.
+    pub fn count() -> u32 {
+        // SAFETY: no arguments; returns 0 when the driver is missing.
+        unsafe { dl_shim_device_count() }.max(0) as u32
+    }
+
+    pub fn open(index: u32) -> Result<Self> {
+        // SAFETY: the shim bounds-checks `index` and returns null on failure.
+        let raw = unsafe { dl_shim_device_open(index as i32) };
+        if raw.is_null() {
+            return Err(Error::Configuration(format!(
+                "DeckLink device {} not found ({} present; is Desktop Video installed?)",
+                index,
+                Self::count()
+            )));
+        }
+        Ok(Self {
+            raw,
+            index,
+            capture: None,
+        })
+    }
+
+    /// Stable id used for reference-signal reports, e.g. `"decklink:0"`.
+    pub fn source_id(&self) -> String {
+        format!("decklink:{}", self.index)
+    }
+
+    pub fn name(&self) -> String {
+        let mut buf = [0 as c_char; 256];
+        // SAFETY: `buf` is writable for its full length; the shim
+        // NUL-terminates within it.
+        let status = unsafe { dl_shim_device_name(self.raw, buf.as_mut_ptr(), buf.len()) };
+        if status != 0 {
+            return self.source_id();
+        }
+        // SAFETY: NUL-terminated by the shim (checked status above).
+        unsafe { CStr::from_ptr(buf.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Start capture; `handler` runs on the card's callback thread until
+    /// [`Self::stop_input`].
+    pub fn start_input(
+        &mut self,
+        mode: &DisplayMode,
+        depth: BitDepth,
+        audio_channels: u8,
+        handler: Box<dyn CaptureHandler>,
+    ) -> Result<()> {
+        let capture: Box<SharedHandler> = Box::new(Mutex::new(handler));
+        let user = &*capture as *const SharedHandler as *mut c_void;
+        // SAFETY: `user` points at the boxed handler, which stays alive in
+        // `self.capture` until `stop_input` has returned (the shim
+        // guarantees no callback runs after that).
+        let status = unsafe {
+            dl_shim_input_start(
+                self.raw,
+                mode.fourcc,
+                depth.fourcc(),
+                i32::from(audio_channels),
+                video_trampoline,
+                audio_trampoline,
+                user,
+            )
+        };
+        if status != 0 {
+            return Err(Error::Configuration(format!(
+                "{}: capture in {} ({:?}) failed (shim status {})",
+                self.name(),
+                mode.name,
+                depth,
+                status
+            )));
+        }
+        self.capture = Some(capture);
+        Ok(())
+    }
+
+    pub fn stop_input(&mut self) {
+        // SAFETY: a no-op when capture never started; returns once no
+        // callback is running, so the handler can be dropped after.
+        unsafe { dl_shim_input_stop(self.raw) };
+        self.capture = None;
+    }
+
+    pub fn start_output(
+        &mut self,
+        mode: &DisplayMode,
+        depth: BitDepth,
+        audio_channels: u8,
+    ) -> Result<()> {
+        // SAFETY: `raw` is a live device.
+        let status = unsafe {
+            dl_shim_output_start(
+                self.raw,
+                mode.fourcc,
+                depth.fourcc(),
+                mode.width as i32,
+                mode.height as i32,
+                i32::from(audio_channels),
+            )
+        };
+        if status != 0 {
+            return Err(Error::Configuration(format!(
+                "{}: playout in {} ({:?}) failed (shim status {})",
+                self.name(),
+                mode.name,
+                depth,
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    /// Display one frame in the card's layout (blocks until scheduled).
+    pub fn output_frame(&self, bytes: &[u8], row_bytes: usize) -> Result<()> {
+        // SAFETY: the shim reads `frame height` rows of `row_bytes` from
+        // `bytes`, which callers size from the same mode.
+        let status = unsafe { dl_shim_output_frame(self.raw, bytes.as_ptr(), row_bytes as i32) };
+        if status != 0 {
+            return Err(Error::Runtime(format!(
+                "DeckLink frame output failed (shim status {status})"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Queue interleaved audio; returns the sample frames the card took.
+    pub fn output_audio(&self, samples: &[i32], channels: u8) -> Result<usize> {
+        let sample_frames = samples.len() / usize::from(channels.max(1));
+        // SAFETY: `samples` holds `sample_frames * channels` values, the
+        // channel count the output was enabled with.
+        let written =
+            unsafe { dl_shim_output_audio(self.raw, samples.as_ptr(), sample_frames as i32) };
+        if written < 0 {
+            return Err(Error::Runtime(format!(
+                "DeckLink audio output failed (shim status {written})"
+            )));
+        }
+        Ok(written as usize)
+    }
+
+    pub fn stop_output(&mut self) {
+        // SAFETY: a no-op when playout never started.
+        unsafe { dl_shim_output_stop(self.raw) };
+    }
+
+    /// `None` when the card has no reference input.
+    pub fn reference_status(&self) -> Option<ReferenceStatus> {
+        let (mut locked, mut mode) = (0i32, 0u32);
+        // SAFETY: both out-pointers are valid for writes.
+        let status = unsafe { dl_shim_reference_status(self.raw, &mut locked, &mut mode) };
+        (status == 0).then_some(ReferenceStatus {
+            locked: locked != 0,
+            mode: (mode != 0).then_some(mode),
+        })
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        // SAFETY: stops capture/playout before releasing, and `raw` is not
+        // used again.
+        unsafe { dl_shim_device_close(self.raw) };
+        self.capture = None;
+    }
+}
+
+unsafe extern "C" fn video_trampoline(
+    user: *mut c_void,
+    bytes: *const u8,
+    row_bytes: i32,
+    width: i32,
+    height: i32,
+    no_signal: i32,
+    _stream_time_ns: i64,
+) {
+    // SAFETY: `user` is the `SharedHandler` `start_input` registered, alive
+    // until `stop_input` returns.
+    let handler = unsafe { &*(user as *const SharedHandler) };
+    let (row_bytes, height_rows) = (row_bytes.max(0) as usize, height.max(0) as usize);
+    // SAFETY: the card's frame buffer holds `height` rows of `row_bytes`
+    // for the duration of this call.
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, row_bytes * height_rows) };
+    handler.lock().video(
+        bytes,
+        row_bytes,
+        width.max(0) as u32,
+        height_rows as u32,
+        no_signal != 0,
+    );
+}
+
+unsafe extern "C" fn audio_trampoline(
+    user: *mut c_void,
+    samples: *const i32,
+    sample_frames: i32,
+    channels: i32,
+    _packet_time_ns: i64,
+) {
+    // SAFETY: as in `video_trampoline`.
+    let handler = unsafe { &*(user as *const SharedHandler) };
+    let len = sample_frames.max(0) as usize * channels.max(0) as usize;
+    // SAFETY: the packet holds `sample_frames * channels` interleaved
+    // 32-bit samples for the duration of this call.
+    let samples = unsafe { std::slice::from_raw_parts(samples, len) };
+    handler.lock().audio(samples, channels as u8);
+}
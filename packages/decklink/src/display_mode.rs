@@ -0,0 +1,196 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! SDI display modes by the names the config uses, mapped to their
+//! `BMDDisplayMode` FourCCs, and the config-to-signal-format resolution
+//! both processors share.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::pixel::BitDepth;
+
+/// One supported `BMDDisplayMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayMode {
+    /// Config name, e.g. `"1080i59.94"`.
+    pub name: &'static str,
+    pub fourcc: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Frame rate as `frame_rate_num / frame_rate_den` (frames, not
+    /// fields, per second).
+    pub frame_rate_num: u32,
+    pub frame_rate_den: u32,
+}
+
+/// Temporal order of the two fields of an interlaced mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldDominance {
+    Progressive,
+    /// Upper (even-line) field first.
+    UpperFirst,
+    /// Lower (odd-line) field first.
+    LowerFirst,
+}
+
+impl DisplayMode {
+    /// The card's `BMDFieldDominance` for this mode: NTSC carries the
+    /// lower field first, PAL and 1080i the upper field.
+    pub fn field_dominance(&self) -> FieldDominance {
+        match self.name {
+            "ntsc" => FieldDominance::LowerFirst,
+            "pal" => FieldDominance::UpperFirst,
+            name if name.contains('i') => FieldDominance::UpperFirst,
+            _ => FieldDominance::Progressive,
+        }
+    }
+}
+
+const fn mode(
+    name: &'static str,
+    fourcc: &[u8; 4],
+    width: u32,
+    height: u32,
+    frame_rate_num: u32,
+    frame_rate_den: u32,
+) -> DisplayMode {
+    DisplayMode {
+        name,
+        fourcc: u32::from_be_bytes(*fourcc),
+        width,
+        height,
+        frame_rate_num,
+        frame_rate_den,
+    }
+}
+
+pub const DISPLAY_MODES: &[DisplayMode] = &[
+    mode("ntsc", b"ntsc", 720, 486, 30000, 1001),
+    mode("pal", b"pal ", 720, 576, 25, 1),
+    mode("720p50", b"hp50", 1280, 720, 50, 1),
+    mode("720p59.94", b"hp59", 1280, 720, 60000, 1001),
+    mode("720p60", b"hp60", 1280, 720, 60, 1),
+    mode("1080p23.98", b"23ps", 1920, 1080, 24000, 1001),
+    mode("1080p24", b"24ps", 1920, 1080, 24, 1),
+    mode("1080p25", b"Hp25", 1920, 1080, 25, 1),
+    mode("1080p29.97", b"Hp29", 1920, 1080, 30000, 1001),
+    mode("1080p30", b"Hp30", 1920, 1080, 30, 1),
+    mode("1080p50", b"Hp50", 1920, 1080, 50, 1),
+    mode("1080p59.94", b"Hp59", 1920, 1080, 60000, 1001),
+    mode("1080p60", b"Hp60", 1920, 1080, 60, 1),
+    mode("1080i50", b"Hi50", 1920, 1080, 25, 1),
+    mode("1080i59.94", b"Hi59", 1920, 1080, 30000, 1001),
+    mode("1080i60", b"Hi60", 1920, 1080, 30, 1),
+    mode("2160p25", b"4k25", 3840, 2160, 25, 1),
+    mode("2160p29.97", b"4k29", 3840, 2160, 30000, 1001),
+    mode("2160p30", b"4k30", 3840, 2160, 30, 1),
+    mode("2160p50", b"4k50", 3840, 2160, 50, 1),
+    mode("2160p59.94", b"4k59", 3840, 2160, 60000, 1001),
+    mode("2160p60", b"4k60", 3840, 2160, 60, 1),
+];
+
+/// Look a mode up by config name.
+pub fn by_name(name: &str) -> Option<&'static DisplayMode> {
+    DISPLAY_MODES.iter().find(|mode| mode.name == name)
+}
+
+/// Name for a mode the card reports (e.g. the detected reference format).
+/// Modes outside the table are shown as their FourCC.
+pub fn name_of(fourcc: u32) -> String {
+    DISPLAY_MODES
+        .iter()
+        .find(|mode| mode.fourcc == fourcc)
+        .map(|mode| mode.name.to_string())
+        .unwrap_or_else(|| String::from_utf8_lossy(&fourcc.to_be_bytes()).into_owned())
+}
+
+/// Embedded-audio channel counts the card supports that fit an
+/// `AudioFrame` (at most 8 channels).
+pub const SUPPORTED_AUDIO_CHANNELS: [u8; 3] = [0, 2, 8];
+
+const DEFAULT_BIT_DEPTH: u8 = 10;
+const DEFAULT_AUDIO_CHANNELS: u8 = 2;
+
+/// Video mode, YUV depth and embedded-audio channel count of one SDI port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalFormat {
+    pub mode: &'static DisplayMode,
+    pub depth: BitDepth,
+    /// 0 when audio is disabled.
+    pub audio_channels: u8,
+}
+
+impl SignalFormat {
+    /// Validate the config fields both processors share.
+    pub fn resolve(
+        display_mode: &str,
+        bit_depth: Option<u8>,
+        audio_channels: Option<u8>,
+    ) -> Result<Self> {
+        let mode = by_name(display_mode).ok_or_else(|| {
+            let known: Vec<&str> = DISPLAY_MODES.iter().map(|mode| mode.name).collect();
+            Error::Configuration(format!(
+                "unknown display_mode '{}' (expected one of: {})",
+                display_mode,
+                known.join(", ")
+            ))
+        })?;
+        let depth = match bit_depth.unwrap_or(DEFAULT_BIT_DEPTH) {
+            8 => BitDepth::Eight,
+            10 => BitDepth::Ten,
+            other => {
+                return Err(Error::Configuration(format!(
+                    "bit_depth must be 8 or 10, got {other}"
+                )));
+            }
+        };
+        let audio_channels = audio_channels.unwrap_or(DEFAULT_AUDIO_CHANNELS);
+        if !SUPPORTED_AUDIO_CHANNELS.contains(&audio_channels) {
+            return Err(Error::Configuration(format!(
+                "audio_channels must be one of {SUPPORTED_AUDIO_CHANNELS:?}, got {audio_channels}"
+            )));
+        }
+        Ok(Self {
+            mode,
+            depth,
+            audio_channels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_fourccs_resolve_both_ways() {
+        let mode = by_name("1080i59.94").unwrap();
+
+        assert_eq!(mode.fourcc, u32::from_be_bytes(*b"Hi59"));
+        assert_eq!(name_of(mode.fourcc), "1080i59.94");
+        assert_eq!(name_of(u32::from_be_bytes(*b"4d25")), "4d25");
+        assert!(by_name("1080p61").is_none());
+    }
+
+    #[test]
+    fn interlaced_modes_report_their_field_dominance() {
+        let dominance = |name| by_name(name).unwrap().field_dominance();
+
+        assert_eq!(dominance("ntsc"), FieldDominance::LowerFirst);
+        assert_eq!(dominance("pal"), FieldDominance::UpperFirst);
+        assert_eq!(dominance("1080i59.94"), FieldDominance::UpperFirst);
+        assert_eq!(dominance("1080p59.94"), FieldDominance::Progressive);
+        assert_eq!(dominance("2160p25"), FieldDominance::Progressive);
+    }
+
+    #[test]
+    fn signal_format_applies_defaults_and_rejects_bad_values() {
+        let format = SignalFormat::resolve("1080p25", None, None).unwrap();
+        assert_eq!(format.depth, BitDepth::Ten);
+        assert_eq!(format.audio_channels, 2);
+
+        assert!(SignalFormat::resolve("1080p25", Some(12), None).is_err());
+        assert!(SignalFormat::resolve("1080p25", None, Some(16)).is_err());
+        assert!(SignalFormat::resolve("1080p26", None, None).is_err());
+    }
+}
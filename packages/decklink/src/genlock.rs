@@ -0,0 +1,78 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Reference-input (genlock) monitor shared by the source and sink.
+//!
+//! Polls the card's reference status on its own device handle and reports
+//! it to the host clock subsystem through
+//! [`streamlib_plugin_sdk::sdk::media_clock::report_reference_signal`]. The
+//! host only announces changes, so reporting every poll is cheap.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::report_reference_signal;
+
+use crate::device::Device;
+use crate::display_mode;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Granularity of the stop check while waiting out a poll interval.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+pub(crate) struct ReferenceMonitor {
+    is_running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReferenceMonitor {
+    pub(crate) fn spawn(device_index: u32) -> Result<Self> {
+        let device = Device::open(device_index)?;
+        let is_running = Arc::new(AtomicBool::new(true));
+        let running = Arc::clone(&is_running);
+        let handle = std::thread::Builder::new()
+            .name("decklink-reference".into())
+            .spawn(move || {
+                while running.load(Ordering::Acquire) {
+                    report(&device);
+                    let mut waited = Duration::ZERO;
+                    while waited < POLL_INTERVAL && running.load(Ordering::Acquire) {
+                        std::thread::sleep(STOP_CHECK_INTERVAL);
+                        waited += STOP_CHECK_INTERVAL;
+                    }
+                }
+            })
+            .map_err(|e| Error::Configuration(format!("Failed to spawn reference monitor: {e}")))?;
+        Ok(Self {
+            is_running,
+            handle: Some(handle),
+        })
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.is_running.store(false, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ReferenceMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn report(device: &Device) {
+    // Cards without a reference input have nothing to report.
+    let Some(status) = device.reference_status() else {
+        return;
+    };
+    let mode = status.mode.map(display_mode::name_of);
+    if let Err(e) = report_reference_signal(&device.source_id(), status.locked, mode.as_deref()) {
+        tracing::debug!("[Decklink] reference status not reported: {}", e);
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/decklink` — SDI capture and playout on Blackmagic DeckLink
+//! cards. `DecklinkSource` captures 8/10-bit YUV video and embedded audio;
+//! `DecklinkSink` plays them out. Both report the card's reference-input
+//! (genlock) status to the host clock subsystem.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod display_mode;
+pub mod pixel;
+
+// The DeckLink SDK shim is built for Linux only (Desktop Video's
+// libDeckLinkAPI.so); see build.rs.
+#[cfg(target_os = "linux")]
+pub mod decklink_sink;
+#[cfg(target_os = "linux")]
+pub mod decklink_source;
+#[cfg(target_os = "linux")]
+pub mod device;
+#[cfg(target_os = "linux")]
+mod genlock;
+
+#[cfg(target_os = "linux")]
+pub use decklink_sink::DecklinkSinkProcessor;
+#[cfg(target_os = "linux")]
+pub use decklink_source::DecklinkSourceProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::DecklinkSourceProcessor::Processor,
+    crate::DecklinkSinkProcessor::Processor,
+);
@@ -0,0 +1,347 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! CPU conversions between the pipeline's RGBA8 frames and the DeckLink
+//! wire formats: 8-bit `2vuy` (UYVY) and 10-bit `v210`, both 4:2:2 BT.709
+//! limited range.
+//!
+//! `v210` packs six pixels into four little-endian 32-bit words, three
+//! 10-bit components per word:
+//!
+//! | word | bits 0..10 | bits 10..20 | bits 20..30 |
+//! |------|------------|-------------|-------------|
+//! | 0    | Cb0        | Y0          | Cr0         |
+//! | 1    | Y1         | Cb2         | Y2          |
+//! | 2    | Cr2        | Y3          | Cb4         |
+//! | 3    | Y4         | Cr4         | Y5          |
+//!
+//! and pads each row to a multiple of 48 pixels (128 bytes).
+
+/// Component depth of a DeckLink YUV format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitDepth {
+    /// `bmdFormat8BitYUV` ('2vuy').
+    Eight,
+    /// `bmdFormat10BitYUV` ('v210').
+    Ten,
+}
+
+impl BitDepth {
+    /// `BMDPixelFormat` FourCC.
+    pub fn fourcc(self) -> u32 {
+        match self {
+            BitDepth::Eight => u32::from_be_bytes(*b"2vuy"),
+            BitDepth::Ten => u32::from_be_bytes(*b"v210"),
+        }
+    }
+
+    /// Bytes per row the card expects for `width` pixels.
+    pub fn row_bytes(self, width: u32) -> usize {
+        match self {
+            BitDepth::Eight => width.div_ceil(2) as usize * 4,
+            BitDepth::Ten => width.div_ceil(48) as usize * 128,
+        }
+    }
+
+    fn scale(self) -> f32 {
+        match self {
+            BitDepth::Eight => 1.0,
+            BitDepth::Ten => 4.0,
+        }
+    }
+
+    fn max_code(self) -> f32 {
+        match self {
+            BitDepth::Eight => 255.0,
+            BitDepth::Ten => 1023.0,
+        }
+    }
+}
+
+/// Limited-range BT.709 Y'CbCr code values at the given depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ycc {
+    y: u16,
+    cb: u16,
+    cr: u16,
+}
+
+fn rgb_to_ycc(rgba: &[u8], depth: BitDepth) -> Ycc {
+    let [r, g, b] = [rgba[0], rgba[1], rgba[2]].map(|c| f32::from(c) / 255.0);
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let cb = (b - y) / 1.8556;
+    let cr = (r - y) / 1.5748;
+    let scale = depth.scale();
+    let code = |v: f32| v.round().clamp(0.0, depth.max_code()) as u16;
+    Ycc {
+        y: code((16.0 + 219.0 * y) * scale),
+        cb: code((128.0 + 224.0 * cb) * scale),
+        cr: code((128.0 + 224.0 * cr) * scale),
+    }
+}
+
+fn ycc_to_rgb(y: u16, cb: u16, cr: u16, depth: BitDepth, rgba: &mut [u8]) {
+    let scale = depth.scale();
+    let y = (f32::from(y) / scale - 16.0) / 219.0;
+    let cb = (f32::from(cb) / scale - 128.0) / 224.0;
+    let cr = (f32::from(cr) / scale - 128.0) / 224.0;
+    let rgb = [
+        y + 1.5748 * cr,
+        y - 0.1873 * cb - 0.4681 * cr,
+        y + 1.8556 * cb,
+    ];
+    for (dst, v) in rgba.iter_mut().zip(rgb) {
+        *dst = (v * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    rgba[3] = 255;
+}
+
+/// Y'CbCr for a horizontal pixel pair: own luma, averaged chroma. The last
+/// pixel of an odd-width row pairs with itself.
+fn encode_pair(row: &[u8], x: usize, width: usize, depth: BitDepth) -> (Ycc, u16) {
+    let a = rgb_to_ycc(&row[x * 4..x * 4 + 4], depth);
+    let next = (x + 1).min(width - 1);
+    let b = rgb_to_ycc(&row[next * 4..next * 4 + 4], depth);
+    let pair = Ycc {
+        y: a.y,
+        cb: (a.cb + b.cb).div_ceil(2),
+        cr: (a.cr + b.cr).div_ceil(2),
+    };
+    (pair, b.y)
+}
+
+/// Convert a tightly packed RGBA8 image to the card's layout.
+/// `dst` must hold `depth.row_bytes(width) * height` bytes.
+pub fn rgba_to_yuv(rgba: &[u8], width: u32, height: u32, depth: BitDepth, dst: &mut [u8]) {
+    let (width, height) = (width as usize, height as usize);
+    let row_bytes = depth.row_bytes(width as u32);
+    for (src_row, dst_row) in rgba
+        .chunks_exact(width * 4)
+        .zip(dst.chunks_exact_mut(row_bytes))
+        .take(height)
+    {
+        match depth {
+            BitDepth::Eight => encode_uyvy_row(src_row, width, dst_row),
+            BitDepth::Ten => encode_v210_row(src_row, width, dst_row),
+        }
+    }
+}
+
+/// Convert the card's layout (`row_bytes` stride) to tightly packed RGBA8.
+/// `dst` must hold `width * height * 4` bytes.
+pub fn yuv_to_rgba(
+    src: &[u8],
+    row_bytes: usize,
+    width: u32,
+    height: u32,
+    depth: BitDepth,
+    dst: &mut [u8],
+) {
+    let (width, height) = (width as usize, height as usize);
+    for (src_row, dst_row) in src
+        .chunks(row_bytes)
+        .zip(dst.chunks_exact_mut(width * 4))
+        .take(height)
+    {
+        match depth {
+            BitDepth::Eight => decode_uyvy_row(src_row, width, dst_row),
+            BitDepth::Ten => decode_v210_row(src_row, width, dst_row),
+        }
+    }
+}
+
+fn encode_uyvy_row(src: &[u8], width: usize, dst: &mut [u8]) {
+    for x in (0..width).step_by(2) {
+        let (first, second_y) = encode_pair(src, x, width, BitDepth::Eight);
+        let out = &mut dst[x * 2..x * 2 + 4];
+        out[0] = first.cb as u8;
+        out[1] = first.y as u8;
+        out[2] = first.cr as u8;
+        out[3] = second_y as u8;
+    }
+}
+
+fn decode_uyvy_row(src: &[u8], width: usize, dst: &mut [u8]) {
+    for x in (0..width).step_by(2) {
+        let group = &src[x * 2..x * 2 + 4];
+        let (cb, cr) = (u16::from(group[0]), u16::from(group[2]));
+        ycc_to_rgb(
+            u16::from(group[1]),
+            cb,
+            cr,
+            BitDepth::Eight,
+            &mut dst[x * 4..x * 4 + 4],
+        );
+        if x + 1 < width {
+            ycc_to_rgb(
+                u16::from(group[3]),
+                cb,
+                cr,
+                BitDepth::Eight,
+                &mut dst[(x + 1) * 4..(x + 2) * 4],
+            );
+        }
+    }
+}
+
+fn encode_v210_row(src: &[u8], width: usize, dst: &mut [u8]) {
+    for (block, out) in dst.chunks_exact_mut(16).enumerate() {
+        let first = block * 6;
+        if first >= width {
+            out.fill(0);
+            continue;
+        }
+        // Three pixel pairs: (Cb, Y, Cr, Y) each.
+        let mut components = [0u16; 12];
+        for pair in 0..3 {
+            let x = (first + pair * 2).min(width - 1);
+            let (ycc, second_y) = encode_pair(src, x, width, BitDepth::Ten);
+            components[pair * 4..pair * 4 + 4].copy_from_slice(&[ycc.cb, ycc.y, ycc.cr, second_y]);
+        }
+        for (word, c) in out.chunks_exact_mut(4).zip(components.chunks_exact(3)) {
+            let packed = u32::from(c[0]) | (u32::from(c[1]) << 10) | (u32::from(c[2]) << 20);
+            word.copy_from_slice(&packed.to_le_bytes());
+        }
+    }
+}
+
+fn decode_v210_row(src: &[u8], width: usize, dst: &mut [u8]) {
+    for (block, words) in src.chunks_exact(16).enumerate() {
+        let first = block * 6;
+        if first >= width {
+            break;
+        }
+        let mut components = [0u16; 12];
+        for (i, word) in words.chunks_exact(4).enumerate() {
+            let packed = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+            for j in 0..3 {
+                components[i * 3 + j] = ((packed >> (10 * j)) & 0x3ff) as u16;
+            }
+        }
+        for pair in 0..3 {
+            let [cb, y0, cr, y1] = [0, 1, 2, 3].map(|k| components[pair * 4 + k]);
+            for (offset, y) in [(0, y0), (1, y1)] {
+                let x = first + pair * 2 + offset;
+                if x < width {
+                    ycc_to_rgb(y, cb, cr, BitDepth::Ten, &mut dst[x * 4..x * 4 + 4]);
+                }
+            }
+        }
+    }
+}
+
+/// Convert interleaved signed 32-bit embedded audio to the pipeline's f32.
+pub fn pcm_i32_to_f32(samples: &[i32]) -> Vec<f32> {
+    samples
+        .iter()
+        .map(|&s| s as f32 / 2_147_483_648.0)
+        .collect()
+}
+
+/// Convert the pipeline's f32 audio to interleaved signed 32-bit PCM.
+pub fn pcm_f32_to_i32(samples: &[f32]) -> Vec<i32> {
+    samples
+        .iter()
+        .map(|&s| (f64::from(s.clamp(-1.0, 1.0)) * 2_147_483_647.0) as i32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 100% colour bars, one pixel each, repeated to `width`.
+    fn bars(width: u32, height: u32) -> Vec<u8> {
+        const BARS: [[u8; 4]; 8] = [
+            [255, 255, 255, 255],
+            [255, 255, 0, 255],
+            [0, 255, 255, 255],
+            [0, 255, 0, 255],
+            [255, 0, 255, 255],
+            [255, 0, 0, 255],
+            [0, 0, 255, 255],
+            [0, 0, 0, 255],
+        ];
+        (0..width * height)
+            .flat_map(|i| BARS[((i % width) / 2) as usize % BARS.len()])
+            .collect()
+    }
+
+    fn round_trip(width: u32, height: u32, depth: BitDepth) -> (Vec<u8>, Vec<u8>) {
+        let rgba = bars(width, height);
+        let row_bytes = depth.row_bytes(width);
+        let mut yuv = vec![0u8; row_bytes * height as usize];
+        rgba_to_yuv(&rgba, width, height, depth, &mut yuv);
+        let mut back = vec![0u8; rgba.len()];
+        yuv_to_rgba(&yuv, row_bytes, width, height, depth, &mut back);
+        (rgba, back)
+    }
+
+    fn max_error(a: &[u8], b: &[u8]) -> u8 {
+        a.iter().zip(b).map(|(x, y)| x.abs_diff(*y)).max().unwrap()
+    }
+
+    #[test]
+    fn v210_rows_pad_to_48_pixel_blocks() {
+        assert_eq!(BitDepth::Ten.row_bytes(1920), 5120);
+        assert_eq!(BitDepth::Ten.row_bytes(1280), 3456);
+        assert_eq!(BitDepth::Ten.row_bytes(720), 1920);
+        assert_eq!(BitDepth::Eight.row_bytes(1920), 3840);
+    }
+
+    #[test]
+    fn white_and_black_hit_legal_range_limits() {
+        assert_eq!(
+            rgb_to_ycc(&[255, 255, 255, 255], BitDepth::Eight),
+            Ycc {
+                y: 235,
+                cb: 128,
+                cr: 128
+            }
+        );
+        assert_eq!(
+            rgb_to_ycc(&[0, 0, 0, 255], BitDepth::Ten),
+            Ycc {
+                y: 64,
+                cb: 512,
+                cr: 512
+            }
+        );
+    }
+
+    #[test]
+    fn colour_bars_round_trip_through_uyvy() {
+        // Bars are two pixels wide, so 4:2:2 loses no chroma.
+        let (rgba, back) = round_trip(32, 4, BitDepth::Eight);
+
+        assert!(
+            max_error(&rgba, &back) <= 2,
+            "max error {}",
+            max_error(&rgba, &back)
+        );
+    }
+
+    #[test]
+    fn colour_bars_round_trip_through_v210_with_row_padding() {
+        // 50 pixels: one full 48-pixel group plus a padded partial block.
+        let (rgba, back) = round_trip(50, 3, BitDepth::Ten);
+
+        assert!(
+            max_error(&rgba, &back) <= 1,
+            "max error {}",
+            max_error(&rgba, &back)
+        );
+    }
+
+    #[test]
+    fn pcm_conversion_round_trips_and_clips() {
+        let samples = [0.0, 0.5, -0.5, 1.0, -1.0];
+
+        let back = pcm_i32_to_f32(&pcm_f32_to_i32(&samples));
+
+        for (a, b) in samples.iter().zip(&back) {
+            assert!((a - b).abs() < 1e-6);
+        }
+        assert_eq!(pcm_f32_to_i32(&[2.0]), [i32::MAX]);
+    }
+}
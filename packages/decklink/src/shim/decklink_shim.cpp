@@ -0,0 +1,312 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+#include "decklink_shim.h"
+
+#include <DeckLinkAPI.h>
+
+#include <atomic>
+#include <cstdlib>
+#include <cstring>
+#include <mutex>
+
+namespace {
+
+constexpr int32_t kOk = 0;
+constexpr int32_t kUnsupported = -1;
+constexpr int32_t kFailed = -2;
+constexpr BMDTimeScale kNanoseconds = 1000000000;
+
+class InputCallback final : public IDeckLinkInputCallback {
+public:
+    InputCallback(dl_shim_video_cb video_cb, dl_shim_audio_cb audio_cb, void* user,
+                  int32_t audio_channels)
+        : video_cb_(video_cb), audio_cb_(audio_cb), user_(user),
+          audio_channels_(audio_channels) {}
+
+    HRESULT STDMETHODCALLTYPE VideoInputFormatChanged(
+        BMDVideoInputFormatChangedEvents, IDeckLinkDisplayMode*,
+        BMDDetectedVideoInputFormatFlags) override {
+        // The configured mode is fixed; a mismatched signal arrives as
+        // no-signal frames.
+        return S_OK;
+    }
+
+    HRESULT STDMETHODCALLTYPE VideoInputFrameArrived(IDeckLinkVideoInputFrame* video,
+                                                     IDeckLinkAudioInputPacket* audio) override {
+        if (video != nullptr) {
+            void* bytes = nullptr;
+            BMDTimeValue time = 0;
+            BMDTimeValue duration = 0;
+            video->GetStreamTime(&time, &duration, kNanoseconds);
+            if (video->GetBytes(&bytes) == S_OK) {
+                video_cb_(user_, static_cast<const uint8_t*>(bytes),
+                          static_cast<int32_t>(video->GetRowBytes()),
+                          static_cast<int32_t>(video->GetWidth()),
+                          static_cast<int32_t>(video->GetHeight()),
+                          (video->GetFlags() & bmdFrameHasNoInputSource) != 0, time);
+            }
+        }
+        if (audio != nullptr && audio_channels_ > 0) {
+            void* bytes = nullptr;
+            BMDTimeValue time = 0;
+            audio->GetPacketTime(&time, kNanoseconds);
+            if (audio->GetBytes(&bytes) == S_OK) {
+                audio_cb_(user_, static_cast<const int32_t*>(bytes),
+                          static_cast<int32_t>(audio->GetSampleFrameCount()), audio_channels_,
+                          time);
+            }
+        }
+        return S_OK;
+    }
+
+    HRESULT STDMETHODCALLTYPE QueryInterface(REFIID, LPVOID* ppv) override {
+        *ppv = nullptr;
+        return E_NOINTERFACE;
+    }
+
+    ULONG STDMETHODCALLTYPE AddRef() override { return ++refs_; }
+
+    ULONG STDMETHODCALLTYPE Release() override {
+        ULONG remaining = --refs_;
+        if (remaining == 0) {
+            delete this;
+        }
+        return remaining;
+    }
+
+private:
+    dl_shim_video_cb video_cb_;
+    dl_shim_audio_cb audio_cb_;
+    void* user_;
+    int32_t audio_channels_;
+    std::atomic<ULONG> refs_{1};
+};
+
+template <typename T>
+void release(T*& iface) {
+    if (iface != nullptr) {
+        iface->Release();
+        iface = nullptr;
+    }
+}
+
+}  // namespace
+
+struct DlShimDevice {
+    IDeckLink* decklink = nullptr;
+    IDeckLinkInput* input = nullptr;
+    IDeckLinkOutput* output = nullptr;
+    IDeckLinkStatus* status = nullptr;
+    InputCallback* callback = nullptr;
+    IDeckLinkMutableVideoFrame* frame = nullptr;
+    std::mutex output_lock;
+};
+
+extern "C" {
+
+int32_t dl_shim_device_count(void) {
+    IDeckLinkIterator* iterator = CreateDeckLinkIteratorInstance();
+    if (iterator == nullptr) {
+        return 0;
+    }
+    int32_t count = 0;
+    IDeckLink* decklink = nullptr;
+    while (iterator->Next(&decklink) == S_OK) {
+        ++count;
+        decklink->Release();
+    }
+    iterator->Release();
+    return count;
+}
+
+DlShimDevice* dl_shim_device_open(int32_t index) {
+    IDeckLinkIterator* iterator = CreateDeckLinkIteratorInstance();
+    if (iterator == nullptr) {
+        return nullptr;
+    }
+    IDeckLink* decklink = nullptr;
+    for (int32_t i = 0; iterator->Next(&decklink) == S_OK; ++i) {
+        if (i == index) {
+            break;
+        }
+        release(decklink);
+    }
+    iterator->Release();
+    if (decklink == nullptr) {
+        return nullptr;
+    }
+    auto* device = new DlShimDevice();
+    device->decklink = decklink;
+    decklink->QueryInterface(IID_IDeckLinkStatus, reinterpret_cast<void**>(&device->status));
+    return device;
+}
+
+void dl_shim_device_close(DlShimDevice* device) {
+    if (device == nullptr) {
+        return;
+    }
+    dl_shim_input_stop(device);
+    dl_shim_output_stop(device);
+    release(device->status);
+    release(device->decklink);
+    delete device;
+}
+
+int32_t dl_shim_device_name(DlShimDevice* device, char* buf, size_t len) {
+    if (len == 0) {
+        return kFailed;
+    }
+    const char* name = nullptr;
+    if (device->decklink->GetDisplayName(&name) != S_OK || name == nullptr) {
+        return kFailed;
+    }
+    std::strncpy(buf, name, len - 1);
+    buf[len - 1] = '\0';
+    std::free(const_cast<char*>(name));
+    return kOk;
+}
+
+int32_t dl_shim_input_start(DlShimDevice* device, uint32_t display_mode, uint32_t pixel_format,
+                            int32_t audio_channels, dl_shim_video_cb video_cb,
+                            dl_shim_audio_cb audio_cb, void* user) {
+    if (device->input != nullptr) {
+        return kFailed;
+    }
+    if (device->decklink->QueryInterface(IID_IDeckLinkInput,
+                                         reinterpret_cast<void**>(&device->input)) != S_OK) {
+        return kUnsupported;
+    }
+    if (device->input->EnableVideoInput(display_mode, pixel_format, bmdVideoInputFlagDefault) !=
+        S_OK) {
+        release(device->input);
+        return kUnsupported;
+    }
+    if (audio_channels > 0 &&
+        device->input->EnableAudioInput(bmdAudioSampleRate48kHz, bmdAudioSampleType32bitInteger,
+                                        static_cast<uint32_t>(audio_channels)) != S_OK) {
+        device->input->DisableVideoInput();
+        release(device->input);
+        return kUnsupported;
+    }
+    device->callback = new InputCallback(video_cb, audio_cb, user, audio_channels);
+    device->input->SetCallback(device->callback);
+    if (device->input->StartStreams() != S_OK) {
+        dl_shim_input_stop(device);
+        return kFailed;
+    }
+    return kOk;
+}
+
+void dl_shim_input_stop(DlShimDevice* device) {
+    if (device->input == nullptr) {
+        return;
+    }
+    // StopStreams returns once the callback thread is idle.
+    device->input->StopStreams();
+    device->input->SetCallback(nullptr);
+    device->input->DisableAudioInput();
+    device->input->DisableVideoInput();
+    release(device->input);
+    if (device->callback != nullptr) {
+        device->callback->Release();
+        device->callback = nullptr;
+    }
+}
+
+int32_t dl_shim_output_start(DlShimDevice* device, uint32_t display_mode, uint32_t pixel_format,
+                             int32_t width, int32_t height, int32_t audio_channels) {
+    std::lock_guard<std::mutex> guard(device->output_lock);
+    if (device->output != nullptr) {
+        return kFailed;
+    }
+    if (device->decklink->QueryInterface(IID_IDeckLinkOutput,
+                                         reinterpret_cast<void**>(&device->output)) != S_OK) {
+        return kUnsupported;
+    }
+    if (device->output->EnableVideoOutput(display_mode, bmdVideoOutputFlagDefault) != S_OK) {
+        release(device->output);
+        return kUnsupported;
+    }
+    if (audio_channels > 0 &&
+        device->output->EnableAudioOutput(bmdAudioSampleRate48kHz, bmdAudioSampleType32bitInteger,
+                                          static_cast<uint32_t>(audio_channels),
+                                          bmdAudioOutputStreamContinuous) != S_OK) {
+        device->output->DisableVideoOutput();
+        release(device->output);
+        return kUnsupported;
+    }
+    int32_t row_bytes = 0;
+    if (device->output->RowBytesForPixelFormat(pixel_format, width, &row_bytes) != S_OK ||
+        device->output->CreateVideoFrame(width, height, row_bytes, pixel_format,
+                                         bmdFrameFlagDefault, &device->frame) != S_OK) {
+        device->output->DisableAudioOutput();
+        device->output->DisableVideoOutput();
+        release(device->output);
+        return kUnsupported;
+    }
+    return kOk;
+}
+
+int32_t dl_shim_output_frame(DlShimDevice* device, const uint8_t* bytes, int32_t row_bytes) {
+    std::lock_guard<std::mutex> guard(device->output_lock);
+    if (device->output == nullptr || device->frame == nullptr) {
+        return kFailed;
+    }
+    void* dst = nullptr;
+    if (device->frame->GetBytes(&dst) != S_OK) {
+        return kFailed;
+    }
+    const int32_t dst_row_bytes = static_cast<int32_t>(device->frame->GetRowBytes());
+    const int32_t copy = row_bytes < dst_row_bytes ? row_bytes : dst_row_bytes;
+    for (long row = 0; row < device->frame->GetHeight(); ++row) {
+        std::memcpy(static_cast<uint8_t*>(dst) + row * dst_row_bytes, bytes + row * row_bytes,
+                    static_cast<size_t>(copy));
+    }
+    return device->output->DisplayVideoFrameSync(device->frame) == S_OK ? kOk : kFailed;
+}
+
+int32_t dl_shim_output_audio(DlShimDevice* device, const int32_t* samples, int32_t sample_frames) {
+    std::lock_guard<std::mutex> guard(device->output_lock);
+    if (device->output == nullptr) {
+        return kFailed;
+    }
+    uint32_t written = 0;
+    if (device->output->WriteAudioSamplesSync(const_cast<int32_t*>(samples),
+                                              static_cast<uint32_t>(sample_frames),
+                                              &written) != S_OK) {
+        return kFailed;
+    }
+    return static_cast<int32_t>(written);
+}
+
+void dl_shim_output_stop(DlShimDevice* device) {
+    std::lock_guard<std::mutex> guard(device->output_lock);
+    if (device->output == nullptr) {
+        return;
+    }
+    release(device->frame);
+    device->output->DisableAudioOutput();
+    device->output->DisableVideoOutput();
+    release(device->output);
+}
+
+int32_t dl_shim_reference_status(DlShimDevice* device, int32_t* locked, uint32_t* mode) {
+    if (device->status == nullptr) {
+        return kUnsupported;
+    }
+    bool is_locked = false;
+    if (device->status->GetFlag(bmdDeckLinkStatusReferenceSignalLocked, &is_locked) != S_OK) {
+        return kUnsupported;
+    }
+    int64_t reference_mode = 0;
+    if (!is_locked ||
+        device->status->GetInt(bmdDeckLinkStatusReferenceSignalMode, &reference_mode) != S_OK) {
+        reference_mode = 0;
+    }
+    *locked = is_locked ? 1 : 0;
+    *mode = static_cast<uint32_t>(reference_mode);
+    return kOk;
+}
+
+}  // extern "C"
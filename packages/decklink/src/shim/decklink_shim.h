@@ -0,0 +1,63 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// C surface over the DeckLink COM-style C++ API, consumed by `src/ffi.rs`.
+// Every call returns 0 on success and a negative value on failure unless
+// noted otherwise. Callbacks run on DeckLink's own threads.
+
+#ifndef STREAMLIB_DECKLINK_SHIM_H
+#define STREAMLIB_DECKLINK_SHIM_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct DlShimDevice DlShimDevice;
+
+// One captured frame. `bytes` is valid only for the duration of the call.
+// `no_signal` is non-zero when the card reports no input source.
+typedef void (*dl_shim_video_cb)(void* user, const uint8_t* bytes, int32_t row_bytes,
+                                 int32_t width, int32_t height, int32_t no_signal,
+                                 int64_t stream_time_ns);
+
+// Interleaved signed 32-bit embedded audio accompanying a frame.
+typedef void (*dl_shim_audio_cb)(void* user, const int32_t* samples, int32_t sample_frames,
+                                 int32_t channels, int64_t packet_time_ns);
+
+// Number of DeckLink devices present (0 when the driver is missing).
+int32_t dl_shim_device_count(void);
+
+// Open device `index`; NULL when it does not exist.
+DlShimDevice* dl_shim_device_open(int32_t index);
+void dl_shim_device_close(DlShimDevice* device);
+
+// Copy the display name (NUL-terminated, truncated to `len`).
+int32_t dl_shim_device_name(DlShimDevice* device, char* buf, size_t len);
+
+// Start capture. `audio_channels` of 0 leaves audio disabled.
+int32_t dl_shim_input_start(DlShimDevice* device, uint32_t display_mode, uint32_t pixel_format,
+                            int32_t audio_channels, dl_shim_video_cb video_cb,
+                            dl_shim_audio_cb audio_cb, void* user);
+// Blocks until no callback is running; `user` may be freed afterwards.
+void dl_shim_input_stop(DlShimDevice* device);
+
+int32_t dl_shim_output_start(DlShimDevice* device, uint32_t display_mode, uint32_t pixel_format,
+                             int32_t width, int32_t height, int32_t audio_channels);
+// Display one frame synchronously; `bytes` holds `height` rows of `row_bytes`.
+int32_t dl_shim_output_frame(DlShimDevice* device, const uint8_t* bytes, int32_t row_bytes);
+// Queue interleaved 32-bit audio; returns sample frames accepted.
+int32_t dl_shim_output_audio(DlShimDevice* device, const int32_t* samples, int32_t sample_frames);
+void dl_shim_output_stop(DlShimDevice* device);
+
+// Reference input status: `locked` is 0/1, `mode` the detected
+// BMDDisplayMode (0 when unlocked).
+int32_t dl_shim_reference_status(DlShimDevice* device, int32_t* locked, uint32_t* mode);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
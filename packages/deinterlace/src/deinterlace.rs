@@ -0,0 +1,646 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deinterlace (Linux) — interlaced to progressive on the GPU.
+//!
+//! Every interlaced input is first copied into one of three pooled RGBA8
+//! history textures (`store_field_history.comp`), which rotate so the
+//! previous two inputs stay available. When neither config nor the frame
+//! names a field order, `comb_detect.comp` then compares the newest two
+//! into a host-mapped storage buffer that feeds the
+//! [`FieldOrderDetector`]. Each output field is one `deinterlace.comp`
+//! dispatch into the next slot of an RGBA8 output ring: bob works on the
+//! input just stored; yadif works on the input before it, with the inputs
+//! either side as temporal neighbours, so its output runs one frame
+//! behind. Weave mode and frames marked progressive pass through without
+//! GPU work. History textures stay in `GENERAL` layout and are reacquired,
+//! with detection restarting, whenever the input size changes.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, PooledTextureHandle, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TexturePoolDescriptor, TextureRing, TextureUsages, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::_generated_::tatolab__core::video_frame::FieldOrder;
+use crate::detect::{CombCounts, FieldOrderDetector, Scan, resolve_scan};
+use crate::params::{COMB_THRESHOLD, DeinterlaceMode, DeinterlaceParams, second_field_delay_ns};
+
+const STORE_FIELD_HISTORY_SPV: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/store_field_history.spv"));
+const COMB_DETECT_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/comb_detect.spv"));
+const DEINTERLACE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/deinterlace.spv"));
+
+const STORE_FIELD_HISTORY_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+const COMB_DETECT_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+const DEINTERLACE_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_image(2),
+    ComputeBindingSpec::storage_image(3),
+];
+
+/// Matches the 16x16 workgroup of all three shaders.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Output ring depth — field rate yields two frames per input, and earlier
+/// ones may still be sampled downstream while the next is written.
+const OUTPUT_RING_DEPTH: usize = 4;
+
+/// Longer gaps between inputs are stalls, not the frame interval.
+const MAX_INTERVAL_NS: u64 = 500_000_000;
+
+/// `deinterlace.comp` modes.
+const SHADER_MODE_BOB: u32 = 0;
+const SHADER_MODE_YADIF: u32 = 1;
+const SHADER_MODE_COPY: u32 = 2;
+
+/// Push constants of `store_field_history.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StorePushConstants {
+    width: u32,
+    height: u32,
+}
+
+/// Push constants of `comb_detect.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CombDetectPushConstants {
+    width: u32,
+    height: u32,
+    threshold: f32,
+}
+
+/// Push constants of `deinterlace.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DeinterlacePushConstants {
+    width: u32,
+    height: u32,
+    mode: u32,
+    keep_parity: u32,
+    kept_field_first: u32,
+}
+
+/// The last three interlaced inputs, as the de-interlacer's own copies.
+struct History {
+    slots: [PooledTextureHandle; 3],
+    /// Slot holding the newest input.
+    newest: usize,
+    /// How many slots hold inputs of the current run, up to three.
+    stored: usize,
+    /// False until the first submit: the pooled textures' contents and
+    /// layout are undefined until then.
+    initialized: bool,
+}
+
+impl History {
+    /// Index of the slot holding the input `age` inputs before the newest.
+    fn index(&self, age: usize) -> usize {
+        (self.newest + 3 - age) % 3
+    }
+
+    fn slot(&self, age: usize) -> &PooledTextureHandle {
+        &self.slots[self.index(age)]
+    }
+}
+
+/// Which history slots one output reads.
+#[derive(Clone, Copy)]
+struct Neighbours {
+    previous: usize,
+    current: usize,
+    next: usize,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/deinterlace/Deinterlace",
+    description = "Converts interlaced frames to progressive on the GPU with bob, weave or yadif, at frame or field rate. The field order comes from each frame's field_order, a configured override, or detection from combing; frames marked progressive pass through untouched.",
+    execution = reactive,
+    config = crate::_generated_::DeinterlaceConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Interlaced (woven) frames"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Progressive frames (RGBA8, the input's color description)"),
+)]
+pub struct DeinterlaceProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    store_kernel: Option<VulkanComputeKernel>,
+    comb_kernel: Option<VulkanComputeKernel>,
+    deinterlace_kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    comb_counts: Option<StorageBuffer>,
+    params: Option<DeinterlaceParams>,
+    detector: FieldOrderDetector,
+    history: Option<History>,
+    /// Yadif's input awaiting the next one.
+    held: Option<VideoFrame>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    last_timestamp_ns: Option<u64>,
+    last_interval_ns: Option<u64>,
+    frames_in: u64,
+    frames_out: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for DeinterlaceProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = DeinterlaceParams::from_config(&self.config);
+        let full = ctx.gpu_full_access();
+        self.store_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "deinterlace-store-field-history",
+            spv: STORE_FIELD_HISTORY_SPV,
+            bindings: STORE_FIELD_HISTORY_BINDINGS,
+            push_constant_size: std::mem::size_of::<StorePushConstants>() as u32,
+        })?);
+        self.comb_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "deinterlace-comb-detect",
+            spv: COMB_DETECT_SPV,
+            bindings: COMB_DETECT_BINDINGS,
+            push_constant_size: std::mem::size_of::<CombDetectPushConstants>() as u32,
+        })?);
+        self.deinterlace_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "deinterlace",
+            spv: DEINTERLACE_SPV,
+            bindings: DEINTERLACE_BINDINGS,
+            push_constant_size: std::mem::size_of::<DeinterlacePushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("deinterlace")?);
+        let comb_counts = full.acquire_storage_buffer(std::mem::size_of::<CombCounts>() as u64)?;
+        if comb_counts.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Deinterlace: comb count buffer is not host-mapped".into(),
+            ));
+        }
+        self.comb_counts = Some(comb_counts);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.params = Some(params);
+        tracing::info!("[Deinterlace] Setup ({:?})", params);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.store_kernel = None;
+        self.comb_kernel = None;
+        self.deinterlace_kernel = None;
+        self.comb_counts = None;
+        self.history = None;
+        self.held = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[Deinterlace] Teardown ({} frames in, {} out)",
+            self.frames_in,
+            self.frames_out
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = DeinterlaceParams::from_config(&self.config);
+        // A different mode starts over; a held yadif input is dropped
+        // rather than rendered under the new settings.
+        if self
+            .params
+            .is_some_and(|previous| previous.mode != params.mode)
+        {
+            self.history = None;
+            self.held = None;
+        }
+        self.params = Some(params);
+        tracing::info!("[Deinterlace] Config updated ({:?})", params);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let Some(params) = self.params else {
+            return Err(Error::Configuration("Deinterlace: not initialized".into()));
+        };
+        let timestamp_ns: u64 = frame.timestamp_ns.parse().map_err(|_| {
+            Error::Runtime(format!(
+                "Deinterlace: timestamp_ns must be a non-negative integer, got {:?}",
+                frame.timestamp_ns
+            ))
+        })?;
+        self.frames_in += 1;
+        self.observe_timestamp(timestamp_ns);
+
+        let marked = frame.field_order.as_ref().map(Scan::from_marked);
+        let outputs = if params.mode == DeinterlaceMode::Weave {
+            vec![VideoFrame {
+                field_order: Some(FieldOrder::Progressive),
+                ..frame
+            }]
+        } else if params.field_order.is_none() && marked == Some(Scan::Progressive) {
+            // Nothing to de-interlace, and nothing to hold it back for. The
+            // held yadif input goes out first, without a next frame.
+            let mut outputs = self.flush_held(&params)?;
+            if let Some(history) = self.history.as_mut() {
+                history.stored = 0;
+            }
+            outputs.push(frame);
+            outputs
+        } else {
+            self.deinterlace(&frame, marked, &params)?
+        };
+        for output in &outputs {
+            self.outputs.write("video_out", output)?;
+        }
+        self.frames_out += outputs.len() as u64;
+        Ok(())
+    }
+}
+
+impl DeinterlaceProcessor::Processor {
+    /// Track the interval between inputs, which spaces field-rate output.
+    fn observe_timestamp(&mut self, timestamp_ns: u64) {
+        let interval_ns = self
+            .last_timestamp_ns
+            .replace(timestamp_ns)
+            .and_then(|previous| timestamp_ns.checked_sub(previous))
+            .filter(|interval| (1..=MAX_INTERVAL_NS).contains(interval));
+        if interval_ns.is_some() {
+            self.last_interval_ns = interval_ns;
+        }
+    }
+
+    /// Store `frame` in the history, update detection, and render the
+    /// outputs it completes.
+    fn deinterlace(
+        &mut self,
+        frame: &VideoFrame,
+        marked: Option<Scan>,
+        params: &DeinterlaceParams,
+    ) -> Result<Vec<VideoFrame>> {
+        let (Some(gpu), Some(store_kernel), Some(comb_kernel), Some(recorder), Some(counts)) = (
+            self.gpu_context.as_ref(),
+            self.store_kernel.as_ref(),
+            self.comb_kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.comb_counts.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "Deinterlace: kernels not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let history = match self.history.take() {
+            Some(history)
+                if (history.slots[0].width(), history.slots[0].height()) == (width, height) =>
+            {
+                history
+            }
+            previous => {
+                if previous.is_some() {
+                    tracing::debug!(
+                        "[Deinterlace] Input size changed to {}x{}; restarting",
+                        width,
+                        height
+                    );
+                }
+                self.held = None;
+                self.detector = FieldOrderDetector::default();
+                let acquire = || {
+                    gpu.acquire_texture(&TexturePoolDescriptor {
+                        width,
+                        height,
+                        format: TextureFormat::Rgba8Unorm,
+                        usage: TextureUsages::STORAGE_BINDING,
+                        label: Some("deinterlace-history"),
+                    })
+                };
+                History {
+                    slots: [acquire()?, acquire()?, acquire()?],
+                    newest: 0,
+                    stored: 0,
+                    initialized: false,
+                }
+            }
+        };
+        let history = self.history.insert(history);
+        history.newest = (history.newest + 1) % 3;
+        history.stored = (history.stored + 1).min(3);
+        let detect = params.field_order.is_none() && marked.is_none() && history.stored >= 2;
+
+        // ---- Input in, into the history, combing counted ----------------------
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        // Fresh pooled textures are transitioned out of UNDEFINED; after
+        // that they stay in GENERAL, and the barrier only orders the last
+        // reads of the slot being overwritten before the store.
+        if history.initialized {
+            recorder.record_image_barrier(
+                history.slot(0).texture(),
+                VulkanLayout::GENERAL,
+                VulkanLayout::GENERAL,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::SHADER_READ,
+                VulkanAccess::SHADER_WRITE,
+            )?;
+        } else {
+            for slot in &history.slots {
+                recorder.record_image_barrier(
+                    slot.texture(),
+                    VulkanLayout::UNDEFINED,
+                    VulkanLayout::GENERAL,
+                    VulkanStage::COMPUTE_SHADER,
+                    VulkanStage::COMPUTE_SHADER,
+                    VulkanAccess::NONE,
+                    VulkanAccess::SHADER_READ | VulkanAccess::SHADER_WRITE,
+                )?;
+            }
+        }
+        store_kernel.set_sampled_texture(0, &texture)?;
+        store_kernel.set_storage_image(1, history.slot(0).texture())?;
+        store_kernel.set_push_constants_value(&StorePushConstants { width, height })?;
+        recorder.record_dispatch(
+            store_kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.record_image_barrier(
+            history.slot(0).texture(),
+            VulkanLayout::GENERAL,
+            VulkanLayout::GENERAL,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::SHADER_READ,
+        )?;
+        if detect {
+            // SAFETY: `counts` is a persistently-mapped host-visible
+            // allocation of one `CombCounts` (checked non-null in setup),
+            // and the previous submit has completed, so the GPU is not
+            // using it. Host writes before the submit are visible to the
+            // kernel.
+            unsafe {
+                std::ptr::write(
+                    counts.mapped_ptr() as *mut CombCounts,
+                    CombCounts::default(),
+                );
+            }
+            comb_kernel.set_storage_image(0, history.slot(0).texture())?;
+            comb_kernel.set_storage_image(1, history.slot(1).texture())?;
+            comb_kernel.set_storage_buffer_storage(2, counts)?;
+            comb_kernel.set_push_constants_value(&CombDetectPushConstants {
+                width,
+                height,
+                threshold: COMB_THRESHOLD,
+            })?;
+            recorder.record_dispatch(
+                comb_kernel,
+                width.div_ceil(WORKGROUP_SIZE),
+                (height / 2).div_ceil(WORKGROUP_SIZE),
+                1,
+            )?;
+            recorder.record_buffer_barrier(
+                counts,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanStage::HOST,
+                VulkanAccess::SHADER_WRITE,
+                VulkanAccess::HOST_READ,
+            )?;
+        }
+        recorder.submit_and_wait()?;
+        registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+        history.initialized = true;
+
+        if detect {
+            // SAFETY: as above; the submit has completed, so the kernel's
+            // writes are visible.
+            let observed = unsafe { std::ptr::read(counts.mapped_ptr() as *const CombCounts) };
+            self.detector
+                .observe(&observed, width as u64 * (height / 2) as u64);
+        }
+
+        // ---- The outputs this input completes ---------------------------------
+        if params.mode == DeinterlaceMode::Yadif {
+            // The first input after a reset has no earlier one; yadif then
+            // treats the input it renders as its own predecessor.
+            let neighbours = Neighbours {
+                previous: history.index(if history.stored >= 3 { 2 } else { 1 }),
+                current: history.index(1),
+                next: history.index(0),
+            };
+            let Some(source) = self.held.replace(frame.clone()) else {
+                return Ok(Vec::new());
+            };
+            self.render(&source, neighbours, params)
+        } else {
+            let newest = history.index(0);
+            let neighbours = Neighbours {
+                previous: newest,
+                current: newest,
+                next: newest,
+            };
+            self.render(frame, neighbours, params)
+        }
+    }
+
+    /// Render the held yadif input with no next frame, when the run of
+    /// interlaced inputs ends.
+    fn flush_held(&mut self, params: &DeinterlaceParams) -> Result<Vec<VideoFrame>> {
+        let (Some(source), Some(history)) = (self.held.take(), self.history.as_ref()) else {
+            return Ok(Vec::new());
+        };
+        let neighbours = Neighbours {
+            previous: history.index(if history.stored >= 2 { 1 } else { 0 }),
+            current: history.index(0),
+            next: history.index(0),
+        };
+        self.render(&source, neighbours, params)
+    }
+
+    /// One progressive frame per output field of `source`, whose copy is
+    /// history slot `neighbours.current`.
+    fn render(
+        &mut self,
+        source: &VideoFrame,
+        neighbours: Neighbours,
+        params: &DeinterlaceParams,
+    ) -> Result<Vec<VideoFrame>> {
+        let (Some(gpu), Some(kernel), Some(recorder), Some(history)) = (
+            self.gpu_context.as_ref(),
+            self.deinterlace_kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.history.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "Deinterlace: kernels not initialized".into(),
+            ));
+        };
+        let timestamp_ns: u64 = source.timestamp_ns.parse().map_err(|_| {
+            Error::Runtime(format!(
+                "Deinterlace: timestamp_ns must be a non-negative integer, got {:?}",
+                source.timestamp_ns
+            ))
+        })?;
+        let current = &history.slots[neighbours.current];
+        let (width, height) = (current.width(), current.height());
+
+        let scan = resolve_scan(
+            params.field_order,
+            source.field_order.as_ref().map(Scan::from_marked),
+            self.detector.detected(),
+        );
+        // (keep_parity, kept_field_first, timestamp) per output.
+        let fields: Vec<(u32, bool, u64)> = match scan.first_field_parity() {
+            // Detected progressive: the stored copy is the frame as is.
+            None => vec![(0, true, timestamp_ns)],
+            Some(first) => {
+                let mut fields = vec![(first, true, timestamp_ns)];
+                if params.field_rate {
+                    match second_field_delay_ns(self.last_interval_ns, source.fps) {
+                        Some(delay_ns) => fields.push((1 - first, false, timestamp_ns + delay_ns)),
+                        None => tracing::debug!(
+                            "[Deinterlace] No frame interval; dropped the second field at {} ns",
+                            timestamp_ns
+                        ),
+                    }
+                }
+                fields
+            }
+        };
+        let (shader_mode, fps) = match (scan, params.mode) {
+            (Scan::Progressive, _) => (SHADER_MODE_COPY, source.fps),
+            (_, DeinterlaceMode::Bob) => (
+                SHADER_MODE_BOB,
+                source.fps.map(|fps| fps * params.fields_out()),
+            ),
+            _ => (
+                SHADER_MODE_YADIF,
+                source.fps.map(|fps| fps * params.fields_out()),
+            ),
+        };
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let mut outputs = Vec::with_capacity(fields.len());
+        for (keep_parity, kept_field_first, field_timestamp_ns) in fields {
+            let slot = ring.acquire_next();
+            let slot_surface_id = slot.surface_id().to_string();
+            let slot_registration = gpu.resolve_texture_registration_by_surface_id(
+                &slot_surface_id,
+                None,
+                width,
+                height,
+            )?;
+
+            kernel.set_storage_image(0, history.slots[neighbours.previous].texture())?;
+            kernel.set_storage_image(1, current.texture())?;
+            kernel.set_storage_image(2, history.slots[neighbours.next].texture())?;
+            kernel.set_storage_image(3, &slot.texture)?;
+            kernel.set_push_constants_value(&DeinterlacePushConstants {
+                width,
+                height,
+                mode: shader_mode,
+                keep_parity,
+                kept_field_first: kept_field_first as u32,
+            })?;
+
+            recorder.begin()?;
+            recorder.record_image_barrier(
+                &slot.texture,
+                slot_registration.current_layout(),
+                VulkanLayout::GENERAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_READ,
+                VulkanAccess::SHADER_WRITE,
+            )?;
+            recorder.record_dispatch(
+                kernel,
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            )?;
+            recorder.finish_compute_output(&slot_registration, &[])?;
+
+            outputs.push(VideoFrame {
+                surface_id: slot_surface_id,
+                width,
+                height,
+                timestamp_ns: field_timestamp_ns.to_string(),
+                fps,
+                texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+                color_info: source.color_info.clone(),
+                mastering_display: source.mastering_display.clone(),
+                content_light: source.content_light.clone(),
+                field_order: Some(FieldOrder::Progressive),
+            });
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_blocks() {
+        assert_eq!(std::mem::size_of::<StorePushConstants>(), 8);
+        assert_eq!(std::mem::size_of::<CombDetectPushConstants>(), 12);
+        assert_eq!(std::mem::offset_of!(CombDetectPushConstants, threshold), 8);
+        assert_eq!(std::mem::size_of::<DeinterlacePushConstants>(), 20);
+        assert_eq!(
+            std::mem::offset_of!(DeinterlacePushConstants, kept_field_first),
+            16
+        );
+    }
+}
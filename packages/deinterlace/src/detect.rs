@@ -0,0 +1,253 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Field-order detection for sources that don't mark their frames.
+//!
+//! `comb_detect.comp` counts "combed" pixels — a line that differs from
+//! both its neighbours in the same direction — in three weaves of the
+//! newest frame's fields with the previous frame's. Whichever pairs fields
+//! closest in time combs least: a progressive frame's own two fields (one
+//! instant), or, for interlaced video, the newest top field with the
+//! previous bottom field when the top field comes first, and the previous
+//! top field with the newest bottom field when the bottom field does.
+//! Single frames are noisy — still pictures comb in no weave at all — so
+//! [`FieldOrderDetector`] votes over recent frames and only reports an
+//! order once one clearly leads.
+
+use crate::_generated_::tatolab__core::video_frame::FieldOrder;
+
+/// Fraction of the tested pixels that must comb in some weave before a
+/// frame counts as a vote; below it there is too little motion to tell.
+pub const MIN_COMBED_SHARE: f64 = 0.0005;
+/// A frame is progressive when its own weave combs this many times less
+/// than either cross-frame weave.
+const PROGRESSIVE_RATIO: f64 = 2.5;
+/// A frame votes for an order when one cross-frame weave combs this many
+/// times less than the other.
+const ORDER_RATIO: f64 = 1.5;
+/// Weight kept by past votes per frame; about the last 20 frames count.
+const VOTE_DECAY: f32 = 0.95;
+/// Decayed votes the leading scan needs before it is reported.
+const MIN_VOTES: f32 = 4.0;
+/// The leading scan must have this many times the runner-up's votes.
+const LEAD_RATIO: f32 = 2.0;
+
+/// How a frame's two fields relate in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scan {
+    /// Both fields are one instant.
+    Progressive,
+    /// The top (even) lines are the earlier field.
+    TopFieldFirst,
+    /// The bottom (odd) lines are the earlier field.
+    BottomFieldFirst,
+}
+
+impl Scan {
+    const ALL: [Scan; 3] = [
+        Scan::Progressive,
+        Scan::TopFieldFirst,
+        Scan::BottomFieldFirst,
+    ];
+
+    pub fn from_marked(order: &FieldOrder) -> Self {
+        match order {
+            FieldOrder::Progressive => Self::Progressive,
+            FieldOrder::TopFieldFirst => Self::TopFieldFirst,
+            FieldOrder::BottomFieldFirst => Self::BottomFieldFirst,
+        }
+    }
+
+    /// Line parity (0 = even/top lines) of the field that comes first in
+    /// time, or `None` for progressive frames.
+    pub fn first_field_parity(self) -> Option<u32> {
+        match self {
+            Self::Progressive => None,
+            Self::TopFieldFirst => Some(0),
+            Self::BottomFieldFirst => Some(1),
+        }
+    }
+}
+
+/// The scan a frame is treated as: a configured order wins over the frame's
+/// own marking, which wins over detection. With none of them — detection
+/// still undecided — the frame is taken as top field first, the order of
+/// every interlaced HD format.
+pub fn resolve_scan(
+    configured: Option<Scan>,
+    marked: Option<Scan>,
+    detected: Option<Scan>,
+) -> Scan {
+    configured
+        .or(marked)
+        .or(detected)
+        .unwrap_or(Scan::TopFieldFirst)
+}
+
+/// Combed-pixel counts written by `comb_detect.comp`; matches its
+/// `uint counts[3]` storage buffer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CombCounts {
+    /// The newest frame as is.
+    pub woven: u32,
+    /// The newest frame's top field with the previous frame's bottom field.
+    pub top_with_previous_bottom: u32,
+    /// The previous frame's top field with the newest frame's bottom field.
+    pub previous_top_with_bottom: u32,
+}
+
+impl CombCounts {
+    /// What this frame pair alone suggests, out of `tested` pixels per
+    /// weave; `None` when it is too still or too ambiguous to say.
+    pub fn classify(&self, tested: u64) -> Option<Scan> {
+        let woven = self.woven as f64;
+        let top_first = self.top_with_previous_bottom as f64;
+        let bottom_first = self.previous_top_with_bottom as f64;
+        if woven.max(top_first).max(bottom_first) < tested as f64 * MIN_COMBED_SHARE {
+            return None;
+        }
+        if woven * PROGRESSIVE_RATIO < top_first.min(bottom_first) {
+            Some(Scan::Progressive)
+        } else if top_first * ORDER_RATIO < bottom_first {
+            Some(Scan::TopFieldFirst)
+        } else if bottom_first * ORDER_RATIO < top_first {
+            Some(Scan::BottomFieldFirst)
+        } else {
+            None
+        }
+    }
+}
+
+/// Accumulates per-frame [`CombCounts`] verdicts into a field order.
+#[derive(Debug, Clone, Default)]
+pub struct FieldOrderDetector {
+    /// Decayed votes, indexed like [`Scan::ALL`].
+    votes: [f32; 3],
+    detected: Option<Scan>,
+}
+
+impl FieldOrderDetector {
+    /// Add one frame's counts; returns the order detected so far.
+    pub fn observe(&mut self, counts: &CombCounts, tested: u64) -> Option<Scan> {
+        for vote in &mut self.votes {
+            *vote *= VOTE_DECAY;
+        }
+        if let Some(scan) = counts.classify(tested) {
+            self.votes[scan as usize] += 1.0;
+        }
+
+        let mut ranked = Scan::ALL;
+        ranked.sort_by(|a, b| self.votes[*b as usize].total_cmp(&self.votes[*a as usize]));
+        let (leader, runner_up) = (ranked[0], ranked[1]);
+        let leading_votes = self.votes[leader as usize];
+        if leading_votes >= MIN_VOTES
+            && leading_votes >= self.votes[runner_up as usize] * LEAD_RATIO
+            && self.detected != Some(leader)
+        {
+            tracing::info!("[Deinterlace] Detected {:?} source", leader);
+            self.detected = Some(leader);
+        }
+        self.detected
+    }
+
+    pub fn detected(&self) -> Option<Scan> {
+        self.detected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TESTED: u64 = 1920 * 540;
+
+    fn counts(
+        woven: u32,
+        top_with_previous_bottom: u32,
+        previous_top_with_bottom: u32,
+    ) -> CombCounts {
+        CombCounts {
+            woven,
+            top_with_previous_bottom,
+            previous_top_with_bottom,
+        }
+    }
+
+    #[test]
+    fn comb_counts_match_the_shader_buffer() {
+        assert_eq!(std::mem::size_of::<CombCounts>(), 12);
+        assert_eq!(
+            std::mem::offset_of!(CombCounts, previous_top_with_bottom),
+            8
+        );
+    }
+
+    #[test]
+    fn single_frames_classify_by_the_least_combed_weave() {
+        // Interlaced motion: the woven frame combs about as much as the
+        // adjacent-field weave; the far-apart weave combs most.
+        assert_eq!(
+            counts(9_000, 8_000, 30_000).classify(TESTED),
+            Some(Scan::TopFieldFirst)
+        );
+        assert_eq!(
+            counts(9_000, 30_000, 8_000).classify(TESTED),
+            Some(Scan::BottomFieldFirst)
+        );
+        // Progressive motion: only the cross-frame weaves comb.
+        assert_eq!(
+            counts(500, 20_000, 21_000).classify(TESTED),
+            Some(Scan::Progressive)
+        );
+        // Still, or too close to call.
+        assert_eq!(counts(10, 20, 30).classify(TESTED), None);
+        assert_eq!(counts(9_000, 10_000, 11_000).classify(TESTED), None);
+    }
+
+    #[test]
+    fn detector_needs_a_clear_lead_and_follows_a_changed_source() {
+        let mut detector = FieldOrderDetector::default();
+        let bottom_first = counts(9_000, 30_000, 8_000);
+        for _ in 0..4 {
+            assert_eq!(detector.observe(&bottom_first, TESTED), None);
+        }
+        assert_eq!(
+            detector.observe(&bottom_first, TESTED),
+            Some(Scan::BottomFieldFirst)
+        );
+
+        // Still frames don't vote and don't undo a decision.
+        for _ in 0..10 {
+            detector.observe(&counts(0, 0, 0), TESTED);
+        }
+        assert_eq!(detector.detected(), Some(Scan::BottomFieldFirst));
+
+        // A source switched to progressive takes over once it leads.
+        let progressive = counts(500, 20_000, 21_000);
+        let switched_after = (1..100)
+            .find(|_| detector.observe(&progressive, TESTED) == Some(Scan::Progressive))
+            .unwrap();
+        assert!(switched_after < 20, "{switched_after}");
+    }
+
+    #[test]
+    fn configured_order_beats_marking_beats_detection() {
+        let top = Some(Scan::TopFieldFirst);
+        let bottom = Some(Scan::BottomFieldFirst);
+        let progressive = Some(Scan::Progressive);
+        assert_eq!(
+            resolve_scan(bottom, progressive, top),
+            Scan::BottomFieldFirst
+        );
+        assert_eq!(resolve_scan(None, progressive, bottom), Scan::Progressive);
+        assert_eq!(resolve_scan(None, None, bottom), Scan::BottomFieldFirst);
+        assert_eq!(resolve_scan(None, None, None), Scan::TopFieldFirst);
+        assert_eq!(
+            Scan::from_marked(&FieldOrder::BottomFieldFirst),
+            Scan::BottomFieldFirst
+        );
+        assert_eq!(Scan::BottomFieldFirst.first_field_parity(), Some(1));
+        assert_eq!(Scan::Progressive.first_field_parity(), None);
+    }
+}
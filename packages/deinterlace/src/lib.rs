@@ -0,0 +1,26 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/deinterlace` — interlaced to progressive conversion.
+//! `Deinterlace` rebuilds woven frames from interlaced SDI and capture
+//! sources with bob, weave or GPU yadif, at frame or field rate, taking
+//! the field order from `VideoFrame::field_order`, config, or detection.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod detect;
+pub mod params;
+
+// Bob, weave and yadif record through `sdk::rhi`, which is Linux-only;
+// field-order `detect`ion and `params` stay portable.
+#[cfg(target_os = "linux")]
+pub mod deinterlace;
+
+#[cfg(target_os = "linux")]
+pub use deinterlace::DeinterlaceProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::DeinterlaceProcessor::Processor);
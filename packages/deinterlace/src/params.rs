@@ -0,0 +1,123 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! De-interlacing parameters — `DeinterlaceConfig` resolved, and the
+//! timing of field-rate output.
+
+use crate::_generated_::DeinterlaceConfig;
+use crate::_generated_::tatolab__deinterlace::deinterlace_config::{FieldOrder, Mode, OutputRate};
+use crate::detect::Scan;
+
+/// Luma difference a line must have from both its neighbours, in the same
+/// direction, to count as combed in `comb_detect.comp`.
+pub const COMB_THRESHOLD: f32 = 0.04;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeinterlaceMode {
+    /// Interpolate each field's missing lines spatially.
+    Bob,
+    /// Forward the woven frame, marked progressive.
+    Weave,
+    /// Temporal where still, edge-directed spatial where moving; one frame
+    /// of latency.
+    #[default]
+    Yadif,
+}
+
+/// `DeinterlaceConfig`, resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeinterlaceParams {
+    pub mode: DeinterlaceMode,
+    /// Field order forced by config; `None` follows each frame's marking
+    /// and detection.
+    pub field_order: Option<Scan>,
+    /// One output per field instead of per frame.
+    pub field_rate: bool,
+}
+
+impl DeinterlaceParams {
+    pub fn from_config(config: &DeinterlaceConfig) -> Self {
+        let mode = match config.mode {
+            Some(Mode::Bob) => DeinterlaceMode::Bob,
+            Some(Mode::Weave) => DeinterlaceMode::Weave,
+            Some(Mode::Yadif) | None => DeinterlaceMode::Yadif,
+        };
+        let field_order = match config.field_order {
+            Some(FieldOrder::Auto) | None => None,
+            Some(FieldOrder::TopFieldFirst) => Some(Scan::TopFieldFirst),
+            Some(FieldOrder::BottomFieldFirst) => Some(Scan::BottomFieldFirst),
+        };
+        // Weave has no fields to split.
+        let field_rate =
+            config.output_rate == Some(OutputRate::Field) && mode != DeinterlaceMode::Weave;
+        Self {
+            mode,
+            field_order,
+            field_rate,
+        }
+    }
+
+    /// Outputs per interlaced input frame.
+    pub fn fields_out(&self) -> u32 {
+        if self.field_rate { 2 } else { 1 }
+    }
+}
+
+/// How long after a frame's first field its second field was captured:
+/// half the frame interval, measured between timestamps when known
+/// (`interval_ns`), otherwise from the frame's nominal `fps`. `None` when
+/// neither is known.
+pub fn second_field_delay_ns(interval_ns: Option<u64>, fps: Option<u32>) -> Option<u64> {
+    interval_ns
+        .filter(|interval| *interval > 0)
+        .or_else(|| {
+            fps.filter(|fps| *fps > 0)
+                .map(|fps| 1_000_000_000 / fps as u64)
+        })
+        .map(|interval| interval / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_frame_rate_yadif_following_the_source() {
+        let params = DeinterlaceParams::from_config(&DeinterlaceConfig::default());
+        assert_eq!(params.mode, DeinterlaceMode::Yadif);
+        assert_eq!(params.field_order, None);
+        assert!(!params.field_rate);
+        assert_eq!(params.fields_out(), 1);
+    }
+
+    #[test]
+    fn overrides_resolve_and_weave_stays_at_frame_rate() {
+        let params = DeinterlaceParams::from_config(&DeinterlaceConfig {
+            mode: Some(Mode::Bob),
+            field_order: Some(FieldOrder::BottomFieldFirst),
+            output_rate: Some(OutputRate::Field),
+        });
+        assert_eq!(params.mode, DeinterlaceMode::Bob);
+        assert_eq!(params.field_order, Some(Scan::BottomFieldFirst));
+        assert_eq!(params.fields_out(), 2);
+
+        let weave = DeinterlaceParams::from_config(&DeinterlaceConfig {
+            mode: Some(Mode::Weave),
+            output_rate: Some(OutputRate::Field),
+            ..DeinterlaceConfig::default()
+        });
+        assert!(!weave.field_rate);
+    }
+
+    #[test]
+    fn second_field_is_half_an_interval_later() {
+        // 1080i59.94: measured interval wins over the rounded nominal rate.
+        assert_eq!(
+            second_field_delay_ns(Some(33_366_667), Some(30)),
+            Some(16_683_333)
+        );
+        assert_eq!(second_field_delay_ns(None, Some(25)), Some(20_000_000));
+        assert_eq!(second_field_delay_ns(Some(0), Some(25)), Some(20_000_000));
+        assert_eq!(second_field_delay_ns(None, None), None);
+    }
+}
@@ -31,7 +31,7 @@ static NEXT_WINDOW_ID: AtomicU64 = AtomicU64::new(1);
     execution = manual,
     scheduling = high,
     config = crate::_generated_::DisplayConfig,
-    input("video", "@tatolab/core/VideoFrame", description = "Video frames to display in the window"),
+    input("video", "@tatolab/core/VideoFrame", format = "rgba8", description = "Video frames to display in the window"),
 )]
 pub struct AppleDisplayProcessor {
     /// Window address stored as usize (NSWindow is !Send, but we leak it anyway)
@@ -0,0 +1,283 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Denoise (Linux) — bilateral filter plus temporal accumulation on the
+//! GPU.
+//!
+//! One `denoise.comp` dispatch per frame filters each texel spatially,
+//! blends it with the running history where the texel is not moving, and
+//! writes the blend to both the history and the next slot of an RGBA8
+//! output ring. The history is a pooled `Rgba16Float` texture that stays
+//! in `GENERAL` layout for the processor's lifetime; it is reacquired, and
+//! starts over, whenever the input size changes.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, PooledTextureHandle,
+    RhiCommandRecorder, TextureFormat, TexturePoolDescriptor, TextureRing, TextureUsages,
+    VulkanAccess, VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::params::DenoiseParams;
+
+const DENOISE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/denoise.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_image(2),
+];
+
+/// Matches `denoise.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Push constants of `denoise.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DenoisePushConstants {
+    width: u32,
+    height: u32,
+    radius: u32,
+    history_valid: u32,
+    sigma_space: f32,
+    sigma_range: f32,
+    history_weight: f32,
+    motion_threshold: f32,
+}
+
+/// The accumulated history and whether it has been written yet.
+struct History {
+    texture: PooledTextureHandle,
+    /// False until the first dispatch: the pooled texture's contents and
+    /// layout are undefined until then.
+    valid: bool,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/enhance/Denoise",
+    description = "Removes sensor noise on the GPU: an edge-preserving bilateral filter, then accumulation with earlier frames. Moving content is detected per pixel and falls back to the spatial result, so motion does not smear.",
+    execution = reactive,
+    config = crate::_generated_::DenoiseConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Noisy frames"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Denoised frames (RGBA8, the input's color description)"),
+)]
+pub struct DenoiseProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    params: Option<DenoiseParams>,
+    history: Option<History>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_denoised: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for DenoiseProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = DenoiseParams::from_config(&self.config)?;
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "denoise",
+            spv: DENOISE_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<DenoisePushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("denoise")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.params = Some(params);
+        tracing::info!("[Denoise] Setup ({:?})", params);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.history = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[Denoise] Teardown ({} frames denoised)",
+            self.frames_denoised
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = DenoiseParams::from_config(&self.config)?;
+        self.params = Some(params);
+        tracing::info!("[Denoise] Config updated ({:?})", params);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let denoised = self.denoise(&frame)?;
+        self.frames_denoised += 1;
+        self.outputs.write("video_out", &denoised)
+    }
+}
+
+impl DenoiseProcessor::Processor {
+    /// Denoise `frame` into the next output slot, advancing the history.
+    fn denoise(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let (Some(gpu), Some(kernel), Some(recorder), Some(params)) = (
+            self.gpu_context.as_ref(),
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.params,
+        ) else {
+            return Err(Error::Configuration(
+                "Denoise: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        let history = match self.history.take() {
+            Some(history)
+                if (history.texture.width(), history.texture.height()) == (width, height) =>
+            {
+                history
+            }
+            _ => {
+                tracing::debug!("[Denoise] History reset at {}x{}", width, height);
+                History {
+                    texture: gpu.acquire_texture(&TexturePoolDescriptor {
+                        width,
+                        height,
+                        format: TextureFormat::Rgba16Float,
+                        usage: TextureUsages::STORAGE_BINDING,
+                        label: Some("denoise-history"),
+                    })?,
+                    valid: false,
+                }
+            }
+        };
+        let history = self.history.insert(history);
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_storage_image(2, history.texture.texture())?;
+        kernel.set_push_constants_value(&DenoisePushConstants {
+            width,
+            height,
+            radius: params.radius,
+            history_valid: history.valid as u32,
+            sigma_space: params.sigma_space,
+            sigma_range: params.sigma_range,
+            history_weight: params.history_weight,
+            motion_threshold: params.motion_threshold,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        // A fresh history is transitioned out of UNDEFINED; after that it
+        // stays in GENERAL, and the barrier only makes the last frame's
+        // writes visible to this frame's reads.
+        recorder.record_image_barrier(
+            history.texture.texture(),
+            if history.valid {
+                VulkanLayout::GENERAL
+            } else {
+                VulkanLayout::UNDEFINED
+            },
+            VulkanLayout::GENERAL,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::SHADER_READ | VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+        history.valid = true;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<DenoisePushConstants>(), 32);
+        assert_eq!(
+            std::mem::offset_of!(DenoisePushConstants, history_valid),
+            12
+        );
+        assert_eq!(std::mem::offset_of!(DenoisePushConstants, sigma_space), 16);
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/enhance` — GPU cleanup for noisy, soft sources. `Denoise`
+//! pairs an edge-preserving bilateral filter with motion-rejecting
+//! temporal accumulation; `Sharpen` is an unsharp mask on luma.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod params;
+
+// Denoise and sharpen dispatch compute kernels through `sdk::rhi`, which is
+// Linux-only; `params` stays portable.
+#[cfg(target_os = "linux")]
+pub mod denoise;
+#[cfg(target_os = "linux")]
+pub mod sharpen;
+
+#[cfg(target_os = "linux")]
+pub use denoise::DenoiseProcessor;
+#[cfg(target_os = "linux")]
+pub use sharpen::SharpenProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::DenoiseProcessor::Processor,
+    crate::SharpenProcessor::Processor,
+);
@@ -0,0 +1,210 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Filter parameters — `DenoiseConfig` and `SharpenConfig` resolved into
+//! the values `denoise.comp` and `sharpen.comp` run with.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__enhance::denoise_config::Quality;
+use crate::_generated_::{DenoiseConfig, SharpenConfig};
+
+pub const DEFAULT_DENOISE_STRENGTH: f32 = 0.5;
+pub const DEFAULT_MOTION_THRESHOLD: f32 = 0.08;
+pub const DEFAULT_SHARPEN_STRENGTH: f32 = 0.6;
+pub const MAX_SHARPEN_STRENGTH: f32 = 4.0;
+pub const DEFAULT_SHARPEN_RADIUS: u32 = 2;
+pub const MAX_SHARPEN_RADIUS: u32 = 8;
+pub const DEFAULT_SHARPEN_THRESHOLD: f32 = 0.02;
+
+/// Range-kernel sigma at full strength, in code values.
+const MAX_SIGMA_RANGE: f32 = 0.15;
+/// History weight at full strength: a static pixel averages over roughly
+/// the last ten frames.
+const MAX_HISTORY_WEIGHT: f32 = 0.9;
+
+/// What `denoise.comp` runs with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DenoiseParams {
+    /// Bilateral footprint is `(2 * radius + 1)²`.
+    pub radius: u32,
+    /// Spatial Gaussian sigma, in pixels.
+    pub sigma_space: f32,
+    /// Range Gaussian sigma, in code values; 0 leaves pixels untouched.
+    pub sigma_range: f32,
+    /// Share of a static pixel taken from the history; 0 disables
+    /// accumulation.
+    pub history_weight: f32,
+    pub motion_threshold: f32,
+}
+
+impl DenoiseParams {
+    pub fn from_config(config: &DenoiseConfig) -> Result<Self> {
+        let strength = config.strength.unwrap_or(DEFAULT_DENOISE_STRENGTH);
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(Error::Configuration(format!(
+                "Denoise: strength must be within 0..=1, got {}",
+                strength
+            )));
+        }
+        let motion_threshold = config.motion_threshold.unwrap_or(DEFAULT_MOTION_THRESHOLD);
+        if !(motion_threshold > 0.0 && motion_threshold <= 1.0) {
+            return Err(Error::Configuration(format!(
+                "Denoise: motion_threshold must be within (0, 1], got {}",
+                motion_threshold
+            )));
+        }
+        let radius = match config.quality {
+            Some(Quality::Low) => 1,
+            Some(Quality::Medium) | None => 2,
+            Some(Quality::High) => 3,
+        };
+        // The square root front-loads accumulation: half strength already
+        // averages a few frames, where the range kernel stays gentle.
+        let history_weight = if config.temporal.unwrap_or(true) {
+            MAX_HISTORY_WEIGHT * strength.sqrt()
+        } else {
+            0.0
+        };
+        Ok(Self {
+            radius,
+            sigma_space: 0.5 * radius as f32 + 0.5,
+            sigma_range: MAX_SIGMA_RANGE * strength,
+            history_weight,
+            motion_threshold,
+        })
+    }
+}
+
+/// What `sharpen.comp` runs with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SharpenParams {
+    /// Blur footprint is `(2 * radius + 1)²`.
+    pub radius: u32,
+    /// Blur Gaussian sigma, in pixels; the footprint covers two sigmas.
+    pub sigma: f32,
+    pub strength: f32,
+    pub threshold: f32,
+}
+
+impl SharpenParams {
+    pub fn from_config(config: &SharpenConfig) -> Result<Self> {
+        let strength = config.strength.unwrap_or(DEFAULT_SHARPEN_STRENGTH);
+        if !(0.0..=MAX_SHARPEN_STRENGTH).contains(&strength) {
+            return Err(Error::Configuration(format!(
+                "Sharpen: strength must be within 0..={}, got {}",
+                MAX_SHARPEN_STRENGTH, strength
+            )));
+        }
+        let radius = config.radius.unwrap_or(DEFAULT_SHARPEN_RADIUS);
+        if !(1..=MAX_SHARPEN_RADIUS).contains(&radius) {
+            return Err(Error::Configuration(format!(
+                "Sharpen: radius must be within 1..={}, got {}",
+                MAX_SHARPEN_RADIUS, radius
+            )));
+        }
+        let threshold = config.threshold.unwrap_or(DEFAULT_SHARPEN_THRESHOLD);
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(Error::Configuration(format!(
+                "Sharpen: threshold must be within 0..=1, got {}",
+                threshold
+            )));
+        }
+        Ok(Self {
+            radius,
+            sigma: 0.5 * radius as f32,
+            strength,
+            threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denoise_defaults_are_a_medium_footprint_with_accumulation() {
+        let params = DenoiseParams::from_config(&DenoiseConfig::default()).unwrap();
+
+        assert_eq!(params.radius, 2);
+        assert_eq!(params.sigma_space, 1.5);
+        assert!((params.sigma_range - 0.075).abs() < 1e-6);
+        assert!((params.history_weight - 0.9 * 0.5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(params.motion_threshold, DEFAULT_MOTION_THRESHOLD);
+    }
+
+    #[test]
+    fn denoise_quality_sets_the_footprint_and_temporal_the_history() {
+        let config = DenoiseConfig {
+            quality: Some(Quality::High),
+            temporal: Some(false),
+            ..DenoiseConfig::default()
+        };
+        let params = DenoiseParams::from_config(&config).unwrap();
+
+        assert_eq!(params.radius, 3);
+        assert_eq!(params.history_weight, 0.0);
+
+        let off = DenoiseConfig {
+            strength: Some(0.0),
+            ..DenoiseConfig::default()
+        };
+        let params = DenoiseParams::from_config(&off).unwrap();
+        assert_eq!((params.sigma_range, params.history_weight), (0.0, 0.0));
+    }
+
+    #[test]
+    fn out_of_range_denoise_settings_are_rejected() {
+        for config in [
+            DenoiseConfig {
+                strength: Some(1.5),
+                ..DenoiseConfig::default()
+            },
+            DenoiseConfig {
+                motion_threshold: Some(0.0),
+                ..DenoiseConfig::default()
+            },
+        ] {
+            assert!(matches!(
+                DenoiseParams::from_config(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn sharpen_defaults_and_bounds() {
+        let params = SharpenParams::from_config(&SharpenConfig::default()).unwrap();
+        assert_eq!(
+            params,
+            SharpenParams {
+                radius: 2,
+                sigma: 1.0,
+                strength: 0.6,
+                threshold: 0.02,
+            }
+        );
+
+        for config in [
+            SharpenConfig {
+                strength: Some(5.0),
+                ..SharpenConfig::default()
+            },
+            SharpenConfig {
+                radius: Some(0),
+                ..SharpenConfig::default()
+            },
+            SharpenConfig {
+                radius: Some(9),
+                ..SharpenConfig::default()
+            },
+            SharpenConfig {
+                threshold: Some(-0.1),
+                ..SharpenConfig::default()
+            },
+        ] {
+            assert!(SharpenParams::from_config(&config).is_err());
+        }
+    }
+}
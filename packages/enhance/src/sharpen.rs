@@ -0,0 +1,221 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Sharpen (Linux) — unsharp mask on the GPU.
+//!
+//! One `sharpen.comp` dispatch per frame blurs luma with a Gaussian of
+//! `radius`, and adds the detail above `threshold`, scaled by `strength`,
+//! back onto the frame. It writes the next slot of an RGBA8 output ring,
+//! reallocated when the input size changes.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel, VulkanLayout,
+    VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::params::SharpenParams;
+
+const SHARPEN_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sharpen.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+/// Matches `sharpen.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Push constants of `sharpen.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SharpenPushConstants {
+    width: u32,
+    height: u32,
+    radius: u32,
+    sigma: f32,
+    strength: f32,
+    threshold: f32,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/enhance/Sharpen",
+    description = "Sharpens frames on the GPU with an unsharp mask on luma, so edges gain contrast without color fringes. Detail below `threshold` is left alone rather than amplified.",
+    execution = reactive,
+    config = crate::_generated_::SharpenConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to sharpen"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Sharpened frames (RGBA8, the input's color description)"),
+)]
+pub struct SharpenProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    params: Option<SharpenParams>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_sharpened: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SharpenProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = SharpenParams::from_config(&self.config)?;
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "sharpen",
+            spv: SHARPEN_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<SharpenPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("sharpen")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.params = Some(params);
+        tracing::info!("[Sharpen] Setup ({:?})", params);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[Sharpen] Teardown ({} frames sharpened)",
+            self.frames_sharpened
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = SharpenParams::from_config(&self.config)?;
+        self.params = Some(params);
+        tracing::info!("[Sharpen] Config updated ({:?})", params);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let sharpened = self.sharpen(&frame)?;
+        self.frames_sharpened += 1;
+        self.outputs.write("video_out", &sharpened)
+    }
+}
+
+impl SharpenProcessor::Processor {
+    /// Sharpen `frame` into the next output slot.
+    fn sharpen(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let (Some(gpu), Some(kernel), Some(recorder), Some(params)) = (
+            self.gpu_context.as_ref(),
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.params,
+        ) else {
+            return Err(Error::Configuration(
+                "Sharpen: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_push_constants_value(&SharpenPushConstants {
+            width,
+            height,
+            radius: params.radius,
+            sigma: params.sigma,
+            strength: params.strength,
+            threshold: params.threshold,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<SharpenPushConstants>(), 24);
+        assert_eq!(std::mem::offset_of!(SharpenPushConstants, sigma), 12);
+    }
+}
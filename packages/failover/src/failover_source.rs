@@ -0,0 +1,270 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Primary/backup source failover.
+//!
+//! "Goes away" means the primary stops delivering frames: an unplugged
+//! camera, a crashed decoder and a stalled network source all look the
+//! same from here, so no device-specific signal is needed. Switching back
+//! waits for a gap-free run of primary frames (the hysteresis), so a
+//! flapping source doesn't bounce the output.
+//!
+//! Each switch is published on the [`FAILOVER_SWITCH_TOPIC`] custom-event
+//! topic so operator UIs can show which input is on air.
+
+use std::time::{Duration, Instant};
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::Result;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+use crate::_generated_::VideoFrame;
+
+/// Custom-event topic carrying `{processor_id, active}` on every switch.
+pub const FAILOVER_SWITCH_TOPIC: &str = "failover:switch";
+
+const DEFAULT_FAILOVER_AFTER_MS: u32 = 500;
+const DEFAULT_RECOVER_AFTER_MS: u32 = 3000;
+
+/// Input currently forwarded to the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverInput {
+    Primary,
+    Backup,
+}
+
+impl FailoverInput {
+    fn port(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Backup => "backup",
+        }
+    }
+}
+
+/// The switching decision, separate from port I/O so it can be driven
+/// with synthetic instants.
+#[derive(Debug)]
+struct FailoverSwitch {
+    failover_after: Duration,
+    recover_after: Duration,
+    active: FailoverInput,
+    last_primary_frame: Instant,
+    /// Start of the current gap-free run of primary frames.
+    primary_run_start: Option<Instant>,
+}
+
+impl FailoverSwitch {
+    /// Starts on the primary; it gets `failover_after` to deliver a first
+    /// frame.
+    fn new(now: Instant, failover_after: Duration, recover_after: Duration) -> Self {
+        Self {
+            failover_after,
+            recover_after,
+            active: FailoverInput::Primary,
+            last_primary_frame: now,
+            primary_run_start: Some(now),
+        }
+    }
+
+    fn on_primary_frame(&mut self, now: Instant) {
+        if now.duration_since(self.last_primary_frame) > self.failover_after {
+            self.primary_run_start = Some(now);
+        } else if self.primary_run_start.is_none() {
+            self.primary_run_start = Some(now);
+        }
+        self.last_primary_frame = now;
+    }
+
+    /// Re-evaluate at `now`. Returns the newly active input when it changed.
+    fn update(&mut self, now: Instant) -> Option<FailoverInput> {
+        let primary_alive = now.duration_since(self.last_primary_frame) <= self.failover_after;
+        match self.active {
+            FailoverInput::Primary if !primary_alive => {
+                self.active = FailoverInput::Backup;
+                self.primary_run_start = None;
+                Some(self.active)
+            }
+            FailoverInput::Backup
+                if primary_alive
+                    && self
+                        .primary_run_start
+                        .is_some_and(|start| now.duration_since(start) >= self.recover_after) =>
+            {
+                self.active = FailoverInput::Primary;
+                Some(self.active)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/failover/FailoverSource",
+    description = "Forwards the primary video input; switches to the backup input (color bars, slate, secondary camera) when the primary stops delivering frames, and back once it has been steady for the recovery window.",
+    execution = reactive,
+    config = crate::_generated_::FailoverSourceConfig,
+    input("primary", "@tatolab/core/VideoFrame", description = "Preferred video source"),
+    input("backup", "@tatolab/core/VideoFrame", description = "Video forwarded while the primary is away"),
+    output("video", "@tatolab/core/VideoFrame", description = "Frames from whichever input is active"),
+)]
+pub struct FailoverSourceProcessor {
+    processor_id: Option<String>,
+    switch: Option<FailoverSwitch>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for FailoverSourceProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        let (failover_after, recover_after) = self.windows();
+        self.switch = Some(FailoverSwitch::new(
+            Instant::now(),
+            failover_after,
+            recover_after,
+        ));
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let (failover_after, recover_after) = self.windows();
+        if let Some(switch) = &mut self.switch {
+            switch.failover_after = failover_after;
+            switch.recover_after = recover_after;
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let now = Instant::now();
+        let primary: Option<VideoFrame> = if self.inputs.has_data("primary") {
+            Some(self.inputs.read("primary")?)
+        } else {
+            None
+        };
+        let backup: Option<VideoFrame> = if self.inputs.has_data("backup") {
+            Some(self.inputs.read("backup")?)
+        } else {
+            None
+        };
+
+        let Some(switch) = &mut self.switch else {
+            return Ok(());
+        };
+        if primary.is_some() {
+            switch.on_primary_frame(now);
+        }
+        let switched = switch.update(now);
+        let active = switch.active;
+        if let Some(active) = switched {
+            self.announce(active);
+        }
+
+        let frame = match active {
+            FailoverInput::Primary => primary,
+            FailoverInput::Backup => backup,
+        };
+        if let Some(frame) = frame {
+            self.outputs.write("video", &frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl FailoverSourceProcessor::Processor {
+    /// `(failover_after, recover_after)` from the config.
+    fn windows(&self) -> (Duration, Duration) {
+        let failover_after = self
+            .config
+            .failover_after_ms
+            .unwrap_or(DEFAULT_FAILOVER_AFTER_MS);
+        let recover_after = self
+            .config
+            .recover_after_ms
+            .unwrap_or(DEFAULT_RECOVER_AFTER_MS);
+        (
+            Duration::from_millis(failover_after.into()),
+            Duration::from_millis(recover_after.into()),
+        )
+    }
+
+    fn announce(&self, active: FailoverInput) {
+        match active {
+            FailoverInput::Backup => tracing::warn!("FailoverSource: primary went away, on backup"),
+            FailoverInput::Primary => {
+                tracing::info!("FailoverSource: primary steady, back on primary")
+            }
+        }
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "active": active.port(),
+        });
+        if let Err(e) = publish_custom_event(FAILOVER_SWITCH_TOPIC, &payload) {
+            tracing::debug!("FailoverSource: switch not published: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAILOVER_AFTER: Duration = Duration::from_millis(500);
+    const RECOVER_AFTER: Duration = Duration::from_millis(3000);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn switches_to_backup_once_the_primary_goes_quiet() {
+        let t0 = Instant::now();
+        let mut switch = FailoverSwitch::new(t0, FAILOVER_AFTER, RECOVER_AFTER);
+
+        switch.on_primary_frame(t0 + ms(33));
+        assert_eq!(
+            switch.update(t0 + ms(500)),
+            None,
+            "within the failover window"
+        );
+
+        assert_eq!(switch.update(t0 + ms(600)), Some(FailoverInput::Backup));
+        assert_eq!(switch.update(t0 + ms(700)), None, "reported once");
+        assert_eq!(switch.active, FailoverInput::Backup);
+    }
+
+    #[test]
+    fn a_primary_that_never_delivers_fails_over() {
+        let t0 = Instant::now();
+        let mut switch = FailoverSwitch::new(t0, FAILOVER_AFTER, RECOVER_AFTER);
+
+        assert_eq!(switch.update(t0 + ms(501)), Some(FailoverInput::Backup));
+    }
+
+    #[test]
+    fn returns_to_primary_only_after_a_gap_free_recovery_window() {
+        let t0 = Instant::now();
+        let mut switch = FailoverSwitch::new(t0, FAILOVER_AFTER, RECOVER_AFTER);
+        switch.update(t0 + ms(600));
+        assert_eq!(switch.active, FailoverInput::Backup);
+
+        // Primary returns at 1s, then drops out for 800ms at 2s.
+        for frame_ms in (1000..=2000).step_by(100) {
+            switch.on_primary_frame(t0 + ms(frame_ms));
+            assert_eq!(switch.update(t0 + ms(frame_ms)), None);
+        }
+        // The gap restarts the run at 2.8s, so 4s is too soon to switch back.
+        for frame_ms in (2800..=5700).step_by(100) {
+            switch.on_primary_frame(t0 + ms(frame_ms));
+            assert_eq!(switch.update(t0 + ms(frame_ms)), None, "at {frame_ms}ms");
+        }
+
+        switch.on_primary_frame(t0 + ms(5800));
+        assert_eq!(switch.update(t0 + ms(5800)), Some(FailoverInput::Primary));
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/failover` — keeps a video output on air when its source goes
+//! away. `FailoverSource` forwards a primary input and switches to a backup
+//! (color bars, a slate, a second camera) when the primary stops
+//! delivering, returning once the primary has been steady for a recovery
+//! window.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod failover_source;
+
+pub use failover_source::{FAILOVER_SWITCH_TOPIC, FailoverInput, FailoverSourceProcessor};
+
+streamlib_plugin_abi::export_plugin!(crate::FailoverSourceProcessor::Processor,);
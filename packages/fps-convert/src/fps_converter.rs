@@ -0,0 +1,501 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! FpsConverter (Linux) — frame-rate conversion onto an exact output
+//! timeline.
+//!
+//! Drop/duplicate (the default) forwards the input surface once per output
+//! frame it is nearest to, restamped, with no GPU work. Blend and
+//! motion-compensated modes hold the last input in a pooled RGBA8
+//! reference texture (`store_reference.comp`) and, when the next input
+//! arrives, synthesize every output frame between the two with one
+//! `interpolate.comp` dispatch each into the next slot of an RGBA8 output
+//! ring. Motion-compensated mode first estimates block motion between the
+//! two frames with `motion_search.comp` into a pooled `Rgba16Float`
+//! texture at block resolution. The reference and motion textures stay in
+//! `GENERAL` layout; they are reacquired, and the timeline restarts,
+//! whenever the input size changes.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, PooledTextureHandle, RhiCommandRecorder,
+    TextureFormat, TexturePoolDescriptor, TextureRing, TextureUsages, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::params::{
+    BLOCK_SIZE, ConversionMode, FpsConvertParams, MOTION_PENALTY, OCCLUSION_THRESHOLD,
+    SEARCH_RADIUS,
+};
+use crate::timing::{FrameClock, OutputTick, TickContent};
+
+const STORE_REFERENCE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/store_reference.spv"));
+const MOTION_SEARCH_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/motion_search.spv"));
+const INTERPOLATE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/interpolate.spv"));
+
+const STORE_REFERENCE_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+const MOTION_SEARCH_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_image(2),
+];
+
+const INTERPOLATE_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_image(2),
+    ComputeBindingSpec::storage_image(3),
+];
+
+/// Matches the 16x16 workgroup of all three shaders.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Output ring depth — one input can yield several output frames (two at
+/// 30 → 60), and earlier ones may still be sampled downstream while the
+/// next is written.
+const OUTPUT_RING_DEPTH: usize = 4;
+
+/// Push constants of `store_reference.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StoreReferencePushConstants {
+    width: u32,
+    height: u32,
+}
+
+/// Push constants of `motion_search.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MotionSearchPushConstants {
+    width: u32,
+    height: u32,
+    blocks_x: u32,
+    blocks_y: u32,
+    search_radius: u32,
+    motion_penalty: f32,
+}
+
+/// Push constants of `interpolate.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct InterpolatePushConstants {
+    width: u32,
+    height: u32,
+    blocks_x: u32,
+    blocks_y: u32,
+    use_motion: u32,
+    phase: f32,
+    occlusion_threshold: f32,
+}
+
+/// The held input frame and the block motion towards the next one.
+struct Reference {
+    texture: PooledTextureHandle,
+    motion: PooledTextureHandle,
+    /// False until the first submit: the pooled textures' contents and
+    /// layout are undefined until then.
+    initialized: bool,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/fps-convert/FpsConverter",
+    description = "Converts a video stream to a fixed frame rate. Output frames are stamped on an exact timeline at the target rate, anchored to the first input. By default source frames are dropped or duplicated; the quality setting switches to GPU cross-fading or motion-compensated interpolation between source frames.",
+    execution = reactive,
+    config = crate::_generated_::FpsConverterConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames at the source rate"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Frames at the target rate (interpolated frames are RGBA8, the input's color description)"),
+)]
+pub struct FpsConverterProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    store_kernel: Option<VulkanComputeKernel>,
+    motion_kernel: Option<VulkanComputeKernel>,
+    interpolate_kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    params: Option<FpsConvertParams>,
+    clock: Option<FrameClock>,
+    reference: Option<Reference>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_in: u64,
+    frames_out: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for FpsConverterProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = FpsConvertParams::from_config(&self.config)?;
+        let full = ctx.gpu_full_access();
+        self.store_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "fps-convert-store-reference",
+            spv: STORE_REFERENCE_SPV,
+            bindings: STORE_REFERENCE_BINDINGS,
+            push_constant_size: std::mem::size_of::<StoreReferencePushConstants>() as u32,
+        })?);
+        self.motion_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "fps-convert-motion-search",
+            spv: MOTION_SEARCH_SPV,
+            bindings: MOTION_SEARCH_BINDINGS,
+            push_constant_size: std::mem::size_of::<MotionSearchPushConstants>() as u32,
+        })?);
+        self.interpolate_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "fps-convert-interpolate",
+            spv: INTERPOLATE_SPV,
+            bindings: INTERPOLATE_BINDINGS,
+            push_constant_size: std::mem::size_of::<InterpolatePushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("fps-convert")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.params = Some(params);
+        tracing::info!("[FpsConverter] Setup ({:?})", params);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.store_kernel = None;
+        self.motion_kernel = None;
+        self.interpolate_kernel = None;
+        self.reference = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[FpsConverter] Teardown ({} frames in, {} out)",
+            self.frames_in,
+            self.frames_out
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = FpsConvertParams::from_config(&self.config)?;
+        // A new rate or mode starts a new timeline at the next input.
+        if self.params != Some(params) {
+            self.clock = None;
+        }
+        self.params = Some(params);
+        tracing::info!("[FpsConverter] Config updated ({:?})", params);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let Some(params) = self.params else {
+            return Err(Error::Configuration("FpsConverter: not initialized".into()));
+        };
+        let timestamp_ns: u64 = frame.timestamp_ns.parse().map_err(|_| {
+            Error::Runtime(format!(
+                "FpsConverter: timestamp_ns must be a non-negative integer, got {:?}",
+                frame.timestamp_ns
+            ))
+        })?;
+        self.frames_in += 1;
+
+        if params.mode.interpolates()
+            && self.reference.as_ref().is_some_and(|reference| {
+                (reference.texture.width(), reference.texture.height())
+                    != (frame.width, frame.height)
+            })
+        {
+            tracing::debug!(
+                "[FpsConverter] Input size changed to {}x{}; restarting the timeline",
+                frame.width,
+                frame.height
+            );
+            self.reference = None;
+            self.clock = None;
+        }
+        let clock = self
+            .clock
+            .get_or_insert_with(|| FrameClock::new(params.rate));
+
+        let outputs = if params.mode.interpolates() {
+            let ticks = clock.advance_interpolating(timestamp_ns);
+            self.interpolate(&frame, &ticks, &params)?
+        } else {
+            clock
+                .advance_hold(timestamp_ns)
+                .into_iter()
+                .map(|tick| VideoFrame {
+                    timestamp_ns: tick.timestamp_ns.to_string(),
+                    fps: Some(params.rate.nominal_fps()),
+                    ..frame.clone()
+                })
+                .collect()
+        };
+        for output in &outputs {
+            self.outputs.write("video_out", output)?;
+        }
+        self.frames_out += outputs.len() as u64;
+        Ok(())
+    }
+}
+
+impl FpsConverterProcessor::Processor {
+    /// Synthesize one output frame per tick between the held reference and
+    /// `frame`, then make `frame` the new reference.
+    fn interpolate(
+        &mut self,
+        frame: &VideoFrame,
+        ticks: &[OutputTick],
+        params: &FpsConvertParams,
+    ) -> Result<Vec<VideoFrame>> {
+        let (
+            Some(gpu),
+            Some(store_kernel),
+            Some(motion_kernel),
+            Some(interpolate_kernel),
+            Some(recorder),
+        ) = (
+            self.gpu_context.as_ref(),
+            self.store_kernel.as_ref(),
+            self.motion_kernel.as_ref(),
+            self.interpolate_kernel.as_ref(),
+            self.recorder.as_mut(),
+        )
+        else {
+            return Err(Error::Configuration(
+                "FpsConverter: kernels not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+        let blocks_x = width.div_ceil(BLOCK_SIZE);
+        let blocks_y = height.div_ceil(BLOCK_SIZE);
+
+        let reference = match self.reference.take() {
+            Some(reference)
+                if (reference.texture.width(), reference.texture.height()) == (width, height) =>
+            {
+                reference
+            }
+            _ => Reference {
+                texture: gpu.acquire_texture(&TexturePoolDescriptor {
+                    width,
+                    height,
+                    format: TextureFormat::Rgba8Unorm,
+                    usage: TextureUsages::STORAGE_BINDING,
+                    label: Some("fps-convert-reference"),
+                })?,
+                motion: gpu.acquire_texture(&TexturePoolDescriptor {
+                    width: blocks_x,
+                    height: blocks_y,
+                    format: TextureFormat::Rgba16Float,
+                    usage: TextureUsages::STORAGE_BINDING,
+                    label: Some("fps-convert-motion"),
+                })?,
+                initialized: false,
+            },
+        };
+        let reference = self.reference.insert(reference);
+        let use_motion = params.mode == ConversionMode::MotionCompensated && !ticks.is_empty();
+
+        // ---- Input in, references ready, motion estimated ---------------------
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        // Fresh pooled textures are transitioned out of UNDEFINED; after
+        // that they stay in GENERAL, and the barriers only make the last
+        // input's writes visible to this input's reads.
+        let history_layout = if reference.initialized {
+            VulkanLayout::GENERAL
+        } else {
+            VulkanLayout::UNDEFINED
+        };
+        for history in [reference.texture.texture(), reference.motion.texture()] {
+            recorder.record_image_barrier(
+                history,
+                history_layout,
+                VulkanLayout::GENERAL,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::SHADER_WRITE,
+                VulkanAccess::SHADER_READ | VulkanAccess::SHADER_WRITE,
+            )?;
+        }
+        if use_motion {
+            motion_kernel.set_sampled_texture(0, &texture)?;
+            motion_kernel.set_storage_image(1, reference.texture.texture())?;
+            motion_kernel.set_storage_image(2, reference.motion.texture())?;
+            motion_kernel.set_push_constants_value(&MotionSearchPushConstants {
+                width,
+                height,
+                blocks_x,
+                blocks_y,
+                search_radius: SEARCH_RADIUS,
+                motion_penalty: MOTION_PENALTY,
+            })?;
+            recorder.record_dispatch(
+                motion_kernel,
+                blocks_x.div_ceil(WORKGROUP_SIZE),
+                blocks_y.div_ceil(WORKGROUP_SIZE),
+                1,
+            )?;
+            recorder.record_image_barrier(
+                reference.motion.texture(),
+                VulkanLayout::GENERAL,
+                VulkanLayout::GENERAL,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::SHADER_WRITE,
+                VulkanAccess::SHADER_READ,
+            )?;
+        }
+        recorder.submit_and_wait()?;
+        registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+        reference.initialized = true;
+
+        // ---- One output frame per tick ----------------------------------------
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let mut outputs = Vec::with_capacity(ticks.len());
+        for tick in ticks {
+            let phase = match tick.content {
+                TickContent::Interpolated { phase } => phase,
+                TickContent::Current => 1.0,
+            };
+            let slot = ring.acquire_next();
+            let slot_surface_id = slot.surface_id().to_string();
+            let slot_registration = gpu.resolve_texture_registration_by_surface_id(
+                &slot_surface_id,
+                None,
+                width,
+                height,
+            )?;
+
+            interpolate_kernel.set_sampled_texture(0, &texture)?;
+            interpolate_kernel.set_storage_image(1, reference.texture.texture())?;
+            interpolate_kernel.set_storage_image(2, reference.motion.texture())?;
+            interpolate_kernel.set_storage_image(3, &slot.texture)?;
+            interpolate_kernel.set_push_constants_value(&InterpolatePushConstants {
+                width,
+                height,
+                blocks_x,
+                blocks_y,
+                use_motion: use_motion as u32,
+                phase,
+                occlusion_threshold: OCCLUSION_THRESHOLD,
+            })?;
+
+            recorder.begin()?;
+            recorder.record_image_barrier(
+                &slot.texture,
+                slot_registration.current_layout(),
+                VulkanLayout::GENERAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_READ,
+                VulkanAccess::SHADER_WRITE,
+            )?;
+            recorder.record_dispatch(
+                interpolate_kernel,
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            )?;
+            recorder.finish_compute_output(&slot_registration, &[])?;
+
+            outputs.push(VideoFrame {
+                surface_id: slot_surface_id,
+                width,
+                height,
+                timestamp_ns: tick.timestamp_ns.to_string(),
+                fps: Some(params.rate.nominal_fps()),
+                texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+                color_info: frame.color_info.clone(),
+                mastering_display: frame.mastering_display.clone(),
+                content_light: frame.content_light.clone(),
+                // Synthesized between two inputs; fields don't survive.
+                field_order: None,
+            });
+        }
+
+        // ---- The input becomes the next reference -----------------------------
+        store_kernel.set_sampled_texture(0, &texture)?;
+        store_kernel.set_storage_image(1, reference.texture.texture())?;
+        store_kernel.set_push_constants_value(&StoreReferencePushConstants { width, height })?;
+        recorder.begin()?;
+        // This input's interpolation reads of the reference finish before
+        // it is overwritten.
+        recorder.record_image_barrier(
+            reference.texture.texture(),
+            VulkanLayout::GENERAL,
+            VulkanLayout::GENERAL,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::SHADER_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            store_kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.submit_and_wait()?;
+
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_blocks() {
+        assert_eq!(std::mem::size_of::<StoreReferencePushConstants>(), 8);
+        assert_eq!(std::mem::size_of::<MotionSearchPushConstants>(), 24);
+        assert_eq!(
+            std::mem::offset_of!(MotionSearchPushConstants, motion_penalty),
+            20
+        );
+        assert_eq!(std::mem::size_of::<InterpolatePushConstants>(), 28);
+        assert_eq!(std::mem::offset_of!(InterpolatePushConstants, phase), 20);
+        assert_eq!(
+            std::mem::offset_of!(InterpolatePushConstants, occlusion_threshold),
+            24
+        );
+    }
+}
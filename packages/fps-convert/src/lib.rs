@@ -0,0 +1,26 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/fps-convert` — frame-rate conversion. `FpsConverter` restamps
+//! a stream onto an exact timeline at a target rate, dropping or
+//! duplicating frames by default, or cross-fading / motion-compensating
+//! between them on the GPU for smooth up-conversion.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod params;
+pub mod timing;
+
+// Cross-fading and motion-compensated blending record through `sdk::rhi`,
+// which is Linux-only; the `timing` cadence maths stays portable.
+#[cfg(target_os = "linux")]
+pub mod fps_converter;
+
+#[cfg(target_os = "linux")]
+pub use fps_converter::FpsConverterProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::FpsConverterProcessor::Processor);
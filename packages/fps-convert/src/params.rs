@@ -0,0 +1,130 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Conversion parameters — `FpsConverterConfig` resolved into the output
+//! rate and how output frames are produced.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::FpsConverterConfig;
+use crate::_generated_::tatolab__fps_convert::fps_converter_config::Quality;
+use crate::timing::FrameRate;
+
+pub const MAX_TARGET_FPS: f64 = 240.0;
+
+/// Motion block edge, in pixels. Matches `motion_search.comp`.
+pub const BLOCK_SIZE: u32 = 8;
+/// Largest motion searched per axis between two input frames, in pixels.
+pub const SEARCH_RADIUS: u32 = 16;
+/// Block-match cost added per pixel of motion, in mean luma difference —
+/// flat regions settle on zero motion instead of an arbitrary match.
+pub const MOTION_PENALTY: f32 = 0.002;
+/// Luma mismatch between the two motion-compensated samples above which a
+/// texel is treated as occluded and taken from the nearer input frame.
+pub const OCCLUSION_THRESHOLD: f32 = 0.12;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversionMode {
+    /// Show the nearest input frame; no GPU work, no added latency.
+    #[default]
+    DropDuplicate,
+    /// Cross-fade the two input frames around each output time.
+    Blend,
+    /// Interpolate along block motion estimated between the two input
+    /// frames.
+    MotionCompensated,
+}
+
+impl ConversionMode {
+    /// Whether output frames are synthesized from two input frames, which
+    /// holds one input frame back.
+    pub fn interpolates(self) -> bool {
+        self != Self::DropDuplicate
+    }
+}
+
+/// `FpsConverterConfig`, validated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FpsConvertParams {
+    pub rate: FrameRate,
+    pub mode: ConversionMode,
+}
+
+impl FpsConvertParams {
+    pub fn from_config(config: &FpsConverterConfig) -> Result<Self> {
+        let denominator = config.target_fps_denominator.unwrap_or(1);
+        if config.target_fps == 0 || denominator == 0 {
+            return Err(Error::Configuration(format!(
+                "FpsConverter: target rate must be nonzero, got {}/{}",
+                config.target_fps, denominator
+            )));
+        }
+        let rate = FrameRate::new(config.target_fps, denominator);
+        if rate.as_f64() > MAX_TARGET_FPS {
+            return Err(Error::Configuration(format!(
+                "FpsConverter: target rate must be at most {} fps, got {}/{}",
+                MAX_TARGET_FPS, config.target_fps, denominator
+            )));
+        }
+        let mode = match config.quality {
+            Some(Quality::Fast) | None => ConversionMode::DropDuplicate,
+            Some(Quality::Balanced) => ConversionMode::Blend,
+            Some(Quality::High) => ConversionMode::MotionCompensated,
+        };
+        Ok(Self { rate, mode })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(target_fps: u32) -> FpsConverterConfig {
+        FpsConverterConfig {
+            target_fps,
+            ..FpsConverterConfig::default()
+        }
+    }
+
+    #[test]
+    fn defaults_to_drop_duplicate_at_a_whole_rate() {
+        let params = FpsConvertParams::from_config(&config(60)).unwrap();
+        assert_eq!(params.rate, FrameRate::new(60, 1));
+        assert_eq!(params.mode, ConversionMode::DropDuplicate);
+        assert!(!params.mode.interpolates());
+    }
+
+    #[test]
+    fn quality_selects_the_mode_and_fractional_rates_are_kept() {
+        let params = FpsConvertParams::from_config(&FpsConverterConfig {
+            target_fps: 60_000,
+            target_fps_denominator: Some(1001),
+            quality: Some(Quality::High),
+        })
+        .unwrap();
+        assert_eq!(params.rate, FrameRate::new(60_000, 1001));
+        assert_eq!(params.mode, ConversionMode::MotionCompensated);
+        assert!(params.mode.interpolates());
+
+        let balanced = FpsConvertParams::from_config(&FpsConverterConfig {
+            quality: Some(Quality::Balanced),
+            ..config(30)
+        })
+        .unwrap();
+        assert_eq!(balanced.mode, ConversionMode::Blend);
+    }
+
+    #[test]
+    fn zero_and_excessive_rates_are_rejected() {
+        assert!(FpsConvertParams::from_config(&config(0)).is_err());
+        assert!(
+            FpsConvertParams::from_config(&FpsConverterConfig {
+                target_fps_denominator: Some(0),
+                ..config(30)
+            })
+            .is_err()
+        );
+        assert!(FpsConvertParams::from_config(&config(241)).is_err());
+        assert!(FpsConvertParams::from_config(&config(240)).is_ok());
+    }
+}
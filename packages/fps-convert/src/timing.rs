@@ -0,0 +1,287 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Output timeline — which output frames an input frame produces, and the
+//! timestamps they carry.
+//!
+//! Output frame `n` is stamped `origin + n / rate`, computed exactly from
+//! the rational rate rather than by accumulating a rounded interval, so a
+//! 30000/1001 stream does not drift over hours. The origin is the first
+//! input's timestamp, and is re-anchored when the input timeline jumps
+//! (backwards, or forward by more than [`MAX_GAP_NS`]) — a restarted or
+//! spliced source, not a late frame.
+
+/// Input gaps beyond this re-anchor the output timeline instead of being
+/// filled with duplicated or interpolated frames.
+pub const MAX_GAP_NS: u64 = 500_000_000;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// A frame rate of `numerator / denominator` frames per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl FrameRate {
+    pub fn new(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// The rate rounded to whole frames per second, for `VideoFrame::fps`.
+    pub fn nominal_fps(&self) -> u32 {
+        (self.as_f64().round() as u32).max(1)
+    }
+
+    /// Offset of output frame `index` from the origin, rounded to the
+    /// nearest nanosecond.
+    pub fn offset_ns(&self, index: u64) -> u64 {
+        let numerator = self.numerator as u128;
+        let scaled = index as u128 * self.denominator as u128 * NANOS_PER_SECOND;
+        ((scaled + numerator / 2) / numerator) as u64
+    }
+
+    /// Half an output interval, in nanoseconds.
+    fn half_interval_ns(&self) -> u64 {
+        (self.denominator as u128 * NANOS_PER_SECOND / (2 * self.numerator as u128)) as u64
+    }
+}
+
+/// What an output frame shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TickContent {
+    /// The input frame that produced the tick, as is.
+    Current,
+    /// A frame `phase` of the way from the previous input frame (0) to the
+    /// current one (1).
+    Interpolated { phase: f32 },
+}
+
+/// One output frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputTick {
+    pub timestamp_ns: u64,
+    pub content: TickContent,
+}
+
+/// Walks the output timeline as input frames arrive.
+#[derive(Debug, Clone)]
+pub struct FrameClock {
+    rate: FrameRate,
+    origin_ns: Option<u64>,
+    /// Index of the first output frame not yet emitted.
+    next_index: u64,
+    /// Timestamp of the last input frame.
+    previous_ns: Option<u64>,
+}
+
+impl FrameClock {
+    pub fn new(rate: FrameRate) -> Self {
+        Self {
+            rate,
+            origin_ns: None,
+            next_index: 0,
+            previous_ns: None,
+        }
+    }
+
+    pub fn rate(&self) -> FrameRate {
+        self.rate
+    }
+
+    /// Forget the timeline; the next input frame starts a new one.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.rate);
+    }
+
+    fn tick_ns(&self, index: u64, origin_ns: u64) -> u64 {
+        origin_ns.saturating_add(self.rate.offset_ns(index))
+    }
+
+    /// Record an input frame at `timestamp_ns`; returns the previous
+    /// input's timestamp, or `None` when this frame starts a timeline.
+    fn observe(&mut self, timestamp_ns: u64) -> Option<u64> {
+        let previous = self.previous_ns.replace(timestamp_ns);
+        match (previous, self.origin_ns) {
+            (Some(previous), Some(_))
+                if timestamp_ns >= previous && timestamp_ns - previous <= MAX_GAP_NS =>
+            {
+                Some(previous)
+            }
+            (previous, _) => {
+                if previous.is_some() {
+                    tracing::debug!(
+                        "[FpsConverter] Timeline re-anchored at {} ns (previous input {:?} ns)",
+                        timestamp_ns,
+                        previous
+                    );
+                }
+                self.origin_ns = Some(timestamp_ns);
+                self.next_index = 0;
+                None
+            }
+        }
+    }
+
+    /// Drop/duplicate: every output frame within half an output interval
+    /// after `timestamp_ns` and not yet emitted shows this input frame, so
+    /// each output shows the input nearest to it and nothing waits on the
+    /// next input. Yields nothing when the input is dropped.
+    pub fn advance_hold(&mut self, timestamp_ns: u64) -> Vec<OutputTick> {
+        self.observe(timestamp_ns);
+        let Some(origin_ns) = self.origin_ns else {
+            return Vec::new();
+        };
+        let horizon_ns = timestamp_ns.saturating_add(self.rate.half_interval_ns());
+        let mut ticks = Vec::new();
+        loop {
+            let tick_ns = self.tick_ns(self.next_index, origin_ns);
+            // Ticks that land exactly half-way go to the later input.
+            if tick_ns >= horizon_ns {
+                break;
+            }
+            ticks.push(OutputTick {
+                timestamp_ns: tick_ns,
+                content: TickContent::Current,
+            });
+            self.next_index += 1;
+        }
+        ticks
+    }
+
+    /// Interpolation: every output frame from the previous input frame up
+    /// to (not including) `timestamp_ns`, each with its phase between the
+    /// two. The output runs one input interval behind; the first input
+    /// frame of a timeline yields nothing.
+    pub fn advance_interpolating(&mut self, timestamp_ns: u64) -> Vec<OutputTick> {
+        let Some(previous_ns) = self.observe(timestamp_ns) else {
+            return Vec::new();
+        };
+        let Some(origin_ns) = self.origin_ns else {
+            return Vec::new();
+        };
+        let span_ns = timestamp_ns - previous_ns;
+        let mut ticks = Vec::new();
+        loop {
+            let tick_ns = self.tick_ns(self.next_index, origin_ns);
+            if tick_ns >= timestamp_ns {
+                break;
+            }
+            let phase = (tick_ns.saturating_sub(previous_ns)) as f64 / span_ns as f64;
+            ticks.push(OutputTick {
+                timestamp_ns: tick_ns,
+                content: TickContent::Interpolated {
+                    phase: phase as f32,
+                },
+            });
+            self.next_index += 1;
+        }
+        ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTSC: FrameRate = FrameRate {
+        numerator: 30_000,
+        denominator: 1001,
+    };
+
+    fn timestamps(ticks: &[OutputTick]) -> Vec<u64> {
+        ticks.iter().map(|tick| tick.timestamp_ns).collect()
+    }
+
+    #[test]
+    fn fractional_rates_stay_on_the_exact_grid() {
+        assert_eq!(NTSC.offset_ns(1), 33_366_667);
+        assert_eq!(NTSC.nominal_fps(), 30);
+        // 30000 frames of 29.97 span exactly 1001 s — no accumulated drift.
+        assert_eq!(NTSC.offset_ns(30_000), 1_001_000_000_000);
+        assert_eq!(FrameRate::new(60, 1).offset_ns(3), 50_000_000);
+    }
+
+    #[test]
+    fn hold_duplicates_30_to_60() {
+        let mut clock = FrameClock::new(FrameRate::new(60, 1));
+        let first = clock.advance_hold(1_000_000_000);
+        assert_eq!(timestamps(&first), [1_000_000_000]);
+        assert!(
+            first
+                .iter()
+                .all(|tick| tick.content == TickContent::Current)
+        );
+
+        // 33.3 ms later: the 16.7 ms and 33.3 ms ticks are nearer to it.
+        let second = clock.advance_hold(1_033_333_333);
+        assert_eq!(timestamps(&second), [1_016_666_667, 1_033_333_333]);
+        let third = clock.advance_hold(1_066_666_667);
+        assert_eq!(timestamps(&third), [1_050_000_000, 1_066_666_667]);
+    }
+
+    #[test]
+    fn hold_drops_60_to_30() {
+        let mut clock = FrameClock::new(FrameRate::new(30, 1));
+        let emitted: Vec<usize> = (0..6u64)
+            .map(|i| clock.advance_hold(FrameRate::new(60, 1).offset_ns(i)).len())
+            .collect();
+        assert_eq!(emitted, [1, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn interpolation_lags_one_input_and_reports_phase() {
+        let mut clock = FrameClock::new(FrameRate::new(60, 1));
+        assert!(clock.advance_interpolating(0).is_empty());
+
+        let ticks = clock.advance_interpolating(33_333_333);
+        assert_eq!(timestamps(&ticks), [0, 16_666_667]);
+        let phases: Vec<f32> = ticks
+            .iter()
+            .map(|tick| match tick.content {
+                TickContent::Interpolated { phase } => phase,
+                TickContent::Current => panic!("interpolating clock emitted Current"),
+            })
+            .collect();
+        assert_eq!(phases[0], 0.0);
+        assert!((phases[1] - 0.5).abs() < 1e-6, "{phases:?}");
+
+        // A repeated timestamp emits nothing.
+        assert!(clock.advance_interpolating(33_333_333).is_empty());
+    }
+
+    #[test]
+    fn timeline_jumps_re_anchor() {
+        let mut clock = FrameClock::new(FrameRate::new(60, 1));
+        clock.advance_hold(5_000_000_000);
+        clock.advance_hold(5_033_333_333);
+
+        // Backwards: a restarted source.
+        assert_eq!(timestamps(&clock.advance_hold(100)), [100]);
+        // Forward past the gap limit: a stall, not frames to fill.
+        let resumed = 100 + MAX_GAP_NS + 1;
+        assert_eq!(timestamps(&clock.advance_hold(resumed)), [resumed]);
+
+        let mut interpolating = FrameClock::new(FrameRate::new(60, 1));
+        interpolating.advance_interpolating(0);
+        assert!(
+            interpolating
+                .advance_interpolating(MAX_GAP_NS * 2)
+                .is_empty()
+        );
+        assert_eq!(
+            interpolating
+                .advance_interpolating(MAX_GAP_NS * 2 + 33_333_333)
+                .len(),
+            2
+        );
+    }
+}
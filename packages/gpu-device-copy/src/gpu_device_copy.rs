@@ -0,0 +1,182 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! GpuDeviceCopy (Linux) — move frames between GPU adapters.
+//!
+//! Textures on one adapter can't be sampled by another, so each frame is
+//! resolved on the source adapter's context, read back into host memory
+//! and uploaded into a pixel buffer on this processor's own adapter. The
+//! hop costs a PCIe round trip per frame; place it where the graph
+//! actually changes devices, not between every stage.
+
+use std::time::Duration;
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    PixelFormat, TextureFormat, TextureReadback, TextureSourceLayout, VulkanLayout,
+};
+
+use crate::_generated_::VideoFrame;
+
+/// Upper bound on one frame's GPU→CPU copy on the source adapter.
+const READBACK_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/gpu-device-copy/GpuDeviceCopy",
+    description = "Copies frames produced on one GPU adapter onto another. Pin the processor (and everything downstream) to the destination adapter with the processor spec's gpu_adapter; frames are read back from source_adapter and uploaded on the destination.",
+    execution = reactive,
+    config = crate::_generated_::GpuDeviceCopyConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames on the source adapter (RGBA8 or BGRA8)"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames on this processor's adapter (RGBA8)"),
+)]
+pub struct GpuDeviceCopyProcessor {
+    /// Context on the adapter upstream frames live on.
+    source_gpu: Option<GpuContextLimitedAccess>,
+    /// This processor's own context — the destination.
+    destination_gpu: Option<GpuContextLimitedAccess>,
+    readback: Option<TextureReadback>,
+    /// Set once an unsupported input has been logged.
+    warned_format: bool,
+    frames_copied: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for GpuDeviceCopyProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let source_adapter = self.config.source_adapter;
+        self.source_gpu = Some(ctx.gpu_limited_access_for_adapter(source_adapter)?);
+        self.destination_gpu = Some(ctx.gpu_limited_access().clone());
+        tracing::info!("[GpuDeviceCopy] Setup (source adapter {})", source_adapter);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.readback = None;
+        self.source_gpu = None;
+        self.destination_gpu = None;
+        tracing::info!(
+            "[GpuDeviceCopy] Teardown ({} frames copied)",
+            self.frames_copied
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let Some(copied) = self.copy(frame)? else {
+            return Ok(());
+        };
+        self.frames_copied += 1;
+        if self.frames_copied == 1 {
+            tracing::info!("[GpuDeviceCopy] First frame copied");
+        }
+        self.outputs.write("video_out", &copied)
+    }
+}
+
+impl GpuDeviceCopyProcessor::Processor {
+    /// Read `frame` back on the source adapter and upload it on this one.
+    /// `None` drops a frame in a format the hop can't carry.
+    fn copy(&mut self, frame: VideoFrame) -> Result<Option<VideoFrame>> {
+        let (Some(source), Some(destination)) =
+            (self.source_gpu.as_ref(), self.destination_gpu.as_ref())
+        else {
+            return Err(Error::Configuration(
+                "GpuDeviceCopy: not initialized".into(),
+            ));
+        };
+        let registration = source.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height, format) = (texture.width(), texture.height(), texture.format());
+        if !matches!(
+            format,
+            TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm
+        ) {
+            if !self.warned_format {
+                self.warned_format = true;
+                tracing::warn!(
+                    "[GpuDeviceCopy] Dropping {:?} frames: only RGBA8/BGRA8 can cross adapters",
+                    format
+                );
+            }
+            return Ok(None);
+        }
+
+        let readback = match self.readback.take() {
+            Some(readback)
+                if (readback.width(), readback.height(), readback.format())
+                    == (width, height, format) =>
+            {
+                readback
+            }
+            _ => source.escalate(|full| {
+                full.create_texture_readback("gpu-device-copy", width, height, format)
+            })??,
+        };
+        let source_layout =
+            if registration.current_layout() == VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+                TextureSourceLayout::ShaderReadOnly
+            } else {
+                TextureSourceLayout::General
+            };
+        let ticket = readback.submit(&texture, source_layout)?;
+        let uploaded = readback
+            .wait_and_read(ticket, READBACK_TIMEOUT.as_nanos() as u64)
+            .and_then(|pixels| upload(destination, pixels, width, height, format));
+        self.readback = Some(readback);
+        let surface_id = uploaded?;
+
+        Ok(Some(VideoFrame {
+            surface_id,
+            width,
+            height,
+            // Freshly uploaded: the destination resolves the pixel buffer
+            // into a texture on first use.
+            texture_layout: None,
+            ..frame
+        }))
+    }
+}
+
+/// Copy tightly packed RGBA8/BGRA8 `pixels` into a pooled RGBA pixel
+/// buffer on `gpu`; returns its surface id.
+fn upload(
+    gpu: &GpuContextLimitedAccess,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+) -> Result<String> {
+    let (pool_id, pixel_buffer) = gpu.acquire_pixel_buffer(width, height, PixelFormat::Rgba32)?;
+    let dst_ptr = pixel_buffer.plane_base_address(0);
+    let rgba_len = width as usize * height as usize * 4;
+    if dst_ptr.is_null()
+        || (pixel_buffer.plane_size(0) as usize) < rgba_len
+        || pixels.len() < rgba_len
+    {
+        return Err(Error::GpuError(
+            "GpuDeviceCopy: destination pixel buffer is unmapped or too small".into(),
+        ));
+    }
+    // SAFETY: `dst_ptr` is the mapped base of a (width, height, Rgba32)
+    // buffer, checked to hold `rgba_len` bytes, and nothing else references
+    // it until its id is published.
+    let dst = unsafe { std::slice::from_raw_parts_mut(dst_ptr, rgba_len) };
+    dst.copy_from_slice(&pixels[..rgba_len]);
+    if format == TextureFormat::Bgra8Unorm {
+        dst.chunks_exact_mut(4).for_each(|px| px.swap(0, 2));
+    }
+    Ok(pool_id.to_string())
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/gpu-device-copy` — explicit cross-GPU hop. `GpuDeviceCopy`
+//! moves frames from one adapter to the adapter it is pinned to, for
+//! iGPU + dGPU machines and multi-GPU render boxes where part of a graph
+//! runs on another device.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+// The copy reads the source adapter's frame back through
+// `sdk::rhi::TextureReadback` and re-uploads it on the pinned adapter;
+// `sdk::rhi` is Linux-only.
+#[cfg(target_os = "linux")]
+pub mod gpu_device_copy;
+
+#[cfg(target_os = "linux")]
+pub use gpu_device_copy::GpuDeviceCopyProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::GpuDeviceCopyProcessor::Processor);
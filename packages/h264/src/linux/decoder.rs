@@ -181,6 +181,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for H264DecoderPro
                 color_info: color_info.clone(),
                 mastering_display: encoded.mastering_display.clone(),
                 content_light: encoded.content_light.clone(),
+                field_order: None,
             };
 
             let log_color = self.frames_decoded == 0;
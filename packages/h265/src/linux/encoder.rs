@@ -15,9 +15,17 @@
 // The camera's GPU-resident texture is resolved by `surface_id` and handed
 // to `submit_texture`, which resolves the encode-src image view host-side —
 // no `host_vulkan_texture_arc` / raw-view bridge in the cdylib.
+//
+// The optional `control` input takes `EncoderControl` messages. The host
+// programs rate control once per session, so a bitrate / QP change drops
+// the session and the next frame re-mints it (opening with an IDR) — the
+// one other escalate this processor does, at control cadence, not per
+// frame. `force_keyframe` rides the live session's `force_idr`. A change
+// in incoming frame size (an upstream scaler stepping a resolution ladder)
+// drops the session the same way, so the re-mint matches the new size.
 
 
-use crate::_generated_::{EncodedVideoFrame, VideoFrame};
+use crate::_generated_::{EncodedVideoFrame, EncoderControl, VideoFrame};
 use crate::linux::color_vui_translate::color_info_to_h273_repr;
 use streamlib_plugin_sdk::sdk::context::{
     GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
@@ -39,7 +47,8 @@ use streamlib_plugin_abi::{
     execution = reactive,
     scheduling = high,
     config = crate::_generated_::H265EncoderConfig,
-    input("video_in", "@tatolab/core/VideoFrame", delivery_profile = "every_sample", description = "Raw video frames to encode"),
+    input("video_in", "@tatolab/core/VideoFrame", format = "rgba8", delivery_profile = "every_sample", description = "Raw video frames to encode"),
+    input("control", "@tatolab/core/EncoderControl", optional = true, description = "Runtime bitrate / QP changes and keyframe requests"),
     output("encoded_video_out", "@tatolab/core/EncodedVideoFrame", description = "H.265 encoded video frames"),
 )]
 pub struct H265EncoderProcessor {
@@ -52,6 +61,13 @@ pub struct H265EncoderProcessor {
     /// full access for the one-shot lazy encoder-session mint.
     gpu_context: Option<GpuContextLimitedAccess>,
 
+    /// Rate control requested over the `control` input; overrides
+    /// `config.bitrate_bps` for every session minted after it arrives.
+    rate_control_override: Option<RateControl>,
+
+    /// Frame size the live session was minted for.
+    session_extent: Option<(u32, u32)>,
+
     /// Frames encoded counter.
     frames_encoded: u64,
 }
@@ -76,6 +92,10 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for H265EncoderPro
     }
 
     fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("control") {
+            let control: EncoderControl = self.inputs.read("control")?;
+            self.apply_control(&control)?;
+        }
         if !self.inputs.has_data("video_in") {
             return Ok(());
         }
@@ -87,9 +107,20 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for H265EncoderPro
             .ok_or_else(|| Error::Runtime("GPU context not initialized".into()))?
             .clone();
 
+        let extent = (frame.width, frame.height);
+        if self.session.is_some() && self.session_extent != Some(extent) {
+            tracing::info!(
+                from = ?self.session_extent,
+                to = ?extent,
+                "[H265Encoder] Frame size changed; re-minting session"
+            );
+            self.session = None;
+        }
         if self.session.is_none() {
-            let session = build_encoder_session_lazily(&gpu_ctx, &self.config, &frame)?;
+            let session =
+                build_encoder_session_lazily(&gpu_ctx, &self.config, self.rate_control(), &frame)?;
             self.session = Some(session);
+            self.session_extent = Some(extent);
         }
 
         // Resolve the incoming frame's GPU texture; `submit_texture` resolves
@@ -148,6 +179,57 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for H265EncoderPro
     }
 }
 
+impl H265EncoderProcessor::Processor {
+    /// Rate control the next session mint uses: the last `control`
+    /// override, else `config.bitrate_bps`, else the codec default.
+    fn rate_control(&self) -> Option<RateControl> {
+        self.rate_control_override
+            .or(self.config.bitrate_bps.map(RateControl::Bitrate))
+    }
+
+    fn apply_control(&mut self, control: &EncoderControl) -> Result<()> {
+        if let Some(requested) = RateControl::requested(control)
+            && self.rate_control() != Some(requested)
+        {
+            tracing::info!(
+                from = ?self.rate_control(),
+                to = ?requested,
+                "[H265Encoder] Rate control changed; re-minting session on the next frame"
+            );
+            self.rate_control_override = Some(requested);
+            // The fresh session opens with an IDR, which covers any
+            // `force_keyframe` riding along.
+            self.session = None;
+        }
+        if control.force_keyframe.unwrap_or(false)
+            && let Some(session) = self.session.as_mut()
+        {
+            session
+                .force_idr()
+                .map_err(|e| Error::Runtime(format!("H.265 force IDR failed: {e}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Session rate-control mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateControl {
+    Bitrate(u32),
+    ConstantQp(u32),
+}
+
+impl RateControl {
+    /// The mode an `EncoderControl` message asks for, if any. QP wins when
+    /// a message carries both.
+    fn requested(control: &EncoderControl) -> Option<Self> {
+        control
+            .qp
+            .map(Self::ConstantQp)
+            .or(control.bitrate_bps.map(Self::Bitrate))
+    }
+}
+
 /// Resolve the encoder's (width, height, fps) from the first frame, treating
 /// `config.width` / `config.height` / `config.fps` as guardrails. Frame wins on
 /// mismatch (mirrors `frame.fps.unwrap_or(self.config.fps)` in `mp4_writer`).
@@ -186,6 +268,7 @@ fn select_encoder_dims(
 fn build_encoder_session_lazily(
     gpu_ctx: &GpuContextLimitedAccess,
     config: &crate::_generated_::H265EncoderConfig,
+    rate_control: Option<RateControl>,
     frame: &VideoFrame,
 ) -> Result<EncoderSession> {
     let (width, height, fps) = select_encoder_dims(
@@ -207,9 +290,10 @@ fn build_encoder_session_lazily(
         .map(color_info_to_h273_repr)
         .unwrap_or_default();
 
-    let (has_bitrate, bitrate_bps) = match config.bitrate_bps {
-        Some(bps) => (1, bps),
-        None => (0, 0),
+    let (has_bitrate, bitrate_bps, has_qp, qp) = match rate_control {
+        Some(RateControl::Bitrate(bps)) => (1, bps, 0, 0),
+        Some(RateControl::ConstantQp(qp)) => (0, 0, 1, qp as i32),
+        None => (0, 0, 0, 0),
     };
     let (has_effort_level, effort_level) = match config.effort_level {
         Some(level) => (1, level),
@@ -225,6 +309,8 @@ fn build_encoder_session_lazily(
         preset: VideoEncoderPresetRepr::Medium as u32,
         bitrate_bps,
         has_bitrate,
+        qp,
+        has_qp,
         idr_interval_secs,
         effort_level,
         has_effort_level,
@@ -295,7 +381,8 @@ fn hex_encode(bytes: &[u8]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::select_encoder_dims;
+    use super::{RateControl, select_encoder_dims};
+    use crate::_generated_::EncoderControl;
 
     #[test]
     fn frame_dimensions_win_over_config() {
@@ -319,4 +406,23 @@ mod tests {
         let (w, h, _) = select_encoder_dims(None, None, None, 3840, 2160, Some(30));
         assert_eq!((w, h), (3840, 2160));
     }
+
+    #[test]
+    fn control_qp_wins_over_bitrate() {
+        let control = EncoderControl {
+            bitrate_bps: Some(4_000_000),
+            qp: Some(28),
+            force_keyframe: None,
+        };
+        assert_eq!(
+            RateControl::requested(&control),
+            Some(RateControl::ConstantQp(28))
+        );
+        let keyframe_only = EncoderControl {
+            bitrate_bps: None,
+            qp: None,
+            force_keyframe: Some(true),
+        };
+        assert_eq!(RateControl::requested(&keyframe_only), None);
+    }
 }
@@ -1,10 +1,12 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
-//! `@tatolab/jpeg` — GPU-backed JPEG decoder processor, thin wrapper
-//! around `vulkan-jpeg::SimpleJpegDecoder`.
+//! `@tatolab/jpeg` — GPU-backed JPEG decoder and encoder processors, thin
+//! wrappers around `vulkan-jpeg::SimpleJpegDecoder` / `SimpleJpegEncoder`.
+//! Both speak `EncodedJpegFrame` (one self-contained `image/jpeg` image
+//! per message), so MJPEG sources and preview thumbnails plug straight in.
 //!
-//! Linux-only today; the underlying primitive is Linux-only
+//! Linux-only today; the underlying primitives are Linux-only
 //! (VulkanComputeBackend, bound to
 //! `streamlib_plugin_sdk::sdk::context::GpuContextFullAccess`).
 
@@ -17,9 +19,12 @@ pub mod _generated_ {
 pub mod linux;
 
 #[cfg(target_os = "linux")]
-pub use linux::JpegDecoderProcessor;
+pub use linux::{JpegDecoderProcessor, JpegEncoderProcessor};
 
-pub use _generated_::{EncodedJpegFrame, JpegDecoderConfig};
+pub use _generated_::{EncodedJpegFrame, JpegDecoderConfig, JpegEncoderConfig};
 
 #[cfg(target_os = "linux")]
-streamlib_plugin_abi::export_plugin!(crate::JpegDecoderProcessor::Processor);
+streamlib_plugin_abi::export_plugin!(
+    crate::JpegDecoderProcessor::Processor,
+    crate::JpegEncoderProcessor::Processor,
+);
@@ -125,6 +125,7 @@ impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for JpegDecoderPro
             color_info: Some(color_info),
             mastering_display: None,
             content_light: None,
+            field_order: None,
         };
 
         self.outputs.write("video_out", &video_frame)?;
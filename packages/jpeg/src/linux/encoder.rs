@@ -0,0 +1,211 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// JPEG Encoder Processor
+//
+// Thin wrapper around vulkan_jpeg::SimpleJpegEncoder — the mirror of the
+// decoder processor. The primitive runs color conversion, 4:2:0
+// downsample, forward DCT and quantization on the GPU and Huffman-codes
+// on the host; this processor resolves the input surface, makes sure it
+// is sampleable, and wraps the bytes in an EncodedJpegFrame.
+//
+// Construction runs once at setup() with the privileged FullAccess
+// handle (same reasoning as the decoder — no escalate from setup).
+// Per-frame work is Limited-safe.
+
+use crate::_generated_::{EncodedJpegFrame, VideoFrame};
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{RhiCommandRecorder, VulkanAccess, VulkanLayout, VulkanStage};
+
+use vulkan_jpeg::SimpleJpegEncoder;
+
+/// Default quality when `JpegEncoderConfig::quality` is unset. Visually
+/// clean for previews and MJPEG without the size blow-up above 90.
+const DEFAULT_QUALITY: u32 = 85;
+/// Default max width when `JpegEncoderConfig::max_width` is unset.
+const DEFAULT_MAX_WIDTH: u32 = 3840;
+/// Default max height when `JpegEncoderConfig::max_height` is unset.
+const DEFAULT_MAX_HEIGHT: u32 = 2160;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/jpeg/JpegEncoder",
+    description = "Encodes VideoFrame to EncodedJpegFrame (baseline 4:2:0 JFIF) via the GPU SimpleJpegEncoder primitive",
+    execution = reactive,
+    scheduling = high,
+    config = crate::_generated_::JpegEncoderConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to encode"),
+    output("encoded_jpeg_out", "@tatolab/jpeg/EncodedJpegFrame", description = "JPEG-encoded frames"),
+)]
+pub struct JpegEncoderProcessor {
+    /// Underlying GPU JPEG encoder primitive.
+    encoder: Option<SimpleJpegEncoder>,
+
+    /// Resolves input surfaces per frame.
+    gpu_context: Option<GpuContextLimitedAccess>,
+
+    /// Records the layout transition for inputs not yet in
+    /// `SHADER_READ_ONLY_OPTIMAL`.
+    recorder: Option<RhiCommandRecorder>,
+
+    /// Reused output buffer; moved into each EncodedJpegFrame.
+    scratch: Vec<u8>,
+
+    /// Frames encoded counter — numbers output frames and drives
+    /// periodic progress logs.
+    frames_encoded: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for JpegEncoderProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let max_width = self.config.max_width.unwrap_or(DEFAULT_MAX_WIDTH);
+        let max_height = self.config.max_height.unwrap_or(DEFAULT_MAX_HEIGHT);
+        let quality = quality_from_config(self.config.quality);
+
+        // See JpegDecoderProcessor::setup — setup() already runs
+        // privileged, so use the FullAccess handle directly.
+        let full = ctx.gpu_full_access();
+        let encoder = SimpleJpegEncoder::new(full, max_width, max_height, quality)?;
+        self.recorder = Some(full.create_command_recorder("jpeg_encoder")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+
+        tracing::info!(
+            quality = quality,
+            max_width = max_width,
+            max_height = max_height,
+            "[JpegEncoder] Initialized (GPU SimpleJpegEncoder)"
+        );
+
+        self.encoder = Some(encoder);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            frames_encoded = self.frames_encoded,
+            "[JpegEncoder] Shutting down"
+        );
+        self.recorder.take();
+        self.encoder.take();
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let quality = quality_from_config(self.config.quality);
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.set_quality(quality);
+        }
+        tracing::info!(quality = quality, "[JpegEncoder] Config updated");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+
+        let (Some(encoder), Some(gpu), Some(recorder)) = (
+            self.encoder.as_mut(),
+            self.gpu_context.as_ref(),
+            self.recorder.as_mut(),
+        ) else {
+            return Err(Error::Runtime("JPEG encoder not initialized".into()));
+        };
+
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.begin()?;
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+            recorder.submit_and_wait()?;
+            registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+        }
+
+        encoder
+            .encode(&texture, frame.width, frame.height, &mut self.scratch)
+            .map_err(wrap_encode_error)?;
+
+        let encoded = EncodedJpegFrame {
+            data: std::mem::take(&mut self.scratch),
+            timestamp_ns: frame.timestamp_ns.clone(),
+            frame_number: self.frames_encoded.to_string(),
+            fps: frame.fps,
+        };
+        let encoded_bytes = encoded.data.len();
+        self.outputs.write("encoded_jpeg_out", &encoded)?;
+        // Keep the allocation for the next frame.
+        self.scratch = encoded.data;
+        self.frames_encoded += 1;
+
+        if self.frames_encoded == 1 {
+            tracing::info!(
+                width = frame.width,
+                height = frame.height,
+                bytes = encoded_bytes,
+                "[JpegEncoder] First frame encoded"
+            );
+        } else if self.frames_encoded % 300 == 0 {
+            tracing::info!(
+                frames = self.frames_encoded,
+                "[JpegEncoder] Encode progress"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Config quality → encoder quality: default when unset, clamped to the
+/// IJG 1..=100 range.
+fn quality_from_config(quality: Option<u32>) -> u8 {
+    quality.unwrap_or(DEFAULT_QUALITY).clamp(1, 100) as u8
+}
+
+/// Wrap a SimpleJpegEncoder error into the typed `Error::Runtime` variant
+/// the processor surfaces from `process()`, matching the decoder.
+fn wrap_encode_error(inner: Error) -> Error {
+    Error::Runtime(format!("JPEG encode failed: {inner}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quality_defaults_and_clamps() {
+        assert_eq!(quality_from_config(None), DEFAULT_QUALITY as u8);
+        assert_eq!(quality_from_config(Some(0)), 1);
+        assert_eq!(quality_from_config(Some(70)), 70);
+        assert_eq!(quality_from_config(Some(1000)), 100);
+    }
+
+    #[test]
+    fn wrap_encode_error_produces_runtime_variant() {
+        let mapped = wrap_encode_error(Error::GpuError("frame 8000x8000 outside maxima".into()));
+        match mapped {
+            Error::Runtime(msg) => {
+                assert!(msg.contains("JPEG encode failed"), "got: {msg}");
+                assert!(msg.contains("outside maxima"), "got: {msg}");
+            }
+            other => panic!("expected Error::Runtime, got {other:?}"),
+        }
+    }
+}
@@ -3,5 +3,7 @@
 
 pub mod color_resolved_to_core;
 pub mod decoder;
+pub mod encoder;
 
 pub use decoder::JpegDecoderProcessor;
+pub use encoder::JpegEncoderProcessor;
@@ -0,0 +1,232 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Latency detect (Linux) — reads the [`crate::timecode`] marker back out
+//! of incoming frames and reports the end-to-end latency distribution.
+//!
+//! A compute kernel samples the frame and averages each marker cell's
+//! luma into a small host-visible buffer (80 floats), so the full frame
+//! never comes back to the CPU. Decoding, the latency sample and the
+//! statistics happen on the CPU. Every `report_interval_ms` the window's
+//! distribution is logged and published on [`LATENCY_REPORT_TOPIC`].
+
+use std::time::{Duration, Instant};
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder, StorageBuffer, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::latency_stamp::{DEFAULT_CELL_SIZE_PX, DEFAULT_ORIGIN_PX};
+use crate::timecode::{self, CELL_COUNT, LatencyWindow};
+
+/// Custom-event topic carrying `{processor_id, report}` once per window.
+pub const LATENCY_REPORT_TOPIC: &str = "latency_probe:report";
+
+const DEFAULT_REPORT_INTERVAL_MS: u32 = 1000;
+
+const LATENCY_SAMPLE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/latency_sample.spv"));
+
+const LATENCY_SAMPLE_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_buffer(1),
+];
+
+/// Matches the shader's `local_size_x`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Push constants of `latency_sample.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SamplePushConstants {
+    origin_x: u32,
+    origin_y: u32,
+    cell_size: u32,
+    cell_count: u32,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/latency-probe/LatencyDetect",
+    description = "Reads the latency marker from incoming frames on the GPU and reports the end-to-end latency distribution (min, mean, p50, p95, p99, max) per window on the latency_probe:report topic. A sink: attach it at the end of the path, or behind a capture card looping the output back in.",
+    execution = reactive,
+    config = crate::_generated_::LatencyDetectConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames carrying the marker"),
+)]
+pub struct LatencyDetectProcessor {
+    processor_id: Option<String>,
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-visible per-cell luma written by the kernel.
+    luma: Option<StorageBuffer>,
+    window: LatencyWindow,
+    window_started: Option<Instant>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for LatencyDetectProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "latency_sample",
+            spv: LATENCY_SAMPLE_SPV,
+            bindings: LATENCY_SAMPLE_BINDINGS,
+            push_constant_size: std::mem::size_of::<SamplePushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("latency_sample")?);
+        let luma = full.acquire_storage_buffer(std::mem::size_of::<[f32; CELL_COUNT]>() as u64)?;
+        if luma.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "LatencyDetect: luma buffer is not host-mapped".into(),
+            ));
+        }
+        self.luma = Some(luma);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.report();
+        self.recorder = None;
+        self.kernel = None;
+        self.luma = None;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        // Sample the clock before the GPU readout so its cost isn't counted.
+        let now_us = timecode::now_micros();
+        let stamp_us = match self.read_marker(&frame) {
+            Ok(luma) => timecode::decode(&luma),
+            Err(e) => {
+                tracing::debug!("LatencyDetect: marker readout failed: {}", e);
+                None
+            }
+        };
+        self.window.record(stamp_us, now_us);
+
+        let interval = Duration::from_millis(
+            self.config
+                .report_interval_ms
+                .unwrap_or(DEFAULT_REPORT_INTERVAL_MS)
+                .into(),
+        );
+        let started = *self.window_started.get_or_insert_with(Instant::now);
+        if started.elapsed() >= interval {
+            self.report();
+            self.window_started = Some(Instant::now());
+        }
+        Ok(())
+    }
+}
+
+impl LatencyDetectProcessor::Processor {
+    /// Average each marker cell's luma on the GPU and copy the result out.
+    fn read_marker(&mut self, frame: &VideoFrame) -> Result<[f32; CELL_COUNT]> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("LatencyDetect: GPU context not initialized".into())
+        })?;
+        let (Some(kernel), Some(recorder), Some(luma)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.luma.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "LatencyDetect: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_buffer_storage(1, luma)?;
+        kernel.set_push_constants_value(&SamplePushConstants {
+            origin_x: self.config.origin_x.unwrap_or(DEFAULT_ORIGIN_PX),
+            origin_y: self.config.origin_y.unwrap_or(DEFAULT_ORIGIN_PX),
+            cell_size: self
+                .config
+                .cell_size_px
+                .unwrap_or(DEFAULT_CELL_SIZE_PX)
+                .max(1),
+            cell_count: CELL_COUNT as u32,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_dispatch(kernel, (CELL_COUNT as u32).div_ceil(WORKGROUP_SIZE), 1, 1)?;
+        recorder.record_buffer_barrier(
+            luma,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::HOST,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::HOST_READ,
+        )?;
+        recorder.submit_and_wait()?;
+        registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let mut cells = [0f32; CELL_COUNT];
+        // SAFETY: `luma` is a persistently-mapped host-visible allocation of
+        // exactly `CELL_COUNT` f32s (checked non-null in setup), and the
+        // submit above has completed, so the kernel's writes are visible.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                luma.mapped_ptr() as *const f32,
+                cells.as_mut_ptr(),
+                CELL_COUNT,
+            );
+        }
+        Ok(cells)
+    }
+
+    /// Log and publish the current window, if it saw any frames.
+    fn report(&mut self) {
+        let Some(report) = self.window.take_report() else {
+            return;
+        };
+        tracing::info!(
+            "LatencyDetect: {} samples ({} missing) — min {:.1} ms, p50 {:.1} ms, p95 {:.1} ms, p99 {:.1} ms, max {:.1} ms",
+            report.samples,
+            report.missing,
+            report.min_ms,
+            report.p50_ms,
+            report.p95_ms,
+            report.p99_ms,
+            report.max_ms,
+        );
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "report": report,
+        });
+        if let Err(e) = publish_custom_event(LATENCY_REPORT_TOPIC, &payload) {
+            tracing::debug!("LatencyDetect: report not published: {}", e);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Latency stamp (Linux) — paints the [`crate::timecode`] marker for the
+//! current wall-clock time into each frame and forwards it.
+//!
+//! The marker is written in place with a compute kernel bound to the
+//! frame's texture as a storage image, so there is no extra copy and
+//! every consumer of `video_out` sees the same stamped frame. That also
+//! means a fan-out sibling of the *input* sees the stamp: put the stamper
+//! on its own branch if that matters. Only `Rgba8Unorm` frames (the
+//! camera's output format) can be written as storage images; other
+//! formats pass through unstamped.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder, TextureFormat, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::timecode::{self, GRID_COLUMNS, GRID_ROWS, MarkerBits};
+
+const LATENCY_STAMP_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/latency_stamp.spv"));
+
+const LATENCY_STAMP_BINDINGS: &[ComputeBindingSpec] = &[ComputeBindingSpec::storage_image(0)];
+
+/// Matches the shader's 8x8 workgroup.
+const WORKGROUP_SIZE: u32 = 8;
+
+pub(crate) const DEFAULT_ORIGIN_PX: u32 = 16;
+pub(crate) const DEFAULT_CELL_SIZE_PX: u32 = 12;
+
+/// Push constants of `latency_stamp.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StampPushConstants {
+    origin_x: u32,
+    origin_y: u32,
+    cell_size: u32,
+    bits: MarkerBits,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/latency-probe/LatencyStamp",
+    description = "Paints a machine-readable wall-clock timestamp marker into each frame on the GPU. Place it right after the source whose latency you're measuring.",
+    execution = reactive,
+    config = crate::_generated_::LatencyStampConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to stamp (RGBA8)"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames, marker painted in place"),
+)]
+pub struct LatencyStampProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Set once the unsupported-format warning has been logged.
+    warned_format: bool,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for LatencyStampProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "latency_stamp",
+            spv: LATENCY_STAMP_SPV,
+            bindings: LATENCY_STAMP_BINDINGS,
+            push_constant_size: std::mem::size_of::<StampPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("latency_stamp")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        // Sample the clock before any GPU work so the stamp marks arrival.
+        let stamp_us = timecode::now_micros();
+        if let Err(e) = self.stamp(&frame, stamp_us) {
+            tracing::warn!("LatencyStamp: frame forwarded unstamped: {}", e);
+        }
+        self.outputs.write("video_out", &frame)
+    }
+}
+
+impl LatencyStampProcessor::Processor {
+    fn stamp(&mut self, frame: &VideoFrame, stamp_us: u64) -> Result<()> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("LatencyStamp: GPU context not initialized".into())
+        })?;
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        if texture.format() != TextureFormat::Rgba8Unorm {
+            if !self.warned_format {
+                self.warned_format = true;
+                tracing::warn!(
+                    "LatencyStamp: {:?} frames can't be stamped (needs Rgba8Unorm); forwarding unstamped",
+                    texture.format()
+                );
+            }
+            return Ok(());
+        }
+
+        let cell_size = self
+            .config
+            .cell_size_px
+            .unwrap_or(DEFAULT_CELL_SIZE_PX)
+            .max(1);
+        let push = StampPushConstants {
+            origin_x: self.config.origin_x.unwrap_or(DEFAULT_ORIGIN_PX),
+            origin_y: self.config.origin_y.unwrap_or(DEFAULT_ORIGIN_PX),
+            cell_size,
+            bits: timecode::encode(stamp_us),
+        };
+        let (Some(kernel), Some(recorder)) = (self.kernel.as_ref(), self.recorder.as_mut()) else {
+            return Err(Error::Configuration(
+                "LatencyStamp: kernel not initialized".into(),
+            ));
+        };
+        kernel.set_storage_image(0, &texture)?;
+        kernel.set_push_constants_value(&push)?;
+
+        recorder.begin()?;
+        recorder.record_image_barrier(
+            &texture,
+            registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_WRITE,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            (GRID_COLUMNS * cell_size).div_ceil(WORKGROUP_SIZE),
+            (GRID_ROWS * cell_size).div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&registration, &[])?;
+        Ok(())
+    }
+}
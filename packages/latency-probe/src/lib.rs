@@ -0,0 +1,33 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/latency-probe` — glass-to-glass latency measurement with an
+//! in-band marker. `LatencyStamp` paints a wall-clock timestamp marker
+//! into frames near the source; `LatencyDetect` reads it back at the end
+//! of the path (or from a capture card looping the output back in) and
+//! reports the latency distribution.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod timecode;
+
+// Painting and reading back the marker both need `sdk::rhi`, which is
+// Linux-only; `timecode` encoding stays portable.
+#[cfg(target_os = "linux")]
+pub mod latency_detect;
+#[cfg(target_os = "linux")]
+pub mod latency_stamp;
+
+#[cfg(target_os = "linux")]
+pub use latency_detect::{LATENCY_REPORT_TOPIC, LatencyDetectProcessor};
+#[cfg(target_os = "linux")]
+pub use latency_stamp::LatencyStampProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::LatencyStampProcessor::Processor,
+    crate::LatencyDetectProcessor::Processor,
+);
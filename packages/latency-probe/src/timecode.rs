@@ -0,0 +1,232 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The in-band timestamp marker and the latency statistics built from it.
+//!
+//! The marker is a [`GRID_COLUMNS`] x [`GRID_ROWS`] grid of black/white
+//! cells, read row-major:
+//!
+//! | cells   | content                                      |
+//! |---------|----------------------------------------------|
+//! | 0, 1    | white, black — per-frame threshold reference |
+//! | 2..66   | wall-clock microseconds since the Unix epoch |
+//! | 66..74  | CRC-8 of the timestamp's big-endian bytes    |
+//! | 74..80  | `101010` sync tail                           |
+//!
+//! Big cells and a per-frame threshold keep the marker readable after
+//! scaling, chroma subsampling and lossy compression, so it survives a
+//! trip through an encoder or an HDMI capture loop. The stamp uses wall
+//! time, so stamper and detector on different hosts need synced clocks.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const GRID_COLUMNS: u32 = 16;
+pub const GRID_ROWS: u32 = 5;
+pub const CELL_COUNT: usize = (GRID_COLUMNS * GRID_ROWS) as usize;
+
+/// Packed cell bits as the stamp shader reads them: cell `i` is bit
+/// `i % 32` of word `i / 32`.
+pub type MarkerBits = [u32; 3];
+
+const TIMESTAMP_FIRST_CELL: usize = 2;
+const CRC_FIRST_CELL: usize = TIMESTAMP_FIRST_CELL + 64;
+const SYNC_FIRST_CELL: usize = CRC_FIRST_CELL + 8;
+const SYNC_TAIL: [bool; 6] = [true, false, true, false, true, false];
+
+/// Minimum white-minus-black luma gap of the reference cells. Below this
+/// the frame has no marker (or it was washed out) and decoding is refused.
+const MIN_REFERENCE_CONTRAST: f32 = 0.25;
+
+/// Current wall-clock time in microseconds since the Unix epoch.
+pub fn now_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros() as u64)
+}
+
+/// Lay out the cells for `timestamp_us`.
+pub fn encode(timestamp_us: u64) -> MarkerBits {
+    let mut cells = [false; CELL_COUNT];
+    cells[0] = true;
+    for bit in 0..64 {
+        cells[TIMESTAMP_FIRST_CELL + bit] = (timestamp_us >> (63 - bit)) & 1 == 1;
+    }
+    let crc = crc8(&timestamp_us.to_be_bytes());
+    for bit in 0..8 {
+        cells[CRC_FIRST_CELL + bit] = (crc >> (7 - bit)) & 1 == 1;
+    }
+    cells[SYNC_FIRST_CELL..].copy_from_slice(&SYNC_TAIL);
+
+    let mut bits = MarkerBits::default();
+    for (index, _) in cells.iter().enumerate().filter(|(_, white)| **white) {
+        bits[index / 32] |= 1 << (index % 32);
+    }
+    bits
+}
+
+/// Recover the timestamp from per-cell luma (0.0..=1.0, row-major).
+/// `None` when the frame carries no intact marker.
+pub fn decode(luma: &[f32; CELL_COUNT]) -> Option<u64> {
+    let (white, black) = (luma[0], luma[1]);
+    if white - black < MIN_REFERENCE_CONTRAST {
+        return None;
+    }
+    let threshold = (white + black) / 2.0;
+    let cell = |index: usize| luma[index] > threshold;
+
+    if (0..SYNC_TAIL.len()).any(|i| cell(SYNC_FIRST_CELL + i) != SYNC_TAIL[i]) {
+        return None;
+    }
+    let timestamp_us = (0..64).fold(0u64, |acc, bit| {
+        (acc << 1) | u64::from(cell(TIMESTAMP_FIRST_CELL + bit))
+    });
+    let crc = (0..8).fold(0u8, |acc, bit| {
+        (acc << 1) | u8::from(cell(CRC_FIRST_CELL + bit))
+    });
+    (crc == crc8(&timestamp_us.to_be_bytes())).then_some(timestamp_us)
+}
+
+/// CRC-8 (polynomial 0x07, init 0).
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// One reporting window's latency distribution, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct LatencyReport {
+    /// Frames whose marker decoded.
+    pub samples: usize,
+    /// Frames with no readable marker, or one stamped in the future
+    /// (unsynced clocks).
+    pub missing: u64,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Latency samples collected over one reporting window.
+#[derive(Debug, Default)]
+pub struct LatencyWindow {
+    samples_us: Vec<u64>,
+    missing: u64,
+}
+
+impl LatencyWindow {
+    /// Record a frame that reached the detector at `now_us`, carrying the
+    /// decoded stamp (if any).
+    pub fn record(&mut self, stamp_us: Option<u64>, now_us: u64) {
+        match stamp_us.and_then(|stamp| now_us.checked_sub(stamp)) {
+            Some(latency_us) => self.samples_us.push(latency_us),
+            None => self.missing += 1,
+        }
+    }
+
+    /// Summarize and reset the window. `None` when no frames arrived.
+    pub fn take_report(&mut self) -> Option<LatencyReport> {
+        let mut samples = std::mem::take(&mut self.samples_us);
+        let missing = std::mem::take(&mut self.missing);
+        if samples.is_empty() && missing == 0 {
+            return None;
+        }
+        samples.sort_unstable();
+        let ms = |us: u64| us as f64 / 1000.0;
+        // Nearest-rank percentile.
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * samples.len() as f64).ceil() as usize;
+            ms(samples[rank.clamp(1, samples.len()) - 1])
+        };
+        if samples.is_empty() {
+            return Some(LatencyReport {
+                missing,
+                ..LatencyReport::default()
+            });
+        }
+        Some(LatencyReport {
+            samples: samples.len(),
+            missing,
+            min_ms: ms(samples[0]),
+            mean_ms: ms(samples.iter().sum::<u64>() / samples.len() as u64),
+            p50_ms: percentile(50.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+            max_ms: ms(samples[samples.len() - 1]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render `bits` the way the stamp shader does, with the given luma
+    /// for white and black cells.
+    fn render(bits: MarkerBits, white: f32, black: f32) -> [f32; CELL_COUNT] {
+        std::array::from_fn(|index| {
+            if bits[index / 32] & (1 << (index % 32)) != 0 {
+                white
+            } else {
+                black
+            }
+        })
+    }
+
+    #[test]
+    fn round_trips_through_a_washed_out_capture() {
+        let stamp = 1_760_000_000_123_456;
+
+        // Limited-range video after a capture loop: white ~0.92, black ~0.06.
+        let luma = render(encode(stamp), 0.92, 0.06);
+
+        assert_eq!(decode(&luma), Some(stamp));
+    }
+
+    #[test]
+    fn rejects_frames_without_a_marker() {
+        assert_eq!(decode(&[0.5; CELL_COUNT]), None);
+        assert_eq!(decode(&[0.0; CELL_COUNT]), None);
+    }
+
+    #[test]
+    fn a_flipped_timestamp_cell_fails_the_crc() {
+        let mut luma = render(encode(42_000_000), 1.0, 0.0);
+        let flipped = TIMESTAMP_FIRST_CELL + 40;
+        luma[flipped] = 1.0 - luma[flipped];
+
+        assert_eq!(decode(&luma), None);
+    }
+
+    #[test]
+    fn reports_percentiles_and_resets() {
+        let mut window = LatencyWindow::default();
+        let now = 10_000_000;
+        for latency_ms in 1..=100u64 {
+            window.record(Some(now - latency_ms * 1000), now);
+        }
+        window.record(None, now);
+        window.record(Some(now + 5_000), now);
+
+        let report = window.take_report().unwrap();
+
+        assert_eq!(report.samples, 100);
+        assert_eq!(report.missing, 2);
+        assert_eq!(report.min_ms, 1.0);
+        assert_eq!(report.p50_ms, 50.0);
+        assert_eq!(report.p95_ms, 95.0);
+        assert_eq!(report.p99_ms, 99.0);
+        assert_eq!(report.max_ms, 100.0);
+        assert_eq!(window.take_report(), None);
+    }
+}
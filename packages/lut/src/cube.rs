@@ -0,0 +1,311 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `.cube` 3D LUT files (Adobe Cube LUT Specification 1.0).
+//!
+//! A file is keyword lines — `TITLE`, `LUT_3D_SIZE`, `DOMAIN_MIN`,
+//! `DOMAIN_MAX`, and Resolve's `LUT_3D_INPUT_RANGE` — followed by
+//! `size³` lines of output RGB, red varying fastest, then green, then
+//! blue. `#` starts a comment. Unknown keywords are skipped so vendor
+//! extensions still load; 1D LUTs are rejected.
+
+use std::path::Path;
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// Lattice sizes the specification allows.
+pub const MIN_LUT_SIZE: u32 = 2;
+pub const MAX_LUT_SIZE: u32 = 256;
+
+/// A parsed 3D LUT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeLut {
+    pub title: Option<String>,
+    /// Lattice points per axis.
+    pub size: u32,
+    /// Input values that map to the first and last lattice points.
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    /// `size³` output colors, red fastest, then green, then blue.
+    pub table: Vec<[f32; 3]>,
+}
+
+impl CubeLut {
+    /// Read and parse the `.cube` file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::Configuration(format!("Lut: cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&text)
+            .map_err(|e| Error::Configuration(format!("Lut: {}: {}", path.display(), e)))
+    }
+
+    /// Parse `.cube` text. Errors name the offending line.
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let mut title = None;
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = raw.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let keyword = line.split_whitespace().next().unwrap_or_default();
+            if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                if !table.is_empty() {
+                    return Err(format!("line {}: keyword after table data", number));
+                }
+                let rest = line[keyword.len()..].trim();
+                match keyword {
+                    "TITLE" => title = Some(rest.trim_matches('"').to_string()),
+                    "LUT_3D_SIZE" => {
+                        let value: u32 = rest
+                            .parse()
+                            .map_err(|_| format!("line {}: bad LUT_3D_SIZE '{}'", number, rest))?;
+                        if !(MIN_LUT_SIZE..=MAX_LUT_SIZE).contains(&value) {
+                            return Err(format!(
+                                "line {}: LUT_3D_SIZE {} is outside {}..={}",
+                                number, value, MIN_LUT_SIZE, MAX_LUT_SIZE
+                            ));
+                        }
+                        size = Some(value);
+                    }
+                    "LUT_1D_SIZE" => {
+                        return Err(format!(
+                            "line {}: 1D LUTs are not supported; Lut applies 3D LUTs",
+                            number
+                        ));
+                    }
+                    "DOMAIN_MIN" => domain_min = parse_triple(rest, number)?,
+                    "DOMAIN_MAX" => domain_max = parse_triple(rest, number)?,
+                    "LUT_3D_INPUT_RANGE" => {
+                        let [min, max] = parse_values::<2>(rest, number)?;
+                        domain_min = [min; 3];
+                        domain_max = [max; 3];
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            table.push(parse_triple(line, number)?);
+        }
+
+        let size = size.ok_or("missing LUT_3D_SIZE")?;
+        let expected = (size as usize).pow(3);
+        if table.len() != expected {
+            return Err(format!(
+                "LUT_3D_SIZE {} needs {} entries, found {}",
+                size,
+                expected,
+                table.len()
+            ));
+        }
+        if (0..3).any(|c| domain_max[c] <= domain_min[c]) {
+            return Err(format!(
+                "DOMAIN_MAX {:?} must exceed DOMAIN_MIN {:?} on every channel",
+                domain_max, domain_min
+            ));
+        }
+        Ok(Self {
+            title,
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    /// The table padded to `vec4`s, the layout `lut.comp` reads.
+    pub fn lattice(&self) -> Vec<[f32; 4]> {
+        self.table.iter().map(|&[r, g, b]| [r, g, b, 1.0]).collect()
+    }
+
+    /// Look up `rgb` with trilinear interpolation — the CPU mirror of
+    /// `lut.comp`.
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let mut base = [0usize; 3];
+        let mut frac = [0.0f32; 3];
+        for c in 0..3 {
+            let t = ((rgb[c] - self.domain_min[c]) / (self.domain_max[c] - self.domain_min[c]))
+                .clamp(0.0, 1.0)
+                * last;
+            let floor = t.floor().min(last - 1.0);
+            base[c] = floor as usize;
+            frac[c] = t - floor;
+        }
+        let n = self.size as usize;
+        let at = |r: usize, g: usize, b: usize| self.table[r + g * n + b * n * n];
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| {
+            [
+                a[0] + (b[0] - a[0]) * t,
+                a[1] + (b[1] - a[1]) * t,
+                a[2] + (b[2] - a[2]) * t,
+            ]
+        };
+        let [r, g, b] = base;
+        let plane = |b: usize| {
+            let low = lerp(at(r, g, b), at(r + 1, g, b), frac[0]);
+            let high = lerp(at(r, g + 1, b), at(r + 1, g + 1, b), frac[0]);
+            lerp(low, high, frac[1])
+        };
+        lerp(plane(b), plane(b + 1), frac[2])
+    }
+}
+
+fn parse_values<const N: usize>(
+    text: &str,
+    number: usize,
+) -> std::result::Result<[f32; N], String> {
+    let mut values = [0.0; N];
+    let mut fields = text.split_whitespace();
+    for value in &mut values {
+        let field = fields
+            .next()
+            .ok_or_else(|| format!("line {}: expected {} numbers", number, N))?;
+        *value = field
+            .parse()
+            .map_err(|_| format!("line {}: bad number '{}'", number, field))?;
+    }
+    if fields.next().is_some() {
+        return Err(format!("line {}: expected {} numbers", number, N));
+    }
+    Ok(values)
+}
+
+fn parse_triple(text: &str, number: usize) -> std::result::Result<[f32; 3], String> {
+    parse_values::<3>(text, number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An identity lattice of `size` points per axis, in file order.
+    fn identity_cube(size: u32, header: &str) -> String {
+        let mut text = format!("{}LUT_3D_SIZE {}\n", header, size);
+        let last = (size - 1) as f32;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    text += &format!(
+                        "{} {} {}\n",
+                        r as f32 / last,
+                        g as f32 / last,
+                        b as f32 / last
+                    );
+                }
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn parses_header_and_table_in_file_order() {
+        let lut = CubeLut::parse(&identity_cube(2, "# graded\nTITLE \"Warm\"\n\n")).unwrap();
+
+        assert_eq!(lut.title.as_deref(), Some("Warm"));
+        assert_eq!(lut.size, 2);
+        assert_eq!((lut.domain_min, lut.domain_max), ([0.0; 3], [1.0; 3]));
+        assert_eq!(lut.table.len(), 8);
+        // Red varies fastest.
+        assert_eq!(lut.table[1], [1.0, 0.0, 0.0]);
+        assert_eq!(lut.table[2], [0.0, 1.0, 0.0]);
+        assert_eq!(lut.table[4], [0.0, 0.0, 1.0]);
+        assert_eq!(lut.lattice()[7], [1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn identity_lut_samples_back_its_input() {
+        let lut = CubeLut::parse(&identity_cube(17, "")).unwrap();
+
+        for rgb in [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.2, 0.55, 0.9],
+            [0.03, 0.97, 0.5],
+        ] {
+            let out = lut.sample(rgb);
+            for c in 0..3 {
+                assert!((out[c] - rgb[c]).abs() < 1e-5, "{:?} -> {:?}", rgb, out);
+            }
+        }
+    }
+
+    #[test]
+    fn sampling_interpolates_between_lattice_points() {
+        // Inverts red, leaves green and blue.
+        let mut text = String::from("LUT_3D_SIZE 2\n");
+        for b in 0..2 {
+            for g in 0..2 {
+                for r in 0..2 {
+                    text += &format!("{} {} {}\n", 1 - r, g, b);
+                }
+            }
+        }
+        let lut = CubeLut::parse(&text).unwrap();
+
+        let out = lut.sample([0.25, 0.5, 0.75]);
+        assert!((out[0] - 0.75).abs() < 1e-6);
+        assert!((out[1] - 0.5).abs() < 1e-6);
+        assert!((out[2] - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn domain_maps_input_onto_the_lattice_and_clamps() {
+        let lut =
+            CubeLut::parse(&identity_cube(2, "DOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n")).unwrap();
+
+        assert_eq!(lut.sample([1.0, 0.5, 2.0]), [0.5, 0.25, 1.0]);
+        assert_eq!(lut.sample([-1.0, 3.0, 0.0]), [0.0, 1.0, 0.0]);
+
+        let resolve = CubeLut::parse(&identity_cube(2, "LUT_3D_INPUT_RANGE 0 4\n")).unwrap();
+        assert_eq!(resolve.domain_max, [4.0; 3]);
+    }
+
+    #[test]
+    fn unknown_keywords_are_skipped() {
+        let lut = CubeLut::parse(&identity_cube(2, "LUT_IN_VIDEO_RANGE\n")).unwrap();
+
+        assert_eq!(lut.size, 2);
+    }
+
+    #[test]
+    fn malformed_files_are_rejected_with_the_line() {
+        let cases = [
+            ("0 0 0\n", "missing LUT_3D_SIZE"),
+            ("LUT_3D_SIZE 2\n0 0 0\n", "needs 8 entries, found 1"),
+            ("LUT_3D_SIZE 1\n", "outside 2..=256"),
+            ("LUT_3D_SIZE 300\n", "outside 2..=256"),
+            ("LUT_1D_SIZE 1024\n", "1D LUTs are not supported"),
+            ("LUT_3D_SIZE 2\n0 0 x\n", "line 2: bad number 'x'"),
+            ("LUT_3D_SIZE 2\n0 0\n", "line 2: expected 3 numbers"),
+            (
+                "LUT_3D_SIZE 2\n0 0 0\nDOMAIN_MIN 0 0 0\n",
+                "line 3: keyword after table data",
+            ),
+        ];
+        for (text, expected) in cases {
+            let error = CubeLut::parse(text).unwrap_err();
+            assert!(error.contains(expected), "{:?}: {}", text, error);
+        }
+
+        let inverted = identity_cube(2, "DOMAIN_MIN 1 0 0\nDOMAIN_MAX 0.5 1 1\n");
+        assert!(
+            CubeLut::parse(&inverted)
+                .unwrap_err()
+                .contains("must exceed")
+        );
+    }
+
+    #[test]
+    fn load_names_the_file() {
+        let error = CubeLut::load(Path::new("/nonexistent/grade.cube")).unwrap_err();
+
+        assert!(error.to_string().contains("/nonexistent/grade.cube"));
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/lut` — 3D LUT color grading. `Lut` grades frames on the GPU
+//! through a `.cube` LUT, swappable at runtime and blended with the
+//! source by an intensity.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod cube;
+
+// The 3D-texture lookup runs on `sdk::rhi`, which is Linux-only; the
+// `.cube` parser stays portable.
+#[cfg(target_os = "linux")]
+pub mod lut;
+
+#[cfg(target_os = "linux")]
+pub use lut::LutProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::LutProcessor::Processor);
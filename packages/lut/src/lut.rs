@@ -0,0 +1,410 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Lut (Linux) — grades frames on the GPU through a `.cube` 3D LUT.
+//!
+//! The plugin RHI has no 3D textures, so the parsed lattice (see
+//! [`crate::cube`]) is copied into a host-mapped storage buffer and
+//! `lut.comp` interpolates it trilinearly itself, then mixes the result
+//! with the source by `intensity`. One dispatch per frame writes the next
+//! slot of an RGBA8 output ring, reallocated when the input size changes.
+//!
+//! A new LUT — from a config update, or from `watch` noticing the file
+//! changed — is parsed on the spot and copied into the buffer before the
+//! next dispatch, so frames switch grades cleanly between one and the next.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel,
+    VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::{LutConfig, VideoFrame};
+use crate::cube::CubeLut;
+
+const LUT_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/lut.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+/// Matches `lut.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// How often `watch` checks the LUT file's modification time.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Push constants of `lut.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct LutPushConstants {
+    width: u32,
+    height: u32,
+    size: u32,
+    intensity: f32,
+    domain_min: [f32; 4],
+    domain_max: [f32; 4],
+}
+
+/// What the kernel needs of the LUT in the lattice buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Lattice {
+    size: u32,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+}
+
+/// `intensity` from the config, checked.
+fn resolve_intensity(config: &LutConfig) -> Result<f32> {
+    let intensity = config.intensity.unwrap_or(1.0);
+    if !(0.0..=1.0).contains(&intensity) {
+        return Err(Error::Configuration(format!(
+            "Lut: intensity must be within 0..=1, got {}",
+            intensity
+        )));
+    }
+    Ok(intensity)
+}
+
+fn is_url(lut: &str) -> bool {
+    lut.starts_with("http://") || lut.starts_with("https://")
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Copy `lut` into the lattice buffer, growing it first if it is too
+/// small, and return what the kernel needs to read it.
+fn upload_lattice(
+    gpu: &GpuContextLimitedAccess,
+    buffer: &mut Option<StorageBuffer>,
+    lut: &CubeLut,
+) -> Result<Lattice> {
+    let lattice = lut.lattice();
+    let bytes = std::mem::size_of_val(lattice.as_slice()) as u64;
+    if buffer
+        .as_ref()
+        .is_none_or(|existing| existing.byte_size() < bytes)
+    {
+        let grown = gpu.acquire_storage_buffer(bytes)?;
+        if grown.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Lut: lattice buffer must be host-mapped".into(),
+            ));
+        }
+        *buffer = Some(grown);
+    }
+    let buffer = buffer
+        .as_ref()
+        .ok_or_else(|| Error::Configuration("Lut: lattice buffer not allocated".into()))?;
+    // SAFETY: the buffer is a persistently-mapped host-visible allocation
+    // of at least `bytes` (checked above), and the previous submit has
+    // completed, so the GPU is not reading it. Host writes before the
+    // submit are visible to the kernel.
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            lattice.as_ptr(),
+            buffer.mapped_ptr() as *mut [f32; 4],
+            lattice.len(),
+        );
+    }
+    Ok(Lattice {
+        size: lut.size,
+        domain_min: lut.domain_min,
+        domain_max: lut.domain_max,
+    })
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/lut/Lut",
+    description = "Grades frames on the GPU through a .cube 3D LUT with trilinear interpolation, mixed with the source by `intensity`. The LUT is swapped at runtime by a config update, or reloaded when its file changes with `watch`.",
+    execution = reactive,
+    config = crate::_generated_::LutConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to grade"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Graded frames (RGBA8, the input's color description)"),
+)]
+pub struct LutProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-mapped `vec4` lattice; grown when a larger LUT arrives.
+    lattice_buffer: Option<StorageBuffer>,
+    /// The LUT in `lattice_buffer`.
+    lattice: Option<Lattice>,
+    /// A loaded LUT not yet copied into `lattice_buffer`.
+    pending: Option<CubeLut>,
+    /// `config.lut` as last applied, and the file it resolved to.
+    source: String,
+    path: PathBuf,
+    /// The file's modification time when it was last loaded.
+    modified: Option<SystemTime>,
+    last_watch_check: Option<Instant>,
+    intensity: f32,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_graded: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for LutProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.intensity = resolve_intensity(&self.config)?;
+        let source = self.config.lut.clone();
+        let path = if is_url(&source) {
+            ctx.assets().fetch(&source)?
+        } else {
+            PathBuf::from(&source)
+        };
+        self.load(source, path)?;
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "lut",
+            spv: LUT_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<LutPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("lut")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!("[Lut] Setup (intensity {})", self.intensity);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.lattice_buffer = None;
+        self.output_ring = None;
+        tracing::info!("[Lut] Teardown ({} frames graded)", self.frames_graded);
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.intensity = resolve_intensity(&self.config)?;
+        if self.config.lut != self.source {
+            let source = self.config.lut.clone();
+            if is_url(&source) {
+                return Err(Error::Configuration(format!(
+                    "Lut: URLs are fetched at setup; re-add the processor to use '{}'",
+                    source
+                )));
+            }
+            let path = PathBuf::from(&source);
+            self.load(source, path)?;
+        }
+        tracing::info!("[Lut] Config updated (intensity {})", self.intensity);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        if self.config.watch == Some(true) {
+            self.reload_if_changed();
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let graded = self.grade(&frame)?;
+        self.frames_graded += 1;
+        self.outputs.write("video_out", &graded)
+    }
+}
+
+impl LutProcessor::Processor {
+    /// Parse the LUT at `path` and queue it for the next frame.
+    fn load(&mut self, source: String, path: PathBuf) -> Result<()> {
+        let lut = CubeLut::load(&path)?;
+        tracing::info!(
+            "[Lut] Loaded {} ({}, {}³)",
+            path.display(),
+            lut.title.as_deref().unwrap_or("untitled"),
+            lut.size
+        );
+        self.modified = modified(&path);
+        self.source = source;
+        self.path = path;
+        self.pending = Some(lut);
+        Ok(())
+    }
+
+    /// `watch`: reload a local LUT whose file changed since it was loaded,
+    /// at most once per [`WATCH_INTERVAL`]. A file that fails to load
+    /// keeps the current LUT.
+    fn reload_if_changed(&mut self) {
+        if is_url(&self.source)
+            || self
+                .last_watch_check
+                .is_some_and(|checked| checked.elapsed() < WATCH_INTERVAL)
+        {
+            return;
+        }
+        self.last_watch_check = Some(Instant::now());
+        let current = modified(&self.path);
+        if current.is_none() || current == self.modified {
+            return;
+        }
+        // Remember the new time either way, so a broken save is reported
+        // once rather than every interval.
+        self.modified = current;
+        match CubeLut::load(&self.path) {
+            Ok(lut) => {
+                tracing::info!("[Lut] Reloaded {} ({}³)", self.path.display(), lut.size);
+                self.pending = Some(lut);
+            }
+            Err(e) => tracing::warn!("[Lut] Keeping the current LUT: {}", e),
+        }
+    }
+
+    /// Grade `frame` into the next output slot.
+    fn grade(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let gpu = self
+            .gpu_context
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Lut: GPU context not initialized".into()))?;
+        if let Some(lut) = self.pending.take() {
+            self.lattice = Some(upload_lattice(gpu, &mut self.lattice_buffer, &lut)?);
+        }
+        let (Some(kernel), Some(recorder), Some(buffer), Some(lattice)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.lattice_buffer.as_ref(),
+            self.lattice,
+        ) else {
+            return Err(Error::Configuration("Lut: kernel not initialized".into()));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        let [min_r, min_g, min_b] = lattice.domain_min;
+        let [max_r, max_g, max_b] = lattice.domain_max;
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_storage_buffer_storage(2, buffer)?;
+        kernel.set_push_constants_value(&LutPushConstants {
+            width,
+            height,
+            size: lattice.size,
+            intensity: self.intensity,
+            domain_min: [min_r, min_g, min_b, 0.0],
+            domain_max: [max_r, max_g, max_b, 1.0],
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        // A grade changes the look, not the encoding the values are in.
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(intensity: Option<f32>) -> LutConfig {
+        LutConfig {
+            lut: "grade.cube".into(),
+            intensity,
+            watch: None,
+        }
+    }
+
+    #[test]
+    fn intensity_defaults_to_full_and_is_bounded() {
+        assert_eq!(resolve_intensity(&config(None)).unwrap(), 1.0);
+        assert_eq!(resolve_intensity(&config(Some(0.25))).unwrap(), 0.25);
+        assert!(resolve_intensity(&config(Some(1.5))).is_err());
+        assert!(resolve_intensity(&config(Some(-0.1))).is_err());
+    }
+
+    #[test]
+    fn only_http_sources_go_through_the_asset_cache() {
+        assert!(is_url("https://example.com/grades/warm.cube"));
+        assert!(is_url("http://10.0.0.2/warm.cube"));
+        assert!(!is_url("/srv/luts/warm.cube"));
+        assert!(!is_url("luts/https.cube"));
+    }
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<LutPushConstants>(), 48);
+        assert_eq!(std::mem::offset_of!(LutPushConstants, domain_min), 16);
+    }
+}
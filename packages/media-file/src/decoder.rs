@@ -0,0 +1,159 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Demux + decode via ffmpeg subprocesses: one for video, decoding to RGBA
+//! frames on its stdout, one for audio, decoding to interleaved 32-bit
+//! float. Seeking restarts them at the new position.
+
+use std::io::{ErrorKind, Read};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::probe::VideoStreamInfo;
+
+/// Audio is decoded to this rate and channel count whatever the file holds.
+pub const AUDIO_SAMPLE_RATE: u32 = 48_000;
+pub const AUDIO_CHANNELS: u8 = 2;
+
+/// One ffmpeg decoding to its stdout.
+struct DecodeProcess {
+    child: Child,
+    stdout: ChildStdout,
+}
+
+impl DecodeProcess {
+    fn spawn(args: &[&str]) -> Result<Self> {
+        let mut child = Command::new("ffmpeg")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| Error::Runtime(format!("Failed to spawn ffmpeg: {e}")))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| Error::Runtime("ffmpeg stdout not available".into()))?;
+        Ok(Self { child, stdout })
+    }
+
+    /// Fill `buf`; `false` at the end of the stream.
+    fn read(&mut self, buf: &mut [u8]) -> Result<bool> {
+        match self.stdout.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(Error::Runtime(format!("Failed to read from ffmpeg: {e}"))),
+        }
+    }
+}
+
+impl Drop for DecodeProcess {
+    fn drop(&mut self) {
+        // Seeks abandon a decoder mid-stream.
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// `-ss` argument for a position in nanoseconds.
+fn seconds(position_ns: i64) -> String {
+    format!("{:.6}", position_ns.max(0) as f64 / 1e9)
+}
+
+/// RGBA frames of the first video stream, at its constant frame rate.
+pub struct VideoDecoder {
+    process: DecodeProcess,
+}
+
+impl VideoDecoder {
+    pub fn spawn(
+        path: &str,
+        video: &VideoStreamInfo,
+        position_ns: i64,
+        hardware_decode: bool,
+    ) -> Result<Self> {
+        let start = seconds(position_ns);
+        let rate = format!("{}/{}", video.fps_num, video.fps_den);
+        let mut args = vec!["-v", "error", "-nostdin"];
+        if hardware_decode {
+            // Falls back to software decode when no hardware decoder fits.
+            args.extend_from_slice(&["-hwaccel", "auto"]);
+        }
+        args.extend_from_slice(&[
+            // Frames keep the probed dimensions; rotation metadata is not applied.
+            "-noautorotate",
+            "-ss",
+            &start,
+            "-i",
+            path,
+            "-map",
+            "0:v:0",
+            "-r",
+            &rate,
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgba",
+            "pipe:1",
+        ]);
+        Ok(Self {
+            process: DecodeProcess::spawn(&args)?,
+        })
+    }
+
+    /// Decode the next frame into `frame` (`width * height * 4` bytes);
+    /// `false` at the end of the file.
+    pub fn read_frame(&mut self, frame: &mut [u8]) -> Result<bool> {
+        self.process.read(frame)
+    }
+}
+
+/// Interleaved stereo 48 kHz samples of the first audio stream.
+pub struct AudioDecoder {
+    process: DecodeProcess,
+    bytes: Vec<u8>,
+}
+
+impl AudioDecoder {
+    pub fn spawn(path: &str, position_ns: i64) -> Result<Self> {
+        let start = seconds(position_ns);
+        let sample_rate = AUDIO_SAMPLE_RATE.to_string();
+        let channels = AUDIO_CHANNELS.to_string();
+        let args = [
+            "-v",
+            "error",
+            "-nostdin",
+            "-ss",
+            &start,
+            "-i",
+            path,
+            "-map",
+            "0:a:0",
+            "-f",
+            "f32le",
+            "-ar",
+            &sample_rate,
+            "-ac",
+            &channels,
+            "pipe:1",
+        ];
+        Ok(Self {
+            process: DecodeProcess::spawn(&args)?,
+            bytes: Vec::new(),
+        })
+    }
+
+    /// Decode the next `samples.len()` interleaved samples; `false` at the
+    /// end of the file.
+    pub fn read_samples(&mut self, samples: &mut [f32]) -> Result<bool> {
+        self.bytes.resize(samples.len() * 4, 0);
+        if !self.process.read(&mut self.bytes)? {
+            return Ok(false);
+        }
+        for (sample, bytes) in samples.iter_mut().zip(self.bytes.chunks_exact(4)) {
+            *sample = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/media-file` — local media files as graph sources.
+//! `MediaFileSource` decodes a file with ffmpeg and plays it out against
+//! the media clock, with seek, loop and rate control.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod decoder;
+pub mod media_file_source;
+pub mod probe;
+pub mod transport;
+
+pub use _generated_::{MediaFileControl, MediaFileSourceConfig};
+pub use media_file_source::{MEDIA_FILE_ENDED_TOPIC, MediaFileSourceProcessor};
+pub use probe::{MediaInfo, VideoStreamInfo, probe};
+pub use transport::Transport;
+
+streamlib_plugin_abi::export_plugin!(crate::MediaFileSourceProcessor::Processor,);
@@ -0,0 +1,476 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Media file source — plays a local file (MP4, MKV, MOV, …) into the
+//! graph, for replay, VTR-style playout and test inputs.
+//!
+//! ffmpeg demuxes and decodes (hardware decode where it has one); the
+//! playback thread releases each video frame and 20 ms audio chunk when
+//! the [`Transport`] says its file position is due on the media clock, and
+//! stamps it with that time. Commands on the `control` input play, pause,
+//! seek, change the rate or toggle looping. Audio plays at normal speed
+//! only — at any other rate the file plays silent. At the end of a
+//! non-looping file playback holds and [`MEDIA_FILE_ENDED_TOPIC`] is
+//! published; `play` then starts over.
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde_json::json;
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::{InputMailboxes, OutputWriter};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+use streamlib_plugin_sdk::sdk::rhi::PixelFormat;
+
+use crate::_generated_::tatolab__core::audio_frame::ChannelLayout;
+use crate::_generated_::tatolab__media_file::media_file_control::Command;
+use crate::_generated_::{AudioFrame, MediaFileControl, VideoFrame};
+use crate::decoder::{AUDIO_CHANNELS, AUDIO_SAMPLE_RATE, AudioDecoder, VideoDecoder};
+use crate::probe::{MediaInfo, probe};
+use crate::transport::Transport;
+
+/// Custom-event topic carrying `{processor_id, path}` when a non-looping
+/// file plays to its end.
+pub const MEDIA_FILE_ENDED_TOPIC: &str = "media_file:ended";
+
+/// Longest the playback thread waits before checking the control input.
+const CONTROL_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Audio is released in chunks this long: 960 frames at 48 kHz.
+const AUDIO_CHUNK_NS: i64 = 20_000_000;
+const AUDIO_CHUNK_FRAMES: usize = 960;
+
+/// What the playback thread is told to do, from the control input or the
+/// processor's own lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TransportCommand {
+    Play,
+    Pause,
+    Seek { position_ns: i64 },
+    SetRate(f64),
+    SetLooping(bool),
+    Stop,
+}
+
+impl TransportCommand {
+    /// `None` when a command lacks its argument.
+    fn from_control(control: &MediaFileControl) -> Option<Self> {
+        let command = match control.command {
+            Command::Play => Self::Play,
+            Command::Pause => Self::Pause,
+            Command::Seek => Self::Seek {
+                position_ns: (control.position_ms? * 1e6) as i64,
+            },
+            Command::SetRate => Self::SetRate(control.rate?),
+            Command::SetLoop => Self::SetLooping(control.looping?),
+        };
+        Some(command)
+    }
+}
+
+fn now_ns() -> i64 {
+    MediaClock::now().as_nanos() as i64
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/media-file/MediaFileSource",
+    description = "Plays a local media file (MP4, MKV, MOV, ...) into the graph: ffmpeg demuxes and decodes, with hardware decode where available, and frames are paced against the media clock. The control input plays, pauses, seeks, changes the rate and toggles looping.",
+    execution = manual,
+    config = crate::_generated_::MediaFileSourceConfig,
+    input("control", "@tatolab/media-file/MediaFileControl", description = "Transport commands: play, pause, seek, set_rate, set_loop"),
+    output("video", "@tatolab/core/VideoFrame", description = "Decoded video frames, stamped with the media clock time they play at"),
+    output("audio", "@tatolab/core/AudioFrame", format = "48khz", description = "Decoded audio, 48 kHz stereo, at normal speed only"),
+)]
+pub struct MediaFileSourceProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    processor_id: Option<String>,
+    info: Option<MediaInfo>,
+    commands: Option<mpsc::Sender<TransportCommand>>,
+    playback_thread: Option<JoinHandle<()>>,
+}
+
+impl ManualProcessor for MediaFileSourceProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let info = probe(&self.config.path)?;
+        tracing::info!(
+            "[MediaFileSource] {} — video: {:?}, audio: {}, duration: {:?}ns",
+            self.config.path,
+            info.video,
+            info.has_audio,
+            info.duration_ns
+        );
+        self.info = Some(info);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.stop(ctx)?;
+        self.gpu_context.take();
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        self.send(TransportCommand::Pause);
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        self.send(TransportCommand::Play);
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let gpu_context = self
+            .gpu_context
+            .clone()
+            .ok_or_else(|| Error::Configuration("GPU context not initialized".into()))?;
+        let info = self
+            .info
+            .clone()
+            .ok_or_else(|| Error::Configuration("Media file not probed".into()))?;
+
+        let start_ns = i64::from(self.config.start_ms.unwrap_or(0)) * 1_000_000;
+        let transport = Transport::new(
+            start_ns,
+            self.config.rate.unwrap_or(1.0),
+            self.config.start_paused.unwrap_or(false),
+            now_ns(),
+        );
+        let mut player = Player {
+            path: self.config.path.clone(),
+            frame: vec![
+                0;
+                info.video
+                    .map_or(0, |video| video.width as usize * video.height as usize * 4)
+            ],
+            samples: vec![0.0; AUDIO_CHUNK_FRAMES * usize::from(AUDIO_CHANNELS)],
+            info,
+            hardware_decode: self.config.hardware_decode.unwrap_or(true),
+            audio_enabled: self.config.audio.unwrap_or(true),
+            looping: self.config.looping.unwrap_or(false),
+            processor_id: self.processor_id.clone(),
+            transport,
+            video: None,
+            audio: None,
+            next_video_ns: start_ns,
+            next_audio_ns: start_ns,
+            ended: false,
+            outputs: self.outputs.clone(),
+            gpu_context,
+            frames_sent: 0,
+            frames_dropped: 0,
+            audio_chunks_sent: 0,
+        };
+        player.open_decoders(start_ns)?;
+
+        let (commands_tx, commands_rx) = mpsc::channel();
+        let inputs = self.inputs.clone();
+        let handle = std::thread::Builder::new()
+            .name("media-file-source".into())
+            .spawn(move || player.run(&inputs, &commands_rx))
+            .map_err(|e| Error::Configuration(format!("Failed to spawn playback thread: {e}")))?;
+        self.commands = Some(commands_tx);
+        self.playback_thread = Some(handle);
+        tracing::info!("[MediaFileSource] Playback started");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.send(TransportCommand::Stop);
+        self.commands = None;
+        if let Some(handle) = self.playback_thread.take() {
+            let _ = handle.join();
+            tracing::info!("[MediaFileSource] Stopped");
+        }
+        Ok(())
+    }
+}
+
+impl MediaFileSourceProcessor::Processor {
+    fn send(&self, command: TransportCommand) {
+        if let Some(commands) = &self.commands {
+            let _ = commands.send(command);
+        }
+    }
+}
+
+/// Playback state, owned by the playback thread.
+struct Player {
+    path: String,
+    info: MediaInfo,
+    hardware_decode: bool,
+    audio_enabled: bool,
+    looping: bool,
+    processor_id: Option<String>,
+    transport: Transport,
+    video: Option<VideoDecoder>,
+    /// Absent when audio is off, the file has none, it ran out, or the
+    /// rate isn't 1.
+    audio: Option<AudioDecoder>,
+    /// File positions of the next video frame and audio chunk.
+    next_video_ns: i64,
+    next_audio_ns: i64,
+    /// Played to the end without looping.
+    ended: bool,
+    frame: Vec<u8>,
+    samples: Vec<f32>,
+    outputs: OutputWriter,
+    gpu_context: GpuContextLimitedAccess,
+    frames_sent: u64,
+    frames_dropped: u64,
+    audio_chunks_sent: u64,
+}
+
+impl Player {
+    fn run(mut self, inputs: &InputMailboxes, commands: &mpsc::Receiver<TransportCommand>) {
+        let mut wait = Duration::ZERO;
+        loop {
+            match commands.recv_timeout(wait) {
+                Ok(TransportCommand::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+                Ok(command) => self.apply(command),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+            while inputs.has_data("control") {
+                match inputs.read::<MediaFileControl>("control") {
+                    Ok(control) => match TransportCommand::from_control(&control) {
+                        Some(command) => self.apply(command),
+                        None => tracing::warn!(
+                            "[MediaFileSource] {:?} is missing its argument, ignored",
+                            control.command
+                        ),
+                    },
+                    Err(e) => tracing::warn!("[MediaFileSource] Unreadable control frame: {e}"),
+                }
+            }
+            wait = match self.step(now_ns()) {
+                Ok(wait) => wait.min(CONTROL_POLL_INTERVAL),
+                Err(e) => {
+                    // Hold rather than retry a broken decoder every poll.
+                    tracing::error!("[MediaFileSource] Playback failed, pausing: {e}");
+                    self.transport.pause(now_ns());
+                    CONTROL_POLL_INTERVAL
+                }
+            };
+        }
+        tracing::info!(
+            frames = self.frames_sent,
+            dropped = self.frames_dropped,
+            audio_chunks = self.audio_chunks_sent,
+            "[MediaFileSource] Playback thread done"
+        );
+    }
+
+    fn apply(&mut self, command: TransportCommand) {
+        let now = now_ns();
+        let result = match command {
+            TransportCommand::Play if self.ended => {
+                self.transport.play(now);
+                self.seek_to(0, now)
+            }
+            TransportCommand::Play => {
+                self.transport.play(now);
+                Ok(())
+            }
+            TransportCommand::Pause => {
+                self.transport.pause(now);
+                Ok(())
+            }
+            TransportCommand::Seek { position_ns } => {
+                let position_ns = match self.info.duration_ns {
+                    Some(duration_ns) => position_ns.min(duration_ns),
+                    None => position_ns,
+                };
+                self.seek_to(position_ns, now)
+            }
+            TransportCommand::SetRate(rate) => {
+                self.transport.set_rate(rate, now);
+                self.follow_rate_with_audio()
+            }
+            TransportCommand::SetLooping(looping) => {
+                self.looping = looping;
+                Ok(())
+            }
+            TransportCommand::Stop => Ok(()),
+        };
+        if let Err(e) = result {
+            tracing::error!("[MediaFileSource] {command:?} failed: {e}");
+        }
+    }
+
+    /// Release whatever is due at `now`. Returns how long until the next
+    /// frame or chunk is due.
+    fn step(&mut self, now: i64) -> Result<Duration> {
+        let mut next_due = None::<i64>;
+        if self.video.is_some()
+            && let Some(due) = self.transport.clock_at(self.next_video_ns)
+        {
+            if due <= now {
+                self.release_video(due, now)?;
+                return Ok(Duration::ZERO);
+            }
+            next_due = Some(due);
+        }
+        if self.audio.is_some()
+            && let Some(due) = self.transport.clock_at(self.next_audio_ns)
+        {
+            if due <= now {
+                self.release_audio(due, now)?;
+                return Ok(Duration::ZERO);
+            }
+            next_due = Some(next_due.map_or(due, |video_due| video_due.min(due)));
+        }
+        Ok(next_due.map_or(CONTROL_POLL_INTERVAL, |due| {
+            Duration::from_nanos((due - now) as u64)
+        }))
+    }
+
+    fn release_video(&mut self, due: i64, now: i64) -> Result<()> {
+        let (Some(decoder), Some(video)) = (self.video.as_mut(), self.info.video) else {
+            return Ok(());
+        };
+        if !decoder.read_frame(&mut self.frame)? {
+            return self.reached_end(now);
+        }
+        self.next_video_ns += video.frame_interval_ns();
+
+        // More than a frame behind (decode can't keep up with the rate):
+        // skip it rather than fall further behind.
+        let interval_on_clock = (video.frame_interval_ns() as f64 / self.transport.rate()) as i64;
+        if now - due > interval_on_clock {
+            self.frames_dropped += 1;
+            return Ok(());
+        }
+
+        let (pool_id, pixel_buffer) = self.gpu_context.acquire_pixel_buffer(
+            video.width,
+            video.height,
+            PixelFormat::Rgba32,
+        )?;
+        let dst_ptr = pixel_buffer.plane_base_address(0);
+        if dst_ptr.is_null() {
+            return Err(Error::Runtime(
+                "Pixel buffer plane base address is null".into(),
+            ));
+        }
+        let copy_len = self.frame.len().min(pixel_buffer.plane_size(0) as usize);
+        // SAFETY: `dst_ptr` is the mapped host-visible base of a
+        // (width, height, Rgba32) pixel buffer; `copy_len` is clamped to
+        // both the decoded frame and the plane, and the regions don't overlap.
+        unsafe {
+            std::ptr::copy_nonoverlapping(self.frame.as_ptr(), dst_ptr, copy_len);
+        }
+
+        let video_frame = VideoFrame {
+            surface_id: pool_id.to_string(),
+            width: video.width,
+            height: video.height,
+            timestamp_ns: due.to_string(),
+            fps: Some(video.fps()),
+            texture_layout: None,
+            color_info: None,
+            mastering_display: None,
+            content_light: None,
+            field_order: None,
+        };
+        self.outputs.write("video", &video_frame)?;
+        self.frames_sent += 1;
+        if self.frames_sent == 1 {
+            tracing::info!("[MediaFileSource] First frame published");
+        }
+        Ok(())
+    }
+
+    fn release_audio(&mut self, due: i64, now: i64) -> Result<()> {
+        let Some(decoder) = self.audio.as_mut() else {
+            return Ok(());
+        };
+        if !decoder.read_samples(&mut self.samples)? {
+            self.audio = None;
+            // Video, when there is any, decides where the file ends.
+            if self.info.video.is_none() {
+                return self.reached_end(now);
+            }
+            return Ok(());
+        }
+        self.next_audio_ns += AUDIO_CHUNK_NS;
+        let audio_frame = AudioFrame {
+            samples: self.samples.clone(),
+            channels: AUDIO_CHANNELS,
+            sample_rate: AUDIO_SAMPLE_RATE,
+            timestamp_ns: due.to_string(),
+            frame_index: self.audio_chunks_sent.to_string(),
+            channel_layout: Some(ChannelLayout::Stereo),
+        };
+        self.outputs.write("audio", &audio_frame)?;
+        self.audio_chunks_sent += 1;
+        Ok(())
+    }
+
+    fn reached_end(&mut self, now: i64) -> Result<()> {
+        if self.looping {
+            return self.seek_to(0, now);
+        }
+        self.transport.pause(now);
+        self.ended = true;
+        self.video = None;
+        self.audio = None;
+        tracing::info!("[MediaFileSource] Reached the end of {}", self.path);
+        let payload = json!({ "processor_id": self.processor_id, "path": self.path });
+        if let Err(e) = publish_custom_event(MEDIA_FILE_ENDED_TOPIC, &payload) {
+            tracing::debug!("[MediaFileSource] End of file not published: {e}");
+        }
+        Ok(())
+    }
+
+    fn seek_to(&mut self, position_ns: i64, now: i64) -> Result<()> {
+        let position_ns = position_ns.max(0);
+        self.transport.seek(position_ns, now);
+        self.next_video_ns = position_ns;
+        self.next_audio_ns = position_ns;
+        self.ended = false;
+        self.open_decoders(position_ns)
+    }
+
+    /// (Re)start decoding at `position_ns`.
+    fn open_decoders(&mut self, position_ns: i64) -> Result<()> {
+        self.video = None;
+        self.audio = None;
+        if let Some(video) = &self.info.video {
+            self.video = Some(VideoDecoder::spawn(
+                &self.path,
+                video,
+                position_ns,
+                self.hardware_decode,
+            )?);
+        }
+        if self.plays_audio() {
+            self.audio = Some(AudioDecoder::spawn(&self.path, position_ns)?);
+        }
+        Ok(())
+    }
+
+    /// After a rate change: audio stops away from normal speed and picks up
+    /// again, in step with the video, back at it.
+    fn follow_rate_with_audio(&mut self) -> Result<()> {
+        if !self.plays_audio() {
+            self.audio = None;
+        } else if self.audio.is_none() && !self.ended {
+            let position_ns = self.transport.position_at(now_ns());
+            self.audio = Some(AudioDecoder::spawn(&self.path, position_ns)?);
+            self.next_audio_ns = position_ns;
+        }
+        Ok(())
+    }
+
+    fn plays_audio(&self) -> bool {
+        self.audio_enabled && self.info.has_audio && self.transport.rate() == 1.0
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! What a media file holds, from `ffprobe`.
+
+use std::process::Command;
+
+use serde_json::Value;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// The first video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoStreamInfo {
+    pub width: u32,
+    pub height: u32,
+    /// Frame rate as a fraction, e.g. 30000/1001.
+    pub fps_num: u32,
+    pub fps_den: u32,
+}
+
+impl VideoStreamInfo {
+    pub fn frame_interval_ns(&self) -> i64 {
+        i64::from(self.fps_den) * 1_000_000_000 / i64::from(self.fps_num)
+    }
+
+    /// Nearest whole frame rate, for `VideoFrame::fps`.
+    pub fn fps(&self) -> u32 {
+        (f64::from(self.fps_num) / f64::from(self.fps_den)).round() as u32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    pub video: Option<VideoStreamInfo>,
+    pub has_audio: bool,
+    pub duration_ns: Option<i64>,
+}
+
+/// Inspect `path` with `ffprobe`.
+pub fn probe(path: &str) -> Result<MediaInfo> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "stream=codec_type,width,height,avg_frame_rate,r_frame_rate:format=duration",
+            "-of",
+            "json",
+            path,
+        ])
+        .output()
+        .map_err(|e| Error::Runtime(format!("Failed to run ffprobe: {e}")))?;
+    if !output.status.success() {
+        return Err(Error::Runtime(format!(
+            "ffprobe could not read {path}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    parse_probe(&output.stdout)
+}
+
+/// Parse `ffprobe -of json` output.
+pub fn parse_probe(json: &[u8]) -> Result<MediaInfo> {
+    let probe: Value = serde_json::from_slice(json)
+        .map_err(|e| Error::Runtime(format!("Unreadable ffprobe output: {e}")))?;
+    let streams = probe["streams"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let video = streams
+        .iter()
+        .find(|stream| stream["codec_type"] == "video")
+        .and_then(|stream| {
+            let width = u32::try_from(stream["width"].as_u64()?).ok()?;
+            let height = u32::try_from(stream["height"].as_u64()?).ok()?;
+            // avg_frame_rate is 0/0 for some containers; r_frame_rate is always set.
+            let (fps_num, fps_den) = ["avg_frame_rate", "r_frame_rate"]
+                .iter()
+                .find_map(|key| parse_rate(stream[key].as_str()?))?;
+            Some(VideoStreamInfo {
+                width,
+                height,
+                fps_num,
+                fps_den,
+            })
+        });
+    let has_audio = streams.iter().any(|stream| stream["codec_type"] == "audio");
+    if video.is_none() && !has_audio {
+        return Err(Error::Runtime("File has no video or audio stream".into()));
+    }
+    let duration_ns = probe["format"]["duration"]
+        .as_str()
+        .and_then(|duration| duration.parse::<f64>().ok())
+        .map(|secs| (secs * 1e9) as i64);
+
+    Ok(MediaInfo {
+        video,
+        has_audio,
+        duration_ns,
+    })
+}
+
+/// `"30000/1001"` → `(30000, 1001)`; `None` for `0/0` and the like.
+fn parse_rate(rate: &str) -> Option<(u32, u32)> {
+    let (num, den) = rate.split_once('/')?;
+    let num = num.parse().ok().filter(|num| *num > 0)?;
+    let den = den.parse().ok().filter(|den| *den > 0)?;
+    Some((num, den))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_first_video_stream_and_audio_presence() {
+        let json = br#"{
+            "streams": [
+                { "codec_type": "video", "width": 1920, "height": 1080,
+                  "avg_frame_rate": "0/0", "r_frame_rate": "30000/1001" },
+                { "codec_type": "audio" }
+            ],
+            "format": { "duration": "12.500000" }
+        }"#;
+        let info = parse_probe(json).unwrap();
+        let video = info.video.unwrap();
+        assert_eq!(
+            (video.width, video.height, video.fps_num, video.fps_den),
+            (1920, 1080, 30000, 1001)
+        );
+        assert_eq!(video.fps(), 30);
+        assert_eq!(video.frame_interval_ns(), 33_366_666);
+        assert!(info.has_audio);
+        assert_eq!(info.duration_ns, Some(12_500_000_000));
+    }
+
+    #[test]
+    fn audio_only_files_have_no_video() {
+        let json = br#"{ "streams": [{ "codec_type": "audio" }], "format": {} }"#;
+        let info = parse_probe(json).unwrap();
+        assert_eq!(info.video, None);
+        assert_eq!(info.duration_ns, None);
+
+        assert!(parse_probe(br#"{ "streams": [{ "codec_type": "data" }] }"#).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Playback transport: where in the file playback is, against the media
+//! clock.
+//!
+//! Playback runs along a line anchored at one point — file position
+//! `anchor_position_ns` plays at media clock time `anchor_clock_ns` — with
+//! slope `rate`. Every command re-anchors at the current position, so
+//! changing the rate or resuming never jumps.
+
+/// Slowest and fastest playback rates accepted.
+pub const MIN_RATE: f64 = 0.05;
+pub const MAX_RATE: f64 = 16.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transport {
+    anchor_clock_ns: i64,
+    anchor_position_ns: i64,
+    rate: f64,
+    paused: bool,
+}
+
+impl Transport {
+    /// Playback from `position_ns`, starting at media clock time `now_ns`.
+    pub fn new(position_ns: i64, rate: f64, paused: bool, now_ns: i64) -> Self {
+        Self {
+            anchor_clock_ns: now_ns,
+            anchor_position_ns: position_ns,
+            rate: clamp_rate(rate),
+            paused,
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// File position playing at media clock time `now_ns`.
+    pub fn position_at(&self, now_ns: i64) -> i64 {
+        if self.paused {
+            return self.anchor_position_ns;
+        }
+        self.anchor_position_ns + ((now_ns - self.anchor_clock_ns) as f64 * self.rate) as i64
+    }
+
+    /// Media clock time file position `position_ns` plays at; `None` while
+    /// paused.
+    pub fn clock_at(&self, position_ns: i64) -> Option<i64> {
+        if self.paused {
+            return None;
+        }
+        Some(
+            self.anchor_clock_ns
+                + ((position_ns - self.anchor_position_ns) as f64 / self.rate) as i64,
+        )
+    }
+
+    pub fn play(&mut self, now_ns: i64) {
+        if self.paused {
+            self.anchor_clock_ns = now_ns;
+            self.paused = false;
+        }
+    }
+
+    pub fn pause(&mut self, now_ns: i64) {
+        if !self.paused {
+            self.anchor_position_ns = self.position_at(now_ns);
+            self.paused = true;
+        }
+    }
+
+    /// Jump to `position_ns`, keeping the play/pause state.
+    pub fn seek(&mut self, position_ns: i64, now_ns: i64) {
+        self.anchor_clock_ns = now_ns;
+        self.anchor_position_ns = position_ns.max(0);
+    }
+
+    /// Change speed from the current position on. Clamped to
+    /// [`MIN_RATE`]..=[`MAX_RATE`].
+    pub fn set_rate(&mut self, rate: f64, now_ns: i64) {
+        self.anchor_position_ns = self.position_at(now_ns);
+        self.anchor_clock_ns = now_ns;
+        self.rate = clamp_rate(rate);
+    }
+}
+
+fn clamp_rate(rate: f64) -> f64 {
+    if rate.is_finite() {
+        rate.clamp(MIN_RATE, MAX_RATE)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    #[test]
+    fn position_follows_the_clock_at_the_rate() {
+        let mut transport = Transport::new(10 * SECOND, 1.0, false, 100 * SECOND);
+        assert_eq!(transport.position_at(102 * SECOND), 12 * SECOND);
+        assert_eq!(transport.clock_at(15 * SECOND), Some(105 * SECOND));
+
+        transport.set_rate(2.0, 102 * SECOND);
+        assert_eq!(transport.position_at(103 * SECOND), 14 * SECOND);
+        assert_eq!(transport.clock_at(16 * SECOND), Some(104 * SECOND));
+    }
+
+    #[test]
+    fn pause_holds_and_play_resumes_without_a_jump() {
+        let mut transport = Transport::new(0, 1.0, false, 0);
+        transport.pause(3 * SECOND);
+        assert_eq!(transport.position_at(10 * SECOND), 3 * SECOND);
+        assert_eq!(transport.clock_at(4 * SECOND), None);
+
+        transport.play(10 * SECOND);
+        assert_eq!(transport.position_at(11 * SECOND), 4 * SECOND);
+    }
+
+    #[test]
+    fn seek_keeps_the_play_state() {
+        let mut transport = Transport::new(0, 1.0, true, 0);
+        transport.seek(30 * SECOND, 5 * SECOND);
+        assert!(transport.is_paused());
+        assert_eq!(transport.position_at(9 * SECOND), 30 * SECOND);
+        transport.seek(-SECOND, 9 * SECOND);
+        assert_eq!(transport.position_at(9 * SECOND), 0);
+    }
+
+    #[test]
+    fn rates_are_clamped() {
+        assert_eq!(Transport::new(0, 0.0, false, 0).rate(), MIN_RATE);
+        assert_eq!(Transport::new(0, 100.0, false, 0).rate(), MAX_RATE);
+        assert_eq!(Transport::new(0, f64::NAN, false, 0).rate(), 1.0);
+    }
+}
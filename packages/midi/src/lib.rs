@@ -0,0 +1,20 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/midi` — hardware control surfaces as graph inputs.
+//! `MidiInput` emits control changes and notes as data frames and
+//! publishes each control change for the runtime's MIDI mappings, which
+//! bind knobs and faders to any processor's config parameters.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod midi_input;
+pub mod midi_message;
+
+pub use midi_input::{MIDI_CONTROL_CHANGE_TOPIC, MidiInputProcessor};
+pub use midi_message::{MidiMessage, MidiParser};
+
+streamlib_plugin_abi::export_plugin!(crate::MidiInputProcessor::Processor,);
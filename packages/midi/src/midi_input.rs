@@ -0,0 +1,270 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MIDI input — control changes and notes from a hardware control surface
+//! as data frames.
+//!
+//! `midir` delivers messages on its own thread (CoreMIDI's client thread on
+//! macOS, an ALSA sequencer thread on Linux); the callback parses them and
+//! writes straight to the outputs. Each control change is also published
+//! on [`MIDI_CONTROL_CHANGE_TOPIC`], where the runtime's MIDI mappings
+//! (`PUT /api/midi/mappings`) turn it into processor config updates, so a
+//! knob can drive any parameter without wiring a link to it.
+
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use streamlib_plugin_sdk::sdk::context::RuntimeContextFullAccess;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+use crate::_generated_::{MidiControlChange, MidiNote};
+use crate::midi_message::{MidiMessage, MidiParser};
+
+/// Custom-event topic carrying `{processor_id, port, channel, controller,
+/// value}` for every control change. The runtime's MIDI mappings listen
+/// here; keep in step with `streamlib::sdk::midi_mapping`.
+pub const MIDI_CONTROL_CHANGE_TOPIC: &str = "midi:control_change";
+
+/// Client name the MIDI system lists the connection under.
+const MIDI_CLIENT_NAME: &str = "streamlib";
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/midi/MidiInput",
+    description = "Receives MIDI from a hardware control surface (CoreMIDI on macOS, ALSA on Linux) and emits control changes and notes as data frames. Every control change is also published on the midi:control_change event topic, where the runtime's MIDI mappings bind it to processor parameters.",
+    execution = manual,
+    scheduling = realtime,
+    config = crate::_generated_::MidiInputConfig,
+    output("control_change", "@tatolab/midi/MidiControlChange", description = "One data frame per control change"),
+    output("note", "@tatolab/midi/MidiNote", description = "One data frame per note on or note off"),
+)]
+pub struct MidiInputProcessor {
+    processor_id: Option<String>,
+    port_name: Option<String>,
+    // The connection is confined to a dedicated thread that opens it and
+    // holds it alive, as `AudioCapture` does with its stream, so nothing
+    // backend-specific has to be `Send`. Dropping the sender in `teardown`
+    // wakes the thread, which closes the connection.
+    input_thread: Option<JoinHandle<()>>,
+    shutdown_sender: Option<mpsc::Sender<()>>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for MidiInputProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(channel) = self.config.channel
+            && !(1..=16).contains(&channel)
+        {
+            return Err(Error::Configuration(format!(
+                "MIDI channel {} is out of range (1-16)",
+                channel
+            )));
+        }
+        self.processor_id = ctx.processor_id();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.shutdown_sender = None;
+        if let Some(handle) = self.input_thread.take() {
+            let _ = handle.join();
+        }
+        if let Some(port_name) = self.port_name.take() {
+            tracing::info!("MidiInput: closed '{}'", port_name);
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.input_thread.is_some() {
+            return Ok(());
+        }
+        let sink = MidiSink {
+            parser: MidiParser::new(),
+            outputs: self.outputs.clone(),
+            processor_id: self.processor_id.clone(),
+            port: String::new(),
+            channel: self.config.channel,
+        };
+        let wanted = self.config.port.clone();
+
+        let (ready_sender, ready_receiver) = mpsc::channel::<Result<String>>();
+        let (shutdown_sender, shutdown_receiver) = mpsc::channel::<()>();
+        let handle = std::thread::Builder::new()
+            .name("midi-input".to_string())
+            .spawn(move || match connect(wanted.as_deref(), sink) {
+                Ok((port_name, connection)) => {
+                    if ready_sender.send(Ok(port_name)).is_err() {
+                        return;
+                    }
+                    let _ = shutdown_receiver.recv();
+                    connection.close();
+                }
+                Err(e) => {
+                    let _ = ready_sender.send(Err(e));
+                }
+            })
+            .map_err(|e| {
+                Error::Configuration(format!("Failed to spawn MIDI input thread: {}", e))
+            })?;
+
+        let port_name = ready_receiver.recv().map_err(|_| {
+            Error::Configuration("MIDI input thread exited before connecting".into())
+        })??;
+        tracing::info!("MidiInput: listening on '{}'", port_name);
+
+        self.port_name = Some(port_name);
+        self.input_thread = Some(handle);
+        self.shutdown_sender = Some(shutdown_sender);
+        Ok(())
+    }
+}
+
+/// State the `midir` callback owns.
+struct MidiSink {
+    parser: MidiParser,
+    outputs: OutputWriter,
+    processor_id: Option<String>,
+    port: String,
+    /// Only messages on this channel pass.
+    channel: Option<u8>,
+}
+
+impl MidiSink {
+    fn receive(&mut self, bytes: &[u8]) {
+        for message in self.parser.parse(bytes) {
+            let timestamp_ns = MediaClock::now().as_nanos().to_string();
+            match message {
+                MidiMessage::ControlChange {
+                    channel,
+                    controller,
+                    value,
+                } if self.passes(channel) => {
+                    self.publish_control_change(channel, controller, value);
+                    let frame = MidiControlChange {
+                        timestamp_ns,
+                        port: self.port.clone(),
+                        channel,
+                        controller,
+                        value,
+                    };
+                    if let Err(e) = self.outputs.write("control_change", &frame) {
+                        tracing::error!(error = %e, "MidiInput: failed to write control change");
+                    }
+                }
+                MidiMessage::Note {
+                    channel,
+                    note,
+                    velocity,
+                    on,
+                } if self.passes(channel) => {
+                    let frame = MidiNote {
+                        timestamp_ns,
+                        port: self.port.clone(),
+                        channel,
+                        note,
+                        velocity,
+                        on,
+                    };
+                    if let Err(e) = self.outputs.write("note", &frame) {
+                        tracing::error!(error = %e, "MidiInput: failed to write note");
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn passes(&self, channel: u8) -> bool {
+        self.channel.is_none_or(|wanted| wanted == channel)
+    }
+
+    fn publish_control_change(&self, channel: u8, controller: u8, value: u8) {
+        let payload = serde_json::json!({
+            "processor_id": self.processor_id,
+            "port": self.port,
+            "channel": channel,
+            "controller": controller,
+            "value": value,
+        });
+        if let Err(e) = publish_custom_event(MIDI_CONTROL_CHANGE_TOPIC, &payload) {
+            tracing::debug!("MidiInput: control change not published: {}", e);
+        }
+    }
+}
+
+/// Open the input port whose name contains `wanted` (the first port when
+/// `None`) and start delivering to `sink`. Runs on the input thread.
+fn connect(
+    wanted: Option<&str>,
+    mut sink: MidiSink,
+) -> Result<(String, MidiInputConnection<MidiSink>)> {
+    let mut input = MidiInput::new(MIDI_CLIENT_NAME)
+        .map_err(|e| Error::Configuration(format!("Failed to open MIDI: {}", e)))?;
+    // Control surfaces have no use for sysex, clock or active sensing.
+    input.ignore(Ignore::All);
+
+    let ports = input.ports();
+    let names: Vec<String> = ports
+        .iter()
+        .map(|port| input.port_name(port).unwrap_or_default())
+        .collect();
+    let index = select_port(&names, wanted).ok_or_else(|| match wanted {
+        Some(wanted) => Error::Configuration(format!(
+            "No MIDI input port matching '{}' (available: {})",
+            wanted,
+            names.join(", ")
+        )),
+        None => Error::Configuration("No MIDI input ports".into()),
+    })?;
+
+    let port_name = names[index].clone();
+    sink.port = port_name.clone();
+    let connection = input
+        .connect(
+            &ports[index],
+            "streamlib-midi-input",
+            |_timestamp_us, bytes, sink| sink.receive(bytes),
+            sink,
+        )
+        .map_err(|e| {
+            Error::Configuration(format!(
+                "Failed to connect to MIDI port '{}': {}",
+                port_name, e
+            ))
+        })?;
+    Ok((port_name, connection))
+}
+
+/// Index of the first port whose name contains `wanted`, ignoring case;
+/// the first port when `wanted` is `None`.
+fn select_port(names: &[String], wanted: Option<&str>) -> Option<usize> {
+    match wanted {
+        None => (!names.is_empty()).then_some(0),
+        Some(wanted) => {
+            let wanted = wanted.to_lowercase();
+            names
+                .iter()
+                .position(|name| name.to_lowercase().contains(&wanted))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_port_matches_part_of_the_name_ignoring_case() {
+        let names = vec![
+            "Midi Through:Midi Through Port-0 14:0".to_string(),
+            "nanoKONTROL2:nanoKONTROL2 nanoKONTROL2 _ CTR 20:0".to_string(),
+        ];
+        assert_eq!(select_port(&names, Some("nanokontrol")), Some(1));
+        assert_eq!(select_port(&names, Some("Launchpad")), None);
+        assert_eq!(select_port(&names, None), Some(0));
+        assert_eq!(select_port(&[], None), None);
+    }
+}
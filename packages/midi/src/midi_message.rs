@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Byte-stream decoding of the MIDI channel messages `MidiInput` emits.
+//!
+//! A CoreMIDI packet can carry several messages, and devices lean on
+//! running status (data bytes repeating the previous status) to save
+//! bandwidth during fader sweeps, so input is parsed byte by byte rather
+//! than one message per callback. Realtime bytes (clock, active sensing)
+//! may sit anywhere, even mid-message, and are skipped; sysex and other
+//! system messages are dropped whole.
+
+/// A decoded channel message. Channels are `1..=16`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiMessage {
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// Note on or off; a note on with velocity 0 decodes as off.
+    Note {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+        on: bool,
+    },
+}
+
+const NOTE_OFF: u8 = 0x80;
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+const PROGRAM_CHANGE: u8 = 0xC0;
+const CHANNEL_PRESSURE: u8 = 0xD0;
+const SYSTEM: u8 = 0xF0;
+const REALTIME: u8 = 0xF8;
+
+/// Incremental MIDI byte-stream decoder. Keep one per input port; state
+/// carries across callbacks.
+#[derive(Debug, Default)]
+pub struct MidiParser {
+    /// Status the pending data bytes belong to; `None` inside sysex or
+    /// system messages, whose data is dropped.
+    running_status: Option<u8>,
+    data: [u8; 2],
+    data_len: usize,
+}
+
+impl MidiParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `bytes`, returning the channel messages they complete.
+    pub fn parse(&mut self, bytes: &[u8]) -> Vec<MidiMessage> {
+        bytes.iter().filter_map(|&byte| self.push(byte)).collect()
+    }
+
+    /// Feed one byte, returning the message it completes, if any.
+    pub fn push(&mut self, byte: u8) -> Option<MidiMessage> {
+        if byte >= REALTIME {
+            return None;
+        }
+        if byte >= SYSTEM {
+            // Sysex (0xF0 ... 0xF7) and system common messages cancel
+            // running status, so their data bytes are dropped below.
+            self.running_status = None;
+            self.data_len = 0;
+            return None;
+        }
+        if byte & 0x80 != 0 {
+            self.running_status = Some(byte);
+            self.data_len = 0;
+            return None;
+        }
+
+        let status = self.running_status?;
+        self.data[self.data_len] = byte;
+        self.data_len += 1;
+        if self.data_len < data_len(status) {
+            return None;
+        }
+        // Running status: the next data bytes start another message with
+        // the same status.
+        self.data_len = 0;
+        decode(status, self.data)
+    }
+}
+
+fn data_len(status: u8) -> usize {
+    match status & 0xF0 {
+        PROGRAM_CHANGE | CHANNEL_PRESSURE => 1,
+        _ => 2,
+    }
+}
+
+fn decode(status: u8, [first, second]: [u8; 2]) -> Option<MidiMessage> {
+    let channel = (status & 0x0F) + 1;
+    match status & 0xF0 {
+        CONTROL_CHANGE => Some(MidiMessage::ControlChange {
+            channel,
+            controller: first,
+            value: second,
+        }),
+        NOTE_ON | NOTE_OFF => Some(MidiMessage::Note {
+            channel,
+            note: first,
+            velocity: second,
+            on: status & 0xF0 == NOTE_ON && second > 0,
+        }),
+        // Aftertouch, program change and pitch bend aren't emitted.
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cc(channel: u8, controller: u8, value: u8) -> MidiMessage {
+        MidiMessage::ControlChange {
+            channel,
+            controller,
+            value,
+        }
+    }
+
+    #[test]
+    fn running_status_repeats_the_previous_status() {
+        let mut parser = MidiParser::new();
+        assert_eq!(
+            parser.parse(&[0xB0, 7, 10, 7, 20, 7, 30]),
+            vec![cc(1, 7, 10), cc(1, 7, 20), cc(1, 7, 30)]
+        );
+        // State carries across callbacks.
+        assert_eq!(parser.parse(&[7]), vec![]);
+        assert_eq!(parser.parse(&[40]), vec![cc(1, 7, 40)]);
+    }
+
+    #[test]
+    fn note_on_with_zero_velocity_is_a_note_off() {
+        let mut parser = MidiParser::new();
+        assert_eq!(
+            parser.parse(&[0x93, 60, 100, 60, 0, 0x83, 62, 64]),
+            vec![
+                MidiMessage::Note {
+                    channel: 4,
+                    note: 60,
+                    velocity: 100,
+                    on: true
+                },
+                MidiMessage::Note {
+                    channel: 4,
+                    note: 60,
+                    velocity: 0,
+                    on: false
+                },
+                MidiMessage::Note {
+                    channel: 4,
+                    note: 62,
+                    velocity: 64,
+                    on: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn realtime_bytes_are_skipped_mid_message() {
+        let mut parser = MidiParser::new();
+        assert_eq!(
+            parser.parse(&[0xBF, 0xF8, 74, 0xFE, 127]),
+            vec![cc(16, 74, 127)]
+        );
+    }
+
+    #[test]
+    fn sysex_and_unemitted_messages_are_dropped() {
+        let mut parser = MidiParser::new();
+        assert_eq!(
+            parser.parse(&[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7, 0x40, 0x41]),
+            vec![]
+        );
+        // Program change takes one data byte, pitch bend two; neither is
+        // emitted, and neither swallows the control change after it.
+        assert_eq!(
+            parser.parse(&[0xC0, 5, 0xE0, 0, 64, 0xB1, 1, 2]),
+            vec![cc(2, 1, 2)]
+        );
+    }
+}
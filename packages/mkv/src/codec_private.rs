@@ -0,0 +1,245 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Codec-specific track setup: the `CodecPrivate` blobs Matroska needs for
+//! Opus and AV1, and the AV1 bitstream shape it stores in blocks.
+
+/// AV1 OBU types the muxer looks at.
+const OBU_SEQUENCE_HEADER: u8 = 1;
+const OBU_TEMPORAL_DELIMITER: u8 = 2;
+
+/// `seq_level_idx` meaning "no level constraints", used when the sequence
+/// header can't be read that far.
+const AV1_LEVEL_MAX: u8 = 31;
+
+/// Opus `CodecPrivate`: the 19-byte `OpusHead` of RFC 7845 with channel
+/// mapping family 0 (mono or stereo).
+pub fn opus_head(channels: u8, sample_rate: u32, pre_skip: u16) -> Vec<u8> {
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead");
+    head.push(1);
+    head.push(channels);
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&sample_rate.to_le_bytes());
+    // Output gain, then the mapping family.
+    head.extend_from_slice(&0i16.to_le_bytes());
+    head.push(0);
+    head
+}
+
+/// One OBU in a temporal unit: its type and its full bytes, header
+/// included.
+struct Obu<'a> {
+    obu_type: u8,
+    bytes: &'a [u8],
+    /// Offset of the payload within `bytes`.
+    payload_start: usize,
+}
+
+/// The OBUs of a low-overhead-format temporal unit. Stops at the first
+/// malformed OBU.
+fn obus(mut data: &[u8]) -> impl Iterator<Item = Obu<'_>> {
+    std::iter::from_fn(move || {
+        let header = *data.first()?;
+        let obu_type = (header >> 3) & 0x0F;
+        let has_extension = header & 0x04 != 0;
+        let has_size = header & 0x02 != 0;
+        let mut offset = 1 + usize::from(has_extension);
+        let payload_len = if has_size {
+            let (size, leb_len) = leb128(data.get(offset..)?)?;
+            offset += leb_len;
+            usize::try_from(size).ok()?
+        } else {
+            data.len().checked_sub(offset)?
+        };
+        let end = offset
+            .checked_add(payload_len)
+            .filter(|end| *end <= data.len())?;
+        let (bytes, rest) = data.split_at(end);
+        data = rest;
+        Some(Obu {
+            obu_type,
+            bytes,
+            payload_start: offset,
+        })
+    })
+}
+
+fn leb128(data: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().take(8).enumerate() {
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// An AV1 temporal unit as Matroska stores it: without temporal
+/// delimiters.
+pub fn av1_block(data: &[u8]) -> Vec<u8> {
+    obus(data)
+        .filter(|obu| obu.obu_type != OBU_TEMPORAL_DELIMITER)
+        .flat_map(|obu| obu.bytes.iter().copied())
+        .collect()
+}
+
+/// AV1 `CodecPrivate` — the `av1C` record with the stream's sequence header
+/// as its config OBUs — built from a keyframe's temporal unit. `None` when
+/// the keyframe carries no sequence header.
+///
+/// Profile, level and tier are read from the sequence header; the
+/// bit-depth and subsampling flags are left at the profile's defaults, as
+/// decoders take those from the config OBUs themselves.
+pub fn av1_codec_private(keyframe: &[u8]) -> Option<Vec<u8>> {
+    let sequence_header = obus(keyframe).find(|obu| obu.obu_type == OBU_SEQUENCE_HEADER)?;
+    let payload = &sequence_header.bytes[sequence_header.payload_start..];
+    let mut bits = BitReader::new(payload);
+    let profile = bits.read(3)? as u8;
+    let (level, tier) = av1_level_and_tier(&mut bits).unwrap_or((AV1_LEVEL_MAX, 0));
+
+    // 4:2:0 for the Main profile, 4:4:4 for High; Professional varies.
+    let subsampling = if profile == 0 { 0b0000_1100 } else { 0 };
+    let mut av1c = vec![0x81, (profile << 5) | level, (tier << 7) | subsampling, 0];
+    // Config OBUs carry their size field.
+    if sequence_header.bytes[0] & 0x02 == 0 {
+        av1c.extend_from_slice(&with_size_field(&sequence_header));
+    } else {
+        av1c.extend_from_slice(sequence_header.bytes);
+    }
+    Some(av1c)
+}
+
+/// The first operating point's level and tier, reading on from just after
+/// `seq_profile`.
+fn av1_level_and_tier(bits: &mut BitReader<'_>) -> Option<(u8, u8)> {
+    let _still_picture = bits.read(1)?;
+    if bits.read(1)? == 1 {
+        // reduced_still_picture_header
+        return Some((bits.read(5)? as u8, 0));
+    }
+    if bits.read(1)? == 1 {
+        // timing_info
+        bits.read(32)?;
+        bits.read(32)?;
+        if bits.read(1)? == 1 {
+            bits.uvlc()?;
+        }
+        // decoder_model_info
+        if bits.read(1)? == 1 {
+            bits.read(5)?;
+            bits.read(32)?;
+            bits.read(5)?;
+            bits.read(5)?;
+        }
+    }
+    let _initial_display_delay_present = bits.read(1)?;
+    let _operating_points = bits.read(5)?;
+    let _operating_point_idc = bits.read(12)?;
+    let level = bits.read(5)? as u8;
+    let tier = if level > 7 { bits.read(1)? as u8 } else { 0 };
+    Some((level, tier))
+}
+
+fn with_size_field(obu: &Obu<'_>) -> Vec<u8> {
+    let header = &obu.bytes[..obu.payload_start];
+    let payload = &obu.bytes[obu.payload_start..];
+    let mut bytes = header.to_vec();
+    bytes[0] |= 0x02;
+    let mut size = payload.len();
+    loop {
+        let byte = (size & 0x7F) as u8;
+        size >>= 7;
+        if size == 0 {
+            bytes.push(byte);
+            break;
+        }
+        bytes.push(byte | 0x80);
+    }
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+/// Most-significant-bit-first reader over an OBU payload.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read(&mut self, count: u32) -> Option<u32> {
+        let mut value = 0u32;
+        for _ in 0..count {
+            let byte = self.data.get(self.position / 8)?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    fn uvlc(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while self.read(1)? == 0 {
+            leading_zeros += 1;
+            if leading_zeros >= 32 {
+                return Some(u32::MAX);
+            }
+        }
+        Some(self.read(leading_zeros)? + ((1u32 << leading_zeros) - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A temporal delimiter, then a Main-profile level-8 (4.0) sequence
+    /// header with no timing info, then a frame OBU.
+    fn temporal_unit() -> Vec<u8> {
+        // seq_profile 0, still 0, reduced 0, timing 0, initial_display_delay 0,
+        // operating_points_cnt_minus_1 0, idc 0 (12 bits), level 8, tier 1.
+        let sequence_header = [0b0000_0000, 0b0000_0000, 0b0000_0000, 0b0100_0100];
+        let mut unit = vec![0x12, 0x00];
+        unit.extend_from_slice(&[0x0A, sequence_header.len() as u8]);
+        unit.extend_from_slice(&sequence_header);
+        unit.extend_from_slice(&[0x32, 0x02, 0xAA, 0xBB]);
+        unit
+    }
+
+    #[test]
+    fn opus_head_is_rfc_7845() {
+        let head = opus_head(2, 48_000, 312);
+        assert_eq!(head.len(), 19);
+        assert_eq!(&head[..8], b"OpusHead");
+        assert_eq!(head[9], 2);
+        assert_eq!(u16::from_le_bytes([head[10], head[11]]), 312);
+        assert_eq!(
+            u32::from_le_bytes([head[12], head[13], head[14], head[15]]),
+            48_000
+        );
+    }
+
+    #[test]
+    fn blocks_drop_temporal_delimiters() {
+        let unit = temporal_unit();
+        assert_eq!(av1_block(&unit), unit[2..]);
+    }
+
+    #[test]
+    fn codec_private_carries_profile_level_and_the_sequence_header() {
+        let unit = temporal_unit();
+        let av1c = av1_codec_private(&unit).unwrap();
+        assert_eq!(av1c[0], 0x81);
+        assert_eq!(av1c[1], 8);
+        assert_eq!(av1c[2] >> 7, 1);
+        assert_eq!(&av1c[4..], &unit[2..8]);
+
+        assert_eq!(av1_codec_private(&[0x32, 0x02, 0xAA, 0xBB]), None);
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! EBML element encoding — the binary layout Matroska and WebM are built on.
+//!
+//! An element is its ID, its size as a variable-length integer, then its
+//! body. Master elements are built body-first in memory so their size is
+//! known when the header is written; only the Segment is written with the
+//! "unknown" size and patched when the file is closed.
+
+/// Matroska element IDs, marker bits included.
+pub mod id {
+    pub const EBML: u32 = 0x1A45_DFA3;
+    pub const EBML_VERSION: u32 = 0x4286;
+    pub const EBML_READ_VERSION: u32 = 0x42F7;
+    pub const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+    pub const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+    pub const DOC_TYPE: u32 = 0x4282;
+    pub const DOC_TYPE_VERSION: u32 = 0x4287;
+    pub const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+    pub const VOID: u32 = 0xEC;
+
+    pub const SEGMENT: u32 = 0x1853_8067;
+    pub const SEEK_HEAD: u32 = 0x114D_9B74;
+    pub const SEEK: u32 = 0x4DBB;
+    pub const SEEK_ID: u32 = 0x53AB;
+    pub const SEEK_POSITION: u32 = 0x53AC;
+
+    pub const INFO: u32 = 0x1549_A966;
+    pub const TIMESTAMP_SCALE: u32 = 0x2A_D7B1;
+    pub const DURATION: u32 = 0x4489;
+    pub const MUXING_APP: u32 = 0x4D80;
+    pub const WRITING_APP: u32 = 0x5741;
+
+    pub const TRACKS: u32 = 0x1654_AE6B;
+    pub const TRACK_ENTRY: u32 = 0xAE;
+    pub const TRACK_NUMBER: u32 = 0xD7;
+    pub const TRACK_UID: u32 = 0x73C5;
+    pub const TRACK_TYPE: u32 = 0x83;
+    pub const FLAG_LACING: u32 = 0x9C;
+    pub const NAME: u32 = 0x536E;
+    pub const LANGUAGE: u32 = 0x22_B59C;
+    pub const CODEC_ID: u32 = 0x86;
+    pub const CODEC_PRIVATE: u32 = 0x63A2;
+    pub const CODEC_DELAY: u32 = 0x56AA;
+    pub const SEEK_PRE_ROLL: u32 = 0x56BB;
+    pub const VIDEO: u32 = 0xE0;
+    pub const PIXEL_WIDTH: u32 = 0xB0;
+    pub const PIXEL_HEIGHT: u32 = 0xBA;
+    pub const AUDIO: u32 = 0xE1;
+    pub const SAMPLING_FREQUENCY: u32 = 0xB5;
+    pub const CHANNELS: u32 = 0x9F;
+
+    pub const CLUSTER: u32 = 0x1F43_B675;
+    pub const TIMESTAMP: u32 = 0xE7;
+    pub const SIMPLE_BLOCK: u32 = 0xA3;
+    pub const BLOCK_GROUP: u32 = 0xA0;
+    pub const BLOCK: u32 = 0xA1;
+    pub const BLOCK_DURATION: u32 = 0x9B;
+
+    pub const CUES: u32 = 0x1C53_BB6B;
+    pub const CUE_POINT: u32 = 0xBB;
+    pub const CUE_TIME: u32 = 0xB3;
+    pub const CUE_TRACK_POSITIONS: u32 = 0xB7;
+    pub const CUE_TRACK: u32 = 0xF7;
+    pub const CUE_CLUSTER_POSITION: u32 = 0xF1;
+
+    pub const TAGS: u32 = 0x1254_C367;
+    pub const TAG: u32 = 0x7373;
+    pub const TARGETS: u32 = 0x63C0;
+    pub const SIMPLE_TAG: u32 = 0x67C8;
+    pub const TAG_NAME: u32 = 0x45A3;
+    pub const TAG_STRING: u32 = 0x4487;
+}
+
+/// The reserved all-ones 8-byte size: "until the parent ends", which lets a
+/// crashed recording's Segment run to the end of the file.
+pub const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+pub fn write_id(buf: &mut Vec<u8>, id: u32) {
+    let bytes = id.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count();
+    buf.extend_from_slice(&bytes[skip..]);
+}
+
+/// `size` as the shortest variable-length integer that holds it. Each
+/// length's all-ones value is reserved, hence the `- 1`.
+pub fn write_size(buf: &mut Vec<u8>, size: u64) {
+    let length = (1..=8u32)
+        .find(|length| size < (1u64 << (7 * length)) - 1)
+        .unwrap_or(8);
+    let marked = size | (1u64 << (7 * length));
+    buf.extend_from_slice(&marked.to_be_bytes()[8 - length as usize..]);
+}
+
+/// `size` as an 8-byte variable-length integer, for sizes patched in place.
+pub fn size_8(size: u64) -> [u8; 8] {
+    let mut bytes = size.to_be_bytes();
+    bytes[0] = 0x01;
+    bytes
+}
+
+pub fn uint(buf: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count().min(7);
+    binary(buf, id, &bytes[skip..]);
+}
+
+pub fn float(buf: &mut Vec<u8>, id: u32, value: f64) {
+    binary(buf, id, &value.to_be_bytes());
+}
+
+pub fn string(buf: &mut Vec<u8>, id: u32, value: &str) {
+    binary(buf, id, value.as_bytes());
+}
+
+pub fn binary(buf: &mut Vec<u8>, id: u32, value: &[u8]) {
+    write_id(buf, id);
+    write_size(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+/// A master element whose children `body` writes.
+pub fn master(buf: &mut Vec<u8>, id: u32, body: impl FnOnce(&mut Vec<u8>)) {
+    let mut children = Vec::new();
+    body(&mut children);
+    binary(buf, id, &children);
+}
+
+/// A Void element exactly `len` bytes long, `len >= 2`.
+pub fn void(buf: &mut Vec<u8>, len: usize) {
+    write_id(buf, id::VOID);
+    // One size byte holds up to 126; past that the size takes eight.
+    let body = if len - 2 <= 126 {
+        buf.push(0x80 | (len - 2) as u8);
+        len - 2
+    } else {
+        buf.extend_from_slice(&size_8((len - 9) as u64));
+        len - 9
+    };
+    buf.resize(buf.len() + body, 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_use_the_shortest_encoding() {
+        let encode = |size| {
+            let mut buf = Vec::new();
+            write_size(&mut buf, size);
+            buf
+        };
+        assert_eq!(encode(0), [0x80]);
+        assert_eq!(encode(126), [0xFE]);
+        // 127 is one byte's reserved all-ones value.
+        assert_eq!(encode(127), [0x40, 0x7F]);
+        assert_eq!(encode(0x3FFE), [0x7F, 0xFE]);
+        assert_eq!(encode(0x3FFF), [0x20, 0x3F, 0xFF]);
+        assert_eq!(size_8(5), [0x01, 0, 0, 0, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn elements_are_id_size_body() {
+        let mut buf = Vec::new();
+        uint(&mut buf, id::TRACK_NUMBER, 1);
+        string(&mut buf, id::CODEC_ID, "V_VP9");
+        assert_eq!(
+            buf,
+            [0xD7, 0x81, 0x01, 0x86, 0x85, b'V', b'_', b'V', b'P', b'9']
+        );
+
+        let mut buf = Vec::new();
+        uint(&mut buf, id::TIMESTAMP_SCALE, 1_000_000);
+        assert_eq!(buf, [0x2A, 0xD7, 0xB1, 0x83, 0x0F, 0x42, 0x40]);
+
+        let mut buf = Vec::new();
+        master(&mut buf, id::VIDEO, |video| uint(video, id::PIXEL_WIDTH, 0));
+        assert_eq!(buf, [0xE0, 0x83, 0xB0, 0x81, 0x00]);
+    }
+
+    #[test]
+    fn voids_fill_exactly() {
+        for len in [2, 60, 128, 129, 400] {
+            let mut buf = Vec::new();
+            void(&mut buf, len);
+            assert_eq!(buf.len(), len);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/mkv` — Matroska and WebM files. `MkvWriter` muxes VP9/AV1
+//! video, Opus audio and timed metadata tracks (subtitles, timecode, data)
+//! — codec and side-data combinations MP4 can't carry.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod codec_private;
+pub mod ebml;
+pub mod matroska;
+pub mod mkv_writer;
+
+pub use _generated_::{MkvMetadataFrame, MkvWriterConfig};
+pub use matroska::{DocType, MatroskaWriter, Track, TrackCodec};
+pub use mkv_writer::MkvWriterProcessor;
+
+streamlib_plugin_abi::export_plugin!(crate::MkvWriterProcessor::Processor,);
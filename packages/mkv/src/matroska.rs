@@ -0,0 +1,595 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Matroska / WebM muxing.
+//!
+//! The header (EBML header, Info, Tracks, Tags) is written up front and
+//! blocks are gathered into clusters, each written whole once it closes —
+//! at a video keyframe, or after [`MAX_CLUSTER_MS`] without one. The
+//! Segment starts out with the "unknown" size, so a file cut short by a
+//! crash still plays up to its last cluster. [`MatroskaWriter::finish`]
+//! then adds Cues for seeking, the Duration, the SeekHead (into space held
+//! by a Void) and the Segment's real size.
+
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::codec_private::opus_head;
+use crate::ebml::{self, id};
+
+/// Nanoseconds per timestamp tick: the default millisecond timestamps.
+const TIMESTAMP_SCALE_NS: i64 = 1_000_000;
+
+/// Longest a cluster runs without a video keyframe closing it.
+const MAX_CLUSTER_MS: i64 = 5_000;
+
+/// Space held after the Segment header for the SeekHead.
+const SEEK_HEAD_RESERVED: usize = 128;
+
+/// Opus decoders need this much audio before a seek target to converge.
+const OPUS_SEEK_PRE_ROLL_NS: u64 = 80_000_000;
+
+const TRACK_TYPE_VIDEO: u64 = 1;
+const TRACK_TYPE_AUDIO: u64 = 2;
+const TRACK_TYPE_SUBTITLE: u64 = 0x11;
+const TRACK_TYPE_METADATA: u64 = 0x21;
+
+const BLOCK_FLAG_KEYFRAME: u8 = 0x80;
+
+/// Which flavour of Matroska to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocType {
+    Matroska,
+    /// The WebM subset: VP9/AV1, Opus and WebVTT subtitles only.
+    WebM,
+}
+
+impl DocType {
+    /// WebM for a `.webm` path, Matroska otherwise.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("webm") => Self::WebM,
+            _ => Self::Matroska,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Matroska => "matroska",
+            Self::WebM => "webm",
+        }
+    }
+}
+
+/// What a track carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackCodec {
+    Vp9 {
+        width: u32,
+        height: u32,
+    },
+    /// `codec_private` is the `av1C` record from the first keyframe.
+    Av1 {
+        width: u32,
+        height: u32,
+        codec_private: Vec<u8>,
+    },
+    /// `pre_skip` is in 48 kHz samples.
+    Opus {
+        channels: u8,
+        sample_rate: u32,
+        pre_skip: u16,
+    },
+    /// UTF-8 text, one cue per block.
+    Subtitle,
+    /// SMPTE timecode as text (`HH:MM:SS:FF`), one per block.
+    Timecode,
+    /// Opaque bytes.
+    Data,
+}
+
+impl TrackCodec {
+    pub fn is_video(&self) -> bool {
+        matches!(self, Self::Vp9 { .. } | Self::Av1 { .. })
+    }
+
+    fn track_type(&self) -> u64 {
+        match self {
+            Self::Vp9 { .. } | Self::Av1 { .. } => TRACK_TYPE_VIDEO,
+            Self::Opus { .. } => TRACK_TYPE_AUDIO,
+            Self::Subtitle => TRACK_TYPE_SUBTITLE,
+            Self::Timecode | Self::Data => TRACK_TYPE_METADATA,
+        }
+    }
+
+    /// `None` when `doc_type` can't carry the codec.
+    pub fn codec_id(&self, doc_type: DocType) -> Option<&'static str> {
+        match (self, doc_type) {
+            (Self::Vp9 { .. }, _) => Some("V_VP9"),
+            (Self::Av1 { .. }, _) => Some("V_AV1"),
+            (Self::Opus { .. }, _) => Some("A_OPUS"),
+            (Self::Subtitle, DocType::Matroska) => Some("S_TEXT/UTF8"),
+            (Self::Subtitle, DocType::WebM) => Some("D_WEBVTT/SUBTITLES"),
+            (Self::Timecode, DocType::Matroska) => Some("D_STREAMLIB/TIMECODE"),
+            (Self::Data, DocType::Matroska) => Some("D_STREAMLIB/DATA"),
+            (Self::Timecode | Self::Data, DocType::WebM) => None,
+        }
+    }
+}
+
+/// One track. Track numbers follow the order tracks are given in, from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    pub codec: TrackCodec,
+    pub name: Option<String>,
+    /// BCP 47 tag, e.g. `en`.
+    pub language: Option<String>,
+}
+
+/// The cluster blocks are being gathered into.
+struct Cluster {
+    timestamp_ms: i64,
+    body: Vec<u8>,
+}
+
+/// Writes a Matroska or WebM file to `W`.
+pub struct MatroskaWriter<W: Write + Seek> {
+    out: W,
+    /// Bytes written so far.
+    position: u64,
+    /// Where the Segment's size field is.
+    segment_size_at: u64,
+    /// Where the Segment's children start; positions inside the Segment
+    /// are relative to this.
+    segment_start: u64,
+    /// Segment-relative positions of the top-level elements the SeekHead
+    /// points at.
+    seek_entries: Vec<(u32, u64)>,
+    /// Where the Duration's 8-byte value is.
+    duration_at: u64,
+    video_track: Option<u64>,
+    cluster: Option<Cluster>,
+    /// `(time_ms, track, cluster position)` of every cluster a seek can
+    /// start at.
+    cues: Vec<(i64, u64, u64)>,
+    end_ms: i64,
+}
+
+impl<W: Write + Seek> MatroskaWriter<W> {
+    /// Write the file header for `tracks`, with `tags` as the file's
+    /// metadata (title, artist, …).
+    pub fn new(
+        out: W,
+        doc_type: DocType,
+        tracks: &[Track],
+        tags: &[(String, String)],
+    ) -> io::Result<Self> {
+        let mut writer = Self {
+            out,
+            position: 0,
+            segment_size_at: 0,
+            segment_start: 0,
+            seek_entries: Vec::new(),
+            duration_at: 0,
+            video_track: None,
+            cluster: None,
+            cues: Vec::new(),
+            end_ms: 0,
+        };
+
+        let mut header = Vec::new();
+        ebml::master(&mut header, id::EBML, |ebml_header| {
+            ebml::uint(ebml_header, id::EBML_VERSION, 1);
+            ebml::uint(ebml_header, id::EBML_READ_VERSION, 1);
+            ebml::uint(ebml_header, id::EBML_MAX_ID_LENGTH, 4);
+            ebml::uint(ebml_header, id::EBML_MAX_SIZE_LENGTH, 8);
+            ebml::string(ebml_header, id::DOC_TYPE, doc_type.name());
+            ebml::uint(ebml_header, id::DOC_TYPE_VERSION, 4);
+            ebml::uint(ebml_header, id::DOC_TYPE_READ_VERSION, 2);
+        });
+        ebml::write_id(&mut header, id::SEGMENT);
+        writer.emit(&header)?;
+        writer.segment_size_at = writer.position;
+        writer.emit(&ebml::UNKNOWN_SIZE)?;
+        writer.segment_start = writer.position;
+
+        let mut reserved = Vec::new();
+        ebml::void(&mut reserved, SEEK_HEAD_RESERVED);
+        writer.emit(&reserved)?;
+
+        // Duration goes last so its value is the Info's final 8 bytes.
+        let mut info = Vec::new();
+        ebml::master(&mut info, id::INFO, |info| {
+            ebml::uint(info, id::TIMESTAMP_SCALE, TIMESTAMP_SCALE_NS as u64);
+            ebml::string(info, id::MUXING_APP, "streamlib");
+            ebml::string(info, id::WRITING_APP, "streamlib");
+            ebml::float(info, id::DURATION, 0.0);
+        });
+        writer
+            .seek_entries
+            .push((id::INFO, writer.segment_offset()));
+        writer.emit(&info)?;
+        writer.duration_at = writer.position - 8;
+
+        let mut track_entries = Vec::new();
+        for (index, track) in tracks.iter().enumerate() {
+            let number = index as u64 + 1;
+            let codec_id = track.codec.codec_id(doc_type).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{:?} tracks can't be written to WebM", track.codec),
+                )
+            })?;
+            if track.codec.is_video() && writer.video_track.is_none() {
+                writer.video_track = Some(number);
+            }
+            track_entry(&mut track_entries, number, track, codec_id);
+        }
+        let mut tracks_element = Vec::new();
+        ebml::binary(&mut tracks_element, id::TRACKS, &track_entries);
+        writer
+            .seek_entries
+            .push((id::TRACKS, writer.segment_offset()));
+        writer.emit(&tracks_element)?;
+
+        if !tags.is_empty() {
+            let mut tags_element = Vec::new();
+            ebml::master(&mut tags_element, id::TAGS, |tags_body| {
+                ebml::master(tags_body, id::TAG, |tag| {
+                    // Empty targets: the tags describe the whole file.
+                    ebml::master(tag, id::TARGETS, |_| {});
+                    for (name, value) in tags {
+                        ebml::master(tag, id::SIMPLE_TAG, |simple_tag| {
+                            ebml::string(simple_tag, id::TAG_NAME, name);
+                            ebml::string(simple_tag, id::TAG_STRING, value);
+                        });
+                    }
+                });
+            });
+            writer
+                .seek_entries
+                .push((id::TAGS, writer.segment_offset()));
+            writer.emit(&tags_element)?;
+        }
+        writer.out.flush()?;
+        Ok(writer)
+    }
+
+    /// Add a block to `track` at `timestamp_ns` from the start of the file.
+    /// Blocks with a `duration_ns` (subtitle cues) are written as block
+    /// groups so players know when they end.
+    pub fn write_block(
+        &mut self,
+        track: u64,
+        timestamp_ns: i64,
+        keyframe: bool,
+        data: &[u8],
+        duration_ns: Option<i64>,
+    ) -> io::Result<()> {
+        let timestamp_ms = timestamp_ns.max(0) / TIMESTAMP_SCALE_NS;
+        let starts_gop = keyframe && self.video_track == Some(track);
+        let roll = match &self.cluster {
+            None => true,
+            Some(cluster) => {
+                (starts_gop && !cluster.body.is_empty())
+                    || timestamp_ms - cluster.timestamp_ms >= MAX_CLUSTER_MS
+            }
+        };
+        if roll {
+            self.flush_cluster()?;
+            // Seeks land on video keyframes, or on any cluster when there's no video.
+            if starts_gop || self.video_track.is_none() {
+                self.cues.push((timestamp_ms, track, self.segment_offset()));
+            }
+            self.cluster = Some(Cluster {
+                timestamp_ms,
+                body: Vec::new(),
+            });
+        }
+        let Some(cluster) = self.cluster.as_mut() else {
+            return Ok(());
+        };
+
+        // Late blocks (audio behind video) sit before the cluster start.
+        let relative = (timestamp_ms - cluster.timestamp_ms)
+            .clamp(i64::from(i16::MIN), i64::from(i16::MAX)) as i16;
+        let mut block = Vec::with_capacity(data.len() + 4);
+        ebml::write_size(&mut block, track);
+        block.extend_from_slice(&relative.to_be_bytes());
+        match duration_ns {
+            Some(duration_ns) => {
+                block.push(0);
+                block.extend_from_slice(data);
+                ebml::master(&mut cluster.body, id::BLOCK_GROUP, |group| {
+                    ebml::binary(group, id::BLOCK, &block);
+                    ebml::uint(
+                        group,
+                        id::BLOCK_DURATION,
+                        (duration_ns.max(0) / TIMESTAMP_SCALE_NS) as u64,
+                    );
+                });
+            }
+            None => {
+                block.push(if keyframe { BLOCK_FLAG_KEYFRAME } else { 0 });
+                block.extend_from_slice(data);
+                ebml::binary(&mut cluster.body, id::SIMPLE_BLOCK, &block);
+            }
+        }
+        let end_ms = timestamp_ms + duration_ns.unwrap_or(0).max(0) / TIMESTAMP_SCALE_NS;
+        self.end_ms = self.end_ms.max(end_ms);
+        Ok(())
+    }
+
+    /// Write the open cluster out.
+    pub fn flush_cluster(&mut self) -> io::Result<()> {
+        let Some(cluster) = self.cluster.take() else {
+            return Ok(());
+        };
+        let mut element = Vec::with_capacity(cluster.body.len() + 16);
+        ebml::master(&mut element, id::CLUSTER, |body| {
+            ebml::uint(body, id::TIMESTAMP, cluster.timestamp_ms as u64);
+            body.extend_from_slice(&cluster.body);
+        });
+        self.emit(&element)?;
+        self.out.flush()
+    }
+
+    /// Close the file: the last cluster, then Cues, Duration, SeekHead and
+    /// the Segment size.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_cluster()?;
+
+        if !self.cues.is_empty() {
+            let mut cues = Vec::new();
+            ebml::master(&mut cues, id::CUES, |cues_body| {
+                for (time_ms, track, cluster_position) in &self.cues {
+                    ebml::master(cues_body, id::CUE_POINT, |cue_point| {
+                        ebml::uint(cue_point, id::CUE_TIME, *time_ms as u64);
+                        ebml::master(cue_point, id::CUE_TRACK_POSITIONS, |positions| {
+                            ebml::uint(positions, id::CUE_TRACK, *track);
+                            ebml::uint(positions, id::CUE_CLUSTER_POSITION, *cluster_position);
+                        });
+                    });
+                }
+            });
+            self.seek_entries.push((id::CUES, self.segment_offset()));
+            self.emit(&cues)?;
+        }
+        let end = self.position;
+
+        let mut seek_head = Vec::new();
+        ebml::master(&mut seek_head, id::SEEK_HEAD, |seeks| {
+            for (element_id, position) in &self.seek_entries {
+                ebml::master(seeks, id::SEEK, |seek| {
+                    ebml::binary(seek, id::SEEK_ID, &element_id.to_be_bytes());
+                    ebml::uint(seek, id::SEEK_POSITION, *position);
+                });
+            }
+        });
+        let padding = SEEK_HEAD_RESERVED - seek_head.len();
+        ebml::void(&mut seek_head, padding);
+
+        self.patch(self.segment_start, &seek_head)?;
+        self.patch(self.duration_at, &(self.end_ms as f64).to_be_bytes())?;
+        self.patch(
+            self.segment_size_at,
+            &ebml::size_8(end - self.segment_start),
+        )?;
+        self.out.seek(SeekFrom::Start(end))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.out.write_all(bytes)?;
+        self.position += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn patch(&mut self, at: u64, bytes: &[u8]) -> io::Result<()> {
+        self.out.seek(SeekFrom::Start(at))?;
+        self.out.write_all(bytes)
+    }
+
+    fn segment_offset(&self) -> u64 {
+        self.position - self.segment_start
+    }
+}
+
+fn track_entry(entries: &mut Vec<u8>, number: u64, track: &Track, codec_id: &str) {
+    ebml::master(entries, id::TRACK_ENTRY, |entry| {
+        ebml::uint(entry, id::TRACK_NUMBER, number);
+        ebml::uint(entry, id::TRACK_UID, number);
+        ebml::uint(entry, id::TRACK_TYPE, track.codec.track_type());
+        ebml::uint(entry, id::FLAG_LACING, 0);
+        if let Some(name) = &track.name {
+            ebml::string(entry, id::NAME, name);
+        }
+        if let Some(language) = &track.language {
+            ebml::string(entry, id::LANGUAGE, language);
+        }
+        ebml::string(entry, id::CODEC_ID, codec_id);
+        match &track.codec {
+            TrackCodec::Vp9 { width, height } | TrackCodec::Av1 { width, height, .. } => {
+                if let TrackCodec::Av1 { codec_private, .. } = &track.codec {
+                    ebml::binary(entry, id::CODEC_PRIVATE, codec_private);
+                }
+                ebml::master(entry, id::VIDEO, |video| {
+                    ebml::uint(video, id::PIXEL_WIDTH, u64::from(*width));
+                    ebml::uint(video, id::PIXEL_HEIGHT, u64::from(*height));
+                });
+            }
+            TrackCodec::Opus {
+                channels,
+                sample_rate,
+                pre_skip,
+            } => {
+                ebml::binary(
+                    entry,
+                    id::CODEC_PRIVATE,
+                    &opus_head(*channels, *sample_rate, *pre_skip),
+                );
+                // Pre-skip is counted at 48 kHz whatever the input rate.
+                ebml::uint(
+                    entry,
+                    id::CODEC_DELAY,
+                    u64::from(*pre_skip) * 1_000_000_000 / 48_000,
+                );
+                ebml::uint(entry, id::SEEK_PRE_ROLL, OPUS_SEEK_PRE_ROLL_NS);
+                ebml::master(entry, id::AUDIO, |audio| {
+                    ebml::float(audio, id::SAMPLING_FREQUENCY, f64::from(*sample_rate));
+                    ebml::uint(audio, id::CHANNELS, u64::from(*channels));
+                });
+            }
+            TrackCodec::Subtitle | TrackCodec::Timecode | TrackCodec::Data => {}
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Reads one element header: `(id, size, header length)`.
+    fn element_header(data: &[u8]) -> (u32, u64, usize) {
+        let id_len = data[0].leading_zeros() as usize + 1;
+        let element_id = data[..id_len]
+            .iter()
+            .fold(0u32, |id, byte| (id << 8) | u32::from(*byte));
+        let size_len = data[id_len].leading_zeros() as usize + 1;
+        let size = data[id_len..id_len + size_len]
+            .iter()
+            .fold(0u64, |size, byte| (size << 8) | u64::from(*byte))
+            & ((1u64 << (7 * size_len)) - 1);
+        (element_id, size, id_len + size_len)
+    }
+
+    /// The IDs of the Segment's children, in order.
+    fn segment_children(file: &[u8]) -> Vec<u32> {
+        let (_, ebml_size, ebml_header_len) = element_header(file);
+        let segment = &file[ebml_header_len + ebml_size as usize..];
+        let (segment_id, segment_size, segment_header_len) = element_header(segment);
+        assert_eq!(segment_id, id::SEGMENT);
+        let mut body = &segment[segment_header_len..];
+        assert_eq!(body.len() as u64, segment_size);
+        let mut children = Vec::new();
+        while !body.is_empty() {
+            let (child_id, size, header_len) = element_header(body);
+            children.push(child_id);
+            body = &body[header_len + size as usize..];
+        }
+        children
+    }
+
+    fn tracks() -> Vec<Track> {
+        vec![
+            Track {
+                codec: TrackCodec::Vp9 {
+                    width: 1280,
+                    height: 720,
+                },
+                name: None,
+                language: None,
+            },
+            Track {
+                codec: TrackCodec::Opus {
+                    channels: 2,
+                    sample_rate: 48_000,
+                    pre_skip: 312,
+                },
+                name: None,
+                language: None,
+            },
+            Track {
+                codec: TrackCodec::Subtitle,
+                name: Some("captions".into()),
+                language: Some("en".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn clusters_start_at_video_keyframes_and_close_out_the_segment() {
+        let tags = vec![("TITLE".to_string(), "Show".to_string())];
+        let mut writer =
+            MatroskaWriter::new(Cursor::new(Vec::new()), DocType::WebM, &tracks(), &tags).unwrap();
+        for frame in 0..6i64 {
+            let timestamp_ns = frame * 500_000_000;
+            writer
+                .write_block(1, timestamp_ns, frame % 3 == 0, &[0xAA; 16], None)
+                .unwrap();
+            writer
+                .write_block(2, timestamp_ns + 10_000_000, true, &[0xBB; 8], None)
+                .unwrap();
+        }
+        writer
+            .write_block(3, 1_000_000_000, true, b"Hello", Some(1_500_000_000))
+            .unwrap();
+        let file = writer.finish().unwrap().into_inner();
+
+        assert_eq!(
+            segment_children(&file),
+            [
+                id::SEEK_HEAD,
+                id::VOID,
+                id::INFO,
+                id::TRACKS,
+                id::TAGS,
+                id::CLUSTER,
+                id::CLUSTER,
+                id::CUES
+            ]
+        );
+        let duration_at = file
+            .windows(2)
+            .position(|window| window == [0x44, 0x89])
+            .unwrap()
+            + 3;
+        let duration = f64::from_be_bytes(file[duration_at..duration_at + 8].try_into().unwrap());
+        assert_eq!(duration, 2_510.0);
+    }
+
+    #[test]
+    fn audio_only_clusters_roll_on_time() {
+        let opus = vec![tracks().remove(1)];
+        let mut writer =
+            MatroskaWriter::new(Cursor::new(Vec::new()), DocType::Matroska, &opus, &[]).unwrap();
+        for packet in 0..600i64 {
+            writer
+                .write_block(1, packet * 20_000_000, true, &[0; 4], None)
+                .unwrap();
+        }
+        let file = writer.finish().unwrap().into_inner();
+        let clusters = segment_children(&file)
+            .into_iter()
+            .filter(|child| *child == id::CLUSTER)
+            .count();
+        assert_eq!(clusters, 3);
+    }
+
+    #[test]
+    fn webm_rejects_data_tracks() {
+        let data = [Track {
+            codec: TrackCodec::Data,
+            name: None,
+            language: None,
+        }];
+        assert!(MatroskaWriter::new(Cursor::new(Vec::new()), DocType::WebM, &data, &[]).is_err());
+        assert!(
+            MatroskaWriter::new(Cursor::new(Vec::new()), DocType::Matroska, &data, &[]).is_ok()
+        );
+    }
+
+    #[test]
+    fn doc_type_follows_the_extension() {
+        assert_eq!(
+            DocType::for_path(Path::new("/rec/show.WebM")),
+            DocType::WebM
+        );
+        assert_eq!(
+            DocType::for_path(Path::new("/rec/show.mkv")),
+            DocType::Matroska
+        );
+    }
+}
@@ -0,0 +1,298 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MKV writer — encoded video, audio and timed metadata into one Matroska
+//! or WebM file.
+//!
+//! The tracks come from config, since Matroska declares them before the
+//! first block. The file is opened at the first video keyframe (an AV1
+//! track's `CodecPrivate` is taken from it), or at the first frame of any
+//! kind when there's no video track; earlier frames are dropped, as
+//! nothing before the first keyframe could be decoded. Timestamps in the
+//! file count from that first frame.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::_generated_::tatolab__mkv::mkv_writer_config::{Codec, Kind};
+use crate::_generated_::{EncodedAudioFrame, EncodedVideoFrame, MkvMetadataFrame};
+use crate::codec_private::{av1_block, av1_codec_private};
+use crate::matroska::{DocType, MatroskaWriter, Track, TrackCodec};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::processors::ReactiveProcessor;
+
+/// libopus's encoder lookahead at 48 kHz.
+const DEFAULT_OPUS_PRE_SKIP: u16 = 312;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/mkv/MkvWriter",
+    description = "Muxes encoded VP9/AV1 video, Opus audio and timed metadata tracks into a Matroska or WebM file (by extension). Clusters are written as they close, so a crash keeps everything up to the last one; seek cues and the duration are added on stop.",
+    execution = reactive,
+    config = crate::_generated_::MkvWriterConfig,
+    input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", delivery_profile = "lossless", description = "Encoded VP9 or AV1 frames"),
+    input("encoded_audio_in", "@tatolab/core/EncodedAudioFrame", delivery_profile = "lossless", description = "Encoded Opus packets"),
+    input("metadata_in", "@tatolab/mkv/MkvMetadataFrame", delivery_profile = "lossless", description = "Subtitle cues, timecodes and data packets for the metadata tracks"),
+)]
+pub struct MkvWriterProcessor {
+    doc_type: Option<DocType>,
+
+    /// Tracks in file order; an AV1 track's `CodecPrivate` is filled in at
+    /// the first keyframe.
+    tracks: Vec<Track>,
+    video_track: Option<u64>,
+    audio_track: Option<u64>,
+    metadata_tracks: HashMap<String, u64>,
+
+    /// Opened at the first frame that can start the file.
+    writer: Option<MatroskaWriter<BufWriter<File>>>,
+
+    /// Timestamp of the first frame written; the file's zero.
+    origin_ns: i64,
+
+    /// Frames dropped while waiting for the first video keyframe.
+    frames_before_start: u64,
+
+    /// Inputs or metadata track names already warned about, so a stream of
+    /// unconfigured frames logs once.
+    warned: HashSet<String>,
+
+    blocks_written: u64,
+}
+
+impl ReactiveProcessor for MkvWriterProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let doc_type = DocType::for_path(Path::new(&self.config.output_path));
+
+        if let Some(video) = &self.config.video {
+            let codec = match video.codec {
+                Codec::Vp9 => TrackCodec::Vp9 {
+                    width: video.width,
+                    height: video.height,
+                },
+                Codec::Av1 => TrackCodec::Av1 {
+                    width: video.width,
+                    height: video.height,
+                    codec_private: Vec::new(),
+                },
+            };
+            self.video_track = Some(self.add_track(codec, None, None));
+        }
+        if let Some(audio) = &self.config.audio {
+            if !(1..=2).contains(&audio.channels) {
+                return Err(Error::Configuration(format!(
+                    "MkvWriter: Opus tracks are mono or stereo, got {} channels",
+                    audio.channels
+                )));
+            }
+            let codec = TrackCodec::Opus {
+                channels: audio.channels,
+                sample_rate: audio.sample_rate,
+                pre_skip: audio.pre_skip.unwrap_or(DEFAULT_OPUS_PRE_SKIP),
+            };
+            self.audio_track = Some(self.add_track(codec, None, None));
+        }
+        for metadata_track in self.config.metadata_tracks.clone().unwrap_or_default() {
+            let codec = match metadata_track.kind {
+                Kind::Subtitle => TrackCodec::Subtitle,
+                Kind::Timecode => TrackCodec::Timecode,
+                Kind::Data => TrackCodec::Data,
+            };
+            if codec.codec_id(doc_type).is_none() {
+                return Err(Error::Configuration(format!(
+                    "MkvWriter: metadata track '{}' ({:?}) needs a Matroska file, not WebM",
+                    metadata_track.name, metadata_track.kind
+                )));
+            }
+            let number = self.add_track(
+                codec,
+                Some(metadata_track.name.clone()),
+                metadata_track.language,
+            );
+            if self
+                .metadata_tracks
+                .insert(metadata_track.name.clone(), number)
+                .is_some()
+            {
+                return Err(Error::Configuration(format!(
+                    "MkvWriter: metadata track '{}' is configured twice",
+                    metadata_track.name
+                )));
+            }
+        }
+        if self.tracks.is_empty() {
+            return Err(Error::Configuration(
+                "MkvWriter: configure at least one of video, audio or metadata_tracks".into(),
+            ));
+        }
+
+        self.doc_type = Some(doc_type);
+        tracing::info!(
+            "[MkvWriter] Initialized (output: {}, {:?}, {} tracks)",
+            self.config.output_path,
+            doc_type,
+            self.tracks.len()
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        match self.writer.take() {
+            Some(writer) => {
+                writer.finish()?;
+                tracing::info!(
+                    blocks = self.blocks_written,
+                    "[MkvWriter] Written to {}",
+                    self.config.output_path
+                );
+            }
+            None => tracing::warn!("[MkvWriter] No frames received, skipping file creation"),
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("encoded_video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("encoded_video_in")?;
+            self.write_video(frame)?;
+        }
+        while self.inputs.has_data("encoded_audio_in") {
+            let frame: EncodedAudioFrame = self.inputs.read("encoded_audio_in")?;
+            let Some(track) = self.configured(self.audio_track, "encoded_audio_in") else {
+                continue;
+            };
+            let timestamp_ns = frame.timestamp_ns.parse().unwrap_or(0);
+            self.write(track, timestamp_ns, true, &frame.data, None)?;
+        }
+        while self.inputs.has_data("metadata_in") {
+            let frame: MkvMetadataFrame = self.inputs.read("metadata_in")?;
+            let track = self.metadata_tracks.get(&frame.track).copied();
+            let Some(track) = self.configured(track, &frame.track) else {
+                continue;
+            };
+            let timestamp_ns = frame.timestamp_ns.parse().unwrap_or(0);
+            let duration_ns = frame
+                .duration_ns
+                .as_deref()
+                .and_then(|duration| duration.parse().ok());
+            self.write(track, timestamp_ns, true, &frame.data, duration_ns)?;
+        }
+        Ok(())
+    }
+}
+
+impl MkvWriterProcessor::Processor {
+    /// Append a track, returning its number.
+    fn add_track(
+        &mut self,
+        codec: TrackCodec,
+        name: Option<String>,
+        language: Option<String>,
+    ) -> u64 {
+        self.tracks.push(Track {
+            codec,
+            name,
+            language,
+        });
+        self.tracks.len() as u64
+    }
+
+    /// `track`, or `None` — with a warning the first time — when frames
+    /// arrive for a track that isn't configured.
+    fn configured(&mut self, track: Option<u64>, source: &str) -> Option<u64> {
+        if track.is_none() && self.warned.insert(source.to_string()) {
+            tracing::warn!(
+                "[MkvWriter] Dropping frames for '{source}': no track configured for it"
+            );
+        }
+        track
+    }
+
+    fn write_video(&mut self, frame: EncodedVideoFrame) -> Result<()> {
+        let Some(track) = self.configured(self.video_track, "encoded_video_in") else {
+            return Ok(());
+        };
+        let timestamp_ns = frame.timestamp_ns.parse().unwrap_or(0);
+        let index = (track - 1) as usize;
+        let data = match &mut self.tracks[index].codec {
+            TrackCodec::Av1 { codec_private, .. } => {
+                if self.writer.is_none() && frame.is_keyframe {
+                    match av1_codec_private(&frame.data) {
+                        Some(private) => *codec_private = private,
+                        None => {
+                            tracing::warn!(
+                                "[MkvWriter] AV1 keyframe without a sequence header, waiting for the next"
+                            );
+                            return Ok(());
+                        }
+                    }
+                }
+                av1_block(&frame.data)
+            }
+            _ => frame.data,
+        };
+        if self.writer.is_none() && frame.is_keyframe {
+            self.open(timestamp_ns)?;
+        }
+        self.write(track, timestamp_ns, frame.is_keyframe, &data, None)
+    }
+
+    /// Create the file, counting time from `origin_ns`.
+    fn open(&mut self, origin_ns: i64) -> Result<()> {
+        let doc_type = self
+            .doc_type
+            .ok_or_else(|| Error::Runtime("MkvWriter: not set up".into()))?;
+        let file = File::create(&self.config.output_path).map_err(|e| {
+            Error::Runtime(format!("Failed to create {}: {e}", self.config.output_path))
+        })?;
+        let tags: Vec<(String, String)> = self
+            .config
+            .tags
+            .iter()
+            .flatten()
+            .map(|tag| (tag.name.clone(), tag.value.clone()))
+            .collect();
+        self.writer = Some(MatroskaWriter::new(
+            BufWriter::new(file),
+            doc_type,
+            &self.tracks,
+            &tags,
+        )?);
+        self.origin_ns = origin_ns;
+        if self.frames_before_start > 0 {
+            tracing::info!(
+                dropped = self.frames_before_start,
+                "[MkvWriter] Started at the first video keyframe"
+            );
+        }
+        Ok(())
+    }
+
+    fn write(
+        &mut self,
+        track: u64,
+        timestamp_ns: i64,
+        keyframe: bool,
+        data: &[u8],
+        duration_ns: Option<i64>,
+    ) -> Result<()> {
+        if self.writer.is_none() && self.video_track.is_none() {
+            self.open(timestamp_ns)?;
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            self.frames_before_start += 1;
+            return Ok(());
+        };
+        writer.write_block(
+            track,
+            timestamp_ns - self.origin_ns,
+            keyframe,
+            data,
+            duration_ns,
+        )?;
+        self.blocks_written += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MoQ Publish — publishes encoded video and audio as two MoQ tracks.
+//!
+//! Unlike [`crate::MoqPublishTrackProcessor`], which forwards opaque bytes,
+//! this processor knows its media: video goes out one MoQ group per GOP
+//! (a keyframe opens a group, frames before the first keyframe are
+//! dropped) and audio in groups of `audio_frames_per_group` frames — see
+//! [`streamlib_moq::moq_media_mapping`]. Objects are the msgpack-encoded
+//! frames exactly as they arrive, so [`crate::MoqSubscribeProcessor`] on
+//! the far side emits the same `EncodedVideoFrame` / `EncodedAudioFrame`.
+
+use crate::_generated_::EncodedVideoFrame;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use streamlib_moq::{
+    MoqAudioGroupMapper, MoqObjectPlacement, MoqPublishSession, MoqVideoGroupMapper,
+    SharedMoqSessions, sessions_for_runtime,
+};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+pub(crate) const DEFAULT_VIDEO_TRACK: &str = "video";
+pub(crate) const DEFAULT_AUDIO_TRACK: &str = "audio";
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/moq/MoqPublish",
+    description = "Publishes encoded video (one MoQ group per GOP) and audio to a MoQ relay",
+    execution = reactive,
+    config = crate::_generated_::MoqPublishConfig,
+    input("video_in", "@tatolab/core/EncodedVideoFrame", description = "Encoded video to publish on the video track"),
+    input("audio_in", "@tatolab/core/EncodedAudioFrame", description = "Encoded audio to publish on the audio track"),
+)]
+pub struct MoqPublishProcessor {
+    shared_publish_session: Option<Arc<Mutex<MoqPublishSession>>>,
+    sessions: Option<SharedMoqSessions>,
+    video_track: String,
+    audio_track: String,
+    video_groups: MoqVideoGroupMapper,
+    audio_groups: Option<MoqAudioGroupMapper>,
+    video_frames_published: u64,
+    video_frames_dropped: u64,
+    audio_frames_published: u64,
+    /// Plugin-owned tokio runtime, as in `MoqPublishTrack`: drives
+    /// `get_publish_session().await` from sync `setup()` and keeps the
+    /// session's QUIC tasks alive.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for MoqPublishProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("MoqPublish: failed to build tokio runtime: {e}"))
+            })?;
+
+        self.video_track = self
+            .config
+            .video_track
+            .clone()
+            .unwrap_or_else(|| DEFAULT_VIDEO_TRACK.to_string());
+        self.audio_track = self
+            .config
+            .audio_track
+            .clone()
+            .unwrap_or_else(|| DEFAULT_AUDIO_TRACK.to_string());
+        if self.video_track == self.audio_track {
+            return Err(Error::Configuration(format!(
+                "MoqPublish: video and audio can't share the track '{}'",
+                self.video_track
+            )));
+        }
+        let audio_groups =
+            MoqAudioGroupMapper::new(self.config.audio_frames_per_group.unwrap_or(1));
+
+        let sessions = sessions_for_runtime(&ctx.runtime_id().to_string());
+        let session = runtime.block_on(sessions.get_publish_session())?;
+        sessions.register_published_track(&self.video_track);
+        sessions.register_published_track(&self.audio_track);
+
+        tracing::info!(
+            broadcast = %sessions.broadcast_path(),
+            video_track = %self.video_track,
+            audio_track = %self.audio_track,
+            audio_frames_per_group = audio_groups.frames_per_group(),
+            "[MoqPublish] Using shared session"
+        );
+
+        self.audio_groups = Some(audio_groups);
+        self.shared_publish_session = Some(session);
+        self.sessions = Some(sessions);
+        self.tokio_runtime = Some(runtime);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            video_frames_published = self.video_frames_published,
+            video_frames_dropped = self.video_frames_dropped,
+            audio_frames_published = self.audio_frames_published,
+            "[MoqPublish] Shutting down"
+        );
+        self.shared_publish_session.take();
+        self.sessions.take();
+        self.tokio_runtime.take();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if self.inputs.has_data("video_in") {
+            if let Some((bytes, _timestamp_ns)) = self.inputs.read_raw("video_in")? {
+                self.publish_video(&bytes)?;
+            }
+        }
+        if self.inputs.has_data("audio_in") {
+            if let Some((bytes, _timestamp_ns)) = self.inputs.read_raw("audio_in")? {
+                self.publish_audio(&bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MoqPublishProcessor::Processor {
+    fn publish_video(&mut self, bytes: &[u8]) -> Result<()> {
+        // Only the keyframe flag is needed; the object is the frame's
+        // bytes as received.
+        let is_keyframe = rmp_serde::from_slice::<EncodedVideoFrame>(bytes)
+            .map(|frame| frame.is_keyframe)
+            .map_err(|e| Error::Runtime(format!("MoqPublish: undecodable video frame: {e}")))?;
+        let placement = self.video_groups.place(is_keyframe);
+        if placement == MoqObjectPlacement::Drop {
+            self.video_frames_dropped += 1;
+            if self.video_frames_dropped == 1 {
+                tracing::info!(
+                    track = %self.video_track,
+                    "[MoqPublish] Dropping video until the first keyframe"
+                );
+            }
+            return Ok(());
+        }
+
+        let result = self.session()?.lock().publish_frame(
+            &self.video_track,
+            bytes,
+            placement.starts_group(),
+        );
+        if let Err(e) = result {
+            // The GOP's group is gone; resume at the next keyframe.
+            self.video_groups.reset();
+            return Err(e);
+        }
+
+        self.video_frames_published += 1;
+        if self.video_frames_published == 1 {
+            tracing::info!(track = %self.video_track, "[MoqPublish] First video frame published");
+        } else if self.video_frames_published % 300 == 0 {
+            tracing::info!(
+                frames = self.video_frames_published,
+                "[MoqPublish] Video publish progress"
+            );
+        }
+        Ok(())
+    }
+
+    fn publish_audio(&mut self, bytes: &[u8]) -> Result<()> {
+        let audio_groups = self
+            .audio_groups
+            .as_mut()
+            .ok_or_else(|| Error::Runtime("MoqPublish not initialized".into()))?;
+        let placement = audio_groups.place();
+
+        let result = self.session()?.lock().publish_frame(
+            &self.audio_track,
+            bytes,
+            placement.starts_group(),
+        );
+        if let Err(e) = result {
+            if let Some(audio_groups) = self.audio_groups.as_mut() {
+                audio_groups.reset();
+            }
+            return Err(e);
+        }
+
+        self.audio_frames_published += 1;
+        if self.audio_frames_published == 1 {
+            tracing::info!(track = %self.audio_track, "[MoqPublish] First audio frame published");
+        }
+        Ok(())
+    }
+
+    fn session(&self) -> Result<&Arc<Mutex<MoqPublishSession>>> {
+        self.shared_publish_session
+            .as_ref()
+            .ok_or_else(|| Error::Runtime("MoQ session not connected".into()))
+    }
+}
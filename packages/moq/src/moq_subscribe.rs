@@ -0,0 +1,142 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MoQ Subscribe — the receiving end of [`crate::MoqPublishProcessor`].
+//!
+//! Subscribes to the broadcast's video and audio tracks and emits their
+//! objects as `EncodedVideoFrame` / `EncodedAudioFrame`. Each track runs
+//! its own receive loop (reconnecting with backoff like
+//! `MoqSubscribeTrack`), so a stalled audio track never holds up video.
+//! Because the publisher opens groups at keyframes, a late joiner starts
+//! at the next GOP.
+
+use crate::moq_publish::{DEFAULT_AUDIO_TRACK, DEFAULT_VIDEO_TRACK};
+use crate::moq_subscribe_track::run_moq_subscribe_track_receive_loop_with_retry;
+use streamlib_moq::sessions_for_runtime;
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/moq/MoqSubscribe",
+    description = "Subscribes to a MoqPublish broadcast and outputs its encoded video and audio",
+    execution = manual,
+    config = crate::_generated_::MoqSubscribeConfig,
+    output("video_out", "@tatolab/core/EncodedVideoFrame", description = "Encoded video received on the video track"),
+    output("audio_out", "@tatolab/core/EncodedAudioFrame", description = "Encoded audio received on the audio track"),
+)]
+pub struct MoqSubscribeProcessor {
+    runtime_id: Option<String>,
+    /// Plugin-owned tokio runtime, as in `MoqSubscribeTrack`: MoQ's QUIC
+    /// futures need it, and it hosts both receive loops.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+    tokio_handle: Option<tokio::runtime::Handle>,
+    shutdown_signal_senders: Vec<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for MoqSubscribeProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("MoqSubscribe: failed to build tokio runtime: {e}"))
+            })?;
+        let (video_track, audio_track) = self.track_names();
+        if video_track == audio_track {
+            return Err(Error::Configuration(format!(
+                "MoqSubscribe: video and audio can't share the track '{video_track}'"
+            )));
+        }
+
+        self.tokio_handle = Some(runtime.handle().clone());
+        self.tokio_runtime = Some(runtime);
+        self.runtime_id = Some(ctx.runtime_id().to_string());
+
+        let sessions = sessions_for_runtime(self.runtime_id.as_ref().unwrap());
+        tracing::info!(
+            broadcast = %sessions.broadcast_path(),
+            video_track = %video_track,
+            audio_track = %audio_track,
+            "[MoqSubscribe] Configured (will connect on start)"
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!("[MoqSubscribe] Shutting down");
+        self.signal_shutdown();
+        self.runtime_id.take();
+        self.tokio_handle.take();
+        self.tokio_runtime.take();
+        tracing::info!("[MoqSubscribe] Shutdown complete");
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime_id = self
+            .runtime_id
+            .clone()
+            .ok_or_else(|| Error::Runtime("runtime_id not captured in setup()".into()))?;
+        let handle = self
+            .tokio_handle
+            .clone()
+            .ok_or_else(|| Error::Runtime("tokio handle not captured in setup()".into()))?;
+
+        let (video_track, audio_track) = self.track_names();
+        for (track_name, output_port) in [(video_track, "video_out"), (audio_track, "audio_out")] {
+            let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+            self.shutdown_signal_senders.push(shutdown_tx);
+            let outputs = self.outputs.clone();
+            let runtime_id = runtime_id.clone();
+            handle.spawn(async move {
+                run_moq_subscribe_track_receive_loop_with_retry(
+                    track_name,
+                    runtime_id,
+                    outputs,
+                    output_port,
+                    shutdown_rx,
+                )
+                .await;
+            });
+        }
+
+        tracing::info!("[MoqSubscribe] Started video and audio receive loops");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.signal_shutdown();
+        tracing::info!("[MoqSubscribe] Stopped");
+        Ok(())
+    }
+}
+
+impl MoqSubscribeProcessor::Processor {
+    fn track_names(&self) -> (String, String) {
+        (
+            self.config
+                .video_track
+                .clone()
+                .unwrap_or_else(|| DEFAULT_VIDEO_TRACK.to_string()),
+            self.config
+                .audio_track
+                .clone()
+                .unwrap_or_else(|| DEFAULT_AUDIO_TRACK.to_string()),
+        )
+    }
+
+    fn signal_shutdown(&mut self) {
+        for tx in self.shutdown_signal_senders.drain(..) {
+            let _ = tx.send(());
+        }
+    }
+}
@@ -99,7 +99,7 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for MoqSubscribeTrac
 
         handle.clone().spawn(async move {
             run_moq_subscribe_track_receive_loop_with_retry(
-                track_name, runtime_id, outputs, shutdown_rx,
+                track_name, runtime_id, outputs, "data_out", shutdown_rx,
             )
             .await;
         });
@@ -120,14 +120,17 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for MoqSubscribeTrac
     }
 }
 
-/// Outer loop — opens / re-opens the subscribe session on failure.
-async fn run_moq_subscribe_track_receive_loop_with_retry(
+/// Outer loop — opens / re-opens the subscribe session on failure and
+/// forwards each received object to `output_port`. Shared with
+/// [`crate::MoqSubscribeProcessor`], which runs one per track.
+pub(crate) async fn run_moq_subscribe_track_receive_loop_with_retry(
     track_name: String,
     runtime_id: String,
     outputs: OutputWriter,
+    output_port: &'static str,
     mut shutdown_rx: tokio::sync::oneshot::Receiver<()>,
 ) {
-    let has_output_port = outputs.has_port("data_out");
+    let has_output_port = outputs.has_port(output_port);
     if !has_output_port {
         tracing::info!(
             track = %track_name,
@@ -206,6 +209,7 @@ async fn run_moq_subscribe_track_receive_loop_with_retry(
             &track_name,
             track_reader,
             &outputs,
+            output_port,
             has_output_port,
             &mut total_frames,
             &mut shutdown_rx,
@@ -261,6 +265,7 @@ async fn run_receive_loop(
     track_name: &str,
     mut track_reader: MoqTrackReader,
     outputs: &OutputWriter,
+    output_port: &str,
     has_output_port: bool,
     total_frames: &mut u64,
     shutdown_rx: &mut tokio::sync::oneshot::Receiver<()>,
@@ -280,7 +285,7 @@ async fn run_receive_loop(
 
                                     if has_output_port {
                                         let timestamp_ns = MediaClock::now().as_nanos() as i64;
-                                        if let Err(e) = outputs.write_raw("data_out", &frame_bytes, timestamp_ns) {
+                                        if let Err(e) = outputs.write_raw(output_port, &frame_bytes, timestamp_ns) {
                                             tracing::warn!(
                                                 track = %track_name,
                                                 %e,
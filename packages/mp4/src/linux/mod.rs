@@ -2,3 +2,4 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 pub mod mp4_writer;
+mod segmenter;
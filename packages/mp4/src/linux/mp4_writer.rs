@@ -6,12 +6,15 @@ use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContext
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 use streamlib_plugin_sdk::sdk::processors::ReactiveProcessor;
 
+use super::segmenter::{self, ClosedSegment, SegmentFinalizer, SegmentLimits, SegmentRecord};
+
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 
 #[streamlib_plugin_sdk::sdk::processor(
     "@tatolab/mp4/LinuxMp4Writer",
-    description = "Writes video frames to MP4 via ffmpeg encode + mux with silent audio track",
+    description = "Writes video frames to MP4 via ffmpeg encode + mux with silent audio track, optionally split into rolling segments",
     execution = reactive,
     config = crate::_generated_::LinuxMp4WriterConfig,
     input("video_in", "@tatolab/core/VideoFrame", delivery_profile = "lossless", description = "Decoded video frames (raw pixels) to encode and write"),
@@ -19,47 +22,79 @@ use std::process::{Child, Command, Stdio};
 pub struct LinuxMp4WriterProcessor {
     gpu_context: Option<GpuContextLimitedAccess>,
 
-    /// ffmpeg child process (spawned on first frame).
-    ffmpeg_process: Option<Child>,
+    limits: SegmentLimits,
+
+    /// Width, height and fps, fixed by the first frame.
+    format: Option<(u32, u32, u32)>,
+
+    /// The file ffmpeg is writing now (spawned on first frame and again at
+    /// each segment boundary).
+    segment: Option<OpenSegment>,
+
+    /// Segments opened so far.
+    segments_opened: u32,
+
+    /// Finishes closed segments in the background.
+    finalizer: Option<SegmentFinalizer>,
 
     /// Frames received counter.
     frames_received: u64,
 }
 
+/// The segment ffmpeg is currently writing.
+struct OpenSegment {
+    ffmpeg_process: Child,
+    index: u32,
+    /// Where ffmpeg writes: `final_path` with a `.partial` suffix, or
+    /// `final_path` itself when fragmented.
+    partial_path: PathBuf,
+    final_path: PathBuf,
+    /// `frames_received` when the segment was opened.
+    start_frame: u64,
+    /// `frames_received` when the file was last flushed to disk.
+    synced_frame: u64,
+}
+
 impl ReactiveProcessor for LinuxMp4WriterProcessor::Processor {
     fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
         self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.limits = SegmentLimits::new(self.config.segment_duration_secs, self.config.segment_max_mb)?;
+        if self.config.fragment_ms == Some(0) {
+            return Err(Error::Config("LinuxMp4Writer: fragment_ms must be positive".into()));
+        }
+        let manifest_path = self
+            .limits
+            .is_segmenting()
+            .then(|| segmenter::manifest_path(Path::new(&self.config.output_path)));
+        self.finalizer = Some(SegmentFinalizer::spawn(manifest_path)?);
         tracing::info!(
-            "[LinuxMp4Writer] Initialized (output: {}, config fps: {})",
+            "[LinuxMp4Writer] Initialized (output: {}, config fps: {}, segments: {:?}, fragment_ms: {:?})",
             self.config.output_path,
             self.config.fps,
+            self.limits,
+            self.config.fragment_ms,
         );
         Ok(())
     }
 
     fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
-        if let Some(mut child) = self.ffmpeg_process.take() {
-            // Closing stdin signals ffmpeg that input is done.
-            drop(child.stdin.take());
-
-            let output = child.wait_with_output().map_err(|e| {
-                Error::Runtime(format!("Failed to wait for ffmpeg: {e}"))
-            })?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(Error::Runtime(format!(
-                    "ffmpeg exited with status {}: {stderr}", output.status
-                )));
-            }
+        if self.segment.is_some() {
+            self.close_segment()?;
+        } else {
+            tracing::warn!("[LinuxMp4Writer] No frames received, skipping MP4 creation");
+        }
 
+        // Waits for ffmpeg to finish every segment still being written.
+        if let Some(finalizer) = self.finalizer.take() {
+            finalizer.join()?;
+        }
+        if self.segments_opened > 0 {
             tracing::info!(
                 frames = self.frames_received,
+                segments = self.segments_opened,
                 "[LinuxMp4Writer] MP4 written to {}",
                 self.config.output_path
             );
-        } else {
-            tracing::warn!("[LinuxMp4Writer] No frames received, skipping MP4 creation");
         }
 
         self.gpu_context.take();
@@ -87,62 +122,37 @@ impl ReactiveProcessor for LinuxMp4WriterProcessor::Processor {
         }
         let raw_data = unsafe { std::slice::from_raw_parts(raw_ptr, frame_byte_size) };
 
-        // ffmpeg spawns lazily on the first frame so width/height/fps come from the frame, not config.
-        if self.ffmpeg_process.is_none() {
+        // Width/height/fps come from the first frame, not config.
+        let (_, _, fps) = *self.format.get_or_insert_with(|| {
             let fps = frame.fps.unwrap_or(self.config.fps);
-            let width = frame.width;
-            let height = frame.height;
-
             tracing::info!(
                 "[LinuxMp4Writer] First frame: {}x{}, {}fps{} — spawning ffmpeg",
-                width, height, fps,
+                frame.width, frame.height, fps,
                 if frame.fps.is_some() { " from camera" } else { " from config" }
             );
+            (frame.width, frame.height, fps)
+        });
 
-            let duration_secs = self.config.duration_secs.map(|d| d.to_string());
-            let fps_str = fps.to_string();
-            let size_str = format!("{width}x{height}");
-
-            let mut args: Vec<&str> = vec![
-                "-y",
-                "-f", "rawvideo",
-                "-pix_fmt", "rgba",
-                "-s", &size_str,
-                "-r", &fps_str,
-                "-i", "pipe:0",
-            ];
-
-            // Silent audio track: fixed duration when configured; otherwise -shortest trims to video length when stdin closes.
-            if let Some(ref dur) = duration_secs {
-                args.extend_from_slice(&["-f", "lavfi", "-t", dur,
-                    "-i", "anullsrc=r=48000:cl=stereo"]);
-            } else {
-                args.extend_from_slice(&["-f", "lavfi",
-                    "-i", "anullsrc=r=48000:cl=stereo"]);
+        let frames_per_fragment = self.frames_per_fragment(fps);
+        if let Some(segment) = &self.segment {
+            // Only stat the file when a size limit can trip.
+            let bytes = match self.limits.max_bytes {
+                Some(_) => std::fs::metadata(&segment.partial_path).map_or(0, |m| m.len()),
+                None => 0,
+            };
+            if self.limits.is_full(self.frames_received - segment.start_frame, fps, bytes) {
+                self.close_segment()?;
             }
-
-            args.extend_from_slice(&[
-                "-c:v", "mpeg4",
-                "-q:v", "1",
-                "-c:a", "aac",
-                "-shortest",
-                "-movflags", "+faststart",
-                &self.config.output_path,
-            ]);
-
-            let child = Command::new("ffmpeg")
-                .args(&args)
-                .stdin(Stdio::piped())
-                .stdout(Stdio::null())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| Error::Runtime(format!("Failed to spawn ffmpeg: {e}")))?;
-
-            self.ffmpeg_process = Some(child);
+        }
+        if self.segment.is_none() {
+            self.open_segment()?;
         }
 
-        let child = self.ffmpeg_process.as_mut().unwrap();
-        let stdin = child.stdin.as_mut().ok_or_else(|| {
+        let segment = self
+            .segment
+            .as_mut()
+            .ok_or_else(|| Error::Runtime("ffmpeg not running".into()))?;
+        let stdin = segment.ffmpeg_process.stdin.as_mut().ok_or_else(|| {
             Error::Runtime("ffmpeg stdin not available".into())
         })?;
 
@@ -152,6 +162,17 @@ impl ReactiveProcessor for LinuxMp4WriterProcessor::Processor {
 
         self.frames_received += 1;
 
+        // Fragmented files are playable up to their last fragment, so push
+        // each one to disk rather than leaving it in the page cache.
+        if let Some(frames_per_fragment) = frames_per_fragment
+            && self.frames_received - segment.synced_frame >= frames_per_fragment
+        {
+            segment.synced_frame = self.frames_received;
+            if let Err(e) = std::fs::File::open(&segment.partial_path).and_then(|file| file.sync_data()) {
+                tracing::warn!("[LinuxMp4Writer] Failed to flush {}: {e}", segment.partial_path.display());
+            }
+        }
+
         if self.frames_received == 1 {
             tracing::info!("[LinuxMp4Writer] First frame written to ffmpeg");
         } else if self.frames_received % 300 == 0 {
@@ -164,3 +185,149 @@ impl ReactiveProcessor for LinuxMp4WriterProcessor::Processor {
         Ok(())
     }
 }
+
+impl LinuxMp4WriterProcessor::Processor {
+    /// Frames per fMP4 fragment, when writing fragmented MP4.
+    fn frames_per_fragment(&self, fps: u32) -> Option<u64> {
+        self.config
+            .fragment_ms
+            .map(|ms| (u64::from(fps) * u64::from(ms) / 1000).max(1))
+    }
+
+    /// Spawn ffmpeg for the next segment, starting at the current frame.
+    fn open_segment(&mut self) -> Result<()> {
+        let (width, height, fps) = self
+            .format
+            .ok_or_else(|| Error::Runtime("Segment opened before the first frame".into()))?;
+        let index = self.segments_opened + 1;
+        let final_path = segmenter::segment_path(
+            Path::new(&self.config.output_path),
+            index,
+            self.limits.is_segmenting(),
+        );
+        let frames_per_fragment = self.frames_per_fragment(fps);
+        // A fragmented file is playable as soon as its first fragment lands.
+        let partial_path = match frames_per_fragment {
+            Some(_) => final_path.clone(),
+            None => segmenter::partial_path(&final_path),
+        };
+
+        let fps_str = fps.to_string();
+        let size_str = format!("{width}x{height}");
+        // Later segments carry on the recording's timeline rather than restarting at zero.
+        let ts_offset = (self.frames_received as f64 / f64::from(fps.max(1))).to_string();
+        // A fixed duration describes the whole recording, so it can't size a segment's audio.
+        let duration_secs = self
+            .config
+            .duration_secs
+            .filter(|_| !self.limits.is_segmenting())
+            .map(|d| d.to_string());
+
+        let mut args: Vec<&str> = vec![
+            "-y",
+            "-f", "rawvideo",
+            "-pix_fmt", "rgba",
+            "-s", &size_str,
+            "-r", &fps_str,
+            "-i", "pipe:0",
+        ];
+
+        // Silent audio track: fixed duration when configured; otherwise -shortest trims to video length when stdin closes.
+        if let Some(ref dur) = duration_secs {
+            args.extend_from_slice(&["-f", "lavfi", "-t", dur,
+                "-i", "anullsrc=r=48000:cl=stereo"]);
+        } else {
+            args.extend_from_slice(&["-f", "lavfi",
+                "-i", "anullsrc=r=48000:cl=stereo"]);
+        }
+
+        args.extend_from_slice(&[
+            "-c:v", "mpeg4",
+            "-q:v", "1",
+            "-c:a", "aac",
+            "-shortest",
+            "-output_ts_offset", &ts_offset,
+            // The `.partial` suffix hides the container from ffmpeg's extension sniffing.
+            "-f", "mp4",
+        ]);
+        // Fragmented: an empty moov up front, then a moof/mdat fragment per
+        // GOP, flushed as written. Otherwise one moov, moved to the front on close.
+        let fragment_args = frames_per_fragment.map(|frames| {
+            let fragment_us = u64::from(self.config.fragment_ms.unwrap_or_default()) * 1000;
+            [frames.to_string(), fragment_us.to_string()]
+        });
+        match &fragment_args {
+            Some([gop, fragment_us]) => args.extend_from_slice(&[
+                "-g", gop,
+                "-movflags", "+frag_keyframe+empty_moov+default_base_moof",
+                "-frag_duration", fragment_us,
+                "-flush_packets", "1",
+            ]),
+            None => args.extend_from_slice(&["-movflags", "+faststart"]),
+        }
+        let partial_path_str = partial_path.to_string_lossy();
+        args.push(&partial_path_str);
+
+        let ffmpeg_process = Command::new("ffmpeg")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::Runtime(format!("Failed to spawn ffmpeg: {e}")))?;
+
+        if self.limits.is_segmenting() {
+            tracing::info!(
+                index,
+                start_frame = self.frames_received,
+                "[LinuxMp4Writer] Segment started: {}",
+                final_path.display()
+            );
+        }
+        self.segments_opened = index;
+        self.segment = Some(OpenSegment {
+            ffmpeg_process,
+            index,
+            partial_path,
+            final_path,
+            start_frame: self.frames_received,
+            synced_frame: self.frames_received,
+        });
+        Ok(())
+    }
+
+    /// Close ffmpeg's input for the open segment and hand it to the
+    /// finalizer, which waits for the file and renames it.
+    fn close_segment(&mut self) -> Result<()> {
+        let Some(mut segment) = self.segment.take() else {
+            return Ok(());
+        };
+        // Closing stdin signals ffmpeg that input is done.
+        drop(segment.ffmpeg_process.stdin.take());
+
+        let fps = f64::from(self.format.map_or(self.config.fps, |(_, _, fps)| fps).max(1));
+        let frames = self.frames_received - segment.start_frame;
+        let file = segment
+            .final_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let finalizer = self
+            .finalizer
+            .as_ref()
+            .ok_or_else(|| Error::Runtime("Segment finalizer not running".into()))?;
+        finalizer.finish(ClosedSegment {
+            child: segment.ffmpeg_process,
+            partial_path: segment.partial_path,
+            final_path: segment.final_path,
+            record: SegmentRecord {
+                index: segment.index,
+                file,
+                start_secs: segment.start_frame as f64 / fps,
+                duration_secs: frames as f64 / fps,
+                frames,
+                bytes: 0,
+            },
+        })
+    }
+}
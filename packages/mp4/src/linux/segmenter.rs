@@ -0,0 +1,315 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Recording segments for [`super::mp4_writer`].
+//!
+//! A long recording is split into numbered files so a crash loses at most
+//! the open segment rather than one huge file whose `moov` was never
+//! written. Every file but a fragmented one — playable throughout — is
+//! written under a `.partial` suffix and renamed once ffmpeg has finished
+//! it, so a complete-looking name is always a playable file. Closed segments are finished in order on a background
+//! thread — ffmpeg writing the trailer and relocating `moov` for
+//! `+faststart` takes a while, and the processor thread must keep taking
+//! frames meanwhile. When segmenting, the thread also keeps
+//! `<name>.manifest.json` up to date, listing each finished segment with
+//! its place on the recording's timeline.
+
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use serde::Serialize;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const BYTES_PER_MB: u64 = 1_000_000;
+
+/// Suffix of a file still being written.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// When the open segment is closed and the next one started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SegmentLimits {
+    pub max_duration_secs: Option<u32>,
+    pub max_bytes: Option<u64>,
+}
+
+impl SegmentLimits {
+    pub fn new(segment_duration_secs: Option<u32>, segment_max_mb: Option<u32>) -> Result<Self> {
+        if segment_duration_secs == Some(0) || segment_max_mb == Some(0) {
+            return Err(Error::Config(
+                "LinuxMp4Writer: segment limits must be positive".into(),
+            ));
+        }
+        Ok(Self {
+            max_duration_secs: segment_duration_secs,
+            max_bytes: segment_max_mb.map(|mb| u64::from(mb) * BYTES_PER_MB),
+        })
+    }
+
+    /// Whether the recording is split at all.
+    pub fn is_segmenting(&self) -> bool {
+        self.max_duration_secs.is_some() || self.max_bytes.is_some()
+    }
+
+    /// Whether a segment of `frames` frames at `fps`, `bytes` long on disk,
+    /// has reached a limit.
+    pub fn is_full(&self, frames: u64, fps: u32, bytes: u64) -> bool {
+        self.max_duration_secs
+            .is_some_and(|secs| frames >= u64::from(secs) * u64::from(fps))
+            || self.max_bytes.is_some_and(|max_bytes| bytes >= max_bytes)
+    }
+}
+
+/// Path of segment `index` (from 1) of a recording to `output_path`:
+/// `<stem>_0001.<ext>` and so on next to it, or `output_path` itself when
+/// the recording isn't segmented.
+pub fn segment_path(output_path: &Path, index: u32, segmenting: bool) -> PathBuf {
+    if !segmenting {
+        return output_path.to_path_buf();
+    }
+    let stem = output_path
+        .file_stem()
+        .map_or_else(|| "recording".into(), |stem| stem.to_string_lossy());
+    let name = match output_path.extension() {
+        Some(extension) => format!("{stem}_{index:04}.{}", extension.to_string_lossy()),
+        None => format!("{stem}_{index:04}"),
+    };
+    output_path.with_file_name(name)
+}
+
+/// `<stem>.manifest.json` next to `output_path`.
+pub fn manifest_path(output_path: &Path) -> PathBuf {
+    output_path.with_extension("manifest.json")
+}
+
+/// Where `path` is written until it is complete.
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(PARTIAL_SUFFIX);
+    PathBuf::from(partial)
+}
+
+/// One finished segment, as listed in the manifest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SegmentRecord {
+    pub index: u32,
+    /// File name, relative to the manifest.
+    pub file: String,
+    /// Where the segment starts on the recording's timeline; its own
+    /// timestamps start here too.
+    pub start_secs: f64,
+    pub duration_secs: f64,
+    pub frames: u64,
+    pub bytes: u64,
+}
+
+/// `<name>.manifest.json`: every finished segment, in order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SegmentManifest {
+    pub segments: Vec<SegmentRecord>,
+}
+
+impl SegmentManifest {
+    /// Replace `path` with this manifest, atomically.
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        let partial = partial_path(path);
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, path)
+    }
+}
+
+/// A segment whose ffmpeg input has been closed.
+pub struct ClosedSegment {
+    pub child: Child,
+    pub partial_path: PathBuf,
+    pub final_path: PathBuf,
+    /// `bytes` is filled in once the file is finished.
+    pub record: SegmentRecord,
+}
+
+/// Wait for ffmpeg to finish `segment`, then move it to its final name.
+/// A failed segment keeps its `.partial` name. Fragmented segments are
+/// written under their final name already.
+fn finish_segment(segment: ClosedSegment) -> Result<SegmentRecord> {
+    let ClosedSegment {
+        child,
+        partial_path,
+        final_path,
+        mut record,
+    } = segment;
+    let output = child
+        .wait_with_output()
+        .map_err(|e| Error::Runtime(format!("Failed to wait for ffmpeg: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Runtime(format!(
+            "ffmpeg exited with status {} writing {}: {stderr}",
+            output.status,
+            partial_path.display()
+        )));
+    }
+    if partial_path != final_path {
+        std::fs::rename(&partial_path, &final_path).map_err(|e| {
+            Error::Runtime(format!(
+                "Failed to rename {} to {}: {e}",
+                partial_path.display(),
+                final_path.display()
+            ))
+        })?;
+    }
+    record.bytes = std::fs::metadata(&final_path).map_or(0, |metadata| metadata.len());
+    Ok(record)
+}
+
+/// Finishes closed segments in order on a background thread.
+pub struct SegmentFinalizer {
+    segments_tx: mpsc::Sender<ClosedSegment>,
+    thread: JoinHandle<Result<()>>,
+}
+
+impl SegmentFinalizer {
+    /// Start the finalizer thread; with `manifest_path`, it rewrites the
+    /// manifest there after every finished segment.
+    pub fn spawn(manifest_path: Option<PathBuf>) -> Result<Self> {
+        let (segments_tx, segments_rx) = mpsc::channel::<ClosedSegment>();
+        let thread = std::thread::Builder::new()
+            .name("mp4-segment-finalizer".into())
+            .spawn(move || {
+                let mut manifest = SegmentManifest::default();
+                let mut first_error = None;
+                for segment in segments_rx {
+                    match finish_segment(segment) {
+                        Ok(record) => {
+                            tracing::info!(
+                                index = record.index,
+                                frames = record.frames,
+                                bytes = record.bytes,
+                                "[LinuxMp4Writer] Segment written to {}",
+                                record.file
+                            );
+                            let Some(manifest_path) = &manifest_path else {
+                                continue;
+                            };
+                            manifest.segments.push(record);
+                            if let Err(e) = manifest.write(manifest_path) {
+                                tracing::warn!(
+                                    "[LinuxMp4Writer] Failed to write manifest {}: {e}",
+                                    manifest_path.display()
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("[LinuxMp4Writer] Segment failed: {e}");
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                first_error.map_or(Ok(()), Err)
+            })
+            .map_err(|e| Error::Runtime(format!("Failed to spawn segment finalizer: {e}")))?;
+        Ok(Self {
+            segments_tx,
+            thread,
+        })
+    }
+
+    /// Queue `segment` to be finished after the ones before it.
+    pub fn finish(&self, segment: ClosedSegment) -> Result<()> {
+        self.segments_tx
+            .send(segment)
+            .map_err(|_| Error::Runtime("Segment finalizer has exited".into()))
+    }
+
+    /// Finish every queued segment, returning the first failure.
+    pub fn join(self) -> Result<()> {
+        drop(self.segments_tx);
+        self.thread
+            .join()
+            .map_err(|_| Error::Runtime("Segment finalizer panicked".into()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn limits_trip_on_duration_or_size() {
+        let limits = SegmentLimits::new(Some(600), Some(2_000)).unwrap();
+        assert!(limits.is_segmenting());
+        assert!(!limits.is_full(17_999, 30, 0));
+        assert!(limits.is_full(18_000, 30, 0));
+        assert!(limits.is_full(1, 30, 2_000 * BYTES_PER_MB));
+
+        let unlimited = SegmentLimits::new(None, None).unwrap();
+        assert!(!unlimited.is_segmenting());
+        assert!(!unlimited.is_full(u64::MAX, 30, u64::MAX));
+        assert!(SegmentLimits::new(Some(0), None).is_err());
+    }
+
+    #[test]
+    fn segments_are_numbered_next_to_the_output() {
+        let output = Path::new("/rec/show.mp4");
+        assert_eq!(segment_path(output, 3, false), output);
+        assert_eq!(
+            segment_path(output, 3, true),
+            Path::new("/rec/show_0003.mp4")
+        );
+        assert_eq!(manifest_path(output), Path::new("/rec/show.manifest.json"));
+        assert_eq!(
+            partial_path(&segment_path(output, 12, true)),
+            Path::new("/rec/show_0012.mp4.partial")
+        );
+    }
+
+    #[test]
+    fn finished_segments_are_renamed_and_listed_in_order() {
+        let dir =
+            std::env::temp_dir().join(format!("streamlib-mp4-segments-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = dir.join("show.mp4");
+        let finalizer = SegmentFinalizer::spawn(Some(manifest_path(&output))).unwrap();
+        for index in 1..=2 {
+            let final_path = segment_path(&output, index, true);
+            let partial = partial_path(&final_path);
+            std::fs::write(&partial, vec![0u8; 10 * index as usize]).unwrap();
+            // Stands in for ffmpeg: exits successfully once its stdin closes.
+            let child = Command::new("cat")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+                .unwrap();
+            finalizer
+                .finish(ClosedSegment {
+                    child,
+                    partial_path: partial,
+                    final_path,
+                    record: SegmentRecord {
+                        index,
+                        file: format!("show_{index:04}.mp4"),
+                        start_secs: f64::from(index - 1) * 2.0,
+                        duration_secs: 2.0,
+                        frames: 60,
+                        bytes: 0,
+                    },
+                })
+                .unwrap();
+        }
+        finalizer.join().unwrap();
+
+        assert!(dir.join("show_0001.mp4").exists());
+        assert!(!dir.join("show_0002.mp4.partial").exists());
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(manifest_path(&output)).unwrap()).unwrap();
+        let segments = manifest["segments"].as_array().unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1]["file"], "show_0002.mp4");
+        assert_eq!(segments[1]["start_secs"], 2.0);
+        assert_eq!(segments[1]["bytes"], 20);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
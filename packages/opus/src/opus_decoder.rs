@@ -3,6 +3,7 @@
 
 //! Opus audio decoder — libopus codec + reactive processor wrapper.
 
+use crate::_generated_::tatolab__core::audio_frame::ChannelLayout;
 use crate::_generated_::{AudioFrame, EncodedAudioFrame};
 use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
@@ -160,6 +161,7 @@ impl OpusDecoder {
             timestamp_ns: timestamp_ns.to_string(),
             frame_index: "0".to_string(), // frame_number (will be set by caller if needed)
             sample_rate: self.sample_rate,
+            channel_layout: Some(ChannelLayout::Stereo),
         })
     }
 
@@ -203,7 +203,7 @@ impl AudioEncoderOpus for OpusEncoder {
     execution = reactive,
     scheduling = realtime,
     config = crate::_generated_::OpusEncoderConfig,
-    input("audio_in", "@tatolab/core/AudioFrame", description = "Raw audio frames to encode"),
+    input("audio_in", "@tatolab/core/AudioFrame", format = "48khz", description = "Raw audio frames to encode"),
     output("encoded_audio_out", "@tatolab/core/EncodedAudioFrame", description = "Opus encoded audio frames"),
 )]
 pub struct OpusEncoderProcessor {
@@ -0,0 +1,25 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/qr-detect` — QR codes in live video. `QrDetector` reads
+//! sampled frames back from the GPU, decodes them off the hot path and
+//! emits each code's payload and bounding box as a `QrDetections` data
+//! frame tagged with the frame's timestamp, for automated latency and sync
+//! measurement across capture chains.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod scan;
+
+// GPU texture readback is Linux-only, as for the frame tap.
+#[cfg(target_os = "linux")]
+pub mod qr_detector;
+
+#[cfg(target_os = "linux")]
+pub use qr_detector::QrDetectorProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::QrDetectorProcessor::Processor);
@@ -0,0 +1,424 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! QR detector (Linux) — forwards every frame and reports the QR codes
+//! it finds as `QrDetections` data frames.
+//!
+//! Sampled frames are copied GPU→CPU through the SDK's [`TextureReadback`]
+//! with the non-blocking `submit` / `try_read_copy` pair, single in
+//! flight, exactly as the frame tap does; a frame due while a copy is
+//! still pending is skipped. Completed copies go to a scanner thread over
+//! a one-slot channel (drop-on-full), which reduces them to luma and
+//! decodes them ([`crate::scan`]); `process()` drains its results on
+//! later calls. Detections carry the analyzed frame's `timestamp_ns` and
+//! index, not the frame they are written alongside, so latency and sync
+//! measurements line up with the frame the code was in.
+
+use std::sync::mpsc::{Receiver, SyncSender, channel, sync_channel};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ReadbackTicket, TextureFormat, TextureReadback, TextureSourceLayout, VulkanLayout,
+};
+
+use crate::_generated_::tatolab__qr_detect::qr_detections::QrDetectionsCode;
+use crate::_generated_::{QrDetections, VideoFrame};
+use crate::scan::{self, Code, PixelOrder};
+
+const DEFAULT_EVERY_N_FRAMES: u32 = 1;
+const DEFAULT_MAX_SCAN_HEIGHT: u32 = 1080;
+
+/// Wait before re-escalating after a failed readback-handle creation;
+/// each `escalate` drains the device, so a persistent failure must not
+/// retry every frame.
+const READBACK_CREATION_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// One in-flight GPU→CPU readback.
+struct PendingReadback {
+    ticket: ReadbackTicket,
+    width: u32,
+    height: u32,
+    order: PixelOrder,
+    timestamp_ns: String,
+    frame_index: u64,
+}
+
+/// A read-back frame for the scanner thread.
+struct ScanJob {
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    order: PixelOrder,
+    max_scan_height: u32,
+    timestamp_ns: String,
+    frame_index: u64,
+}
+
+/// What the scanner found in one frame.
+struct Scanned {
+    timestamp_ns: String,
+    frame_index: u64,
+    width: u32,
+    height: u32,
+    codes: Vec<Code>,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/qr-detect/QrDetector",
+    description = "Finds and decodes QR codes in video frames (GPU readback, CPU decode off the hot path) and emits their payloads with normalized bounding boxes as QrDetections data frames tagged with the analyzed frame's timestamp — for automated latency and sync measurement across capture chains. Forwards every frame unmodified.",
+    execution = reactive,
+    config = crate::_generated_::QrDetectorConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to scan (RGBA8 or BGRA8)"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames, unmodified"),
+    output("detections", "@tatolab/qr-detect/QrDetections", description = "Decoded codes per scanned frame"),
+)]
+pub struct QrDetectorProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    /// Created via `escalate` once the source extent/format is known.
+    readback: Option<TextureReadback>,
+    readback_key: Option<(u32, u32, TextureFormat)>,
+    /// Key whose readback creation failed, and when to retry it.
+    readback_retry: Option<((u32, u32, TextureFormat), Instant)>,
+    pending: Option<PendingReadback>,
+    scanner: Option<Scanner>,
+    frames_seen: u64,
+    frames_scanned: u64,
+    frames_skipped: u64,
+    codes_found: u64,
+    /// Payloads of the last scanned frame, to log changes once.
+    last_payloads: Vec<String>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for QrDetectorProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.scanner = Some(Scanner::spawn()?);
+        tracing::info!(
+            "QrDetector: setup (every {} frames, scan height ≤ {})",
+            self.every_n_frames(),
+            self.max_scan_height()
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "QrDetector: teardown — {} frames seen, {} scanned, {} skipped, {} codes found",
+            self.frames_seen,
+            self.frames_scanned,
+            self.frames_skipped,
+            self.codes_found,
+        );
+        // Joins the scanner thread after its current frame.
+        self.scanner = None;
+        self.pending = None;
+        self.readback = None;
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let frame_index = self.frames_seen;
+        self.frames_seen += 1;
+
+        if let Some(pending) = self.pending.take() {
+            self.drain_pending(pending);
+        }
+        if frame_index.is_multiple_of(u64::from(self.every_n_frames())) {
+            if self.pending.is_none() {
+                let gpu = self.gpu_context.clone().ok_or_else(|| {
+                    Error::Configuration("QrDetector: GPU context not initialized".into())
+                })?;
+                self.submit(&gpu, &frame, frame_index)?;
+            } else {
+                self.frames_skipped += 1;
+            }
+        }
+
+        let results = self
+            .scanner
+            .as_ref()
+            .map(Scanner::take_results)
+            .unwrap_or_default();
+        for scanned in results {
+            self.write(scanned)?;
+        }
+        self.outputs.write("video_out", &frame)
+    }
+}
+
+impl QrDetectorProcessor::Processor {
+    fn every_n_frames(&self) -> u32 {
+        self.config
+            .every_n_frames
+            .unwrap_or(DEFAULT_EVERY_N_FRAMES)
+            .max(1)
+    }
+
+    fn max_scan_height(&self) -> u32 {
+        self.config
+            .max_scan_height
+            .unwrap_or(DEFAULT_MAX_SCAN_HEIGHT)
+            .max(1)
+    }
+
+    /// Hand a completed readback to the scanner, or keep waiting.
+    fn drain_pending(&mut self, pending: PendingReadback) {
+        let read_result = match self.readback.as_ref() {
+            Some(readback) => readback.try_read_copy(pending.ticket),
+            None => return,
+        };
+        match read_result {
+            Ok(Some(pixels)) => {
+                let job = ScanJob {
+                    pixels,
+                    width: pending.width,
+                    height: pending.height,
+                    order: pending.order,
+                    max_scan_height: self.max_scan_height(),
+                    timestamp_ns: pending.timestamp_ns,
+                    frame_index: pending.frame_index,
+                };
+                let queued = self.scanner.as_ref().is_some_and(|s| s.try_enqueue(job));
+                if queued {
+                    self.frames_scanned += 1;
+                } else {
+                    self.frames_skipped += 1;
+                }
+            }
+            Ok(None) => self.pending = Some(pending),
+            Err(e) => tracing::warn!("QrDetector: readback try_read_copy failed: {}", e),
+        }
+    }
+
+    /// (Re)build the readback handle if needed and submit a non-blocking
+    /// copy of `frame`.
+    fn submit(
+        &mut self,
+        gpu: &GpuContextLimitedAccess,
+        frame: &VideoFrame,
+        frame_index: u64,
+    ) -> Result<()> {
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let layout = registration.current_layout();
+        let format = texture.format();
+        let Some(order) = pixel_order_for(format) else {
+            tracing::warn!(
+                "QrDetector: unsupported texture format {:?} — frame not scanned",
+                format
+            );
+            return Ok(());
+        };
+
+        let key = (texture.width(), texture.height(), format);
+        if self.readback_key != Some(key) {
+            if self
+                .readback_retry
+                .is_some_and(|(failed, retry_at)| failed == key && Instant::now() < retry_at)
+            {
+                return Ok(());
+            }
+            let (width, height) = (texture.width(), texture.height());
+            match gpu
+                .escalate(|full| full.create_texture_readback("qr-detect", width, height, format))
+            {
+                Ok(Ok(readback)) => {
+                    tracing::info!(
+                        "QrDetector: created readback handle ({:?}, {}x{})",
+                        format,
+                        width,
+                        height,
+                    );
+                    self.readback = Some(readback);
+                    self.readback_key = Some(key);
+                    self.readback_retry = None;
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!("QrDetector: readback handle creation failed: {}", e);
+                    self.readback_retry =
+                        Some((key, Instant::now() + READBACK_CREATION_RETRY_BACKOFF));
+                    return Ok(());
+                }
+                Err(e) => {
+                    tracing::warn!("QrDetector: escalate for readback creation failed: {}", e);
+                    self.readback_retry =
+                        Some((key, Instant::now() + READBACK_CREATION_RETRY_BACKOFF));
+                    return Ok(());
+                }
+            }
+        }
+
+        let ticket = {
+            let Some(readback) = self.readback.as_ref() else {
+                return Ok(());
+            };
+            match readback.submit(&texture, source_layout_for(layout)) {
+                Ok(ticket) => ticket,
+                Err(e) => {
+                    tracing::warn!("QrDetector: readback submit failed: {}", e);
+                    return Ok(());
+                }
+            }
+        };
+        self.pending = Some(PendingReadback {
+            ticket,
+            width: texture.width(),
+            height: texture.height(),
+            order,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            frame_index,
+        });
+        Ok(())
+    }
+
+    fn write(&mut self, scanned: Scanned) -> Result<()> {
+        let payloads: Vec<String> = scanned.codes.iter().map(|c| c.payload.clone()).collect();
+        if payloads != self.last_payloads {
+            tracing::debug!(
+                "QrDetector: frame {} has {} code(s): {:?}",
+                scanned.frame_index,
+                payloads.len(),
+                payloads
+            );
+            self.last_payloads = payloads;
+        }
+        if scanned.codes.is_empty() && !self.config.emit_empty.unwrap_or(false) {
+            return Ok(());
+        }
+        self.codes_found += scanned.codes.len() as u64;
+        let codes = scanned
+            .codes
+            .into_iter()
+            .map(|code| {
+                let [x, y, width, height] = code.bounding_box();
+                QrDetectionsCode {
+                    x,
+                    y,
+                    width,
+                    height,
+                    corners: code.corners.iter().flatten().copied().collect(),
+                    version: code.version,
+                    payload: code.payload,
+                }
+            })
+            .collect();
+        self.outputs.write(
+            "detections",
+            &QrDetections {
+                timestamp_ns: scanned.timestamp_ns,
+                frame_index: scanned.frame_index.to_string(),
+                width: scanned.width,
+                height: scanned.height,
+                codes,
+            },
+        )
+    }
+}
+
+/// Byte order of the texture formats the scanner reads.
+fn pixel_order_for(format: TextureFormat) -> Option<PixelOrder> {
+    match format {
+        TextureFormat::Bgra8Unorm => Some(PixelOrder::Bgra),
+        TextureFormat::Rgba8Unorm => Some(PixelOrder::Rgba),
+        _ => None,
+    }
+}
+
+/// The readback's source-layout hint must match the texture's real
+/// layout to stay validation-clean.
+fn source_layout_for(layout: VulkanLayout) -> TextureSourceLayout {
+    if layout == VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+        TextureSourceLayout::ShaderReadOnly
+    } else {
+        TextureSourceLayout::General
+    }
+}
+
+/// Background decoder: one frame queued at most, results returned in
+/// order.
+struct Scanner {
+    jobs: Option<SyncSender<ScanJob>>,
+    results: Receiver<Scanned>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Scanner {
+    fn spawn() -> Result<Self> {
+        let (jobs, job_receiver) = sync_channel::<ScanJob>(1);
+        let (result_sender, results) = channel();
+        let handle = std::thread::Builder::new()
+            .name("qr-detect-scanner".to_string())
+            .spawn(move || {
+                for job in job_receiver {
+                    if result_sender.send(scan_frame(job)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| Error::Runtime(format!("QrDetector: cannot start scanner: {}", e)))?;
+        Ok(Self {
+            jobs: Some(jobs),
+            results,
+            handle: Some(handle),
+        })
+    }
+
+    /// `false` when a frame is already queued (drop-on-full) or the
+    /// thread is gone.
+    fn try_enqueue(&self, job: ScanJob) -> bool {
+        self.jobs
+            .as_ref()
+            .is_some_and(|jobs| jobs.try_send(job).is_ok())
+    }
+
+    fn take_results(&self) -> Vec<Scanned> {
+        self.results.try_iter().collect()
+    }
+}
+
+impl Drop for Scanner {
+    fn drop(&mut self) {
+        self.jobs.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn scan_frame(job: ScanJob) -> Scanned {
+    let (width, height) = (job.width as usize, job.height as usize);
+    let codes = if job.pixels.len() < width * height * 4 {
+        tracing::warn!(
+            "QrDetector: readback of {} bytes is short for {}x{}",
+            job.pixels.len(),
+            width,
+            height
+        );
+        Vec::new()
+    } else {
+        let factor = scan::scale_factor(job.height, job.max_scan_height);
+        let image = scan::luma(&job.pixels, width, height, job.order, factor as usize);
+        scan::decode(&image, factor, job.width, job.height)
+    };
+    Scanned {
+        timestamp_ns: job.timestamp_ns,
+        frame_index: job.frame_index,
+        width: job.width,
+        height: job.height,
+        codes,
+    }
+}
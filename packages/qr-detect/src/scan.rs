@@ -0,0 +1,185 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Finding and decoding QR codes in a frame read back to the CPU.
+//!
+//! The 8-bit RGBA or BGRA pixels are reduced to luma, box-filtered down
+//! by a whole factor when the frame is taller than the scan limit, and
+//! handed to `rqrr`. Corners come back in frame coordinates normalized to
+//! 0..1, origin at the top-left, y down — the convention of the other
+//! analysis packages, so overlays scale by the frame size directly.
+
+/// Byte order of a 4-byte pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelOrder {
+    Rgba,
+    Bgra,
+}
+
+/// 8-bit luma image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Luma {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// A decoded code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Code {
+    pub payload: String,
+    /// QR version, 1..=40; the code is `17 + 4 * version` modules wide.
+    pub version: u32,
+    /// Corners clockwise from the code's own top-left, normalized.
+    pub corners: [[f32; 2]; 4],
+}
+
+impl Code {
+    /// Axis-aligned box around the corners: x, y, width, height.
+    pub fn bounding_box(&self) -> [f32; 4] {
+        let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+        for corner in &self.corners {
+            for axis in 0..2 {
+                min[axis] = min[axis].min(corner[axis]);
+                max[axis] = max[axis].max(corner[axis]);
+            }
+        }
+        [min[0], min[1], max[0] - min[0], max[1] - min[1]]
+    }
+}
+
+/// Whole factor that brings `height` to `max_height` or below.
+pub fn scale_factor(height: u32, max_height: u32) -> u32 {
+    height.div_ceil(max_height.max(1)).max(1)
+}
+
+/// Luma of tightly packed 4-byte pixels, averaged over `factor` ×
+/// `factor` blocks. Edge pixels that don't fill a block are dropped.
+pub fn luma(pixels: &[u8], width: usize, height: usize, order: PixelOrder, factor: usize) -> Luma {
+    let factor = factor.max(1);
+    let (out_width, out_height) = (width / factor, height / factor);
+    let (r, b) = match order {
+        PixelOrder::Rgba => (0, 2),
+        PixelOrder::Bgra => (2, 0),
+    };
+    let mut out = Vec::with_capacity(out_width * out_height);
+    for y in 0..out_height {
+        for x in 0..out_width {
+            let mut sum = 0u32;
+            for row in y * factor..(y + 1) * factor {
+                let start = (row * width + x * factor) * 4;
+                for pixel in pixels[start..start + factor * 4].chunks_exact(4) {
+                    // BT.601 weights in 8-bit fixed point.
+                    sum += (77 * u32::from(pixel[r])
+                        + 150 * u32::from(pixel[1])
+                        + 29 * u32::from(pixel[b]))
+                        >> 8;
+                }
+            }
+            out.push((sum / (factor * factor) as u32) as u8);
+        }
+    }
+    Luma {
+        width: out_width,
+        height: out_height,
+        pixels: out,
+    }
+}
+
+/// Decode every QR code in `image`, a frame of `frame_width` ×
+/// `frame_height` scaled down by `factor`. Codes that are found but
+/// fail to decode, e.g. blurred or cut off, are skipped.
+pub fn decode(image: &Luma, factor: u32, frame_width: u32, frame_height: u32) -> Vec<Code> {
+    let mut prepared =
+        rqrr::PreparedImage::prepare_from_greyscale(image.width, image.height, |x, y| {
+            image.pixels[y * image.width + x]
+        });
+    prepared
+        .detect_grids()
+        .into_iter()
+        .filter_map(|grid| {
+            let (meta, payload) = grid.decode().ok()?;
+            let corners = grid.bounds.map(|point| {
+                normalize(
+                    [point.x as f32, point.y as f32],
+                    factor,
+                    frame_width,
+                    frame_height,
+                )
+            });
+            Some(Code {
+                payload,
+                version: meta.version.0 as u32,
+                corners,
+            })
+        })
+        .collect()
+}
+
+/// A point in the scaled image to normalized frame coordinates.
+fn normalize(point: [f32; 2], factor: u32, frame_width: u32, frame_height: u32) -> [f32; 2] {
+    // A scaled pixel covers `factor` frame pixels; map its centre.
+    let scale = factor as f32;
+    let offset = (scale - 1.0) / 2.0;
+    [
+        ((point[0] * scale + offset) / frame_width.max(1) as f32).clamp(0.0, 1.0),
+        ((point[1] * scale + offset) / frame_height.max(1) as f32).clamp(0.0, 1.0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_taller_than_the_limit_scale_by_whole_factors() {
+        assert_eq!(scale_factor(1080, 1080), 1);
+        assert_eq!(scale_factor(720, 1080), 1);
+        assert_eq!(scale_factor(2160, 1080), 2);
+        assert_eq!(scale_factor(2161, 1080), 3);
+        assert_eq!(scale_factor(480, 0), 480);
+    }
+
+    #[test]
+    fn luma_follows_byte_order_and_averages_blocks() {
+        // 3×2 pixels: red, green, blue / white, black, grey.
+        let rgba = [
+            255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, //
+            255, 255, 255, 255, 0, 0, 0, 255, 128, 128, 128, 255,
+        ];
+        let full = luma(&rgba, 3, 2, PixelOrder::Rgba, 1);
+        assert_eq!(full.pixels, [76, 149, 28, 255, 0, 128]);
+
+        let mut bgra = rgba;
+        for pixel in bgra.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        assert_eq!(luma(&bgra, 3, 2, PixelOrder::Bgra, 1), full);
+
+        // One 2×2 block, (76 + 149 + 255 + 0) / 4; the third column is
+        // dropped.
+        let half = luma(&rgba, 3, 2, PixelOrder::Rgba, 2);
+        assert_eq!((half.width, half.height), (1, 1));
+        assert_eq!(half.pixels, [120]);
+    }
+
+    #[test]
+    fn corners_map_back_to_normalized_frame_coordinates() {
+        let code = Code {
+            payload: "1234".into(),
+            version: 1,
+            corners: [
+                normalize([10.0, 20.0], 2, 400, 200),
+                normalize([60.0, 20.0], 2, 400, 200),
+                normalize([60.0, 70.0], 2, 400, 200),
+                normalize([10.0, 70.0], 2, 400, 200),
+            ],
+        };
+
+        assert_eq!(code.corners[0], [20.5 / 400.0, 40.5 / 200.0]);
+        let [x, y, width, height] = code.bounding_box();
+        assert_eq!((x, y), (20.5 / 400.0, 40.5 / 200.0));
+        assert!((width - 0.25).abs() < 1e-6 && (height - 0.5).abs() < 1e-6);
+        assert_eq!(normalize([500.0, -1.0], 1, 400, 200), [1.0, 0.0]);
+    }
+}
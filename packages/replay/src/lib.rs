@@ -0,0 +1,20 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/replay` — instant replay. `ReplayBuffer` keeps the last
+//! seconds of encoded video in a content-addressed frame cache and, on a
+//! trigger, plays a stretch of it back at a chosen speed on a secondary
+//! output while the live path carries on.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod replay_buffer;
+pub mod ring;
+pub mod store;
+
+pub use replay_buffer::{REPLAY_STATE_TOPIC, ReplayBufferProcessor};
+
+streamlib_plugin_abi::export_plugin!(crate::ReplayBufferProcessor::Processor,);
@@ -0,0 +1,301 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Instant replay over encoded video.
+//!
+//! Every live frame goes into a [`ReplayRing`] spanning `buffer_seconds`.
+//! A `ReplayControl` trigger picks the keyframe at or before the requested
+//! point and plays from there on `replay_out`, each frame released once
+//! its slowed (or sped-up) offset from the replay's start has elapsed on
+//! the media clock and stamped with that time, so downstream decoders and
+//! muxers see an ordinary stream. Pacing is driven by the processor's own
+//! wake-ups, which come with each live frame, so a replay advances at
+//! live frame granularity and stalls if the live feed does.
+//!
+//! GPU frames aren't buffered: encode first and decode the replay on the
+//! way out, which keeps a 30 s window at a few megabytes per second
+//! instead of gigabytes of VRAM.
+
+use std::path::PathBuf;
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::pubsub::publish_custom_event;
+
+use crate::_generated_::tatolab__replay::replay_buffer_config::Storage;
+use crate::_generated_::{EncodedVideoFrame, ReplayControl};
+use crate::ring::{Playback, ReplayRing};
+use crate::store::{DiskStore, FrameStore, MemoryStore};
+
+/// Custom-event topic carrying `{processor_id, state, ...}` when a replay
+/// starts, finishes, or is stopped.
+pub const REPLAY_STATE_TOPIC: &str = "replay:state";
+
+const DEFAULT_BUFFER_SECONDS: u32 = 30;
+const DEFAULT_SECONDS_BACK: f32 = 10.0;
+const DEFAULT_SPEED: f32 = 0.5;
+
+const NS_PER_SECOND: f64 = 1_000_000_000.0;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/replay/ReplayBuffer",
+    description = "Keeps the last buffer_seconds of encoded video in a content-addressed frame cache (RAM or disk) and, on a ReplayControl trigger, plays a stretch of it back from the preceding keyframe at the requested speed on replay_out, retimed onto the live clock. Attach it as a fan-out branch beside the live path. Replay start and end are published on the replay:state topic.",
+    execution = reactive,
+    config = crate::_generated_::ReplayBufferConfig,
+    input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", description = "Live encoded video to buffer"),
+    input("control", "@tatolab/replay/ReplayControl", description = "Replay triggers", optional = true),
+    output("replay_out", "@tatolab/core/EncodedVideoFrame", description = "Replayed frames, retimed onto the live clock"),
+)]
+pub struct ReplayBufferProcessor {
+    processor_id: Option<String>,
+    ring: Option<ReplayRing>,
+    /// Disk storage directory, removed on teardown once emptied.
+    directory: Option<PathBuf>,
+    playback: Option<Playback>,
+    /// Frames written to `replay_out`; their `frame_number`s.
+    frames_replayed: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for ReplayBufferProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.processor_id = ctx.processor_id();
+        self.validate()?;
+        let store: Box<dyn FrameStore> = match self.config.storage {
+            Some(Storage::Disk) => {
+                let directory = self.config.directory.as_ref().map_or_else(
+                    || {
+                        std::env::temp_dir().join("streamlib-replay").join(
+                            self.processor_id
+                                .clone()
+                                .unwrap_or_else(|| std::process::id().to_string()),
+                        )
+                    },
+                    PathBuf::from,
+                );
+                let store = DiskStore::new(&directory).map_err(|e| {
+                    Error::Configuration(format!(
+                        "ReplayBuffer: cannot use {}: {e}",
+                        directory.display()
+                    ))
+                })?;
+                self.directory = Some(directory);
+                Box::new(store)
+            }
+            Some(Storage::Memory) | None => Box::new(MemoryStore::default()),
+        };
+        self.ring = Some(ReplayRing::new(self.window_ns(), store));
+        tracing::info!(
+            "[ReplayBuffer] Setup ({} s, {})",
+            self.config.buffer_seconds.unwrap_or(DEFAULT_BUFFER_SECONDS),
+            self.directory
+                .as_ref()
+                .map_or_else(|| "memory".to_string(), |d| d.display().to_string())
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(mut ring) = self.ring.take() {
+            ring.clear()?;
+        }
+        if let Some(directory) = self.directory.take()
+            && let Err(e) = std::fs::remove_dir(&directory)
+        {
+            tracing::debug!("[ReplayBuffer] Left {}: {}", directory.display(), e);
+        }
+        tracing::info!(
+            "[ReplayBuffer] Teardown ({} frames replayed)",
+            self.frames_replayed
+        );
+        Ok(())
+    }
+
+    /// Buffer length and replay defaults apply at once; `storage` and
+    /// `directory` on the next setup.
+    fn on_config_update(&mut self) -> Result<()> {
+        self.validate()?;
+        let window_ns = self.window_ns();
+        if let Some(ring) = &mut self.ring {
+            ring.set_window_ns(window_ns);
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let Some(ring) = &mut self.ring else {
+            return Ok(());
+        };
+        while self.inputs.has_data("encoded_video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("encoded_video_in")?;
+            let timestamp_ns = frame.timestamp_ns.parse().unwrap_or_else(|_| now_ns());
+            ring.push(frame, timestamp_ns)?;
+        }
+        let mut trigger = None;
+        while self.inputs.has_data("control") {
+            let control: ReplayControl = self.inputs.read("control")?;
+            trigger = Some(control);
+        }
+        if let Some(control) = trigger {
+            self.on_control(&control);
+        }
+        self.play()
+    }
+}
+
+impl ReplayBufferProcessor::Processor {
+    fn validate(&self) -> Result<()> {
+        let config = &self.config;
+        if config.buffer_seconds == Some(0) {
+            return Err(Error::Configuration(
+                "ReplayBuffer: buffer_seconds must be nonzero".into(),
+            ));
+        }
+        if config
+            .default_speed
+            .is_some_and(|speed| !valid_speed(speed))
+        {
+            return Err(Error::Configuration(
+                "ReplayBuffer: default_speed must be positive".into(),
+            ));
+        }
+        if config
+            .default_seconds_back
+            .is_some_and(|seconds| !(seconds.is_finite() && seconds >= 0.0))
+        {
+            return Err(Error::Configuration(
+                "ReplayBuffer: default_seconds_back must be non-negative".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn window_ns(&self) -> i64 {
+        i64::from(self.config.buffer_seconds.unwrap_or(DEFAULT_BUFFER_SECONDS)) * 1_000_000_000
+    }
+
+    /// Start a replay, replacing any in progress, or stop one. A trigger
+    /// with unusable values is logged and ignored rather than failing the
+    /// live path.
+    fn on_control(&mut self, control: &ReplayControl) {
+        if control.stop == Some(true) {
+            if let Some(playback) = self.playback.take() {
+                self.finish(&playback, "stopped");
+            }
+            return;
+        }
+        let speed = control
+            .speed
+            .or(self.config.default_speed)
+            .unwrap_or(DEFAULT_SPEED);
+        let seconds_back = control
+            .seconds_back
+            .or(self.config.default_seconds_back)
+            .unwrap_or(DEFAULT_SECONDS_BACK);
+        if !valid_speed(speed) || !(seconds_back.is_finite() && seconds_back >= 0.0) {
+            tracing::warn!(
+                "[ReplayBuffer] Ignoring trigger: speed {} / seconds_back {}",
+                speed,
+                seconds_back
+            );
+            return;
+        }
+        let Some(ring) = &self.ring else {
+            return;
+        };
+        let Some(start) = ring.replay_start(seconds_to_ns(seconds_back)) else {
+            tracing::warn!("[ReplayBuffer] Ignoring trigger: no keyframe buffered yet");
+            return;
+        };
+        let end = match control.duration_seconds {
+            Some(duration) => ring.replay_end(start, seconds_to_ns(duration.max(0.0))),
+            None => ring.newest_sequence(),
+        }
+        .unwrap_or(start);
+        let start_timestamp_ns = ring.timestamp_ns(start).unwrap_or_default();
+        if let Some(previous) = self.playback.take() {
+            self.finish(&previous, "stopped");
+        }
+        if let Some(ring) = &mut self.ring {
+            ring.pin(Some(start));
+        }
+        let playback = Playback::new(start, end, start_timestamp_ns, f64::from(speed), now_ns());
+        tracing::info!(
+            "[ReplayBuffer] Replay of {} frames at {}x from {:.2} s back",
+            end - start + 1,
+            speed,
+            seconds_back
+        );
+        self.publish(serde_json::json!({
+            "processor_id": self.processor_id,
+            "state": "started",
+            "start_timestamp_ns": start_timestamp_ns.to_string(),
+            "frames": end - start + 1,
+            "speed": speed,
+        }));
+        self.playback = Some(playback);
+    }
+
+    /// Write every replay frame that has come due.
+    fn play(&mut self) -> Result<()> {
+        let (Some(ring), Some(playback)) = (&mut self.ring, &mut self.playback) else {
+            return Ok(());
+        };
+        let now = now_ns();
+        while !playback.is_finished() {
+            let Some(source_ns) = ring.timestamp_ns(playback.next) else {
+                // Evicted before the pin was taken; nothing to play from here.
+                playback.next = playback.end + 1;
+                break;
+            };
+            let output_ns = playback.output_timestamp_ns(source_ns);
+            if output_ns > now {
+                break;
+            }
+            if let Some(mut frame) = ring.frame(playback.next)? {
+                frame.timestamp_ns = output_ns.to_string();
+                frame.frame_number = self.frames_replayed.to_string();
+                self.outputs.write("replay_out", &frame)?;
+                self.frames_replayed += 1;
+            }
+            playback.next += 1;
+            ring.pin(Some(playback.next));
+        }
+        if playback.is_finished() {
+            ring.pin(None);
+            if let Some(playback) = self.playback.take() {
+                self.finish(&playback, "finished");
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, playback: &Playback, state: &str) {
+        if let Some(ring) = &mut self.ring {
+            ring.pin(None);
+        }
+        tracing::info!("[ReplayBuffer] Replay {} at frame {}", state, playback.next);
+        self.publish(serde_json::json!({
+            "processor_id": self.processor_id,
+            "state": state,
+        }));
+    }
+
+    fn publish(&self, payload: serde_json::Value) {
+        if let Err(e) = publish_custom_event(REPLAY_STATE_TOPIC, &payload) {
+            tracing::debug!("[ReplayBuffer] State not published: {}", e);
+        }
+    }
+}
+
+fn valid_speed(speed: f32) -> bool {
+    speed.is_finite() && speed > 0.0
+}
+
+fn seconds_to_ns(seconds: f32) -> i64 {
+    (f64::from(seconds) * NS_PER_SECOND).round() as i64
+}
+
+fn now_ns() -> i64 {
+    MediaClock::now().as_nanos() as i64
+}
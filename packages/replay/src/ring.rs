@@ -0,0 +1,311 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The replay window and playback schedule, separate from port I/O so
+//! they can be driven with synthetic frames and clocks.
+//!
+//! The ring keeps frame metadata in arrival order and the payloads in a
+//! [`FrameStore`] under their digests. Frames older than the window are
+//! evicted, except that a replay in progress pins everything from its
+//! cursor on: a slow-motion replay reaching back the whole window would
+//! otherwise lose its tail before it's played.
+
+use std::collections::{HashMap, VecDeque};
+
+use streamlib_plugin_sdk::sdk::error::Result;
+
+use crate::_generated_::EncodedVideoFrame;
+use crate::store::{Digest, FrameStore, digest};
+
+/// One buffered frame. The payload is in the store under `digest`; the
+/// `frame` kept here has its `data` emptied.
+#[derive(Debug, Clone)]
+struct ReplayEntry {
+    sequence: u64,
+    timestamp_ns: i64,
+    digest: Digest,
+    frame: EncodedVideoFrame,
+}
+
+pub struct ReplayRing {
+    window_ns: i64,
+    entries: VecDeque<ReplayEntry>,
+    next_sequence: u64,
+    /// Buffered frames per stored payload.
+    references: HashMap<Digest, u32>,
+    store: Box<dyn FrameStore>,
+    /// Entries from this sequence on survive eviction.
+    pinned_from: Option<u64>,
+}
+
+impl ReplayRing {
+    pub fn new(window_ns: i64, store: Box<dyn FrameStore>) -> Self {
+        Self {
+            window_ns,
+            entries: VecDeque::new(),
+            next_sequence: 0,
+            references: HashMap::new(),
+            store,
+            pinned_from: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Distinct payloads held by the store.
+    pub fn stored_payloads(&self) -> usize {
+        self.references.len()
+    }
+
+    /// Buffer a frame stamped `timestamp_ns`, then evict what has fallen
+    /// out of the window behind it.
+    pub fn push(&mut self, mut frame: EncodedVideoFrame, timestamp_ns: i64) -> Result<()> {
+        let digest = digest(&frame.data);
+        if !self.references.contains_key(&digest) {
+            self.store.put(&digest, &frame.data)?;
+        }
+        *self.references.entry(digest).or_insert(0) += 1;
+        frame.data = Vec::new();
+        self.entries.push_back(ReplayEntry {
+            sequence: self.next_sequence,
+            timestamp_ns,
+            digest,
+            frame,
+        });
+        self.next_sequence += 1;
+        self.evict()
+    }
+
+    fn evict(&mut self) -> Result<()> {
+        let Some(newest) = self.entries.back().map(|entry| entry.timestamp_ns) else {
+            return Ok(());
+        };
+        let oldest_kept = newest.saturating_sub(self.window_ns);
+        while let Some(front) = self.entries.front() {
+            if front.timestamp_ns >= oldest_kept
+                || self.pinned_from.is_some_and(|pin| front.sequence >= pin)
+            {
+                break;
+            }
+            let entry = self.entries.pop_front().expect("front exists");
+            self.release(&entry.digest)?;
+        }
+        Ok(())
+    }
+
+    fn release(&mut self, digest: &Digest) -> Result<()> {
+        let Some(count) = self.references.get_mut(digest) else {
+            return Ok(());
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.references.remove(digest);
+            self.store.remove(digest)?;
+        }
+        Ok(())
+    }
+
+    /// Takes effect as frames arrive; a shorter window evicts on the next
+    /// push.
+    pub fn set_window_ns(&mut self, window_ns: i64) {
+        self.window_ns = window_ns;
+    }
+
+    /// Keep entries from `sequence` on past the window, or release the pin.
+    pub fn pin(&mut self, sequence: Option<u64>) {
+        self.pinned_from = sequence;
+    }
+
+    pub fn newest_sequence(&self) -> Option<u64> {
+        self.entries.back().map(|entry| entry.sequence)
+    }
+
+    pub fn timestamp_ns(&self, sequence: u64) -> Option<i64> {
+        self.entry(sequence).map(|entry| entry.timestamp_ns)
+    }
+
+    /// The keyframe a replay `back_ns` behind the newest frame starts at:
+    /// the last one at or before that point, or the oldest buffered
+    /// keyframe when the window doesn't reach back that far.
+    pub fn replay_start(&self, back_ns: i64) -> Option<u64> {
+        let target = self.entries.back()?.timestamp_ns.saturating_sub(back_ns);
+        let mut keyframes = self.entries.iter().filter(|entry| entry.frame.is_keyframe);
+        let first = keyframes.next()?;
+        let start = std::iter::once(first)
+            .chain(keyframes)
+            .take_while(|entry| entry.timestamp_ns <= target)
+            .last()
+            .unwrap_or(first);
+        Some(start.sequence)
+    }
+
+    /// The last frame no more than `duration_ns` after `start`.
+    pub fn replay_end(&self, start: u64, duration_ns: i64) -> Option<u64> {
+        let limit = self.timestamp_ns(start)?.saturating_add(duration_ns);
+        self.entries
+            .iter()
+            .skip_while(|entry| entry.sequence < start)
+            .take_while(|entry| entry.timestamp_ns <= limit)
+            .last()
+            .map(|entry| entry.sequence)
+    }
+
+    /// The buffered frame at `sequence` with its payload read back.
+    pub fn frame(&self, sequence: u64) -> Result<Option<EncodedVideoFrame>> {
+        let Some(entry) = self.entry(sequence) else {
+            return Ok(None);
+        };
+        let mut frame = entry.frame.clone();
+        frame.data = self.store.get(&entry.digest)?;
+        Ok(Some(frame))
+    }
+
+    fn entry(&self, sequence: u64) -> Option<&ReplayEntry> {
+        let front = self.entries.front()?.sequence;
+        let index = usize::try_from(sequence.checked_sub(front)?).ok()?;
+        self.entries.get(index)
+    }
+
+    /// Drop every frame and its payload.
+    pub fn clear(&mut self) -> Result<()> {
+        self.entries.clear();
+        self.pinned_from = None;
+        for (digest, _) in self.references.drain() {
+            self.store.remove(&digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// A replay in progress: frames `next..=end` of the ring, released at
+/// `speed` relative to the clock they were captured on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playback {
+    pub next: u64,
+    pub end: u64,
+    speed: f64,
+    source_origin_ns: i64,
+    output_origin_ns: i64,
+}
+
+impl Playback {
+    /// Start at `start` (captured at `start_timestamp_ns`), playing its
+    /// first frame at `now_ns`.
+    pub fn new(start: u64, end: u64, start_timestamp_ns: i64, speed: f64, now_ns: i64) -> Self {
+        Self {
+            next: start,
+            end,
+            speed,
+            source_origin_ns: start_timestamp_ns,
+            output_origin_ns: now_ns,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next > self.end
+    }
+
+    /// When a frame captured at `source_ns` plays: its offset from the
+    /// replay's first frame, stretched by the speed, after `now_ns` at the
+    /// trigger. Also the timestamp it goes out with.
+    pub fn output_timestamp_ns(&self, source_ns: i64) -> i64 {
+        let offset = source_ns.saturating_sub(self.source_origin_ns) as f64 / self.speed;
+        self.output_origin_ns.saturating_add(offset.round() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+
+    const MS: i64 = 1_000_000;
+
+    fn frame(data: &[u8], keyframe: bool) -> EncodedVideoFrame {
+        EncodedVideoFrame {
+            data: data.to_vec(),
+            is_keyframe: keyframe,
+            ..Default::default()
+        }
+    }
+
+    /// Frames every 100 ms with a keyframe every 10th, each payload unique.
+    fn ring_of(window_ms: i64, frames: i64) -> ReplayRing {
+        let mut ring = ReplayRing::new(window_ms * MS, Box::new(MemoryStore::default()));
+        for n in 0..frames {
+            ring.push(frame(&n.to_le_bytes(), n % 10 == 0), n * 100 * MS)
+                .unwrap();
+        }
+        ring
+    }
+
+    #[test]
+    fn identical_payloads_are_stored_once_until_the_last_leaves() {
+        let mut ring = ReplayRing::new(250 * MS, Box::new(MemoryStore::default()));
+        for n in 0..3 {
+            ring.push(frame(b"slate", n == 0), n * 100 * MS).unwrap();
+        }
+        assert_eq!((ring.len(), ring.stored_payloads()), (3, 1));
+
+        ring.push(frame(b"live", true), 300 * MS).unwrap();
+        ring.push(frame(b"live", false), 400 * MS).unwrap();
+        ring.push(frame(b"live", false), 500 * MS).unwrap();
+        ring.push(frame(b"live", false), 600 * MS).unwrap();
+        assert_eq!(ring.stored_payloads(), 1);
+        assert_eq!(
+            ring.frame(ring.newest_sequence().unwrap())
+                .unwrap()
+                .unwrap()
+                .data,
+            b"live"
+        );
+    }
+
+    #[test]
+    fn eviction_keeps_the_window_and_anything_pinned() {
+        let mut ring = ring_of(1_000, 30);
+        assert_eq!(ring.len(), 11);
+        assert_eq!(ring.frame(18).unwrap(), None);
+
+        ring.pin(Some(19));
+        for n in 30..40_i64 {
+            ring.push(frame(&n.to_le_bytes(), false), n * 100 * MS)
+                .unwrap();
+        }
+        assert_eq!(ring.len(), 21);
+        assert!(ring.frame(19).unwrap().is_some());
+
+        ring.pin(None);
+        ring.push(frame(b"next", false), 4_000 * MS).unwrap();
+        assert_eq!(ring.len(), 11);
+    }
+
+    #[test]
+    fn replay_starts_at_the_keyframe_before_the_requested_point() {
+        let ring = ring_of(10_000, 40);
+        // Newest is 3.9 s; 1.5 s back is 2.4 s; the keyframe before is 2.0 s.
+        assert_eq!(ring.replay_start(1_500 * MS), Some(20));
+        assert_eq!(ring.replay_start(0), Some(30));
+        // Further back than buffered: the oldest keyframe.
+        assert_eq!(ring.replay_start(60_000 * MS), Some(0));
+        assert_eq!(ring.replay_end(20, 1_000 * MS), Some(30));
+        assert_eq!(ring.replay_end(20, 60_000 * MS), Some(39));
+    }
+
+    #[test]
+    fn playback_stretches_source_time_by_the_speed() {
+        let playback = Playback::new(20, 30, 2_000 * MS, 0.5, 50_000 * MS);
+        assert_eq!(playback.output_timestamp_ns(2_000 * MS), 50_000 * MS);
+        assert_eq!(playback.output_timestamp_ns(2_100 * MS), 50_200 * MS);
+        assert!(!playback.is_finished());
+
+        let fast = Playback::new(0, 0, 0, 2.0, 0);
+        assert_eq!(fast.output_timestamp_ns(100 * MS), 50 * MS);
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Content-addressed payload stores for the replay ring.
+//!
+//! Payloads are keyed by the SHA-256 of their bytes, so a frame repeated
+//! bit-for-bit (a held slate, a static scene under CQP) is stored once
+//! however often it recurs. The ring counts references and removes a
+//! payload when its last frame leaves the window.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use sha2::{Digest as _, Sha256};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// SHA-256 of a frame payload.
+pub type Digest = [u8; 32];
+
+pub fn digest(data: &[u8]) -> Digest {
+    Sha256::digest(data).into()
+}
+
+/// Lowercase hex of a digest, as used for file names.
+pub fn hex(digest: &Digest) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Where payloads live, keyed by digest. `put` is only called for a
+/// digest the store doesn't hold.
+pub trait FrameStore: Send {
+    fn put(&mut self, digest: &Digest, data: &[u8]) -> Result<()>;
+    fn get(&self, digest: &Digest) -> Result<Vec<u8>>;
+    fn remove(&mut self, digest: &Digest) -> Result<()>;
+}
+
+/// Payloads in RAM.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    payloads: HashMap<Digest, Vec<u8>>,
+}
+
+impl FrameStore for MemoryStore {
+    fn put(&mut self, digest: &Digest, data: &[u8]) -> Result<()> {
+        self.payloads.insert(*digest, data.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, digest: &Digest) -> Result<Vec<u8>> {
+        self.payloads
+            .get(digest)
+            .cloned()
+            .ok_or_else(|| missing(digest))
+    }
+
+    fn remove(&mut self, digest: &Digest) -> Result<()> {
+        self.payloads.remove(digest);
+        Ok(())
+    }
+}
+
+/// One file per payload, named by its hex digest, under `directory`.
+#[derive(Debug)]
+pub struct DiskStore {
+    directory: PathBuf,
+}
+
+impl DiskStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    fn path(&self, digest: &Digest) -> PathBuf {
+        self.directory.join(format!("{}.bin", hex(digest)))
+    }
+}
+
+impl FrameStore for DiskStore {
+    /// Written under a temporary name and renamed into place, so a crash
+    /// mid-write never leaves a truncated payload under a valid digest.
+    fn put(&mut self, digest: &Digest, data: &[u8]) -> Result<()> {
+        let path = self.path(digest);
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, data)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn get(&self, digest: &Digest) -> Result<Vec<u8>> {
+        std::fs::read(self.path(digest)).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => missing(digest),
+            _ => e.into(),
+        })
+    }
+
+    fn remove(&mut self, digest: &Digest) -> Result<()> {
+        match std::fs::remove_file(self.path(digest)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn missing(digest: &Digest) -> Error {
+    Error::Runtime(format!(
+        "ReplayBuffer: payload {} not in store",
+        hex(digest)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disk_store_round_trips_and_removes_by_digest() {
+        let directory = std::env::temp_dir().join(format!(
+            "streamlib-replay-store-test-{}",
+            std::process::id()
+        ));
+        let mut store = DiskStore::new(&directory).unwrap();
+        let key = digest(b"frame");
+        store.put(&key, b"frame").unwrap();
+        assert!(directory.join(format!("{}.bin", hex(&key))).exists());
+        assert_eq!(store.get(&key).unwrap(), b"frame");
+
+        store.remove(&key).unwrap();
+        assert!(store.get(&key).is_err());
+        store.remove(&key).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
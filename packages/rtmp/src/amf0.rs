@@ -0,0 +1,288 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! AMF0 — the serialization RTMP commands and `onMetaData` are written in.
+//! Only the value types an ingest server sends a publisher are decoded.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const MARKER_NUMBER: u8 = 0x00;
+const MARKER_BOOLEAN: u8 = 0x01;
+const MARKER_STRING: u8 = 0x02;
+const MARKER_OBJECT: u8 = 0x03;
+const MARKER_NULL: u8 = 0x05;
+const MARKER_UNDEFINED: u8 = 0x06;
+const MARKER_ECMA_ARRAY: u8 = 0x08;
+const MARKER_OBJECT_END: u8 = 0x09;
+const MARKER_STRICT_ARRAY: u8 = 0x0A;
+const MARKER_LONG_STRING: u8 = 0x0C;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amf0Value {
+    Number(f64),
+    Boolean(bool),
+    String(String),
+    Object(Vec<(String, Amf0Value)>),
+    Null,
+    Undefined,
+    EcmaArray(Vec<(String, Amf0Value)>),
+    StrictArray(Vec<Amf0Value>),
+}
+
+impl Amf0Value {
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::String(value.into())
+    }
+
+    pub fn object<K: Into<String>>(properties: impl IntoIterator<Item = (K, Amf0Value)>) -> Self {
+        Self::Object(
+            properties
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// A property of an object or ECMA array.
+    pub fn get(&self, key: &str) -> Option<&Amf0Value> {
+        match self {
+            Self::Object(properties) | Self::EcmaArray(properties) => properties
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Number(value) => {
+                out.push(MARKER_NUMBER);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            Self::Boolean(value) => {
+                out.push(MARKER_BOOLEAN);
+                out.push(u8::from(*value));
+            }
+            Self::String(value) => {
+                if value.len() > usize::from(u16::MAX) {
+                    out.push(MARKER_LONG_STRING);
+                    out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                    out.extend_from_slice(value.as_bytes());
+                } else {
+                    out.push(MARKER_STRING);
+                    encode_short_string(value, out);
+                }
+            }
+            Self::Object(properties) => {
+                out.push(MARKER_OBJECT);
+                encode_properties(properties, out);
+            }
+            Self::Null => out.push(MARKER_NULL),
+            Self::Undefined => out.push(MARKER_UNDEFINED),
+            Self::EcmaArray(properties) => {
+                out.push(MARKER_ECMA_ARRAY);
+                out.extend_from_slice(&(properties.len() as u32).to_be_bytes());
+                encode_properties(properties, out);
+            }
+            Self::StrictArray(values) => {
+                out.push(MARKER_STRICT_ARRAY);
+                out.extend_from_slice(&(values.len() as u32).to_be_bytes());
+                for value in values {
+                    value.encode(out);
+                }
+            }
+        }
+    }
+}
+
+fn encode_short_string(value: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn encode_properties(properties: &[(String, Amf0Value)], out: &mut Vec<u8>) {
+    for (key, value) in properties {
+        encode_short_string(key, out);
+        value.encode(out);
+    }
+    out.extend_from_slice(&[0, 0, MARKER_OBJECT_END]);
+}
+
+/// Encode a command or data message body: each value in turn.
+pub fn encode_values(values: &[Amf0Value]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in values {
+        value.encode(&mut out);
+    }
+    out
+}
+
+/// Decode every value in a command or data message body.
+pub fn decode_values(mut data: &[u8]) -> Result<Vec<Amf0Value>> {
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        values.push(decode_value(&mut data)?);
+    }
+    Ok(values)
+}
+
+fn truncated() -> Error {
+    Error::Runtime("RTMP: truncated AMF0 value".into())
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if data.len() < len {
+        return Err(truncated());
+    }
+    let (head, rest) = data.split_at(len);
+    *data = rest;
+    Ok(head)
+}
+
+fn decode_u16(data: &mut &[u8]) -> Result<usize> {
+    let bytes = take(data, 2)?;
+    Ok(usize::from(u16::from_be_bytes([bytes[0], bytes[1]])))
+}
+
+fn decode_u32(data: &mut &[u8]) -> Result<usize> {
+    let bytes = take(data, 4)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+}
+
+fn decode_string(data: &mut &[u8], len: usize) -> Result<String> {
+    Ok(String::from_utf8_lossy(take(data, len)?).into_owned())
+}
+
+fn decode_properties(data: &mut &[u8]) -> Result<Vec<(String, Amf0Value)>> {
+    let mut properties = Vec::new();
+    loop {
+        let key_len = decode_u16(data)?;
+        if key_len == 0 && data.first() == Some(&MARKER_OBJECT_END) {
+            take(data, 1)?;
+            return Ok(properties);
+        }
+        let key = decode_string(data, key_len)?;
+        properties.push((key, decode_value(data)?));
+    }
+}
+
+fn decode_value(data: &mut &[u8]) -> Result<Amf0Value> {
+    let marker = take(data, 1)?[0];
+    let value = match marker {
+        MARKER_NUMBER => {
+            let bytes = take(data, 8)?;
+            Amf0Value::Number(f64::from_be_bytes(bytes.try_into().expect("8 bytes")))
+        }
+        MARKER_BOOLEAN => Amf0Value::Boolean(take(data, 1)?[0] != 0),
+        MARKER_STRING => {
+            let len = decode_u16(data)?;
+            Amf0Value::String(decode_string(data, len)?)
+        }
+        MARKER_LONG_STRING => {
+            let len = decode_u32(data)?;
+            Amf0Value::String(decode_string(data, len)?)
+        }
+        MARKER_OBJECT => Amf0Value::Object(decode_properties(data)?),
+        MARKER_NULL => Amf0Value::Null,
+        MARKER_UNDEFINED => Amf0Value::Undefined,
+        MARKER_ECMA_ARRAY => {
+            // The count is advisory; the end marker terminates the array.
+            decode_u32(data)?;
+            Amf0Value::EcmaArray(decode_properties(data)?)
+        }
+        MARKER_STRICT_ARRAY => {
+            let count = decode_u32(data)?;
+            let mut values = Vec::with_capacity(count.min(1024));
+            for _ in 0..count {
+                values.push(decode_value(data)?);
+            }
+            Amf0Value::StrictArray(values)
+        }
+        other => {
+            return Err(Error::Runtime(format!(
+                "RTMP: unsupported AMF0 marker 0x{other:02x}"
+            )));
+        }
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_command_round_trips() {
+        let values = vec![
+            Amf0Value::string("connect"),
+            Amf0Value::Number(1.0),
+            Amf0Value::object([
+                ("app", Amf0Value::string("live2")),
+                ("type", Amf0Value::string("nonprivate")),
+                ("fpad", Amf0Value::Boolean(false)),
+            ]),
+            Amf0Value::Null,
+        ];
+        let encoded = encode_values(&values);
+        assert_eq!(&encoded[..3], &[MARKER_STRING, 0, 7]);
+        assert_eq!(decode_values(&encoded).unwrap(), values);
+    }
+
+    #[test]
+    fn numbers_are_big_endian_doubles() {
+        let encoded = encode_values(&[Amf0Value::Number(1.0)]);
+        assert_eq!(encoded, [0x00, 0x3F, 0xF0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn on_status_info_object_is_searchable() {
+        let encoded = encode_values(&[
+            Amf0Value::string("onStatus"),
+            Amf0Value::Number(0.0),
+            Amf0Value::Null,
+            Amf0Value::object([
+                ("level", Amf0Value::string("status")),
+                ("code", Amf0Value::string("NetStream.Publish.Start")),
+            ]),
+        ]);
+        let values = decode_values(&encoded).unwrap();
+        assert_eq!(
+            values[3].get("code").and_then(Amf0Value::as_str),
+            Some("NetStream.Publish.Start")
+        );
+        assert_eq!(values[1].as_number(), Some(0.0));
+    }
+
+    #[test]
+    fn ecma_arrays_carry_a_count() {
+        let encoded = encode_values(&[Amf0Value::EcmaArray(vec![(
+            "videocodecid".into(),
+            Amf0Value::Number(7.0),
+        )])]);
+        assert_eq!(&encoded[..5], &[MARKER_ECMA_ARRAY, 0, 0, 0, 1]);
+        assert_eq!(&encoded[encoded.len() - 3..], &[0, 0, MARKER_OBJECT_END]);
+        assert!(decode_values(&encoded).is_ok());
+    }
+
+    #[test]
+    fn truncated_values_are_errors() {
+        assert!(decode_values(&[MARKER_NUMBER, 0x3F]).is_err());
+        assert!(decode_values(&[MARKER_STRING, 0, 5, b'a']).is_err());
+        assert!(decode_values(&[0x42]).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! FLV tag bodies — what RTMP video and audio messages carry.
+//!
+//! Video is H.264: Annex-B access units from the encoders are re-framed
+//! as 4-byte length-prefixed NAL units, and the SPS/PPS of a keyframe go
+//! out once as an `AVCDecoderConfigurationRecord` sequence header. Audio
+//! is AAC: ADTS headers are stripped, and the stream's
+//! `AudioSpecificConfig` goes out once as the AAC sequence header.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const NAL_TYPE_SPS: u8 = 7;
+const NAL_TYPE_PPS: u8 = 8;
+const NAL_TYPE_AUD: u8 = 9;
+
+/// FLV `CodecID` for AVC.
+const FLV_CODEC_AVC: u8 = 7;
+const FLV_FRAME_KEY: u8 = 1;
+const FLV_FRAME_INTER: u8 = 2;
+const AVC_PACKET_SEQUENCE_HEADER: u8 = 0;
+const AVC_PACKET_NALU: u8 = 1;
+
+/// `SoundFormat` 10 (AAC) with the flags the spec fixes for it: 44 kHz,
+/// 16-bit, stereo — decoders read the real values from the
+/// `AudioSpecificConfig`.
+const FLV_AUDIO_AAC: u8 = 0xAF;
+const AAC_PACKET_SEQUENCE_HEADER: u8 = 0;
+const AAC_PACKET_RAW: u8 = 1;
+
+/// AAC-LC audio object type.
+const AAC_OBJECT_TYPE_LC: u8 = 2;
+
+/// Samples per channel in one AAC access unit.
+pub const AAC_FRAME_SAMPLES: u32 = 1024;
+
+/// Sampling frequencies with an ADTS / AudioSpecificConfig index.
+const SAMPLING_FREQUENCIES: [u32; 13] = [
+    96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025, 8_000,
+    7_350,
+];
+
+/// The NAL units of an Annex-B access unit, start codes stripped.
+pub fn annex_b_nal_units(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            starts.push(i + 3);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    let ends: Vec<usize> = starts
+        .iter()
+        .skip(1)
+        .map(|&next| {
+            // A 4-byte start code's leading zero belongs to it, not to the
+            // NAL unit before.
+            let end = next - 3;
+            if end > 0 && data[end - 1] == 0 {
+                end - 1
+            } else {
+                end
+            }
+        })
+        .chain(std::iter::once(data.len()))
+        .collect();
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(move |(start, end)| &data[start..end.max(start)])
+        .filter(|nal| !nal.is_empty())
+}
+
+fn nal_type(nal: &[u8]) -> u8 {
+    nal[0] & 0x1F
+}
+
+/// The `AVCDecoderConfigurationRecord` for an access unit's SPS and PPS;
+/// `None` when it carries neither.
+pub fn avc_decoder_configuration(access_unit: &[u8]) -> Option<Vec<u8>> {
+    let mut sps = None;
+    let mut pps = None;
+    for nal in annex_b_nal_units(access_unit) {
+        match nal_type(nal) {
+            NAL_TYPE_SPS if sps.is_none() && nal.len() >= 4 => sps = Some(nal),
+            NAL_TYPE_PPS if pps.is_none() => pps = Some(nal),
+            _ => {}
+        }
+    }
+    let (sps, pps) = (sps?, pps?);
+    // Version 1; profile, constraint flags and level straight from the
+    // SPS; 4-byte NAL unit lengths; one SPS.
+    let mut record = vec![1, sps[1], sps[2], sps[3], 0xFF, 0xE1];
+    record.extend_from_slice(&(sps.len() as u16).to_be_bytes());
+    record.extend_from_slice(sps);
+    record.push(1);
+    record.extend_from_slice(&(pps.len() as u16).to_be_bytes());
+    record.extend_from_slice(pps);
+    Some(record)
+}
+
+/// Video tag body for the AVC sequence header.
+pub fn avc_sequence_header_tag(configuration: &[u8]) -> Vec<u8> {
+    let mut tag = vec![
+        (FLV_FRAME_KEY << 4) | FLV_CODEC_AVC,
+        AVC_PACKET_SEQUENCE_HEADER,
+        0,
+        0,
+        0,
+    ];
+    tag.extend_from_slice(configuration);
+    tag
+}
+
+/// Video tag body for one access unit: length-prefixed NAL units without
+/// parameter sets or access unit delimiters (the sequence header carries
+/// the former). `None` when nothing is left to send. The composition time
+/// offset is 0 — the encoders emit no B-frames.
+pub fn avc_access_unit_tag(access_unit: &[u8], is_keyframe: bool) -> Option<Vec<u8>> {
+    let frame_type = if is_keyframe {
+        FLV_FRAME_KEY
+    } else {
+        FLV_FRAME_INTER
+    };
+    let mut tag = vec![(frame_type << 4) | FLV_CODEC_AVC, AVC_PACKET_NALU, 0, 0, 0];
+    let header_len = tag.len();
+    for nal in annex_b_nal_units(access_unit) {
+        if matches!(nal_type(nal), NAL_TYPE_SPS | NAL_TYPE_PPS | NAL_TYPE_AUD) {
+            continue;
+        }
+        tag.extend_from_slice(&(nal.len() as u32).to_be_bytes());
+        tag.extend_from_slice(nal);
+    }
+    (tag.len() > header_len).then_some(tag)
+}
+
+/// An AAC stream's configuration, as the sequence header signals it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AacConfig {
+    pub object_type: u8,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+impl AacConfig {
+    /// AAC-LC at `sample_rate` with `channels`.
+    pub fn low_complexity(sample_rate: u32, channels: u8) -> Result<Self> {
+        let config = Self {
+            object_type: AAC_OBJECT_TYPE_LC,
+            sample_rate,
+            channels,
+        };
+        config.frequency_index()?;
+        if !(1..=7).contains(&channels) {
+            return Err(Error::Configuration(format!(
+                "RTMP: AAC can't signal {channels} channels"
+            )));
+        }
+        Ok(config)
+    }
+
+    fn frequency_index(&self) -> Result<u8> {
+        SAMPLING_FREQUENCIES
+            .iter()
+            .position(|&rate| rate == self.sample_rate)
+            .map(|index| index as u8)
+            .ok_or_else(|| {
+                Error::Configuration(format!(
+                    "RTMP: AAC has no sampling frequency index for {}Hz",
+                    self.sample_rate
+                ))
+            })
+    }
+
+    /// The 2-byte `AudioSpecificConfig`.
+    pub fn audio_specific_config(&self) -> Result<[u8; 2]> {
+        let frequency_index = self.frequency_index()?;
+        Ok([
+            (self.object_type << 3) | (frequency_index >> 1),
+            ((frequency_index & 1) << 7) | (self.channels << 3),
+        ])
+    }
+}
+
+/// Audio tag body for the AAC sequence header.
+pub fn aac_sequence_header_tag(config: &AacConfig) -> Result<Vec<u8>> {
+    let mut tag = vec![FLV_AUDIO_AAC, AAC_PACKET_SEQUENCE_HEADER];
+    tag.extend_from_slice(&config.audio_specific_config()?);
+    Ok(tag)
+}
+
+/// Audio tag body for one raw AAC access unit.
+pub fn aac_raw_tag(access_unit: &[u8]) -> Vec<u8> {
+    let mut tag = Vec::with_capacity(access_unit.len() + 2);
+    tag.extend_from_slice(&[FLV_AUDIO_AAC, AAC_PACKET_RAW]);
+    tag.extend_from_slice(access_unit);
+    tag
+}
+
+/// Whether `data` starts with an ADTS header.
+pub fn is_adts(data: &[u8]) -> bool {
+    data.len() >= 7 && data[0] == 0xFF && data[1] & 0xF6 == 0xF0
+}
+
+/// Split ADTS-framed audio into its access units, reading the stream
+/// configuration from the first header.
+pub fn split_adts(mut data: &[u8]) -> Result<(AacConfig, Vec<&[u8]>)> {
+    let mut config = None;
+    let mut access_units = Vec::new();
+    while !data.is_empty() {
+        if !is_adts(data) {
+            return Err(Error::Runtime("RTMP: lost ADTS sync in audio frame".into()));
+        }
+        let protection_absent = data[1] & 0x01 != 0;
+        let header_len = if protection_absent { 7 } else { 9 };
+        let frame_len = (usize::from(data[3] & 0x03) << 11)
+            | (usize::from(data[4]) << 3)
+            | usize::from(data[5] >> 5);
+        if frame_len < header_len || frame_len > data.len() {
+            return Err(Error::Runtime(format!(
+                "RTMP: ADTS frame length {frame_len} doesn't fit its {} byte buffer",
+                data.len()
+            )));
+        }
+        if config.is_none() {
+            let frequency_index = usize::from((data[2] >> 2) & 0x0F);
+            config = Some(AacConfig {
+                object_type: (data[2] >> 6) + 1,
+                sample_rate: *SAMPLING_FREQUENCIES.get(frequency_index).ok_or_else(|| {
+                    Error::Runtime(format!(
+                        "RTMP: reserved ADTS sampling frequency index {frequency_index}"
+                    ))
+                })?,
+                channels: ((data[2] & 0x01) << 2) | (data[3] >> 6),
+            });
+        }
+        access_units.push(&data[header_len..frame_len]);
+        data = &data[frame_len..];
+    }
+    let config = config.ok_or_else(|| Error::Runtime("RTMP: empty audio frame".into()))?;
+    Ok((config, access_units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPS: &[u8] = &[0x67, 0x64, 0x00, 0x1F, 0xAC, 0xD9];
+    const PPS: &[u8] = &[0x68, 0xEB, 0xE3];
+    const IDR: &[u8] = &[0x65, 0x88, 0x84, 0x21];
+
+    fn access_unit(nals: &[&[u8]]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for (i, nal) in nals.iter().enumerate() {
+            // Mix 4- and 3-byte start codes.
+            if i % 2 == 0 {
+                data.extend_from_slice(&[0, 0, 0, 1]);
+            } else {
+                data.extend_from_slice(&[0, 0, 1]);
+            }
+            data.extend_from_slice(nal);
+        }
+        data
+    }
+
+    #[test]
+    fn annex_b_splits_on_both_start_code_lengths() {
+        let data = access_unit(&[SPS, PPS, IDR]);
+        let nals: Vec<_> = annex_b_nal_units(&data).collect();
+        assert_eq!(nals, [SPS, PPS, IDR]);
+    }
+
+    #[test]
+    fn decoder_configuration_wraps_sps_and_pps() {
+        let record = avc_decoder_configuration(&access_unit(&[SPS, PPS, IDR])).unwrap();
+        assert_eq!(&record[..6], &[1, 0x64, 0x00, 0x1F, 0xFF, 0xE1]);
+        assert_eq!(&record[6..8], &[0, SPS.len() as u8]);
+        assert_eq!(&record[8..8 + SPS.len()], SPS);
+        let pps_at = 8 + SPS.len();
+        assert_eq!(&record[pps_at..pps_at + 3], &[1, 0, PPS.len() as u8]);
+        assert!(avc_decoder_configuration(&access_unit(&[IDR])).is_none());
+    }
+
+    #[test]
+    fn access_unit_tag_is_length_prefixed_without_parameter_sets() {
+        let aud: &[u8] = &[0x09, 0xF0];
+        let tag = avc_access_unit_tag(&access_unit(&[aud, SPS, PPS, IDR]), true).unwrap();
+        assert_eq!(&tag[..5], &[0x17, 1, 0, 0, 0]);
+        assert_eq!(&tag[5..9], &(IDR.len() as u32).to_be_bytes());
+        assert_eq!(&tag[9..], IDR);
+
+        let p_slice: &[u8] = &[0x41, 0x9A];
+        assert_eq!(
+            avc_access_unit_tag(&access_unit(&[p_slice]), false).unwrap()[0],
+            0x27
+        );
+        assert!(avc_access_unit_tag(&access_unit(&[SPS, PPS]), true).is_none());
+    }
+
+    #[test]
+    fn audio_specific_config_for_48k_stereo_lc() {
+        let config = AacConfig::low_complexity(48_000, 2).unwrap();
+        assert_eq!(config.audio_specific_config().unwrap(), [0x11, 0x90]);
+        assert_eq!(
+            aac_sequence_header_tag(&config).unwrap(),
+            [0xAF, 0x00, 0x11, 0x90]
+        );
+        assert!(AacConfig::low_complexity(47_000, 2).is_err());
+    }
+
+    #[test]
+    fn adts_frames_are_split_and_configured() {
+        // 48 kHz (index 3), AAC-LC, stereo, 2-byte payloads.
+        let frame = |payload: [u8; 2]| {
+            let len = 9u16;
+            vec![
+                0xFF,
+                0xF1,
+                (1 << 6) | (3 << 2),
+                (2 << 6) | (len >> 11) as u8,
+                (len >> 3) as u8,
+                ((len & 0x7) as u8) << 5 | 0x1F,
+                0xFC,
+                payload[0],
+                payload[1],
+            ]
+        };
+        let mut data = frame([1, 2]);
+        data.extend(frame([3, 4]));
+        let (config, access_units) = split_adts(&data).unwrap();
+        assert_eq!(config, AacConfig::low_complexity(48_000, 2).unwrap());
+        assert_eq!(access_units, [&[1u8, 2][..], &[3, 4]]);
+
+        assert!(split_adts(&data[..8]).is_err());
+        assert!(!is_adts(&[1, 2]));
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/rtmp` — RTMP output. `RtmpSink` pushes H.264 + AAC straight
+//! to YouTube, Twitch or any RTMP/RTMPS ingest, without an external ffmpeg
+//! relay process.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod amf0;
+pub mod flv;
+pub mod rtmp_client;
+pub mod rtmp_sink;
+
+pub use _generated_::RtmpSinkConfig;
+pub use rtmp_client::{RtmpEndpoint, RtmpPublisher};
+pub use rtmp_sink::RtmpSinkProcessor;
+
+streamlib_plugin_abi::export_plugin!(crate::RtmpSinkProcessor::Processor,);
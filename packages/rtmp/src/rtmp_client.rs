@@ -0,0 +1,636 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! A minimal RTMP publishing client: the plain (librtmp-style) handshake,
+//! chunk stream framing and the `connect` → `createStream` → `publish`
+//! command exchange ingest servers (YouTube, Twitch, nginx-rtmp) expect.
+//! `rtmps://` URLs run the same protocol over TLS.
+//!
+//! Blocking I/O on the caller's thread: a publisher only writes once
+//! publishing has started, and the commands before that are answered
+//! within the connect timeout.
+
+use crate::amf0::{Amf0Value, decode_values, encode_values};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const RTMP_VERSION: u8 = 3;
+const HANDSHAKE_SIZE: usize = 1536;
+const DEFAULT_RTMP_PORT: u16 = 1935;
+const DEFAULT_RTMPS_PORT: u16 = 443;
+
+/// Protocol default until either side sends Set Chunk Size.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+/// What the client announces for its own chunks.
+const OUTGOING_CHUNK_SIZE: usize = 4096;
+/// The server's announced chunk size is capped here before buffers are
+/// sized from it.
+const MAX_INCOMING_CHUNK_SIZE: usize = 1 << 24;
+
+const MSG_SET_CHUNK_SIZE: u8 = 1;
+const MSG_AUDIO: u8 = 8;
+const MSG_VIDEO: u8 = 9;
+const MSG_DATA_AMF0: u8 = 18;
+const MSG_COMMAND_AMF0: u8 = 20;
+
+const CSID_PROTOCOL_CONTROL: u8 = 2;
+const CSID_COMMAND: u8 = 3;
+const CSID_AUDIO: u8 = 4;
+const CSID_DATA: u8 = 5;
+const CSID_VIDEO: u8 = 6;
+
+/// Timestamps at or above this ride in the extended timestamp field.
+const EXTENDED_TIMESTAMP: u32 = 0xFF_FFFF;
+
+/// An `rtmp://` or `rtmps://` ingest URL, e.g.
+/// `rtmp://a.rtmp.youtube.com/live2` or `rtmps://live.twitch.tv/app`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtmpEndpoint {
+    pub tls: bool,
+    pub host: String,
+    pub port: u16,
+    /// Application name: the URL path without its slashes.
+    pub app: String,
+    /// The URL as the `connect` command's `tcUrl`.
+    pub tc_url: String,
+}
+
+impl RtmpEndpoint {
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| Error::Configuration(format!("RTMP: bad url '{url}': {reason}"));
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("rtmp://") {
+            (false, rest)
+        } else if let Some(rest) = url.strip_prefix("rtmps://") {
+            (true, rest)
+        } else {
+            return Err(invalid("expected rtmp:// or rtmps://"));
+        };
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let app = path.trim_matches('/').to_string();
+        if app.is_empty() {
+            return Err(invalid("no application name in the path"));
+        }
+        let default_port = if tls {
+            DEFAULT_RTMPS_PORT
+        } else {
+            DEFAULT_RTMP_PORT
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid("bad port"))?),
+            None => (authority, default_port),
+        };
+        if host.is_empty() {
+            return Err(invalid("no host"));
+        }
+        Ok(Self {
+            tls,
+            host: host.to_string(),
+            port,
+            app,
+            tc_url: url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+enum RtmpTransport {
+    Plain(TcpStream),
+    Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+}
+
+impl Read for RtmpTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for RtmpTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+fn tls_client_config() -> Result<Arc<rustls::ClientConfig>> {
+    let mut roots = rustls::RootCertStore::empty();
+    let native_certs = rustls_native_certs::load_native_certs();
+    for e in &native_certs.errors {
+        tracing::warn!(%e, "[RtmpSink] Skipping an unreadable native root certificate");
+    }
+    roots.add_parsable_certificates(native_certs.certs);
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| Error::Runtime(format!("RTMP: TLS setup failed: {e}")))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// One message reassembled from the server's chunks.
+struct RtmpMessage {
+    message_type: u8,
+    payload: Vec<u8>,
+}
+
+/// Per-chunk-stream header state for reading.
+#[derive(Default)]
+struct ChunkStreamState {
+    length: usize,
+    message_type: u8,
+    extended_timestamp: bool,
+    payload: Vec<u8>,
+}
+
+/// An RTMP connection that has been accepted for publishing.
+pub struct RtmpPublisher {
+    transport: RtmpTransport,
+    stream_key: String,
+    message_stream_id: u32,
+    incoming_chunk_size: usize,
+    chunk_streams: HashMap<u32, ChunkStreamState>,
+    next_transaction_id: f64,
+}
+
+impl RtmpPublisher {
+    /// Connect to `url`, then ask to publish `stream_key` on it. Returns
+    /// once the server has answered `NetStream.Publish.Start`.
+    pub fn connect(url: &str, stream_key: &str, timeout: Duration) -> Result<Self> {
+        let endpoint = RtmpEndpoint::parse(url)?;
+        let address = (endpoint.host.as_str(), endpoint.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::Runtime(format!("RTMP: {} did not resolve", endpoint.host)))?;
+        let tcp = TcpStream::connect_timeout(&address, timeout)?;
+        tcp.set_nodelay(true)?;
+        tcp.set_read_timeout(Some(timeout))?;
+        tcp.set_write_timeout(Some(timeout))?;
+        let transport = if endpoint.tls {
+            let server_name = rustls::pki_types::ServerName::try_from(endpoint.host.clone())
+                .map_err(|e| Error::Configuration(format!("RTMP: bad TLS host name: {e}")))?;
+            let connection = rustls::ClientConnection::new(tls_client_config()?, server_name)
+                .map_err(|e| Error::Runtime(format!("RTMP: TLS setup failed: {e}")))?;
+            RtmpTransport::Tls(Box::new(rustls::StreamOwned::new(connection, tcp)))
+        } else {
+            RtmpTransport::Plain(tcp)
+        };
+
+        let mut publisher = Self {
+            transport,
+            stream_key: stream_key.to_string(),
+            message_stream_id: 0,
+            incoming_chunk_size: DEFAULT_CHUNK_SIZE,
+            chunk_streams: HashMap::new(),
+            next_transaction_id: 1.0,
+        };
+        let deadline = Instant::now() + timeout;
+        publisher.handshake()?;
+        publisher.write_message(
+            CSID_PROTOCOL_CONTROL,
+            MSG_SET_CHUNK_SIZE,
+            0,
+            0,
+            &(OUTGOING_CHUNK_SIZE as u32).to_be_bytes(),
+        )?;
+        publisher.connect_app(&endpoint, deadline)?;
+        publisher.create_stream(deadline)?;
+        publisher.publish(deadline)?;
+        tracing::info!(
+            host = %endpoint.host,
+            app = %endpoint.app,
+            tls = endpoint.tls,
+            "[RtmpSink] Publishing"
+        );
+        Ok(publisher)
+    }
+
+    /// C0+C1, S0+S1, C2 (S1 echoed), S2 — the plain handshake, without
+    /// the digest scheme Flash Player used.
+    fn handshake(&mut self) -> Result<()> {
+        let mut c0c1 = vec![0u8; 1 + HANDSHAKE_SIZE];
+        c0c1[0] = RTMP_VERSION;
+        // Time and zero fields stay 0; the rest is random.
+        for byte in &mut c0c1[9..] {
+            *byte = fastrand::u8(..);
+        }
+        self.transport.write_all(&c0c1)?;
+        self.transport.flush()?;
+
+        let mut s0s1 = vec![0u8; 1 + HANDSHAKE_SIZE];
+        self.transport.read_exact(&mut s0s1)?;
+        if s0s1[0] != RTMP_VERSION {
+            return Err(Error::Runtime(format!(
+                "RTMP: server answered the handshake with version {}",
+                s0s1[0]
+            )));
+        }
+        self.transport.write_all(&s0s1[1..])?;
+        self.transport.flush()?;
+        let mut s2 = vec![0u8; HANDSHAKE_SIZE];
+        self.transport.read_exact(&mut s2)?;
+        Ok(())
+    }
+
+    fn connect_app(&mut self, endpoint: &RtmpEndpoint, deadline: Instant) -> Result<()> {
+        let transaction_id = self.send_command(
+            0,
+            "connect",
+            vec![Amf0Value::object([
+                ("app", Amf0Value::string(endpoint.app.clone())),
+                ("type", Amf0Value::string("nonprivate")),
+                (
+                    "flashVer",
+                    Amf0Value::string("FMLE/3.0 (compatible; streamlib)"),
+                ),
+                ("tcUrl", Amf0Value::string(endpoint.tc_url.clone())),
+            ])],
+        )?;
+        self.await_result(transaction_id, "connect", deadline)?;
+        Ok(())
+    }
+
+    fn create_stream(&mut self, deadline: Instant) -> Result<()> {
+        // Announced the way FMLE / OBS do; servers that don't know these
+        // commands ignore them.
+        let key = Amf0Value::string(self.stream_key.clone());
+        self.send_command(0, "releaseStream", vec![Amf0Value::Null, key.clone()])?;
+        self.send_command(0, "FCPublish", vec![Amf0Value::Null, key])?;
+        let transaction_id = self.send_command(0, "createStream", vec![Amf0Value::Null])?;
+        let result = self.await_result(transaction_id, "createStream", deadline)?;
+        let stream_id = result
+            .get(3)
+            .and_then(Amf0Value::as_number)
+            .ok_or_else(|| Error::Runtime("RTMP: createStream returned no stream id".into()))?;
+        self.message_stream_id = stream_id as u32;
+        Ok(())
+    }
+
+    fn publish(&mut self, deadline: Instant) -> Result<()> {
+        self.send_command(
+            self.message_stream_id,
+            "publish",
+            vec![
+                Amf0Value::Null,
+                Amf0Value::string(self.stream_key.clone()),
+                Amf0Value::string("live"),
+            ],
+        )?;
+        loop {
+            let values = self.read_command(deadline)?;
+            if values.first().and_then(Amf0Value::as_str) != Some("onStatus") {
+                continue;
+            }
+            let info = values.get(3);
+            let code = info
+                .and_then(|info| info.get("code"))
+                .and_then(Amf0Value::as_str)
+                .unwrap_or_default();
+            if code == "NetStream.Publish.Start" {
+                return Ok(());
+            }
+            let level = info
+                .and_then(|info| info.get("level"))
+                .and_then(Amf0Value::as_str);
+            if level == Some("error") || code.contains("Failed") || code.contains("BadName") {
+                let description = info
+                    .and_then(|info| info.get("description"))
+                    .and_then(Amf0Value::as_str)
+                    .unwrap_or_default();
+                return Err(Error::Runtime(format!(
+                    "RTMP: publish refused ({code}) {description}"
+                )));
+            }
+        }
+    }
+
+    /// Send `onMetaData` through `@setDataFrame`, so it's replayed to
+    /// every viewer.
+    pub fn send_metadata(&mut self, metadata: Vec<(String, Amf0Value)>) -> Result<()> {
+        let payload = encode_values(&[
+            Amf0Value::string("@setDataFrame"),
+            Amf0Value::string("onMetaData"),
+            Amf0Value::EcmaArray(metadata),
+        ]);
+        self.write_message(
+            CSID_DATA,
+            MSG_DATA_AMF0,
+            self.message_stream_id,
+            0,
+            &payload,
+        )
+    }
+
+    pub fn send_video(&mut self, timestamp_ms: u32, tag: &[u8]) -> Result<()> {
+        self.write_message(
+            CSID_VIDEO,
+            MSG_VIDEO,
+            self.message_stream_id,
+            timestamp_ms,
+            tag,
+        )
+    }
+
+    pub fn send_audio(&mut self, timestamp_ms: u32, tag: &[u8]) -> Result<()> {
+        self.write_message(
+            CSID_AUDIO,
+            MSG_AUDIO,
+            self.message_stream_id,
+            timestamp_ms,
+            tag,
+        )
+    }
+
+    fn send_command(
+        &mut self,
+        message_stream_id: u32,
+        name: &str,
+        arguments: Vec<Amf0Value>,
+    ) -> Result<f64> {
+        let transaction_id = self.next_transaction_id;
+        self.next_transaction_id += 1.0;
+        let mut values = vec![Amf0Value::string(name), Amf0Value::Number(transaction_id)];
+        values.extend(arguments);
+        self.write_message(
+            CSID_COMMAND,
+            MSG_COMMAND_AMF0,
+            message_stream_id,
+            0,
+            &encode_values(&values),
+        )?;
+        Ok(transaction_id)
+    }
+
+    /// Read commands until the `_result` / `_error` for `transaction_id`.
+    fn await_result(
+        &mut self,
+        transaction_id: f64,
+        command: &str,
+        deadline: Instant,
+    ) -> Result<Vec<Amf0Value>> {
+        loop {
+            let values = self.read_command(deadline)?;
+            if values.get(1).and_then(Amf0Value::as_number) != Some(transaction_id) {
+                continue;
+            }
+            match values.first().and_then(Amf0Value::as_str) {
+                Some("_result") => return Ok(values),
+                Some("_error") => {
+                    let description = values
+                        .get(3)
+                        .and_then(|info| info.get("description").or_else(|| info.get("code")))
+                        .and_then(Amf0Value::as_str)
+                        .unwrap_or("no description");
+                    return Err(Error::Runtime(format!(
+                        "RTMP: {command} refused: {description}"
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The next AMF0 command from the server; other messages are consumed.
+    fn read_command(&mut self, deadline: Instant) -> Result<Vec<Amf0Value>> {
+        loop {
+            if Instant::now() >= deadline {
+                return Err(Error::Runtime(
+                    "RTMP: timed out waiting for the server".into(),
+                ));
+            }
+            let message = self.read_message()?;
+            match message.message_type {
+                MSG_COMMAND_AMF0 => return decode_values(&message.payload),
+                MSG_SET_CHUNK_SIZE if message.payload.len() >= 4 => {
+                    let size =
+                        u32::from_be_bytes(message.payload[..4].try_into().expect("4 bytes"))
+                            & 0x7FFF_FFFF;
+                    self.incoming_chunk_size = (size as usize).clamp(1, MAX_INCOMING_CHUNK_SIZE);
+                }
+                // Window acknowledgement size, peer bandwidth, user control:
+                // nothing a short-lived publisher handshake has to answer.
+                _ => {}
+            }
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let mut byte = [0u8; 1];
+        self.transport.read_exact(&mut byte)?;
+        Ok(byte[0])
+    }
+
+    fn read_be(&mut self, len: usize) -> Result<u32> {
+        let mut bytes = [0u8; 4];
+        self.transport.read_exact(&mut bytes[4 - len..])?;
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    /// Read chunks until one message is complete.
+    fn read_message(&mut self) -> Result<RtmpMessage> {
+        loop {
+            let basic_header = self.read_u8()?;
+            let format = basic_header >> 6;
+            let chunk_stream_id = match basic_header & 0x3F {
+                0 => 64 + u32::from(self.read_u8()?),
+                1 => {
+                    let low = u32::from(self.read_u8()?);
+                    64 + low + (u32::from(self.read_u8()?) << 8)
+                }
+                id => u32::from(id),
+            };
+
+            let mut state = self
+                .chunk_streams
+                .remove(&chunk_stream_id)
+                .unwrap_or_default();
+            if format <= 2 {
+                let timestamp = self.read_be(3)?;
+                state.extended_timestamp = timestamp == EXTENDED_TIMESTAMP;
+                if format <= 1 {
+                    state.length = self.read_be(3)? as usize;
+                    state.message_type = self.read_u8()?;
+                }
+                if format == 0 {
+                    // Message stream id, little-endian; unused by a publisher.
+                    let mut stream_id = [0u8; 4];
+                    self.transport.read_exact(&mut stream_id)?;
+                }
+            }
+            if state.extended_timestamp {
+                self.read_be(4)?;
+            }
+
+            let remaining = state.length.saturating_sub(state.payload.len());
+            let mut chunk = vec![0u8; remaining.min(self.incoming_chunk_size)];
+            self.transport.read_exact(&mut chunk)?;
+            state.payload.extend_from_slice(&chunk);
+
+            if state.payload.len() >= state.length {
+                let message = RtmpMessage {
+                    message_type: state.message_type,
+                    payload: std::mem::take(&mut state.payload),
+                };
+                self.chunk_streams.insert(chunk_stream_id, state);
+                return Ok(message);
+            }
+            self.chunk_streams.insert(chunk_stream_id, state);
+        }
+    }
+
+    /// Write one message: a type-0 chunk header, then type-3 continuation
+    /// chunks every [`OUTGOING_CHUNK_SIZE`] bytes.
+    fn write_message(
+        &mut self,
+        chunk_stream_id: u8,
+        message_type: u8,
+        message_stream_id: u32,
+        timestamp_ms: u32,
+        payload: &[u8],
+    ) -> Result<()> {
+        let buffer = encode_chunks(
+            chunk_stream_id,
+            message_type,
+            message_stream_id,
+            timestamp_ms,
+            payload,
+            OUTGOING_CHUNK_SIZE,
+        );
+        self.transport.write_all(&buffer)?;
+        self.transport.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for RtmpPublisher {
+    fn drop(&mut self) {
+        // Best effort: lets the server end the broadcast now rather than on
+        // its idle timeout.
+        let key = Amf0Value::string(self.stream_key.clone());
+        let _ = self.send_command(0, "FCUnpublish", vec![Amf0Value::Null, key]);
+        let stream_id = Amf0Value::Number(f64::from(self.message_stream_id));
+        let _ = self.send_command(0, "deleteStream", vec![Amf0Value::Null, stream_id]);
+    }
+}
+
+/// Frame one message as chunks of at most `chunk_size` payload bytes.
+fn encode_chunks(
+    chunk_stream_id: u8,
+    message_type: u8,
+    message_stream_id: u32,
+    timestamp_ms: u32,
+    payload: &[u8],
+    chunk_size: usize,
+) -> Vec<u8> {
+    let extended = timestamp_ms >= EXTENDED_TIMESTAMP;
+    let header_timestamp = timestamp_ms.min(EXTENDED_TIMESTAMP);
+    let chunks = payload.len().div_ceil(chunk_size).max(1);
+    let mut buffer = Vec::with_capacity(payload.len() + 16 + chunks * 5);
+
+    buffer.push(chunk_stream_id & 0x3F);
+    buffer.extend_from_slice(&header_timestamp.to_be_bytes()[1..]);
+    buffer.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    buffer.push(message_type);
+    buffer.extend_from_slice(&message_stream_id.to_le_bytes());
+    if extended {
+        buffer.extend_from_slice(&timestamp_ms.to_be_bytes());
+    }
+    for (i, chunk) in payload.chunks(chunk_size).enumerate() {
+        if i > 0 {
+            buffer.push(0xC0 | (chunk_stream_id & 0x3F));
+            if extended {
+                buffer.extend_from_slice(&timestamp_ms.to_be_bytes());
+            }
+        }
+        buffer.extend_from_slice(chunk);
+    }
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_parse_with_default_ports() {
+        let youtube = RtmpEndpoint::parse("rtmp://a.rtmp.youtube.com/live2").unwrap();
+        assert_eq!(
+            youtube,
+            RtmpEndpoint {
+                tls: false,
+                host: "a.rtmp.youtube.com".into(),
+                port: 1935,
+                app: "live2".into(),
+                tc_url: "rtmp://a.rtmp.youtube.com/live2".into(),
+            }
+        );
+
+        let twitch = RtmpEndpoint::parse("rtmps://live.twitch.tv:443/app/").unwrap();
+        assert!(twitch.tls);
+        assert_eq!((twitch.port, twitch.app.as_str()), (443, "app"));
+        assert_eq!(twitch.tc_url, "rtmps://live.twitch.tv:443/app");
+
+        let local = RtmpEndpoint::parse("rtmp://127.0.0.1:1936/live/cam").unwrap();
+        assert_eq!((local.port, local.app.as_str()), (1936, "live/cam"));
+    }
+
+    #[test]
+    fn bad_endpoints_are_configuration_errors() {
+        for url in [
+            "http://example.com/live",
+            "rtmp://example.com",
+            "rtmp://example.com:port/live",
+            "rtmp:///live",
+        ] {
+            assert!(
+                matches!(RtmpEndpoint::parse(url), Err(Error::Configuration(_))),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn messages_split_into_type_3_continuation_chunks() {
+        let payload: Vec<u8> = (0..10).collect();
+        let chunks = encode_chunks(CSID_VIDEO, MSG_VIDEO, 1, 40, &payload, 4);
+        // Type-0 header: csid, timestamp, length, type, stream id (LE).
+        assert_eq!(
+            &chunks[..12],
+            &[CSID_VIDEO, 0, 0, 40, 0, 0, 10, MSG_VIDEO, 1, 0, 0, 0]
+        );
+        assert_eq!(&chunks[12..16], &[0, 1, 2, 3]);
+        assert_eq!(chunks[16], 0xC0 | CSID_VIDEO);
+        assert_eq!(&chunks[17..21], &[4, 5, 6, 7]);
+        assert_eq!(chunks[21], 0xC0 | CSID_VIDEO);
+        assert_eq!(&chunks[22..], &[8, 9]);
+    }
+
+    #[test]
+    fn large_timestamps_use_the_extended_field() {
+        let timestamp = 0x0100_0000;
+        let chunks = encode_chunks(CSID_AUDIO, MSG_AUDIO, 1, timestamp, &[1, 2, 3], 2);
+        assert_eq!(&chunks[1..4], &[0xFF, 0xFF, 0xFF]);
+        assert_eq!(&chunks[12..16], &timestamp.to_be_bytes());
+        assert_eq!(&chunks[16..18], &[1, 2]);
+        // Continuation chunks repeat the extended timestamp.
+        assert_eq!(chunks[18], 0xC0 | CSID_AUDIO);
+        assert_eq!(&chunks[19..23], &timestamp.to_be_bytes());
+        assert_eq!(chunks[23], 3);
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! RTMP Sink — pushes H.264 + AAC straight to a streaming platform's RTMP
+//! ingest, with no ffmpeg relay in between.
+//!
+//! Publishing starts at the first video keyframe that carries SPS and
+//! PPS; audio before it is dropped so the broadcast opens on a decodable
+//! picture with audio alongside. Timestamps are milliseconds since the
+//! first frame sent on the connection. If the connection drops, the sink
+//! reconnects (at most every few seconds) and starts again from the next
+//! keyframe.
+
+use crate::_generated_::{EncodedAudioFrame, EncodedVideoFrame};
+use crate::amf0::Amf0Value;
+use crate::flv::{
+    AAC_FRAME_SAMPLES, AacConfig, aac_raw_tag, aac_sequence_header_tag, avc_access_unit_tag,
+    avc_decoder_configuration, avc_sequence_header_tag, is_adts, split_adts,
+};
+use crate::rtmp_client::RtmpPublisher;
+use std::time::{Duration, Instant};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const DEFAULT_CONNECT_TIMEOUT_MS: u32 = 10_000;
+
+/// Minimum spacing between reconnect attempts after the connection drops.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(3);
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/rtmp/RtmpSink",
+    description = "Publishes H.264 video and AAC audio to an RTMP or RTMPS ingest URL (YouTube, Twitch, nginx-rtmp)",
+    execution = reactive,
+    config = crate::_generated_::RtmpSinkConfig,
+    input("video_in", "@tatolab/core/EncodedVideoFrame", description = "H.264 Annex-B access units; publishing starts at the first keyframe carrying SPS and PPS"),
+    input("audio_in", "@tatolab/core/EncodedAudioFrame", description = "AAC access units, ADTS-framed or raw (raw needs audio_sample_rate and audio_channels)"),
+)]
+pub struct RtmpSinkProcessor {
+    publisher: Option<RtmpPublisher>,
+    last_connect_attempt: Option<Instant>,
+    /// `timestamp_ns` of the first frame sent on the current connection.
+    base_timestamp_ns: Option<i64>,
+    /// Sequence headers sent on the current connection; a change (new
+    /// SPS/PPS, new audio format) sends a fresh one.
+    sent_avc_configuration: Option<Vec<u8>>,
+    sent_aac_config: Option<AacConfig>,
+    /// Fallback for raw AAC input, from the config.
+    raw_aac_config: Option<AacConfig>,
+    video_frames_sent: u64,
+    audio_frames_sent: u64,
+    frames_dropped: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for RtmpSinkProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.raw_aac_config = match (self.config.audio_sample_rate, self.config.audio_channels) {
+            (Some(sample_rate), Some(channels)) => {
+                Some(AacConfig::low_complexity(sample_rate, channels)?)
+            }
+            (None, None) => None,
+            _ => {
+                return Err(Error::Configuration(
+                    "RtmpSink: set audio_sample_rate and audio_channels together".into(),
+                ));
+            }
+        };
+        // Connect up front so a bad URL or stream key fails the graph's
+        // start instead of surfacing as dropped frames.
+        self.connect()?;
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            video_frames_sent = self.video_frames_sent,
+            audio_frames_sent = self.audio_frames_sent,
+            frames_dropped = self.frames_dropped,
+            "[RtmpSink] Shutting down"
+        );
+        self.publisher.take();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if self.inputs.has_data("video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("video_in")?;
+            self.with_reconnect(|sink| sink.send_video(&frame))?;
+        }
+        if self.inputs.has_data("audio_in") {
+            let frame: EncodedAudioFrame = self.inputs.read("audio_in")?;
+            self.with_reconnect(|sink| sink.send_audio(&frame))?;
+        }
+        Ok(())
+    }
+}
+
+impl RtmpSinkProcessor::Processor {
+    fn connect(&mut self) -> Result<()> {
+        self.last_connect_attempt = Some(Instant::now());
+        let timeout = Duration::from_millis(u64::from(
+            self.config
+                .connect_timeout_ms
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS),
+        ));
+        let mut publisher =
+            RtmpPublisher::connect(&self.config.url, &self.config.stream_key, timeout)?;
+        publisher.send_metadata(vec![
+            ("videocodecid".into(), Amf0Value::Number(7.0)),
+            ("audiocodecid".into(), Amf0Value::Number(10.0)),
+            ("encoder".into(), Amf0Value::string("streamlib")),
+        ])?;
+        self.publisher = Some(publisher);
+        self.base_timestamp_ns = None;
+        self.sent_avc_configuration = None;
+        self.sent_aac_config = None;
+        Ok(())
+    }
+
+    /// Run `send` on a live connection. A dropped connection is reconnected
+    /// when [`RECONNECT_INTERVAL`] has passed since the last attempt;
+    /// until then — and when the attempt fails — frames are dropped rather
+    /// than failing the graph. Errors other than I/O (bad input) are
+    /// returned and leave the connection up.
+    fn with_reconnect(&mut self, send: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        if self.publisher.is_none() {
+            let due = self
+                .last_connect_attempt
+                .is_none_or(|attempt| attempt.elapsed() >= RECONNECT_INTERVAL);
+            if !due {
+                self.frames_dropped += 1;
+                return Ok(());
+            }
+            if let Err(e) = self.connect() {
+                tracing::warn!(%e, "[RtmpSink] Reconnect failed");
+                self.frames_dropped += 1;
+                return Ok(());
+            }
+            tracing::info!("[RtmpSink] Reconnected");
+        }
+        match send(self) {
+            Err(Error::Io(e)) => {
+                tracing::warn!(%e, "[RtmpSink] Connection lost");
+                self.publisher = None;
+                self.frames_dropped += 1;
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn timestamp_ms(&mut self, timestamp_ns: &str) -> u32 {
+        let timestamp_ns: i64 = timestamp_ns.parse().unwrap_or(0);
+        let base = *self.base_timestamp_ns.get_or_insert(timestamp_ns);
+        // RTMP timestamps are 32-bit milliseconds and wrap after ~49 days.
+        ((timestamp_ns - base).max(0) / 1_000_000) as u32
+    }
+
+    fn send_video(&mut self, frame: &EncodedVideoFrame) -> Result<()> {
+        if frame.is_keyframe {
+            if let Some(configuration) = avc_decoder_configuration(&frame.data) {
+                if self.sent_avc_configuration.as_ref() != Some(&configuration) {
+                    let timestamp_ms = self.timestamp_ms(&frame.timestamp_ns);
+                    self.publisher()?
+                        .send_video(timestamp_ms, &avc_sequence_header_tag(&configuration))?;
+                    self.sent_avc_configuration = Some(configuration);
+                }
+            }
+        }
+        if self.sent_avc_configuration.is_none() {
+            self.frames_dropped += 1;
+            return Ok(());
+        }
+        let Some(tag) = avc_access_unit_tag(&frame.data, frame.is_keyframe) else {
+            return Ok(());
+        };
+        let timestamp_ms = self.timestamp_ms(&frame.timestamp_ns);
+        self.publisher()?.send_video(timestamp_ms, &tag)?;
+
+        self.video_frames_sent += 1;
+        if self.video_frames_sent == 1 {
+            tracing::info!("[RtmpSink] First video frame sent");
+        }
+        Ok(())
+    }
+
+    fn send_audio(&mut self, frame: &EncodedAudioFrame) -> Result<()> {
+        // Hold audio until video is flowing; see the module docs.
+        if self.sent_avc_configuration.is_none() {
+            self.frames_dropped += 1;
+            return Ok(());
+        }
+        let (config, access_units) = if is_adts(&frame.data) {
+            split_adts(&frame.data)?
+        } else {
+            let config = self.raw_aac_config.ok_or_else(|| {
+                Error::Configuration(
+                    "RtmpSink: raw AAC input needs audio_sample_rate and audio_channels".into(),
+                )
+            })?;
+            (config, vec![frame.data.as_slice()])
+        };
+
+        let mut timestamp_ms = self.timestamp_ms(&frame.timestamp_ns);
+        if self.sent_aac_config != Some(config) {
+            self.publisher()?
+                .send_audio(timestamp_ms, &aac_sequence_header_tag(&config)?)?;
+            self.sent_aac_config = Some(config);
+        }
+        // Several ADTS frames in one buffer are spaced one access unit apart.
+        let frame_ms = AAC_FRAME_SAMPLES * 1000 / config.sample_rate;
+        for access_unit in access_units {
+            self.publisher()?
+                .send_audio(timestamp_ms, &aac_raw_tag(access_unit))?;
+            timestamp_ms += frame_ms;
+            self.audio_frames_sent += 1;
+        }
+        Ok(())
+    }
+
+    fn publisher(&mut self) -> Result<&mut RtmpPublisher> {
+        self.publisher
+            .as_mut()
+            .ok_or_else(|| Error::Runtime("RtmpSink: not connected".into()))
+    }
+}
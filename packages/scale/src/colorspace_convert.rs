@@ -0,0 +1,67 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! ColorspaceConvert (Linux) — BGRA frames rewritten as RGBA.
+//!
+//! Sources like `BgraFileSource` stage BGRA bytes into an RGBA texture, so
+//! consumers sampling it see red and blue swapped. One 1:1 `scale.comp`
+//! dispatch per frame swaps them back into an RGBA8 output ring; size and
+//! color description are unchanged. The runtime inserts it automatically
+//! between a `bgra8` output and an `rgba8` input when auto-convert is on.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::VideoFrame;
+use crate::params::ScaleParams;
+use crate::scaler::{GpuScaler, OutputRing};
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scale/ColorspaceConvert",
+    description = "Reorders BGRA frames into RGBA on the GPU, at the same size. Inserted automatically between BGRA producers and RGBA consumers when auto-convert is on.",
+    execution = reactive,
+    input("video_in", "@tatolab/core/VideoFrame", format = "bgra8", description = "BGRA frames"),
+    output("video_out", "@tatolab/core/VideoFrame", format = "rgba8", description = "The same frames in RGBA8"),
+)]
+pub struct ColorspaceConvertProcessor {
+    scaler: Option<GpuScaler>,
+    output_ring: Option<OutputRing>,
+    frames_converted: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for ColorspaceConvertProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.scaler = Some(GpuScaler::new(ctx, "colorspace-convert")?.swapping_red_blue());
+        tracing::info!("[ColorspaceConvert] Setup (bgra8 -> rgba8)");
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.scaler = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[ColorspaceConvert] Teardown ({} frames converted)",
+            self.frames_converted
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let Some(scaler) = self.scaler.as_mut() else {
+            return Err(Error::Configuration(
+                "ColorspaceConvert: kernel not initialized".into(),
+            ));
+        };
+        let params = ScaleParams::copy(frame.width, frame.height);
+        let mapping = params.mapping(frame.width, frame.height)?;
+        let converted = scaler.scale(&frame, &params, &mapping, &mut self.output_ring)?;
+        self.frames_converted += 1;
+        self.outputs.write("video_out", &converted)
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/scale` — GPU resize. `Scale` resamples frames to a configured
+//! size with a bilinear, bicubic or Lanczos kernel and letterboxes, crops
+//! or stretches when the aspect ratio changes. `Simulcast` does the same
+//! for up to four renditions at once, in one downscale chain.
+//! `ColorspaceConvert` reuses the same kernel at 1:1 to turn BGRA frames
+//! into RGBA.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod params;
+
+// The shared resampling kernel in `scaler` records through `sdk::rhi`,
+// which is Linux-only, so every processor built on it is too.
+#[cfg(target_os = "linux")]
+pub mod colorspace_convert;
+#[cfg(target_os = "linux")]
+pub mod scale;
+#[cfg(target_os = "linux")]
+mod scaler;
+#[cfg(target_os = "linux")]
+pub mod simulcast;
+
+#[cfg(target_os = "linux")]
+pub use colorspace_convert::ColorspaceConvertProcessor;
+#[cfg(target_os = "linux")]
+pub use scale::ScaleProcessor;
+#[cfg(target_os = "linux")]
+pub use simulcast::SimulcastProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::ScaleProcessor::Processor,
+    crate::SimulcastProcessor::Processor,
+    crate::ColorspaceConvertProcessor::Processor,
+);
@@ -0,0 +1,494 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scale geometry — `ScaleConfig` resolved against an input size into the
+//! output size, where the picture lands in it, which part of the input it
+//! shows, and how wide the resampling kernel is — plus the order Simulcast
+//! resamples its renditions in.
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::ScaleConfig;
+use crate::_generated_::tatolab__scale::scale_config::{Aspect, Quality};
+
+/// Widest kernel footprint per axis, in input pixels on each side of the
+/// sample point. Reductions steeper than `MAX_RADIUS / support` (5x for
+/// Lanczos, 16x for bilinear) get a kernel narrower than ideal rather than
+/// an unbounded number of taps.
+pub const MAX_RADIUS: u32 = 16;
+
+/// Resampling filter. Values match `scale.comp`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ScaleFilter {
+    Bilinear = 0,
+    /// Catmull-Rom.
+    #[default]
+    Bicubic = 1,
+    Lanczos3 = 2,
+}
+
+impl ScaleFilter {
+    /// Kernel half-width, in input pixels at 1:1.
+    pub fn support(self) -> f32 {
+        match self {
+            Self::Bilinear => 1.0,
+            Self::Bicubic => 2.0,
+            Self::Lanczos3 => 3.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AspectPolicy {
+    /// Fit the whole picture; pad the rest with black.
+    #[default]
+    Letterbox,
+    /// Fill the output; trim the overflow evenly from both sides.
+    Crop,
+    /// Fill the output with the whole picture, distorting it.
+    Stretch,
+}
+
+/// An axis-aligned rectangle, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    fn sized(width: u32, height: u32) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: width as f32,
+            height: height as f32,
+        }
+    }
+}
+
+/// What `scale.comp` runs with for one input size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleMapping {
+    pub width: u32,
+    pub height: u32,
+    /// Where the picture lands in the output; black outside it.
+    pub content: Rect,
+    /// The part of the input shown in `content`.
+    pub source: Rect,
+    /// Per axis, how much the kernel is stretched over the input; above 1
+    /// when downscaling, so every input pixel contributes.
+    pub kernel_scale: [f32; 2],
+    /// Per axis, taps taken on each side of the sample point.
+    pub radius: [u32; 2],
+}
+
+impl ScaleMapping {
+    /// Output identical to the input: the frame can be forwarded as is.
+    pub fn is_identity(&self, input_width: u32, input_height: u32) -> bool {
+        let full = Rect::sized(input_width, input_height);
+        (self.width, self.height) == (input_width, input_height)
+            && self.content == full
+            && self.source == full
+    }
+
+    /// The whole input fills the whole output — no bars, nothing trimmed —
+    /// so the output can stand in for the input when scaling further down.
+    pub fn is_full_frame(&self, input_width: u32, input_height: u32) -> bool {
+        self.content == Rect::sized(self.width, self.height)
+            && self.source == Rect::sized(input_width, input_height)
+    }
+}
+
+/// One resample of a Simulcast frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CascadeStep {
+    /// Index of the rendition produced.
+    pub rendition: usize,
+    /// Rendition resampled from, or `None` for the input frame.
+    pub source: Option<usize>,
+    /// Mapping from the source's size.
+    pub mapping: ScaleMapping,
+}
+
+/// Order and sources for producing every rendition of an
+/// `input_width`x`input_height` frame, largest first. A rendition is
+/// resampled from the smallest already-produced full-frame rendition at
+/// least its size, so each step shrinks an already-shrunk picture instead
+/// of the full input. Letterboxed or cropped renditions are never used as
+/// sources, since their bars or trimming would carry into the next one.
+pub fn cascade(
+    renditions: &[ScaleParams],
+    input_width: u32,
+    input_height: u32,
+) -> Result<Vec<CascadeStep>> {
+    let mut sizes: Vec<(usize, (u32, u32))> = renditions
+        .iter()
+        .map(|params| params.output_size(input_width, input_height))
+        .enumerate()
+        .collect();
+    sizes.sort_by_key(|(_, (width, height))| {
+        std::cmp::Reverse(u64::from(*width) * u64::from(*height))
+    });
+
+    let mut steps: Vec<CascadeStep> = Vec::with_capacity(sizes.len());
+    // Produced renditions usable as sources: (index, width, height).
+    let mut sources: Vec<(usize, u32, u32)> = Vec::new();
+    for (rendition, (width, height)) in sizes {
+        let params = &renditions[rendition];
+        let source = sources
+            .iter()
+            .filter(|(_, source_width, source_height)| {
+                *source_width >= width && *source_height >= height
+            })
+            .min_by_key(|(_, source_width, source_height)| {
+                u64::from(*source_width) * u64::from(*source_height)
+            })
+            .copied();
+        // Sizes and full-frame-ness are judged against the input, so a
+        // cascaded rendition comes out exactly as if scaled from it.
+        let from_input = params.mapping(input_width, input_height)?;
+        let full_frame = from_input.is_full_frame(input_width, input_height);
+        let mapping = match source {
+            Some((_, source_width, source_height)) => {
+                // A full-frame source has the input's aspect ratio up to
+                // rounding; stretch over that rounding rather than letting
+                // it open a one-pixel bar.
+                let exact = params.with_size(Some(width), Some(height))?;
+                let exact = if full_frame {
+                    ScaleParams {
+                        aspect: AspectPolicy::Stretch,
+                        ..exact
+                    }
+                } else {
+                    exact
+                };
+                exact.mapping(source_width, source_height)?
+            }
+            None => from_input,
+        };
+        if full_frame {
+            sources.push((rendition, width, height));
+        }
+        steps.push(CascadeStep {
+            rendition,
+            source: source.map(|(index, _, _)| index),
+            mapping,
+        });
+    }
+    Ok(steps)
+}
+
+/// `ScaleConfig`, validated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleParams {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub filter: ScaleFilter,
+    pub aspect: AspectPolicy,
+}
+
+impl ScaleParams {
+    pub fn from_config(config: &ScaleConfig) -> Result<Self> {
+        let (width, height) = (config.width, config.height);
+        validate_size(width, height)?;
+        let filter = match config.quality {
+            Some(Quality::Fast) => ScaleFilter::Bilinear,
+            Some(Quality::Balanced) | None => ScaleFilter::Bicubic,
+            Some(Quality::High) => ScaleFilter::Lanczos3,
+        };
+        let aspect = match config.aspect {
+            Some(Aspect::Letterbox) | None => AspectPolicy::Letterbox,
+            Some(Aspect::Crop) => AspectPolicy::Crop,
+            Some(Aspect::Stretch) => AspectPolicy::Stretch,
+        };
+        Ok(Self {
+            width,
+            height,
+            filter,
+            aspect,
+        })
+    }
+
+    /// A 1:1 resample at `width`x`height`: bilinear taps at 1:1 land on
+    /// texel centers, so every pixel is copied exactly. For passes that only
+    /// rewrite the pixel format.
+    pub fn copy(width: u32, height: u32) -> Self {
+        Self {
+            width: Some(width),
+            height: Some(height),
+            filter: ScaleFilter::Bilinear,
+            aspect: AspectPolicy::Stretch,
+        }
+    }
+
+    /// These params at another output size — a `ScaleControl` override of
+    /// the configured one, validated the same way.
+    pub fn with_size(self, width: Option<u32>, height: Option<u32>) -> Result<Self> {
+        validate_size(width, height)?;
+        Ok(Self {
+            width,
+            height,
+            ..self
+        })
+    }
+
+    /// Output size for an input of `input_width`x`input_height`. A missing
+    /// dimension follows the input's aspect ratio, rounded to an even
+    /// count for the chroma-subsampled encoders downstream.
+    pub fn output_size(&self, input_width: u32, input_height: u32) -> (u32, u32) {
+        let derive = |other: u32, numerator: u32, denominator: u32| {
+            let exact = other as f64 * numerator as f64 / denominator as f64;
+            ((exact / 2.0).round() as u32 * 2).max(2)
+        };
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, derive(width, input_height, input_width)),
+            (None, Some(height)) => (derive(height, input_width, input_height), height),
+            // `from_config` rejects a config with neither.
+            (None, None) => (input_width, input_height),
+        }
+    }
+
+    pub fn mapping(&self, input_width: u32, input_height: u32) -> Result<ScaleMapping> {
+        if input_width == 0 || input_height == 0 {
+            return Err(Error::Runtime(format!(
+                "Scale: empty input frame ({}x{})",
+                input_width, input_height
+            )));
+        }
+        let (width, height) = self.output_size(input_width, input_height);
+        let input = Rect::sized(input_width, input_height);
+        let output = Rect::sized(width, height);
+        let fit_x = output.width / input.width;
+        let fit_y = output.height / input.height;
+
+        let (content, source) = match self.aspect {
+            AspectPolicy::Stretch => (output, input),
+            AspectPolicy::Letterbox => {
+                // Whole-pixel bars, so the picture's edge is not blended
+                // into black.
+                let scale = fit_x.min(fit_y);
+                let content_width = (input.width * scale).round().clamp(1.0, output.width);
+                let content_height = (input.height * scale).round().clamp(1.0, output.height);
+                let content = Rect {
+                    x: ((output.width - content_width) / 2.0).floor(),
+                    y: ((output.height - content_height) / 2.0).floor(),
+                    width: content_width,
+                    height: content_height,
+                };
+                (content, input)
+            }
+            AspectPolicy::Crop => {
+                let scale = fit_x.max(fit_y);
+                let source_width = output.width / scale;
+                let source_height = output.height / scale;
+                let source = Rect {
+                    x: (input.width - source_width) / 2.0,
+                    y: (input.height - source_height) / 2.0,
+                    width: source_width,
+                    height: source_height,
+                };
+                (output, source)
+            }
+        };
+
+        let support = self.filter.support();
+        let axis = |source_len: f32, content_len: f32| {
+            let scale = (source_len / content_len).clamp(1.0, MAX_RADIUS as f32 / support);
+            (scale, (support * scale).ceil() as u32)
+        };
+        let (scale_x, radius_x) = axis(source.width, content.width);
+        let (scale_y, radius_y) = axis(source.height, content.height);
+
+        Ok(ScaleMapping {
+            width,
+            height,
+            content,
+            source,
+            kernel_scale: [scale_x, scale_y],
+            radius: [radius_x, radius_y],
+        })
+    }
+}
+
+fn validate_size(width: Option<u32>, height: Option<u32>) -> Result<()> {
+    if width.is_none() && height.is_none() {
+        return Err(Error::Configuration(
+            "Scale: set width, height, or both".into(),
+        ));
+    }
+    if width == Some(0) || height == Some(0) {
+        return Err(Error::Configuration(format!(
+            "Scale: output size must be nonzero, got {:?}x{:?}",
+            width, height
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(width: Option<u32>, height: Option<u32>, aspect: Aspect) -> ScaleParams {
+        ScaleParams::from_config(&ScaleConfig {
+            width,
+            height,
+            aspect: Some(aspect),
+            ..ScaleConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn defaults_are_bicubic_letterbox_and_a_size_is_required() {
+        let defaults = params(Some(1920), Some(1080), Aspect::Letterbox);
+        assert_eq!(defaults.filter, ScaleFilter::Bicubic);
+
+        assert!(ScaleParams::from_config(&ScaleConfig::default()).is_err());
+        assert!(
+            ScaleParams::from_config(&ScaleConfig {
+                width: Some(0),
+                ..ScaleConfig::default()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn a_missing_dimension_follows_the_input_aspect() {
+        let by_height = params(None, Some(1080), Aspect::Letterbox);
+        assert_eq!(by_height.output_size(3840, 2160), (1920, 1080));
+        // 4:3 at 721 wide is 540.75 high — rounded to an even 540.
+        let by_width = params(Some(721), None, Aspect::Letterbox);
+        assert_eq!(by_width.output_size(640, 480), (721, 540));
+    }
+
+    #[test]
+    fn a_size_override_keeps_filter_and_aspect() {
+        let configured = ScaleParams {
+            filter: ScaleFilter::Lanczos3,
+            ..params(Some(1920), Some(1080), Aspect::Crop)
+        };
+        let stepped = configured.with_size(None, Some(540)).unwrap();
+        assert_eq!(stepped.filter, ScaleFilter::Lanczos3);
+        assert_eq!(stepped.aspect, AspectPolicy::Crop);
+        assert_eq!(stepped.output_size(1920, 1080), (960, 540));
+
+        assert!(configured.with_size(None, None).is_err());
+        assert!(configured.with_size(Some(0), Some(540)).is_err());
+    }
+
+    #[test]
+    fn cascade_shrinks_full_frame_renditions_in_turn() {
+        let ladder = [
+            params(None, Some(360), Aspect::Letterbox),
+            params(Some(1920), Some(1080), Aspect::Letterbox),
+            // 4:3 from a 16:9 input: letterboxed, so not a source.
+            params(Some(960), Some(720), Aspect::Letterbox),
+            params(None, Some(540), Aspect::Letterbox),
+        ];
+        let steps = cascade(&ladder, 3840, 2160).unwrap();
+        let order: Vec<(usize, Option<usize>)> = steps
+            .iter()
+            .map(|step| (step.rendition, step.source))
+            .collect();
+        assert_eq!(order, [(1, None), (2, Some(1)), (3, Some(1)), (0, Some(3))]);
+
+        let last = steps[3].mapping;
+        assert_eq!((last.width, last.height), (640, 360));
+        assert_eq!(last.source, Rect::sized(960, 540));
+
+        // An upscale rendition comes from the input.
+        let up = cascade(&[params(None, Some(2160), Aspect::Letterbox)], 1280, 720).unwrap();
+        assert_eq!(up[0].source, None);
+    }
+
+    #[test]
+    fn four_k_to_1080p_halves_with_a_doubled_kernel() {
+        let mapping = params(Some(1920), Some(1080), Aspect::Letterbox)
+            .mapping(3840, 2160)
+            .unwrap();
+
+        assert_eq!(mapping.content, Rect::sized(1920, 1080));
+        assert_eq!(mapping.source, Rect::sized(3840, 2160));
+        assert_eq!(mapping.kernel_scale, [2.0, 2.0]);
+        assert_eq!(mapping.radius, [4, 4]);
+        assert!(!mapping.is_identity(3840, 2160));
+    }
+
+    #[test]
+    fn letterbox_pads_and_crop_trims() {
+        // 4:3 into 16:9.
+        let letterbox = params(Some(1920), Some(1080), Aspect::Letterbox)
+            .mapping(640, 480)
+            .unwrap();
+        assert_eq!(
+            letterbox.content,
+            Rect {
+                x: 240.0,
+                y: 0.0,
+                width: 1440.0,
+                height: 1080.0,
+            }
+        );
+        assert_eq!(letterbox.source, Rect::sized(640, 480));
+        // Upscaling keeps the kernel at its 1:1 width.
+        assert_eq!(letterbox.kernel_scale, [1.0, 1.0]);
+        assert_eq!(letterbox.radius, [2, 2]);
+
+        let crop = params(Some(1920), Some(1080), Aspect::Crop)
+            .mapping(640, 480)
+            .unwrap();
+        assert_eq!(crop.content, Rect::sized(1920, 1080));
+        assert_eq!(
+            crop.source,
+            Rect {
+                x: 0.0,
+                y: 60.0,
+                width: 640.0,
+                height: 360.0,
+            }
+        );
+
+        let stretch = params(Some(1920), Some(1080), Aspect::Stretch)
+            .mapping(640, 480)
+            .unwrap();
+        assert_eq!(stretch.content, Rect::sized(1920, 1080));
+        assert_eq!(stretch.source, Rect::sized(640, 480));
+    }
+
+    #[test]
+    fn steep_reductions_cap_the_kernel_and_same_size_is_identity() {
+        let lanczos = ScaleParams {
+            filter: ScaleFilter::Lanczos3,
+            ..params(Some(384), Some(216), Aspect::Stretch)
+        };
+        let mapping = lanczos.mapping(3840, 2160).unwrap();
+        assert_eq!(mapping.radius, [MAX_RADIUS, MAX_RADIUS]);
+
+        for aspect in [Aspect::Letterbox, Aspect::Crop, Aspect::Stretch] {
+            let same = params(Some(1280), Some(720), aspect)
+                .mapping(1280, 720)
+                .unwrap();
+            assert!(same.is_identity(1280, 720), "{aspect:?}");
+        }
+        assert!(
+            params(Some(1280), Some(720), Aspect::Letterbox)
+                .mapping(0, 720)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn copy_params_sample_one_texel_per_pixel() {
+        let mapping = ScaleParams::copy(1920, 1080).mapping(1920, 1080).unwrap();
+        assert!(mapping.is_identity(1920, 1080));
+        assert_eq!(mapping.kernel_scale, [1.0, 1.0]);
+        assert_eq!(mapping.radius, [1, 1]);
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scale (Linux) — GPU resize with a configurable filter and aspect policy.
+//!
+//! One `scale.comp` dispatch per frame resamples the input into the next
+//! slot of an RGBA8 output ring sized to the configured output, reallocated
+//! when that size changes. Frames already at the output size are forwarded
+//! without a dispatch.
+//!
+//! A `ScaleControl` on the optional `control` input overrides the
+//! configured output size until a message with neither dimension clears
+//! it; the override outlives config updates.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::{ScaleControl, VideoFrame};
+use crate::params::ScaleParams;
+use crate::scaler::{GpuScaler, OutputRing};
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scale/Scale",
+    description = "Resizes frames on the GPU to a configured size with a bilinear, bicubic or Lanczos filter. A differing aspect ratio is letterboxed, cropped or stretched. Frames already at the output size pass through untouched. The output size can be changed at runtime through the control input.",
+    execution = reactive,
+    config = crate::_generated_::ScaleConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to resize"),
+    input("control", "@tatolab/scale/ScaleControl", optional = true, description = "Runtime output-size changes; a message with neither dimension restores the configured size"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Resized frames (RGBA8, the input's color description)"),
+)]
+pub struct ScaleProcessor {
+    scaler: Option<GpuScaler>,
+    params: Option<ScaleParams>,
+    /// Output size set over the `control` input, in place of the config's.
+    size_override: Option<(Option<u32>, Option<u32>)>,
+    output_ring: Option<OutputRing>,
+    frames_scaled: u64,
+    frames_forwarded: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for ScaleProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = self.resolve_params()?;
+        self.scaler = Some(GpuScaler::new(ctx, "scale")?);
+        self.params = Some(params);
+        tracing::info!("[Scale] Setup ({:?})", params);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.scaler = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[Scale] Teardown ({} frames scaled, {} forwarded)",
+            self.frames_scaled,
+            self.frames_forwarded
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = self.resolve_params()?;
+        self.params = Some(params);
+        tracing::info!("[Scale] Config updated ({:?})", params);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("control") {
+            let control: ScaleControl = self.inputs.read("control")?;
+            self.apply_control(&control);
+        }
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let Some(params) = self.params else {
+            return Err(Error::Configuration("Scale: not initialized".into()));
+        };
+        let mapping = params.mapping(frame.width, frame.height)?;
+        if mapping.is_identity(frame.width, frame.height) {
+            self.frames_forwarded += 1;
+            return self.outputs.write("video_out", &frame);
+        }
+        let Some(scaler) = self.scaler.as_mut() else {
+            return Err(Error::Configuration("Scale: kernel not initialized".into()));
+        };
+        let scaled = scaler.scale(&frame, &params, &mapping, &mut self.output_ring)?;
+        self.frames_scaled += 1;
+        self.outputs.write("video_out", &scaled)
+    }
+}
+
+impl ScaleProcessor::Processor {
+    /// The config's params, at the `control` override's size if one is set.
+    fn resolve_params(&self) -> Result<ScaleParams> {
+        let params = ScaleParams::from_config(&self.config)?;
+        match self.size_override {
+            Some((width, height)) => params.with_size(width, height),
+            None => Ok(params),
+        }
+    }
+
+    /// Take a `ScaleControl`. An invalid size is logged and ignored rather
+    /// than failing the frame path.
+    fn apply_control(&mut self, control: &ScaleControl) {
+        let previous = self.size_override;
+        self.size_override = match (control.width, control.height) {
+            (None, None) => None,
+            size => Some(size),
+        };
+        match self.resolve_params() {
+            Ok(params) => {
+                if self.params != Some(params) {
+                    tracing::info!("[Scale] Output size changed by control ({:?})", params);
+                }
+                self.params = Some(params);
+            }
+            Err(e) => {
+                tracing::warn!("[Scale] Ignoring control message: {}", e);
+                self.size_override = previous;
+            }
+        }
+    }
+}
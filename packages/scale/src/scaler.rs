@@ -0,0 +1,230 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The `scale.comp` dispatch shared by Scale and Simulcast: one compute
+//! kernel and command recorder, resampling into whichever output ring the
+//! caller owns.
+
+use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContextFullAccess};
+use streamlib_plugin_sdk::sdk::error::Result;
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel, VulkanLayout,
+    VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::params::{ScaleMapping, ScaleParams};
+
+const SCALE_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/scale.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+/// Matches `scale.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Push constants of `scale.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ScalePushConstants {
+    source_width: u32,
+    source_height: u32,
+    width: u32,
+    height: u32,
+    filter_kind: u32,
+    radius_x: u32,
+    radius_y: u32,
+    content_x: f32,
+    content_y: f32,
+    content_width: f32,
+    content_height: f32,
+    source_x: f32,
+    source_y: f32,
+    source_rect_width: f32,
+    source_rect_height: f32,
+    kernel_scale_x: f32,
+    kernel_scale_y: f32,
+    swap_red_blue: u32,
+}
+
+impl ScalePushConstants {
+    fn new(
+        source_width: u32,
+        source_height: u32,
+        params: &ScaleParams,
+        mapping: &ScaleMapping,
+        swap_red_blue: bool,
+    ) -> Self {
+        Self {
+            source_width,
+            source_height,
+            width: mapping.width,
+            height: mapping.height,
+            filter_kind: params.filter as u32,
+            radius_x: mapping.radius[0],
+            radius_y: mapping.radius[1],
+            content_x: mapping.content.x,
+            content_y: mapping.content.y,
+            content_width: mapping.content.width,
+            content_height: mapping.content.height,
+            source_x: mapping.source.x,
+            source_y: mapping.source.y,
+            source_rect_width: mapping.source.width,
+            source_rect_height: mapping.source.height,
+            kernel_scale_x: mapping.kernel_scale[0],
+            kernel_scale_y: mapping.kernel_scale[1],
+            swap_red_blue: swap_red_blue as u32,
+        }
+    }
+}
+
+/// An output ring and the size it was allocated at.
+pub(crate) type OutputRing = (TextureRing, u32, u32);
+
+pub(crate) struct GpuScaler {
+    gpu_context: GpuContextLimitedAccess,
+    kernel: VulkanComputeKernel,
+    recorder: RhiCommandRecorder,
+    swap_red_blue: bool,
+}
+
+impl GpuScaler {
+    pub(crate) fn new(ctx: &RuntimeContextFullAccess<'_>, label: &'static str) -> Result<Self> {
+        let full = ctx.gpu_full_access();
+        let kernel = full.create_compute_kernel(&ComputeKernelDescriptor {
+            label,
+            spv: SCALE_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<ScalePushConstants>() as u32,
+        })?;
+        let recorder = full.create_command_recorder(label)?;
+        Ok(Self {
+            gpu_context: ctx.gpu_limited_access().clone(),
+            kernel,
+            recorder,
+            swap_red_blue: false,
+        })
+    }
+
+    /// Also reorder each texel's channels from BGRA to RGBA, for frames
+    /// whose RGBA texture carries BGRA bytes.
+    pub(crate) fn swapping_red_blue(mut self) -> Self {
+        self.swap_red_blue = true;
+        self
+    }
+
+    /// Resample `frame` into the next slot of `output_ring`, (re)allocating
+    /// the ring when the output size changes.
+    pub(crate) fn scale(
+        &mut self,
+        frame: &VideoFrame,
+        params: &ScaleParams,
+        mapping: &ScaleMapping,
+        output_ring: &mut Option<OutputRing>,
+    ) -> Result<VideoFrame> {
+        let (gpu, kernel, recorder) = (&self.gpu_context, &self.kernel, &mut self.recorder);
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (mapping.width, mapping.height);
+
+        let ring = match output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_push_constants_value(&ScalePushConstants::new(
+            texture.width(),
+            texture.height(),
+            params,
+            mapping,
+            self.swap_red_blue,
+        ))?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            // Vertical resampling blends the two fields of an interlaced
+            // input into one picture.
+            field_order: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<ScalePushConstants>(), 72);
+        assert_eq!(std::mem::offset_of!(ScalePushConstants, content_x), 28);
+        assert_eq!(std::mem::offset_of!(ScalePushConstants, kernel_scale_y), 64);
+        assert_eq!(std::mem::offset_of!(ScalePushConstants, swap_red_blue), 68);
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Simulcast (Linux) — one input frame resampled into up to four
+//! renditions, one output port each, for feeding parallel encoders of an
+//! ABR ladder.
+//!
+//! The renditions share one `scale.comp` kernel and come out of a single
+//! downscale chain: each is resampled from the smallest full-frame
+//! rendition already produced that is at least its size (see
+//! [`cascade`]), so a 1080p/720p/360p ladder reads the camera frame once
+//! and each later step shrinks an already-shrunk picture. Every rendition
+//! owns its output ring. A rendition at the input size forwards the input
+//! frame without a dispatch.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__scale::scale_config;
+use crate::_generated_::tatolab__scale::simulcast_config::{Aspect, Quality};
+use crate::_generated_::{ScaleConfig, VideoFrame};
+use crate::params::{CascadeStep, ScaleParams, cascade};
+use crate::scaler::{GpuScaler, OutputRing};
+
+/// Output ports, in rendition order.
+const RENDITION_PORTS: [&str; 4] = ["rendition_0", "rendition_1", "rendition_2", "rendition_3"];
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scale/Simulcast",
+    description = "Resamples each frame into up to four renditions on the GPU in one downscale chain, each on its own output port, for encoding an ABR ladder in parallel. Each rendition is scaled from the nearest larger full-frame rendition rather than the source. Filter and aspect policy are shared and behave as for Scale.",
+    execution = reactive,
+    config = crate::_generated_::SimulcastConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to resample"),
+    output("rendition_0", "@tatolab/core/VideoFrame", description = "First configured rendition"),
+    output("rendition_1", "@tatolab/core/VideoFrame", description = "Second configured rendition"),
+    output("rendition_2", "@tatolab/core/VideoFrame", description = "Third configured rendition"),
+    output("rendition_3", "@tatolab/core/VideoFrame", description = "Fourth configured rendition"),
+)]
+pub struct SimulcastProcessor {
+    scaler: Option<GpuScaler>,
+    renditions: Vec<ScaleParams>,
+    /// Cascade for the last input size seen.
+    plan: Option<((u32, u32), Vec<CascadeStep>)>,
+    output_rings: Vec<Option<OutputRing>>,
+    frames_processed: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SimulcastProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.set_renditions()?;
+        self.scaler = Some(GpuScaler::new(ctx, "simulcast")?);
+        tracing::info!("[Simulcast] Setup ({:?})", self.renditions);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.scaler = None;
+        self.output_rings.clear();
+        tracing::info!("[Simulcast] Teardown ({} frames)", self.frames_processed);
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.set_renditions()?;
+        tracing::info!("[Simulcast] Config updated ({:?})", self.renditions);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let input_size = (frame.width, frame.height);
+        if self
+            .plan
+            .as_ref()
+            .is_none_or(|(size, _)| *size != input_size)
+        {
+            let steps = cascade(&self.renditions, frame.width, frame.height)?;
+            tracing::debug!("[Simulcast] Cascade for {:?}: {:?}", input_size, steps);
+            self.plan = Some((input_size, steps));
+        }
+        let (Some(scaler), Some((_, steps))) = (self.scaler.as_mut(), self.plan.as_ref()) else {
+            return Err(Error::Configuration("Simulcast: not initialized".into()));
+        };
+
+        let mut produced: Vec<Option<VideoFrame>> = vec![None; self.renditions.len()];
+        for step in steps {
+            let source = match step.source {
+                Some(index) => produced[index]
+                    .as_ref()
+                    .ok_or_else(|| Error::Runtime("Simulcast: cascade out of order".into()))?,
+                None => &frame,
+            };
+            let output = if step.mapping.is_identity(source.width, source.height) {
+                source.clone()
+            } else {
+                scaler.scale(
+                    source,
+                    &self.renditions[step.rendition],
+                    &step.mapping,
+                    &mut self.output_rings[step.rendition],
+                )?
+            };
+            produced[step.rendition] = Some(output);
+        }
+
+        for (port, output) in RENDITION_PORTS.iter().zip(&produced) {
+            if let Some(output) = output
+                && self.outputs.has_port(port)
+            {
+                self.outputs.write(port, output)?;
+            }
+        }
+        self.frames_processed += 1;
+        Ok(())
+    }
+}
+
+impl SimulcastProcessor::Processor {
+    /// Resolve the configured renditions, each validated as a Scale config
+    /// would be. Drops the cascade so the next frame re-plans.
+    fn set_renditions(&mut self) -> Result<()> {
+        let config = &self.config;
+        if config.renditions.is_empty() || config.renditions.len() > RENDITION_PORTS.len() {
+            return Err(Error::Configuration(format!(
+                "Simulcast: configure 1 to {} renditions, got {}",
+                RENDITION_PORTS.len(),
+                config.renditions.len()
+            )));
+        }
+        let quality = config.quality.as_ref().map(|quality| match quality {
+            Quality::Fast => scale_config::Quality::Fast,
+            Quality::Balanced => scale_config::Quality::Balanced,
+            Quality::High => scale_config::Quality::High,
+        });
+        let aspect = config.aspect.as_ref().map(|aspect| match aspect {
+            Aspect::Letterbox => scale_config::Aspect::Letterbox,
+            Aspect::Crop => scale_config::Aspect::Crop,
+            Aspect::Stretch => scale_config::Aspect::Stretch,
+        });
+        let renditions = config
+            .renditions
+            .iter()
+            .map(|rendition| {
+                ScaleParams::from_config(&ScaleConfig {
+                    width: rendition.width,
+                    height: rendition.height,
+                    quality: quality.clone(),
+                    aspect: aspect.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.output_rings.resize_with(renditions.len(), || None);
+        self.renditions = renditions;
+        self.plan = None;
+        Ok(())
+    }
+}
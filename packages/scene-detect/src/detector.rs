@@ -0,0 +1,162 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Cut decision from the per-frame GPU statistics.
+//!
+//! Each frame yields two differences against the previous one, both in
+//! `0.0..=1.0`: the total-variation distance between luma histograms
+//! (global tone changes) and the mean absolute difference of the luma
+//! thumbnails (structural changes). Their mean is the frame's score. A
+//! cut is reported when the score exceeds the recent baseline — an
+//! exponential moving average of non-cut scores — by `threshold`, so
+//! steady motion such as a pan raises the bar instead of firing on every
+//! frame. `min_scene_frames` suppresses bursts from flashes and fades.
+
+/// Luma histogram bins written by `scene_stats.comp`.
+pub const HISTOGRAM_BINS: usize = 64;
+/// Thumbnail the shader reduces each frame to.
+pub const GRID_WIDTH: u32 = 64;
+pub const GRID_HEIGHT: u32 = 36;
+pub const GRID_CELLS: usize = (GRID_WIDTH * GRID_HEIGHT) as usize;
+
+pub const DEFAULT_THRESHOLD: f32 = 0.3;
+pub const DEFAULT_MIN_SCENE_FRAMES: u32 = 12;
+
+/// Weight of the newest score in the baseline average.
+const BASELINE_ALPHA: f32 = 0.1;
+
+/// One frame's statistics as the shader leaves them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameStats {
+    pub histogram: [u32; HISTOGRAM_BINS],
+    /// Sum of per-cell absolute luma differences (0..=255 each) against
+    /// the previous frame; meaningless on the first frame.
+    pub sad: u32,
+}
+
+/// A detected cut.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneCut {
+    /// 0.5 at exactly `threshold` above baseline, 1.0 at twice that.
+    pub confidence: f32,
+    pub histogram_distance: f32,
+    pub pixel_difference: f32,
+}
+
+#[derive(Debug)]
+pub struct SceneDetector {
+    threshold: f32,
+    min_scene_frames: u32,
+    previous_histogram: Option<[u32; HISTOGRAM_BINS]>,
+    baseline: f32,
+    frames_since_cut: u32,
+}
+
+impl SceneDetector {
+    pub fn new(threshold: f32, min_scene_frames: u32) -> Self {
+        Self {
+            threshold: threshold.clamp(f32::EPSILON, 1.0),
+            min_scene_frames,
+            previous_histogram: None,
+            baseline: 0.0,
+            frames_since_cut: 0,
+        }
+    }
+
+    /// Feed the next frame; `Some` when it starts a new scene.
+    pub fn observe(&mut self, stats: &FrameStats) -> Option<SceneCut> {
+        // The first frame only primes the comparison.
+        let previous = self.previous_histogram.replace(stats.histogram)?;
+        self.frames_since_cut = self.frames_since_cut.saturating_add(1);
+
+        let histogram_distance = histogram_distance(&previous, &stats.histogram);
+        let pixel_difference = stats.sad as f32 / (GRID_CELLS as f32 * 255.0);
+        let score = (histogram_distance + pixel_difference) / 2.0;
+        let excess = score - self.baseline;
+
+        if excess >= self.threshold && self.frames_since_cut >= self.min_scene_frames {
+            self.frames_since_cut = 0;
+            return Some(SceneCut {
+                confidence: (excess / (2.0 * self.threshold)).min(1.0),
+                histogram_distance,
+                pixel_difference,
+            });
+        }
+        self.baseline += BASELINE_ALPHA * (score - self.baseline);
+        None
+    }
+}
+
+/// Total-variation distance between two histograms, `0.0..=1.0`.
+fn histogram_distance(a: &[u32; HISTOGRAM_BINS], b: &[u32; HISTOGRAM_BINS]) -> f32 {
+    let (total_a, total_b) = (a.iter().sum::<u32>(), b.iter().sum::<u32>());
+    if total_a == 0 || total_b == 0 {
+        return 0.0;
+    }
+    let difference: f32 = a
+        .iter()
+        .zip(b)
+        .map(|(&x, &y)| (x as f32 / total_a as f32 - y as f32 / total_b as f32).abs())
+        .sum();
+    difference / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A uniform frame at `luma` (0..=255), `sad` against the previous.
+    fn flat(luma: usize, sad: u32) -> FrameStats {
+        let mut histogram = [0; HISTOGRAM_BINS];
+        histogram[luma >> 2] = GRID_CELLS as u32;
+        FrameStats { histogram, sad }
+    }
+
+    #[test]
+    fn a_hard_cut_is_reported_once_with_full_confidence() {
+        let mut detector = SceneDetector::new(DEFAULT_THRESHOLD, 0);
+
+        assert_eq!(detector.observe(&flat(40, 0)), None);
+        assert_eq!(detector.observe(&flat(40, 0)), None);
+        let cut = detector
+            .observe(&flat(220, 180 * GRID_CELLS as u32))
+            .unwrap();
+        assert_eq!(detector.observe(&flat(220, 0)), None);
+
+        assert_eq!(cut.histogram_distance, 1.0);
+        assert!((cut.pixel_difference - 180.0 / 255.0).abs() < 1e-6);
+        assert_eq!(cut.confidence, 1.0);
+    }
+
+    #[test]
+    fn steady_motion_raises_the_baseline_instead_of_firing() {
+        let mut detector = SceneDetector::new(DEFAULT_THRESHOLD, 0);
+        detector.observe(&flat(100, 0));
+
+        // A fast pan: same tones, large structural difference every frame.
+        // The first frames may fire; once the baseline catches up, none do.
+        let pan = flat(100, 120 * GRID_CELLS as u32);
+        for _ in 0..30 {
+            detector.observe(&pan);
+        }
+        let fired = (0..30).filter(|_| detector.observe(&pan).is_some()).count();
+
+        assert_eq!(fired, 0);
+    }
+
+    #[test]
+    fn cuts_closer_than_min_scene_frames_are_suppressed() {
+        let mut detector = SceneDetector::new(DEFAULT_THRESHOLD, 5);
+        let strobe = |frame: usize| {
+            let luma = if frame.is_multiple_of(2) { 10 } else { 250 };
+            flat(luma, 240 * GRID_CELLS as u32)
+        };
+        detector.observe(&strobe(0));
+
+        let cuts: Vec<usize> = (1..12)
+            .filter(|&frame| detector.observe(&strobe(frame)).is_some())
+            .collect();
+
+        assert_eq!(cuts, [5, 10]);
+    }
+}
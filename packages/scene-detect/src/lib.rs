@@ -0,0 +1,34 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/scene-detect` — frame-accurate scene-change and motion events
+//! from video content. `SceneChange` compares consecutive frames on the
+//! GPU and emits a `SceneChange` data frame on the first frame of each new
+//! scene; `MotionDetector` reports per-zone differences and emits
+//! `MotionEvent`s when motion starts or ends in a zone or the scene cuts.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod detector;
+pub mod motion;
+
+// The per-frame histogram and zone-difference kernels need `sdk::rhi`, which
+// is Linux-only; the `detector` and `motion` decision logic stays portable.
+#[cfg(target_os = "linux")]
+pub mod motion_detector;
+#[cfg(target_os = "linux")]
+pub mod scene_change;
+
+#[cfg(target_os = "linux")]
+pub use motion_detector::MotionDetectorProcessor;
+#[cfg(target_os = "linux")]
+pub use scene_change::SceneChangeProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::SceneChangeProcessor::Processor,
+    crate::MotionDetectorProcessor::Processor
+);
@@ -0,0 +1,443 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Motion-in-zone and scene-cut events from per-frame luma thumbnails.
+//!
+//! Each frame arrives as the `GRID_WIDTH` × `GRID_HEIGHT` luma thumbnail
+//! written by `luma_grid.comp`. A cell has changed when its luma moved by
+//! more than `pixel_threshold` since the previous frame; a zone — a
+//! rectangle of the frame, owning the cells whose centres fall inside it
+//! — sees motion when at least `motion_threshold` of its cells changed.
+//! Motion starts after `min_motion_frames` such frames in a row and ends
+//! after `hold_frames` without, so a recording trigger neither fires on a
+//! single noisy frame nor drops out between steps. The same thumbnails
+//! feed a [`SceneDetector`]; a cut changes every zone at once, so cut
+//! frames leave the zones' state alone.
+
+use crate::detector::{
+    FrameStats, GRID_CELLS, GRID_HEIGHT, GRID_WIDTH, HISTOGRAM_BINS, SceneCut, SceneDetector,
+};
+
+pub const DEFAULT_PIXEL_THRESHOLD: f32 = 0.06;
+pub const DEFAULT_MOTION_THRESHOLD: f32 = 0.02;
+pub const DEFAULT_MIN_MOTION_FRAMES: u32 = 3;
+pub const DEFAULT_HOLD_FRAMES: u32 = 15;
+
+/// Name of the zone covering the whole frame when none are configured.
+pub const FRAME_ZONE: &str = "frame";
+
+/// A rectangle of the frame, normalized to 0..1 from the top-left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zone {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Zone {
+    pub fn whole_frame() -> Self {
+        Self {
+            name: FRAME_ZONE.to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+
+    /// Thumbnail cells whose centres fall inside; the cell under the
+    /// zone's centre when it is smaller than a cell.
+    fn cells(&self) -> Vec<usize> {
+        let centre = |cell: u32, cells: u32| (cell as f32 + 0.5) / cells as f32;
+        let inside = |value: f32, start: f32, size: f32| value >= start && value < start + size;
+        let cells: Vec<usize> = (0..GRID_CELLS)
+            .filter(|&cell| {
+                let (column, row) = (cell as u32 % GRID_WIDTH, cell as u32 / GRID_WIDTH);
+                inside(centre(column, GRID_WIDTH), self.x, self.width)
+                    && inside(centre(row, GRID_HEIGHT), self.y, self.height)
+            })
+            .collect();
+        if !cells.is_empty() {
+            return cells;
+        }
+        let index = |start: f32, size: f32, cells: u32| {
+            (((start + size / 2.0) * cells as f32) as u32).min(cells - 1)
+        };
+        let (column, row) = (
+            index(self.x, self.width, GRID_WIDTH),
+            index(self.y, self.height, GRID_HEIGHT),
+        );
+        vec![(row * GRID_WIDTH + column) as usize]
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        let fits = |start: f32, size: f32| {
+            start.is_finite() && size.is_finite() && start >= 0.0 && size > 0.0
+                // Allow for rounding in configs like x 0.7, width 0.3.
+                && start + size <= 1.0 + 1e-4
+        };
+        if self.name.is_empty() {
+            return Err("zone names must not be empty".into());
+        }
+        if !fits(self.x, self.width) || !fits(self.y, self.height) {
+            return Err(format!(
+                "zone '{}' must lie within the frame (0..1) with a positive size",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionSettings {
+    /// Luma change, 0..1, above which a cell counts as changed.
+    pub pixel_threshold: f32,
+    /// Share of a zone's cells that must change for it to see motion.
+    pub motion_threshold: f32,
+    pub min_motion_frames: u32,
+    pub hold_frames: u32,
+}
+
+impl Default for MotionSettings {
+    fn default() -> Self {
+        Self {
+            pixel_threshold: DEFAULT_PIXEL_THRESHOLD,
+            motion_threshold: DEFAULT_MOTION_THRESHOLD,
+            min_motion_frames: DEFAULT_MIN_MOTION_FRAMES,
+            hold_frames: DEFAULT_HOLD_FRAMES,
+        }
+    }
+}
+
+/// One zone's difference from the previous frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneMetrics {
+    pub name: String,
+    /// Mean absolute luma difference over the zone, 0..1.
+    pub mean_difference: f32,
+    /// Share of the zone's cells that changed, 0..1.
+    pub changed_fraction: f32,
+    /// Whether the zone is in a motion event.
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionChange {
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotionEvent {
+    pub zone: String,
+    pub change: MotionChange,
+    /// The zone's changed fraction on the frame that started or ended it.
+    pub changed_fraction: f32,
+}
+
+/// Everything one frame produced.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MotionFrame {
+    pub zones: Vec<ZoneMetrics>,
+    pub events: Vec<MotionEvent>,
+    pub cut: Option<SceneCut>,
+}
+
+#[derive(Debug)]
+struct ZoneState {
+    name: String,
+    cells: Vec<usize>,
+    active: bool,
+    /// Frames in a row with motion while inactive, or without while
+    /// active.
+    streak: u32,
+}
+
+#[derive(Debug)]
+pub struct MotionDetector {
+    settings: MotionSettings,
+    zones: Vec<ZoneState>,
+    scene: SceneDetector,
+    previous: Option<Vec<u32>>,
+}
+
+impl MotionDetector {
+    /// An empty `zones` watches the whole frame as one zone.
+    pub fn new(
+        settings: MotionSettings,
+        zones: &[Zone],
+        scene: SceneDetector,
+    ) -> std::result::Result<Self, String> {
+        let whole_frame = [Zone::whole_frame()];
+        let zones = if zones.is_empty() {
+            &whole_frame
+        } else {
+            zones
+        };
+        let mut states: Vec<ZoneState> = Vec::with_capacity(zones.len());
+        for zone in zones {
+            zone.validate()?;
+            if states.iter().any(|state| state.name == zone.name) {
+                return Err(format!("zone '{}' is defined twice", zone.name));
+            }
+            states.push(ZoneState {
+                name: zone.name.clone(),
+                cells: zone.cells(),
+                active: false,
+                streak: 0,
+            });
+        }
+        Ok(Self {
+            settings,
+            zones: states,
+            scene,
+            previous: None,
+        })
+    }
+
+    /// Feed the next frame's thumbnail, `GRID_CELLS` luma values 0..=255.
+    pub fn observe(&mut self, luma: &[u32]) -> MotionFrame {
+        let mut histogram = [0u32; HISTOGRAM_BINS];
+        for &value in luma {
+            histogram[(value.min(255) >> 2) as usize] += 1;
+        }
+        let Some(previous) = self.previous.replace(luma.to_vec()) else {
+            // The first frame only primes the comparison.
+            self.scene.observe(&FrameStats { histogram, sad: 0 });
+            return MotionFrame {
+                zones: self.metrics(|_| (0.0, 0.0)),
+                ..Default::default()
+            };
+        };
+        let difference: Vec<u32> = luma
+            .iter()
+            .zip(&previous)
+            .map(|(&a, &b)| a.abs_diff(b))
+            .collect();
+        let cut = self.scene.observe(&FrameStats {
+            histogram,
+            sad: difference.iter().sum(),
+        });
+
+        let pixel_threshold = (self.settings.pixel_threshold * 255.0) as u32;
+        let mut events = Vec::new();
+        let mut measured = Vec::with_capacity(self.zones.len());
+        for zone in &mut self.zones {
+            let cells = zone.cells.len() as f32;
+            let sum: u32 = zone.cells.iter().map(|&cell| difference[cell]).sum();
+            let changed = zone
+                .cells
+                .iter()
+                .filter(|&&cell| difference[cell] > pixel_threshold)
+                .count();
+            let mean_difference = sum as f32 / (cells * 255.0);
+            let changed_fraction = changed as f32 / cells;
+            measured.push((mean_difference, changed_fraction));
+            if cut.is_some() {
+                continue;
+            }
+            let moving = changed > 0 && changed_fraction >= self.settings.motion_threshold;
+            let change = if moving == zone.active {
+                zone.streak = 0;
+                None
+            } else {
+                zone.streak += 1;
+                let needed = if zone.active {
+                    self.settings.hold_frames
+                } else {
+                    self.settings.min_motion_frames
+                };
+                (zone.streak >= needed.max(1)).then(|| {
+                    zone.active = !zone.active;
+                    zone.streak = 0;
+                    if zone.active {
+                        MotionChange::Start
+                    } else {
+                        MotionChange::End
+                    }
+                })
+            };
+            if let Some(change) = change {
+                events.push(MotionEvent {
+                    zone: zone.name.clone(),
+                    change,
+                    changed_fraction,
+                });
+            }
+        }
+        MotionFrame {
+            zones: self.metrics(|index| measured[index]),
+            events,
+            cut,
+        }
+    }
+
+    /// End events for the zones still in motion, e.g. before the
+    /// detector is replaced.
+    pub fn finish(&mut self) -> Vec<MotionEvent> {
+        self.zones
+            .iter_mut()
+            .filter(|zone| zone.active)
+            .map(|zone| {
+                zone.active = false;
+                zone.streak = 0;
+                MotionEvent {
+                    zone: zone.name.clone(),
+                    change: MotionChange::End,
+                    changed_fraction: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    fn metrics(&self, measured: impl Fn(usize) -> (f32, f32)) -> Vec<ZoneMetrics> {
+        self.zones
+            .iter()
+            .enumerate()
+            .map(|(index, zone)| {
+                let (mean_difference, changed_fraction) = measured(index);
+                ZoneMetrics {
+                    name: zone.name.clone(),
+                    mean_difference,
+                    changed_fraction,
+                    active: zone.active,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::DEFAULT_THRESHOLD;
+
+    fn detector(zones: &[Zone]) -> MotionDetector {
+        MotionDetector::new(
+            MotionSettings::default(),
+            zones,
+            SceneDetector::new(DEFAULT_THRESHOLD, 0),
+        )
+        .unwrap()
+    }
+
+    fn zone(name: &str, x: f32, y: f32, width: f32, height: f32) -> Zone {
+        Zone {
+            name: name.into(),
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// A mid-grey frame with a bright 4×4-cell square at `column`, row 2.
+    fn square_at(column: u32) -> Vec<u32> {
+        let mut luma = vec![100; GRID_CELLS];
+        for row in 2..6 {
+            for offset in 0..4 {
+                luma[(row * GRID_WIDTH + column + offset) as usize] = 200;
+            }
+        }
+        luma
+    }
+
+    #[test]
+    fn motion_in_a_zone_starts_after_min_frames_and_ends_after_the_hold() {
+        // Left and right halves; the square moves about the left half.
+        let mut detector = detector(&[
+            zone("left", 0.0, 0.0, 0.5, 1.0),
+            zone("right", 0.5, 0.0, 0.5, 1.0),
+        ]);
+        detector.observe(&square_at(0));
+
+        let mut events = Vec::new();
+        for frame in 1..=4 {
+            let observed = detector.observe(&square_at(frame * 4));
+            events.extend(
+                observed
+                    .events
+                    .into_iter()
+                    .map(|e| (frame, e.zone, e.change)),
+            );
+        }
+        for frame in 5..=5 + DEFAULT_HOLD_FRAMES {
+            let observed = detector.observe(&square_at(16));
+            events.extend(
+                observed
+                    .events
+                    .into_iter()
+                    .map(|e| (frame, e.zone, e.change)),
+            );
+        }
+
+        assert_eq!(
+            events,
+            [
+                (3, "left".to_string(), MotionChange::Start),
+                (
+                    4 + DEFAULT_HOLD_FRAMES,
+                    "left".to_string(),
+                    MotionChange::End
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn zones_report_their_own_difference_and_a_cut_leaves_them_alone() {
+        let mut detector = detector(&[]);
+        detector.observe(&vec![100; GRID_CELLS]);
+
+        let moved = detector.observe(&square_at(0));
+        assert_eq!(moved.zones.len(), 1);
+        assert_eq!(moved.zones[0].name, FRAME_ZONE);
+        assert!((moved.zones[0].changed_fraction - 16.0 / GRID_CELLS as f32).abs() < 1e-6);
+        assert!(
+            (moved.zones[0].mean_difference - 16.0 * 100.0 / (GRID_CELLS as f32 * 255.0)).abs()
+                < 1e-6
+        );
+        assert_eq!(moved.cut, None);
+
+        // Hard cut to white: reported, but it is not motion.
+        let cut = detector.observe(&vec![250; GRID_CELLS]);
+        assert!(cut.cut.is_some());
+        assert!(cut.events.is_empty() && !cut.zones[0].active);
+    }
+
+    #[test]
+    fn zones_are_validated_and_small_ones_still_get_a_cell() {
+        let scene = || SceneDetector::new(DEFAULT_THRESHOLD, 0);
+        for zones in [
+            vec![zone("", 0.0, 0.0, 1.0, 1.0)],
+            vec![zone("a", 0.8, 0.0, 0.3, 1.0)],
+            vec![zone("a", 0.0, 0.0, 0.0, 1.0)],
+            vec![zone("a", 0.0, 0.0, 0.5, 0.5), zone("a", 0.5, 0.5, 0.5, 0.5)],
+        ] {
+            assert!(MotionDetector::new(MotionSettings::default(), &zones, scene()).is_err());
+        }
+
+        assert_eq!(zone("tiny", 0.5, 0.5, 0.001, 0.001).cells().len(), 1);
+        assert_eq!(
+            zone("half", 0.0, 0.0, 0.5, 1.0).cells().len(),
+            GRID_CELLS / 2
+        );
+    }
+
+    #[test]
+    fn finish_ends_active_zones() {
+        let mut detector = detector(&[zone("left", 0.0, 0.0, 0.5, 1.0)]);
+        detector.observe(&square_at(0));
+        for frame in 1..=DEFAULT_MIN_MOTION_FRAMES {
+            detector.observe(&square_at(frame * 4));
+        }
+
+        let ended = detector.finish();
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].change, MotionChange::End);
+        assert!(detector.finish().is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Motion detector (Linux) — forwards every frame, reports per-zone
+//! difference metrics for each one and emits `MotionEvent` data frames
+//! when motion starts or ends in a zone or the scene cuts.
+//!
+//! A compute kernel reduces each frame to the same coarse luma thumbnail
+//! the scene-change kernel uses, written straight into a ~9 KiB
+//! host-visible buffer; the frame itself never leaves the GPU. Zones,
+//! thresholds and the cut decision run on the CPU ([`crate::motion`]).
+//! Events carry the frame's `timestamp_ns`, so a recorder or switcher
+//! acts on the exact frame.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder, StorageBuffer, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::tatolab__scene_detect::motion_event::Kind;
+use crate::_generated_::tatolab__scene_detect::motion_metrics::MotionMetricsZone;
+use crate::_generated_::{MotionDetectorConfig, MotionEvent, MotionMetrics, VideoFrame};
+use crate::detector::{
+    DEFAULT_MIN_SCENE_FRAMES, DEFAULT_THRESHOLD, GRID_CELLS, GRID_HEIGHT, GRID_WIDTH, SceneDetector,
+};
+use crate::motion::{
+    self, MotionChange, MotionDetector, MotionEvent as ZoneEvent, MotionFrame, MotionSettings, Zone,
+};
+
+const LUMA_GRID_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/luma_grid.spv"));
+
+const LUMA_GRID_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_buffer(1),
+];
+
+/// Matches the shader's `local_size_x`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Push constants of `luma_grid.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct GridPushConstants {
+    grid_width: u32,
+    grid_height: u32,
+}
+
+/// Builds the detector the config describes.
+fn new_detector(config: &MotionDetectorConfig) -> Result<MotionDetector> {
+    let fraction = |value: Option<f32>, default: f32, name: &str| {
+        let value = value.unwrap_or(default);
+        if (0.0..=1.0).contains(&value) {
+            Ok(value)
+        } else {
+            Err(Error::Configuration(format!(
+                "MotionDetector: {name} must be 0 … 1, got {value}"
+            )))
+        }
+    };
+    let settings = MotionSettings {
+        pixel_threshold: fraction(
+            config.pixel_threshold,
+            motion::DEFAULT_PIXEL_THRESHOLD,
+            "pixel_threshold",
+        )?,
+        motion_threshold: fraction(
+            config.motion_threshold,
+            motion::DEFAULT_MOTION_THRESHOLD,
+            "motion_threshold",
+        )?,
+        min_motion_frames: config
+            .min_motion_frames
+            .unwrap_or(motion::DEFAULT_MIN_MOTION_FRAMES),
+        hold_frames: config.hold_frames.unwrap_or(motion::DEFAULT_HOLD_FRAMES),
+    };
+    let zones: Vec<Zone> = config
+        .zones
+        .iter()
+        .flatten()
+        .map(|zone| Zone {
+            name: zone.name.clone(),
+            x: zone.x,
+            y: zone.y,
+            width: zone.width,
+            height: zone.height,
+        })
+        .collect();
+    let scene = SceneDetector::new(
+        config.scene_threshold.unwrap_or(DEFAULT_THRESHOLD),
+        config.min_scene_frames.unwrap_or(DEFAULT_MIN_SCENE_FRAMES),
+    );
+    MotionDetector::new(settings, &zones, scene)
+        .map_err(|e| Error::Configuration(format!("MotionDetector: {e}")))
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scene-detect/MotionDetector",
+    description = "Measures per-zone luma differences between frames on the GPU and emits MotionEvent data frames when motion starts or ends in a configured zone, or the scene cuts — to trigger recording or switcher actions. Forwards every frame and reports each frame's zone metrics.",
+    execution = reactive,
+    config = crate::_generated_::MotionDetectorConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to analyze"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames, unmodified"),
+    output("events", "@tatolab/scene-detect/MotionEvent", description = "Motion start/end per zone and scene cuts"),
+    output("metrics", "@tatolab/scene-detect/MotionMetrics", description = "Every analyzed frame's per-zone difference metrics"),
+)]
+pub struct MotionDetectorProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-visible luma thumbnail, overwritten by every dispatch.
+    grid: Option<StorageBuffer>,
+    detector: Option<MotionDetector>,
+    /// End events for zones a config update removed mid-motion, sent with
+    /// the next frame.
+    carried_events: Vec<ZoneEvent>,
+    frames_seen: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor
+    for MotionDetectorProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.detector = Some(new_detector(&self.config)?);
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "luma_grid",
+            spv: LUMA_GRID_SPV,
+            bindings: LUMA_GRID_BINDINGS,
+            push_constant_size: std::mem::size_of::<GridPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("luma_grid")?);
+        let grid = full.acquire_storage_buffer(std::mem::size_of::<[u32; GRID_CELLS]>() as u64)?;
+        if grid.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "MotionDetector: grid buffer is not host-mapped".into(),
+            ));
+        }
+        self.grid = Some(grid);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.grid = None;
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        // New zones and thresholds start fresh; zones still in motion get
+        // their end event with the next frame.
+        let detector = new_detector(&self.config)?;
+        if let Some(mut previous) = self.detector.replace(detector) {
+            self.carried_events.extend(previous.finish());
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let frame_index = self.frames_seen;
+
+        match self.measure(&frame) {
+            Ok(luma) => {
+                self.frames_seen += 1;
+                let observed = self
+                    .detector
+                    .as_mut()
+                    .map(|detector| detector.observe(&luma))
+                    .unwrap_or_default();
+                self.report(&frame, frame_index, observed)?;
+            }
+            Err(e) => tracing::warn!("MotionDetector: frame not analyzed: {}", e),
+        }
+        self.outputs.write("video_out", &frame)
+    }
+}
+
+impl MotionDetectorProcessor::Processor {
+    fn report(
+        &mut self,
+        frame: &VideoFrame,
+        frame_index: u64,
+        observed: MotionFrame,
+    ) -> Result<()> {
+        let MotionFrame { zones, events, cut } = observed;
+        let event = |kind: Kind, zone: Option<String>, score: f32| MotionEvent {
+            timestamp_ns: frame.timestamp_ns.clone(),
+            frame_index: frame_index.to_string(),
+            kind,
+            zone,
+            score,
+        };
+        if let Some(cut) = cut {
+            tracing::debug!(
+                "MotionDetector: cut at frame {} (confidence {:.2})",
+                frame_index,
+                cut.confidence
+            );
+            self.outputs
+                .write("events", &event(Kind::SceneCut, None, cut.confidence))?;
+        }
+        for zone_event in std::mem::take(&mut self.carried_events)
+            .into_iter()
+            .chain(events)
+        {
+            let kind = match zone_event.change {
+                MotionChange::Start => Kind::MotionStart,
+                MotionChange::End => Kind::MotionEnd,
+            };
+            tracing::debug!(
+                "MotionDetector: {:?} in zone '{}' at frame {}",
+                kind,
+                zone_event.zone,
+                frame_index
+            );
+            self.outputs.write(
+                "events",
+                &event(kind, Some(zone_event.zone), zone_event.changed_fraction),
+            )?;
+        }
+        self.outputs.write(
+            "metrics",
+            &MotionMetrics {
+                timestamp_ns: frame.timestamp_ns.clone(),
+                frame_index: frame_index.to_string(),
+                zones: zones
+                    .into_iter()
+                    .map(|zone| MotionMetricsZone {
+                        name: zone.name,
+                        mean_difference: zone.mean_difference,
+                        changed_fraction: zone.changed_fraction,
+                        active: zone.active,
+                    })
+                    .collect(),
+            },
+        )
+    }
+
+    /// Run the luma grid kernel over `frame` and copy the thumbnail out.
+    fn measure(&mut self, frame: &VideoFrame) -> Result<Vec<u32>> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("MotionDetector: GPU context not initialized".into())
+        })?;
+        let (Some(kernel), Some(recorder), Some(grid)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.grid.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "MotionDetector: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_buffer_storage(1, grid)?;
+        kernel.set_push_constants_value(&GridPushConstants {
+            grid_width: GRID_WIDTH,
+            grid_height: GRID_HEIGHT,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_dispatch(kernel, (GRID_CELLS as u32).div_ceil(WORKGROUP_SIZE), 1, 1)?;
+        recorder.record_buffer_barrier(
+            grid,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::HOST,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::HOST_READ,
+        )?;
+        recorder.submit_and_wait()?;
+        registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let mut luma = vec![0u32; GRID_CELLS];
+        // SAFETY: `grid` is a persistently-mapped host-visible allocation
+        // of `GRID_CELLS` u32s (checked non-null in setup); the submit has
+        // completed and the barrier made the kernel's writes host-visible.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                grid.mapped_ptr() as *const u32,
+                luma.as_mut_ptr(),
+                GRID_CELLS,
+            );
+        }
+        Ok(luma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_generated_::tatolab__scene_detect::motion_detector_config::MotionDetectorConfigZone;
+
+    fn zone(name: &str, x: f32, width: f32) -> MotionDetectorConfigZone {
+        MotionDetectorConfigZone {
+            name: name.into(),
+            x,
+            y: 0.0,
+            width,
+            height: 1.0,
+        }
+    }
+
+    #[test]
+    fn config_is_validated_into_a_detector() {
+        assert!(new_detector(&MotionDetectorConfig::default()).is_ok());
+        assert!(
+            new_detector(&MotionDetectorConfig {
+                zones: Some(vec![zone("door", 0.0, 0.3), zone("desk", 0.5, 0.5)]),
+                ..Default::default()
+            })
+            .is_ok()
+        );
+
+        for config in [
+            MotionDetectorConfig {
+                pixel_threshold: Some(1.5),
+                ..Default::default()
+            },
+            MotionDetectorConfig {
+                motion_threshold: Some(-0.1),
+                ..Default::default()
+            },
+            MotionDetectorConfig {
+                zones: Some(vec![zone("door", 0.8, 0.5)]),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                new_detector(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+    }
+}
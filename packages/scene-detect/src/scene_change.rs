@@ -0,0 +1,241 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scene change (Linux) — forwards every frame and emits a `SceneChange`
+//! data frame on the first frame of each new scene.
+//!
+//! A compute kernel reduces each frame to a 64-bin luma histogram and a
+//! coarse luma thumbnail, accumulating the thumbnail's SAD against the
+//! previous frame on the GPU; only ~9 KiB of statistics live in
+//! host-visible memory and the frame itself never leaves the GPU. The
+//! cut decision ([`crate::detector`]) runs on the CPU. The data frame
+//! carries the frame's `timestamp_ns`, so consumers — an encoder forcing
+//! a keyframe, a switcher, a dataset sampler — act on the exact frame.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder, StorageBuffer, VulkanAccess,
+    VulkanComputeKernel, VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::{SceneChange, VideoFrame};
+use crate::detector::{
+    DEFAULT_MIN_SCENE_FRAMES, DEFAULT_THRESHOLD, FrameStats, GRID_CELLS, GRID_HEIGHT, GRID_WIDTH,
+    HISTOGRAM_BINS, SceneDetector,
+};
+
+const SCENE_STATS_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/scene_stats.spv"));
+
+const SCENE_STATS_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_buffer(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+/// Matches the shader's `local_size_x`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// `Stats` block of `scene_stats.comp`: histogram bins, then the SAD.
+const STATS_WORDS: usize = HISTOGRAM_BINS + 1;
+
+/// Push constants of `scene_stats.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StatsPushConstants {
+    grid_width: u32,
+    grid_height: u32,
+    has_previous: u32,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scene-detect/SceneChange",
+    description = "Detects scene changes from GPU luma-histogram and thumbnail-SAD differences between frames. Forwards every frame and emits a SceneChange data frame (with confidence) on the first frame of each new scene — use it to force encoder keyframes, drive switcher actions, or sample datasets.",
+    execution = reactive,
+    config = crate::_generated_::SceneChangeConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to analyze"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The same frames, unmodified"),
+    output("scene_change", "@tatolab/scene-detect/SceneChange", description = "One data frame per detected cut"),
+)]
+pub struct SceneChangeProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-visible histogram + SAD, zeroed before every dispatch.
+    stats: Option<StorageBuffer>,
+    /// Previous frame's thumbnail, updated in place by the kernel.
+    thumbnail: Option<StorageBuffer>,
+    detector: Option<SceneDetector>,
+    frames_seen: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SceneChangeProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "scene_stats",
+            spv: SCENE_STATS_SPV,
+            bindings: SCENE_STATS_BINDINGS,
+            push_constant_size: std::mem::size_of::<StatsPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("scene_stats")?);
+        let stats = full.acquire_storage_buffer(std::mem::size_of::<[u32; STATS_WORDS]>() as u64)?;
+        if stats.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "SceneChange: stats buffer is not host-mapped".into(),
+            ));
+        }
+        self.stats = Some(stats);
+        self.thumbnail =
+            Some(full.acquire_storage_buffer(std::mem::size_of::<[u32; GRID_CELLS]>() as u64)?);
+        self.detector = Some(self.new_detector());
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.stats = None;
+        self.thumbnail = None;
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        // New thresholds start a fresh baseline; the next frame re-primes
+        // the detector.
+        self.detector = Some(self.new_detector());
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let frame_index = self.frames_seen;
+
+        match self.measure(&frame) {
+            Ok(stats) => {
+                self.frames_seen += 1;
+                let cut = self
+                    .detector
+                    .as_mut()
+                    .and_then(|detector| detector.observe(&stats));
+                if let Some(cut) = cut {
+                    tracing::debug!(
+                        "SceneChange: cut at frame {} (confidence {:.2})",
+                        frame_index,
+                        cut.confidence
+                    );
+                    self.outputs.write(
+                        "scene_change",
+                        &SceneChange {
+                            timestamp_ns: frame.timestamp_ns.clone(),
+                            frame_index: frame_index.to_string(),
+                            confidence: cut.confidence,
+                            histogram_distance: cut.histogram_distance,
+                            pixel_difference: cut.pixel_difference,
+                        },
+                    )?;
+                }
+            }
+            Err(e) => tracing::warn!("SceneChange: frame not analyzed: {}", e),
+        }
+        self.outputs.write("video_out", &frame)
+    }
+}
+
+impl SceneChangeProcessor::Processor {
+    fn new_detector(&self) -> SceneDetector {
+        SceneDetector::new(
+            self.config.threshold.unwrap_or(DEFAULT_THRESHOLD),
+            self.config
+                .min_scene_frames
+                .unwrap_or(DEFAULT_MIN_SCENE_FRAMES),
+        )
+    }
+
+    /// Run the statistics kernel over `frame` and copy the result out.
+    fn measure(&mut self, frame: &VideoFrame) -> Result<FrameStats> {
+        let gpu = self.gpu_context.as_ref().ok_or_else(|| {
+            Error::Configuration("SceneChange: GPU context not initialized".into())
+        })?;
+        let (Some(kernel), Some(recorder), Some(stats), Some(thumbnail)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.stats.as_ref(),
+            self.thumbnail.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "SceneChange: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+
+        // SAFETY: `stats` is a persistently-mapped host-visible allocation
+        // of `STATS_WORDS` u32s (checked non-null in setup), and the
+        // previous submit has completed, so the GPU is not using it. Host
+        // writes before the submit are visible to the kernel.
+        unsafe {
+            std::ptr::write_bytes(stats.mapped_ptr() as *mut u32, 0, STATS_WORDS);
+        }
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_buffer_storage(1, stats)?;
+        kernel.set_storage_buffer_storage(2, thumbnail)?;
+        kernel.set_push_constants_value(&StatsPushConstants {
+            grid_width: GRID_WIDTH,
+            grid_height: GRID_HEIGHT,
+            has_previous: u32::from(self.frames_seen > 0),
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_dispatch(kernel, (GRID_CELLS as u32).div_ceil(WORKGROUP_SIZE), 1, 1)?;
+        recorder.record_buffer_barrier(
+            stats,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::HOST,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::HOST_READ,
+        )?;
+        recorder.submit_and_wait()?;
+        registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let mut words = [0u32; STATS_WORDS];
+        // SAFETY: as above; the submit has completed and the barrier made
+        // the kernel's writes host-visible.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                stats.mapped_ptr() as *const u32,
+                words.as_mut_ptr(),
+                STATS_WORDS,
+            );
+        }
+        let mut histogram = [0u32; HISTOGRAM_BINS];
+        histogram.copy_from_slice(&words[..HISTOGRAM_BINS]);
+        Ok(FrameStats {
+            histogram,
+            sad: words[HISTOGRAM_BINS],
+        })
+    }
+}
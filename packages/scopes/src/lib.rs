@@ -0,0 +1,25 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/scopes` — video scopes for colorists. `Scopes` measures a
+//! luma waveform, RGB parade and vectorscope of its input on the GPU,
+//! renders them into an output frame, and publishes the raw counts as
+//! `ScopeData` for UIs.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod scope_layout;
+
+// Waveform, parade and vectorscope accumulation run on `sdk::rhi`, which
+// is Linux-only; `scope_layout` stays portable.
+#[cfg(target_os = "linux")]
+pub mod scopes;
+
+#[cfg(target_os = "linux")]
+pub use scopes::ScopesProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::ScopesProcessor::Processor);
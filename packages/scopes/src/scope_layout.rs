@@ -0,0 +1,110 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scope geometry shared by the shaders and the CPU side — bin counts,
+//! the accumulation buffer's section layout, panel arrangement, and the
+//! sampling/brightness scaling derived from the input size. The constants
+//! are mirrored in `scope_accumulate.comp` and `scope_render.comp`.
+
+/// Horizontal bins of the waveform and of each parade channel.
+pub const COLUMNS: u32 = 256;
+/// Vertical (level) bins of the waveform and of each parade channel.
+pub const LEVELS: u32 = 128;
+/// Width and height of the vectorscope grid.
+pub const VECTORSCOPE_SIZE: u32 = 128;
+
+/// u32 counters in the waveform, and in each parade channel.
+pub const WAVEFORM_WORDS: usize = (COLUMNS * LEVELS) as usize;
+/// The parade's R, G and B sections follow the waveform.
+pub const PARADE_OFFSET: usize = WAVEFORM_WORDS;
+pub const PARADE_WORDS: usize = 3 * WAVEFORM_WORDS;
+/// The vectorscope follows the parade.
+pub const VECTORSCOPE_OFFSET: usize = PARADE_OFFSET + PARADE_WORDS;
+pub const VECTORSCOPE_WORDS: usize = (VECTORSCOPE_SIZE * VECTORSCOPE_SIZE) as usize;
+/// Size of the accumulation buffer in u32 words.
+pub const TOTAL_WORDS: usize = VECTORSCOPE_OFFSET + VECTORSCOPE_WORDS;
+
+/// Upper bound on pixels measured per frame (about one 1080p frame);
+/// larger frames are measured on a regular sub-grid.
+pub const MAX_SAMPLES: u64 = 1 << 21;
+
+pub const DEFAULT_PANEL_SIZE: u32 = 512;
+pub const DEFAULT_DATA_INTERVAL: u32 = 15;
+
+/// One scope panel; the discriminant is the shader's panel code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Panel {
+    Waveform = 0,
+    Parade = 1,
+    Vectorscope = 2,
+}
+
+pub const ALL_PANELS: &[Panel] = &[Panel::Waveform, Panel::Parade, Panel::Vectorscope];
+
+/// Output frame size for `panels` drawn side by side.
+pub fn output_size(panels: &[Panel], panel_size: u32) -> (u32, u32) {
+    (panels.len() as u32 * panel_size, panel_size)
+}
+
+/// Pixel stride, on both axes, that keeps a `width` x `height` frame
+/// within [`MAX_SAMPLES`].
+pub fn sample_stride(width: u32, height: u32) -> u32 {
+    let pixels = u64::from(width) * u64::from(height);
+    let mut stride = 1u64;
+    while pixels.div_ceil(stride * stride) > MAX_SAMPLES {
+        stride += 1;
+    }
+    stride as u32
+}
+
+/// Pixels measured at `stride`.
+pub fn sample_count(width: u32, height: u32, stride: u32) -> u32 {
+    width.div_ceil(stride) * height.div_ceil(stride)
+}
+
+/// Trace brightness gains `(waveform, vectorscope)` for `samples`
+/// measured pixels. Brightness is `log2(1 + count) * gain`, so a bin
+/// reaches full brightness at `full_scale` counts: an eighth of a
+/// waveform column, or 1/256th of the frame in one vectorscope cell.
+pub fn trace_gains(samples: u32) -> (f32, f32) {
+    let gain = |full_scale: f32| 1.0 / (1.0 + full_scale).log2().max(1.0);
+    let samples = samples as f32;
+    (gain(samples / COLUMNS as f32 / 8.0), gain(samples / 256.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_tile_the_buffer() {
+        assert_eq!(PARADE_OFFSET, WAVEFORM_WORDS);
+        assert_eq!(VECTORSCOPE_OFFSET, 4 * WAVEFORM_WORDS);
+        assert_eq!(TOTAL_WORDS, 4 * 256 * 128 + 128 * 128);
+        assert_eq!(output_size(ALL_PANELS, 512), (1536, 512));
+        assert_eq!(output_size(&[Panel::Vectorscope], 300), (300, 300));
+    }
+
+    #[test]
+    fn large_frames_are_subsampled_within_budget() {
+        assert_eq!(sample_stride(1920, 1080), 1);
+        assert_eq!(sample_stride(3840, 2160), 2);
+        for (width, height) in [(1, 1), (1920, 1080), (3840, 2160), (7680, 4320), (8191, 3)] {
+            let stride = sample_stride(width, height);
+            assert!(u64::from(sample_count(width, height, stride)) <= MAX_SAMPLES);
+        }
+        assert_eq!(sample_count(3840, 2160, 2), 1920 * 1080);
+    }
+
+    #[test]
+    fn gains_are_finite_and_dim_with_more_samples() {
+        let (small_waveform, small_vectorscope) = trace_gains(0);
+        assert_eq!((small_waveform, small_vectorscope), (1.0, 1.0));
+        let (waveform, vectorscope) = trace_gains(1920 * 1080);
+        assert!(waveform > 0.0 && waveform < 1.0);
+        assert!(vectorscope > 0.0 && vectorscope < 1.0);
+        let (more_waveform, _) = trace_gains(2 * 1920 * 1080);
+        assert!(more_waveform < waveform);
+    }
+}
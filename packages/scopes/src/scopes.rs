@@ -0,0 +1,339 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scopes (Linux) — measures each input frame's luma waveform, RGB
+//! parade and vectorscope on the GPU and renders them into an output
+//! frame.
+//!
+//! An accumulate kernel samples the frame into ~600 KiB of host-visible
+//! counters (see [`crate::scope_layout`]); a render kernel draws the
+//! configured panels from those counters into the next slot of an
+//! output texture ring. Both run in one submission, so the frame itself
+//! never leaves the GPU. Every `data_interval` frames the counters are
+//! also copied out as a `ScopeData` frame for UIs that draw their own
+//! scopes.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel,
+    VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::tatolab__scopes::scopes_config::Layout;
+use crate::_generated_::{ScopeData, VideoFrame};
+use crate::scope_layout::{
+    self, ALL_PANELS, COLUMNS, DEFAULT_DATA_INTERVAL, DEFAULT_PANEL_SIZE, LEVELS, PARADE_OFFSET,
+    PARADE_WORDS, Panel, TOTAL_WORDS, VECTORSCOPE_OFFSET, VECTORSCOPE_SIZE, VECTORSCOPE_WORDS,
+    WAVEFORM_WORDS,
+};
+
+const SCOPE_ACCUMULATE_SPV: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/scope_accumulate.spv"));
+const SCOPE_RENDER_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/scope_render.spv"));
+
+const ACCUMULATE_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_buffer(1),
+];
+
+const RENDER_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_buffer(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+/// Matches `scope_accumulate.comp`'s 16x16 workgroup.
+const ACCUMULATE_WORKGROUP_SIZE: u32 = 16;
+/// Matches `scope_render.comp`'s 8x8 workgroup.
+const RENDER_WORKGROUP_SIZE: u32 = 8;
+
+/// Push constants of `scope_accumulate.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct AccumulatePushConstants {
+    width: u32,
+    height: u32,
+    stride: u32,
+}
+
+/// Push constants of `scope_render.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct RenderPushConstants {
+    panel_size: u32,
+    panel_count: u32,
+    panels: [u32; 3],
+    waveform_gain: f32,
+    vectorscope_gain: f32,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/scopes/Scopes",
+    description = "Computes a luma waveform, RGB parade and vectorscope of its input on the GPU and renders them side by side into an output frame. Raw scope counts go out on a data port for UIs that draw their own scopes.",
+    execution = reactive,
+    config = crate::_generated_::ScopesConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to measure"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "The rendered scopes (RGBA8)"),
+    output("scope_data", "@tatolab/scopes/ScopeData", description = "Raw scope counts, every data_interval frames"),
+)]
+pub struct ScopesProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    accumulate_kernel: Option<VulkanComputeKernel>,
+    render_kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-visible scope counters, zeroed before every frame.
+    bins: Option<StorageBuffer>,
+    output_ring: Option<TextureRing>,
+    panels: Vec<Panel>,
+    panel_size: u32,
+    frames_measured: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for ScopesProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.panels = match self.config.layout.as_ref().unwrap_or(&Layout::All) {
+            Layout::All => ALL_PANELS.to_vec(),
+            Layout::Waveform => vec![Panel::Waveform],
+            Layout::Parade => vec![Panel::Parade],
+            Layout::Vectorscope => vec![Panel::Vectorscope],
+        };
+        self.panel_size = self.config.panel_size.unwrap_or(DEFAULT_PANEL_SIZE);
+        if self.panel_size == 0 {
+            return Err(Error::Configuration(
+                "Scopes: panel_size must be > 0".into(),
+            ));
+        }
+        let (width, height) = scope_layout::output_size(&self.panels, self.panel_size);
+
+        let full = ctx.gpu_full_access();
+        self.accumulate_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "scope_accumulate",
+            spv: SCOPE_ACCUMULATE_SPV,
+            bindings: ACCUMULATE_BINDINGS,
+            push_constant_size: std::mem::size_of::<AccumulatePushConstants>() as u32,
+        })?);
+        self.render_kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "scope_render",
+            spv: SCOPE_RENDER_SPV,
+            bindings: RENDER_BINDINGS,
+            push_constant_size: std::mem::size_of::<RenderPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("scopes")?);
+        let bins = full.acquire_storage_buffer(std::mem::size_of::<[u32; TOTAL_WORDS]>() as u64)?;
+        if bins.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Scopes: scope buffer is not host-mapped".into(),
+            ));
+        }
+        self.bins = Some(bins);
+        self.output_ring = Some(full.create_texture_ring(
+            width,
+            height,
+            TextureFormat::Rgba8Unorm,
+            TextureUsages::STORAGE_BINDING
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            COMPUTE_OUTPUT_RING_DEPTH,
+        )?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!(
+            "[Scopes] Setup ({:?}, {}x{} output)",
+            self.panels,
+            width,
+            height
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.accumulate_kernel = None;
+        self.render_kernel = None;
+        self.bins = None;
+        self.output_ring = None;
+        tracing::info!("[Scopes] Teardown ({} frames)", self.frames_measured);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let data_interval = self.config.data_interval.unwrap_or(DEFAULT_DATA_INTERVAL);
+        let publish_data = data_interval > 0
+            && self
+                .frames_measured
+                .is_multiple_of(u64::from(data_interval));
+
+        let (scopes_frame, samples) = self.measure(&frame)?;
+        self.frames_measured += 1;
+        self.outputs.write("video_out", &scopes_frame)?;
+        if publish_data {
+            let data = self.scope_data(&frame, samples)?;
+            self.outputs.write("scope_data", &data)?;
+        }
+        Ok(())
+    }
+}
+
+impl ScopesProcessor::Processor {
+    /// Accumulate `frame`'s scopes and render them into the next output
+    /// slot. Returns the output frame and the number of pixels measured.
+    fn measure(&mut self, frame: &VideoFrame) -> Result<(VideoFrame, u32)> {
+        let gpu = self
+            .gpu_context
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Scopes: GPU context not initialized".into()))?;
+        let (Some(accumulate), Some(render), Some(recorder), Some(bins), Some(ring)) = (
+            self.accumulate_kernel.as_ref(),
+            self.render_kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.bins.as_ref(),
+            self.output_ring.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "Scopes: kernels not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+        let stride = scope_layout::sample_stride(width, height);
+        let samples = scope_layout::sample_count(width, height, stride);
+        let (waveform_gain, vectorscope_gain) = scope_layout::trace_gains(samples);
+
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let (out_width, out_height) = (slot.texture.width(), slot.texture.height());
+        let slot_registration = gpu.resolve_texture_registration_by_surface_id(
+            &slot_surface_id,
+            None,
+            out_width,
+            out_height,
+        )?;
+
+        // SAFETY: `bins` is a persistently-mapped host-visible allocation
+        // of `TOTAL_WORDS` u32s (checked non-null in setup), and the
+        // previous submit has completed, so the GPU is not using it. Host
+        // writes before the submit are visible to the kernels.
+        unsafe {
+            std::ptr::write_bytes(bins.mapped_ptr() as *mut u32, 0, TOTAL_WORDS);
+        }
+        accumulate.set_sampled_texture(0, &texture)?;
+        accumulate.set_storage_buffer_storage(1, bins)?;
+        accumulate.set_push_constants_value(&AccumulatePushConstants {
+            width,
+            height,
+            stride,
+        })?;
+        let mut panels = [Panel::Vectorscope as u32; 3];
+        for (code, panel) in panels.iter_mut().zip(&self.panels) {
+            *code = *panel as u32;
+        }
+        render.set_storage_buffer_storage(0, bins)?;
+        render.set_storage_image(1, &slot.texture)?;
+        render.set_push_constants_value(&RenderPushConstants {
+            panel_size: self.panel_size,
+            panel_count: self.panels.len() as u32,
+            panels,
+            waveform_gain,
+            vectorscope_gain,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_dispatch(
+            accumulate,
+            width.div_ceil(stride).div_ceil(ACCUMULATE_WORKGROUP_SIZE),
+            height.div_ceil(stride).div_ceil(ACCUMULATE_WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.record_buffer_barrier(
+            bins,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::SHADER_READ,
+        )?;
+        recorder.record_buffer_barrier(
+            bins,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::HOST,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::HOST_READ,
+        )?;
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            render,
+            out_width.div_ceil(RENDER_WORKGROUP_SIZE),
+            out_height.div_ceil(RENDER_WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        let scopes_frame = VideoFrame {
+            surface_id: slot_surface_id,
+            width: out_width,
+            height: out_height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            // A rendered graphic, not picture content.
+            color_info: None,
+            mastering_display: None,
+            content_light: None,
+            field_order: None,
+        };
+        Ok((scopes_frame, samples))
+    }
+
+    /// Copy the counters of the frame just measured out as `ScopeData`.
+    fn scope_data(&self, frame: &VideoFrame, samples: u32) -> Result<ScopeData> {
+        let bins = self
+            .bins
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Scopes: scope buffer not initialized".into()))?;
+        // SAFETY: `bins` maps `TOTAL_WORDS` u32s; the submit has completed
+        // and the host barrier made the accumulate kernel's writes visible.
+        let words =
+            unsafe { std::slice::from_raw_parts(bins.mapped_ptr() as *const u32, TOTAL_WORDS) };
+        Ok(ScopeData {
+            timestamp_ns: frame.timestamp_ns.clone(),
+            columns: COLUMNS,
+            levels: LEVELS,
+            vectorscope_size: VECTORSCOPE_SIZE,
+            samples,
+            waveform: words[..WAVEFORM_WORDS].to_vec(),
+            parade: words[PARADE_OFFSET..PARADE_OFFSET + PARADE_WORDS].to_vec(),
+            vectorscope: words[VECTORSCOPE_OFFSET..VECTORSCOPE_OFFSET + VECTORSCOPE_WORDS].to_vec(),
+        })
+    }
+}
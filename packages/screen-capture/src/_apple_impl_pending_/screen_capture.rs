@@ -30,10 +30,73 @@ pub use crate::_generated_::tatolab__screen_capture::screen_capture_config::{
 };
 
 type CMSampleBufferRef = *mut c_void;
+type CMBlockBufferRef = *mut c_void;
+type CMFormatDescriptionRef = *const c_void;
 
 #[link(name = "CoreMedia", kind = "framework")]
 extern "C" {
     fn CMSampleBufferGetImageBuffer(sbuf: CMSampleBufferRef) -> *mut CVPixelBuffer;
+    fn CMSampleBufferGetFormatDescription(sbuf: CMSampleBufferRef) -> CMFormatDescriptionRef;
+    fn CMAudioFormatDescriptionGetStreamBasicDescription(
+        desc: CMFormatDescriptionRef,
+    ) -> *const AudioStreamBasicDescription;
+    fn CMSampleBufferGetAudioBufferListWithRetainedBlockBuffer(
+        sbuf: CMSampleBufferRef,
+        buffer_list_size_needed_out: *mut usize,
+        buffer_list_out: *mut AudioBufferList,
+        buffer_list_size: usize,
+        block_buffer_structure_allocator: *const c_void,
+        block_buffer_block_allocator: *const c_void,
+        flags: u32,
+        block_buffer_out: *mut CMBlockBufferRef,
+    ) -> i32;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFRelease(cf: *const c_void);
+}
+
+/// `kAudioFormatFlagIsFloat`.
+const AUDIO_FORMAT_FLAG_IS_FLOAT: u32 = 1 << 0;
+/// `kAudioFormatFlagIsNonInterleaved`.
+const AUDIO_FORMAT_FLAG_IS_NON_INTERLEAVED: u32 = 1 << 5;
+/// `kCMSampleBufferFlag_AudioBufferList_Assure16ByteAlignment`.
+const SAMPLE_BUFFER_FLAG_ASSURE_16_BYTE_ALIGNMENT: u32 = 1 << 0;
+/// Channel ceiling of `@tatolab/core/AudioFrame`, and so of the buffer list
+/// we hand CoreMedia to fill.
+const MAX_AUDIO_CHANNELS: usize = 8;
+
+/// CoreAudio `AudioStreamBasicDescription`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct AudioStreamBasicDescription {
+    sample_rate: f64,
+    format_id: u32,
+    format_flags: u32,
+    bytes_per_packet: u32,
+    frames_per_packet: u32,
+    bytes_per_frame: u32,
+    channels_per_frame: u32,
+    bits_per_channel: u32,
+    reserved: u32,
+}
+
+/// CoreAudio `AudioBuffer`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AudioBuffer {
+    number_channels: u32,
+    data_byte_size: u32,
+    data: *mut c_void,
+}
+
+/// CoreAudio `AudioBufferList`, sized for [`MAX_AUDIO_CHANNELS`] planes
+/// (the C type's trailing array is variable-length).
+#[repr(C)]
+struct AudioBufferList {
+    number_buffers: u32,
+    buffers: [AudioBuffer; MAX_AUDIO_CHANNELS],
 }
 
 /// CMTime structure for setting frame interval on SCStreamConfiguration.
@@ -110,6 +173,10 @@ struct ScreenCaptureCallbackContext {
     output_writer: *const OutputWriter,
     gpu_context: GpuContextLimitedAccess,
     frame_count: AtomicU64,
+    /// Audio buffers written to the `audio` port; doubles as `frame_index`.
+    audio_frame_count: AtomicU64,
+    /// Set once an undecodable audio format has been logged.
+    audio_format_warned: AtomicBool,
     _outputs_arc: OutputWriter,
 }
 
@@ -136,16 +203,19 @@ define_class!(
             sample_buffer: &CMSampleBuffer,
             output_type: SCStreamOutputType,
         ) {
-            // Only process screen content, not audio
-            if output_type != SCStreamOutputType::Screen {
-                return;
-            }
-
             let Some(ctx) = SCREEN_CAPTURE_CALLBACK_CONTEXT.get() else {
                 return;
             };
 
             let sample_buffer_ptr = sample_buffer as *const CMSampleBuffer as *mut c_void;
+
+            if output_type == SCStreamOutputType::Audio {
+                write_audio_sample_buffer(ctx, sample_buffer_ptr);
+                return;
+            }
+            if output_type != SCStreamOutputType::Screen {
+                return;
+            }
             let pixel_buffer_ptr = CMSampleBufferGetImageBuffer(sample_buffer_ptr);
             if pixel_buffer_ptr.is_null() {
                 return;
@@ -255,6 +325,132 @@ unsafe fn blit_iosurface_to_pooled_buffer(
         .blit_copy_iosurface(source_iosurface, pooled_buffer, width, height)
 }
 
+/// Copy one ScreenCaptureKit audio sample buffer out as an interleaved
+/// `AudioFrame`. ScreenCaptureKit delivers Float32 PCM, planar (one
+/// `AudioBuffer` per channel) on every macOS release so far; interleaved
+/// Float32 is accepted too. Anything else is logged once and dropped.
+unsafe fn write_audio_sample_buffer(
+    ctx: &ScreenCaptureCallbackContext,
+    sample_buffer: CMSampleBufferRef,
+) {
+    let format = CMSampleBufferGetFormatDescription(sample_buffer);
+    if format.is_null() {
+        return;
+    }
+    let asbd = CMAudioFormatDescriptionGetStreamBasicDescription(format);
+    if asbd.is_null() {
+        return;
+    }
+    let asbd = *asbd;
+    let channels = asbd.channels_per_frame as usize;
+    if asbd.format_flags & AUDIO_FORMAT_FLAG_IS_FLOAT == 0
+        || asbd.bits_per_channel != 32
+        || channels == 0
+        || channels > MAX_AUDIO_CHANNELS
+    {
+        if !ctx.audio_format_warned.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                "[ScreenCapture] Unsupported system-audio format ({:?}); audio dropped",
+                asbd
+            );
+        }
+        return;
+    }
+
+    let mut buffer_list = AudioBufferList {
+        number_buffers: 0,
+        buffers: [AudioBuffer {
+            number_channels: 0,
+            data_byte_size: 0,
+            data: std::ptr::null_mut(),
+        }; MAX_AUDIO_CHANNELS],
+    };
+    let mut block_buffer: CMBlockBufferRef = std::ptr::null_mut();
+    let status = CMSampleBufferGetAudioBufferListWithRetainedBlockBuffer(
+        sample_buffer,
+        std::ptr::null_mut(),
+        &mut buffer_list,
+        std::mem::size_of::<AudioBufferList>(),
+        std::ptr::null(),
+        std::ptr::null(),
+        SAMPLE_BUFFER_FLAG_ASSURE_16_BYTE_ALIGNMENT,
+        &mut block_buffer,
+    );
+    if status != 0 {
+        tracing::debug!("[ScreenCapture] Audio buffer list unavailable (status {})", status);
+        return;
+    }
+
+    let buffer_count = (buffer_list.number_buffers as usize).min(MAX_AUDIO_CHANNELS);
+    let buffers = &buffer_list.buffers[..buffer_count];
+    let samples = if asbd.format_flags & AUDIO_FORMAT_FLAG_IS_NON_INTERLEAVED != 0 {
+        let planes: Vec<&[f32]> = buffers
+            .iter()
+            .take(channels)
+            .map(|b| {
+                std::slice::from_raw_parts(
+                    b.data as *const f32,
+                    b.data_byte_size as usize / std::mem::size_of::<f32>(),
+                )
+            })
+            .collect();
+        interleave_planes(&planes)
+    } else {
+        buffers
+            .first()
+            .map(|b| {
+                std::slice::from_raw_parts(
+                    b.data as *const f32,
+                    b.data_byte_size as usize / std::mem::size_of::<f32>(),
+                )
+                .to_vec()
+            })
+            .unwrap_or_default()
+    };
+    if !block_buffer.is_null() {
+        CFRelease(block_buffer);
+    }
+    if samples.is_empty() {
+        return;
+    }
+
+    let frame_index = ctx.audio_frame_count.fetch_add(1, Ordering::Relaxed);
+    let frame = crate::_generated_::AudioFrame {
+        samples,
+        channels: channels as u8,
+        sample_rate: asbd.sample_rate as u32,
+        timestamp_ns: (streamlib_plugin_sdk::sdk::media_clock::MediaClock::now().as_nanos()
+            as i64)
+            .to_string(),
+        frame_index: frame_index.to_string(),
+    };
+    let outputs = &*ctx.output_writer;
+    if let Err(e) = outputs.write("audio", &frame) {
+        tracing::warn!("[ScreenCapture] Failed to write audio frame: {}", e);
+        return;
+    }
+    if frame_index == 0 {
+        tracing::info!(
+            "[ScreenCapture] First system-audio buffer ({} ch @ {} Hz)",
+            channels,
+            asbd.sample_rate
+        );
+    }
+}
+
+/// Interleave equal-length planes (`L L L`, `R R R` → `L R L R L R`). A
+/// short plane truncates every channel to its length.
+fn interleave_planes(planes: &[&[f32]]) -> Vec<f32> {
+    let frames = planes.iter().map(|p| p.len()).min().unwrap_or(0);
+    let mut interleaved = Vec::with_capacity(frames * planes.len());
+    for frame in 0..frames {
+        for plane in planes {
+            interleaved.push(plane[frame]);
+        }
+    }
+    interleaved
+}
+
 /// Fall back to direct IOSurface forwarding.
 unsafe fn forward_iosurface_directly(
     ctx: &ScreenCaptureCallbackContext,
@@ -278,6 +474,7 @@ unsafe fn forward_iosurface_directly(
     execution = manual,
     config = crate::_generated_::ScreenCaptureConfig,
     output("video", "@tatolab/core/VideoFrame", description = "Captured video frames from screen content"),
+    output("audio", "@tatolab/core/AudioFrame", description = "Captured system audio, interleaved Float32 (when capture_audio is set)"),
 )]
 pub struct AppleScreenCaptureProcessor {
     /// GPU context for surface pooling (set in setup).
@@ -319,6 +516,8 @@ impl streamlib_plugin_sdk::sdk::processors::ManualProcessor for AppleScreenCaptu
             output_writer: output_writer_ptr,
             gpu_context,
             frame_count: AtomicU64::new(0),
+            audio_frame_count: AtomicU64::new(0),
+            audio_format_warned: AtomicBool::new(false),
             _outputs_arc: outputs_arc,
         });
 
@@ -473,6 +672,18 @@ impl AppleScreenCaptureProcessor::Processor {
         // Set pixel format to BGRA for consistency
         stream_config.setPixelFormat(0x42475241); // 'BGRA'
 
+        // System audio (macOS 13+). The current process's own output is
+        // excluded alongside its windows so a monitoring AudioOutput in the
+        // same graph doesn't feed back into the capture.
+        let capture_audio = config.capture_audio.unwrap_or(false);
+        if capture_audio {
+            stream_config.setCapturesAudio(true);
+            stream_config.setSampleRate(config.audio_sample_rate.unwrap_or(48_000) as isize);
+            stream_config.setChannelCount(config.audio_channels.unwrap_or(2) as isize);
+            stream_config
+                .setExcludesCurrentProcessAudio(config.exclude_current_app.unwrap_or(true));
+        }
+
         // Create and start stream
         let stream = SCStream::initWithFilter_configuration_delegate(
             SCStream::alloc(),
@@ -502,6 +713,27 @@ impl AppleScreenCaptureProcessor::Processor {
             )));
         }
 
+        // Audio on its own serial queue so a slow video blit never delays
+        // audio delivery.
+        if capture_audio {
+            let audio_queue = dispatch2::DispatchQueue::new(
+                "com.streamlib.screen_capture.audio",
+                dispatch2::DispatchQueueAttr::SERIAL,
+            );
+            let add_audio_result: std::result::Result<(), Retained<NSError>> = stream
+                .addStreamOutput_type_sampleHandlerQueue_error(
+                    ProtocolObject::from_ref(&*delegate),
+                    SCStreamOutputType::Audio,
+                    Some(&audio_queue),
+                );
+            if let Err(e) = add_audio_result {
+                return Err(Error::Configuration(format!(
+                    "Failed to add audio stream output: {}",
+                    e.localizedDescription()
+                )));
+            }
+        }
+
         // Start capture
         let start_block = RcBlock::new(move |error: *mut NSError| {
             if !error.is_null() {
@@ -615,6 +847,22 @@ impl AppleScreenCaptureProcessor::Processor {
 
 /// Validate config based on target_type.
 fn validate_config(config: &ScreenCaptureConfig) -> Result<()> {
+    if let Some(rate) = config.audio_sample_rate
+        && !matches!(rate, 8_000 | 16_000 | 24_000 | 48_000)
+    {
+        return Err(Error::Configuration(format!(
+            "audio_sample_rate {} unsupported by ScreenCaptureKit (8000, 16000, 24000, or 48000)",
+            rate
+        )));
+    }
+    if let Some(channels) = config.audio_channels
+        && !(1..=2).contains(&channels)
+    {
+        return Err(Error::Configuration(format!(
+            "audio_channels {} unsupported by ScreenCaptureKit (1 or 2)",
+            channels
+        )));
+    }
     match config.target_type {
         TargetType::Display => Ok(()),
         TargetType::Window => {
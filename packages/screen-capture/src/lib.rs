@@ -3,16 +3,22 @@
 
 //! `@tatolab/screen-capture` — screen capture processor for streamlib.
 //!
-//! Apple-only today (macOS / iOS via ScreenCaptureKit). The sole processor —
-//! the ScreenCaptureKit capture — is parked under `_apple_impl_pending_`, so
-//! this package currently ships no live processor on any target. A Linux
-//! screen-capture processor is tracked separately.
+//! Linux captures a monitor or window through xdg-desktop-portal + PipeWire
+//! (see [`linux`]). The macOS / iOS ScreenCaptureKit capture — display,
+//! window, and application filters plus system audio on the `audio` port —
+//! is parked under `_apple_impl_pending_`, so Apple targets currently ship no
+//! live processor.
 
 #[allow(non_snake_case, unused_imports, clippy::all)]
 pub mod _generated_ {
     include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
 }
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(target_os = "linux")]
+pub mod screen_capture;
+
 // The Apple ScreenCaptureKit processor references an engine-free Apple RHI /
 // surface-pool surface (`streamlib_plugin_sdk::sdk::rhi::{PixelBuffer,
 // PixelBufferRef, PixelFormat}` plus the surface-pool acquire/check-in APIs)
@@ -26,3 +32,8 @@ mod _apple_impl_pending_;
 
 pub use _generated_::ScreenCaptureConfig;
 pub use _generated_::tatolab__screen_capture::screen_capture_config::TargetType;
+#[cfg(target_os = "linux")]
+pub use screen_capture::ScreenCaptureProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::ScreenCaptureProcessor::Processor);
@@ -0,0 +1,7 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+pub mod portal;
+pub mod screen_capture;
+
+pub use screen_capture::LinuxScreenCaptureProcessor;
@@ -0,0 +1,170 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! xdg-desktop-portal ScreenCast handshake.
+//!
+//! Wayland compositors hand screen contents out only through the portal:
+//! the app asks for a monitor or a window, the user picks one in the
+//! portal dialog (or a restore token from an earlier session picks it
+//! silently), and the portal answers with a PipeWire remote fd plus the
+//! node id of the stream to connect to.
+
+use std::os::fd::OwnedFd;
+
+use ashpd::desktop::PersistMode;
+use ashpd::desktop::Session;
+use ashpd::desktop::screencast::{CursorMode, Screencast, SourceType};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__screen_capture::screen_capture_config::TargetType;
+
+/// What to ask the portal for.
+#[derive(Debug, Clone)]
+pub struct PortalRequest {
+    pub source_type: SourceType,
+    pub show_cursor: bool,
+    /// Allow the portal to hand back more than one stream — only useful
+    /// with a restore token covering several monitors.
+    pub multiple: bool,
+    pub restore_token: Option<String>,
+    /// Which of the returned streams to capture.
+    pub stream_index: usize,
+}
+
+impl PortalRequest {
+    pub fn from_config(config: &crate::_generated_::ScreenCaptureConfig) -> Result<Self> {
+        let source_type = match config.target_type {
+            TargetType::Display => SourceType::Monitor,
+            TargetType::Window => SourceType::Window,
+            TargetType::Application => {
+                return Err(Error::Configuration(
+                    "ScreenCapture: Application capture is not available on Linux — the \
+                     ScreenCast portal offers Display and Window only"
+                        .into(),
+                ));
+            }
+        };
+        Ok(Self {
+            source_type,
+            show_cursor: config.show_cursor.unwrap_or(false),
+            multiple: config.display_index.is_some(),
+            restore_token: config.restore_token.clone(),
+            stream_index: config.display_index.unwrap_or(0) as usize,
+        })
+    }
+}
+
+/// The stream the portal granted, plus the session keeping it alive.
+pub struct PortalStream {
+    pub pipewire_fd: OwnedFd,
+    pub node_id: u32,
+    /// Size the portal reported for the stream; the negotiated PipeWire
+    /// format is authoritative.
+    pub size: Option<(i32, i32)>,
+    /// Token reusing this pick next time, when the portal issued one.
+    pub restore_token: Option<String>,
+    proxy: Screencast<'static>,
+    session: Session<'static, Screencast<'static>>,
+}
+
+impl PortalStream {
+    /// Close the portal session; the compositor stops the cast.
+    pub async fn close(self) {
+        if let Err(e) = self.session.close().await {
+            tracing::debug!(error = %e, "ScreenCapture: portal session close failed");
+        }
+        drop(self.proxy);
+    }
+}
+
+/// Run the handshake. Blocks on the portal dialog unless the request
+/// carries a restore token the portal accepts.
+pub async fn open_screencast(request: &PortalRequest) -> Result<PortalStream> {
+    let portal_error = |step: &str| {
+        let step = step.to_string();
+        move |e: ashpd::Error| {
+            Error::Configuration(format!("ScreenCapture: portal {} failed: {}", step, e))
+        }
+    };
+
+    let proxy = Screencast::new()
+        .await
+        .map_err(portal_error("ScreenCast connect"))?;
+
+    let available = proxy
+        .available_source_types()
+        .await
+        .map_err(portal_error("AvailableSourceTypes"))?;
+    if !available.contains(request.source_type) {
+        return Err(Error::Configuration(format!(
+            "ScreenCapture: the desktop portal cannot capture {:?} sources (available: {:?})",
+            request.source_type, available
+        )));
+    }
+
+    let cursor_mode = if request.show_cursor {
+        let modes = proxy
+            .available_cursor_modes()
+            .await
+            .map_err(portal_error("AvailableCursorModes"))?;
+        if modes.contains(CursorMode::Embedded) {
+            CursorMode::Embedded
+        } else {
+            tracing::warn!(
+                "ScreenCapture: portal cannot embed the cursor ({:?}); capturing without it",
+                modes
+            );
+            CursorMode::Hidden
+        }
+    } else {
+        CursorMode::Hidden
+    };
+
+    let session = proxy
+        .create_session()
+        .await
+        .map_err(portal_error("CreateSession"))?;
+    proxy
+        .select_sources(
+            &session,
+            cursor_mode,
+            request.source_type.into(),
+            request.multiple,
+            request.restore_token.as_deref(),
+            PersistMode::ExplicitlyRevoked,
+        )
+        .await
+        .map_err(portal_error("SelectSources"))?;
+
+    let streams = proxy
+        .start(&session, None)
+        .await
+        .map_err(portal_error("Start"))?
+        .response()
+        .map_err(portal_error("Start (user response)"))?;
+
+    let stream = streams.streams().get(request.stream_index).ok_or_else(|| {
+        Error::Configuration(format!(
+            "ScreenCapture: display_index {} is out of range — the portal granted {} stream(s)",
+            request.stream_index,
+            streams.streams().len()
+        ))
+    })?;
+    let node_id = stream.pipe_wire_node_id();
+    let size = stream.size();
+    let restore_token = streams.restore_token().map(str::to_string);
+
+    let pipewire_fd = proxy
+        .open_pipe_wire_remote(&session)
+        .await
+        .map_err(portal_error("OpenPipeWireRemote"))?;
+
+    Ok(PortalStream {
+        pipewire_fd,
+        node_id,
+        size,
+        restore_token,
+        proxy,
+        session,
+    })
+}
@@ -0,0 +1,910 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! xdg-desktop-portal + PipeWire screen capture processor.
+//!
+//! The capture thread runs the portal handshake, then consumes the granted
+//! PipeWire stream. Formats are offered LINEAR DMA-BUF first: those buffers
+//! are imported as storage buffers and swizzled BGRx → RGBA by the color
+//! converter straight into the ring texture, with no CPU copy. Compositors
+//! that only share memory (or a failed import) fall back to a memcpy into a
+//! host-visible staging buffer feeding the same kernel.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use pipewire as pw;
+use pw::spa::buffer::DataType;
+use pw::spa::param::ParamType;
+use pw::spa::param::format::{FormatProperties, MediaSubtype, MediaType};
+use pw::spa::param::video::{VideoFormat, VideoInfoRaw};
+use pw::spa::pod::serialize::PodSerializer;
+use pw::spa::pod::{ChoiceValue, Pod, Property, PropertyFlags, Value};
+use pw::spa::utils::{Choice, ChoiceEnum, ChoiceFlags, Direction, Fraction, Rectangle, SpaTypes};
+use pw::stream::{StreamFlags, StreamRef, StreamState};
+use streamlib_plugin_sdk::sdk::color::{
+    ColorSpaceKind, ResolvedColorInfo, TransferId, resolve_color_defaults,
+};
+use streamlib_plugin_sdk::sdk::context::{GpuContextLimitedAccess, RuntimeContextFullAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::rhi::{
+    HostTimelineSemaphore, ImageCopyRegion, PixelFormat, RhiColorConverter, RhiCommandRecorder,
+    SourceLayoutInfo, StorageBuffer, Texture, TextureFormat, VulkanAccess, VulkanLayout,
+    VulkanStage,
+};
+
+use super::portal::{self, PortalRequest};
+use crate::_generated_::tatolab__core::color_info::{Matrix, Primaries, Range, Transfer};
+
+/// Number of ring textures for GPU-resident pipeline (matches MAX_FRAMES_IN_FLIGHT).
+const RING_TEXTURE_COUNT: usize = 2;
+
+/// `DRM_FORMAT_MOD_LINEAR` — the only DMA-BUF layout the storage-buffer
+/// import can address.
+const DRM_FORMAT_MOD_LINEAR: i64 = 0;
+
+/// Frame rate offered to the compositor when the config leaves it unset.
+const DEFAULT_FRAME_RATE: f64 = 30.0;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/screen-capture/ScreenCapture",
+    description = "Captures a monitor or window (xdg-desktop-portal + PipeWire on Linux, ScreenCaptureKit on macOS/iOS)",
+    execution = manual,
+    scheduling = high,
+    config = crate::_generated_::ScreenCaptureConfig,
+    output("video", "@tatolab/core/VideoFrame", description = "Captured screen frames"),
+    // Declared so the processor's ports match the ScreenCaptureKit build;
+    // the portal has no system-audio source, so Linux never writes it.
+    output("audio", "@tatolab/core/AudioFrame", description = "Captured system audio (macOS only)"),
+)]
+pub struct LinuxScreenCaptureProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    frame_counter: Arc<AtomicU64>,
+    /// Quits the capture thread's PipeWire main loop.
+    quit_sender: Option<pw::channel::Sender<()>>,
+    capture_thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ManualProcessor
+    for LinuxScreenCaptureProcessor::Processor
+{
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!("ScreenCapture: setup() complete");
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "ScreenCapture: Teardown (generated {} frames)",
+            self.frame_counter.load(Ordering::Relaxed)
+        );
+        if let Some(quit_sender) = self.quit_sender.take() {
+            let _ = quit_sender.send(());
+        }
+        if let Some(handle) = self.capture_thread_handle.take() {
+            let _ = handle.join();
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let gpu_context = self.gpu_context.clone().ok_or_else(|| {
+            Error::Configuration("GPU context not initialized. Call setup() first.".into())
+        })?;
+        let request = PortalRequest::from_config(&self.config)?;
+        let frame_rate = self.config.frame_rate.unwrap_or(DEFAULT_FRAME_RATE);
+        if frame_rate.is_nan() || frame_rate <= 0.0 {
+            return Err(Error::Configuration(format!(
+                "ScreenCapture: frame_rate must be positive, got {}",
+                frame_rate
+            )));
+        }
+
+        // The portal handshake can sit on the source-picker dialog for as
+        // long as the user likes, so it runs on the capture thread rather
+        // than blocking start().
+        let (quit_sender, quit_receiver) = pw::channel::channel::<()>();
+        let frame_counter = Arc::clone(&self.frame_counter);
+        let outputs: OutputWriter = self.outputs.clone();
+
+        let handle = std::thread::Builder::new()
+            .name("screen-capture".into())
+            .spawn(move || {
+                capture_thread_main(
+                    request,
+                    frame_rate,
+                    quit_receiver,
+                    gpu_context,
+                    outputs,
+                    frame_counter,
+                );
+            })
+            .map_err(|e| Error::Configuration(format!("Failed to spawn capture thread: {}", e)))?;
+
+        self.quit_sender = Some(quit_sender);
+        self.capture_thread_handle = Some(handle);
+        tracing::info!(
+            "ScreenCapture: started ({:?}, {} fps requested)",
+            self.config.target_type,
+            frame_rate
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if let Some(quit_sender) = self.quit_sender.take() {
+            let _ = quit_sender.send(());
+        }
+
+        // Bounded wait — same shape as the camera's stop(). A capture thread
+        // still parked on the portal dialog never reaches the PipeWire loop
+        // that would see the quit, so detach after the grace window rather
+        // than hold up the runtime's shutdown chain.
+        if let Some(handle) = self.capture_thread_handle.take() {
+            let deadline = Instant::now() + Duration::from_secs(2);
+            while !handle.is_finished() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            if handle.is_finished() {
+                let _ = handle.join();
+            } else {
+                tracing::warn!("ScreenCapture: capture thread did not exit within 2s, detaching");
+            }
+        }
+
+        tracing::info!(
+            "ScreenCapture: Stopped ({} frames)",
+            self.frame_counter.load(Ordering::Relaxed)
+        );
+        Ok(())
+    }
+}
+
+fn capture_thread_main(
+    request: PortalRequest,
+    frame_rate: f64,
+    quit_receiver: pw::channel::Receiver<()>,
+    gpu_context: GpuContextLimitedAccess,
+    outputs: OutputWriter,
+    frame_counter: Arc<AtomicU64>,
+) {
+    // zbus needs a reactor for the handshake; a current-thread runtime on
+    // this thread keeps the portal session's lifetime here too.
+    let tokio_runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            tracing::error!(error = %e, "ScreenCapture: failed to build portal runtime");
+            return;
+        }
+    };
+
+    let portal_stream = match tokio_runtime.block_on(portal::open_screencast(&request)) {
+        Ok(stream) => stream,
+        Err(e) => {
+            tracing::error!(error = %e, "ScreenCapture: portal handshake failed");
+            return;
+        }
+    };
+    match &portal_stream.restore_token {
+        Some(token) => tracing::info!(
+            restore_token = %token,
+            "ScreenCapture: set config.restore_token to this value to skip the portal dialog next time"
+        ),
+        None => tracing::debug!("ScreenCapture: portal issued no restore token"),
+    }
+    tracing::info!(
+        node_id = portal_stream.node_id,
+        size = ?portal_stream.size,
+        "ScreenCapture: portal granted stream"
+    );
+
+    let run_result = portal_stream
+        .pipewire_fd
+        .try_clone()
+        .map_err(|e| {
+            Error::Configuration(format!("ScreenCapture: failed to dup PipeWire fd: {}", e))
+        })
+        .and_then(|fd| {
+            run_pipewire_stream(
+                fd,
+                portal_stream.node_id,
+                frame_rate,
+                quit_receiver,
+                gpu_context,
+                outputs,
+                frame_counter,
+            )
+        });
+    if let Err(e) = run_result {
+        tracing::error!(error = %e, "ScreenCapture: PipeWire stream failed");
+    }
+
+    tokio_runtime.block_on(portal_stream.close());
+}
+
+/// Connect to the portal's PipeWire remote and run the stream until
+/// `quit_receiver` fires.
+fn run_pipewire_stream(
+    pipewire_fd: std::os::fd::OwnedFd,
+    node_id: u32,
+    frame_rate: f64,
+    quit_receiver: pw::channel::Receiver<()>,
+    gpu_context: GpuContextLimitedAccess,
+    outputs: OutputWriter,
+    frame_counter: Arc<AtomicU64>,
+) -> Result<()> {
+    let pipewire_error = |step: &'static str| {
+        move |e: pw::Error| Error::Configuration(format!("ScreenCapture: {} failed: {}", step, e))
+    };
+
+    // Only import DMA-BUFs on devices that can; everything else negotiates
+    // shared memory from the start.
+    let allow_dma_buf = gpu_context
+        .escalate(|full| full.gpu_capabilities())
+        .and_then(std::convert::identity)
+        .map(|caps| caps.supports_external_memory)
+        .unwrap_or(false);
+
+    let max_fps = frame_rate.round().max(1.0) as u32;
+    let shm_format = enum_format_pod(max_fps, false)?;
+    let dma_buf_format = enum_format_pod(max_fps, true)?;
+
+    pw::init();
+    let mainloop = pw::main_loop::MainLoop::new(None).map_err(pipewire_error("MainLoop"))?;
+    let context = pw::context::Context::new(&mainloop).map_err(pipewire_error("Context"))?;
+    let core = context
+        .connect_fd(pipewire_fd, None)
+        .map_err(pipewire_error("connect to portal remote"))?;
+
+    let _quit = quit_receiver.attach(mainloop.loop_(), {
+        let mainloop = mainloop.clone();
+        move |_| mainloop.quit()
+    });
+
+    let stream = pw::stream::Stream::new(
+        &core,
+        "streamlib-screen-capture",
+        pw::properties::properties! {
+            *pw::keys::MEDIA_TYPE => "Video",
+            *pw::keys::MEDIA_CATEGORY => "Capture",
+            *pw::keys::MEDIA_ROLE => "Screen",
+        },
+    )
+    .map_err(pipewire_error("Stream"))?;
+
+    let state = CaptureStreamState {
+        gpu_context,
+        outputs,
+        frame_counter,
+        format: VideoInfoRaw::new(),
+        fps: None,
+        allow_dma_buf,
+        shm_format: shm_format.clone(),
+        resolved_color: resolve_color_defaults(None, None, None, None, ColorSpaceKind::Rgb),
+        gpu: None,
+    };
+
+    let _listener = stream
+        .add_local_listener_with_user_data(state)
+        .state_changed(|_, _, old, new| {
+            tracing::debug!("ScreenCapture: stream state {:?} -> {:?}", old, new);
+            if let StreamState::Error(message) = new {
+                tracing::error!("ScreenCapture: PipeWire stream error: {}", message);
+            }
+        })
+        .param_changed(|stream, state, id, param| {
+            if let Some(param) = param
+                && id == ParamType::Format.as_raw()
+            {
+                state.on_format_changed(stream, param);
+            }
+        })
+        .process(|stream, state| state.on_process(stream))
+        .register()
+        .map_err(pipewire_error("stream listener"))?;
+
+    let mut formats: Vec<&[u8]> = Vec::with_capacity(2);
+    if allow_dma_buf {
+        formats.push(&dma_buf_format);
+    }
+    formats.push(&shm_format);
+    let mut params: Vec<&Pod> = formats.into_iter().filter_map(Pod::from_bytes).collect();
+    stream
+        .connect(
+            Direction::Input,
+            Some(node_id),
+            StreamFlags::AUTOCONNECT | StreamFlags::MAP_BUFFERS,
+            &mut params,
+        )
+        .map_err(pipewire_error("stream connect"))?;
+
+    mainloop.run();
+    let _ = stream.disconnect();
+    Ok(())
+}
+
+/// An `EnumFormat` offering BGRx / BGRA. With `linear_dma_buf` the format
+/// carries a mandatory LINEAR modifier, which is how a consumer asks a
+/// PipeWire producer for DMA-BUFs.
+fn enum_format_pod(max_fps: u32, linear_dma_buf: bool) -> Result<Vec<u8>> {
+    let mut object = pw::spa::pod::object!(
+        SpaTypes::ObjectParamFormat,
+        ParamType::EnumFormat,
+        pw::spa::pod::property!(FormatProperties::MediaType, Id, MediaType::Video),
+        pw::spa::pod::property!(FormatProperties::MediaSubtype, Id, MediaSubtype::Raw),
+        pw::spa::pod::property!(
+            FormatProperties::VideoFormat,
+            Choice,
+            Enum,
+            Id,
+            VideoFormat::BGRx,
+            VideoFormat::BGRx,
+            VideoFormat::BGRA
+        ),
+        pw::spa::pod::property!(
+            FormatProperties::VideoSize,
+            Choice,
+            Range,
+            Rectangle,
+            Rectangle {
+                width: 1920,
+                height: 1080
+            },
+            Rectangle {
+                width: 1,
+                height: 1
+            },
+            Rectangle {
+                width: 8192,
+                height: 8192
+            }
+        ),
+        pw::spa::pod::property!(
+            FormatProperties::VideoFramerate,
+            Choice,
+            Range,
+            Fraction,
+            Fraction {
+                num: max_fps,
+                denom: 1
+            },
+            Fraction { num: 0, denom: 1 },
+            Fraction {
+                num: max_fps,
+                denom: 1
+            }
+        ),
+    );
+    if linear_dma_buf {
+        object.properties.push(Property {
+            key: FormatProperties::VideoModifier.as_raw(),
+            flags: PropertyFlags::MANDATORY,
+            value: Value::Long(DRM_FORMAT_MOD_LINEAR),
+        });
+    }
+    serialize_pod(Value::Object(object))
+}
+
+/// A `Buffers` param naming the buffer memory types this consumer accepts.
+fn buffers_pod(allow_dma_buf: bool) -> Result<Vec<u8>> {
+    let mut data_types = (1 << DataType::MemFd.as_raw()) | (1 << DataType::MemPtr.as_raw());
+    if allow_dma_buf {
+        data_types |= 1 << DataType::DmaBuf.as_raw();
+    }
+    let object = pw::spa::pod::Object {
+        type_: SpaTypes::ObjectParamBuffers.as_raw(),
+        id: ParamType::Buffers.as_raw(),
+        properties: vec![Property {
+            key: pw::spa::sys::SPA_PARAM_BUFFERS_dataType,
+            flags: PropertyFlags::empty(),
+            value: Value::Choice(ChoiceValue::Int(Choice(
+                ChoiceFlags::empty(),
+                ChoiceEnum::Flags {
+                    default: data_types as i32,
+                    flags: vec![data_types as i32],
+                },
+            ))),
+        }],
+    };
+    serialize_pod(Value::Object(object))
+}
+
+fn serialize_pod(value: Value) -> Result<Vec<u8>> {
+    PodSerializer::serialize(Cursor::new(Vec::new()), &value)
+        .map(|(cursor, _)| cursor.into_inner())
+        .map_err(|e| {
+            Error::Configuration(format!("ScreenCapture: failed to serialize pod: {:?}", e))
+        })
+}
+
+/// PipeWire listener state, owned by the capture thread's main loop.
+struct CaptureStreamState {
+    gpu_context: GpuContextLimitedAccess,
+    outputs: OutputWriter,
+    frame_counter: Arc<AtomicU64>,
+    format: VideoInfoRaw,
+    fps: Option<u32>,
+    /// Cleared for good once a DMA-BUF import fails.
+    allow_dma_buf: bool,
+    shm_format: Vec<u8>,
+    /// Screencast pixels are sRGB already; the converter only swizzles.
+    resolved_color: ResolvedColorInfo,
+    /// Rebuilt whenever the negotiated size changes.
+    gpu: Option<ScreenCaptureGpuResources>,
+}
+
+impl CaptureStreamState {
+    fn on_format_changed(&mut self, stream: &StreamRef, param: &Pod) {
+        let Ok((media_type, media_subtype)) = pw::spa::param::format_utils::parse_format(param)
+        else {
+            return;
+        };
+        if media_type != MediaType::Video || media_subtype != MediaSubtype::Raw {
+            return;
+        }
+        if let Err(e) = self.format.parse(param) {
+            tracing::warn!("ScreenCapture: unparseable video format: {:?}", e);
+            return;
+        }
+        let size = self.format.size();
+        let framerate = match self.format.max_framerate() {
+            rate if rate.num > 0 => rate,
+            _ => self.format.framerate(),
+        };
+        self.fps =
+            (framerate.num > 0 && framerate.denom > 0).then(|| framerate.num / framerate.denom);
+        tracing::info!(
+            "ScreenCapture: negotiated {:?} {}x{} @ {}/{}",
+            self.format.format(),
+            size.width,
+            size.height,
+            framerate.num,
+            framerate.denom
+        );
+
+        // Buffers are reallocated after every format change, so fds cached
+        // from the previous negotiation may be reused for new memory.
+        match self.gpu.as_mut() {
+            Some(gpu) if gpu.width == size.width && gpu.height == size.height => {
+                gpu.dma_buf_imports.clear();
+            }
+            _ => {
+                self.gpu = match ScreenCaptureGpuResources::new(
+                    &self.gpu_context,
+                    size.width,
+                    size.height,
+                ) {
+                    Ok(gpu) => Some(gpu),
+                    Err(e) => {
+                        tracing::error!(error = %e, "ScreenCapture: failed to set up GPU resources");
+                        None
+                    }
+                };
+            }
+        }
+
+        match buffers_pod(self.allow_dma_buf) {
+            Ok(bytes) => {
+                if let Some(pod) = Pod::from_bytes(&bytes)
+                    && let Err(e) = stream.update_params(&mut [pod])
+                {
+                    tracing::warn!("ScreenCapture: failed to set buffer params: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "ScreenCapture: buffer params unavailable"),
+        }
+    }
+
+    fn on_process(&mut self, stream: &StreamRef) {
+        // Only the newest frame matters; dropping older buffers requeues them.
+        let mut newest = None;
+        while let Some(buffer) = stream.dequeue_buffer() {
+            newest = Some(buffer);
+        }
+        let Some(mut buffer) = newest else {
+            return;
+        };
+        let Some(gpu) = self.gpu.as_mut() else {
+            return;
+        };
+        let Some(data) = buffer.datas_mut().first_mut() else {
+            return;
+        };
+
+        let (width, height) = (gpu.width, gpu.height);
+        let chunk = data.chunk();
+        let (chunk_offset, chunk_size) = (chunk.offset() as usize, chunk.size() as usize);
+        let stride = match chunk.stride() {
+            stride if stride > 0 => stride as u32,
+            _ => width * 4,
+        };
+        let frame_bytes = stride as usize * height as usize;
+
+        let input = match data.type_() {
+            DataType::DmaBuf => {
+                if chunk_offset != 0 {
+                    tracing::debug!("ScreenCapture: skipping DMA-BUF frame at a non-zero offset");
+                    return;
+                }
+                let fd = data.as_raw().fd;
+                match gpu.dma_buf_input(&self.gpu_context, fd, frame_bytes as u64) {
+                    Ok(()) => FrameInput::DmaBuf(fd),
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            "ScreenCapture: DMA-BUF import failed — renegotiating shared memory"
+                        );
+                        self.allow_dma_buf = false;
+                        if let Some(pod) = Pod::from_bytes(&self.shm_format)
+                            && let Err(e) = stream.update_params(&mut [pod])
+                        {
+                            tracing::error!("ScreenCapture: renegotiation failed: {}", e);
+                        }
+                        return;
+                    }
+                }
+            }
+            DataType::MemFd | DataType::MemPtr => {
+                if chunk_size == 0 {
+                    return;
+                }
+                let Some(bytes) = data.data() else {
+                    return;
+                };
+                let Some(frame) = bytes.get(chunk_offset..chunk_offset + frame_bytes) else {
+                    tracing::warn!(
+                        "ScreenCapture: shared-memory frame shorter than {}x{} at stride {}",
+                        width,
+                        height,
+                        stride
+                    );
+                    return;
+                };
+                match gpu.shm_input(&self.gpu_context, frame) {
+                    Ok(index) => FrameInput::Shm(index),
+                    Err(e) => {
+                        tracing::error!(error = %e, "ScreenCapture: shared-memory upload failed");
+                        return;
+                    }
+                }
+            }
+            other => {
+                tracing::debug!("ScreenCapture: ignoring {:?} buffer", other);
+                return;
+            }
+        };
+
+        let frame_num = self.frame_counter.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = gpu.convert_and_publish(
+            &self.gpu_context,
+            &self.outputs,
+            &self.resolved_color,
+            input,
+            stride,
+            frame_num,
+            self.fps,
+        ) {
+            if frame_num == 0 {
+                tracing::error!(error = %e, "ScreenCapture: failed to publish frame");
+            } else {
+                tracing::debug!(error = %e, frame = frame_num, "ScreenCapture: frame dropped");
+            }
+            return;
+        }
+
+        if frame_num == 0 {
+            let mode = match input {
+                FrameInput::DmaBuf(_) => "DMA-BUF zero-copy",
+                FrameInput::Shm(_) => "shared memory + memcpy",
+            };
+            tracing::info!(mode, width, height, "ScreenCapture: first frame captured");
+        } else if frame_num % 300 == 0 {
+            tracing::debug!(frame = frame_num, "ScreenCapture: frame milestone");
+        }
+    }
+}
+
+/// Where the current frame's pixels live on the GPU.
+#[derive(Debug, Clone, Copy)]
+enum FrameInput {
+    /// Imported PipeWire DMA-BUF, keyed by its PipeWire-side fd.
+    DmaBuf(i64),
+    /// Ping-pong shared-memory staging buffer index.
+    Shm(usize),
+}
+
+struct ScreenCaptureGpuResources {
+    width: u32,
+    height: u32,
+    color_converter: RhiColorConverter,
+    recorder: RhiCommandRecorder,
+    timeline: HostTimelineSemaphore,
+    // Per-ring-slot single-writer-per-edge exportable timeline pairs —
+    // see `docs/architecture/adapter-timeline-single-writer.md`.
+    ring_produce_done: Vec<HostTimelineSemaphore>,
+    ring_consume_done: Vec<HostTimelineSemaphore>,
+    ring_textures: Vec<Texture>,
+    ring_texture_ids: Vec<String>,
+    /// Double-buffered HOST_VISIBLE staging for the shared-memory path,
+    /// allocated on first use.
+    shm_buffers: Vec<StorageBuffer>,
+    shm_capacity: u64,
+    shm_index: usize,
+    /// PipeWire recycles a fixed buffer set, so each DMA-BUF is imported
+    /// once and reused until the next format change.
+    dma_buf_imports: HashMap<i64, StorageBuffer>,
+}
+
+impl ScreenCaptureGpuResources {
+    fn new(gpu_context: &GpuContextLimitedAccess, width: u32, height: u32) -> Result<Self> {
+        let resources = gpu_context
+            .escalate(|full| {
+                let color_converter =
+                    full.color_converter(PixelFormat::Bgra32, PixelFormat::Rgba32)?;
+                let recorder = full.create_command_recorder("screen_capture")?;
+                let timeline = full.create_exportable_timeline_semaphore(0)?;
+
+                let mut ring_textures = Vec::with_capacity(RING_TEXTURE_COUNT);
+                let mut ring_texture_ids = Vec::with_capacity(RING_TEXTURE_COUNT);
+                let mut ring_produce_done = Vec::with_capacity(RING_TEXTURE_COUNT);
+                let mut ring_consume_done = Vec::with_capacity(RING_TEXTURE_COUNT);
+                for _ in 0..RING_TEXTURE_COUNT {
+                    ring_textures.push(full.acquire_render_target_dma_buf_image(
+                        width,
+                        height,
+                        TextureFormat::Rgba8Unorm,
+                    )?);
+                    ring_texture_ids.push(uuid::Uuid::new_v4().to_string());
+                    ring_produce_done.push(full.create_exportable_timeline_semaphore(0)?);
+                    ring_consume_done.push(full.create_exportable_timeline_semaphore(0)?);
+                }
+
+                Ok(Self {
+                    width,
+                    height,
+                    color_converter,
+                    recorder,
+                    timeline,
+                    ring_produce_done,
+                    ring_consume_done,
+                    ring_textures,
+                    ring_texture_ids,
+                    shm_buffers: Vec::new(),
+                    shm_capacity: 0,
+                    shm_index: 0,
+                    dma_buf_imports: HashMap::new(),
+                })
+            })
+            // `escalate` wraps the closure's own `Result` — flatten it.
+            .and_then(std::convert::identity)?;
+
+        // Same dual registration as the camera ring — see
+        // `docs/architecture/adapter-runtime-integration.md`.
+        let store = gpu_context.surface_store();
+        for (i, (texture_id, texture)) in resources
+            .ring_texture_ids
+            .iter()
+            .zip(resources.ring_textures.iter())
+            .enumerate()
+        {
+            if !store.is_none()
+                && let Err(e) = store.register_texture(
+                    texture_id,
+                    texture,
+                    Some(&resources.ring_produce_done[i]),
+                    Some(&resources.ring_consume_done[i]),
+                    VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                )
+            {
+                tracing::warn!(
+                    ring_index = i,
+                    error = %e,
+                    "ScreenCapture: failed to register ring texture with the surface-share service — cross-process GPU sharing unavailable, same-process still works",
+                );
+            }
+            gpu_context.register_texture_with_layout(
+                texture_id,
+                texture.clone(),
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+            );
+        }
+
+        tracing::info!(
+            count = RING_TEXTURE_COUNT,
+            width,
+            height,
+            "ScreenCapture: ring textures created"
+        );
+        Ok(resources)
+    }
+
+    /// Make sure the DMA-BUF behind PipeWire `fd` is imported.
+    fn dma_buf_input(
+        &mut self,
+        gpu_context: &GpuContextLimitedAccess,
+        fd: i64,
+        byte_size: u64,
+    ) -> Result<()> {
+        if self.dma_buf_imports.contains_key(&fd) {
+            return Ok(());
+        }
+        // The import takes ownership of the fd it is given; PipeWire keeps
+        // its own, so hand over a duplicate.
+        let duplicate = unsafe { libc::fcntl(fd as i32, libc::F_DUPFD_CLOEXEC, 0) };
+        if duplicate < 0 {
+            return Err(Error::GpuError(format!(
+                "failed to dup DMA-BUF fd {}: {}",
+                fd,
+                std::io::Error::last_os_error()
+            )));
+        }
+        let imported = gpu_context
+            .escalate(|full| full.import_dma_buf_storage_buffer(duplicate, byte_size))
+            .and_then(std::convert::identity);
+        match imported {
+            Ok(buffer) => {
+                self.dma_buf_imports.insert(fd, buffer);
+                Ok(())
+            }
+            Err(e) => {
+                unsafe { libc::close(duplicate) };
+                Err(e)
+            }
+        }
+    }
+
+    /// Copy a shared-memory frame into the next staging buffer, returning
+    /// its index.
+    fn shm_input(&mut self, gpu_context: &GpuContextLimitedAccess, frame: &[u8]) -> Result<usize> {
+        let needed = frame.len().next_multiple_of(4) as u64;
+        if self.shm_capacity < needed {
+            self.shm_buffers = gpu_context
+                .escalate(|full| {
+                    (0..2)
+                        .map(|_| full.acquire_storage_buffer(needed))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .and_then(std::convert::identity)?;
+            self.shm_capacity = needed;
+        }
+        self.shm_index = 1 - self.shm_index;
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                frame.as_ptr(),
+                self.shm_buffers[self.shm_index].mapped_ptr(),
+                frame.len(),
+            );
+        }
+        Ok(self.shm_index)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn convert_and_publish(
+        &mut self,
+        gpu_context: &GpuContextLimitedAccess,
+        outputs: &OutputWriter,
+        resolved_color: &ResolvedColorInfo,
+        input: FrameInput,
+        stride: u32,
+        frame_num: u64,
+        fps: Option<u32>,
+    ) -> Result<()> {
+        let (width, height) = (self.width, self.height);
+        let ring_index = (frame_num as usize) % RING_TEXTURE_COUNT;
+        let ring_texture = &self.ring_textures[ring_index];
+        let input_buffer = match input {
+            FrameInput::DmaBuf(fd) => &self.dma_buf_imports[&fd],
+            FrameInput::Shm(index) => &self.shm_buffers[index],
+        };
+
+        let (pool_id, pooled_buffer) =
+            gpu_context.acquire_pixel_buffer(width, height, PixelFormat::Rgba32)?;
+        gpu_context.register_texture_with_layout(
+            &pool_id.to_string(),
+            ring_texture.clone(),
+            VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+        );
+
+        let kernel = self.color_converter.prepare_buffer_to_image_storage(
+            input_buffer,
+            SourceLayoutInfo::bgra(stride),
+            ring_texture,
+            resolved_color,
+            TransferId::Srgb,
+        )?;
+
+        self.recorder.begin()?;
+        self.recorder.record_image_barrier(
+            ring_texture,
+            VulkanLayout::UNDEFINED,
+            VulkanLayout::GENERAL,
+            VulkanStage::NONE,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::NONE,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        // The compositor rendered into the DMA-BUF before queueing it; make
+        // that write visible to the compute read. Host-written staging
+        // buffers need no GPU-side barrier.
+        if let FrameInput::DmaBuf(_) = input {
+            self.recorder.record_buffer_barrier(
+                input_buffer,
+                VulkanStage::NONE,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::NONE,
+                VulkanAccess::SHADER_READ,
+            )?;
+        }
+        self.recorder
+            .record_dispatch(&kernel, width.div_ceil(16), height.div_ceil(16), 1)?;
+        self.recorder.record_image_barrier(
+            ring_texture,
+            VulkanLayout::GENERAL,
+            VulkanLayout::TRANSFER_SRC_OPTIMAL,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::ALL_TRANSFER,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::TRANSFER_READ,
+        )?;
+        self.recorder.record_copy_image_to_pixel_buffer(
+            ring_texture,
+            VulkanLayout::TRANSFER_SRC_OPTIMAL,
+            &pooled_buffer,
+            ImageCopyRegion::tightly_packed(width, height),
+        )?;
+        self.recorder.record_image_barrier(
+            ring_texture,
+            VulkanLayout::TRANSFER_SRC_OPTIMAL,
+            VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+            VulkanStage::ALL_TRANSFER,
+            VulkanStage::FRAGMENT_SHADER,
+            VulkanAccess::TRANSFER_READ,
+            VulkanAccess::SHADER_READ,
+        )?;
+        self.recorder.record_pixel_buffer_barrier(
+            &pooled_buffer,
+            VulkanStage::ALL_TRANSFER,
+            VulkanStage::HOST,
+            VulkanAccess::TRANSFER_WRITE,
+            VulkanAccess::HOST_READ,
+        )?;
+
+        // Waiting here also keeps the PipeWire buffer alive until the GPU
+        // has read it, and the ring slot free for the next frame.
+        let timeline_signal_value = frame_num + 1;
+        self.recorder
+            .submit_signaling_timeline(&self.timeline, timeline_signal_value)?;
+        self.timeline.wait(timeline_signal_value, u64::MAX)?;
+
+        let frame = crate::_generated_::VideoFrame {
+            surface_id: pool_id.to_string(),
+            width,
+            height,
+            timestamp_ns: (streamlib_plugin_sdk::sdk::media_clock::MediaClock::now().as_nanos()
+                as i64)
+                .to_string(),
+            fps,
+            texture_layout: None,
+            // The ring holds sRGB-encoded RGB, exactly as the compositor
+            // rendered it.
+            color_info: Some(crate::_generated_::ColorInfo {
+                primaries: Some(Primaries::Bt709),
+                transfer: Some(Transfer::Srgb),
+                matrix: Some(Matrix::Identity),
+                range: Some(Range::Full),
+            }),
+            mastering_display: None,
+            content_light: None,
+            field_order: None,
+        };
+        outputs.write("video", &frame)
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Platform-specific re-exports with unified names.
+
+pub use crate::linux::LinuxScreenCaptureProcessor as ScreenCaptureProcessor;
@@ -0,0 +1,13 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+#![allow(clippy::disallowed_macros)] // build.rs uses println! for `cargo:` directives
+
+//! Codegen for the shader-effect package: generates the typed config + the
+//! imported `@tatolab/core` wire types (VideoFrame). The shader itself
+//! arrives in the config and is compiled at setup, so there is no `glslc`
+//! step.
+
+fn main() {
+    streamlib_jtd_codegen::build_rs::run_for_rust_crate();
+}
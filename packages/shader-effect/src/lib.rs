@@ -0,0 +1,25 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/shader-effect` — `ShaderEffect` runs a one-pass WGSL compute
+//! shader written in its config or a file, so a simple effect needs no
+//! plugin build. The shader is compiled to SPIR-V at setup and recompiled
+//! when the config changes — or, when watched, when the file is saved.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod program;
+
+// User shaders compile and dispatch through `sdk::rhi`, which is
+// Linux-only; `program` parsing and validation stay portable.
+#[cfg(target_os = "linux")]
+pub mod shader_effect;
+
+#[cfg(target_os = "linux")]
+pub use shader_effect::ShaderEffectProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::ShaderEffectProcessor::Processor,);
@@ -0,0 +1,628 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `ShaderEffectConfig` resolved into a compiled kernel and its uniform
+//! bytes.
+//!
+//! The config's WGSL is completed with declarations of the two frames and
+//! the `uniforms` block, parsed and validated by naga, and written out as
+//! SPIR-V. The declarations go after the author's source — WGSL
+//! module-scope declarations are order-independent — so line numbers in
+//! compile errors match the source as written. Uniforms travel as push
+//! constants, packed at the member offsets naga laid the block out with.
+//!
+//! The source is inline in the config or read from `source_path`; errors
+//! in a file's source name the file.
+
+use std::path::PathBuf;
+
+use naga::back::spv;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::ShaderEffectConfig;
+use crate::_generated_::tatolab__shader_effect::shader_effect_config::{Kind, Uniform};
+
+pub const DEFAULT_ENTRY_POINT: &str = "main";
+pub const DEFAULT_INPUT_BINDING: u32 = 0;
+pub const DEFAULT_OUTPUT_BINDING: u32 = 1;
+/// Push-constant bytes every Vulkan device provides.
+pub const MAX_UNIFORM_BYTES: u32 = 128;
+
+/// WGSL names of the declarations added to the author's source.
+pub const INPUT_NAME: &str = "input_frame";
+pub const OUTPUT_NAME: &str = "output_frame";
+pub const UNIFORMS_NAME: &str = "uniforms";
+const UNIFORMS_STRUCT: &str = "ShaderEffectUniforms";
+
+/// The kernel's SPIR-V entry point; the compute pipeline is created with
+/// this name whatever the config's entry point is called.
+const SPIRV_ENTRY_POINT: &str = "main";
+
+/// WGSL type of one uniform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniformKind {
+    F32,
+    I32,
+    U32,
+    Vec2,
+    Vec3,
+    Vec4,
+}
+
+impl UniformKind {
+    fn from_config(kind: &Kind) -> Self {
+        match kind {
+            Kind::F32 => Self::F32,
+            Kind::I32 => Self::I32,
+            Kind::U32 => Self::U32,
+            Kind::Vec2 => Self::Vec2,
+            Kind::Vec3 => Self::Vec3,
+            Kind::Vec4 => Self::Vec4,
+        }
+    }
+
+    fn wgsl(self) -> &'static str {
+        match self {
+            Self::F32 => "f32",
+            Self::I32 => "i32",
+            Self::U32 => "u32",
+            Self::Vec2 => "vec2<f32>",
+            Self::Vec3 => "vec3<f32>",
+            Self::Vec4 => "vec4<f32>",
+        }
+    }
+
+    fn components(self) -> usize {
+        match self {
+            Self::F32 | Self::I32 | Self::U32 => 1,
+            Self::Vec2 => 2,
+            Self::Vec3 => 3,
+            Self::Vec4 => 4,
+        }
+    }
+
+    /// Little-endian bytes of one component.
+    fn component_bytes(self, value: f32) -> [u8; 4] {
+        match self {
+            Self::I32 => (value as i32).to_le_bytes(),
+            Self::U32 => (value as u32).to_le_bytes(),
+            _ => value.to_le_bytes(),
+        }
+    }
+}
+
+/// Everything that shapes the compiled kernel; a config update that
+/// changes any of it recompiles, one that only changes uniform values
+/// does not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramSource {
+    pub source: String,
+    /// The file `source` was read from, if it was not inline.
+    pub source_path: Option<PathBuf>,
+    pub entry_point: String,
+    pub input_binding: u32,
+    pub output_binding: u32,
+    pub uniforms: Vec<(String, UniformKind)>,
+}
+
+/// A validated config: the program to compile and the uniform values to
+/// run it with, one `Vec` per uniform.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectParams {
+    pub program: ProgramSource,
+    pub uniform_values: Vec<Vec<f32>>,
+}
+
+impl EffectParams {
+    pub fn from_config(config: &ShaderEffectConfig) -> Result<Self> {
+        let (source, source_path) = match (&config.source, &config.source_path) {
+            (Some(source), None) => (source.clone(), None),
+            (None, Some(path)) => {
+                let source = std::fs::read_to_string(path).map_err(|e| {
+                    Error::Configuration(format!("ShaderEffect: cannot read {}: {}", path, e))
+                })?;
+                (source, Some(PathBuf::from(path)))
+            }
+            _ => {
+                return Err(Error::Configuration(
+                    "ShaderEffect: set exactly one of source and source_path".into(),
+                ));
+            }
+        };
+        if source.trim().is_empty() {
+            return Err(Error::Configuration("ShaderEffect: source is empty".into()));
+        }
+        let input_binding = config.input_binding.unwrap_or(DEFAULT_INPUT_BINDING);
+        let output_binding = config.output_binding.unwrap_or(DEFAULT_OUTPUT_BINDING);
+        if input_binding == output_binding {
+            return Err(Error::Configuration(format!(
+                "ShaderEffect: input_binding and output_binding are both {}",
+                input_binding
+            )));
+        }
+
+        let configured: &[Uniform] = config.uniforms.as_deref().unwrap_or_default();
+        let mut uniforms = Vec::with_capacity(configured.len());
+        let mut uniform_values = Vec::with_capacity(configured.len());
+        for uniform in configured {
+            let kind = UniformKind::from_config(&uniform.kind);
+            if !is_identifier(&uniform.name) {
+                return Err(Error::Configuration(format!(
+                    "ShaderEffect: uniform name '{}' is not a WGSL identifier",
+                    uniform.name
+                )));
+            }
+            if uniforms.iter().any(|(name, _)| *name == uniform.name) {
+                return Err(Error::Configuration(format!(
+                    "ShaderEffect: uniform '{}' is defined twice",
+                    uniform.name
+                )));
+            }
+            let value = match &uniform.value {
+                None => vec![0.0; kind.components()],
+                Some(value) if value.len() == kind.components() => value.clone(),
+                Some(value) => {
+                    return Err(Error::Configuration(format!(
+                        "ShaderEffect: uniform '{}' is {} but has {} values",
+                        uniform.name,
+                        kind.wgsl(),
+                        value.len()
+                    )));
+                }
+            };
+            let representable = match kind {
+                UniformKind::I32 => value.iter().all(|v| v.fract() == 0.0),
+                UniformKind::U32 => value.iter().all(|v| v.fract() == 0.0 && *v >= 0.0),
+                _ => value.iter().all(|v| v.is_finite()),
+            };
+            if !representable {
+                return Err(Error::Configuration(format!(
+                    "ShaderEffect: uniform '{}' value {:?} is not a valid {}",
+                    uniform.name,
+                    value,
+                    kind.wgsl()
+                )));
+            }
+            uniforms.push((uniform.name.clone(), kind));
+            uniform_values.push(value);
+        }
+
+        Ok(Self {
+            program: ProgramSource {
+                source,
+                source_path,
+                entry_point: config
+                    .entry_point
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ENTRY_POINT.to_string()),
+                input_binding,
+                output_binding,
+                uniforms,
+            },
+            uniform_values,
+        })
+    }
+}
+
+/// A program compiled to SPIR-V, with what the dispatch needs to know
+/// about it.
+#[derive(Debug, Clone)]
+pub struct CompiledProgram {
+    pub spv: Vec<u8>,
+    pub workgroup_size: [u32; 3],
+    /// Whether the entry point reads `input_frame`; a generator that does
+    /// not has no input binding.
+    pub reads_input: bool,
+    /// Size of the `uniforms` block; 0 when there are no uniforms or the
+    /// entry point never reads them.
+    pub uniform_size: u32,
+    /// Byte offset of each uniform within the block, in config order.
+    uniform_offsets: Vec<u32>,
+}
+
+impl CompiledProgram {
+    pub fn compile(program: &ProgramSource) -> Result<Self> {
+        let full_source = format!("{}\n{}", program.source, declarations(program));
+        let origin = program
+            .source_path
+            .as_ref()
+            .map_or_else(|| "wgsl".to_string(), |path| path.display().to_string());
+        let mut module = naga::front::wgsl::parse_str(&full_source).map_err(|e| {
+            Error::Configuration(format!(
+                "ShaderEffect: WGSL does not parse:\n{}",
+                e.emit_to_string_with_path(&full_source, &origin)
+            ))
+        })?;
+
+        let entry = module
+            .entry_points
+            .iter()
+            .position(|ep| ep.stage == naga::ShaderStage::Compute && ep.name == program.entry_point)
+            .ok_or_else(|| {
+                let compute: Vec<&str> = module
+                    .entry_points
+                    .iter()
+                    .filter(|ep| ep.stage == naga::ShaderStage::Compute)
+                    .map(|ep| ep.name.as_str())
+                    .collect();
+                Error::Configuration(format!(
+                    "ShaderEffect: no @compute entry point '{}' (found: {:?})",
+                    program.entry_point, compute
+                ))
+            })?;
+        if !module.overrides.is_empty()
+            || module.entry_points[entry]
+                .workgroup_size_overrides
+                .is_some()
+        {
+            return Err(Error::Configuration(
+                "ShaderEffect: pipeline-overridable constants are not supported".into(),
+            ));
+        }
+        if let Some(name) = module.global_variables.iter().find_map(|(_, var)| {
+            let binding = var.binding.as_ref()?;
+            let ours = binding.group == 0
+                && (binding.binding == program.input_binding
+                    || binding.binding == program.output_binding);
+            (!ours).then(|| var.name.clone().unwrap_or_default())
+        }) {
+            return Err(Error::Configuration(format!(
+                "ShaderEffect: '{}' is bound, but only {} and {} can be — read the input \
+                 with textureLoad rather than a sampler",
+                name, INPUT_NAME, OUTPUT_NAME
+            )));
+        }
+
+        // The pipeline is created against a single entry point called
+        // `main`; drop the others so the rename cannot collide.
+        let mut chosen = module.entry_points.swap_remove(entry);
+        let workgroup_size = chosen.workgroup_size;
+        chosen.name = SPIRV_ENTRY_POINT.to_string();
+        module.entry_points = vec![chosen];
+
+        let info = Validator::new(ValidationFlags::all(), Capabilities::IMMEDIATES)
+            .validate(&module)
+            .map_err(|e| {
+                Error::Configuration(format!(
+                    "ShaderEffect: WGSL does not validate:\n{}",
+                    e.emit_to_string_with_path(&full_source, &origin)
+                ))
+            })?;
+
+        // naga leaves globals the entry point never touches out of the
+        // SPIR-V, and the kernel must declare exactly what the SPIR-V uses.
+        let used = |name: &str| {
+            module.global_variables.iter().any(|(handle, var)| {
+                var.name.as_deref() == Some(name) && !info.get_entry_point(0)[handle].is_empty()
+            })
+        };
+        if !used(OUTPUT_NAME) {
+            return Err(Error::Configuration(format!(
+                "ShaderEffect: entry point '{}' never writes {}",
+                program.entry_point, OUTPUT_NAME
+            )));
+        }
+        let reads_input = used(INPUT_NAME);
+
+        let (uniform_size, uniform_offsets) = if used(UNIFORMS_NAME) {
+            uniform_layout(&module, program)?
+        } else {
+            (0, Vec::new())
+        };
+        if uniform_size > MAX_UNIFORM_BYTES {
+            return Err(Error::Configuration(format!(
+                "ShaderEffect: uniforms take {} bytes, more than the {} a device guarantees",
+                uniform_size, MAX_UNIFORM_BYTES
+            )));
+        }
+
+        let words = spv::write_vec(
+            &module,
+            &info,
+            &spv::Options::default(),
+            Some(&spv::PipelineOptions {
+                shader_stage: naga::ShaderStage::Compute,
+                entry_point: SPIRV_ENTRY_POINT.to_string(),
+            }),
+        )
+        .map_err(|e| Error::Configuration(format!("ShaderEffect: SPIR-V output failed: {}", e)))?;
+
+        Ok(Self {
+            spv: words.iter().flat_map(|word| word.to_le_bytes()).collect(),
+            workgroup_size,
+            reads_input,
+            uniform_size,
+            uniform_offsets,
+        })
+    }
+
+    /// The `uniforms` block holding `values`, ready for
+    /// `set_push_constants`.
+    pub fn pack_uniforms(&self, program: &ProgramSource, values: &[Vec<f32>]) -> Vec<u8> {
+        let mut bytes = vec![0u8; self.uniform_size as usize];
+        for (((_, kind), value), offset) in program
+            .uniforms
+            .iter()
+            .zip(values)
+            .zip(&self.uniform_offsets)
+        {
+            for (component, v) in value.iter().enumerate() {
+                let at = *offset as usize + component * 4;
+                bytes[at..at + 4].copy_from_slice(&kind.component_bytes(*v));
+            }
+        }
+        bytes
+    }
+
+    /// Workgroups covering a `width` x `height` frame.
+    pub fn dispatch_size(&self, width: u32, height: u32) -> (u32, u32) {
+        (
+            width.div_ceil(self.workgroup_size[0].max(1)),
+            height.div_ceil(self.workgroup_size[1].max(1)),
+        )
+    }
+}
+
+/// WGSL declarations of the frames and the `uniforms` block.
+fn declarations(program: &ProgramSource) -> String {
+    let mut wgsl = format!(
+        "@group(0) @binding({}) var {}: texture_2d<f32>;\n\
+         @group(0) @binding({}) var {}: texture_storage_2d<rgba8unorm, write>;\n",
+        program.input_binding, INPUT_NAME, program.output_binding, OUTPUT_NAME
+    );
+    if !program.uniforms.is_empty() {
+        wgsl.push_str(&format!("struct {} {{\n", UNIFORMS_STRUCT));
+        for (name, kind) in &program.uniforms {
+            wgsl.push_str(&format!("    {}: {},\n", name, kind.wgsl()));
+        }
+        wgsl.push_str(&format!(
+            "}}\nvar<immediate> {}: {};\n",
+            UNIFORMS_NAME, UNIFORMS_STRUCT
+        ));
+    }
+    wgsl
+}
+
+/// Size and member offsets of the `uniforms` block as naga laid it out.
+/// The size runs to the end of the last member, not the struct's padded
+/// span — that is the push-constant range the SPIR-V reflects as, and the
+/// kernel requires the two to agree.
+fn uniform_layout(module: &naga::Module, program: &ProgramSource) -> Result<(u32, Vec<u32>)> {
+    if program.uniforms.is_empty() {
+        return Ok((0, Vec::new()));
+    }
+    let members = module
+        .types
+        .iter()
+        .find_map(|(_, ty)| match &ty.inner {
+            naga::TypeInner::Struct { members, .. }
+                if ty.name.as_deref() == Some(UNIFORMS_STRUCT) =>
+            {
+                Some(members)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            Error::Configuration(format!(
+                "ShaderEffect: the source redefines {}",
+                UNIFORMS_STRUCT
+            ))
+        })?;
+    let offsets: Vec<u32> = members.iter().map(|member| member.offset).collect();
+    let size = offsets
+        .iter()
+        .zip(&program.uniforms)
+        .map(|(offset, (_, kind))| offset + 4 * kind.components() as u32)
+        .max()
+        .unwrap_or(0);
+    Ok((size, offsets))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVERT: &str = "
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(input_frame);
+    if (id.x >= size.x || id.y >= size.y) {
+        return;
+    }
+    let texel = textureLoad(input_frame, vec2<i32>(id.xy), 0);
+    textureStore(output_frame, vec2<i32>(id.xy), vec4<f32>(1.0 - texel.rgb, texel.a));
+}
+";
+
+    const TINT: &str = "
+@compute @workgroup_size(16, 4)
+fn tint(@builtin(global_invocation_id) id: vec3<u32>) {
+    let texel = textureLoad(input_frame, vec2<i32>(id.xy), 0);
+    let mixed = mix(texel.rgb, uniforms.color, uniforms.amount);
+    textureStore(output_frame, vec2<i32>(id.xy), vec4<f32>(mixed, texel.a));
+}
+";
+
+    fn config(source: &str) -> ShaderEffectConfig {
+        ShaderEffectConfig {
+            source: Some(source.to_string()),
+            ..ShaderEffectConfig::default()
+        }
+    }
+
+    fn uniform(name: &str, kind: Kind, value: Option<Vec<f32>>) -> Uniform {
+        Uniform {
+            name: name.to_string(),
+            kind,
+            value,
+        }
+    }
+
+    #[test]
+    fn a_plain_effect_compiles_with_the_default_bindings() {
+        let params = EffectParams::from_config(&config(INVERT)).unwrap();
+        let compiled = CompiledProgram::compile(&params.program).unwrap();
+
+        assert_eq!(&compiled.spv[..4], &0x0723_0203u32.to_le_bytes());
+        assert_eq!(compiled.workgroup_size, [8, 8, 1]);
+        assert!(compiled.reads_input);
+        assert_eq!(compiled.uniform_size, 0);
+        assert_eq!(compiled.dispatch_size(1920, 1080), (240, 135));
+    }
+
+    #[test]
+    fn uniforms_are_packed_at_naga_offsets() {
+        let config = ShaderEffectConfig {
+            entry_point: Some("tint".into()),
+            uniforms: Some(vec![
+                uniform("amount", Kind::F32, Some(vec![0.25])),
+                uniform("color", Kind::Vec3, Some(vec![1.0, 0.5, 0.0])),
+            ]),
+            ..config(TINT)
+        };
+        let params = EffectParams::from_config(&config).unwrap();
+        let compiled = CompiledProgram::compile(&params.program).unwrap();
+
+        // vec3<f32> aligns to 16: amount at 0, color at 16, ending at 28.
+        assert_eq!(compiled.uniform_offsets, vec![0, 16]);
+        assert_eq!(compiled.uniform_size, 28);
+        let bytes = compiled.pack_uniforms(&params.program, &params.uniform_values);
+        assert_eq!(&bytes[0..4], &0.25f32.to_le_bytes());
+        assert_eq!(&bytes[16..20], &1.0f32.to_le_bytes());
+        assert_eq!(&bytes[20..24], &0.5f32.to_le_bytes());
+        assert_eq!(compiled.workgroup_size, [16, 4, 1]);
+    }
+
+    #[test]
+    fn unused_frames_and_uniforms_are_left_unbound() {
+        let solid = "@compute @workgroup_size(8, 8)\nfn main(@builtin(global_invocation_id) id: vec3<u32>) {\n    textureStore(output_frame, vec2<i32>(id.xy), vec4<f32>(1.0));\n}\n";
+        let params = EffectParams::from_config(&ShaderEffectConfig {
+            uniforms: Some(vec![uniform("gain", Kind::F32, None)]),
+            ..config(solid)
+        })
+        .unwrap();
+        let compiled = CompiledProgram::compile(&params.program).unwrap();
+        assert!(!compiled.reads_input);
+        assert_eq!(compiled.uniform_size, 0);
+
+        let silent = "@compute @workgroup_size(8)\nfn main() {}\n";
+        let params = EffectParams::from_config(&config(silent)).unwrap();
+        let error = CompiledProgram::compile(&params.program)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("never writes output_frame"), "{error}");
+    }
+
+    #[test]
+    fn compile_errors_point_at_the_authors_line() {
+        let broken = "@compute @workgroup_size(8)\nfn main() {\n    let x = ;\n}\n";
+        let params = EffectParams::from_config(&config(broken)).unwrap();
+        let error = CompiledProgram::compile(&params.program)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(":3:"), "{error}");
+    }
+
+    #[test]
+    fn source_files_are_read_and_named_in_errors() {
+        let path = std::env::temp_dir().join(format!("shader-effect-{}.wgsl", std::process::id()));
+        std::fs::write(&path, INVERT).unwrap();
+        let from_file = ShaderEffectConfig {
+            source: None,
+            source_path: Some(path.display().to_string()),
+            ..ShaderEffectConfig::default()
+        };
+        let params = EffectParams::from_config(&from_file).unwrap();
+        assert_eq!(params.program.source, INVERT);
+        assert!(CompiledProgram::compile(&params.program).is_ok());
+
+        std::fs::write(&path, "fn main( {").unwrap();
+        let params = EffectParams::from_config(&from_file).unwrap();
+        let error = CompiledProgram::compile(&params.program)
+            .unwrap_err()
+            .to_string();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.contains(&path.display().to_string()), "{error}");
+    }
+
+    #[test]
+    fn a_missing_entry_point_or_extra_binding_is_rejected() {
+        let params = EffectParams::from_config(&ShaderEffectConfig {
+            entry_point: Some("nope".into()),
+            ..config(INVERT)
+        })
+        .unwrap();
+        assert!(CompiledProgram::compile(&params.program).is_err());
+
+        let sampled = format!("@group(0) @binding(2) var smp: sampler;\n{}", INVERT);
+        let params = EffectParams::from_config(&config(&sampled)).unwrap();
+        let error = CompiledProgram::compile(&params.program)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'smp' is bound"), "{error}");
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        for bad in [
+            config("  "),
+            ShaderEffectConfig::default(),
+            ShaderEffectConfig {
+                source_path: Some("effect.wgsl".into()),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                source: None,
+                source_path: Some("/nonexistent/effect.wgsl".into()),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                input_binding: Some(1),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                uniforms: Some(vec![uniform("1st", Kind::F32, None)]),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                uniforms: Some(vec![
+                    uniform("gain", Kind::F32, None),
+                    uniform("gain", Kind::F32, None),
+                ]),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                uniforms: Some(vec![uniform("tint", Kind::Vec3, Some(vec![1.0]))]),
+                ..config(INVERT)
+            },
+            ShaderEffectConfig {
+                uniforms: Some(vec![uniform("count", Kind::U32, Some(vec![-1.0]))]),
+                ..config(INVERT)
+            },
+        ] {
+            assert!(matches!(
+                EffectParams::from_config(&bad),
+                Err(Error::Configuration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn unset_uniform_values_default_to_zero() {
+        let params = EffectParams::from_config(&ShaderEffectConfig {
+            uniforms: Some(vec![uniform("offset", Kind::Vec2, None)]),
+            ..config(INVERT)
+        })
+        .unwrap();
+        assert_eq!(params.uniform_values, vec![vec![0.0, 0.0]]);
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! ShaderEffect (Linux) — a WGSL compute shader from the config, run once
+//! per frame.
+//!
+//! The shader reads the input frame as `input_frame` and writes the next
+//! slot of an RGBA8 output ring as `output_frame`, reallocated when the
+//! input size changes. A config update that changes the source, entry
+//! point, bindings or uniform declarations recompiles; if the new shader
+//! does not compile, the previous one keeps running. Updates to uniform
+//! values alone take effect on the next frame without recompiling.
+//!
+//! With `watch` (or `STREAMLIB_WATCH_SHADERS=1`), a `source_path` file is
+//! polled from `process`; a save recompiles before the next dispatch, and a
+//! save that does not compile is logged and keeps the running shader.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextFullAccess, GpuContextLimitedAccess, RuntimeContextFullAccess,
+    RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel, VulkanLayout,
+    VulkanStage,
+};
+use streamlib_plugin_sdk::sdk::shader_watch::{ShaderWatcher, shader_watch_enabled};
+
+use crate::_generated_::VideoFrame;
+use crate::program::{CompiledProgram, EffectParams, ProgramSource};
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/shader-effect/ShaderEffect",
+    description = "Runs a WGSL compute shader from its config over every frame. The shader reads `input_frame` and writes `output_frame`; editing the source or uniforms in the config takes effect on the next frame.",
+    execution = reactive,
+    config = crate::_generated_::ShaderEffectConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to process"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Processed frames (RGBA8, the input's color description)"),
+)]
+pub struct ShaderEffectProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    /// The running kernel and the program it was compiled from.
+    effect: Option<(VulkanComputeKernel, CompiledProgram)>,
+    recorder: Option<RhiCommandRecorder>,
+    params: Option<EffectParams>,
+    /// Polls `source_path` when watching.
+    watcher: Option<ShaderWatcher>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_processed: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for ShaderEffectProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let params = EffectParams::from_config(&self.config)?;
+        let full = ctx.gpu_full_access();
+        self.effect = Some(build_effect(full, &params.program)?);
+        self.recorder = Some(full.create_command_recorder("shader_effect")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!(
+            "[ShaderEffect] Setup (entry point '{}', {} uniforms)",
+            params.program.entry_point,
+            params.program.uniforms.len()
+        );
+        self.params = Some(params);
+        self.watch_source();
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.effect = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[ShaderEffect] Teardown ({} frames processed)",
+            self.frames_processed
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let params = EffectParams::from_config(&self.config)?;
+        if !self.switch_to(params)? {
+            tracing::info!("[ShaderEffect] Uniforms updated");
+        }
+        self.watch_source();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        self.reload_if_changed();
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let processed = self.apply(&frame)?;
+        self.frames_processed += 1;
+        self.outputs.write("video_out", &processed)
+    }
+}
+
+/// Compile `program` and create its kernel.
+fn build_effect(
+    full: &GpuContextFullAccess,
+    program: &ProgramSource,
+) -> Result<(VulkanComputeKernel, CompiledProgram)> {
+    let compiled = CompiledProgram::compile(program)?;
+    let mut bindings = vec![ComputeBindingSpec::storage_image(program.output_binding)];
+    if compiled.reads_input {
+        bindings.push(ComputeBindingSpec::sampled_image(program.input_binding));
+    }
+    let kernel = full.create_compute_kernel(&ComputeKernelDescriptor {
+        label: "shader_effect",
+        spv: &compiled.spv,
+        bindings: &bindings,
+        push_constant_size: compiled.uniform_size,
+    })?;
+    Ok((kernel, compiled))
+}
+
+impl ShaderEffectProcessor::Processor {
+    /// Run with `params` from the next frame, recompiling if its program
+    /// differs from the running one. Returns whether it recompiled; on
+    /// failure the running kernel and params stay in place.
+    fn switch_to(&mut self, params: EffectParams) -> Result<bool> {
+        let recompile = self
+            .params
+            .as_ref()
+            .is_none_or(|current| current.program != params.program);
+        if recompile {
+            let Some(gpu) = self.gpu_context.as_ref() else {
+                return Err(Error::Configuration(
+                    "ShaderEffect: kernel not initialized".into(),
+                ));
+            };
+            let effect = gpu.escalate(|full| build_effect(full, &params.program))??;
+            self.effect = Some(effect);
+            tracing::info!(
+                "[ShaderEffect] Recompiled (entry point '{}', {} uniforms)",
+                params.program.entry_point,
+                params.program.uniforms.len()
+            );
+        }
+        self.params = Some(params);
+        Ok(recompile)
+    }
+
+    /// Start (or stop) watching the current `source_path`.
+    fn watch_source(&mut self) {
+        let path = self
+            .params
+            .as_ref()
+            .and_then(|params| params.program.source_path.clone());
+        self.watcher = path
+            .filter(|_| shader_watch_enabled(self.config.watch))
+            .map(|path| ShaderWatcher::new([path]));
+    }
+
+    /// `watch`: recompile when the source file changed. A file that fails
+    /// to read or compile keeps the running shader.
+    fn reload_if_changed(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        let changed = watcher.poll();
+        let Some(path) = changed.first() else {
+            return;
+        };
+        match EffectParams::from_config(&self.config).and_then(|params| self.switch_to(params)) {
+            Ok(true) => {}
+            Ok(false) => tracing::debug!("[ShaderEffect] {} saved unchanged", path.display()),
+            Err(e) => tracing::warn!(
+                "[ShaderEffect] Keeping the running shader; {} did not reload: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    /// Run the effect on `frame` into the next output slot.
+    fn apply(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let (Some(gpu), Some((kernel, compiled)), Some(recorder), Some(params)) = (
+            self.gpu_context.as_ref(),
+            self.effect.as_ref(),
+            self.recorder.as_mut(),
+            self.params.as_ref(),
+        ) else {
+            return Err(Error::Configuration(
+                "ShaderEffect: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        if compiled.reads_input {
+            kernel.set_sampled_texture(params.program.input_binding, &texture)?;
+        }
+        kernel.set_storage_image(params.program.output_binding, &slot.texture)?;
+        if compiled.uniform_size > 0 {
+            kernel.set_push_constants(
+                &compiled.pack_uniforms(&params.program, &params.uniform_values),
+            )?;
+        }
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if compiled.reads_input && current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        let (groups_x, groups_y) = compiled.dispatch_size(width, height);
+        recorder.record_dispatch(kernel, groups_x, groups_y, 1)?;
+        let sampled = compiled.reads_input.then_some(&registration);
+        recorder.finish_compute_output(&slot_registration, sampled.as_slice())?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
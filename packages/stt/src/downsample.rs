@@ -0,0 +1,169 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Audio to whisper's input: 16 kHz mono.
+//!
+//! Channels are averaged, higher rates low-passed below the new Nyquist
+//! by two cascaded Butterworth biquads, then linearly interpolated onto
+//! the 16 kHz grid. That is far from a mastering-grade resampler but
+//! plenty for speech recognition, and it keeps its phase across frames
+//! of any size.
+
+/// Whisper's sample rate.
+pub const SPEECH_SAMPLE_RATE: u32 = 16_000;
+
+/// Low-pass corner for downsampling, under the 8 kHz Nyquist.
+const CUTOFF_HZ: f64 = 7_000.0;
+
+/// Direct-form-I biquad.
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    /// Butterworth low-pass (Q = 1/√2) at `cutoff_hz`.
+    fn low_pass(sample_rate: f64, cutoff_hz: f64) -> Self {
+        let omega = std::f64::consts::TAU * cutoff_hz / sample_rate;
+        let alpha = omega.sin() / std::f64::consts::SQRT_2;
+        let cos = omega.cos();
+        let a0 = 1.0 + alpha;
+        let b1 = (1.0 - cos) / a0;
+        Self {
+            b: [b1 / 2.0, b1, b1 / 2.0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            ..Default::default()
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// Converts interleaved audio at one rate and channel count to 16 kHz
+/// mono, carrying its state from frame to frame.
+#[derive(Debug, Clone)]
+pub struct Downsampler {
+    sample_rate: u32,
+    channels: u8,
+    /// Input samples per output sample.
+    step: f64,
+    filter: Option<[Biquad; 2]>,
+    /// Last filtered input sample, and how far past it the next output
+    /// falls in input samples.
+    previous: Option<f64>,
+    position: f64,
+}
+
+impl Downsampler {
+    pub fn new(sample_rate: u32, channels: u8) -> Self {
+        let rate = f64::from(sample_rate.max(1));
+        Self {
+            sample_rate,
+            channels: channels.max(1),
+            step: rate / f64::from(SPEECH_SAMPLE_RATE),
+            filter: (sample_rate > SPEECH_SAMPLE_RATE)
+                .then(|| [Biquad::low_pass(rate, CUTOFF_HZ); 2]),
+            previous: None,
+            position: 0.0,
+        }
+    }
+
+    /// Whether this converter was made for audio in this format.
+    pub fn accepts(&self, sample_rate: u32, channels: u8) -> bool {
+        (self.sample_rate, self.channels) == (sample_rate, channels.max(1))
+    }
+
+    pub fn process(&mut self, interleaved: &[f32]) -> Vec<f32> {
+        let channels = usize::from(self.channels);
+        let mut output = Vec::with_capacity(
+            (interleaved.len() as f64 / channels as f64 / self.step) as usize + 1,
+        );
+        for frame in interleaved.chunks_exact(channels) {
+            let mut sample = frame.iter().map(|&s| f64::from(s)).sum::<f64>() / channels as f64;
+            for stage in self.filter.iter_mut().flatten() {
+                sample = stage.process(sample);
+            }
+            let Some(previous) = self.previous.replace(sample) else {
+                output.push(sample as f32);
+                self.position = self.step;
+                continue;
+            };
+            while self.position <= 1.0 {
+                // Landing on an input sample takes it as is.
+                let value = if self.position == 1.0 {
+                    sample
+                } else {
+                    previous + (sample - previous) * self.position
+                };
+                output.push(value as f32);
+                self.position += self.step;
+            }
+            self.position -= 1.0;
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(rate: u32, hz: f64, seconds: f64) -> Vec<f32> {
+        (0..(f64::from(rate) * seconds) as usize)
+            .map(|i| (std::f64::consts::TAU * hz * i as f64 / f64::from(rate)).sin() as f32)
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f64 {
+        (samples.iter().map(|&s| f64::from(s).powi(2)).sum::<f64>() / samples.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn frames_of_any_size_come_out_at_16_khz() {
+        let stereo: Vec<f32> = tone(48_000, 440.0, 1.0)
+            .into_iter()
+            .flat_map(|s| [s, s])
+            .collect();
+        let mut downsampler = Downsampler::new(48_000, 2);
+        let output: Vec<f32> = stereo
+            .chunks(2 * 441)
+            .flat_map(|chunk| downsampler.process(chunk))
+            .collect();
+
+        assert!(
+            (output.len() as i64 - 16_000).abs() <= 1,
+            "{}",
+            output.len()
+        );
+        // Speech-band tones pass.
+        assert!((rms(&output[1000..]) - 0.5f64.sqrt()).abs() < 0.02);
+    }
+
+    #[test]
+    fn content_above_the_new_nyquist_is_attenuated() {
+        let mut downsampler = Downsampler::new(44_100, 1);
+        let output = downsampler.process(&tone(44_100, 12_000.0, 0.5));
+
+        assert!(rms(&output[1000..]) < 0.2);
+    }
+
+    #[test]
+    fn sixteen_khz_mono_passes_through() {
+        let input = tone(16_000, 1000.0, 0.1);
+        let mut downsampler = Downsampler::new(16_000, 1);
+
+        assert_eq!(downsampler.process(&input), input);
+        assert!(downsampler.accepts(16_000, 1));
+        assert!(!downsampler.accepts(48_000, 1));
+    }
+}
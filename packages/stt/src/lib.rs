@@ -0,0 +1,22 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/stt` — speech to text with whisper.cpp. `Stt` brings audio
+//! to 16 kHz mono ([`downsample`]), cuts it into utterances at pauses
+//! ([`segmenter`]) and transcribes them on a worker thread, writing
+//! partial and final [`transcript`] segments on the media clock.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod downsample;
+pub mod segmenter;
+pub mod stt;
+pub mod transcript;
+mod whisper;
+
+pub use stt::SttProcessor;
+
+streamlib_plugin_abi::export_plugin!(crate::SttProcessor::Processor);
@@ -0,0 +1,263 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Cutting 16 kHz speech into windows for whisper.
+//!
+//! Audio collects into an utterance window. While someone is speaking,
+//! the window so far goes out as a partial every `step` samples, to be
+//! transcribed provisionally; it goes out final once speech is followed
+//! by `silence` samples of quiet, reaches `max_window` samples, or the
+//! audio jumps in time. Speech is any 20 ms block louder than
+//! `threshold` RMS. Quiet before speech is dropped, bar a short lead-in,
+//! so windows start near the first word and silence is never
+//! transcribed — whisper invents text for it.
+
+use crate::downsample::SPEECH_SAMPLE_RATE;
+
+/// Loudness is measured over 20 ms blocks.
+const BLOCK: usize = SPEECH_SAMPLE_RATE as usize / 50;
+
+/// Quiet kept ahead of the first word.
+const LEAD_IN: usize = 10 * BLOCK;
+
+/// A jump in timestamps larger than this ends the utterance.
+const GAP_TOLERANCE_NS: i64 = 100_000_000;
+
+const NS_PER_SAMPLE: i64 = 1_000_000_000 / SPEECH_SAMPLE_RATE as i64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmenterSettings {
+    /// Samples between partials; 0 sends finals only.
+    pub step: usize,
+    pub max_window: usize,
+    /// Quiet after speech that ends the utterance.
+    pub silence: usize,
+    pub threshold: f32,
+}
+
+/// Audio to transcribe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    /// Counts utterances from 0; a final and the partials before it share
+    /// one.
+    pub utterance: u32,
+    /// Media-clock time of the first sample.
+    pub start_ns: i64,
+    pub samples: Vec<f32>,
+    pub is_final: bool,
+}
+
+#[derive(Debug)]
+pub struct Segmenter {
+    settings: SegmenterSettings,
+    buffer: Vec<f32>,
+    start_ns: i64,
+    /// Samples measured for loudness so far, whole blocks.
+    measured: usize,
+    /// End of the last loud block, if any block was loud.
+    speech_end: Option<usize>,
+    /// Samples since the last partial.
+    since_partial: usize,
+    utterance: u32,
+}
+
+impl Segmenter {
+    pub fn new(settings: SegmenterSettings) -> Self {
+        Self {
+            settings,
+            buffer: Vec::new(),
+            start_ns: 0,
+            measured: 0,
+            speech_end: None,
+            since_partial: 0,
+            utterance: 0,
+        }
+    }
+
+    pub fn settings(&self) -> SegmenterSettings {
+        self.settings
+    }
+
+    /// Takes effect from the next window.
+    pub fn set_settings(&mut self, settings: SegmenterSettings) {
+        self.settings = settings;
+    }
+
+    /// Samples held, not yet sent in a final window.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Add 16 kHz samples whose first falls at `timestamp_ns`. Returns
+    /// the windows they complete, oldest first.
+    pub fn push(&mut self, samples: &[f32], timestamp_ns: i64) -> Vec<Window> {
+        let mut windows = Vec::new();
+        if !self.buffer.is_empty() && (timestamp_ns - self.end_ns()).abs() > GAP_TOLERANCE_NS {
+            windows.extend(self.finish());
+        }
+        if self.buffer.is_empty() {
+            self.start_ns = timestamp_ns;
+        }
+        self.buffer.extend_from_slice(samples);
+        self.since_partial += samples.len();
+        self.measure();
+
+        let Some(speech_end) = self.speech_end else {
+            // Nothing said yet: keep only the lead-in, in whole blocks.
+            let excess = self.buffer.len().saturating_sub(LEAD_IN) / BLOCK * BLOCK;
+            self.buffer.drain(..excess);
+            self.start_ns += excess as i64 * NS_PER_SAMPLE;
+            self.measured -= excess;
+            self.since_partial = 0;
+            return windows;
+        };
+        let quiet = self.measured - speech_end;
+        if self.buffer.len() >= self.settings.max_window || quiet >= self.settings.silence {
+            windows.extend(self.finish());
+        } else if self.settings.step > 0 && self.since_partial >= self.settings.step {
+            self.since_partial = 0;
+            windows.push(Window {
+                utterance: self.utterance,
+                start_ns: self.start_ns,
+                samples: self.buffer.clone(),
+                is_final: false,
+            });
+        }
+        windows
+    }
+
+    /// End the utterance now, e.g. when the stream stops.
+    pub fn finish(&mut self) -> Option<Window> {
+        let samples = std::mem::take(&mut self.buffer);
+        let spoke = self.speech_end.is_some();
+        self.reset();
+        if !spoke {
+            return None;
+        }
+        let utterance = self.utterance;
+        self.utterance = self.utterance.wrapping_add(1);
+        Some(Window {
+            utterance,
+            start_ns: self.start_ns,
+            samples,
+            is_final: true,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.measured = 0;
+        self.speech_end = None;
+        self.since_partial = 0;
+    }
+
+    fn end_ns(&self) -> i64 {
+        self.start_ns + self.buffer.len() as i64 * NS_PER_SAMPLE
+    }
+
+    /// Measure the blocks completed since the last call.
+    fn measure(&mut self) {
+        let threshold = f64::from(self.settings.threshold);
+        while self.measured + BLOCK <= self.buffer.len() {
+            let block = &self.buffer[self.measured..self.measured + BLOCK];
+            let power = block.iter().map(|&s| f64::from(s).powi(2)).sum::<f64>() / BLOCK as f64;
+            self.measured += BLOCK;
+            if power > threshold * threshold {
+                self.speech_end = Some(self.measured);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: usize = SPEECH_SAMPLE_RATE as usize;
+
+    fn settings() -> SegmenterSettings {
+        SegmenterSettings {
+            step: SECOND,
+            max_window: 5 * SECOND,
+            silence: SECOND / 2,
+            threshold: 0.01,
+        }
+    }
+
+    fn speech(samples: usize) -> Vec<f32> {
+        (0..samples).map(|i| (i as f32 * 0.3).sin() * 0.5).collect()
+    }
+
+    /// Feeds `audio` in 10 ms frames from `start_ns` on.
+    fn feed(segmenter: &mut Segmenter, audio: &[f32], start_ns: i64) -> Vec<Window> {
+        audio
+            .chunks(SECOND / 100)
+            .enumerate()
+            .flat_map(|(i, chunk)| segmenter.push(chunk, start_ns + i as i64 * 10_000_000))
+            .collect()
+    }
+
+    #[test]
+    fn an_utterance_goes_out_as_partials_then_final_after_a_pause() {
+        let mut segmenter = Segmenter::new(settings());
+        let mut audio = vec![0.0; 3 * SECOND];
+        audio.extend(speech(2 * SECOND + SECOND / 2));
+        audio.extend(vec![0.0; SECOND]);
+        let windows = feed(&mut segmenter, &audio, 1_000_000_000);
+
+        let summary: Vec<_> = windows
+            .iter()
+            .map(|w| (w.utterance, w.is_final, w.samples.len()))
+            .collect();
+        // Speech is first measured one 10 ms frame into the block after
+        // the lead-in; partials follow a second and two seconds later, and
+        // the final half a second into the pause.
+        let first_partial = LEAD_IN + BLOCK / 2 + SECOND;
+        assert_eq!(
+            summary,
+            [
+                (0, false, first_partial),
+                (0, false, first_partial + SECOND),
+                (0, true, LEAD_IN + 3 * SECOND),
+            ]
+        );
+        // Timestamps follow the audio, not the dropped silence.
+        assert_eq!(
+            windows[0].start_ns,
+            1_000_000_000 + (3 * SECOND - LEAD_IN) as i64 * NS_PER_SAMPLE
+        );
+        // Silence afterwards sends nothing.
+        assert!(feed(&mut segmenter, &vec![0.0; 2 * SECOND], 7_000_000_000).is_empty());
+    }
+
+    #[test]
+    fn long_speech_is_cut_at_the_window_limit() {
+        let mut segmenter = Segmenter::new(SegmenterSettings {
+            step: 0,
+            ..settings()
+        });
+        let windows = feed(&mut segmenter, &speech(12 * SECOND), 0);
+
+        assert_eq!(windows.len(), 2);
+        assert!(
+            windows
+                .iter()
+                .all(|w| w.is_final && w.samples.len() == 5 * SECOND)
+        );
+        assert_eq!(windows[1].utterance, 1);
+        assert_eq!(windows[1].start_ns, 5 * SECOND as i64 * NS_PER_SAMPLE);
+        assert_eq!(segmenter.pending(), 2 * SECOND);
+        assert_eq!(segmenter.finish().map(|w| w.utterance), Some(2));
+    }
+
+    #[test]
+    fn a_jump_in_timestamps_ends_the_utterance() {
+        let mut segmenter = Segmenter::new(settings());
+        let before = segmenter.push(&speech(SECOND / 4), 0);
+        let after = segmenter.push(&speech(SECOND / 4), 10_000_000_000);
+
+        assert!(before.is_empty());
+        assert_eq!(after.len(), 1);
+        assert!(after[0].is_final && after[0].start_ns == 0);
+        assert_eq!(segmenter.pending(), SECOND / 4);
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Speech to text.
+//!
+//! Audio is brought to 16 kHz mono by a [`Downsampler`], cut into
+//! utterance windows by a [`Segmenter`] and transcribed by a
+//! [`WhisperWorker`]. While an utterance goes on, its text so far goes
+//! out as a partial `TranscriptSegment` — one line over the whole window,
+//! replaced by the next — and once it ends, each of whisper's segments
+//! goes out final with its own timing. Partials are skipped while the
+//! model is still busy, so a slow model falls back to finals only rather
+//! than falling behind.
+//!
+//! Results are written as audio arrives, so they trail the speech by the
+//! transcription time.
+
+use std::path::PathBuf;
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+
+use crate::_generated_::{AudioFrame, SttConfig, TranscriptSegment};
+use crate::downsample::{Downsampler, SPEECH_SAMPLE_RATE};
+use crate::segmenter::{Segmenter, SegmenterSettings, Window};
+use crate::transcript::{self, Transcript};
+use crate::whisper::{DecodeSettings, Transcribed, WhisperWorker};
+
+const DEFAULT_LANGUAGE: &str = "en";
+const DEFAULT_STEP_MS: u32 = 1000;
+const DEFAULT_MAX_SEGMENT_MS: u32 = 10_000;
+const DEFAULT_SILENCE_MS: u32 = 600;
+const DEFAULT_VAD_THRESHOLD: f32 = 0.01;
+
+/// Whisper's context is 30 s of audio.
+const MAX_SEGMENT_MS: u32 = 30_000;
+
+/// Final windows queued past this many mean the model can't keep up.
+const BACKLOG_WARNING: usize = 4;
+
+fn samples(ms: u32) -> usize {
+    (u64::from(ms) * u64::from(SPEECH_SAMPLE_RATE) / 1000) as usize
+}
+
+fn resolve_segmenter(config: &SttConfig) -> Result<SegmenterSettings> {
+    let max_segment_ms = config.max_segment_ms.unwrap_or(DEFAULT_MAX_SEGMENT_MS);
+    if !(1000..=MAX_SEGMENT_MS).contains(&max_segment_ms) {
+        return Err(Error::Configuration(format!(
+            "Stt: max_segment_ms must be 1000 … {MAX_SEGMENT_MS}, got {max_segment_ms}"
+        )));
+    }
+    let silence_ms = config.silence_ms.unwrap_or(DEFAULT_SILENCE_MS);
+    if silence_ms == 0 {
+        return Err(Error::Configuration(
+            "Stt: silence_ms must be positive".into(),
+        ));
+    }
+    let threshold = config.vad_threshold.unwrap_or(DEFAULT_VAD_THRESHOLD);
+    if !(0.0..1.0).contains(&threshold) {
+        return Err(Error::Configuration(format!(
+            "Stt: vad_threshold must be 0 … 1, got {threshold}"
+        )));
+    }
+    Ok(SegmenterSettings {
+        step: samples(config.step_ms.unwrap_or(DEFAULT_STEP_MS)),
+        max_window: samples(max_segment_ms),
+        silence: samples(silence_ms),
+        threshold,
+    })
+}
+
+fn resolve_decode(config: &SttConfig) -> Result<DecodeSettings> {
+    let language = config
+        .language
+        .clone()
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+    if language != "auto" && whisper_rs::get_lang_id(&language).is_none() {
+        return Err(Error::Configuration(format!(
+            "Stt: language must be a language whisper knows or `auto`, got '{language}'"
+        )));
+    }
+    let threads = match config.threads {
+        Some(0) => {
+            return Err(Error::Configuration("Stt: threads must be positive".into()));
+        }
+        Some(threads) => threads,
+        None => std::thread::available_parallelism().map_or(4, |n| n.get().min(8) as u32),
+    };
+    Ok(DecodeSettings {
+        language,
+        translate: config.translate.unwrap_or(false),
+        threads: threads.min(i32::MAX as u32) as i32,
+        initial_prompt: config
+            .initial_prompt
+            .clone()
+            .filter(|prompt| !prompt.is_empty()),
+    })
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/stt/Stt",
+    description = "Transcribes speech with whisper.cpp (on the GPU when built with a GPU backend) into timestamped TranscriptSegments: provisional partials while someone speaks, then final segments once they pause. Ready to feed a Subtitle renderer or CaptionInserter.",
+    execution = reactive,
+    config = crate::_generated_::SttConfig,
+    input("audio_in", "@tatolab/core/AudioFrame", description = "Audio to transcribe, at any sample rate and channel count"),
+    output("segments", "@tatolab/stt/TranscriptSegment", description = "Partial and final transcription segments on the media clock"),
+)]
+pub struct SttProcessor {
+    worker: Option<WhisperWorker>,
+    /// Model and GPU choice the worker was started with.
+    model: Option<(PathBuf, bool)>,
+    decode: Option<DecodeSettings>,
+    downsampler: Option<Downsampler>,
+    segmenter: Option<Segmenter>,
+    partials_skipped: u64,
+    segments_written: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SttProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let segmenter = resolve_segmenter(&self.config)?;
+        self.decode = Some(resolve_decode(&self.config)?);
+        let model = self.model_choice()?;
+        self.worker = Some(WhisperWorker::start(&model.0, model.1)?);
+        self.segmenter = Some(Segmenter::new(segmenter));
+        tracing::info!(
+            "[Stt] Setup (model {}, gpu {}, language {})",
+            model.0.display(),
+            model.1,
+            self.decode
+                .as_ref()
+                .map_or(DEFAULT_LANGUAGE, |decode| decode.language.as_str())
+        );
+        self.model = Some(model);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let pending = self.segmenter.as_ref().map_or(0, Segmenter::pending);
+        // Waits for the window being transcribed, if any.
+        self.worker = None;
+        self.segmenter = None;
+        self.downsampler = None;
+        tracing::info!(
+            "[Stt] Teardown ({} segments, {} partials skipped, {} ms untranscribed)",
+            self.segments_written,
+            self.partials_skipped,
+            pending as u64 * 1000 / u64::from(SPEECH_SAMPLE_RATE)
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let segmenter = resolve_segmenter(&self.config)?;
+        let decode = resolve_decode(&self.config)?;
+        let model = self.model_choice()?;
+        if let Some(current) = &self.model
+            && *current != model
+        {
+            return Err(Error::Configuration(
+                "Stt: model_path and use_gpu are fixed at setup; re-add the processor".into(),
+            ));
+        }
+        if let Some(current) = self.segmenter.as_mut() {
+            current.set_settings(segmenter);
+        }
+        self.decode = Some(decode);
+        tracing::info!("[Stt] Config updated");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        while self.inputs.has_data("audio_in") {
+            let frame: AudioFrame = self.inputs.read("audio_in")?;
+            let downsampler = match self.downsampler.take() {
+                Some(downsampler) if downsampler.accepts(frame.sample_rate, frame.channels) => {
+                    downsampler
+                }
+                _ => Downsampler::new(frame.sample_rate, frame.channels),
+            };
+            let downsampler = self.downsampler.insert(downsampler);
+            let speech = downsampler.process(&frame.samples);
+            let timestamp_ns = frame
+                .timestamp_ns
+                .parse()
+                .unwrap_or_else(|_| MediaClock::now().as_nanos() as i64);
+            let windows = match self.segmenter.as_mut() {
+                Some(segmenter) => segmenter.push(&speech, timestamp_ns),
+                None => Vec::new(),
+            };
+            for window in windows {
+                self.submit(window)?;
+            }
+        }
+
+        let results = match self.worker.as_mut() {
+            Some(worker) => worker.take_results(),
+            None => Vec::new(),
+        };
+        for result in results {
+            self.write(result)?;
+        }
+        Ok(())
+    }
+}
+
+impl SttProcessor::Processor {
+    fn model_choice(&self) -> Result<(PathBuf, bool)> {
+        let path = self
+            .config
+            .model_path
+            .as_deref()
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| {
+                Error::Configuration(
+                    "Stt: model_path must name a whisper.cpp ggml model, e.g. ggml-base.en.bin"
+                        .into(),
+                )
+            })?;
+        Ok((PathBuf::from(path), self.config.use_gpu.unwrap_or(true)))
+    }
+
+    fn submit(&mut self, window: Window) -> Result<()> {
+        let (Some(worker), Some(decode)) = (self.worker.as_mut(), self.decode.as_ref()) else {
+            return Err(Error::Configuration("Stt: worker not started".into()));
+        };
+        if !window.is_final && worker.in_flight() > 0 {
+            self.partials_skipped += 1;
+            return Ok(());
+        }
+        if worker.in_flight() >= BACKLOG_WARNING {
+            tracing::warn!(
+                "[Stt] {} windows waiting for the model; transcripts are falling behind",
+                worker.in_flight()
+            );
+        }
+        worker.submit(window, decode.clone())
+    }
+
+    fn write(&mut self, result: Transcribed) -> Result<()> {
+        let segments = match result.segments {
+            Ok(segments) => segments,
+            Err(e) => {
+                tracing::warn!(
+                    "[Stt] Transcribing utterance {} failed: {}",
+                    result.utterance,
+                    e
+                );
+                return Ok(());
+            }
+        };
+        let transcripts: Vec<Transcript> = if result.is_final {
+            transcript::finals(result.start_ns, &segments)
+        } else {
+            transcript::partial(result.start_ns, result.window_ms, &segments)
+                .into_iter()
+                .collect()
+        };
+        for transcript in transcripts {
+            self.outputs.write(
+                "segments",
+                &TranscriptSegment {
+                    text: transcript.text,
+                    timestamp_ns: transcript.start_ns.to_string(),
+                    duration_ms: transcript.duration_ms,
+                    is_final: result.is_final,
+                    utterance: result.utterance,
+                    language: Some(result.language.clone()),
+                },
+            )?;
+            self.segments_written += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_give_ten_second_windows_with_partials_each_second() {
+        let settings = resolve_segmenter(&SttConfig::default()).unwrap();
+
+        assert_eq!(
+            settings,
+            SegmenterSettings {
+                step: 16_000,
+                max_window: 160_000,
+                silence: 9_600,
+                threshold: DEFAULT_VAD_THRESHOLD,
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_range_settings_are_rejected() {
+        for config in [
+            SttConfig {
+                max_segment_ms: Some(MAX_SEGMENT_MS + 1),
+                ..Default::default()
+            },
+            SttConfig {
+                silence_ms: Some(0),
+                ..Default::default()
+            },
+            SttConfig {
+                vad_threshold: Some(1.5),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                resolve_segmenter(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+        for config in [
+            SttConfig {
+                language: Some("klingon".into()),
+                ..Default::default()
+            },
+            SttConfig {
+                threads: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                resolve_decode(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+        assert!(
+            resolve_decode(&SttConfig {
+                language: Some("auto".into()),
+                ..Default::default()
+            })
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Whisper's segments to timed transcript text.
+//!
+//! Whisper times segments in centiseconds from the start of the audio it
+//! was given and marks sounds it heard but could not transcribe as
+//! `[BLANK_AUDIO]`, `(music)` and the like; those are dropped. A final
+//! window yields each segment on its own, a partial one the whole window
+//! as one provisional line.
+
+/// Text on the media clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub text: String,
+    pub start_ns: i64,
+    pub duration_ms: u32,
+}
+
+/// One whisper segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhisperSegment {
+    pub text: String,
+    /// Centiseconds from the window's start.
+    pub t0: i64,
+    pub t1: i64,
+}
+
+const NS_PER_CS: i64 = 10_000_000;
+
+/// Segment text worth showing, trimmed.
+fn spoken(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let annotation = |open: char, close: char| {
+        text.len() >= 2 && text.starts_with(open) && text[1..].find(close) == Some(text.len() - 2)
+    };
+    (!text.is_empty() && !annotation('[', ']') && !annotation('(', ')') && !annotation('*', '*'))
+        .then_some(text)
+}
+
+/// Each spoken segment of a final window.
+pub fn finals(window_start_ns: i64, segments: &[WhisperSegment]) -> Vec<Transcript> {
+    segments
+        .iter()
+        .filter_map(|segment| {
+            let text = spoken(&segment.text)?;
+            Some(Transcript {
+                text: text.to_string(),
+                start_ns: window_start_ns + segment.t0 * NS_PER_CS,
+                duration_ms: ((segment.t1 - segment.t0).max(0) * 10) as u32,
+            })
+        })
+        .collect()
+}
+
+/// A partial window's segments as one line spanning the window.
+pub fn partial(
+    window_start_ns: i64,
+    window_ms: u32,
+    segments: &[WhisperSegment],
+) -> Option<Transcript> {
+    let text = segments
+        .iter()
+        .filter_map(|segment| spoken(&segment.text))
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(Transcript {
+        text,
+        start_ns: window_start_ns,
+        duration_ms: window_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, t0: i64, t1: i64) -> WhisperSegment {
+        WhisperSegment {
+            text: text.into(),
+            t0,
+            t1,
+        }
+    }
+
+    #[test]
+    fn finals_are_timed_from_the_window_and_skip_annotations() {
+        let segments = [
+            segment(" Hello there.", 0, 150),
+            segment(" [BLANK_AUDIO]", 150, 200),
+            segment(" (upbeat music)", 200, 300),
+            segment(" (1) is the answer (mostly)", 300, 420),
+        ];
+        let transcripts = finals(5_000_000_000, &segments);
+
+        assert_eq!(
+            transcripts,
+            [
+                Transcript {
+                    text: "Hello there.".into(),
+                    start_ns: 5_000_000_000,
+                    duration_ms: 1500,
+                },
+                Transcript {
+                    text: "(1) is the answer (mostly)".into(),
+                    start_ns: 8_000_000_000,
+                    duration_ms: 1200,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_partial_joins_the_window_into_one_line() {
+        let segments = [segment(" So far", 0, 80), segment(" so good", 80, 150)];
+
+        assert_eq!(
+            partial(7, 2000, &segments).map(|t| (t.text, t.start_ns, t.duration_ms)),
+            Some(("So far so good".into(), 7, 2000))
+        );
+        assert_eq!(partial(7, 2000, &[segment(" [Music]", 0, 200)]), None);
+        assert_eq!(partial(7, 2000, &[segment("*", 0, 200)]).unwrap().text, "*");
+    }
+}
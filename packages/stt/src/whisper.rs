@@ -0,0 +1,217 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! whisper.cpp, through `whisper-rs`, on a worker thread.
+//!
+//! Transcribing a window takes from tens of milliseconds on a GPU to
+//! seconds on a small CPU, far longer than an audio frame, so the model
+//! runs on its own thread: windows go in over one channel and segments
+//! come back over another. Each final window's text is handed to the
+//! next as its prompt, which keeps spelling and punctuation consistent
+//! across cuts.
+
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use crate::downsample::SPEECH_SAMPLE_RATE;
+use crate::segmenter::Window;
+use crate::transcript::WhisperSegment;
+
+/// Whisper pads shorter input and warns; pad it here instead.
+const MIN_SAMPLES: usize = SPEECH_SAMPLE_RATE as usize + SPEECH_SAMPLE_RATE as usize / 10;
+
+/// Prompt carried from one final window to the next, in characters.
+const MAX_CARRIED_PROMPT: usize = 200;
+
+/// Decoding settings, sent with every window so config updates apply
+/// from the next one.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DecodeSettings {
+    /// ISO 639-1 code, or `auto` to detect.
+    pub language: String,
+    pub translate: bool,
+    pub threads: i32,
+    pub initial_prompt: Option<String>,
+}
+
+struct Job {
+    window: Window,
+    settings: DecodeSettings,
+}
+
+/// A transcribed window.
+pub(crate) struct Transcribed {
+    pub utterance: u32,
+    pub start_ns: i64,
+    pub window_ms: u32,
+    pub is_final: bool,
+    /// Detected language when decoding with `auto`, else the one set.
+    pub language: String,
+    pub segments: std::result::Result<Vec<WhisperSegment>, String>,
+}
+
+pub(crate) struct WhisperWorker {
+    jobs: Option<mpsc::Sender<Job>>,
+    results: mpsc::Receiver<Transcribed>,
+    thread: Option<thread::JoinHandle<()>>,
+    /// Windows sent and not yet returned.
+    in_flight: usize,
+}
+
+impl WhisperWorker {
+    /// Load the ggml model at `model_path` and start the worker. With
+    /// `use_gpu`, whisper.cpp offloads to the GPU backend the package was
+    /// built with, if any, and otherwise runs on the CPU.
+    pub(crate) fn start(model_path: &Path, use_gpu: bool) -> Result<Self> {
+        whisper_rs::install_logging_hooks();
+        let path = model_path.to_str().ok_or_else(|| {
+            Error::Configuration(format!(
+                "Stt: model path {} is not valid UTF-8",
+                model_path.display()
+            ))
+        })?;
+        let mut params = WhisperContextParameters::default();
+        params.use_gpu(use_gpu);
+        let context = WhisperContext::new_with_params(path, params)
+            .map_err(|e| Error::Configuration(format!("Stt: cannot load model {}: {}", path, e)))?;
+        let mut state = context
+            .create_state()
+            .map_err(|e| Error::Runtime(format!("Stt: cannot create whisper state: {}", e)))?;
+
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, results) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("stt-whisper".into())
+            .spawn(move || {
+                let mut carried = String::new();
+                // Ends when the processor drops its sender.
+                for job in job_receiver {
+                    let transcribed = transcribe(&mut state, job, &mut carried);
+                    if result_sender.send(transcribed).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| Error::Runtime(format!("Stt: cannot start worker thread: {}", e)))?;
+        Ok(Self {
+            jobs: Some(jobs),
+            results,
+            thread: Some(thread),
+            in_flight: 0,
+        })
+    }
+
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    pub(crate) fn submit(&mut self, window: Window, settings: DecodeSettings) -> Result<()> {
+        self.jobs
+            .as_ref()
+            .and_then(|jobs| jobs.send(Job { window, settings }).ok())
+            .ok_or_else(|| Error::Runtime("Stt: whisper worker has stopped".into()))?;
+        self.in_flight += 1;
+        Ok(())
+    }
+
+    /// Windows transcribed since the last call, in submission order.
+    pub(crate) fn take_results(&mut self) -> Vec<Transcribed> {
+        let results: Vec<_> = self.results.try_iter().collect();
+        self.in_flight -= results.len();
+        results
+    }
+}
+
+impl Drop for WhisperWorker {
+    fn drop(&mut self) {
+        // Closing the channel ends the worker once its current window is
+        // done; windows still queued are dropped with it.
+        self.jobs = None;
+        while self.results.try_recv().is_ok() {}
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn transcribe(state: &mut whisper_rs::WhisperState, job: Job, carried: &mut String) -> Transcribed {
+    let Job { window, settings } = job;
+    let window_ms = (window.samples.len() as u64 * 1000 / u64::from(SPEECH_SAMPLE_RATE)) as u32;
+    let mut samples = window.samples;
+    if samples.len() < MIN_SAMPLES {
+        samples.resize(MIN_SAMPLES, 0.0);
+    }
+
+    let mut prompt = settings.initial_prompt.clone().unwrap_or_default();
+    if !carried.is_empty() {
+        if !prompt.is_empty() {
+            prompt.push(' ');
+        }
+        prompt.push_str(carried);
+    }
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_n_threads(settings.threads);
+    params.set_language(Some(&settings.language));
+    params.set_translate(settings.translate);
+    params.set_no_context(true);
+    params.set_suppress_blank(true);
+    // A partial is shown as one line; decoding it as one is faster too.
+    params.set_single_segment(!window.is_final);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    if !prompt.is_empty() {
+        params.set_initial_prompt(&prompt);
+    }
+
+    let segments = state
+        .full(params, &samples)
+        .and_then(|_| {
+            (0..state.full_n_segments()?)
+                .map(|i| {
+                    Ok(WhisperSegment {
+                        text: state.full_get_segment_text_lossy(i)?,
+                        t0: state.full_get_segment_t0(i)?,
+                        t1: state.full_get_segment_t1(i)?,
+                    })
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string());
+    let language = if settings.language == "auto" {
+        state
+            .full_lang_id_from_state()
+            .ok()
+            .and_then(whisper_rs::get_lang_str)
+            .unwrap_or("auto")
+            .to_string()
+    } else {
+        settings.language
+    };
+
+    if window.is_final
+        && let Ok(segments) = &segments
+    {
+        let text: String = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+        let text = text.trim();
+        // Keep the end of it, cut at a character boundary.
+        let skip = text.chars().count().saturating_sub(MAX_CARRIED_PROMPT);
+        *carried = text.chars().skip(skip).collect();
+    }
+    Transcribed {
+        utterance: window.utterance,
+        start_ns: window.start_ns,
+        window_ms,
+        is_final: window.is_final,
+        language,
+        segments,
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hardware-free AudioFrame sink for the end-to-end suite.
+//!
+//! ReactiveProcessor that appends a marker line per lifecycle hook and
+//! per received frame to `config.output_path`:
+//!
+//! ```text
+//! SETUP
+//! FRAME:1 peak=0.500
+//! FRAME:2 peak=0.500
+//! TEARDOWN
+//! ```
+//!
+//! The lines are written by cdylib code, so a test reading them knows
+//! frames really crossed the link — and, through `peak`, what the
+//! upstream source was configured with when it produced them.
+//! `config.max_frames` caps the FRAME lines; later frames are still
+//! drained, just not recorded.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::{Error, Result};
+use streamlib::sdk::processors::ReactiveProcessor;
+
+use crate::_generated_::AudioFrame;
+
+#[streamlib::sdk::processor(
+    "@tatolab/test-fixtures/AudioProbeTestProcessor",
+    description = "Hardware-free AudioFrame sink for the end-to-end suite. Appends SETUP, FRAME:n peak=p and TEARDOWN marker lines to a file so the test can assert frames crossed a link and what they carried.",
+    execution = reactive,
+    config = crate::_generated_::AudioProbeTestProcessorConfig,
+    input("audio", "@tatolab/core/AudioFrame", delivery_profile = "every_sample", description = "AudioFrames to record"),
+)]
+pub struct AudioProbe {
+    frame_count: AtomicU32,
+}
+
+impl AudioProbe::Processor {
+    fn append_line(&self, line: &str) -> Result<()> {
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.output_path)
+            .map_err(|e| {
+                Error::Runtime(format!("AudioProbe: open {}: {e}", self.config.output_path))
+            })?;
+        writeln!(f, "{line}").map_err(|e| {
+            Error::Runtime(format!(
+                "AudioProbe: write {}: {e}",
+                self.config.output_path
+            ))
+        })?;
+        Ok(())
+    }
+}
+
+impl ReactiveProcessor for AudioProbe::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.append_line("SETUP")
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("audio") {
+            return Ok(());
+        }
+        let frame: AudioFrame = self.inputs.read("audio")?;
+        let n = self.frame_count.fetch_add(1, Ordering::SeqCst) + 1;
+        if n > self.config.max_frames {
+            return Ok(());
+        }
+        let peak = frame
+            .samples
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        self.append_line(&format!("FRAME:{n} peak={peak:.3}"))
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.append_line("TEARDOWN")
+    }
+}
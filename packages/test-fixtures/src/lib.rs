@@ -3,13 +3,15 @@
 
 //! Attribute-macro test fixtures (TestConfiguredProcessor, TcpBindTestProcessor)
 //! for streamlib SDK macro contract tests and the #885 dlopen-owns-tokio
-//! integration test.
+//! integration test, plus the CPU-only tone source / audio probe pair the
+//! hardware-free end-to-end suite builds its graphs from.
 
 #[allow(non_snake_case, unused_imports, clippy::all)]
 pub mod _generated_ {
     include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
 }
 
+pub mod audio_probe_test_processor;
 pub mod compute_kernel_test_processor;
 pub mod concurrent_escalate_test_processor;
 pub mod escalate_smoke_test_processor;
@@ -20,7 +22,9 @@ pub mod panicking_lifecycle_processor;
 pub mod ray_tracing_kernel_smoke_test_processor;
 pub mod tcp_bind_test_processor;
 pub mod test_configured_processor;
+pub mod tone_source_test_processor;
 
+pub use audio_probe_test_processor::AudioProbe;
 pub use compute_kernel_test_processor::ComputeKernelTest;
 pub use concurrent_escalate_test_processor::ConcurrentEscalateTest;
 pub use escalate_smoke_test_processor::EscalateSmokeTest;
@@ -31,6 +35,7 @@ pub use panicking_lifecycle_processor::{PanickingContinuousLifecycle, PanickingM
 pub use ray_tracing_kernel_smoke_test_processor::RayTracingKernelSmokeTest;
 pub use tcp_bind_test_processor::TcpBindTest;
 pub use test_configured_processor::ConfiguredProcessor;
+pub use tone_source_test_processor::ToneSource;
 
 streamlib_plugin_abi::export_plugin!(
     crate::ConfiguredProcessor::Processor,
@@ -44,4 +49,6 @@ streamlib_plugin_abi::export_plugin!(
     crate::PanickingManualLifecycle::Processor,
     crate::PanickingContinuousLifecycle::Processor,
     crate::ConcurrentEscalateTest::Processor,
+    crate::ToneSource::Processor,
+    crate::AudioProbe::Processor,
 );
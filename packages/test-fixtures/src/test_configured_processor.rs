@@ -6,7 +6,7 @@
 //! code against a `streamlib.yaml` `package:` block.
 
 use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
-use streamlib::sdk::error::Result;
+use streamlib::sdk::error::{Error, Result};
 use streamlib::sdk::processors::ContinuousProcessor;
 
 #[streamlib::sdk::processor(
@@ -26,6 +26,13 @@ impl ContinuousProcessor for ConfiguredProcessor::Processor {
         Ok(())
     }
 
+    fn on_config_update(&mut self) -> Result<()> {
+        if self.config.threshold < 0.0 {
+            return Err(Error::Config("threshold must be non-negative".into()));
+        }
+        Ok(())
+    }
+
     fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
         let _threshold = self.config.threshold;
         Ok(())
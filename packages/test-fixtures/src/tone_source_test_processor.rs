@@ -0,0 +1,102 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hardware-free tone source for the end-to-end suite.
+//!
+//! ContinuousProcessor that synthesizes a mono sine on the CPU and
+//! publishes it on `audio` as 10 ms `AudioFrame`s at 48 kHz. Frames are
+//! paced against the wall clock — one per 10 ms, without bursting to
+//! catch up after a pause — and stamped from the sample count rather than
+//! a device clock, so the stream is identical on a GPU rig and a bare CI
+//! runner.
+//!
+//! `config.amplitude` is read on every frame, so a live config update
+//! shows up in the very next frame's samples; `AudioProbeTestProcessor`
+//! reports each frame's peak back to the test.
+
+use std::f64::consts::TAU;
+use std::time::{Duration, Instant};
+
+use streamlib::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib::sdk::error::Result;
+use streamlib::sdk::processors::ContinuousProcessor;
+
+use crate::_generated_::AudioFrame;
+use crate::_generated_::tatolab__core::audio_frame::ChannelLayout;
+
+pub const TONE_SAMPLE_RATE: u32 = 48_000;
+pub const TONE_FRAME_SAMPLES: usize = 480;
+const FRAME_INTERVAL: Duration = Duration::from_millis(10);
+
+#[streamlib::sdk::processor(
+    "@tatolab/test-fixtures/ToneSourceTestProcessor",
+    description = "Hardware-free tone source for the end-to-end suite. Synthesizes a mono sine on the CPU and publishes 10 ms AudioFrames paced against the wall clock — no capture device, GPU work, or audio clock involved.",
+    execution = continuous,
+    config = crate::_generated_::ToneSourceTestProcessorConfig,
+    output("audio", "@tatolab/core/AudioFrame", description = "Mono sine AudioFrames"),
+)]
+pub struct ToneSource {
+    phase: f64,
+    frames_sent: u64,
+    next_frame_at: Option<Instant>,
+}
+
+impl ToneSource::Processor {
+    fn next_frame(&mut self) -> AudioFrame {
+        let phase_inc = TAU * self.config.frequency_hz as f64 / TONE_SAMPLE_RATE as f64;
+        let amplitude = self.config.amplitude as f64;
+        let samples = (0..TONE_FRAME_SAMPLES)
+            .map(|_| {
+                let sample = (self.phase.sin() * amplitude) as f32;
+                self.phase = (self.phase + phase_inc) % TAU;
+                sample
+            })
+            .collect();
+        let index = self.frames_sent;
+        self.frames_sent += 1;
+        let timestamp_ns =
+            index * TONE_FRAME_SAMPLES as u64 * 1_000_000_000 / TONE_SAMPLE_RATE as u64;
+        AudioFrame {
+            samples,
+            channels: 1,
+            sample_rate: TONE_SAMPLE_RATE,
+            timestamp_ns: timestamp_ns.to_string(),
+            frame_index: index.to_string(),
+            channel_layout: Some(ChannelLayout::Mono),
+        }
+    }
+}
+
+impl ContinuousProcessor for ToneSource::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            "ToneSource: {} Hz at amplitude {}",
+            self.config.frequency_hz,
+            self.config.amplitude
+        );
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        // Unlinked (before the first connect, or mid-rewire): nothing to
+        // pace against yet.
+        if !self.outputs.has_port("audio") {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let due = *self.next_frame_at.get_or_insert(now);
+        if now < due {
+            return Ok(());
+        }
+        // Re-anchor rather than burst when the loop fell behind (a pause,
+        // a slow runner).
+        self.next_frame_at = Some((due + FRAME_INTERVAL).max(now));
+        let frame = self.next_frame();
+        self.outputs.write("audio", &frame)
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!("ToneSource: teardown after {} frames", self.frames_sent);
+        Ok(())
+    }
+}
@@ -3,7 +3,8 @@
 
 //! Attribute-macro test: verifies that `#[streamlib::sdk::processor(...)]`
 //! against a `streamlib.yaml`-declared config schema instantiates and
-//! round-trips through `from_config` / `update_config`.
+//! round-trips through `from_config` / `update_config`, with `update_config`
+//! running the processor's `on_config_update` hook.
 
 use streamlib::sdk::processors::GeneratedProcessor;
 use streamlib_test_fixtures::_generated_::TestConfiguredProcessorConfig;
@@ -26,3 +27,16 @@ fn test_config_update() {
 
     assert_eq!(processor.config.threshold, 0.8);
 }
+
+#[test]
+fn test_config_update_runs_on_config_update_hook() {
+    let config = TestConfiguredProcessorConfig { threshold: 0.5 };
+    let mut processor = ConfiguredProcessor::Processor::from_config(config).unwrap();
+
+    let rejected = processor.update_config(TestConfiguredProcessorConfig { threshold: -1.0 });
+
+    assert!(
+        rejected.is_err(),
+        "the hook's error is returned to the config-update caller"
+    );
+}
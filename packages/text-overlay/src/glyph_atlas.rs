@@ -0,0 +1,268 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Glyph atlas — glyphs rasterized from a font fallback chain and packed
+//! into one R8 coverage image the overlay kernels read.
+//!
+//! Cdylib-side counterpart of the engine's shared `GlyphAtlas`: the host
+//! resolves the font name to files through `ctx.fonts()`, and the overlay
+//! rasterizes from them itself.
+
+use std::collections::HashMap;
+
+use ab_glyph::{Font, FontVec, GlyphId, PxScale, ScaleFont, point};
+use streamlib_plugin_sdk::sdk::context::FontFace;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::text_layout::{AtlasGlyph, GlyphSource, LineMetrics};
+
+/// Atlas width in texels; rows are this long in the uploaded buffer.
+pub const ATLAS_WIDTH: u32 = 1024;
+const INITIAL_HEIGHT: u32 = 256;
+/// Height the atlas grows to before it is cleared.
+pub const MAX_ATLAS_HEIGHT: u32 = 2048;
+/// Empty texels around each glyph so neighbours never touch.
+const PADDING: u32 = 1;
+
+/// Rasterized glyphs for one fallback chain at one pixel size.
+///
+/// Glyphs are rasterized on first use and packed into shelves. The atlas
+/// grows in height up to [`MAX_ATLAS_HEIGHT`]; past that it is cleared
+/// and [`Self::generation`] bumps, invalidating every placement handed
+/// out before.
+pub struct GlyphAtlas {
+    faces: Vec<FontVec>,
+    scale: PxScale,
+    height: u32,
+    pixels: Vec<u8>,
+    glyphs: HashMap<char, Option<AtlasGlyph>>,
+    packer: ShelfPacker,
+    generation: u64,
+    /// Pixels changed since the last [`Self::take_dirty`].
+    dirty: bool,
+}
+
+impl GlyphAtlas {
+    /// Load every face of `chain` and start an empty atlas at `px_size`.
+    /// Faces that fail to load are skipped; an error is returned only
+    /// when none do.
+    pub fn new(chain: &[FontFace], px_size: f32) -> Result<Self> {
+        let faces: Vec<FontVec> = chain
+            .iter()
+            .filter_map(|face| {
+                let data = std::fs::read(&face.path)
+                    .map_err(|e| {
+                        tracing::warn!("[TextOverlay] Cannot read {}: {}", face.path.display(), e)
+                    })
+                    .ok()?;
+                FontVec::try_from_vec_and_index(data, face.face_index)
+                    .map_err(|e| {
+                        tracing::warn!("[TextOverlay] Cannot parse {}: {}", face.path.display(), e)
+                    })
+                    .ok()
+            })
+            .collect();
+        if faces.is_empty() {
+            return Err(Error::FontNotFound(format!(
+                "none of {} fallback face(s) could be loaded",
+                chain.len()
+            )));
+        }
+        Ok(Self {
+            faces,
+            scale: PxScale::from(px_size),
+            height: INITIAL_HEIGHT,
+            pixels: vec![0; (ATLAS_WIDTH * INITIAL_HEIGHT) as usize],
+            glyphs: HashMap::new(),
+            packer: ShelfPacker::new(ATLAS_WIDTH, INITIAL_HEIGHT),
+            generation: 0,
+            dirty: true,
+        })
+    }
+
+    /// R8 coverage, row-major, `ATLAS_WIDTH * height()` bytes.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Bumped whenever existing glyph placements are invalidated.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Whether the pixels changed since the last call.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    fn rasterize(&mut self, c: char) -> Option<AtlasGlyph> {
+        let (face, id) = self
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(index, font)| (index, font.glyph_id(c)))
+            .find(|(_, id)| *id != GlyphId(0))?;
+        let font = self.faces[face].as_scaled(self.scale);
+        let advance = font.h_advance(id);
+        let Some(outline) =
+            font.outline_glyph(id.with_scale_and_position(self.scale, point(0.0, 0.0)))
+        else {
+            // Whitespace — advances the pen, occupies no texels.
+            return Some(AtlasGlyph {
+                advance,
+                face,
+                ..AtlasGlyph::default()
+            });
+        };
+        let bounds = outline.px_bounds();
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        let (x, y) = self.allocate(width, height)?;
+        let pixels = &mut self.pixels;
+        outline.draw(|gx, gy, coverage| {
+            let index = ((y + gy) * ATLAS_WIDTH + x + gx) as usize;
+            pixels[index] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+        self.dirty = true;
+        Some(AtlasGlyph {
+            x,
+            y,
+            width,
+            height,
+            bearing_x: bounds.min.x,
+            bearing_y: -bounds.min.y,
+            advance,
+            face,
+        })
+    }
+
+    /// Find room for a `width × height` glyph, growing or — once at
+    /// [`MAX_ATLAS_HEIGHT`] — clearing the atlas.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        loop {
+            if let Some(slot) = self.packer.pack(width + PADDING, height + PADDING) {
+                return Some(slot);
+            }
+            if self.height < MAX_ATLAS_HEIGHT {
+                self.height = (self.height * 2).min(MAX_ATLAS_HEIGHT);
+                self.pixels.resize((ATLAS_WIDTH * self.height) as usize, 0);
+                self.packer.height = self.height;
+            } else if self.glyphs.is_empty() {
+                tracing::warn!(
+                    "[TextOverlay] {}x{} glyph does not fit a {}x{} atlas",
+                    width,
+                    height,
+                    ATLAS_WIDTH,
+                    self.height
+                );
+                return None;
+            } else {
+                self.pixels.fill(0);
+                self.glyphs.clear();
+                self.packer = ShelfPacker::new(ATLAS_WIDTH, self.height);
+                self.generation += 1;
+            }
+        }
+    }
+}
+
+impl GlyphSource for GlyphAtlas {
+    fn glyph(&mut self, c: char) -> Option<AtlasGlyph> {
+        if let Some(cached) = self.glyphs.get(&c) {
+            return *cached;
+        }
+        let glyph = self.rasterize(c);
+        self.glyphs.insert(c, glyph);
+        glyph
+    }
+
+    fn kern(&self, left: (char, &AtlasGlyph), right: (char, &AtlasGlyph)) -> f32 {
+        if left.1.face != right.1.face {
+            return 0.0;
+        }
+        let font = self.faces[left.1.face].as_scaled(self.scale);
+        font.kern(font.glyph_id(left.0), font.glyph_id(right.0))
+    }
+
+    fn line_metrics(&self) -> LineMetrics {
+        let font = self.faces[0].as_scaled(self.scale);
+        LineMetrics {
+            ascent: font.ascent(),
+            descent: font.descent(),
+            line_gap: font.line_gap(),
+        }
+    }
+}
+
+/// Rows ("shelves") filled left to right; a new shelf opens below the
+/// tallest item of the last one.
+struct ShelfPacker {
+    width: u32,
+    height: u32,
+    shelf_y: u32,
+    shelf_height: u32,
+    cursor_x: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelf_y: 0,
+            shelf_height: 0,
+            cursor_x: 0,
+        }
+    }
+
+    fn pack(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width > self.width {
+            return None;
+        }
+        if self.cursor_x + width > self.width {
+            self.shelf_y += self.shelf_height;
+            self.shelf_height = 0;
+            self.cursor_x = 0;
+        }
+        if self.shelf_y + height > self.height {
+            return None;
+        }
+        let slot = (self.cursor_x, self.shelf_y);
+        self.cursor_x += width;
+        self.shelf_height = self.shelf_height.max(height);
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shelf_packer_opens_a_new_shelf_under_the_tallest_item() {
+        let mut packer = ShelfPacker::new(10, 10);
+
+        assert_eq!(packer.pack(6, 3), Some((0, 0)));
+        assert_eq!(packer.pack(4, 5), Some((6, 0)));
+        assert_eq!(packer.pack(1, 1), Some((0, 5)));
+        assert_eq!(packer.pack(11, 1), None);
+        assert_eq!(packer.pack(1, 6), None);
+    }
+
+    #[test]
+    fn a_chain_with_no_loadable_face_is_font_not_found() {
+        let chain = [FontFace {
+            path: "/nonexistent/Overlay.ttf".into(),
+            face_index: 0,
+        }];
+
+        assert!(matches!(
+            GlyphAtlas::new(&chain, 32.0),
+            Err(Error::FontNotFound(_))
+        ));
+    }
+}
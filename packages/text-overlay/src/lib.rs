@@ -0,0 +1,39 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/text-overlay` — GPU text overlays. `TextOverlay` draws a
+//! string in any font the runtime's font registry resolves, laid out by
+//! [`text_layout`] against a [`glyph_atlas::GlyphAtlas`] and rasterized
+//! by compute kernels; the string can be replaced while the graph runs.
+//! `Subtitle` draws the same way from [`subtitles`] — SRT/WebVTT files
+//! and live cues timed against the media clock.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod glyph_atlas;
+pub mod subtitles;
+pub mod text_layout;
+
+// Glyph rasterization and compositing run on `sdk::rhi`, which is
+// Linux-only; layout, the glyph atlas packer and subtitle parsing stay
+// portable.
+#[cfg(target_os = "linux")]
+pub mod subtitle;
+#[cfg(target_os = "linux")]
+pub mod text_overlay;
+#[cfg(target_os = "linux")]
+mod text_renderer;
+
+#[cfg(target_os = "linux")]
+pub use subtitle::SubtitleProcessor;
+#[cfg(target_os = "linux")]
+pub use text_overlay::TextOverlayProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(
+    crate::TextOverlayProcessor::Processor,
+    crate::SubtitleProcessor::Processor,
+);
@@ -0,0 +1,288 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Subtitle renderer (Linux) — draws SRT/WebVTT cues and live cues onto
+//! video frames on the GPU.
+//!
+//! Cues are timed against each frame's media-clock timestamp. A file's
+//! 00:00:00 falls on `origin_ns`, or on the first frame when that is
+//! unset, shifted by `offset_ms`; cues on the `cues` input carry
+//! media-clock times of their own. Every cue active at a frame is drawn,
+//! file cues above live ones, through the shared [`TextRenderer`].
+
+use std::path::Path;
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+
+use crate::_generated_::tatolab__text_overlay::subtitle_config::{
+    Align as ConfigAlign, VerticalAlign as ConfigVerticalAlign,
+};
+use crate::_generated_::{SubtitleConfig, SubtitleCue, VideoFrame};
+use crate::subtitles::{LiveCues, Subtitles};
+use crate::text_layout::{Align, VerticalAlign};
+use crate::text_renderer::{DEFAULT_FONT, Style, StyleOptions, TextRenderer};
+
+const DEFAULT_SIZE_PX: u32 = 40;
+const DEFAULT_Y: f32 = 0.92;
+const DEFAULT_OUTLINE_PX: u32 = 2;
+const DEFAULT_LIVE_HOLD_MS: u32 = 5000;
+
+/// Subtitle defaults: bottom-center, outlined so cues read on any
+/// picture.
+fn resolve_style(config: &SubtitleConfig) -> Result<Style> {
+    Style::resolve(
+        "Subtitle",
+        &StyleOptions {
+            size_px: Some(config.size_px.unwrap_or(DEFAULT_SIZE_PX)),
+            color: config.color.as_deref(),
+            x: config.x,
+            y: Some(config.y.unwrap_or(DEFAULT_Y)),
+            align: config.align.as_ref().map(|align| match align {
+                ConfigAlign::Left => Align::Left,
+                ConfigAlign::Center => Align::Center,
+                ConfigAlign::Right => Align::Right,
+            }),
+            vertical_align: Some(match config.vertical_align {
+                Some(ConfigVerticalAlign::Top) => VerticalAlign::Top,
+                Some(ConfigVerticalAlign::Middle) => VerticalAlign::Middle,
+                Some(ConfigVerticalAlign::Bottom) | None => VerticalAlign::Bottom,
+            }),
+            line_spacing: config.line_spacing,
+            outline_px: Some(config.outline_px.unwrap_or(DEFAULT_OUTLINE_PX)),
+            outline_color: config.outline_color.as_deref(),
+            shadow_offset_x: config.shadow_offset_x,
+            shadow_offset_y: config.shadow_offset_y,
+            shadow_color: config.shadow_color.as_deref(),
+        },
+    )
+}
+
+/// Timing settings resolved once per setup or config update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Timing {
+    origin_ns: Option<i64>,
+    offset_ns: i64,
+    live_hold_ns: i64,
+}
+
+fn resolve_timing(config: &SubtitleConfig) -> Result<Timing> {
+    let origin_ns = match config.origin_ns.as_deref() {
+        None => None,
+        Some(text) => Some(text.parse().map_err(|_| {
+            Error::Configuration(format!(
+                "Subtitle: origin_ns must be an int64 nanosecond count, got '{text}'"
+            ))
+        })?),
+    };
+    let live_hold_ms = config.live_hold_ms.unwrap_or(DEFAULT_LIVE_HOLD_MS);
+    if live_hold_ms == 0 {
+        return Err(Error::Configuration(
+            "Subtitle: live_hold_ms must be positive".into(),
+        ));
+    }
+    Ok(Timing {
+        origin_ns,
+        offset_ns: i64::from(config.offset_ms.unwrap_or(0)) * 1_000_000,
+        live_hold_ns: i64::from(live_hold_ms) * 1_000_000,
+    })
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/text-overlay/Subtitle",
+    description = "Renders subtitles onto video frames on the GPU: cues from an SRT or WebVTT file and SubtitleCue frames arriving on the cues input, timed against the frames' media-clock timestamps and drawn with the TextOverlay text path's font, size, color, position, outline and drop shadow.",
+    execution = reactive,
+    config = crate::_generated_::SubtitleConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to draw on; each produces an output frame"),
+    input("cues", "@tatolab/text-overlay/SubtitleCue", optional = true, description = "Live cues, shown at their timestamps"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Frames with the active cues drawn on (RGBA8)"),
+)]
+pub struct SubtitleProcessor {
+    renderer: Option<TextRenderer>,
+    timing: Option<Timing>,
+    /// The file as loaded, and the path it came from.
+    subtitles: Option<(Subtitles, String)>,
+    live: LiveCues,
+    /// First frame's timestamp, the file's origin unless one is set.
+    first_frame_ns: Option<i64>,
+    /// Latest frame's timestamp, when untimed live cues start.
+    last_frame_ns: Option<i64>,
+    cues_received: u64,
+    frames_drawn: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for SubtitleProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let style = resolve_style(&self.config)?;
+        self.timing = Some(resolve_timing(&self.config)?);
+        self.load_file()?;
+        let font = self.config.font.as_deref().unwrap_or(DEFAULT_FONT);
+        let renderer = TextRenderer::new(ctx, "Subtitle", font, style, String::new())?;
+        tracing::info!(
+            "[Subtitle] Setup (font '{}', {} px, {} file cue(s))",
+            renderer.font(),
+            renderer.style().size_px,
+            self.subtitles
+                .as_ref()
+                .map_or(0, |(subtitles, _)| subtitles.cues.len())
+        );
+        self.renderer = Some(renderer);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.renderer = None;
+        self.live.clear();
+        tracing::info!(
+            "[Subtitle] Teardown ({} frames drawn, {} live cues)",
+            self.frames_drawn,
+            self.cues_received
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let style = resolve_style(&self.config)?;
+        let timing = resolve_timing(&self.config)?;
+        if let Some(renderer) = &self.renderer {
+            let font = self.config.font.as_deref().unwrap_or(DEFAULT_FONT);
+            if font != renderer.font() {
+                return Err(Error::Configuration(format!(
+                    "Subtitle: font is fixed at setup ('{}'); re-add the processor to use '{}'",
+                    renderer.font(),
+                    font
+                )));
+            }
+        }
+        self.load_file()?;
+        self.timing = Some(timing);
+        if let Some(renderer) = self.renderer.as_mut() {
+            renderer.set_style(style)?;
+        }
+        tracing::info!("[Subtitle] Config updated");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let (Some(renderer), Some(timing)) = (self.renderer.as_mut(), self.timing) else {
+            return Err(Error::Configuration(
+                "Subtitle: renderer not initialized".into(),
+            ));
+        };
+        while self.inputs.has_data("cues") {
+            let cue: SubtitleCue = self.inputs.read("cues")?;
+            let start_ns = cue
+                .timestamp_ns
+                .as_deref()
+                .and_then(|text| text.parse().ok())
+                .or(self.last_frame_ns)
+                .unwrap_or_else(|| MediaClock::now().as_nanos() as i64);
+            let duration_ns = cue.duration_ms.map(|ms| i64::from(ms) * 1_000_000);
+            self.live
+                .push(start_ns, duration_ns, &cue.text, timing.live_hold_ns);
+            self.cues_received += 1;
+        }
+        while self.inputs.has_data("video_in") {
+            let frame: VideoFrame = self.inputs.read("video_in")?;
+            let now_ns = frame
+                .timestamp_ns
+                .parse()
+                .unwrap_or_else(|_| MediaClock::now().as_nanos() as i64);
+            let origin_ns = timing
+                .origin_ns
+                .unwrap_or(*self.first_frame_ns.get_or_insert(now_ns));
+            let file_time_ns = now_ns
+                .saturating_sub(origin_ns)
+                .saturating_sub(timing.offset_ns);
+            let mut lines = self
+                .subtitles
+                .as_ref()
+                .map(|(subtitles, _)| subtitles.text_at(file_time_ns))
+                .unwrap_or_default();
+            lines.extend(self.live.text_at(now_ns));
+            renderer.set_text(lines.join("\n"));
+
+            let output = renderer.draw(&frame)?;
+            self.frames_drawn += 1;
+            self.outputs.write("video_out", &output)?;
+            self.live.prune(now_ns);
+            self.last_frame_ns = Some(now_ns);
+        }
+        Ok(())
+    }
+}
+
+impl SubtitleProcessor::Processor {
+    /// Load `config.file` unless it is the file already loaded.
+    fn load_file(&mut self) -> Result<()> {
+        let path = self.config.file.as_deref().filter(|path| !path.is_empty());
+        if path == self.subtitles.as_ref().map(|(_, loaded)| loaded.as_str()) {
+            return Ok(());
+        }
+        self.subtitles = match path {
+            None => None,
+            Some(path) => {
+                let subtitles = Subtitles::load(Path::new(path))?;
+                tracing::info!(
+                    "[Subtitle] Loaded {} cue(s) from {} ({:?})",
+                    subtitles.cues.len(),
+                    path,
+                    subtitles.format
+                );
+                Some((subtitles, path.to_string()))
+            }
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_place_outlined_cues_at_the_bottom_center() {
+        let style = resolve_style(&SubtitleConfig::default()).unwrap();
+
+        assert_eq!(style.size_px, DEFAULT_SIZE_PX);
+        assert_eq!((style.x, style.y), (0.5, DEFAULT_Y));
+        assert_eq!(style.align, Align::Center);
+        assert_eq!(style.vertical_align, VerticalAlign::Bottom);
+        assert_eq!(style.outline_px, DEFAULT_OUTLINE_PX);
+    }
+
+    #[test]
+    fn timing_is_validated_and_converted_to_nanoseconds() {
+        let timing = resolve_timing(&SubtitleConfig {
+            origin_ns: Some("1500000000".into()),
+            offset_ms: Some(-250),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            timing,
+            Timing {
+                origin_ns: Some(1_500_000_000),
+                offset_ns: -250_000_000,
+                live_hold_ns: 5_000_000_000,
+            }
+        );
+
+        for config in [
+            SubtitleConfig {
+                origin_ns: Some("soon".into()),
+                ..Default::default()
+            },
+            SubtitleConfig {
+                live_hold_ms: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                resolve_timing(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+    }
+}
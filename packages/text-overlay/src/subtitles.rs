@@ -0,0 +1,359 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! SubRip (`.srt`) and WebVTT (`.vtt`) subtitle files, and the cues
+//! shown at a point in time.
+//!
+//! Both formats are blocks separated by blank lines, each a timing line
+//! `start --> end` followed by the cue's text; SRT puts a counter above
+//! it, WebVTT an optional identifier and cue settings after it. A file
+//! starting with `WEBVTT` is read as WebVTT, anything else as SRT.
+//! WebVTT `NOTE`, `STYLE` and `REGION` blocks and cue settings are
+//! skipped. Markup — `<i>`, `<font …>`, `<v Speaker>`, inline timestamps,
+//! `{\an8}` overrides — is stripped and the common entities decoded, so
+//! cues render as plain text.
+
+use std::path::Path;
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// One cue, in nanoseconds from the file's start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub start_ns: i64,
+    pub end_ns: i64,
+    /// Plain text; `\n` separates lines.
+    pub text: String,
+}
+
+impl Cue {
+    pub fn is_active(&self, time_ns: i64) -> bool {
+        (self.start_ns..self.end_ns).contains(&time_ns)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Srt,
+    WebVtt,
+}
+
+/// A parsed subtitle file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subtitles {
+    pub format: Format,
+    /// Cues by start time; cues starting together keep file order.
+    pub cues: Vec<Cue>,
+}
+
+impl Subtitles {
+    /// Read and parse the subtitle file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::Configuration(format!("Subtitle: cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&text)
+            .map_err(|e| Error::Configuration(format!("Subtitle: {}: {}", path.display(), e)))
+    }
+
+    /// Parse SRT or WebVTT text. Errors name the offending line.
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+        let lines: Vec<&str> = text.lines().collect();
+        let format = match lines.first() {
+            Some(first) if is_vtt_header(first) => Format::WebVtt,
+            _ => Format::Srt,
+        };
+
+        let mut cues = Vec::new();
+        let mut index = 0;
+        if format == Format::WebVtt {
+            // The header block runs to the first blank line.
+            while index < lines.len() && !lines[index].trim().is_empty() {
+                index += 1;
+            }
+        }
+        while index < lines.len() {
+            if lines[index].trim().is_empty() {
+                index += 1;
+                continue;
+            }
+            let block_start = index;
+            while index < lines.len() && !lines[index].trim().is_empty() {
+                index += 1;
+            }
+            let block = &lines[block_start..index];
+            if format == Format::WebVtt
+                && ["NOTE", "STYLE", "REGION"]
+                    .iter()
+                    .any(|keyword| is_keyword_line(block[0], keyword))
+            {
+                continue;
+            }
+            // The timing line is the first or, under a counter or
+            // identifier, the second.
+            let Some(timing) = block.iter().take(2).position(|line| line.contains("-->")) else {
+                return Err(format!(
+                    "line {}: expected a 'start --> end' timing line",
+                    block_start + 1
+                ));
+            };
+            let number = block_start + timing + 1;
+            let (start_ns, end_ns) = parse_timing(block[timing])
+                .ok_or_else(|| format!("line {}: bad timing '{}'", number, block[timing]))?;
+            if end_ns < start_ns {
+                return Err(format!("line {}: cue ends before it starts", number));
+            }
+            let text = block[timing + 1..]
+                .iter()
+                .map(|line| plain_text(line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            cues.push(Cue {
+                start_ns,
+                end_ns,
+                text: text.trim_matches('\n').to_string(),
+            });
+        }
+        cues.sort_by_key(|cue| cue.start_ns);
+        Ok(Self { format, cues })
+    }
+
+    /// Text of the cues active at `time_ns`, one per line, in start order.
+    pub fn text_at(&self, time_ns: i64) -> Vec<&str> {
+        let started = self.cues.partition_point(|cue| cue.start_ns <= time_ns);
+        self.cues[..started]
+            .iter()
+            .filter(|cue| cue.is_active(time_ns) && !cue.text.is_empty())
+            .map(|cue| cue.text.as_str())
+            .collect()
+    }
+}
+
+fn is_vtt_header(line: &str) -> bool {
+    is_keyword_line(line, "WEBVTT")
+}
+
+/// `line` is `keyword` alone or followed by whitespace.
+fn is_keyword_line(line: &str, keyword: &str) -> bool {
+    line.strip_prefix(keyword)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// `start --> end`, ignoring anything after the end time (WebVTT cue
+/// settings, SRT coordinates).
+fn parse_timing(line: &str) -> Option<(i64, i64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// `[hh:]mm:ss[.,]mmm` in nanoseconds. SRT writes a comma, WebVTT a
+/// period and may drop the hours.
+fn parse_timestamp(text: &str) -> Option<i64> {
+    let (clock, millis) = text.split_once(['.', ','])?;
+    if millis.len() != 3 || !millis.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut fields = clock.split(':').rev();
+    let seconds: i64 = parse_field(fields.next()?, 59)?;
+    let minutes: i64 = parse_field(fields.next()?, 59)?;
+    let hours: i64 = match fields.next() {
+        Some(hours) if !hours.is_empty() && hours.bytes().all(|b| b.is_ascii_digit()) => {
+            hours.parse().ok()?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+    let millis: i64 = millis.parse().ok()?;
+    let seconds = hours.checked_mul(3600)? + minutes * 60 + seconds;
+    seconds
+        .checked_mul(1_000_000_000)?
+        .checked_add(millis * 1_000_000)
+}
+
+fn parse_field(text: &str, max: i64) -> Option<i64> {
+    if text.len() != 2 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok().filter(|value| *value <= max)
+}
+
+/// `line` without markup, with entities decoded.
+fn plain_text(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(at) = rest.find(['<', '{']) {
+        text.push_str(&rest[..at]);
+        let close = if rest[at..].starts_with('<') {
+            '>'
+        } else {
+            '}'
+        };
+        // `{` opens an override only as `{\`; otherwise it is text.
+        if close == '}' && !rest[at + 1..].starts_with('\\') {
+            text.push('{');
+            rest = &rest[at + 1..];
+            continue;
+        }
+        match rest[at..].find(close) {
+            Some(end) => rest = &rest[at + end + 1..],
+            None => rest = "",
+        }
+    }
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", "\u{A0}")
+        .replace("&lrm;", "\u{200E}")
+        .replace("&rlm;", "\u{200F}")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Cues arriving while the graph runs, on the media clock.
+///
+/// A cue without a duration shows until the next one arrives, or for
+/// the hold time if none does; an empty cue just ends the open one.
+#[derive(Debug, Default)]
+pub struct LiveCues {
+    /// Cues by arrival, each with whether its end is provisional.
+    cues: Vec<(Cue, bool)>,
+}
+
+impl LiveCues {
+    pub fn push(&mut self, start_ns: i64, duration_ns: Option<i64>, text: &str, hold_ns: i64) {
+        for (cue, open) in &mut self.cues {
+            if *open && cue.start_ns <= start_ns {
+                cue.end_ns = cue.end_ns.min(start_ns);
+                *open = false;
+            }
+        }
+        if text.is_empty() {
+            return;
+        }
+        self.cues.push((
+            Cue {
+                start_ns,
+                end_ns: start_ns.saturating_add(duration_ns.unwrap_or(hold_ns)),
+                text: text.to_string(),
+            },
+            duration_ns.is_none(),
+        ));
+    }
+
+    /// Text of the cues active at `time_ns`, oldest first.
+    pub fn text_at(&self, time_ns: i64) -> Vec<&str> {
+        self.cues
+            .iter()
+            .filter(|(cue, _)| cue.is_active(time_ns))
+            .map(|(cue, _)| cue.text.as_str())
+            .collect()
+    }
+
+    /// Forget cues that ended by `time_ns`.
+    pub fn prune(&mut self, time_ns: i64) {
+        self.cues.retain(|(cue, _)| cue.end_ns > time_ns);
+    }
+
+    pub fn clear(&mut self) {
+        self.cues.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: i64 = 1_000_000;
+
+    #[test]
+    fn srt_cues_are_timed_and_stripped_of_markup() {
+        let srt = "\u{FEFF}1\r\n00:00:01,000 --> 00:00:04,500\r\n<i>Hello</i>, {\\an8}world\r\n\
+                   second &amp; line\r\n\r\n2\r\n00:01:02,003 --> 00:01:03,000 X1:10 X2:20\r\n\
+                   <font color=\"#ff0\">Bye</font>\r\n";
+        let subtitles = Subtitles::parse(srt).unwrap();
+
+        assert_eq!(subtitles.format, Format::Srt);
+        assert_eq!(
+            subtitles.cues,
+            [
+                Cue {
+                    start_ns: 1000 * MS,
+                    end_ns: 4500 * MS,
+                    text: "Hello, world\nsecond & line".into(),
+                },
+                Cue {
+                    start_ns: 62_003 * MS,
+                    end_ns: 63_000 * MS,
+                    text: "Bye".into(),
+                },
+            ]
+        );
+        assert_eq!(subtitles.text_at(4499 * MS).len(), 1);
+        assert!(subtitles.text_at(4500 * MS).is_empty());
+    }
+
+    #[test]
+    fn webvtt_skips_metadata_blocks_and_cue_settings() {
+        let vtt = "WEBVTT - Example\nKind: captions\n\nNOTE a comment\nspanning lines\n\n\
+                   STYLE\n::cue { color: lime }\n\nintro\n00:01.000 --> 00:03.000 line:0 \
+                   align:start\n<v Alice>Hi <00:01.500><c.loud>there</c>\n\n\
+                   00:00:02.000 --> 00:00:05.000\n{not an override} 1 &lt; 2\n";
+        let subtitles = Subtitles::parse(vtt).unwrap();
+
+        assert_eq!(subtitles.format, Format::WebVtt);
+        assert_eq!(subtitles.cues.len(), 2);
+        assert_eq!(subtitles.cues[0].text, "Hi there");
+        // Overlapping cues both show, earliest first.
+        assert_eq!(
+            subtitles.text_at(2500 * MS),
+            ["Hi there", "{not an override} 1 < 2"]
+        );
+    }
+
+    #[test]
+    fn malformed_timings_name_the_line() {
+        for (text, line) in [
+            ("1\n00:00:01,000 -> 00:00:02,000\ntext\n", "line 1"),
+            ("1\n00:00:01,000 --> 00:00:02\ntext\n", "line 2"),
+            ("WEBVTT\n\n00:02.000 --> 00:01.000\ntext\n", "line 3"),
+            ("1\n00:61:00,000 --> 01:00:00,000\ntext\n", "line 2"),
+        ] {
+            let error = Subtitles::parse(text).unwrap_err();
+            assert!(error.starts_with(line), "{error}");
+        }
+    }
+
+    #[test]
+    fn live_cues_hold_until_replaced_or_expired() {
+        let mut live = LiveCues::default();
+        live.push(0, None, "one", 5000 * MS);
+        live.push(2000 * MS, Some(1000 * MS), "two", 5000 * MS);
+        assert_eq!(live.text_at(1999 * MS), ["one"]);
+        // A timed cue ends when it says and closes the open one.
+        assert_eq!(live.text_at(2000 * MS), ["two"]);
+        assert!(live.text_at(3000 * MS).is_empty());
+
+        live.push(4000 * MS, None, "three", 5000 * MS);
+        assert_eq!(live.text_at(8999 * MS), ["three"]);
+        assert!(live.text_at(9000 * MS).is_empty());
+
+        live.push(10_000 * MS, None, "four", 5000 * MS);
+        live.push(11_000 * MS, None, "", 5000 * MS);
+        assert!(live.text_at(11_000 * MS).is_empty());
+        live.prune(11_000 * MS);
+        assert!(live.cues.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Text layout: turning a string into glyph instances for the mask kernel.
+//!
+//! Lines break at `\n` only. Each line is laid out on the pen from the
+//! font's advances and kerning, aligned within the text box, and every
+//! inked glyph becomes a [`GlyphInstance`] — an atlas rectangle copied to
+//! a position in the coverage mask. The mask covers the ink only; its
+//! offset from the box's top-left corner is reported so the overlay can
+//! anchor the box and place the mask under it.
+
+/// Where a glyph sits in the atlas and how to place it on a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AtlasGlyph {
+    /// Top-left texel of the glyph's coverage rectangle.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Offset from the pen position to the rectangle's left edge.
+    pub bearing_x: f32,
+    /// Distance from the baseline up to the rectangle's top edge.
+    pub bearing_y: f32,
+    /// Pen advance after this glyph.
+    pub advance: f32,
+    /// Index into the fallback chain of the face that supplied the glyph.
+    pub face: usize,
+}
+
+/// Vertical metrics of the primary face, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    pub ascent: f32,
+    /// Negative below the baseline.
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl LineMetrics {
+    /// Baseline-to-baseline distance.
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+}
+
+/// Glyphs and metrics a layout draws on; [`crate::glyph_atlas::GlyphAtlas`]
+/// in the processor.
+pub trait GlyphSource {
+    /// The glyph for `c`, `None` when no face covers it.
+    fn glyph(&mut self, c: char) -> Option<AtlasGlyph>;
+    /// Kerning between two adjacent glyphs.
+    fn kern(&self, left: (char, &AtlasGlyph), right: (char, &AtlasGlyph)) -> f32;
+    fn line_metrics(&self) -> LineMetrics;
+}
+
+/// Horizontal alignment of the text box on its anchor, and of lines
+/// within the box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl Align {
+    /// Fraction of the box width left of the anchor.
+    pub fn factor(self) -> f32 {
+        match self {
+            Align::Left => 0.0,
+            Align::Center => 0.5,
+            Align::Right => 1.0,
+        }
+    }
+}
+
+/// Which edge of the text box sits on the anchor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerticalAlign {
+    Top,
+    #[default]
+    Middle,
+    Bottom,
+}
+
+impl VerticalAlign {
+    /// Fraction of the box height above the anchor.
+    pub fn factor(self) -> f32 {
+        match self {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => 0.5,
+            VerticalAlign::Bottom => 1.0,
+        }
+    }
+}
+
+/// One glyph copy for `text_mask.comp`: the atlas rectangle at
+/// `(atlas_x, atlas_y)` lands with its top-left at `(x, y)` in the mask.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlyphInstance {
+    pub x: i32,
+    pub y: i32,
+    pub atlas_x: u32,
+    pub atlas_y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub _pad: [u32; 2],
+}
+
+/// A laid-out string.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLayout {
+    /// Inked glyphs, in mask coordinates.
+    pub glyphs: Vec<GlyphInstance>,
+    /// Text box — every line's advance width by the lines' height.
+    pub box_width: f32,
+    pub box_height: f32,
+    /// Offset of the mask's top-left from the box's top-left.
+    pub mask_x: i32,
+    pub mask_y: i32,
+    pub mask_width: u32,
+    pub mask_height: u32,
+    /// Characters no face in the chain covers; they are skipped.
+    pub missing: usize,
+}
+
+/// Lay `text` out with glyphs from `source`, keeping at most
+/// `max_glyphs` inked glyphs.
+pub fn layout_text(
+    text: &str,
+    source: &mut impl GlyphSource,
+    align: Align,
+    line_spacing: f32,
+    max_glyphs: usize,
+) -> TextLayout {
+    let metrics = source.line_metrics();
+    let line_height = metrics.line_height() * line_spacing;
+    let mut layout = TextLayout::default();
+
+    // Pen positions per line, then aligned once the box width is known.
+    let mut lines: Vec<(f32, Vec<(f32, AtlasGlyph)>)> = Vec::new();
+    for line in text.split('\n') {
+        let mut pen = 0.0f32;
+        let mut placed = Vec::new();
+        let mut previous: Option<(char, AtlasGlyph)> = None;
+        for c in line.chars().filter(|c| *c != '\r') {
+            let Some(glyph) = source.glyph(c) else {
+                layout.missing += 1;
+                continue;
+            };
+            if let Some((left_char, left)) = &previous {
+                pen += source.kern((*left_char, left), (c, &glyph));
+            }
+            placed.push((pen, glyph));
+            pen += glyph.advance;
+            previous = Some((c, glyph));
+        }
+        lines.push((pen, placed));
+    }
+    layout.box_width = lines.iter().map(|(width, _)| *width).fold(0.0, f32::max);
+    layout.box_height = metrics.ascent - metrics.descent + line_height * (lines.len() - 1) as f32;
+
+    let mut ink = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
+    'lines: for (index, (line_width, placed)) in lines.iter().enumerate() {
+        let offset = (layout.box_width - line_width) * align.factor();
+        let baseline = metrics.ascent + line_height * index as f32;
+        for (pen, glyph) in placed {
+            if glyph.width == 0 || glyph.height == 0 {
+                continue;
+            }
+            if layout.glyphs.len() == max_glyphs {
+                break 'lines;
+            }
+            let x = (offset + pen + glyph.bearing_x).round() as i32;
+            let y = (baseline - glyph.bearing_y).round() as i32;
+            ink = (
+                ink.0.min(x),
+                ink.1.min(y),
+                ink.2.max(x + glyph.width as i32),
+                ink.3.max(y + glyph.height as i32),
+            );
+            layout.glyphs.push(GlyphInstance {
+                x,
+                y,
+                atlas_x: glyph.x,
+                atlas_y: glyph.y,
+                width: glyph.width,
+                height: glyph.height,
+                _pad: [0; 2],
+            });
+        }
+    }
+    if layout.glyphs.is_empty() {
+        return layout;
+    }
+
+    let (min_x, min_y, max_x, max_y) = ink;
+    for glyph in &mut layout.glyphs {
+        glyph.x -= min_x;
+        glyph.y -= min_y;
+    }
+    layout.mask_x = min_x;
+    layout.mask_y = min_y;
+    layout.mask_width = (max_x - min_x) as u32;
+    layout.mask_height = (max_y - min_y) as u32;
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monospaced 10 px advances; every letter is an 8x10 box sitting on
+    /// the baseline, space has no ink, and `?` is not covered.
+    struct FixedFont;
+
+    impl GlyphSource for FixedFont {
+        fn glyph(&mut self, c: char) -> Option<AtlasGlyph> {
+            match c {
+                '?' => None,
+                ' ' => Some(AtlasGlyph {
+                    advance: 10.0,
+                    ..AtlasGlyph::default()
+                }),
+                _ => Some(AtlasGlyph {
+                    x: 100,
+                    y: 200,
+                    width: 8,
+                    height: 10,
+                    bearing_x: 1.0,
+                    bearing_y: 10.0,
+                    advance: 10.0,
+                    face: 0,
+                }),
+            }
+        }
+
+        fn kern(&self, _left: (char, &AtlasGlyph), _right: (char, &AtlasGlyph)) -> f32 {
+            0.0
+        }
+
+        fn line_metrics(&self) -> LineMetrics {
+            LineMetrics {
+                ascent: 12.0,
+                descent: -4.0,
+                line_gap: 4.0,
+            }
+        }
+    }
+
+    #[test]
+    fn one_line_is_inked_from_the_first_bearing_to_the_last_glyph() {
+        let layout = layout_text("ab c", &mut FixedFont, Align::Left, 1.0, 64);
+
+        assert_eq!(layout.glyphs.len(), 3, "space has no ink");
+        assert_eq!(layout.box_width, 40.0);
+        assert_eq!(layout.box_height, 16.0);
+        assert_eq!((layout.mask_x, layout.mask_y), (1, 2));
+        assert_eq!((layout.mask_width, layout.mask_height), (38, 10));
+        assert_eq!(
+            layout.glyphs.iter().map(|g| g.x).collect::<Vec<_>>(),
+            [0, 10, 30]
+        );
+        assert!(layout.glyphs.iter().all(|g| g.y == 0));
+        assert_eq!(
+            (layout.glyphs[0].atlas_x, layout.glyphs[0].atlas_y),
+            (100, 200)
+        );
+    }
+
+    #[test]
+    fn lines_align_within_the_widest_line() {
+        let centered = layout_text("abcd\nab", &mut FixedFont, Align::Center, 1.0, 64);
+        let right = layout_text("abcd\nab", &mut FixedFont, Align::Right, 1.0, 64);
+
+        // Line height 20: the second line's ink starts 20 px lower.
+        assert_eq!(centered.box_height, 36.0);
+        assert_eq!((centered.glyphs[4].x, centered.glyphs[4].y), (10, 20));
+        assert_eq!(right.glyphs[4].x, 20);
+        assert_eq!(centered.mask_height, 30);
+    }
+
+    #[test]
+    fn line_spacing_scales_the_distance_between_baselines() {
+        let layout = layout_text("a\na", &mut FixedFont, Align::Left, 1.5, 64);
+
+        assert_eq!(layout.glyphs[1].y, 30);
+        assert_eq!(layout.box_height, 46.0);
+    }
+
+    #[test]
+    fn uncovered_characters_are_counted_and_skipped() {
+        let layout = layout_text("a?b", &mut FixedFont, Align::Left, 1.0, 64);
+
+        assert_eq!(layout.missing, 1);
+        assert_eq!(layout.glyphs.len(), 2);
+        assert_eq!(layout.box_width, 20.0);
+    }
+
+    #[test]
+    fn glyphs_past_the_limit_are_dropped() {
+        let layout = layout_text("abcdef", &mut FixedFont, Align::Left, 1.0, 4);
+
+        assert_eq!(layout.glyphs.len(), 4);
+        assert_eq!(layout.mask_width, 38);
+    }
+
+    #[test]
+    fn empty_and_blank_text_has_no_mask() {
+        for text in ["", "   ", "\n"] {
+            let layout = layout_text(text, &mut FixedFont, Align::Center, 1.0, 64);
+            assert!(layout.glyphs.is_empty());
+            assert_eq!((layout.mask_width, layout.mask_height), (0, 0));
+        }
+    }
+
+    #[test]
+    fn glyph_instance_matches_the_shader_stride() {
+        assert_eq!(std::mem::size_of::<GlyphInstance>(), 32);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Text overlay (Linux) — draws a string onto video frames on the GPU.
+//!
+//! The string comes from the config and is replaced by each
+//! [`OverlayText`] on the `text` input; drawing is the shared
+//! [`TextRenderer`]'s.
+
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+use crate::_generated_::tatolab__text_overlay::text_overlay_config::{
+    Align as ConfigAlign, VerticalAlign as ConfigVerticalAlign,
+};
+use crate::_generated_::{OverlayText, TextOverlayConfig, VideoFrame};
+use crate::text_layout::{Align, VerticalAlign};
+use crate::text_renderer::{DEFAULT_FONT, Style, StyleOptions, TextRenderer};
+
+fn resolve_style(config: &TextOverlayConfig) -> Result<Style> {
+    Style::resolve(
+        "TextOverlay",
+        &StyleOptions {
+            size_px: config.size_px,
+            color: config.color.as_deref(),
+            x: config.x,
+            y: config.y,
+            align: config.align.as_ref().map(|align| match align {
+                ConfigAlign::Left => Align::Left,
+                ConfigAlign::Center => Align::Center,
+                ConfigAlign::Right => Align::Right,
+            }),
+            vertical_align: config.vertical_align.as_ref().map(|align| match align {
+                ConfigVerticalAlign::Top => VerticalAlign::Top,
+                ConfigVerticalAlign::Middle => VerticalAlign::Middle,
+                ConfigVerticalAlign::Bottom => VerticalAlign::Bottom,
+            }),
+            line_spacing: config.line_spacing,
+            outline_px: config.outline_px,
+            outline_color: config.outline_color.as_deref(),
+            shadow_offset_x: config.shadow_offset_x,
+            shadow_offset_y: config.shadow_offset_y,
+            shadow_color: config.shadow_color.as_deref(),
+        },
+    )
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/text-overlay/TextOverlay",
+    description = "Draws Unicode text onto video frames on the GPU, in any font the runtime's font registry resolves, with size, color, alignment, outline and drop shadow. Glyphs are rasterized once into an atlas and laid out per string; the string is replaced at runtime by OverlayText frames on the text input.",
+    execution = reactive,
+    config = crate::_generated_::TextOverlayConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to draw on; each produces an output frame"),
+    input("text", "@tatolab/text-overlay/OverlayText", optional = true, description = "Replacement strings, applied from the next frame"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Frames with the text drawn on (RGBA8)"),
+)]
+pub struct TextOverlayProcessor {
+    renderer: Option<TextRenderer>,
+    /// `config.text` as last applied, so a config update that leaves it
+    /// alone keeps a string that arrived on the `text` input.
+    configured_text: Option<String>,
+    frames_drawn: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for TextOverlayProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let style = resolve_style(&self.config)?;
+        let font = self.config.font.as_deref().unwrap_or(DEFAULT_FONT);
+        self.configured_text = self.config.text.clone();
+        let renderer = TextRenderer::new(
+            ctx,
+            "TextOverlay",
+            font,
+            style,
+            self.config.text.clone().unwrap_or_default(),
+        )?;
+        tracing::info!(
+            "[TextOverlay] Setup (font '{}', {} face(s), {} px)",
+            renderer.font(),
+            renderer.face_count(),
+            renderer.style().size_px
+        );
+        self.renderer = Some(renderer);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.renderer = None;
+        tracing::info!(
+            "[TextOverlay] Teardown ({} frames drawn)",
+            self.frames_drawn
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        let style = resolve_style(&self.config)?;
+        let Some(renderer) = self.renderer.as_mut() else {
+            return Ok(());
+        };
+        let font = self.config.font.as_deref().unwrap_or(DEFAULT_FONT);
+        if font != renderer.font() {
+            return Err(Error::Configuration(format!(
+                "TextOverlay: font is fixed at setup ('{}'); re-add the processor to use '{}'",
+                renderer.font(),
+                font
+            )));
+        }
+        renderer.set_style(style)?;
+        if self.config.text != self.configured_text {
+            self.configured_text = self.config.text.clone();
+            renderer.set_text(self.config.text.clone().unwrap_or_default());
+        }
+        tracing::info!("[TextOverlay] Config updated");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        let renderer = self
+            .renderer
+            .as_mut()
+            .ok_or_else(|| Error::Configuration("TextOverlay: renderer not initialized".into()))?;
+        let mut replacement = None;
+        while self.inputs.has_data("text") {
+            let update: OverlayText = self.inputs.read("text")?;
+            replacement = Some(update.text);
+        }
+        if let Some(text) = replacement {
+            renderer.set_text(text);
+        }
+        while self.inputs.has_data("video_in") {
+            let frame: VideoFrame = self.inputs.read("video_in")?;
+            let output = renderer.draw(&frame)?;
+            self.frames_drawn += 1;
+            self.outputs.write("video_out", &output)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_renderer::{DEFAULT_COLOR, DEFAULT_SIZE_PX, MAX_OUTLINE_PX};
+
+    #[test]
+    fn style_defaults_draw_centered_white_text_without_effects() {
+        let style = resolve_style(&TextOverlayConfig::default()).unwrap();
+
+        assert_eq!(style.size_px, DEFAULT_SIZE_PX);
+        assert_eq!(style.color, DEFAULT_COLOR);
+        assert_eq!((style.x, style.y), (0.5, 0.5));
+        assert_eq!(style.align, Align::Center);
+        assert_eq!(style.vertical_align, VerticalAlign::Middle);
+        assert_eq!(style.outline_px, 0);
+        assert_eq!(style.shadow_offset, None);
+    }
+
+    #[test]
+    fn out_of_range_style_is_rejected() {
+        for config in [
+            TextOverlayConfig {
+                size_px: Some(0),
+                ..Default::default()
+            },
+            TextOverlayConfig {
+                outline_px: Some(MAX_OUTLINE_PX + 1),
+                ..Default::default()
+            },
+            TextOverlayConfig {
+                line_spacing: Some(0.0),
+                ..Default::default()
+            },
+            TextOverlayConfig {
+                color: Some(vec![1.0, 1.0, 1.0]),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                resolve_style(&config),
+                Err(Error::Configuration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn a_shadow_is_drawn_when_either_offset_is_set() {
+        let style = resolve_style(&TextOverlayConfig {
+            shadow_offset_y: Some(3),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(style.shadow_offset, Some([0, 3]));
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! GPU text renderer (Linux) shared by the text processors.
+//!
+//! The string is laid out on the CPU against a [`GlyphAtlas`] (see
+//! [`crate::text_layout`]) whenever it or the style changes. The atlas
+//! and the glyph instances go to host-mapped buffers, and
+//! `text_mask.comp` rasterizes them into a coverage mask on the GPU.
+//! Every frame, `text_overlay.comp` copies the input into the next slot
+//! of an RGBA8 output ring and draws the shadow, outline and fill from
+//! that mask.
+
+use streamlib_plugin_sdk::sdk::context::{
+    FontFace, GpuContextLimitedAccess, RuntimeContextFullAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel,
+    VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::VideoFrame;
+use crate::glyph_atlas::{ATLAS_WIDTH, GlyphAtlas, MAX_ATLAS_HEIGHT};
+use crate::text_layout::{Align, GlyphInstance, TextLayout, VerticalAlign, layout_text};
+
+const TEXT_MASK_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/text_mask.spv"));
+const TEXT_OVERLAY_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/text_overlay.spv"));
+
+const MASK_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_buffer(0),
+    ComputeBindingSpec::storage_buffer(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+const OVERLAY_BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::storage_image(0),
+    ComputeBindingSpec::sampled_texture(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+/// Inked glyphs drawn per string; the rest are dropped.
+const MAX_GLYPHS: usize = 1024;
+
+/// Matches `text_overlay.comp`'s `MAX_OUTLINE_PX`.
+pub(crate) const MAX_OUTLINE_PX: u32 = 8;
+
+const MAX_SIZE_PX: u32 = 512;
+
+/// Matches both kernels' 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+pub(crate) const DEFAULT_FONT: &str = "sans";
+pub(crate) const DEFAULT_SIZE_PX: u32 = 48;
+pub(crate) const DEFAULT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const DEFAULT_OUTLINE_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+const DEFAULT_SHADOW_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+
+/// Push constants of `text_mask.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MaskPushConstants {
+    mask_width: u32,
+    mask_height: u32,
+    mask_stride: u32,
+    glyph_count: u32,
+    atlas_width: u32,
+}
+
+/// Push constants of `text_overlay.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct OverlayPushConstants {
+    width: u32,
+    height: u32,
+    mask_origin: [i32; 2],
+    mask_width: u32,
+    mask_height: u32,
+    mask_stride: u32,
+    outline_px: u32,
+    shadow_offset: [i32; 2],
+    shadow_enabled: u32,
+    _pad: u32,
+    color: [f32; 4],
+    outline_color: [f32; 4],
+    shadow_color: [f32; 4],
+}
+
+/// The styling fields a text processor's config carries, unresolved.
+/// Unset fields take the renderer's defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct StyleOptions<'a> {
+    pub size_px: Option<u32>,
+    pub color: Option<&'a [f32]>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub align: Option<Align>,
+    pub vertical_align: Option<VerticalAlign>,
+    pub line_spacing: Option<f32>,
+    pub outline_px: Option<u32>,
+    pub outline_color: Option<&'a [f32]>,
+    pub shadow_offset_x: Option<i32>,
+    pub shadow_offset_y: Option<i32>,
+    pub shadow_color: Option<&'a [f32]>,
+}
+
+/// Style resolved once per setup or config update.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Style {
+    pub size_px: u32,
+    pub color: [f32; 4],
+    pub x: f32,
+    pub y: f32,
+    pub align: Align,
+    pub vertical_align: VerticalAlign,
+    pub line_spacing: f32,
+    pub outline_px: u32,
+    pub outline_color: [f32; 4],
+    pub shadow_offset: Option<[i32; 2]>,
+    pub shadow_color: [f32; 4],
+}
+
+impl Style {
+    /// Validate `options` for the processor named `label`.
+    pub(crate) fn resolve(label: &str, options: &StyleOptions<'_>) -> Result<Self> {
+        let size_px = options.size_px.unwrap_or(DEFAULT_SIZE_PX);
+        if size_px == 0 || size_px > MAX_SIZE_PX {
+            return Err(Error::Configuration(format!(
+                "{label}: size_px must be 1..={MAX_SIZE_PX}, got {size_px}"
+            )));
+        }
+        let outline_px = options.outline_px.unwrap_or(0);
+        if outline_px > MAX_OUTLINE_PX {
+            return Err(Error::Configuration(format!(
+                "{label}: outline_px must be 0..={MAX_OUTLINE_PX}, got {outline_px}"
+            )));
+        }
+        let line_spacing = options.line_spacing.unwrap_or(1.0);
+        if !(line_spacing.is_finite() && line_spacing > 0.0) {
+            return Err(Error::Configuration(format!(
+                "{label}: line_spacing must be > 0, got {line_spacing}"
+            )));
+        }
+        let shadow_offset = match (
+            options.shadow_offset_x.unwrap_or(0),
+            options.shadow_offset_y.unwrap_or(0),
+        ) {
+            (0, 0) => None,
+            (dx, dy) => Some([dx, dy]),
+        };
+        let color = |field: &str, value: Option<&[f32]>, default: [f32; 4]| match value {
+            None => Ok(default),
+            Some(&[r, g, b, a]) => Ok([r, g, b, a]),
+            Some(other) => Err(Error::Configuration(format!(
+                "{label}: {field} must be [r, g, b, a], got {} values",
+                other.len()
+            ))),
+        };
+        Ok(Style {
+            size_px,
+            color: color("color", options.color, DEFAULT_COLOR)?,
+            x: options.x.unwrap_or(0.5),
+            y: options.y.unwrap_or(0.5),
+            align: options.align.unwrap_or(Align::Center),
+            vertical_align: options.vertical_align.unwrap_or(VerticalAlign::Middle),
+            line_spacing,
+            outline_px,
+            outline_color: color(
+                "outline_color",
+                options.outline_color,
+                DEFAULT_OUTLINE_COLOR,
+            )?,
+            shadow_offset,
+            shadow_color: color("shadow_color", options.shadow_color, DEFAULT_SHADOW_COLOR)?,
+        })
+    }
+
+    /// Whether a change from `other` needs a new layout, not just new
+    /// push constants.
+    pub(crate) fn relayout_needed(&self, other: &Style) -> bool {
+        self.size_px != other.size_px
+            || self.align != other.align
+            || self.line_spacing != other.line_spacing
+    }
+}
+
+/// Draws one string, in one font and style, onto frames.
+pub(crate) struct TextRenderer {
+    /// Processor name for logs and errors.
+    label: &'static str,
+    gpu_context: GpuContextLimitedAccess,
+    mask_kernel: VulkanComputeKernel,
+    overlay_kernel: VulkanComputeKernel,
+    recorder: RhiCommandRecorder,
+    /// Host-mapped copy of the atlas pixels.
+    atlas_buffer: StorageBuffer,
+    /// Host-mapped `[GlyphInstance; MAX_GLYPHS]`.
+    glyph_buffer: StorageBuffer,
+    /// Coverage mask written by `text_mask.comp`; grown as strings need.
+    mask_buffer: Option<StorageBuffer>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    /// Font the chain was resolved for.
+    font: String,
+    font_chain: Vec<FontFace>,
+    atlas: GlyphAtlas,
+    style: Style,
+    /// The string drawn now.
+    text: String,
+    layout: Option<TextLayout>,
+    /// The layout changed since the mask was last rasterized.
+    mask_dirty: bool,
+}
+
+impl TextRenderer {
+    /// Resolve `font`, set up the kernels and buffers and lay `text`
+    /// out. The font is fixed for the renderer's life.
+    pub(crate) fn new(
+        ctx: &RuntimeContextFullAccess<'_>,
+        label: &'static str,
+        font: &str,
+        style: Style,
+        text: String,
+    ) -> Result<Self> {
+        let font_chain = ctx.fonts().resolve(font)?;
+        let atlas = GlyphAtlas::new(&font_chain, style.size_px as f32)?;
+
+        let full = ctx.gpu_full_access();
+        let mask_kernel = full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "text_mask",
+            spv: TEXT_MASK_SPV,
+            bindings: MASK_BINDINGS,
+            push_constant_size: std::mem::size_of::<MaskPushConstants>() as u32,
+        })?;
+        let overlay_kernel = full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "text_overlay",
+            spv: TEXT_OVERLAY_SPV,
+            bindings: OVERLAY_BINDINGS,
+            push_constant_size: std::mem::size_of::<OverlayPushConstants>() as u32,
+        })?;
+        let recorder = full.create_command_recorder("text_overlay")?;
+        let atlas_buffer =
+            full.acquire_storage_buffer(u64::from(ATLAS_WIDTH) * u64::from(MAX_ATLAS_HEIGHT))?;
+        let glyph_buffer =
+            full.acquire_storage_buffer(std::mem::size_of::<[GlyphInstance; MAX_GLYPHS]>() as u64)?;
+        if atlas_buffer.mapped_ptr().is_null() || glyph_buffer.mapped_ptr().is_null() {
+            return Err(Error::Configuration(format!(
+                "{label}: atlas and glyph buffers must be host-mapped"
+            )));
+        }
+        let mut renderer = Self {
+            label,
+            gpu_context: ctx.gpu_limited_access().clone(),
+            mask_kernel,
+            overlay_kernel,
+            recorder,
+            atlas_buffer,
+            glyph_buffer,
+            mask_buffer: None,
+            output_ring: None,
+            font: font.to_string(),
+            font_chain,
+            atlas,
+            style,
+            text,
+            layout: None,
+            mask_dirty: false,
+        };
+        renderer.relayout();
+        Ok(renderer)
+    }
+
+    pub(crate) fn font(&self) -> &str {
+        &self.font
+    }
+
+    /// Faces the font resolved to, fallbacks included.
+    pub(crate) fn face_count(&self) -> usize {
+        self.font_chain.len()
+    }
+
+    pub(crate) fn style(&self) -> &Style {
+        &self.style
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Switch to `style`, re-rasterizing the atlas and laying out again
+    /// only when the change needs it.
+    pub(crate) fn set_style(&mut self, style: Style) -> Result<()> {
+        let previous = std::mem::replace(&mut self.style, style);
+        if previous.size_px != self.style.size_px {
+            self.atlas = GlyphAtlas::new(&self.font_chain, self.style.size_px as f32)?;
+        }
+        if previous.relayout_needed(&self.style) {
+            self.relayout();
+        }
+        Ok(())
+    }
+
+    /// Draw `text` from the next frame on.
+    pub(crate) fn set_text(&mut self, text: String) {
+        if text != self.text {
+            self.text = text;
+            self.relayout();
+        }
+    }
+
+    /// Lay the current string out and mark the mask for rasterizing.
+    fn relayout(&mut self) {
+        let (atlas, style, label) = (&mut self.atlas, &self.style, self.label);
+        // A string whose glyphs overflow the atlas clears it mid-layout,
+        // invalidating the glyphs placed before; lay out again against the
+        // cleared atlas, once.
+        let mut layout = None;
+        for _ in 0..2 {
+            let generation = atlas.generation();
+            let attempt = layout_text(
+                &self.text,
+                atlas,
+                style.align,
+                style.line_spacing,
+                MAX_GLYPHS,
+            );
+            if atlas.generation() == generation {
+                layout = Some(attempt);
+                break;
+            }
+        }
+        let Some(layout) = layout else {
+            tracing::warn!(
+                "[{label}] '{}' needs more glyphs than the atlas holds at {} px; not drawn",
+                self.text,
+                style.size_px
+            );
+            self.layout = None;
+            return;
+        };
+        if layout.missing > 0 {
+            tracing::warn!(
+                "[{label}] {} character(s) of '{}' are not in font '{}'",
+                layout.missing,
+                self.text,
+                self.font
+            );
+        }
+        if layout.glyphs.len() == MAX_GLYPHS {
+            tracing::warn!(
+                "[{label}] Only the first {} glyphs of the string are drawn",
+                MAX_GLYPHS
+            );
+        }
+        self.layout = Some(layout);
+        self.mask_dirty = true;
+    }
+
+    /// Draw the current text onto `frame` into the next output slot.
+    pub(crate) fn draw(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let label = self.label;
+        let gpu = &self.gpu_context;
+        let style = &self.style;
+        let (width, height) = (frame.width, frame.height);
+        let empty = TextLayout::default();
+        let layout = self.layout.as_ref().unwrap_or(&empty);
+        let mask_stride = layout.mask_width.div_ceil(4);
+        let mask_bytes = u64::from(mask_stride) * u64::from(layout.mask_height) * 4;
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+        let input_registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            width,
+            height,
+        )?;
+
+        let rasterize = self.mask_dirty && !layout.glyphs.is_empty();
+        if self
+            .mask_buffer
+            .as_ref()
+            .is_none_or(|buffer| buffer.byte_size() < mask_bytes.max(4))
+        {
+            // Grown to a power of two so a changing string does not
+            // reallocate every time.
+            self.mask_buffer =
+                Some(gpu.acquire_storage_buffer(mask_bytes.max(4).next_power_of_two())?);
+        }
+        let Some(mask_buffer) = self.mask_buffer.as_ref() else {
+            return Err(Error::Configuration(format!(
+                "{label}: mask buffer not allocated"
+            )));
+        };
+
+        let (mask_kernel, overlay_kernel) = (&self.mask_kernel, &self.overlay_kernel);
+        if rasterize {
+            // SAFETY: both buffers are persistently-mapped host-visible
+            // allocations (checked non-null in `new`) sized for the whole
+            // atlas and `MAX_GLYPHS` instances, and the previous submit has
+            // completed, so the GPU is not reading them. Host writes before
+            // the submit are visible to the kernels.
+            unsafe {
+                if self.atlas.take_dirty() {
+                    let pixels = self.atlas.pixels();
+                    std::ptr::copy_nonoverlapping(
+                        pixels.as_ptr(),
+                        self.atlas_buffer.mapped_ptr(),
+                        pixels.len(),
+                    );
+                }
+                std::ptr::copy_nonoverlapping(
+                    layout.glyphs.as_ptr(),
+                    self.glyph_buffer.mapped_ptr() as *mut GlyphInstance,
+                    layout.glyphs.len(),
+                );
+            }
+            mask_kernel.set_storage_buffer_storage(0, &self.atlas_buffer)?;
+            mask_kernel.set_storage_buffer_storage(1, &self.glyph_buffer)?;
+            mask_kernel.set_storage_buffer_storage(2, mask_buffer)?;
+            mask_kernel.set_push_constants_value(&MaskPushConstants {
+                mask_width: layout.mask_width,
+                mask_height: layout.mask_height,
+                mask_stride,
+                glyph_count: layout.glyphs.len() as u32,
+                atlas_width: ATLAS_WIDTH,
+            })?;
+        }
+
+        // Anchor the text box, then place the mask under it.
+        let box_left = style.x * width as f32 - style.align.factor() * layout.box_width;
+        let box_top = style.y * height as f32 - style.vertical_align.factor() * layout.box_height;
+        overlay_kernel.set_storage_image(0, &slot.texture)?;
+        overlay_kernel.set_sampled_texture(1, input_registration.texture())?;
+        overlay_kernel.set_storage_buffer_storage(2, mask_buffer)?;
+        overlay_kernel.set_push_constants_value(&OverlayPushConstants {
+            width,
+            height,
+            mask_origin: [
+                box_left.round() as i32 + layout.mask_x,
+                box_top.round() as i32 + layout.mask_y,
+            ],
+            mask_width: layout.mask_width,
+            mask_height: layout.mask_height,
+            mask_stride,
+            outline_px: style.outline_px,
+            shadow_offset: style.shadow_offset.unwrap_or([0, 0]),
+            shadow_enabled: u32::from(style.shadow_offset.is_some()),
+            _pad: 0,
+            color: style.color,
+            outline_color: style.outline_color,
+            shadow_color: style.shadow_color,
+        })?;
+
+        let recorder = &mut self.recorder;
+        recorder.begin()?;
+        if rasterize {
+            recorder.record_dispatch(
+                mask_kernel,
+                mask_stride.div_ceil(WORKGROUP_SIZE),
+                layout.mask_height.div_ceil(WORKGROUP_SIZE),
+                1,
+            )?;
+            recorder.record_buffer_barrier(
+                mask_buffer,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::SHADER_WRITE,
+                VulkanAccess::SHADER_READ,
+            )?;
+        }
+        let current_layout = input_registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                input_registration.texture(),
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            overlay_kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&input_registration])?;
+        if rasterize {
+            self.mask_dirty = false;
+        }
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            // Text is drawn in the frame's own encoding; the signal
+            // description carries over.
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_layout_affecting_changes_need_a_relayout() {
+        let style = Style::resolve("Test", &StyleOptions::default()).unwrap();
+        let recolored = Style {
+            color: [1.0, 0.0, 0.0, 1.0],
+            x: 0.1,
+            ..style.clone()
+        };
+        let realigned = Style {
+            align: Align::Left,
+            ..style.clone()
+        };
+
+        assert!(!recolored.relayout_needed(&style));
+        assert!(realigned.relayout_needed(&style));
+    }
+
+    #[test]
+    fn push_constants_match_the_shader_blocks() {
+        assert_eq!(std::mem::size_of::<MaskPushConstants>(), 20);
+        assert_eq!(std::mem::size_of::<OverlayPushConstants>(), 96);
+        assert_eq!(std::mem::offset_of!(OverlayPushConstants, color), 48);
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/tone-map` — HDR to SDR conversion. `ToneMap` maps PQ and
+//! HLG frames to SDR BT.709 on the GPU with a BT.2390 EETF or Reinhard
+//! operator, using the frame's mastering metadata for the source peak.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod tone_curve;
+
+// The EETF/Reinhard pass runs on `sdk::rhi`, which is Linux-only; the
+// `tone_curve` reference maths stays portable for its tests.
+#[cfg(target_os = "linux")]
+pub mod tone_map;
+
+#[cfg(target_os = "linux")]
+pub use tone_map::ToneMapProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::ToneMapProcessor::Processor);
@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tone curve parameters resolved on the CPU once per frame — the source
+//! peak picked from the frame's HDR metadata, the BT.2390 knee, and the
+//! HLG system gamma. [`ToneCurve::bt2390`] and [`ToneCurve::reinhard`]
+//! are CPU mirrors of the curves in `tone_map.comp`; the shader applies
+//! them to max(R, G, B) in absolute cd/m².
+
+/// Luminance of PQ signal 1.0 (SMPTE ST 2084).
+pub const PQ_PEAK_NITS: f32 = 10000.0;
+
+pub const DEFAULT_SOURCE_PEAK_NITS: u32 = 1000;
+/// BT.2408 HDR reference white.
+pub const DEFAULT_TARGET_PEAK_NITS: u32 = 203;
+
+/// Input signal; the discriminant is the shader's transfer code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InputTransfer {
+    Pq = 0,
+    Hlg = 1,
+}
+
+/// Tone curve; the discriminant is the shader's operator code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Operator {
+    Bt2390 = 0,
+    Reinhard = 1,
+}
+
+/// SDR encoding; the discriminant is the shader's output code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum OutputTransfer {
+    Srgb = 0,
+    /// Inverse BT.1886 (pure 2.4 gamma).
+    Bt1886 = 1,
+}
+
+const PQ_M1: f32 = 0.159_301_76;
+const PQ_M2: f32 = 78.843_75;
+const PQ_C1: f32 = 0.835_937_5;
+const PQ_C2: f32 = 18.851_563;
+const PQ_C3: f32 = 18.6875;
+
+/// PQ inverse EOTF: absolute luminance in cd/m² to signal in 0..1.
+pub fn pq_encode(nits: f32) -> f32 {
+    let y = (nits / PQ_PEAK_NITS).clamp(0.0, 1.0).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/// PQ EOTF: signal in 0..1 to absolute luminance in cd/m².
+pub fn pq_decode(signal: f32) -> f32 {
+    let p = signal.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+    ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1) * PQ_PEAK_NITS
+}
+
+/// BT.2100 HLG system gamma for a display of `peak_nits` (1.2 at
+/// 1000 cd/m², with the extended formula away from it).
+pub fn hlg_system_gamma(peak_nits: f32) -> f32 {
+    1.2 + 0.42 * (peak_nits / 1000.0).log10()
+}
+
+/// Source peak luminance for a PQ frame: MaxCLL when the frame carries
+/// it, else the mastering display's peak (0.0001 cd/m² units), else
+/// `fallback`. Zero means unknown in both HDR10 sidecars.
+pub fn source_peak_nits(
+    max_cll: Option<u32>,
+    mastering_max_luminance: Option<u32>,
+    fallback: u32,
+) -> f32 {
+    let nits = match (max_cll, mastering_max_luminance) {
+        (Some(cll), _) if cll > 0 => cll as f32,
+        (_, Some(max)) if max > 0 => max as f32 / 10000.0,
+        _ => fallback as f32,
+    };
+    nits.min(PQ_PEAK_NITS)
+}
+
+/// One frame's mapping from `[0, source_peak]` to `[0, target_peak]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneCurve {
+    pub source_peak: f32,
+    pub target_peak: f32,
+    /// PQ signal of `source_peak`; the EETF works on PQ normalized to it.
+    pub source_pq: f32,
+    /// Target peak on that normalized scale.
+    pub max_lum: f32,
+    /// Knee start on that scale; signal below it is left as is.
+    pub knee: f32,
+}
+
+impl ToneCurve {
+    /// A source darker than the target needs no compression: the source
+    /// peak is raised to the target and both curves become the identity.
+    pub fn new(source_peak: f32, target_peak: f32) -> Self {
+        let target_peak = target_peak.clamp(1.0, PQ_PEAK_NITS);
+        let source_peak = source_peak.clamp(target_peak, PQ_PEAK_NITS);
+        let source_pq = pq_encode(source_peak);
+        let max_lum = pq_encode(target_peak) / source_pq;
+        Self {
+            source_peak,
+            target_peak,
+            source_pq,
+            max_lum,
+            knee: (1.5 * max_lum - 0.5).clamp(0.0, 1.0),
+        }
+    }
+
+    /// BT.2390 EETF: a hermite spline from the knee to `max_lum`.
+    pub fn bt2390(&self, nits: f32) -> f32 {
+        let e1 = (pq_encode(nits) / self.source_pq).min(1.0);
+        if e1 < self.knee || self.knee >= 1.0 {
+            return pq_decode(e1 * self.source_pq);
+        }
+        let t = (e1 - self.knee) / (1.0 - self.knee);
+        let (t2, t3) = (t * t, t * t * t);
+        let e2 = (2.0 * t3 - 3.0 * t2 + 1.0) * self.knee
+            + (t3 - 2.0 * t2 + t) * (1.0 - self.knee)
+            + (-2.0 * t3 + 3.0 * t2) * self.max_lum;
+        pq_decode(e2 * self.source_pq)
+    }
+
+    /// Extended Reinhard with the white point at the source peak.
+    pub fn reinhard(&self, nits: f32) -> f32 {
+        let white = self.source_peak / self.target_peak;
+        let l = (nits / self.target_peak).min(white);
+        self.target_peak * l * (1.0 + l / (white * white)) / (1.0 + l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32, tolerance: f32) -> bool {
+        (a - b).abs() <= tolerance
+    }
+
+    #[test]
+    fn pq_round_trips_and_hits_reference_points() {
+        assert!(close(pq_encode(PQ_PEAK_NITS), 1.0, 1e-6));
+        assert_eq!(pq_encode(0.0), pq_encode(-1.0));
+        assert!(close(pq_encode(100.0), 0.508, 1e-3));
+        assert!(close(pq_encode(203.0), 0.58, 1e-3));
+        for nits in [0.1, 1.0, 100.0, 203.0, 1000.0, 4000.0] {
+            assert!(close(pq_decode(pq_encode(nits)), nits, nits * 1e-3));
+        }
+    }
+
+    #[test]
+    fn source_peak_prefers_max_cll_then_mastering_display() {
+        assert_eq!(source_peak_nits(Some(1500), Some(40_000_000), 1000), 1500.0);
+        assert_eq!(source_peak_nits(Some(0), Some(40_000_000), 1000), 4000.0);
+        assert_eq!(source_peak_nits(None, Some(0), 1000), 1000.0);
+        assert_eq!(source_peak_nits(None, None, 600), 600.0);
+        assert_eq!(source_peak_nits(Some(20_000), None, 1000), PQ_PEAK_NITS);
+        assert!(close(hlg_system_gamma(1000.0), 1.2, 1e-6));
+        assert!(hlg_system_gamma(2000.0) > 1.2);
+    }
+
+    #[test]
+    fn curves_map_source_peak_to_target_peak() {
+        let curve = ToneCurve::new(1000.0, 203.0);
+        assert!(curve.knee > 0.0 && curve.knee < 1.0);
+        for map in [ToneCurve::bt2390, ToneCurve::reinhard] {
+            assert!(close(map(&curve, 1000.0), 203.0, 0.5));
+            assert!(close(map(&curve, 5000.0), 203.0, 0.5));
+            let mut previous = 0.0;
+            for step in 1..=100 {
+                let out = map(&curve, step as f32 * 10.0);
+                assert!(out >= previous && out <= 203.5);
+                previous = out;
+            }
+        }
+        // Below the knee the EETF leaves the signal alone.
+        assert!(close(curve.bt2390(20.0), 20.0, 0.05));
+    }
+
+    #[test]
+    fn dim_sources_are_not_expanded() {
+        let curve = ToneCurve::new(100.0, 203.0);
+        assert_eq!((curve.source_peak, curve.knee), (203.0, 1.0));
+        for nits in [1.0, 50.0, 100.0, 203.0] {
+            assert!(close(curve.bt2390(nits), nits, nits * 1e-3));
+            assert!(close(curve.reinhard(nits), nits, nits * 1e-3));
+        }
+    }
+}
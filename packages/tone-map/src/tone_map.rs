@@ -0,0 +1,294 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! ToneMap (Linux) — converts PQ and HLG frames to SDR BT.709 on the GPU.
+//!
+//! Each frame's curve is resolved on the CPU (see [`crate::tone_curve`]):
+//! the PQ source peak comes from the frame's MaxCLL or mastering display,
+//! falling back to `source_peak_nits`, which is also HLG's nominal
+//! display peak. One compute dispatch decodes, converts the gamut, tone
+//! maps and encodes into the next slot of an RGBA8 output ring, which is
+//! reallocated when the input size changes. Frames whose transfer is
+//! neither PQ nor HLG are forwarded untouched.
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel, VulkanLayout,
+    VulkanStage,
+};
+
+use crate::_generated_::tatolab__core::color_info::{Matrix, Primaries, Range, Transfer};
+use crate::_generated_::tatolab__tone_map::tone_map_config;
+use crate::_generated_::{ColorInfo, VideoFrame};
+use crate::tone_curve::{
+    self, DEFAULT_SOURCE_PEAK_NITS, DEFAULT_TARGET_PEAK_NITS, InputTransfer, Operator,
+    OutputTransfer, ToneCurve,
+};
+
+const TONE_MAP_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tone_map.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+];
+
+/// Matches `tone_map.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Push constants of `tone_map.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ToneMapPushConstants {
+    width: u32,
+    height: u32,
+    input_transfer: u32,
+    tone_operator: u32,
+    output_transfer: u32,
+    convert_gamut: u32,
+    source_peak: f32,
+    target_peak: f32,
+    source_pq: f32,
+    max_lum: f32,
+    knee: f32,
+    hlg_gamma: f32,
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/tone-map/ToneMap",
+    description = "Converts PQ and HLG frames to SDR BT.709 on the GPU (BT.2408-style display-light mapping). The source peak comes from the frame's MaxCLL or mastering display when present. Frames that are not PQ or HLG pass through unchanged.",
+    execution = reactive,
+    config = crate::_generated_::ToneMapConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "HDR frames (PQ or HLG)"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "SDR frames (RGBA8, BT.709 primaries)"),
+)]
+pub struct ToneMapProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    operator: Option<Operator>,
+    output_transfer: Option<OutputTransfer>,
+    frames_mapped: u64,
+    warned_passthrough: bool,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for ToneMapProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let operator = match self.config.operator {
+            Some(tone_map_config::Operator::Reinhard) => Operator::Reinhard,
+            Some(tone_map_config::Operator::Bt2390) | None => Operator::Bt2390,
+        };
+        let output_transfer = match self.config.output_transfer {
+            Some(tone_map_config::OutputTransfer::Bt709) => OutputTransfer::Bt1886,
+            Some(tone_map_config::OutputTransfer::Srgb) | None => OutputTransfer::Srgb,
+        };
+        if self.config.target_peak_nits == Some(0) {
+            return Err(Error::Configuration(
+                "ToneMap: target_peak_nits must be > 0".into(),
+            ));
+        }
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "tone_map",
+            spv: TONE_MAP_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<ToneMapPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("tone_map")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        self.operator = Some(operator);
+        self.output_transfer = Some(output_transfer);
+        tracing::info!(
+            "[ToneMap] Setup ({:?}, {:?} output, {} cd/m² SDR peak)",
+            operator,
+            output_transfer,
+            self.config
+                .target_peak_nits
+                .unwrap_or(DEFAULT_TARGET_PEAK_NITS)
+        );
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.output_ring = None;
+        tracing::info!("[ToneMap] Teardown ({} frames mapped)", self.frames_mapped);
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let input_transfer = match frame.color_info.as_ref().and_then(|c| c.transfer.as_ref()) {
+            Some(Transfer::Smpte2084) => InputTransfer::Pq,
+            Some(Transfer::AribStdB67) => InputTransfer::Hlg,
+            transfer => {
+                if !self.warned_passthrough {
+                    self.warned_passthrough = true;
+                    tracing::info!(
+                        "[ToneMap] Passing through frames with transfer {:?}: only PQ and HLG are tone mapped",
+                        transfer
+                    );
+                }
+                return self.outputs.write("video_out", &frame);
+            }
+        };
+        let sdr_frame = self.map(&frame, input_transfer)?;
+        self.frames_mapped += 1;
+        self.outputs.write("video_out", &sdr_frame)
+    }
+}
+
+impl ToneMapProcessor::Processor {
+    /// Tone map `frame` into the next output slot.
+    fn map(&mut self, frame: &VideoFrame, input_transfer: InputTransfer) -> Result<VideoFrame> {
+        let gpu = self
+            .gpu_context
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("ToneMap: GPU context not initialized".into()))?;
+        let (Some(kernel), Some(recorder), Some(operator), Some(output_transfer)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.operator,
+            self.output_transfer,
+        ) else {
+            return Err(Error::Configuration(
+                "ToneMap: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        let fallback_peak = self
+            .config
+            .source_peak_nits
+            .unwrap_or(DEFAULT_SOURCE_PEAK_NITS);
+        let source_peak = match input_transfer {
+            InputTransfer::Pq => tone_curve::source_peak_nits(
+                frame.content_light.as_ref().map(|c| c.max_cll),
+                frame.mastering_display.as_ref().map(|m| m.max_luminance),
+                fallback_peak,
+            ),
+            // HLG is scene-referred; its display peak is a rendering choice.
+            InputTransfer::Hlg => fallback_peak as f32,
+        };
+        let target_peak = self
+            .config
+            .target_peak_nits
+            .unwrap_or(DEFAULT_TARGET_PEAK_NITS) as f32;
+        let curve = ToneCurve::new(source_peak, target_peak);
+        // PQ and HLG are BT.2100 signals, BT.2020 primaries unless stated.
+        let convert_gamut = !matches!(
+            frame.color_info.as_ref().and_then(|c| c.primaries.as_ref()),
+            Some(Primaries::Bt709)
+        );
+
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_push_constants_value(&ToneMapPushConstants {
+            width,
+            height,
+            input_transfer: input_transfer as u32,
+            tone_operator: operator as u32,
+            output_transfer: output_transfer as u32,
+            convert_gamut: u32::from(convert_gamut),
+            source_peak: curve.source_peak,
+            target_peak: curve.target_peak,
+            source_pq: curve.source_pq,
+            max_lum: curve.max_lum,
+            knee: curve.knee,
+            hlg_gamma: tone_curve::hlg_system_gamma(curve.source_peak),
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: Some(ColorInfo {
+                primaries: Some(Primaries::Bt709),
+                transfer: Some(match output_transfer {
+                    OutputTransfer::Srgb => Transfer::Srgb,
+                    OutputTransfer::Bt1886 => Transfer::Bt709,
+                }),
+                matrix: Some(Matrix::Identity),
+                range: Some(Range::Full),
+            }),
+            // SDR output carries no HDR static metadata.
+            mastering_display: None,
+            content_light: None,
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
@@ -0,0 +1,856 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! VST3 plugin host — one audio effect instance from a `.vst3` bundle,
+//! presented through the same surface as `ClapPluginHost`.
+//!
+//! Parameter values on this surface are VST3 normalized values in `[0, 1]`;
+//! `ParameterInfo::display` carries the plugin's formatted plain value.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use parking_lot::Mutex as ParkingLotMutex;
+use streamlib_audio::plugin_host::{ParameterInfo, PluginInfo};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use vst3::Steinberg::Vst::BusDirections_::{kInput, kOutput};
+use vst3::Steinberg::Vst::MediaTypes_::kAudio;
+use vst3::Steinberg::Vst::ParameterInfo_::ParameterFlags_::{
+    kCanAutomate, kIsBypass, kIsHidden, kIsReadOnly, kIsWrapAround,
+};
+use vst3::Steinberg::Vst::ProcessContext_::StatesAndFlags_::{
+    kContTimeValid, kPlaying, kTempoValid, kTimeSigValid,
+};
+use vst3::Steinberg::Vst::ProcessModes_::kRealtime;
+use vst3::Steinberg::Vst::SymbolicSampleSizes_::kSample32;
+use vst3::Steinberg::Vst::{
+    AudioBusBuffers, AudioBusBuffers__type0, BusDirection, BusInfo, IAudioProcessor,
+    IAudioProcessorTrait, IComponent, IComponentHandler, IComponentTrait, IConnectionPoint,
+    IConnectionPointTrait, IEditController, IEditControllerTrait, IHostApplication, MediaType,
+    ProcessContext, ProcessData, ProcessSetup, SpeakerArr, SpeakerArrangement, String128,
+};
+use vst3::Steinberg::{
+    FUnknown, IBStream, IPluginBaseTrait, IPluginFactory, IPluginFactoryTrait, TUID, kResultOk,
+    kResultTrue, tresult,
+};
+use vst3::{ComPtr, ComWrapper, Interface};
+
+use crate::_generated_::AudioFrame;
+use crate::host_objects::{ComponentHandler, HostApplication, MemoryStream, ParameterChanges};
+use crate::module::Vst3Module;
+use crate::scanner::{AudioClass, audio_classes};
+use crate::state::PluginState;
+use crate::strings::{class_id_hex, from_string128};
+
+/// Parameter values shared between the control path, the component
+/// handler, and the audio path.
+#[derive(Default)]
+pub(crate) struct SharedState {
+    /// Current normalized value of every parameter.
+    pub parameters: HashMap<u32, f64>,
+    /// Changes not yet delivered to the processor.
+    pub pending: HashMap<u32, f64>,
+}
+
+pub struct Vst3PluginHost {
+    plugin_info: PluginInfo,
+
+    parameter_info: Vec<ParameterInfo>,
+
+    shared_state: Arc<ParkingLotMutex<SharedState>>,
+
+    component: ComPtr<IComponent>,
+
+    processor: ComPtr<IAudioProcessor>,
+
+    controller: Option<ComPtr<IEditController>>,
+
+    /// The controller is its own object (not the component), initialized
+    /// and terminated by the host.
+    separate_controller: bool,
+
+    connection: Option<(ComPtr<IConnectionPoint>, ComPtr<IConnectionPoint>)>,
+
+    component_handler: ComWrapper<ComponentHandler>,
+
+    host_application: ComWrapper<HostApplication>,
+
+    factory: ComPtr<IPluginFactory>,
+
+    /// Declared last so it is dropped (and the module exited) after every
+    /// COM object above has been released.
+    module: Vst3Module,
+
+    is_activated: bool,
+
+    sample_rate: u32,
+    buffer_size: usize,
+
+    input_channels: usize,
+    output_channels: usize,
+
+    input_buffers: Vec<Vec<f32>>,
+    output_buffers: Vec<Vec<f32>>,
+    input_pointers: Vec<*mut f32>,
+    output_pointers: Vec<*mut f32>,
+
+    /// Samples processed since activation, reported as the transport position.
+    processed_samples: i64,
+}
+
+// SAFETY: VST3 objects may be called from any one thread at a time; the
+// host is moved to the processor's audio thread and only used there between
+// start() and teardown(). The channel pointers point into the host's own
+// buffers.
+unsafe impl Send for Vst3PluginHost {}
+
+impl Vst3PluginHost {
+    pub fn load_by_name<P: AsRef<Path>>(
+        path: P,
+        plugin_name: &str,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        let path_str = path.as_ref().display().to_string();
+        Self::load_internal(path, sample_rate, buffer_size, |classes| {
+            let names: Vec<&str> = classes.iter().map(|class| class.name.as_str()).collect();
+            for name in &names {
+                tracing::debug!("Found plugin in bundle: '{}'", name);
+            }
+            classes
+                .iter()
+                .find(|class| class.name == plugin_name)
+                .cloned()
+                .ok_or_else(|| {
+                    let available = if names.is_empty() {
+                        "none found".to_string()
+                    } else {
+                        names.join(", ")
+                    };
+                    Error::Configuration(format!(
+                        "Plugin '{}' not found in bundle {}. Available plugins: [{}]",
+                        plugin_name, path_str, available
+                    ))
+                })
+        })
+    }
+
+    pub fn load_by_index<P: AsRef<Path>>(
+        path: P,
+        index: usize,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Result<Self> {
+        Self::load_internal(path, sample_rate, buffer_size, |classes| {
+            classes.get(index).cloned().ok_or_else(|| {
+                Error::Configuration(format!("Plugin index {} not found in bundle", index))
+            })
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P, sample_rate: u32, buffer_size: usize) -> Result<Self> {
+        Self::load_internal(path, sample_rate, buffer_size, |classes| {
+            classes
+                .first()
+                .cloned()
+                .ok_or_else(|| Error::Configuration("VST3 bundle contains no audio plugins".into()))
+        })
+    }
+
+    /// Instantiate the selected audio class: component, controller,
+    /// component ↔ controller connection, and parameter discovery. The
+    /// plugin is not activated until [`Self::activate`].
+    fn load_internal<P, F>(path: P, sample_rate: u32, buffer_size: usize, select: F) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&[AudioClass]) -> Result<AudioClass>,
+    {
+        let module = Vst3Module::load(path.as_ref())?;
+        let factory = module.factory()?;
+        let class = select(&audio_classes(&factory))?;
+
+        let component: ComPtr<IComponent> =
+            create_instance(&factory, &class.cid).ok_or_else(|| {
+                Error::Configuration(format!("Failed to create plugin instance '{}'", class.name))
+            })?;
+
+        let host_application = ComWrapper::new(HostApplication);
+        // SAFETY: the context outlives the component (held by the host).
+        let result = unsafe { component.initialize(host_context(&host_application)) };
+        if result != kResultOk {
+            return Err(Error::Configuration(format!(
+                "Failed to initialize plugin '{}' (result {})",
+                class.name, result
+            )));
+        }
+
+        let Some(processor) = component.cast::<IAudioProcessor>() else {
+            // SAFETY: initialized above.
+            unsafe { component.terminate() };
+            return Err(Error::Configuration(format!(
+                "Plugin '{}' is not an audio processor",
+                class.name
+            )));
+        };
+
+        let shared_state = Arc::new(ParkingLotMutex::new(SharedState::default()));
+        let mut host = Self {
+            plugin_info: PluginInfo {
+                name: class.name.clone(),
+                vendor: class.vendor.clone(),
+                version: class.version.clone(),
+                format: "VST3".to_string(),
+                id: class_id_hex(&class.cid),
+                num_inputs: 0,
+                num_outputs: 0,
+            },
+            parameter_info: Vec::new(),
+            component_handler: ComWrapper::new(ComponentHandler::new(Arc::clone(&shared_state))),
+            shared_state,
+            component,
+            processor,
+            controller: None,
+            separate_controller: false,
+            connection: None,
+            host_application,
+            factory,
+            module,
+            is_activated: false,
+            sample_rate,
+            buffer_size,
+            input_channels: 0,
+            output_channels: 0,
+            input_buffers: Vec::new(),
+            output_buffers: Vec::new(),
+            input_pointers: Vec::new(),
+            output_pointers: Vec::new(),
+            processed_samples: 0,
+        };
+
+        // From here on, Drop terminates whatever was set up.
+        host.plugin_info.num_inputs = host.main_bus_channels(kInput as BusDirection)?;
+        host.plugin_info.num_outputs = host.main_bus_channels(kOutput as BusDirection)?;
+        host.attach_controller()?;
+        host.discover_parameters();
+
+        tracing::info!(
+            "Loaded VST3 plugin '{}' by {} from {:?} ({} parameters)",
+            host.plugin_info.name,
+            host.plugin_info.vendor,
+            host.module.path(),
+            host.parameter_info.len()
+        );
+
+        Ok(host)
+    }
+
+    /// Channel count of the main audio bus in `direction`. Effects need a
+    /// main input and output; instruments (no audio input) are rejected.
+    fn main_bus_channels(&self, direction: BusDirection) -> Result<u32> {
+        let label = if direction == kInput as BusDirection {
+            "input"
+        } else {
+            "output"
+        };
+        // SAFETY: plain C struct the component fills in.
+        let mut info: BusInfo = unsafe { std::mem::zeroed() };
+        let count = unsafe { self.component.getBusCount(kAudio as MediaType, direction) };
+        if count < 1
+            || unsafe {
+                self.component
+                    .getBusInfo(kAudio as MediaType, direction, 0, &mut info)
+            } != kResultOk
+        {
+            return Err(Error::Configuration(format!(
+                "Plugin '{}' has no audio {} bus (only effects are supported)",
+                self.plugin_info.name, label
+            )));
+        }
+        Ok(info.channelCount.max(0) as u32)
+    }
+
+    /// Find the edit controller — the component itself, or a separate class
+    /// the host instantiates, connects, and syncs to the component's state.
+    fn attach_controller(&mut self) -> Result<()> {
+        let controller = if let Some(controller) = self.component.cast::<IEditController>() {
+            controller
+        } else {
+            let mut cid: TUID = [0; 16];
+            if unsafe { self.component.getControllerClassId(&mut cid) } != kResultOk {
+                tracing::warn!(
+                    "VST3 plugin '{}' has no edit controller; parameters are unavailable",
+                    self.plugin_info.name
+                );
+                return Ok(());
+            }
+            let controller: ComPtr<IEditController> = create_instance(&self.factory, &cid)
+                .ok_or_else(|| {
+                    Error::Configuration(format!(
+                        "Failed to create edit controller for '{}'",
+                        self.plugin_info.name
+                    ))
+                })?;
+            // SAFETY: the context outlives the controller (held by the host).
+            if unsafe { controller.initialize(host_context(&self.host_application)) } != kResultOk {
+                return Err(Error::Configuration(format!(
+                    "Failed to initialize edit controller for '{}'",
+                    self.plugin_info.name
+                )));
+            }
+            self.separate_controller = true;
+
+            if let (Some(component_point), Some(controller_point)) = (
+                self.component.cast::<IConnectionPoint>(),
+                controller.cast::<IConnectionPoint>(),
+            ) {
+                unsafe {
+                    component_point.connect(controller_point.as_ptr());
+                    controller_point.connect(component_point.as_ptr());
+                }
+                self.connection = Some((component_point, controller_point));
+            }
+            controller
+        };
+
+        if let Some(handler) = self.component_handler.as_com_ref::<IComponentHandler>() {
+            unsafe { controller.setComponentHandler(handler.as_ptr()) };
+        }
+
+        // The controller starts from the processor's state.
+        if let Some(component_state) =
+            read_stream(|stream| unsafe { self.component.getState(stream) })
+        {
+            write_stream(component_state, |stream| unsafe {
+                controller.setComponentState(stream)
+            });
+        }
+
+        self.controller = Some(controller);
+        Ok(())
+    }
+
+    fn discover_parameters(&mut self) {
+        let Some(controller) = &self.controller else {
+            return;
+        };
+
+        let param_count = unsafe { controller.getParameterCount() };
+        tracing::info!("Plugin has {} parameters", param_count);
+
+        let mut parameter_infos = Vec::new();
+        for i in 0..param_count {
+            // SAFETY: plain C struct the controller fills in.
+            let mut info: vst3::Steinberg::Vst::ParameterInfo = unsafe { std::mem::zeroed() };
+            if unsafe { controller.getParameterInfo(i, &mut info) } != kResultOk {
+                continue;
+            }
+
+            let value = unsafe { controller.getParamNormalized(info.id) };
+            let flags = info.flags;
+            let name = from_string128(&info.title);
+
+            tracing::debug!(
+                "Parameter {}: {} [ID={}] = {:.2} (default {:.2}, {} steps)",
+                i,
+                name,
+                info.id,
+                value,
+                info.defaultNormalizedValue,
+                info.stepCount
+            );
+
+            parameter_infos.push(ParameterInfo {
+                id: info.id,
+                name,
+                value,
+                min: 0.0,
+                max: 1.0,
+                default: info.defaultNormalizedValue,
+                is_automatable: flags & kCanAutomate != 0,
+                is_stepped: info.stepCount > 0,
+                is_periodic: flags & kIsWrapAround != 0,
+                is_hidden: flags & kIsHidden != 0,
+                is_readonly: flags & kIsReadOnly != 0,
+                is_bypass: flags & kIsBypass != 0,
+                display: self.display_value(info.id, value),
+            });
+        }
+
+        self.shared_state.lock().parameters = parameter_infos
+            .iter()
+            .map(|info| (info.id, info.value))
+            .collect();
+        self.parameter_info = parameter_infos;
+    }
+
+    fn display_value(&self, id: u32, value: f64) -> String {
+        let Some(controller) = &self.controller else {
+            return String::new();
+        };
+        let mut text: String128 = [0; 128];
+        if unsafe { controller.getParamStringByValue(id, value, &mut text) } == kResultOk {
+            from_string128(&text)
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn plugin_info(&self) -> &PluginInfo {
+        &self.plugin_info
+    }
+
+    pub fn list_parameters(&self) -> Vec<ParameterInfo> {
+        let values = self.shared_state.lock().parameters.clone();
+        self.parameter_info
+            .iter()
+            .map(|info| {
+                let value = values.get(&info.id).copied().unwrap_or(info.value);
+                ParameterInfo {
+                    value,
+                    display: self.display_value(info.id, value),
+                    ..info.clone()
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_parameter(&self, id: u32) -> Result<f64> {
+        let state = self.shared_state.lock();
+        state
+            .parameters
+            .get(&id)
+            .copied()
+            .ok_or_else(|| Error::Configuration(format!("Parameter ID {} not found", id)))
+    }
+
+    /// Set a parameter's normalized value. The controller sees it now; the
+    /// processor with the next processed block.
+    pub fn set_parameter(&mut self, id: u32, value: f64) -> Result<()> {
+        let value = value.clamp(0.0, 1.0);
+        {
+            let mut state = self.shared_state.lock();
+            if !state.parameters.contains_key(&id) {
+                return Err(Error::Configuration(format!(
+                    "Parameter ID {} not found",
+                    id
+                )));
+            }
+            state.parameters.insert(id, value);
+            state.pending.insert(id, value);
+        }
+
+        if let Some(controller) = &self.controller {
+            unsafe { controller.setParamNormalized(id, value) };
+        }
+
+        tracing::debug!(
+            "Parameter {} set to {} (will be sent during next process)",
+            id,
+            value
+        );
+
+        Ok(())
+    }
+
+    /// VST3 edit gestures run from the controller to the host, so there is
+    /// nothing to send; accepted for the shared automation surface.
+    pub fn begin_edit(&mut self, id: u32) -> Result<()> {
+        tracing::trace!("begin_edit({}) - no-op for VST3", id);
+        Ok(())
+    }
+
+    pub fn end_edit(&mut self, id: u32) -> Result<()> {
+        tracing::trace!("end_edit({}) - no-op for VST3", id);
+        Ok(())
+    }
+
+    /// Processing latency the plugin reports, in samples.
+    pub fn latency_samples(&self) -> u32 {
+        unsafe { self.processor.getLatencySamples() }
+    }
+
+    /// The plugin's complete state (processor and controller).
+    pub fn save_state(&self) -> Result<Vec<u8>> {
+        let component = read_stream(|stream| unsafe { self.component.getState(stream) })
+            .ok_or_else(|| {
+                Error::Runtime(format!(
+                    "Plugin '{}' failed to save its state",
+                    self.plugin_info.name
+                ))
+            })?;
+        let controller = self
+            .controller
+            .as_ref()
+            .and_then(|controller| read_stream(|stream| unsafe { controller.getState(stream) }))
+            .unwrap_or_default();
+
+        PluginState {
+            component,
+            controller,
+        }
+        .to_bytes()
+    }
+
+    /// Restore a state produced by [`Self::save_state`] — into the processor,
+    /// then the controller — and re-read every parameter value.
+    pub fn restore_state(&mut self, bytes: &[u8]) -> Result<()> {
+        let state = PluginState::from_bytes(bytes)?;
+
+        let result = write_stream(state.component.clone(), |stream| unsafe {
+            self.component.setState(stream)
+        });
+        if result != kResultOk {
+            return Err(Error::Runtime(format!(
+                "Plugin '{}' rejected the saved state (result {})",
+                self.plugin_info.name, result
+            )));
+        }
+
+        if let Some(controller) = &self.controller {
+            write_stream(state.component, |stream| unsafe {
+                controller.setComponentState(stream)
+            });
+            if !state.controller.is_empty() {
+                write_stream(state.controller, |stream| unsafe {
+                    controller.setState(stream)
+                });
+            }
+
+            // The processor has the restored values; only the mirror needs them.
+            let mut shared = self.shared_state.lock();
+            for info in &self.parameter_info {
+                let value = unsafe { controller.getParamNormalized(info.id) };
+                shared.parameters.insert(info.id, value);
+                shared.pending.remove(&info.id);
+            }
+        }
+
+        tracing::info!(
+            "Restored VST3 plugin '{}' state ({} bytes)",
+            self.plugin_info.name,
+            bytes.len()
+        );
+        Ok(())
+    }
+
+    pub fn activate(&mut self, sample_rate: u32, max_frames: usize) -> Result<()> {
+        if self.is_activated {
+            tracing::debug!("Plugin already activated, skipping");
+            return Ok(());
+        }
+
+        if unsafe { self.processor.canProcessSampleSize(kSample32 as i32) } != kResultTrue {
+            return Err(Error::Configuration(format!(
+                "Plugin '{}' cannot process 32-bit float audio",
+                self.plugin_info.name
+            )));
+        }
+
+        self.negotiate_stereo();
+        self.input_channels = self.main_bus_arrangement_channels(kInput as BusDirection)?;
+        self.output_channels = self.main_bus_arrangement_channels(kOutput as BusDirection)?;
+
+        for direction in [kInput as BusDirection, kOutput as BusDirection] {
+            let bus_count = unsafe { self.component.getBusCount(kAudio as MediaType, direction) };
+            for index in 0..bus_count {
+                // Only the main buses are fed; side-chains stay off.
+                let state = u8::from(index == 0);
+                unsafe {
+                    self.component
+                        .activateBus(kAudio as MediaType, direction, index, state)
+                };
+            }
+        }
+
+        let mut setup = ProcessSetup {
+            processMode: kRealtime as i32,
+            symbolicSampleSize: kSample32 as i32,
+            maxSamplesPerBlock: max_frames as i32,
+            sampleRate: sample_rate as f64,
+        };
+        let result = unsafe { self.processor.setupProcessing(&mut setup) };
+        if result != kResultOk {
+            return Err(Error::Configuration(format!(
+                "Plugin '{}' rejected {}Hz / {} frames (result {})",
+                self.plugin_info.name, sample_rate, max_frames, result
+            )));
+        }
+
+        let result = unsafe { self.component.setActive(1) };
+        if result != kResultOk {
+            return Err(Error::Configuration(format!(
+                "Failed to activate plugin '{}' (result {})",
+                self.plugin_info.name, result
+            )));
+        }
+        // Plenty of plugins return kNotImplemented here; that's not a failure.
+        unsafe { self.processor.setProcessing(1) };
+
+        self.is_activated = true;
+        self.sample_rate = sample_rate;
+        self.buffer_size = max_frames;
+        self.processed_samples = 0;
+
+        self.input_buffers = vec![vec![0.0; max_frames]; self.input_channels];
+        self.output_buffers = vec![vec![0.0; max_frames]; self.output_channels];
+        self.input_pointers = self
+            .input_buffers
+            .iter_mut()
+            .map(|buf| buf.as_mut_ptr())
+            .collect();
+        self.output_pointers = self
+            .output_buffers
+            .iter_mut()
+            .map(|buf| buf.as_mut_ptr())
+            .collect();
+
+        tracing::info!(
+            "✅ Activated VST3 plugin '{}' at {}Hz, {} max frames ({} in / {} out channels, {} samples latency)",
+            self.plugin_info.name,
+            sample_rate,
+            max_frames,
+            self.input_channels,
+            self.output_channels,
+            self.latency_samples()
+        );
+
+        Ok(())
+    }
+
+    /// Ask for stereo main buses, keeping any side-chain bus arrangements.
+    /// A refusal leaves the plugin's own arrangement, mono included.
+    fn negotiate_stereo(&self) {
+        let arrangements = |direction: BusDirection| -> Vec<SpeakerArrangement> {
+            let count = unsafe { self.component.getBusCount(kAudio as MediaType, direction) };
+            (0..count)
+                .map(|index| {
+                    let mut arrangement: SpeakerArrangement = SpeakerArr::kStereo;
+                    if index > 0 {
+                        unsafe {
+                            self.processor
+                                .getBusArrangement(direction, index, &mut arrangement)
+                        };
+                    }
+                    arrangement
+                })
+                .collect()
+        };
+        let mut inputs = arrangements(kInput as BusDirection);
+        let mut outputs = arrangements(kOutput as BusDirection);
+
+        let result: tresult = unsafe {
+            self.processor.setBusArrangements(
+                inputs.as_mut_ptr(),
+                inputs.len() as i32,
+                outputs.as_mut_ptr(),
+                outputs.len() as i32,
+            )
+        };
+        if result != kResultTrue {
+            tracing::debug!(
+                "VST3 plugin '{}' refused stereo buses; using its own arrangement",
+                self.plugin_info.name
+            );
+        }
+    }
+
+    fn main_bus_arrangement_channels(&self, direction: BusDirection) -> Result<usize> {
+        let mut arrangement: SpeakerArrangement = 0;
+        unsafe {
+            self.processor
+                .getBusArrangement(direction, 0, &mut arrangement)
+        };
+        match arrangement.count_ones() {
+            channels @ (1 | 2) => Ok(channels as usize),
+            channels => Err(Error::Configuration(format!(
+                "Plugin '{}' main bus has {} channels; only mono and stereo are supported",
+                self.plugin_info.name, channels
+            ))),
+        }
+    }
+
+    pub fn deactivate(&mut self) -> Result<()> {
+        if !self.is_activated {
+            return Ok(());
+        }
+
+        unsafe {
+            self.processor.setProcessing(0);
+            self.component.setActive(0);
+        }
+
+        self.is_activated = false;
+
+        tracing::info!("✅ Deactivated VST3 plugin '{}'", self.plugin_info.name);
+
+        Ok(())
+    }
+
+    pub fn process_audio(&mut self, input: &AudioFrame) -> Result<AudioFrame> {
+        if !self.is_activated {
+            return Err(Error::Configuration("Plugin not activated".into()));
+        }
+
+        let num_samples = input.samples.len() / input.channels as usize;
+        if num_samples > self.buffer_size {
+            return Err(Error::Runtime(format!(
+                "Frame of {} samples exceeds the {} activated with",
+                num_samples, self.buffer_size
+            )));
+        }
+
+        for i in 0..num_samples {
+            let base_idx = i * 2; // 2 channels (stereo)
+            let (left, right) = (input.samples[base_idx], input.samples[base_idx + 1]);
+            if self.input_channels == 2 {
+                self.input_buffers[0][i] = left;
+                self.input_buffers[1][i] = right;
+            } else {
+                self.input_buffers[0][i] = 0.5 * (left + right);
+            }
+        }
+
+        self.process_audio_channels_inplace(num_samples)?;
+
+        let output_len = num_samples * 2;
+        let mut output_samples = Vec::with_capacity(output_len);
+        let right_channel = self.output_channels - 1;
+        for i in 0..num_samples {
+            output_samples.push(self.output_buffers[0][i]);
+            output_samples.push(self.output_buffers[right_channel][i]);
+        }
+
+        Ok(AudioFrame {
+            samples: output_samples,
+            channels: input.channels,
+            timestamp_ns: input.timestamp_ns.clone(),
+            frame_index: input.frame_index.clone(),
+            sample_rate: input.sample_rate,
+            channel_layout: input.channel_layout.clone(),
+        })
+    }
+
+    fn process_audio_channels_inplace(&mut self, num_samples: usize) -> Result<()> {
+        let mut input_bus = AudioBusBuffers {
+            numChannels: self.input_channels as i32,
+            silenceFlags: 0,
+            __field0: AudioBusBuffers__type0 {
+                channelBuffers32: self.input_pointers.as_mut_ptr(),
+            },
+        };
+        let mut output_bus = AudioBusBuffers {
+            numChannels: self.output_channels as i32,
+            silenceFlags: 0,
+            __field0: AudioBusBuffers__type0 {
+                channelBuffers32: self.output_pointers.as_mut_ptr(),
+            },
+        };
+
+        let pending: Vec<(u32, f64)> = self.shared_state.lock().pending.drain().collect();
+        let parameter_changes =
+            (!pending.is_empty()).then(|| ComWrapper::new(ParameterChanges::new(pending)));
+
+        // SAFETY: plain C struct; the fields used are set below.
+        let mut context: ProcessContext = unsafe { std::mem::zeroed() };
+        context.state = kPlaying | kTempoValid | kTimeSigValid | kContTimeValid;
+        context.sampleRate = self.sample_rate as f64;
+        context.projectTimeSamples = self.processed_samples;
+        context.continousTimeSamples = self.processed_samples;
+        context.tempo = 120.0;
+        context.timeSigNumerator = 4;
+        context.timeSigDenominator = 4;
+
+        let mut data = ProcessData {
+            processMode: kRealtime as i32,
+            symbolicSampleSize: kSample32 as i32,
+            numSamples: num_samples as i32,
+            numInputs: 1,
+            numOutputs: 1,
+            inputs: &mut input_bus,
+            outputs: &mut output_bus,
+            inputParameterChanges: parameter_changes
+                .as_ref()
+                .and_then(|changes| changes.as_com_ref())
+                .map_or(std::ptr::null_mut(), |changes| changes.as_ptr()),
+            outputParameterChanges: std::ptr::null_mut(),
+            inputEvents: std::ptr::null_mut(),
+            outputEvents: std::ptr::null_mut(),
+            processContext: &mut context,
+        };
+
+        let result = unsafe { self.processor.process(&mut data) };
+        if result != kResultOk {
+            return Err(Error::Runtime(format!(
+                "Plugin processing failed (result {})",
+                result
+            )));
+        }
+
+        self.processed_samples += num_samples as i64;
+        Ok(())
+    }
+}
+
+impl Drop for Vst3PluginHost {
+    fn drop(&mut self) {
+        if let Err(e) = self.deactivate() {
+            tracing::warn!(
+                "VST3 plugin '{}' deactivate failed: {}",
+                self.plugin_info.name,
+                e
+            );
+        }
+
+        unsafe {
+            if let Some((component_point, controller_point)) = self.connection.take() {
+                component_point.disconnect(controller_point.as_ptr());
+                controller_point.disconnect(component_point.as_ptr());
+            }
+            if let Some(controller) = self.controller.take() {
+                controller.setComponentHandler(std::ptr::null_mut());
+                if self.separate_controller {
+                    controller.terminate();
+                }
+            }
+            self.component.terminate();
+        }
+    }
+}
+
+/// The host application as the `FUnknown` context `initialize` takes.
+fn host_context(host_application: &ComWrapper<HostApplication>) -> *mut FUnknown {
+    host_application
+        .as_com_ref::<IHostApplication>()
+        .map_or(std::ptr::null_mut(), |host| host.as_ptr() as *mut FUnknown)
+}
+
+fn create_instance<I: Interface>(
+    factory: &ComPtr<IPluginFactory>,
+    cid: &TUID,
+) -> Option<ComPtr<I>> {
+    let mut instance = std::ptr::null_mut();
+    let result =
+        unsafe { factory.createInstance(cid.as_ptr(), I::IID.as_ptr() as *const _, &mut instance) };
+    if result != kResultOk {
+        return None;
+    }
+    // SAFETY: createInstance hands out an owned reference to `I`.
+    unsafe { ComPtr::from_raw(instance as *mut I) }
+}
+
+/// Run `get` against a fresh memory stream; the bytes written on success.
+fn read_stream(get: impl FnOnce(*mut IBStream) -> tresult) -> Option<Vec<u8>> {
+    let stream = ComWrapper::new(MemoryStream::new());
+    let result = get(stream.as_com_ref::<IBStream>()?.as_ptr());
+    (result == kResultOk).then(|| stream.bytes())
+}
+
+/// Run `set` against a memory stream holding `bytes`.
+fn write_stream(bytes: Vec<u8>, set: impl FnOnce(*mut IBStream) -> tresult) -> tresult {
+    let stream = ComWrapper::new(MemoryStream::from_bytes(bytes));
+    match stream.as_com_ref::<IBStream>() {
+        Some(ptr) => set(ptr.as_ptr()),
+        None => vst3::Steinberg::kInternalError,
+    }
+}
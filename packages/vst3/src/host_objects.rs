@@ -0,0 +1,422 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! COM objects the host hands to a VST3 plugin — the host application
+//! context, the component handler that receives controller edits, the
+//! in-memory stream state is saved to and restored from, and the parameter
+//! changes delivered with each processed block.
+
+use std::ffi::c_void;
+use std::sync::Arc;
+
+use parking_lot::Mutex as ParkingLotMutex;
+use vst3::Steinberg::IBStream_::IStreamSeekMode_::{kIBSeekCur, kIBSeekEnd, kIBSeekSet};
+use vst3::Steinberg::Vst::{
+    IComponentHandler, IComponentHandlerTrait, IHostApplication, IHostApplicationTrait,
+    IParamValueQueue, IParamValueQueueTrait, IParameterChanges, IParameterChangesTrait, ParamID,
+    ParamValue, String128,
+};
+use vst3::Steinberg::{
+    IBStream, IBStreamTrait, TUID, int32, int64, kInvalidArgument, kResultFalse, kResultOk, tresult,
+};
+use vst3::{Class, ComWrapper};
+
+use crate::host::SharedState;
+
+const HOST_NAME: &str = "streamlib";
+
+/// `IHostApplication` — the context passed to `initialize`.
+pub struct HostApplication;
+
+impl Class for HostApplication {
+    type Interfaces = (IHostApplication,);
+}
+
+impl IHostApplicationTrait for HostApplication {
+    unsafe fn getName(&self, name: *mut String128) -> tresult {
+        if name.is_null() {
+            return kInvalidArgument;
+        }
+        // SAFETY: the plugin passes a writable String128.
+        let name = unsafe { &mut *name };
+        name.fill(0);
+        for (dst, src) in name.iter_mut().take(127).zip(HOST_NAME.encode_utf16()) {
+            *dst = src;
+        }
+        kResultOk
+    }
+
+    unsafe fn createInstance(
+        &self,
+        _cid: *mut TUID,
+        _iid: *mut TUID,
+        obj: *mut *mut c_void,
+    ) -> tresult {
+        // No IMessage / IAttributeList: headless hosting never routes
+        // messages other than through the direct component ↔ controller
+        // connection.
+        if !obj.is_null() {
+            // SAFETY: the plugin passes a writable out-pointer.
+            unsafe { *obj = std::ptr::null_mut() };
+        }
+        kResultFalse
+    }
+}
+
+/// `IComponentHandler` — controller-side edits (from plugin logic or a
+/// restored state) land in the shared parameter table and are forwarded to
+/// the processor with the next block, as VST3 requires of the host.
+pub struct ComponentHandler {
+    shared: Arc<ParkingLotMutex<SharedState>>,
+}
+
+impl ComponentHandler {
+    pub(crate) fn new(shared: Arc<ParkingLotMutex<SharedState>>) -> Self {
+        Self { shared }
+    }
+}
+
+impl Class for ComponentHandler {
+    type Interfaces = (IComponentHandler,);
+}
+
+impl IComponentHandlerTrait for ComponentHandler {
+    unsafe fn beginEdit(&self, _id: ParamID) -> tresult {
+        kResultOk
+    }
+
+    unsafe fn performEdit(&self, id: ParamID, value_normalized: ParamValue) -> tresult {
+        let mut state = self.shared.lock();
+        state.parameters.insert(id, value_normalized);
+        state.pending.insert(id, value_normalized);
+        kResultOk
+    }
+
+    unsafe fn endEdit(&self, _id: ParamID) -> tresult {
+        kResultOk
+    }
+
+    unsafe fn restartComponent(&self, flags: int32) -> tresult {
+        tracing::debug!("[Vst3] Plugin requested restart (flags {:#x})", flags);
+        kResultOk
+    }
+}
+
+#[derive(Default)]
+struct StreamBuffer {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+/// `IBStream` over a growable byte buffer.
+#[derive(Default)]
+pub struct MemoryStream {
+    buffer: ParkingLotMutex<StreamBuffer>,
+}
+
+impl MemoryStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A stream positioned at the start of `bytes`.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            buffer: ParkingLotMutex::new(StreamBuffer { bytes, position: 0 }),
+        }
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        self.buffer.lock().bytes.clone()
+    }
+}
+
+impl Class for MemoryStream {
+    type Interfaces = (IBStream,);
+}
+
+impl IBStreamTrait for MemoryStream {
+    unsafe fn read(
+        &self,
+        buffer: *mut c_void,
+        num_bytes: int32,
+        num_bytes_read: *mut int32,
+    ) -> tresult {
+        if (buffer.is_null() && num_bytes > 0) || num_bytes < 0 {
+            return kInvalidArgument;
+        }
+        let mut stream = self.buffer.lock();
+        let start = stream.position.min(stream.bytes.len());
+        let count = (num_bytes as usize).min(stream.bytes.len() - start);
+        if count > 0 {
+            // SAFETY: the plugin passes a buffer of at least `num_bytes`.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    stream.bytes[start..].as_ptr(),
+                    buffer as *mut u8,
+                    count,
+                )
+            };
+        }
+        stream.position = start + count;
+        if !num_bytes_read.is_null() {
+            // SAFETY: optional out-pointer, checked above.
+            unsafe { *num_bytes_read = count as int32 };
+        }
+        kResultOk
+    }
+
+    unsafe fn write(
+        &self,
+        buffer: *mut c_void,
+        num_bytes: int32,
+        num_bytes_written: *mut int32,
+    ) -> tresult {
+        if (buffer.is_null() && num_bytes > 0) || num_bytes < 0 {
+            return kInvalidArgument;
+        }
+        let count = num_bytes as usize;
+        let mut stream = self.buffer.lock();
+        let start = stream.position;
+        let end = start + count;
+        if stream.bytes.len() < end {
+            stream.bytes.resize(end, 0);
+        }
+        if count > 0 {
+            // SAFETY: the plugin passes a buffer of `num_bytes`.
+            let source = unsafe { std::slice::from_raw_parts(buffer as *const u8, count) };
+            stream.bytes[start..end].copy_from_slice(source);
+        }
+        stream.position = end;
+        if !num_bytes_written.is_null() {
+            // SAFETY: optional out-pointer, checked above.
+            unsafe { *num_bytes_written = num_bytes };
+        }
+        kResultOk
+    }
+
+    unsafe fn seek(&self, pos: int64, mode: int32, result: *mut int64) -> tresult {
+        let mut stream = self.buffer.lock();
+        let base = match mode {
+            mode if mode == kIBSeekSet as int32 => 0,
+            mode if mode == kIBSeekCur as int32 => stream.position as int64,
+            mode if mode == kIBSeekEnd as int32 => stream.bytes.len() as int64,
+            _ => return kInvalidArgument,
+        };
+        let Some(position) = base.checked_add(pos).filter(|&position| position >= 0) else {
+            return kInvalidArgument;
+        };
+        stream.position = position as usize;
+        if !result.is_null() {
+            // SAFETY: optional out-pointer, checked above.
+            unsafe { *result = position };
+        }
+        kResultOk
+    }
+
+    unsafe fn tell(&self, pos: *mut int64) -> tresult {
+        if pos.is_null() {
+            return kInvalidArgument;
+        }
+        // SAFETY: checked above.
+        unsafe { *pos = self.buffer.lock().position as int64 };
+        kResultOk
+    }
+}
+
+/// One parameter's value for a block, applied from its first sample.
+pub struct ParamValueQueue {
+    id: ParamID,
+    value: ParamValue,
+}
+
+impl Class for ParamValueQueue {
+    type Interfaces = (IParamValueQueue,);
+}
+
+impl IParamValueQueueTrait for ParamValueQueue {
+    unsafe fn getParameterId(&self) -> ParamID {
+        self.id
+    }
+
+    unsafe fn getPointCount(&self) -> int32 {
+        1
+    }
+
+    unsafe fn getPoint(
+        &self,
+        index: int32,
+        sample_offset: *mut int32,
+        value: *mut ParamValue,
+    ) -> tresult {
+        if index != 0 || sample_offset.is_null() || value.is_null() {
+            return kInvalidArgument;
+        }
+        // SAFETY: checked above.
+        unsafe {
+            *sample_offset = 0;
+            *value = self.value;
+        }
+        kResultOk
+    }
+
+    unsafe fn addPoint(
+        &self,
+        _sample_offset: int32,
+        _value: ParamValue,
+        _index: *mut int32,
+    ) -> tresult {
+        kResultFalse
+    }
+}
+
+/// `IParameterChanges` for one block's input. Read-only: the plugin's
+/// output changes are not collected.
+pub struct ParameterChanges {
+    queues: Vec<ComWrapper<ParamValueQueue>>,
+}
+
+impl ParameterChanges {
+    pub fn new(changes: impl IntoIterator<Item = (ParamID, ParamValue)>) -> Self {
+        Self {
+            queues: changes
+                .into_iter()
+                .map(|(id, value)| ComWrapper::new(ParamValueQueue { id, value }))
+                .collect(),
+        }
+    }
+}
+
+impl Class for ParameterChanges {
+    type Interfaces = (IParameterChanges,);
+}
+
+impl IParameterChangesTrait for ParameterChanges {
+    unsafe fn getParameterCount(&self) -> int32 {
+        self.queues.len() as int32
+    }
+
+    unsafe fn getParameterData(&self, index: int32) -> *mut IParamValueQueue {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.queues.get(index))
+            .and_then(|queue| queue.as_com_ref::<IParamValueQueue>())
+            .map_or(std::ptr::null_mut(), |queue| queue.as_ptr())
+    }
+
+    unsafe fn addParameterData(
+        &self,
+        _id: *const ParamID,
+        _index: *mut int32,
+    ) -> *mut IParamValueQueue {
+        std::ptr::null_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vst3::Steinberg::IBStream_::IStreamSeekMode;
+    use vst3::{ComPtr, ComRef};
+
+    fn stream_ptr(stream: &ComWrapper<MemoryStream>) -> ComPtr<IBStream> {
+        stream.to_com_ptr::<IBStream>().unwrap()
+    }
+
+    #[test]
+    fn memory_stream_round_trips_through_the_com_interface() {
+        let stream = ComWrapper::new(MemoryStream::new());
+        let ptr = stream_ptr(&stream);
+
+        let mut payload = *b"preset";
+        let mut written = 0;
+        unsafe {
+            assert_eq!(
+                ptr.write(payload.as_mut_ptr() as *mut c_void, 6, &mut written),
+                kResultOk
+            );
+        }
+        assert_eq!(written, 6);
+        assert_eq!(stream.bytes(), b"preset");
+
+        let mut position = -1;
+        unsafe {
+            assert_eq!(
+                ptr.seek(-3, kIBSeekEnd as IStreamSeekMode as int32, &mut position),
+                kResultOk
+            );
+        }
+        assert_eq!(position, 3);
+
+        let mut read_back = [0u8; 8];
+        let mut read = 0;
+        unsafe {
+            ptr.read(read_back.as_mut_ptr() as *mut c_void, 8, &mut read);
+        }
+        assert_eq!(&read_back[..read as usize], b"set");
+
+        // Reading at the end yields zero bytes, not an error.
+        unsafe {
+            assert_eq!(
+                ptr.read(read_back.as_mut_ptr() as *mut c_void, 8, &mut read),
+                kResultOk
+            );
+        }
+        assert_eq!(read, 0);
+        unsafe {
+            assert_eq!(
+                ptr.seek(
+                    -1,
+                    kIBSeekSet as IStreamSeekMode as int32,
+                    std::ptr::null_mut()
+                ),
+                kInvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn writes_past_the_end_grow_the_stream() {
+        let stream = ComWrapper::new(MemoryStream::from_bytes(b"ab".to_vec()));
+        let ptr = stream_ptr(&stream);
+        let mut tail = *b"cd";
+        unsafe {
+            ptr.seek(
+                4,
+                kIBSeekSet as IStreamSeekMode as int32,
+                std::ptr::null_mut(),
+            );
+            ptr.write(tail.as_mut_ptr() as *mut c_void, 2, std::ptr::null_mut());
+        }
+        assert_eq!(stream.bytes(), b"ab\0\0cd");
+    }
+
+    #[test]
+    fn parameter_changes_expose_one_point_per_parameter() {
+        let changes = ComWrapper::new(ParameterChanges::new([(7, 0.25), (9, 1.0)]));
+        let ptr = changes.to_com_ptr::<IParameterChanges>().unwrap();
+
+        unsafe {
+            assert_eq!(ptr.getParameterCount(), 2);
+            assert!(ptr.getParameterData(2).is_null());
+
+            // getParameterData doesn't hand out a reference.
+            let queue = ComRef::from_raw(ptr.getParameterData(0)).unwrap();
+            assert_eq!(queue.getParameterId(), 7);
+            assert_eq!(queue.getPointCount(), 1);
+            let (mut offset, mut value) = (-1, 0.0);
+            assert_eq!(queue.getPoint(0, &mut offset, &mut value), kResultOk);
+            assert_eq!((offset, value), (0, 0.25));
+            assert_eq!(queue.getPoint(1, &mut offset, &mut value), kInvalidArgument);
+        }
+    }
+
+    #[test]
+    fn host_application_reports_its_name() {
+        let host = ComWrapper::new(HostApplication);
+        let ptr = host.to_com_ptr::<IHostApplication>().unwrap();
+        let mut name: String128 = [0; 128];
+        unsafe { assert_eq!(ptr.getName(&mut name), kResultOk) };
+        assert_eq!(crate::strings::from_string128(&name), HOST_NAME);
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/vst3` — VST3 audio plugin host processor for streamlib.
+//!
+//! Linux and macOS. Hosts audio effects (a main audio input and output);
+//! instruments and plugin editors are out of scope.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod host;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod host_objects;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod module;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod scanner;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod state;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod strings;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub mod vst3_effect;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use host::Vst3PluginHost;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use scanner::{Vst3PluginInfo, Vst3Scanner};
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use state::PluginState;
+// The format-agnostic hosting surface lives in `streamlib-audio`, shared with
+// the CLAP host.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use streamlib_audio::plugin_host::{
+    LfoWaveform, ParameterAutomation, ParameterInfo, ParameterModulator, PluginInfo,
+    PluginParameterControl,
+};
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use vst3_effect::Vst3EffectProcessor;
+
+pub use _generated_::Vst3EffectConfig;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+streamlib_plugin_abi::export_plugin!(crate::Vst3EffectProcessor::Processor);
@@ -0,0 +1,247 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! A loaded VST3 module — the shared library inside a `.vst3` bundle, its
+//! platform entry/exit calls, and the plugin factory it exports.
+
+use std::path::{Path, PathBuf};
+
+use libloading::os::unix::Library;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use vst3::ComPtr;
+use vst3::Steinberg::IPluginFactory;
+
+type GetPluginFactoryFn = unsafe extern "system" fn() -> *mut IPluginFactory;
+
+/// Resolve the module binary inside a `.vst3` bundle. Paths that are already
+/// a file (a bare module binary) are returned unchanged.
+pub fn bundle_binary_path(bundle_path: &Path) -> Result<PathBuf> {
+    if bundle_path.is_file() {
+        return Ok(bundle_path.to_path_buf());
+    }
+
+    let binary_path = bundle_binary_candidate(bundle_path)?;
+    if binary_path.exists() {
+        Ok(binary_path)
+    } else {
+        Err(Error::Configuration(format!(
+            "Binary not found in bundle: {:?}",
+            binary_path
+        )))
+    }
+}
+
+/// Where the bundle layout places the module binary for this platform, per
+/// the VST3 module architecture.
+fn bundle_binary_candidate(bundle_path: &Path) -> Result<PathBuf> {
+    let binary_name = bundle_path
+        .file_stem()
+        .ok_or_else(|| Error::Configuration("Invalid bundle path".into()))?;
+
+    #[cfg(target_os = "macos")]
+    {
+        Ok(bundle_path.join("Contents").join("MacOS").join(binary_name))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let mut file_name = binary_name.to_os_string();
+        file_name.push(".so");
+        Ok(bundle_path
+            .join("Contents")
+            .join(format!("{}-linux", std::env::consts::ARCH))
+            .join(file_name))
+    }
+}
+
+/// The module's shared library, entered for as long as this value lives.
+/// Every COM object created from [`Vst3Module::factory`] must be released
+/// before the module is dropped.
+pub struct Vst3Module {
+    path: PathBuf,
+    #[cfg(target_os = "macos")]
+    bundle: Option<core_foundation::bundle::CFBundle>,
+    library: Library,
+    /// Whether the entry point ran, so the exit point is owed.
+    entered: bool,
+}
+
+// SAFETY: the module handle is only used to look up symbols and is never
+// shared between threads concurrently — `Vst3PluginHost` owns it.
+unsafe impl Send for Vst3Module {}
+
+impl Vst3Module {
+    pub fn load(bundle_path: &Path) -> Result<Self> {
+        let binary_path = bundle_binary_path(bundle_path)?;
+
+        // SAFETY: loading a VST3 module is inherently unsafe — it runs the
+        // library's initializers.
+        let library = unsafe { Library::new(&binary_path) }.map_err(|e| {
+            Error::Configuration(format!(
+                "Failed to load VST3 module from {:?}: {}",
+                binary_path, e
+            ))
+        })?;
+
+        let mut module = Self {
+            path: bundle_path.to_path_buf(),
+            #[cfg(target_os = "macos")]
+            bundle: None,
+            library,
+            entered: false,
+        };
+        module.enter()?;
+        Ok(module)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// `ModuleEntry` (Linux) / `bundleEntry` (macOS). Modules built before
+    /// the entry points became mandatory don't export them.
+    #[cfg(not(target_os = "macos"))]
+    fn enter(&mut self) -> Result<()> {
+        type ModuleEntryFn = unsafe extern "C" fn(*mut std::ffi::c_void) -> bool;
+
+        // SAFETY: signature per the VST3 module architecture.
+        let entry = match unsafe { self.library.get::<ModuleEntryFn>(b"ModuleEntry\0") } {
+            Ok(entry) => *entry,
+            Err(_) => {
+                tracing::debug!("[Vst3] {:?} exports no ModuleEntry", self.path);
+                return Ok(());
+            }
+        };
+
+        // ModuleEntry takes the dlopen handle; take it out of the library and
+        // wrap it again without closing it.
+        let library = std::mem::replace(&mut self.library, Library::this());
+        let handle = library.into_raw();
+        // SAFETY: `handle` came from `into_raw` just above.
+        self.library = unsafe { Library::from_raw(handle) };
+
+        // SAFETY: the library stays loaded for the lifetime of `self`.
+        if unsafe { entry(handle) } {
+            self.entered = true;
+            Ok(())
+        } else {
+            Err(Error::Configuration(format!(
+                "VST3 ModuleEntry failed for {:?}",
+                self.path
+            )))
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn enter(&mut self) -> Result<()> {
+        use core_foundation::base::TCFType;
+        use core_foundation::bundle::{CFBundle, CFBundleRef};
+        use core_foundation::url::CFURL;
+
+        type BundleEntryFn = unsafe extern "C" fn(CFBundleRef) -> bool;
+
+        // SAFETY: signature per the VST3 module architecture.
+        let entry = match unsafe { self.library.get::<BundleEntryFn>(b"bundleEntry\0") } {
+            Ok(entry) => *entry,
+            Err(_) => {
+                tracing::debug!("[Vst3] {:?} exports no bundleEntry", self.path);
+                return Ok(());
+            }
+        };
+
+        let bundle = CFURL::from_path(&self.path, true)
+            .and_then(CFBundle::new)
+            .ok_or_else(|| {
+                Error::Configuration(format!("{:?} is not a loadable bundle", self.path))
+            })?;
+
+        // SAFETY: the bundle reference outlives the module (held in `self`).
+        if unsafe { entry(bundle.as_concrete_TypeRef()) } {
+            self.bundle = Some(bundle);
+            self.entered = true;
+            Ok(())
+        } else {
+            Err(Error::Configuration(format!(
+                "VST3 bundleEntry failed for {:?}",
+                self.path
+            )))
+        }
+    }
+
+    /// The module's plugin factory (`GetPluginFactory`).
+    pub fn factory(&self) -> Result<ComPtr<IPluginFactory>> {
+        // SAFETY: signature per the VST3 module architecture.
+        let get_factory = unsafe {
+            self.library
+                .get::<GetPluginFactoryFn>(b"GetPluginFactory\0")
+        }
+        .map_err(|e| {
+            Error::Configuration(format!(
+                "{:?} is not a VST3 module (no GetPluginFactory): {}",
+                self.path, e
+            ))
+        })?;
+
+        // SAFETY: GetPluginFactory returns an owned reference (or null).
+        unsafe { ComPtr::from_raw(get_factory()) }
+            .ok_or_else(|| Error::Configuration("VST3 module returned no plugin factory".into()))
+    }
+}
+
+impl Drop for Vst3Module {
+    fn drop(&mut self) {
+        #[cfg(not(target_os = "macos"))]
+        const EXIT_SYMBOL: &[u8] = b"ModuleExit\0";
+        #[cfg(target_os = "macos")]
+        const EXIT_SYMBOL: &[u8] = b"bundleExit\0";
+
+        if !self.entered {
+            return;
+        }
+
+        // SAFETY: signature per the VST3 module architecture; called once,
+        // after every object from the factory has been released.
+        if let Ok(exit) = unsafe {
+            self.library
+                .get::<unsafe extern "C" fn() -> bool>(EXIT_SYMBOL)
+        } && !unsafe { exit() }
+        {
+            tracing::warn!("[Vst3] Module exit failed for {:?}", self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn linux_bundles_resolve_to_the_arch_directory() {
+        let binary = bundle_binary_candidate(Path::new("/usr/lib/vst3/Reverb.vst3")).unwrap();
+        assert_eq!(
+            binary,
+            PathBuf::from(format!(
+                "/usr/lib/vst3/Reverb.vst3/Contents/{}-linux/Reverb.so",
+                std::env::consts::ARCH
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn macos_bundles_resolve_to_contents_macos() {
+        let binary =
+            bundle_binary_candidate(Path::new("/Library/Audio/Plug-Ins/VST3/Reverb.vst3")).unwrap();
+        assert_eq!(
+            binary,
+            PathBuf::from("/Library/Audio/Plug-Ins/VST3/Reverb.vst3/Contents/MacOS/Reverb")
+        );
+    }
+
+    #[test]
+    fn missing_bundles_are_a_configuration_error() {
+        assert!(bundle_binary_path(Path::new("/nonexistent/Missing.vst3")).is_err());
+        assert!(Vst3Module::load(Path::new("/nonexistent/Missing.vst3")).is_err());
+    }
+}
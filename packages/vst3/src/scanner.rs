@@ -0,0 +1,223 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::path::{Path, PathBuf};
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use vst3::ComPtr;
+use vst3::Steinberg::{
+    IPluginFactory, IPluginFactory2, IPluginFactory2Trait, IPluginFactoryTrait, PClassInfo,
+    PClassInfo2, PFactoryInfo, TUID, kResultOk,
+};
+
+use crate::module::Vst3Module;
+use crate::strings::{class_id_hex, from_char8};
+
+/// Factory category of audio processor components (`kVstAudioEffectClass`).
+pub const AUDIO_EFFECT_CLASS: &str = "Audio Module Class";
+
+#[derive(Debug, Clone)]
+pub struct Vst3PluginInfo {
+    pub path: PathBuf,
+
+    /// Processor component class ID, as 32 hex digits.
+    pub id: String,
+
+    pub name: String,
+
+    pub vendor: String,
+
+    pub version: String,
+
+    /// `|`-separated VST3 sub-categories (`Fx`, `Reverb`, `Instrument`, ...).
+    pub sub_categories: Vec<String>,
+}
+
+/// An audio processor class in a module's factory.
+#[derive(Debug, Clone)]
+pub(crate) struct AudioClass {
+    pub cid: TUID,
+    pub name: String,
+    pub vendor: String,
+    pub version: String,
+    pub sub_categories: Vec<String>,
+}
+
+/// The factory's audio processor classes, in factory order. Vendor and
+/// version come from `PClassInfo2` when the factory has it, else from the
+/// factory info.
+pub(crate) fn audio_classes(factory: &ComPtr<IPluginFactory>) -> Vec<AudioClass> {
+    // SAFETY: PFactoryInfo / PClassInfo / PClassInfo2 are plain C structs
+    // the factory fills in.
+    let mut factory_info: PFactoryInfo = unsafe { std::mem::zeroed() };
+    let factory_vendor = if unsafe { factory.getFactoryInfo(&mut factory_info) } == kResultOk {
+        from_char8(&factory_info.vendor)
+    } else {
+        String::new()
+    };
+    let factory2 = factory.cast::<IPluginFactory2>();
+
+    let mut classes = Vec::new();
+    for index in 0..unsafe { factory.countClasses() } {
+        let mut info: PClassInfo = unsafe { std::mem::zeroed() };
+        if unsafe { factory.getClassInfo(index, &mut info) } != kResultOk
+            || from_char8(&info.category) != AUDIO_EFFECT_CLASS
+        {
+            continue;
+        }
+
+        let mut class = AudioClass {
+            cid: info.cid,
+            name: from_char8(&info.name),
+            vendor: factory_vendor.clone(),
+            version: String::new(),
+            sub_categories: Vec::new(),
+        };
+
+        if let Some(factory2) = &factory2 {
+            let mut info2: PClassInfo2 = unsafe { std::mem::zeroed() };
+            if unsafe { factory2.getClassInfo2(index, &mut info2) } == kResultOk {
+                let vendor = from_char8(&info2.vendor);
+                if !vendor.is_empty() {
+                    class.vendor = vendor;
+                }
+                class.version = from_char8(&info2.version);
+                class.sub_categories = from_char8(&info2.subCategories)
+                    .split('|')
+                    .filter(|category| !category.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+        }
+
+        classes.push(class);
+    }
+    classes
+}
+
+pub struct Vst3Scanner;
+
+impl Vst3Scanner {
+    pub fn scan_system_plugins() -> Result<Vec<Vst3PluginInfo>> {
+        let paths = Self::get_system_paths();
+        let mut all_plugins = Vec::new();
+
+        for path in paths {
+            match Self::scan_directory(&path) {
+                Ok(plugins) => all_plugins.extend(plugins),
+                Err(e) => {
+                    tracing::debug!("Failed to scan directory {:?}: {}", path, e);
+                }
+            }
+        }
+
+        Ok(all_plugins)
+    }
+
+    fn get_system_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(home) = std::env::var_os("HOME") {
+                paths.push(PathBuf::from(home).join("Library/Audio/Plug-Ins/VST3"));
+            }
+            paths.push(PathBuf::from("/Library/Audio/Plug-Ins/VST3"));
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(home) = std::env::var_os("HOME") {
+                paths.push(PathBuf::from(home).join(".vst3"));
+            }
+            paths.push(PathBuf::from("/usr/lib/vst3"));
+            paths.push(PathBuf::from("/usr/local/lib/vst3"));
+        }
+
+        paths
+    }
+
+    /// Scan `path` for `.vst3` bundles. Vendor sub-folders are searched too;
+    /// bundles themselves are not descended into.
+    pub fn scan_directory<P: AsRef<Path>>(path: P) -> Result<Vec<Vst3PluginInfo>> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut plugins = Vec::new();
+
+        for entry in std::fs::read_dir(path).map_err(|e| {
+            Error::Configuration(format!("Failed to read directory {:?}: {}", path, e))
+        })? {
+            let entry =
+                entry.map_err(|e| Error::Configuration(format!("Failed to read entry: {}", e)))?;
+            let entry_path = entry.path();
+
+            if Self::is_vst3_bundle(&entry_path) {
+                match Self::scan_plugin_bundle(&entry_path) {
+                    Ok(bundle_plugins) => plugins.extend(bundle_plugins),
+                    Err(e) => {
+                        tracing::debug!("Failed to scan bundle {:?}: {}", entry_path, e);
+                    }
+                }
+            } else if entry_path.is_dir() {
+                plugins.extend(Self::scan_directory(&entry_path)?);
+            }
+        }
+
+        Ok(plugins)
+    }
+
+    fn is_vst3_bundle(path: &Path) -> bool {
+        path.extension().and_then(|s| s.to_str()) == Some("vst3")
+    }
+
+    fn scan_plugin_bundle(path: &Path) -> Result<Vec<Vst3PluginInfo>> {
+        let module = Vst3Module::load(path)?;
+        let factory = module.factory()?;
+
+        let plugins = audio_classes(&factory)
+            .into_iter()
+            .map(|class| Vst3PluginInfo {
+                path: path.to_path_buf(),
+                id: class_id_hex(&class.cid),
+                name: class.name,
+                vendor: class.vendor,
+                version: class.version,
+                sub_categories: class.sub_categories,
+            })
+            .collect();
+
+        // The factory must be released before the module exits.
+        drop(factory);
+        drop(module);
+        Ok(plugins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vendor_sub_folders_are_searched_and_broken_bundles_skipped() {
+        let root = std::env::temp_dir().join(format!("streamlib-vst3-scan-{}", std::process::id()));
+        let bundle = root.join("Vendor").join("Broken.vst3");
+        std::fs::create_dir_all(&bundle).unwrap();
+
+        let plugins = Vst3Scanner::scan_directory(&root).unwrap();
+        assert!(plugins.is_empty());
+        assert!(Vst3Scanner::is_vst3_bundle(&bundle));
+        assert!(!Vst3Scanner::is_vst3_bundle(&root.join("Vendor")));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn missing_directories_scan_empty() {
+        let plugins = Vst3Scanner::scan_directory("/nonexistent/vst3").unwrap();
+        assert!(plugins.is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Saved plugin state — a VST3 plugin's state is two opaque blobs, the
+//! processor component's and the edit controller's, kept together here in
+//! one byte string so it can be stored as a single file.
+//!
+//! Layout: `SLV3`, a little-endian `u32` format version, then each blob as a
+//! little-endian `u32` length followed by its bytes (component first).
+
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const MAGIC: &[u8; 4] = b"SLV3";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginState {
+    /// `IComponent::getState`.
+    pub component: Vec<u8>,
+    /// `IEditController::getState`; empty when the plugin keeps all its state
+    /// in the component.
+    pub controller: Vec<u8>,
+}
+
+impl PluginState {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes =
+            Vec::with_capacity(MAGIC.len() + 12 + self.component.len() + self.controller.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        for blob in [&self.component, &self.controller] {
+            let len = u32::try_from(blob.len()).map_err(|_| {
+                Error::Runtime(format!(
+                    "VST3 state blob of {} bytes is too large",
+                    blob.len()
+                ))
+            })?;
+            bytes.extend_from_slice(&len.to_le_bytes());
+            bytes.extend_from_slice(blob);
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(Error::Configuration(
+                "Not a streamlib VST3 state (bad magic)".into(),
+            ));
+        }
+        let version = reader.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(Error::Configuration(format!(
+                "Unsupported VST3 state format version {} (expected {})",
+                version, FORMAT_VERSION
+            )));
+        }
+        let component = reader.blob()?;
+        let controller = reader.blob()?;
+        if !reader.bytes.is_empty() {
+            return Err(Error::Configuration(format!(
+                "VST3 state has {} trailing bytes",
+                reader.bytes.len()
+            )));
+        }
+        Ok(Self {
+            component,
+            controller,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(Error::Configuration("VST3 state is truncated".into()));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn blob(&mut self) -> Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_both_blobs() {
+        let state = PluginState {
+            component: vec![1, 2, 3],
+            controller: b"controller".to_vec(),
+        };
+        let bytes = state.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"SLV3");
+        assert_eq!(PluginState::from_bytes(&bytes).unwrap(), state);
+
+        let empty = PluginState::default();
+        assert_eq!(
+            PluginState::from_bytes(&empty.to_bytes().unwrap()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_truncated_and_padded_input() {
+        let bytes = PluginState {
+            component: vec![9; 8],
+            controller: Vec::new(),
+        }
+        .to_bytes()
+        .unwrap();
+
+        assert!(PluginState::from_bytes(b"VST3\x01\0\0\0").is_err());
+        assert!(PluginState::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut padded = bytes.clone();
+        padded.push(0);
+        assert!(PluginState::from_bytes(&padded).is_err());
+
+        let mut future = bytes;
+        future[4] = 2;
+        assert!(PluginState::from_bytes(&future).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Conversions from the fixed-size string fields of VST3 structs.
+
+use std::ffi::c_char;
+
+use vst3::Steinberg::TUID;
+use vst3::Steinberg::Vst::String128;
+
+/// A NUL-terminated `char8` field (UTF-8 by convention), lossily decoded.
+pub fn from_char8(field: &[c_char]) -> String {
+    let bytes: Vec<u8> = field
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// A NUL-terminated UTF-16 `String128`, lossily decoded.
+pub fn from_string128(field: &String128) -> String {
+    let units = field.iter().take_while(|&&unit| unit != 0).copied();
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// A class ID as 32 uppercase hex digits, in memory order — the form
+/// `moduleinfo.json` and most hosts print.
+pub fn class_id_hex(cid: &TUID) -> String {
+    cid.iter()
+        .map(|&byte| format!("{:02X}", byte as u8))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char8_fields_stop_at_nul() {
+        let mut field = [0 as c_char; 16];
+        for (dst, src) in field.iter_mut().zip(b"Fx|Reverb") {
+            *dst = *src as c_char;
+        }
+        assert_eq!(from_char8(&field), "Fx|Reverb");
+        assert_eq!(from_char8(&[0; 4]), "");
+    }
+
+    #[test]
+    fn string128_decodes_utf16() {
+        let mut field: String128 = [0; 128];
+        for (dst, src) in field.iter_mut().zip("Größe".encode_utf16()) {
+            *dst = src;
+        }
+        assert_eq!(from_string128(&field), "Größe");
+    }
+
+    #[test]
+    fn class_ids_print_in_memory_order() {
+        let mut cid: TUID = [0; 16];
+        cid[0] = 0x56;
+        cid[15] = -1;
+        assert_eq!(class_id_hex(&cid), "560000000000000000000000000000FF");
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The forensic mark: a 64-bit payload spread over the frame as a faint
+//! pseudo-random luma pattern.
+//!
+//! The frame is tiled with `TILE` × `TILE` pixel tiles of 8 × 8 cells,
+//! one payload bit per cell, so every tile carries the whole payload and
+//! any region a few tiles large is enough to read it. Each pixel gets
+//! `±strength` added to all three channels — a luma shift with no chroma
+//! — the sign being the pixel's chip from a keyed hash, flipped where its
+//! bit is 0. Without the key the pattern is indistinguishable from
+//! noise. [`extract`] recovers the payload by correlating a high-passed
+//! luma plane with the chips; `watermark.comp` mirrors [`offset`]
+//! exactly.
+//!
+//! The payload is a hash of the session id rather than the id itself:
+//! the id → payload pair is logged when marking starts, and a leak is
+//! traced by looking the extracted payload up.
+
+/// Pixels per cell side; a cell carries one bit.
+pub const CELL: u32 = 16;
+/// Cells per tile side; a tile carries all `PAYLOAD_BITS`.
+pub const TILE_CELLS: u32 = 8;
+pub const TILE: u32 = CELL * TILE_CELLS;
+pub const PAYLOAD_BITS: usize = (TILE_CELLS * TILE_CELLS) as usize;
+
+/// Key used when none is configured. Marks made with it can be read by
+/// anyone with this source; configure a secret key for real deployments.
+pub const DEFAULT_KEY: &str = "streamlib-watermark";
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The 64 bits marked for a session.
+pub fn session_payload(session_id: &str) -> u64 {
+    fnv1a(session_id.as_bytes())
+}
+
+/// Seed of the chip sequence for a key.
+pub fn key_seed(key: &str) -> u32 {
+    let hash = fnv1a(key.as_bytes());
+    (hash ^ (hash >> 32)) as u32
+}
+
+/// Integer avalanche hash ("lowbias32"); the shader has the same.
+fn mix32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+/// ±1 chip of the pixel.
+pub fn chip(seed: u32, x: u32, y: u32) -> f32 {
+    if mix32(seed ^ mix32(x ^ mix32(y))) >> 31 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Payload bit the pixel carries.
+pub fn bit_index(x: u32, y: u32) -> usize {
+    (((y / CELL) % TILE_CELLS) * TILE_CELLS + (x / CELL) % TILE_CELLS) as usize
+}
+
+/// What to add to each channel of the pixel, in 0..1 units.
+pub fn offset(payload: u64, seed: u32, x: u32, y: u32, strength: f32) -> f32 {
+    let bit = (payload >> bit_index(x, y)) & 1;
+    let sign = if bit == 1 { 1.0 } else { -1.0 };
+    sign * chip(seed, x, y) * strength
+}
+
+/// A payload read back from a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extracted {
+    pub payload: u64,
+    /// Weakest bit's correlation relative to the mean, 0..1; near 0 when
+    /// the frame carries no mark made with this key.
+    pub confidence: f32,
+}
+
+/// Read the payload from a `width` × `height` luma plane in 0..1 units.
+pub fn extract(luma: &[f32], width: u32, height: u32, seed: u32) -> Extracted {
+    let (w, h) = (width as usize, height as usize);
+    let mut sums = [0.0f64; PAYLOAD_BITS];
+    for y in 1..h.saturating_sub(1) {
+        for x in 1..w.saturating_sub(1) {
+            // The chips are independent per pixel, so subtracting the
+            // neighbours' mean keeps the mark and drops most of the image.
+            let at = |x: usize, y: usize| f64::from(luma[y * w + x]);
+            let neighbours = (at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1)) / 4.0;
+            let residual = at(x, y) - neighbours;
+            let (px, py) = (x as u32, y as u32);
+            sums[bit_index(px, py)] += residual * f64::from(chip(seed, px, py));
+        }
+    }
+    let payload = sums
+        .iter()
+        .enumerate()
+        .filter(|&(_, &sum)| sum > 0.0)
+        .fold(0u64, |payload, (bit, _)| payload | 1 << bit);
+    let mean = sums.iter().map(|sum| sum.abs()).sum::<f64>() / PAYLOAD_BITS as f64;
+    let weakest = sums.iter().map(|sum| sum.abs()).fold(f64::MAX, f64::min);
+    Extracted {
+        payload,
+        confidence: if mean > 0.0 {
+            (weakest / mean) as f32
+        } else {
+            0.0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: u32 = 512;
+    const HEIGHT: u32 = 256;
+
+    /// A frame with a gradient, a hard edge and noise, marked and
+    /// quantized to 8 bits like the RGBA8 output.
+    fn marked(payload: u64, seed: u32, strength: f32) -> Vec<f32> {
+        let mut noise = 0x1234_5678u32;
+        (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                noise = mix32(noise);
+                let base = x as f32 / WIDTH as f32 * 0.6
+                    + if y > HEIGHT / 2 { 0.2 } else { 0.0 }
+                    + (noise % 21) as f32 / 255.0;
+                let value = base + offset(payload, seed, x, y, strength);
+                (value.clamp(0.0, 1.0) * 255.0).round() / 255.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_payload_survives_8_bit_quantization_of_a_busy_frame() {
+        let payload = session_payload("session-42");
+        let seed = key_seed(DEFAULT_KEY);
+        let frame = marked(payload, seed, 1.5 / 255.0);
+
+        let extracted = extract(&frame, WIDTH, HEIGHT, seed);
+        assert_eq!(extracted.payload, payload);
+        assert!(extracted.confidence > 0.3, "{}", extracted.confidence);
+    }
+
+    #[test]
+    fn the_wrong_key_reads_noise() {
+        let payload = session_payload("session-42");
+        let frame = marked(payload, key_seed("secret"), 1.5 / 255.0);
+
+        let extracted = extract(&frame, WIDTH, HEIGHT, key_seed("guess"));
+        assert_ne!(extracted.payload, payload);
+        assert!(extracted.confidence < 0.3, "{}", extracted.confidence);
+    }
+
+    #[test]
+    fn payloads_and_bits_are_laid_out_as_documented() {
+        assert_ne!(session_payload("a"), session_payload("b"));
+        assert_eq!(session_payload(""), FNV_OFFSET);
+        assert_eq!(bit_index(0, 0), 0);
+        assert_eq!(bit_index(CELL, 0), 1);
+        assert_eq!(bit_index(0, CELL), TILE_CELLS as usize);
+        assert_eq!(bit_index(TILE + 5, TILE + 5), 0);
+        assert_eq!(bit_index(TILE - 1, TILE - 1), PAYLOAD_BITS - 1);
+        // Both chip signs occur about equally.
+        let positive = (0..64)
+            .flat_map(|y| (0..64).map(move |x| (x, y)))
+            .filter(|&(x, y)| chip(7, x, y) > 0.0)
+            .count();
+        assert!((1800..2300).contains(&positive), "{positive}");
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/watermark` — forensic watermarking. `Watermark` embeds an
+//! imperceptible mark identifying the session into every frame on the
+//! GPU, and optionally overlays a logo.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod forensic;
+pub mod logo;
+
+// Embedding the mark and blending the logo happen on `sdk::rhi`, which is
+// Linux-only; the `forensic` payload coding and `logo` placement stay
+// portable.
+#[cfg(target_os = "linux")]
+pub mod watermark;
+
+#[cfg(target_os = "linux")]
+pub use watermark::WatermarkProcessor;
+
+#[cfg(target_os = "linux")]
+streamlib_plugin_abi::export_plugin!(crate::WatermarkProcessor::Processor);
@@ -0,0 +1,142 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The visible logo: a PNG decoded to straight-alpha RGBA8 and placed in
+//! a corner or the centre of the frame, sized relative to its height.
+
+use std::path::Path;
+
+/// Where the logo sits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+/// A decoded logo, one `u32` per pixel as the shader's `unpackUnorm4x8`
+/// reads it: red in the low byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logo {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u32>,
+}
+
+impl Logo {
+    pub fn load(path: &Path) -> std::result::Result<Self, String> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("cannot open logo {}: {}", path.display(), e))?;
+        let mut decoder = png::Decoder::new(std::io::BufReader::new(file));
+        // Palette and low bit depths expand, 16-bit channels drop to 8.
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| format!("logo {} is not a PNG: {}", path.display(), e))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|e| format!("cannot decode logo {}: {}", path.display(), e))?;
+        let channels = match info.color_type {
+            png::ColorType::Grayscale => 1,
+            png::ColorType::GrayscaleAlpha => 2,
+            png::ColorType::Rgb => 3,
+            png::ColorType::Rgba => 4,
+            png::ColorType::Indexed => {
+                return Err(format!("logo {} has an unexpanded palette", path.display()));
+            }
+        };
+        Ok(Self::from_channels(
+            info.width,
+            info.height,
+            channels,
+            &buffer[..info.buffer_size()],
+        ))
+    }
+
+    /// Build from 8-bit gray, gray+alpha, RGB or RGBA pixels.
+    pub fn from_channels(width: u32, height: u32, channels: usize, bytes: &[u8]) -> Self {
+        let pixels = bytes
+            .chunks_exact(channels)
+            .map(|pixel| {
+                let [r, g, b, a] = match *pixel {
+                    [gray] => [gray, gray, gray, 255],
+                    [gray, alpha] => [gray, gray, gray, alpha],
+                    [r, g, b] => [r, g, b, 255],
+                    [r, g, b, a, ..] => [r, g, b, a],
+                    [] => [0; 4],
+                };
+                u32::from_le_bytes([r, g, b, a])
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// The logo's rectangle in frame pixels — x, y, width, height — at
+/// `scale` of the frame height (aspect kept), `margin` of the frame
+/// height in from the edges.
+pub fn placement(
+    anchor: Anchor,
+    frame: (u32, u32),
+    logo: (u32, u32),
+    scale: f32,
+    margin: f32,
+) -> [f32; 4] {
+    let (frame_width, frame_height) = (frame.0 as f32, frame.1 as f32);
+    let height = frame_height * scale;
+    let width = height * logo.0 as f32 / logo.1.max(1) as f32;
+    let margin = frame_height * margin;
+    let (left, right) = (margin, frame_width - margin - width);
+    let (top, bottom) = (margin, frame_height - margin - height);
+    let (x, y) = match anchor {
+        Anchor::TopLeft => (left, top),
+        Anchor::TopRight => (right, top),
+        Anchor::BottomLeft => (left, bottom),
+        Anchor::BottomRight => (right, bottom),
+        Anchor::Center => ((frame_width - width) / 2.0, (frame_height - height) / 2.0),
+    };
+    [x, y, width, height]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_channel_layout_becomes_rgba() {
+        let logo = Logo::from_channels(2, 1, 2, &[10, 0, 200, 255]);
+        assert_eq!(
+            logo.pixels,
+            [
+                u32::from_le_bytes([10, 10, 10, 0]),
+                u32::from_le_bytes([200, 200, 200, 255])
+            ]
+        );
+        assert_eq!(
+            Logo::from_channels(1, 1, 3, &[1, 2, 3]).pixels,
+            [u32::from_le_bytes([1, 2, 3, 255])]
+        );
+        assert_eq!(
+            Logo::from_channels(1, 1, 4, &[1, 2, 3, 4]).pixels,
+            [u32::from_le_bytes([1, 2, 3, 4])]
+        );
+    }
+
+    #[test]
+    fn the_logo_is_placed_by_anchor_with_its_aspect_kept() {
+        let place = |anchor| placement(anchor, (1920, 1080), (400, 200), 0.1, 0.05);
+
+        assert_eq!(place(Anchor::TopLeft), [54.0, 54.0, 216.0, 108.0]);
+        assert_eq!(place(Anchor::TopRight), [1650.0, 54.0, 216.0, 108.0]);
+        assert_eq!(place(Anchor::BottomLeft), [54.0, 918.0, 216.0, 108.0]);
+        assert_eq!(place(Anchor::BottomRight), [1650.0, 918.0, 216.0, 108.0]);
+        assert_eq!(place(Anchor::Center), [852.0, 486.0, 216.0, 108.0]);
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Watermark (Linux) — marks frames on the GPU.
+//!
+//! One dispatch of `watermark.comp` per frame composites the optional logo
+//! and adds the forensic mark (see [`crate::forensic`]), writing the next
+//! slot of an RGBA8 output ring, reallocated when the input size changes.
+//! The plugin RHI uploads no textures, so the decoded logo is copied into
+//! a host-mapped storage buffer and the kernel samples it bilinearly
+//! itself; when there is none a one-texel buffer stands in.
+//!
+//! The session marked is `session_id` from the config, else the runtime's
+//! id. Its payload is logged when marking starts so an extracted payload
+//! can be traced back to it.
+
+use std::path::{Path, PathBuf};
+
+use streamlib_plugin_sdk::sdk::context::{
+    GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    COMPUTE_OUTPUT_RING_DEPTH, ComputeBindingSpec, ComputeKernelDescriptor, RhiCommandRecorder,
+    StorageBuffer, TextureFormat, TextureRing, TextureUsages, VulkanAccess, VulkanComputeKernel,
+    VulkanLayout, VulkanStage,
+};
+
+use crate::_generated_::tatolab__watermark::watermark_config::LogoPosition;
+use crate::_generated_::{VideoFrame, WatermarkConfig};
+use crate::forensic;
+use crate::logo::{self, Anchor, Logo};
+
+const WATERMARK_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/watermark.spv"));
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0),
+    ComputeBindingSpec::storage_image(1),
+    ComputeBindingSpec::storage_buffer(2),
+];
+
+/// Matches `watermark.comp`'s 16x16 workgroup.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Upper bound of `strength`, in 8-bit levels; beyond it the mark is
+/// plainly visible grain.
+const MAX_STRENGTH: f32 = 8.0;
+
+/// `flags` bits of the push constants.
+const FLAG_FORENSIC: u32 = 1;
+const FLAG_LOGO: u32 = 2;
+
+/// Push constants of `watermark.comp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WatermarkPushConstants {
+    width: u32,
+    height: u32,
+    payload_lo: u32,
+    payload_hi: u32,
+    seed: u32,
+    strength: f32,
+    logo_width: u32,
+    logo_height: u32,
+    logo_rect: [f32; 4],
+    logo_opacity: f32,
+    flags: u32,
+}
+
+/// The forensic mark to embed.
+#[derive(Debug, Clone, PartialEq)]
+struct Marking {
+    enabled: bool,
+    session_id: String,
+    payload: u64,
+    seed: u32,
+    /// In 0..1 units.
+    strength: f32,
+}
+
+/// How the logo is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LogoStyle {
+    anchor: Anchor,
+    opacity: f32,
+    scale: f32,
+    margin: f32,
+}
+
+/// The forensic settings from the config, checked; `runtime_id` is the
+/// session when the config names none.
+fn resolve_marking(config: &WatermarkConfig, runtime_id: &str) -> Result<Marking> {
+    let strength = config.strength.unwrap_or(1.5);
+    if !(0.0..=MAX_STRENGTH).contains(&strength) {
+        return Err(Error::Configuration(format!(
+            "Watermark: strength must be within 0..={}, got {}",
+            MAX_STRENGTH, strength
+        )));
+    }
+    let session_id = config
+        .session_id
+        .clone()
+        .unwrap_or_else(|| runtime_id.to_string());
+    Ok(Marking {
+        enabled: config.forensic != Some(false),
+        payload: forensic::session_payload(&session_id),
+        session_id,
+        seed: forensic::key_seed(config.key.as_deref().unwrap_or(forensic::DEFAULT_KEY)),
+        strength: strength / 255.0,
+    })
+}
+
+/// The logo settings from the config, checked.
+fn resolve_style(config: &WatermarkConfig) -> Result<LogoStyle> {
+    let check = |name: &str, value: Option<f32>, default: f32| {
+        let value = value.unwrap_or(default);
+        if (0.0..=1.0).contains(&value) {
+            Ok(value)
+        } else {
+            Err(Error::Configuration(format!(
+                "Watermark: {} must be within 0..=1, got {}",
+                name, value
+            )))
+        }
+    };
+    let anchor = match config.logo_position {
+        Some(LogoPosition::TopLeft) => Anchor::TopLeft,
+        Some(LogoPosition::TopRight) | None => Anchor::TopRight,
+        Some(LogoPosition::BottomLeft) => Anchor::BottomLeft,
+        Some(LogoPosition::BottomRight) => Anchor::BottomRight,
+        Some(LogoPosition::Center) => Anchor::Center,
+    };
+    Ok(LogoStyle {
+        anchor,
+        opacity: check("logo_opacity", config.logo_opacity, 0.8)?,
+        scale: check("logo_scale", config.logo_scale, 0.1)?,
+        margin: check("logo_margin", config.logo_margin, 0.03)?,
+    })
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Decode the PNG at `path`.
+fn load_logo(path: &Path) -> Result<Logo> {
+    let logo = Logo::load(path).map_err(|e| Error::Configuration(format!("Watermark: {}", e)))?;
+    if logo.width == 0 || logo.height == 0 {
+        return Err(Error::Configuration(format!(
+            "Watermark: logo {} is empty",
+            path.display()
+        )));
+    }
+    tracing::info!(
+        "[Watermark] Loaded logo {} ({}x{})",
+        path.display(),
+        logo.width,
+        logo.height
+    );
+    Ok(logo)
+}
+
+/// Copy `pixels` into the logo buffer, growing it first if it is too
+/// small.
+fn upload_pixels(
+    gpu: &GpuContextLimitedAccess,
+    buffer: &mut Option<StorageBuffer>,
+    pixels: &[u32],
+) -> Result<()> {
+    let bytes = std::mem::size_of_val(pixels) as u64;
+    if buffer
+        .as_ref()
+        .is_none_or(|existing| existing.byte_size() < bytes)
+    {
+        let grown = gpu.acquire_storage_buffer(bytes)?;
+        if grown.mapped_ptr().is_null() {
+            return Err(Error::Configuration(
+                "Watermark: logo buffer must be host-mapped".into(),
+            ));
+        }
+        *buffer = Some(grown);
+    }
+    let buffer = buffer
+        .as_ref()
+        .ok_or_else(|| Error::Configuration("Watermark: logo buffer not allocated".into()))?;
+    // SAFETY: the buffer is a persistently-mapped host-visible allocation
+    // of at least `bytes` (checked above), and the previous submit has
+    // completed, so the GPU is not reading it. Host writes before the
+    // submit are visible to the kernel.
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            pixels.as_ptr(),
+            buffer.mapped_ptr() as *mut u32,
+            pixels.len(),
+        );
+    }
+    Ok(())
+}
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/watermark/Watermark",
+    description = "Embeds an imperceptible forensic mark identifying the session into every frame on the GPU, and optionally overlays a logo with configurable opacity and position.",
+    execution = reactive,
+    config = crate::_generated_::WatermarkConfig,
+    input("video_in", "@tatolab/core/VideoFrame", description = "Frames to mark"),
+    output("video_out", "@tatolab/core/VideoFrame", description = "Marked frames (RGBA8, the input's color description)"),
+)]
+pub struct WatermarkProcessor {
+    gpu_context: Option<GpuContextLimitedAccess>,
+    kernel: Option<VulkanComputeKernel>,
+    recorder: Option<RhiCommandRecorder>,
+    /// Host-mapped packed RGBA8 logo; grown when a larger logo arrives.
+    logo_buffer: Option<StorageBuffer>,
+    /// Size of the logo in `logo_buffer`, if one is drawn.
+    logo_size: Option<(u32, u32)>,
+    /// A loaded logo not yet copied into `logo_buffer`; `Some(None)`
+    /// removes the current one.
+    pending_logo: Option<Option<Logo>>,
+    /// `config.logo` as last applied.
+    logo_source: Option<String>,
+    /// The session when the config names none.
+    runtime_id: String,
+    marking: Option<Marking>,
+    style: Option<LogoStyle>,
+    /// Output ring and the size it was allocated at.
+    output_ring: Option<(TextureRing, u32, u32)>,
+    frames_marked: u64,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for WatermarkProcessor::Processor {
+    fn setup(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.runtime_id = ctx.runtime_id().to_string();
+        self.apply_settings()?;
+        if let Some(source) = self.config.logo.clone() {
+            let path = if is_url(&source) {
+                ctx.assets().fetch(&source)?
+            } else {
+                PathBuf::from(&source)
+            };
+            self.pending_logo = Some(Some(load_logo(&path)?));
+            self.logo_source = Some(source);
+        }
+
+        let full = ctx.gpu_full_access();
+        self.kernel = Some(full.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "watermark",
+            spv: WATERMARK_SPV,
+            bindings: BINDINGS,
+            push_constant_size: std::mem::size_of::<WatermarkPushConstants>() as u32,
+        })?);
+        self.recorder = Some(full.create_command_recorder("watermark")?);
+        self.gpu_context = Some(ctx.gpu_limited_access().clone());
+        tracing::info!("[Watermark] Setup");
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        self.recorder = None;
+        self.kernel = None;
+        self.logo_buffer = None;
+        self.output_ring = None;
+        tracing::info!(
+            "[Watermark] Teardown ({} frames marked)",
+            self.frames_marked
+        );
+        Ok(())
+    }
+
+    fn on_config_update(&mut self) -> Result<()> {
+        self.apply_settings()?;
+        if self.config.logo != self.logo_source {
+            match self.config.logo.clone() {
+                Some(source) if is_url(&source) => {
+                    return Err(Error::Configuration(format!(
+                        "Watermark: URLs are fetched at setup; re-add the processor to use '{}'",
+                        source
+                    )));
+                }
+                Some(source) => {
+                    self.pending_logo = Some(Some(load_logo(&PathBuf::from(&source))?));
+                    self.logo_source = Some(source);
+                }
+                None => {
+                    self.pending_logo = Some(None);
+                    self.logo_source = None;
+                }
+            }
+        }
+        tracing::info!("[Watermark] Config updated");
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("video_in") {
+            return Ok(());
+        }
+        let frame: VideoFrame = self.inputs.read("video_in")?;
+        let marked = self.mark(&frame)?;
+        self.frames_marked += 1;
+        self.outputs.write("video_out", &marked)
+    }
+}
+
+impl WatermarkProcessor::Processor {
+    /// Resolve the mark and logo style from the config, logging the
+    /// session → payload pair whenever it changes.
+    fn apply_settings(&mut self) -> Result<()> {
+        let marking = resolve_marking(&self.config, &self.runtime_id)?;
+        let style = resolve_style(&self.config)?;
+        let changed = self.marking.as_ref().is_none_or(|current| {
+            (current.enabled, current.payload) != (marking.enabled, marking.payload)
+        });
+        if changed {
+            if marking.enabled {
+                tracing::info!(
+                    "[Watermark] Marking session '{}' as payload {:016x}",
+                    marking.session_id,
+                    marking.payload
+                );
+            } else {
+                tracing::info!("[Watermark] Forensic mark disabled");
+            }
+        }
+        self.marking = Some(marking);
+        self.style = Some(style);
+        Ok(())
+    }
+
+    /// Mark `frame` into the next output slot.
+    fn mark(&mut self, frame: &VideoFrame) -> Result<VideoFrame> {
+        let gpu = self
+            .gpu_context
+            .as_ref()
+            .ok_or_else(|| Error::Configuration("Watermark: GPU context not initialized".into()))?;
+        match self.pending_logo.take() {
+            Some(Some(logo)) => {
+                upload_pixels(gpu, &mut self.logo_buffer, &logo.pixels)?;
+                self.logo_size = Some((logo.width, logo.height));
+            }
+            Some(None) => self.logo_size = None,
+            None => {}
+        }
+        if self.logo_buffer.is_none() {
+            // Binding 2 must be bound even when no logo is drawn.
+            upload_pixels(gpu, &mut self.logo_buffer, &[0])?;
+        }
+        let (Some(kernel), Some(recorder), Some(buffer), Some(marking), Some(style)) = (
+            self.kernel.as_ref(),
+            self.recorder.as_mut(),
+            self.logo_buffer.as_ref(),
+            self.marking.as_ref(),
+            self.style,
+        ) else {
+            return Err(Error::Configuration(
+                "Watermark: kernel not initialized".into(),
+            ));
+        };
+        let registration = gpu.resolve_texture_registration_by_surface_id(
+            &frame.surface_id,
+            frame.texture_layout,
+            frame.width,
+            frame.height,
+        )?;
+        let texture = registration.texture().clone();
+        let (width, height) = (texture.width(), texture.height());
+
+        let ring = match self.output_ring.take() {
+            Some((ring, ring_width, ring_height))
+                if (ring_width, ring_height) == (width, height) =>
+            {
+                ring
+            }
+            _ => gpu.escalate(|full| {
+                full.create_texture_ring(
+                    width,
+                    height,
+                    TextureFormat::Rgba8Unorm,
+                    TextureUsages::STORAGE_BINDING
+                        | TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::COPY_SRC,
+                    COMPUTE_OUTPUT_RING_DEPTH,
+                )
+            })??,
+        };
+        let ring = &self.output_ring.insert((ring, width, height)).0;
+        let slot = ring.acquire_next();
+        let slot_surface_id = slot.surface_id().to_string();
+        let slot_registration =
+            gpu.resolve_texture_registration_by_surface_id(&slot_surface_id, None, width, height)?;
+
+        let mut flags = 0;
+        if marking.enabled {
+            flags |= FLAG_FORENSIC;
+        }
+        let (logo_width, logo_height) = self.logo_size.unwrap_or((1, 1));
+        let logo_rect = logo::placement(
+            style.anchor,
+            (width, height),
+            (logo_width, logo_height),
+            style.scale,
+            style.margin,
+        );
+        if self.logo_size.is_some() && logo_rect[2] >= 1.0 && logo_rect[3] >= 1.0 {
+            flags |= FLAG_LOGO;
+        }
+        kernel.set_sampled_texture(0, &texture)?;
+        kernel.set_storage_image(1, &slot.texture)?;
+        kernel.set_storage_buffer_storage(2, buffer)?;
+        kernel.set_push_constants_value(&WatermarkPushConstants {
+            width,
+            height,
+            payload_lo: marking.payload as u32,
+            payload_hi: (marking.payload >> 32) as u32,
+            seed: marking.seed,
+            strength: marking.strength,
+            logo_width,
+            logo_height,
+            logo_rect,
+            logo_opacity: style.opacity,
+            flags,
+        })?;
+
+        recorder.begin()?;
+        let current_layout = registration.current_layout();
+        if current_layout != VulkanLayout::SHADER_READ_ONLY_OPTIMAL {
+            recorder.record_image_barrier(
+                &texture,
+                current_layout,
+                VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+                VulkanStage::ALL_COMMANDS,
+                VulkanStage::COMPUTE_SHADER,
+                VulkanAccess::MEMORY_WRITE,
+                VulkanAccess::SHADER_SAMPLED_READ,
+            )?;
+        }
+        recorder.record_image_barrier(
+            &slot.texture,
+            slot_registration.current_layout(),
+            VulkanLayout::GENERAL,
+            VulkanStage::ALL_COMMANDS,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanAccess::MEMORY_READ,
+            VulkanAccess::SHADER_WRITE,
+        )?;
+        recorder.record_dispatch(
+            kernel,
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        )?;
+        recorder.finish_compute_output(&slot_registration, &[&registration])?;
+
+        Ok(VideoFrame {
+            surface_id: slot_surface_id,
+            width,
+            height,
+            timestamp_ns: frame.timestamp_ns.clone(),
+            fps: frame.fps,
+            texture_layout: Some(VulkanLayout::SHADER_READ_ONLY_OPTIMAL.0),
+            color_info: frame.color_info.clone(),
+            mastering_display: frame.mastering_display.clone(),
+            content_light: frame.content_light.clone(),
+            field_order: frame.field_order.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_session_defaults_to_the_runtime_and_strength_is_bounded() {
+        let marking = resolve_marking(&WatermarkConfig::default(), "runtime-7").unwrap();
+        assert!(marking.enabled);
+        assert_eq!(marking.session_id, "runtime-7");
+        assert_eq!(marking.payload, forensic::session_payload("runtime-7"));
+        assert_eq!(marking.seed, forensic::key_seed(forensic::DEFAULT_KEY));
+        assert_eq!(marking.strength, 1.5 / 255.0);
+
+        let config = WatermarkConfig {
+            session_id: Some("viewer-42".into()),
+            key: Some("secret".into()),
+            forensic: Some(false),
+            ..Default::default()
+        };
+        let marking = resolve_marking(&config, "runtime-7").unwrap();
+        assert!(!marking.enabled);
+        assert_eq!(marking.payload, forensic::session_payload("viewer-42"));
+        assert_eq!(marking.seed, forensic::key_seed("secret"));
+
+        for strength in [-0.5, 8.5] {
+            let config = WatermarkConfig {
+                strength: Some(strength),
+                ..Default::default()
+            };
+            assert!(resolve_marking(&config, "runtime-7").is_err());
+        }
+    }
+
+    #[test]
+    fn the_logo_style_has_defaults_and_bounds() {
+        let style = resolve_style(&WatermarkConfig::default()).unwrap();
+        assert_eq!(
+            style,
+            LogoStyle {
+                anchor: Anchor::TopRight,
+                opacity: 0.8,
+                scale: 0.1,
+                margin: 0.03,
+            }
+        );
+        let config = WatermarkConfig {
+            logo_position: Some(LogoPosition::BottomLeft),
+            logo_opacity: Some(1.0),
+            ..Default::default()
+        };
+        let style = resolve_style(&config).unwrap();
+        assert_eq!((style.anchor, style.opacity), (Anchor::BottomLeft, 1.0));
+        let config = WatermarkConfig {
+            logo_opacity: Some(1.2),
+            ..Default::default()
+        };
+        assert!(resolve_style(&config).is_err());
+    }
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(std::mem::size_of::<WatermarkPushConstants>(), 56);
+        assert_eq!(std::mem::offset_of!(WatermarkPushConstants, logo_rect), 32);
+    }
+}
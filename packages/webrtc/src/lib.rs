@@ -1,22 +1,29 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
-//! WebRTC WHIP/WHEP transport processors for streamlib.
+//! WebRTC WHIP/WHEP transport processors for streamlib, plus plain RTP
+//! send/receive processors for non-WebRTC RTP workflows.
 
 #[allow(non_snake_case, unused_imports, clippy::all)]
 pub mod _generated_ {
     include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
 }
+pub mod rtp_receiver;
+pub mod rtp_sender;
 pub mod streaming;
 pub mod webrtc_whep;
 pub mod webrtc_whip;
 
+pub use rtp_receiver::RtpReceiverProcessor;
+pub use rtp_sender::RtpSenderProcessor;
 pub use webrtc_whep::WebRtcWhepProcessor;
 pub use webrtc_whip::WebRtcWhipProcessor;
 
-pub use _generated_::{WebrtcWhepConfig, WebrtcWhipConfig};
+pub use _generated_::{RtpReceiverConfig, RtpSenderConfig, WebrtcWhepConfig, WebrtcWhipConfig};
 
 streamlib_plugin_abi::export_plugin!(
+    crate::RtpReceiverProcessor::Processor,
+    crate::RtpSenderProcessor::Processor,
     crate::WebRtcWhepProcessor::Processor,
     crate::WebRtcWhipProcessor::Processor,
 );
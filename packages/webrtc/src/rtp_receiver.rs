@@ -0,0 +1,739 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// RTP Receiver Processor
+//
+// The receiving half of RtpSender: listens for plain RTP on one UDP port
+// per stream, depacketizes H.264 (RFC 6184) into access units and Opus
+// (RFC 7587) into packets, and outputs EncodedVideoFrame /
+// EncodedAudioFrame. Video loss is repaired with FlexFEC when the sender
+// provides it and with RTCP NACK otherwise; a gap neither fills raises a
+// Picture Loss Indication. RTCP is multiplexed on the media port and sent
+// back to wherever the media comes from.
+
+use crate::_generated_::{EncodedAudioFrame, EncodedVideoFrame};
+use crate::streaming::rtcp::{RtcpPacket, RtpReceptionStats, parse_compound, serialize_compound};
+use crate::streaming::rtp_packet::{RtpPacket, RtpPacketCache, is_rtcp, sequence_delta};
+use crate::streaming::{
+    FlexFecDecoder, H264RtpDepacketizer, OPUS_RTP_CLOCK_RATE, RtpReorderBuffer,
+    RtpTimestampUnwrapper, opus_packet_samples,
+};
+use bytes::Bytes;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::processors::ManualProcessor;
+use tokio::net::UdpSocket;
+use tokio::sync::oneshot;
+
+const DEFAULT_VIDEO_PAYLOAD_TYPE: u8 = 96;
+const DEFAULT_AUDIO_PAYLOAD_TYPE: u8 = 111;
+const DEFAULT_FEC_PAYLOAD_TYPE: u8 = 118;
+const DEFAULT_REORDER_DEPTH: u32 = 64;
+const DEFAULT_RTCP_INTERVAL_MS: u32 = 1000;
+const VIDEO_CLOCK_RATE: u32 = 90_000;
+
+/// Received video packets kept for FlexFEC recovery and duplicate
+/// detection.
+const RECEIVED_HISTORY: usize = 1024;
+
+/// Longest run of missing packets one NACK asks for; a longer gap is a
+/// lost cause better answered with a keyframe.
+const MAX_NACK_RUN: i16 = 64;
+
+/// Minimum spacing between Picture Loss Indications.
+const PLI_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 20 ms at 48 kHz, for Opus packets whose TOC byte doesn't parse.
+const DEFAULT_OPUS_SAMPLES: u32 = 960;
+
+// ============================================================================
+// PROCESSOR
+// ============================================================================
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/webrtc/RtpReceiver",
+    description = "Receives plain RTP over UDP (RFC 6184 H.264 / RFC 7587 Opus) with RTCP reports, NACK, PLI, and FlexFEC recovery",
+    execution = manual,
+    config = crate::_generated_::RtpReceiverConfig,
+    output("encoded_video_out", "@tatolab/core/EncodedVideoFrame", description = "H.264 access units received over RTP"),
+    output("encoded_audio_out", "@tatolab/core/EncodedAudioFrame", description = "Opus packets received over RTP"),
+)]
+pub struct RtpReceiverProcessor {
+    video_socket: Option<UdpSocket>,
+    audio_socket: Option<UdpSocket>,
+
+    // One per receive loop.
+    shutdown_signal_senders: Vec<oneshot::Sender<()>>,
+
+    // Plugin-owned tokio runtime. Constructed in `setup()`; the host's
+    // runtime is not reachable across the plugin ABI per #885.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl ManualProcessor for RtpReceiverProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.video_port.is_none() && self.config.audio_port.is_none() {
+            return Err(Error::Configuration(
+                "RtpReceiver: set video_port, audio_port, or both".into(),
+            ));
+        }
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("RtpReceiver: failed to build tokio runtime: {e}"))
+            })?;
+        let bind_address = self.config.bind_address.as_deref().unwrap_or("0.0.0.0");
+
+        // Bind in setup so a port already in use fails the graph's start.
+        let bind = |port: u32| -> Result<UdpSocket> {
+            let port = u16::try_from(port).map_err(|_| {
+                Error::Configuration(format!("RtpReceiver: port {port} out of range"))
+            })?;
+            runtime
+                .block_on(UdpSocket::bind((bind_address, port)))
+                .map_err(|e| {
+                    Error::Configuration(format!(
+                        "RtpReceiver: failed to bind {bind_address}:{port}: {e}"
+                    ))
+                })
+        };
+        self.video_socket = self.config.video_port.map(bind).transpose()?;
+        self.audio_socket = self.config.audio_port.map(bind).transpose()?;
+
+        tracing::info!(
+            "[RtpReceiver] Listening on {} (video port {:?}, audio port {:?})",
+            bind_address,
+            self.config.video_port,
+            self.config.audio_port
+        );
+        self.tokio_runtime = Some(runtime);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        for tx in self.shutdown_signal_senders.drain(..) {
+            let _ = tx.send(());
+        }
+        self.video_socket.take();
+        self.audio_socket.take();
+        self.tokio_runtime.take();
+        tracing::info!("[RtpReceiver] Shutdown complete");
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    fn start(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let runtime = self
+            .tokio_runtime
+            .as_ref()
+            .ok_or_else(|| Error::Runtime("tokio runtime not initialized in setup()".into()))?;
+        let rtcp_interval = Duration::from_millis(u64::from(
+            self.config
+                .rtcp_interval_ms
+                .unwrap_or(DEFAULT_RTCP_INTERVAL_MS),
+        ));
+
+        if let Some(socket) = self.video_socket.take() {
+            let session = VideoReceiveSession::new(
+                self.config
+                    .video_payload_type
+                    .unwrap_or(DEFAULT_VIDEO_PAYLOAD_TYPE),
+                self.config
+                    .fec_payload_type
+                    .unwrap_or(DEFAULT_FEC_PAYLOAD_TYPE),
+                self.config.reorder_depth.unwrap_or(DEFAULT_REORDER_DEPTH) as usize,
+                self.config.nack.unwrap_or(true),
+            );
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            runtime.spawn(run_rtp_video_receive_loop(
+                socket,
+                session,
+                self.outputs.clone(),
+                rtcp_interval,
+                shutdown_rx,
+            ));
+            self.shutdown_signal_senders.push(shutdown_tx);
+        }
+
+        if let Some(socket) = self.audio_socket.take() {
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            runtime.spawn(run_rtp_audio_receive_loop(
+                socket,
+                self.config
+                    .audio_payload_type
+                    .unwrap_or(DEFAULT_AUDIO_PAYLOAD_TYPE),
+                self.outputs.clone(),
+                rtcp_interval,
+                shutdown_rx,
+            ));
+            self.shutdown_signal_senders.push(shutdown_tx);
+        }
+
+        tracing::info!("[RtpReceiver] Started receive loops");
+        Ok(())
+    }
+
+    fn stop(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        for tx in self.shutdown_signal_senders.drain(..) {
+            let _ = tx.send(());
+        }
+        tracing::info!("[RtpReceiver] Stopped");
+        Ok(())
+    }
+}
+
+// ============================================================================
+// VIDEO
+// ============================================================================
+
+/// What one datagram produced: finished access units and RTCP feedback
+/// for the sender.
+#[derive(Default)]
+struct VideoReceiveOutcome {
+    frames: Vec<EncodedVideoFrame>,
+    feedback: Vec<RtcpPacket>,
+}
+
+/// Everything between the video socket and `encoded_video_out`: FEC
+/// recovery, NACK, reordering, depacketization, and access-unit assembly.
+struct VideoReceiveSession {
+    media_payload_type: u8,
+    fec_payload_type: u8,
+    reorder_depth: usize,
+    nack: bool,
+    own_ssrc: u32,
+    media_ssrc: Option<u32>,
+
+    stats: RtpReceptionStats,
+    received: RtpPacketCache,
+    fec: FlexFecDecoder,
+    reorder: RtpReorderBuffer,
+    highest_sequence: Option<u16>,
+    depacketizer: H264RtpDepacketizer,
+
+    access_unit: Vec<Bytes>,
+    access_unit_timestamp: Option<u32>,
+    timestamps: Option<RtpTimestampUnwrapper>,
+    keyframe_seen: bool,
+    last_pli: Option<Instant>,
+
+    frames_out: u64,
+    packets_recovered: u64,
+    packets_lost: u64,
+}
+
+impl VideoReceiveSession {
+    fn new(media_payload_type: u8, fec_payload_type: u8, reorder_depth: usize, nack: bool) -> Self {
+        Self {
+            media_payload_type,
+            fec_payload_type,
+            reorder_depth,
+            nack,
+            own_ssrc: fastrand::u32(..),
+            media_ssrc: None,
+            stats: RtpReceptionStats::new(VIDEO_CLOCK_RATE),
+            received: RtpPacketCache::new(RECEIVED_HISTORY),
+            fec: FlexFecDecoder::new(0),
+            reorder: RtpReorderBuffer::new(reorder_depth),
+            highest_sequence: None,
+            depacketizer: H264RtpDepacketizer::new(),
+            access_unit: Vec::new(),
+            access_unit_timestamp: None,
+            timestamps: None,
+            keyframe_seen: false,
+            last_pli: None,
+            frames_out: 0,
+            packets_recovered: 0,
+            packets_lost: 0,
+        }
+    }
+
+    fn on_datagram(&mut self, wire: Bytes) -> VideoReceiveOutcome {
+        let mut outcome = VideoReceiveOutcome::default();
+        if is_rtcp(&wire) {
+            self.on_rtcp(&wire);
+            return outcome;
+        }
+        let packet = match RtpPacket::parse(wire.clone()) {
+            Ok(packet) => packet,
+            Err(e) => {
+                tracing::trace!("[RtpReceiver] Ignoring datagram: {}", e);
+                return outcome;
+            }
+        };
+
+        if packet.header.payload_type == self.fec_payload_type {
+            if self.media_ssrc.is_none() {
+                return outcome;
+            }
+            match self.fec.recover(&packet.payload, &self.received) {
+                Ok(Some(recovered)) => {
+                    self.packets_recovered += 1;
+                    tracing::debug!(
+                        sequence_number = recovered.header.sequence_number,
+                        "[RtpReceiver] Recovered a packet from FlexFEC"
+                    );
+                    let recovered_wire = recovered.serialize();
+                    self.on_media(recovered, recovered_wire, &mut outcome);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("[RtpReceiver] Unusable FlexFEC packet: {}", e),
+            }
+        } else if packet.header.payload_type == self.media_payload_type {
+            self.on_media(packet, wire, &mut outcome);
+        } else {
+            tracing::trace!(
+                "[RtpReceiver] Ignoring payload type {}",
+                packet.header.payload_type
+            );
+        }
+        outcome
+    }
+
+    fn on_rtcp(&mut self, wire: &[u8]) {
+        let Ok(packets) = parse_compound(wire) else {
+            return;
+        };
+        for packet in packets {
+            if let RtcpPacket::SenderReport {
+                ssrc,
+                ntp_timestamp,
+                ..
+            } = packet
+                && Some(ssrc) == self.media_ssrc
+            {
+                self.stats.on_sender_report(ntp_timestamp);
+            }
+        }
+    }
+
+    /// Start over for a new sender (first packet, or the SSRC changed).
+    fn reset_source(&mut self, ssrc: u32) {
+        if self.media_ssrc.is_some() {
+            tracing::info!("[RtpReceiver] Video source changed to SSRC {:08x}", ssrc);
+        }
+        self.media_ssrc = Some(ssrc);
+        self.fec.set_media_ssrc(ssrc);
+        self.stats = RtpReceptionStats::new(VIDEO_CLOCK_RATE);
+        self.received = RtpPacketCache::new(RECEIVED_HISTORY);
+        self.reorder = RtpReorderBuffer::new(self.reorder_depth);
+        self.highest_sequence = None;
+        self.depacketizer = H264RtpDepacketizer::new();
+        self.access_unit.clear();
+        self.access_unit_timestamp = None;
+        self.timestamps = None;
+        self.keyframe_seen = false;
+    }
+
+    fn on_media(&mut self, packet: RtpPacket, wire: Bytes, outcome: &mut VideoReceiveOutcome) {
+        let ssrc = packet.header.ssrc;
+        if self.media_ssrc != Some(ssrc) {
+            self.reset_source(ssrc);
+        }
+        let sequence_number = packet.header.sequence_number;
+        if self.received.contains(sequence_number) {
+            // A retransmission of something FEC already rebuilt, or a
+            // network duplicate.
+            return;
+        }
+        self.received.insert(sequence_number, wire);
+        self.stats
+            .on_packet(sequence_number, packet.header.timestamp);
+
+        match self.highest_sequence {
+            Some(highest) => {
+                let delta = sequence_delta(sequence_number, highest);
+                if self.nack && (2..=MAX_NACK_RUN).contains(&delta) {
+                    let lost: Vec<u16> = (1..delta as u16)
+                        .map(|offset| highest.wrapping_add(offset))
+                        .filter(|lost| !self.received.contains(*lost))
+                        .collect();
+                    if !lost.is_empty() {
+                        outcome.feedback.push(RtcpPacket::Nack {
+                            sender_ssrc: self.own_ssrc,
+                            media_ssrc: ssrc,
+                            lost,
+                        });
+                    }
+                }
+                if delta > 0 {
+                    self.highest_sequence = Some(sequence_number);
+                }
+            }
+            None => self.highest_sequence = Some(sequence_number),
+        }
+
+        let (ready, skipped) = self.reorder.push(packet);
+        if skipped > 0 {
+            self.packets_lost += u64::from(skipped);
+            tracing::debug!(skipped, "[RtpReceiver] Gave up on lost video packets");
+            self.request_picture(outcome);
+        }
+        for packet in ready {
+            self.depacketize(packet, outcome);
+        }
+    }
+
+    fn depacketize(&mut self, packet: RtpPacket, outcome: &mut VideoReceiveOutcome) {
+        let timestamp = packet.header.timestamp;
+        // A new timestamp before the marker means the marker packet was
+        // lost; finish what we have.
+        if self
+            .access_unit_timestamp
+            .is_some_and(|current| current != timestamp)
+        {
+            self.finish_access_unit(outcome);
+        }
+        self.access_unit_timestamp = Some(timestamp);
+
+        match self.depacketizer.process_packet(
+            packet.payload,
+            timestamp,
+            packet.header.sequence_number,
+        ) {
+            Ok(nal_units) => self.access_unit.extend(nal_units),
+            Err(e) => tracing::trace!("[RtpReceiver] H.264 depacketization failed: {}", e),
+        }
+        if packet.header.marker {
+            self.finish_access_unit(outcome);
+        }
+    }
+
+    fn finish_access_unit(&mut self, outcome: &mut VideoReceiveOutcome) {
+        let Some(timestamp) = self.access_unit_timestamp.take() else {
+            return;
+        };
+        // Drop FU-A fragments whose end never arrived (older than 1s).
+        self.depacketizer
+            .cleanup_stale_buffers(timestamp, VIDEO_CLOCK_RATE);
+        if self.access_unit.is_empty() {
+            return;
+        }
+        let nal_units = std::mem::take(&mut self.access_unit);
+        let is_keyframe = nal_units
+            .iter()
+            .any(|nal| nal.first().is_some_and(|header| header & 0x1F == 5));
+
+        // A decoder can't start mid-GOP; wait for (and ask for) an IDR.
+        if !self.keyframe_seen && !is_keyframe {
+            self.request_picture(outcome);
+            return;
+        }
+        self.keyframe_seen = true;
+
+        let mut data = Vec::with_capacity(nal_units.iter().map(|nal| nal.len() + 4).sum());
+        for nal in &nal_units {
+            data.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+            data.extend_from_slice(nal);
+        }
+        let timestamp_ns = self
+            .timestamps
+            .get_or_insert_with(|| {
+                RtpTimestampUnwrapper::new(
+                    timestamp,
+                    MediaClock::now().as_nanos() as i64,
+                    VIDEO_CLOCK_RATE,
+                )
+            })
+            .timestamp_ns(timestamp);
+        self.frames_out += 1;
+        outcome.frames.push(EncodedVideoFrame {
+            data,
+            fps: None,
+            timestamp_ns: timestamp_ns.to_string(),
+            is_keyframe,
+            frame_number: self.frames_out.to_string(),
+            // Plain RTP carries no container-level color metadata.
+            color_info: None,
+            mastering_display: None,
+            content_light: None,
+        });
+    }
+
+    fn request_picture(&mut self, outcome: &mut VideoReceiveOutcome) {
+        let (Some(media_ssrc), true) = (
+            self.media_ssrc,
+            self.last_pli.is_none_or(|at| at.elapsed() >= PLI_INTERVAL),
+        ) else {
+            return;
+        };
+        self.last_pli = Some(Instant::now());
+        outcome.feedback.push(RtcpPacket::PictureLossIndication {
+            sender_ssrc: self.own_ssrc,
+            media_ssrc,
+        });
+    }
+
+    fn receiver_report(&mut self) -> Option<RtcpPacket> {
+        let media_ssrc = self.media_ssrc?;
+        let block = self.stats.report_block(media_ssrc)?;
+        Some(RtcpPacket::ReceiverReport {
+            ssrc: self.own_ssrc,
+            reports: vec![block],
+        })
+    }
+}
+
+async fn run_rtp_video_receive_loop(
+    socket: UdpSocket,
+    mut session: VideoReceiveSession,
+    outputs: OutputWriter,
+    rtcp_interval: Duration,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    let mut ticker = tokio::time::interval(rtcp_interval);
+    let mut buffer = vec![0u8; 65_536];
+    let mut peer: Option<SocketAddr> = None;
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown_rx => break,
+            _ = ticker.tick() => {
+                if let (Some(peer), Some(report)) = (peer, session.receiver_report()) {
+                    let _ = socket.send_to(&serialize_compound(&[report]), peer).await;
+                }
+            }
+            received = socket.recv_from(&mut buffer) => {
+                let (len, from) = match received {
+                    Ok(received) => received,
+                    Err(e) => {
+                        tracing::trace!("[RtpReceiver] Video receive failed: {}", e);
+                        continue;
+                    }
+                };
+                peer = Some(from);
+                let outcome = session.on_datagram(Bytes::copy_from_slice(&buffer[..len]));
+                // Feedback goes out alone (reduced-size RTCP, RFC 5506)
+                // rather than waiting for the next report.
+                if !outcome.feedback.is_empty() {
+                    let _ = socket.send_to(&serialize_compound(&outcome.feedback), from).await;
+                }
+                for frame in outcome.frames {
+                    if let Err(e) = outputs.write("encoded_video_out", &frame) {
+                        tracing::warn!("[RtpReceiver] Failed to write encoded video: {}", e);
+                    }
+                    if session.frames_out == 1 {
+                        tracing::info!("[RtpReceiver] First encoded video frame output");
+                    }
+                }
+            }
+        }
+    }
+    tracing::info!(
+        frames = session.frames_out,
+        packets_recovered = session.packets_recovered,
+        packets_lost = session.packets_lost,
+        "[RtpReceiver] Video receive loop stopped"
+    );
+}
+
+// ============================================================================
+// AUDIO
+// ============================================================================
+
+async fn run_rtp_audio_receive_loop(
+    socket: UdpSocket,
+    payload_type: u8,
+    outputs: OutputWriter,
+    rtcp_interval: Duration,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    let own_ssrc = fastrand::u32(..);
+    let mut ticker = tokio::time::interval(rtcp_interval);
+    let mut buffer = vec![0u8; 65_536];
+    let mut peer: Option<SocketAddr> = None;
+    let mut media_ssrc: Option<u32> = None;
+    let mut stats = RtpReceptionStats::new(OPUS_RTP_CLOCK_RATE);
+    let mut timestamps: Option<RtpTimestampUnwrapper> = None;
+    let mut frames_out: u64 = 0;
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown_rx => break,
+            _ = ticker.tick() => {
+                let report = media_ssrc.and_then(|ssrc| stats.report_block(ssrc));
+                if let (Some(peer), Some(block)) = (peer, report) {
+                    let report = RtcpPacket::ReceiverReport {
+                        ssrc: own_ssrc,
+                        reports: vec![block],
+                    };
+                    let _ = socket.send_to(&serialize_compound(&[report]), peer).await;
+                }
+            }
+            received = socket.recv_from(&mut buffer) => {
+                let (len, from) = match received {
+                    Ok(received) => received,
+                    Err(e) => {
+                        tracing::trace!("[RtpReceiver] Audio receive failed: {}", e);
+                        continue;
+                    }
+                };
+                peer = Some(from);
+                let wire = Bytes::copy_from_slice(&buffer[..len]);
+                if is_rtcp(&wire) {
+                    for packet in parse_compound(&wire).unwrap_or_default() {
+                        if let RtcpPacket::SenderReport { ssrc, ntp_timestamp, .. } = packet
+                            && Some(ssrc) == media_ssrc
+                        {
+                            stats.on_sender_report(ntp_timestamp);
+                        }
+                    }
+                    continue;
+                }
+                let Ok(packet) = RtpPacket::parse(wire) else {
+                    continue;
+                };
+                if packet.header.payload_type != payload_type || packet.payload.is_empty() {
+                    continue;
+                }
+                if media_ssrc != Some(packet.header.ssrc) {
+                    media_ssrc = Some(packet.header.ssrc);
+                    stats = RtpReceptionStats::new(OPUS_RTP_CLOCK_RATE);
+                    timestamps = None;
+                }
+                stats.on_packet(packet.header.sequence_number, packet.header.timestamp);
+
+                let timestamp_ns = timestamps
+                    .get_or_insert_with(|| {
+                        RtpTimestampUnwrapper::new(
+                            packet.header.timestamp,
+                            MediaClock::now().as_nanos() as i64,
+                            OPUS_RTP_CLOCK_RATE,
+                        )
+                    })
+                    .timestamp_ns(packet.header.timestamp);
+                let encoded = EncodedAudioFrame {
+                    sample_count: opus_packet_samples(&packet.payload)
+                        .unwrap_or(DEFAULT_OPUS_SAMPLES),
+                    data: packet.payload.to_vec(),
+                    timestamp_ns: timestamp_ns.to_string(),
+                };
+                if let Err(e) = outputs.write("encoded_audio_out", &encoded) {
+                    tracing::warn!("[RtpReceiver] Failed to write encoded audio: {}", e);
+                }
+                frames_out += 1;
+                if frames_out == 1 {
+                    tracing::info!("[RtpReceiver] First encoded audio frame output");
+                }
+            }
+        }
+    }
+    tracing::info!(
+        frames = frames_out,
+        "[RtpReceiver] Audio receive loop stopped"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::rtp_packet::RtpSequencer;
+    use crate::streaming::{FlexFecEncoder, H264RtpPacketizer};
+
+    const SPS: &[u8] = &[0x67, 0x42, 0x00, 0x1F];
+    const PPS: &[u8] = &[0x68, 0xCE, 0x3C, 0x80];
+
+    /// Wire packets for one IDR access unit split into FU-A fragments.
+    fn idr_packets(sequencer: &mut RtpSequencer, timestamp: u32) -> Vec<RtpPacket> {
+        let idr: Vec<u8> = std::iter::once(0x65).chain(1..=40).collect();
+        let nal_units = [
+            Bytes::from_static(SPS),
+            Bytes::from_static(PPS),
+            Bytes::from(idr),
+        ];
+        H264RtpPacketizer::new(16)
+            .packetize(&nal_units)
+            .into_iter()
+            .map(|(payload, marker)| sequencer.next_packet(payload, timestamp, marker))
+            .collect()
+    }
+
+    #[test]
+    fn an_access_unit_comes_out_whole_despite_reordering() {
+        let mut sequencer = RtpSequencer::new(0xABCD, 96);
+        let mut packets = idr_packets(&mut sequencer, 3000);
+        packets.swap(2, 3);
+
+        let mut session = VideoReceiveSession::new(96, 118, 8, true);
+        let mut frames = Vec::new();
+        for packet in &packets {
+            frames.extend(session.on_datagram(packet.serialize()).frames);
+        }
+        assert_eq!(frames.len(), 1);
+        assert!(frames[0].is_keyframe);
+        assert_eq!(&frames[0].data[..8], &[0, 0, 0, 1, 0x67, 0x42, 0x00, 0x1F]);
+        assert_eq!(frames[0].data.len(), 3 * 4 + SPS.len() + PPS.len() + 41);
+    }
+
+    #[test]
+    fn a_gap_is_nacked_and_flexfec_fills_it() {
+        let mut sequencer = RtpSequencer::new(0xABCD, 96);
+        let packets = idr_packets(&mut sequencer, 3000);
+        let mut fec = FlexFecEncoder::new(packets.len(), 0x5555, 118);
+        let mut repair = None;
+        for packet in &packets {
+            repair = fec.push(packet, &packet.serialize());
+        }
+
+        let mut session = VideoReceiveSession::new(96, 118, 8, true);
+        let mut frames = Vec::new();
+        let mut feedback = Vec::new();
+        for (i, packet) in packets.iter().enumerate() {
+            if i == 1 {
+                continue; // lost
+            }
+            let outcome = session.on_datagram(packet.serialize());
+            frames.extend(outcome.frames);
+            feedback.extend(outcome.feedback);
+        }
+        assert!(frames.is_empty());
+        assert_eq!(
+            feedback,
+            [RtcpPacket::Nack {
+                sender_ssrc: session.own_ssrc,
+                media_ssrc: 0xABCD,
+                lost: vec![packets[1].header.sequence_number],
+            }]
+        );
+
+        let outcome = session.on_datagram(repair.unwrap().serialize());
+        assert_eq!(outcome.frames.len(), 1);
+        assert_eq!(session.packets_recovered, 1);
+        // The late retransmission is a duplicate now.
+        assert!(
+            session
+                .on_datagram(packets[1].serialize())
+                .frames
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn delta_frames_before_the_first_keyframe_raise_a_pli() {
+        let mut sequencer = RtpSequencer::new(0xABCD, 96);
+        let p_frame = sequencer.next_packet(Bytes::from_static(&[0x41, 0x9A, 0x00]), 0, true);
+        let mut session = VideoReceiveSession::new(96, 118, 8, true);
+        let outcome = session.on_datagram(p_frame.serialize());
+        assert!(outcome.frames.is_empty());
+        assert!(matches!(
+            outcome.feedback.as_slice(),
+            [RtcpPacket::PictureLossIndication {
+                media_ssrc: 0xABCD,
+                ..
+            }]
+        ));
+    }
+}
@@ -0,0 +1,418 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+// RTP Sender Processor
+//
+// Plain RTP over UDP without the WebRTC session around it: H.264 per
+// RFC 6184 (packetization-mode=1) and Opus per RFC 7587, each on its own
+// port with RTCP multiplexed alongside. Video can carry a FlexFEC repair
+// stream and is answered on NACK from a retransmission history; a Picture
+// Loss Indication from the receiver becomes a keyframe request on
+// `encoder_control`. Video receiver reports are forwarded on
+// `congestion_out` as CongestionReports (loss, RTT, jitter, send rate)
+// for a bitrate controller. For ST 2110-style contribution links and
+// custom receivers; for browsers use WebrtcWhip.
+
+use crate::_generated_::{CongestionReport, EncodedAudioFrame, EncodedVideoFrame, EncoderControl};
+use crate::streaming::rtcp::{
+    ReportBlock, RtcpPacket, ntp_now, parse_compound, serialize_compound,
+};
+use crate::streaming::rtp::parse_nal_units;
+use crate::streaming::{FlexFecEncoder, H264RtpPacketizer, OPUS_RTP_CLOCK_RATE, RtpSendStream};
+use bytes::Bytes;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use streamlib_plugin_sdk::sdk::processors::ReactiveProcessor;
+use tokio::sync::oneshot;
+
+const DEFAULT_VIDEO_PAYLOAD_TYPE: u8 = 96;
+const DEFAULT_AUDIO_PAYLOAD_TYPE: u8 = 111;
+const DEFAULT_FEC_PAYLOAD_TYPE: u8 = 118;
+const DEFAULT_MAX_PAYLOAD_BYTES: u32 = 1200;
+const DEFAULT_NACK_HISTORY: u32 = 512;
+const DEFAULT_RTCP_INTERVAL_MS: u32 = 1000;
+const VIDEO_CLOCK_RATE: u32 = 90_000;
+
+/// Audio packets are not retransmitted — a late Opus frame is useless —
+/// so the history only backs sender-report counters.
+const AUDIO_HISTORY: usize = 1;
+
+/// Receivers repeat PLIs until a keyframe arrives; forward at most one
+/// keyframe request per interval.
+const KEYFRAME_REQUEST_INTERVAL: Duration = Duration::from_millis(500);
+
+// ============================================================================
+// PROCESSOR
+// ============================================================================
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/webrtc/RtpSender",
+    description = "Sends pre-encoded H.264 and Opus as plain RTP over UDP (RFC 6184 / RFC 7587) with RTCP, NACK retransmission, and optional FlexFEC",
+    execution = reactive,
+    config = crate::_generated_::RtpSenderConfig,
+    input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", optional = true, description = "H.264 encoded video frames to send"),
+    input("encoded_audio_in", "@tatolab/core/EncodedAudioFrame", optional = true, description = "Opus encoded audio frames to send"),
+    output("encoder_control", "@tatolab/core/EncoderControl", description = "Keyframe requests raised by receivers' Picture Loss Indications"),
+    output("congestion_out", "@tatolab/core/CongestionReport", description = "Loss, round-trip time and jitter from receivers' reports on the video stream"),
+)]
+pub struct RtpSenderProcessor {
+    video_stream: Option<Arc<RtpSendStream>>,
+    audio_stream: Option<Arc<RtpSendStream>>,
+    video_packetizer: Option<H264RtpPacketizer>,
+    fec_encoder: Option<FlexFecEncoder>,
+
+    // One per RTCP feedback loop.
+    shutdown_signal_senders: Vec<oneshot::Sender<()>>,
+
+    // Plugin-owned tokio runtime. Constructed in `setup()`; the host's
+    // runtime is not reachable across the plugin ABI per #885. Hosts the
+    // sockets and their RTCP loops.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+
+    video_frames_sent: u64,
+    audio_frames_sent: u64,
+}
+
+impl ReactiveProcessor for RtpSenderProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        if self.config.video_port.is_none() && self.config.audio_port.is_none() {
+            return Err(Error::Configuration(
+                "RtpSender: set video_port, audio_port, or both".into(),
+            ));
+        }
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("RtpSender: failed to build tokio runtime: {e}"))
+            })?;
+        let host = self.config.destination_host.clone();
+        let rtcp_interval = Duration::from_millis(u64::from(
+            self.config
+                .rtcp_interval_ms
+                .unwrap_or(DEFAULT_RTCP_INTERVAL_MS),
+        ));
+
+        if let Some(port) = self.config.video_port {
+            let ssrc = self.config.video_ssrc.unwrap_or_else(|| fastrand::u32(..));
+            let stream = Arc::new(
+                runtime.block_on(RtpSendStream::connect(
+                    &host,
+                    port_number(port)?,
+                    ssrc,
+                    self.config
+                        .video_payload_type
+                        .unwrap_or(DEFAULT_VIDEO_PAYLOAD_TYPE),
+                    VIDEO_CLOCK_RATE,
+                    self.config.nack_history.unwrap_or(DEFAULT_NACK_HISTORY) as usize,
+                ))?,
+            );
+            self.video_packetizer = Some(H264RtpPacketizer::new(
+                self.config
+                    .max_payload_bytes
+                    .unwrap_or(DEFAULT_MAX_PAYLOAD_BYTES) as usize,
+            ));
+            self.fec_encoder = self.config.fec_group_size.map(|group_size| {
+                // The repair stream's SSRC only has to differ from the media's.
+                FlexFecEncoder::new(
+                    group_size as usize,
+                    ssrc ^ 0x5EC0_0000,
+                    self.config
+                        .fec_payload_type
+                        .unwrap_or(DEFAULT_FEC_PAYLOAD_TYPE),
+                )
+            });
+
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            runtime.spawn(run_rtp_send_feedback_loop(
+                Arc::clone(&stream),
+                Some(self.outputs.clone()),
+                rtcp_interval,
+                shutdown_rx,
+            ));
+            self.shutdown_signal_senders.push(shutdown_tx);
+            tracing::info!(
+                "[RtpSender] Video to {}:{} (SSRC {:08x}, FEC group {:?})",
+                host,
+                port,
+                ssrc,
+                self.config.fec_group_size
+            );
+            self.video_stream = Some(stream);
+        }
+
+        if let Some(port) = self.config.audio_port {
+            let ssrc = self.config.audio_ssrc.unwrap_or_else(|| fastrand::u32(..));
+            let stream = Arc::new(
+                runtime.block_on(RtpSendStream::connect(
+                    &host,
+                    port_number(port)?,
+                    ssrc,
+                    self.config
+                        .audio_payload_type
+                        .unwrap_or(DEFAULT_AUDIO_PAYLOAD_TYPE),
+                    OPUS_RTP_CLOCK_RATE,
+                    AUDIO_HISTORY,
+                ))?,
+            );
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            runtime.spawn(run_rtp_send_feedback_loop(
+                Arc::clone(&stream),
+                None,
+                rtcp_interval,
+                shutdown_rx,
+            ));
+            self.shutdown_signal_senders.push(shutdown_tx);
+            tracing::info!("[RtpSender] Audio to {}:{} (SSRC {:08x})", host, port, ssrc);
+            self.audio_stream = Some(stream);
+        }
+
+        self.tokio_runtime = Some(runtime);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            video_frames_sent = self.video_frames_sent,
+            audio_frames_sent = self.audio_frames_sent,
+            "[RtpSender] Shutting down"
+        );
+        for tx in self.shutdown_signal_senders.drain(..) {
+            let _ = tx.send(());
+        }
+        self.video_stream.take();
+        self.audio_stream.take();
+        self.tokio_runtime.take();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if self.inputs.has_data("encoded_video_in") {
+            let frame: EncodedVideoFrame = self.inputs.read("encoded_video_in")?;
+            self.send_video(&frame);
+        }
+        if self.inputs.has_data("encoded_audio_in") {
+            let frame: EncodedAudioFrame = self.inputs.read("encoded_audio_in")?;
+            self.send_audio(&frame);
+        }
+        Ok(())
+    }
+}
+
+impl RtpSenderProcessor::Processor {
+    fn send_video(&mut self, frame: &EncodedVideoFrame) {
+        let (Some(stream), Some(packetizer)) = (&self.video_stream, &self.video_packetizer) else {
+            return;
+        };
+        let nal_units: Vec<Bytes> = parse_nal_units(&frame.data)
+            .into_iter()
+            .map(Bytes::from)
+            .collect();
+        if nal_units.is_empty() {
+            tracing::warn!("[RtpSender] No NAL units in video frame, skipping");
+            return;
+        }
+        let timestamp = stream.rtp_timestamp(frame.timestamp_ns.parse().unwrap_or(0));
+        for (payload, marker) in packetizer.packetize(&nal_units) {
+            let (packet, wire) = stream.send(payload, timestamp, marker);
+            if let Some(repair) = self
+                .fec_encoder
+                .as_mut()
+                .and_then(|fec| fec.push(&packet, &wire))
+            {
+                stream.send_companion(&repair.serialize());
+            }
+        }
+        self.video_frames_sent += 1;
+        if self.video_frames_sent == 1 {
+            tracing::info!("[RtpSender] First video frame sent");
+        }
+    }
+
+    fn send_audio(&mut self, frame: &EncodedAudioFrame) {
+        let Some(stream) = &self.audio_stream else {
+            return;
+        };
+        if frame.data.is_empty() {
+            return;
+        }
+        // RFC 7587: one Opus packet per RTP packet, marker unused.
+        let timestamp = stream.rtp_timestamp(frame.timestamp_ns.parse().unwrap_or(0));
+        stream.send(Bytes::from(frame.data.clone()), timestamp, false);
+        self.audio_frames_sent += 1;
+        if self.audio_frames_sent == 1 {
+            tracing::info!("[RtpSender] First audio frame sent");
+        }
+    }
+}
+
+fn port_number(port: u32) -> Result<u16> {
+    u16::try_from(port)
+        .map_err(|_| Error::Configuration(format!("RtpSender: port {port} out of range")))
+}
+
+// ============================================================================
+// RTCP FEEDBACK LOOP
+// ============================================================================
+
+/// Sends sender reports on `interval` and answers the receiver's
+/// feedback: NACKs are served from the stream's history, PLIs become
+/// keyframe requests on `encoder_control`, and receiver reports become
+/// CongestionReports on `congestion_out` (video only).
+async fn run_rtp_send_feedback_loop(
+    stream: Arc<RtpSendStream>,
+    outputs: Option<OutputWriter>,
+    interval: Duration,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    let mut buffer = vec![0u8; 1500];
+    let mut last_keyframe_request: Option<Instant> = None;
+    let mut packets_retransmitted: u64 = 0;
+    let mut send_rate = SendRateMeter::new(stream.octet_count());
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown_rx => break,
+            _ = ticker.tick() => {
+                if let Some(report) = stream.sender_report() {
+                    if let Err(e) = stream.send_rtcp(&serialize_compound(&[report])).await {
+                        tracing::debug!("[RtpSender] Sender report not sent: {}", e);
+                    }
+                }
+            }
+            received = stream.recv(&mut buffer) => {
+                let len = match received {
+                    Ok(len) => len,
+                    // ICMP port unreachable surfaces here until a receiver
+                    // is listening; keep going.
+                    Err(e) => {
+                        tracing::trace!("[RtpSender] RTCP receive failed: {}", e);
+                        continue;
+                    }
+                };
+                let packets = match parse_compound(&buffer[..len]) {
+                    Ok(packets) => packets,
+                    Err(e) => {
+                        tracing::debug!("[RtpSender] Ignoring malformed RTCP: {}", e);
+                        continue;
+                    }
+                };
+                for packet in packets {
+                    match packet {
+                        RtcpPacket::Nack { media_ssrc, lost, .. }
+                            if media_ssrc == stream.ssrc() =>
+                        {
+                            packets_retransmitted += stream.retransmit(&lost).await as u64;
+                            tracing::debug!(
+                                requested = lost.len(),
+                                packets_retransmitted,
+                                "[RtpSender] NACK served"
+                            );
+                        }
+                        RtcpPacket::PictureLossIndication { media_ssrc, .. }
+                            if media_ssrc == stream.ssrc() =>
+                        {
+                            let due = last_keyframe_request
+                                .is_none_or(|at| at.elapsed() >= KEYFRAME_REQUEST_INTERVAL);
+                            if let (true, Some(outputs)) = (due, &outputs) {
+                                last_keyframe_request = Some(Instant::now());
+                                request_keyframe(outputs);
+                            }
+                        }
+                        RtcpPacket::ReceiverReport { reports, .. } => {
+                            for report in reports.iter().filter(|r| r.ssrc == stream.ssrc()) {
+                                tracing::debug!(
+                                    ssrc = report.ssrc,
+                                    fraction_lost = report.fraction_lost,
+                                    cumulative_lost = report.cumulative_lost,
+                                    jitter = report.jitter,
+                                    "[RtpSender] Receiver report"
+                                );
+                                if let Some(outputs) = &outputs {
+                                    let send_bitrate_bps =
+                                        send_rate.measure(stream.octet_count());
+                                    report_congestion(
+                                        outputs,
+                                        report,
+                                        stream.clock_rate(),
+                                        send_bitrate_bps,
+                                    );
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Payload bitrate between successive receiver reports.
+struct SendRateMeter {
+    octets: u32,
+    at: Instant,
+}
+
+impl SendRateMeter {
+    fn new(octets: u32) -> Self {
+        Self {
+            octets,
+            at: Instant::now(),
+        }
+    }
+
+    fn measure(&mut self, octets: u32) -> Option<u32> {
+        let elapsed = self.at.elapsed().as_secs_f64();
+        let sent = octets.wrapping_sub(self.octets);
+        *self = Self::new(octets);
+        (elapsed > 0.0).then(|| (f64::from(sent) * 8.0 / elapsed) as u32)
+    }
+}
+
+fn report_congestion(
+    outputs: &OutputWriter,
+    report: &ReportBlock,
+    clock_rate: u32,
+    send_bitrate_bps: Option<u32>,
+) {
+    if !outputs.has_port("congestion_out") {
+        return;
+    }
+    let congestion = CongestionReport {
+        timestamp_ns: MediaClock::now().as_nanos().to_string(),
+        source: Some("rtcp".into()),
+        fraction_lost: Some(report.loss_ratio()),
+        rtt_ms: report
+            .round_trip_time(ntp_now())
+            .map(|rtt| rtt.as_secs_f32() * 1000.0),
+        jitter_ms: Some(report.jitter as f32 * 1000.0 / clock_rate as f32),
+        available_bitrate_bps: None,
+        send_bitrate_bps,
+    };
+    if let Err(e) = outputs.write("congestion_out", &congestion) {
+        tracing::warn!("[RtpSender] Failed to write congestion report: {}", e);
+    }
+}
+
+fn request_keyframe(outputs: &OutputWriter) {
+    if !outputs.has_port("encoder_control") {
+        tracing::debug!("[RtpSender] PLI received but encoder_control is not connected");
+        return;
+    }
+    let control = EncoderControl {
+        bitrate_bps: None,
+        qp: None,
+        force_keyframe: Some(true),
+    };
+    match outputs.write("encoder_control", &control) {
+        Ok(()) => tracing::info!("[RtpSender] PLI received, requested a keyframe"),
+        Err(e) => tracing::warn!("[RtpSender] Failed to request a keyframe: {}", e),
+    }
+}
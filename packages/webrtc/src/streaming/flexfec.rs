@@ -0,0 +1,286 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! FlexFEC (RFC 8627) row protection: one repair packet per group of up
+//! to 15 consecutive media packets, enough to rebuild any single loss in
+//! the group without a retransmission round trip.
+//!
+//! Only the flexible-mask form (R=0, F=0) with a 15-bit mask (k=1) is
+//! written or read. The repair stream has its own SSRC and payload type
+//! and shares the media stream's port.
+
+use super::rtp_packet::{RTP_FIXED_HEADER_LEN, RtpPacket, RtpPacketCache, RtpSequencer};
+use bytes::{BufMut, Bytes, BytesMut};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+/// The most media packets one 15-bit mask covers.
+pub const FLEXFEC_MAX_GROUP_SIZE: usize = 15;
+
+/// R/F/P/X/CC, M/PT, length, TS, SN base, k + mask.
+const FLEXFEC_HEADER_LEN: usize = 12;
+
+/// XOR of the recoverable fields of a set of media packets.
+#[derive(Default)]
+struct FecAccumulator {
+    first_byte: u8,
+    second_byte: u8,
+    length: u16,
+    timestamp: u32,
+    payload: Vec<u8>,
+}
+
+impl FecAccumulator {
+    /// Fold in a packet as it appears on the wire.
+    fn add(&mut self, wire: &[u8]) {
+        self.first_byte ^= wire[0];
+        self.second_byte ^= wire[1];
+        self.timestamp ^= u32::from_be_bytes([wire[4], wire[5], wire[6], wire[7]]);
+        let body = &wire[RTP_FIXED_HEADER_LEN..];
+        self.length ^= body.len() as u16;
+        if self.payload.len() < body.len() {
+            self.payload.resize(body.len(), 0);
+        }
+        for (acc, byte) in self.payload.iter_mut().zip(body) {
+            *acc ^= byte;
+        }
+    }
+}
+
+/// Builds repair packets for one media stream.
+pub struct FlexFecEncoder {
+    group_size: usize,
+    sequencer: RtpSequencer,
+    group_base: Option<u16>,
+    group_len: usize,
+    accumulator: FecAccumulator,
+}
+
+impl FlexFecEncoder {
+    pub fn new(group_size: usize, ssrc: u32, payload_type: u8) -> Self {
+        Self {
+            group_size: group_size.clamp(1, FLEXFEC_MAX_GROUP_SIZE),
+            sequencer: RtpSequencer::new(ssrc, payload_type),
+            group_base: None,
+            group_len: 0,
+            accumulator: FecAccumulator::default(),
+        }
+    }
+
+    /// Add a media packet as sent (packets must arrive in sequence order).
+    /// Returns the group's repair packet once the group is full.
+    pub fn push(&mut self, media: &RtpPacket, wire: &[u8]) -> Option<RtpPacket> {
+        let base = *self.group_base.get_or_insert(media.header.sequence_number);
+        self.accumulator.add(wire);
+        self.group_len += 1;
+        if self.group_len < self.group_size {
+            return None;
+        }
+
+        let accumulator = std::mem::take(&mut self.accumulator);
+        let group_len = self.group_len;
+        self.group_base = None;
+        self.group_len = 0;
+
+        let mut mask: u16 = 0x8000; // k=1: the 15-bit mask is the whole mask
+        for i in 0..group_len {
+            mask |= 1 << (14 - i);
+        }
+        let mut payload = BytesMut::with_capacity(FLEXFEC_HEADER_LEN + accumulator.payload.len());
+        // R=0, F=0 in place of the version bits; P, X, CC recovered.
+        payload.put_u8(accumulator.first_byte & 0x3F);
+        payload.put_u8(accumulator.second_byte);
+        payload.put_u16(accumulator.length);
+        payload.put_u32(accumulator.timestamp);
+        payload.put_u16(base);
+        payload.put_u16(mask);
+        payload.put_slice(&accumulator.payload);
+        // The repair packet's own timestamp is the last protected one's.
+        Some(
+            self.sequencer
+                .next_packet(payload.freeze(), media.header.timestamp, false),
+        )
+    }
+}
+
+/// A parsed repair packet.
+struct FlexFecRepair {
+    first_byte: u8,
+    second_byte: u8,
+    length: u16,
+    timestamp: u32,
+    protected: Vec<u16>,
+    payload: Bytes,
+}
+
+impl FlexFecRepair {
+    fn parse(payload: &Bytes) -> Result<Self> {
+        if payload.len() < FLEXFEC_HEADER_LEN {
+            return Err(Error::Runtime("FlexFEC header truncated".into()));
+        }
+        if payload[0] & 0xC0 != 0 {
+            return Err(Error::Runtime(
+                "FlexFEC retransmission and fixed-mask forms are unsupported".into(),
+            ));
+        }
+        let base = u16::from_be_bytes([payload[8], payload[9]]);
+        let mask = u16::from_be_bytes([payload[10], payload[11]]);
+        if mask & 0x8000 == 0 {
+            return Err(Error::Runtime(
+                "FlexFEC masks longer than 15 bits are unsupported".into(),
+            ));
+        }
+        let protected = (0..15u16)
+            .filter(|i| mask & (1 << (14 - i)) != 0)
+            .map(|i| base.wrapping_add(i))
+            .collect();
+        Ok(Self {
+            first_byte: payload[0],
+            second_byte: payload[1],
+            length: u16::from_be_bytes([payload[2], payload[3]]),
+            timestamp: u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]),
+            protected,
+            payload: payload.slice(FLEXFEC_HEADER_LEN..),
+        })
+    }
+}
+
+/// Rebuilds a lost media packet from a repair packet and the rest of its
+/// group.
+pub struct FlexFecDecoder {
+    media_ssrc: u32,
+}
+
+impl FlexFecDecoder {
+    pub fn new(media_ssrc: u32) -> Self {
+        Self { media_ssrc }
+    }
+
+    pub fn set_media_ssrc(&mut self, media_ssrc: u32) {
+        self.media_ssrc = media_ssrc;
+    }
+
+    /// Recover the group's one missing packet from `received` (media
+    /// packets as they arrived on the wire). `None` when nothing is
+    /// missing or more than one packet is.
+    pub fn recover(
+        &self,
+        repair_payload: &Bytes,
+        received: &RtpPacketCache,
+    ) -> Result<Option<RtpPacket>> {
+        let repair = FlexFecRepair::parse(repair_payload)?;
+        let mut missing = repair
+            .protected
+            .iter()
+            .copied()
+            .filter(|sequence_number| !received.contains(*sequence_number));
+        let (Some(lost), None) = (missing.next(), missing.next()) else {
+            return Ok(None);
+        };
+
+        let mut accumulator = FecAccumulator {
+            first_byte: repair.first_byte,
+            second_byte: repair.second_byte,
+            length: repair.length,
+            timestamp: repair.timestamp,
+            payload: repair.payload.to_vec(),
+        };
+        for sequence_number in &repair.protected {
+            if let Some(wire) = received.get(*sequence_number) {
+                accumulator.add(wire);
+            }
+        }
+
+        let length = usize::from(accumulator.length);
+        if length > accumulator.payload.len() {
+            return Err(Error::Runtime(
+                "FlexFEC recovered length exceeds repair payload".into(),
+            ));
+        }
+        let mut wire = BytesMut::with_capacity(RTP_FIXED_HEADER_LEN + length);
+        wire.put_u8(0x80 | (accumulator.first_byte & 0x3F));
+        wire.put_u8(accumulator.second_byte);
+        wire.put_u16(lost);
+        wire.put_u32(accumulator.timestamp);
+        wire.put_u32(self.media_ssrc);
+        wire.put_slice(&accumulator.payload[..length]);
+        RtpPacket::parse(wire.freeze()).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn media(sequencer: &mut RtpSequencer, payload: &'static [u8], ts: u32) -> RtpPacket {
+        sequencer.next_packet(Bytes::from_static(payload), ts, payload.len() == 1)
+    }
+
+    #[test]
+    fn a_single_loss_is_recovered() {
+        let mut sequencer = RtpSequencer::new(0x1111, 96);
+        let mut encoder = FlexFecEncoder::new(3, 0x2222, 118);
+        let packets = [
+            media(&mut sequencer, b"first packet", 3000),
+            media(&mut sequencer, b"x", 3000),
+            media(&mut sequencer, b"third", 6000),
+        ];
+        let mut repair = None;
+        for packet in &packets {
+            repair = encoder.push(packet, &packet.serialize());
+        }
+        let repair = repair.expect("a full group yields a repair packet");
+        assert_eq!(repair.header.payload_type, 118);
+        assert_eq!(repair.header.ssrc, 0x2222);
+
+        // The second packet (the one with the marker bit) is lost.
+        let mut received = RtpPacketCache::new(16);
+        for packet in [&packets[0], &packets[2]] {
+            received.insert(packet.header.sequence_number, packet.serialize());
+        }
+        let recovered = FlexFecDecoder::new(0x1111)
+            .recover(&repair.payload, &received)
+            .unwrap()
+            .expect("one loss is recoverable");
+        assert_eq!(recovered, packets[1]);
+    }
+
+    #[test]
+    fn nothing_or_too_much_missing_recovers_nothing() {
+        let mut sequencer = RtpSequencer::new(1, 96);
+        let mut encoder = FlexFecEncoder::new(2, 2, 118);
+        let a = media(&mut sequencer, b"aa", 0);
+        let b = media(&mut sequencer, b"bb", 0);
+        encoder.push(&a, &a.serialize());
+        let repair = encoder.push(&b, &b.serialize()).unwrap();
+        let decoder = FlexFecDecoder::new(1);
+
+        let mut received = RtpPacketCache::new(4);
+        assert!(
+            decoder
+                .recover(&repair.payload, &received)
+                .unwrap()
+                .is_none()
+        );
+        received.insert(a.header.sequence_number, a.serialize());
+        received.insert(b.header.sequence_number, b.serialize());
+        assert!(
+            decoder
+                .recover(&repair.payload, &received)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn the_mask_covers_the_group() {
+        let mut sequencer = RtpSequencer::new(1, 96);
+        let mut encoder = FlexFecEncoder::new(FLEXFEC_MAX_GROUP_SIZE, 2, 118);
+        let mut repair = None;
+        for _ in 0..FLEXFEC_MAX_GROUP_SIZE {
+            let packet = media(&mut sequencer, b"p", 0);
+            repair = encoder.push(&packet, &packet.serialize());
+        }
+        let repair = repair.unwrap();
+        assert_eq!(&repair.payload[10..12], &[0xFF, 0xFF]);
+    }
+}
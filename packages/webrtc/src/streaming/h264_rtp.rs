@@ -249,6 +249,65 @@ impl Default for H264RtpDepacketizer {
     }
 }
 
+/// H.264 RTP packetizer (RFC 6184, packetization-mode=1): NAL units that
+/// fit `max_payload_size` go out as Single NAL Unit packets, larger ones
+/// as FU-A fragments. Returns `(payload, marker)` pairs; the marker is set
+/// on the last packet of the access unit.
+pub struct H264RtpPacketizer {
+    max_payload_size: usize,
+}
+
+impl H264RtpPacketizer {
+    pub fn new(max_payload_size: usize) -> Self {
+        Self {
+            // FU indicator + FU header + at least one payload byte.
+            max_payload_size: max_payload_size.max(3),
+        }
+    }
+
+    /// Packetize one access unit given as NAL units without start codes.
+    pub fn packetize(&self, nal_units: &[Bytes]) -> Vec<(Bytes, bool)> {
+        let mut packets = Vec::new();
+        for (i, nal) in nal_units.iter().enumerate() {
+            if nal.is_empty() {
+                continue;
+            }
+            let is_last_nal = i == nal_units.len() - 1;
+
+            if nal.len() <= self.max_payload_size {
+                packets.push((nal.clone(), is_last_nal));
+                continue;
+            }
+
+            // FU-A (RFC 6184 §5.8): the NAL header is replaced by an FU
+            // indicator (F + NRI, type 28) and an FU header (S/E + type).
+            let nal_header = nal[0];
+            let fu_indicator = (nal_header & 0xE0) | NAL_TYPE_FU_A;
+            let nal_type = nal_header & 0x1F;
+            let fragment_size = self.max_payload_size - 2;
+            let nal_payload = &nal[1..];
+
+            let mut offset = 0;
+            while offset < nal_payload.len() {
+                let size = (nal_payload.len() - offset).min(fragment_size);
+                let is_start = offset == 0;
+                let is_end = offset + size == nal_payload.len();
+                let fu_header = (if is_start { 0x80 } else { 0x00 })
+                    | (if is_end { 0x40 } else { 0x00 })
+                    | nal_type;
+
+                let mut payload = Vec::with_capacity(2 + size);
+                payload.push(fu_indicator);
+                payload.push(fu_header);
+                payload.extend_from_slice(&nal_payload[offset..offset + size]);
+                packets.push((Bytes::from(payload), is_end && is_last_nal));
+                offset += size;
+            }
+        }
+        packets
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,4 +358,29 @@ mod tests {
         let expected = vec![0x65, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
         assert_eq!(result[0].as_ref(), &expected);
     }
+
+    #[test]
+    fn test_packetizer_round_trips_through_depacketizer() {
+        let packetizer = H264RtpPacketizer::new(8);
+        let sps = Bytes::from_static(&[0x67, 0x42, 0x00]);
+        let idr = Bytes::from((0..20u8).map(|b| if b == 0 { 0x65 } else { b }).collect::<Vec<_>>());
+        let packets = packetizer.packetize(&[sps.clone(), idr.clone()]);
+
+        // SPS fits; the 20-byte IDR needs ceil(19 / 6) = 4 FU-A fragments.
+        assert_eq!(packets.len(), 5);
+        assert_eq!(packets[0], (sps.clone(), false));
+        assert_eq!(packets[1].0[0] & 0x1F, NAL_TYPE_FU_A);
+        assert!(packets.iter().all(|(payload, _)| payload.len() <= 8));
+        assert_eq!(
+            packets.iter().map(|(_, marker)| *marker).collect::<Vec<_>>(),
+            [false, false, false, false, true]
+        );
+
+        let mut depacketizer = H264RtpDepacketizer::new();
+        let mut nal_units = Vec::new();
+        for (seq, (payload, _)) in packets.into_iter().enumerate() {
+            nal_units.extend(depacketizer.process_packet(payload, 9000, seq as u16).unwrap());
+        }
+        assert_eq!(nal_units, [sps, idr]);
+    }
 }
@@ -2,16 +2,26 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 //! WebRTC session + WHIP/WHEP client primitives backing the
-//! `WebRtcWhipProcessor` and `WebRtcWhepProcessor`.
+//! `WebRtcWhipProcessor` and `WebRtcWhepProcessor`, and the plain RTP
+//! packetization, FEC, and RTCP behind `RtpSenderProcessor` and
+//! `RtpReceiverProcessor`.
 
+pub mod flexfec;
 pub mod h264_rtp;
+pub mod opus_rtp;
+pub mod rtcp;
 pub mod rtp;
+pub mod rtp_packet;
+pub mod rtp_session;
 pub mod session;
 pub mod whep_client;
 pub mod whip_client;
 
-pub use h264_rtp::H264RtpDepacketizer;
+pub use flexfec::{FlexFecDecoder, FlexFecEncoder};
+pub use h264_rtp::{H264RtpDepacketizer, H264RtpPacketizer};
+pub use opus_rtp::{OPUS_RTP_CLOCK_RATE, opus_packet_samples};
 pub use rtp::{convert_audio_to_sample, convert_video_to_samples, RtpTimestampCalculator};
+pub use rtp_session::{RtpReorderBuffer, RtpSendStream, RtpTimestampUnwrapper};
 pub use session::WebRtcSession;
 pub use whep_client::{RtpSample, WhepClient, WhepConfig};
 pub use whip_client::{WhipClient, WhipConfig};
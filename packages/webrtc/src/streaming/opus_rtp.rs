@@ -0,0 +1,58 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Opus over RTP (RFC 7587): each RTP payload is exactly one Opus packet
+//! and the RTP clock always runs at 48 kHz, whatever the coded bandwidth.
+//! There is no payload header; what the receiver needs to know — how
+//! many samples the packet holds — comes from the packet's TOC byte.
+
+/// The RTP clock rate for Opus regardless of the input sample rate.
+pub const OPUS_RTP_CLOCK_RATE: u32 = 48_000;
+
+/// Samples per channel in an Opus packet at 48 kHz (RFC 6716 §3.1), or
+/// `None` for a malformed packet.
+pub fn opus_packet_samples(packet: &[u8]) -> Option<u32> {
+    let toc = *packet.first()?;
+    let config = toc >> 3;
+    // Frame duration in 48 kHz samples by configuration number.
+    let frame_samples = match config {
+        // SILK-only: 10, 20, 40, 60 ms.
+        0..=11 => [480, 960, 1920, 2880][usize::from(config % 4)],
+        // Hybrid: 10, 20 ms.
+        12..=15 => [480, 960][usize::from(config % 2)],
+        // CELT-only: 2.5, 5, 10, 20 ms.
+        _ => [120, 240, 480, 960][usize::from(config % 4)],
+    };
+    let frames = match toc & 0x03 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => u32::from(*packet.get(1)? & 0x3F),
+    };
+    let samples = frame_samples * frames;
+    // A packet never holds more than 120 ms.
+    (frames > 0 && samples <= 5760).then_some(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toc_byte_gives_the_duration() {
+        // CELT fullband 20 ms, one frame.
+        assert_eq!(opus_packet_samples(&[0xF8, 0xFF]), Some(960));
+        // SILK wideband 60 ms, two frames — 120 ms.
+        assert_eq!(opus_packet_samples(&[(11 << 3) | 1]), Some(5760));
+        // CELT 2.5 ms, code 3 with four frames.
+        assert_eq!(opus_packet_samples(&[(16 << 3) | 3, 4]), Some(480));
+    }
+
+    #[test]
+    fn malformed_packets_have_no_duration() {
+        assert_eq!(opus_packet_samples(&[]), None);
+        assert_eq!(opus_packet_samples(&[(16 << 3) | 3]), None);
+        assert_eq!(opus_packet_samples(&[(16 << 3) | 3, 0]), None);
+        // Code 3 with 48 frames of 20 ms is far past 120 ms.
+        assert_eq!(opus_packet_samples(&[(31 << 3) | 3, 48]), None);
+    }
+}
@@ -0,0 +1,500 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The RTCP subset the standalone RTP processors speak: sender and
+//! receiver reports (RFC 3550 §6.4) plus the two feedback messages that
+//! matter for live video — Generic NACK and Picture Loss Indication
+//! (RFC 4585 §6.2.1, §6.3.1). Anything else in a compound packet (SDES,
+//! BYE, APP, other feedback) is skipped.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+const RTCP_VERSION: u8 = 2;
+const PT_SENDER_REPORT: u8 = 200;
+const PT_RECEIVER_REPORT: u8 = 201;
+const PT_TRANSPORT_FEEDBACK: u8 = 205;
+const PT_PAYLOAD_FEEDBACK: u8 = 206;
+const FMT_GENERIC_NACK: u8 = 1;
+const FMT_PICTURE_LOSS: u8 = 1;
+
+/// Seconds between the NTP epoch (1900) and the Unix epoch.
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+/// One reception report block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReportBlock {
+    pub ssrc: u32,
+    pub fraction_lost: u8,
+    /// 24-bit signed on the wire; clamped.
+    pub cumulative_lost: i32,
+    pub extended_highest_sequence: u32,
+    pub jitter: u32,
+    pub last_sender_report: u32,
+    /// In units of 1/65536 seconds.
+    pub delay_since_last_sender_report: u32,
+}
+
+impl ReportBlock {
+    /// Share of packets lost in the reported interval, 0..1.
+    pub fn loss_ratio(&self) -> f32 {
+        f32::from(self.fraction_lost) / 256.0
+    }
+
+    /// Round-trip time per RFC 3550 §6.4.1: arrival time less the echoed
+    /// sender-report time and the receiver's hold, from the middle 32 bits
+    /// of `arrival_ntp`. `None` until the receiver has seen a sender report.
+    pub fn round_trip_time(&self, arrival_ntp: u64) -> Option<Duration> {
+        if self.last_sender_report == 0 {
+            return None;
+        }
+        let rtt = ((arrival_ntp >> 16) as u32)
+            .wrapping_sub(self.last_sender_report)
+            .wrapping_sub(self.delay_since_last_sender_report);
+        // A negative result (clock skew, a stale echo) wraps huge.
+        (rtt < 0x8000_0000).then(|| Duration::from_micros((u64::from(rtt) * 1_000_000) >> 16))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RtcpPacket {
+    SenderReport {
+        ssrc: u32,
+        ntp_timestamp: u64,
+        rtp_timestamp: u32,
+        packet_count: u32,
+        octet_count: u32,
+        reports: Vec<ReportBlock>,
+    },
+    ReceiverReport {
+        ssrc: u32,
+        reports: Vec<ReportBlock>,
+    },
+    Nack {
+        sender_ssrc: u32,
+        media_ssrc: u32,
+        lost: Vec<u16>,
+    },
+    PictureLossIndication {
+        sender_ssrc: u32,
+        media_ssrc: u32,
+    },
+}
+
+impl RtcpPacket {
+    pub fn serialize(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        match self {
+            Self::SenderReport {
+                ssrc,
+                ntp_timestamp,
+                rtp_timestamp,
+                packet_count,
+                octet_count,
+                reports,
+            } => {
+                put_common_header(out, reports.len() as u8, PT_SENDER_REPORT);
+                out.extend_from_slice(&ssrc.to_be_bytes());
+                out.extend_from_slice(&ntp_timestamp.to_be_bytes());
+                out.extend_from_slice(&rtp_timestamp.to_be_bytes());
+                out.extend_from_slice(&packet_count.to_be_bytes());
+                out.extend_from_slice(&octet_count.to_be_bytes());
+                for report in reports {
+                    put_report_block(out, report);
+                }
+            }
+            Self::ReceiverReport { ssrc, reports } => {
+                put_common_header(out, reports.len() as u8, PT_RECEIVER_REPORT);
+                out.extend_from_slice(&ssrc.to_be_bytes());
+                for report in reports {
+                    put_report_block(out, report);
+                }
+            }
+            Self::Nack {
+                sender_ssrc,
+                media_ssrc,
+                lost,
+            } => {
+                put_common_header(out, FMT_GENERIC_NACK, PT_TRANSPORT_FEEDBACK);
+                out.extend_from_slice(&sender_ssrc.to_be_bytes());
+                out.extend_from_slice(&media_ssrc.to_be_bytes());
+                for (pid, blp) in nack_items(lost) {
+                    out.extend_from_slice(&pid.to_be_bytes());
+                    out.extend_from_slice(&blp.to_be_bytes());
+                }
+            }
+            Self::PictureLossIndication {
+                sender_ssrc,
+                media_ssrc,
+            } => {
+                put_common_header(out, FMT_PICTURE_LOSS, PT_PAYLOAD_FEEDBACK);
+                out.extend_from_slice(&sender_ssrc.to_be_bytes());
+                out.extend_from_slice(&media_ssrc.to_be_bytes());
+            }
+        }
+        // Length in 32-bit words minus one.
+        let words = ((out.len() - start) / 4 - 1) as u16;
+        out[start + 2..start + 4].copy_from_slice(&words.to_be_bytes());
+    }
+}
+
+fn put_common_header(out: &mut Vec<u8>, count: u8, packet_type: u8) {
+    out.push((RTCP_VERSION << 6) | (count & 0x1F));
+    out.push(packet_type);
+    out.extend_from_slice(&[0, 0]); // length, filled in by serialize()
+}
+
+fn put_report_block(out: &mut Vec<u8>, report: &ReportBlock) {
+    let cumulative_lost = report.cumulative_lost.clamp(-0x80_0000, 0x7F_FFFF) as u32 & 0xFF_FFFF;
+    out.extend_from_slice(&report.ssrc.to_be_bytes());
+    out.extend_from_slice(
+        &((u32::from(report.fraction_lost) << 24) | cumulative_lost).to_be_bytes(),
+    );
+    out.extend_from_slice(&report.extended_highest_sequence.to_be_bytes());
+    out.extend_from_slice(&report.jitter.to_be_bytes());
+    out.extend_from_slice(&report.last_sender_report.to_be_bytes());
+    out.extend_from_slice(&report.delay_since_last_sender_report.to_be_bytes());
+}
+
+/// Serialize packets into one compound datagram.
+pub fn serialize_compound(packets: &[RtcpPacket]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for packet in packets {
+        packet.serialize(&mut out);
+    }
+    out
+}
+
+/// Pack lost sequence numbers into NACK `(PID, BLP)` items: each item
+/// names one packet and flags up to 16 following ones.
+fn nack_items(lost: &[u16]) -> Vec<(u16, u16)> {
+    let mut items: Vec<(u16, u16)> = Vec::new();
+    for &sequence_number in lost {
+        if let Some((pid, blp)) = items.last_mut() {
+            let offset = sequence_number.wrapping_sub(*pid);
+            if (1..=16).contains(&offset) {
+                *blp |= 1 << (offset - 1);
+                continue;
+            }
+        }
+        items.push((sequence_number, 0));
+    }
+    items
+}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+fn parse_report_blocks(data: &[u8], count: usize) -> Result<Vec<ReportBlock>> {
+    if data.len() < count * 24 {
+        return Err(Error::Runtime("RTCP report blocks truncated".into()));
+    }
+    Ok((0..count)
+        .map(|i| {
+            let block = &data[i * 24..];
+            // Sign-extend the 24-bit cumulative loss.
+            let cumulative_lost = ((read_u32(block, 4) << 8) as i32) >> 8;
+            ReportBlock {
+                ssrc: read_u32(block, 0),
+                fraction_lost: block[4],
+                cumulative_lost,
+                extended_highest_sequence: read_u32(block, 8),
+                jitter: read_u32(block, 12),
+                last_sender_report: read_u32(block, 16),
+                delay_since_last_sender_report: read_u32(block, 20),
+            }
+        })
+        .collect())
+}
+
+/// Parse the packets of a compound RTCP datagram that this module knows.
+pub fn parse_compound(mut data: &[u8]) -> Result<Vec<RtcpPacket>> {
+    let mut packets = Vec::new();
+    while data.len() >= 4 {
+        if data[0] >> 6 != RTCP_VERSION {
+            return Err(Error::Runtime("RTCP version unsupported".into()));
+        }
+        let count = usize::from(data[0] & 0x1F);
+        let packet_type = data[1];
+        let len = (usize::from(u16::from_be_bytes([data[2], data[3]])) + 1) * 4;
+        if data.len() < len {
+            return Err(Error::Runtime("RTCP packet truncated".into()));
+        }
+        let body = &data[4..len];
+        data = &data[len..];
+
+        match packet_type {
+            PT_SENDER_REPORT if body.len() >= 24 => packets.push(RtcpPacket::SenderReport {
+                ssrc: read_u32(body, 0),
+                ntp_timestamp: (u64::from(read_u32(body, 4)) << 32) | u64::from(read_u32(body, 8)),
+                rtp_timestamp: read_u32(body, 12),
+                packet_count: read_u32(body, 16),
+                octet_count: read_u32(body, 20),
+                reports: parse_report_blocks(&body[24..], count)?,
+            }),
+            PT_RECEIVER_REPORT if body.len() >= 4 => packets.push(RtcpPacket::ReceiverReport {
+                ssrc: read_u32(body, 0),
+                reports: parse_report_blocks(&body[4..], count)?,
+            }),
+            PT_TRANSPORT_FEEDBACK if count == usize::from(FMT_GENERIC_NACK) && body.len() >= 8 => {
+                let mut lost = Vec::new();
+                for item in body[8..].chunks_exact(4) {
+                    let pid = u16::from_be_bytes([item[0], item[1]]);
+                    let blp = u16::from_be_bytes([item[2], item[3]]);
+                    lost.push(pid);
+                    lost.extend(
+                        (0..16u16)
+                            .filter(|bit| blp & (1 << bit) != 0)
+                            .map(|bit| pid.wrapping_add(bit + 1)),
+                    );
+                }
+                packets.push(RtcpPacket::Nack {
+                    sender_ssrc: read_u32(body, 0),
+                    media_ssrc: read_u32(body, 4),
+                    lost,
+                });
+            }
+            PT_PAYLOAD_FEEDBACK if count == usize::from(FMT_PICTURE_LOSS) && body.len() >= 8 => {
+                packets.push(RtcpPacket::PictureLossIndication {
+                    sender_ssrc: read_u32(body, 0),
+                    media_ssrc: read_u32(body, 4),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(packets)
+}
+
+/// The current wall-clock time as a 64-bit NTP timestamp.
+pub fn ntp_now() -> u64 {
+    let since_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let seconds = since_unix.as_secs() + NTP_UNIX_OFFSET_SECS;
+    let fraction = (u64::from(since_unix.subsec_nanos()) << 32) / 1_000_000_000;
+    (seconds << 32) | fraction
+}
+
+/// Receive-side statistics for one source, following RFC 3550 Appendix
+/// A.1 (sequence tracking), A.3 (loss), and A.8 (interarrival jitter).
+pub struct RtpReceptionStats {
+    clock_rate: u32,
+    started: Option<Instant>,
+    base_sequence: u16,
+    max_sequence: u16,
+    cycles: u32,
+    received: u32,
+    expected_prior: u32,
+    received_prior: u32,
+    /// Jitter in timestamp units, scaled by 16 as in A.8.
+    jitter_q4: u32,
+    last_transit: Option<i64>,
+    last_sender_report: Option<(u32, Instant)>,
+}
+
+impl RtpReceptionStats {
+    pub fn new(clock_rate: u32) -> Self {
+        Self {
+            clock_rate,
+            started: None,
+            base_sequence: 0,
+            max_sequence: 0,
+            cycles: 0,
+            received: 0,
+            expected_prior: 0,
+            received_prior: 0,
+            jitter_q4: 0,
+            last_transit: None,
+            last_sender_report: None,
+        }
+    }
+
+    pub fn on_packet(&mut self, sequence_number: u16, rtp_timestamp: u32) {
+        let now = Instant::now();
+        let started = *self.started.get_or_insert(now);
+        if self.received == 0 {
+            self.base_sequence = sequence_number;
+            self.max_sequence = sequence_number;
+        } else {
+            let delta = sequence_number.wrapping_sub(self.max_sequence);
+            if delta != 0 && delta < 0x8000 {
+                if sequence_number < self.max_sequence {
+                    self.cycles += 1;
+                }
+                self.max_sequence = sequence_number;
+            }
+        }
+        self.received += 1;
+
+        let arrival = (now.duration_since(started).as_nanos() * u128::from(self.clock_rate)
+            / 1_000_000_000) as i64;
+        let transit = arrival - i64::from(rtp_timestamp);
+        if let Some(last_transit) = self.last_transit {
+            let d = (transit - last_transit).unsigned_abs() as u32;
+            // J += (|D| - J) / 16, kept in Q4.
+            self.jitter_q4 = self
+                .jitter_q4
+                .wrapping_add(d)
+                .wrapping_sub((self.jitter_q4 + 8) >> 4);
+        }
+        self.last_transit = Some(transit);
+    }
+
+    pub fn on_sender_report(&mut self, ntp_timestamp: u64) {
+        // The middle 32 bits of the NTP timestamp identify the report.
+        self.last_sender_report = Some(((ntp_timestamp >> 16) as u32, Instant::now()));
+    }
+
+    pub fn extended_highest_sequence(&self) -> u32 {
+        (self.cycles << 16) | u32::from(self.max_sequence)
+    }
+
+    /// A report block for `ssrc`; starts a new interval for the fraction
+    /// lost.
+    pub fn report_block(&mut self, ssrc: u32) -> Option<ReportBlock> {
+        if self.received == 0 {
+            return None;
+        }
+        let extended_max = self.extended_highest_sequence();
+        let expected = extended_max.wrapping_sub(u32::from(self.base_sequence)) + 1;
+        let cumulative_lost = expected as i64 - i64::from(self.received);
+
+        let expected_interval = expected.wrapping_sub(self.expected_prior);
+        let received_interval = self.received.wrapping_sub(self.received_prior);
+        self.expected_prior = expected;
+        self.received_prior = self.received;
+        let lost_interval = i64::from(expected_interval) - i64::from(received_interval);
+        let fraction_lost = if expected_interval == 0 || lost_interval <= 0 {
+            0
+        } else {
+            ((lost_interval << 8) / i64::from(expected_interval)).min(255) as u8
+        };
+
+        let (last_sender_report, delay_since_last_sender_report) = match self.last_sender_report {
+            Some((middle, at)) => (middle, duration_to_q16(at.elapsed())),
+            None => (0, 0),
+        };
+        Some(ReportBlock {
+            ssrc,
+            fraction_lost,
+            cumulative_lost: cumulative_lost.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+            extended_highest_sequence: extended_max,
+            jitter: self.jitter_q4 >> 4,
+            last_sender_report,
+            delay_since_last_sender_report,
+        })
+    }
+}
+
+fn duration_to_q16(duration: Duration) -> u32 {
+    (duration.as_micros() * 65_536 / 1_000_000).min(u128::from(u32::MAX)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_round_trip() {
+        let packets = vec![
+            RtcpPacket::SenderReport {
+                ssrc: 1,
+                ntp_timestamp: 0x0102_0304_0506_0708,
+                rtp_timestamp: 90_000,
+                packet_count: 10,
+                octet_count: 12_000,
+                reports: vec![],
+            },
+            RtcpPacket::ReceiverReport {
+                ssrc: 2,
+                reports: vec![ReportBlock {
+                    ssrc: 1,
+                    fraction_lost: 64,
+                    cumulative_lost: -3,
+                    extended_highest_sequence: 0x0001_0005,
+                    jitter: 12,
+                    last_sender_report: 0x0304_0506,
+                    delay_since_last_sender_report: 65_536,
+                }],
+            },
+        ];
+        let wire = serialize_compound(&packets);
+        assert_eq!(wire.len(), 28 + 32);
+        assert_eq!(&wire[..4], &[0x80, 200, 0, 6]);
+        assert_eq!(parse_compound(&wire).unwrap(), packets);
+    }
+
+    #[test]
+    fn nack_bitmasks_cover_following_losses() {
+        assert_eq!(
+            nack_items(&[100, 101, 103, 116, 117]),
+            [(100, 0x8005), (117, 0)]
+        );
+        assert_eq!(nack_items(&[0xFFFF, 0]), [(0xFFFF, 0x0001)]);
+
+        let nack = RtcpPacket::Nack {
+            sender_ssrc: 7,
+            media_ssrc: 8,
+            lost: vec![100, 101, 103, 116, 117],
+        };
+        assert_eq!(
+            parse_compound(&serialize_compound(&[nack.clone()])).unwrap(),
+            [nack]
+        );
+    }
+
+    #[test]
+    fn pli_and_unknown_packets() {
+        let pli = RtcpPacket::PictureLossIndication {
+            sender_ssrc: 7,
+            media_ssrc: 8,
+        };
+        let mut wire = serialize_compound(&[pli.clone()]);
+        assert_eq!(&wire[..4], &[0x81, 206, 0, 2]);
+        // A BYE for one SSRC is skipped.
+        wire.extend_from_slice(&[0x81, 203, 0, 1, 0, 0, 0, 8]);
+        assert_eq!(parse_compound(&wire).unwrap(), [pli]);
+        assert!(parse_compound(&[0x80, 200, 0, 6, 0]).is_err());
+    }
+
+    #[test]
+    fn round_trip_time_subtracts_the_receivers_hold() {
+        let block = ReportBlock {
+            fraction_lost: 64,
+            last_sender_report: 0x0001_0000,
+            // Half a second held by the receiver.
+            delay_since_last_sender_report: 0x8000,
+            ..Default::default()
+        };
+        assert_eq!(block.loss_ratio(), 0.25);
+        // Arriving 0.75 s after the sender report.
+        let arrival = (0x0001_0000u64 + 0xC000) << 16;
+        assert_eq!(
+            block.round_trip_time(arrival),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            ReportBlock::default().round_trip_time(arrival),
+            None,
+            "no sender report echoed yet"
+        );
+    }
+
+    #[test]
+    fn reception_stats_count_losses_across_the_wrap() {
+        let mut stats = RtpReceptionStats::new(90_000);
+        for sequence_number in [0xFFFE, 0xFFFF, 1, 2] {
+            stats.on_packet(sequence_number, 0);
+        }
+        let block = stats.report_block(9).unwrap();
+        assert_eq!(block.extended_highest_sequence, 0x0001_0002);
+        assert_eq!(block.cumulative_lost, 1);
+        assert_eq!(block.fraction_lost, 256 / 5);
+
+        // A fresh interval without losses reports none.
+        stats.on_packet(3, 0);
+        assert_eq!(stats.report_block(9).unwrap().fraction_lost, 0);
+    }
+}
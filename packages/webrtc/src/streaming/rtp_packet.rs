@@ -0,0 +1,249 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! RTP packets on the wire (RFC 3550 §5.1), for the standalone RTP
+//! processors that own their sockets. The WHIP/WHEP path hands packets to
+//! webrtc-rs tracks instead and never touches these bytes.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use std::collections::{HashMap, VecDeque};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+
+pub const RTP_VERSION: u8 = 2;
+pub const RTP_FIXED_HEADER_LEN: usize = 12;
+
+/// The RTP fixed header. CSRCs and header extensions are skipped on parse
+/// and never written — none of the senders here mix or extend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RtpHeader {
+    pub marker: bool,
+    pub payload_type: u8,
+    pub sequence_number: u16,
+    pub timestamp: u32,
+    pub ssrc: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtpPacket {
+    pub header: RtpHeader,
+    pub payload: Bytes,
+}
+
+impl RtpPacket {
+    pub fn serialize(&self) -> Bytes {
+        let mut out = BytesMut::with_capacity(RTP_FIXED_HEADER_LEN + self.payload.len());
+        out.put_u8(RTP_VERSION << 6);
+        out.put_u8((u8::from(self.header.marker) << 7) | (self.header.payload_type & 0x7F));
+        out.put_u16(self.header.sequence_number);
+        out.put_u32(self.header.timestamp);
+        out.put_u32(self.header.ssrc);
+        out.put_slice(&self.payload);
+        out.freeze()
+    }
+
+    pub fn parse(data: Bytes) -> Result<Self> {
+        if data.len() < RTP_FIXED_HEADER_LEN {
+            return Err(Error::Runtime(
+                "RTP packet shorter than its fixed header".into(),
+            ));
+        }
+        if data[0] >> 6 != RTP_VERSION {
+            return Err(Error::Runtime(format!(
+                "RTP version {} unsupported",
+                data[0] >> 6
+            )));
+        }
+        let padding = data[0] & 0x20 != 0;
+        let extension = data[0] & 0x10 != 0;
+        let csrc_count = usize::from(data[0] & 0x0F);
+        let header = RtpHeader {
+            marker: data[1] & 0x80 != 0,
+            payload_type: data[1] & 0x7F,
+            sequence_number: u16::from_be_bytes([data[2], data[3]]),
+            timestamp: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            ssrc: u32::from_be_bytes([data[8], data[9], data[10], data[11]]),
+        };
+
+        let mut start = RTP_FIXED_HEADER_LEN + 4 * csrc_count;
+        if extension {
+            if data.len() < start + 4 {
+                return Err(Error::Runtime("RTP header extension truncated".into()));
+            }
+            let words = usize::from(u16::from_be_bytes([data[start + 2], data[start + 3]]));
+            start += 4 + 4 * words;
+        }
+        let mut end = data.len();
+        if padding {
+            end = end.saturating_sub(usize::from(data[data.len() - 1]));
+        }
+        if start > end {
+            return Err(Error::Runtime("RTP payload bounds invalid".into()));
+        }
+        Ok(Self {
+            header,
+            payload: data.slice(start..end),
+        })
+    }
+}
+
+/// True when a datagram on an rtcp-mux port is RTCP rather than RTP
+/// (RFC 5761 §4: RTCP packet types 192–223 land on RTP payload types
+/// 64–95 with the marker bit set).
+pub fn is_rtcp(data: &[u8]) -> bool {
+    data.len() >= 2 && (192..=223).contains(&data[1])
+}
+
+/// Stamps payloads with one stream's SSRC, payload type, and running
+/// sequence number. The first sequence number is random (RFC 3550 §5.1).
+pub struct RtpSequencer {
+    ssrc: u32,
+    payload_type: u8,
+    next_sequence_number: u16,
+}
+
+impl RtpSequencer {
+    pub fn new(ssrc: u32, payload_type: u8) -> Self {
+        Self {
+            ssrc,
+            payload_type,
+            next_sequence_number: fastrand::u16(..),
+        }
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    pub fn next_packet(&mut self, payload: Bytes, timestamp: u32, marker: bool) -> RtpPacket {
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number = sequence_number.wrapping_add(1);
+        RtpPacket {
+            header: RtpHeader {
+                marker,
+                payload_type: self.payload_type,
+                sequence_number,
+                timestamp,
+                ssrc: self.ssrc,
+            },
+            payload,
+        }
+    }
+}
+
+/// The most recent packets of one stream by sequence number, as sent or
+/// received on the wire — the retransmission history behind NACK and the
+/// survivors FlexFEC recovers from.
+pub struct RtpPacketCache {
+    capacity: usize,
+    packets: HashMap<u16, Bytes>,
+    order: VecDeque<u16>,
+}
+
+impl RtpPacketCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            packets: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn insert(&mut self, sequence_number: u16, wire: Bytes) {
+        if self.packets.insert(sequence_number, wire).is_none() {
+            self.order.push_back(sequence_number);
+        }
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.packets.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, sequence_number: u16) -> Option<&Bytes> {
+        self.packets.get(&sequence_number)
+    }
+
+    pub fn contains(&self, sequence_number: u16) -> bool {
+        self.packets.contains_key(&sequence_number)
+    }
+}
+
+/// Signed distance from `b` to `a` in sequence-number space, so ordering
+/// survives the 16-bit wrap.
+pub fn sequence_delta(a: u16, b: u16) -> i16 {
+    a.wrapping_sub(b) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packets_round_trip() {
+        let packet = RtpPacket {
+            header: RtpHeader {
+                marker: true,
+                payload_type: 96,
+                sequence_number: 0xFFFE,
+                timestamp: 0x1234_5678,
+                ssrc: 0xDEAD_BEEF,
+            },
+            payload: Bytes::from_static(&[0x65, 1, 2, 3]),
+        };
+        let wire = packet.serialize();
+        assert_eq!(&wire[..2], &[0x80, 0x80 | 96]);
+        assert_eq!(RtpPacket::parse(wire).unwrap(), packet);
+    }
+
+    #[test]
+    fn csrcs_extension_and_padding_are_skipped() {
+        let mut wire = vec![0x80 | 0x20 | 0x10 | 1, 96, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
+        wire.extend_from_slice(&[0, 0, 0, 9]); // CSRC
+        wire.extend_from_slice(&[0xBE, 0xDE, 0, 1, 0xAA, 0xBB, 0xCC, 0xDD]); // 1-word extension
+        wire.extend_from_slice(&[7, 8, 9]); // payload
+        wire.extend_from_slice(&[0, 0, 3]); // 3 bytes of padding
+        let packet = RtpPacket::parse(Bytes::from(wire)).unwrap();
+        assert_eq!(packet.payload.as_ref(), &[7, 8, 9]);
+        assert_eq!(packet.header.ssrc, 3);
+    }
+
+    #[test]
+    fn short_or_foreign_packets_are_rejected() {
+        assert!(RtpPacket::parse(Bytes::from_static(&[0x80, 96, 0])).is_err());
+        assert!(RtpPacket::parse(Bytes::from(vec![0x40; 12])).is_err());
+    }
+
+    #[test]
+    fn rtcp_is_told_apart_from_rtp() {
+        assert!(is_rtcp(&[0x80, 200]));
+        assert!(is_rtcp(&[0x81, 205]));
+        assert!(!is_rtcp(&[0x80, 96]));
+        assert!(!is_rtcp(&[0x80, 0x80 | 96]));
+    }
+
+    #[test]
+    fn sequence_numbers_wrap() {
+        let mut sequencer = RtpSequencer::new(1, 96);
+        let first = sequencer
+            .next_packet(Bytes::new(), 0, false)
+            .header
+            .sequence_number;
+        let second = sequencer
+            .next_packet(Bytes::new(), 0, false)
+            .header
+            .sequence_number;
+        assert_eq!(second, first.wrapping_add(1));
+        assert_eq!(sequence_delta(2, 0xFFFF), 3);
+        assert_eq!(sequence_delta(0xFFFF, 2), -3);
+    }
+
+    #[test]
+    fn cache_evicts_oldest_first() {
+        let mut cache = RtpPacketCache::new(2);
+        cache.insert(1, Bytes::from_static(b"a"));
+        cache.insert(2, Bytes::from_static(b"b"));
+        cache.insert(3, Bytes::from_static(b"c"));
+        assert!(!cache.contains(1));
+        assert_eq!(cache.get(3).map(|b| b.as_ref()), Some(&b"c"[..]));
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Socket-level plumbing for the standalone RTP processors: one UDP socket
+//! per stream, carrying RTP, RTCP (rtcp-mux, RFC 5761), and — for video —
+//! the FlexFEC repair stream.
+
+use super::rtcp::{RtcpPacket, ntp_now};
+use super::rtp::RtpTimestampCalculator;
+use super::rtp_packet::{RtpPacket, RtpPacketCache, RtpSequencer, sequence_delta};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use tokio::net::UdpSocket;
+
+/// One outgoing RTP stream and the state its RTCP needs: the NACK
+/// history and the sender-report counters.
+pub struct RtpSendStream {
+    socket: UdpSocket,
+    ssrc: u32,
+    clock_rate: u32,
+    state: Mutex<RtpSendState>,
+}
+
+struct RtpSendState {
+    sequencer: RtpSequencer,
+    timestamps: Option<RtpTimestampCalculator>,
+    history: RtpPacketCache,
+    packet_count: u32,
+    octet_count: u32,
+}
+
+impl RtpSendStream {
+    /// Bind an ephemeral local port and connect it to `host:port`. Must be
+    /// called on the runtime that will drive the feedback loop.
+    pub async fn connect(
+        host: &str,
+        port: u16,
+        ssrc: u32,
+        payload_type: u8,
+        clock_rate: u32,
+        history: usize,
+    ) -> Result<Self> {
+        let destination = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| Error::Configuration(format!("RTP: cannot resolve {host}: {e}")))?
+            .next()
+            .ok_or_else(|| Error::Configuration(format!("RTP: {host} has no address")))?;
+        let local: SocketAddr = if destination.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local).await?;
+        socket.connect(destination).await?;
+        Ok(Self {
+            socket,
+            ssrc,
+            clock_rate,
+            state: Mutex::new(RtpSendState {
+                sequencer: RtpSequencer::new(ssrc, payload_type),
+                timestamps: None,
+                history: RtpPacketCache::new(history),
+                packet_count: 0,
+                octet_count: 0,
+            }),
+        })
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    pub fn clock_rate(&self) -> u32 {
+        self.clock_rate
+    }
+
+    /// Payload octets sent so far; wraps at 2^32 like the sender report's.
+    pub fn octet_count(&self) -> u32 {
+        self.lock().octet_count
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RtpSendState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The RTP timestamp of a media-clock time. The first call fixes the
+    /// stream's random timestamp base.
+    pub fn rtp_timestamp(&self, timestamp_ns: i64) -> u32 {
+        let clock_rate = self.clock_rate;
+        self.lock()
+            .timestamps
+            .get_or_insert_with(|| RtpTimestampCalculator::new(timestamp_ns, clock_rate))
+            .calculate(timestamp_ns)
+    }
+
+    /// Send one payload. Called from the processor thread, so the socket
+    /// is written without waiting: a full send buffer drops the packet,
+    /// which a NACK can still recover. Returns the packet and its wire
+    /// form for FEC.
+    pub fn send(&self, payload: Bytes, timestamp: u32, marker: bool) -> (RtpPacket, Bytes) {
+        let mut state = self.lock();
+        let packet = state.sequencer.next_packet(payload, timestamp, marker);
+        let wire = packet.serialize();
+        state
+            .history
+            .insert(packet.header.sequence_number, wire.clone());
+        state.packet_count = state.packet_count.wrapping_add(1);
+        state.octet_count = state.octet_count.wrapping_add(packet.payload.len() as u32);
+        drop(state);
+
+        if let Err(e) = self.socket.try_send(&wire) {
+            tracing::debug!(ssrc = self.ssrc, %e, "[RTP] Send dropped a packet");
+        }
+        (packet, wire)
+    }
+
+    /// Send a packet of another stream sharing this port (FlexFEC repair).
+    pub fn send_companion(&self, wire: &[u8]) {
+        if let Err(e) = self.socket.try_send(wire) {
+            tracing::debug!(ssrc = self.ssrc, %e, "[RTP] Send dropped a repair packet");
+        }
+    }
+
+    /// Resend the packets a NACK asks for that are still in the history.
+    /// Returns how many were resent.
+    pub async fn retransmit(&self, lost: &[u16]) -> usize {
+        let packets: Vec<Bytes> = {
+            let state = self.lock();
+            lost.iter()
+                .filter_map(|sequence_number| state.history.get(*sequence_number).cloned())
+                .collect()
+        };
+        let mut resent = 0;
+        for wire in packets {
+            if self.socket.send(&wire).await.is_ok() {
+                resent += 1;
+            }
+        }
+        resent
+    }
+
+    /// A sender report for now, or `None` before the first packet.
+    pub fn sender_report(&self) -> Option<RtcpPacket> {
+        let state = self.lock();
+        let timestamps = state.timestamps.as_ref()?;
+        Some(RtcpPacket::SenderReport {
+            ssrc: self.ssrc,
+            ntp_timestamp: ntp_now(),
+            rtp_timestamp: timestamps.calculate(MediaClock::now().as_nanos() as i64),
+            packet_count: state.packet_count,
+            octet_count: state.octet_count,
+            reports: Vec::new(),
+        })
+    }
+
+    pub async fn send_rtcp(&self, wire: &[u8]) -> Result<()> {
+        self.socket.send(wire).await?;
+        Ok(())
+    }
+
+    pub async fn recv(&self, buffer: &mut [u8]) -> Result<usize> {
+        Ok(self.socket.recv(buffer).await?)
+    }
+}
+
+/// Holds back out-of-order packets so the depacketizer sees sequence
+/// order. A gap is waited on until `depth` packets are queued behind it —
+/// long enough for a NACK retransmission or FEC recovery to fill it —
+/// then skipped.
+pub struct RtpReorderBuffer {
+    depth: usize,
+    next: Option<u16>,
+    pending: HashMap<u16, RtpPacket>,
+}
+
+impl RtpReorderBuffer {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            next: None,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Queue a packet. Returns the packets now in order and how many
+    /// sequence numbers were given up on to release them.
+    pub fn push(&mut self, packet: RtpPacket) -> (Vec<RtpPacket>, u16) {
+        let sequence_number = packet.header.sequence_number;
+        let next = *self.next.get_or_insert(sequence_number);
+        if sequence_delta(sequence_number, next) < 0 {
+            // Already released or skipped; a late duplicate.
+            return (Vec::new(), 0);
+        }
+        self.pending.insert(sequence_number, packet);
+
+        let mut ready = Vec::new();
+        let mut skipped = 0;
+        loop {
+            self.drain_in_order(&mut ready);
+            if self.pending.len() <= self.depth {
+                break;
+            }
+            let next = self.next.expect("set above");
+            let Some(oldest) = self
+                .pending
+                .keys()
+                .copied()
+                .min_by_key(|sequence_number| sequence_number.wrapping_sub(next))
+            else {
+                break;
+            };
+            skipped += oldest.wrapping_sub(next);
+            self.next = Some(oldest);
+        }
+        (ready, skipped)
+    }
+
+    fn drain_in_order(&mut self, ready: &mut Vec<RtpPacket>) {
+        while let Some(next) = self.next {
+            let Some(packet) = self.pending.remove(&next) else {
+                break;
+            };
+            ready.push(packet);
+            self.next = Some(next.wrapping_add(1));
+        }
+    }
+}
+
+/// Maps a received stream's RTP timestamps onto the local media clock:
+/// the first packet lands at its arrival time and later ones follow the
+/// sender's clock, unwrapped past the 32-bit wrap.
+pub struct RtpTimestampUnwrapper {
+    clock_rate: u32,
+    base_ns: i64,
+    last: u32,
+    ticks: i64,
+}
+
+impl RtpTimestampUnwrapper {
+    pub fn new(first_timestamp: u32, arrival_ns: i64, clock_rate: u32) -> Self {
+        Self {
+            clock_rate,
+            base_ns: arrival_ns,
+            last: first_timestamp,
+            ticks: 0,
+        }
+    }
+
+    pub fn timestamp_ns(&mut self, timestamp: u32) -> i64 {
+        self.ticks += i64::from(timestamp.wrapping_sub(self.last) as i32);
+        self.last = timestamp;
+        self.base_ns + self.ticks * 1_000_000_000 / i64::from(self.clock_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rtp_packet::RtpHeader;
+    use super::*;
+
+    fn packet(sequence_number: u16) -> RtpPacket {
+        RtpPacket {
+            header: RtpHeader {
+                sequence_number,
+                ..Default::default()
+            },
+            payload: Bytes::new(),
+        }
+    }
+
+    fn sequence_numbers(packets: &[RtpPacket]) -> Vec<u16> {
+        packets.iter().map(|p| p.header.sequence_number).collect()
+    }
+
+    #[test]
+    fn reorder_buffer_waits_for_a_late_packet() {
+        let mut buffer = RtpReorderBuffer::new(4);
+        assert_eq!(sequence_numbers(&buffer.push(packet(0xFFFE)).0), [0xFFFE]);
+        assert!(buffer.push(packet(0)).0.is_empty());
+        let (ready, skipped) = buffer.push(packet(0xFFFF));
+        assert_eq!(sequence_numbers(&ready), [0xFFFF, 0]);
+        assert_eq!(skipped, 0);
+        // A duplicate of something released is dropped.
+        assert!(buffer.push(packet(0xFFFF)).0.is_empty());
+    }
+
+    #[test]
+    fn reorder_buffer_gives_up_on_a_gap_past_its_depth() {
+        let mut buffer = RtpReorderBuffer::new(2);
+        buffer.push(packet(10));
+        assert!(buffer.push(packet(13)).0.is_empty());
+        assert!(buffer.push(packet(14)).0.is_empty());
+        let (ready, skipped) = buffer.push(packet(15));
+        assert_eq!(sequence_numbers(&ready), [13, 14, 15]);
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn unwrapper_follows_the_sender_clock_across_the_wrap() {
+        let mut unwrapper = RtpTimestampUnwrapper::new(u32::MAX - 44_999, 1_000, 90_000);
+        assert_eq!(unwrapper.timestamp_ns(u32::MAX - 44_999), 1_000);
+        assert_eq!(unwrapper.timestamp_ns(45_000), 1_000 + 1_000_000_000);
+        // A B-frame style step backwards.
+        assert_eq!(unwrapper.timestamp_ns(0), 1_000 + 500_000_000);
+    }
+}
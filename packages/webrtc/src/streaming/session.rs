@@ -6,6 +6,7 @@
 // Manages WebRTC PeerConnection, tracks, and RTP packetization using webrtc-rs.
 // Supports both send (WHIP) and receive (WHEP) modes.
 
+use super::h264_rtp::H264RtpPacketizer;
 use std::sync::Arc;
 use streamlib_plugin_sdk::sdk::error::{Error, Result};
 use webrtc::track::track_local::TrackLocalWriter;
@@ -926,9 +927,7 @@ impl WebRtcSession {
             );
         }
 
-        // RFC 6184 H.264 RTP Packetization
-        // - Single NAL Unit mode: NAL < MTU (send as-is)
-        // - FU-A mode: NAL >= MTU (fragment into multiple packets)
+        // RFC 6184 H.264 RTP Packetization (Single NAL Unit / FU-A)
         use webrtc::rtp::header::Header as RtpHeader;
         use webrtc::rtp::packet::Packet as RtpPacket;
 
@@ -936,11 +935,10 @@ impl WebRtcSession {
         let timestamp_increment = 90000 / 30; // H.264 @ 90kHz, 30fps
         let current_timestamp = VIDEO_TIMESTAMP.load(std::sync::atomic::Ordering::Relaxed);
 
-        for (i, sample) in samples.iter().enumerate() {
-            let is_last_nal_in_frame = i == samples.len() - 1;
-
+        let nal_units: Vec<bytes::Bytes> = samples.iter().map(|s| s.data.clone()).collect();
+        for nal in &nal_units {
             // Decode NAL unit type (bits 0-4 of first byte)
-            let nal_type = sample.data[0] & 0x1F;
+            let nal_type = nal[0] & 0x1F;
             let nal_type_name = match nal_type {
                 1 => "P-frame (non-IDR)",
                 5 => "IDR (keyframe)",
@@ -957,117 +955,50 @@ impl WebRtcSession {
                     counter,
                     nal_type,
                     nal_type_name,
-                    sample.data.len()
+                    nal.len()
                 );
             }
+        }
 
-            if sample.data.len() <= MAX_PAYLOAD_SIZE {
-                // Single NAL Unit mode - send entire NAL as one RTP packet
-                let seq_num = VIDEO_SEQ_NUM.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-
-                let rtp_packet = RtpPacket {
-                    header: RtpHeader {
-                        version: 2,
-                        padding: false,
-                        extension: false,
-                        marker: is_last_nal_in_frame, // Mark last NAL of frame
-                        payload_type: 102,            // H.264 (registered as PT=102)
-                        sequence_number: seq_num as u16,
-                        timestamp: current_timestamp,
-                        ssrc: 0, // Will be set by track
-                        ..Default::default()
-                    },
-                    payload: sample.data.clone(),
-                };
-
-                tokio_handle.block_on(async {
-                    track.write_rtp(&rtp_packet).await.map_err(|e| {
-                        Error::Runtime(format!("Failed to write video RTP: {}", e))
-                    })
-                })?;
-
-                if counter == 0 && i == 0 {
-                    tracing::info!("[WebRTC] Successfully wrote first video RTP packet (Single NAL, {} bytes)", sample.data.len());
-                } else if counter.is_multiple_of(30) && i == 0 {
-                    tracing::info!(
-                        "[WebRTC] Video RTP packet #{} sent (Single NAL, {} bytes)",
-                        counter,
-                        sample.data.len()
-                    );
-                }
-            } else {
-                // FU-A (Fragmentation Unit) mode - split NAL into multiple RTP packets
-                // RFC 6184 Section 5.8: FU-A format
-
-                let nal_header = sample.data[0]; // First byte is NAL header
-                let nal_payload = &sample.data[1..]; // Rest is NAL payload
-
-                // FU Indicator: F=0, NRI from NAL header, Type=28 (FU-A)
-                let fu_indicator = (nal_header & 0xE0) | 28;
-
-                // FU Header: S (start), E (end), R=0, Type from NAL header
-                let nal_type = nal_header & 0x1F;
-
-                let mut offset = 0;
-                let mut frag_count = 0;
-
-                while offset < nal_payload.len() {
-                    let remaining = nal_payload.len() - offset;
-                    let payload_size = remaining.min(MAX_PAYLOAD_SIZE - 2); // -2 for FU indicator + header
-
-                    let is_start = offset == 0;
-                    let is_end = offset + payload_size >= nal_payload.len();
-
-                    // FU Header: S | E | R | Type
-                    let fu_header = (if is_start { 0x80 } else { 0x00 }) |  // S bit
-                        (if is_end { 0x40 } else { 0x00 }) |    // E bit
-                        nal_type; // Type
-
-                    // Build FU-A payload: FU indicator + FU header + NAL fragment
-                    let mut fu_payload = Vec::with_capacity(2 + payload_size);
-                    fu_payload.push(fu_indicator);
-                    fu_payload.push(fu_header);
-                    fu_payload.extend_from_slice(&nal_payload[offset..offset + payload_size]);
-
-                    let seq_num = VIDEO_SEQ_NUM.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-
-                    let rtp_packet = RtpPacket {
-                        header: RtpHeader {
-                            version: 2,
-                            padding: false,
-                            extension: false,
-                            marker: is_end && is_last_nal_in_frame, // Mark last fragment of last NAL
-                            payload_type: 102,
-                            sequence_number: seq_num as u16,
-                            timestamp: current_timestamp,
-                            ssrc: 0,
-                            ..Default::default()
-                        },
-                        payload: fu_payload.into(),
-                    };
-
-                    tokio_handle.block_on(async {
-                        track.write_rtp(&rtp_packet).await.map_err(|e| {
-                            Error::Runtime(format!("Failed to write video RTP: {}", e))
-                        })
-                    })?;
-
-                    if counter == 0 && i == 0 && frag_count == 0 {
-                        tracing::info!("[WebRTC] Successfully wrote first video RTP packet (FU-A mode, NAL size {} bytes, fragments ~{})",
-                            sample.data.len(),
-                            sample.data.len().div_ceil(MAX_PAYLOAD_SIZE));
-                    } else if counter.is_multiple_of(30) && i == 0 && frag_count == 0 {
-                        tracing::info!(
-                            "[WebRTC] Video RTP packet #{} sent (FU-A mode, NAL size {} bytes)",
-                            counter,
-                            sample.data.len()
-                        );
-                    }
+        let packets = H264RtpPacketizer::new(MAX_PAYLOAD_SIZE).packetize(&nal_units);
+        let packet_count = packets.len();
+        for (payload, marker) in packets {
+            let seq_num = VIDEO_SEQ_NUM.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+            let rtp_packet = RtpPacket {
+                header: RtpHeader {
+                    version: 2,
+                    padding: false,
+                    extension: false,
+                    marker, // Last packet of the frame
+                    payload_type: 102, // H.264 (registered as PT=102)
+                    sequence_number: seq_num as u16,
+                    timestamp: current_timestamp,
+                    ssrc: 0, // Will be set by track
+                    ..Default::default()
+                },
+                payload,
+            };
 
-                    offset += payload_size;
-                    frag_count += 1;
-                }
-            }
+            tokio_handle.block_on(async {
+                track
+                    .write_rtp(&rtp_packet)
+                    .await
+                    .map_err(|e| Error::Runtime(format!("Failed to write video RTP: {}", e)))
+            })?;
+        }
+
+        if counter == 0 {
+            tracing::info!(
+                "[WebRTC] Successfully wrote first video frame ({} RTP packets)",
+                packet_count
+            );
+        } else if counter.is_multiple_of(30) {
+            tracing::info!(
+                "[WebRTC] Video frame #{} sent ({} RTP packets)",
+                counter,
+                packet_count
+            );
         }
 
         // Increment timestamp for next frame
@@ -6,8 +6,14 @@
 // Transport-only: accepts pre-encoded video (EncodedVideoFrame) and audio
 // (EncodedAudioFrame), RTP-packetizes them, and sends via WebRTC WHIP.
 // Encoding is handled by upstream H264EncoderProcessor / OpusEncoderProcessor.
+//
+// Each stats poll also writes a CongestionReport on the optional
+// `congestion_out` output: the video stream's loss and round-trip time
+// from the peer's receiver reports, and the send rate since the last poll.
+// webrtc-rs negotiates transport-wide congestion control but exposes no
+// bandwidth estimate, so `available_bitrate_bps` is left unset.
 
-use crate::_generated_::{EncodedAudioFrame, EncodedVideoFrame};
+use crate::_generated_::{CongestionReport, EncodedAudioFrame, EncodedVideoFrame};
 use crate::streaming::{convert_audio_to_sample, convert_video_to_samples};
 use crate::streaming::{WhipClient, WhipConfig};
 use std::sync::Arc;
@@ -38,6 +44,7 @@ enum WhipClientMessage {
     config = crate::_generated_::WebrtcWhipConfig,
     input("encoded_video_in", "@tatolab/core/EncodedVideoFrame", description = "H.264 encoded video frames to stream"),
     input("encoded_audio_in", "@tatolab/core/EncodedAudioFrame", description = "Opus encoded audio frames to stream"),
+    output("congestion_out", "@tatolab/core/CongestionReport", description = "Loss, round-trip time and send rate of the video stream, from WebRTC stats"),
 )]
 pub struct WebRtcWhipProcessor {
     // Session state
@@ -62,6 +69,7 @@ pub struct WebRtcWhipProcessor {
 
     // Stats tracking
     last_stats_time_ns: i64,
+    last_video_bytes_sent: u64,
 }
 
 impl ReactiveProcessor for WebRtcWhipProcessor::Processor {
@@ -153,7 +161,7 @@ impl ReactiveProcessor for WebRtcWhipProcessor::Processor {
             let elapsed = current_time_ns - self.last_stats_time_ns;
 
             if elapsed >= 2_000_000_000 {
-                self.log_stats(ctx);
+                self.log_stats(ctx, elapsed);
                 self.last_stats_time_ns = current_time_ns;
             }
         }
@@ -271,7 +279,7 @@ impl WebRtcWhipProcessor::Processor {
         Ok(())
     }
 
-    fn log_stats(&self, _ctx: &RuntimeContextLimitedAccess<'_>) {
+    fn log_stats(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>, elapsed_ns: i64) {
         if let Some(pc) = &self.peer_connection_for_stats {
             let Some(tokio_handle) = self.tokio_handle.as_ref() else {
                 tracing::debug!("[WebRtcWhip] tokio runtime gone, skipping stats");
@@ -282,16 +290,45 @@ impl WebRtcWhipProcessor::Processor {
             let mut audio_bytes_sent = 0u64;
             let mut video_packets_sent = 0u64;
             let mut audio_packets_sent = 0u64;
+            let mut video_remote_inbound = None;
 
             for (_id, stat_type) in stats.reports.iter() {
-                if let webrtc::stats::StatsReportType::OutboundRTP(outbound) = stat_type {
-                    if outbound.kind == "video" {
-                        video_bytes_sent = outbound.bytes_sent;
-                        video_packets_sent = outbound.packets_sent;
-                    } else if outbound.kind == "audio" {
-                        audio_bytes_sent = outbound.bytes_sent;
-                        audio_packets_sent = outbound.packets_sent;
+                match stat_type {
+                    webrtc::stats::StatsReportType::OutboundRTP(outbound) => {
+                        if outbound.kind == "video" {
+                            video_bytes_sent = outbound.bytes_sent;
+                            video_packets_sent = outbound.packets_sent;
+                        } else if outbound.kind == "audio" {
+                            audio_bytes_sent = outbound.bytes_sent;
+                            audio_packets_sent = outbound.packets_sent;
+                        }
+                    }
+                    webrtc::stats::StatsReportType::RemoteInboundRTP(remote)
+                        if remote.kind == "video" =>
+                    {
+                        video_remote_inbound = Some(remote);
                     }
+                    _ => {}
+                }
+            }
+
+            let sent = video_bytes_sent.saturating_sub(self.last_video_bytes_sent);
+            self.last_video_bytes_sent = video_bytes_sent;
+            if self.outputs.has_port("congestion_out") {
+                let report = CongestionReport {
+                    timestamp_ns: MediaClock::now().as_nanos().to_string(),
+                    source: Some("webrtc".into()),
+                    fraction_lost: video_remote_inbound.map(|r| r.fraction_lost as f32),
+                    rtt_ms: video_remote_inbound
+                        .and_then(|r| r.round_trip_time)
+                        .map(|rtt| (rtt * 1000.0) as f32),
+                    jitter_ms: None,
+                    available_bitrate_bps: None,
+                    send_bitrate_bps: (elapsed_ns > 0)
+                        .then(|| (sent as f64 * 8.0 * 1e9 / elapsed_ns as f64) as u32),
+                };
+                if let Err(e) = self.outputs.write("congestion_out", &report) {
+                    tracing::warn!("[WebRtcWhip] Failed to write congestion report: {}", e);
                 }
             }
 
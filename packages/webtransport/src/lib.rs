@@ -0,0 +1,21 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `@tatolab/webtransport` — an HTTP/3 WebTransport data channel.
+//! `WebTransport` lets browser clients exchange control and data messages
+//! (detections, telemetry, remote control) with a running graph without
+//! going through the REST API.
+
+#[allow(non_snake_case, unused_imports, clippy::all)]
+pub mod _generated_ {
+    include!(concat!(env!("OUT_DIR"), "/_generated_shim.rs"));
+}
+
+pub mod webtransport;
+pub mod webtransport_server;
+
+pub use _generated_::WebTransportConfig;
+pub use webtransport::WebTransportProcessor;
+pub use webtransport_server::WebTransportCertificate;
+
+streamlib_plugin_abi::export_plugin!(crate::WebTransportProcessor::Processor,);
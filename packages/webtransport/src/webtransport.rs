@@ -0,0 +1,177 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! WebTransport — an HTTP/3 data channel between browsers and the graph.
+//!
+//! Browsers open a session with `new WebTransport("https://host:4433/path")`
+//! and exchange messages with the graph directly, without the REST API in
+//! between: detections and telemetry out, remote-control commands in.
+//! Type-agnostic like `MoqSubscribeTrack` — bytes pass through untouched;
+//! see [`crate::webtransport_server`] for the framing.
+
+use crate::webtransport_server::{
+    WebTransportCertificate, WebTransportServerState, run_webtransport_server,
+};
+use bytes::Bytes;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use streamlib_plugin_sdk::sdk::context::{RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use tokio::sync::broadcast;
+
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:4433";
+const DEFAULT_MAX_SESSIONS: u32 = 16;
+const DEFAULT_MAX_MESSAGE_BYTES: u32 = 1024 * 1024;
+
+/// Graph → browser messages buffered per session before a slow session
+/// starts losing them.
+const OUTBOUND_QUEUE_DEPTH: usize = 256;
+
+#[streamlib_plugin_sdk::sdk::processor(
+    "@tatolab/webtransport/WebTransport",
+    description = "Serves HTTP/3 WebTransport sessions: every message on data_in goes to each connected browser, and every message a browser sends (a datagram or a unidirectional stream) comes out of data_out.",
+    execution = reactive,
+    config = crate::_generated_::WebTransportConfig,
+    input("data_in", any, description = "Messages to send to every connected browser session (any serialized type)"),
+    output("data_out", any, description = "Messages received from browser sessions (any serialized type)"),
+)]
+pub struct WebTransportProcessor {
+    outbound: Option<broadcast::Sender<Bytes>>,
+    state: Option<Arc<WebTransportServerState>>,
+    shutdown_signal_sender: Option<tokio::sync::oneshot::Sender<()>>,
+    messages_sent: u64,
+    /// Plugin-owned tokio runtime. Constructed in `setup()`; the host's
+    /// runtime is not reachable across the plugin ABI per #885. Hosts the
+    /// quinn endpoint and every session task.
+    tokio_runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl streamlib_plugin_sdk::sdk::processors::ReactiveProcessor for WebTransportProcessor::Processor {
+    fn setup(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        let bind_address: SocketAddr = self
+            .config
+            .bind_address
+            .as_deref()
+            .unwrap_or(DEFAULT_BIND_ADDRESS)
+            .parse()
+            .map_err(|e| Error::Configuration(format!("WebTransport: bad bind_address: {e}")))?;
+        let certificate = match (
+            self.config.certificate_path.as_deref(),
+            self.config.private_key_path.as_deref(),
+        ) {
+            (Some(certificate_path), Some(private_key_path)) => {
+                WebTransportCertificate::load(certificate_path, private_key_path)?
+            }
+            (None, None) => {
+                let certificate = WebTransportCertificate::self_signed(vec!["localhost".into()])?;
+                tracing::info!(
+                    sha256 = %certificate.sha256_hex(),
+                    "[WebTransport] Using a self-signed development certificate; \
+                     pass this hash as serverCertificateHashes"
+                );
+                certificate
+            }
+            _ => {
+                return Err(Error::Configuration(
+                    "WebTransport: set certificate_path and private_key_path together".into(),
+                ));
+            }
+        };
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Error::Runtime(format!("WebTransport: failed to build tokio runtime: {e}"))
+            })?;
+        // The quinn endpoint binds its socket on the current runtime.
+        let server = {
+            let _guard = runtime.enter();
+            web_transport_quinn::ServerBuilder::new()
+                .with_addr(bind_address)
+                .with_certificate(certificate.chain, certificate.key)
+                .map_err(|e| {
+                    Error::Runtime(format!(
+                        "WebTransport: failed to listen on {bind_address}: {e}"
+                    ))
+                })?
+        };
+
+        let (outbound, _) = broadcast::channel(OUTBOUND_QUEUE_DEPTH);
+        let state = Arc::new(WebTransportServerState {
+            path: self.config.path.clone().unwrap_or_else(|| "/".to_string()),
+            max_sessions: self.config.max_sessions.unwrap_or(DEFAULT_MAX_SESSIONS) as usize,
+            max_message_bytes: self
+                .config
+                .max_message_bytes
+                .unwrap_or(DEFAULT_MAX_MESSAGE_BYTES) as usize,
+            datagrams: self.config.datagrams.unwrap_or(false),
+            outputs: self.outputs.clone(),
+            outbound: outbound.clone(),
+            active_sessions: AtomicUsize::new(0),
+            next_session_id: AtomicU64::new(1),
+            messages_received: AtomicU64::new(0),
+        });
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        runtime.spawn(run_webtransport_server(
+            server,
+            Arc::clone(&state),
+            shutdown_rx,
+        ));
+        tracing::info!(
+            %bind_address,
+            path = %state.path,
+            max_sessions = state.max_sessions,
+            datagrams = state.datagrams,
+            "[WebTransport] Listening"
+        );
+
+        self.outbound = Some(outbound);
+        self.state = Some(state);
+        self.shutdown_signal_sender = Some(shutdown_tx);
+        self.tokio_runtime = Some(runtime);
+        Ok(())
+    }
+
+    fn teardown(&mut self, _ctx: &RuntimeContextFullAccess<'_>) -> Result<()> {
+        tracing::info!(
+            messages_sent = self.messages_sent,
+            messages_received = self
+                .state
+                .as_ref()
+                .map_or(0, |state| state.messages_received.load(Ordering::Relaxed)),
+            "[WebTransport] Shutting down"
+        );
+        if let Some(tx) = self.shutdown_signal_sender.take() {
+            let _ = tx.send(());
+        }
+        self.outbound.take();
+        self.state.take();
+        // Dropping the runtime cancels the session tasks and closes the
+        // endpoint.
+        self.tokio_runtime.take();
+        Ok(())
+    }
+
+    fn process(&mut self, _ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()> {
+        if !self.inputs.has_data("data_in") {
+            return Ok(());
+        }
+        let Some((bytes, _timestamp_ns)) = self.inputs.read_raw("data_in")? else {
+            return Ok(());
+        };
+        let outbound = self
+            .outbound
+            .as_ref()
+            .ok_or_else(|| Error::Runtime("WebTransport server not started".into()))?;
+        // Fails only when no browser is connected; the message has nowhere
+        // to go.
+        if outbound.send(Bytes::from(bytes)).is_ok() {
+            self.messages_sent += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The HTTP/3 side of `WebTransport`: certificate, accept loop and one task
+//! per browser session.
+//!
+//! Message framing, both directions:
+//!
+//! - **Browser → graph**: every datagram, and every unidirectional stream
+//!   read to its end, is one message on `data_out`.
+//! - **Graph → browser**: every `data_in` message goes to each session on a
+//!   unidirectional stream of its own (closed after the message), or as a
+//!   datagram when `datagrams` is set.
+//!
+//! Messages are carried as-is: the bytes are the msgpack-encoded frame, so
+//! a browser decodes what the graph sends with any msgpack library and
+//! encodes what it sends in the schema the downstream processor reads.
+
+use bytes::Bytes;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::iceoryx2::OutputWriter;
+use streamlib_plugin_sdk::sdk::media_clock::MediaClock;
+use tokio::sync::{broadcast, oneshot};
+use web_transport_quinn::{RecvStream, Request, Server, Session};
+
+/// Browsers reject a `serverCertificateHashes` certificate valid for more
+/// than 14 days; stay under it.
+const SELF_SIGNED_VALIDITY_DAYS: i64 = 13;
+
+/// The server's TLS identity.
+pub struct WebTransportCertificate {
+    pub chain: Vec<CertificateDer<'static>>,
+    pub key: PrivateKeyDer<'static>,
+}
+
+impl WebTransportCertificate {
+    /// Load a PEM certificate chain and private key.
+    pub fn load(certificate_path: &str, private_key_path: &str) -> Result<Self> {
+        let chain = CertificateDer::pem_file_iter(certificate_path)
+            .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+            .map_err(|e| {
+                Error::Configuration(format!(
+                    "WebTransport: can't read certificate '{certificate_path}': {e}"
+                ))
+            })?;
+        if chain.is_empty() {
+            return Err(Error::Configuration(format!(
+                "WebTransport: '{certificate_path}' holds no certificate"
+            )));
+        }
+        let key = PrivateKeyDer::from_pem_file(private_key_path).map_err(|e| {
+            Error::Configuration(format!(
+                "WebTransport: can't read private key '{private_key_path}': {e}"
+            ))
+        })?;
+        Ok(Self { chain, key })
+    }
+
+    /// A fresh ECDSA P-256 certificate for `hostnames`, valid for 13 days —
+    /// the kind browsers accept by hash without a CA.
+    pub fn self_signed(hostnames: Vec<String>) -> Result<Self> {
+        let cert_error =
+            |e: rcgen::Error| Error::Runtime(format!("WebTransport: self-signed certificate: {e}"));
+        let mut params = rcgen::CertificateParams::new(hostnames).map_err(cert_error)?;
+        params.not_before = time::OffsetDateTime::now_utc() - time::Duration::hours(1);
+        params.not_after = params.not_before + time::Duration::days(SELF_SIGNED_VALIDITY_DAYS);
+        let key_pair = rcgen::KeyPair::generate().map_err(cert_error)?;
+        let cert = params.self_signed(&key_pair).map_err(cert_error)?;
+        Ok(Self {
+            chain: vec![cert.der().clone()],
+            key: PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der())),
+        })
+    }
+
+    /// Hex SHA-256 of the leaf certificate — the `serverCertificateHashes`
+    /// value a browser needs to trust a self-signed certificate.
+    pub fn sha256_hex(&self) -> String {
+        Sha256::digest(self.chain[0].as_ref())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+/// What every session task shares.
+pub(crate) struct WebTransportServerState {
+    pub path: String,
+    pub max_sessions: usize,
+    pub max_message_bytes: usize,
+    pub datagrams: bool,
+    pub outputs: OutputWriter,
+    pub outbound: broadcast::Sender<Bytes>,
+    pub active_sessions: AtomicUsize,
+    pub next_session_id: AtomicU64,
+    pub messages_received: AtomicU64,
+}
+
+/// Whether a request for `request_path` is for the configured `path`; a
+/// trailing slash on either side doesn't matter.
+pub(crate) fn path_matches(path: &str, request_path: &str) -> bool {
+    path.trim_end_matches('/') == request_path.trim_end_matches('/')
+}
+
+/// Accept sessions until `shutdown_rx` fires or the endpoint closes.
+pub(crate) async fn run_webtransport_server(
+    mut server: Server,
+    state: Arc<WebTransportServerState>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    loop {
+        let request = tokio::select! {
+            _ = &mut shutdown_rx => break,
+            request = server.accept() => match request {
+                Some(request) => request,
+                None => break,
+            },
+        };
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            if let Err(e) = accept_session(request, state).await {
+                tracing::warn!(%e, "[WebTransport] Session handshake failed");
+            }
+        });
+    }
+    tracing::info!("[WebTransport] Server stopped");
+}
+
+async fn accept_session(request: Request, state: Arc<WebTransportServerState>) -> Result<()> {
+    let url = request.url().clone();
+    if !path_matches(&state.path, url.path()) {
+        tracing::info!(url = %url, "[WebTransport] Refused session on unknown path");
+        return request
+            .close(http::StatusCode::NOT_FOUND)
+            .await
+            .map_err(|e| Error::Runtime(e.to_string()));
+    }
+    // Reserve the slot before the handshake so concurrent requests can't
+    // overshoot the limit.
+    if state.active_sessions.fetch_add(1, Ordering::AcqRel) >= state.max_sessions {
+        state.active_sessions.fetch_sub(1, Ordering::AcqRel);
+        tracing::warn!(
+            max_sessions = state.max_sessions,
+            "[WebTransport] Refused session: at capacity"
+        );
+        return request
+            .close(http::StatusCode::SERVICE_UNAVAILABLE)
+            .await
+            .map_err(|e| Error::Runtime(e.to_string()));
+    }
+
+    let result = match request.ok().await {
+        Ok(session) => {
+            let session_id = state.next_session_id.fetch_add(1, Ordering::Relaxed);
+            tracing::info!(session_id, url = %url, "[WebTransport] Session opened");
+            run_session(session, session_id, &state).await;
+            tracing::info!(session_id, "[WebTransport] Session closed");
+            Ok(())
+        }
+        Err(e) => Err(Error::Runtime(e.to_string())),
+    };
+    state.active_sessions.fetch_sub(1, Ordering::AcqRel);
+    result
+}
+
+/// Forward the session's messages to the graph and the graph's to the
+/// session until either side goes away.
+async fn run_session(session: Session, session_id: u64, state: &Arc<WebTransportServerState>) {
+    let mut outbound = state.outbound.subscribe();
+    loop {
+        tokio::select! {
+            stream = session.accept_uni() => match stream {
+                // Read each stream on its own task so a slow sender can't
+                // hold up the session's other traffic.
+                Ok(stream) => {
+                    let state = Arc::clone(state);
+                    tokio::spawn(async move {
+                        read_stream_message(stream, session_id, &state).await
+                    });
+                }
+                Err(_) => break,
+            },
+            datagram = session.read_datagram() => match datagram {
+                Ok(datagram) => forward_to_graph(&datagram, session_id, state),
+                Err(_) => break,
+            },
+            message = outbound.recv() => match message {
+                Ok(message) => {
+                    if let Err(e) = send_to_session(&session, message, state.datagrams).await {
+                        tracing::info!(
+                            session_id,
+                            %e,
+                            "[WebTransport] Send failed, dropping session"
+                        );
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        session_id,
+                        skipped,
+                        "[WebTransport] Session fell behind, messages dropped"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+async fn read_stream_message(
+    mut stream: RecvStream,
+    session_id: u64,
+    state: &WebTransportServerState,
+) {
+    match stream.read_to_end(state.max_message_bytes).await {
+        Ok(message) => forward_to_graph(&message, session_id, state),
+        Err(e) => tracing::warn!(
+            session_id,
+            %e,
+            max_message_bytes = state.max_message_bytes,
+            "[WebTransport] Dropped unreadable or oversized stream message"
+        ),
+    }
+}
+
+fn forward_to_graph(message: &[u8], session_id: u64, state: &WebTransportServerState) {
+    let received = state.messages_received.fetch_add(1, Ordering::Relaxed) + 1;
+    if received == 1 {
+        tracing::info!(session_id, "[WebTransport] First message received");
+    }
+    // Unwired output: messages are counted but go nowhere.
+    if !state.outputs.has_port("data_out") {
+        return;
+    }
+    let timestamp_ns = MediaClock::now().as_nanos() as i64;
+    if let Err(e) = state.outputs.write_raw("data_out", message, timestamp_ns) {
+        tracing::warn!(session_id, %e, "[WebTransport] Failed to write message to data_out");
+    }
+}
+
+async fn send_to_session(session: &Session, message: Bytes, datagram: bool) -> Result<()> {
+    if datagram {
+        return session
+            .send_datagram(message)
+            .map_err(|e| Error::Runtime(e.to_string()));
+    }
+    let mut stream = session
+        .open_uni()
+        .await
+        .map_err(|e| Error::Runtime(e.to_string()))?;
+    stream
+        .write_all(&message)
+        .await
+        .map_err(|e| Error::Runtime(e.to_string()))?;
+    stream.finish().map_err(|e| Error::Runtime(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_match_ignoring_trailing_slashes() {
+        assert!(path_matches("/", "/"));
+        assert!(path_matches("/", ""));
+        assert!(path_matches("/graph", "/graph/"));
+        assert!(path_matches("/graph/", "/graph"));
+        assert!(!path_matches("/graph", "/"));
+        assert!(!path_matches("/graph", "/graph/other"));
+    }
+
+    #[test]
+    fn self_signed_certificate_is_hashable() {
+        let certificate = WebTransportCertificate::self_signed(vec!["localhost".into()]).unwrap();
+        assert_eq!(certificate.chain.len(), 1);
+        let hash = certificate.sha256_hex();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
@@ -133,6 +133,7 @@ fn build_inner_with_connection(tag: &str) -> BenchFixture {
             trust_tier: ChannelTrustTier::Trusted,
             expected_payload_bytes: 4096,
             ceiling_bytes: TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+            cipher: None,
         },
     );
     inner.add_channel_notifier("out", "L-bench-ffi-hop", notifier);
@@ -260,6 +261,7 @@ fn build_inner_with_fanout(tag: &str, subscriber_count: usize) -> FanoutFixture
             trust_tier: ChannelTrustTier::Trusted,
             expected_payload_bytes: 4096,
             ceiling_bytes: TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+            cipher: None,
         },
     );
 
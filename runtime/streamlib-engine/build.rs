@@ -113,6 +113,11 @@ fn compile_shaders() {
             "color_convert_yuyv_buffer_to_rgba.spv",
             "compute",
         ),
+        (
+            "src/vulkan/rhi/shaders/color_convert_bgra_buffer_to_rgba.comp",
+            "color_convert_bgra_buffer_to_rgba.spv",
+            "compute",
+        ),
         (
             "src/vulkan/rhi/shaders/tone_curve.comp",
             "tone_curve.spv",
@@ -0,0 +1,426 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Content-addressed cache for the files processors load by URL — LUTs,
+//! stills, fonts, models, subtitle tracks.
+//!
+//! Fetched files live under the working tree's `assets/` directory
+//! ([`get_streamlib_data_dir`]`/assets`):
+//!
+//! ```text
+//! assets/
+//! ├── sha256/<digest>/<file-name>   # the bytes, keyed by their SHA-256
+//! ├── urls/<sha256-of-url>           # URL → content digest index
+//! └── tmp/                           # in-flight downloads
+//! ```
+//!
+//! The URL's file name is kept so loaders that sniff extensions (`.cube`,
+//! `.onnx`, `.srt`) work on the cached path. A pinned fetch whose digest is
+//! already cached never touches the network, whatever URL it names; an
+//! unpinned fetch is served from the URL index once that URL has been
+//! fetched. In offline mode every cache miss is [`Error::AssetOffline`].
+//!
+//! Downloads publish [`RuntimeEvent::AssetFetchProgress`] /
+//! [`RuntimeEvent::AssetFetchDidComplete`] / [`RuntimeEvent::AssetFetchDidFail`]
+//! on [`topics::RUNTIME_GLOBAL`]; cache hits publish nothing.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use sha2::{Digest, Sha256};
+
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+use crate::core::streamlib_home::get_streamlib_data_dir;
+use crate::core::{Error, Result};
+
+/// Environment variable that starts every [`AssetManager::from_environment`]
+/// in offline mode when set to `1` / `true`.
+pub const ASSETS_OFFLINE_ENV: &str = "STREAMLIB_ASSETS_OFFLINE";
+
+/// Bytes between two [`RuntimeEvent::AssetFetchProgress`] events.
+const PROGRESS_EVENT_STRIDE_BYTES: u64 = 1024 * 1024;
+
+/// Fetches remote assets into the content-addressed cache. One per
+/// runtime, reached from processors as `ctx.assets()`.
+pub struct AssetManager {
+    root: PathBuf,
+    offline: AtomicBool,
+}
+
+impl AssetManager {
+    /// An asset cache rooted at `root`, online.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            offline: AtomicBool::new(false),
+        }
+    }
+
+    /// The working tree's asset cache, offline when [`ASSETS_OFFLINE_ENV`]
+    /// says so.
+    pub fn from_environment() -> Self {
+        let manager = Self::new(get_streamlib_data_dir().join("assets"));
+        let offline = std::env::var(ASSETS_OFFLINE_ENV)
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "True"));
+        manager.set_offline(offline);
+        manager
+    }
+
+    /// Directory the cache lives in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Acquire)
+    }
+
+    /// Serve fetches from the cache only. Cache misses fail with
+    /// [`Error::AssetOffline`] instead of going to the network.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Release);
+    }
+
+    /// Path of the cached copy of `url`, downloading it on first use.
+    /// Blocks for the download — call it from `setup()`, not `process()`.
+    pub fn fetch(&self, url: &str) -> Result<PathBuf> {
+        self.fetch_impl(url, None)
+    }
+
+    /// Like [`Self::fetch`], but the content must hash to `sha256` (hex).
+    /// A cached copy with that digest is returned without touching the
+    /// network; a download that hashes differently is discarded.
+    pub fn fetch_pinned(&self, url: &str, sha256: &str) -> Result<PathBuf> {
+        let pin = sha256.trim().to_ascii_lowercase();
+        if pin.len() != 64 || !pin.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::AssetFetchFailed {
+                url: url.to_string(),
+                detail: format!("'{sha256}' is not a SHA-256 hex digest"),
+            });
+        }
+        self.fetch_impl(url, Some(&pin))
+    }
+
+    fn fetch_impl(&self, url: &str, pin: Option<&str>) -> Result<PathBuf> {
+        let cached = match pin {
+            Some(digest) => self.content_path(digest),
+            None => self
+                .indexed_digest(url)
+                .and_then(|digest| self.content_path(&digest)),
+        };
+        if let Some(path) = cached {
+            tracing::debug!("[assets] {} served from cache: {}", url, path.display());
+            return Ok(path);
+        }
+        if self.is_offline() {
+            return Err(Error::AssetOffline(url.to_string()));
+        }
+
+        let result = self.download(url, pin);
+        if let Err(e) = &result {
+            publish(RuntimeEvent::AssetFetchDidFail {
+                url: url.to_string(),
+                error: e.to_string(),
+            });
+        }
+        result
+    }
+
+    /// Stream `url` into `tmp/`, hashing as it goes, then move it into
+    /// place under its digest and index the URL.
+    fn download(&self, url: &str, pin: Option<&str>) -> Result<PathBuf> {
+        let fetch_err = |detail: String| Error::AssetFetchFailed {
+            url: url.to_string(),
+            detail,
+        };
+
+        let (mut reader, total_bytes) = open_source(url).map_err(fetch_err)?;
+
+        let tmp_dir = self.root.join("tmp");
+        std::fs::create_dir_all(&tmp_dir)?;
+        let tmp_path = tmp_dir.join(format!("{}.part", uuid::Uuid::new_v4()));
+        let written = (|| -> std::io::Result<(String, u64)> {
+            let mut file = std::fs::File::create(&tmp_path)?;
+            let mut hasher = Sha256::new();
+            let mut buf = vec![0u8; 64 * 1024];
+            let mut bytes_received = 0u64;
+            let mut next_progress = PROGRESS_EVENT_STRIDE_BYTES;
+            loop {
+                let n = reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+                file.write_all(&buf[..n])?;
+                bytes_received += n as u64;
+                if bytes_received >= next_progress {
+                    next_progress = bytes_received + PROGRESS_EVENT_STRIDE_BYTES;
+                    publish(RuntimeEvent::AssetFetchProgress {
+                        url: url.to_string(),
+                        bytes_received,
+                        total_bytes,
+                    });
+                }
+            }
+            file.sync_all()?;
+            Ok((hex(&hasher.finalize()), bytes_received))
+        })();
+        let (digest, bytes) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = std::fs::remove_file(&tmp_path);
+                return Err(fetch_err(format!("downloading: {e}")));
+            }
+        };
+
+        if let Some(expected) = pin
+            && digest != expected
+        {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(fetch_err(format!(
+                "sha256 expected {expected}, got {digest}"
+            )));
+        }
+
+        let content_dir = self.root.join("sha256").join(&digest);
+        std::fs::create_dir_all(&content_dir)?;
+        let path = content_dir.join(file_name_for(url));
+        std::fs::rename(&tmp_path, &path)?;
+        self.write_index(url, &digest)?;
+
+        tracing::info!(
+            "[assets] Fetched {} ({} bytes, sha256 {})",
+            url,
+            bytes,
+            digest
+        );
+        publish(RuntimeEvent::AssetFetchDidComplete {
+            url: url.to_string(),
+            sha256: digest,
+            bytes,
+        });
+        Ok(path)
+    }
+
+    /// The cached file for `digest`, if one is on disk.
+    fn content_path(&self, digest: &str) -> Option<PathBuf> {
+        std::fs::read_dir(self.root.join("sha256").join(digest))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| path.is_file())
+    }
+
+    fn index_path(&self, url: &str) -> PathBuf {
+        self.root
+            .join("urls")
+            .join(hex(&Sha256::digest(url.as_bytes())))
+    }
+
+    fn indexed_digest(&self, url: &str) -> Option<String> {
+        let digest = std::fs::read_to_string(self.index_path(url)).ok()?;
+        Some(digest.trim().to_string())
+    }
+
+    fn write_index(&self, url: &str, digest: &str) -> Result<()> {
+        let index_path = self.index_path(url);
+        if let Some(parent) = index_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write-then-rename so a concurrent reader never sees a torn digest.
+        let tmp_path = self
+            .root
+            .join("tmp")
+            .join(format!("{}.index", uuid::Uuid::new_v4()));
+        std::fs::write(&tmp_path, digest)?;
+        std::fs::rename(&tmp_path, &index_path)?;
+        Ok(())
+    }
+}
+
+/// Open `url` for reading, with its length when the source reports one.
+fn open_source(url: &str) -> std::result::Result<(Box<dyn Read>, Option<u64>), String> {
+    if let Some(path) = url.strip_prefix("file://") {
+        let file = std::fs::File::open(path).map_err(|e| format!("opening {path}: {e}"))?;
+        let len = file.metadata().ok().map(|metadata| metadata.len());
+        return Ok((Box::new(file), len));
+    }
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let response = ureq::get(url)
+            .call()
+            .map_err(|e| format!("HTTP request failed: {e}"))?;
+        let len = response
+            .header("Content-Length")
+            .and_then(|value| value.parse().ok());
+        return Ok((Box::new(response.into_reader()), len));
+    }
+    Err("unsupported URL scheme (expected file://, http://, or https://)".to_string())
+}
+
+/// The last path segment of `url`, reduced to a safe file name.
+fn file_name_for(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let segment = path.rsplit('/').next().unwrap_or("");
+    let name: String = segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.trim_matches('.').is_empty() {
+        "asset".to_string()
+    } else {
+        name
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn publish(event: RuntimeEvent) {
+    PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256_of(bytes: &[u8]) -> String {
+        hex(&Sha256::digest(bytes))
+    }
+
+    /// A `file://` source holding `bytes`, named `name`.
+    fn source_file(dir: &Path, name: &str, bytes: &[u8]) -> String {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        format!("file://{}", path.display())
+    }
+
+    #[test]
+    fn fetch_caches_by_content_and_keeps_the_file_name() {
+        let cache = tempfile::tempdir().unwrap();
+        let sources = tempfile::tempdir().unwrap();
+        let url = source_file(sources.path(), "warm.cube", b"LUT_3D_SIZE 2");
+        let assets = AssetManager::new(cache.path());
+
+        let path = assets.fetch(&url).unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"LUT_3D_SIZE 2");
+        assert_eq!(path.file_name().unwrap(), "warm.cube");
+        assert_eq!(
+            path.parent().unwrap(),
+            cache
+                .path()
+                .join("sha256")
+                .join(sha256_of(b"LUT_3D_SIZE 2"))
+        );
+    }
+
+    #[test]
+    fn a_fetched_url_is_served_offline_from_the_index() {
+        let cache = tempfile::tempdir().unwrap();
+        let sources = tempfile::tempdir().unwrap();
+        let url = source_file(sources.path(), "still.png", b"png");
+        let assets = AssetManager::new(cache.path());
+        let first = assets.fetch(&url).unwrap();
+
+        std::fs::remove_file(sources.path().join("still.png")).unwrap();
+        assets.set_offline(true);
+
+        assert_eq!(assets.fetch(&url).unwrap(), first);
+        let missing = source_file(sources.path(), "other.png", b"other");
+        assert!(matches!(
+            assets.fetch(&missing),
+            Err(Error::AssetOffline(_))
+        ));
+    }
+
+    #[test]
+    fn a_pinned_digest_already_cached_needs_no_source() {
+        let cache = tempfile::tempdir().unwrap();
+        let sources = tempfile::tempdir().unwrap();
+        let url = source_file(sources.path(), "model.onnx", b"weights");
+        let assets = AssetManager::new(cache.path());
+        let cached = assets.fetch(&url).unwrap();
+        assets.set_offline(true);
+
+        let mirror = "https://mirror.invalid/model.onnx";
+        let pin = sha256_of(b"weights").to_ascii_uppercase();
+        assert_eq!(assets.fetch_pinned(mirror, &pin).unwrap(), cached);
+    }
+
+    #[test]
+    fn a_pin_mismatch_is_rejected_and_nothing_is_cached() {
+        let cache = tempfile::tempdir().unwrap();
+        let sources = tempfile::tempdir().unwrap();
+        let url = source_file(sources.path(), "font.ttf", b"glyphs");
+        let assets = AssetManager::new(cache.path());
+
+        let err = assets
+            .fetch_pinned(&url, &sha256_of(b"other glyphs"))
+            .expect_err("digest mismatch must fail");
+        assert!(matches!(err, Error::AssetFetchFailed { .. }));
+        assert!(!cache.path().join("urls").exists());
+        assert!(
+            std::fs::read_dir(cache.path().join("tmp"))
+                .unwrap()
+                .next()
+                .is_none()
+        );
+
+        assert!(matches!(
+            assets.fetch_pinned(&url, "not-a-digest"),
+            Err(Error::AssetFetchFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn fetch_http_url_downloads_once() {
+        use std::io::{Read, Write};
+
+        let body = b"1\n00:00:00,000 --> 00:00:01,000\nhi\n".to_vec();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let body_for_server = body.clone();
+        let server = std::thread::spawn(move || {
+            // Exactly one connection — a second fetch must hit the cache.
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body_for_server.len()
+                );
+                let _ = stream.write_all(response.as_bytes());
+                let _ = stream.write_all(&body_for_server);
+                let _ = stream.flush();
+            }
+        });
+
+        let cache = tempfile::tempdir().unwrap();
+        let assets = AssetManager::new(cache.path());
+        let url = format!("http://127.0.0.1:{port}/subs/track.srt?lang=en");
+        let path = assets.fetch(&url).expect("http fetch must succeed");
+        server.join().unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert_eq!(path.file_name().unwrap(), "track.srt");
+        assert_eq!(assets.fetch(&url).unwrap(), path);
+    }
+
+    #[test]
+    fn file_names_are_sanitized() {
+        assert_eq!(
+            file_name_for("https://x.test/a/b%20c.cube?v=2"),
+            "b_20c.cube"
+        );
+        assert_eq!(file_name_for("https://x.test/dir/"), "asset");
+        assert_eq!(file_name_for("https://x.test/.."), "asset");
+    }
+}
@@ -0,0 +1,463 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Chaos mode: injecting faults into a running graph to exercise
+//! watchdogs, failover and reconnect paths.
+//!
+//! A [`ChaosMode`] lists the faults the runner may draw from and how
+//! often; enabled with
+//! [`Runner::enable_chaos_mode`](crate::core::runtime::Runner::enable_chaos_mode),
+//! the runner injects one at random every `interval_ms` while started, and
+//! [`Runner::inject_chaos_fault`](crate::core::runtime::Runner::inject_chaos_fault)
+//! injects a given one on request. Faults with a duration are lifted when
+//! it runs out.
+//!
+//! ```json
+//! { "interval_ms": 30000, "seed": 7,
+//!   "faults": [{ "kind": "drop_frames", "link_id": "L1", "percent": 20.0, "duration_ms": 5000 },
+//!              { "kind": "stall_link", "link_id": "L2", "duration_ms": 2000 }] }
+//! ```
+//!
+//! Link faults act where a destination receives from its channels, so
+//! they need an in-process destination: stalling stops receiving (the
+//! channel backs up as it would behind a hung consumer), dropped frames
+//! count as dropped in the link's drop accounting, and corruption flips
+//! payload bytes — only in integrity-check mode, for graphs whose
+//! consumers validate what they decode.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::graph::{LinkUniqueId, ProcessorUniqueId};
+use crate::core::{Error, Result};
+use crate::iceoryx2::FRAME_HEADER_SIZE;
+
+/// One fault to inject.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChaosFault {
+    /// Remove a processor from the graph, as if it had crashed.
+    KillProcessor {
+        #[schema(value_type = String)]
+        processor_id: ProcessorUniqueId,
+    },
+    /// Stop delivering a link's frames for `duration_ms`.
+    StallLink {
+        #[schema(value_type = String)]
+        link_id: LinkUniqueId,
+        duration_ms: u64,
+    },
+    /// Drop `percent` of a link's frames for `duration_ms`.
+    DropFrames {
+        #[schema(value_type = String)]
+        link_id: LinkUniqueId,
+        percent: f64,
+        duration_ms: u64,
+    },
+    /// Flip a payload byte in `percent` of a link's frames for
+    /// `duration_ms`. Integrity-check mode only.
+    CorruptPayload {
+        #[schema(value_type = String)]
+        link_id: LinkUniqueId,
+        percent: f64,
+        duration_ms: u64,
+    },
+    /// Tear a sink down — closing whatever connections it holds — and
+    /// bring it back with its config and links after `duration_ms`. The
+    /// sink returns under a new processor id.
+    DisconnectNetworkSink {
+        #[schema(value_type = String)]
+        processor_id: ProcessorUniqueId,
+        duration_ms: u64,
+    },
+}
+
+impl ChaosFault {
+    /// The fault's `kind` tag.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::KillProcessor { .. } => "kill_processor",
+            Self::StallLink { .. } => "stall_link",
+            Self::DropFrames { .. } => "drop_frames",
+            Self::CorruptPayload { .. } => "corrupt_payload",
+            Self::DisconnectNetworkSink { .. } => "disconnect_network_sink",
+        }
+    }
+
+    /// The processor or link the fault targets.
+    pub fn target(&self) -> String {
+        match self {
+            Self::KillProcessor { processor_id }
+            | Self::DisconnectNetworkSink { processor_id, .. } => processor_id.to_string(),
+            Self::StallLink { link_id, .. }
+            | Self::DropFrames { link_id, .. }
+            | Self::CorruptPayload { link_id, .. } => link_id.to_string(),
+        }
+    }
+
+    /// How long the fault lasts; `None` for a kill, which is not undone.
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            Self::KillProcessor { .. } => None,
+            Self::StallLink { duration_ms, .. }
+            | Self::DropFrames { duration_ms, .. }
+            | Self::CorruptPayload { duration_ms, .. }
+            | Self::DisconnectNetworkSink { duration_ms, .. } => {
+                Some(Duration::from_millis(*duration_ms))
+            }
+        }
+    }
+
+    /// Check the fault's parameters; `integrity_check` is whether the
+    /// chaos mode permits payload corruption.
+    pub fn validate(&self, integrity_check: bool) -> Result<()> {
+        match self {
+            Self::DropFrames { percent, .. } | Self::CorruptPayload { percent, .. }
+                if !(0.0..=100.0).contains(percent) =>
+            {
+                return Err(Error::Configuration(format!(
+                    "{}: percent must be within 0..=100, got {percent}",
+                    self.kind()
+                )));
+            }
+            Self::CorruptPayload { .. } if !integrity_check => {
+                return Err(Error::Configuration(
+                    "corrupt_payload needs a chaos mode with integrity_check on".into(),
+                ));
+            }
+            _ => {}
+        }
+        if self.duration().is_some_and(|duration| duration.is_zero()) {
+            return Err(Error::Configuration(format!(
+                "{}: duration_ms must be greater than zero",
+                self.kind()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What a runner in chaos mode may inject, and how often.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChaosMode {
+    /// Faults scheduled injection draws from, uniformly.
+    #[serde(default)]
+    pub faults: Vec<ChaosFault>,
+    /// Time between scheduled injections. Zero: faults are only injected
+    /// on request.
+    #[serde(default)]
+    pub interval_ms: u64,
+    /// Seed for the fault draws and per-frame decisions, for reproducible
+    /// runs. Omitted: seeded from the clock.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Permit [`ChaosFault::CorruptPayload`]. Only for graphs whose
+    /// consumers check what they decode; a corrupted frame otherwise
+    /// reaches them as-is.
+    #[serde(default)]
+    pub integrity_check: bool,
+}
+
+impl ChaosMode {
+    pub fn validate(&self) -> Result<()> {
+        if self.interval_ms > 0 && self.faults.is_empty() {
+            return Err(Error::Configuration(
+                "chaos mode: interval_ms is set but there are no faults to draw from".into(),
+            ));
+        }
+        self.faults
+            .iter()
+            .try_for_each(|fault| fault.validate(self.integrity_check))
+    }
+
+    pub fn interval(&self) -> Option<Duration> {
+        (self.interval_ms > 0).then(|| Duration::from_millis(self.interval_ms))
+    }
+}
+
+/// What asked for a fault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChaosFaultSource {
+    /// Drawn by chaos mode's schedule.
+    Scheduled,
+    /// Injected through `inject_chaos_fault`.
+    Requested,
+}
+
+/// Log entry for one injected fault.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChaosFaultRecord {
+    pub fault_id: u64,
+    pub fault: ChaosFault,
+    pub source: ChaosFaultSource,
+    /// Unix-epoch milliseconds.
+    pub injected_at_ms: u64,
+    /// When the fault was lifted; `None` while active, and for kills.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleared_at_ms: Option<u64>,
+    /// Why injecting (or lifting) the fault failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// For [`ChaosFault::DisconnectNetworkSink`], the id the sink came
+    /// back under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub restored_processor_id: Option<ProcessorUniqueId>,
+}
+
+/// Small splitmix64 generator: enough for fault draws, and lock-free
+/// when shared.
+#[derive(Debug)]
+pub(crate) struct ChaosRng(AtomicU64);
+
+impl ChaosRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(AtomicU64::new(seed))
+    }
+
+    pub(crate) fn next_u64(&self) -> u64 {
+        let mut z = self
+            .0
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound`; `bound` must be non-zero.
+    pub(crate) fn below(&self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+/// Per-million resolution of the per-frame percentages.
+const PER_MILLION: u32 = 1_000_000;
+
+fn percent_to_per_million(percent: f64) -> u32 {
+    (percent.clamp(0.0, 100.0) * 10_000.0).round() as u32
+}
+
+/// What the receive path does with one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LinkChaosVerdict {
+    Deliver,
+    Drop,
+    Corrupt,
+}
+
+/// Faults currently applied to one link, read per frame by the
+/// destination's receive path.
+#[derive(Debug)]
+pub(crate) struct LinkChaos {
+    stalled: AtomicBool,
+    drop_per_million: AtomicU32,
+    corrupt_per_million: AtomicU32,
+    rng: ChaosRng,
+}
+
+impl LinkChaos {
+    fn new(seed: u64) -> Self {
+        Self {
+            stalled: AtomicBool::new(false),
+            drop_per_million: AtomicU32::new(0),
+            corrupt_per_million: AtomicU32::new(0),
+            rng: ChaosRng::new(seed),
+        }
+    }
+
+    /// Whether the destination should leave the channel unread.
+    pub fn is_stalled(&self) -> bool {
+        self.stalled.load(Ordering::Relaxed)
+    }
+
+    /// Decide one frame's fate. No draw is made while the link is clean.
+    pub fn verdict(&self) -> LinkChaosVerdict {
+        let drop = self.drop_per_million.load(Ordering::Relaxed);
+        let corrupt = self.corrupt_per_million.load(Ordering::Relaxed);
+        if drop == 0 && corrupt == 0 {
+            return LinkChaosVerdict::Deliver;
+        }
+        if drop > 0 && self.rng.below(PER_MILLION as u64) < drop as u64 {
+            return LinkChaosVerdict::Drop;
+        }
+        if corrupt > 0 && self.rng.below(PER_MILLION as u64) < corrupt as u64 {
+            return LinkChaosVerdict::Corrupt;
+        }
+        LinkChaosVerdict::Deliver
+    }
+
+    /// Invert one byte of the frame's payload, leaving the header intact
+    /// so the frame still routes. Frames without a payload are untouched.
+    pub fn corrupt(&self, frame: &mut [u8]) {
+        let payload_len = frame.len().saturating_sub(FRAME_HEADER_SIZE);
+        if payload_len > 0 {
+            let offset = FRAME_HEADER_SIZE + self.rng.below(payload_len as u64) as usize;
+            frame[offset] = !frame[offset];
+        }
+    }
+
+    pub(crate) fn set_stalled(&self, stalled: bool) {
+        self.stalled.store(stalled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_drop_percent(&self, percent: f64) {
+        self.drop_per_million
+            .store(percent_to_per_million(percent), Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_corrupt_percent(&self, percent: f64) {
+        self.corrupt_per_million
+            .store(percent_to_per_million(percent), Ordering::Relaxed);
+    }
+}
+
+/// Fault state of every wired link, keyed by link id. Entries are made
+/// when a destination binds the link's channel and dropped when it
+/// unbinds, so the receive path holds its handle without a lookup.
+static LINK_CHAOS: LazyLock<Mutex<HashMap<String, Arc<LinkChaos>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The fault state for `link_id`, created clean if the link has none.
+pub(crate) fn link_chaos(link_id: &str) -> Arc<LinkChaos> {
+    Arc::clone(LINK_CHAOS.lock().entry(link_id.to_string()).or_insert_with(|| {
+        let mut hasher = DefaultHasher::new();
+        link_id.hash(&mut hasher);
+        Arc::new(LinkChaos::new(hasher.finish()))
+    }))
+}
+
+/// The fault state for `link_id`, if its channel is bound.
+pub(crate) fn bound_link_chaos(link_id: &str) -> Option<Arc<LinkChaos>> {
+    LINK_CHAOS.lock().get(link_id).cloned()
+}
+
+/// Forget `link_id`'s fault state once its destination unbinds it.
+pub(crate) fn release_link_chaos(link_id: &str) {
+    LINK_CHAOS.lock().remove(link_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop_frames(percent: f64, duration_ms: u64) -> ChaosFault {
+        ChaosFault::DropFrames {
+            link_id: "L1".into(),
+            percent,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn faults_round_trip_through_their_kind_tag() {
+        let json = r#"{"kind":"stall_link","link_id":"L1","duration_ms":250}"#;
+        let fault: ChaosFault = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            fault,
+            ChaosFault::StallLink {
+                link_id: "L1".into(),
+                duration_ms: 250
+            }
+        );
+        assert_eq!(fault.kind(), "stall_link");
+        assert_eq!(fault.target(), "L1");
+        assert_eq!(serde_json::to_string(&fault).unwrap(), json);
+    }
+
+    #[test]
+    fn validation_rejects_bad_percentages_zero_durations_and_unpermitted_corruption() {
+        assert!(drop_frames(25.0, 1000).validate(false).is_ok());
+        assert!(drop_frames(120.0, 1000).validate(false).is_err());
+        assert!(drop_frames(-1.0, 1000).validate(false).is_err());
+        assert!(drop_frames(25.0, 0).validate(false).is_err());
+
+        let corrupt = ChaosFault::CorruptPayload {
+            link_id: "L1".into(),
+            percent: 5.0,
+            duration_ms: 1000,
+        };
+        assert!(corrupt.validate(false).is_err());
+        assert!(corrupt.validate(true).is_ok());
+
+        let kill = ChaosFault::KillProcessor {
+            processor_id: "cam".into(),
+        };
+        assert!(kill.validate(false).is_ok());
+        assert_eq!(kill.duration(), None);
+    }
+
+    #[test]
+    fn a_schedule_needs_faults_to_draw_from() {
+        let mode = ChaosMode {
+            interval_ms: 1000,
+            ..Default::default()
+        };
+        assert!(mode.validate().is_err());
+        assert!(ChaosMode::default().validate().is_ok());
+        assert_eq!(ChaosMode::default().interval(), None);
+    }
+
+    #[test]
+    fn a_clean_link_delivers_everything_and_a_full_drop_drops_everything() {
+        let chaos = LinkChaos::new(1);
+        assert!((0..1000).all(|_| chaos.verdict() == LinkChaosVerdict::Deliver));
+
+        chaos.set_drop_percent(100.0);
+        assert!((0..1000).all(|_| chaos.verdict() == LinkChaosVerdict::Drop));
+
+        chaos.set_drop_percent(0.0);
+        chaos.set_corrupt_percent(100.0);
+        assert!((0..1000).all(|_| chaos.verdict() == LinkChaosVerdict::Corrupt));
+    }
+
+    #[test]
+    fn partial_drop_rate_lands_near_the_requested_percentage() {
+        let chaos = LinkChaos::new(42);
+        chaos.set_drop_percent(20.0);
+        let dropped = (0..100_000)
+            .filter(|_| chaos.verdict() == LinkChaosVerdict::Drop)
+            .count();
+        assert!((18_000..22_000).contains(&dropped), "dropped {dropped}");
+    }
+
+    #[test]
+    fn corruption_leaves_the_header_and_flips_one_payload_byte() {
+        let chaos = LinkChaos::new(3);
+        let mut frame = vec![0u8; FRAME_HEADER_SIZE + 16];
+        chaos.corrupt(&mut frame);
+        assert!(frame[..FRAME_HEADER_SIZE].iter().all(|&b| b == 0));
+        assert_eq!(
+            frame[FRAME_HEADER_SIZE..]
+                .iter()
+                .filter(|&&b| b == 0xFF)
+                .count(),
+            1
+        );
+
+        let mut header_only = vec![0u8; FRAME_HEADER_SIZE];
+        chaos.corrupt(&mut header_only);
+        assert!(header_only.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn link_chaos_is_shared_per_link_until_released() {
+        let first = link_chaos("chaos-test-link");
+        first.set_stalled(true);
+        assert!(link_chaos("chaos-test-link").is_stalled());
+        assert!(bound_link_chaos("chaos-test-link").is_some());
+
+        release_link_chaos("chaos-test-link");
+        assert!(bound_link_chaos("chaos-test-link").is_none());
+        assert!(!link_chaos("chaos-test-link").is_stalled());
+        release_link_chaos("chaos-test-link");
+    }
+}
@@ -12,7 +12,9 @@ mod subprocess_escalate;
 
 pub use open_iceoryx2_service_op::{close_iceoryx2_service, open_iceoryx2_service};
 pub(crate) use open_iceoryx2_service_op::{
-    ChannelSizing, find_channel_source_port, resolve_channel_sizing,
+    ChannelSizing, FederatedChannel, channel_cipher, close_federated_link,
+    find_channel_source_port, open_federated_dest, open_federated_source, resolve_channel_cipher,
+    resolve_channel_sizing, resolve_federated_channel,
 };
 pub(crate) use prepare_processor_op::prepare_processor;
 pub(crate) use spawn_deno_subprocess_op::create_deno_subprocess_host_constructor;
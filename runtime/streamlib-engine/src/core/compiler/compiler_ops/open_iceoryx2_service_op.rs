@@ -11,6 +11,10 @@
 //! plus one reserved slot for a phase-3.5 tap. The paired Event (notify) service
 //! stays destination-keyed (`streamlib/{dest}/notify`) so a destination waits on
 //! ONE listener fd regardless of fan-in.
+//!
+//! A channel is encrypted when the link that installs its publisher asks for it
+//! ([`LinkEncryptionComponent`]); every later link out of the port subscribes
+//! under the same key. See [`crate::iceoryx2::PayloadCipher`].
 
 use std::sync::Arc;
 
@@ -24,14 +28,16 @@ use crate::core::embedded_schemas::{
 };
 use crate::core::error::{Error, Result};
 use crate::core::graph::{
-    Graph, GraphEdgeWithComponents, GraphNodeWithComponents, LinkState, LinkStateComponent,
-    LinkUniqueId, ProcessorInstanceComponent, SubprocessHandleComponent,
+    FederatedLinkDirection, FederatedLinksComponent, Graph, GraphEdgeWithComponents,
+    GraphNodeWithComponents, LinkEncryptionComponent, LinkFrameDropComponent, LinkState,
+    LinkStateComponent, LinkUniqueId, ProcessorInstanceComponent, SubprocessHandleComponent,
 };
 use crate::core::json_schema::SchemaIdentOutput;
 use crate::core::processors::{PROCESSOR_REGISTRY, ProcessorInstance};
 use crate::iceoryx2::{
-    ChannelEgressConfig, ChannelTrustTier, Iceoryx2NotifyService, Iceoryx2Service,
-    RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL, SchemaIdentWire, effective_channel_ceiling_bytes,
+    ChannelEgressConfig, ChannelTrustTier, Iceoryx2Node, Iceoryx2NotifyService, Iceoryx2Service,
+    MailboxFrameCounters, PayloadCipher, PayloadKey, RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL,
+    SchemaIdentWire, effective_channel_ceiling_bytes,
 };
 
 use super::spawn_deno_subprocess_op::DenoSubprocessHostProcessor;
@@ -162,6 +168,22 @@ pub fn open_iceoryx2_service(
     } = resolve_channel_sizing(graph, &source_proc_id, &source_port)?;
     let max_notifiers = destination_fanin(graph, &dest_proc_id);
 
+    // Settle the channel's key before anything is opened, so a refused
+    // encrypted link leaves no half-wired ports behind.
+    let encrypt = graph
+        .traversal_mut()
+        .e(link_id)
+        .first()
+        .is_some_and(|link| link.has::<LinkEncryptionComponent>());
+    let cipher = resolve_channel_cipher(graph, &source_proc_id, &source_port, encrypt)?;
+    if cipher.is_some() && dest_is_subprocess {
+        return Err(Error::NotSupported(format!(
+            "encrypted channel '{}' into subprocess processor '{}' — subprocess runtimes \
+             cannot open sealed payloads",
+            channel_service_name, dest_proc_id
+        )));
+    }
+
     let iceoryx2_node = runtime_ctx.iceoryx2_node();
     let service = iceoryx2_node.open_or_create_service(
         &channel_service_name,
@@ -202,13 +224,15 @@ pub fn open_iceoryx2_service(
                 trust_tier,
                 expected_payload_bytes: expected_payload,
                 ceiling_bytes: channel_ceiling_bytes,
+                cipher: cipher.clone(),
             },
         )?;
     }
 
     // Destination side: subscribe to the channel bound to this local input port,
-    // and ensure the destination's single listener exists.
-    if dest_is_subprocess {
+    // and ensure the destination's single listener exists. A Rust destination
+    // hands back its mailbox counters for the link's drop accounting.
+    let dest_frame_counters = if dest_is_subprocess {
         wire_subprocess_dest(
             graph,
             &dest_proc_id,
@@ -220,6 +244,7 @@ pub fn open_iceoryx2_service(
             max_subscribers,
             max_notifiers,
         )?;
+        None
     } else {
         let dest_processor = get_single_processor(graph, &dest_proc_id)?;
         wire_rust_dest(
@@ -231,8 +256,9 @@ pub fn open_iceoryx2_service(
             max_queued_messages,
             &service,
             &notify_service,
-        )?;
-    }
+            cipher,
+        )?
+    };
 
     let link = graph
         .traversal_mut()
@@ -240,6 +266,9 @@ pub fn open_iceoryx2_service(
         .first_mut()
         .ok_or_else(|| Error::LinkNotFound(link_id.to_string()))?;
     link.insert(LinkStateComponent(LinkState::Wired));
+    if let Some(counters) = dest_frame_counters {
+        link.insert(LinkFrameDropComponent::new(counters));
+    }
 
     tracing::info!(
         channel = %channel_service_name,
@@ -340,6 +369,276 @@ pub fn close_iceoryx2_service(graph: &mut Graph, link_id: &LinkUniqueId) -> Resu
     Ok(())
 }
 
+/// The channel a federated link rides, as the source runtime opened it. Shipped
+/// to the destination runtime so its publisher-free reopen requests the same
+/// iceoryx2-verified parameters.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct FederatedChannel {
+    pub(crate) channel_service_name: String,
+    pub(crate) max_subscribers: usize,
+    pub(crate) max_queued_messages: usize,
+    pub(crate) enable_safe_overflow: bool,
+    /// The channel key when the channel is encrypted — the broker handshake is
+    /// how it reaches the destination runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) payload_key: Option<PayloadKey>,
+}
+
+/// Resolve the channel a federated link out of `(source_proc_id, source_port)`
+/// publishes on. Call after recording the link on the source node, so the
+/// sizing counts the remote subscriber.
+pub(crate) fn resolve_federated_channel(
+    graph: &mut Graph,
+    source_proc_id: &ProcessorUniqueId,
+    source_port: &str,
+) -> Result<FederatedChannel> {
+    let sizing = resolve_channel_sizing(graph, source_proc_id, source_port)?;
+    Ok(FederatedChannel {
+        channel_service_name: channel_service_name(source_proc_id, source_port)?,
+        max_subscribers: sizing.max_subscribers,
+        max_queued_messages: sizing.max_queued_messages,
+        enable_safe_overflow: sizing.enable_safe_overflow,
+        payload_key: None,
+    })
+}
+
+/// The channel key of the publisher already installed on a Rust source's
+/// `source_port`, if that channel is encrypted.
+pub(crate) fn channel_cipher(
+    graph: &mut Graph,
+    source_proc_id: &ProcessorUniqueId,
+    source_port: &str,
+) -> Option<PayloadCipher> {
+    let source_processor = get_single_processor(graph, source_proc_id).ok()?;
+    let source_guard = source_processor.lock();
+    source_guard
+        .iceoryx2_output_writer_inner()?
+        .channel_cipher(source_port)
+}
+
+/// The cipher a new link out of `source_port` rides under.
+///
+/// A channel's encryption is fixed by the link that installs its publisher: an
+/// existing publisher's key is reused whether or not this link asked, while a
+/// link asking for encryption onto a channel already publishing in the clear is
+/// refused. With no publisher yet, `encrypt` generates a fresh key. Subprocess
+/// sources publish from their own runtime and cannot seal.
+pub(crate) fn resolve_channel_cipher(
+    graph: &mut Graph,
+    source_proc_id: &ProcessorUniqueId,
+    source_port: &str,
+    encrypt: bool,
+) -> Result<Option<PayloadCipher>> {
+    if is_subprocess_processor(graph, source_proc_id) {
+        if encrypt {
+            return Err(Error::NotSupported(format!(
+                "encrypted link out of subprocess processor '{}' — subprocess runtimes \
+                 cannot seal payloads",
+                source_proc_id
+            )));
+        }
+        return Ok(None);
+    }
+    let source_processor = get_single_processor(graph, source_proc_id)?;
+    let source_guard = source_processor.lock();
+    let Some(output_inner) = source_guard.iceoryx2_output_writer_inner() else {
+        return Ok(None);
+    };
+    if output_inner.has_channel_publisher(source_port) {
+        let existing = output_inner.channel_cipher(source_port);
+        if encrypt && existing.is_none() {
+            return Err(Error::Link(format!(
+                "'{}:{}' already publishes in the clear to other links; payload encryption \
+                 is fixed by the port's first link, so connect encrypted links first",
+                source_proc_id, source_port
+            )));
+        }
+        return Ok(existing);
+    }
+    if encrypt {
+        PayloadCipher::generate().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Source half of a federated link: install the port's channel publisher (if
+/// no local link already did) and append a notifier on the remote destination's
+/// notify service, sized to the fan-in the destination runtime reported.
+pub(crate) fn open_federated_source(
+    graph: &mut Graph,
+    iceoryx2_node: &Iceoryx2Node,
+    link_id: &LinkUniqueId,
+    source_proc_id: &ProcessorUniqueId,
+    source_port: &str,
+    notify_service_name: &str,
+    max_notifiers: usize,
+    cipher: Option<PayloadCipher>,
+) -> Result<()> {
+    if is_subprocess_processor(graph, source_proc_id) {
+        return Err(Error::NotSupported(format!(
+            "federated link out of subprocess processor '{}' — only Rust processors \
+             can publish across runtimes",
+            source_proc_id
+        )));
+    }
+
+    let channel = resolve_federated_channel(graph, source_proc_id, source_port)?;
+    let output_schema = resolve_output_schema(graph, source_proc_id, source_port);
+    let expected_payload = expected_payload_bytes_for_port_spec(&output_schema)?;
+
+    let service = iceoryx2_node.open_or_create_service(
+        &channel.channel_service_name,
+        channel.max_subscribers,
+        channel.max_queued_messages,
+        channel.enable_safe_overflow,
+    )?;
+    let notify_service =
+        iceoryx2_node.open_or_create_notify_service(notify_service_name, max_notifiers)?;
+
+    // Both ends are host runtimes of the same user, so the channel stays on the
+    // trusted tier.
+    let source_processor = get_single_processor(graph, source_proc_id)?;
+    wire_rust_source(
+        &source_processor,
+        source_port,
+        link_id,
+        &output_schema,
+        &service,
+        &notify_service,
+        ChannelEgressConfig {
+            service_name: channel.channel_service_name.clone(),
+            trust_tier: ChannelTrustTier::Trusted,
+            expected_payload_bytes: expected_payload,
+            ceiling_bytes: effective_channel_ceiling_bytes(ChannelTrustTier::Trusted),
+            cipher,
+        },
+    )?;
+
+    tracing::info!(
+        channel = %channel.channel_service_name,
+        notify = %notify_service_name,
+        "Opened federated source: {}:{} [{}]",
+        source_proc_id,
+        source_port,
+        link_id
+    );
+    Ok(())
+}
+
+/// Destination half of a federated link: reopen the source runtime's channel
+/// with its shipped sizing, subscribe the local input port, and ensure the
+/// destination's listener exists. Returns the notify service name and
+/// `max_notifiers` the source runtime must open its notifier with.
+pub(crate) fn open_federated_dest(
+    graph: &mut Graph,
+    iceoryx2_node: &Iceoryx2Node,
+    link_id: &LinkUniqueId,
+    channel: &FederatedChannel,
+    dest_proc_id: &ProcessorUniqueId,
+    dest_port: &str,
+) -> Result<(String, usize)> {
+    if is_subprocess_processor(graph, dest_proc_id) {
+        return Err(Error::NotSupported(format!(
+            "federated link into subprocess processor '{}' — only Rust processors \
+             can subscribe across runtimes",
+            dest_proc_id
+        )));
+    }
+
+    let dest_schema = resolve_port_schema(
+        graph,
+        dest_proc_id,
+        dest_port,
+        crate::core::PortDirection::Input,
+    );
+    let dest_type = graph
+        .traversal_mut()
+        .v(dest_proc_id)
+        .first()
+        .map(|node| node.processor_type().clone());
+    let drain_order = match dest_type.as_ref() {
+        Some(ident) => delivery_profile_for_input_port(ident, dest_port)?,
+        None => crate::iceoryx2::DeliveryProfile::Latest,
+    }
+    .resolve()
+    .drain_order;
+
+    let notify_service_name = notify_service_name_for(dest_proc_id);
+    let max_notifiers = destination_fanin(graph, dest_proc_id);
+
+    let service = iceoryx2_node.open_or_create_service(
+        &channel.channel_service_name,
+        channel.max_subscribers,
+        channel.max_queued_messages,
+        channel.enable_safe_overflow,
+    )?;
+    let notify_service =
+        iceoryx2_node.open_or_create_notify_service(&notify_service_name, max_notifiers)?;
+
+    let cipher = channel
+        .payload_key
+        .clone()
+        .map(PayloadCipher::new)
+        .transpose()?;
+    let dest_processor = get_single_processor(graph, dest_proc_id)?;
+    wire_rust_dest(
+        &dest_processor,
+        dest_port,
+        link_id,
+        &dest_schema,
+        drain_order,
+        channel.max_queued_messages,
+        &service,
+        &notify_service,
+        cipher,
+    )?;
+
+    tracing::info!(
+        channel = %channel.channel_service_name,
+        notify = %notify_service_name,
+        "Opened federated destination: {}:{} [{}]",
+        dest_proc_id,
+        dest_port,
+        link_id
+    );
+    Ok((notify_service_name, max_notifiers))
+}
+
+/// Reclaim this runtime's half of a federated link — the source's notifier (and
+/// publisher, when it was the port's last link) or the destination's
+/// subscriber. A processor already torn down has nothing left to reclaim.
+pub(crate) fn close_federated_link(
+    graph: &mut Graph,
+    link_id: &LinkUniqueId,
+    proc_id: &ProcessorUniqueId,
+    direction: FederatedLinkDirection,
+    local_port: &str,
+) {
+    let Ok(processor) = get_single_processor(graph, proc_id) else {
+        tracing::debug!(
+            "close_federated_link: processor '{}' is gone; nothing to reclaim for [{}]",
+            proc_id,
+            link_id
+        );
+        return;
+    };
+    let guard = processor.lock();
+    match direction {
+        FederatedLinkDirection::Outbound => {
+            if let Some(output_inner) = guard.iceoryx2_output_writer_inner() {
+                output_inner.remove_channel_link(local_port, link_id.as_str());
+            }
+        }
+        FederatedLinkDirection::Inbound => {
+            if let Some(input_inner) = guard.iceoryx2_input_mailboxes_inner() {
+                input_inner.remove_channel_link(link_id.as_str());
+            }
+        }
+    }
+    tracing::info!("Closed federated link half on {}: [{}]", proc_id, link_id);
+}
+
 // ============================================================================
 // Internal helpers
 // ============================================================================
@@ -400,17 +699,41 @@ fn channel_destinations(
 }
 
 /// The `max_subscribers` a channel data service must be created with: the count
-/// of destinations the channel feeds (each is one destination subscriber) plus
-/// [`RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL`].
+/// of destinations the channel feeds (each is one destination subscriber), plus
+/// one per federated link out of the port (a subscriber in another runtime),
+/// plus [`RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL`].
 fn channel_max_subscribers(
     graph: &mut Graph,
     source_proc_id: &ProcessorUniqueId,
     source_port: &str,
 ) -> usize {
     channel_destinations(graph, source_proc_id, source_port).len()
+        + federated_link_count(
+            graph,
+            source_proc_id,
+            FederatedLinkDirection::Outbound,
+            Some(source_port),
+        )
         + RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL
 }
 
+/// Federated links recorded on `proc_id` in `direction`, narrowed to one local
+/// port when given.
+fn federated_link_count(
+    graph: &mut Graph,
+    proc_id: &ProcessorUniqueId,
+    direction: FederatedLinkDirection,
+    local_port: Option<&str>,
+) -> usize {
+    graph
+        .traversal_mut()
+        .v(proc_id)
+        .first()
+        .and_then(|node| node.get::<FederatedLinksComponent>())
+        .map(|federated| federated.count(direction, local_port))
+        .unwrap_or(0)
+}
+
 /// The iceoryx2 sizing a channel data service is opened with — the fixed
 /// parameters iceoryx2 verifies on every reopen of the same service name.
 ///
@@ -474,9 +797,11 @@ pub(crate) fn find_channel_source_port(
 }
 
 /// The destination's compile-time fan-in — the count of inbound `connect()`
-/// links — which sizes `max_notifiers` on its destination-keyed notify service.
+/// links plus inbound federated links, each holding one notifier — which sizes
+/// `max_notifiers` on its destination-keyed notify service.
 fn destination_fanin(graph: &mut Graph, dest_proc_id: &ProcessorUniqueId) -> usize {
     graph.traversal_mut().v(dest_proc_id).in_e().iter().count()
+        + federated_link_count(graph, dest_proc_id, FederatedLinkDirection::Inbound, None)
 }
 
 /// The channel's [`DeliveryProfile`], agreed across every destination the
@@ -670,7 +995,9 @@ fn wire_rust_source(
 }
 
 /// Subscribe the Rust destination to the channel bound to its local input port,
-/// and ensure its single listener exists.
+/// opening frames under `cipher` when the channel is encrypted, and ensure its
+/// single listener exists. Returns the port mailbox's frame counters, or `None`
+/// when the processor has no input mailboxes.
 fn wire_rust_dest(
     dest_processor: &Arc<Mutex<ProcessorInstance>>,
     dest_port: &str,
@@ -680,10 +1007,11 @@ fn wire_rust_dest(
     depth: usize,
     service: &Iceoryx2Service,
     notify_service: &Iceoryx2NotifyService,
-) -> Result<()> {
+    cipher: Option<PayloadCipher>,
+) -> Result<Option<Arc<MailboxFrameCounters>>> {
     let dest_guard = dest_processor.lock();
     let Some(input_inner) = dest_guard.iceoryx2_input_mailboxes_inner() else {
-        return Ok(());
+        return Ok(None);
     };
 
     if !input_inner.has_port(dest_port) {
@@ -692,7 +1020,7 @@ fn wire_rust_dest(
     input_inner.set_port_expected_schema_ident(dest_port, schema_ident_wire_for_spec(dest_schema));
 
     let subscriber = service.create_subscriber()?;
-    input_inner.add_channel_subscriber(dest_port, link_id.as_str(), subscriber);
+    input_inner.add_channel_subscriber_with_cipher(dest_port, link_id.as_str(), subscriber, cipher);
     tracing::debug!(
         "Bound channel subscriber to destination input port '{}'",
         dest_port
@@ -703,7 +1031,7 @@ fn wire_rust_dest(
         input_inner.set_listener(listener);
         tracing::debug!("Created listener for destination on its notify service");
     }
-    Ok(())
+    Ok(input_inner.port_frame_counters(dest_port))
 }
 
 /// Record this link's source-side wiring on a subprocess host processor so the
@@ -925,6 +1253,90 @@ mod tests {
         );
     }
 
+    fn record_federated_link(
+        graph: &mut Graph,
+        proc_id: &str,
+        direction: crate::core::graph::FederatedLinkDirection,
+        local_port: &str,
+    ) {
+        let node = graph
+            .traversal_mut()
+            .v(proc_id)
+            .first_mut()
+            .expect("processor is in the graph");
+        if !node.has::<FederatedLinksComponent>() {
+            node.insert(FederatedLinksComponent::default());
+        }
+        node.get_mut::<FederatedLinksComponent>()
+            .expect("component was just inserted")
+            .links
+            .push(crate::core::graph::FederatedLink {
+                link_id: LinkUniqueId::new(),
+                direction,
+                local_port: local_port.to_string(),
+                remote_runtime_id: "Rremote".to_string(),
+                remote_processor_id: "Premote".into(),
+                remote_port: "port".to_string(),
+            });
+    }
+
+    /// A federated link out of a port is one more subscriber on that port's
+    /// channel — in another runtime, so absent from the graph's edges. Links
+    /// out of a different port on the same processor don't count. Drop the
+    /// federated term from `channel_max_subscribers` and the remote subscriber
+    /// overflows the service.
+    #[test]
+    fn channel_max_subscribers_counts_federated_links_out_of_the_port() {
+        let mut graph = Graph::new();
+        let src_id = add_mock_output_only(&mut graph);
+        let dest_id = add_mock_input_only(&mut graph);
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&src_id, "out1"),
+            InputLinkPortRef::new(&dest_id, "in1"),
+        );
+        record_federated_link(
+            &mut graph,
+            &src_id,
+            FederatedLinkDirection::Outbound,
+            "out1",
+        );
+        record_federated_link(
+            &mut graph,
+            &src_id,
+            FederatedLinkDirection::Outbound,
+            "out2",
+        );
+
+        let src_uid: ProcessorUniqueId = src_id.as_str().into();
+        assert_eq!(
+            channel_max_subscribers(&mut graph, &src_uid, "out1"),
+            2 + RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL,
+        );
+        assert_eq!(
+            resolve_federated_channel(&mut graph, &src_uid, "out1")
+                .expect("federated channel resolves")
+                .max_subscribers,
+            2 + RESERVED_TAP_SUBSCRIBER_SLOTS_PER_CHANNEL,
+        );
+    }
+
+    /// A federated link into a processor holds one notifier on its notify
+    /// service, so it counts toward the fan-in alongside local inbound links.
+    #[test]
+    fn destination_fanin_counts_federated_inbound_links() {
+        let mut graph = Graph::new();
+        let src_id = add_mock_output_only(&mut graph);
+        let dest_id = add_mock_input_only(&mut graph);
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&src_id, "out1"),
+            InputLinkPortRef::new(&dest_id, "in1"),
+        );
+        record_federated_link(&mut graph, &dest_id, FederatedLinkDirection::Inbound, "in1");
+
+        let dest_uid: ProcessorUniqueId = dest_id.as_str().into();
+        assert_eq!(destination_fanin(&mut graph, &dest_uid), 2);
+    }
+
     /// A wired channel's data-service name reverse-resolves to the exact
     /// `(source_proc, source_port)` that publishes to it; an unknown name
     /// resolves to `None` (the tap op maps that to `TapChannelNotFound`).
@@ -1084,6 +1496,7 @@ mod tests {
             entrypoint: None,
             config_schema: None,
             scheduling: ProcessorScheduling::default(),
+            execution: Default::default(),
             inputs: Vec::new(),
             outputs: vec![PortDescriptor::iceoryx2(
                 "out_unloaded",
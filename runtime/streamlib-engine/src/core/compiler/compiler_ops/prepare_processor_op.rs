@@ -3,8 +3,9 @@
 
 use crate::core::error::{Error, Result};
 use crate::core::graph::{
-    Graph, GraphNodeWithComponents, ProcessorPauseGateComponent, ProcessorReadyBarrierComponent,
-    ProcessorReadyBarrierHandle, ProcessorUniqueId, ShutdownChannelComponent, StateComponent,
+    Graph, GraphNodeWithComponents, ProcessorBypassGateComponent, ProcessorParameterPortComponent,
+    ProcessorPauseGateComponent, ProcessorReadyBarrierComponent, ProcessorReadyBarrierHandle,
+    ProcessorUniqueId, ShutdownChannelComponent, StateComponent,
 };
 
 /// Attach infrastructure components to a processor node.
@@ -27,6 +28,9 @@ pub(crate) fn prepare_processor(
     node_mut.insert(ShutdownChannelComponent::new());
     node_mut.insert(StateComponent::default());
     node_mut.insert(ProcessorPauseGateComponent::new());
+    node_mut.insert(ProcessorBypassGateComponent::new());
+    let config = node_mut.config.clone().unwrap_or_default();
+    node_mut.insert(ProcessorParameterPortComponent::new(config));
 
     tracing::debug!("[{}] Infrastructure components attached", proc_id);
     Ok(barrier_handle)
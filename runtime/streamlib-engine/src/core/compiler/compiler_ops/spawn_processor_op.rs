@@ -17,8 +17,10 @@ use crate::core::context::{
 use crate::core::descriptors::ProcessorRuntime;
 use crate::core::error::{Error, Result};
 use crate::core::execution::run_processor_loop;
+use crate::core::execution::thread_runner::bypass_routes;
 use crate::core::graph::{
-    Graph, GraphNodeWithComponents, ProcessorInstanceComponent, ProcessorPauseGateComponent,
+    GpuAdapterAffinityComponent, Graph, GraphNodeWithComponents, ProcessorBypassGateComponent,
+    ProcessorInstanceComponent, ProcessorParameterPortComponent, ProcessorPauseGateComponent,
     ProcessorReadyBarrierComponent, ProcessorUniqueId, ShutdownChannelComponent, StateComponent,
     SubprocessHandleComponent, ThreadHandleComponent,
 };
@@ -151,16 +153,27 @@ fn spawn_dedicated_thread(
     // Create processor instance now (with lock) since factory needs node
     // reference; read the org off the same guaranteed-present node so the
     // isolation tier is always derived from provenance, never from node absence.
-    let (processor_arc, org) = {
+    let (processor_arc, org, gpu_adapter, routes) = {
         let graph = graph_arc.read();
         let node = graph.traversal().v(&processor_id).first().ok_or_else(|| {
             Error::ProcessorNotFound(format!("Processor '{}' not found", processor_id))
         })?;
         let org = node.processor_type().org.clone();
+        let gpu_adapter = node.get::<GpuAdapterAffinityComponent>().map(|a| a.0);
+        let routes = PROCESSOR_REGISTRY
+            .descriptor(node.processor_type())
+            .map(|descriptor| bypass_routes(&descriptor.inputs, &descriptor.outputs))
+            .unwrap_or_default();
         let processor = factory.create(node)?;
-        (Arc::new(Mutex::new(processor)), org)
+        (Arc::new(Mutex::new(processor)), org, gpu_adapter, routes)
     };
 
+    // Processors pinned to another adapter get that adapter's context;
+    // opening it here surfaces a bad index as a spawn error.
+    let pinned_gpu = gpu_adapter
+        .map(|index| runtime_ctx.gpu_for_adapter(index))
+        .transpose()?;
+
     let processor_arc_clone = Arc::clone(&processor_arc);
 
     // Resolve the isolation trust tier through the opt-in session isolation tier:
@@ -273,7 +286,15 @@ fn spawn_dedicated_thread(
             );
 
             // === PHASE 3: Extract components for setup and loop ===
-            let (state_arc, shutdown_rx, shutdown_eventfd, pause_gate_inner, exec_config) = {
+            let (
+                state_arc,
+                shutdown_rx,
+                shutdown_eventfd,
+                pause_gate_inner,
+                bypass_gate_inner,
+                parameter_port,
+                exec_config,
+            ) = {
                 let mut graph = graph_arc_clone.write();
                 let node = match graph.traversal_mut().v(&proc_id_clone).first_mut() {
                     Some(n) => n,
@@ -321,6 +342,22 @@ fn spawn_dedicated_thread(
                     }
                 };
 
+                let bypass_gate_inner = match node.get::<ProcessorBypassGateComponent>() {
+                    Some(bg) => bg.clone_inner(),
+                    None => {
+                        tracing::error!("[{}] No ProcessorBypassGateComponent", proc_id_clone);
+                        return;
+                    }
+                };
+
+                let parameter_port = match node.get::<ProcessorParameterPortComponent>() {
+                    Some(port) => port.clone(),
+                    None => {
+                        tracing::error!("[{}] No ProcessorParameterPortComponent", proc_id_clone);
+                        return;
+                    }
+                };
+
                 let exec_config = processor_arc_clone.lock().execution_config();
 
                 (
@@ -328,15 +365,20 @@ fn spawn_dedicated_thread(
                     shutdown_rx,
                     shutdown_eventfd,
                     pause_gate_inner,
+                    bypass_gate_inner,
+                    parameter_port,
                     exec_config,
                 )
             }; // Lock released here
 
             // === PHASE 4: Setup ===
             // Create processor-specific context with both processor ID and pause gate
-            let processor_context = runtime_ctx_clone
+            let mut processor_context = runtime_ctx_clone
                 .with_processor_id(proc_id_clone.clone())
                 .with_pause_gate(pause_gate_inner.clone());
+            if let Some(gpu) = pinned_gpu {
+                processor_context = processor_context.with_gpu(gpu);
+            }
             {
                 let tokio_handle = runtime_ctx_clone.tokio_handle();
                 // The trust-axis moat: an untrusted tier yields no
@@ -360,7 +402,7 @@ fn spawn_dedicated_thread(
                     thread_id,
                     runtime,
                 );
-                let setup_result = run_setup_phase(runtime, &runtime_ctx_clone.gpu, || {
+                let setup_result = run_setup_phase(runtime, &processor_context.gpu, || {
                     let _ = &tokio_handle; // block_on now happens inside the
                     // ProcessorInstance::setup dispatch — VTable variant calls
                     // through extern "C" (cdylib block_ons on its own tokio
@@ -393,6 +435,10 @@ fn spawn_dedicated_thread(
                 shutdown_eventfd,
                 state_arc,
                 pause_gate_inner,
+                bypass_gate_inner,
+                routes,
+                parameter_port,
+                graph_arc_clone,
                 exec_config,
                 processor_context,
                 isolation_tier,
@@ -3,9 +3,9 @@
 
 use crate::core::context::TextureRegistration;
 use crate::core::rhi::{
-    CommandBuffer, GpuDevice, PixelBuffer, PixelBufferDescriptor, PixelBufferPoolId, PixelFormat,
-    RhiBlitter, RhiColorConverter, RhiCommandQueue, RhiPixelBufferPool, Texture, TextureDescriptor,
-    TextureFormat, TextureUsages,
+    CommandBuffer, GpuAdapterInfo, GpuAdapterPreference, GpuDevice, PixelBuffer,
+    PixelBufferDescriptor, PixelBufferPoolId, PixelFormat, RhiBlitter, RhiColorConverter,
+    RhiCommandQueue, RhiPixelBufferPool, Texture, TextureDescriptor, TextureFormat, TextureUsages,
 };
 use crate::core::{Error, Result};
 #[cfg(target_os = "linux")]
@@ -61,7 +61,7 @@ use super::graphics_kernel_bridge::GraphicsKernelBridge;
 use super::ray_tracing_kernel_bridge::RayTracingKernelBridge;
 use super::surface_store::SurfaceStore;
 use super::texture_pool::{
-    PooledTextureHandle, TexturePool, TexturePoolConfig, TexturePoolDescriptor,
+    PooledTextureHandle, TexturePool, TexturePoolConfig, TexturePoolDescriptor, TexturePoolStats,
 };
 
 /// Key for caching pixel buffer pools.
@@ -398,6 +398,68 @@ pub struct GpuCapabilitiesSnapshot {
     pub supports_ray_tracing_pipeline: bool,
 }
 
+/// A texture exported for zero-copy import into CUDA, returned by
+/// [`GpuContext::export_cuda`].
+///
+/// Maps one-to-one onto the importer's CUDA calls: `memory` +
+/// `memory_size` feed `cudaImportExternalMemory`
+/// (`cudaExternalMemoryHandleTypeOpaqueFd` with
+/// `cudaExternalMemoryDedicated` — the image always owns its memory
+/// block), `width` / `height` / [`Self::cuda_array_format`] describe the
+/// single-level, 4-channel array for
+/// `cudaExternalMemoryGetMappedMipmappedArray` (the `CUarray` NVENC's
+/// `NV_ENC_INPUT_RESOURCE_TYPE_CUDAARRAY` registers and NVDEC output is
+/// copied into), and `timeline` feeds `cudaImportExternalSemaphore`
+/// (`cudaExternalSemaphoreHandleTypeTimelineSemaphoreFd`). The importer
+/// must bind the CUDA device whose `cudaDeviceProp::uuid` equals
+/// `device_uuid` — never a silent fall-through to CUDA device 0.
+///
+/// The fds are owned until handed off. CUDA takes ownership on a
+/// successful import, so pass them with
+/// [`std::os::fd::IntoRawFd::into_raw_fd`]; dropping the export closes
+/// them.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct CudaExport {
+    /// OPAQUE_FD of the image's dedicated `VkDeviceMemory`.
+    pub memory: std::os::fd::OwnedFd,
+    /// Size of the memory block in bytes (`cudaExternalMemoryHandleDesc::size`).
+    pub memory_size: u64,
+    /// `VkPhysicalDeviceIDProperties::deviceUUID` of the owning device.
+    pub device_uuid: [u8; 16],
+    pub width: u32,
+    pub height: u32,
+    pub format: TextureFormat,
+    /// OPAQUE_FD of the timeline semaphore ordering Vulkan and CUDA
+    /// access, when one was passed to [`GpuContext::export_cuda`].
+    pub timeline: Option<std::os::fd::OwnedFd>,
+}
+
+#[cfg(target_os = "linux")]
+impl CudaExport {
+    /// `CUarray_format` of each of the array's four channels
+    /// (`CU_AD_FORMAT_UNSIGNED_INT8` / `_HALF` / `_FLOAT`), or `None` for
+    /// a format CUDA cannot map. [`GpuContext::create_cuda_texture`] only
+    /// allocates mappable formats.
+    pub fn cuda_array_format(&self) -> Option<u32> {
+        cuda_array_format(self.format)
+    }
+}
+
+/// `CUarray_format` values from `cuda.h`.
+#[cfg(target_os = "linux")]
+fn cuda_array_format(format: TextureFormat) -> Option<u32> {
+    const CU_AD_FORMAT_UNSIGNED_INT8: u32 = 0x01;
+    const CU_AD_FORMAT_HALF: u32 = 0x10;
+    const CU_AD_FORMAT_FLOAT: u32 = 0x20;
+    match format {
+        TextureFormat::Rgba8Unorm => Some(CU_AD_FORMAT_UNSIGNED_INT8),
+        TextureFormat::Rgba16Float => Some(CU_AD_FORMAT_HALF),
+        TextureFormat::Rgba32Float => Some(CU_AD_FORMAT_FLOAT),
+        _ => None,
+    }
+}
+
 #[derive(Clone)]
 pub struct GpuContext {
     device: Arc<GpuDevice>,
@@ -1090,6 +1152,13 @@ impl GpuContext {
         &self.texture_pool
     }
 
+    /// Occupancy, high-water mark and failure counters of the texture pool
+    /// backing [`Self::acquire_texture`] (IOSurface on macOS, DMA-BUF
+    /// exportable images on Linux).
+    pub fn pool_stats(&self) -> TexturePoolStats {
+        self.texture_pool.stats()
+    }
+
     /// Acquire a pooled texture for in-process GPU work.
     ///
     /// Uses `VK_IMAGE_TILING_OPTIMAL` and is **not** safe to share with
@@ -1782,6 +1851,78 @@ impl GpuContext {
         Ok((fd, size, uuid))
     }
 
+    /// Allocate a texture CUDA can import zero-copy — a DEVICE_LOCAL,
+    /// OPTIMAL-tiled `VkImage` with dedicated OPAQUE_FD-exportable memory
+    /// (see [`crate::vulkan::rhi::HostVulkanTexture::new_opaque_fd_export`]
+    /// for the constraints CUDA imposes). `format` must be `Rgba8Unorm`,
+    /// `Rgba16Float` or `Rgba32Float`. Pair with [`Self::export_cuda`].
+    /// Backs [`GpuContextFullAccess::create_cuda_texture`].
+    #[cfg(target_os = "linux")]
+    pub fn create_cuda_texture(
+        &self,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+    ) -> Result<Texture> {
+        let desc = TextureDescriptor::new(width, height, format).with_label("cuda_export");
+        let texture =
+            crate::vulkan::rhi::HostVulkanTexture::new_opaque_fd_export(&self.device.inner, &desc)?;
+        Ok(Texture::from_vulkan(texture))
+    }
+
+    /// Export `texture` (from [`Self::create_cuda_texture`]) and,
+    /// optionally, the timeline semaphore that orders access to it, for
+    /// zero-copy import into CUDA — NVENC / NVDEC and CUDA-based ML
+    /// processors read and write the frame in place, with no round trip
+    /// through host memory. Each call exports fresh fds.
+    ///
+    /// `timeline` must come from [`Self::create_exportable_timeline_semaphore`].
+    /// The Vulkan side signals it after its last write and CUDA waits on
+    /// the value before reading (and the reverse for CUDA writes); the
+    /// image must be in `GENERAL` layout while CUDA owns it. Textures
+    /// from any other allocator are rejected — their memory carries no
+    /// OPAQUE_FD export handle type. Backs
+    /// [`GpuContextFullAccess::export_cuda`].
+    #[cfg(target_os = "linux")]
+    pub fn export_cuda(
+        &self,
+        texture: &Texture,
+        timeline: Option<&crate::vulkan::rhi::HostVulkanTimelineSemaphore>,
+    ) -> Result<CudaExport> {
+        use std::os::fd::{FromRawFd, OwnedFd};
+
+        let inner = texture.vulkan_inner();
+        // Same rule as `export_storage_buffer_opaque_fd`: the UUID names
+        // the device that owns the memory, not this context's device.
+        let device_uuid = inner
+            .vulkan_device()
+            .ok_or_else(|| {
+                Error::GpuError("export_cuda: texture has no owning HostVulkanDevice".into())
+            })?
+            .physical_device_uuid();
+        let memory_fd = inner.export_opaque_fd_memory()?;
+        // SAFETY: `vkGetMemoryFdKHR` returned a fresh fd nobody else owns.
+        let memory = unsafe { OwnedFd::from_raw_fd(memory_fd) };
+        let timeline = match timeline {
+            Some(semaphore) => {
+                let fd = semaphore.export_opaque_fd()?;
+                // SAFETY: `vkGetSemaphoreFdKHR` returned a fresh fd
+                // nobody else owns.
+                Some(unsafe { OwnedFd::from_raw_fd(fd) })
+            }
+            None => None,
+        };
+        Ok(CudaExport {
+            memory,
+            memory_size: inner.vma_allocation_size(),
+            device_uuid,
+            width: inner.width(),
+            height: inner.height(),
+            format: inner.format(),
+            timeline,
+        })
+    }
+
     /// Wrap an existing OPAQUE_FD `StorageBuffer` (flat `VkBuffer`) as a
     /// `PixelBuffer` sharing the same `Arc<HostVulkanBuffer>`, so the flat
     /// CUDA buffer can register through the existing
@@ -1932,52 +2073,80 @@ impl GpuContext {
         Ok(Arc::new(handle))
     }
 
-    /// Mint a hardware video [`SimpleEncoder`](crate::vulkan::video::encode::SimpleEncoder)
-    /// on this context's host device — the modern, cdylib-safe encoder
-    /// construction path. Builds directly from the host-owned
-    /// `Arc<HostVulkanDevice>` (`self.device.inner`), NOT through the
-    /// retiring `host_vulkan_device_arc` FullAccess ABI transit that
-    /// `SimpleEncoder::from_full_access` uses. Backs the plugin-ABI
+    /// Mint a hardware video encoder on this context's host device — the
+    /// modern, cdylib-safe encoder construction path. Backs the plugin-ABI
     /// `create_encoder_session` FullAccess slot (M32 #1259 fill-in,
     /// #1376).
     ///
+    /// The backend is picked by [`crate::linux::codec_factory`]: a Vulkan
+    /// Video [`SimpleEncoder`](crate::vulkan::video::encode::SimpleEncoder)
+    /// built directly from the host-owned `Arc<HostVulkanDevice>`
+    /// (`self.device.inner`) when the device exposes video encode, else a
+    /// VAAPI H.264 encoder when libva reports one.
+    /// `STREAMLIB_VIDEO_CODEC_BACKEND` pins either.
+    ///
     /// When `prepare_gpu_input` is `true` (the descriptor's
-    /// `disable_gpu_input_prealloc == 0`), eagerly runs
-    /// [`SimpleEncoder::prepare_gpu_encode_resources`] so the first
-    /// `submit_texture` frame doesn't pay the RGB→NV12 converter
-    /// allocation latency.
+    /// `disable_gpu_input_prealloc == 0`) and the session is on Vulkan
+    /// Video, eagerly runs
+    /// [`SimpleEncoder::prepare_gpu_encode_resources`](crate::vulkan::video::encode::SimpleEncoder::prepare_gpu_encode_resources)
+    /// so the first `submit_texture` frame doesn't pay the RGB→NV12
+    /// converter allocation latency.
     #[cfg(target_os = "linux")]
     #[tracing::instrument(skip(self, config), fields(rhi_op = "create_encoder_session"))]
     pub fn create_encoder_session(
         &self,
         config: crate::vulkan::video::encode::SimpleEncoderConfig,
         prepare_gpu_input: bool,
-    ) -> Result<crate::vulkan::video::encode::SimpleEncoder> {
-        let host_device = Arc::clone(&self.device.inner);
-        let mut encoder =
-            crate::vulkan::video::encode::SimpleEncoder::from_host_device(host_device, config)
+    ) -> Result<crate::linux::codec_factory::VideoEncoderBackend> {
+        use crate::linux::codec_factory::{
+            self, VideoCodecBackend, VideoCodecBackendPreference, VideoEncoderBackend,
+        };
+        let preference = VideoCodecBackendPreference::resolve(None);
+        let backend = codec_factory::select_backend(
+            preference,
+            self.device.inner.supports_video_encode(),
+            codec_factory::vaapi_supports(config.codec, true),
+        )
+        .ok_or_else(|| {
+            Error::GpuError(format!(
+                "create_encoder_session: no {:?} encode backend available (preference {preference})",
+                config.codec
+            ))
+        })?;
+        tracing::debug!(%backend, "create_encoder_session: codec backend selected");
+        match backend {
+            VideoCodecBackend::VulkanVideo => {
+                let host_device = Arc::clone(&self.device.inner);
+                let mut encoder = crate::vulkan::video::encode::SimpleEncoder::from_host_device(
+                    host_device,
+                    config,
+                )
                 .map_err(|e| Error::GpuError(format!("create_encoder_session: {e}")))?;
-        if prepare_gpu_input {
-            encoder.prepare_gpu_encode_resources().map_err(|e| {
-                Error::GpuError(format!(
-                    "create_encoder_session: prepare GPU encode resources: {e}"
-                ))
-            })?;
+                if prepare_gpu_input {
+                    encoder.prepare_gpu_encode_resources().map_err(|e| {
+                        Error::GpuError(format!(
+                            "create_encoder_session: prepare GPU encode resources: {e}"
+                        ))
+                    })?;
+                }
+                Ok(VideoEncoderBackend::VulkanVideo(encoder))
+            }
+            VideoCodecBackend::Vaapi => crate::linux::vaapi::VaapiH264Encoder::new(config)
+                .map(VideoEncoderBackend::Vaapi)
+                .map_err(|e| Error::GpuError(format!("create_encoder_session: {e}"))),
         }
-        Ok(encoder)
     }
 
-    /// Mint a hardware video [`SimpleDecoder`](crate::vulkan::video::decode::SimpleDecoder)
-    /// on this context's host device — the modern, cdylib-safe decoder
-    /// construction path. Builds directly from the host-owned
-    /// `Arc<HostVulkanDevice>` (`self.device.inner`), NOT through the
-    /// retiring `host_vulkan_device_arc` FullAccess ABI transit that
-    /// `SimpleDecoder::from_full_access` uses. Backs the plugin-ABI
+    /// Mint a hardware video decoder on this context's host device — the
+    /// modern, cdylib-safe decoder construction path. Backs the plugin-ABI
     /// `create_decoder_session` FullAccess slot (M32 #1259 fill-in,
-    /// #1377).
+    /// #1377). Backend selection mirrors [`Self::create_encoder_session`]:
+    /// a Vulkan Video
+    /// [`SimpleDecoder`](crate::vulkan::video::decode::SimpleDecoder) when
+    /// the device exposes video decode, else VAAPI H.264.
     ///
     /// Coded dimensions are auto-detected from the first SPS (query via
-    /// [`SimpleDecoder::dimensions`](crate::vulkan::video::decode::SimpleDecoder::dimensions)
+    /// [`VideoDecoderBackend::dimensions`](crate::linux::codec_factory::VideoDecoderBackend::dimensions)
     /// after the first `feed`); the config's `max_width` / `max_height` of
     /// `0` request that auto-detection.
     #[cfg(target_os = "linux")]
@@ -1985,37 +2154,63 @@ impl GpuContext {
     pub fn create_decoder_session(
         &self,
         config: crate::vulkan::video::decode::SimpleDecoderConfig,
-    ) -> Result<crate::vulkan::video::decode::SimpleDecoder> {
-        let host_device = Arc::clone(&self.device.inner);
-        crate::vulkan::video::decode::SimpleDecoder::from_host_device(host_device, config)
-            .map_err(|e| Error::GpuError(format!("create_decoder_session: {e}")))
+    ) -> Result<crate::linux::codec_factory::VideoDecoderBackend> {
+        use crate::linux::codec_factory::{
+            self, VideoCodecBackend, VideoCodecBackendPreference, VideoDecoderBackend,
+        };
+        let preference = VideoCodecBackendPreference::resolve(None);
+        let backend = codec_factory::select_backend(
+            preference,
+            self.device.inner.supports_video_decode(),
+            codec_factory::vaapi_supports(config.codec, false),
+        )
+        .ok_or_else(|| {
+            Error::GpuError(format!(
+                "create_decoder_session: no {:?} decode backend available (preference {preference})",
+                config.codec
+            ))
+        })?;
+        tracing::debug!(%backend, "create_decoder_session: codec backend selected");
+        match backend {
+            VideoCodecBackend::VulkanVideo => {
+                let host_device = Arc::clone(&self.device.inner);
+                crate::vulkan::video::decode::SimpleDecoder::from_host_device(host_device, config)
+                    .map(VideoDecoderBackend::VulkanVideo)
+                    .map_err(|e| Error::GpuError(format!("create_decoder_session: {e}")))
+            }
+            VideoCodecBackend::Vaapi => crate::linux::vaapi::VaapiH264Decoder::new(config)
+                .map(VideoDecoderBackend::Vaapi)
+                .map_err(|e| Error::GpuError(format!("create_decoder_session: {e}"))),
+        }
     }
 
     /// Initialize GPU context for the current platform.
     pub fn init_for_platform() -> Result<Self> {
-        #[cfg(target_os = "macos")]
-        {
-            let device = GpuDevice::new()?;
-            tracing::info!("GPU: Using Metal device");
-            Ok(Self::new(device))
-        }
+        Self::init_for_platform_with_texture_pool_config(TexturePoolConfig::default())
+    }
 
-        #[cfg(target_os = "linux")]
+    /// [`Self::init_for_platform`] with a custom texture pool, prewarmed
+    /// per [`TexturePoolConfig::prewarm`] before it is returned.
+    pub fn init_for_platform_with_texture_pool_config(
+        pool_config: TexturePoolConfig,
+    ) -> Result<Self> {
+        #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
         {
             let device = GpuDevice::new()?;
+            #[cfg(target_os = "macos")]
+            tracing::info!("GPU: Using Metal device");
+            #[cfg(target_os = "linux")]
             tracing::info!("GPU: Using Vulkan device");
-            Ok(Self::new(device))
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            let device = GpuDevice::new()?;
+            #[cfg(target_os = "windows")]
             tracing::info!("GPU: Using DX12 device");
-            Ok(Self::new(device))
+            let gpu = Self::with_texture_pool_config(device, pool_config);
+            gpu.texture_pool.prewarm_configured()?;
+            Ok(gpu)
         }
 
         #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
         {
+            let _ = pool_config;
             Err(Error::GpuError(
                 "Unsupported platform for GPU initialization".into(),
             ))
@@ -2027,6 +2222,28 @@ impl GpuContext {
         Self::init_for_platform()
     }
 
+    /// Open a secondary context on the adapter `preference` picks, for
+    /// processors pinned to a GPU other than the runtime's. It gets its
+    /// own device, queues and default-sized texture pool; nothing is
+    /// shared with the primary context, so frames cross over only through
+    /// an explicit copy.
+    pub fn init_for_adapter(preference: GpuAdapterPreference) -> Result<Self> {
+        let device = GpuDevice::new_with_adapter(preference)?;
+        tracing::info!("GPU: Opened secondary device on adapter {}", device.adapter_index());
+        Ok(Self::with_texture_pool_config(device, TexturePoolConfig::default()))
+    }
+
+    /// Every GPU adapter the backend can open, in the order
+    /// [`GpuAdapterPreference::Index`] counts them.
+    pub fn enumerate_adapters() -> Result<Vec<GpuAdapterInfo>> {
+        GpuDevice::enumerate_adapters()
+    }
+
+    /// This context's position in [`Self::enumerate_adapters`] order.
+    pub fn adapter_index(&self) -> usize {
+        self.device.adapter_index()
+    }
+
     /// Get the underlying Metal device (macOS only).
     #[cfg(target_os = "macos")]
     pub fn metal_device(&self) -> &crate::metal::rhi::MetalDevice {
@@ -5174,6 +5391,45 @@ impl GpuContextFullAccess {
         }
     }
 
+    /// Allocate a CUDA-importable texture. Host-only: the CUDA export
+    /// hands out raw fds and device UUIDs for in-process CUDA consumers;
+    /// subprocess customers reach CUDA through the cuda surface adapter
+    /// and the surface-share registry. See [`GpuContext::create_cuda_texture`].
+    #[cfg(target_os = "linux")]
+    pub fn create_cuda_texture(
+        &self,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+    ) -> Result<Texture> {
+        match self.handle_kind {
+            HandleKind::Boxed => self.host_inner().create_cuda_texture(width, height, format),
+            HandleKind::ScopeToken => Err(Error::GpuError(
+                "create_cuda_texture: CUDA export is host-only; subprocess \
+                 customers import surfaces through the cuda surface adapter"
+                    .into(),
+            )),
+        }
+    }
+
+    /// Export a texture from [`Self::create_cuda_texture`] for zero-copy
+    /// CUDA import. Same host-only story; see [`GpuContext::export_cuda`].
+    #[cfg(target_os = "linux")]
+    pub fn export_cuda(
+        &self,
+        texture: &Texture,
+        timeline: Option<&crate::vulkan::rhi::HostVulkanTimelineSemaphore>,
+    ) -> Result<CudaExport> {
+        match self.handle_kind {
+            HandleKind::Boxed => self.host_inner().export_cuda(texture, timeline),
+            HandleKind::ScopeToken => Err(Error::GpuError(
+                "export_cuda: CUDA export is host-only; subprocess customers \
+                 import surfaces through the cuda surface adapter"
+                    .into(),
+            )),
+        }
+    }
+
     /// Wrap an OPAQUE_FD `StorageBuffer` as a `PixelBuffer` sharing the
     /// same allocation so it can register through the surface-store
     /// `register_pixel_buffer_with_timeline` path (#1262). Mode-routed:
@@ -5682,6 +5938,74 @@ mod tests {
         println!("OPAQUE_FD mint/export/wrap OK — byte_size={BYTES} uuid={uuid:02x?}");
     }
 
+    /// CUDA array channel formats for the CUDA-mappable texture formats;
+    /// every other format must map to `None`, never to a guessed value.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn cuda_array_format_covers_only_mappable_formats() {
+        assert_eq!(cuda_array_format(TextureFormat::Rgba8Unorm), Some(0x01));
+        assert_eq!(cuda_array_format(TextureFormat::Rgba16Float), Some(0x10));
+        assert_eq!(cuda_array_format(TextureFormat::Rgba32Float), Some(0x20));
+        assert_eq!(cuda_array_format(TextureFormat::Bgra8Unorm), None);
+        assert_eq!(cuda_array_format(TextureFormat::Rgba8UnormSrgb), None);
+    }
+
+    /// `export_cuda` on a `create_cuda_texture` image hands back owned
+    /// memory + timeline fds, the dedicated allocation size, and the
+    /// owning device's UUID; a DMA-BUF texture is rejected. GPU-gated:
+    /// skips when no device or OPAQUE_FD image pool is present.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn export_cuda_round_trip_and_rejects_dma_buf_textures() {
+        use std::os::fd::AsRawFd;
+
+        let gpu = match GpuContext::init_for_platform() {
+            Ok(g) => g,
+            Err(_) => {
+                println!("Skipping - no GPU device available");
+                return;
+            }
+        };
+        let texture = match gpu.create_cuda_texture(64, 32, TextureFormat::Rgba8Unorm) {
+            Ok(t) => t,
+            Err(e) => {
+                println!("Skipping - OPAQUE_FD image pool unavailable: {e}");
+                return;
+            }
+        };
+        let timeline = gpu
+            .create_exportable_timeline_semaphore(0)
+            .expect("create_exportable_timeline_semaphore failed");
+
+        let export = gpu
+            .export_cuda(&texture, Some(&timeline))
+            .expect("export_cuda failed");
+        assert!(export.memory.as_raw_fd() >= 0);
+        assert!(
+            export
+                .timeline
+                .as_ref()
+                .is_some_and(|fd| fd.as_raw_fd() >= 0)
+        );
+        assert!(
+            export.memory_size >= 64 * 32 * 4,
+            "dedicated allocation must cover the image, got {}",
+            export.memory_size
+        );
+        assert_eq!(
+            export.device_uuid,
+            gpu.device().inner.physical_device_uuid()
+        );
+        assert_eq!((export.width, export.height), (64, 32));
+        assert_eq!(export.cuda_array_format(), Some(0x01));
+
+        let dma_buf_texture = gpu
+            .device()
+            .create_texture(&TextureDescriptor::new(64, 32, TextureFormat::Rgba8Unorm))
+            .expect("create_texture failed");
+        assert!(gpu.export_cuda(&dma_buf_texture, None).is_err());
+    }
+
     /// #1262 followup #1 — the ONLY positive coverage of the batch's
     /// riskiest FullAccess slot, `copy_texture_to_storage_buffer_and_signal`.
     ///
@@ -15,6 +15,7 @@ mod graphics_kernel_bridge;
 pub(crate) mod isolation;
 #[cfg(target_os = "linux")]
 mod ray_tracing_kernel_bridge;
+pub(crate) mod reference_signal;
 mod runtime_context;
 mod runtime_ops_shim;
 pub(crate) mod surface_store;
@@ -33,7 +34,7 @@ pub use compute_kernel_bridge::ComputeKernelBridge;
 #[cfg(target_os = "linux")]
 pub use cpu_readback_bridge::{CpuReadbackBridge, CpuReadbackCopyDirection};
 #[cfg(target_os = "linux")]
-pub use gpu_context::GpuCapabilitiesSnapshot;
+pub use gpu_context::{CudaExport, GpuCapabilitiesSnapshot};
 pub use gpu_context::{GpuContext, GpuContextFullAccess, GpuContextLimitedAccess};
 #[cfg(target_os = "linux")]
 pub use graphics_kernel_bridge::{
@@ -54,7 +55,10 @@ pub use ray_tracing_kernel_bridge::{
 };
 pub use isolation::IsolationTier;
 pub(crate) use isolation::FullAccessGrant;
-pub use runtime_context::{RuntimeContext, RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+pub use reference_signal::{ReferenceSignalStatus, report_reference_signal};
+pub use runtime_context::{
+    AssetsShim, FontsShim, RuntimeContext, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+};
 pub use runtime_ops_shim::RuntimeOpsShim;
 pub use surface_store::SurfaceStore;
 pub use texture_pool::*;
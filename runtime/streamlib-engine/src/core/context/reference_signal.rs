@@ -0,0 +1,107 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Reference-signal (genlock) status reported by hardware processors.
+//!
+//! Devices with a reference input — SDI cards locked to house sync, audio
+//! interfaces on word clock — report whether they are locked and to what.
+//! Reports arrive from host processors directly or from plugins over the
+//! reserved [`streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS`]
+//! control topic. The latest report per source is kept process-wide and
+//! read through [`crate::core::context::TimeContext::reference_signals`];
+//! every change is announced as [`RuntimeEvent::ReferenceSignalChanged`].
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+
+/// Lock state of one device's reference input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceSignalStatus {
+    /// Device the report is about, stable across reports (e.g.
+    /// `"decklink:0"`).
+    pub source: String,
+    /// Whether the device is locked to its reference input.
+    pub locked: bool,
+    /// Detected reference format while locked (e.g. `"1080i59.94"`).
+    pub mode: Option<String>,
+}
+
+static REFERENCE_SIGNALS: LazyLock<Mutex<BTreeMap<String, ReferenceSignalStatus>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Record `status` as the latest report for its source. Publishes
+/// [`RuntimeEvent::ReferenceSignalChanged`] when it differs from the
+/// previous report, so periodic re-reports stay quiet.
+pub fn report_reference_signal(status: ReferenceSignalStatus) {
+    if !record_reference_signal(&REFERENCE_SIGNALS, status.clone()) {
+        return;
+    }
+    if status.locked {
+        tracing::info!(source = %status.source, mode = ?status.mode, "reference signal locked");
+    } else {
+        tracing::warn!(source = %status.source, "reference signal not locked");
+    }
+    let event = Event::RuntimeGlobal(RuntimeEvent::ReferenceSignalChanged {
+        source: status.source,
+        locked: status.locked,
+        mode: status.mode,
+    });
+    PUBSUB.publish(topics::RUNTIME_GLOBAL, &event);
+}
+
+/// Latest report for every source that has reported, ordered by source.
+pub(crate) fn reference_signals() -> Vec<ReferenceSignalStatus> {
+    REFERENCE_SIGNALS.lock().values().cloned().collect()
+}
+
+/// Store `status`; returns whether it changed anything.
+fn record_reference_signal(
+    registry: &Mutex<BTreeMap<String, ReferenceSignalStatus>>,
+    status: ReferenceSignalStatus,
+) -> bool {
+    let mut registry = registry.lock();
+    if registry.get(&status.source) == Some(&status) {
+        return false;
+    }
+    registry.insert(status.source.clone(), status);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(locked: bool, mode: Option<&str>) -> ReferenceSignalStatus {
+        ReferenceSignalStatus {
+            source: "decklink:0".into(),
+            locked,
+            mode: mode.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn only_changes_are_recorded() {
+        let registry = Mutex::new(BTreeMap::new());
+
+        assert!(record_reference_signal(&registry, status(false, None)));
+        assert!(!record_reference_signal(&registry, status(false, None)));
+        assert!(record_reference_signal(
+            &registry,
+            status(true, Some("1080i59.94"))
+        ));
+        assert!(!record_reference_signal(
+            &registry,
+            status(true, Some("1080i59.94"))
+        ));
+
+        assert_eq!(
+            registry.lock().get("decklink:0"),
+            Some(&status(true, Some("1080i59.94")))
+        );
+    }
+}
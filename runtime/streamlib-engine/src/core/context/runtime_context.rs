@@ -1,18 +1,23 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::collections::HashMap;
 use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use parking_lot::Mutex;
 use streamlib_plugin_abi::RuntimeContextVTable;
 
 use super::{
     AudioClockShim, GpuContext, GpuContextFullAccess, GpuContextLimitedAccess, RuntimeOpsShim,
     SharedAudioClock, TimeContext,
 };
+use crate::core::assets::AssetManager;
+use crate::core::fonts::{FontFace, FontRegistry};
 use crate::core::graph::ProcessorUniqueId;
+use crate::core::rhi::GpuAdapterPreference;
 use crate::core::runtime::{RuntimeOperations, RuntimeUniqueId};
 use crate::iceoryx2::Iceoryx2Node;
 
@@ -24,6 +29,10 @@ pub struct RuntimeContext {
     /// Runtime-internal code (shutdown, diagnostics) still uses this field
     /// directly for operations not mirrored on the capability types.
     pub(crate) gpu: GpuContext,
+    /// Secondary GPU contexts opened for processors pinned to another
+    /// adapter, keyed by adapter index. Shared by every clone so each
+    /// adapter is opened once per runtime.
+    adapter_gpus: Arc<Mutex<HashMap<usize, GpuContext>>>,
     /// Shared timing context - monotonic clock starting at runtime creation.
     pub time: Arc<TimeContext>,
     /// Unique identifier for this runtime instance.
@@ -40,6 +49,10 @@ pub struct RuntimeContext {
     iceoryx2_node: Iceoryx2Node,
     /// Audio clock for synchronized audio timing.
     audio_clock: SharedAudioClock,
+    /// Content-addressed cache for files processors fetch by URL.
+    assets: Arc<AssetManager>,
+    /// Logical font names and shared glyph atlases.
+    fonts: Arc<FontRegistry>,
     /// Per-runtime surface-sharing Unix socket path. Polyglot subprocesses
     /// receive this via the `STREAMLIB_SURFACE_SOCKET` env var so their
     /// `streamlib-surface-client` connects to the runtime-internal service
@@ -57,10 +70,13 @@ impl RuntimeContext {
         tokio_handle: tokio::runtime::Handle,
         iceoryx2_node: Iceoryx2Node,
         audio_clock: SharedAudioClock,
+        assets: Arc<AssetManager>,
+        fonts: Arc<FontRegistry>,
         #[cfg(target_os = "linux")] surface_socket_path: std::path::PathBuf,
     ) -> Self {
         Self {
             gpu,
+            adapter_gpus: Arc::new(Mutex::new(HashMap::new())),
             time,
             runtime_id,
             processor_id: None,
@@ -69,6 +85,8 @@ impl RuntimeContext {
             tokio_handle,
             iceoryx2_node,
             audio_clock,
+            assets,
+            fonts,
             #[cfg(target_os = "linux")]
             surface_socket_path,
         }
@@ -166,10 +184,23 @@ impl RuntimeContext {
         &self.audio_clock
     }
 
+    /// The runtime's asset cache. Processors reach it through
+    /// [`RuntimeContextFullAccess::assets`].
+    pub fn assets(&self) -> &Arc<AssetManager> {
+        &self.assets
+    }
+
+    /// The runtime's font registry, including the shared glyph atlases.
+    /// Processors resolve names through [`RuntimeContextFullAccess::fonts`].
+    pub fn fonts(&self) -> &Arc<FontRegistry> {
+        &self.fonts
+    }
+
     /// Create a processor-specific context with a processor ID.
     pub fn with_processor_id(&self, processor_id: ProcessorUniqueId) -> Self {
         Self {
             gpu: self.gpu.clone(),
+            adapter_gpus: Arc::clone(&self.adapter_gpus),
             time: Arc::clone(&self.time),
             runtime_id: Arc::clone(&self.runtime_id),
             processor_id: Some(processor_id),
@@ -178,6 +209,8 @@ impl RuntimeContext {
             tokio_handle: self.tokio_handle.clone(),
             iceoryx2_node: self.iceoryx2_node.clone(),
             audio_clock: Arc::clone(&self.audio_clock),
+            assets: Arc::clone(&self.assets),
+            fonts: Arc::clone(&self.fonts),
             #[cfg(target_os = "linux")]
             surface_socket_path: self.surface_socket_path.clone(),
         }
@@ -187,6 +220,7 @@ impl RuntimeContext {
     pub fn with_pause_gate(&self, pause_gate: Arc<AtomicBool>) -> Self {
         Self {
             gpu: self.gpu.clone(),
+            adapter_gpus: Arc::clone(&self.adapter_gpus),
             time: Arc::clone(&self.time),
             runtime_id: Arc::clone(&self.runtime_id),
             processor_id: self.processor_id.clone(),
@@ -195,11 +229,39 @@ impl RuntimeContext {
             tokio_handle: self.tokio_handle.clone(),
             iceoryx2_node: self.iceoryx2_node.clone(),
             audio_clock: Arc::clone(&self.audio_clock),
+            assets: Arc::clone(&self.assets),
+            fonts: Arc::clone(&self.fonts),
             #[cfg(target_os = "linux")]
             surface_socket_path: self.surface_socket_path.clone(),
         }
     }
 
+    /// Create a context whose GPU operations run on `gpu` — used for
+    /// processors pinned to another adapter via
+    /// [`ProcessorSpec::gpu_adapter`](crate::core::ProcessorSpec::gpu_adapter).
+    pub(crate) fn with_gpu(&self, gpu: GpuContext) -> Self {
+        Self {
+            gpu,
+            ..self.clone()
+        }
+    }
+
+    /// GPU context for the adapter at `index`: the runtime's own context
+    /// when it already lives there, otherwise a secondary context opened
+    /// on first use and reused afterwards.
+    pub(crate) fn gpu_for_adapter(&self, index: usize) -> crate::core::Result<GpuContext> {
+        if index == self.gpu.adapter_index() {
+            return Ok(self.gpu.clone());
+        }
+        let mut adapter_gpus = self.adapter_gpus.lock();
+        if let Some(gpu) = adapter_gpus.get(&index) {
+            return Ok(gpu.clone());
+        }
+        let gpu = GpuContext::init_for_adapter(GpuAdapterPreference::Index(index))?;
+        adapter_gpus.insert(index, gpu.clone());
+        Ok(gpu)
+    }
+
     /// Check if this processor is paused.
     ///
     /// For Manual mode processors, call this in your processing loop/callback
@@ -788,6 +850,30 @@ impl<'a> RuntimeContextFullAccess<'a> {
         RuntimeOpsShim::from_ffi(owned_handle, rov) as Arc<dyn RuntimeOperations>
     }
 
+    /// The runtime's asset cache — fetch LUTs, stills, fonts, and models
+    /// by URL. Fetches block for the download, so this lives on the
+    /// setup-time capability only. Routed through
+    /// [`RuntimeContextVTable::assets_fetch`].
+    pub fn assets(&self) -> AssetsShim<'a> {
+        AssetsShim {
+            handle: self.handle,
+            vtable: self.vtable,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The runtime's font registry — resolve the logical font names a
+    /// config carries to fallback chains of font files. URL sources may
+    /// download, so this lives on the setup-time capability only. Routed
+    /// through [`RuntimeContextVTable::fonts_resolve`].
+    pub fn fonts(&self) -> FontsShim<'a> {
+        FontsShim {
+            handle: self.handle,
+            vtable: self.vtable,
+            _marker: PhantomData,
+        }
+    }
+
     /// Run `f` against a cdylib-shaped sibling
     /// `RuntimeContextFullAccess` whose `gpu_full` is a `ScopeToken`-
     /// flavored [`GpuContextFullAccess`] (instead of the `Boxed`
@@ -1051,6 +1137,167 @@ unsafe fn vtable_copy_processor_id(
     }
 }
 
+/// Borrow-scoped view of the host's [`AssetManager`], returned by
+/// [`RuntimeContextFullAccess::assets`].
+pub struct AssetsShim<'a> {
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    _marker: PhantomData<&'a RuntimeContext>,
+}
+
+impl AssetsShim<'_> {
+    /// Path of the cached copy of `url`, downloading it on first use.
+    /// See [`AssetManager::fetch`].
+    pub fn fetch(&self, url: &str) -> crate::core::error::Result<std::path::PathBuf> {
+        unsafe { vtable_assets_fetch(self.handle, self.vtable, url, None) }
+    }
+
+    /// Like [`Self::fetch`], but the content must hash to `sha256` (hex).
+    /// See [`AssetManager::fetch_pinned`].
+    pub fn fetch_pinned(
+        &self,
+        url: &str,
+        sha256: &str,
+    ) -> crate::core::error::Result<std::path::PathBuf> {
+        unsafe { vtable_assets_fetch(self.handle, self.vtable, url, Some(sha256)) }
+    }
+}
+
+/// Adapter that turns the ABI's completion-callback `assets_fetch` into a
+/// `Result`. The host fires the completion synchronously, exactly once.
+///
+/// # Safety
+///
+/// `vtable` must point at a valid [`RuntimeContextVTable`] of layout
+/// version 2 or later.
+unsafe fn vtable_assets_fetch(
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    url: &str,
+    sha256: Option<&str>,
+) -> crate::core::error::Result<std::path::PathBuf> {
+    use crate::core::error::Error;
+    use streamlib_plugin_abi::{ASSET_FETCH_OFFLINE, ASSET_FETCH_OK};
+
+    unsafe extern "C" fn record(
+        user_data: *mut c_void,
+        status: i32,
+        result_ptr: *const u8,
+        result_len: usize,
+    ) {
+        // SAFETY: `user_data` is the `Option<(i32, Vec<u8>)>` below; the
+        // payload bytes are valid for the duration of this call.
+        let slot = unsafe { &mut *(user_data as *mut Option<(i32, Vec<u8>)>) };
+        let payload = if result_ptr.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(result_ptr, result_len) }.to_vec()
+        };
+        *slot = Some((status, payload));
+    }
+
+    let (pin_ptr, pin_len) = sha256.map_or((std::ptr::null(), 0), |pin| (pin.as_ptr(), pin.len()));
+    let mut slot: Option<(i32, Vec<u8>)> = None;
+    unsafe {
+        ((*vtable).assets_fetch)(
+            handle,
+            url.as_ptr(),
+            url.len(),
+            pin_ptr,
+            pin_len,
+            record,
+            &mut slot as *mut Option<(i32, Vec<u8>)> as *mut c_void,
+        )
+    };
+    match slot {
+        Some((ASSET_FETCH_OK, payload)) => Ok(std::path::PathBuf::from(
+            String::from_utf8_lossy(&payload).into_owned(),
+        )),
+        Some((ASSET_FETCH_OFFLINE, _)) => Err(Error::AssetOffline(url.to_string())),
+        Some((_, payload)) => Err(Error::AssetFetchFailed {
+            url: url.to_string(),
+            detail: String::from_utf8_lossy(&payload).into_owned(),
+        }),
+        None => Err(Error::AssetFetchFailed {
+            url: url.to_string(),
+            detail: "host did not complete the fetch".to_string(),
+        }),
+    }
+}
+
+/// Borrow-scoped view of the host's [`FontRegistry`], returned by
+/// [`RuntimeContextFullAccess::fonts`].
+pub struct FontsShim<'a> {
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    _marker: PhantomData<&'a RuntimeContext>,
+}
+
+impl FontsShim<'_> {
+    /// The fallback chain for logical font `name`, primary face first.
+    /// See [`FontRegistry::resolve`].
+    pub fn resolve(&self, name: &str) -> crate::core::error::Result<Vec<FontFace>> {
+        unsafe { vtable_fonts_resolve(self.handle, self.vtable, name) }
+    }
+}
+
+/// Adapter that turns the ABI's completion-callback `fonts_resolve` into a
+/// `Result`, parsing the `"<face_index>\t<path>"` payload lines.
+///
+/// # Safety
+///
+/// `vtable` must point at a valid [`RuntimeContextVTable`] of layout
+/// version 3 or later.
+unsafe fn vtable_fonts_resolve(
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    name: &str,
+) -> crate::core::error::Result<Vec<FontFace>> {
+    use crate::core::error::Error;
+    use streamlib_plugin_abi::FONT_RESOLVE_OK;
+
+    unsafe extern "C" fn record(
+        user_data: *mut c_void,
+        status: i32,
+        result_ptr: *const u8,
+        result_len: usize,
+    ) {
+        // SAFETY: `user_data` is the `Option<(i32, Vec<u8>)>` below; the
+        // payload bytes are valid for the duration of this call.
+        let slot = unsafe { &mut *(user_data as *mut Option<(i32, Vec<u8>)>) };
+        let payload = if result_ptr.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(result_ptr, result_len) }.to_vec()
+        };
+        *slot = Some((status, payload));
+    }
+
+    let mut slot: Option<(i32, Vec<u8>)> = None;
+    unsafe {
+        ((*vtable).fonts_resolve)(
+            handle,
+            name.as_ptr(),
+            name.len(),
+            record,
+            &mut slot as *mut Option<(i32, Vec<u8>)> as *mut c_void,
+        )
+    };
+    match slot {
+        Some((FONT_RESOLVE_OK, payload)) => Ok(String::from_utf8_lossy(&payload)
+            .lines()
+            .filter_map(|line| {
+                let (index, path) = line.split_once('\t')?;
+                Some(FontFace {
+                    path: std::path::PathBuf::from(path),
+                    face_index: index.parse().ok()?,
+                })
+            })
+            .collect()),
+        _ => Err(Error::FontNotFound(name.to_string())),
+    }
+}
+
 // Mark the raw pointers as Send + Sync. The shim itself is `!Send` /
 // `!Sync` via its `PhantomData<&'a RuntimeContext>` borrow — the
 // unsafe impls below cover the inner field requirements that the
@@ -1264,6 +1511,8 @@ mod host_runtime_ops_wiring_tests {
             tokio_runtime.handle().clone(),
             node,
             audio_clock,
+            Arc::clone(runner.assets()),
+            Arc::clone(runner.fonts()),
             #[cfg(target_os = "linux")]
             std::path::PathBuf::from("/tmp/streamlib-test-tap-wiring.sock"),
         );
@@ -19,12 +19,21 @@
 //! site.
 
 use std::ffi::c_void;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use streamlib_plugin_abi::{RuntimeOpCompletionCallback, RuntimeOpsVTable};
 
+use crate::core::chaos::{ChaosFault, ChaosFaultRecord};
 use crate::core::error::{Error, Result};
+use crate::core::frame_snapshot::{FrameSnapshot, PreviewOptions};
 use crate::core::graph::{LinkUniqueId, ProcessorUniqueId};
+use crate::core::graph_edit_history::GraphEdit;
+use crate::core::graph_patch::{GraphPatch, GraphPatchReceipt};
+use crate::core::graph_snapshot::GraphSnapshot;
+use crate::core::midi_mapping::MidiControlBinding;
+use crate::core::parameter_automation::ParameterChange;
+use crate::core::preset_morph::PresetMorph;
 use crate::core::processors::ProcessorSpec;
 use crate::core::runtime::{
     BoxFuture, RegisterProcessorReceipt, ReplaceProcessorFromSource, RuntimeOperations,
@@ -215,6 +224,14 @@ unsafe extern "C" fn runtime_ops_completion_trampoline(
     )
 }
 
+/// The rejection for a host-side-only operation reached across the plugin ABI.
+fn host_side_only(operation: &str) -> Error {
+    Error::NotSupported(format!(
+        "{operation} is a host-side operation with no RuntimeOpsVTable op; call it from the \
+         host api-server surface"
+    ))
+}
+
 impl RuntimeOperations for RuntimeOpsShim {
     fn add_processor_async(&self, spec: ProcessorSpec) -> BoxFuture<'_, Result<ProcessorUniqueId>> {
         self.submit_msgpack(
@@ -336,6 +353,91 @@ impl RuntimeOperations for RuntimeOpsShim {
         })
     }
 
+    // Config updates, per-processor pause and snapshots are host-side
+    // control-plane operations with no `RuntimeOpsVTable` op.
+    fn update_processor_config_async(
+        &self,
+        _processor_id: ProcessorUniqueId,
+        _config: serde_json::Value,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Err(host_side_only("update_processor_config")) })
+    }
+
+    fn set_processor_paused_async(
+        &self,
+        _processor_id: ProcessorUniqueId,
+        _paused: bool,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Err(host_side_only("set_processor_paused")) })
+    }
+
+    fn save_graph_snapshot_async(&self) -> BoxFuture<'_, Result<GraphSnapshot>> {
+        Box::pin(async move { Err(host_side_only("save_graph_snapshot")) })
+    }
+
+    fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+        Box::pin(async move { Err(host_side_only("undo_graph_edit")) })
+    }
+
+    fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+        Box::pin(async move { Err(host_side_only("redo_graph_edit")) })
+    }
+
+    fn apply_graph_patch_async(
+        &self,
+        _patch: GraphPatch,
+    ) -> BoxFuture<'_, Result<GraphPatchReceipt>> {
+        Box::pin(async move { Err(host_side_only("apply_graph_patch")) })
+    }
+
+    fn morph_processor_configs_async(&self, _morph: PresetMorph) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Err(host_side_only("morph_processor_configs")) })
+    }
+
+    fn set_midi_mappings_async(
+        &self,
+        _bindings: Vec<MidiControlBinding>,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Err(host_side_only("set_midi_mappings")) })
+    }
+
+    fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>> {
+        Box::pin(async move { Err(host_side_only("midi_mappings")) })
+    }
+
+    fn set_parameter_async(&self, _change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { Err(host_side_only("set_parameter")) })
+    }
+
+    fn inject_chaos_fault_async(
+        &self,
+        _fault: ChaosFault,
+    ) -> BoxFuture<'_, Result<ChaosFaultRecord>> {
+        Box::pin(async move { Err(host_side_only("inject_chaos_fault")) })
+    }
+
+    fn chaos_fault_log_async(&self) -> BoxFuture<'_, Result<Vec<ChaosFaultRecord>>> {
+        Box::pin(async move { Err(host_side_only("chaos_fault_log")) })
+    }
+
+    fn snapshot_async(
+        &self,
+        _processor_id: ProcessorUniqueId,
+        _port: String,
+        _path: PathBuf,
+    ) -> BoxFuture<'_, Result<FrameSnapshot>> {
+        Box::pin(async move { Err(host_side_only("snapshot")) })
+    }
+
+    fn preview_async(
+        &self,
+        _processor_id: ProcessorUniqueId,
+        _port: String,
+        _options: PreviewOptions,
+    ) -> BoxFuture<'_, Result<crate::core::runtime::PortPreview>> {
+        Box::pin(async move { Err(host_side_only("preview")) })
+    }
+
     // -------------------------------------------------------------------------
     // Sync convenience wrappers — `block_on` against the caller's
     // ambient tokio context. Plugins driving these from non-async
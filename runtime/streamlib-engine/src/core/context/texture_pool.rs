@@ -6,7 +6,8 @@
 use std::collections::HashMap;
 use std::ffi::c_void;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 use parking_lot::{Condvar, Mutex};
 use streamlib_plugin_abi::GpuContextLimitedAccessVTable;
@@ -92,6 +93,53 @@ impl Default for TexturePoolExhaustionPolicy {
     }
 }
 
+/// Policy for freeing idle textures when a bucket needs to grow.
+///
+/// Buckets are keyed by resolution and format, so a pipeline that changes
+/// resolution mid-session leaves the old buckets' textures allocated.
+/// Eviction only ever frees textures no processor holds, and only from
+/// buckets other than the one growing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TexturePoolEvictionPolicy {
+    /// Keep every texture until [`TexturePool::clear_unused`].
+    #[default]
+    Never,
+    /// Free the idle textures of buckets that handed out nothing for
+    /// `idle_ms`.
+    Idle { idle_ms: u64 },
+    /// Cap the textures held across all buckets. Growing past the cap frees
+    /// idle textures from the least recently used buckets first; when none
+    /// are idle the acquire falls through to the exhaustion policy.
+    LeastRecentlyUsed { max_total_textures: usize },
+}
+
+/// Per-resolution override of
+/// [`TexturePoolConfig::max_pool_size_per_bucket`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TexturePoolBucketLimit {
+    pub width: u32,
+    pub height: u32,
+    /// Format the limit applies to; `None` matches every format.
+    pub format: Option<TextureFormat>,
+    /// Maximum number of textures in each matching bucket.
+    pub max_textures: usize,
+}
+
+impl TexturePoolBucketLimit {
+    fn matches(&self, key: &TexturePoolKey) -> bool {
+        self.width == key.width
+            && self.height == key.height
+            && self.format.is_none_or(|format| format == key.format)
+    }
+}
+
+/// Textures to allocate when the pool is created.
+#[derive(Clone, Debug)]
+pub struct TexturePoolPrewarm {
+    pub descriptor: TexturePoolDescriptor,
+    pub count: usize,
+}
+
 /// Configuration for the texture pool.
 #[derive(Clone, Debug)]
 pub struct TexturePoolConfig {
@@ -99,8 +147,26 @@ pub struct TexturePoolConfig {
     pub initial_pool_size_per_bucket: usize,
     /// Maximum number of textures per bucket.
     pub max_pool_size_per_bucket: usize,
+    /// Per-resolution / per-format overrides of
+    /// `max_pool_size_per_bucket`; the first matching limit wins.
+    pub bucket_limits: Vec<TexturePoolBucketLimit>,
+    /// Textures allocated up front by [`TexturePool::prewarm_configured`],
+    /// so the first frames at a known resolution don't pay for allocation.
+    pub prewarm: Vec<TexturePoolPrewarm>,
     /// Policy when pool is exhausted.
     pub exhaustion_policy: TexturePoolExhaustionPolicy,
+    /// Policy for freeing idle textures when a bucket grows.
+    pub eviction_policy: TexturePoolEvictionPolicy,
+}
+
+impl TexturePoolConfig {
+    /// Maximum number of textures the bucket for `key` may hold.
+    pub fn max_textures_for(&self, key: &TexturePoolKey) -> usize {
+        self.bucket_limits
+            .iter()
+            .find(|limit| limit.matches(key))
+            .map_or(self.max_pool_size_per_bucket, |limit| limit.max_textures)
+    }
 }
 
 impl Default for TexturePoolConfig {
@@ -108,18 +174,45 @@ impl Default for TexturePoolConfig {
         Self {
             initial_pool_size_per_bucket: 4,
             max_pool_size_per_bucket: 16,
+            bucket_limits: Vec::new(),
+            prewarm: Vec::new(),
             exhaustion_policy: TexturePoolExhaustionPolicy::default(),
+            eviction_policy: TexturePoolEvictionPolicy::default(),
         }
     }
 }
 
 /// Statistics about texture pool usage.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct TexturePoolStats {
     pub total_textures: usize,
     pub textures_in_use: usize,
     pub textures_available: usize,
     pub bucket_count: usize,
+    /// Textures the existing buckets may hold before acquires hit the
+    /// exhaustion policy — the sum of each bucket's limit, capped by a
+    /// `LeastRecentlyUsed` eviction policy's total.
+    pub capacity: usize,
+    /// Most textures ever in use at once since the pool was created.
+    pub high_water_in_use: usize,
+    /// Device allocations that failed while growing a bucket.
+    pub allocation_failures: u64,
+    /// Acquires that failed because a bucket was exhausted — the
+    /// `ReturnError` policy, a maxed-out `GrowPool`, or a `Block` timeout.
+    pub exhausted_acquires: u64,
+    /// Idle textures freed by the eviction policy.
+    pub evicted_textures: u64,
+}
+
+impl TexturePoolStats {
+    /// Fraction of [`Self::capacity`] in use, `0.0` for an empty pool.
+    pub fn occupancy(&self) -> f64 {
+        if self.capacity == 0 {
+            0.0
+        } else {
+            self.textures_in_use as f64 / self.capacity as f64
+        }
+    }
 }
 
 /// A slot in the texture pool.
@@ -146,6 +239,87 @@ impl PoolSlot {
     }
 }
 
+/// A pooled slot as eviction sees it — only whether it can be freed.
+pub(crate) trait EvictableSlot {
+    fn is_evictable(&self) -> bool;
+}
+
+impl EvictableSlot for Arc<PoolSlot> {
+    fn is_evictable(&self) -> bool {
+        self.is_available()
+    }
+}
+
+/// LRU bookkeeping behind the eviction policies: when each bucket was last
+/// used, and how many textures eviction has freed.
+#[derive(Default)]
+pub(crate) struct TexturePoolEviction {
+    /// When each bucket last handed out or gained a texture. Lock order:
+    /// the pool's `buckets` before this.
+    pub(crate) last_used: Mutex<HashMap<TexturePoolKey, Instant>>,
+    pub(crate) evicted_textures: AtomicU64,
+}
+
+impl TexturePoolEviction {
+    pub(crate) fn touch(&self, key: &TexturePoolKey, now: Instant) {
+        self.last_used.lock().insert(key.clone(), now);
+    }
+
+    /// Free up to `limit` evictable slots from `buckets` other than `keep`
+    /// that have gone unused for at least `min_idle`, least recently used
+    /// bucket first. Emptied buckets are dropped. Returns how many were
+    /// freed.
+    pub(crate) fn evict_idle<S: EvictableSlot>(
+        &self,
+        buckets: &mut HashMap<TexturePoolKey, Vec<S>>,
+        keep: &TexturePoolKey,
+        min_idle: Duration,
+        limit: usize,
+        now: Instant,
+    ) -> usize {
+        let mut last_used = self.last_used.lock();
+        let mut candidates: Vec<(Instant, TexturePoolKey)> = buckets
+            .keys()
+            .filter(|key| *key != keep)
+            .filter_map(|key| Some((*last_used.get(key)?, key.clone())))
+            .filter(|(used, _)| now.saturating_duration_since(*used) >= min_idle)
+            .collect();
+        candidates.sort_by_key(|(used, _)| *used);
+
+        let mut freed = 0;
+        for (_, key) in candidates {
+            if freed == limit {
+                break;
+            }
+            if let Some(slots) = buckets.get_mut(&key) {
+                slots.retain(|slot| {
+                    if freed < limit && slot.is_evictable() {
+                        freed += 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+        }
+        buckets.retain(|_, slots| !slots.is_empty());
+        last_used.retain(|key, _| buckets.contains_key(key));
+
+        if freed > 0 {
+            self.evicted_textures
+                .fetch_add(freed as u64, Ordering::Relaxed);
+            tracing::debug!("Texture pool evicted {} idle texture(s)", freed);
+        }
+        freed
+    }
+}
+
+/// Textures a `LeastRecentlyUsed { max_total_textures }` pool holding
+/// `total_textures` must evict before it can allocate one more.
+fn lru_evictions_needed(total_textures: usize, max_total_textures: usize) -> usize {
+    (total_textures + 1).saturating_sub(max_total_textures)
+}
+
 /// Inner pool state (behind Arc for sharing).
 pub(crate) struct TexturePoolInner {
     pub(crate) buckets: Mutex<HashMap<TexturePoolKey, Vec<Arc<PoolSlot>>>>,
@@ -154,6 +328,11 @@ pub(crate) struct TexturePoolInner {
     pub(crate) next_slot_id: AtomicU64,
     pub(crate) available_condvar: Condvar,
     pub(crate) buckets_mutex_for_condvar: Mutex<()>,
+    pub(crate) in_use_count: AtomicUsize,
+    pub(crate) high_water_in_use: AtomicUsize,
+    pub(crate) allocation_failures: AtomicU64,
+    pub(crate) exhausted_acquires: AtomicU64,
+    pub(crate) eviction: TexturePoolEviction,
 }
 
 impl TexturePoolInner {
@@ -167,6 +346,7 @@ impl TexturePoolInner {
             for slot in slots {
                 if slot.id == slot_id {
                     slot.release();
+                    self.in_use_count.fetch_sub(1, Ordering::AcqRel);
                     // Signal waiting acquirers
                     self.available_condvar.notify_one();
                     return;
@@ -193,8 +373,43 @@ impl TexturePoolInner {
     }
 
     pub(crate) fn add_slot(&self, slot: Arc<PoolSlot>) {
+        let key = slot.key.clone();
+        self.buckets
+            .lock()
+            .entry(key.clone())
+            .or_default()
+            .push(slot);
+        self.touch(&key);
+    }
+
+    /// Count a slot handed out by `acquire` toward the high-water mark.
+    pub(crate) fn note_acquired(&self, key: &TexturePoolKey) {
+        let in_use = self.in_use_count.fetch_add(1, Ordering::AcqRel) + 1;
+        self.high_water_in_use.fetch_max(in_use, Ordering::AcqRel);
+        self.touch(key);
+    }
+
+    fn touch(&self, key: &TexturePoolKey) {
+        self.eviction.touch(key, Instant::now());
+    }
+
+    pub(crate) fn total_textures(&self) -> usize {
+        self.buckets.lock().values().map(Vec::len).sum()
+    }
+
+    /// Free up to `limit` idle textures from buckets other than `keep`
+    /// that have gone unused for at least `min_idle`, least recently used
+    /// bucket first. Returns how many were freed.
+    pub(crate) fn evict_idle(
+        &self,
+        keep: &TexturePoolKey,
+        min_idle: Duration,
+        limit: usize,
+        now: Instant,
+    ) -> usize {
         let mut buckets = self.buckets.lock();
-        buckets.entry(slot.key.clone()).or_default().push(slot);
+        self.eviction
+            .evict_idle(&mut *buckets, keep, min_idle, limit, now)
     }
 
     pub(crate) fn stats(&self) -> TexturePoolStats {
@@ -209,11 +424,25 @@ impl TexturePoolInner {
                 }
             }
         }
+        let mut capacity: usize = buckets
+            .keys()
+            .map(|key| self.config.max_textures_for(key))
+            .sum();
+        if let TexturePoolEvictionPolicy::LeastRecentlyUsed { max_total_textures } =
+            self.config.eviction_policy
+        {
+            capacity = capacity.min(max_total_textures);
+        }
         TexturePoolStats {
             total_textures: total,
             textures_in_use: in_use,
             textures_available: total - in_use,
             bucket_count: buckets.len(),
+            capacity,
+            high_water_in_use: self.high_water_in_use.load(Ordering::Acquire),
+            allocation_failures: self.allocation_failures.load(Ordering::Relaxed),
+            exhausted_acquires: self.exhausted_acquires.load(Ordering::Relaxed),
+            evicted_textures: self.eviction.evicted_textures.load(Ordering::Relaxed),
         }
     }
 }
@@ -438,6 +667,11 @@ impl TexturePool {
                 next_slot_id: AtomicU64::new(0),
                 available_condvar: Condvar::new(),
                 buckets_mutex_for_condvar: Mutex::new(()),
+                in_use_count: AtomicUsize::new(0),
+                high_water_in_use: AtomicUsize::new(0),
+                allocation_failures: AtomicU64::new(0),
+                exhausted_acquires: AtomicU64::new(0),
+                eviction: TexturePoolEviction::default(),
             }),
         }
     }
@@ -453,7 +687,8 @@ impl TexturePool {
 
         // No available slot - check if we can grow
         let current_size = self.inner.bucket_size(&key);
-        let can_grow = current_size < self.inner.config.max_pool_size_per_bucket;
+        let can_grow =
+            current_size < self.inner.config.max_textures_for(&key) && self.make_room(&key);
 
         if can_grow {
             // Allocate a new texture
@@ -475,14 +710,12 @@ impl TexturePool {
                     self.inner.add_slot(Arc::clone(&slot));
                     Ok(self.create_handle_from_slot(&slot))
                 } else {
-                    Err(Error::TextureError(
-                        "Texture pool exhausted (max size reached)".into(),
-                    ))
+                    Err(self.exhausted("Texture pool exhausted (max size reached)".into()))
                 }
             }
-            TexturePoolExhaustionPolicy::ReturnError => Err(Error::TextureError(
-                "Texture pool exhausted (no available slots)".into(),
-            )),
+            TexturePoolExhaustionPolicy::ReturnError => {
+                Err(self.exhausted("Texture pool exhausted (no available slots)".into()))
+            }
         }
     }
 
@@ -503,7 +736,7 @@ impl TexturePool {
             // Check timeout
             let now = std::time::Instant::now();
             if now >= deadline {
-                return Err(Error::TextureError(format!(
+                return Err(self.exhausted(format!(
                     "Texture pool exhausted (timeout after {}ms)",
                     timeout_ms
                 )));
@@ -519,7 +752,7 @@ impl TexturePool {
                 if let Some(slot) = self.inner.find_available_slot(key) {
                     return Ok(self.create_handle_from_slot(&slot));
                 }
-                return Err(Error::TextureError(format!(
+                return Err(self.exhausted(format!(
                     "Texture pool exhausted (timeout after {}ms)",
                     timeout_ms
                 )));
@@ -527,7 +760,41 @@ impl TexturePool {
         }
     }
 
+    /// Apply the eviction policy before the bucket for `key` grows by one
+    /// texture. Returns whether the pool has room for it.
+    fn make_room(&self, key: &TexturePoolKey) -> bool {
+        match self.inner.config.eviction_policy {
+            TexturePoolEvictionPolicy::Never => true,
+            TexturePoolEvictionPolicy::Idle { idle_ms } => {
+                self.inner.evict_idle(
+                    key,
+                    Duration::from_millis(idle_ms),
+                    usize::MAX,
+                    Instant::now(),
+                );
+                true
+            }
+            TexturePoolEvictionPolicy::LeastRecentlyUsed { max_total_textures } => {
+                let needed = lru_evictions_needed(self.inner.total_textures(), max_total_textures);
+                needed == 0
+                    || self
+                        .inner
+                        .evict_idle(key, Duration::ZERO, needed, Instant::now())
+                        == needed
+            }
+        }
+    }
+
+    /// Count an acquire that hit the exhaustion policy and build its error.
+    fn exhausted(&self, message: String) -> Error {
+        self.inner
+            .exhausted_acquires
+            .fetch_add(1, Ordering::Relaxed);
+        Error::TextureError(message)
+    }
+
     fn create_handle_from_slot(&self, slot: &Arc<PoolSlot>) -> PooledTextureHandle {
+        self.inner.note_acquired(&slot.key);
         PooledTextureHandle::from_parts(
             slot.texture.clone(),
             Arc::clone(&self.inner),
@@ -538,9 +805,27 @@ impl TexturePool {
         )
     }
 
-    /// Allocate a new texture slot.
-    #[cfg(not(target_os = "macos"))]
+    /// Allocate a new texture slot, counting device failures.
     fn allocate_slot(&self, desc: &TexturePoolDescriptor) -> Result<Arc<PoolSlot>> {
+        let slot = self.allocate_device_slot(desc);
+        if let Err(e) = &slot {
+            self.inner
+                .allocation_failures
+                .fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                width = desc.width,
+                height = desc.height,
+                format = ?desc.format,
+                "Texture pool allocation failed: {}",
+                e
+            );
+        }
+        slot
+    }
+
+    /// Allocate a new device texture for a slot.
+    #[cfg(not(target_os = "macos"))]
+    fn allocate_device_slot(&self, desc: &TexturePoolDescriptor) -> Result<Arc<PoolSlot>> {
         let texture_desc =
             TextureDescriptor::new(desc.width, desc.height, desc.format).with_usage(desc.usage);
 
@@ -556,7 +841,7 @@ impl TexturePool {
 
     /// Allocate a new IOSurface-backed texture slot (macOS).
     #[cfg(target_os = "macos")]
-    fn allocate_slot(&self, desc: &TexturePoolDescriptor) -> Result<Arc<PoolSlot>> {
+    fn allocate_device_slot(&self, desc: &TexturePoolDescriptor) -> Result<Arc<PoolSlot>> {
         // Delegate to macOS-specific implementation
         crate::apple::texture_pool_macos::allocate_iosurface_slot(&self.inner, desc)
     }
@@ -570,6 +855,21 @@ impl TexturePool {
         Ok(())
     }
 
+    /// Allocate every [`TexturePoolConfig::prewarm`] entry.
+    pub fn prewarm_configured(&self) -> Result<()> {
+        for prewarm in &self.inner.config.prewarm {
+            self.prewarm(&prewarm.descriptor, prewarm.count)?;
+            tracing::debug!(
+                "Texture pool prewarmed {} texture(s) at {}x{} {:?}",
+                prewarm.count,
+                prewarm.descriptor.width,
+                prewarm.descriptor.height,
+                prewarm.descriptor.format
+            );
+        }
+        Ok(())
+    }
+
     /// Get statistics about pool usage.
     pub fn stats(&self) -> TexturePoolStats {
         self.inner.stats()
@@ -600,10 +900,155 @@ impl std::fmt::Debug for TexturePool {
             .field("textures_in_use", &stats.textures_in_use)
             .field("textures_available", &stats.textures_available)
             .field("bucket_count", &stats.bucket_count)
+            .field("high_water_in_use", &stats.high_water_in_use)
             .finish()
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(width: u32, height: u32, format: TextureFormat) -> TexturePoolKey {
+        TexturePoolKey::from_descriptor(&TexturePoolDescriptor::new(width, height, format))
+    }
+
+    /// A slot without a device texture; `true` while handed out.
+    struct FakeSlot(bool);
+
+    impl EvictableSlot for FakeSlot {
+        fn is_evictable(&self) -> bool {
+            !self.0
+        }
+    }
+
+    #[test]
+    fn evict_idle_frees_least_recently_used_buckets_first_and_spares_the_growing_one() {
+        let t0 = Instant::now();
+        let oldest = key(640, 480, TextureFormat::Rgba8Unorm);
+        let older = key(1280, 720, TextureFormat::Rgba8Unorm);
+        let growing = key(1920, 1080, TextureFormat::Rgba8Unorm);
+        let eviction = TexturePoolEviction::default();
+        let mut buckets = HashMap::new();
+        for (bucket, used) in [
+            (&growing, t0),
+            (&oldest, t0 + Duration::from_secs(1)),
+            (&older, t0 + Duration::from_secs(2)),
+        ] {
+            buckets.insert(bucket.clone(), vec![FakeSlot(false), FakeSlot(false)]);
+            eviction.touch(bucket, used);
+        }
+
+        let freed = eviction.evict_idle(
+            &mut buckets,
+            &growing,
+            Duration::ZERO,
+            3,
+            t0 + Duration::from_secs(10),
+        );
+
+        assert_eq!(freed, 3);
+        assert!(!buckets.contains_key(&oldest), "emptied bucket is dropped");
+        assert_eq!(
+            buckets[&older].len(),
+            1,
+            "the limit stops the second bucket"
+        );
+        assert_eq!(
+            buckets[&growing].len(),
+            2,
+            "the growing bucket is never evicted"
+        );
+        let last_used = eviction.last_used.lock();
+        assert!(
+            !last_used.contains_key(&oldest),
+            "dropped bucket's timestamp is cleared"
+        );
+        assert!(last_used.contains_key(&older) && last_used.contains_key(&growing));
+        drop(last_used);
+        assert_eq!(eviction.evicted_textures.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn evict_idle_skips_in_use_slots_and_buckets_used_within_min_idle() {
+        let t0 = Instant::now();
+        let idle = key(640, 480, TextureFormat::Rgba8Unorm);
+        let recent = key(1280, 720, TextureFormat::Rgba8Unorm);
+        let growing = key(1920, 1080, TextureFormat::Rgba8Unorm);
+        let eviction = TexturePoolEviction::default();
+        let mut buckets = HashMap::new();
+        buckets.insert(idle.clone(), vec![FakeSlot(true), FakeSlot(false)]);
+        eviction.touch(&idle, t0);
+        buckets.insert(recent.clone(), vec![FakeSlot(false)]);
+        eviction.touch(&recent, t0 + Duration::from_secs(9));
+        let now = t0 + Duration::from_secs(10);
+
+        let freed = eviction.evict_idle(
+            &mut buckets,
+            &growing,
+            Duration::from_secs(5),
+            usize::MAX,
+            now,
+        );
+
+        assert_eq!(freed, 1, "only the idle bucket's free slot goes");
+        assert_eq!(buckets[&idle].len(), 1, "the in-use slot stays");
+        assert_eq!(buckets[&recent].len(), 1);
+        assert!(eviction.last_used.lock().contains_key(&idle));
+
+        assert_eq!(
+            eviction.evict_idle(&mut buckets, &growing, Duration::from_secs(5), 1, now),
+            0
+        );
+        assert_eq!(
+            eviction.evicted_textures.load(Ordering::Relaxed),
+            1,
+            "a pass that frees nothing leaves the counter alone"
+        );
+    }
+
+    #[test]
+    fn lru_evictions_needed_counts_the_overflow_of_one_more_texture() {
+        assert_eq!(lru_evictions_needed(3, 4), 0);
+        assert_eq!(lru_evictions_needed(4, 4), 1);
+        assert_eq!(lru_evictions_needed(6, 4), 3);
+    }
+
+    #[test]
+    fn bucket_limits_override_the_default_per_resolution() {
+        let config = TexturePoolConfig {
+            bucket_limits: vec![
+                TexturePoolBucketLimit {
+                    width: 3840,
+                    height: 2160,
+                    format: Some(TextureFormat::Rgba16Float),
+                    max_textures: 2,
+                },
+                TexturePoolBucketLimit {
+                    width: 3840,
+                    height: 2160,
+                    format: None,
+                    max_textures: 4,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.max_textures_for(&key(3840, 2160, TextureFormat::Rgba16Float)),
+            2
+        );
+        assert_eq!(
+            config.max_textures_for(&key(3840, 2160, TextureFormat::Rgba8Unorm)),
+            4
+        );
+        assert_eq!(
+            config.max_textures_for(&key(1920, 1080, TextureFormat::Rgba8Unorm)),
+            16
+        );
+    }
+}
+
 // =============================================================================
 // Layout regression tests
 // =============================================================================
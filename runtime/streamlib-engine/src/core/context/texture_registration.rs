@@ -22,13 +22,25 @@
 //! `vkCmdPipelineBarrier2` source layout. On other platforms only the
 //! texture is held — Metal manages texture state automatically and
 //! Vulkan layouts don't apply.
+//!
+//! Both Linux and macOS registrations also carry the producer's
+//! [`GpuSyncPoint`] — the timeline-semaphore (Vulkan) or shared-event
+//! (Metal) value the producer's last write signals — so consumers on a
+//! different queue wait for the write instead of relying on implicit
+//! driver ordering.
 
 use std::ffi::c_void;
 use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use parking_lot::Mutex;
 use streamlib_plugin_abi::GpuContextLimitedAccessVTable;
 
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use crate::core::rhi::GpuSyncPoint;
 use crate::core::rhi::Texture;
+use crate::core::{Error, Result};
 
 #[cfg(target_os = "linux")]
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -51,6 +63,12 @@ pub(crate) struct TextureRegistrationInner {
     /// next reader."
     #[cfg(target_os = "linux")]
     pub(crate) current_layout: AtomicI32,
+    /// Completion point of the producer's most recent write. `None`
+    /// until a producer publishes one — consumers then fall back to
+    /// queue-submission ordering, which is correct for single-queue
+    /// producers.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub(crate) producer_sync: Mutex<Option<GpuSyncPoint>>,
 }
 
 /// Per-surface registration record held by
@@ -87,6 +105,7 @@ impl TextureRegistration {
         let inner = TextureRegistrationInner {
             texture,
             current_layout: AtomicI32::new(initial_layout.0),
+            producer_sync: Mutex::new(None),
         };
         Self::from_arc_into_raw(Arc::new(inner))
     }
@@ -94,7 +113,11 @@ impl TextureRegistration {
     /// Construct a registration on platforms without Vulkan layout tracking.
     #[cfg(not(target_os = "linux"))]
     pub fn new(texture: Texture) -> Self {
-        let inner = TextureRegistrationInner { texture };
+        let inner = TextureRegistrationInner {
+            texture,
+            #[cfg(target_os = "macos")]
+            producer_sync: Mutex::new(None),
+        };
         Self::from_arc_into_raw(Arc::new(inner))
     }
 
@@ -178,6 +201,56 @@ impl TextureRegistration {
             ((*self.vtable).texture_registration_update_layout)(self.handle, new_layout.0);
         }
     }
+
+    /// Publish the completion point of the producer's latest write
+    /// (`None` clears it). Host-side producers call this right after the
+    /// submit that signals the point; cdylib producers reach the same
+    /// state through the SDK twin's `set_producer_timeline`.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub fn set_producer_sync_point(&self, sync_point: Option<GpuSyncPoint>) {
+        *self.host_inner().producer_sync.lock() = sync_point;
+    }
+
+    /// Completion point of the producer's latest write, if one was
+    /// published. Consumers that submit GPU work feed it into their own
+    /// submit's wait list (timeline wait / `encodeWaitForEvent:value:`).
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub fn producer_sync_point(&self) -> Option<GpuSyncPoint> {
+        self.host_inner().producer_sync.lock().clone()
+    }
+
+    /// CPU-block until the producer's published completion point is
+    /// reached. Returns immediately when no point is published.
+    ///
+    /// Dispatches through the vtable's
+    /// [`GpuContextLimitedAccessVTable::texture_registration_wait_producer`]
+    /// callback.
+    pub fn wait_for_producer(&self, timeout: Duration) -> Result<()> {
+        if self.handle.is_null() || self.vtable.is_null() {
+            return Ok(());
+        }
+        let timeout_ns = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+        let mut err_buf = [0u8; 256];
+        let mut err_len: usize = 0;
+        // SAFETY: vtable + handle were paired at construction; err_buf is
+        // caller-owned stack storage the host writes at most `len` bytes to.
+        let status = unsafe {
+            ((*self.vtable).texture_registration_wait_producer)(
+                self.handle,
+                timeout_ns,
+                err_buf.as_mut_ptr(),
+                err_buf.len(),
+                &mut err_len as *mut usize,
+            )
+        };
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(Error::GpuError(
+                String::from_utf8_lossy(&err_buf[..err_len.min(err_buf.len())]).into_owned(),
+            ))
+        }
+    }
 }
 
 impl Clone for TextureRegistration {
@@ -299,4 +372,41 @@ mod tests {
             final_layout
         );
     }
+
+    #[test]
+    fn wait_for_producer_follows_published_timeline() {
+        let Ok(gpu) = GpuContext::init_for_platform() else {
+            println!("Skipping - no GPU device available");
+            return;
+        };
+        let desc = TextureDescriptor::new(64, 64, TextureFormat::Rgba8Unorm)
+            .with_usage(TextureUsages::TEXTURE_BINDING);
+        let Ok(texture) = gpu.device().create_texture(&desc) else {
+            println!("Skipping - texture allocation failed");
+            return;
+        };
+        let reg = TextureRegistration::new(texture, VulkanLayout::UNDEFINED);
+
+        // Nothing published: consumers proceed immediately.
+        reg.wait_for_producer(Duration::ZERO)
+            .expect("no sync point is a no-op wait");
+
+        let timeline = Arc::new(
+            crate::vulkan::rhi::HostVulkanTimelineSemaphore::new(gpu.device().inner.device(), 0)
+                .expect("timeline"),
+        );
+        reg.set_producer_sync_point(Some(GpuSyncPoint::Timeline {
+            semaphore: Arc::clone(&timeline),
+            value: 3,
+        }));
+        assert_eq!(reg.producer_sync_point().map(|p| p.value()), Some(3));
+        assert!(reg.wait_for_producer(Duration::ZERO).is_err());
+
+        timeline.signal_host(3).expect("host signal");
+        reg.wait_for_producer(Duration::from_millis(100))
+            .expect("signalled point is reached");
+
+        reg.set_producer_sync_point(None);
+        assert!(reg.producer_sync_point().is_none());
+    }
 }
@@ -6,6 +6,7 @@
 //! Provides a single monotonic clock that starts when the runtime starts.
 //! All processors share this clock for coordinated animations and timing.
 
+use crate::core::context::reference_signal::{self, ReferenceSignalStatus};
 use crate::core::media_clock::MediaClock;
 
 /// Shared timing context for all processors.
@@ -42,6 +43,12 @@ impl TimeContext {
     pub fn now_ns(&self) -> i64 {
         MediaClock::now().as_nanos() as i64
     }
+
+    /// Latest reference-signal (genlock) status of every device that has
+    /// reported one, ordered by source. Empty when nothing is genlocked.
+    pub fn reference_signals(&self) -> Vec<ReferenceSignalStatus> {
+        reference_signal::reference_signals()
+    }
 }
 
 impl Default for TimeContext {
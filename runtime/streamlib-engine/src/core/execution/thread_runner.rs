@@ -11,12 +11,15 @@ use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(unix)]
 use std::os::fd::OwnedFd;
 
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 
 use crate::core::RuntimeContext;
 use crate::core::context::{IsolationTier, RuntimeContextFullAccess, RuntimeContextLimitedAccess};
+use crate::core::descriptors::PortDescriptor;
 use crate::core::execution::{ExecutionConfig, ProcessExecution};
-use crate::core::graph::ProcessorUniqueId;
+use crate::core::graph::{Graph, ProcessorParameterPortComponent, ProcessorUniqueId};
+use crate::core::media_clock::MediaClock;
+use crate::core::parameter_automation::ParameterTimeline;
 use crate::core::processors::{ProcessorInstance, ProcessorState};
 /// Duration to sleep when paused (avoids busy-waiting).
 const PAUSE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
@@ -26,8 +29,16 @@ const PAUSE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_mill
 /// with a working waiter uses `epoll_wait(-1)` and never sleeps.
 const NO_WAITER_FALLBACK_SLEEP: std::time::Duration = std::time::Duration::from_millis(100);
 
+/// Longest a manual processor's lifecycle thread sleeps between checks for
+/// shutdown, pause, and due parameters.
+const MANUAL_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// How often a manual processor's lifecycle thread steps a running
+/// parameter ramp.
+const MANUAL_RAMP_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
 /// Run the processor thread main loop based on execution mode.
-#[tracing::instrument(name = "processor.lifecycle", skip(processor, shutdown_rx, shutdown_eventfd, state, pause_gate, exec_config, runtime_ctx), fields(processor_id = %id, isolation_tier = isolation_tier.as_str()))]
+#[tracing::instrument(name = "processor.lifecycle", skip(processor, shutdown_rx, shutdown_eventfd, state, pause_gate, bypass_gate, bypass_routes, parameter_port, graph, exec_config, runtime_ctx), fields(processor_id = %id, isolation_tier = isolation_tier.as_str()))]
 pub fn run_processor_loop(
     id: ProcessorUniqueId,
     processor: Arc<Mutex<ProcessorInstance>>,
@@ -35,6 +46,10 @@ pub fn run_processor_loop(
     #[cfg(unix)] shutdown_eventfd: Option<OwnedFd>,
     state: Arc<Mutex<ProcessorState>>,
     pause_gate: Arc<AtomicBool>,
+    bypass_gate: Arc<AtomicBool>,
+    bypass_routes: Vec<(String, String)>,
+    parameter_port: ProcessorParameterPortComponent,
+    graph: Arc<RwLock<Graph>>,
     exec_config: ExecutionConfig,
     runtime_ctx: RuntimeContext,
     isolation_tier: IsolationTier,
@@ -45,6 +60,15 @@ pub fn run_processor_loop(
         exec_config.execution.description()
     );
 
+    let parameters = ParameterDelivery {
+        port: parameter_port.clone_inner(),
+        graph,
+    };
+    let bypass = BypassForwarding {
+        gate: bypass_gate,
+        routes: bypass_routes,
+    };
+
     match exec_config.execution {
         ProcessExecution::Continuous { interval_ms } => {
             run_continuous_mode(
@@ -52,6 +76,7 @@ pub fn run_processor_loop(
                 &processor,
                 &shutdown_rx,
                 &pause_gate,
+                &parameters,
                 interval_ms,
                 &runtime_ctx,
             );
@@ -64,6 +89,8 @@ pub fn run_processor_loop(
                 #[cfg(unix)]
                 shutdown_eventfd,
                 &pause_gate,
+                &bypass,
+                &parameters,
                 &runtime_ctx,
             );
         }
@@ -73,6 +100,7 @@ pub fn run_processor_loop(
                 &processor,
                 &shutdown_rx,
                 &pause_gate,
+                &parameters,
                 &runtime_ctx,
                 isolation_tier,
             );
@@ -115,6 +143,7 @@ fn run_continuous_mode(
     processor: &Arc<Mutex<ProcessorInstance>>,
     shutdown_rx: &crossbeam_channel::Receiver<()>,
     pause_gate: &Arc<AtomicBool>,
+    parameters: &ParameterDelivery,
     interval_ms: u32,
     runtime_ctx: &RuntimeContext,
 ) {
@@ -147,6 +176,7 @@ fn run_continuous_mode(
             continue;
         }
 
+        parameters.deliver(id, processor);
         {
             let limited_ctx = RuntimeContextLimitedAccess::new(runtime_ctx);
             let mut guard = processor.lock();
@@ -165,26 +195,32 @@ fn run_reactive_mode(
     shutdown_rx: &crossbeam_channel::Receiver<()>,
     #[cfg(unix)] shutdown_eventfd: Option<OwnedFd>,
     pause_gate: &Arc<AtomicBool>,
+    bypass: &BypassForwarding,
+    parameters: &ParameterDelivery,
     runtime_ctx: &RuntimeContext,
 ) {
-    // Reactive mode waits on two fds via epoll: the destination's iceoryx2
-    // Listener fd (any upstream Notifier::notify() wakes the loop) and the
-    // shutdown eventfd (compiler signals teardown). epoll_wait blocks
-    // indefinitely — idle CPU is truly zero until one of those fds fires.
+    // Reactive mode waits on three fds via epoll: the destination's iceoryx2
+    // Listener fd (any upstream Notifier::notify() wakes the loop), the
+    // inject-wake eventfd (a port replay pushed a frame) and the shutdown
+    // eventfd (compiler signals teardown). epoll_wait blocks indefinitely —
+    // idle CPU is truly zero until one of those fds fires.
     //
     // Processors with no Rust-side listener fd (subprocess host, audio-only,
     // etc.) fall through to the channel-poll sleep loop so process() still
     // ticks at NO_WAITER_FALLBACK_SLEEP cadence — same shape they had before.
-    let listener_fd = {
+    let (listener_fd, inject_wake_fd) = {
         let guard = processor.lock();
-        guard
-            .iceoryx2_input_mailboxes_inner()
-            .and_then(|inner| inner.listener_fd())
+        let inner = guard.iceoryx2_input_mailboxes_inner();
+        #[cfg(target_os = "linux")]
+        let inject_wake_fd = inner.as_ref().and_then(|inner| inner.inject_wake_fd());
+        #[cfg(not(target_os = "linux"))]
+        let inject_wake_fd: Option<i32> = None;
+        (inner.and_then(|inner| inner.listener_fd()), inject_wake_fd)
     };
 
     #[cfg(target_os = "linux")]
     let waiter = match listener_fd {
-        Some(fd) => match ReactiveLoopFdWaiter::new(fd, shutdown_eventfd) {
+        Some(fd) => match ReactiveLoopFdWaiter::new(fd, inject_wake_fd, shutdown_eventfd) {
             Ok(w) => Some(w),
             Err(e) => {
                 tracing::warn!(
@@ -273,7 +309,10 @@ fn run_reactive_mode(
         // shutdown signaling — without it, the outer loop's
         // shutdown_rx.try_recv at the top never fires.
         loop {
-            {
+            parameters.deliver(id, processor);
+            if bypass.is_bypassed() {
+                bypass.forward(id, processor);
+            } else {
                 let limited_ctx = RuntimeContextLimitedAccess::new(runtime_ctx);
                 let mut guard = processor.lock();
                 if let Err(e) = guard.process(&limited_ctx) {
@@ -318,8 +357,13 @@ enum ReactiveLoopWakeOutcome {
 #[cfg(target_os = "linux")]
 const SHUTDOWN_EVENTFD_TAG: u64 = u64::MAX;
 
-/// Linux-only: epoll fd watching the iceoryx2 listener fd plus an optional
-/// shutdown eventfd, used by the reactive runner.
+/// Tag stored in `epoll_event.u64` for the inject-wake eventfd. Waking on
+/// it is handled like a notify.
+#[cfg(target_os = "linux")]
+const INJECT_WAKE_TAG: u64 = 1;
+
+/// Linux-only: epoll fd watching the iceoryx2 listener fd plus optional
+/// inject-wake and shutdown eventfds, used by the reactive runner.
 #[cfg(target_os = "linux")]
 struct ReactiveLoopFdWaiter {
     epoll_fd: i32,
@@ -331,7 +375,11 @@ struct ReactiveLoopFdWaiter {
 
 #[cfg(target_os = "linux")]
 impl ReactiveLoopFdWaiter {
-    fn new(listener_fd: i32, shutdown_eventfd: Option<OwnedFd>) -> std::io::Result<Self> {
+    fn new(
+        listener_fd: i32,
+        inject_wake_fd: Option<i32>,
+        shutdown_eventfd: Option<OwnedFd>,
+    ) -> std::io::Result<Self> {
         use std::os::fd::AsRawFd;
 
         // SAFETY: epoll_create1 returns -1 on failure; checked below.
@@ -360,6 +408,12 @@ impl ReactiveLoopFdWaiter {
             unsafe { libc::close(epoll_fd) };
             return Err(e);
         }
+        if let Some(fd) = inject_wake_fd {
+            if let Err(e) = register(fd, INJECT_WAKE_TAG) {
+                unsafe { libc::close(epoll_fd) };
+                return Err(e);
+            }
+        }
         if let Some(ref efd) = shutdown_eventfd {
             if let Err(e) = register(efd.as_raw_fd(), SHUTDOWN_EVENTFD_TAG) {
                 unsafe { libc::close(epoll_fd) };
@@ -374,11 +428,13 @@ impl ReactiveLoopFdWaiter {
     }
 
     fn wait(&self) -> ReactiveLoopWakeOutcome {
-        let mut events = [libc::epoll_event { events: 0, u64: 0 }; 2];
+        let mut events = [libc::epoll_event { events: 0, u64: 0 }; 3];
         // -1 = block forever. Wakes only when one of the registered fds is
         // actually readable, or a signal interrupts the call.
         // SAFETY: epoll_wait writes up to events.len() events into the buffer.
-        let n = unsafe { libc::epoll_wait(self.epoll_fd, events.as_mut_ptr(), 2, -1) };
+        let n = unsafe {
+            libc::epoll_wait(self.epoll_fd, events.as_mut_ptr(), events.len() as i32, -1)
+        };
         if n < 0 {
             let err = std::io::Error::last_os_error();
             if err.kind() == std::io::ErrorKind::Interrupted {
@@ -421,6 +477,7 @@ fn run_manual_mode(
     processor: &Arc<Mutex<ProcessorInstance>>,
     shutdown_rx: &crossbeam_channel::Receiver<()>,
     pause_gate: &Arc<AtomicBool>,
+    parameters: &ParameterDelivery,
     runtime_ctx: &RuntimeContext,
     isolation_tier: IsolationTier,
 ) {
@@ -475,7 +532,10 @@ fn run_manual_mode(
             was_paused = false;
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        // Manual processors have no process() tick, so scheduled
+        // parameters are delivered from here, waking early when one is due.
+        parameters.deliver(id, processor);
+        std::thread::sleep(parameters.until_next_due(MANUAL_CHECK_INTERVAL));
     }
 
     // Call stop() - stops callbacks and waits for in-flight work. Privileged
@@ -502,6 +562,137 @@ fn run_manual_mode(
     }
 }
 
+/// Pair each input port with the first unclaimed output port carrying the
+/// same schema, in declaration order — the routes a bypassed processor's
+/// frames take. Inputs without a matching output are left out and their
+/// frames are dropped while bypassed.
+pub(crate) fn bypass_routes(
+    inputs: &[PortDescriptor],
+    outputs: &[PortDescriptor],
+) -> Vec<(String, String)> {
+    let mut claimed = vec![false; outputs.len()];
+    let mut routes = Vec::new();
+    for input in inputs {
+        let matching = outputs
+            .iter()
+            .enumerate()
+            .find(|(index, output)| !claimed[*index] && output.schema == input.schema);
+        if let Some((index, output)) = matching {
+            claimed[index] = true;
+            routes.push((input.name.clone(), output.name.clone()));
+        }
+    }
+    routes
+}
+
+/// The processor's bypass gate, and the input → output routes its frames
+/// take while the gate is set.
+struct BypassForwarding {
+    gate: Arc<AtomicBool>,
+    routes: Vec<(String, String)>,
+}
+
+impl BypassForwarding {
+    fn is_bypassed(&self) -> bool {
+        self.gate.load(Ordering::Acquire)
+    }
+
+    /// Stand in for `process()`: move every queued input frame, bytes and
+    /// timestamp untouched, onto its routed output. Frames on unrouted
+    /// inputs are drained so the drain loop still terminates.
+    fn forward(&self, id: &ProcessorUniqueId, processor: &Arc<Mutex<ProcessorInstance>>) {
+        let guard = processor.lock();
+        let Some(inputs) = guard.iceoryx2_input_mailboxes_inner() else {
+            return;
+        };
+        let outputs = guard.iceoryx2_output_writer_inner();
+        for port in inputs.port_names() {
+            let route = self
+                .routes
+                .iter()
+                .find(|(input, _)| *input == port)
+                .map(|(_, output)| output.as_str())
+                .filter(|output| outputs.as_ref().is_some_and(|o| o.has_port(output)));
+            loop {
+                let (data, timestamp_ns) = match inputs.read_raw(&port) {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!("[{}] Bypass read of '{}' failed: {}", id, port, e);
+                        break;
+                    }
+                };
+                if let (Some(output), Some(writer)) = (route, outputs.as_ref())
+                    && let Err(e) = writer.write_raw(output, &data, timestamp_ns)
+                {
+                    tracing::warn!(
+                        "[{}] Bypass forward '{}' -> '{}' failed: {}",
+                        id,
+                        port,
+                        output,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The processor's parameter port, and the graph whose node config mirrors
+/// what it delivers.
+struct ParameterDelivery {
+    port: Arc<Mutex<ParameterTimeline>>,
+    graph: Arc<RwLock<Graph>>,
+}
+
+impl ParameterDelivery {
+    /// Apply the scheduled parameters due at the current media-clock time.
+    /// Runs on the processor thread right before `process()`, so a change
+    /// lands on the first tick at or after its scheduled time.
+    fn deliver(&self, id: &ProcessorUniqueId, processor: &Arc<Mutex<ProcessorInstance>>) {
+        let due = self.port.lock().due(media_clock_ns());
+        if let Some(config) = due
+            && let Err(e) = processor.lock().apply_config_json(&config)
+        {
+            tracing::warn!("[{}] Scheduled parameter update failed: {}", id, e);
+        }
+
+        // Mirror into the graph node so readers see the live config. Never
+        // block the processor thread on the graph lock — retry next tick.
+        if self.port.lock().unsynced_config().is_none() {
+            return;
+        }
+        let Some(mut graph) = self.graph.try_write() else {
+            return;
+        };
+        let mut port = self.port.lock();
+        let Some(config) = port.unsynced_config() else {
+            return;
+        };
+        if let Some(node) = graph.traversal_mut().v(id).first_mut() {
+            node.set_config(config.clone());
+        }
+        port.mark_synced();
+    }
+
+    /// How long to sleep, at most `max`, before the next delivery is due.
+    /// A running ramp is stepped every [`MANUAL_RAMP_INTERVAL`].
+    fn until_next_due(&self, max: std::time::Duration) -> std::time::Duration {
+        let Some(due_ns) = self.port.lock().next_due_ns() else {
+            return max;
+        };
+        let wait_ns = due_ns.saturating_sub(media_clock_ns());
+        if wait_ns <= 0 {
+            return max.min(MANUAL_RAMP_INTERVAL);
+        }
+        max.min(std::time::Duration::from_nanos(wait_ns as u64))
+    }
+}
+
+fn media_clock_ns() -> i64 {
+    MediaClock::now().as_nanos() as i64
+}
+
 // Helper dispatchers for on_pause / on_resume — shared across Continuous,
 // Reactive, and Manual modes. Each builds a fresh RuntimeContextLimitedAccess
 // for the call. Keeping these tiny avoids duplicating the tokio-block-on +
@@ -602,8 +793,8 @@ mod tests {
         // only while listener stays alive (listener outlives the waiter
         // thread because we join it before this function returns).
         let listener_fd = unsafe { listener.file_descriptor().native_handle() };
-        let waiter =
-            ReactiveLoopFdWaiter::new(listener_fd, Some(make_eventfd())).expect("epoll setup");
+        let waiter = ReactiveLoopFdWaiter::new(listener_fd, None, Some(make_eventfd()))
+            .expect("epoll setup");
 
         // Move the waiter to a worker thread, then fire notify() from this
         // thread. The worker reports the outcome and elapsed time back via
@@ -667,8 +858,8 @@ mod tests {
         let shutdown_eventfd = make_eventfd();
         let shutdown_raw = shutdown_eventfd.as_raw_fd();
 
-        let waiter =
-            ReactiveLoopFdWaiter::new(listener_fd, Some(shutdown_eventfd)).expect("epoll setup");
+        let waiter = ReactiveLoopFdWaiter::new(listener_fd, None, Some(shutdown_eventfd))
+            .expect("epoll setup");
 
         let (tx, rx) = std::sync::mpsc::channel();
         let worker = std::thread::spawn(move || {
@@ -698,4 +889,85 @@ mod tests {
             elapsed
         );
     }
+
+    /// A frame injected by a port replay signals the inject-wake eventfd,
+    /// which must wake `wait` as a notify does — the processor's upstream
+    /// may not be running at all.
+    #[test]
+    fn reactive_loop_wakes_on_injected_frame() {
+        let node = NodeBuilder::new().create::<ipc::Service>().unwrap();
+        let name = unique_suffix("inject");
+        let svc = node
+            .service_builder(&ServiceName::new(&name).unwrap())
+            .event()
+            .max_notifiers(1)
+            .max_listeners(1)
+            .open_or_create()
+            .unwrap();
+        let listener = svc.listener_builder().create().unwrap();
+        // SAFETY: listener outlives the worker thread (joined below).
+        let listener_fd = unsafe { listener.file_descriptor().native_handle() };
+
+        let inject_eventfd = make_eventfd();
+        let inject_raw = inject_eventfd.as_raw_fd();
+
+        let waiter = ReactiveLoopFdWaiter::new(listener_fd, Some(inject_raw), Some(make_eventfd()))
+            .expect("epoll setup");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let worker = std::thread::spawn(move || {
+            let outcome = waiter.wait();
+            tx.send(outcome).unwrap();
+            waiter
+        });
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        write_eventfd(inject_raw);
+
+        let outcome = rx
+            .recv_timeout(std::time::Duration::from_millis(800))
+            .expect("worker did not respond — the injection did not wake the waiter");
+        let _waiter = worker.join().expect("worker panicked");
+
+        assert!(
+            matches!(outcome, ReactiveLoopWakeOutcome::Notified),
+            "expected Notified, got {:?}",
+            outcome
+        );
+    }
+
+    #[test]
+    fn bypass_routes_pair_inputs_with_same_schema_outputs() {
+        use crate::core::descriptors::{
+            Org, Package, PortSchemaSpec, SchemaIdent, SemVer, TypeName,
+        };
+        let schema = |name: &str| {
+            PortSchemaSpec::Specific(SchemaIdent::new(
+                Org::new("tatolab").unwrap(),
+                Package::new("core").unwrap(),
+                TypeName::new(name).unwrap(),
+                SemVer::new(1, 0, 0),
+            ))
+        };
+        let port =
+            |name: &str, type_name: &str| PortDescriptor::new(name, "", schema(type_name), true);
+        let inputs = [
+            port("video_in", "VideoFrame"),
+            port("key_in", "VideoFrame"),
+            port("audio_in", "AudioFrame"),
+            port("caption_in", "Caption"),
+        ];
+        let outputs = [
+            port("audio_out", "AudioFrame"),
+            port("video_out", "VideoFrame"),
+        ];
+
+        assert_eq!(
+            bypass_routes(&inputs, &outputs),
+            vec![
+                ("video_in".to_string(), "video_out".to_string()),
+                ("audio_in".to_string(), "audio_out".to_string()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,316 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Glyph atlas — rasterized glyphs packed into one R8 coverage image.
+
+use std::collections::HashMap;
+
+use ab_glyph::{Font, FontVec, GlyphId, PxScale, ScaleFont, point};
+
+use super::registry::FontFace;
+use crate::core::{Error, Result};
+
+const INITIAL_WIDTH: u32 = 512;
+const INITIAL_HEIGHT: u32 = 256;
+const MAX_HEIGHT: u32 = 4096;
+/// Empty texels around each glyph so bilinear sampling never bleeds
+/// into a neighbour.
+const PADDING: u32 = 1;
+
+/// Where a glyph sits in the atlas and how to place it on a line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasGlyph {
+    /// Top-left texel of the glyph's coverage rectangle.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Offset from the pen position to the rectangle's left edge.
+    pub bearing_x: f32,
+    /// Distance from the baseline up to the rectangle's top edge.
+    pub bearing_y: f32,
+    /// Pen advance after this glyph.
+    pub advance: f32,
+    /// Index into the fallback chain of the face that supplied the glyph.
+    pub face: usize,
+}
+
+/// Vertical metrics of the chain's primary face, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl LineMetrics {
+    /// Baseline-to-baseline distance.
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+}
+
+/// Rasterized glyphs for one fallback chain at one pixel size.
+///
+/// Glyphs are rasterized on first [`Self::glyph`] call and packed into
+/// shelves. The atlas grows in height up to 4096 texels; past that it is
+/// cleared and [`Self::generation`] bumps, which tells renderers holding
+/// uploaded copies or cached [`AtlasGlyph`]s to start over.
+pub struct GlyphAtlas {
+    faces: Vec<FontVec>,
+    scale: PxScale,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    glyphs: HashMap<char, Option<AtlasGlyph>>,
+    packer: ShelfPacker,
+    generation: u64,
+}
+
+impl GlyphAtlas {
+    /// Load every face of `chain` and start an empty atlas at `px_size`.
+    /// Faces that fail to parse are skipped; an error is returned only
+    /// when none load.
+    pub fn new(chain: &[FontFace], px_size: f32) -> Result<Self> {
+        let faces: Vec<FontVec> = chain
+            .iter()
+            .filter_map(|face| {
+                let data = match std::fs::read(&face.path) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        tracing::warn!("[fonts] Cannot read {}: {}", face.path.display(), e);
+                        return None;
+                    }
+                };
+                match FontVec::try_from_vec_and_index(data, face.face_index) {
+                    Ok(font) => Some(font),
+                    Err(e) => {
+                        tracing::warn!("[fonts] Cannot parse {}: {}", face.path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        if faces.is_empty() {
+            return Err(Error::FontNotFound(format!(
+                "none of {} fallback face(s) could be loaded",
+                chain.len()
+            )));
+        }
+        Ok(Self {
+            faces,
+            scale: PxScale::from(px_size),
+            width: INITIAL_WIDTH,
+            height: INITIAL_HEIGHT,
+            pixels: vec![0; (INITIAL_WIDTH * INITIAL_HEIGHT) as usize],
+            glyphs: HashMap::new(),
+            packer: ShelfPacker::new(INITIAL_WIDTH, INITIAL_HEIGHT),
+            generation: 0,
+        })
+    }
+
+    /// The glyph for `c`, rasterizing it on first use from the first face in
+    /// the chain that covers it. `None` when no face does.
+    pub fn glyph(&mut self, c: char) -> Option<AtlasGlyph> {
+        if let Some(cached) = self.glyphs.get(&c) {
+            return *cached;
+        }
+        let glyph = self.rasterize(c);
+        self.glyphs.insert(c, glyph);
+        glyph
+    }
+
+    /// Vertical metrics of the primary face.
+    pub fn line_metrics(&self) -> LineMetrics {
+        let font = self.faces[0].as_scaled(self.scale);
+        LineMetrics {
+            ascent: font.ascent(),
+            descent: font.descent(),
+            line_gap: font.line_gap(),
+        }
+    }
+
+    /// Kerning between two glyphs from the same face, 0 across faces.
+    pub fn kern(
+        &self,
+        left: &AtlasGlyph,
+        left_char: char,
+        right: &AtlasGlyph,
+        right_char: char,
+    ) -> f32 {
+        if left.face != right.face {
+            return 0.0;
+        }
+        let font = self.faces[left.face].as_scaled(self.scale);
+        font.kern(font.glyph_id(left_char), font.glyph_id(right_char))
+    }
+
+    /// R8 coverage, row-major, `width() * height()` bytes.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Bumped whenever existing glyph placements are invalidated.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn rasterize(&mut self, c: char) -> Option<AtlasGlyph> {
+        let (face, id) = self
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(index, font)| (index, font.glyph_id(c)))
+            .find(|(_, id)| *id != GlyphId(0))?;
+        let font = self.faces[face].as_scaled(self.scale);
+        let advance = font.h_advance(id);
+        let Some(outline) =
+            font.outline_glyph(id.with_scale_and_position(self.scale, point(0.0, 0.0)))
+        else {
+            // Whitespace — advances the pen, occupies no texels.
+            return Some(AtlasGlyph {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+                bearing_x: 0.0,
+                bearing_y: 0.0,
+                advance,
+                face,
+            });
+        };
+        let bounds = outline.px_bounds();
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        let (x, y) = self.allocate(width, height)?;
+        let atlas_width = self.width;
+        let pixels = &mut self.pixels;
+        outline.draw(|gx, gy, coverage| {
+            let index = ((y + gy) * atlas_width + x + gx) as usize;
+            pixels[index] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+        Some(AtlasGlyph {
+            x,
+            y,
+            width,
+            height,
+            bearing_x: bounds.min.x,
+            bearing_y: -bounds.min.y,
+            advance,
+            face,
+        })
+    }
+
+    /// Find room for a `width × height` glyph, growing or — once at
+    /// [`MAX_HEIGHT`] — clearing the atlas.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        loop {
+            if let Some(slot) = self.packer.pack(width + PADDING, height + PADDING) {
+                return Some(slot);
+            }
+            if self.height < MAX_HEIGHT {
+                self.height = (self.height * 2).min(MAX_HEIGHT);
+                self.pixels.resize((self.width * self.height) as usize, 0);
+                self.packer.height = self.height;
+            } else if self.glyphs.is_empty() {
+                // Glyph larger than an empty atlas — nothing to evict.
+                tracing::warn!(
+                    "[fonts] {}x{} glyph does not fit a {}x{} atlas",
+                    width,
+                    height,
+                    self.width,
+                    self.height
+                );
+                return None;
+            } else {
+                self.pixels.fill(0);
+                self.glyphs.clear();
+                self.packer = ShelfPacker::new(self.width, self.height);
+                self.generation += 1;
+            }
+        }
+    }
+}
+
+/// Rows ("shelves") filled left to right; a new shelf opens below the
+/// tallest item of the last one.
+struct ShelfPacker {
+    width: u32,
+    height: u32,
+    shelf_y: u32,
+    shelf_height: u32,
+    cursor_x: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelf_y: 0,
+            shelf_height: 0,
+            cursor_x: 0,
+        }
+    }
+
+    fn pack(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width > self.width {
+            return None;
+        }
+        if self.cursor_x + width > self.width {
+            self.shelf_y += self.shelf_height;
+            self.shelf_height = 0;
+            self.cursor_x = 0;
+        }
+        if self.shelf_y + height > self.height {
+            return None;
+        }
+        let slot = (self.cursor_x, self.shelf_y);
+        self.cursor_x += width;
+        self.shelf_height = self.shelf_height.max(height);
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shelf_packer_fills_rows_then_opens_new_shelves() {
+        let mut packer = ShelfPacker::new(10, 10);
+
+        assert_eq!(packer.pack(4, 3), Some((0, 0)));
+        assert_eq!(packer.pack(4, 5), Some((4, 0)));
+        assert_eq!(
+            packer.pack(4, 2),
+            Some((0, 5)),
+            "row full; next shelf sits under the tallest item"
+        );
+        assert_eq!(packer.pack(11, 1), None, "wider than the atlas");
+        assert_eq!(packer.pack(2, 6), None, "taller than the remaining space");
+        packer.height = 20;
+        assert_eq!(packer.pack(2, 6), Some((4, 5)));
+    }
+
+    #[test]
+    fn missing_chain_is_font_not_found() {
+        let chain = [FontFace {
+            path: "/nonexistent/font.ttf".into(),
+            face_index: 0,
+        }];
+
+        assert!(matches!(
+            GlyphAtlas::new(&chain, 24.0),
+            Err(Error::FontNotFound(_))
+        ));
+    }
+}
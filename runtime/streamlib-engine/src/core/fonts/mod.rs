@@ -0,0 +1,21 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Font management for text-rendering processors (overlays, subtitles,
+//! slates).
+//!
+//! Configs name fonts logically — `"sans"`, `"mono"`, `"title"` — and the
+//! runtime's [`FontRegistry`] resolves a name to a fallback chain of font
+//! files. Chains draw on registered files, families installed in the
+//! project's `.streamlib/fonts/` or the machine's font directories, and URLs
+//! fetched through the asset cache, so the same graph renders with the same
+//! fonts on every machine that ships the same files.
+//!
+//! [`GlyphAtlas`]es are cached per `(name, pixel size)` on the registry and
+//! shared by every processor that asks for the same pair.
+
+mod atlas;
+mod registry;
+
+pub use atlas::{AtlasGlyph, GlyphAtlas, LineMetrics};
+pub use registry::{FONT_DIRS_ENV, FontFace, FontRegistry, FontSource};
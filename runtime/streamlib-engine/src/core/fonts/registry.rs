@@ -0,0 +1,570 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Logical font names → fallback chains of font files.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::{Mutex, RwLock};
+
+use super::atlas::GlyphAtlas;
+use crate::core::assets::AssetManager;
+use crate::core::streamlib_home::get_streamlib_data_dir;
+use crate::core::{Error, Result};
+
+/// Path-list (`:`-separated, `;` on Windows) of extra directories scanned
+/// for installed families, ahead of the platform font directories.
+pub const FONT_DIRS_ENV: &str = "STREAMLIB_FONT_DIRS";
+
+/// Aliases are followed this deep before resolution gives up on a cycle.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// Font directories are walked this deep.
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Chains every registry starts with. Family candidates cover the usual
+/// Linux, macOS, and Windows installs; the first one present wins, so a
+/// project pins its look by dropping a font into `.streamlib/fonts/`.
+const DEFAULT_CHAINS: &[(&str, &[&str])] = &[
+    (
+        "sans",
+        &[
+            "Inter",
+            "Noto Sans",
+            "DejaVu Sans",
+            "Liberation Sans",
+            "Helvetica Neue",
+            "Helvetica",
+            "Arial",
+            "Segoe UI",
+        ],
+    ),
+    (
+        "serif",
+        &[
+            "Noto Serif",
+            "DejaVu Serif",
+            "Liberation Serif",
+            "Times New Roman",
+            "Times",
+        ],
+    ),
+    (
+        "mono",
+        &[
+            "JetBrains Mono",
+            "Noto Sans Mono",
+            "DejaVu Sans Mono",
+            "Liberation Mono",
+            "Menlo",
+            "Consolas",
+            "Courier New",
+        ],
+    ),
+    (
+        "emoji",
+        &["Noto Color Emoji", "Apple Color Emoji", "Segoe UI Emoji"],
+    ),
+];
+
+/// CSS-style names accepted for the default chains.
+const DEFAULT_ALIASES: &[(&str, &str)] = &[
+    ("default", "sans"),
+    ("sans-serif", "sans"),
+    ("monospace", "mono"),
+];
+
+/// One entry in a logical name's chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontSource {
+    /// A font file on disk. `face_index` selects a face in a `.ttc` / `.otc`
+    /// collection (0 otherwise).
+    File { path: PathBuf, face_index: u32 },
+    /// An installed family (or full face name, e.g. `"DejaVu Sans Bold"`),
+    /// looked up in the scanned font directories.
+    Family(String),
+    /// A font file fetched through the runtime's [`AssetManager`], optionally
+    /// pinned to a SHA-256 digest.
+    Url { url: String, sha256: Option<String> },
+    /// Another logical name, spliced in at this position.
+    Alias(String),
+}
+
+/// A resolved face — what a rasterizer opens.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FontFace {
+    pub path: PathBuf,
+    pub face_index: u32,
+}
+
+/// Installed faces keyed by lowercased family and full name.
+type InstalledIndex = HashMap<String, FontFace>;
+
+/// Shared atlases keyed by `(logical name, pixel size)`.
+type AtlasCache = HashMap<(String, u32), Arc<Mutex<GlyphAtlas>>>;
+
+/// Resolves logical font names to fallback chains and caches glyph atlases
+/// per `(name, pixel size)`. One per runtime, reached from processors as
+/// `ctx.fonts()`.
+pub struct FontRegistry {
+    assets: Arc<AssetManager>,
+    font_dirs: Vec<PathBuf>,
+    chains: RwLock<HashMap<String, Vec<FontSource>>>,
+    /// Scanned lazily on the first family lookup; cleared by [`Self::rescan`].
+    installed: RwLock<Option<Arc<InstalledIndex>>>,
+    atlases: Mutex<AtlasCache>,
+}
+
+impl FontRegistry {
+    /// A registry scanning `font_dirs` (first directory wins for a family)
+    /// and fetching URL sources through `assets`. Starts with the default
+    /// `sans` / `serif` / `mono` / `emoji` chains.
+    pub fn new(assets: Arc<AssetManager>, font_dirs: Vec<PathBuf>) -> Self {
+        let mut chains: HashMap<String, Vec<FontSource>> = DEFAULT_CHAINS
+            .iter()
+            .map(|(name, families)| {
+                let chain = families
+                    .iter()
+                    .map(|family| FontSource::Family((*family).to_string()))
+                    .collect();
+                ((*name).to_string(), chain)
+            })
+            .collect();
+        for (alias, target) in DEFAULT_ALIASES {
+            chains.insert(
+                (*alias).to_string(),
+                vec![FontSource::Alias((*target).to_string())],
+            );
+        }
+        Self {
+            assets,
+            font_dirs,
+            chains: RwLock::new(chains),
+            installed: RwLock::new(None),
+            atlases: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The runtime's registry: `.streamlib/fonts/` first, then
+    /// [`FONT_DIRS_ENV`], then the platform font directories.
+    pub fn from_environment(assets: Arc<AssetManager>) -> Self {
+        let mut dirs = vec![get_streamlib_data_dir().join("fonts")];
+        if let Some(extra) = std::env::var_os(FONT_DIRS_ENV) {
+            dirs.extend(std::env::split_paths(&extra));
+        }
+        dirs.extend(platform_font_dirs());
+        Self::new(assets, dirs)
+    }
+
+    /// Directories scanned for installed families, in priority order.
+    pub fn font_dirs(&self) -> &[PathBuf] {
+        &self.font_dirs
+    }
+
+    /// Define (or replace) logical `name` as `chain`. Cached atlases for the
+    /// name are dropped; processors holding one keep their copy.
+    pub fn register(&self, name: impl Into<String>, chain: Vec<FontSource>) {
+        let name = name.into();
+        self.atlases
+            .lock()
+            .retain(|(atlas_name, _), _| *atlas_name != name);
+        self.chains.write().insert(name, chain);
+    }
+
+    /// Logical names currently defined, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.chains.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Forget the installed-font index; the next family lookup rescans.
+    pub fn rescan(&self) {
+        *self.installed.write() = None;
+    }
+
+    /// The fallback chain for `name`: every source that resolves, in chain
+    /// order, without duplicates. An unregistered name is looked up as an
+    /// installed family, so `"DejaVu Sans"` works without registration.
+    /// Fails with [`Error::FontNotFound`] when nothing resolves.
+    pub fn resolve(&self, name: &str) -> Result<Vec<FontFace>> {
+        let mut faces = Vec::new();
+        let mut visiting = HashSet::new();
+        self.resolve_into(name, 0, &mut visiting, &mut faces);
+        if faces.is_empty() {
+            return Err(Error::FontNotFound(name.to_string()));
+        }
+        Ok(faces)
+    }
+
+    /// The shared glyph atlas for `name` at `px_size` pixels, built on first
+    /// request from the name's fallback chain.
+    pub fn atlas(&self, name: &str, px_size: u32) -> Result<Arc<Mutex<GlyphAtlas>>> {
+        let key = (name.to_string(), px_size);
+        if let Some(atlas) = self.atlases.lock().get(&key) {
+            return Ok(Arc::clone(atlas));
+        }
+        // Built outside the lock — parsing a chain's fonts is slow and a
+        // second builder for the same key only costs a discarded atlas.
+        let atlas = Arc::new(Mutex::new(GlyphAtlas::new(
+            &self.resolve(name)?,
+            px_size as f32,
+        )?));
+        Ok(Arc::clone(self.atlases.lock().entry(key).or_insert(atlas)))
+    }
+
+    fn resolve_into(
+        &self,
+        name: &str,
+        depth: usize,
+        visiting: &mut HashSet<String>,
+        faces: &mut Vec<FontFace>,
+    ) {
+        if depth > MAX_ALIAS_DEPTH || !visiting.insert(name.to_string()) {
+            tracing::warn!("[fonts] Alias cycle through '{}' ignored", name);
+            return;
+        }
+        let chain = self.chains.read().get(name).cloned();
+        match chain {
+            Some(chain) => {
+                for source in chain {
+                    match source {
+                        FontSource::Alias(target) => {
+                            self.resolve_into(&target, depth + 1, visiting, faces)
+                        }
+                        source => {
+                            if let Some(face) = self.resolve_source(&source)
+                                && !faces.contains(&face)
+                            {
+                                faces.push(face);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                if let Some(face) = self.installed_face(name)
+                    && !faces.contains(&face)
+                {
+                    faces.push(face);
+                }
+            }
+        }
+        visiting.remove(name);
+    }
+
+    fn resolve_source(&self, source: &FontSource) -> Option<FontFace> {
+        match source {
+            FontSource::File { path, face_index } => path.is_file().then(|| FontFace {
+                path: path.clone(),
+                face_index: *face_index,
+            }),
+            FontSource::Family(family) => self.installed_face(family),
+            FontSource::Url { url, sha256 } => {
+                let fetched = match sha256 {
+                    Some(digest) => self.assets.fetch_pinned(url, digest),
+                    None => self.assets.fetch(url),
+                };
+                match fetched {
+                    Ok(path) => Some(FontFace {
+                        path,
+                        face_index: 0,
+                    }),
+                    Err(e) => {
+                        tracing::warn!("[fonts] Skipping {} in fallback chain: {}", url, e);
+                        None
+                    }
+                }
+            }
+            FontSource::Alias(_) => None,
+        }
+    }
+
+    fn installed_face(&self, family: &str) -> Option<FontFace> {
+        let index = self.installed_index();
+        index.get(&family.to_lowercase()).cloned()
+    }
+
+    fn installed_index(&self) -> Arc<InstalledIndex> {
+        if let Some(index) = self.installed.read().as_ref() {
+            return Arc::clone(index);
+        }
+        let mut installed = self.installed.write();
+        if let Some(index) = installed.as_ref() {
+            return Arc::clone(index);
+        }
+        let index = Arc::new(scan_font_dirs(&self.font_dirs));
+        tracing::debug!(
+            "[fonts] Indexed {} installed face names across {} directories",
+            index.len(),
+            self.font_dirs.len()
+        );
+        *installed = Some(Arc::clone(&index));
+        index
+    }
+}
+
+/// Platform font directories, user before system.
+fn platform_font_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::font_dir().into_iter().collect();
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(home) = dirs::home_dir() {
+            dirs.push(home.join(".fonts"));
+        }
+        dirs.push(PathBuf::from("/usr/local/share/fonts"));
+        dirs.push(PathBuf::from("/usr/share/fonts"));
+    }
+    #[cfg(target_os = "macos")]
+    {
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/System/Library/Fonts/Supplemental"));
+    }
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(windir) = std::env::var_os("WINDIR") {
+            dirs.push(PathBuf::from(windir).join("Fonts"));
+        }
+        if let Some(local) = dirs::data_local_dir() {
+            dirs.push(local.join("Microsoft").join("Windows").join("Fonts"));
+        }
+    }
+    dirs
+}
+
+/// Index every face under `dirs` by family and full name. Earlier
+/// directories win; within a family, a regular face beats bold / italic
+/// ones so `"DejaVu Sans"` maps to the upright weight.
+fn scan_font_dirs(dirs: &[PathBuf]) -> InstalledIndex {
+    let mut index: HashMap<String, (FontFace, bool)> = HashMap::new();
+    for dir in dirs {
+        let mut files = Vec::new();
+        collect_font_files(dir, 0, &mut files);
+        files.sort();
+        let mut dir_index: HashMap<String, (FontFace, bool)> = HashMap::new();
+        for path in files {
+            index_font_file(&path, &mut dir_index);
+        }
+        for (name, entry) in dir_index {
+            index.entry(name).or_insert(entry);
+        }
+    }
+    index
+        .into_iter()
+        .map(|(name, (face, _))| (name, face))
+        .collect()
+}
+
+fn collect_font_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_font_files(&path, depth + 1, files);
+        } else if is_font_file(&path) {
+            files.push(path);
+        }
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            matches!(
+                ext.to_ascii_lowercase().as_str(),
+                "ttf" | "otf" | "ttc" | "otc"
+            )
+        })
+}
+
+fn index_font_file(path: &Path, index: &mut HashMap<String, (FontFace, bool)>) {
+    let Ok(data) = std::fs::read(path) else {
+        return;
+    };
+    let face_count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+    for face_index in 0..face_count {
+        let Ok(face) = ttf_parser::Face::parse(&data, face_index) else {
+            continue;
+        };
+        let regular = face.is_regular();
+        let font_face = FontFace {
+            path: path.to_path_buf(),
+            face_index,
+        };
+        for record in face.names() {
+            let is_family = matches!(
+                record.name_id,
+                ttf_parser::name_id::FAMILY | ttf_parser::name_id::TYPOGRAPHIC_FAMILY
+            );
+            if !is_family && record.name_id != ttf_parser::name_id::FULL_NAME {
+                continue;
+            }
+            let Some(name) = record.to_string() else {
+                continue;
+            };
+            let key = name.to_lowercase();
+            // A full name picks exactly this face; a family name prefers
+            // the regular face over whichever style sorted first.
+            let preferred = !is_family || regular;
+            match index.get(&key) {
+                Some((_, existing_preferred)) if *existing_preferred || !preferred => {}
+                _ => {
+                    index.insert(key, (font_face.clone(), preferred));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(cache: &Path) -> FontRegistry {
+        FontRegistry::new(Arc::new(AssetManager::new(cache.join("assets"))), vec![])
+    }
+
+    fn file_source(path: &Path) -> FontSource {
+        FontSource::File {
+            path: path.to_path_buf(),
+            face_index: 0,
+        }
+    }
+
+    #[test]
+    fn chains_skip_missing_sources_and_splice_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let title = dir.path().join("title.otf");
+        let body = dir.path().join("body.ttf");
+        std::fs::write(&title, b"otf").unwrap();
+        std::fs::write(&body, b"ttf").unwrap();
+        let fonts = registry(dir.path());
+        fonts.register("body", vec![file_source(&body)]);
+        fonts.register(
+            "title",
+            vec![
+                file_source(&dir.path().join("missing.ttf")),
+                file_source(&title),
+                FontSource::Alias("body".to_string()),
+                file_source(&title),
+            ],
+        );
+
+        let chain = fonts.resolve("title").unwrap();
+
+        assert_eq!(
+            chain.iter().map(|f| f.path.clone()).collect::<Vec<_>>(),
+            vec![title, body],
+            "missing files are skipped, aliases spliced in place, duplicates dropped"
+        );
+    }
+
+    #[test]
+    fn alias_cycles_terminate() {
+        let dir = tempfile::tempdir().unwrap();
+        let font = dir.path().join("a.ttf");
+        std::fs::write(&font, b"ttf").unwrap();
+        let fonts = registry(dir.path());
+        fonts.register(
+            "a",
+            vec![file_source(&font), FontSource::Alias("b".to_string())],
+        );
+        fonts.register("b", vec![FontSource::Alias("a".to_string())]);
+
+        assert_eq!(fonts.resolve("b").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unresolvable_names_are_font_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let fonts = registry(dir.path());
+
+        assert!(matches!(
+            fonts.resolve("No Such Family"),
+            Err(Error::FontNotFound(name)) if name == "No Such Family"
+        ));
+        assert!(fonts.names().contains(&"monospace".to_string()));
+    }
+
+    #[test]
+    fn url_sources_resolve_through_the_asset_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("brand.ttf");
+        std::fs::write(&source, b"brand font bytes").unwrap();
+        let fonts = registry(dir.path());
+        fonts.register(
+            "brand",
+            vec![FontSource::Url {
+                url: format!("file://{}", source.display()),
+                sha256: None,
+            }],
+        );
+
+        let chain = fonts.resolve("brand").unwrap();
+
+        assert!(chain[0].path.starts_with(dir.path().join("assets")));
+        assert_eq!(chain[0].path.file_name().unwrap(), "brand.ttf");
+    }
+
+    #[test]
+    fn installed_families_resolve_from_scanned_directories() {
+        // Any TrueType font the host has; the test needs real font tables.
+        let Some(system_font) = [
+            "/usr/share/fonts",
+            "/System/Library/Fonts",
+            "C:\\Windows\\Fonts",
+        ]
+        .iter()
+        .flat_map(|dir| {
+            let mut files = Vec::new();
+            collect_font_files(Path::new(dir), 0, &mut files);
+            files
+        })
+        .find(|path| path.extension().is_some_and(|ext| ext == "ttf")) else {
+            tracing::warn!("no system TrueType font found; skipping");
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let pinned = dir.path().join("fonts");
+        std::fs::create_dir_all(&pinned).unwrap();
+        std::fs::copy(&system_font, pinned.join("pinned.ttf")).unwrap();
+
+        let data = std::fs::read(&system_font).unwrap();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+        let family = face
+            .names()
+            .into_iter()
+            .filter(|n| n.name_id == ttf_parser::name_id::FAMILY)
+            .find_map(|n| n.to_string())
+            .expect("font has a Unicode family name");
+
+        let fonts = FontRegistry::new(
+            Arc::new(AssetManager::new(dir.path().join("assets"))),
+            vec![pinned.clone(), system_font.parent().unwrap().to_path_buf()],
+        );
+        let chain = fonts.resolve(&family.to_uppercase()).unwrap();
+
+        assert_eq!(
+            chain[0].path,
+            pinned.join("pinned.ttf"),
+            "the first scanned directory wins for a family"
+        );
+
+        let atlas = fonts.atlas(&family, 24).unwrap();
+        assert!(
+            Arc::ptr_eq(&atlas, &fonts.atlas(&family, 24).unwrap()),
+            "one atlas per (name, size), shared by every caller"
+        );
+        let glyph = atlas.lock().glyph('A').expect("'A' is covered");
+        assert!(glyph.width > 0 && glyph.height > 0 && glyph.advance > 0.0);
+    }
+}
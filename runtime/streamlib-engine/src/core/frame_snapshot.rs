@@ -0,0 +1,465 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Frame snapshots: the next video frame through an output port, written
+//! to a PNG file — and port previews, a low-rate stream of downscaled
+//! JPEG / WebP thumbnails of the same frames.
+//!
+//! [`Runner::snapshot_port`](crate::core::runtime::Runner::snapshot_port)
+//! attaches a one-bag tap to the port's channel, reads the frame's
+//! surface back to the host and encodes it. It works on any wired video
+//! output, whatever the link's destination, because the tap is a
+//! read-only subscriber on the channel rather than a graph edit.
+//! [`Runner::preview_port`](crate::core::runtime::Runner::preview_port)
+//! keeps that tap attached and reads back one frame every `1 / fps`
+//! seconds, skipping the rest.
+//!
+//! The engine carries no wire schemas, so a tapped bag is decoded only as
+//! far as the surface reference every `@tatolab/core/VideoFrame` carries
+//! (`surface_id`, `width`, `height`, `timestamp_ns`, `texture_layout`);
+//! the rest of the frame is ignored.
+
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::rhi::TextureFormat;
+use crate::core::{Error, Result};
+use crate::iceoryx2::{FRAME_HEADER_SIZE, FrameHeader};
+
+/// What a snapshot captured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FrameSnapshot {
+    /// The PNG file written.
+    #[schema(value_type = String)]
+    pub path: PathBuf,
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// The captured frame's `timestamp_ns`.
+    pub timestamp_ns: i64,
+    /// Size of the written file in bytes.
+    pub bytes: u64,
+}
+
+/// Encoding of a preview thumbnail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    #[default]
+    Jpeg,
+    /// Lossless WebP: exact, but several times the size of a JPEG.
+    Webp,
+}
+
+impl PreviewFormat {
+    /// The thumbnail's MIME type.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Webp => "image/webp",
+        }
+    }
+}
+
+/// The preview rates allowed, in thumbnails per second.
+pub const PREVIEW_FPS_RANGE: RangeInclusive<f32> = 2.0..=5.0;
+
+/// How a port preview samples and shrinks frames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreviewOptions {
+    /// Thumbnails per second, clamped to [`PREVIEW_FPS_RANGE`].
+    pub fps: f32,
+    /// Widest thumbnail in pixels. Frames are downscaled to it keeping
+    /// their aspect ratio, never upscaled.
+    pub max_width: u32,
+    pub format: PreviewFormat,
+    /// JPEG quality, 1–100. Ignored for WebP.
+    pub quality: u8,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            fps: *PREVIEW_FPS_RANGE.start(),
+            max_width: 320,
+            format: PreviewFormat::Jpeg,
+            quality: 70,
+        }
+    }
+}
+
+impl PreviewOptions {
+    /// Time between thumbnails, with `fps` clamped to
+    /// [`PREVIEW_FPS_RANGE`] (a non-finite rate gets the slowest).
+    pub fn interval(&self) -> Duration {
+        let fps = if self.fps.is_finite() {
+            self.fps
+                .clamp(*PREVIEW_FPS_RANGE.start(), *PREVIEW_FPS_RANGE.end())
+        } else {
+            *PREVIEW_FPS_RANGE.start()
+        };
+        Duration::from_secs_f32(1.0 / fps)
+    }
+}
+
+/// One preview thumbnail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewFrame {
+    /// Thumbnail width in pixels.
+    pub width: u32,
+    /// Thumbnail height in pixels.
+    pub height: u32,
+    /// The source frame's `timestamp_ns`.
+    pub timestamp_ns: i64,
+    pub format: PreviewFormat,
+    /// The encoded image.
+    pub bytes: Vec<u8>,
+}
+
+/// The surface reference of a tapped `VideoFrame` — the subset of the
+/// schema the snapshot path reads.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct SnapshotFrameReference {
+    pub(crate) surface_id: String,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) timestamp_ns: String,
+    #[serde(default)]
+    pub(crate) texture_layout: Option<i32>,
+}
+
+impl SnapshotFrameReference {
+    /// Decode the frame reference from a raw channel bag
+    /// (`FrameHeader`-framed, exactly as a tap forwards it).
+    pub(crate) fn from_bag(bag: &[u8]) -> Result<Self> {
+        if bag.len() < FRAME_HEADER_SIZE {
+            return Err(Error::Runtime(format!(
+                "snapshot: truncated bag ({} bytes, header needs {FRAME_HEADER_SIZE})",
+                bag.len()
+            )));
+        }
+        let len = FrameHeader::read_from_slice(bag).len as usize;
+        let end = (FRAME_HEADER_SIZE + len).min(bag.len());
+        rmp_serde::from_slice(&bag[FRAME_HEADER_SIZE..end]).map_err(|e| {
+            Error::NotSupported(format!(
+                "snapshot: the port's frames are not VideoFrames ({e})"
+            ))
+        })
+    }
+
+    /// `timestamp_ns` parsed from its on-wire string form; `0` when the
+    /// producer left it unparseable.
+    pub(crate) fn timestamp_ns(&self) -> i64 {
+        self.timestamp_ns.parse().unwrap_or(0)
+    }
+}
+
+/// Refuse any target but a `.png` file, before a frame is waited for.
+pub(crate) fn check_snapshot_path(path: &Path) -> Result<()> {
+    let is_png = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    if is_png {
+        Ok(())
+    } else {
+        Err(Error::NotSupported(format!(
+            "snapshot: {} — snapshots are written as PNG; give the path a .png extension",
+            path.display()
+        )))
+    }
+}
+
+/// Encode the top-left `width x height` of a read-back texture as an
+/// 8-bit RGBA PNG. `pixels` holds `texture_width`-wide rows in `format`;
+/// BGRA rows are swizzled.
+pub(crate) fn encode_png(
+    format: TextureFormat,
+    texture_width: u32,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<Vec<u8>> {
+    let rgba = readback_to_rgba(format, texture_width, width, height, pixels)?;
+
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| Error::Runtime(format!("snapshot: PNG header: {e}")))?;
+    writer
+        .write_image_data(&rgba)
+        .map_err(|e| Error::Runtime(format!("snapshot: PNG data: {e}")))?;
+    writer
+        .finish()
+        .map_err(|e| Error::Runtime(format!("snapshot: PNG finish: {e}")))?;
+    Ok(png_bytes)
+}
+
+/// Shrink the top-left `width x height` of a read-back texture to at most
+/// `options.max_width` wide and encode it as a thumbnail. Returns the
+/// thumbnail's size and bytes.
+pub(crate) fn encode_preview(
+    format: TextureFormat,
+    texture_width: u32,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    options: &PreviewOptions,
+) -> Result<(u32, u32, Vec<u8>)> {
+    let rgba = readback_to_rgba(format, texture_width, width, height, pixels)?;
+    let (rgba, width, height) = downscale_rgba(&rgba, width, height, options.max_width.max(1));
+    let bytes = encode_rgba(&rgba, width, height, options.format, options.quality)?;
+    Ok((width, height, bytes))
+}
+
+/// Encode tightly packed 8-bit RGBA rows as `format`, at full size. `quality`
+/// is the JPEG quality, 1–100, and is ignored for WebP. This is the preview
+/// encoder minus the readback and downscale, so `streamlib bench` can time it
+/// on synthetic frames.
+pub fn encode_rgba(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    format: PreviewFormat,
+    quality: u8,
+) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        PreviewFormat::Jpeg => {
+            let (Ok(jpeg_width), Ok(jpeg_height)) = (u16::try_from(width), u16::try_from(height))
+            else {
+                return Err(Error::NotSupported(format!(
+                    "preview: {width}x{height} is too large for a JPEG thumbnail"
+                )));
+            };
+            jpeg_encoder::Encoder::new(&mut bytes, quality.clamp(1, 100))
+                .encode(rgba, jpeg_width, jpeg_height, jpeg_encoder::ColorType::Rgba)
+                .map_err(|e| Error::Runtime(format!("preview: JPEG: {e}")))?;
+        }
+        PreviewFormat::Webp => {
+            image_webp::WebPEncoder::new(&mut bytes)
+                .encode(rgba, width, height, image_webp::ColorType::Rgba8)
+                .map_err(|e| Error::Runtime(format!("preview: WebP: {e}")))?;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Crop a read-back texture to its top-left `width x height` as tightly
+/// packed 8-bit RGBA rows. `pixels` holds `texture_width`-wide rows in
+/// `format`; BGRA rows are swizzled.
+fn readback_to_rgba(
+    format: TextureFormat,
+    texture_width: u32,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<Vec<u8>> {
+    let swizzle = match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+        other => {
+            return Err(Error::NotSupported(format!(
+                "snapshot: {other:?} surfaces can't be written as PNG (8-bit RGBA/BGRA only)"
+            )));
+        }
+    };
+    let stride = texture_width as usize * 4;
+    let row_bytes = width as usize * 4;
+    if width > texture_width || pixels.len() < stride * height as usize {
+        return Err(Error::Runtime(format!(
+            "snapshot: {width}x{height} frame doesn't fit its {texture_width}-wide, \
+             {} byte readback",
+            pixels.len()
+        )));
+    }
+
+    let mut rgba = Vec::with_capacity(row_bytes * height as usize);
+    for row in pixels.chunks_exact(stride).take(height as usize) {
+        rgba.extend_from_slice(&row[..row_bytes]);
+    }
+    if swizzle {
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    Ok(rgba)
+}
+
+/// Box-filter packed RGBA `width x height` pixels down to `max_width`
+/// wide, keeping the aspect ratio. Narrower images are returned as-is.
+fn downscale_rgba(rgba: &[u8], width: u32, height: u32, max_width: u32) -> (Vec<u8>, u32, u32) {
+    if width <= max_width {
+        return (rgba.to_vec(), width, height);
+    }
+    let (width, height) = (width as usize, height as usize);
+    let out_width = max_width as usize;
+    let out_height = (height * out_width / width).max(1);
+
+    let mut out = Vec::with_capacity(out_width * out_height * 4);
+    for out_y in 0..out_height {
+        let y0 = out_y * height / out_height;
+        let y1 = ((out_y + 1) * height / out_height).max(y0 + 1);
+        for out_x in 0..out_width {
+            let x0 = out_x * width / out_width;
+            let x1 = ((out_x + 1) * width / out_width).max(x0 + 1);
+            let mut sum = [0u32; 4];
+            for y in y0..y1 {
+                for pixel in rgba[(y * width + x0) * 4..(y * width + x1) * 4].chunks_exact(4) {
+                    for (total, value) in sum.iter_mut().zip(pixel) {
+                        *total += u32::from(*value);
+                    }
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as u32;
+            out.extend(sum.map(|total| (total / count) as u8));
+        }
+    }
+    (out, out_width as u32, out_height as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceoryx2::SchemaIdentWire;
+
+    #[derive(Serialize)]
+    struct WireVideoFrame<'a> {
+        surface_id: &'a str,
+        width: u32,
+        height: u32,
+        timestamp_ns: &'a str,
+        fps: Option<u32>,
+    }
+
+    fn bag(body: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0u8; FRAME_HEADER_SIZE + body.len()];
+        FrameHeader::new(
+            "video_out",
+            SchemaIdentWire::default(),
+            0,
+            body.len() as u32,
+        )
+        .expect("short port name")
+        .write_to_slice(&mut buf);
+        buf[FRAME_HEADER_SIZE..].copy_from_slice(body);
+        buf
+    }
+
+    #[test]
+    fn frame_reference_decodes_from_a_video_frame_bag() {
+        let body = rmp_serde::to_vec_named(&WireVideoFrame {
+            surface_id: "surface-7",
+            width: 640,
+            height: 360,
+            timestamp_ns: "123456789",
+            fps: Some(30),
+        })
+        .unwrap();
+        let frame = SnapshotFrameReference::from_bag(&bag(&body)).unwrap();
+        assert_eq!(frame.surface_id, "surface-7");
+        assert_eq!((frame.width, frame.height), (640, 360));
+        assert_eq!(frame.timestamp_ns(), 123_456_789);
+        assert_eq!(frame.texture_layout, None);
+    }
+
+    #[test]
+    fn non_video_bags_are_rejected() {
+        let body = rmp_serde::to_vec_named(&serde_json::json!({ "samples": [0.0, 1.0] })).unwrap();
+        assert!(matches!(
+            SnapshotFrameReference::from_bag(&bag(&body)),
+            Err(Error::NotSupported(_))
+        ));
+        assert!(SnapshotFrameReference::from_bag(&[0u8; 3]).is_err());
+    }
+
+    #[test]
+    fn only_png_paths_are_accepted() {
+        assert!(check_snapshot_path(Path::new("/tmp/frame.png")).is_ok());
+        assert!(check_snapshot_path(Path::new("/tmp/frame.PNG")).is_ok());
+        assert!(check_snapshot_path(Path::new("/tmp/frame.jpg")).is_err());
+        assert!(check_snapshot_path(Path::new("/tmp/frame")).is_err());
+    }
+
+    #[test]
+    fn bgra_readback_crops_and_swizzles_into_png() {
+        // 3-wide texture, 2x2 frame: the third column must be cropped.
+        let mut pixels = Vec::new();
+        for _ in 0..2 {
+            pixels.extend_from_slice(&[10, 20, 30, 255, 40, 50, 60, 255, 0, 0, 0, 0]);
+        }
+        let png_bytes = encode_png(TextureFormat::Bgra8Unorm, 3, 2, 2, &pixels).unwrap();
+
+        let decoder = png::Decoder::new(png_bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut out = vec![0u8; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut out).unwrap();
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(&out[..8], &[30, 20, 10, 255, 60, 50, 40, 255]);
+    }
+
+    #[test]
+    fn non_8bit_formats_are_rejected() {
+        let pixels = vec![0u8; 8 * 4];
+        assert!(matches!(
+            encode_png(TextureFormat::Rgba16Float, 2, 2, 2, &pixels),
+            Err(Error::NotSupported(_))
+        ));
+    }
+
+    #[test]
+    fn previews_downscale_to_max_width_keeping_the_aspect_ratio() {
+        // 8x4 RGBA, left half black and right half white.
+        let mut pixels = Vec::new();
+        for _ in 0..4 {
+            for x in 0..8 {
+                let value = if x < 4 { 0 } else { 255 };
+                pixels.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        let (rgba, width, height) = downscale_rgba(&pixels, 8, 4, 2);
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(rgba, vec![0, 0, 0, 255, 255, 255, 255, 255]);
+
+        let (_, width, height) = downscale_rgba(&pixels, 8, 4, 640);
+        assert_eq!((width, height), (8, 4), "never upscaled");
+    }
+
+    #[test]
+    fn previews_encode_as_jpeg_or_webp() {
+        let pixels = vec![128u8; 64 * 48 * 4];
+        let mut options = PreviewOptions {
+            max_width: 32,
+            ..PreviewOptions::default()
+        };
+        let (width, height, jpeg) =
+            encode_preview(TextureFormat::Rgba8Unorm, 64, 64, 48, &pixels, &options).unwrap();
+        assert_eq!((width, height), (32, 24));
+        assert_eq!(&jpeg[..2], &[0xFF, 0xD8], "JPEG start-of-image marker");
+
+        options.format = PreviewFormat::Webp;
+        let (_, _, webp) =
+            encode_preview(TextureFormat::Rgba8Unorm, 64, 64, 48, &pixels, &options).unwrap();
+        assert_eq!(&webp[..4], b"RIFF");
+        assert_eq!(&webp[8..12], b"WEBP");
+    }
+
+    #[test]
+    fn preview_rate_is_clamped() {
+        let at = |fps: f32| PreviewOptions {
+            fps,
+            ..PreviewOptions::default()
+        };
+        assert_eq!(at(4.0).interval(), Duration::from_millis(250));
+        assert_eq!(at(60.0).interval(), Duration::from_secs_f32(1.0 / 5.0));
+        assert_eq!(at(0.0).interval(), Duration::from_millis(500));
+        assert_eq!(at(f32::NAN).interval(), Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+use crate::core::{InputLinkPortRef, OutputLinkPortRef};
+
+/// Marks a processor the runtime inserted to convert between a producer and
+/// a consumer whose port schemas disagree.
+///
+/// Records the connection the caller actually asked for, so snapshots save
+/// the converter away as the original `upstream -> downstream` link and
+/// reloading re-derives it.
+pub struct AutoConverterComponent {
+    pub upstream: OutputLinkPortRef,
+    pub downstream: InputLinkPortRef,
+}
+
+impl JsonSerializableComponent for AutoConverterComponent {
+    fn json_key(&self) -> &'static str {
+        "auto_converter"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!({
+            "upstream": self.upstream.to_string(),
+            "downstream": self.downstream.to_string()
+        })
+    }
+}
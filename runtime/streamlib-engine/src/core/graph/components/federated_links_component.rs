@@ -0,0 +1,84 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+use crate::core::graph::{LinkUniqueId, ProcessorUniqueId};
+
+/// Which end of a federated link this runtime holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FederatedLinkDirection {
+    /// This processor's output port publishes to a processor in another runtime.
+    Outbound,
+    /// This processor's input port subscribes to a processor in another runtime.
+    Inbound,
+}
+
+/// One link whose other end lives in another runtime process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FederatedLink {
+    /// Shared by both runtimes, so either side can name the link to the other.
+    pub link_id: LinkUniqueId,
+    pub direction: FederatedLinkDirection,
+    /// Port on this processor — an output for outbound, an input for inbound.
+    pub local_port: String,
+    pub remote_runtime_id: String,
+    pub remote_processor_id: ProcessorUniqueId,
+    pub remote_port: String,
+}
+
+/// Links from one processor into other runtimes.
+///
+/// The graph only holds local edges, so these live on the node instead. They
+/// count toward iceoryx2 sizing: an outbound link adds a subscriber to its
+/// port's channel, an inbound link adds a notifier to the processor's notify
+/// service.
+#[derive(Default)]
+pub struct FederatedLinksComponent {
+    pub links: Vec<FederatedLink>,
+}
+
+impl FederatedLinksComponent {
+    /// Federated links in `direction`, narrowed to `local_port` when given.
+    pub fn count(&self, direction: FederatedLinkDirection, local_port: Option<&str>) -> usize {
+        self.links
+            .iter()
+            .filter(|link| link.direction == direction)
+            .filter(|link| local_port.is_none_or(|port| link.local_port == port))
+            .count()
+    }
+
+    /// Remove the link `link_id`, returning it if it was recorded here.
+    pub fn remove(&mut self, link_id: &str) -> Option<FederatedLink> {
+        let index = self
+            .links
+            .iter()
+            .position(|link| link.link_id.as_str() == link_id)?;
+        Some(self.links.remove(index))
+    }
+}
+
+impl JsonSerializableComponent for FederatedLinksComponent {
+    fn json_key(&self) -> &'static str {
+        "federated_links"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(
+            self.links
+                .iter()
+                .map(|link| {
+                    serde_json::json!({
+                        "link_id": link.link_id.as_str(),
+                        "direction": format!("{:?}", link.direction),
+                        "local_port": link.local_port,
+                        "remote_runtime_id": link.remote_runtime_id,
+                        "remote_processor_id": link.remote_processor_id.as_str(),
+                        "remote_port": link.remote_port,
+                    })
+                })
+                .collect(),
+        )
+    }
+}
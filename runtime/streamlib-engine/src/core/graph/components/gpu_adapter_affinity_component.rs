@@ -0,0 +1,23 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+
+/// Pins a processor to a GPU adapter other than the runtime's.
+///
+/// Set from [`ProcessorSpec::gpu_adapter`](crate::core::ProcessorSpec::gpu_adapter);
+/// the spawn op hands the processor a GPU context opened on this adapter
+/// (index into [`GpuContext::enumerate_adapters`](crate::core::context::GpuContext::enumerate_adapters)).
+pub struct GpuAdapterAffinityComponent(pub usize);
+
+impl JsonSerializableComponent for GpuAdapterAffinityComponent {
+    fn json_key(&self) -> &'static str {
+        "gpu_adapter"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!(self.0)
+    }
+}
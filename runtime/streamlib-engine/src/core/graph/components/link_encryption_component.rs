@@ -0,0 +1,26 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+
+/// Marker component requesting AEAD encryption of the link's payloads.
+///
+/// Inserted by `connect()` when [`ConnectOptions::encrypt_payloads`] is set.
+/// The compiler reads it when it opens the source port's channel: the first
+/// link to open a channel fixes whether the channel is encrypted, and a
+/// later encrypted link onto a plaintext channel is rejected.
+///
+/// [`ConnectOptions::encrypt_payloads`]: crate::core::runtime::ConnectOptions::encrypt_payloads
+pub struct LinkEncryptionComponent;
+
+impl JsonSerializableComponent for LinkEncryptionComponent {
+    fn json_key(&self) -> &'static str {
+        "payload_encryption"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!("chacha20-poly1305")
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+use crate::core::json_schema::{LINK_FRAME_DROPS_COMPONENT, LinkFrameDropsOutput};
+use crate::iceoryx2::MailboxFrameCounters;
+
+/// Span of history the windowed drop rate is computed over.
+pub const LINK_DROP_RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Windowed drop rate at or above which a link counts as congested.
+pub const LINK_CONGESTION_DROP_RATE_THRESHOLD: f64 = 0.1;
+
+/// One point-in-time reading of the mailbox counters.
+#[derive(Debug, Clone, Copy)]
+struct FrameCounterSample {
+    at: Instant,
+    frames_delivered: u64,
+    frames_dropped: u64,
+}
+
+/// Rolling window of counter samples plus the congestion latch.
+#[derive(Debug, Default)]
+struct LinkDropRateWindow {
+    samples: VecDeque<FrameCounterSample>,
+    congested: bool,
+}
+
+impl LinkDropRateWindow {
+    /// Frames delivered and dropped between the oldest and newest sample.
+    fn deltas(&self) -> (u64, u64) {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(oldest), Some(newest)) => (
+                newest.frames_delivered - oldest.frames_delivered,
+                newest.frames_dropped - oldest.frames_dropped,
+            ),
+            _ => (0, 0),
+        }
+    }
+
+    fn drop_rate(&self) -> f64 {
+        let (delivered, dropped) = self.deltas();
+        let total = delivered + dropped;
+        if total == 0 {
+            0.0
+        } else {
+            dropped as f64 / total as f64
+        }
+    }
+}
+
+/// Result of one [`LinkFrameDropComponent::sample`] tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkDropRateSample {
+    /// Frames dropped inside [`LINK_DROP_RATE_WINDOW`].
+    pub window_frames_dropped: u64,
+    /// Dropped / (delivered + dropped) inside [`LINK_DROP_RATE_WINDOW`].
+    pub window_drop_rate: f64,
+    /// True only on the tick the link crossed into congestion.
+    pub became_congested: bool,
+}
+
+/// Frame-drop accounting for a link, read from its destination mailbox.
+///
+/// Inserted on the link edge when the compiler wires a Rust destination.
+/// Counters are per destination input port, so links fanning in to one
+/// port report the same tallies. Subprocess destinations own their
+/// mailboxes out of process and carry no component.
+pub struct LinkFrameDropComponent {
+    counters: Arc<MailboxFrameCounters>,
+    window: Mutex<LinkDropRateWindow>,
+}
+
+impl LinkFrameDropComponent {
+    /// Track the given destination mailbox counters.
+    pub fn new(counters: Arc<MailboxFrameCounters>) -> Self {
+        Self {
+            counters,
+            window: Mutex::new(LinkDropRateWindow::default()),
+        }
+    }
+
+    /// Total frames delivered to the destination processor.
+    pub fn frames_delivered(&self) -> u64 {
+        self.counters.frames_delivered()
+    }
+
+    /// Total frames dropped before reaching the destination processor.
+    pub fn frames_dropped(&self) -> u64 {
+        self.counters.frames_dropped()
+    }
+
+    /// Whether the link is currently latched as congested.
+    pub fn is_congested(&self) -> bool {
+        self.window.lock().congested
+    }
+
+    /// Record a counter sample at `now`, expire samples older than
+    /// [`LINK_DROP_RATE_WINDOW`], and update the congestion latch.
+    pub fn sample(&self, now: Instant) -> LinkDropRateSample {
+        let mut window = self.window.lock();
+        window.samples.push_back(FrameCounterSample {
+            at: now,
+            frames_delivered: self.counters.frames_delivered(),
+            frames_dropped: self.counters.frames_dropped(),
+        });
+        while window
+            .samples
+            .front()
+            .is_some_and(|s| now.duration_since(s.at) > LINK_DROP_RATE_WINDOW)
+        {
+            window.samples.pop_front();
+        }
+
+        let (_, window_frames_dropped) = window.deltas();
+        let window_drop_rate = window.drop_rate();
+        let congested = window_drop_rate >= LINK_CONGESTION_DROP_RATE_THRESHOLD;
+        let became_congested = congested && !window.congested;
+        window.congested = congested;
+
+        LinkDropRateSample {
+            window_frames_dropped,
+            window_drop_rate,
+            became_congested,
+        }
+    }
+}
+
+impl JsonSerializableComponent for LinkFrameDropComponent {
+    fn json_key(&self) -> &'static str {
+        LINK_FRAME_DROPS_COMPONENT
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let window = self.window.lock();
+        let (_, window_frames_dropped) = window.deltas();
+        serde_json::to_value(LinkFrameDropsOutput {
+            frames_delivered: self.counters.frames_delivered(),
+            frames_dropped: self.counters.frames_dropped(),
+            queue_depth: self.counters.queue_depth(),
+            window_secs: LINK_DROP_RATE_WINDOW.as_secs(),
+            window_frames_dropped,
+            window_drop_rate: window.drop_rate(),
+            congested: window.congested,
+        })
+        .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceoryx2::PortMailbox;
+
+    #[test]
+    fn sample_latches_congestion_once_on_rising_edge() {
+        let mailbox = PortMailbox::new(1);
+        let component = LinkFrameDropComponent::new(mailbox.frame_counters());
+        let t0 = Instant::now();
+        assert!(!component.sample(t0).became_congested);
+
+        // Two evictions, one delivery → 2/3 drop rate.
+        mailbox.push(vec![1]);
+        mailbox.push(vec![2]);
+        mailbox.push(vec![3]);
+        mailbox.pop();
+
+        let first = component.sample(t0 + Duration::from_secs(1));
+        assert_eq!(first.window_frames_dropped, 2);
+        assert!(first.became_congested);
+        assert!(component.is_congested());
+
+        let second = component.sample(t0 + Duration::from_secs(2));
+        assert!(!second.became_congested);
+    }
+
+    #[test]
+    fn samples_outside_window_expire() {
+        let mailbox = PortMailbox::new(1);
+        let component = LinkFrameDropComponent::new(mailbox.frame_counters());
+        let t0 = Instant::now();
+        component.sample(t0);
+        mailbox.push(vec![1]);
+        mailbox.push(vec![2]);
+        component.sample(t0 + Duration::from_secs(1));
+
+        let later = component.sample(t0 + LINK_DROP_RATE_WINDOW + Duration::from_secs(3));
+        assert_eq!(later.window_frames_dropped, 0);
+        assert_eq!(later.window_drop_rate, 0.0);
+        assert!(!component.is_congested());
+
+        let json = component.to_json();
+        assert_eq!(json["frames_dropped"], 1);
+        assert_eq!(json["congested"], false);
+    }
+}
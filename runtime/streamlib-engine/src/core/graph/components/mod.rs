@@ -1,35 +1,51 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+mod auto_converter_component;
 mod component_map;
 mod execution_lightweight_component;
 mod execution_main_thread_component;
 mod execution_rayon_pool_component;
+mod federated_links_component;
+mod gpu_adapter_affinity_component;
 mod json_component_trait;
+mod link_encryption_component;
+mod link_frame_drop_component;
 mod link_state_component;
 mod link_type_info_component;
 mod pending_deletion_component;
+mod processor_bypass_gate_component;
 mod processor_instance_component;
 mod processor_metrics;
+mod processor_parameter_port_component;
 mod processor_pause_gate_component;
 mod processor_ready_barrier_component;
+mod reported_components;
 mod shutdown_channel_component;
 mod state_component;
 mod subprocess_handle_component;
 mod thread_handle_component;
 
+pub use auto_converter_component::*;
 pub use component_map::*;
 pub use execution_lightweight_component::*;
 pub use execution_main_thread_component::*;
 pub use execution_rayon_pool_component::*;
+pub use federated_links_component::*;
+pub use gpu_adapter_affinity_component::*;
 pub use json_component_trait::*;
+pub use link_encryption_component::*;
+pub use link_frame_drop_component::*;
 pub use link_state_component::*;
 pub use link_type_info_component::*;
 pub use pending_deletion_component::*;
+pub use processor_bypass_gate_component::*;
 pub use processor_instance_component::*;
 pub use processor_metrics::*;
+pub use processor_parameter_port_component::*;
 pub use processor_pause_gate_component::*;
 pub use processor_ready_barrier_component::*;
+pub use reported_components::*;
 pub use shutdown_channel_component::*;
 pub use state_component::*;
 pub use subprocess_handle_component::*;
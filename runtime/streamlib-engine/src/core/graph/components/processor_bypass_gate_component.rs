@@ -0,0 +1,61 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+
+/// Lock-free bypass gate for processors.
+///
+/// While set, the thread runner forwards a reactive processor's input
+/// frames unchanged to its outputs instead of calling `process()`.
+///
+/// This is an ECS component attached to processor entities.
+pub struct ProcessorBypassGateComponent(Arc<AtomicBool>);
+
+impl ProcessorBypassGateComponent {
+    /// Create a new bypass gate (not bypassed by default).
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Returns true if the processor is currently bypassed.
+    pub fn is_bypassed(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Set the bypassed state.
+    pub fn set_bypassed(&self, bypassed: bool) {
+        self.0.store(bypassed, Ordering::Release);
+    }
+
+    /// Get a clone of the inner Arc for sharing with other threads.
+    pub fn clone_inner(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.0)
+    }
+}
+
+impl Default for ProcessorBypassGateComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for ProcessorBypassGateComponent {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl JsonSerializableComponent for ProcessorBypassGateComponent {
+    fn json_key(&self) -> &'static str {
+        "bypassed"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!(self.is_bypassed())
+    }
+}
@@ -4,6 +4,7 @@
 use serde_json::Value as JsonValue;
 
 use super::JsonSerializableComponent;
+use crate::core::json_schema::{PROCESSOR_METRICS_COMPONENT, ProcessorMetricsOutput};
 
 /// Runtime metrics for a processor.
 #[derive(Default, Clone)]
@@ -22,16 +23,17 @@ pub struct ProcessorMetrics {
 
 impl JsonSerializableComponent for ProcessorMetrics {
     fn json_key(&self) -> &'static str {
-        "metrics"
+        PROCESSOR_METRICS_COMPONENT
     }
 
     fn to_json(&self) -> JsonValue {
-        serde_json::json!({
-            "throughput_fps": self.throughput_fps,
-            "latency_p50_ms": self.latency_p50_ms,
-            "latency_p99_ms": self.latency_p99_ms,
-            "frames_processed": self.frames_processed,
-            "frames_dropped": self.frames_dropped
+        serde_json::to_value(ProcessorMetricsOutput {
+            throughput_fps: self.throughput_fps,
+            latency_p50_ms: self.latency_p50_ms,
+            latency_p99_ms: self.latency_p99_ms,
+            frames_processed: self.frames_processed,
+            frames_dropped: self.frames_dropped,
         })
+        .unwrap_or_default()
     }
 }
@@ -0,0 +1,62 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde_json::Value as JsonValue;
+
+use super::JsonSerializableComponent;
+use crate::core::parameter_automation::{ParameterChange, ParameterTimeline};
+
+/// Built-in config-update port for processors.
+///
+/// Holds the [`ParameterChange`]s scheduled for the processor. The runtime
+/// schedules into it; the processor thread delivers due values through
+/// `apply_config_json` before each `process()` call.
+///
+/// This is an ECS component attached to processor entities.
+pub struct ProcessorParameterPortComponent(Arc<Mutex<ParameterTimeline>>);
+
+impl ProcessorParameterPortComponent {
+    /// Create a port whose deliveries build on `config`.
+    pub fn new(config: JsonValue) -> Self {
+        Self(Arc::new(Mutex::new(ParameterTimeline::new(config))))
+    }
+
+    /// Queue `change`; `now_ns` is the current media-clock time.
+    pub fn schedule(&self, change: ParameterChange, now_ns: i64) {
+        self.0.lock().schedule(change, now_ns);
+    }
+
+    /// The processor's config was replaced outside the port.
+    pub fn set_config(&self, config: JsonValue) {
+        self.0.lock().set_config(config);
+    }
+
+    /// Number of scheduled values not yet delivered.
+    pub fn pending(&self) -> usize {
+        self.0.lock().pending()
+    }
+
+    /// Get a clone of the inner Arc for sharing with the processor thread.
+    pub(crate) fn clone_inner(&self) -> Arc<Mutex<ParameterTimeline>> {
+        Arc::clone(&self.0)
+    }
+}
+
+impl Clone for ProcessorParameterPortComponent {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl JsonSerializableComponent for ProcessorParameterPortComponent {
+    fn json_key(&self) -> &'static str {
+        "scheduled_parameters"
+    }
+
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!(self.pending())
+    }
+}
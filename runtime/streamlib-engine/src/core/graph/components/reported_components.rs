@@ -0,0 +1,113 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Components a processor reports onto its own graph node.
+//!
+//! Plugin processors can't insert into the [`super::ComponentMap`] — it
+//! lives on the host side of the ABI — so they publish JSON values over
+//! the reserved
+//! [`streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT`]
+//! control topic instead. The latest value per `(processor, key)` is kept
+//! process-wide and merged into the node's `components` when the graph is
+//! serialized; built-in components always win a key collision.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// One report: `value` becomes `components[key]` on the processor's node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessorComponentReport {
+    /// Reporting processor's unique id.
+    pub processor_id: String,
+    /// Key under the node's `components`.
+    pub key: String,
+    /// Component value, replacing any earlier report under `key`.
+    pub value: JsonValue,
+}
+
+type ReportedComponents = BTreeMap<String, BTreeMap<String, JsonValue>>;
+
+static REPORTED_COMPONENTS: LazyLock<Mutex<ReportedComponents>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Record `report` as the latest value of its component.
+pub fn report_processor_component(report: ProcessorComponentReport) {
+    REPORTED_COMPONENTS
+        .lock()
+        .entry(report.processor_id)
+        .or_default()
+        .insert(report.key, report.value);
+}
+
+/// Add `processor_id`'s reported components to `components`, leaving keys
+/// already present (the built-in components) untouched.
+pub(crate) fn merge_reported_components(
+    processor_id: &str,
+    components: &mut serde_json::Map<String, JsonValue>,
+) {
+    merge_from(&REPORTED_COMPONENTS, processor_id, components);
+}
+
+/// Forget everything `processor_id` reported; called when it is removed.
+pub(crate) fn clear_reported_components(processor_id: &str) {
+    REPORTED_COMPONENTS.lock().remove(processor_id);
+}
+
+fn merge_from(
+    registry: &Mutex<ReportedComponents>,
+    processor_id: &str,
+    components: &mut serde_json::Map<String, JsonValue>,
+) {
+    let registry = registry.lock();
+    let Some(reported) = registry.get(processor_id) else {
+        return;
+    };
+    for (key, value) in reported {
+        components
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reported_components_never_override_built_in_keys() {
+        let registry = Mutex::new(BTreeMap::from([(
+            "processor-1".to_string(),
+            BTreeMap::from([
+                ("metrics".to_string(), JsonValue::from("spoofed")),
+                (
+                    "loudness".to_string(),
+                    serde_json::json!({ "integrated_lufs": -23.0 }),
+                ),
+            ]),
+        )]));
+        let mut components = serde_json::Map::new();
+        components.insert(
+            "metrics".to_string(),
+            serde_json::json!({ "frames_processed": 1 }),
+        );
+
+        merge_from(&registry, "processor-1", &mut components);
+
+        assert_eq!(
+            components["metrics"],
+            serde_json::json!({ "frames_processed": 1 })
+        );
+        assert_eq!(
+            components["loudness"],
+            serde_json::json!({ "integrated_lufs": -23.0 })
+        );
+
+        let mut other = serde_json::Map::new();
+        merge_from(&registry, "processor-2", &mut other);
+        assert!(other.is_empty());
+    }
+}
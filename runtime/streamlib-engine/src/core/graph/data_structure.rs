@@ -35,6 +35,10 @@ pub struct Graph {
 
     /// Graph-level state.
     state: GraphState,
+
+    /// Runtime-level components that belong to no single node or link
+    /// (e.g. GPU pool occupancy), serialized as the graph's `components`.
+    components: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Default for Graph {
@@ -50,6 +54,7 @@ impl Graph {
             digraph: DiGraph::new(),
             compiled_at: None,
             state: GraphState::Idle,
+            components: serde_json::Map::new(),
         }
     }
 
@@ -91,6 +96,16 @@ impl Graph {
         self.compiled_at = Some(Instant::now());
     }
 
+    /// Set the runtime-level component `key`, replacing any earlier value.
+    pub fn set_component(&mut self, key: &str, value: serde_json::Value) {
+        self.components.insert(key.to_string(), value);
+    }
+
+    /// Runtime-level component `key`, if one was set.
+    pub fn component(&self, key: &str) -> Option<&serde_json::Value> {
+        self.components.get(key)
+    }
+
     /// Check if recompilation is needed.
     ///
     /// Returns true if the graph has never been compiled.
@@ -142,6 +157,7 @@ impl Serialize for Graph {
                 .edge_indices()
                 .map(|idx| LinkOutput::from(&self.digraph[idx]))
                 .collect(),
+            components: self.components.clone(),
         };
 
         response.serialize(serializer)
@@ -7,6 +7,7 @@ mod data_structure;
 mod edges;
 mod nodes;
 mod processor_state_ecs_component;
+mod topology;
 mod traits;
 mod traversal;
 mod validation;
@@ -17,6 +18,7 @@ mod graph_tests;
 // top level
 pub use data_structure::{Graph, GraphState};
 pub use processor_state_ecs_component::{ProcessorState, ProcessorStateComponent};
+pub use topology::TopologyAnalyzer;
 pub use traits::{GraphEdgeWithComponents, GraphNodeWithComponents, GraphWeight};
 pub use validation::validate_graph;
 
@@ -6,7 +6,9 @@
 use serde::{Deserialize, Serialize};
 
 /// State of a processor instance.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, utoipa::ToSchema,
+)]
 pub enum ProcessorState {
     /// Waiting to be started (registered but not yet running).
     #[default]
@@ -0,0 +1,343 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Structural checks over the live [`Graph`]: cycles a new link would close,
+//! and required input ports left without an incoming link.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::error::TopologyDiagnostic;
+use crate::core::execution::ProcessExecution;
+use crate::core::graph::{
+    Graph, GraphEdgeWithComponents, GraphNodeWithComponents, PendingDeletionComponent,
+    ProcessorUniqueId,
+};
+use crate::core::processors::PROCESSOR_REGISTRY;
+use crate::core::{InputLinkPortRef, OutputLinkPortRef, SchemaIdent};
+
+/// Read-only topology analysis over a [`Graph`].
+///
+/// Processors and links already marked for deletion are ignored, so the
+/// analysis reflects the graph as it will be after the next commit.
+pub struct TopologyAnalyzer<'a> {
+    graph: &'a Graph,
+}
+
+impl<'a> TopologyAnalyzer<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        Self { graph }
+    }
+
+    /// Processor id → processor type, for every processor in the graph.
+    fn processor_types(&self) -> HashMap<&'a str, &'a SchemaIdent> {
+        self.graph
+            .traversal()
+            .v(())
+            .iter()
+            .map(|node| (node.id.as_str(), &node.processor_type))
+            .collect()
+    }
+
+    /// Whether a link landing on `port_name` of `processor_id` makes that
+    /// processor wait on it: the processor is Reactive and the input is
+    /// required. Processors whose type has no registered descriptor count as
+    /// Reactive with every input required — the defaults.
+    fn link_blocks(
+        &self,
+        types: &HashMap<&str, &SchemaIdent>,
+        processor_id: &str,
+        port_name: &str,
+    ) -> bool {
+        let Some(descriptor) = types
+            .get(processor_id)
+            .and_then(|processor_type| PROCESSOR_REGISTRY.descriptor(processor_type))
+        else {
+            return true;
+        };
+        matches!(descriptor.execution, ProcessExecution::Reactive)
+            && descriptor
+                .inputs
+                .iter()
+                .find(|input| input.name == port_name)
+                .is_none_or(|input| input.required)
+    }
+
+    /// Processor → downstream processors, one entry per live link the
+    /// downstream processor waits on. Links into an optional input or into a
+    /// Manual/Continuous processor are left out: a loop through them still
+    /// makes progress.
+    fn blocking_adjacency(
+        &self,
+        types: &HashMap<&str, &SchemaIdent>,
+    ) -> HashMap<ProcessorUniqueId, Vec<ProcessorUniqueId>> {
+        let mut adjacency: HashMap<ProcessorUniqueId, Vec<ProcessorUniqueId>> = HashMap::new();
+        for link in self.graph.traversal().e(()).iter() {
+            if link.has::<PendingDeletionComponent>()
+                || !self.link_blocks(types, &link.target.processor_id, &link.target.port_name)
+            {
+                continue;
+            }
+            adjacency
+                .entry(link.source.processor_id.clone())
+                .or_default()
+                .push(link.target.processor_id.clone());
+        }
+        adjacency
+    }
+
+    /// The deadlocking cycle wiring `from -> to` would close, if any: the
+    /// processors on the loop in dataflow order, from `to.processor_id`
+    /// through `from.processor_id`. A self-link is a one-processor cycle.
+    ///
+    /// Only a loop on which every processor is Reactive and every link feeds
+    /// a required input is reported — nothing on it can ever fire first. A
+    /// loop that lands on an `optional = true` input, or passes through a
+    /// Manual or Continuous processor, is a legitimate feedback path.
+    pub fn cycle_closed_by(
+        &self,
+        from: &OutputLinkPortRef,
+        to: &InputLinkPortRef,
+    ) -> Option<TopologyDiagnostic> {
+        let types = self.processor_types();
+        if !self.link_blocks(&types, &to.processor_id, &to.port_name) {
+            return None;
+        }
+        let adjacency = self.blocking_adjacency(&types);
+        let start = &to.processor_id;
+        let goal = &from.processor_id;
+
+        // Depth-first search from the new link's target back to its source,
+        // tracking the path so the diagnostic can name the loop.
+        let mut visited: HashSet<&ProcessorUniqueId> = HashSet::new();
+        let mut path: Vec<&ProcessorUniqueId> = vec![start];
+        let mut pending: Vec<std::slice::Iter<'_, ProcessorUniqueId>> = vec![
+            adjacency
+                .get(start)
+                .map(|next| next.iter())
+                .unwrap_or_default(),
+        ];
+        visited.insert(start);
+        let found = start == goal
+            || loop {
+                let Some(frontier) = pending.last_mut() else {
+                    break false;
+                };
+                match frontier.next() {
+                    Some(next) if next == goal => {
+                        path.push(next);
+                        break true;
+                    }
+                    Some(next) if visited.insert(next) => {
+                        path.push(next);
+                        pending.push(
+                            adjacency
+                                .get(next)
+                                .map(|after| after.iter())
+                                .unwrap_or_default(),
+                        );
+                    }
+                    Some(_) => {}
+                    None => {
+                        pending.pop();
+                        path.pop();
+                    }
+                }
+            };
+
+        found.then(|| TopologyDiagnostic::Cycle {
+            from_processor: from.processor_id.to_string(),
+            from_port: from.port_name.clone(),
+            to_processor: to.processor_id.to_string(),
+            to_port: to.port_name.clone(),
+            cycle: path.into_iter().map(|id| id.to_string()).collect(),
+        })
+    }
+
+    /// Every input port its processor type declares `required` that has no
+    /// incoming link. Processor types without a registered descriptor are
+    /// skipped.
+    pub fn unsatisfied_required_inputs(&self) -> Vec<TopologyDiagnostic> {
+        let connected: HashSet<(&str, &str)> = self
+            .graph
+            .traversal()
+            .e(())
+            .iter()
+            .filter(|link| !link.has::<PendingDeletionComponent>())
+            .map(|link| {
+                (
+                    link.target.processor_id.as_str(),
+                    link.target.port_name.as_str(),
+                )
+            })
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for node in self.graph.traversal().v(()).iter() {
+            if node.has::<PendingDeletionComponent>() {
+                continue;
+            }
+            let Some(descriptor) = PROCESSOR_REGISTRY.descriptor(&node.processor_type) else {
+                continue;
+            };
+            for input in descriptor.inputs.iter().filter(|input| input.required) {
+                if !connected.contains(&(node.id.as_str(), input.name.as_str())) {
+                    diagnostics.push(TopologyDiagnostic::UnsatisfiedRequiredInput {
+                        processor_id: node.id.to_string(),
+                        port_name: input.name.clone(),
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{
+        MockInputOnlyProcessor, MockOutputOnlyProcessor, MockProcessor, MockReactiveProcessor,
+        ensure_test_mocks_registered,
+    };
+
+    fn add_mock_processor(graph: &mut Graph) -> String {
+        graph
+            .traversal_mut()
+            .add_v(MockProcessor::Processor::node(Default::default()))
+            .first()
+            .expect("should create processor")
+            .id
+            .to_string()
+    }
+
+    fn add_reactive_processor(graph: &mut Graph) -> String {
+        graph
+            .traversal_mut()
+            .add_v(MockReactiveProcessor::Processor::node(Default::default()))
+            .first()
+            .expect("should create processor")
+            .id
+            .to_string()
+    }
+
+    #[test]
+    fn detects_cycle_closed_by_new_link() {
+        ensure_test_mocks_registered();
+        let mut graph = Graph::new();
+        let a = add_reactive_processor(&mut graph);
+        let b = add_reactive_processor(&mut graph);
+        let c = add_reactive_processor(&mut graph);
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&a, "out1"),
+            InputLinkPortRef::new(&b, "in1"),
+        );
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&b, "out1"),
+            InputLinkPortRef::new(&c, "in1"),
+        );
+
+        let analyzer = TopologyAnalyzer::new(&graph);
+        let closing = analyzer.cycle_closed_by(
+            &OutputLinkPortRef::new(&c, "out1"),
+            &InputLinkPortRef::new(&a, "in2"),
+        );
+        match closing {
+            Some(TopologyDiagnostic::Cycle { cycle, .. }) => {
+                assert_eq!(cycle, vec![a.clone(), b.clone(), c.clone()]);
+            }
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+
+        // Parallel fan-out is not a cycle.
+        assert!(
+            analyzer
+                .cycle_closed_by(
+                    &OutputLinkPortRef::new(&a, "out2"),
+                    &InputLinkPortRef::new(&c, "in2"),
+                )
+                .is_none()
+        );
+
+        // Self-link.
+        assert!(
+            analyzer
+                .cycle_closed_by(
+                    &OutputLinkPortRef::new(&b, "out2"),
+                    &InputLinkPortRef::new(&b, "in2"),
+                )
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn feedback_loops_that_can_make_progress_are_not_cycles() {
+        ensure_test_mocks_registered();
+        let mut graph = Graph::new();
+        let a = add_reactive_processor(&mut graph);
+        let b = add_reactive_processor(&mut graph);
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&a, "out1"),
+            InputLinkPortRef::new(&b, "in1"),
+        );
+        let analyzer = TopologyAnalyzer::new(&graph);
+
+        // Closing the loop onto an optional input: `a` fires without it.
+        assert!(
+            analyzer
+                .cycle_closed_by(
+                    &OutputLinkPortRef::new(&b, "out1"),
+                    &InputLinkPortRef::new(&a, "feedback"),
+                )
+                .is_none()
+        );
+
+        // A loop through a self-driven processor: `m` produces on its own.
+        let m = add_mock_processor(&mut graph);
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&b, "out2"),
+            InputLinkPortRef::new(&m, "in1"),
+        );
+        let analyzer = TopologyAnalyzer::new(&graph);
+        assert!(
+            analyzer
+                .cycle_closed_by(
+                    &OutputLinkPortRef::new(&m, "out1"),
+                    &InputLinkPortRef::new(&a, "in1"),
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn reports_unconnected_required_inputs() {
+        ensure_test_mocks_registered();
+        let mut graph = Graph::new();
+        let source = graph
+            .traversal_mut()
+            .add_v(MockOutputOnlyProcessor::Processor::node(Default::default()))
+            .first()
+            .expect("should create processor")
+            .id
+            .to_string();
+        let sink = graph
+            .traversal_mut()
+            .add_v(MockInputOnlyProcessor::Processor::node(Default::default()))
+            .first()
+            .expect("should create processor")
+            .id
+            .to_string();
+        graph.traversal_mut().add_e(
+            OutputLinkPortRef::new(&source, "out1"),
+            InputLinkPortRef::new(&sink, "in1"),
+        );
+
+        let diagnostics = TopologyAnalyzer::new(&graph).unsatisfied_required_inputs();
+        assert_eq!(
+            diagnostics,
+            vec![TopologyDiagnostic::UnsatisfiedRequiredInput {
+                processor_id: sink,
+                port_name: "in2".into(),
+            }]
+        );
+    }
+}
@@ -6,8 +6,8 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 
 use crate::core::graph::{
-    GraphNodeWithComponents, ProcessorNode, ProcessorTraversalMut, StateComponent,
-    TraversalSourceMut,
+    GpuAdapterAffinityComponent, GraphNodeWithComponents, ProcessorNode, ProcessorTraversalMut,
+    StateComponent, TraversalSourceMut,
 };
 use crate::core::processors::{
     PROCESSOR_REGISTRY, ProcessorSpec, ProcessorState, ProcessorTypeReference,
@@ -67,10 +67,18 @@ impl<'a> TraversalSourceMut<'a> {
             .display_name
             .unwrap_or_else(|| node_ident.r#type.as_str().to_string());
 
+        let gpu_adapter = spec.gpu_adapter;
+
         let node = ProcessorNode::new(node_ident, display_name, Some(spec.config), inputs, outputs);
 
         let node_idx = self.graph.add_node(node);
 
+        if let Some(index) = gpu_adapter
+            && let Some(node_mut) = self.graph.node_weight_mut(node_idx)
+        {
+            node_mut.insert(GpuAdapterAffinityComponent(index));
+        }
+
         if registry_miss {
             if let Some(node_mut) = self.graph.node_weight_mut(node_idx) {
                 node_mut.insert(StateComponent(Arc::new(Mutex::new(ProcessorState::Error))));
@@ -0,0 +1,457 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Event-sourced log of graph edits, for undo / redo.
+//!
+//! Every edit made through [`RuntimeOperations`](crate::core::runtime::RuntimeOperations)
+//! on a [`Runner`](crate::core::runtime::Runner) is recorded as a
+//! [`GraphEdit`] carrying enough state to apply its [`GraphEdit::inverse`]:
+//! a removal keeps the processor's spec and links, a config update keeps the
+//! config it replaced. Undo applies the inverse of the newest edit and moves
+//! it onto the redo stack; redo re-applies it. A fresh edit clears the redo
+//! stack, and the undo stack is bounded by [`GraphEditHistory::capacity`].
+//! Edits made together — a [`GraphPatch`](crate::core::graph_patch::GraphPatch)
+//! — are recorded as one [`GraphEdit::Batch`] and undone as a unit.
+//!
+//! The history rides along in [`GraphSnapshot`](crate::core::graph_snapshot::GraphSnapshot)
+//! so an editor session survives a save / load. Processor ids in a saved
+//! history are rewritten to snapshot aliases and resolved back on load.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::graph::{InputLinkPortRef, LinkUniqueId, OutputLinkPortRef, ProcessorUniqueId};
+use crate::core::processors::ProcessorSpec;
+
+/// How many edits [`GraphEditHistory::default`] keeps before dropping the oldest.
+pub const DEFAULT_GRAPH_EDIT_HISTORY_CAPACITY: usize = 100;
+
+/// One link touched by an edit.
+///
+/// `link_id` is the id the link had when recorded. Re-applying an edit
+/// mints fresh ids, so `from` / `to` are what the link is found by when the
+/// id no longer resolves (e.g. after a snapshot reload).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEditLink {
+    pub link_id: LinkUniqueId,
+    pub from: OutputLinkPortRef,
+    pub to: InputLinkPortRef,
+}
+
+/// A single recorded graph edit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphEdit {
+    /// A processor was added. `links` are re-wired after it, so a removal's
+    /// inverse brings the processor back connected.
+    AddProcessor {
+        processor_id: ProcessorUniqueId,
+        spec: ProcessorSpec,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        links: Vec<GraphEditLink>,
+    },
+    /// A processor was removed, together with every link into or out of it.
+    RemoveProcessor {
+        processor_id: ProcessorUniqueId,
+        spec: ProcessorSpec,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        links: Vec<GraphEditLink>,
+    },
+    /// Two ports were connected.
+    Connect { link: GraphEditLink },
+    /// A link was removed.
+    Disconnect { link: GraphEditLink },
+    /// A processor's config was replaced; `previous` is what it replaced.
+    UpdateConfig {
+        processor_id: ProcessorUniqueId,
+        previous: serde_json::Value,
+        config: serde_json::Value,
+    },
+    /// Several edits made as one, in the order they were applied.
+    Batch { edits: Vec<GraphEdit> },
+}
+
+impl GraphEdit {
+    /// The edit that undoes this one.
+    pub fn inverse(&self) -> GraphEdit {
+        match self.clone() {
+            GraphEdit::AddProcessor {
+                processor_id,
+                spec,
+                links,
+            } => GraphEdit::RemoveProcessor {
+                processor_id,
+                spec,
+                links,
+            },
+            GraphEdit::RemoveProcessor {
+                processor_id,
+                spec,
+                links,
+            } => GraphEdit::AddProcessor {
+                processor_id,
+                spec,
+                links,
+            },
+            GraphEdit::Connect { link } => GraphEdit::Disconnect { link },
+            GraphEdit::Disconnect { link } => GraphEdit::Connect { link },
+            GraphEdit::UpdateConfig {
+                processor_id,
+                previous,
+                config,
+            } => GraphEdit::UpdateConfig {
+                processor_id,
+                previous: config,
+                config: previous,
+            },
+            GraphEdit::Batch { edits } => GraphEdit::Batch {
+                edits: edits.iter().rev().map(GraphEdit::inverse).collect(),
+            },
+        }
+    }
+
+    /// One edit standing for `edits`: the edit itself when there is just
+    /// one, a [`GraphEdit::Batch`] otherwise. `None` for no edits.
+    pub fn batch(mut edits: Vec<GraphEdit>) -> Option<GraphEdit> {
+        match edits.len() {
+            0 => None,
+            1 => edits.pop(),
+            _ => Some(GraphEdit::Batch { edits }),
+        }
+    }
+
+    /// Rewrite every processor id this edit refers to through `map`; ids
+    /// `map` returns `None` for are left as they are.
+    pub fn map_processor_ids(
+        &mut self,
+        map: &mut impl FnMut(&ProcessorUniqueId) -> Option<ProcessorUniqueId>,
+    ) {
+        let mut remap = |id: &mut ProcessorUniqueId| {
+            if let Some(mapped) = map(id) {
+                *id = mapped;
+            }
+        };
+        match self {
+            GraphEdit::AddProcessor {
+                processor_id,
+                links,
+                ..
+            }
+            | GraphEdit::RemoveProcessor {
+                processor_id,
+                links,
+                ..
+            } => {
+                remap(processor_id);
+                for link in links {
+                    remap(&mut link.from.processor_id);
+                    remap(&mut link.to.processor_id);
+                }
+            }
+            GraphEdit::Connect { link } | GraphEdit::Disconnect { link } => {
+                remap(&mut link.from.processor_id);
+                remap(&mut link.to.processor_id);
+            }
+            GraphEdit::UpdateConfig { processor_id, .. } => remap(processor_id),
+            GraphEdit::Batch { edits } => {
+                for edit in edits {
+                    edit.map_processor_ids(map);
+                }
+            }
+        }
+    }
+
+    /// Rewrite every recorded link id equal to `from` to `to`.
+    pub fn rename_link(&mut self, from: &LinkUniqueId, to: &LinkUniqueId) {
+        let links: &mut [GraphEditLink] = match self {
+            GraphEdit::AddProcessor { links, .. } | GraphEdit::RemoveProcessor { links, .. } => {
+                links
+            }
+            GraphEdit::Connect { link } | GraphEdit::Disconnect { link } => {
+                std::slice::from_mut(link)
+            }
+            GraphEdit::UpdateConfig { .. } => &mut [],
+            GraphEdit::Batch { edits } => {
+                for edit in edits {
+                    edit.rename_link(from, to);
+                }
+                return;
+            }
+        };
+        for link in links.iter_mut().filter(|link| &link.link_id == from) {
+            link.link_id = to.clone();
+        }
+    }
+}
+
+/// Bounded undo / redo stacks of [`GraphEdit`]s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphEditHistory {
+    /// Applied edits, oldest first.
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    undo: VecDeque<GraphEdit>,
+    /// Undone edits, most recently undone last.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    redo: Vec<GraphEdit>,
+    #[serde(skip, default = "default_capacity")]
+    capacity: usize,
+}
+
+fn default_capacity() -> usize {
+    DEFAULT_GRAPH_EDIT_HISTORY_CAPACITY
+}
+
+impl Default for GraphEditHistory {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_GRAPH_EDIT_HISTORY_CAPACITY)
+    }
+}
+
+impl GraphEditHistory {
+    /// An empty history keeping at most `capacity` undoable edits.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Maximum number of undoable edits kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the bound, dropping the oldest edits if it shrank.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    /// True when there is nothing to undo or redo.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    /// Edits that can be undone, oldest first.
+    pub fn undoable(&self) -> impl Iterator<Item = &GraphEdit> {
+        self.undo.iter()
+    }
+
+    /// Edits that can be redone, next-to-redo last.
+    pub fn redoable(&self) -> impl Iterator<Item = &GraphEdit> {
+        self.redo.iter()
+    }
+
+    /// Record a fresh edit. Clears the redo stack.
+    pub fn record(&mut self, edit: GraphEdit) {
+        self.redo.clear();
+        self.push_undo(edit);
+    }
+
+    /// Take the newest edit to undo.
+    pub fn take_undo(&mut self) -> Option<GraphEdit> {
+        self.undo.pop_back()
+    }
+
+    /// Take the most recently undone edit to redo.
+    pub fn take_redo(&mut self) -> Option<GraphEdit> {
+        self.redo.pop()
+    }
+
+    /// Put an edit back on the undo stack without touching the redo stack —
+    /// after a redo, or when an undo failed to apply.
+    pub fn push_undo(&mut self, edit: GraphEdit) {
+        self.undo.push_back(edit);
+        self.trim();
+    }
+
+    /// Put an undone edit on the redo stack.
+    pub fn push_redo(&mut self, edit: GraphEdit) {
+        self.redo.push(edit);
+    }
+
+    /// Rewrite processor ids across both stacks; see [`GraphEdit::map_processor_ids`].
+    pub fn map_processor_ids(
+        &mut self,
+        mut map: impl FnMut(&ProcessorUniqueId) -> Option<ProcessorUniqueId>,
+    ) {
+        for edit in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+            edit.map_processor_ids(&mut map);
+        }
+    }
+
+    /// Rewrite a link id across both stacks.
+    pub fn rename_link(&mut self, from: &LinkUniqueId, to: &LinkUniqueId) {
+        for edit in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+            edit.rename_link(from, to);
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.capacity {
+            self.undo.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::descriptors::SchemaIdent;
+
+    fn config_edit(id: &str, previous: i64, config: i64) -> GraphEdit {
+        GraphEdit::UpdateConfig {
+            processor_id: ProcessorUniqueId::from(id),
+            previous: serde_json::json!({ "n": previous }),
+            config: serde_json::json!({ "n": config }),
+        }
+    }
+
+    fn link(id: &str, from: &str, to: &str) -> GraphEditLink {
+        GraphEditLink {
+            link_id: LinkUniqueId::from(id),
+            from: OutputLinkPortRef::new(from, "video"),
+            to: InputLinkPortRef::new(to, "video"),
+        }
+    }
+
+    fn spec() -> ProcessorSpec {
+        let ident: SchemaIdent = serde_json::from_value(serde_json::json!({
+            "org": "tatolab",
+            "package": "streamlib",
+            "type": "CameraProcessor",
+            "version": "1.0.0"
+        }))
+        .unwrap();
+        ProcessorSpec::new(ident, serde_json::json!({}))
+    }
+
+    #[test]
+    fn inverse_of_inverse_is_the_edit() {
+        let edits = [
+            GraphEdit::AddProcessor {
+                processor_id: "a".into(),
+                spec: spec(),
+                links: vec![link("l1", "a", "b")],
+            },
+            GraphEdit::Connect {
+                link: link("l1", "a", "b"),
+            },
+            config_edit("a", 1, 2),
+        ];
+        for edit in edits {
+            assert_ne!(edit.inverse(), edit);
+            assert_eq!(edit.inverse().inverse(), edit);
+        }
+    }
+
+    #[test]
+    fn a_batch_inverts_as_a_unit_and_remaps_every_step() {
+        let mut batch = GraphEdit::batch(vec![
+            GraphEdit::AddProcessor {
+                processor_id: "a".into(),
+                spec: spec(),
+                links: Vec::new(),
+            },
+            GraphEdit::Connect {
+                link: link("l1", "a", "b"),
+            },
+        ])
+        .unwrap();
+        assert_eq!(
+            batch.inverse(),
+            GraphEdit::Batch {
+                edits: vec![
+                    GraphEdit::Disconnect {
+                        link: link("l1", "a", "b")
+                    },
+                    GraphEdit::RemoveProcessor {
+                        processor_id: "a".into(),
+                        spec: spec(),
+                        links: Vec::new(),
+                    },
+                ]
+            }
+        );
+        assert_eq!(batch.inverse().inverse(), batch);
+
+        batch.map_processor_ids(&mut |id| (id.as_str() == "a").then(|| "a2".into()));
+        batch.rename_link(&"l1".into(), &"l2".into());
+        let GraphEdit::Batch { edits } = &batch else {
+            unreachable!()
+        };
+        assert_eq!(
+            edits[1],
+            GraphEdit::Connect {
+                link: link("l2", "a2", "b")
+            }
+        );
+
+        assert_eq!(GraphEdit::batch(Vec::new()), None);
+        assert_eq!(
+            GraphEdit::batch(vec![config_edit("a", 0, 1)]),
+            Some(config_edit("a", 0, 1))
+        );
+    }
+
+    #[test]
+    fn recording_clears_redo_and_undo_is_bounded() {
+        let mut history = GraphEditHistory::with_capacity(2);
+        history.record(config_edit("a", 0, 1));
+        history.record(config_edit("a", 1, 2));
+        history.record(config_edit("a", 2, 3));
+        assert_eq!(history.undoable().count(), 2);
+
+        let undone = history.take_undo().unwrap();
+        assert_eq!(undone, config_edit("a", 2, 3));
+        history.push_redo(undone);
+        assert_eq!(history.redoable().count(), 1);
+
+        history.record(config_edit("a", 1, 5));
+        assert_eq!(history.redoable().count(), 0);
+        assert_eq!(history.take_undo(), Some(config_edit("a", 1, 5)));
+        assert_eq!(history.take_undo(), Some(config_edit("a", 1, 2)));
+        assert_eq!(history.take_undo(), None);
+    }
+
+    #[test]
+    fn remapping_rewrites_both_stacks() {
+        let mut history = GraphEditHistory::default();
+        history.record(GraphEdit::Connect {
+            link: link("l1", "a", "b"),
+        });
+        history.push_redo(config_edit("a", 0, 1));
+
+        history.map_processor_ids(|id| (id.as_str() == "a").then(|| "a2".into()));
+        history.rename_link(&"l1".into(), &"l2".into());
+
+        assert_eq!(
+            history.take_undo(),
+            Some(GraphEdit::Connect {
+                link: link("l2", "a2", "b")
+            })
+        );
+        assert_eq!(history.take_redo(), Some(config_edit("a2", 0, 1)));
+    }
+
+    #[test]
+    fn history_round_trips_through_json_with_default_capacity() {
+        let mut history = GraphEditHistory::with_capacity(5);
+        history.record(GraphEdit::RemoveProcessor {
+            processor_id: "a".into(),
+            spec: spec(),
+            links: vec![link("l1", "a", "b")],
+        });
+        let json = serde_json::to_value(&history).unwrap();
+        assert_eq!(json["undo"][0]["op"], "remove_processor");
+
+        let back: GraphEditHistory = serde_json::from_value(json).unwrap();
+        assert_eq!(back.capacity(), DEFAULT_GRAPH_EDIT_HISTORY_CAPACITY);
+        assert_eq!(
+            back.undoable().collect::<Vec<_>>(),
+            history.undoable().collect::<Vec<_>>()
+        );
+    }
+}
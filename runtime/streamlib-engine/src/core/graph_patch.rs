@@ -0,0 +1,220 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Batched graph edits applied all-or-nothing.
+//!
+//! A [`GraphPatch`] is what an editor sends instead of a run of separate
+//! add / connect / remove calls: the whole diff is checked against the live
+//! graph and applied under one graph lock by
+//! [`RuntimeOperations::apply_graph_patch_async`](crate::core::runtime::RuntimeOperations::apply_graph_patch_async).
+//! If any step is rejected — an unknown type, a missing port, a link that
+//! would close a cycle — every step already taken is undone and the graph
+//! is left as it was. A patch that applies publishes a single
+//! `GraphDidChange`, so the compiler sees it as one commit.
+//!
+//! Processors added by the patch have no id until it applies, so they are
+//! named by an `alias` that `connect` and `update_configs` entries may use
+//! in place of a processor id.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{Error, Result};
+use crate::core::graph::{InputLinkPortRef, LinkUniqueId, OutputLinkPortRef, ProcessorUniqueId};
+use crate::core::processors::ProcessorSpec;
+
+/// A processor the patch adds, under a patch-local alias.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphPatchProcessor {
+    pub alias: String,
+    pub spec: ProcessorSpec,
+}
+
+/// A config replacement. `processor_id` may name a processor added by the
+/// same patch through its alias.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphPatchConfig {
+    pub processor_id: ProcessorUniqueId,
+    pub config: serde_json::Value,
+}
+
+/// A link the patch wires. Either end may name a processor added by the
+/// same patch through its alias.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphPatchLink {
+    pub from: OutputLinkPortRef,
+    pub to: InputLinkPortRef,
+}
+
+/// A set of graph edits applied together or not at all.
+///
+/// Steps apply in a fixed order — disconnects, removals, additions, config
+/// replacements, then connects — so a patch can free an input and rewire
+/// it, or swap a processor for another, in one go.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphPatch {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disconnect: Vec<LinkUniqueId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_processors: Vec<ProcessorUniqueId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_processors: Vec<GraphPatchProcessor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub update_configs: Vec<GraphPatchConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connect: Vec<GraphPatchLink>,
+}
+
+impl GraphPatch {
+    /// True when the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.disconnect.is_empty()
+            && self.remove_processors.is_empty()
+            && self.add_processors.is_empty()
+            && self.update_configs.is_empty()
+            && self.connect.is_empty()
+    }
+
+    /// Checks that need no graph: aliases are non-empty and unique, nothing
+    /// is removed or disconnected twice, and no step touches a processor
+    /// the patch also removes.
+    pub fn check(&self) -> Result<()> {
+        let mut aliases = HashSet::new();
+        for processor in &self.add_processors {
+            if processor.alias.is_empty() {
+                return Err(invalid("an added processor has an empty alias"));
+            }
+            if !aliases.insert(processor.alias.as_str()) {
+                return Err(invalid(format!(
+                    "alias '{}' is used by more than one added processor",
+                    processor.alias
+                )));
+            }
+        }
+
+        let mut removed = HashSet::new();
+        for processor_id in &self.remove_processors {
+            if !removed.insert(processor_id) {
+                return Err(invalid(format!("processor '{processor_id}' is removed twice")));
+            }
+        }
+        let mut disconnected = HashSet::new();
+        for link_id in &self.disconnect {
+            if !disconnected.insert(link_id) {
+                return Err(invalid(format!("link '{link_id}' is disconnected twice")));
+            }
+        }
+
+        let touches_removed = |processor_id: &ProcessorUniqueId| {
+            if removed.contains(processor_id) {
+                Err(invalid(format!(
+                    "processor '{processor_id}' is removed by the same patch"
+                )))
+            } else {
+                Ok(())
+            }
+        };
+        for update in &self.update_configs {
+            touches_removed(&update.processor_id)?;
+        }
+        for link in &self.connect {
+            touches_removed(&link.from.processor_id)?;
+            touches_removed(&link.to.processor_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// What an applied patch created: the id each alias was minted, and the
+/// ids of the wired links in `connect` order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphPatchReceipt {
+    pub processors: BTreeMap<String, ProcessorUniqueId>,
+    pub links: Vec<LinkUniqueId>,
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidGraph(format!("graph patch: {}", message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::descriptors::SchemaIdent;
+
+    fn added(alias: &str) -> GraphPatchProcessor {
+        let ident: SchemaIdent = serde_json::from_value(serde_json::json!({
+            "org": "tatolab",
+            "package": "streamlib",
+            "type": "CameraProcessor",
+            "version": "1.0.0"
+        }))
+        .unwrap();
+        GraphPatchProcessor {
+            alias: alias.to_string(),
+            spec: ProcessorSpec::new(ident, serde_json::json!({})),
+        }
+    }
+
+    fn link(from: &str, to: &str) -> GraphPatchLink {
+        GraphPatchLink {
+            from: OutputLinkPortRef::new(from, "video"),
+            to: InputLinkPortRef::new(to, "video"),
+        }
+    }
+
+    #[test]
+    fn check_accepts_a_rewire_through_a_new_processor() {
+        let patch = GraphPatch {
+            disconnect: vec!["l1".into()],
+            add_processors: vec![added("blur")],
+            connect: vec![link("camera", "blur"), link("blur", "display")],
+            ..GraphPatch::default()
+        };
+        patch.check().unwrap();
+        assert!(!patch.is_empty());
+        assert!(GraphPatch::default().is_empty());
+    }
+
+    #[test]
+    fn check_rejects_duplicate_aliases_and_steps_on_removed_processors() {
+        let duplicate = GraphPatch {
+            add_processors: vec![added("blur"), added("blur")],
+            ..GraphPatch::default()
+        };
+        assert!(matches!(duplicate.check(), Err(Error::InvalidGraph(_))));
+
+        let unnamed = GraphPatch {
+            add_processors: vec![added("")],
+            ..GraphPatch::default()
+        };
+        assert!(unnamed.check().is_err());
+
+        let dangling = GraphPatch {
+            remove_processors: vec!["display".into()],
+            connect: vec![link("camera", "display")],
+            ..GraphPatch::default()
+        };
+        let error = dangling.check().unwrap_err().to_string();
+        assert!(error.contains("'display' is removed"), "{error}");
+    }
+
+    #[test]
+    fn patch_round_trips_through_json_omitting_empty_steps() {
+        let patch = GraphPatch {
+            add_processors: vec![added("camera")],
+            update_configs: vec![GraphPatchConfig {
+                processor_id: "display".into(),
+                config: serde_json::json!({ "width": 1280 }),
+            }],
+            ..GraphPatch::default()
+        };
+        let json = serde_json::to_value(&patch).unwrap();
+        assert!(json.get("disconnect").is_none());
+        assert_eq!(json["add_processors"][0]["alias"], "camera");
+
+        let back: GraphPatch = serde_json::from_value(json).unwrap();
+        assert_eq!(back, patch);
+    }
+}
@@ -39,8 +39,19 @@
 
 use serde::{Deserialize, Serialize};
 
+use std::collections::{HashMap, HashSet};
+
 use crate::core::descriptors::SchemaIdent;
-use crate::core::{Error, ProcessorSpec, Result};
+use crate::core::embedded_schemas::resolve_node_port_schema;
+use crate::core::graph::{Graph, ProcessorUniqueId, TopologyAnalyzer};
+use crate::core::graph_edit_history::GraphEditHistory;
+use crate::core::processor_schedule::ScheduleDefinition;
+use crate::core::processors::PROCESSOR_REGISTRY;
+use crate::core::schema_agreement::{SchemaAgreement, classify_port_schema_agreement};
+use crate::core::{
+    Error, InputLinkPortRef, OutputLinkPortRef, PortDirection, ProcessorSpec, Result,
+    TopologyDiagnostic,
+};
 
 /// Round-trippable JSON shape for a runtime's graph.
 ///
@@ -59,6 +70,22 @@ pub struct GraphSnapshot {
     /// Connections between processors using aliases.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub connections: Vec<ConnectionDefinition>,
+
+    /// Cron schedules that start / stop processors, by alias.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ScheduleDefinition>,
+
+    /// Whether the graph was built with automatic converter insertion on.
+    /// Auto-inserted converters are not saved as processors; loading turns
+    /// insertion on before wiring `connections`, which re-inserts them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_convert: bool,
+
+    /// Undo / redo history of the session that produced the graph, so an
+    /// editor resumed from this snapshot can keep undoing. Processors in it
+    /// are referenced by alias.
+    #[serde(default, skip_serializing_if = "GraphEditHistory::is_empty")]
+    pub edit_history: GraphEditHistory,
 }
 
 /// A processor definition in the snapshot.
@@ -90,6 +117,11 @@ pub struct ProcessorDefinition {
     /// the user-intent distinction round-trips.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+
+    /// GPU adapter index the processor is pinned to. Absent ↔ the
+    /// runtime's own device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_adapter: Option<usize>,
 }
 
 /// A connection definition using aliases.
@@ -143,6 +175,9 @@ impl ProcessorDefinition {
         if let Some(name) = &self.display_name {
             spec = spec.with_display_name(name.clone());
         }
+        if let Some(index) = self.gpu_adapter {
+            spec = spec.with_gpu_adapter(index);
+        }
         spec
     }
 }
@@ -196,12 +231,9 @@ impl GraphSnapshot {
     /// Checks:
     /// - All aliases are unique
     /// - All connection references point to valid aliases
+    /// - All schedules reference valid aliases and parse
     /// - All processor types exist in the global processor registry
     pub fn validate(&self) -> Result<()> {
-        use std::collections::HashSet;
-
-        use crate::core::processors::PROCESSOR_REGISTRY;
-
         // Check for duplicate aliases
         let mut aliases: HashSet<&str> = HashSet::new();
         for proc in &self.processors {
@@ -232,6 +264,17 @@ impl GraphSnapshot {
             }
         }
 
+        // Check all schedules target a known alias and parse
+        for schedule in &self.schedules {
+            if !aliases.contains(schedule.processor.as_str()) {
+                return Err(Error::GraphError(format!(
+                    "Schedule references unknown processor alias: '{}'",
+                    schedule.processor
+                )));
+            }
+            schedule.to_processor_schedule()?;
+        }
+
         // Check all processor types resolve through the runtime registry.
         // Bails on the first miss — matches the behavior of the
         // surrounding alias / connection checks above.
@@ -245,6 +288,214 @@ impl GraphSnapshot {
 
         Ok(())
     }
+
+    /// Dry-run the snapshot against a scratch graph and collect every
+    /// problem, instead of stopping at the first one like [`Self::validate`]
+    /// and [`Runner::load_graph_snapshot`] do.
+    ///
+    /// On top of [`Self::validate`]'s checks, each connection is checked the
+    /// way a connect would: both ports exist, the producer and consumer
+    /// schemas agree, and the link closes no cycle. Once every link is
+    /// placed, required inputs left unlinked are reported too. Nothing is
+    /// instantiated. Diagnostics name processors by alias.
+    ///
+    /// [`Runner::load_graph_snapshot`]: crate::core::runtime::Runner::load_graph_snapshot
+    pub fn check(&self) -> GraphSnapshotCheck {
+        let mut check = GraphSnapshotCheck::default();
+        let mut graph = Graph::new();
+        let mut aliases: HashSet<&str> = HashSet::new();
+        // Only processors of a registered type get a node; links touching
+        // the rest are covered by their UnknownProcessorType error.
+        let mut alias_to_id: HashMap<&str, ProcessorUniqueId> = HashMap::new();
+        let mut id_to_alias: HashMap<String, String> = HashMap::new();
+
+        for proc in &self.processors {
+            if !aliases.insert(proc.alias.as_str()) {
+                check.errors.push(Error::GraphError(format!(
+                    "Duplicate processor alias: '{}'",
+                    proc.alias
+                )));
+                continue;
+            }
+            if PROCESSOR_REGISTRY.port_info(&proc.processor_type).is_none() {
+                check.errors.push(Error::UnknownProcessorType {
+                    ident: proc.processor_type.clone(),
+                });
+                continue;
+            }
+            if let Some(id) = graph
+                .traversal_mut()
+                .add_v(proc.to_processor_spec())
+                .first()
+                .map(|node| node.id.clone())
+            {
+                id_to_alias.insert(id.to_string(), proc.alias.clone());
+                alias_to_id.insert(proc.alias.as_str(), id);
+            }
+        }
+
+        for conn in &self.connections {
+            let (from, to) = match (conn.parse_from(), conn.parse_to()) {
+                (Ok(from), Ok(to)) => (from, to),
+                (Err(e), _) | (_, Err(e)) => {
+                    check.errors.push(e);
+                    continue;
+                }
+            };
+            for alias in [from.alias, to.alias] {
+                if !aliases.contains(alias) {
+                    check.errors.push(Error::GraphError(format!(
+                        "Connection references unknown processor alias: '{}'",
+                        alias
+                    )));
+                }
+            }
+            let (Some(from_id), Some(to_id)) =
+                (alias_to_id.get(from.alias), alias_to_id.get(to.alias))
+            else {
+                continue;
+            };
+
+            let has_output = graph
+                .traversal()
+                .v(from_id)
+                .first()
+                .is_some_and(|node| node.has_output(from.port_name));
+            let has_input = graph
+                .traversal()
+                .v(to_id)
+                .first()
+                .is_some_and(|node| node.has_input(to.port_name));
+            if !has_output {
+                check.errors.push(Error::ProcessorPortNotFound {
+                    processor_id: from.alias.to_string(),
+                    port_name: from.port_name.to_string(),
+                    direction: PortDirection::Output,
+                });
+            }
+            if !has_input {
+                check.errors.push(Error::ProcessorPortNotFound {
+                    processor_id: to.alias.to_string(),
+                    port_name: to.port_name.to_string(),
+                    direction: PortDirection::Input,
+                });
+            }
+            if !has_output || !has_input {
+                continue;
+            }
+
+            let producer_schema =
+                resolve_node_port_schema(&graph, from_id, from.port_name, PortDirection::Output);
+            let consumer_schema =
+                resolve_node_port_schema(&graph, to_id, to.port_name, PortDirection::Input);
+            if classify_port_schema_agreement(&producer_schema, &consumer_schema)
+                == SchemaAgreement::Mismatch
+            {
+                check.warnings.push(Error::SchemaIdentMismatch {
+                    from_processor: from.alias.to_string(),
+                    from_port: from.port_name.to_string(),
+                    to_processor: to.alias.to_string(),
+                    to_port: to.port_name.to_string(),
+                    producer_schema: producer_schema.to_string(),
+                    consumer_schema: consumer_schema.to_string(),
+                });
+            }
+
+            let from_ref = OutputLinkPortRef::new(from_id, from.port_name);
+            let to_ref = InputLinkPortRef::new(to_id, to.port_name);
+            if let Some(cycle) = TopologyAnalyzer::new(&graph).cycle_closed_by(&from_ref, &to_ref) {
+                // Leave the link out, as connect would, so later links and
+                // the required-input pass see the graph a load would build.
+                check.errors.push(Error::Topology {
+                    diagnostics: vec![diagnostic_with_aliases(cycle, &id_to_alias)],
+                });
+                continue;
+            }
+            graph.traversal_mut().add_e(from_ref, to_ref);
+        }
+
+        for schedule in &self.schedules {
+            if !aliases.contains(schedule.processor.as_str()) {
+                check.errors.push(Error::GraphError(format!(
+                    "Schedule references unknown processor alias: '{}'",
+                    schedule.processor
+                )));
+            } else if let Err(e) = schedule.to_processor_schedule() {
+                check.errors.push(e);
+            }
+        }
+
+        for diagnostic in TopologyAnalyzer::new(&graph).unsatisfied_required_inputs() {
+            check.errors.push(Error::Topology {
+                diagnostics: vec![diagnostic_with_aliases(diagnostic, &id_to_alias)],
+            });
+        }
+
+        check
+    }
+}
+
+/// Everything [`GraphSnapshot::check`] found.
+#[derive(Debug, Default)]
+pub struct GraphSnapshotCheck {
+    /// Problems that fail a load, or leave part of the graph unable to run.
+    pub errors: Vec<Error>,
+    /// Producer / consumer schema mismatches, which a loose connect wires
+    /// anyway.
+    pub warnings: Vec<Error>,
+}
+
+impl GraphSnapshotCheck {
+    /// No errors and no warnings.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// `diagnostic` with the scratch graph's processor ids swapped for the
+/// snapshot aliases they were created from.
+fn diagnostic_with_aliases(
+    diagnostic: TopologyDiagnostic,
+    id_to_alias: &HashMap<String, String>,
+) -> TopologyDiagnostic {
+    let alias = |id: String| id_to_alias.get(&id).cloned().unwrap_or(id);
+    match diagnostic {
+        TopologyDiagnostic::Cycle {
+            from_processor,
+            from_port,
+            to_processor,
+            to_port,
+            cycle,
+        } => TopologyDiagnostic::Cycle {
+            from_processor: alias(from_processor),
+            from_port,
+            to_processor: alias(to_processor),
+            to_port,
+            cycle: cycle.into_iter().map(alias).collect(),
+        },
+        TopologyDiagnostic::UnsatisfiedRequiredInput {
+            processor_id,
+            port_name,
+        } => TopologyDiagnostic::UnsatisfiedRequiredInput {
+            processor_id: alias(processor_id),
+            port_name,
+        },
+        TopologyDiagnostic::SchemaMismatch {
+            from_processor,
+            from_port,
+            to_processor,
+            to_port,
+            producer_schema,
+            consumer_schema,
+        } => TopologyDiagnostic::SchemaMismatch {
+            from_processor: alias(from_processor),
+            from_port,
+            to_processor: alias(to_processor),
+            to_port,
+            producer_schema,
+            consumer_schema,
+        },
+    }
 }
 
 #[cfg(test)]
@@ -445,6 +696,39 @@ mod tests {
         assert!(snap.validate().is_err());
     }
 
+    #[test]
+    fn test_schedules_round_trip_and_validate() {
+        let json = format!(
+            r#"{{
+                "processors": [
+                    {{ "alias": "camera", "type": {}, "config": {{}} }}
+                ],
+                "schedules": [
+                    {{ "processor": "camera", "cron": "0 9 * * MON-FRI",
+                       "action": "start", "timezone": "-05:00" }},
+                    {{ "processor": "camera", "cron": "0 17 * * MON-FRI",
+                       "action": "stop", "timezone": "-05:00" }}
+                ]
+            }}"#,
+            structured_type("CameraProcessor"),
+        );
+        let snap = GraphSnapshot::from_json_str(&json).unwrap();
+        assert_eq!(snap.schedules.len(), 2);
+        let snap_back = GraphSnapshot::from_json_str(&snap.to_json_string().unwrap()).unwrap();
+        assert_eq!(snap, snap_back);
+
+        let mut unknown_alias = snap.clone();
+        unknown_alias.schedules[0].processor = "display".into();
+        assert!(matches!(
+            unknown_alias.validate(),
+            Err(Error::GraphError(msg)) if msg.contains("Schedule references unknown processor alias")
+        ));
+
+        let mut bad_cron = snap;
+        bad_cron.schedules[0].cron = "0 9 * *".into();
+        assert!(matches!(bad_cron.validate(), Err(Error::Config(_))));
+    }
+
     #[test]
     fn test_minimal_snapshot() {
         let json = r#"{ "processors": [] }"#;
@@ -452,6 +736,7 @@ mod tests {
         assert!(snap.name.is_none());
         assert!(snap.processors.is_empty());
         assert!(snap.connections.is_empty());
+        assert!(snap.schedules.is_empty());
         assert!(snap.validate().is_ok());
     }
 
@@ -481,4 +766,100 @@ mod tests {
             other => panic!("expected UnknownProcessorType, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_check_collects_every_problem() {
+        use crate::core::test_support::{
+            MockInputOnlyProcessor, MockOutputOnlyProcessor, MockReactiveProcessor,
+            ensure_test_mocks_registered,
+        };
+
+        ensure_test_mocks_registered();
+        let processor = |alias: &str, spec: ProcessorSpec| ProcessorDefinition {
+            alias: alias.to_string(),
+            processor_type: spec.name.to_diagnostic_ident(),
+            config: serde_json::json!({}),
+            display_name: None,
+            gpu_adapter: None,
+        };
+        let connection = |from: &str, to: &str| ConnectionDefinition {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let snap = GraphSnapshot {
+            name: None,
+            processors: vec![
+                processor(
+                    "source",
+                    MockOutputOnlyProcessor::Processor::node(Default::default()),
+                ),
+                processor(
+                    "a",
+                    MockReactiveProcessor::Processor::node(Default::default()),
+                ),
+                processor(
+                    "b",
+                    MockReactiveProcessor::Processor::node(Default::default()),
+                ),
+                processor(
+                    "sink",
+                    MockInputOnlyProcessor::Processor::node(Default::default()),
+                ),
+                processor(
+                    "sink",
+                    MockInputOnlyProcessor::Processor::node(Default::default()),
+                ),
+            ],
+            connections: vec![
+                connection("source.out1", "a.in1"),
+                connection("a.out1", "b.in1"),
+                connection("b.out1", "a.in2"),
+                connection("b.out2", "sink.in1"),
+                connection("source.video", "ghost.in1"),
+            ],
+            schedules: vec![],
+            auto_convert: false,
+            edit_history: GraphEditHistory::default(),
+        };
+
+        let check = snap.check();
+        assert!(check.warnings.is_empty(), "{:?}", check.warnings);
+        let messages: Vec<String> = check.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(check.errors.len(), 6, "{messages:#?}");
+        assert!(matches!(
+            &check.errors[0],
+            Error::GraphError(msg) if msg.contains("Duplicate processor alias: 'sink'")
+        ));
+        match &check.errors[1] {
+            Error::Topology { diagnostics } => match &diagnostics[..] {
+                [TopologyDiagnostic::Cycle { cycle, .. }] => assert_eq!(cycle, &["a", "b"]),
+                other => panic!("expected one cycle, got {other:?}"),
+            },
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+        assert!(matches!(
+            &check.errors[2],
+            Error::GraphError(msg) if msg.contains("unknown processor alias: 'ghost'")
+        ));
+        // The cycle-closing link was left out, so a.in2 is unlinked too.
+        let unlinked: Vec<(&str, &str)> = check.errors[3..]
+            .iter()
+            .filter_map(|error| match error {
+                Error::Topology { diagnostics } => match &diagnostics[..] {
+                    [
+                        TopologyDiagnostic::UnsatisfiedRequiredInput {
+                            processor_id,
+                            port_name,
+                        },
+                    ] => Some((processor_id.as_str(), port_name.as_str())),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(unlinked.len(), 3, "{messages:#?}");
+        assert!(unlinked.contains(&("a", "in2")));
+        assert!(unlinked.contains(&("b", "in2")));
+        assert!(unlinked.contains(&("sink", "in2")));
+    }
 }
@@ -3,282 +3,28 @@
 
 //! JSON Schema output types for API documentation.
 //!
-//! These structs mirror the serialization output of the runtime types and are used
-//! for generating JSON Schema files. They implement both `Serialize` and `JsonSchema`
-//! to ensure schemas stay in sync with actual serialization.
-
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
-use crate::core::graph::{GraphEdgeWithComponents, GraphNodeWithComponents};
-
-/// Structured schema-identifier + semantic-version wire DTOs. Relocated to the
-/// engine-free `streamlib-processor-schema` crate so the MoQ catalog and the
-/// plugin authoring chain share one definition; re-exported here so every
-/// `core::json_schema::{SchemaIdentOutput, SemanticVersionOutput}` path — and
-/// the `streamlib::sdk::json_schema` facade the API server consumes — resolves
-/// unchanged. The `utoipa` feature the engine enables gives them the
-/// `utoipa::ToSchema` derive the aggregate response types below require.
+//! The wire DTOs themselves live in the engine-free
+//! `streamlib-processor-schema` crate (`runtime_api`), shared with
+//! `streamlib-client` so the server and its clients deserialize one
+//! definition. They are re-exported here, so every `core::json_schema` path
+//! — and the `streamlib::sdk::json_schema` facade the API server consumes —
+//! resolves to them, and this module keeps the conversions from the
+//! runtime's graph and registry types. The `utoipa` feature the engine
+//! enables gives them the `utoipa::ToSchema` derive the API server requires.
+
+pub use streamlib_processor_schema::runtime_api::{
+    CodeExamplesOutput, ConfigFieldOutput, CreateConnectionRequest, CreateProcessorRequest,
+    ErrorResponse, GPU_POOL_COMPONENT, GpuPoolStatsOutput, GraphResponse, IdResponse,
+    LINK_FRAME_DROPS_COMPONENT, LinkBufferReadModeOutput, LinkFrameDropsOutput, LinkOutput,
+    LinkPortRefOutput, LinkStateOutput, PROCESSOR_METRICS_COMPONENT, PROCESSOR_STATE_COMPONENT,
+    PortDescriptorOutput, PortInfoOutput, PortKindOutput, ProbeCheck, ProbeResponse,
+    ProcessorDescriptorOutput, ProcessorMetricsOutput, ProcessorNodeOutput,
+    ProcessorNodePortsOutput, ProcessorRuntimeOutput, RegistryResponse, SchemaDescriptorOutput,
+    SchemaFieldOutput, UpdateProcessorConfigRequest,
+};
 pub use streamlib_processor_schema::{SchemaIdentOutput, SemanticVersionOutput};
 
-// =============================================================================
-// Graph Response Schema (/api/graph)
-// =============================================================================
-
-/// Response from the `/api/graph` endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct GraphResponse {
-    /// All processor nodes in the graph.
-    pub nodes: Vec<ProcessorNodeOutput>,
-    /// All links (connections) between processors.
-    pub links: Vec<LinkOutput>,
-}
-
-/// A processor node in the graph.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct ProcessorNodeOutput {
-    /// Unique identifier for this processor instance.
-    pub id: String,
-    /// Structured processor identity — `@org/package/Type@version`
-    /// rendered as four typed fields on the wire (the structured-everywhere
-    /// rule). The joined `Display` form is render-only.
-    #[serde(rename = "type")]
-    pub processor_type: SchemaIdentOutput,
-    /// Display name for UI. May differ from type for hosted processors.
-    pub display_name: String,
-    /// Processor configuration as JSON.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub config: Option<serde_json::Value>,
-    /// Checksum of config for change detection.
-    #[serde(default)]
-    pub config_checksum: u64,
-    /// Input and output ports.
-    pub ports: ProcessorNodePortsOutput,
-    /// Runtime components (dynamic, varies based on processor state).
-    pub components: serde_json::Map<String, serde_json::Value>,
-}
-
-/// Container for processor input and output ports.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct ProcessorNodePortsOutput {
-    /// Input ports that receive data.
-    pub inputs: Vec<PortInfoOutput>,
-    /// Output ports that send data.
-    pub outputs: Vec<PortInfoOutput>,
-}
-
-/// Metadata about a port.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct PortInfoOutput {
-    /// Port name (e.g., "video_in", "audio_out").
-    pub name: String,
-    /// Structured schema identifier of the data flowing through this port.
-    ///
-    /// Resolved from the build-time embedded-schema segment table. `None`
-    /// when the port's declared schema doesn't have a structured-segment
-    /// representation (legacy reverse-DNS schemas, or schemas not in the
-    /// resolver dep graph).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub data_type: Option<SchemaIdentOutput>,
-    /// Kind of port: data, event, or control.
-    #[serde(default)]
-    pub port_kind: PortKindOutput,
-}
-
-/// The kind of port - determines how data flows.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-#[serde(rename_all = "lowercase")]
-pub enum PortKindOutput {
-    #[default]
-    Data,
-    Event,
-    Control,
-}
-
-/// A link (connection) between two processor ports.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct LinkOutput {
-    /// Unique identifier for this link.
-    pub id: String,
-    /// Source endpoint (output port).
-    pub source: LinkPortRefOutput,
-    /// Target endpoint (input port).
-    pub target: LinkPortRefOutput,
-    /// Ring buffer capacity for the channel.
-    #[serde(default)]
-    pub capacity: usize,
-    /// Current state of the link.
-    #[serde(default)]
-    pub state: LinkStateOutput,
-    /// Runtime components (dynamic, varies based on link state).
-    pub components: serde_json::Map<String, serde_json::Value>,
-}
-
-/// Reference to a port on a processor.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct LinkPortRefOutput {
-    /// Processor instance ID.
-    pub processor_id: String,
-    /// Port name on that processor.
-    pub port_name: String,
-}
-
-/// State of a link in the graph.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-#[serde(rename_all = "lowercase")]
-pub enum LinkStateOutput {
-    /// Link exists in graph but not yet wired.
-    #[default]
-    Pending,
-    /// Link is actively wired with a ring buffer channel.
-    Wired,
-    /// Link is being disconnected.
-    Disconnecting,
-    /// Link was disconnected.
-    Disconnected,
-    /// Link is in error state.
-    Error,
-}
-
-// =============================================================================
-// Registry Response Schema (/api/registry)
-// =============================================================================
-
-/// Response from the `/api/registry` endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct RegistryResponse {
-    /// Available processor types with their descriptors.
-    pub processors: Vec<ProcessorDescriptorOutput>,
-    /// Available data frame schemas.
-    pub schemas: Vec<SchemaDescriptorOutput>,
-}
-
-/// Runtime environment for a processor.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-#[serde(rename_all = "lowercase")]
-pub enum ProcessorRuntimeOutput {
-    #[default]
-    Rust,
-    Python,
-    TypeScript,
-}
-
-/// Descriptor for a processor type.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct ProcessorDescriptorOutput {
-    /// Structured processor identity — `@org/package/Type@version`
-    /// rendered as four typed fields on the wire.
-    pub name: SchemaIdentOutput,
-    /// Human-readable description.
-    pub description: String,
-    /// Semantic version string.
-    pub version: String,
-    /// Repository URL.
-    pub repository: String,
-    /// Runtime environment.
-    #[serde(default)]
-    pub runtime: ProcessorRuntimeOutput,
-    /// Entrypoint for non-Rust runtimes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub entrypoint: Option<String>,
-    /// Reference to config schema.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub config_schema: Option<String>,
-    /// Input port descriptors.
-    pub inputs: Vec<PortDescriptorOutput>,
-    /// Output port descriptors.
-    pub outputs: Vec<PortDescriptorOutput>,
-    /// Code examples in different languages.
-    pub examples: CodeExamplesOutput,
-}
-
-/// A configuration field for a processor.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct ConfigFieldOutput {
-    /// Field name.
-    pub name: String,
-    /// Field type as string (e.g., "String", "u32", "Option<PathBuf>").
-    #[serde(rename = "type")]
-    pub field_type: String,
-    /// Whether the field is required.
-    pub required: bool,
-    /// Human-readable description.
-    pub description: String,
-}
-
-/// Descriptor for a processor port.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct PortDescriptorOutput {
-    /// Port name.
-    pub name: String,
-    /// Human-readable description.
-    pub description: String,
-    /// Structured schema identifier for data flowing through this port.
-    ///
-    /// Resolved from the build-time embedded-schema segment table. `None`
-    /// when the port's declared schema doesn't have a structured-segment
-    /// representation.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub schema: Option<SchemaIdentOutput>,
-    /// Whether the port is required.
-    pub required: bool,
-}
-
-/// Code examples for a processor in different languages.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct CodeExamplesOutput {
-    /// Rust code example.
-    pub rust: String,
-    /// Python code example.
-    pub python: String,
-    /// TypeScript code example.
-    pub typescript: String,
-}
-
-/// Descriptor for a data schema.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct SchemaDescriptorOutput {
-    /// Schema name (e.g., "VideoFrame", "AudioFrame").
-    pub name: String,
-    /// Semantic version.
-    pub version: SemanticVersionOutput,
-    /// Fields in this schema.
-    pub fields: Vec<SchemaFieldOutput>,
-    /// How data is read from the link buffer.
-    pub read_behavior: LinkBufferReadModeOutput,
-    /// Default ring buffer capacity.
-    pub default_capacity: usize,
-}
-
-/// A field in a data schema.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-pub struct SchemaFieldOutput {
-    /// Field name.
-    pub name: String,
-    /// Human-readable description.
-    #[serde(default, skip_serializing_if = "String::is_empty")]
-    pub description: String,
-    /// Type name (e.g., "u32", "Arc<wgpu::Texture>").
-    #[serde(rename = "type")]
-    pub type_name: String,
-    /// Shape for multi-dimensional fields (e.g., [512] for embeddings).
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub shape: Vec<usize>,
-    /// Whether this is an internal field (not serializable).
-    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
-    pub internal: bool,
-}
-
-/// How data is read from the link buffer.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum LinkBufferReadModeOutput {
-    /// Drain buffer and return only the newest frame (optimal for video).
-    #[default]
-    SkipToLatest,
-    /// Read next frame in FIFO order (required for audio).
-    ReadNextInOrder,
-}
+use crate::core::graph::{GraphEdgeWithComponents, GraphNodeWithComponents};
 
 // =============================================================================
 // Conversion from Runtime Types
@@ -286,6 +32,8 @@ pub enum LinkBufferReadModeOutput {
 
 impl From<&crate::core::graph::ProcessorNode> for ProcessorNodeOutput {
     fn from(node: &crate::core::graph::ProcessorNode) -> Self {
+        let mut components = node.serialize_components();
+        crate::core::graph::merge_reported_components(node.id.as_str(), &mut components);
         Self {
             id: node.id.to_string(),
             processor_type: SchemaIdentOutput::from(&node.processor_type),
@@ -293,7 +41,7 @@ impl From<&crate::core::graph::ProcessorNode> for ProcessorNodeOutput {
             config: node.config.clone(),
             config_checksum: node.config_checksum,
             ports: ProcessorNodePortsOutput::from(&node.ports),
-            components: node.serialize_components(),
+            components,
         }
     }
 }
@@ -0,0 +1,320 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! MIDI control mappings: binding hardware knobs and faders to processor
+//! config parameters.
+//!
+//! `@tatolab/midi/MidiInput` publishes every control change as a custom
+//! event on [`MIDI_CONTROL_CHANGE_TOPIC`]. Each [`MidiControlBinding`]
+//! whose channel, controller and port match scales the 0-127 value onto
+//! `min..=max` along its [`MorphCurve`] and writes it into the processor's
+//! config at `parameter`, a JSON pointer. Set with
+//! [`Runner::set_midi_mappings`](crate::core::runtime::Runner::set_midi_mappings).
+//!
+//! ```json
+//! [{ "controller": 7, "channel": 1, "processor_id": "mixer",
+//!    "parameter": "/inputs/0/gain_db", "min": -60.0, "max": 6.0 }]
+//! ```
+//!
+//! The written value takes the type already at `parameter`: integers stay
+//! integers (rounded), booleans switch at the controller's midpoint.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::preset_morph::MorphCurve;
+use crate::core::{Error, Result};
+
+/// Custom-event topic `@tatolab/midi/MidiInput` publishes control changes on.
+pub const MIDI_CONTROL_CHANGE_TOPIC: &str = "midi:control_change";
+
+/// Highest 7-bit controller number and value.
+const MIDI_DATA_MAX: u8 = 127;
+
+/// One control change, as published on [`MIDI_CONTROL_CHANGE_TOPIC`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MidiControlChange {
+    /// MIDI port the change arrived on.
+    #[serde(default)]
+    pub port: Option<String>,
+    /// `1..=16`.
+    pub channel: u8,
+    pub controller: u8,
+    /// `0..=127`.
+    pub value: u8,
+}
+
+/// Binds one MIDI controller to one config parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MidiControlBinding {
+    /// Controller number, `0..=127`.
+    pub controller: u8,
+    /// MIDI channel `1..=16`. Omitted: any channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u8>,
+    /// MIDI port name. Omitted: any port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    #[schema(value_type = String)]
+    pub processor_id: ProcessorUniqueId,
+    /// JSON pointer into the processor's config (`/inputs/0/gain_db`).
+    /// Missing objects along the way are created.
+    pub parameter: String,
+    /// Value at controller value 0.
+    #[serde(default)]
+    pub min: f64,
+    /// Value at controller value 127. May be below `min` to invert.
+    #[serde(default = "default_binding_max")]
+    pub max: f64,
+    #[serde(default)]
+    pub curve: MorphCurve,
+}
+
+fn default_binding_max() -> f64 {
+    1.0
+}
+
+impl MidiControlBinding {
+    pub fn new(
+        controller: u8,
+        processor_id: impl Into<ProcessorUniqueId>,
+        parameter: impl Into<String>,
+        min: f64,
+        max: f64,
+    ) -> Self {
+        Self {
+            controller,
+            channel: None,
+            port: None,
+            processor_id: processor_id.into(),
+            parameter: parameter.into(),
+            min,
+            max,
+            curve: MorphCurve::default(),
+        }
+    }
+
+    pub fn with_channel(mut self, channel: u8) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    pub fn with_port(mut self, port: impl Into<String>) -> Self {
+        self.port = Some(port.into());
+        self
+    }
+
+    pub fn with_curve(mut self, curve: MorphCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Reject out-of-range MIDI numbers, a parameter that isn't a JSON
+    /// pointer to a member, and non-finite bounds.
+    pub fn validate(&self) -> Result<()> {
+        if self.controller > MIDI_DATA_MAX {
+            return Err(Error::Config(format!(
+                "MIDI controller {} is out of range (0-127)",
+                self.controller
+            )));
+        }
+        if let Some(channel) = self.channel
+            && !(1..=16).contains(&channel)
+        {
+            return Err(Error::Config(format!(
+                "MIDI channel {} is out of range (1-16)",
+                channel
+            )));
+        }
+        if !self.parameter.starts_with('/') {
+            return Err(Error::Config(format!(
+                "MIDI binding parameter '{}' must be a JSON pointer such as '/gain_db'",
+                self.parameter
+            )));
+        }
+        if !self.min.is_finite() || !self.max.is_finite() {
+            return Err(Error::Config(format!(
+                "MIDI binding for '{}' needs finite min and max",
+                self.parameter
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, change: &MidiControlChange) -> bool {
+        self.controller == change.controller
+            && self.channel.is_none_or(|channel| channel == change.channel)
+            && self
+                .port
+                .as_ref()
+                .is_none_or(|port| change.port.as_ref() == Some(port))
+    }
+
+    /// `value` (`0..=127`) scaled onto `min..=max` along the curve.
+    pub fn scale(&self, value: u8) -> f64 {
+        let t = self
+            .curve
+            .ease(f64::from(value.min(MIDI_DATA_MAX)) / f64::from(MIDI_DATA_MAX));
+        self.min + (self.max - self.min) * t
+    }
+
+    /// Write `value` into `config` at the binding's parameter, typed after
+    /// what is already there. A null config becomes an object.
+    pub fn apply(&self, config: &mut Value, value: u8) {
+        let scaled = self.scale(value);
+        let slot = pointer_slot(config, &self.parameter);
+        *slot = match slot {
+            Value::Bool(_) => Value::Bool(value > MIDI_DATA_MAX / 2),
+            Value::Number(number) if number.is_i64() => Value::from(scaled.round() as i64),
+            Value::Number(number) if number.is_u64() => Value::from(scaled.round().max(0.0) as u64),
+            _ => serde_json::Number::from_f64(scaled)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        };
+    }
+}
+
+/// The value at JSON pointer `pointer`, creating objects (and `null`
+/// leaves) for missing members and turning non-containers on the way into
+/// objects. Array indices past the end are appended as `null`.
+pub(crate) fn pointer_slot<'a>(config: &'a mut Value, pointer: &str) -> &'a mut Value {
+    let mut slot = config;
+    for token in pointer.split('/').skip(1) {
+        let key = token.replace("~1", "/").replace("~0", "~");
+        let index = key.parse::<usize>().ok().filter(|_| slot.is_array());
+        slot = match index {
+            Some(index) => {
+                if let Value::Array(items) = &mut *slot
+                    && index >= items.len()
+                {
+                    items.resize(index + 1, Value::Null);
+                }
+                &mut slot[index]
+            }
+            None => {
+                // Indexing `null` by a key makes it an object.
+                if !slot.is_object() {
+                    *slot = Value::Null;
+                }
+                &mut slot[key.as_str()]
+            }
+        };
+    }
+    slot
+}
+
+/// Reject any binding [`MidiControlBinding::validate`] rejects.
+pub fn validate_midi_mappings(bindings: &[MidiControlBinding]) -> Result<()> {
+    bindings.iter().try_for_each(MidiControlBinding::validate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(channel: u8, controller: u8, value: u8) -> MidiControlChange {
+        MidiControlChange {
+            port: Some("nanoKONTROL2".into()),
+            channel,
+            controller,
+            value,
+        }
+    }
+
+    #[test]
+    fn matches_on_controller_channel_and_port() {
+        let binding = MidiControlBinding::new(7, "mixer", "/gain_db", -60.0, 6.0);
+        assert!(binding.matches(&change(3, 7, 0)));
+        assert!(!binding.matches(&change(3, 8, 0)));
+
+        let binding = binding.with_channel(1).with_port("nanoKONTROL2");
+        assert!(binding.matches(&change(1, 7, 0)));
+        assert!(!binding.matches(&change(2, 7, 0)));
+        assert!(
+            !binding
+                .clone()
+                .with_port("Launchpad")
+                .matches(&change(1, 7, 0))
+        );
+    }
+
+    #[test]
+    fn scale_spans_min_to_max_along_the_curve() {
+        let binding = MidiControlBinding::new(1, "blur", "/radius", 10.0, 0.0);
+        assert_eq!(binding.scale(0), 10.0);
+        assert_eq!(binding.scale(127), 0.0);
+
+        let binding =
+            MidiControlBinding::new(1, "blur", "/radius", 0.0, 1.0).with_curve(MorphCurve::EaseIn);
+        assert!((binding.scale(64) - (64.0f64 / 127.0).powi(2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn apply_keeps_the_type_already_in_the_config() {
+        let mut config = json!({ "radius": 2, "gain": 0.5, "bypass": false });
+        MidiControlBinding::new(1, "fx", "/radius", 0.0, 10.0).apply(&mut config, 64);
+        MidiControlBinding::new(2, "fx", "/gain", 0.0, 2.0).apply(&mut config, 127);
+        MidiControlBinding::new(3, "fx", "/bypass", 0.0, 1.0).apply(&mut config, 100);
+        assert_eq!(config, json!({ "radius": 5, "gain": 2.0, "bypass": true }));
+    }
+
+    #[test]
+    fn apply_creates_missing_members_and_indexes_arrays() {
+        let mut config = Value::Null;
+        MidiControlBinding::new(1, "mixer", "/ducking/depth_db", 0.0, -24.0)
+            .apply(&mut config, 127);
+        assert_eq!(config, json!({ "ducking": { "depth_db": -24.0 } }));
+
+        let mut config = json!({ "inputs": [{ "input": "a" }, { "input": "b" }] });
+        MidiControlBinding::new(7, "mixer", "/inputs/1/gain_db", -60.0, 0.0)
+            .apply(&mut config, 127);
+        assert_eq!(
+            config,
+            json!({ "inputs": [{ "input": "a" }, { "input": "b", "gain_db": 0.0 }] })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_numbers_and_parameters() {
+        assert!(
+            MidiControlBinding::new(7, "fx", "/gain", 0.0, 1.0)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            MidiControlBinding::new(128, "fx", "/gain", 0.0, 1.0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            MidiControlBinding::new(7, "fx", "/gain", 0.0, 1.0)
+                .with_channel(0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            MidiControlBinding::new(7, "fx", "gain", 0.0, 1.0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            MidiControlBinding::new(7, "fx", "/gain", 0.0, f64::NAN)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn binding_json_defaults_to_a_unit_range() {
+        let binding: MidiControlBinding = serde_json::from_value(json!({
+            "controller": 74, "processor_id": "filter", "parameter": "/cutoff"
+        }))
+        .unwrap();
+        assert_eq!(
+            binding,
+            MidiControlBinding::new(74, "filter", "/cutoff", 0.0, 1.0)
+        );
+    }
+}
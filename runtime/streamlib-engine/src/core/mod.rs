@@ -28,32 +28,54 @@ pub(crate) mod test_support;
 // Customer-facing modules. Module-path stays `pub` so consumers
 // can reach `streamlib::sdk::<name>` via the SDK's per-module
 // re-exports.
+pub mod assets;
+pub mod chaos;
 pub mod color;
 pub mod context;
 pub mod descriptors;
 pub mod display_info;
 pub mod error;
 pub mod execution;
+pub mod fonts;
+pub mod frame_snapshot;
 pub mod graph;
+pub mod graph_edit_history;
+pub mod graph_patch;
 pub mod graph_snapshot;
 pub mod json_schema;
 pub mod media_clock;
+pub mod midi_mapping;
+pub mod parameter_automation;
+pub mod port_recording;
 pub mod prelude;
+pub mod preset_morph;
+pub mod processor_schedule;
 pub mod processors;
 pub mod pubsub;
 pub mod rhi;
 pub mod runtime;
 pub mod sync;
+pub mod testing;
 pub mod texture;
 pub mod utils;
 
 // Customer-facing modules (wildcard re-exports stay).
+pub use assets::*;
+pub use chaos::*;
 pub use context::*;
 pub use descriptors::*;
 pub use error::*;
 pub use execution::*;
+pub use frame_snapshot::*;
 pub use graph::*;
+pub use graph_edit_history::*;
+pub use graph_patch::*;
 pub use graph_snapshot::*;
+pub use midi_mapping::*;
+pub use parameter_automation::*;
+pub use port_recording::*;
+pub use preset_morph::*;
+pub use processor_schedule::*;
 pub use processors::*;
 pub use rhi::{GlContext, GlTextureBinding, NativeTextureHandle, RhiBackend, gl_constants};
 pub use runtime::*;
@@ -0,0 +1,430 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Scheduled parameter changes: setting one config parameter of a running
+//! processor at a media-clock time, optionally ramping to it.
+//!
+//! A [`ParameterChange`] names a processor, a parameter (a top-level config
+//! field or a JSON pointer), the value, and when it takes effect — a
+//! [`MediaClock`](crate::core::media_clock::MediaClock) time in
+//! nanoseconds, the clock frame `timestamp_ns` values are on. Submitted
+//! with [`Runner::set_parameter`](crate::core::runtime::Runner::set_parameter),
+//! it is queued on the processor's parameter port and delivered by the
+//! processor's own thread, through the same `apply_config_json` path as a
+//! config update, right before the first `process()` call at or after the
+//! scheduled time — not whenever the next graph commit happens to land.
+//!
+//! ```json
+//! { "processor_id": "blur", "parameter": "radius", "value": 12.0,
+//!   "at_ns": 5000000000, "interpolation": "linear" }
+//! ```
+//!
+//! Interpolation follows Web Audio's `AudioParam` automation: a `step`
+//! jumps at `at_ns`; a `linear` or `exponential` ramp starts from the
+//! previous scheduled value for the same parameter (or the value in the
+//! config when the change was scheduled) and arrives at `at_ns`, updated
+//! on every tick in between. Only numbers ramp; anything else steps. The
+//! written value takes the type already at the parameter: integers stay
+//! integers (rounded), booleans switch at 0.5.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::midi_mapping::pointer_slot;
+use crate::core::{Error, Result};
+
+/// How a scheduled value is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterInterpolation {
+    /// Jump to the value at the scheduled time.
+    #[default]
+    Step,
+    /// Ramp linearly from the previous value, arriving at the scheduled time.
+    Linear,
+    /// Ramp geometrically from the previous value — even steps in ratio,
+    /// which sounds even for gains and frequencies. Falls back to linear
+    /// when either end is zero or they differ in sign.
+    Exponential,
+}
+
+impl ParameterInterpolation {
+    /// The value between `from` (at `from_ns`) and `to` (at `to_ns`) at
+    /// `now_ns`, or `None` when nothing ramps: a step, a non-number, or a
+    /// ramp that hasn't an interval to span.
+    fn ramp(
+        self,
+        from: &Value,
+        from_ns: i64,
+        to: &Value,
+        to_ns: i64,
+        now_ns: i64,
+    ) -> Option<Value> {
+        if self == Self::Step || to_ns <= from_ns {
+            return None;
+        }
+        let (from, to) = (from.as_f64()?, to.as_f64()?);
+        let t = ((now_ns - from_ns) as f64 / (to_ns - from_ns) as f64).clamp(0.0, 1.0);
+        let value = if self == Self::Exponential && from * to > 0.0 {
+            from * (to / from).powf(t)
+        } else {
+            from + (to - from) * t
+        };
+        serde_json::Number::from_f64(value).map(Value::Number)
+    }
+}
+
+/// Set one parameter of a processor's config at a media-clock time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ParameterChange {
+    #[schema(value_type = String)]
+    pub processor_id: ProcessorUniqueId,
+    /// Top-level config field (`radius`) or JSON pointer into the config
+    /// (`/inputs/0/gain_db`). Missing objects along a pointer are created.
+    pub parameter: String,
+    pub value: Value,
+    /// Media-clock time in nanoseconds the value takes effect (a ramp
+    /// arrives). Omitted, or already past: the processor's next tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at_ns: Option<i64>,
+    #[serde(default)]
+    pub interpolation: ParameterInterpolation,
+}
+
+impl ParameterChange {
+    pub fn new(
+        processor_id: impl Into<ProcessorUniqueId>,
+        parameter: impl Into<String>,
+        value: Value,
+    ) -> Self {
+        Self {
+            processor_id: processor_id.into(),
+            parameter: parameter.into(),
+            value,
+            at_ns: None,
+            interpolation: ParameterInterpolation::default(),
+        }
+    }
+
+    pub fn at_ns(mut self, at_ns: i64) -> Self {
+        self.at_ns = Some(at_ns);
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: ParameterInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Reject an empty parameter.
+    pub fn validate(&self) -> Result<()> {
+        if self.parameter.trim_start_matches('/').is_empty() {
+            return Err(Error::Config(format!(
+                "parameter change for '{}' names no parameter",
+                self.processor_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// The parameter as a JSON pointer.
+    pub fn pointer(&self) -> String {
+        if self.parameter.starts_with('/') {
+            self.parameter.clone()
+        } else {
+            format!("/{}", self.parameter.replace('~', "~0").replace('/', "~1"))
+        }
+    }
+}
+
+/// `value` typed after `existing`: integers stay integers (rounded),
+/// booleans switch at 0.5; anything else is written as given.
+fn typed_like(existing: &Value, value: Value) -> Value {
+    match (existing, value.as_f64()) {
+        (Value::Bool(_), Some(number)) => Value::Bool(number >= 0.5),
+        (Value::Number(existing), Some(number)) if existing.is_i64() => {
+            Value::from(number.round() as i64)
+        }
+        (Value::Number(existing), Some(number)) if existing.is_u64() => {
+            Value::from(number.round().max(0.0) as u64)
+        }
+        _ => value,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ScheduledValue {
+    at_ns: i64,
+    value: Value,
+    interpolation: ParameterInterpolation,
+}
+
+/// The pending values of one parameter, in time order.
+#[derive(Debug)]
+struct ParameterLane {
+    pointer: String,
+    /// Where the next ramp starts from: the last value reached and when.
+    anchor_ns: i64,
+    anchor: Value,
+    events: Vec<ScheduledValue>,
+}
+
+/// A processor's parameter port: the changes scheduled for it and its
+/// config as last delivered. Shared between the runtime, which schedules,
+/// and the processor thread, which delivers.
+#[derive(Debug, Default)]
+pub(crate) struct ParameterTimeline {
+    config: Value,
+    lanes: Vec<ParameterLane>,
+    /// Delivered since the graph's copy of the config was last updated.
+    unsynced: bool,
+}
+
+impl ParameterTimeline {
+    pub fn new(config: Value) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// The config was replaced outside the port (a config update, a morph
+    /// tick); later deliveries build on it.
+    pub fn set_config(&mut self, config: Value) {
+        self.config = config;
+    }
+
+    /// Queue `change`, scheduled at `now_ns`. Changes at the same time keep
+    /// their submission order.
+    pub fn schedule(&mut self, change: ParameterChange, now_ns: i64) {
+        let pointer = change.pointer();
+        let event = ScheduledValue {
+            at_ns: change.at_ns.unwrap_or(now_ns),
+            value: change.value,
+            interpolation: change.interpolation,
+        };
+        let lane = match self.lanes.iter().position(|lane| lane.pointer == pointer) {
+            Some(index) => &mut self.lanes[index],
+            None => {
+                let anchor = self
+                    .config
+                    .pointer(&pointer)
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                self.lanes.push(ParameterLane {
+                    pointer,
+                    anchor_ns: now_ns,
+                    anchor,
+                    events: Vec::new(),
+                });
+                let last = self.lanes.len() - 1;
+                &mut self.lanes[last]
+            }
+        };
+        let index = lane
+            .events
+            .partition_point(|queued| queued.at_ns <= event.at_ns);
+        lane.events.insert(index, event);
+    }
+
+    /// Advance to `now_ns`, returning the config to deliver when any
+    /// parameter changed.
+    pub fn due(&mut self, now_ns: i64) -> Option<Value> {
+        let mut changed = false;
+        for lane in &mut self.lanes {
+            let mut value = None;
+            let passed = lane.events.partition_point(|event| event.at_ns <= now_ns);
+            for event in lane.events.drain(..passed) {
+                lane.anchor_ns = event.at_ns;
+                lane.anchor = event.value.clone();
+                value = Some(event.value);
+            }
+            if let Some(next) = lane.events.first()
+                && let Some(ramped) = next.interpolation.ramp(
+                    &lane.anchor,
+                    lane.anchor_ns,
+                    &next.value,
+                    next.at_ns,
+                    now_ns,
+                )
+            {
+                value = Some(ramped);
+            }
+            if let Some(value) = value {
+                let slot = pointer_slot(&mut self.config, &lane.pointer);
+                let value = typed_like(slot, value);
+                if *slot != value {
+                    *slot = value;
+                    changed = true;
+                }
+            }
+        }
+        self.lanes.retain(|lane| !lane.events.is_empty());
+        if !changed {
+            return None;
+        }
+        self.unsynced = true;
+        Some(self.config.clone())
+    }
+
+    /// Media-clock time of the next delivery: the start of a running ramp,
+    /// otherwise the earliest scheduled value.
+    pub fn next_due_ns(&self) -> Option<i64> {
+        self.lanes
+            .iter()
+            .filter_map(|lane| {
+                let next = lane.events.first()?;
+                let ramps = next
+                    .interpolation
+                    .ramp(
+                        &lane.anchor,
+                        lane.anchor_ns,
+                        &next.value,
+                        next.at_ns,
+                        lane.anchor_ns,
+                    )
+                    .is_some();
+                Some(if ramps { lane.anchor_ns } else { next.at_ns })
+            })
+            .min()
+    }
+
+    /// Scheduled values not yet reached.
+    pub fn pending(&self) -> usize {
+        self.lanes.iter().map(|lane| lane.events.len()).sum()
+    }
+
+    /// The delivered config, when the graph's copy is behind it.
+    pub fn unsynced_config(&self) -> Option<&Value> {
+        self.unsynced.then_some(&self.config)
+    }
+
+    pub fn mark_synced(&mut self) {
+        self.unsynced = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    fn change(parameter: &str, value: Value, at_ns: i64) -> ParameterChange {
+        ParameterChange::new("blur", parameter, value).at_ns(at_ns)
+    }
+
+    #[test]
+    fn steps_land_at_their_time_in_order() {
+        let mut timeline = ParameterTimeline::new(json!({ "radius": 1.0 }));
+        timeline.schedule(change("radius", json!(8.0), 2 * SECOND), 0);
+        timeline.schedule(change("radius", json!(4.0), SECOND), 0);
+        assert_eq!(timeline.next_due_ns(), Some(SECOND));
+
+        assert_eq!(timeline.due(SECOND - 1), None);
+        assert_eq!(timeline.due(SECOND), Some(json!({ "radius": 4.0 })));
+        assert_eq!(timeline.due(SECOND + 1), None);
+        assert_eq!(timeline.due(3 * SECOND), Some(json!({ "radius": 8.0 })));
+        assert_eq!(timeline.pending(), 0);
+        assert_eq!(timeline.next_due_ns(), None);
+    }
+
+    #[test]
+    fn linear_ramps_run_from_the_previous_value() {
+        let mut timeline = ParameterTimeline::new(json!({ "radius": 0.0 }));
+        timeline.schedule(
+            change("radius", json!(10.0), 2 * SECOND)
+                .with_interpolation(ParameterInterpolation::Linear),
+            0,
+        );
+        assert_eq!(timeline.next_due_ns(), Some(0));
+        assert_eq!(timeline.due(SECOND / 2), Some(json!({ "radius": 2.5 })));
+        assert_eq!(timeline.due(SECOND), Some(json!({ "radius": 5.0 })));
+        assert_eq!(timeline.due(2 * SECOND), Some(json!({ "radius": 10.0 })));
+
+        // The next ramp starts where that one arrived.
+        timeline.schedule(
+            change("radius", json!(0.0), 4 * SECOND)
+                .with_interpolation(ParameterInterpolation::Linear),
+            2 * SECOND,
+        );
+        assert_eq!(timeline.due(3 * SECOND), Some(json!({ "radius": 5.0 })));
+    }
+
+    #[test]
+    fn exponential_ramps_move_in_even_ratios() {
+        let mut timeline = ParameterTimeline::new(json!({ "cutoff_hz": 100.0 }));
+        timeline.schedule(
+            change("cutoff_hz", json!(10_000.0), 2 * SECOND)
+                .with_interpolation(ParameterInterpolation::Exponential),
+            0,
+        );
+        let config = timeline.due(SECOND).unwrap();
+        assert!((config["cutoff_hz"].as_f64().unwrap() - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn non_numeric_ramps_step_at_their_time() {
+        let mut timeline = ParameterTimeline::new(json!({ "mode": "soft" }));
+        timeline.schedule(
+            change("mode", json!("hard"), SECOND)
+                .with_interpolation(ParameterInterpolation::Linear),
+            0,
+        );
+        assert_eq!(timeline.next_due_ns(), Some(SECOND));
+        assert_eq!(timeline.due(SECOND / 2), None);
+        assert_eq!(timeline.due(SECOND), Some(json!({ "mode": "hard" })));
+    }
+
+    #[test]
+    fn values_keep_the_config_type_and_pointers_create_members() {
+        let mut timeline = ParameterTimeline::new(json!({ "taps": 3, "bypass": false }));
+        timeline.schedule(change("taps", json!(4.6), 0), 0);
+        timeline.schedule(change("bypass", json!(1), 0), 0);
+        timeline.schedule(change("/ducking/depth_db", json!(-12.0), 0), 0);
+        assert_eq!(
+            timeline.due(0),
+            Some(json!({ "taps": 5, "bypass": true, "ducking": { "depth_db": -12.0 } }))
+        );
+        assert_eq!(
+            timeline.unsynced_config(),
+            Some(&json!({ "taps": 5, "bypass": true, "ducking": { "depth_db": -12.0 } }))
+        );
+        timeline.mark_synced();
+        assert_eq!(timeline.unsynced_config(), None);
+    }
+
+    #[test]
+    fn replaced_configs_are_built_on() {
+        let mut timeline = ParameterTimeline::new(json!({ "radius": 1.0 }));
+        timeline.schedule(change("radius", json!(2.0), SECOND), 0);
+        timeline.set_config(json!({ "radius": 1.0, "sigma": 0.5 }));
+        assert_eq!(
+            timeline.due(SECOND),
+            Some(json!({ "radius": 2.0, "sigma": 0.5 }))
+        );
+    }
+
+    #[test]
+    fn parameter_names_become_pointers() {
+        assert_eq!(change("radius", json!(1), 0).pointer(), "/radius");
+        assert_eq!(change("a/b", json!(1), 0).pointer(), "/a~1b");
+        assert_eq!(
+            change("/inputs/0/gain_db", json!(1), 0).pointer(),
+            "/inputs/0/gain_db"
+        );
+        assert!(change("/", json!(1), 0).validate().is_err());
+        assert!(change("radius", json!(1), 0).validate().is_ok());
+    }
+
+    #[test]
+    fn change_json_defaults_to_an_immediate_step() {
+        let change: ParameterChange = serde_json::from_value(json!({
+            "processor_id": "blur", "parameter": "radius", "value": 3
+        }))
+        .unwrap();
+        assert_eq!(change, ParameterChange::new("blur", "radius", json!(3)));
+    }
+}
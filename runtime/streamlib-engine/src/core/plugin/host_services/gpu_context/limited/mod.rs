@@ -53,6 +53,9 @@ pub(in crate::core::plugin::host_services) use texture::{
 pub(in crate::core::plugin::host_services) use texture_registration::{
     host_gpu_lim_clone_texture_registration, host_gpu_lim_drop_texture_registration,
     host_gpu_lim_resolve_texture_registration_by_surface_id,
-    host_gpu_lim_texture_registration_current_layout, host_gpu_lim_texture_registration_texture,
-    host_gpu_lim_texture_registration_update_layout,
+    host_gpu_lim_texture_registration_current_layout,
+    host_gpu_lim_texture_registration_producer_timeline,
+    host_gpu_lim_texture_registration_set_producer_timeline,
+    host_gpu_lim_texture_registration_texture, host_gpu_lim_texture_registration_update_layout,
+    host_gpu_lim_texture_registration_wait_producer,
 };
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 //! `GpuContextLimitedAccessVTable` TextureRegistration Arc-handle
-//! lifecycle + method dispatch (v6).
+//! lifecycle + method dispatch (v6) + producer sync (v16).
 //!
 //! Combines two banner-bounded sections of the original file: the
 //! `Arc<TextureRegistration>` clone/drop pair plus the per-method
@@ -231,3 +231,139 @@ pub(in crate::core::plugin::host_services) unsafe extern "C" fn host_gpu_lim_res
         1,
     )
 }
+
+// -------------------------------------------------------------------------
+// TextureRegistration producer sync (v16)
+// -------------------------------------------------------------------------
+
+pub(in crate::core::plugin::host_services) unsafe extern "C" fn host_gpu_lim_texture_registration_set_producer_timeline(
+    handle: *const c_void,
+    timeline_handle: *const c_void,
+    value: u64,
+) {
+    run_host_extern_c(
+        "host_gpu_lim_texture_registration_set_producer_timeline",
+        || {
+            if handle.is_null() {
+                return;
+            }
+            #[cfg(target_os = "linux")]
+            {
+                use crate::vulkan::rhi::HostVulkanTimelineSemaphore;
+                // SAFETY: `timeline_handle` is the borrowed
+                // `Arc::into_raw(Arc<HostVulkanTimelineSemaphore>)` pointer
+                // of the caller's exportable-timeline PluginAbiObject.
+                // Bump + `from_raw` mints our own strong count for the
+                // registration slot; the caller's reference is untouched.
+                let sync_point = (!timeline_handle.is_null()).then(|| unsafe {
+                    Arc::increment_strong_count(
+                        timeline_handle as *const HostVulkanTimelineSemaphore,
+                    );
+                    crate::core::rhi::GpuSyncPoint::Timeline {
+                        semaphore: Arc::from_raw(
+                            timeline_handle as *const HostVulkanTimelineSemaphore,
+                        ),
+                        value,
+                    }
+                });
+                // SAFETY: `handle` is `Arc::into_raw(...)`-shaped.
+                let inner = unsafe {
+                    &*(handle
+                        as *const crate::core::context::texture_registration::TextureRegistrationInner)
+                };
+                *inner.producer_sync.lock() = sync_point;
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                let _ = (handle, timeline_handle, value);
+            }
+        },
+        (),
+    )
+}
+
+pub(in crate::core::plugin::host_services) unsafe extern "C" fn host_gpu_lim_texture_registration_producer_timeline(
+    handle: *const c_void,
+    out_timeline: *mut c_void,
+    out_value: *mut u64,
+) -> i32 {
+    run_host_extern_c(
+        "host_gpu_lim_texture_registration_producer_timeline",
+        || -> i32 {
+            if handle.is_null() || out_timeline.is_null() || out_value.is_null() {
+                return 0;
+            }
+            #[cfg(target_os = "linux")]
+            {
+                // SAFETY: `handle` is `Arc::into_raw(...)`-shaped.
+                let inner = unsafe {
+                    &*(handle
+                        as *const crate::core::context::texture_registration::TextureRegistrationInner)
+                };
+                let Some(crate::core::rhi::GpuSyncPoint::Timeline { semaphore, value }) =
+                    inner.producer_sync.lock().clone()
+                else {
+                    return 0;
+                };
+                // SAFETY: out-params point at caller-allocated storage for
+                // a `(handle, methods)` timeline envelope and a `u64`.
+                unsafe {
+                    std::ptr::write(
+                        out_timeline as *mut crate::core::rhi::HostTimelineSemaphore,
+                        crate::core::rhi::HostTimelineSemaphore::from_arc(semaphore),
+                    );
+                    std::ptr::write(out_value, value);
+                }
+                1
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                0
+            }
+        },
+        0,
+    )
+}
+
+pub(in crate::core::plugin::host_services) unsafe extern "C" fn host_gpu_lim_texture_registration_wait_producer(
+    handle: *const c_void,
+    timeout_ns: u64,
+    err_buf: *mut u8,
+    err_buf_cap: usize,
+    err_len: *mut usize,
+) -> i32 {
+    run_host_extern_c(
+        "host_gpu_lim_texture_registration_wait_producer",
+        || -> i32 {
+            if handle.is_null() {
+                return 0;
+            }
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            {
+                // SAFETY: `handle` is `Arc::into_raw(...)`-shaped.
+                let inner = unsafe {
+                    &*(handle
+                        as *const crate::core::context::texture_registration::TextureRegistrationInner)
+                };
+                // Clone out of the lock so a slow wait never blocks a
+                // producer publishing its next point.
+                let Some(sync_point) = inner.producer_sync.lock().clone() else {
+                    return 0;
+                };
+                match sync_point.wait(std::time::Duration::from_nanos(timeout_ns)) {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        write_err(&format!("{}", e), err_buf, err_buf_cap, err_len);
+                        1
+                    }
+                }
+            }
+            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            {
+                let _ = (timeout_ns, err_buf, err_buf_cap, err_len);
+                0
+            }
+        },
+        1,
+    )
+}
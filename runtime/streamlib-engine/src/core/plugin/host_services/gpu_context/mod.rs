@@ -82,8 +82,11 @@ use limited::{
     host_gpu_lim_resolve_pixel_buffer_by_surface_id, host_gpu_lim_resolve_texture_by_surface_id,
     host_gpu_lim_resolve_texture_registration_by_surface_id, host_gpu_lim_strong_count_pixel_buffer,
     host_gpu_lim_surface_store, host_gpu_lim_texture_native_dma_buf_fd,
-    host_gpu_lim_texture_registration_current_layout, host_gpu_lim_texture_registration_texture,
-    host_gpu_lim_texture_registration_update_layout, host_gpu_lim_unregister_texture,
+    host_gpu_lim_texture_registration_current_layout,
+    host_gpu_lim_texture_registration_producer_timeline,
+    host_gpu_lim_texture_registration_set_producer_timeline,
+    host_gpu_lim_texture_registration_texture, host_gpu_lim_texture_registration_update_layout,
+    host_gpu_lim_texture_registration_wait_producer, host_gpu_lim_unregister_texture,
     host_gpu_lim_update_texture_registration_layout,
 };
 
@@ -295,6 +298,10 @@ pub static HOST_GPU_CONTEXT_LIMITED_ACCESS_VTABLE: GpuContextLimitedAccessVTable
         escalate_begin: host_gpu_lim_escalate_begin,
         escalate_end: host_gpu_lim_escalate_end,
         texture_native_dma_buf_fd: host_gpu_lim_texture_native_dma_buf_fd,
+        texture_registration_set_producer_timeline:
+            host_gpu_lim_texture_registration_set_producer_timeline,
+        texture_registration_producer_timeline: host_gpu_lim_texture_registration_producer_timeline,
+        texture_registration_wait_producer: host_gpu_lim_texture_registration_wait_producer,
     };
 
 /// Pointer to the [`GpuContextLimitedAccessVTable`] this plugin should
@@ -488,6 +495,39 @@ mod gpu_lim_tier1_wire_format_tests {
         }
     }
 
+    #[test]
+    fn texture_registration_producer_sync_slots_handle_null() {
+        let mut out_timeline = [0usize; 2];
+        let mut out_value = 0u64;
+        let mut err_buf = [0u8; 64];
+        let mut err_len = 0usize;
+        unsafe {
+            (HOST_GPU_CONTEXT_LIMITED_ACCESS_VTABLE.texture_registration_set_producer_timeline)(
+                std::ptr::null(),
+                std::ptr::null(),
+                7,
+            );
+            let hit = (HOST_GPU_CONTEXT_LIMITED_ACCESS_VTABLE
+                .texture_registration_producer_timeline)(
+                std::ptr::null(),
+                out_timeline.as_mut_ptr() as *mut c_void,
+                &mut out_value,
+            );
+            assert_eq!(hit, 0);
+            let status = (HOST_GPU_CONTEXT_LIMITED_ACCESS_VTABLE
+                .texture_registration_wait_producer)(
+                std::ptr::null(),
+                0,
+                err_buf.as_mut_ptr(),
+                err_buf.len(),
+                &mut err_len,
+            );
+            assert_eq!(status, 0, "null registration has nothing to wait on");
+        }
+        assert_eq!(out_value, 0);
+        assert_eq!(err_len, 0);
+    }
+
     // ------------------------------------------------------------------
     // Update / register callbacks (no err_buf, no return) — null gpu
     // handle is a documented no-op
@@ -723,6 +723,32 @@ unsafe extern "C" fn host_pubsub_publish(
                 crate::core::runtime::request_runtime_shutdown_from_plugin_abi_boundary(&reason);
                 return;
             }
+            if topic == streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS {
+                match rmp_serde::from_slice::<crate::core::context::ReferenceSignalStatus>(
+                    event_bytes,
+                ) {
+                    Ok(status) => crate::core::context::report_reference_signal(status),
+                    Err(e) => tracing::warn!(
+                        target: "streamlib::plugin",
+                        "host_pubsub_publish: malformed reference-signal status: {}",
+                        e
+                    ),
+                }
+                return;
+            }
+            if topic == streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT {
+                match rmp_serde::from_slice::<crate::core::graph::ProcessorComponentReport>(
+                    event_bytes,
+                ) {
+                    Ok(report) => crate::core::graph::report_processor_component(report),
+                    Err(e) => tracing::warn!(
+                        target: "streamlib::plugin",
+                        "host_pubsub_publish: malformed processor component report: {}",
+                        e
+                    ),
+                }
+                return;
+            }
 
             // The `control:` namespace is reserved for host-interpreted
             // control topics (matched above), never delivered to `Event` /
@@ -1268,6 +1294,72 @@ mod runtime_shutdown_control_topic_tests {
         drop(listener);
     }
 
+    /// The reference-status control topic carries a named-map msgpack
+    /// `ReferenceSignalStatus` (as the SDK encodes it) and lands in the
+    /// registry the clock subsystem reads. Mental-revert: without the
+    /// handler the topic is warn-dropped and the lookup below finds nothing.
+    #[test]
+    fn host_pubsub_publish_records_clock_reference_status() {
+        let status = crate::core::context::ReferenceSignalStatus {
+            source: format!("test-reference-{}", uuid::Uuid::new_v4()),
+            locked: true,
+            mode: Some("1080i59.94".to_string()),
+        };
+        let bytes = rmp_serde::to_vec_named(&status).expect("encode reference status");
+        let topic = streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS;
+
+        // SAFETY: `host` is unused by `host_pubsub_publish`; the topic and
+        // payload slices outlive the call.
+        unsafe {
+            host_pubsub_publish(
+                std::ptr::null(),
+                topic.as_ptr(),
+                topic.len(),
+                bytes.as_ptr(),
+                bytes.len(),
+            );
+        }
+
+        let recorded = crate::core::context::TimeContext::new()
+            .reference_signals()
+            .into_iter()
+            .find(|s| s.source == status.source);
+        assert_eq!(recorded, Some(status));
+    }
+
+    /// The processor-component control topic carries a named-map msgpack
+    /// `ProcessorComponentReport` and lands on the node's serialized
+    /// `components`. Mental-revert: without the handler the topic is
+    /// warn-dropped and the merged map stays empty.
+    #[test]
+    fn host_pubsub_publish_records_processor_component() {
+        let report = crate::core::graph::ProcessorComponentReport {
+            processor_id: format!("test-component-{}", uuid::Uuid::new_v4()),
+            key: "loudness".to_string(),
+            value: serde_json::json!({ "integrated_lufs": -23.0 }),
+        };
+        let bytes = rmp_serde::to_vec_named(&report).expect("encode component report");
+        let topic = streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT;
+
+        // SAFETY: `host` is unused by `host_pubsub_publish`; the topic and
+        // payload slices outlive the call.
+        unsafe {
+            host_pubsub_publish(
+                std::ptr::null(),
+                topic.as_ptr(),
+                topic.len(),
+                bytes.as_ptr(),
+                bytes.len(),
+            );
+        }
+
+        let mut components = serde_json::Map::new();
+        crate::core::graph::merge_reported_components(&report.processor_id, &mut components);
+        assert_eq!(components.get("loudness"), Some(&report.value));
+
+        crate::core::graph::clear_reported_components(&report.processor_id);
+    }
+
     /// A `control:`-prefixed topic with NO registered host handler must be
     /// warn-dropped by `host_pubsub_publish`, never routed into the general
     /// `Event` decode / re-publish path — otherwise an `Event::Custom`
@@ -13,14 +13,19 @@
 use std::ffi::c_void;
 use std::sync::Arc;
 
-use streamlib_plugin_abi::{RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION, RuntimeContextVTable};
+use streamlib_plugin_abi::{
+    ASSET_FETCH_FAILED, ASSET_FETCH_OFFLINE, ASSET_FETCH_OK, AssetFetchCompletionCallback,
+    FONT_RESOLVE_NOT_FOUND, FONT_RESOLVE_OK, FontResolveCompletionCallback,
+    RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION, RuntimeContextVTable,
+};
 
-use crate::core::context::{RuntimeContext, SharedAudioClock};
+use crate::core::Error;
+use crate::core::context::{GpuContextLimitedAccess, RuntimeContext, SharedAudioClock};
 use crate::core::runtime::RuntimeOperations;
 
 use super::host_callbacks;
 use super::run_host_extern_c;
-use super::shared::wire::write_id_bytes;
+use super::shared::wire::{write_err, write_id_bytes};
 
 unsafe extern "C" fn host_rcv_runtime_id_copy(
     ctx: *const c_void,
@@ -181,6 +186,163 @@ unsafe extern "C" fn host_rcv_runtime_ops_handle(ctx: *const c_void) -> *const c
     )
 }
 
+unsafe extern "C" fn host_rcv_assets_fetch(
+    ctx: *const c_void,
+    url_ptr: *const u8,
+    url_len: usize,
+    sha256_ptr: *const u8,
+    sha256_len: usize,
+    completion: AssetFetchCompletionCallback,
+    user_data: *mut c_void,
+) {
+    // The result is computed inside the panic boundary and the
+    // completion fired outside it, so a panicking fetch still honours
+    // the exactly-once contract (with the FAILED default).
+    let (status, payload) = run_host_extern_c(
+        "host_rcv_assets_fetch",
+        || {
+            if ctx.is_null() || url_ptr.is_null() {
+                return (
+                    ASSET_FETCH_FAILED,
+                    b"assets_fetch: null ctx or url".to_vec(),
+                );
+            }
+            let rc = unsafe { &*(ctx as *const RuntimeContext) };
+            // SAFETY: cdylib passes a valid (ptr, len) pair for the call.
+            let url_bytes = unsafe { std::slice::from_raw_parts(url_ptr, url_len) };
+            let Ok(url) = std::str::from_utf8(url_bytes) else {
+                return (
+                    ASSET_FETCH_FAILED,
+                    b"assets_fetch: url is not UTF-8".to_vec(),
+                );
+            };
+            let result = if sha256_ptr.is_null() || sha256_len == 0 {
+                rc.assets().fetch(url)
+            } else {
+                // SAFETY: as above.
+                let pin = unsafe { std::slice::from_raw_parts(sha256_ptr, sha256_len) };
+                match std::str::from_utf8(pin) {
+                    Ok(pin) => rc.assets().fetch_pinned(url, pin),
+                    Err(_) => {
+                        return (
+                            ASSET_FETCH_FAILED,
+                            b"assets_fetch: sha256 is not UTF-8".to_vec(),
+                        );
+                    }
+                }
+            };
+            match result {
+                Ok(path) => (
+                    ASSET_FETCH_OK,
+                    path.to_string_lossy().into_owned().into_bytes(),
+                ),
+                Err(e @ Error::AssetOffline(_)) => {
+                    (ASSET_FETCH_OFFLINE, e.to_string().into_bytes())
+                }
+                // The shim rebuilds `AssetFetchFailed` around the payload,
+                // so send the bare detail rather than the rendered error.
+                Err(Error::AssetFetchFailed { detail, .. }) => {
+                    (ASSET_FETCH_FAILED, detail.into_bytes())
+                }
+                Err(e) => (ASSET_FETCH_FAILED, e.to_string().into_bytes()),
+            }
+        },
+        (
+            ASSET_FETCH_FAILED,
+            b"assets_fetch: host callback panicked".to_vec(),
+        ),
+    );
+    // SAFETY: cdylib promises completion is safe to invoke with the
+    // user_data pointer; payload bytes are valid for the call.
+    unsafe { completion(user_data, status, payload.as_ptr(), payload.len()) };
+}
+
+unsafe extern "C" fn host_rcv_fonts_resolve(
+    ctx: *const c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    completion: FontResolveCompletionCallback,
+    user_data: *mut c_void,
+) {
+    // Same exactly-once shape as `host_rcv_assets_fetch`.
+    let (status, payload) = run_host_extern_c(
+        "host_rcv_fonts_resolve",
+        || {
+            if ctx.is_null() || name_ptr.is_null() {
+                return (
+                    FONT_RESOLVE_NOT_FOUND,
+                    b"fonts_resolve: null ctx or name".to_vec(),
+                );
+            }
+            let rc = unsafe { &*(ctx as *const RuntimeContext) };
+            // SAFETY: cdylib passes a valid (ptr, len) pair for the call.
+            let name_bytes = unsafe { std::slice::from_raw_parts(name_ptr, name_len) };
+            let Ok(name) = std::str::from_utf8(name_bytes) else {
+                return (
+                    FONT_RESOLVE_NOT_FOUND,
+                    b"fonts_resolve: name is not UTF-8".to_vec(),
+                );
+            };
+            match rc.fonts().resolve(name) {
+                Ok(chain) => {
+                    let lines: Vec<String> = chain
+                        .iter()
+                        .map(|face| format!("{}\t{}", face.face_index, face.path.to_string_lossy()))
+                        .collect();
+                    (FONT_RESOLVE_OK, lines.join("\n").into_bytes())
+                }
+                Err(e) => (FONT_RESOLVE_NOT_FOUND, e.to_string().into_bytes()),
+            }
+        },
+        (
+            FONT_RESOLVE_NOT_FOUND,
+            b"fonts_resolve: host callback panicked".to_vec(),
+        ),
+    );
+    // SAFETY: as in `host_rcv_assets_fetch`.
+    unsafe { completion(user_data, status, payload.as_ptr(), payload.len()) };
+}
+
+unsafe extern "C" fn host_rcv_gpu_limited_access_for_adapter(
+    ctx: *const c_void,
+    adapter_index: u32,
+    out_handle: *mut *const c_void,
+    err_buf: *mut u8,
+    err_buf_cap: usize,
+    err_len: *mut usize,
+) -> i32 {
+    run_host_extern_c(
+        "host_rcv_gpu_limited_access_for_adapter",
+        || -> i32 {
+            if ctx.is_null() || out_handle.is_null() {
+                write_err(
+                    "gpu_limited_access_for_adapter: null ctx or out_handle",
+                    err_buf,
+                    err_buf_cap,
+                    err_len,
+                );
+                return 1;
+            }
+            let rc = unsafe { &*(ctx as *const RuntimeContext) };
+            match rc.gpu_for_adapter(adapter_index as usize) {
+                Ok(gpu) => {
+                    // Hand the owning `Box<Arc<GpuContext>>` handle to the
+                    // caller; its `drop_handle` releases it.
+                    let limited = std::mem::ManuallyDrop::new(GpuContextLimitedAccess::new(gpu));
+                    // SAFETY: `out_handle` checked non-null above.
+                    unsafe { *out_handle = limited.handle };
+                    0
+                }
+                Err(e) => {
+                    write_err(&e.to_string(), err_buf, err_buf_cap, err_len);
+                    1
+                }
+            }
+        },
+        1,
+    )
+}
+
 /// Static [`RuntimeContextVTable`] installed once per process and
 /// reused for every cdylib's `RuntimeContext*Access` shim
 /// construction. The host-side `RuntimeContextFullAccess::new` /
@@ -197,6 +359,9 @@ pub static HOST_RUNTIME_CONTEXT_VTABLE: RuntimeContextVTable = RuntimeContextVTa
     gpu_limited_access: host_rcv_gpu_limited_access,
     audio_clock_handle: host_rcv_audio_clock_handle,
     runtime_ops_handle: host_rcv_runtime_ops_handle,
+    assets_fetch: host_rcv_assets_fetch,
+    fonts_resolve: host_rcv_fonts_resolve,
+    gpu_limited_access_for_adapter: host_rcv_gpu_limited_access_for_adapter,
 };
 
 /// Pointer to the [`RuntimeContextVTable`] this plugin should dispatch
@@ -300,6 +465,26 @@ mod runtime_context_vtable_null_handle_guards {
         assert!(p.is_null());
     }
 
+    #[test]
+    fn gpu_limited_access_for_adapter_errors_on_null_ctx() {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err = [0u8; 128];
+        let mut err_len: usize = 0;
+        let status = unsafe {
+            (HOST_RUNTIME_CONTEXT_VTABLE.gpu_limited_access_for_adapter)(
+                std::ptr::null(),
+                1,
+                &mut out,
+                err.as_mut_ptr(),
+                err.len(),
+                &mut err_len,
+            )
+        };
+        assert_ne!(status, 0);
+        assert!(out.is_null(), "no handle is written on failure");
+        assert!(err_len > 0, "failure carries a message");
+    }
+
     #[test]
     fn audio_clock_handle_returns_null_on_null_ctx() {
         let p = unsafe { (HOST_RUNTIME_CONTEXT_VTABLE.audio_clock_handle)(std::ptr::null()) };
@@ -311,6 +496,68 @@ mod runtime_context_vtable_null_handle_guards {
         let p = unsafe { (HOST_RUNTIME_CONTEXT_VTABLE.runtime_ops_handle)(std::ptr::null()) };
         assert!(p.is_null());
     }
+
+    #[test]
+    fn assets_fetch_completes_with_failed_on_null_ctx() {
+        unsafe extern "C" fn record(
+            user_data: *mut c_void,
+            status: i32,
+            _result_ptr: *const u8,
+            _result_len: usize,
+        ) {
+            let calls = unsafe { &mut *(user_data as *mut Vec<i32>) };
+            calls.push(status);
+        }
+
+        let url = b"https://example.invalid/lut.cube";
+        let mut calls: Vec<i32> = Vec::new();
+        unsafe {
+            (HOST_RUNTIME_CONTEXT_VTABLE.assets_fetch)(
+                std::ptr::null(),
+                url.as_ptr(),
+                url.len(),
+                std::ptr::null(),
+                0,
+                record,
+                &mut calls as *mut Vec<i32> as *mut c_void,
+            )
+        };
+        assert_eq!(
+            calls,
+            vec![ASSET_FETCH_FAILED],
+            "completion fires exactly once"
+        );
+    }
+
+    #[test]
+    fn fonts_resolve_completes_with_not_found_on_null_ctx() {
+        unsafe extern "C" fn record(
+            user_data: *mut c_void,
+            status: i32,
+            _result_ptr: *const u8,
+            _result_len: usize,
+        ) {
+            let calls = unsafe { &mut *(user_data as *mut Vec<i32>) };
+            calls.push(status);
+        }
+
+        let name = b"sans";
+        let mut calls: Vec<i32> = Vec::new();
+        unsafe {
+            (HOST_RUNTIME_CONTEXT_VTABLE.fonts_resolve)(
+                std::ptr::null(),
+                name.as_ptr(),
+                name.len(),
+                record,
+                &mut calls as *mut Vec<i32> as *mut c_void,
+            )
+        };
+        assert_eq!(
+            calls,
+            vec![FONT_RESOLVE_NOT_FOUND],
+            "completion fires exactly once"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -327,6 +574,9 @@ mod runtime_context_vtable_tier1_wire_format_tests {
     //! No callback on `RuntimeContextVTable` takes an out-param or
     //! a variant-typed input, so the "null out-param" and
     //! "invalid input" tier-1 categories don't apply here.
+    //! (`assets_fetch` and `fonts_resolve` report through their
+    //! completion callbacks; their null-ctx cases are covered by the
+    //! guard suite.)
 
     use super::*;
 
@@ -5,8 +5,8 @@
 //! accessor (M32 #1259; decoder fill-in #1377).
 //!
 //! The seven live per-session method slots drive a boxed
-//! [`HostVideoDecoderSession`] (a [`SimpleDecoder`] plus its host-side
-//! staged-frame buffer). The session Box is minted by the FullAccess
+//! [`HostVideoDecoderSession`] (a [`VideoDecoderBackend`] — Vulkan Video
+//! `SimpleDecoder` or VAAPI — plus its host-side staged-frame buffer). The session Box is minted by the FullAccess
 //! `create_decoder_session` slot (`gpu_context/full/reserved_m32.rs`)
 //! and reclaimed by `drop_decoder_session`; the per-frame methods here
 //! are Limited-only (no re-escalation) and take just the opaque `session`
@@ -24,8 +24,8 @@
 //! `decode_into_ring` slot (zero-copy decode into a `TextureRing`), which
 //! stays a typed NotYetProvided stub here.
 //!
-//! Linux-only: [`SimpleDecoder`] lives in the `#[cfg(target_os = "linux")]`
-//! `vulkan::video` module. Off-Linux every method returns a typed
+//! Linux-only: both backends live in `#[cfg(target_os = "linux")]`
+//! modules (`vulkan::video`, `linux::vaapi`). Off-Linux every method returns a typed
 //! "not available on this platform" error (no session is ever minted
 //! there — `create_decoder_session` refuses off-Linux).
 
@@ -45,25 +45,28 @@ use streamlib_plugin_abi::VideoDecoderSessionDescriptorRepr;
 
 #[cfg(target_os = "linux")]
 use crate::vulkan::video::decode::{
-    DpbOutputMode, SimpleDecodedFrame, SimpleDecoder, SimpleDecoderConfig,
+    DpbOutputMode, SimpleDecodedFrame, SimpleDecoderConfig,
 };
 
+#[cfg(target_os = "linux")]
+use crate::linux::codec_factory::VideoDecoderBackend;
+
 // ============================================================================
 // Boxed host session (Linux-only)
 // ============================================================================
 
 /// The opaque handle behind the `create_decoder_session` slot: a
-/// host-owned [`SimpleDecoder`] plus the frames it staged on the last
+/// host-owned [`VideoDecoderBackend`] plus the frames it staged on the last
 /// `feed` call, awaiting `drain_frame` pulls. Boxed and handed across the
 /// plugin ABI as `Box::into_raw(..) as *const c_void`;
-/// `drop_decoder_session` reclaims it (`Box::from_raw` + `SimpleDecoder`
+/// `drop_decoder_session` reclaims it (`Box::from_raw` + the decoder's
 /// Drop, which `wait_idle`s + tears down spec-ordered, host-side).
 ///
 /// Single-owner stateful pipeline (`!Clone`): the parent vtable carries
 /// only `drop_decoder_session`, no clone slot.
 #[cfg(target_os = "linux")]
 pub(in crate::core::plugin::host_services) struct HostVideoDecoderSession {
-    decoder: SimpleDecoder,
+    decoder: VideoDecoderBackend,
     /// Frames staged by the most recent `feed`, pulled by
     /// `drain_frame(index)`. Each `feed` replaces the staging set;
     /// `drain_frame` never re-decodes (pure copy of CPU pixel bytes).
@@ -72,8 +75,8 @@ pub(in crate::core::plugin::host_services) struct HostVideoDecoderSession {
 
 #[cfg(target_os = "linux")]
 impl HostVideoDecoderSession {
-    /// Wrap a freshly-minted [`SimpleDecoder`] with an empty staging set.
-    pub(in crate::core::plugin::host_services) fn new(decoder: SimpleDecoder) -> Self {
+    /// Wrap a freshly-minted decoder with an empty staging set.
+    pub(in crate::core::plugin::host_services) fn new(decoder: VideoDecoderBackend) -> Self {
         Self {
             decoder,
             staged_frames: Vec::new(),
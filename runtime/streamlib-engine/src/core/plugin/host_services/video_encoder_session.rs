@@ -5,9 +5,9 @@
 //! accessor (M32 #1259; encoder fill-in #1376).
 //!
 //! The six per-session method slots drive a boxed
-//! [`HostVideoEncoderSession`] (a [`SimpleEncoder`] plus its host-side
-//! staged-packet buffer). The session Box is minted by the FullAccess
-//! `create_encoder_session` slot (`gpu_context/full/reserved_m32.rs`)
+//! [`HostVideoEncoderSession`] (a [`VideoEncoderBackend`] — Vulkan Video
+//! `SimpleEncoder` or VAAPI — plus its host-side staged-packet buffer).
+//! The session Box is minted by the FullAccess `create_encoder_session` slot (`gpu_context/full/reserved_m32.rs`)
 //! and reclaimed by `drop_encoder_session`; the per-frame methods here
 //! are Limited-only (no re-escalation) and take just the opaque `session`
 //! handle. Every body runs under the [`run_host_extern_c`] panic net.
@@ -17,8 +17,8 @@
 //! and report `N`; the caller then pulls each staged packet's meta +
 //! bitstream via `drain_packet(index)`.
 //!
-//! Linux-only: [`SimpleEncoder`] lives in the `#[cfg(target_os = "linux")]`
-//! `vulkan::video` module. Off-Linux every method returns a typed
+//! Linux-only: both backends live in `#[cfg(target_os = "linux")]`
+//! modules (`vulkan::video`, `linux::vaapi`). Off-Linux every method returns a typed
 //! "not available on this platform" error (no session is ever minted
 //! there — `create_encoder_session` refuses off-Linux).
 
@@ -37,24 +37,26 @@ use super::shared::wire::write_err;
 use streamlib_plugin_abi::{VideoEncoderSessionDescriptorRepr, VideoFrameTypeRepr};
 
 #[cfg(target_os = "linux")]
-use crate::vulkan::video::encode::{EncodePacket, FrameType, SimpleEncoder, SimpleEncoderConfig};
+use crate::linux::codec_factory::VideoEncoderBackend;
+#[cfg(target_os = "linux")]
+use crate::vulkan::video::encode::{EncodePacket, FrameType, SimpleEncoderConfig};
 
 // ============================================================================
 // Boxed host session (Linux-only)
 // ============================================================================
 
 /// The opaque handle behind the `create_encoder_session` slot: a
-/// host-owned [`SimpleEncoder`] plus the packets it staged on the last
+/// host-owned [`VideoEncoderBackend`] plus the packets it staged on the last
 /// `submit_*` / `finish` call, awaiting `drain_packet` pulls. Boxed and
 /// handed across the plugin ABI as `Box::into_raw(..) as *const c_void`;
-/// `drop_encoder_session` reclaims it (`Box::from_raw` + `SimpleEncoder`
+/// `drop_encoder_session` reclaims it (`Box::from_raw` + the encoder's
 /// Drop, which `wait_idle`s + tears down spec-ordered, host-side).
 ///
 /// Single-owner stateful pipeline (`!Clone`): the parent vtable carries
 /// only `drop_encoder_session`, no clone slot.
 #[cfg(target_os = "linux")]
 pub(in crate::core::plugin::host_services) struct HostVideoEncoderSession {
-    encoder: SimpleEncoder,
+    encoder: VideoEncoderBackend,
     /// Packets staged by the most recent `submit_*` / `finish`, pulled by
     /// `drain_packet(index)`. Each `submit_*` / `finish` replaces the
     /// staging set; `drain_packet` never re-encodes (pure copy).
@@ -63,8 +65,8 @@ pub(in crate::core::plugin::host_services) struct HostVideoEncoderSession {
 
 #[cfg(target_os = "linux")]
 impl HostVideoEncoderSession {
-    /// Wrap a freshly-minted [`SimpleEncoder`] with an empty staging set.
-    pub(in crate::core::plugin::host_services) fn new(encoder: SimpleEncoder) -> Self {
+    /// Wrap a freshly-minted encoder with an empty staging set.
+    pub(in crate::core::plugin::host_services) fn new(encoder: VideoEncoderBackend) -> Self {
         Self {
             encoder,
             staged_packets: Vec::new(),
@@ -414,20 +416,8 @@ unsafe extern "C" fn host_video_encoder_submit_texture(
                     );
                     return 1;
                 }
-                let image_view = match texture.vulkan_inner().image_view() {
-                    Ok(view) => view,
-                    Err(e) => {
-                        write_err(
-                            &format!("submit_texture: resolve image view: {e}"),
-                            err_buf,
-                            err_buf_cap,
-                            err_len,
-                        );
-                        return 1;
-                    }
-                };
                 let timestamp = (has_timestamp != 0).then_some(timestamp_ns);
-                match session.encoder.encode_image(image_view, timestamp) {
+                match session.encoder.encode_texture(texture.vulkan_inner(), timestamp) {
                     Ok(packets) => {
                         let count = packets.len() as u32;
                         session.staged_packets = packets;
@@ -1236,8 +1226,13 @@ mod encoder_session_gpu_gated_tests {
             16,
             crate::core::rhi::TextureFormat::Rgba8Unorm,
         );
+        // The undersize guard is backend-independent; a VAAPI session has
+        // no host device to mint the texture on, so exercise Vulkan Video.
+        let VideoEncoderBackend::VulkanVideo(encoder) = &session.encoder else {
+            return;
+        };
         let host_texture =
-            match crate::vulkan::rhi::HostVulkanTexture::new(&session.encoder.host_device, &desc) {
+            match crate::vulkan::rhi::HostVulkanTexture::new(&encoder.host_device, &desc) {
                 Ok(t) => t,
                 Err(_) => return,
             };
@@ -0,0 +1,850 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Port recording: capturing the frames selected links deliver to disk,
+//! and replaying them into a graph later.
+//!
+//! [`Runner::start_port_recording`](crate::core::runtime::Runner::start_port_recording)
+//! taps each selected link where its in-process destination receives, so
+//! a recording holds exactly what the consumer saw — after any chaos-mode
+//! drops or corruption — in arrival order, each frame stamped with its
+//! offset from the start of the recording.
+//! [`Runner::replay_port_recording`](crate::core::runtime::Runner::replay_port_recording)
+//! feeds a recording back into input ports, by default the ones the frames
+//! were recorded at, or others named by [`ReplayRoute`]s — so a modified
+//! processor can be regression-tested against real captured traffic
+//! without its upstream running.
+//!
+//! Frames are recorded whole, header included: replayed frames keep their
+//! port key, schema tag and media-clock timestamp. Frames that reference
+//! GPU surfaces (`VideoFrame` and friends) record the descriptor, not the
+//! pixels, so replaying those needs the surfaces to still be resolvable.
+//!
+//! # File format
+//!
+//! `SLPREC01`, then a little-endian `u32` length and that many bytes of
+//! JSON [`PortRecordingHeader`], then one record per frame until end of
+//! file: `u16` link index into the header's links, `u64` offset in
+//! nanoseconds, `u32` frame length, the frame bytes. A recording cut short
+//! mid-record (a crash) reads back up to its last whole record.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::sync::{Arc, LazyLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::graph::{LinkUniqueId, ProcessorUniqueId};
+use crate::core::{Error, Result};
+use crate::iceoryx2::{FRAME_HEADER_SIZE, FrameHeader, InputMailboxesInner};
+
+/// First bytes of every port recording.
+pub const PORT_RECORDING_MAGIC: [u8; 8] = *b"SLPREC01";
+
+/// Headers larger than this are taken as a corrupt file, not allocated.
+const MAX_HEADER_LEN: u32 = 16 * 1024 * 1024;
+
+/// Frames queued between the receive paths and the writer thread; frames
+/// arriving while it is full are counted as dropped rather than stalling
+/// a destination on disk I/O.
+const RECORDING_QUEUE_DEPTH: usize = 1024;
+
+/// Mailbox depth given to an input port that had none when a replay
+/// started injecting into it.
+pub(crate) const REPLAY_MAILBOX_DEPTH: usize = 16;
+
+/// How long a replay sleeps between checks for cancellation or a stepped
+/// destination catching up.
+const REPLAY_POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// A link a recording captured, as it was wired when recording started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedLink {
+    pub link_id: LinkUniqueId,
+    pub source_processor_id: ProcessorUniqueId,
+    pub source_port: String,
+    pub destination_processor_id: ProcessorUniqueId,
+    pub destination_port: String,
+}
+
+/// What a recording captured; written once at the start of the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortRecordingHeader {
+    /// Wall-clock start of the recording, milliseconds since the epoch.
+    pub started_at_unix_ms: u64,
+    /// Records refer to links by their index here.
+    pub links: Vec<RecordedLink>,
+}
+
+/// One frame a recorded link delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedMessage {
+    /// Index of the link into [`PortRecordingHeader::links`].
+    pub link_index: u16,
+    /// When the destination received it, from the start of the recording.
+    pub offset_ns: u64,
+    /// The whole wire frame, header included.
+    pub frame: Vec<u8>,
+}
+
+impl RecordedMessage {
+    /// The media-clock timestamp the producer stamped on the frame.
+    pub fn timestamp_ns(&self) -> i64 {
+        FrameHeader::read_from_slice(&self.frame).timestamp_ns
+    }
+
+    /// The frame's serialized body, header stripped.
+    pub fn payload(&self) -> &[u8] {
+        let len = FrameHeader::read_from_slice(&self.frame).len as usize;
+        let end = (FRAME_HEADER_SIZE + len).min(self.frame.len());
+        &self.frame[FRAME_HEADER_SIZE..end]
+    }
+}
+
+/// Writes a port recording to `W`.
+pub struct PortRecordingWriter<W: Write> {
+    writer: W,
+    links: usize,
+}
+
+impl<W: Write> PortRecordingWriter<W> {
+    /// Start a recording, writing the magic and `header`.
+    pub fn new(mut writer: W, header: &PortRecordingHeader) -> Result<Self> {
+        if header.links.len() > usize::from(u16::MAX) + 1 {
+            return Err(Error::Configuration(format!(
+                "a port recording holds at most {} links, not {}",
+                usize::from(u16::MAX) + 1,
+                header.links.len()
+            )));
+        }
+        let json = serde_json::to_vec(header)
+            .map_err(|e| Error::Runtime(format!("encoding port recording header: {e}")))?;
+        writer.write_all(&PORT_RECORDING_MAGIC)?;
+        writer.write_all(&(json.len() as u32).to_le_bytes())?;
+        writer.write_all(&json)?;
+        Ok(Self {
+            writer,
+            links: header.links.len(),
+        })
+    }
+
+    /// Append one message.
+    pub fn write_message(&mut self, message: &RecordedMessage) -> Result<()> {
+        if usize::from(message.link_index) >= self.links {
+            return Err(Error::Configuration(format!(
+                "link index {} is outside the recording's {} links",
+                message.link_index, self.links
+            )));
+        }
+        let len = u32::try_from(message.frame.len()).map_err(|_| {
+            Error::Configuration(format!(
+                "a {}-byte frame is too large to record",
+                message.frame.len()
+            ))
+        })?;
+        self.writer.write_all(&message.link_index.to_le_bytes())?;
+        self.writer.write_all(&message.offset_ns.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&message.frame)?;
+        Ok(())
+    }
+
+    /// Flush and hand back the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads a port recording from `R`.
+pub struct PortRecordingReader<R: Read> {
+    reader: R,
+    header: PortRecordingHeader,
+}
+
+impl PortRecordingReader<BufReader<File>> {
+    /// Open the recording at `path`.
+    pub fn open_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> PortRecordingReader<R> {
+    /// Read the magic and header, leaving the reader at the first record.
+    pub fn open(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != PORT_RECORDING_MAGIC {
+            return Err(Error::Configuration(
+                "not a port recording (bad magic)".into(),
+            ));
+        }
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len);
+        if len > MAX_HEADER_LEN {
+            return Err(Error::Configuration(format!(
+                "port recording header of {len} bytes is implausibly large"
+            )));
+        }
+        let mut json = vec![0u8; len as usize];
+        reader.read_exact(&mut json)?;
+        let header: PortRecordingHeader = serde_json::from_slice(&json)
+            .map_err(|e| Error::Configuration(format!("port recording header: {e}")))?;
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &PortRecordingHeader {
+        &self.header
+    }
+
+    /// The next message, or `None` at the end of the recording.
+    pub fn next_message(&mut self) -> Result<Option<RecordedMessage>> {
+        let mut prefix = [0u8; 14];
+        match read_full(&mut self.reader, &mut prefix)? {
+            0 => return Ok(None),
+            n if n < prefix.len() => {
+                tracing::warn!("[port_recording] Recording ends mid-record; stopping there");
+                return Ok(None);
+            }
+            _ => {}
+        }
+        let link_index = u16::from_le_bytes([prefix[0], prefix[1]]);
+        let offset_ns = u64::from_le_bytes(prefix[2..10].try_into().unwrap_or_default());
+        let len = u32::from_le_bytes(prefix[10..14].try_into().unwrap_or_default()) as usize;
+        if usize::from(link_index) >= self.header.links.len() {
+            return Err(Error::Configuration(format!(
+                "record refers to link {link_index} of {}",
+                self.header.links.len()
+            )));
+        }
+        if len < FRAME_HEADER_SIZE {
+            return Err(Error::Configuration(format!(
+                "recorded frame of {len} bytes is shorter than a frame header"
+            )));
+        }
+        let mut frame = vec![0u8; len];
+        if read_full(&mut self.reader, &mut frame)? < len {
+            tracing::warn!("[port_recording] Recording ends mid-record; stopping there");
+            return Ok(None);
+        }
+        Ok(Some(RecordedMessage {
+            link_index,
+            offset_ns,
+            frame,
+        }))
+    }
+}
+
+/// Fill `buf` as far as the reader allows, returning how much was read;
+/// short only at end of input.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Where an armed link's frames go.
+#[derive(Debug)]
+struct RecordingSink {
+    link_index: u16,
+    started: Instant,
+    messages: SyncSender<RecordedMessage>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// Recording state of one link, read per frame by the destination's
+/// receive path. Unarmed, recording costs one atomic load.
+#[derive(Debug, Default)]
+pub(crate) struct LinkRecordingTap {
+    armed: AtomicBool,
+    sink: Mutex<Option<RecordingSink>>,
+}
+
+impl LinkRecordingTap {
+    /// Record `frame` if the link is being recorded.
+    pub fn record(&self, frame: &[u8]) {
+        if !self.armed.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(sink) = self.sink.lock().as_ref() {
+            let message = RecordedMessage {
+                link_index: sink.link_index,
+                offset_ns: sink.started.elapsed().as_nanos() as u64,
+                frame: frame.to_vec(),
+            };
+            if let Err(TrySendError::Full(_)) = sink.messages.try_send(message) {
+                sink.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn arm(&self, sink: RecordingSink) {
+        *self.sink.lock() = Some(sink);
+        self.armed.store(true, Ordering::Relaxed);
+    }
+
+    fn disarm(&self) {
+        self.armed.store(false, Ordering::Relaxed);
+        self.sink.lock().take();
+    }
+}
+
+/// Recording state of every wired link, keyed by link id. Entries are made
+/// when a destination binds the link's channel and dropped when it
+/// unbinds, like the chaos-mode fault state.
+static LINK_RECORDING_TAPS: LazyLock<Mutex<HashMap<String, Arc<LinkRecordingTap>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The recording state for `link_id`, created unarmed if the link has none.
+pub(crate) fn link_recording_tap(link_id: &str) -> Arc<LinkRecordingTap> {
+    Arc::clone(
+        LINK_RECORDING_TAPS
+            .lock()
+            .entry(link_id.to_string())
+            .or_default(),
+    )
+}
+
+/// The recording state for `link_id`, if its channel is bound.
+pub(crate) fn bound_link_recording_tap(link_id: &str) -> Option<Arc<LinkRecordingTap>> {
+    LINK_RECORDING_TAPS.lock().get(link_id).cloned()
+}
+
+/// Forget `link_id`'s recording state once its destination unbinds it. A
+/// recording running at the time captures nothing more from the link,
+/// even if it is wired again.
+pub(crate) fn release_link_recording_tap(link_id: &str) {
+    if let Some(tap) = LINK_RECORDING_TAPS.lock().remove(link_id) {
+        tap.disarm();
+    }
+}
+
+/// What a finished recording captured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PortRecordingSummary {
+    #[schema(value_type = String)]
+    pub path: PathBuf,
+    pub links: usize,
+    /// Frames written to the file.
+    pub messages: u64,
+    /// Frames that arrived while the writer was behind, and were not
+    /// recorded.
+    pub dropped: u64,
+    pub duration_ms: u64,
+}
+
+/// A running recording: the armed link taps and the thread writing what
+/// they capture. Dropping it stops the recording.
+pub struct PortRecorder {
+    path: PathBuf,
+    links: usize,
+    taps: Vec<Arc<LinkRecordingTap>>,
+    dropped: Arc<AtomicU64>,
+    started: Instant,
+    writer: Option<JoinHandle<Result<u64>>>,
+}
+
+impl PortRecorder {
+    /// Create `path` and start recording each link through its tap.
+    pub(crate) fn start(
+        path: impl AsRef<Path>,
+        links: Vec<(RecordedLink, Arc<LinkRecordingTap>)>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (links, taps): (Vec<_>, Vec<_>) = links.into_iter().unzip();
+        let header = PortRecordingHeader {
+            started_at_unix_ms: unix_millis(),
+            links,
+        };
+        let writer = PortRecordingWriter::new(BufWriter::new(File::create(&path)?), &header)?;
+        let (messages, received) = sync_channel(RECORDING_QUEUE_DEPTH);
+        let handle = std::thread::Builder::new()
+            .name("port-recorder".into())
+            .spawn(move || write_recording(writer, received))
+            .map_err(|e| Error::Runtime(format!("spawning port recorder thread: {e}")))?;
+
+        let dropped = Arc::new(AtomicU64::new(0));
+        let started = Instant::now();
+        for (link_index, tap) in taps.iter().enumerate() {
+            tap.arm(RecordingSink {
+                link_index: link_index as u16,
+                started,
+                messages: messages.clone(),
+                dropped: Arc::clone(&dropped),
+            });
+        }
+        tracing::info!(
+            "[port_recording] Recording {} links to {}",
+            taps.len(),
+            path.display()
+        );
+        Ok(Self {
+            path,
+            links: header.links.len(),
+            taps,
+            dropped,
+            started,
+            writer: Some(handle),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stop recording, wait for the file to be written out and report what
+    /// it holds.
+    pub fn stop(mut self) -> Result<PortRecordingSummary> {
+        let messages = self.finish()?;
+        let summary = PortRecordingSummary {
+            path: self.path.clone(),
+            links: self.links,
+            messages,
+            dropped: self.dropped.load(Ordering::Relaxed),
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        };
+        tracing::info!(
+            "[port_recording] Recorded {} frames ({} dropped) to {}",
+            summary.messages,
+            summary.dropped,
+            summary.path.display()
+        );
+        Ok(summary)
+    }
+
+    /// Disarm the taps — dropping the writer thread's last senders — and
+    /// join it.
+    fn finish(&mut self) -> Result<u64> {
+        for tap in &self.taps {
+            tap.disarm();
+        }
+        match self.writer.take() {
+            Some(handle) => handle
+                .join()
+                .map_err(|_| Error::Runtime("port recorder thread panicked".into()))?,
+            None => Ok(0),
+        }
+    }
+}
+
+impl Drop for PortRecorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            tracing::warn!(
+                "[port_recording] Recording to {} did not finish cleanly: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Writer thread: append every captured message until all taps disarm.
+fn write_recording(
+    mut writer: PortRecordingWriter<BufWriter<File>>,
+    received: Receiver<RecordedMessage>,
+) -> Result<u64> {
+    let mut written = 0;
+    for message in received {
+        writer.write_message(&message)?;
+        written += 1;
+    }
+    writer.finish()?;
+    Ok(written)
+}
+
+/// How a replay paces what it injects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayPace {
+    /// Inject each frame at its recorded offset from the first.
+    #[default]
+    Recorded,
+    /// Inject each frame once the destination has consumed the previous
+    /// one on its port, as fast as it processes them. Nothing is dropped
+    /// for lack of mailbox room, so a run's output depends only on the
+    /// recording — the mode for regression tests.
+    Stepped,
+}
+
+/// Send one recorded link's frames to another input port.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReplayRoute {
+    #[schema(value_type = String)]
+    pub link_id: LinkUniqueId,
+    #[schema(value_type = String)]
+    pub processor_id: ProcessorUniqueId,
+    pub port: String,
+}
+
+/// How to replay a recording.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PortReplayOptions {
+    #[serde(default)]
+    pub pace: ReplayPace,
+    /// Ports to send links' frames to instead of where they were recorded.
+    #[serde(default)]
+    pub routes: Vec<ReplayRoute>,
+    /// Replay only these recorded links; empty replays all of them.
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub only_links: Vec<LinkUniqueId>,
+}
+
+/// The input port a recorded link is replayed into.
+pub(crate) struct ReplayTarget {
+    pub mailboxes: Arc<InputMailboxesInner>,
+    pub port: String,
+}
+
+/// What a replay injected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PortReplaySummary {
+    /// Frames pushed into a destination's mailbox.
+    pub injected: u64,
+    /// Frames of links left out of the replay.
+    pub skipped: u64,
+    /// Whether the replay was cancelled before the end of the recording.
+    pub cancelled: bool,
+    pub duration_ms: u64,
+}
+
+/// A running replay. Dropping it cancels the replay.
+pub struct PortReplayer {
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Result<PortReplaySummary>>>,
+}
+
+impl PortReplayer {
+    /// Start replaying `reader`'s messages, each link into its entry in
+    /// `targets` (indexed like the header's links; `None` skips the link).
+    pub(crate) fn start<R: Read + Send + 'static>(
+        reader: PortRecordingReader<R>,
+        targets: Vec<Option<ReplayTarget>>,
+        pace: ReplayPace,
+    ) -> Result<Self> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = Arc::clone(&cancel);
+        let handle = std::thread::Builder::new()
+            .name("port-replayer".into())
+            .spawn(move || replay(reader, targets, pace, thread_cancel))
+            .map_err(|e| Error::Runtime(format!("spawning port replayer thread: {e}")))?;
+        Ok(Self {
+            cancel,
+            handle: Some(handle),
+        })
+    }
+
+    /// Stop injecting; [`Self::wait`] then returns promptly.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(|h| h.is_finished())
+    }
+
+    /// Wait for the replay to reach the end of the recording, or be
+    /// cancelled.
+    pub fn wait(mut self) -> Result<PortReplaySummary> {
+        match self.handle.take() {
+            Some(handle) => handle
+                .join()
+                .map_err(|_| Error::Runtime("port replayer thread panicked".into()))?,
+            None => Err(Error::Runtime("port replay already waited on".into())),
+        }
+    }
+}
+
+impl Drop for PortReplayer {
+    fn drop(&mut self) {
+        self.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Replayer thread body.
+fn replay<R: Read>(
+    mut reader: PortRecordingReader<R>,
+    targets: Vec<Option<ReplayTarget>>,
+    pace: ReplayPace,
+    cancel: Arc<AtomicBool>,
+) -> Result<PortReplaySummary> {
+    let started = Instant::now();
+    let mut summary = PortReplaySummary {
+        injected: 0,
+        skipped: 0,
+        cancelled: false,
+        duration_ms: 0,
+    };
+    // Recorded offsets are replayed relative to the first message, so a
+    // replay starts at once however long the recording idled first.
+    let mut first_offset_ns = None;
+
+    while let Some(message) = reader.next_message()? {
+        let Some(target) = targets
+            .get(usize::from(message.link_index))
+            .and_then(Option::as_ref)
+        else {
+            summary.skipped += 1;
+            continue;
+        };
+        let base = *first_offset_ns.get_or_insert(message.offset_ns);
+        let ready = || match pace {
+            ReplayPace::Recorded => {
+                started.elapsed() >= Duration::from_nanos(message.offset_ns.saturating_sub(base))
+            }
+            ReplayPace::Stepped => target.mailboxes.port_is_drained(&target.port),
+        };
+        while !ready() {
+            if cancel.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                summary.duration_ms = started.elapsed().as_millis() as u64;
+                return Ok(summary);
+            }
+            std::thread::sleep(REPLAY_POLL_INTERVAL);
+        }
+        target
+            .mailboxes
+            .inject(&target.port, message.frame, REPLAY_MAILBOX_DEPTH);
+        summary.injected += 1;
+    }
+
+    summary.duration_ms = started.elapsed().as_millis() as u64;
+    tracing::info!(
+        "[port_recording] Replay done: {} frames injected, {} skipped",
+        summary.injected,
+        summary.skipped
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceoryx2::{ReadMode, SchemaIdentWire};
+
+    fn frame(port: &str, timestamp_ns: i64, body: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0u8; FRAME_HEADER_SIZE + body.len()];
+        FrameHeader::new(
+            port,
+            SchemaIdentWire::default(),
+            timestamp_ns,
+            body.len() as u32,
+        )
+        .expect("port key fits")
+        .write_to_slice(&mut buf);
+        buf[FRAME_HEADER_SIZE..].copy_from_slice(body);
+        buf
+    }
+
+    fn header(links: usize) -> PortRecordingHeader {
+        PortRecordingHeader {
+            started_at_unix_ms: 1_700_000_000_000,
+            links: (0..links)
+                .map(|i| RecordedLink {
+                    link_id: LinkUniqueId::from(format!("L{i}")),
+                    source_processor_id: ProcessorUniqueId::from("P0"),
+                    source_port: "out".into(),
+                    destination_processor_id: ProcessorUniqueId::from("P1"),
+                    destination_port: format!("in{i}"),
+                })
+                .collect(),
+        }
+    }
+
+    fn recording(messages: &[RecordedMessage], links: usize) -> Vec<u8> {
+        let mut writer = PortRecordingWriter::new(Vec::new(), &header(links)).expect("header");
+        for message in messages {
+            writer.write_message(message).expect("message");
+        }
+        writer.finish().expect("finish")
+    }
+
+    fn message(link_index: u16, offset_ns: u64, timestamp_ns: i64) -> RecordedMessage {
+        RecordedMessage {
+            link_index,
+            offset_ns,
+            frame: frame("out", timestamp_ns, &timestamp_ns.to_le_bytes()),
+        }
+    }
+
+    #[test]
+    fn recordings_round_trip_header_and_messages_in_order() {
+        let messages = vec![
+            message(0, 10, 100),
+            message(1, 20, 200),
+            message(0, 35, 300),
+        ];
+        let bytes = recording(&messages, 2);
+
+        let mut reader = PortRecordingReader::open(bytes.as_slice()).expect("open");
+        assert_eq!(reader.header(), &header(2));
+        let mut read = Vec::new();
+        while let Some(message) = reader.next_message().expect("read") {
+            read.push(message);
+        }
+        assert_eq!(read, messages);
+        assert_eq!(read[2].timestamp_ns(), 300);
+        assert_eq!(read[2].payload(), 300i64.to_le_bytes());
+    }
+
+    #[test]
+    fn a_recording_cut_short_reads_back_its_whole_records() {
+        let messages = vec![message(0, 10, 100), message(0, 20, 200)];
+        let bytes = recording(&messages, 1);
+        let truncated = &bytes[..bytes.len() - 3];
+
+        let mut reader = PortRecordingReader::open(truncated).expect("open");
+        assert_eq!(
+            reader.next_message().expect("read"),
+            Some(messages[0].clone())
+        );
+        assert_eq!(reader.next_message().expect("read"), None);
+    }
+
+    #[test]
+    fn bad_magic_and_out_of_range_links_are_rejected() {
+        assert!(PortRecordingReader::open(&b"NOTAREC0\0\0\0\0"[..]).is_err());
+
+        let mut writer = PortRecordingWriter::new(Vec::new(), &header(1)).expect("header");
+        assert!(writer.write_message(&message(1, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn taps_record_only_while_armed_and_count_overflow_as_dropped() {
+        let tap = LinkRecordingTap::default();
+        tap.record(&frame("out", 1, b"ignored"));
+
+        let (messages, received) = sync_channel(1);
+        let dropped = Arc::new(AtomicU64::new(0));
+        tap.arm(RecordingSink {
+            link_index: 3,
+            started: Instant::now(),
+            messages,
+            dropped: Arc::clone(&dropped),
+        });
+        tap.record(&frame("out", 2, b"kept"));
+        tap.record(&frame("out", 3, b"overflow"));
+        tap.disarm();
+        tap.record(&frame("out", 4, b"ignored"));
+
+        let recorded: Vec<_> = received.iter().collect();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].link_index, 3);
+        assert_eq!(recorded[0].timestamp_ns(), 2);
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn releasing_a_link_disarms_its_tap() {
+        let tap = link_recording_tap("recording-test-link");
+        let (messages, received) = sync_channel(4);
+        tap.arm(RecordingSink {
+            link_index: 0,
+            started: Instant::now(),
+            messages,
+            dropped: Arc::new(AtomicU64::new(0)),
+        });
+        assert!(bound_link_recording_tap("recording-test-link").is_some());
+
+        release_link_recording_tap("recording-test-link");
+        tap.record(&frame("out", 1, b"late"));
+        assert!(bound_link_recording_tap("recording-test-link").is_none());
+        assert!(received.try_recv().is_err());
+    }
+
+    #[test]
+    fn stepped_replay_delivers_every_frame_in_order_through_a_one_deep_mailbox() {
+        let messages: Vec<_> = (0..20).map(|i| message(0, 0, i)).collect();
+        let bytes = recording(&messages, 1);
+        let mailboxes = Arc::new(InputMailboxesInner::new());
+        mailboxes.add_port("in", 1, ReadMode::ReadNextInOrder);
+
+        let replayer = PortReplayer::start(
+            PortRecordingReader::open(std::io::Cursor::new(bytes)).expect("open"),
+            vec![Some(ReplayTarget {
+                mailboxes: Arc::clone(&mailboxes),
+                port: "in".into(),
+            })],
+            ReplayPace::Stepped,
+        )
+        .expect("start");
+
+        let mut timestamps = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while timestamps.len() < messages.len() && Instant::now() < deadline {
+            match mailboxes.read_raw("in").expect("read") {
+                Some((_, timestamp_ns)) => timestamps.push(timestamp_ns),
+                None => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+        let summary = replayer.wait().expect("replay");
+        assert_eq!(timestamps, (0..20).collect::<Vec<_>>());
+        assert_eq!(summary.injected, 20);
+        assert_eq!(
+            mailboxes
+                .port_frame_counters("in")
+                .expect("counters")
+                .frames_dropped(),
+            0
+        );
+    }
+
+    #[test]
+    fn replays_skip_links_without_a_target() {
+        let messages = vec![message(0, 0, 1), message(1, 0, 2), message(0, 0, 3)];
+        let bytes = recording(&messages, 2);
+        let mailboxes = Arc::new(InputMailboxesInner::new());
+
+        let summary = PortReplayer::start(
+            PortRecordingReader::open(std::io::Cursor::new(bytes)).expect("open"),
+            vec![
+                Some(ReplayTarget {
+                    mailboxes: Arc::clone(&mailboxes),
+                    port: "in".into(),
+                }),
+                None,
+            ],
+            ReplayPace::Recorded,
+        )
+        .expect("start")
+        .wait()
+        .expect("replay");
+
+        assert_eq!(summary.injected, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(
+            mailboxes.read_raw("in").expect("read").map(|f| f.1),
+            Some(1)
+        );
+        assert_eq!(
+            mailboxes.read_raw("in").expect("read").map(|f| f.1),
+            Some(3)
+        );
+    }
+}
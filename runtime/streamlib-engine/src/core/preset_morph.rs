@@ -0,0 +1,288 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Preset morphs: moving a set of processors from one config snapshot to
+//! another over time.
+//!
+//! A [`PresetMorph`] names, per processor, the config to start from (the
+//! current one when omitted) and the config to arrive at, plus a duration
+//! and a [`MorphCurve`]. Started with
+//! [`Runner::morph_processor_configs`](crate::core::runtime::Runner::morph_processor_configs),
+//! the runner steps every target through [`interpolate_config`] until the
+//! duration elapses.
+//!
+//! ```json
+//! { "duration_ms": 2000, "curve": "ease_in_out",
+//!   "targets": [{ "processor_id": "blur", "to": { "radius": 12.0 } }] }
+//! ```
+//!
+//! Numbers interpolate; objects and equal-length arrays interpolate member
+//! by member. Everything else — strings, booleans, type changes, keys
+//! present on one side only — switches from the old value to the new one
+//! halfway through.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::{Error, Result};
+
+/// Easing applied to a morph's progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MorphCurve {
+    #[default]
+    Linear,
+    /// Quadratic: starts slow.
+    EaseIn,
+    /// Quadratic: ends slow.
+    EaseOut,
+    /// Smoothstep: slow at both ends.
+    EaseInOut,
+}
+
+impl MorphCurve {
+    /// Map linear progress `t` (clamped to `0..=1`) onto the curve.
+    pub fn ease(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// One processor's part in a [`PresetMorph`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PresetMorphTarget {
+    #[schema(value_type = String)]
+    pub processor_id: ProcessorUniqueId,
+    /// Config at the start of the morph. Omitted: the processor's config
+    /// when the morph starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Value>,
+    /// Config at the end of the morph.
+    pub to: Value,
+}
+
+/// Move a set of processors between two config snapshots over `duration_ms`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PresetMorph {
+    pub targets: Vec<PresetMorphTarget>,
+    /// Zero applies the `to` configs at once.
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub curve: MorphCurve,
+}
+
+impl PresetMorph {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            targets: Vec::new(),
+            duration_ms: duration.as_millis() as u64,
+            curve: MorphCurve::default(),
+        }
+    }
+
+    pub fn with_curve(mut self, curve: MorphCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Morph `processor_id` from its current config to `to`.
+    pub fn with_target(self, processor_id: impl Into<ProcessorUniqueId>, to: Value) -> Self {
+        self.push_target(processor_id.into(), None, to)
+    }
+
+    /// Morph `processor_id` from `from` to `to`; it jumps to `from` when
+    /// the morph starts.
+    pub fn with_target_from(
+        self,
+        processor_id: impl Into<ProcessorUniqueId>,
+        from: Value,
+        to: Value,
+    ) -> Self {
+        self.push_target(processor_id.into(), Some(from), to)
+    }
+
+    fn push_target(
+        mut self,
+        processor_id: ProcessorUniqueId,
+        from: Option<Value>,
+        to: Value,
+    ) -> Self {
+        self.targets.push(PresetMorphTarget {
+            processor_id,
+            from,
+            to,
+        });
+        self
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// Reject an empty morph or one naming a processor twice.
+    pub fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            return Err(Error::Config("preset morph has no targets".into()));
+        }
+        let mut seen = HashSet::new();
+        for target in &self.targets {
+            if !seen.insert(&target.processor_id) {
+                return Err(Error::Config(format!(
+                    "preset morph names processor '{}' more than once",
+                    target.processor_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The config `t` of the way from `from` to `to`, where `t` is already
+/// eased. `t <= 0` gives `from` and `t >= 1` gives `to`, exactly.
+pub fn interpolate_config(from: &Value, to: &Value, t: f64) -> Value {
+    if t <= 0.0 {
+        return from.clone();
+    }
+    if t >= 1.0 {
+        return to.clone();
+    }
+    match (from, to) {
+        (Value::Number(a), Value::Number(b)) => {
+            interpolate_number(a, b, t).unwrap_or_else(|| snap(from, to, t))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            let mut merged = serde_json::Map::new();
+            for (key, b_value) in b {
+                match a.get(key) {
+                    Some(a_value) => {
+                        merged.insert(key.clone(), interpolate_config(a_value, b_value, t));
+                    }
+                    None if t >= 0.5 => {
+                        merged.insert(key.clone(), b_value.clone());
+                    }
+                    None => {}
+                }
+            }
+            if t < 0.5 {
+                for (key, a_value) in a {
+                    if !b.contains_key(key) {
+                        merged.insert(key.clone(), a_value.clone());
+                    }
+                }
+            }
+            Value::Object(merged)
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => Value::Array(
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| interpolate_config(a, b, t))
+                .collect(),
+        ),
+        _ => snap(from, to, t),
+    }
+}
+
+fn snap(from: &Value, to: &Value, t: f64) -> Value {
+    if t < 0.5 { from.clone() } else { to.clone() }
+}
+
+/// Integers stay integers (rounded) so integer config fields still
+/// deserialize mid-morph; anything else interpolates as `f64`.
+fn interpolate_number(a: &serde_json::Number, b: &serde_json::Number, t: f64) -> Option<Value> {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        let value = a as f64 + (b as f64 - a as f64) * t;
+        return Some(Value::from(value.round() as i64));
+    }
+    if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+        let value = a as f64 + (b as f64 - a as f64) * t;
+        return Some(Value::from(value.round() as u64));
+    }
+    let (a, b) = (a.as_f64()?, b.as_f64()?);
+    serde_json::Number::from_f64(a + (b - a) * t).map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn curves_hit_both_ends_and_clamp() {
+        for curve in [
+            MorphCurve::Linear,
+            MorphCurve::EaseIn,
+            MorphCurve::EaseOut,
+            MorphCurve::EaseInOut,
+        ] {
+            assert_eq!(curve.ease(0.0), 0.0);
+            assert_eq!(curve.ease(1.0), 1.0);
+            assert_eq!(curve.ease(-1.0), 0.0);
+            assert_eq!(curve.ease(2.0), 1.0);
+        }
+        assert_eq!(MorphCurve::EaseIn.ease(0.5), 0.25);
+        assert_eq!(MorphCurve::EaseOut.ease(0.5), 0.75);
+        assert_eq!(MorphCurve::EaseInOut.ease(0.5), 0.5);
+    }
+
+    #[test]
+    fn numbers_interpolate_and_integers_stay_integers() {
+        let from = json!({ "gain": 0.0, "radius": 2, "taps": [0.0, 10.0] });
+        let to = json!({ "gain": 1.0, "radius": 7, "taps": [1.0, 20.0] });
+        assert_eq!(
+            interpolate_config(&from, &to, 0.5),
+            json!({ "gain": 0.5, "radius": 5, "taps": [0.5, 15.0] })
+        );
+        assert_eq!(interpolate_config(&from, &to, 0.0), from);
+        assert_eq!(interpolate_config(&from, &to, 1.0), to);
+    }
+
+    #[test]
+    fn non_numeric_values_and_one_sided_keys_switch_halfway() {
+        let from = json!({ "mode": "soft", "old_only": true, "nested": { "x": 0.0 } });
+        let to = json!({ "mode": "hard", "new_only": 3, "nested": { "x": 4.0 } });
+        assert_eq!(
+            interpolate_config(&from, &to, 0.25),
+            json!({ "mode": "soft", "old_only": true, "nested": { "x": 1.0 } })
+        );
+        assert_eq!(
+            interpolate_config(&from, &to, 0.75),
+            json!({ "mode": "hard", "new_only": 3, "nested": { "x": 3.0 } })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_empty_and_duplicate_targets() {
+        let morph = PresetMorph::new(Duration::from_secs(1));
+        assert!(morph.validate().is_err());
+
+        let morph = morph
+            .with_target("blur", json!({}))
+            .with_target("blur", json!({}));
+        assert!(morph.validate().is_err());
+    }
+
+    #[test]
+    fn morph_json_round_trips_with_default_curve() {
+        let morph: PresetMorph = serde_json::from_value(json!({
+            "duration_ms": 1500,
+            "targets": [{ "processor_id": "blur", "to": { "radius": 4 } }]
+        }))
+        .unwrap();
+        assert_eq!(morph.curve, MorphCurve::Linear);
+        assert_eq!(morph.duration(), Duration::from_millis(1500));
+        assert_eq!(
+            morph,
+            PresetMorph::new(Duration::from_millis(1500))
+                .with_target("blur", json!({ "radius": 4 }))
+        );
+    }
+}
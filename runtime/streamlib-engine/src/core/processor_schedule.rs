@@ -0,0 +1,655 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Cron-style schedules that start / stop processors at wall-clock times.
+//!
+//! A [`ProcessorSchedule`] binds a five-field cron expression
+//! (`minute hour day-of-month month day-of-week`) and a
+//! [`ScheduleTimezone`] to a [`ProcessorScheduleAction`]. Schedules are
+//! attached with
+//! [`Runner::add_processor_schedule`](crate::core::runtime::Runner::add_processor_schedule),
+//! evaluated once per minute while the runtime is started, and persisted
+//! in the `schedules` section of a
+//! [`GraphSnapshot`](crate::core::graph_snapshot::GraphSnapshot).
+//!
+//! ```json
+//! { "processor": "camera", "cron": "0 9 * * MON-FRI",
+//!   "action": "start", "timezone": "America/New_York" }
+//! ```
+//!
+//! A timezone is either an IANA zone name, whose daylight-saving
+//! transitions the schedule follows, or a fixed UTC offset such as
+//! `-05:00`, which never shifts.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Error, Result};
+
+/// How far back [`ProcessorSchedule::most_recent_firing`] searches.
+const MOST_RECENT_FIRING_LOOKBACK_MINUTES: i64 = 7 * 24 * 60;
+
+/// What a schedule does to its processor when the cron expression fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessorScheduleAction {
+    /// Resume the processor (clears its pause and bypass gates).
+    Start,
+    /// Pause the processor (sets its pause gate).
+    Stop,
+    /// Resume a reactive processor with its bypass gate set, so frames
+    /// pass through it unprocessed.
+    Bypass,
+}
+
+impl fmt::Display for ProcessorScheduleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => f.write_str("start"),
+            Self::Stop => f.write_str("stop"),
+            Self::Bypass => f.write_str("bypass"),
+        }
+    }
+}
+
+/// Wall-clock fields of one minute in a schedule's timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleCivilMinute {
+    pub minute: u32,
+    pub hour: u32,
+    pub day_of_month: u32,
+    pub month: u32,
+    /// `0` = Sunday … `6` = Saturday.
+    pub day_of_week: u32,
+}
+
+/// Timezone a schedule's cron fields are interpreted in.
+///
+/// Parses IANA zone names (`Europe/Berlin`), `UTC`, `Z`, `+HH:MM`,
+/// `-HHMM`, `+HH`, and the same offsets prefixed with `UTC`
+/// (e.g. `UTC+05:30`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleTimezone {
+    rule: TimezoneRule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimezoneRule {
+    /// Minutes east of UTC, all year round.
+    Fixed(i32),
+    Zone(chrono_tz::Tz),
+}
+
+impl ScheduleTimezone {
+    /// UTC (offset zero).
+    pub const UTC: Self = Self {
+        rule: TimezoneRule::Fixed(0),
+    };
+
+    /// Timezone at `offset_minutes` east of UTC.
+    pub fn from_offset_minutes(offset_minutes: i32) -> Result<Self> {
+        if offset_minutes.abs() > 14 * 60 {
+            return Err(Error::Config(format!(
+                "schedule timezone offset {offset_minutes} min is outside ±14:00"
+            )));
+        }
+        Ok(Self {
+            rule: TimezoneRule::Fixed(offset_minutes),
+        })
+    }
+
+    /// Parse a timezone string (see the type docs for accepted forms).
+    pub fn parse(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let rest = trimmed.strip_prefix("UTC").unwrap_or(trimmed);
+        if rest.is_empty() || rest == "Z" {
+            return Ok(Self::UTC);
+        }
+        if let Ok(zone) = trimmed.parse::<chrono_tz::Tz>() {
+            return Ok(Self {
+                rule: TimezoneRule::Zone(zone),
+            });
+        }
+        let invalid = || {
+            Error::Config(format!(
+                "invalid schedule timezone '{s}', expected e.g. 'Europe/Berlin' or '+05:30'"
+            ))
+        };
+        let (sign, digits) = match rest.as_bytes()[0] {
+            b'+' => (1, &rest[1..]),
+            b'-' => (-1, &rest[1..]),
+            _ => return Err(invalid()),
+        };
+        let digits = digits.replace(':', "");
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let (hours, minutes) = match digits.len() {
+            1 | 2 => (digits.parse::<i32>().map_err(|_| invalid())?, 0),
+            4 => (
+                digits[..2].parse::<i32>().map_err(|_| invalid())?,
+                digits[2..].parse::<i32>().map_err(|_| invalid())?,
+            ),
+            _ => return Err(invalid()),
+        };
+        if minutes >= 60 {
+            return Err(invalid());
+        }
+        Self::from_offset_minutes(sign * (hours * 60 + minutes))
+    }
+
+    /// Minutes east of UTC in effect at `unix_minute` (minutes since the
+    /// Unix epoch, UTC).
+    pub fn offset_minutes_at(&self, unix_minute: i64) -> i32 {
+        use chrono::{Offset, TimeZone};
+
+        match self.rule {
+            TimezoneRule::Fixed(offset_minutes) => offset_minutes,
+            TimezoneRule::Zone(zone) => chrono::DateTime::from_timestamp(unix_minute * 60, 0)
+                .map(|at| {
+                    zone.offset_from_utc_datetime(&at.naive_utc())
+                        .fix()
+                        .local_minus_utc()
+                        / 60
+                })
+                .unwrap_or(0),
+        }
+    }
+
+    /// Wall-clock fields for the minute starting at `unix_minute`
+    /// (minutes since the Unix epoch, UTC).
+    pub fn civil_minute(&self, unix_minute: i64) -> ScheduleCivilMinute {
+        let local_minute = unix_minute + i64::from(self.offset_minutes_at(unix_minute));
+        let days = local_minute.div_euclid(24 * 60);
+        let minute_of_day = local_minute.rem_euclid(24 * 60);
+        let (_, month, day_of_month) = civil_from_days(days);
+        ScheduleCivilMinute {
+            minute: (minute_of_day % 60) as u32,
+            hour: (minute_of_day / 60) as u32,
+            day_of_month,
+            month,
+            // 1970-01-01 was a Thursday.
+            day_of_week: (days + 4).rem_euclid(7) as u32,
+        }
+    }
+}
+
+impl Default for ScheduleTimezone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+impl fmt::Display for ScheduleTimezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rule {
+            TimezoneRule::Fixed(0) => f.write_str("UTC"),
+            TimezoneRule::Fixed(offset_minutes) => {
+                let sign = if offset_minutes < 0 { '-' } else { '+' };
+                let abs = offset_minutes.abs();
+                write!(f, "{sign}{:02}:{:02}", abs / 60, abs % 60)
+            }
+            TimezoneRule::Zone(zone) => f.write_str(zone.name()),
+        }
+    }
+}
+
+/// Days since 1970-01-01 → (year, month 1-12, day 1-31), proleptic
+/// Gregorian (Howard Hinnant's `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// One parsed cron field as a bitmask of allowed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CronField {
+    allowed: u64,
+    /// `false` when the field was `*` — needed for the day-of-month /
+    /// day-of-week OR rule.
+    restricted: bool,
+}
+
+impl CronField {
+    fn contains(&self, value: u32) -> bool {
+        self.allowed & (1u64 << value) != 0
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_OF_WEEK_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parse one field value — a number or, where `names` is given, a
+/// three-letter name mapped onto `min + index`.
+fn parse_cron_value(token: &str, min: u32, names: Option<&[&str]>) -> Option<u32> {
+    if let Ok(value) = token.parse::<u32>() {
+        return Some(value);
+    }
+    let upper = token.to_ascii_uppercase();
+    names?
+        .iter()
+        .position(|name| *name == upper)
+        .map(|index| min + index as u32)
+}
+
+fn parse_cron_field(
+    field: &str,
+    field_name: &str,
+    min: u32,
+    max: u32,
+    names: Option<&[&str]>,
+) -> Result<CronField> {
+    let invalid = |detail: &str| {
+        Error::Config(format!(
+            "invalid cron {field_name} field '{field}': {detail}"
+        ))
+    };
+    let mut allowed = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| invalid("step must be a positive integer"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            let start = parse_cron_value(start, min, names)
+                .ok_or_else(|| invalid("unrecognised range start"))?;
+            let end = parse_cron_value(end, min, names)
+                .ok_or_else(|| invalid("unrecognised range end"))?;
+            (start, end)
+        } else {
+            let start =
+                parse_cron_value(range, min, names).ok_or_else(|| invalid("unrecognised value"))?;
+            // `5/15` means "from 5 through max every 15".
+            let end = if part.contains('/') { max } else { start };
+            (start, end)
+        };
+        if start < min || end > max || start > end {
+            return Err(invalid(&format!("values must lie in {min}-{max}")));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            allowed |= 1u64 << value;
+        }
+    }
+    Ok(CronField {
+        allowed,
+        restricted: !field.starts_with('*'),
+    })
+}
+
+/// Parsed five-field cron expression.
+///
+/// Fields support `*`, lists (`1,15`), ranges (`9-17`), steps (`*/5`,
+/// `0-30/10`), and three-letter month / weekday names. Day-of-week
+/// accepts `0`-`7` with both `0` and `7` meaning Sunday. When both
+/// day-of-month and day-of-week are restricted the expression fires when
+/// either matches, following the classic cron rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    source: String,
+    minute: CronField,
+    hour: CronField,
+    day_of_month: CronField,
+    month: CronField,
+    day_of_week: CronField,
+}
+
+impl CronExpression {
+    /// Parse a five-field cron expression.
+    pub fn parse(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(Error::Config(format!(
+                "cron expression '{expression}' must have 5 fields \
+                 (minute hour day-of-month month day-of-week), found {}",
+                fields.len()
+            )));
+        };
+        let mut day_of_week =
+            parse_cron_field(day_of_week, "day-of-week", 0, 7, Some(&DAY_OF_WEEK_NAMES))?;
+        // Fold 7 (Sunday) onto 0.
+        if day_of_week.contains(7) {
+            day_of_week.allowed = (day_of_week.allowed & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            source: fields.join(" "),
+            minute: parse_cron_field(minute, "minute", 0, 59, None)?,
+            hour: parse_cron_field(hour, "hour", 0, 23, None)?,
+            day_of_month: parse_cron_field(day_of_month, "day-of-month", 1, 31, None)?,
+            month: parse_cron_field(month, "month", 1, 12, Some(&MONTH_NAMES))?,
+            day_of_week,
+        })
+    }
+
+    /// The expression as written (whitespace-normalised).
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the expression fires at the given wall-clock minute.
+    pub fn matches(&self, at: &ScheduleCivilMinute) -> bool {
+        if !self.minute.contains(at.minute)
+            || !self.hour.contains(at.hour)
+            || !self.month.contains(at.month)
+        {
+            return false;
+        }
+        let day_of_month = self.day_of_month.contains(at.day_of_month);
+        let day_of_week = self.day_of_week.contains(at.day_of_week);
+        if self.day_of_month.restricted && self.day_of_week.restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A cron expression in a timezone, bound to an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessorSchedule {
+    pub cron: CronExpression,
+    pub timezone: ScheduleTimezone,
+    pub action: ProcessorScheduleAction,
+}
+
+impl ProcessorSchedule {
+    /// Schedule `action` on `cron`, interpreted in UTC.
+    pub fn new(cron: CronExpression, action: ProcessorScheduleAction) -> Self {
+        Self {
+            cron,
+            timezone: ScheduleTimezone::UTC,
+            action,
+        }
+    }
+
+    /// Interpret the cron fields in `timezone` instead of UTC.
+    pub fn with_timezone(mut self, timezone: ScheduleTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Whether the schedule fires in the minute starting at `unix_minute`.
+    pub fn fires_at(&self, unix_minute: i64) -> bool {
+        self.cron.matches(&self.timezone.civil_minute(unix_minute))
+    }
+
+    /// Latest minute at or before `unix_minute` the schedule fired,
+    /// searching back one week. Used to bring processors into their
+    /// scheduled state when the runtime starts between firings.
+    pub fn most_recent_firing(&self, unix_minute: i64) -> Option<i64> {
+        (0..MOST_RECENT_FIRING_LOOKBACK_MINUTES)
+            .map(|back| unix_minute - back)
+            .find(|minute| self.fires_at(*minute))
+    }
+}
+
+/// Snapshot wire shape for one schedule.
+///
+/// `processor` is a snapshot alias; `cron` and `timezone` stay strings so
+/// the snapshot round-trips byte-for-byte and are parsed by
+/// [`Self::to_processor_schedule`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleDefinition {
+    /// Alias of the scheduled processor.
+    pub processor: String,
+
+    /// Five-field cron expression.
+    pub cron: String,
+
+    /// Action applied when the expression fires.
+    pub action: ProcessorScheduleAction,
+
+    /// Fixed UTC offset the cron fields are interpreted in. Absent ↔ UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl ScheduleDefinition {
+    /// Parse into a runtime [`ProcessorSchedule`].
+    pub fn to_processor_schedule(&self) -> Result<ProcessorSchedule> {
+        let timezone = match &self.timezone {
+            Some(tz) => ScheduleTimezone::parse(tz)?,
+            None => ScheduleTimezone::UTC,
+        };
+        Ok(
+            ProcessorSchedule::new(CronExpression::parse(&self.cron)?, self.action)
+                .with_timezone(timezone),
+        )
+    }
+
+    /// Wire shape for `schedule` bound to `processor_alias`.
+    pub fn from_processor_schedule(processor_alias: String, schedule: &ProcessorSchedule) -> Self {
+        Self {
+            processor: processor_alias,
+            cron: schedule.cron.to_string(),
+            action: schedule.action,
+            timezone: (schedule.timezone != ScheduleTimezone::UTC)
+                .then(|| schedule.timezone.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2025-01-06 00:00 UTC, a Monday.
+    const MONDAY_MIDNIGHT_UTC_MINUTE: i64 = 1_736_121_600 / 60;
+
+    #[test]
+    fn civil_minute_matches_known_dates() {
+        let at = ScheduleTimezone::UTC.civil_minute(MONDAY_MIDNIGHT_UTC_MINUTE);
+        assert_eq!(
+            at,
+            ScheduleCivilMinute {
+                minute: 0,
+                hour: 0,
+                day_of_month: 6,
+                month: 1,
+                day_of_week: 1,
+            }
+        );
+
+        // Five hours west of UTC it is still Sunday evening.
+        let new_york = ScheduleTimezone::parse("-05:00").unwrap();
+        let at = new_york.civil_minute(MONDAY_MIDNIGHT_UTC_MINUTE);
+        assert_eq!((at.hour, at.day_of_month, at.day_of_week), (19, 5, 0));
+
+        // Leap day.
+        let at = ScheduleTimezone::UTC.civil_minute(1_709_164_800 / 60);
+        assert_eq!((at.month, at.day_of_month), (2, 29));
+    }
+
+    #[test]
+    fn parse_timezone_forms() {
+        assert_eq!(
+            ScheduleTimezone::parse("UTC").unwrap(),
+            ScheduleTimezone::UTC
+        );
+        assert_eq!(ScheduleTimezone::parse("Z").unwrap(), ScheduleTimezone::UTC);
+        assert_eq!(
+            ScheduleTimezone::parse("+05:30")
+                .unwrap()
+                .offset_minutes_at(0),
+            330
+        );
+        assert_eq!(
+            ScheduleTimezone::parse("UTC-0800")
+                .unwrap()
+                .offset_minutes_at(0),
+            -480
+        );
+        assert_eq!(
+            ScheduleTimezone::parse("+9").unwrap().offset_minutes_at(0),
+            540
+        );
+        assert_eq!(
+            ScheduleTimezone::parse("America/New_York")
+                .unwrap()
+                .to_string(),
+            "America/New_York"
+        );
+        assert!(ScheduleTimezone::parse("America/Nowhere").is_err());
+        assert!(ScheduleTimezone::parse("+15:00").is_err());
+        assert!(ScheduleTimezone::parse("+05:75").is_err());
+        assert_eq!(
+            ScheduleTimezone::parse("-03:30").unwrap().to_string(),
+            "-03:30"
+        );
+    }
+
+    #[test]
+    fn zone_timezone_follows_daylight_saving() {
+        let new_york = ScheduleTimezone::parse("America/New_York").unwrap();
+        // EST in January, EDT from the second Sunday in March.
+        assert_eq!(new_york.offset_minutes_at(MONDAY_MIDNIGHT_UTC_MINUTE), -300);
+        let july_first_2025 = 1_751_328_000 / 60;
+        assert_eq!(new_york.offset_minutes_at(july_first_2025), -240);
+
+        let cron = CronExpression::parse("0 9 * * *").unwrap();
+        let schedule =
+            ProcessorSchedule::new(cron, ProcessorScheduleAction::Start).with_timezone(new_york);
+        assert!(schedule.fires_at(MONDAY_MIDNIGHT_UTC_MINUTE + 14 * 60));
+        assert!(schedule.fires_at(july_first_2025 + 13 * 60));
+    }
+
+    #[test]
+    fn parse_cron_rejects_malformed_expressions() {
+        assert!(CronExpression::parse("* * * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("* 9-25 * * *").is_err());
+        assert!(CronExpression::parse("*/0 * * * *").is_err());
+        assert!(CronExpression::parse("* * 0 * *").is_err());
+        assert!(CronExpression::parse("* * * * FUNDAY").is_err());
+    }
+
+    #[test]
+    fn business_hours_expression_matches_weekdays_only() {
+        let cron = CronExpression::parse("0 9 * * MON-FRI").unwrap();
+        let schedule = ProcessorSchedule::new(cron, ProcessorScheduleAction::Start);
+
+        let monday_nine = MONDAY_MIDNIGHT_UTC_MINUTE + 9 * 60;
+        assert!(schedule.fires_at(monday_nine));
+        assert!(!schedule.fires_at(monday_nine + 1));
+        // Sunday 09:00.
+        assert!(!schedule.fires_at(monday_nine - 24 * 60));
+    }
+
+    #[test]
+    fn steps_lists_and_sunday_alias() {
+        let cron = CronExpression::parse("*/15 0 * * 7").unwrap();
+        let sunday = ScheduleTimezone::UTC.civil_minute(MONDAY_MIDNIGHT_UTC_MINUTE - 24 * 60);
+        assert!(cron.matches(&sunday));
+        assert!(cron.matches(&ScheduleCivilMinute {
+            minute: 45,
+            ..sunday
+        }));
+        assert!(!cron.matches(&ScheduleCivilMinute {
+            minute: 50,
+            ..sunday
+        }));
+
+        let cron = CronExpression::parse("0,30 12 1 jan,jul *").unwrap();
+        assert!(cron.matches(&ScheduleCivilMinute {
+            minute: 30,
+            hour: 12,
+            day_of_month: 1,
+            month: 7,
+            day_of_week: 2,
+        }));
+    }
+
+    #[test]
+    fn restricted_day_of_month_and_week_combine_with_or() {
+        // The 13th, or any Friday.
+        let cron = CronExpression::parse("0 0 13 * FRI").unwrap();
+        let friday_tenth = ScheduleCivilMinute {
+            minute: 0,
+            hour: 0,
+            day_of_month: 10,
+            month: 1,
+            day_of_week: 5,
+        };
+        assert!(cron.matches(&friday_tenth));
+        assert!(cron.matches(&ScheduleCivilMinute {
+            day_of_month: 13,
+            day_of_week: 1,
+            ..friday_tenth
+        }));
+        assert!(!cron.matches(&ScheduleCivilMinute {
+            day_of_month: 14,
+            day_of_week: 2,
+            ..friday_tenth
+        }));
+    }
+
+    #[test]
+    fn most_recent_firing_looks_back_across_days() {
+        let cron = CronExpression::parse("0 17 * * *").unwrap();
+        let schedule = ProcessorSchedule::new(cron, ProcessorScheduleAction::Stop);
+        // Monday 08:00 → the previous firing was Sunday 17:00.
+        let monday_eight = MONDAY_MIDNIGHT_UTC_MINUTE + 8 * 60;
+        assert_eq!(
+            schedule.most_recent_firing(monday_eight),
+            Some(MONDAY_MIDNIGHT_UTC_MINUTE - 7 * 60)
+        );
+    }
+
+    #[test]
+    fn schedule_definition_round_trips() {
+        let json = r#"{ "processor": "camera", "cron": "0  9 * * MON-FRI",
+                        "action": "start", "timezone": "+02:00" }"#;
+        let definition: ScheduleDefinition = serde_json::from_str(json).unwrap();
+        let schedule = definition.to_processor_schedule().unwrap();
+        assert_eq!(schedule.action, ProcessorScheduleAction::Start);
+        assert_eq!(schedule.timezone.offset_minutes_at(0), 120);
+
+        let back = ScheduleDefinition::from_processor_schedule("camera".into(), &schedule);
+        assert_eq!(back.cron, "0 9 * * MON-FRI");
+        assert_eq!(back.timezone.as_deref(), Some("+02:00"));
+
+        let utc = ProcessorSchedule::new(schedule.cron.clone(), ProcessorScheduleAction::Stop);
+        let back = ScheduleDefinition::from_processor_schedule("camera".into(), &utc);
+        assert!(back.timezone.is_none());
+        assert_eq!(serde_json::to_value(&back).unwrap()["action"], "stop");
+
+        let json = r#"{ "processor": "logo", "cron": "0 22 * * *",
+                        "action": "bypass", "timezone": "Europe/Berlin" }"#;
+        let definition: ScheduleDefinition = serde_json::from_str(json).unwrap();
+        let schedule = definition.to_processor_schedule().unwrap();
+        assert_eq!(schedule.action, ProcessorScheduleAction::Bypass);
+        let back = ScheduleDefinition::from_processor_schedule("logo".into(), &schedule);
+        assert_eq!(back.timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(serde_json::to_value(&back).unwrap()["action"], "bypass");
+    }
+}
@@ -19,6 +19,10 @@ pub struct ProcessorSpec {
     /// Display name override. If `None`, defaults to the processor's PascalCase short name.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    /// GPU adapter to run on, as an index into the enumerated adapters.
+    /// `None` runs on the runtime's own device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_adapter: Option<usize>,
 }
 
 impl ProcessorSpec {
@@ -30,6 +34,7 @@ impl ProcessorSpec {
             name: name.into(),
             config,
             display_name: None,
+            gpu_adapter: None,
         }
     }
 
@@ -38,6 +43,12 @@ impl ProcessorSpec {
         self.display_name = Some(display_name.into());
         self
     }
+
+    /// Pin this processor to the GPU adapter at `index`.
+    pub fn with_gpu_adapter(mut self, index: usize) -> Self {
+        self.gpu_adapter = Some(index);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -118,6 +129,21 @@ mod tests {
         assert_eq!(spec.display_name.as_deref(), Some("Camera A"));
     }
 
+    #[test]
+    fn gpu_adapter_round_trips_and_is_omitted_when_unset() {
+        let base = ProcessorSpec::new(
+            ident("tatolab", "core", "VideoFrame", SemVer::new(1, 0, 0)),
+            serde_json::Value::Null,
+        );
+        let json = serde_json::to_value(&base).unwrap();
+        assert!(json.get("gpu_adapter").is_none());
+
+        let pinned = base.with_gpu_adapter(1);
+        let back: ProcessorSpec =
+            serde_json::from_value(serde_json::to_value(&pinned).unwrap()).unwrap();
+        assert_eq!(back.gpu_adapter, Some(1));
+    }
+
     /// A `SchemaIdent` still constructs a spec (via `From<SchemaIdent>`),
     /// landing as a version-pinned reference — the #1325 call-site shape is
     /// unchanged.
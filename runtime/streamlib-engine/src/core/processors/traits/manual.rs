@@ -45,6 +45,14 @@ pub trait ManualProcessor {
         Ok(())
     }
 
+    /// Called after a runtime config update has replaced the processor's
+    /// config field. Processors whose running state derives from config
+    /// (device settings, worker threads) re-apply it here; an error is
+    /// reported back to whoever submitted the update.
+    fn on_config_update(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Called once to start the processor. Privileged ctx.
     fn start(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()>;
 
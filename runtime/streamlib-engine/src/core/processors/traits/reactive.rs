@@ -53,6 +53,14 @@ pub trait ReactiveProcessor {
         Ok(())
     }
 
+    /// Called after a runtime config update has replaced the processor's
+    /// config field. Processors whose running state derives from config
+    /// (device settings, worker threads) re-apply it here; an error is
+    /// reported back to whoever submitted the update.
+    fn on_config_update(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Called when input data arrives. Restricted ctx.
     fn process(&mut self, ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()>;
 }
@@ -7,6 +7,9 @@ use std::collections::HashMap;
 use std::sync::{Arc, LazyLock, OnceLock, Weak};
 
 use super::events::{Event, EventListener, topics};
+use super::journal::{EventJournal, EventJournalConfig, JournalFollow, JournaledEvent};
+use super::recent_errors::{RecentError, RecentErrors};
+use crate::core::error::Result;
 use crate::iceoryx2::{EventPayload, Iceoryx2EventService, Iceoryx2Node, MAX_EVENT_PAYLOAD_SIZE};
 
 type EventPublisher =
@@ -48,6 +51,10 @@ pub struct PubSub {
     node: OnceLock<Iceoryx2Node>,
     // Subscriptions registered before init() — replayed when init() is called
     pending_subscriptions: Mutex<Vec<(String, Arc<Mutex<dyn EventListener>>)>>,
+    // Replay ring of published events, once enable_journal() has run
+    journal: Mutex<Option<EventJournal>>,
+    // The last few failure events, always kept
+    recent_errors: Mutex<RecentErrors>,
 }
 
 impl Default for PubSub {
@@ -62,6 +69,8 @@ impl PubSub {
             runtime_id: OnceLock::new(),
             node: OnceLock::new(),
             pending_subscriptions: Mutex::new(Vec::new()),
+            journal: Mutex::new(None),
+            recent_errors: Mutex::new(RecentErrors::new()),
         }
     }
 
@@ -82,6 +91,45 @@ impl PubSub {
         }
     }
 
+    /// Start journaling published events for replay, replacing any journal
+    /// already running. With a `path`, events already in the file are
+    /// loaded, so replay reaches back across restarts.
+    pub fn enable_journal(&self, config: EventJournalConfig) -> Result<()> {
+        // Stop the running journal first, outside the lock, so its writer
+        // thread has finished with the file before it is reopened.
+        let previous = self.journal.lock().take();
+        drop(previous);
+        let journal = EventJournal::open(config)?;
+        *self.journal.lock() = Some(journal);
+        Ok(())
+    }
+
+    /// Journaled events published after `since_ns` (nanoseconds since the
+    /// Unix epoch), oldest first. `None` when no journal is enabled.
+    pub fn journal_since(&self, since_ns: i64) -> Option<Vec<JournaledEvent>> {
+        self.journal
+            .lock()
+            .as_ref()
+            .map(|journal| journal.since(since_ns))
+    }
+
+    /// [`journal_since`](Self::journal_since) plus every event journaled
+    /// afterwards, with nothing lost or repeated between the two. `None`
+    /// when no journal is enabled.
+    pub fn follow_journal(&self, since_ns: i64) -> Option<JournalFollow> {
+        self.journal
+            .lock()
+            .as_mut()
+            .map(|journal| journal.follow(since_ns))
+    }
+
+    /// The newest `limit` failure events published (runtime, compile and
+    /// processor errors), oldest first. Kept whether or not a journal is
+    /// enabled.
+    pub fn recent_errors(&self, limit: usize) -> Vec<RecentError> {
+        self.recent_errors.lock().latest(limit)
+    }
+
     /// Subscribe a listener to a topic.
     ///
     /// The subscriber thread holds only a Weak reference to the listener.
@@ -265,6 +313,11 @@ impl PubSub {
             .map(|d| d.as_nanos() as i64)
             .unwrap_or(0);
 
+        if let Some(journal) = self.journal.lock().as_mut() {
+            journal.record(topic, timestamp_ns, event);
+        }
+        self.recent_errors.lock().record(topic, timestamp_ns, event);
+
         let payload = EventPayload::new(topic, timestamp_ns, &bytes);
 
         // Send to topic-specific service
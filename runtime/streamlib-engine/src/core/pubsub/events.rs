@@ -1,6 +1,7 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::chaos::ChaosFaultSource;
 use crate::core::descriptors::SchemaIdent;
 use crate::core::error::Result;
 use crate::core::graph::ProcessorUniqueId;
@@ -34,10 +35,13 @@ pub trait EventListener: Send {
     fn on_event(&mut self, event: &Event) -> Result<()>;
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// A pubsub event. `/ws/events` streams each one as externally tagged JSON
+/// text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum Event {
     RuntimeGlobal(RuntimeEvent),
     ProcessorEvent {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
         event: ProcessorEvent,
     },
@@ -129,9 +133,32 @@ impl Event {
             }
         }
     }
+
+    /// The error message this event reports, if it reports a failure: a
+    /// runtime lifecycle or compile failure, a runtime or processor error, a
+    /// failed asset fetch or chaos fault. `None` for everything else.
+    pub fn error_message(&self) -> Option<&str> {
+        match self {
+            Event::RuntimeGlobal(
+                RuntimeEvent::RuntimeStartFailed { error }
+                | RuntimeEvent::RuntimeStopFailed { error }
+                | RuntimeEvent::RuntimePauseFailed { error }
+                | RuntimeEvent::RuntimeResumeFailed { error }
+                | RuntimeEvent::RuntimeError { error }
+                | RuntimeEvent::CompilerDidFail { error }
+                | RuntimeEvent::AssetFetchDidFail { error, .. }
+                | RuntimeEvent::ChaosFaultFailed { error, .. },
+            ) => Some(error),
+            Event::ProcessorEvent {
+                event: ProcessorEvent::Error(error),
+                ..
+            } => Some(error),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum RuntimeEvent {
     // ===== Runtime Lifecycle =====
     /// Emitted when runtime is about to start
@@ -199,18 +226,22 @@ pub enum RuntimeEvent {
     // Emitted by Runtime when user adds/removes processors
     /// Emitted when runtime will add a processor to the graph
     RuntimeWillAddProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when runtime did add a processor to the graph
     RuntimeDidAddProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when runtime will remove a processor from the graph
     RuntimeWillRemoveProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when runtime did remove a processor from the graph
     RuntimeDidRemoveProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
 
@@ -218,8 +249,10 @@ pub enum RuntimeEvent {
     // Emitted by Runtime when user connects/disconnects ports
     /// Emitted when runtime will connect two ports
     RuntimeWillConnect {
+        #[schema(value_type = String)]
         from_processor: ProcessorUniqueId,
         from_port: String,
+        #[schema(value_type = String)]
         to_processor: ProcessorUniqueId,
         to_port: String,
     },
@@ -245,10 +278,12 @@ pub enum RuntimeEvent {
     // ===== Processor State Events =====
     /// Emitted when a processor's configuration is updated
     ProcessorConfigDidChange {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when a processor's state changes (started, stopped, paused, etc.)
     ProcessorStateDidChange {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
         old_state: ProcessorState,
         new_state: ProcessorState,
@@ -266,20 +301,26 @@ pub enum RuntimeEvent {
     },
     /// Emitted when compiler will create a processor instance
     CompilerWillCreateProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
+        #[schema(value_type = Object)]
         processor_type: SchemaIdent,
     },
     /// Emitted when compiler did create a processor instance
     CompilerDidCreateProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
+        #[schema(value_type = Object)]
         processor_type: SchemaIdent,
     },
     /// Emitted when compiler will destroy a processor instance
     CompilerWillDestroyProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when compiler did destroy a processor instance
     CompilerDidDestroyProcessor {
+        #[schema(value_type = String)]
         processor_id: ProcessorUniqueId,
     },
     /// Emitted when compiler will wire a link (create ring buffer)
@@ -317,23 +358,159 @@ pub enum RuntimeEvent {
     // ===== Factory/Registration Events =====
     /// Emitted when a new processor type is registered with the factory
     RuntimeDidRegisterProcessorType {
+        #[schema(value_type = Object)]
         processor_type: SchemaIdent,
     },
     /// Emitted when a processor type is unregistered from the factory
     /// (`remove_module`). Additive variant — appended so existing msgpack
     /// consumers keep decoding earlier variants unchanged.
     RuntimeDidUnregisterProcessorType {
+        #[schema(value_type = Object)]
         processor_type: SchemaIdent,
     },
+
+    // ===== Link Health Events =====
+    /// Emitted when a link's windowed frame-drop rate crosses the congestion
+    /// threshold. Fires once per rising edge, not per sample. Additive
+    /// variant — appended so existing msgpack consumers keep decoding.
+    LinkCongested {
+        link_id: String,
+        from_port: String,
+        to_port: String,
+        window_frames_dropped: u64,
+        window_drop_rate: f64,
+    },
+
+    // ===== Federation Events =====
+    /// Emitted when a link to a processor in another runtime is established.
+    /// Additive variant — appended so existing msgpack consumers keep decoding.
+    RemoteLinkDidConnect {
+        link_id: String,
+        remote_runtime_id: String,
+        from_port: String,
+        to_port: String,
+    },
+    /// Emitted when a link to another runtime is torn down — explicitly, or
+    /// because the remote runtime stopped answering its broker socket.
+    RemoteLinkDidDisconnect {
+        link_id: String,
+        remote_runtime_id: String,
+        reason: String,
+    },
+
+    // ===== Asset Events =====
+    /// Emitted roughly every MiB while the asset manager downloads `url`.
+    /// `total_bytes` is `None` when the source sent no length. Additive
+    /// variant — appended so existing msgpack consumers keep decoding.
+    AssetFetchProgress {
+        url: String,
+        bytes_received: u64,
+        total_bytes: Option<u64>,
+    },
+    /// Emitted when a download finished and landed in the asset cache.
+    AssetFetchDidComplete {
+        url: String,
+        sha256: String,
+        bytes: u64,
+    },
+    /// Emitted when a download failed, including a checksum mismatch.
+    AssetFetchDidFail {
+        url: String,
+        error: String,
+    },
+
+    // ===== Device Events =====
+    /// Emitted when the device monitor sees a capture device appear.
+    /// Additive variant — appended so existing msgpack consumers keep
+    /// decoding.
+    DeviceConnected {
+        device: DeviceInfo,
+    },
+    /// Emitted when a device the monitor had seen goes away.
+    DeviceDisconnected {
+        device: DeviceInfo,
+    },
+
+    // ===== Clock Events =====
+    /// Emitted when a device's reference-signal (genlock) status changes.
+    /// Repeated identical reports are not re-emitted. Additive variant —
+    /// appended so existing msgpack consumers keep decoding.
+    ReferenceSignalChanged {
+        source: String,
+        locked: bool,
+        mode: Option<String>,
+    },
+
+    // ===== Chaos Events =====
+    /// Emitted when chaos mode injects a fault. `kind` is the fault's tag
+    /// (`stall_link`, `kill_processor`, …) and `target` the processor or
+    /// link it hit. Additive variant — appended so existing msgpack
+    /// consumers keep decoding.
+    ChaosFaultInjected {
+        fault_id: u64,
+        kind: String,
+        target: String,
+        source: ChaosFaultSource,
+    },
+    /// Emitted when a fault's duration ran out and it was lifted.
+    ChaosFaultCleared {
+        fault_id: u64,
+        kind: String,
+        target: String,
+    },
+    /// Emitted when injecting or lifting a fault failed.
+    ChaosFaultFailed {
+        fault_id: u64,
+        kind: String,
+        target: String,
+        error: String,
+    },
+
+    // ===== GPU Events =====
+    /// Emitted when the runtime's GPU texture pool comes under pressure —
+    /// occupancy crossed the pressure threshold, or acquires or device
+    /// allocations failed since the previous sample. Fires on the rising
+    /// edge so graphs can shed load before processors stall on `acquire`.
+    /// Additive variant — appended so existing msgpack consumers keep
+    /// decoding.
+    GpuPoolPressure {
+        textures_in_use: u64,
+        capacity: u64,
+        high_water_in_use: u64,
+        occupancy: f64,
+        allocation_failures: u64,
+        exhausted_acquires: u64,
+    },
+}
+
+/// Kind of device the device monitor tracks.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, utoipa::ToSchema,
+)]
+pub enum DeviceKind {
+    Camera,
+    AudioInterface,
+}
+
+/// A device reported by [`RuntimeEvent::DeviceConnected`] /
+/// [`RuntimeEvent::DeviceDisconnected`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeviceInfo {
+    pub kind: DeviceKind,
+    /// Stable handle a processor config can name — the V4L2 node
+    /// (`/dev/video0`) for cameras, the ALSA card (`hw:1`) for audio.
+    pub id: String,
+    /// Human-readable device name as the driver reports it.
+    pub name: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum LinkPortDirection {
     Input,
     Output,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum ProcessorEvent {
     // ===== State Control Commands =====
     Start,
@@ -346,6 +523,8 @@ pub enum ProcessorEvent {
     Stopped,
     Paused,
     Resumed,
+    Bypassed,
+    BypassCleared,
     Error(String),
     StateChanged {
         old_state: ProcessorState,
@@ -392,7 +571,7 @@ pub use crate::core::processors::ProcessorState;
 
 // ===== Input Types =====
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum KeyCode {
     // Letters
     A,
@@ -490,7 +669,7 @@ pub enum KeyCode {
 /// 2. KeyboardInput { key: KeyCode::A, modifiers: { shift: true }, state: Pressed }
 ///
 /// This matches web behavior where modifiers are both keys AND state.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Modifiers {
     pub shift: bool,
     pub ctrl: bool,
@@ -525,14 +704,14 @@ impl Modifiers {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum KeyState {
     Pressed,
     Released,
     Held,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum MouseButton {
     Left,
     Right,
@@ -540,13 +719,13 @@ pub enum MouseButton {
     Other(u16),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum MouseState {
     Pressed,
     Released,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum WindowEventType {
     Resized { width: u32, height: u32 },
     Closed,
@@ -0,0 +1,472 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Event journal — a bounded ring of the events published through this
+//! process's [`PUBSUB`](super::PUBSUB), optionally persisted as JSON lines.
+//!
+//! A client that reconnects (a web UI, the MCP server) replays the journal
+//! from the last timestamp it saw instead of re-deriving state and racing
+//! the live stream. [`EventJournal::follow`] hands out the backlog and a
+//! live receiver under one lock, so nothing published in between is lost
+//! or seen twice.
+//!
+//! Input events (`input:*` topics) are not journaled: pointer motion would
+//! push everything else out of the ring.
+//!
+//! Recording never blocks the publisher. File writes are queued to a writer
+//! thread, and events that overflow its queue are ring-only. Each follower
+//! gets a bounded queue; one that falls that far behind is disconnected and
+//! resumes by following again from the last timestamp it saw.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, SyncSender, TryRecvError, TrySendError, sync_channel};
+use std::thread::JoinHandle;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use super::events::Event;
+use crate::core::error::{Error, Result};
+
+/// Default ring size when none is configured.
+pub const DEFAULT_EVENT_JOURNAL_CAPACITY: usize = 4096;
+
+/// Topic prefix of the input events the journal skips.
+const INPUT_TOPIC_PREFIX: &str = "input:";
+
+/// Events queued for the writer thread; past this they are not persisted.
+const JOURNAL_WRITE_QUEUE_DEPTH: usize = 1024;
+
+/// Events queued for one follower; past this it is disconnected.
+const JOURNAL_FOLLOWER_QUEUE_DEPTH: usize = 1024;
+
+/// How the journal is sized and where it persists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventJournalConfig {
+    /// Events kept for replay; older ones fall off the ring.
+    pub capacity: usize,
+    /// JSON-lines file the ring is persisted to. Events already in the file
+    /// are loaded on enable, so replay reaches back across restarts.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for EventJournalConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_EVENT_JOURNAL_CAPACITY,
+            path: None,
+        }
+    }
+}
+
+/// One journaled event: the event as published, with the topic it went to,
+/// its publish time and a sequence number that orders events sharing a
+/// timestamp.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct JournaledEvent {
+    pub seq: u64,
+    /// Wall-clock publish time, nanoseconds since the Unix epoch.
+    pub timestamp_ns: i64,
+    pub topic: String,
+    pub event: Event,
+}
+
+/// A backlog replayed from the journal, and every event journaled after it.
+pub struct JournalFollow {
+    pub backlog: Vec<JournaledEvent>,
+    /// Closes once the journal is replaced or this follower falls too far
+    /// behind to keep up.
+    pub live: mpsc::Receiver<JournaledEvent>,
+}
+
+/// The ring, its persistence thread and its live followers.
+pub(crate) struct EventJournal {
+    entries: VecDeque<JournaledEvent>,
+    capacity: usize,
+    next_seq: u64,
+    writer: Option<JournalWriter>,
+    followers: Vec<mpsc::Sender<JournaledEvent>>,
+}
+
+/// Hand-off to the thread that owns the persistence file.
+struct JournalWriter {
+    queue: SyncSender<JournaledEvent>,
+    thread: JoinHandle<()>,
+    /// Events not persisted because the queue was full.
+    dropped: u64,
+}
+
+/// The persistence file, owned by the writer thread.
+struct JournalFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    capacity: usize,
+    /// Lines in the file; past twice the capacity it is rewritten from
+    /// `recent`.
+    lines: usize,
+    /// The newest `capacity` events written, mirroring the ring.
+    recent: VecDeque<JournaledEvent>,
+}
+
+impl EventJournal {
+    /// Open a journal, loading the newest `capacity` events already in the
+    /// configured file.
+    pub(crate) fn open(config: EventJournalConfig) -> Result<Self> {
+        if config.capacity == 0 {
+            return Err(Error::Configuration(
+                "event journal capacity must be at least 1".into(),
+            ));
+        }
+        let mut journal = Self {
+            entries: VecDeque::with_capacity(config.capacity),
+            capacity: config.capacity,
+            next_seq: 0,
+            writer: None,
+            followers: Vec::new(),
+        };
+        if let Some(path) = config.path {
+            for entry in read_journal_file(&path)? {
+                journal.push(entry);
+            }
+            journal.next_seq = journal.entries.back().map_or(0, |entry| entry.seq + 1);
+            let file = JournalFile {
+                writer: rewrite_journal_file(&path, &journal.entries)?,
+                path,
+                capacity: config.capacity,
+                lines: journal.entries.len(),
+                recent: journal.entries.clone(),
+            };
+            journal.writer = Some(JournalWriter::spawn(file)?);
+        }
+        Ok(journal)
+    }
+
+    /// Journal `event`, published to `topic` at `timestamp_ns`.
+    pub(crate) fn record(&mut self, topic: &str, timestamp_ns: i64, event: &Event) {
+        if topic.starts_with(INPUT_TOPIC_PREFIX) {
+            return;
+        }
+        let entry = JournaledEvent {
+            seq: self.next_seq,
+            timestamp_ns,
+            topic: topic.to_string(),
+            event: event.clone(),
+        };
+        self.next_seq += 1;
+
+        self.followers
+            .retain(|follower| match follower.try_send(entry.clone()) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    tracing::warn!(
+                        "Event journal: disconnecting a follower {} events behind",
+                        JOURNAL_FOLLOWER_QUEUE_DEPTH
+                    );
+                    false
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            });
+        if let Some(writer) = self.writer.as_mut() {
+            writer.queue(entry.clone());
+        }
+        self.push(entry);
+    }
+
+    /// Events published after `since_ns`, oldest first.
+    pub(crate) fn since(&self, since_ns: i64) -> Vec<JournaledEvent> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.timestamp_ns > since_ns)
+            .cloned()
+            .collect()
+    }
+
+    /// [`since`](Self::since) plus a receiver for every event journaled
+    /// from now on.
+    pub(crate) fn follow(&mut self, since_ns: i64) -> JournalFollow {
+        let (tx, live) = mpsc::channel(JOURNAL_FOLLOWER_QUEUE_DEPTH);
+        self.followers.push(tx);
+        JournalFollow {
+            backlog: self.since(since_ns),
+            live,
+        }
+    }
+
+    fn push(&mut self, entry: JournaledEvent) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+impl Drop for EventJournal {
+    fn drop(&mut self) {
+        // Let the writer thread persist what is still queued.
+        let Some(JournalWriter {
+            queue,
+            thread,
+            dropped,
+        }) = self.writer.take()
+        else {
+            return;
+        };
+        drop(queue);
+        if thread.join().is_err() {
+            tracing::warn!("Event journal: writer thread panicked");
+        }
+        if dropped > 0 {
+            tracing::warn!(
+                "Event journal: {dropped} events were not persisted, the writer fell behind"
+            );
+        }
+    }
+}
+
+impl JournalWriter {
+    fn spawn(file: JournalFile) -> Result<Self> {
+        let (queue, queued) = sync_channel(JOURNAL_WRITE_QUEUE_DEPTH);
+        let thread = std::thread::Builder::new()
+            .name("event-journal".into())
+            .spawn(move || write_journal(file, queued))
+            .map_err(|e| Error::Runtime(format!("spawning event journal writer thread: {e}")))?;
+        Ok(Self {
+            queue,
+            thread,
+            dropped: 0,
+        })
+    }
+
+    /// Queue `entry` for the file without waiting on the writer thread.
+    fn queue(&mut self, entry: JournaledEvent) {
+        match self.queue.try_send(entry) {
+            Ok(()) => {}
+            Err(TrySendError::Full(entry)) => {
+                self.dropped += 1;
+                if self.dropped == 1 {
+                    tracing::warn!(
+                        "Event journal: writer is behind, event {} and any further overflow \
+                         will not be persisted",
+                        entry.seq
+                    );
+                }
+            }
+            // The writer thread is gone; its panic is reported on drop.
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+}
+
+/// Writer thread: append every queued event, flushing whenever the queue
+/// runs dry, until the journal drops its sender.
+fn write_journal(mut file: JournalFile, queued: Receiver<JournaledEvent>) {
+    loop {
+        let entry = match queued.try_recv() {
+            Ok(entry) => entry,
+            Err(TryRecvError::Empty) => {
+                if let Err(e) = file.writer.flush() {
+                    tracing::warn!(
+                        "Event journal: flushing {} failed: {e}",
+                        file.path.display()
+                    );
+                }
+                match queued.recv() {
+                    Ok(entry) => entry,
+                    Err(_) => break,
+                }
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+        let seq = entry.seq;
+        if let Err(e) = file.append(entry) {
+            tracing::warn!("Event journal: persisting event {seq} failed: {e}");
+        }
+    }
+    if let Err(e) = file.writer.flush() {
+        tracing::warn!(
+            "Event journal: flushing {} failed: {e}",
+            file.path.display()
+        );
+    }
+}
+
+impl JournalFile {
+    /// Append `entry`, rewriting the file from `recent` once it holds twice
+    /// the capacity.
+    fn append(&mut self, entry: JournaledEvent) -> Result<()> {
+        write_line(&mut self.writer, &entry)?;
+        self.lines += 1;
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(entry);
+        if self.lines > self.capacity * 2 {
+            self.writer.flush()?;
+            self.writer = rewrite_journal_file(&self.path, &self.recent)?;
+            self.lines = self.recent.len();
+        }
+        Ok(())
+    }
+}
+
+/// Replace the file at `path` with `entries` and reopen it for appending.
+fn rewrite_journal_file(
+    path: &Path,
+    entries: &VecDeque<JournaledEvent>,
+) -> Result<BufWriter<File>> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    let staging = path.with_extension("jsonl.tmp");
+    {
+        let mut writer = BufWriter::new(File::create(&staging)?);
+        for entry in entries {
+            write_line(&mut writer, entry)?;
+        }
+        writer.flush()?;
+    }
+    std::fs::rename(&staging, path)?;
+    let file = OpenOptions::new().append(true).open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+fn write_line(writer: &mut impl Write, entry: &JournaledEvent) -> Result<()> {
+    serde_json::to_writer(&mut *writer, entry)
+        .map_err(|e| Error::Runtime(format!("event journal: encoding event failed: {e}")))?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Every well-formed entry in the file, oldest first. A missing file is an
+/// empty journal; a torn or unreadable line (a crash mid-write, an event
+/// shape from another version) is skipped.
+fn read_journal_file(path: &Path) -> Result<Vec<JournaledEvent>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<JournaledEvent>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => tracing::debug!("Event journal: skipping unreadable line: {e}"),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pubsub::topics;
+
+    fn custom(n: u64) -> Event {
+        Event::custom("test:journal", serde_json::json!({ "n": n }))
+    }
+
+    fn in_memory(capacity: usize) -> EventJournal {
+        EventJournal::open(EventJournalConfig {
+            capacity,
+            path: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn the_ring_keeps_the_newest_events_and_skips_input() {
+        let mut journal = in_memory(2);
+        for n in 0..3 {
+            journal.record("test:journal", 100 + n as i64, &custom(n));
+        }
+        journal.record(topics::MOUSE, 200, &custom(99));
+
+        let replayed = journal.since(0);
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].seq, 1);
+        assert_eq!(replayed[1].event, custom(2));
+        assert_eq!(journal.since(101).len(), 1);
+        assert!(journal.since(102).is_empty());
+    }
+
+    #[test]
+    fn follow_hands_out_the_backlog_and_then_only_new_events() {
+        let mut journal = in_memory(8);
+        journal.record("test:journal", 10, &custom(0));
+        let mut follow = journal.follow(0);
+        journal.record("test:journal", 20, &custom(1));
+
+        assert_eq!(follow.backlog.len(), 1);
+        assert_eq!(follow.live.try_recv().unwrap().event, custom(1));
+        assert!(follow.live.try_recv().is_err());
+
+        drop(follow);
+        journal.record("test:journal", 30, &custom(2));
+        assert!(journal.followers.is_empty());
+    }
+
+    #[test]
+    fn a_follower_that_falls_behind_is_disconnected() {
+        let mut journal = in_memory(8);
+        let mut follow = journal.follow(0);
+        for n in 0..=JOURNAL_FOLLOWER_QUEUE_DEPTH as u64 {
+            journal.record("test:journal", n as i64, &custom(n));
+        }
+        assert!(journal.followers.is_empty());
+
+        let mut received = 0;
+        while follow.live.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, JOURNAL_FOLLOWER_QUEUE_DEPTH);
+        assert_eq!(
+            follow.live.try_recv(),
+            Err(mpsc::error::TryRecvError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn a_persisted_journal_reloads_and_compacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let config = EventJournalConfig {
+            capacity: 2,
+            path: Some(path.clone()),
+        };
+
+        let mut journal = EventJournal::open(config.clone()).unwrap();
+        for n in 0..6 {
+            journal.record("test:journal", n as i64, &custom(n));
+        }
+        drop(journal);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"torn\":")
+            .unwrap();
+
+        let mut reopened = EventJournal::open(config).unwrap();
+        let replayed = reopened.since(i64::MIN);
+        assert_eq!(
+            replayed.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            [4, 5]
+        );
+        reopened.record("test:journal", 6, &custom(6));
+        assert_eq!(reopened.since(5)[0].seq, 6);
+        drop(reopened);
+
+        let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+        assert_eq!(lines, 3);
+    }
+}
@@ -3,9 +3,17 @@
 
 mod bus;
 mod events;
+mod journal;
+mod recent_errors;
 
 #[cfg(test)]
 mod integration_tests;
 
 pub use bus::{PUBSUB, PubSub};
-pub use events::{Event, EventListener, ProcessorEvent, RuntimeEvent, topics};
+pub use events::{
+    DeviceInfo, DeviceKind, Event, EventListener, ProcessorEvent, RuntimeEvent, topics,
+};
+pub use journal::{
+    DEFAULT_EVENT_JOURNAL_CAPACITY, EventJournalConfig, JournalFollow, JournaledEvent,
+};
+pub use recent_errors::{RECENT_ERROR_CAPACITY, RecentError};
@@ -0,0 +1,111 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Recent errors — a small ring of the failure events published through this
+//! process's [`PUBSUB`](super::PUBSUB), kept whether or not the event journal
+//! is enabled.
+//!
+//! A failed compile or a processor error is usually the answer to "why is my
+//! output black", and it scrolls out of a log within seconds. The ring keeps
+//! the last [`RECENT_ERROR_CAPACITY`] of them for the API server and the MCP
+//! resources to hand out on request.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::events::Event;
+
+/// Failure events kept; older ones fall off the ring.
+pub const RECENT_ERROR_CAPACITY: usize = 64;
+
+/// One failure event, with the topic it went to, its publish time and the
+/// error message it carried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RecentError {
+    /// Wall-clock publish time, nanoseconds since the Unix epoch.
+    pub timestamp_ns: i64,
+    pub topic: String,
+    pub message: String,
+    pub event: Event,
+}
+
+/// The ring itself; events that report no error are ignored.
+pub(crate) struct RecentErrors {
+    entries: VecDeque<RecentError>,
+}
+
+impl RecentErrors {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(RECENT_ERROR_CAPACITY),
+        }
+    }
+
+    /// Keep `event` if it reports an error.
+    pub(crate) fn record(&mut self, topic: &str, timestamp_ns: i64, event: &Event) {
+        let Some(message) = event.error_message() else {
+            return;
+        };
+        if self.entries.len() == RECENT_ERROR_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(RecentError {
+            timestamp_ns,
+            topic: topic.to_string(),
+            message: message.to_string(),
+            event: event.clone(),
+        });
+    }
+
+    /// The newest `limit` errors, oldest first.
+    pub(crate) fn latest(&self, limit: usize) -> Vec<RecentError> {
+        let skip = self.entries.len().saturating_sub(limit);
+        self.entries.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pubsub::{ProcessorEvent, RuntimeEvent, topics};
+
+    #[test]
+    fn only_failures_are_kept_and_the_newest_win() {
+        let mut errors = RecentErrors::new();
+        errors.record(
+            topics::RUNTIME_GLOBAL,
+            1,
+            &Event::RuntimeGlobal(RuntimeEvent::RuntimeStarted),
+        );
+        errors.record(
+            "processor:blur",
+            2,
+            &Event::processor("blur", ProcessorEvent::Error("shader failed".into())),
+        );
+        assert_eq!(errors.latest(usize::MAX).len(), 1);
+        assert_eq!(errors.latest(1)[0].message, "shader failed");
+
+        for n in 0..RECENT_ERROR_CAPACITY as i64 {
+            errors.record(
+                topics::RUNTIME_GLOBAL,
+                10 + n,
+                &Event::RuntimeGlobal(RuntimeEvent::CompilerDidFail {
+                    error: format!("compile {n}"),
+                }),
+            );
+        }
+
+        let all = errors.latest(usize::MAX);
+        assert_eq!(all.len(), RECENT_ERROR_CAPACITY);
+        assert_eq!(all[0].message, "compile 0");
+
+        let newest = errors.latest(2);
+        assert_eq!(newest.len(), 2);
+        assert_eq!(
+            newest[1].timestamp_ns,
+            10 + RECENT_ERROR_CAPACITY as i64 - 1
+        );
+        assert!(errors.latest(0).is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! GPU adapter selection — which physical device the backend opens.
+//!
+//! The adapter can be selected at runtime via:
+//! 1. Explicit parameter passed to `GpuAdapterPreference::resolve()`
+//! 2. `STREAMLIB_GPU_ADAPTER` environment variable
+//! 3. `Auto` (discrete GPU if present, otherwise the first device)
+//!
+//! `software` picks a CPU rasterizer — Mesa's lavapipe or SwiftShader on
+//! Vulkan — so texture-touching tests run on CI runners without a GPU.
+//! Slow, but the same code path as hardware.
+//!
+//! On multi-GPU machines (iGPU + dGPU, multi-card render boxes) an
+//! `index:N` preference opens the N-th device of
+//! [`GpuContext::enumerate_adapters`](crate::core::context::GpuContext::enumerate_adapters);
+//! processors pin to a device the same way through
+//! [`ProcessorSpec::gpu_adapter`](crate::core::processors::ProcessorSpec::gpu_adapter).
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Which kind of physical device to open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GpuAdapterPreference {
+    /// Discrete GPU if present, otherwise the first device enumerated.
+    #[default]
+    Auto,
+    /// Any hardware GPU; never a software rasterizer.
+    Hardware,
+    /// A software rasterizer only (lavapipe, SwiftShader).
+    Software,
+    /// The device at this position in enumeration order.
+    Index(usize),
+}
+
+/// The kind of a physical device, as the backend reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuAdapterKind {
+    Discrete,
+    Integrated,
+    Virtual,
+    /// A software rasterizer running on the CPU.
+    Cpu,
+    Other,
+}
+
+/// One physical device the backend can open, as reported by
+/// [`GpuContext::enumerate_adapters`](crate::core::context::GpuContext::enumerate_adapters).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuAdapterInfo {
+    /// Position in enumeration order — the value `index:N` preferences and
+    /// processor pins refer to.
+    pub index: usize,
+    /// Driver-reported device name.
+    pub name: String,
+    pub kind: GpuAdapterKind,
+    /// PCI vendor id (`0x10DE` NVIDIA, `0x1002` AMD, `0x8086` Intel); `0`
+    /// where the backend does not report one.
+    pub vendor_id: u32,
+    /// PCI device id; `0` where the backend does not report one.
+    pub device_id: u32,
+    /// Stable device UUID (`VkPhysicalDeviceIDProperties::deviceUUID` on
+    /// Vulkan, the registry id on Metal) for matching against CUDA or
+    /// other APIs.
+    pub uuid: [u8; 16],
+}
+
+impl GpuAdapterPreference {
+    /// Environment variable name for adapter override.
+    pub const ENV_VAR: &'static str = "STREAMLIB_GPU_ADAPTER";
+
+    /// Resolve the adapter preference to use.
+    ///
+    /// Resolution priority:
+    /// 1. Explicit value (if provided)
+    /// 2. `STREAMLIB_GPU_ADAPTER` environment variable
+    /// 3. `Auto`
+    pub fn resolve(explicit: Option<Self>) -> Self {
+        if let Some(preference) = explicit {
+            return preference;
+        }
+
+        if let Ok(env_value) = std::env::var(Self::ENV_VAR) {
+            match env_value.parse() {
+                Ok(preference) => return preference,
+                Err(e) => tracing::warn!("Ignoring {}: {}", Self::ENV_VAR, e),
+            }
+        }
+
+        Self::Auto
+    }
+
+    /// Index of the device to open among `adapters` (in enumeration
+    /// order), or `None` if no device satisfies the preference.
+    pub fn pick(&self, adapters: &[GpuAdapterKind]) -> Option<usize> {
+        let position = |kind| adapters.iter().position(|a| *a == kind);
+        match self {
+            Self::Auto => {
+                position(GpuAdapterKind::Discrete).or((!adapters.is_empty()).then_some(0))
+            }
+            Self::Hardware => [
+                GpuAdapterKind::Discrete,
+                GpuAdapterKind::Integrated,
+                GpuAdapterKind::Virtual,
+                GpuAdapterKind::Other,
+            ]
+            .into_iter()
+            .find_map(position),
+            Self::Software => position(GpuAdapterKind::Cpu),
+            Self::Index(index) => (*index < adapters.len()).then_some(*index),
+        }
+    }
+
+    /// Get the preference name as a string. `Index` reports `"index"`;
+    /// [`Display`](std::fmt::Display) includes the position.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Hardware => "hardware",
+            Self::Software => "software",
+            Self::Index(_) => "index",
+        }
+    }
+}
+
+impl FromStr for GpuAdapterPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        let index = lower.strip_prefix("index:").unwrap_or(&lower);
+        if let Ok(index) = index.parse::<usize>() {
+            return Ok(Self::Index(index));
+        }
+        match lower.as_str() {
+            "auto" => Ok(Self::Auto),
+            "hardware" | "gpu" => Ok(Self::Hardware),
+            "software" | "cpu" | "lavapipe" | "swiftshader" => Ok(Self::Software),
+            _ => Err(format!(
+                "Unknown GPU adapter '{}'. Valid values: auto, hardware, software, index:N",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for GpuAdapterPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "index:{}", index),
+            _ => write!(f, "{}", self.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use GpuAdapterKind::*;
+
+    #[test]
+    fn test_parse_preference() {
+        assert_eq!(
+            "software".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Software
+        );
+        assert_eq!(
+            "Lavapipe".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Software
+        );
+        assert_eq!(
+            "hardware".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Hardware
+        );
+        assert_eq!(
+            "auto".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Auto
+        );
+        assert!("opengl".parse::<GpuAdapterPreference>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_explicit() {
+        assert_eq!(
+            GpuAdapterPreference::resolve(Some(GpuAdapterPreference::Software)),
+            GpuAdapterPreference::Software
+        );
+    }
+
+    #[test]
+    fn test_auto_prefers_discrete_then_first() {
+        let auto = GpuAdapterPreference::Auto;
+        assert_eq!(auto.pick(&[Cpu, Integrated, Discrete]), Some(2));
+        assert_eq!(auto.pick(&[Cpu, Integrated]), Some(0));
+        assert_eq!(auto.pick(&[]), None);
+    }
+
+    #[test]
+    fn test_hardware_never_picks_a_software_rasterizer() {
+        let hardware = GpuAdapterPreference::Hardware;
+        assert_eq!(hardware.pick(&[Cpu, Integrated]), Some(1));
+        assert_eq!(hardware.pick(&[Cpu]), None);
+    }
+
+    #[test]
+    fn test_index_picks_that_device_when_present() {
+        assert_eq!(
+            "index:1".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Index(1)
+        );
+        assert_eq!(
+            "2".parse::<GpuAdapterPreference>().unwrap(),
+            GpuAdapterPreference::Index(2)
+        );
+        assert_eq!(GpuAdapterPreference::Index(1).to_string(), "index:1");
+
+        let second = GpuAdapterPreference::Index(1);
+        assert_eq!(second.pick(&[Integrated, Discrete]), Some(1));
+        assert_eq!(second.pick(&[Integrated]), None);
+    }
+
+    #[test]
+    fn test_software_picks_only_a_cpu_device() {
+        let software = GpuAdapterPreference::Software;
+        assert_eq!(software.pick(&[Discrete, Cpu]), Some(1));
+        assert_eq!(software.pick(&[Discrete, Integrated]), None);
+    }
+}
@@ -10,6 +10,7 @@ use crate::core::Result;
 ))]
 use crate::host_rhi::HostTextureExt;
 
+use super::adapter::{GpuAdapterInfo, GpuAdapterPreference};
 use super::command_queue::RhiCommandQueue;
 use super::texture::{Texture, TextureDescriptor};
 
@@ -63,13 +64,78 @@ impl GpuDevice {
 
     /// Create a new GPU device using the system default.
     pub fn new() -> Result<Self> {
+        Self::open(None)
+    }
+
+    /// Open a secondary device on the adapter `preference` picks — the
+    /// runtime's path for processors pinned to another GPU. Unlike
+    /// [`Self::new`] it leaves the process-wide DMA-BUF import device
+    /// pointing at the primary device.
+    pub fn new_with_adapter(preference: GpuAdapterPreference) -> Result<Self> {
+        Self::open(Some(preference))
+    }
+
+    /// List the adapters [`Self::new_with_adapter`] can open, in
+    /// enumeration order.
+    pub fn enumerate_adapters() -> Result<Vec<GpuAdapterInfo>> {
+        #[cfg(all(
+            not(feature = "backend-vulkan"),
+            any(feature = "backend-metal", any(target_os = "macos", target_os = "ios"))
+        ))]
+        {
+            Ok(crate::metal::rhi::MetalDevice::enumerate_adapters())
+        }
+
+        #[cfg(any(
+            feature = "backend-vulkan",
+            all(target_os = "linux", not(feature = "backend-metal"))
+        ))]
+        {
+            crate::vulkan::rhi::HostVulkanDevice::enumerate_adapters()
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            Err(crate::core::Error::GpuError(
+                "GPU adapter enumeration is not supported on the DX12 backend".into(),
+            ))
+        }
+    }
+
+    /// Position of this device in [`Self::enumerate_adapters`] order.
+    pub fn adapter_index(&self) -> usize {
+        #[cfg(any(
+            all(
+                not(feature = "backend-vulkan"),
+                any(feature = "backend-metal", any(target_os = "macos", target_os = "ios"))
+            ),
+            any(
+                feature = "backend-vulkan",
+                all(target_os = "linux", not(feature = "backend-metal"))
+            )
+        ))]
+        {
+            self.inner.adapter_index()
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            0
+        }
+    }
+
+    /// `None` opens the default device as the process's primary device.
+    fn open(adapter: Option<GpuAdapterPreference>) -> Result<Self> {
         // Metal backend (default on macOS/iOS when Vulkan not requested)
         #[cfg(all(
             not(feature = "backend-vulkan"),
             any(feature = "backend-metal", any(target_os = "macos", target_os = "ios"))
         ))]
         {
-            let metal_device = crate::metal::rhi::MetalDevice::new()?;
+            let metal_device = match adapter {
+                Some(preference) => crate::metal::rhi::MetalDevice::new_with_adapter(preference)?,
+                None => crate::metal::rhi::MetalDevice::new()?,
+            };
             let metal_queue_wrapper = metal_device.create_command_queue_wrapper();
             let metal_queue_arc = std::sync::Arc::new(metal_queue_wrapper);
             let command_queue = {
@@ -93,7 +159,12 @@ impl GpuDevice {
             all(target_os = "linux", not(feature = "backend-metal"))
         ))]
         {
-            let device_arc = crate::vulkan::rhi::HostVulkanDevice::new()?;
+            let device_arc = match adapter {
+                Some(preference) => {
+                    crate::vulkan::rhi::HostVulkanDevice::new_with_adapter(preference)?
+                }
+                None => crate::vulkan::rhi::HostVulkanDevice::new()?,
+            };
             let vulkan_queue = device_arc.create_command_queue_wrapper();
 
             // On macOS/iOS with Vulkan backend, also create Metal device/queue for Apple services
@@ -117,7 +188,7 @@ impl GpuDevice {
             // The import trait (RhiPixelBufferImport::from_external_handle) is a
             // static method with no device parameter, so the global bridges that gap.
             #[cfg(target_os = "linux")]
-            {
+            if adapter.is_none() {
                 if crate::vulkan::rhi::vulkan_buffer::VULKAN_DEVICE_FOR_IMPORT
                     .set(std::sync::Arc::clone(&device_arc))
                     .is_err()
@@ -138,6 +209,11 @@ impl GpuDevice {
 
         #[cfg(target_os = "windows")]
         {
+            if let Some(preference) = adapter {
+                return Err(crate::core::Error::GpuError(format!(
+                    "GPU adapter selection ({preference}) is not supported on the DX12 backend"
+                )));
+            }
             let dx12_device = crate::windows::rhi::DX12Device::new()?;
             let dx12_queue = dx12_device.create_command_queue_wrapper();
             let command_queue = {
@@ -0,0 +1,125 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Producer-side GPU completion point attached to a frame's texture.
+//!
+//! A producer that finishes writing a texture on its own queue (async
+//! compute plugins, the camera's writeback) publishes the point its GPU
+//! work signals; consumers wait on it before sampling instead of relying
+//! on implicit driver ordering between queues. Vulkan carries the point
+//! as a timeline-semaphore value, Metal as an `MTLSharedEvent` value —
+//! both are monotonic 64-bit counters, so "reached" means
+//! `counter >= value` on either backend.
+
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+
+use crate::core::{Error, Result};
+
+#[cfg(target_os = "linux")]
+use crate::vulkan::rhi::HostVulkanTimelineSemaphore;
+
+/// Counter + target value a consumer waits on before touching a texture.
+#[derive(Clone)]
+pub enum GpuSyncPoint {
+    /// Vulkan timeline semaphore signalled to `value` by the producer's
+    /// submit.
+    #[cfg(target_os = "linux")]
+    Timeline {
+        semaphore: Arc<HostVulkanTimelineSemaphore>,
+        value: u64,
+    },
+    /// Metal shared event signalled to `value` by the producer's command
+    /// buffer (`encodeSignalEvent:value:`).
+    #[cfg(target_os = "macos")]
+    SharedEvent {
+        event: metal::SharedEvent,
+        value: u64,
+    },
+}
+
+impl GpuSyncPoint {
+    /// Counter value the producer signals on completion.
+    pub fn value(&self) -> u64 {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Timeline { value, .. } => *value,
+            #[cfg(target_os = "macos")]
+            Self::SharedEvent { value, .. } => *value,
+        }
+    }
+
+    /// `true` once the producer's GPU work has signalled [`Self::value`].
+    pub fn is_reached(&self) -> Result<bool> {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Timeline { semaphore, value } => Ok(semaphore.current_value()? >= *value),
+            #[cfg(target_os = "macos")]
+            Self::SharedEvent { event, value } => Ok(event.signaled_value() >= *value),
+        }
+    }
+
+    /// CPU-block until the producer's signal lands or `timeout` elapses.
+    ///
+    /// Consumers that submit their own GPU work should prefer a GPU-side
+    /// wait (timeline wait in the submit / `encodeWaitForEvent:value:`);
+    /// this is the fallback for CPU readers and for backends where the
+    /// consumer cannot reach the producer's queue.
+    pub fn wait(&self, timeout: Duration) -> Result<()> {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Timeline { semaphore, value } => {
+                let timeout_ns = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+                semaphore.wait(*value, timeout_ns)?;
+                // `vkWaitSemaphores` reports a timeout as a success code;
+                // re-read the counter so callers see it as an error.
+                let reached = semaphore.current_value()?;
+                if reached < *value {
+                    return Err(Error::GpuError(format!(
+                        "timeline wait timed out before value {} (at {})",
+                        value, reached
+                    )));
+                }
+                Ok(())
+            }
+            #[cfg(target_os = "macos")]
+            Self::SharedEvent { event, value } => {
+                // metal-rs exposes no blocking wait on MTLSharedEvent short
+                // of a listener block; poll the counter — producer signals
+                // land within a frame, so the loop is short.
+                // `None` = no deadline (`u64::MAX` timeouts overflow `Instant`).
+                let deadline = std::time::Instant::now().checked_add(timeout);
+                while event.signaled_value() < *value {
+                    if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                        return Err(Error::GpuError(format!(
+                            "MTLSharedEvent wait timed out before value {} (at {})",
+                            value,
+                            event.signaled_value()
+                        )));
+                    }
+                    std::thread::sleep(Duration::from_micros(100));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GpuSyncPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Timeline { value, .. } => f
+                .debug_struct("GpuSyncPoint::Timeline")
+                .field("value", value)
+                .finish(),
+            #[cfg(target_os = "macos")]
+            Self::SharedEvent { value, .. } => f
+                .debug_struct("GpuSyncPoint::SharedEvent")
+                .field("value", value)
+                .finish(),
+        }
+    }
+}
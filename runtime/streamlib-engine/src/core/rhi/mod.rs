@@ -3,6 +3,7 @@
 
 //! Render Hardware Interface (RHI) - Platform-agnostic GPU abstraction.
 
+mod adapter;
 mod backend;
 pub mod blitter;
 mod color_converter;
@@ -13,6 +14,8 @@ mod device;
 mod external_handle;
 mod gl_interop;
 mod graphics_kernel;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod gpu_sync_point;
 mod host_timeline_semaphore;
 mod index_buffer;
 mod pixel_buffer;
@@ -28,6 +31,7 @@ mod tone_mapper;
 mod uniform_buffer;
 mod vertex_buffer;
 
+pub use adapter::{GpuAdapterInfo, GpuAdapterKind, GpuAdapterPreference};
 pub use backend::RhiBackend;
 pub use blitter::RhiBlitter;
 pub use color_converter::{
@@ -51,6 +55,8 @@ pub use graphics_kernel::{
     ScissorRect, VertexAttributeFormat, VertexInputAttribute, VertexInputBinding, VertexInputRate,
     VertexInputState, Viewport, derive_bindings_from_spirv_multistage,
 };
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use gpu_sync_point::GpuSyncPoint;
 #[cfg(target_os = "linux")]
 pub use host_timeline_semaphore::HostTimelineSemaphore;
 #[cfg(target_os = "linux")]
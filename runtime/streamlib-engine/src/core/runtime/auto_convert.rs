@@ -0,0 +1,332 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Converter rules for automatic converter insertion at connect time.
+//!
+//! With [`Runner::set_auto_convert`] on, a `connect()` whose producer and
+//! consumer ports disagree looks for an [`AutoConverterRule`] covering the
+//! pair. On a hit the runtime adds the rule's converter processor and wires
+//! `producer -> converter -> consumer` instead of the direct link; the
+//! converter carries an
+//! [`AutoConverterComponent`](crate::core::graph::AutoConverterComponent).
+//!
+//! Ports disagree when they declare two different concrete schemas, or the
+//! same schema with two different declared frame formats (`bgra8` into
+//! `rgba8`, `24khz` into `48khz`). A port without a format takes whatever
+//! arrives and never triggers a conversion. [`builtin_auto_converters`]
+//! covers the format conversions the in-tree packages provide.
+
+use streamlib_processor_schema::PortSchemaSpec;
+
+use crate::core::PortDirection;
+use crate::core::descriptors::{Org, Package, SchemaIdent, SemVer, TypeName};
+use crate::core::graph::{Graph, ProcessorUniqueId};
+use crate::core::processors::{PROCESSOR_REGISTRY, ProcessorSpec};
+
+/// One producer → consumer conversion the runtime may insert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoConverterRule {
+    /// Schema on the producer's output port.
+    pub producer_schema: SchemaIdent,
+    /// Schema on the consumer's input port.
+    pub consumer_schema: SchemaIdent,
+    /// Frame format the producer's port must declare; `None` matches any.
+    pub producer_format: Option<String>,
+    /// Frame format the consumer's port must declare; `None` matches any.
+    pub consumer_format: Option<String>,
+    /// Processor type inserted between the two.
+    pub converter_type: SchemaIdent,
+    /// Config the converter is added with.
+    pub converter_config: serde_json::Value,
+    /// Converter input port fed by the producer.
+    pub converter_input: String,
+    /// Converter output port feeding the consumer.
+    pub converter_output: String,
+}
+
+impl AutoConverterRule {
+    /// Convert `producer_schema` into `consumer_schema` through
+    /// `converter_type`'s `converter_input` / `converter_output` ports, with
+    /// an empty config, whatever formats the ports declare.
+    pub fn new(
+        producer_schema: SchemaIdent,
+        consumer_schema: SchemaIdent,
+        converter_type: SchemaIdent,
+        converter_input: impl Into<String>,
+        converter_output: impl Into<String>,
+    ) -> Self {
+        Self {
+            producer_schema,
+            consumer_schema,
+            producer_format: None,
+            consumer_format: None,
+            converter_type,
+            converter_config: serde_json::Value::Object(Default::default()),
+            converter_input: converter_input.into(),
+            converter_output: converter_output.into(),
+        }
+    }
+
+    /// Add the converter with `config` instead of an empty object.
+    pub fn with_config(mut self, config: serde_json::Value) -> Self {
+        self.converter_config = config;
+        self
+    }
+
+    /// Only match producer ports declaring `format`.
+    pub fn with_producer_format(mut self, format: impl Into<String>) -> Self {
+        self.producer_format = Some(format.into());
+        self
+    }
+
+    /// Only match consumer ports declaring `format`.
+    pub fn with_consumer_format(mut self, format: impl Into<String>) -> Self {
+        self.consumer_format = Some(format.into());
+        self
+    }
+
+    /// Spec the converter processor is added from.
+    pub fn converter_spec(&self) -> ProcessorSpec {
+        ProcessorSpec::new(self.converter_type.clone(), self.converter_config.clone())
+    }
+}
+
+/// Converters for the format conversions in-tree packages provide, seeded
+/// into every [`Runner`](super::Runner). A rule whose converter is not
+/// registered — its package not loaded — is skipped at connect time; one
+/// that is runs whichever version of the converter is registered.
+///
+/// - `bgra8` → `rgba8` `VideoFrame`s through `@tatolab/scale/ColorspaceConvert`.
+/// - Any `AudioFrame` rate → `48khz` through `@tatolab/audio/AudioResampler`,
+///   which adapts its source rate to the frames it receives.
+///
+/// There is no `rgba8` → `nv12` rule: no in-tree port declares an `nv12`
+/// format to produce or consume, and NV12 is not a color attachment the
+/// plugin RHI can render a converter's output into. A package that ships
+/// such a converter registers its own rule with
+/// [`Runner::register_auto_converter`](super::Runner::register_auto_converter).
+pub fn builtin_auto_converters() -> Vec<AutoConverterRule> {
+    let ident = |package: &str, ty: &str| {
+        SchemaIdent::new(
+            Org::new("tatolab").expect("valid org"),
+            Package::new(package).expect("valid package"),
+            TypeName::new(ty).expect("valid type name"),
+            SemVer::new(1, 0, 0),
+        )
+    };
+    vec![
+        AutoConverterRule::new(
+            ident("core", "VideoFrame"),
+            ident("core", "VideoFrame"),
+            ident("scale", "ColorspaceConvert"),
+            "video_in",
+            "video_out",
+        )
+        .with_producer_format("bgra8")
+        .with_consumer_format("rgba8"),
+        AutoConverterRule::new(
+            ident("core", "AudioFrame"),
+            ident("core", "AudioFrame"),
+            ident("audio", "AudioResampler"),
+            "audio_in",
+            "audio_out",
+        )
+        .with_consumer_format("48khz")
+        .with_config(serde_json::json!({
+            "source_sample_rate": 48000,
+            "target_sample_rate": 48000,
+            "quality": "High",
+        })),
+    ]
+}
+
+/// `rules` whose converter is registered, each pointed at the highest
+/// registered version of its converter.
+pub(crate) fn registered_auto_converters(rules: &[AutoConverterRule]) -> Vec<AutoConverterRule> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let converter = &rule.converter_type;
+            let converter_type = PROCESSOR_REGISTRY
+                .resolve_any_version(&converter.org, &converter.package, &converter.r#type)
+                .ok()?;
+            Some(AutoConverterRule {
+                converter_type,
+                ..rule.clone()
+            })
+        })
+        .collect()
+}
+
+/// One end of a connect, as converter matching sees it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PortEnd<'a> {
+    pub schema: &'a PortSchemaSpec,
+    /// Declared frame format, `None` when the port takes whatever arrives.
+    pub format: Option<&'a str>,
+}
+
+/// The frame format declared on one port of a graph node, from its
+/// processor's registered descriptor. `None` for an unknown node or port,
+/// or a port that declares none.
+pub(crate) fn resolve_node_port_format(
+    graph: &Graph,
+    proc_id: &ProcessorUniqueId,
+    port_name: &str,
+    direction: PortDirection,
+) -> Option<String> {
+    let processor_type = graph
+        .traversal()
+        .v(proc_id)
+        .first()?
+        .processor_type()
+        .clone();
+    let descriptor = PROCESSOR_REGISTRY.descriptor(&processor_type)?;
+    let ports = match direction {
+        PortDirection::Input => descriptor.inputs,
+        PortDirection::Output => descriptor.outputs,
+    };
+    ports.into_iter().find(|p| p.name == port_name)?.format
+}
+
+/// The first rule converting `producer` into `consumer`. Only two concrete
+/// schemas can match — a wildcard end already agrees — and only when they
+/// differ or both ends declare differing formats.
+pub(crate) fn find_auto_converter<'a>(
+    rules: &'a [AutoConverterRule],
+    producer: PortEnd<'_>,
+    consumer: PortEnd<'_>,
+) -> Option<&'a AutoConverterRule> {
+    let (producer_schema, consumer_schema) =
+        (producer.schema.specific()?, consumer.schema.specific()?);
+    let formats_differ = matches!((producer.format, consumer.format), (Some(p), Some(c)) if p != c);
+    if producer_schema == consumer_schema && !formats_differ {
+        return None;
+    }
+    let format_matches = |wanted: &Option<String>, declared: Option<&str>| {
+        wanted
+            .as_deref()
+            .is_none_or(|wanted| declared == Some(wanted))
+    };
+    rules.iter().find(|rule| {
+        &rule.producer_schema == producer_schema
+            && &rule.consumer_schema == consumer_schema
+            && format_matches(&rule.producer_format, producer.format)
+            && format_matches(&rule.consumer_format, consumer.format)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use streamlib_idents::{Org, Package, SemVer, TypeName};
+
+    fn ident(ty: &str) -> SchemaIdent {
+        SchemaIdent::new(
+            Org::new("tatolab").unwrap(),
+            Package::new("core").unwrap(),
+            TypeName::new(ty).unwrap(),
+            SemVer::new(1, 0, 0),
+        )
+    }
+
+    fn decoder_rule() -> AutoConverterRule {
+        AutoConverterRule::new(
+            ident("EncodedVideoFrame"),
+            ident("VideoFrame"),
+            ident("H264Decoder"),
+            "encoded_video_in",
+            "video_out",
+        )
+    }
+
+    fn end(schema: &PortSchemaSpec, format: Option<&'static str>) -> PortEnd<'_> {
+        PortEnd { schema, format }
+    }
+
+    #[test]
+    fn matches_only_the_registered_schema_pair() {
+        let rules = vec![decoder_rule()];
+        let encoded = PortSchemaSpec::Specific(ident("EncodedVideoFrame"));
+        let video = PortSchemaSpec::Specific(ident("VideoFrame"));
+
+        assert_eq!(
+            find_auto_converter(&rules, end(&encoded, None), end(&video, None)),
+            Some(&rules[0])
+        );
+        assert_eq!(
+            find_auto_converter(&rules, end(&video, None), end(&encoded, None)),
+            None
+        );
+        assert_eq!(
+            find_auto_converter(&rules, end(&video, None), end(&video, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn wildcard_end_never_needs_a_converter() {
+        let rules = vec![decoder_rule()];
+        let encoded = PortSchemaSpec::Specific(ident("EncodedVideoFrame"));
+        assert_eq!(
+            find_auto_converter(&rules, end(&encoded, None), end(&PortSchemaSpec::Any, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn differing_declared_formats_match_a_format_rule() {
+        let rules = builtin_auto_converters();
+        let video = PortSchemaSpec::Specific(ident("VideoFrame"));
+        let audio = PortSchemaSpec::Specific(ident("AudioFrame"));
+
+        let swizzle = find_auto_converter(
+            &rules,
+            end(&video, Some("bgra8")),
+            end(&video, Some("rgba8")),
+        );
+        assert_eq!(
+            swizzle.map(|r| r.converter_type.r#type.as_str()),
+            Some("ColorspaceConvert")
+        );
+        let resample = find_auto_converter(
+            &rules,
+            end(&audio, Some("24khz")),
+            end(&audio, Some("48khz")),
+        );
+        assert_eq!(
+            resample.map(|r| r.converter_type.r#type.as_str()),
+            Some("AudioResampler")
+        );
+
+        // Matching or undeclared formats need no conversion.
+        assert_eq!(
+            find_auto_converter(
+                &rules,
+                end(&audio, Some("48khz")),
+                end(&audio, Some("48khz"))
+            ),
+            None
+        );
+        assert_eq!(
+            find_auto_converter(&rules, end(&video, None), end(&video, Some("rgba8"))),
+            None
+        );
+        // A mismatch no rule covers connects directly.
+        assert_eq!(
+            find_auto_converter(
+                &rules,
+                end(&video, Some("rgba8")),
+                end(&video, Some("bgra8"))
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn converter_spec_carries_config() {
+        let rule = decoder_rule().with_config(serde_json::json!({ "low_latency": true }));
+        let spec = rule.converter_spec();
+        assert_eq!(spec.config["low_latency"], true);
+    }
+}
@@ -0,0 +1,519 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Runner side of chaos mode: injecting [`ChaosFault`]s, lifting them when
+//! their duration runs out, and the scheduled draws.
+//!
+//! Enabling chaos mode spawns one task that ticks every
+//! [`CHAOS_TICK_INTERVAL`]: it lifts expired faults and, while the runtime
+//! is started, injects the next scheduled one. The task holds the runner
+//! weakly and exits once chaos mode is disabled or replaced. Faults are
+//! applied outside the graph edit history — a fault is not an edit to
+//! undo — but a sink brought back by
+//! [`ChaosFault::DisconnectNetworkSink`] has its new id rewritten into the
+//! history like an undo would.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tokio::time::MissedTickBehavior;
+
+use super::operations_runtime::remove_processor_impl;
+use super::{Runner, RuntimeStatus};
+use crate::core::chaos::{
+    ChaosFault, ChaosFaultRecord, ChaosFaultSource, ChaosMode, ChaosRng, LinkChaos,
+    bound_link_chaos,
+};
+use crate::core::graph::{
+    GraphEdgeWithComponents, LinkFrameDropComponent, LinkUniqueId, ProcessorUniqueId,
+};
+use crate::core::graph_edit_history::GraphEdit;
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+use crate::core::{Error, Result};
+
+/// How often the chaos task lifts expired faults and checks the schedule.
+const CHAOS_TICK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Faults kept in the log; the oldest are dropped past this.
+const CHAOS_FAULT_LOG_CAPACITY: usize = 512;
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// How to lift an active fault.
+enum ChaosLift {
+    Stall(Arc<LinkChaos>),
+    Drops(Arc<LinkChaos>),
+    Corruption(Arc<LinkChaos>),
+    /// Bring the removed sink back from its removal edit.
+    Restore(GraphEdit),
+}
+
+impl ChaosLift {
+    /// Whether both lift the same effect on the same link, so lifting the
+    /// older one would cut the newer one short.
+    fn same_effect(&self, other: &ChaosLift) -> bool {
+        match (self, other) {
+            (Self::Stall(a), Self::Stall(b))
+            | (Self::Drops(a), Self::Drops(b))
+            | (Self::Corruption(a), Self::Corruption(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+struct ActiveChaosFault {
+    fault_id: u64,
+    fault: ChaosFault,
+    until: Instant,
+    lift: ChaosLift,
+}
+
+/// Chaos mode and its fault log, shared by the runner and its chaos task.
+#[derive(Default)]
+pub(crate) struct ChaosState {
+    mode: Option<ChaosMode>,
+    /// Bumped whenever chaos mode is enabled or disabled, so a superseded
+    /// chaos task exits.
+    generation: u64,
+    next_fault_id: u64,
+    active: Vec<ActiveChaosFault>,
+    log: VecDeque<ChaosFaultRecord>,
+}
+
+impl ChaosState {
+    fn record(&mut self, record: ChaosFaultRecord) {
+        if self.log.len() == CHAOS_FAULT_LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back(record);
+    }
+
+    fn record_mut(&mut self, fault_id: u64) -> Option<&mut ChaosFaultRecord> {
+        self.log
+            .iter_mut()
+            .rev()
+            .find(|record| record.fault_id == fault_id)
+    }
+
+    /// Remove and return the active faults due at `now`, oldest first.
+    fn take_due(&mut self, now: Instant) -> Vec<ActiveChaosFault> {
+        let (due, active) = std::mem::take(&mut self.active)
+            .into_iter()
+            .partition(|fault| fault.until <= now);
+        self.active = active;
+        due
+    }
+}
+
+fn publish(event: RuntimeEvent) {
+    PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+}
+
+/// The task an enabled chaos mode runs on the runtime's tokio handle.
+struct ChaosTask {
+    runner: Weak<Runner>,
+    generation: u64,
+    rng: ChaosRng,
+    interval: Option<Duration>,
+}
+
+impl ChaosTask {
+    async fn run(self) {
+        let mut ticker = tokio::time::interval(CHAOS_TICK_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut next_injection = self.interval.map(|interval| Instant::now() + interval);
+        loop {
+            ticker.tick().await;
+            let Some(runner) = self.runner.upgrade() else {
+                break;
+            };
+            let faults = {
+                let chaos = runner.chaos.lock();
+                if chaos.generation != self.generation {
+                    break;
+                }
+                chaos
+                    .mode
+                    .as_ref()
+                    .map(|mode| mode.faults.clone())
+                    .unwrap_or_default()
+            };
+
+            let now = Instant::now();
+            let due = runner.chaos.lock().take_due(now);
+            for active in due {
+                runner.lift_active_chaos_fault(active).await;
+            }
+
+            if let (Some(interval), Some(at)) = (self.interval, next_injection)
+                && now >= at
+            {
+                next_injection = Some(now + interval);
+                let started = matches!(*runner.status.lock(), RuntimeStatus::Started);
+                if started && !faults.is_empty() {
+                    let fault = faults[self.rng.below(faults.len() as u64) as usize].clone();
+                    // A failed injection is logged and recorded where it fails.
+                    let _ = runner
+                        .inject_chaos_fault_from(fault, ChaosFaultSource::Scheduled)
+                        .await;
+                }
+            }
+        }
+        tracing::debug!("[chaos] Chaos task {} exiting", self.generation);
+    }
+}
+
+impl Runner {
+    /// Put the runner in chaos mode, replacing any chaos mode already on.
+    ///
+    /// Until [`Self::disable_chaos_mode`], faults can be injected with
+    /// [`Self::inject_chaos_fault`], and — when `interval_ms` is set — one
+    /// of `mode.faults` is drawn at random and injected every interval
+    /// while the runtime is started. Faults already active carry on.
+    pub fn enable_chaos_mode(self: &Arc<Self>, mode: ChaosMode) -> Result<()> {
+        mode.validate()?;
+        let seed = mode.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        let interval = mode.interval();
+        tracing::warn!(
+            "[chaos] Chaos mode on: {} scheduled fault(s), interval {:?}, seed {}, integrity check {}",
+            mode.faults.len(),
+            interval,
+            seed,
+            if mode.integrity_check { "on" } else { "off" }
+        );
+        let generation = {
+            let mut chaos = self.chaos.lock();
+            chaos.mode = Some(mode);
+            chaos.generation += 1;
+            chaos.generation
+        };
+
+        let task = ChaosTask {
+            runner: Arc::downgrade(self),
+            generation,
+            rng: ChaosRng::new(seed),
+            interval,
+        };
+        self.tokio_runtime_variant.handle().spawn(task.run());
+        Ok(())
+    }
+
+    /// Leave chaos mode, lifting every active fault now.
+    pub async fn disable_chaos_mode(&self) {
+        let active = {
+            let mut chaos = self.chaos.lock();
+            if chaos.mode.take().is_none() {
+                return;
+            }
+            chaos.generation += 1;
+            std::mem::take(&mut chaos.active)
+        };
+        for fault in active {
+            self.lift_active_chaos_fault(fault).await;
+        }
+        tracing::info!("[chaos] Chaos mode off");
+    }
+
+    /// The chaos mode in effect, if any.
+    pub fn chaos_mode(&self) -> Option<ChaosMode> {
+        self.chaos.lock().mode.clone()
+    }
+
+    /// Every fault injected so far (up to the newest 512), oldest first.
+    pub fn chaos_fault_log(&self) -> Vec<ChaosFaultRecord> {
+        self.chaos.lock().log.iter().cloned().collect()
+    }
+
+    /// Inject `fault` now. Fails unless chaos mode is on, and when the
+    /// fault's target is missing; failures are logged like injections.
+    pub async fn inject_chaos_fault(&self, fault: ChaosFault) -> Result<ChaosFaultRecord> {
+        self.inject_chaos_fault_from(fault, ChaosFaultSource::Requested)
+            .await
+    }
+
+    async fn inject_chaos_fault_from(
+        &self,
+        fault: ChaosFault,
+        source: ChaosFaultSource,
+    ) -> Result<ChaosFaultRecord> {
+        let integrity_check = match &self.chaos.lock().mode {
+            Some(mode) => mode.integrity_check,
+            None => {
+                return Err(Error::NotSupported(
+                    "chaos mode is not enabled on this runtime".into(),
+                ));
+            }
+        };
+        fault.validate(integrity_check)?;
+
+        let fault_id = {
+            let mut chaos = self.chaos.lock();
+            chaos.next_fault_id += 1;
+            chaos.next_fault_id
+        };
+        let mut record = ChaosFaultRecord {
+            fault_id,
+            fault: fault.clone(),
+            source,
+            injected_at_ms: unix_millis(),
+            cleared_at_ms: None,
+            error: None,
+            restored_processor_id: None,
+        };
+        let (kind, target) = (fault.kind().to_string(), fault.target());
+
+        let applied = self.apply_chaos_fault(&fault).await;
+        let mut chaos = self.chaos.lock();
+        match applied {
+            Ok(lift) => {
+                tracing::warn!(
+                    "[chaos] Fault {} injected ({:?}): {} on {}",
+                    fault_id,
+                    source,
+                    kind,
+                    target
+                );
+                if let (Some(lift), Some(duration)) = (lift, fault.duration()) {
+                    chaos.active.push(ActiveChaosFault {
+                        fault_id,
+                        fault,
+                        until: Instant::now() + duration,
+                        lift,
+                    });
+                }
+                chaos.record(record.clone());
+                drop(chaos);
+                publish(RuntimeEvent::ChaosFaultInjected {
+                    fault_id,
+                    kind,
+                    target,
+                    source,
+                });
+                Ok(record)
+            }
+            Err(error) => {
+                tracing::error!(
+                    "[chaos] Fault {} ({} on {}) failed: {}",
+                    fault_id,
+                    kind,
+                    target,
+                    error
+                );
+                record.error = Some(error.to_string());
+                chaos.record(record);
+                drop(chaos);
+                publish(RuntimeEvent::ChaosFaultFailed {
+                    fault_id,
+                    kind,
+                    target,
+                    error: error.to_string(),
+                });
+                Err(error)
+            }
+        }
+    }
+
+    /// Apply `fault`, returning how to lift it.
+    async fn apply_chaos_fault(&self, fault: &ChaosFault) -> Result<Option<ChaosLift>> {
+        match fault {
+            ChaosFault::KillProcessor { processor_id } => {
+                remove_processor_impl(Arc::clone(&self.compiler), processor_id.clone()).await?;
+                Ok(None)
+            }
+            ChaosFault::StallLink { link_id, .. } => {
+                let chaos = self.in_process_link_chaos(link_id)?;
+                chaos.set_stalled(true);
+                Ok(Some(ChaosLift::Stall(chaos)))
+            }
+            ChaosFault::DropFrames {
+                link_id, percent, ..
+            } => {
+                let chaos = self.in_process_link_chaos(link_id)?;
+                chaos.set_drop_percent(*percent);
+                Ok(Some(ChaosLift::Drops(chaos)))
+            }
+            ChaosFault::CorruptPayload {
+                link_id, percent, ..
+            } => {
+                let chaos = self.in_process_link_chaos(link_id)?;
+                chaos.set_corrupt_percent(*percent);
+                Ok(Some(ChaosLift::Corruption(chaos)))
+            }
+            ChaosFault::DisconnectNetworkSink { processor_id, .. } => {
+                let removal = self
+                    .processor_removal_edit(processor_id)
+                    .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+                remove_processor_impl(Arc::clone(&self.compiler), processor_id.clone()).await?;
+                Ok(Some(ChaosLift::Restore(removal)))
+            }
+        }
+    }
+
+    /// The fault state of a wired link whose destination receives in
+    /// process — the only place link faults can act.
+    fn in_process_link_chaos(&self, link_id: &LinkUniqueId) -> Result<Arc<LinkChaos>> {
+        let in_process = self
+            .compiler
+            .scope(|graph, _tx| {
+                graph
+                    .traversal()
+                    .e(link_id)
+                    .first()
+                    .map(|link| link.has::<LinkFrameDropComponent>())
+            })
+            .ok_or_else(|| Error::LinkNotFound(link_id.to_string()))?;
+        if !in_process {
+            return Err(Error::NotSupported(format!(
+                "link {link_id} has no in-process destination to inject faults at"
+            )));
+        }
+        bound_link_chaos(link_id.as_str()).ok_or_else(|| Error::LinkNotWired(link_id.to_string()))
+    }
+
+    /// Lift `active` unless a newer fault holds the same effect, and log it.
+    async fn lift_active_chaos_fault(&self, active: ActiveChaosFault) {
+        let superseded = self
+            .chaos
+            .lock()
+            .active
+            .iter()
+            .any(|other| other.lift.same_effect(&active.lift));
+        let lifted: Result<Option<ProcessorUniqueId>> = if superseded {
+            Ok(None)
+        } else {
+            match active.lift {
+                ChaosLift::Stall(chaos) => {
+                    chaos.set_stalled(false);
+                    Ok(None)
+                }
+                ChaosLift::Drops(chaos) => {
+                    chaos.set_drop_percent(0.0);
+                    Ok(None)
+                }
+                ChaosLift::Corruption(chaos) => {
+                    chaos.set_corrupt_percent(0.0);
+                    Ok(None)
+                }
+                ChaosLift::Restore(removal) => {
+                    self.restore_removed_processor(removal).await.map(Some)
+                }
+            }
+        };
+
+        let (kind, target) = (active.fault.kind().to_string(), active.fault.target());
+        let event = {
+            let mut chaos = self.chaos.lock();
+            let record = chaos.record_mut(active.fault_id);
+            match lifted {
+                Ok(restored_processor_id) => {
+                    tracing::info!(
+                        "[chaos] Fault {} lifted: {} on {}",
+                        active.fault_id,
+                        kind,
+                        target
+                    );
+                    if let Some(record) = record {
+                        record.cleared_at_ms = Some(unix_millis());
+                        record.restored_processor_id = restored_processor_id;
+                    }
+                    RuntimeEvent::ChaosFaultCleared {
+                        fault_id: active.fault_id,
+                        kind,
+                        target,
+                    }
+                }
+                Err(error) => {
+                    tracing::error!(
+                        "[chaos] Lifting fault {} ({} on {}) failed: {}",
+                        active.fault_id,
+                        kind,
+                        target,
+                        error
+                    );
+                    if let Some(record) = record {
+                        record.error = Some(error.to_string());
+                    }
+                    RuntimeEvent::ChaosFaultFailed {
+                        fault_id: active.fault_id,
+                        kind,
+                        target,
+                        error: error.to_string(),
+                    }
+                }
+            }
+        };
+        publish(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stall(fault_id: u64, chaos: &Arc<LinkChaos>, until: Instant) -> ActiveChaosFault {
+        ActiveChaosFault {
+            fault_id,
+            fault: ChaosFault::StallLink {
+                link_id: "L1".into(),
+                duration_ms: 1000,
+            },
+            until,
+            lift: ChaosLift::Stall(Arc::clone(chaos)),
+        }
+    }
+
+    #[test]
+    fn take_due_returns_expired_faults_and_keeps_the_rest() {
+        let chaos = crate::core::chaos::link_chaos("chaos-injector-test-due");
+        let now = Instant::now();
+        let mut state = ChaosState::default();
+        state.active.push(stall(1, &chaos, now));
+        state
+            .active
+            .push(stall(2, &chaos, now + Duration::from_secs(5)));
+
+        let due = state.take_due(now);
+        assert_eq!(due.iter().map(|f| f.fault_id).collect::<Vec<_>>(), [1]);
+        assert_eq!(state.active.len(), 1);
+        // Fault 2 still stalls the same link, so lifting 1 must leave it be.
+        assert!(state.active[0].lift.same_effect(&due[0].lift));
+        crate::core::chaos::release_link_chaos("chaos-injector-test-due");
+    }
+
+    #[test]
+    fn the_fault_log_keeps_the_newest_entries() {
+        let mut state = ChaosState::default();
+        for fault_id in 1..=(CHAOS_FAULT_LOG_CAPACITY as u64 + 3) {
+            state.record(ChaosFaultRecord {
+                fault_id,
+                fault: ChaosFault::KillProcessor {
+                    processor_id: "cam".into(),
+                },
+                source: ChaosFaultSource::Requested,
+                injected_at_ms: 0,
+                cleared_at_ms: None,
+                error: None,
+                restored_processor_id: None,
+            });
+        }
+        assert_eq!(state.log.len(), CHAOS_FAULT_LOG_CAPACITY);
+        assert_eq!(state.log.front().map(|r| r.fault_id), Some(4));
+        assert!(state.record_mut(3).is_none());
+        assert!(
+            state
+                .record_mut(CHAOS_FAULT_LOG_CAPACITY as u64 + 3)
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Periodic device scan that publishes [`RuntimeEvent::DeviceConnected`] /
+//! [`RuntimeEvent::DeviceDisconnected`] as cameras and audio interfaces come
+//! and go.
+//!
+//! Scans read sysfs / procfs (`/sys/class/video4linux`, `/proc/asound/cards`)
+//! rather than opening devices, so they never contend with a capture
+//! processor holding the node. Linux-only for now.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::core::pubsub::{DeviceInfo, DeviceKind, Event, PUBSUB, RuntimeEvent, topics};
+
+use super::RuntimeStatus;
+
+/// How often the monitor rescans.
+const DEVICE_SCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Devices keyed by `(kind, id)`.
+type DeviceSet = BTreeMap<(DeviceKind, String), DeviceInfo>;
+
+/// Scan `sys_root` / `proc_root` (normally `/sys` and `/proc`) for capture
+/// devices.
+pub(crate) fn scan_linux_devices(sys_root: &Path, proc_root: &Path) -> DeviceSet {
+    let mut devices = DeviceSet::new();
+    for device in scan_video4linux(&sys_root.join("class/video4linux"))
+        .into_iter()
+        .chain(scan_asound_cards(&proc_root.join("asound/cards")))
+    {
+        devices.insert((device.kind, device.id.clone()), device);
+    }
+    devices
+}
+
+/// One camera per `videoN` node whose `index` is 0 — UVC exposes a second
+/// metadata node (index 1) for the same camera.
+fn scan_video4linux(class_dir: &Path) -> Vec<DeviceInfo> {
+    let Ok(entries) = std::fs::read_dir(class_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let node = entry.file_name().into_string().ok()?;
+            if !node.starts_with("video") {
+                return None;
+            }
+            let dir = entry.path();
+            let index = std::fs::read_to_string(dir.join("index")).unwrap_or_default();
+            if index.trim().parse::<u32>().is_ok_and(|index| index != 0) {
+                return None;
+            }
+            let name = std::fs::read_to_string(dir.join("name")).ok()?;
+            Some(DeviceInfo {
+                kind: DeviceKind::Camera,
+                id: format!("/dev/{}", node),
+                name: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `/proc/asound/cards`, whose entries look like
+/// ` 1 [Device         ]: USB-Audio - USB Audio Device` followed by an
+/// indented description line.
+fn scan_asound_cards(cards_path: &Path) -> Vec<DeviceInfo> {
+    let Ok(cards) = std::fs::read_to_string(cards_path) else {
+        return Vec::new();
+    };
+    cards
+        .lines()
+        .filter_map(|line| {
+            let (number, rest) = line.trim_start().split_once(' ')?;
+            let number: u32 = number.parse().ok()?;
+            let (_, description) = rest.split_once("]: ")?;
+            let name = description
+                .split_once(" - ")
+                .map_or(description, |(_, name)| name);
+            Some(DeviceInfo {
+                kind: DeviceKind::AudioInterface,
+                id: format!("hw:{}", number),
+                name: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Events that turn `previous` into `current`. A device whose id stays but
+/// whose name changes was swapped, so it reports a disconnect and a connect.
+pub(crate) fn diff_devices(previous: &DeviceSet, current: &DeviceSet) -> Vec<RuntimeEvent> {
+    let disconnected = previous
+        .iter()
+        .filter(|(key, device)| current.get(*key) != Some(*device))
+        .map(|(_, device)| RuntimeEvent::DeviceDisconnected {
+            device: device.clone(),
+        });
+    let connected = current
+        .iter()
+        .filter(|(key, device)| previous.get(*key) != Some(*device))
+        .map(|(_, device)| RuntimeEvent::DeviceConnected {
+            device: device.clone(),
+        });
+    disconnected.chain(connected).collect()
+}
+
+/// Spawn the monitor on the runtime's tokio handle. Devices present at
+/// start are the baseline and produce no events. Like the link-drop sampler,
+/// the task exits once the runtime leaves the started / paused states and
+/// `stop()` aborts it through the returned handle, so a quick restart never
+/// publishes each device change twice.
+pub(crate) fn spawn_device_monitor(
+    handle: &tokio::runtime::Handle,
+    status: Arc<Mutex<RuntimeStatus>>,
+) -> tokio::task::JoinHandle<()> {
+    let scan = || scan_linux_devices(Path::new("/sys"), Path::new("/proc"));
+    handle.spawn(async move {
+        let mut known = scan();
+        tracing::debug!("[device_monitor] {} device(s) at start", known.len());
+        let mut interval = tokio::time::interval(DEVICE_SCAN_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match *status.lock() {
+                RuntimeStatus::Started | RuntimeStatus::Pausing | RuntimeStatus::Paused => {}
+                _ => break,
+            }
+            let current = scan();
+            for event in diff_devices(&known, &current) {
+                tracing::info!(event = ?event, "device change");
+                PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+            }
+            known = current;
+        }
+        tracing::debug!("[device_monitor] runtime no longer running, monitor exiting");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn camera(id: &str, name: &str) -> DeviceInfo {
+        DeviceInfo {
+            kind: DeviceKind::Camera,
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn set(devices: &[DeviceInfo]) -> DeviceSet {
+        devices
+            .iter()
+            .map(|device| ((device.kind, device.id.clone()), device.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn scans_capture_nodes_and_sound_cards() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sys = tmp.path().join("sys");
+        let proc = tmp.path().join("proc");
+        let v4l = sys.join("class/video4linux");
+        write(&v4l.join("video0/name"), "HD Pro Webcam C920\n");
+        write(&v4l.join("video0/index"), "0\n");
+        write(&v4l.join("video1/name"), "HD Pro Webcam C920\n");
+        write(&v4l.join("video1/index"), "1\n");
+        write(
+            &proc.join("asound/cards"),
+            concat!(
+                " 0 [PCH            ]: HDA-Intel - HDA Intel PCH\n",
+                "                      HDA Intel PCH at 0xf7f10000 irq 32\n",
+                " 1 [Device         ]: USB-Audio - USB Audio Device\n",
+                "                      Generic USB Audio Device at usb-0000:00:14.0-1\n",
+            ),
+        );
+
+        let devices: Vec<DeviceInfo> = scan_linux_devices(&sys, &proc).into_values().collect();
+
+        assert_eq!(
+            devices,
+            [
+                camera("/dev/video0", "HD Pro Webcam C920"),
+                DeviceInfo {
+                    kind: DeviceKind::AudioInterface,
+                    id: "hw:0".into(),
+                    name: "HDA Intel PCH".into(),
+                },
+                DeviceInfo {
+                    kind: DeviceKind::AudioInterface,
+                    id: "hw:1".into(),
+                    name: "USB Audio Device".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_sysfs_and_procfs_scan_as_no_devices() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        assert!(scan_linux_devices(tmp.path(), tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_unplugs_then_plugs() {
+        let webcam = camera("/dev/video0", "Webcam");
+        let capture_card = camera("/dev/video2", "Capture Card");
+        let previous = set(&[webcam.clone()]);
+        let current = set(&[capture_card.clone()]);
+
+        assert_eq!(
+            diff_devices(&previous, &current),
+            [
+                RuntimeEvent::DeviceDisconnected { device: webcam },
+                RuntimeEvent::DeviceConnected {
+                    device: capture_card
+                },
+            ]
+        );
+        assert!(diff_devices(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn a_different_device_on_the_same_node_is_a_swap() {
+        let previous = set(&[camera("/dev/video0", "Webcam")]);
+        let current = set(&[camera("/dev/video0", "Other Webcam")]);
+
+        let events = diff_devices(&previous, &current);
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                RuntimeEvent::DeviceDisconnected { .. },
+                RuntimeEvent::DeviceConnected { .. }
+            ]
+        ));
+    }
+}
@@ -0,0 +1,420 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Runner side of the graph edit history: recording edits as the
+//! [`RuntimeOperations`](super::RuntimeOperations) paths make them, and
+//! applying them back for [`Runner::undo_graph_edit`] /
+//! [`Runner::redo_graph_edit`].
+//!
+//! Applying an edit goes through the same `_impl` functions as the public
+//! paths, minus the recording, so undo and redo publish the usual graph
+//! events. Re-adding a processor or re-wiring a link mints a fresh id; the
+//! old id is rewritten across the whole history so later entries still
+//! resolve. A [`GraphEdit::Batch`] applies its edits in order and, if one
+//! fails, backs out the ones already applied.
+
+use std::sync::Arc;
+
+use super::Runner;
+use super::operations::{BoxFuture, ConnectOptions};
+use super::operations_runtime::{
+    add_processor_impl, connect_through_converter_impl, disconnect_impl, remove_processor_impl,
+};
+use crate::core::graph::{
+    AutoConverterComponent, Graph, GraphEdgeWithComponents, GraphNodeWithComponents, LinkUniqueId,
+    PendingDeletionComponent, ProcessorUniqueId,
+};
+use crate::core::graph_edit_history::{GraphEdit, GraphEditHistory, GraphEditLink};
+use crate::core::processors::ProcessorSpec;
+use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, Result};
+
+/// Ids minted while applying an edit, old → new.
+#[derive(Default)]
+struct MintedIds {
+    processors: Vec<(ProcessorUniqueId, ProcessorUniqueId)>,
+    links: Vec<(LinkUniqueId, LinkUniqueId)>,
+}
+
+impl MintedIds {
+    fn processor(&self, id: &ProcessorUniqueId) -> Option<ProcessorUniqueId> {
+        self.processors
+            .iter()
+            .find(|(old, _)| old == id)
+            .map(|(_, new)| new.clone())
+    }
+
+    fn extend(&mut self, other: MintedIds) {
+        self.processors.extend(other.processors);
+        self.links.extend(other.links);
+    }
+
+    fn rewrite_edit(&self, edit: &mut GraphEdit) {
+        edit.map_processor_ids(&mut |id| self.processor(id));
+        for (old, new) in &self.links {
+            edit.rename_link(old, new);
+        }
+    }
+
+    fn rewrite_history(&self, history: &mut GraphEditHistory) {
+        history.map_processor_ids(|id| self.processor(id));
+        for (old, new) in &self.links {
+            history.rename_link(old, new);
+        }
+    }
+}
+
+impl Runner {
+    /// A copy of the graph edit history.
+    pub fn graph_edit_history(&self) -> GraphEditHistory {
+        self.graph_edits.lock().clone()
+    }
+
+    /// Bound how many undoable edits are kept, dropping the oldest if
+    /// the bound shrank.
+    pub fn set_graph_edit_history_capacity(&self, capacity: usize) {
+        self.graph_edits.lock().set_capacity(capacity);
+    }
+
+    /// Undo the newest graph edit by applying its inverse. Returns the
+    /// undone edit, or `None` when there is nothing to undo.
+    ///
+    /// An edit whose inverse fails to apply stays on the undo stack.
+    pub async fn undo_graph_edit(&self) -> Result<Option<GraphEdit>> {
+        let Some(mut edit) = self.graph_edits.lock().take_undo() else {
+            return Ok(None);
+        };
+        let mut minted = MintedIds::default();
+        let applied = self.apply_graph_edit(edit.inverse(), &mut minted).await;
+        minted.rewrite_edit(&mut edit);
+        let mut graph_edits = self.graph_edits.lock();
+        minted.rewrite_history(&mut graph_edits);
+        match applied {
+            Ok(()) => {
+                graph_edits.push_redo(edit.clone());
+                Ok(Some(edit))
+            }
+            Err(error) => {
+                graph_edits.push_undo(edit);
+                Err(error)
+            }
+        }
+    }
+
+    /// Re-apply the most recently undone graph edit. Returns the redone
+    /// edit, or `None` when there is nothing to redo.
+    ///
+    /// An edit that fails to apply stays on the redo stack.
+    pub async fn redo_graph_edit(&self) -> Result<Option<GraphEdit>> {
+        let Some(mut edit) = self.graph_edits.lock().take_redo() else {
+            return Ok(None);
+        };
+        let mut minted = MintedIds::default();
+        let applied = self.apply_graph_edit(edit.clone(), &mut minted).await;
+        minted.rewrite_edit(&mut edit);
+        let mut graph_edits = self.graph_edits.lock();
+        minted.rewrite_history(&mut graph_edits);
+        match applied {
+            Ok(()) => {
+                graph_edits.push_undo(edit.clone());
+                Ok(Some(edit))
+            }
+            Err(error) => {
+                graph_edits.push_redo(edit);
+                Err(error)
+            }
+        }
+    }
+
+    pub(crate) fn record_graph_edit(&self, edit: GraphEdit) {
+        self.graph_edits.lock().record(edit);
+    }
+
+    /// The edit recording the removal of `processor_id` as it stands now —
+    /// call before removing. `None` for an unknown processor.
+    pub(crate) fn processor_removal_edit(
+        &self,
+        processor_id: &ProcessorUniqueId,
+    ) -> Option<GraphEdit> {
+        self.compiler
+            .scope(|graph, _tx| processor_removal_edit(graph, processor_id))
+    }
+
+    /// The edit recording the removal of `link_id` — call before
+    /// disconnecting. `None` for an unknown link.
+    pub(crate) fn disconnect_edit(&self, link_id: &LinkUniqueId) -> Option<GraphEdit> {
+        self.compiler
+            .scope(|graph, _tx| disconnect_edit(graph, link_id))
+    }
+
+    /// Bring back a processor removed outside the history — a chaos fault —
+    /// from its [`Self::processor_removal_edit`], links included, and point
+    /// the history at the id it comes back under.
+    pub(crate) async fn restore_removed_processor(
+        &self,
+        removal: GraphEdit,
+    ) -> Result<ProcessorUniqueId> {
+        let GraphEdit::RemoveProcessor { processor_id, .. } = &removal else {
+            return Err(Error::Configuration(
+                "only a processor removal can be restored".into(),
+            ));
+        };
+        let processor_id = processor_id.clone();
+        let mut minted = MintedIds::default();
+        let applied = self.apply_graph_edit(removal.inverse(), &mut minted).await;
+        minted.rewrite_history(&mut self.graph_edits.lock());
+        applied?;
+        minted
+            .processor(&processor_id)
+            .ok_or(Error::ProcessorNotFound(processor_id.to_string()))
+    }
+
+    /// Apply `edit` without recording it, noting the ids it mints in
+    /// `minted` — on error too, so the history can follow whatever did
+    /// change.
+    async fn apply_graph_edit(&self, edit: GraphEdit, minted: &mut MintedIds) -> Result<()> {
+        match edit {
+            GraphEdit::AddProcessor {
+                processor_id,
+                spec,
+                links,
+            } => {
+                let new_id = add_processor_impl(Arc::clone(&self.compiler), spec, None).await?;
+                minted.processors.push((processor_id, new_id));
+                // The processor is back; a link that no longer fits (its peer
+                // is gone, the port changed) is dropped rather than failing
+                // the whole edit.
+                for mut link in links {
+                    for id in [&mut link.from.processor_id, &mut link.to.processor_id] {
+                        if let Some(new_id) = minted.processor(id) {
+                            *id = new_id;
+                        }
+                    }
+                    match self
+                        .connect_unrecorded(link.from.clone(), link.to.clone())
+                        .await
+                    {
+                        Ok(new_link_id) => minted.links.push((link.link_id, new_link_id)),
+                        Err(error) => tracing::warn!(
+                            "[edit_history] Could not restore link {} -> {}: {}",
+                            link.from,
+                            link.to,
+                            error
+                        ),
+                    }
+                }
+            }
+            GraphEdit::RemoveProcessor { processor_id, .. } => {
+                remove_processor_impl(Arc::clone(&self.compiler), processor_id.clone()).await?;
+                self.clear_processor_schedules(&processor_id);
+            }
+            GraphEdit::Connect { link } => {
+                let new_link_id = self.connect_unrecorded(link.from, link.to).await?;
+                minted.links.push((link.link_id, new_link_id));
+            }
+            GraphEdit::Disconnect { link } => {
+                // A link made through an auto-inserted converter goes away
+                // with its converter.
+                let (link_id, converter) = self
+                    .compiler
+                    .scope(|graph, _tx| resolve_link(graph, &link))
+                    .ok_or_else(|| Error::LinkNotFound(link.link_id.to_string()))?;
+                match converter {
+                    Some(converter_id) => {
+                        remove_processor_impl(Arc::clone(&self.compiler), converter_id).await?
+                    }
+                    None => disconnect_impl(Arc::clone(&self.compiler), link_id).await?,
+                }
+            }
+            GraphEdit::UpdateConfig {
+                processor_id,
+                config,
+                ..
+            } => {
+                self.replace_processor_config(&processor_id, config)
+                    .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+            }
+            GraphEdit::Batch { edits } => self.apply_graph_edit_batch(edits, minted).await?,
+        }
+        Ok(())
+    }
+
+    /// Apply `edits` in order, each rewritten to the ids the ones before
+    /// it minted. If one fails, the applied ones are backed out newest
+    /// first, and `minted` holds only what backing out minted.
+    ///
+    /// Boxed, since it and [`Self::apply_graph_edit`] call each other.
+    fn apply_graph_edit_batch<'a>(
+        &'a self,
+        edits: Vec<GraphEdit>,
+        minted: &'a mut MintedIds,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut forward = MintedIds::default();
+            let mut applied = Vec::with_capacity(edits.len());
+            let mut failure = None;
+            for mut edit in edits {
+                forward.rewrite_edit(&mut edit);
+                let mut step = MintedIds::default();
+                if let Err(error) = self.apply_graph_edit(edit.clone(), &mut step).await {
+                    failure = Some((error, step));
+                    break;
+                }
+                step.rewrite_edit(&mut edit);
+                forward.extend(step);
+                applied.push(edit);
+            }
+            let Some((error, mut backed_out)) = failure else {
+                minted.extend(forward);
+                return Ok(());
+            };
+
+            for edit in applied.into_iter().rev() {
+                let mut inverse = edit.inverse();
+                backed_out.rewrite_edit(&mut inverse);
+                let mut step = MintedIds::default();
+                if let Err(undo_error) = self.apply_graph_edit(inverse, &mut step).await {
+                    tracing::warn!(
+                        "[edit_history] Could not back out part of a failed batch edit: {}",
+                        undo_error
+                    );
+                }
+                backed_out.extend(step);
+            }
+            minted.extend(backed_out);
+            Err(error)
+        })
+    }
+
+    async fn connect_unrecorded(
+        &self,
+        from: OutputLinkPortRef,
+        to: InputLinkPortRef,
+    ) -> Result<LinkUniqueId> {
+        let converter = self.auto_converter_for(&from, &to);
+        connect_through_converter_impl(
+            Arc::clone(&self.compiler),
+            converter,
+            from,
+            to,
+            ConnectOptions::loose(),
+        )
+        .await
+    }
+}
+
+/// [`Runner::processor_removal_edit`] for callers already holding the graph.
+pub(super) fn processor_removal_edit(
+    graph: &Graph,
+    processor_id: &ProcessorUniqueId,
+) -> Option<GraphEdit> {
+    let node = graph.traversal().v(processor_id).first()?;
+    let mut spec = ProcessorSpec::new(
+        node.processor_type.clone(),
+        node.config.clone().unwrap_or(serde_json::Value::Null),
+    );
+    if node.display_name.as_str() != node.processor_type.r#type.as_str() {
+        spec = spec.with_display_name(node.display_name.clone());
+    }
+    let links = live_links(graph)
+        .filter(|link| {
+            &link.from.processor_id == processor_id || &link.to.processor_id == processor_id
+        })
+        .collect();
+    Some(GraphEdit::RemoveProcessor {
+        processor_id: processor_id.clone(),
+        spec,
+        links,
+    })
+}
+
+/// [`Runner::disconnect_edit`] for callers already holding the graph.
+pub(super) fn disconnect_edit(graph: &Graph, link_id: &LinkUniqueId) -> Option<GraphEdit> {
+    let link = graph.traversal().e(link_id).first()?;
+    Some(GraphEdit::Disconnect {
+        link: GraphEditLink {
+            link_id: link_id.clone(),
+            from: link.source.clone(),
+            to: link.target.clone(),
+        },
+    })
+}
+
+/// Links not already queued for removal, as [`GraphEditLink`]s.
+fn live_links(graph: &Graph) -> impl Iterator<Item = GraphEditLink> + '_ {
+    graph
+        .traversal()
+        .e(())
+        .iter()
+        .filter(|link| !link.has::<PendingDeletionComponent>())
+        .map(|link| GraphEditLink {
+            link_id: link.id.clone(),
+            from: link.source.clone(),
+            to: link.target.clone(),
+        })
+}
+
+/// Find the live link `link` recorded, by id and failing that by its
+/// endpoints — ids don't survive a snapshot reload. The second element is
+/// the auto-inserted converter the link runs through, if any.
+fn resolve_link(
+    graph: &Graph,
+    link: &GraphEditLink,
+) -> Option<(LinkUniqueId, Option<ProcessorUniqueId>)> {
+    let converter_upstream = |processor_id: &ProcessorUniqueId| {
+        graph
+            .traversal()
+            .v(processor_id)
+            .first()
+            .and_then(|node| node.get::<AutoConverterComponent>())
+            .map(|converter| converter.upstream.clone())
+    };
+    let mut candidates = live_links(graph).filter(|live| live.to == link.to);
+    candidates.find_map(|live| {
+        if live.link_id == link.link_id || live.from == link.from {
+            return Some((live.link_id, None));
+        }
+        (converter_upstream(&live.from.processor_id).as_ref() == Some(&link.from))
+            .then(|| (live.link_id, Some(live.from.processor_id)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minted_ids_rewrite_the_edit_and_the_history() {
+        let minted = MintedIds {
+            processors: vec![("old".into(), "new".into())],
+            links: vec![("l_old".into(), "l_new".into())],
+        };
+        let link = |id: &str, from: &str| GraphEditLink {
+            link_id: id.into(),
+            from: OutputLinkPortRef::new(from, "out"),
+            to: InputLinkPortRef::new("sink", "in"),
+        };
+
+        let mut edit = GraphEdit::Disconnect {
+            link: link("l_old", "old"),
+        };
+        minted.rewrite_edit(&mut edit);
+        assert_eq!(
+            edit,
+            GraphEdit::Disconnect {
+                link: link("l_new", "new")
+            }
+        );
+
+        let mut history = GraphEditHistory::default();
+        history.record(GraphEdit::Connect {
+            link: link("l_old", "old"),
+        });
+        minted.rewrite_history(&mut history);
+        assert_eq!(
+            history.take_undo(),
+            Some(GraphEdit::Connect {
+                link: link("l_new", "new")
+            })
+        );
+    }
+}
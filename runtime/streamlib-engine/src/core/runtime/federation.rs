@@ -0,0 +1,720 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Graph federation — links between processors in different runtime processes
+//! on the same host.
+//!
+//! [`Runner::connect_remote`] links a local output port to an input port of a
+//! processor in another runtime. The data path is the ordinary iceoryx2
+//! channel: service names are host-global, so the remote destination
+//! subscribes to the source's `{source_processor}/{source_output_port}`
+//! channel and the source notifies the destination's `streamlib/{dest}/notify`
+//! service. Each side records the link as a [`FederatedLink`] on its processor
+//! node, so channel and notify sizing count it like a local edge.
+//!
+//! The handshake rides the remote runtime's broker — the per-runtime Unix
+//! socket at `$XDG_RUNTIME_DIR/streamlib-<runtime_id>.sock` that already serves
+//! surface sharing. A `federate_link` request ships the channel's sizing; the
+//! reply carries the destination's notify sizing. The source runtime holds that
+//! connection for the link's lifetime and pings over it, which gives both ends
+//! liveness:
+//!
+//! - the broker drops the destination half when the connection closes (the
+//!   source runtime disconnected, stopped, or died);
+//! - the source drops its half when a ping fails (the remote runtime died).
+//!
+//! Either side publishes [`RuntimeEvent::RemoteLinkDidDisconnect`] when its
+//! half goes away.
+//!
+//! Only Linux has a broker socket today; elsewhere `connect_remote` returns
+//! [`Error::NotSupported`]. Frames that reference GPU surfaces resolve them
+//! against the *source* runtime's surface store, so federate encoded or
+//! CPU-resident streams.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::thread::JoinHandle;
+
+use parking_lot::Mutex;
+#[cfg(target_os = "linux")]
+use serde::{Deserialize, Serialize};
+
+use super::Runner;
+use super::operations::ConnectOptions;
+#[cfg(target_os = "linux")]
+use crate::core::compiler::compiler_ops::FederatedChannel;
+use crate::core::graph::{
+    FederatedLink, FederatedLinkDirection, FederatedLinksComponent, Graph, GraphNodeWithComponents,
+    LinkUniqueId, ProcessorInstanceComponent, ProcessorUniqueId,
+};
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, PortDirection, Result};
+#[cfg(target_os = "linux")]
+use crate::iceoryx2::PayloadCipher;
+
+/// Broker request asking a remote runtime to subscribe one of its input ports
+/// to a channel published by the requesting runtime.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FederateLinkRequest {
+    pub(crate) link_id: LinkUniqueId,
+    pub(crate) source_runtime_id: String,
+    pub(crate) source: OutputLinkPortRef,
+    pub(crate) dest: InputLinkPortRef,
+    pub(crate) channel: FederatedChannel,
+}
+
+/// Broker reply to a [`FederateLinkRequest`]: the destination's notify service
+/// and the `max_notifiers` it was created with, which the source must match.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FederateLinkReply {
+    pub(crate) notify_service_name: String,
+    pub(crate) max_notifiers: usize,
+}
+
+/// Destination side of the broker's federation ops. Implemented by the runtime
+/// that owns the broker; the broker calls it from its connection threads.
+#[cfg(target_os = "linux")]
+pub(crate) trait FederationLinkHandler: Send + Sync {
+    /// Wire the destination half of a federated link.
+    fn accept_remote_link(&self, request: FederateLinkRequest) -> Result<FederateLinkReply>;
+
+    /// Tear down the destination half of `link_id`. Unknown ids are ignored —
+    /// the destination processor may already be gone.
+    fn release_remote_link(&self, link_id: &str);
+}
+
+/// [`FederationLinkHandler`] over a runtime's graph.
+#[cfg(target_os = "linux")]
+pub(crate) struct RuntimeFederationHandler {
+    compiler: Arc<crate::core::compiler::Compiler>,
+    iceoryx2_node: crate::iceoryx2::Iceoryx2Node,
+}
+
+#[cfg(target_os = "linux")]
+impl RuntimeFederationHandler {
+    pub(crate) fn new(
+        compiler: Arc<crate::core::compiler::Compiler>,
+        iceoryx2_node: crate::iceoryx2::Iceoryx2Node,
+    ) -> Self {
+        Self {
+            compiler,
+            iceoryx2_node,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl FederationLinkHandler for RuntimeFederationHandler {
+    fn accept_remote_link(&self, request: FederateLinkRequest) -> Result<FederateLinkReply> {
+        let FederateLinkRequest {
+            link_id,
+            source_runtime_id,
+            source,
+            dest,
+            channel,
+        } = request;
+
+        let reply = self
+            .compiler
+            .scope(|graph, _tx| -> Result<FederateLinkReply> {
+                require_running_port(
+                    graph,
+                    &dest.processor_id,
+                    &dest.port_name,
+                    PortDirection::Input,
+                )?;
+                record_federated_link(
+                    graph,
+                    &dest.processor_id,
+                    FederatedLink {
+                        link_id: link_id.clone(),
+                        direction: FederatedLinkDirection::Inbound,
+                        local_port: dest.port_name.clone(),
+                        remote_runtime_id: source_runtime_id.clone(),
+                        remote_processor_id: source.processor_id.clone(),
+                        remote_port: source.port_name.clone(),
+                    },
+                );
+                crate::core::compiler::compiler_ops::open_federated_dest(
+                    graph,
+                    &self.iceoryx2_node,
+                    &link_id,
+                    &channel,
+                    &dest.processor_id,
+                    &dest.port_name,
+                )
+                .map(|(notify_service_name, max_notifiers)| FederateLinkReply {
+                    notify_service_name,
+                    max_notifiers,
+                })
+                .inspect_err(|_| {
+                    forget_federated_link(graph, link_id.as_str());
+                })
+            })?;
+
+        PUBSUB.publish(
+            topics::RUNTIME_GLOBAL,
+            &Event::RuntimeGlobal(RuntimeEvent::RemoteLinkDidConnect {
+                link_id: link_id.to_string(),
+                remote_runtime_id: source_runtime_id,
+                from_port: source.to_string(),
+                to_port: dest.to_string(),
+            }),
+        );
+        Ok(reply)
+    }
+
+    fn release_remote_link(&self, link_id: &str) {
+        if let Some(link) = retire_federated_link(&self.compiler, link_id) {
+            PUBSUB.publish(
+                topics::RUNTIME_GLOBAL,
+                &Event::RuntimeGlobal(RuntimeEvent::RemoteLinkDidDisconnect {
+                    link_id: link_id.to_string(),
+                    remote_runtime_id: link.remote_runtime_id,
+                    reason: "source runtime closed the link".to_string(),
+                }),
+            );
+        }
+    }
+}
+
+/// The source runtime's handle on one outbound federated link: the thread that
+/// holds the broker connection open and pings over it.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) struct RemoteLinkMonitor {
+    stop_flag: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Outbound federated links, keyed by link id.
+pub(crate) type RemoteLinkMonitors = Arc<Mutex<HashMap<LinkUniqueId, RemoteLinkMonitor>>>;
+
+impl Runner {
+    /// Link `local_output` to `remote_input` on a processor in the runtime
+    /// `remote_runtime_id`, another process on this host.
+    ///
+    /// Both runtimes must be started — each side wires its half onto a running
+    /// processor. The link counts toward the output port's channel sizing, so
+    /// connect remotes before local links out of the same port are wired, or
+    /// the existing channel's fixed subscriber count rejects the reopen.
+    ///
+    /// Fails with [`Error::RemoteRuntimeUnreachable`] when the remote runtime's
+    /// broker socket can't be reached, and with the remote's own error when it
+    /// refuses the link (unknown processor or port, subprocess destination).
+    pub fn connect_remote(
+        &self,
+        local_output: impl Into<OutputLinkPortRef>,
+        remote_runtime_id: &str,
+        remote_input: impl Into<InputLinkPortRef>,
+    ) -> Result<LinkUniqueId> {
+        self.connect_remote_with(
+            local_output,
+            remote_runtime_id,
+            remote_input,
+            ConnectOptions::default(),
+        )
+    }
+
+    /// [`Self::connect_remote`] under explicit [`ConnectOptions`].
+    ///
+    /// Only `encrypt_payloads` applies — schema agreement is the remote's to
+    /// check. An encrypted link hands the channel's key to the remote runtime
+    /// in the `federate_link` request, over the broker socket in the
+    /// user-private `$XDG_RUNTIME_DIR`.
+    pub fn connect_remote_with(
+        &self,
+        local_output: impl Into<OutputLinkPortRef>,
+        remote_runtime_id: &str,
+        remote_input: impl Into<InputLinkPortRef>,
+        options: ConnectOptions,
+    ) -> Result<LinkUniqueId> {
+        #[cfg(target_os = "linux")]
+        {
+            self.connect_remote_over_broker(
+                local_output.into(),
+                remote_runtime_id,
+                remote_input.into(),
+                options.encrypt_payloads,
+            )
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (local_output, remote_input, options);
+            Err(Error::NotSupported(format!(
+                "connect_remote to '{}': graph federation rides the per-runtime broker \
+                 socket, which only exists on Linux",
+                remote_runtime_id
+            )))
+        }
+    }
+
+    /// Tear down a link made by [`Self::connect_remote`]. The remote runtime
+    /// drops its half when the broker connection closes.
+    pub fn disconnect_remote(&self, link_id: &LinkUniqueId) -> Result<()> {
+        let monitor = self
+            .remote_links
+            .lock()
+            .remove(link_id)
+            .ok_or_else(|| Error::LinkNotFound(link_id.to_string()))?;
+        monitor
+            .stop_flag
+            .store(true, std::sync::atomic::Ordering::Release);
+        if let Some(thread) = monitor.thread {
+            let _ = thread.join();
+        }
+
+        // The monitor may have retired the link itself on a failed ping just
+        // before the stop flag landed; only the side that retires it reports.
+        if let Some(link) = retire_federated_link(&self.compiler, link_id.as_str()) {
+            PUBSUB.publish(
+                topics::RUNTIME_GLOBAL,
+                &Event::RuntimeGlobal(RuntimeEvent::RemoteLinkDidDisconnect {
+                    link_id: link_id.to_string(),
+                    remote_runtime_id: link.remote_runtime_id,
+                    reason: "disconnected locally".to_string(),
+                }),
+            );
+        }
+        Ok(())
+    }
+
+    /// Tear down every outbound federated link. Called from [`Self::stop`].
+    pub(crate) fn disconnect_all_remote_links(&self) {
+        let link_ids: Vec<LinkUniqueId> = self.remote_links.lock().keys().cloned().collect();
+        for link_id in link_ids {
+            if let Err(e) = self.disconnect_remote(&link_id) {
+                tracing::debug!("[federation] {} already torn down: {}", link_id, e);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn connect_remote_over_broker(
+        &self,
+        from: OutputLinkPortRef,
+        remote_runtime_id: &str,
+        to: InputLinkPortRef,
+        encrypt: bool,
+    ) -> Result<LinkUniqueId> {
+        let link_id = LinkUniqueId::new();
+        let socket_path = broker_socket_path(remote_runtime_id)?;
+        let stream = streamlib_surface_client::connect_to_surface_share_socket(&socket_path)
+            .map_err(|e| {
+                tracing::debug!(
+                    "[federation] connect to {} failed: {}",
+                    socket_path.display(),
+                    e
+                );
+                Error::RemoteRuntimeUnreachable(remote_runtime_id.to_string())
+            })?;
+        stream.set_read_timeout(Some(BROKER_REPLY_TIMEOUT))?;
+
+        let (channel, cipher) = self.compiler.scope(
+            |graph, _tx| -> Result<(FederatedChannel, Option<PayloadCipher>)> {
+                require_running_port(
+                    graph,
+                    &from.processor_id,
+                    &from.port_name,
+                    PortDirection::Output,
+                )?;
+                record_federated_link(
+                    graph,
+                    &from.processor_id,
+                    FederatedLink {
+                        link_id: link_id.clone(),
+                        direction: FederatedLinkDirection::Outbound,
+                        local_port: from.port_name.clone(),
+                        remote_runtime_id: remote_runtime_id.to_string(),
+                        remote_processor_id: to.processor_id.clone(),
+                        remote_port: to.port_name.clone(),
+                    },
+                );
+                let resolved = crate::core::compiler::compiler_ops::resolve_federated_channel(
+                    graph,
+                    &from.processor_id,
+                    &from.port_name,
+                )
+                .and_then(|mut channel| {
+                    // The key rides the request to the remote runtime.
+                    let cipher = crate::core::compiler::compiler_ops::resolve_channel_cipher(
+                        graph,
+                        &from.processor_id,
+                        &from.port_name,
+                        encrypt,
+                    )?;
+                    channel.payload_key = cipher.as_ref().map(|c| c.key().clone());
+                    Ok((channel, cipher))
+                });
+                resolved.inspect_err(|_| {
+                    forget_federated_link(graph, link_id.as_str());
+                })
+            },
+        )?;
+
+        let request = FederateLinkRequest {
+            link_id: link_id.clone(),
+            source_runtime_id: self.runtime_id.to_string(),
+            source: from.clone(),
+            dest: to.clone(),
+            channel,
+        };
+        let reply = match broker_federate_link(&stream, &request, remote_runtime_id) {
+            Ok(reply) => reply,
+            Err(e) => {
+                self.compiler
+                    .scope(|graph, _tx| forget_federated_link(graph, link_id.as_str()));
+                return Err(e);
+            }
+        };
+
+        let opened = self.compiler.scope(|graph, _tx| {
+            crate::core::compiler::compiler_ops::open_federated_source(
+                graph,
+                &self.iceoryx2_node,
+                &link_id,
+                &from.processor_id,
+                &from.port_name,
+                &reply.notify_service_name,
+                reply.max_notifiers,
+                cipher,
+            )
+            .inspect_err(|_| {
+                forget_federated_link(graph, link_id.as_str());
+            })
+        });
+        // On failure, returning drops the stream; the closed connection
+        // releases the remote's half.
+        opened?;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let link_id = link_id.clone();
+            let remote_runtime_id = remote_runtime_id.to_string();
+            let stop_flag = Arc::clone(&stop_flag);
+            let compiler = Arc::clone(&self.compiler);
+            let remote_links = Arc::clone(&self.remote_links);
+            std::thread::Builder::new()
+                .name("streamlib-federation-link".into())
+                .spawn(move || {
+                    run_remote_link_monitor(
+                        stream,
+                        link_id,
+                        remote_runtime_id,
+                        stop_flag,
+                        compiler,
+                        remote_links,
+                    )
+                })
+                .map_err(|e| {
+                    Error::Runtime(format!("failed to spawn federation link monitor: {e}"))
+                })?
+        };
+        self.remote_links.lock().insert(
+            link_id.clone(),
+            RemoteLinkMonitor {
+                stop_flag,
+                thread: Some(thread),
+            },
+        );
+
+        tracing::info!(
+            "[federation] Linked {} -> {}:{} [{}]",
+            from,
+            remote_runtime_id,
+            to,
+            link_id
+        );
+        PUBSUB.publish(
+            topics::RUNTIME_GLOBAL,
+            &Event::RuntimeGlobal(RuntimeEvent::RemoteLinkDidConnect {
+                link_id: link_id.to_string(),
+                remote_runtime_id: remote_runtime_id.to_string(),
+                from_port: from.to_string(),
+                to_port: to.to_string(),
+            }),
+        );
+        Ok(link_id)
+    }
+}
+
+/// How often an outbound link pings the remote broker.
+#[cfg(target_os = "linux")]
+const REMOTE_LINK_PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Granularity at which the link monitor checks its stop flag between pings.
+#[cfg(target_os = "linux")]
+const REMOTE_LINK_STOP_POLL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// How long a broker request waits for its reply before the remote runtime
+/// counts as gone. Covers the remote wiring its half under its graph lock.
+#[cfg(target_os = "linux")]
+const BROKER_REPLY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The broker socket of runtime `runtime_id` —
+/// `$XDG_RUNTIME_DIR/streamlib-<runtime_id>.sock`.
+#[cfg(target_os = "linux")]
+pub(crate) fn broker_socket_path(runtime_id: &str) -> Result<std::path::PathBuf> {
+    let xdg_runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").ok_or_else(|| {
+        Error::Runtime(
+            "XDG_RUNTIME_DIR is not set. The runtime needs a writable directory \
+             for its per-runtime surface-sharing socket — typically /run/user/<uid>. \
+             Set XDG_RUNTIME_DIR or run under a session manager that provides it."
+                .to_string(),
+        )
+    })?;
+    Ok(std::path::PathBuf::from(xdg_runtime_dir).join(format!("streamlib-{}.sock", runtime_id)))
+}
+
+/// Send `federate_link` over the broker connection and decode the reply.
+#[cfg(target_os = "linux")]
+fn broker_federate_link(
+    stream: &std::os::unix::net::UnixStream,
+    request: &FederateLinkRequest,
+    remote_runtime_id: &str,
+) -> Result<FederateLinkReply> {
+    let mut body = serde_json::to_value(request)
+        .map_err(|e| Error::Runtime(format!("failed to encode federate_link: {e}")))?;
+    body["op"] = serde_json::json!("federate_link");
+
+    let (response, _) = streamlib_surface_client::send_request_with_fds(stream, &body, &[], 0)
+        .map_err(|e| {
+            tracing::debug!(
+                "[federation] federate_link to {} failed: {}",
+                remote_runtime_id,
+                e
+            );
+            Error::RemoteRuntimeUnreachable(remote_runtime_id.to_string())
+        })?;
+    if let Some(error) = response.get("error").and_then(|v| v.as_str()) {
+        return Err(Error::Link(format!(
+            "runtime '{}' refused federated link {}: {}",
+            remote_runtime_id, request.link_id, error
+        )));
+    }
+    serde_json::from_value(response).map_err(|e| {
+        Error::Link(format!(
+            "runtime '{}' sent a malformed federate_link reply: {}",
+            remote_runtime_id, e
+        ))
+    })
+}
+
+/// Body of the link monitor thread: ping the remote broker until told to stop
+/// (then release the remote half explicitly) or until a ping fails (then
+/// retire the local half).
+#[cfg(target_os = "linux")]
+fn run_remote_link_monitor(
+    stream: std::os::unix::net::UnixStream,
+    link_id: LinkUniqueId,
+    remote_runtime_id: String,
+    stop_flag: Arc<AtomicBool>,
+    compiler: Arc<crate::core::compiler::Compiler>,
+    remote_links: RemoteLinkMonitors,
+) {
+    use std::sync::atomic::Ordering;
+
+    let ping = serde_json::json!({ "op": "federation_ping", "link_id": link_id.as_str() });
+    loop {
+        let mut waited = std::time::Duration::ZERO;
+        while waited < REMOTE_LINK_PING_INTERVAL && !stop_flag.load(Ordering::Acquire) {
+            std::thread::sleep(REMOTE_LINK_STOP_POLL);
+            waited += REMOTE_LINK_STOP_POLL;
+        }
+
+        if stop_flag.load(Ordering::Acquire) {
+            let release =
+                serde_json::json!({ "op": "unfederate_link", "link_id": link_id.as_str() });
+            let _ = streamlib_surface_client::send_request_with_fds(&stream, &release, &[], 0);
+            return;
+        }
+
+        if let Err(e) = streamlib_surface_client::send_request_with_fds(&stream, &ping, &[], 0) {
+            tracing::warn!(
+                "[federation] Runtime '{}' stopped answering; dropping link [{}]: {}",
+                remote_runtime_id,
+                link_id,
+                e
+            );
+            remote_links.lock().remove(&link_id);
+            if retire_federated_link(&compiler, link_id.as_str()).is_some() {
+                PUBSUB.publish(
+                    topics::RUNTIME_GLOBAL,
+                    &Event::RuntimeGlobal(RuntimeEvent::RemoteLinkDidDisconnect {
+                        link_id: link_id.to_string(),
+                        remote_runtime_id,
+                        reason: format!("remote runtime stopped answering: {e}"),
+                    }),
+                );
+            }
+            return;
+        }
+    }
+}
+
+/// Reject a federated link endpoint that isn't a running processor with a
+/// `direction` port named `port_name`.
+#[cfg(target_os = "linux")]
+fn require_running_port(
+    graph: &mut Graph,
+    proc_id: &ProcessorUniqueId,
+    port_name: &str,
+    direction: PortDirection,
+) -> Result<()> {
+    let node = graph
+        .traversal()
+        .v(proc_id)
+        .first()
+        .ok_or_else(|| Error::ProcessorNotFound(proc_id.to_string()))?;
+    let has_port = match direction {
+        PortDirection::Input => node.has_input(port_name),
+        PortDirection::Output => node.has_output(port_name),
+    };
+    if !has_port {
+        return Err(Error::ProcessorPortNotFound {
+            processor_id: proc_id.to_string(),
+            port_name: port_name.to_string(),
+            direction,
+        });
+    }
+    if !node.has::<ProcessorInstanceComponent>() {
+        return Err(Error::Runtime(format!(
+            "processor '{}' is not running — start its runtime before federating it",
+            proc_id
+        )));
+    }
+    Ok(())
+}
+
+/// Record `link` on `proc_id`'s [`FederatedLinksComponent`].
+#[cfg(target_os = "linux")]
+fn record_federated_link(graph: &mut Graph, proc_id: &ProcessorUniqueId, link: FederatedLink) {
+    let Some(node) = graph.traversal_mut().v(proc_id).first_mut() else {
+        return;
+    };
+    if !node.has::<FederatedLinksComponent>() {
+        node.insert(FederatedLinksComponent::default());
+    }
+    if let Some(federated) = node.get_mut::<FederatedLinksComponent>() {
+        federated.links.push(link);
+    }
+}
+
+/// Remove `link_id` from whichever processor records it, returning that
+/// processor and the link.
+fn forget_federated_link(
+    graph: &mut Graph,
+    link_id: &str,
+) -> Option<(ProcessorUniqueId, FederatedLink)> {
+    let processor_ids: Vec<ProcessorUniqueId> = graph.traversal().v(()).ids();
+    processor_ids.into_iter().find_map(|proc_id| {
+        let link = graph
+            .traversal_mut()
+            .v(&proc_id)
+            .first_mut()?
+            .get_mut::<FederatedLinksComponent>()?
+            .remove(link_id)?;
+        Some((proc_id, link))
+    })
+}
+
+/// Forget `link_id` and reclaim this runtime's iceoryx2 ports for it. Returns
+/// the link when this call retired it, `None` when it was already gone.
+fn retire_federated_link(
+    compiler: &crate::core::compiler::Compiler,
+    link_id: &str,
+) -> Option<FederatedLink> {
+    compiler.scope(|graph, _tx| {
+        let (proc_id, link) = forget_federated_link(graph, link_id)?;
+        crate::core::compiler::compiler_ops::close_federated_link(
+            graph,
+            &link.link_id,
+            &proc_id,
+            link.direction,
+            &link.local_port,
+        );
+        Some(link)
+    })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    /// The request crosses the broker as JSON with an added `op` key; it must
+    /// decode back to the same value with that key present, since the broker
+    /// hands the whole request object to the handler.
+    #[test]
+    fn federate_link_request_round_trips_with_an_op_key() {
+        let request = FederateLinkRequest {
+            link_id: LinkUniqueId::new(),
+            source_runtime_id: "Rsource".to_string(),
+            source: OutputLinkPortRef::new("Pcamera", "video_out"),
+            dest: InputLinkPortRef::new("Pdisplay", "video_in"),
+            channel: FederatedChannel {
+                channel_service_name: "pcamera/video_out".to_string(),
+                max_subscribers: 2,
+                max_queued_messages: 1,
+                enable_safe_overflow: true,
+                payload_key: None,
+            },
+        };
+
+        let mut body = serde_json::to_value(&request).expect("encode");
+        body["op"] = serde_json::json!("federate_link");
+        let back: FederateLinkRequest = serde_json::from_value(body).expect("decode");
+        assert_eq!(back, request);
+    }
+
+    /// Forgetting a link finds it on whichever processor records it and
+    /// leaves that processor's other federated links in place.
+    #[test]
+    fn forget_federated_link_removes_only_that_link() {
+        crate::core::test_support::ensure_test_mocks_registered();
+        let mut graph = Graph::new();
+        let ident = crate::core::processors::PROCESSOR_REGISTRY
+            .list_registered()
+            .into_iter()
+            .find(|d| d.name.r#type.as_str() == "TestMockOutputOnlyProcessor")
+            .expect("mock output processor is registered")
+            .name;
+        let proc_id = graph
+            .traversal_mut()
+            .add_v(crate::core::processors::ProcessorSpec::new(
+                ident,
+                serde_json::Value::Null,
+            ))
+            .first()
+            .expect("processor added")
+            .id
+            .clone();
+
+        let link = |link_id: &LinkUniqueId| FederatedLink {
+            link_id: link_id.clone(),
+            direction: FederatedLinkDirection::Outbound,
+            local_port: "out1".to_string(),
+            remote_runtime_id: "Rremote".to_string(),
+            remote_processor_id: "Premote".into(),
+            remote_port: "in1".to_string(),
+        };
+        let (first, second) = (LinkUniqueId::new(), LinkUniqueId::new());
+        record_federated_link(&mut graph, &proc_id, link(&first));
+        record_federated_link(&mut graph, &proc_id, link(&second));
+
+        let (owner, forgotten) =
+            forget_federated_link(&mut graph, first.as_str()).expect("first link is recorded");
+        assert_eq!(owner, proc_id);
+        assert_eq!(forgotten.link_id, first);
+        assert!(forget_federated_link(&mut graph, first.as_str()).is_none());
+
+        let remaining = graph
+            .traversal()
+            .v(&proc_id)
+            .first()
+            .and_then(|node| node.get::<FederatedLinksComponent>())
+            .map(|federated| federated.count(FederatedLinkDirection::Outbound, None));
+        assert_eq!(remaining, Some(1));
+    }
+}
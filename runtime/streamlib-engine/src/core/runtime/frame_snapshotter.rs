@@ -0,0 +1,263 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Runner side of frame snapshots and previews: tapping the port's
+//! channel, reading frame surfaces back and encoding them.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::{Runner, RuntimeOperations, TapSubscription};
+use crate::core::context::RuntimeContext;
+use crate::core::frame_snapshot::{
+    FrameSnapshot, PreviewFrame, PreviewOptions, SnapshotFrameReference, check_snapshot_path,
+    encode_png, encode_preview,
+};
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::rhi::TextureFormat;
+use crate::core::{Error, PortDirection, Result};
+
+/// How long a snapshot waits for the next frame on the port.
+const SNAPSHOT_FRAME_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the readback waits on the producer's published sync point and
+/// then on its own copy.
+#[cfg(target_os = "linux")]
+const SNAPSHOT_GPU_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl Runner {
+    /// Write the next frame `processor_id` sends on its `port` output to
+    /// the PNG file at `path`.
+    ///
+    /// The frame is taken through the channel's reserved tap slot, so the
+    /// port must be wired and not already tapped
+    /// ([`Error::TapChannelNotFound`] / [`Error::TapSlotOccupied`]); the
+    /// pipeline itself is untouched. Fails if no frame arrives within ten
+    /// seconds.
+    pub async fn snapshot_port(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        port: &str,
+        path: impl AsRef<Path>,
+    ) -> Result<FrameSnapshot> {
+        let path = path.as_ref();
+        check_snapshot_path(path)?;
+        let (runtime_ctx, channel) = self.video_output_channel(processor_id, port, "snapshot")?;
+        let mut tap = RuntimeOperations::tap_async(self, channel, Some(1)).await?;
+        let bag = tokio::time::timeout(SNAPSHOT_FRAME_TIMEOUT, tap.recv())
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                Error::Runtime(format!(
+                    "snapshot: no frame on {processor_id}.{port} within {}s",
+                    SNAPSHOT_FRAME_TIMEOUT.as_secs()
+                ))
+            })?;
+        drop(tap);
+
+        let frame = SnapshotFrameReference::from_bag(&bag)?;
+        let timestamp_ns = frame.timestamp_ns();
+        let (width, height) = (frame.width, frame.height);
+        let png_bytes =
+            tokio::task::spawn_blocking(move || read_back(&runtime_ctx, &frame, encode_png))
+                .await
+                .map_err(|e| {
+                    Error::Runtime(format!("snapshot readback task failed to join: {e}"))
+                })??;
+        std::fs::write(path, &png_bytes)?;
+
+        tracing::info!(
+            "[snapshot] Wrote {}x{} frame from {}.{} to {}",
+            width,
+            height,
+            processor_id,
+            port,
+            path.display()
+        );
+        Ok(FrameSnapshot {
+            path: path.to_path_buf(),
+            width,
+            height,
+            timestamp_ns,
+            bytes: png_bytes.len() as u64,
+        })
+    }
+
+    /// Stream downscaled thumbnails of the frames `processor_id` sends on
+    /// its `port` output, one every `1 / options.fps` seconds.
+    ///
+    /// The preview holds the channel's reserved tap slot for as long as it
+    /// lives, so the port must be wired and not already tapped — a
+    /// snapshot of the same port fails while a preview is open. Frames
+    /// between thumbnails are skipped, never queued.
+    pub async fn preview_port(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        port: &str,
+        options: PreviewOptions,
+    ) -> Result<PortPreview> {
+        let (runtime_ctx, channel) = self.video_output_channel(processor_id, port, "preview")?;
+        let tap = RuntimeOperations::tap_async(self, channel, None).await?;
+        tracing::info!("[preview] Previewing {}.{}", processor_id, port);
+        Ok(PortPreview {
+            tap,
+            runtime_ctx,
+            options,
+            next_due: Instant::now(),
+        })
+    }
+
+    /// Check `processor_id` has a `port` output and resolve the channel it
+    /// writes, plus the GPU context its frames are read back through.
+    fn video_output_channel(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        port: &str,
+        action: &str,
+    ) -> Result<(Arc<RuntimeContext>, String)> {
+        self.compiler.scope(|graph, _tx| {
+            let node = graph
+                .traversal()
+                .v(processor_id)
+                .first()
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+            if node.has_output(port) {
+                Ok(())
+            } else {
+                Err(Error::ProcessorPortNotFound {
+                    processor_id: processor_id.to_string(),
+                    port_name: port.to_string(),
+                    direction: PortDirection::Output,
+                })
+            }
+        })?;
+        let runtime_ctx = self
+            .runtime_context
+            .lock()
+            .clone()
+            .ok_or_else(|| Error::Runtime(format!("{action}: start the runtime first")))?;
+
+        let channel = streamlib_idents::source_channel_name(processor_id.as_str(), port)
+            .map_err(|e| Error::Configuration(e.to_string()))?
+            .into_string();
+        Ok((runtime_ctx, channel))
+    }
+}
+
+/// An open port preview, from [`Runner::preview_port`]. Holds the port's
+/// tap; dropping it (or its [`into_tap`](Self::into_tap) half) frees the
+/// slot.
+pub struct PortPreview {
+    tap: TapSubscription,
+    runtime_ctx: Arc<RuntimeContext>,
+    options: PreviewOptions,
+    next_due: Instant,
+}
+
+impl PortPreview {
+    /// Wait for the next thumbnail, read back from the newest frame once
+    /// the interval has passed. Returns `None` once the tap ends (the link
+    /// was removed or the runtime stopped). A frame that fails to read back
+    /// yields `Some(Err(_))`; the preview carries on with the next one.
+    pub async fn next_frame(&mut self) -> Option<Result<PreviewFrame>> {
+        tokio::time::sleep_until(self.next_due).await;
+        let mut bag = self.tap.recv().await?;
+        while let Some(newer) = self.tap.try_recv() {
+            bag = newer;
+        }
+        self.next_due = Instant::now() + self.options.interval();
+        Some(self.thumbnail(bag).await)
+    }
+
+    async fn thumbnail(&self, bag: Vec<u8>) -> Result<PreviewFrame> {
+        let frame = SnapshotFrameReference::from_bag(&bag)?;
+        let timestamp_ns = frame.timestamp_ns();
+        let runtime_ctx = Arc::clone(&self.runtime_ctx);
+        let options = self.options.clone();
+        let (width, height, bytes) = tokio::task::spawn_blocking(move || {
+            read_back(
+                &runtime_ctx,
+                &frame,
+                |format, texture_width, width, height, pixels| {
+                    encode_preview(format, texture_width, width, height, pixels, &options)
+                },
+            )
+        })
+        .await
+        .map_err(|e| Error::Runtime(format!("preview readback task failed to join: {e}")))??;
+        Ok(PreviewFrame {
+            width,
+            height,
+            timestamp_ns,
+            format: self.options.format,
+            bytes,
+        })
+    }
+
+    /// The preview's tap. Dropping a tap joins its forwarder thread, so an
+    /// async caller takes it out and drops it on a blocking thread.
+    pub fn into_tap(self) -> TapSubscription {
+        self.tap
+    }
+}
+
+/// Read the frame's surface back through the runtime's GPU context and
+/// hand the pixels to `encode` (format, texture width, frame width and
+/// height, rows).
+#[cfg(target_os = "linux")]
+fn read_back<T>(
+    runtime_ctx: &RuntimeContext,
+    frame: &SnapshotFrameReference,
+    encode: impl FnOnce(TextureFormat, u32, u32, u32, &[u8]) -> Result<T>,
+) -> Result<T> {
+    use crate::core::rhi::{TextureReadbackDescriptor, TextureSourceLayout};
+
+    let gpu = &runtime_ctx.gpu;
+    let registration = gpu.resolve_texture_registration_by_surface_id(
+        &frame.surface_id,
+        frame.texture_layout,
+        frame.width,
+        frame.height,
+    )?;
+    registration.wait_for_producer(SNAPSHOT_GPU_TIMEOUT)?;
+    let layout = registration.current_layout();
+    let source_layout = TextureSourceLayout::from_vulkan_layout_raw(layout.0).ok_or_else(|| {
+        Error::NotSupported(format!(
+            "snapshot: surface '{}' is in layout {}, which readback can't copy from",
+            frame.surface_id, layout.0
+        ))
+    })?;
+
+    let texture = registration.texture();
+    let readback = gpu.create_texture_readback(&TextureReadbackDescriptor {
+        label: "runtime-snapshot",
+        format: texture.format(),
+        width: texture.width(),
+        height: texture.height(),
+    })?;
+    let ticket = readback.submit(texture, source_layout)?;
+    let timeout_ns = u64::try_from(SNAPSHOT_GPU_TIMEOUT.as_nanos()).unwrap_or(u64::MAX);
+    let pixels = readback.wait_and_read(ticket, timeout_ns)?;
+    encode(
+        texture.format(),
+        texture.width(),
+        frame.width.min(texture.width()),
+        frame.height.min(texture.height()),
+        pixels,
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_back<T>(
+    _runtime_ctx: &RuntimeContext,
+    _frame: &SnapshotFrameReference,
+    _encode: impl FnOnce(TextureFormat, u32, u32, u32, &[u8]) -> Result<T>,
+) -> Result<T> {
+    Err(Error::NotSupported(
+        "snapshot: host texture readback is only implemented on Linux".into(),
+    ))
+}
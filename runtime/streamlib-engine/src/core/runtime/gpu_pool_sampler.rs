@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Periodic sampler that mirrors the GPU texture pool onto the graph's
+//! `gpu_pool` component and publishes [`RuntimeEvent::GpuPoolPressure`] on
+//! the rising edge.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::core::compiler::Compiler;
+use crate::core::context::{GpuContext, TexturePoolStats};
+use crate::core::json_schema::{GPU_POOL_COMPONENT, GpuPoolStatsOutput};
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+
+use super::RuntimeStatus;
+
+/// How often the sampler reads the pool counters.
+const GPU_POOL_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Occupancy at which the pool counts as under pressure — high enough that
+/// steady-state pipelines stay quiet, low enough to leave a few textures of
+/// headroom before acquires start blocking.
+const GPU_POOL_PRESSURE_OCCUPANCY: f64 = 0.85;
+
+/// Pressure state carried between samples, so [`RuntimeEvent::GpuPoolPressure`]
+/// fires once per rising edge and failures count only when new.
+#[derive(Debug, Default)]
+pub(crate) struct GpuPoolPressureTracker {
+    under_pressure: bool,
+    allocation_failures: u64,
+    exhausted_acquires: u64,
+}
+
+impl GpuPoolPressureTracker {
+    /// Fold in `stats`. Returns the graph component for this sample and the
+    /// event to publish when the pool just came under pressure.
+    pub(crate) fn sample(
+        &mut self,
+        stats: &TexturePoolStats,
+    ) -> (GpuPoolStatsOutput, Option<RuntimeEvent>) {
+        let occupancy = stats.occupancy();
+        let failing = stats.allocation_failures > self.allocation_failures
+            || stats.exhausted_acquires > self.exhausted_acquires;
+        let under_pressure = failing || occupancy >= GPU_POOL_PRESSURE_OCCUPANCY;
+        let became_pressured = under_pressure && !self.under_pressure;
+
+        self.under_pressure = under_pressure;
+        self.allocation_failures = stats.allocation_failures;
+        self.exhausted_acquires = stats.exhausted_acquires;
+
+        let output = GpuPoolStatsOutput {
+            total_textures: stats.total_textures as u64,
+            textures_in_use: stats.textures_in_use as u64,
+            capacity: stats.capacity as u64,
+            occupancy,
+            high_water_in_use: stats.high_water_in_use as u64,
+            allocation_failures: stats.allocation_failures,
+            exhausted_acquires: stats.exhausted_acquires,
+            under_pressure,
+        };
+        let event = became_pressured.then(|| RuntimeEvent::GpuPoolPressure {
+            textures_in_use: output.textures_in_use,
+            capacity: output.capacity,
+            high_water_in_use: output.high_water_in_use,
+            occupancy,
+            allocation_failures: output.allocation_failures,
+            exhausted_acquires: output.exhausted_acquires,
+        });
+        (output, event)
+    }
+}
+
+/// Spawn the sampler on the runtime's tokio handle. Like the link-drop
+/// sampler, the task exits once the runtime leaves the started / paused
+/// states and is aborted through the returned handle on `stop()`, so each
+/// `start()` runs exactly one against that session's [`GpuContext`].
+pub(crate) fn spawn_gpu_pool_sampler(
+    handle: &tokio::runtime::Handle,
+    compiler: Arc<Compiler>,
+    gpu: GpuContext,
+    status: Arc<Mutex<RuntimeStatus>>,
+) -> tokio::task::JoinHandle<()> {
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(GPU_POOL_SAMPLE_INTERVAL);
+        let mut tracker = GpuPoolPressureTracker::default();
+        loop {
+            interval.tick().await;
+            match *status.lock() {
+                RuntimeStatus::Started => {}
+                RuntimeStatus::Pausing | RuntimeStatus::Paused => continue,
+                _ => break,
+            }
+            let (output, event) = tracker.sample(&gpu.pool_stats());
+            match serde_json::to_value(&output) {
+                Ok(value) => compiler.scope(|graph, _tx| {
+                    graph.set_component(GPU_POOL_COMPONENT, value);
+                }),
+                Err(e) => tracing::warn!("[gpu_pool_sampler] failed to serialize stats: {}", e),
+            }
+            if let Some(event) = event {
+                tracing::warn!(event = ?event, "GPU texture pool under pressure");
+                PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+            }
+        }
+        tracing::debug!("[gpu_pool_sampler] runtime no longer running, sampler exiting");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(in_use: usize, capacity: usize, exhausted_acquires: u64) -> TexturePoolStats {
+        TexturePoolStats {
+            total_textures: in_use,
+            textures_in_use: in_use,
+            bucket_count: 1,
+            capacity,
+            high_water_in_use: in_use,
+            exhausted_acquires,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pressure_fires_once_per_rising_edge() {
+        let mut tracker = GpuPoolPressureTracker::default();
+
+        let (output, event) = tracker.sample(&stats(4, 16, 0));
+        assert!(!output.under_pressure);
+        assert!(event.is_none());
+
+        let (output, event) = tracker.sample(&stats(15, 16, 0));
+        assert!(output.under_pressure);
+        assert!(matches!(
+            event,
+            Some(RuntimeEvent::GpuPoolPressure {
+                textures_in_use: 15,
+                capacity: 16,
+                ..
+            })
+        ));
+
+        // Still over the threshold: no repeat.
+        let (_, event) = tracker.sample(&stats(16, 16, 0));
+        assert!(event.is_none());
+
+        let (output, _) = tracker.sample(&stats(2, 16, 0));
+        assert!(!output.under_pressure);
+    }
+
+    #[test]
+    fn new_exhausted_acquires_count_as_pressure_until_they_stop() {
+        let mut tracker = GpuPoolPressureTracker::default();
+
+        let (output, event) = tracker.sample(&stats(1, 16, 3));
+        assert!(output.under_pressure);
+        assert!(event.is_some());
+
+        // The counter is cumulative; an unchanged count means no new failures.
+        let (output, event) = tracker.sample(&stats(1, 16, 3));
+        assert!(!output.under_pressure);
+        assert!(event.is_none());
+    }
+}
@@ -0,0 +1,474 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Runner side of [`GraphPatch`]: applying a batch of graph edits under one
+//! graph lock, all or nothing.
+//!
+//! Every step is staged against the live graph — nodes and links added,
+//! removals and disconnects marked — while the compiler operations they
+//! need are held back. Only once the last step succeeds are those
+//! operations queued, the steps recorded in the edit history as one
+//! [`GraphEdit::Batch`] and their events published, followed by a single
+//! `GraphDidChange`. A rejected step unwinds the staged ones in reverse, so
+//! neither the compiler nor any subscriber ever sees a half-applied patch.
+
+use super::Runner;
+use super::edit_history::{disconnect_edit, processor_removal_edit};
+use super::operations::ConnectOptions;
+use super::operations_runtime::add_validated_link;
+use super::runtime::swap_processor_config;
+use crate::core::compiler::PendingOperation;
+use crate::core::graph::{
+    Graph, GraphEdgeWithComponents, GraphNodeWithComponents, LinkUniqueId,
+    PendingDeletionComponent, ProcessorUniqueId, StateComponent,
+};
+use crate::core::graph_edit_history::{GraphEdit, GraphEditLink};
+use crate::core::graph_patch::{GraphPatch, GraphPatchReceipt};
+use crate::core::processors::ProcessorState;
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, Result};
+
+/// How to back out one staged step.
+enum StagedStep {
+    Disconnected(LinkUniqueId),
+    Removed(ProcessorUniqueId),
+    Added(ProcessorUniqueId),
+    Configured {
+        processor_id: ProcessorUniqueId,
+        previous: serde_json::Value,
+    },
+    Connected(LinkUniqueId),
+}
+
+/// A patch staged against the graph but not yet handed to the compiler.
+#[derive(Default)]
+struct StagedPatch {
+    /// Queued for the compiler once every step has succeeded.
+    operations: Vec<PendingOperation>,
+    /// The steps taken so far, in order.
+    steps: Vec<StagedStep>,
+    /// Recorded in the edit history, in order.
+    edits: Vec<GraphEdit>,
+    /// Published after the patch applies, before `GraphDidChange`.
+    events: Vec<RuntimeEvent>,
+    receipt: GraphPatchReceipt,
+}
+
+impl StagedPatch {
+    /// A processor id as the patch means it: an alias of an added processor
+    /// resolves to the id it was minted.
+    fn resolve(&self, processor_id: &ProcessorUniqueId) -> ProcessorUniqueId {
+        self.receipt
+            .processors
+            .get(processor_id.as_str())
+            .cloned()
+            .unwrap_or_else(|| processor_id.clone())
+    }
+
+    fn stage(&mut self, graph: &mut Graph, patch: GraphPatch) -> Result<()> {
+        for link_id in patch.disconnect {
+            let edit = disconnect_edit(graph, &link_id)
+                .filter(|_| !link_pending_deletion(graph, &link_id))
+                .ok_or_else(|| Error::LinkNotFound(link_id.to_string()))?;
+            if let Some(link) = graph.traversal_mut().e(&link_id).first_mut() {
+                link.insert(PendingDeletionComponent);
+            }
+            self.steps.push(StagedStep::Disconnected(link_id.clone()));
+            self.operations
+                .push(PendingOperation::RemoveLink(link_id.clone()));
+            if let GraphEdit::Disconnect { link } = &edit {
+                self.events.push(RuntimeEvent::RuntimeDidDisconnect {
+                    link_id: link_id.to_string(),
+                    from_port: link.from.to_string(),
+                    to_port: link.to.to_string(),
+                });
+            }
+            self.edits.push(edit);
+        }
+
+        for processor_id in patch.remove_processors {
+            let edit = processor_removal_edit(graph, &processor_id)
+                .filter(|_| !processor_pending_deletion(graph, &processor_id))
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+            if let Some(node) = graph.traversal_mut().v(&processor_id).first_mut() {
+                node.insert(PendingDeletionComponent);
+            }
+            self.steps.push(StagedStep::Removed(processor_id.clone()));
+            self.operations
+                .push(PendingOperation::RemoveProcessor(processor_id.clone()));
+            self.events.push(RuntimeEvent::RuntimeDidRemoveProcessor {
+                processor_id: processor_id.clone(),
+            });
+            self.edits.push(edit);
+        }
+
+        for processor in patch.add_processors {
+            let alias = ProcessorUniqueId::from(processor.alias.as_str());
+            if graph.traversal().v(&alias).exists() {
+                return Err(Error::InvalidGraph(format!(
+                    "graph patch: alias '{alias}' is already a processor id"
+                )));
+            }
+            let ident = processor.spec.name.to_diagnostic_ident();
+            let processor_id = graph
+                .traversal_mut()
+                .add_v(processor.spec.clone())
+                .first()
+                .map(|node| node.id.clone())
+                .ok_or_else(|| Error::GraphError("Could not create node".into()))?;
+            self.steps.push(StagedStep::Added(processor_id.clone()));
+            // `add_v` leaves an unregistered type in the graph in `Error`
+            // state; a patch backs it out instead.
+            if registry_miss(graph, &processor_id) {
+                return Err(Error::UnknownProcessorType { ident });
+            }
+            self.operations
+                .push(PendingOperation::AddProcessor(processor_id.clone()));
+            self.events.push(RuntimeEvent::RuntimeDidAddProcessor {
+                processor_id: processor_id.clone(),
+            });
+            self.edits.push(GraphEdit::AddProcessor {
+                processor_id: processor_id.clone(),
+                spec: processor.spec,
+                links: Vec::new(),
+            });
+            self.receipt
+                .processors
+                .insert(processor.alias, processor_id);
+        }
+
+        for update in patch.update_configs {
+            let processor_id = self.resolve(&update.processor_id);
+            let previous = swap_processor_config(graph, &processor_id, update.config.clone())
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+            self.steps.push(StagedStep::Configured {
+                processor_id: processor_id.clone(),
+                previous: previous.clone(),
+            });
+            self.operations
+                .push(PendingOperation::UpdateProcessorConfig(
+                    processor_id.clone(),
+                ));
+            self.events.push(RuntimeEvent::ProcessorConfigDidChange {
+                processor_id: processor_id.clone(),
+            });
+            self.edits.push(GraphEdit::UpdateConfig {
+                processor_id,
+                previous,
+                config: update.config,
+            });
+        }
+
+        for link in patch.connect {
+            let from =
+                OutputLinkPortRef::new(self.resolve(&link.from.processor_id), link.from.port_name);
+            let to = InputLinkPortRef::new(self.resolve(&link.to.processor_id), link.to.port_name);
+            let (link_id, _channel) =
+                add_validated_link(graph, from.clone(), to.clone(), &ConnectOptions::loose())?;
+            self.steps.push(StagedStep::Connected(link_id.clone()));
+            self.operations
+                .push(PendingOperation::AddLink(link_id.clone()));
+            self.events.push(RuntimeEvent::RuntimeDidConnect {
+                link_id: link_id.to_string(),
+                from_port: from.port_name.clone(),
+                to_port: to.port_name.clone(),
+            });
+            self.edits.push(GraphEdit::Connect {
+                link: GraphEditLink {
+                    link_id: link_id.clone(),
+                    from,
+                    to,
+                },
+            });
+            self.receipt.links.push(link_id);
+        }
+        Ok(())
+    }
+
+    /// Back out every staged step, newest first.
+    fn unwind(self, graph: &mut Graph) {
+        for step in self.steps.into_iter().rev() {
+            match step {
+                StagedStep::Disconnected(link_id) => {
+                    if let Some(link) = graph.traversal_mut().e(&link_id).first_mut() {
+                        link.remove::<PendingDeletionComponent>();
+                    }
+                }
+                StagedStep::Removed(processor_id) => {
+                    if let Some(node) = graph.traversal_mut().v(&processor_id).first_mut() {
+                        node.remove::<PendingDeletionComponent>();
+                    }
+                }
+                StagedStep::Added(processor_id) => {
+                    graph.traversal_mut().v(&processor_id).drop();
+                }
+                StagedStep::Configured {
+                    processor_id,
+                    previous,
+                } => {
+                    swap_processor_config(graph, &processor_id, previous);
+                }
+                StagedStep::Connected(link_id) => {
+                    graph.traversal_mut().e(&link_id).drop();
+                }
+            }
+        }
+    }
+}
+
+impl Runner {
+    /// Apply `patch` all or nothing; see [`GraphPatch`]. Returns the ids
+    /// minted for its aliases and links. On error the graph is unchanged
+    /// and nothing is published.
+    ///
+    /// Links are wired as given, with loose schema validation; no
+    /// converter is auto-inserted. The whole patch lands in the edit
+    /// history as one edit, so a single undo reverts it.
+    pub async fn apply_graph_patch(&self, patch: GraphPatch) -> Result<GraphPatchReceipt> {
+        patch.check()?;
+        if patch.is_empty() {
+            return Ok(GraphPatchReceipt::default());
+        }
+        // Lazy plugin discovery may load a package, so it runs before the
+        // graph is locked. A type still unknown afterwards fails in `stage`.
+        for processor in &patch.add_processors {
+            if let Some(error) = self
+                .lazily_load_provider_for_processor_type(&processor.spec.name)
+                .await
+            {
+                return Err(error);
+            }
+        }
+
+        let staged = self.compiler.scope(|graph, tx| {
+            let mut staged = StagedPatch::default();
+            match staged.stage(graph, patch) {
+                Ok(()) => {
+                    for operation in staged.operations.drain(..) {
+                        tx.log(operation);
+                    }
+                    Ok(staged)
+                }
+                Err(error) => {
+                    staged.unwind(graph);
+                    Err(error)
+                }
+            }
+        })?;
+
+        for edit in &staged.edits {
+            if let GraphEdit::RemoveProcessor { processor_id, .. } = edit {
+                crate::core::graph::clear_reported_components(processor_id.as_str());
+            }
+        }
+        if let Some(edit) = GraphEdit::batch(staged.edits) {
+            self.record_graph_edit(edit);
+        }
+        for event in staged.events {
+            PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+        }
+        PUBSUB.publish(
+            topics::RUNTIME_GLOBAL,
+            &Event::RuntimeGlobal(RuntimeEvent::GraphDidChange),
+        );
+        Ok(staged.receipt)
+    }
+}
+
+fn registry_miss(graph: &Graph, processor_id: &ProcessorUniqueId) -> bool {
+    graph
+        .traversal()
+        .v(processor_id)
+        .first()
+        .and_then(|node| node.get::<StateComponent>())
+        .is_some_and(|state| matches!(*state.0.lock(), ProcessorState::Error))
+}
+
+fn processor_pending_deletion(graph: &Graph, processor_id: &ProcessorUniqueId) -> bool {
+    graph
+        .traversal()
+        .v(processor_id)
+        .first()
+        .is_some_and(|node| node.has::<PendingDeletionComponent>())
+}
+
+fn link_pending_deletion(graph: &Graph, link_id: &LinkUniqueId) -> bool {
+    graph
+        .traversal()
+        .e(link_id)
+        .first()
+        .is_some_and(|link| link.has::<PendingDeletionComponent>())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Once;
+
+    use super::*;
+    use crate::core::descriptors::{PortDescriptor, ProcessorDescriptor};
+    use crate::core::graph_patch::{GraphPatchConfig, GraphPatchLink, GraphPatchProcessor};
+    use crate::core::processors::{PROCESSOR_REGISTRY, ProcessorSpec};
+    use streamlib_idents::{Org, Package, SchemaIdent, SemVer, TypeName};
+    use streamlib_processor_schema::PortSchemaSpec;
+
+    const PASS_TYPE: &str = "PatchPassThrough";
+
+    fn ident(ty: &str) -> SchemaIdent {
+        SchemaIdent::new(
+            Org::new("test").unwrap(),
+            Package::new("graphpatch").unwrap(),
+            TypeName::new(ty).unwrap(),
+            SemVer::new(1, 0, 0),
+        )
+    }
+
+    /// A type with one `in` and one `out` port. Idempotent across tests.
+    fn ensure_pass_type_registered() {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| {
+            let mut descriptor = ProcessorDescriptor::new(ident(PASS_TYPE), "patch pass-through");
+            descriptor
+                .inputs
+                .push(PortDescriptor::iceoryx2("in", "input", PortSchemaSpec::Any));
+            descriptor.outputs.push(PortDescriptor::iceoryx2(
+                "out",
+                "output",
+                PortSchemaSpec::Any,
+            ));
+            PROCESSOR_REGISTRY
+                .register_descriptor_only(descriptor)
+                .expect("register pass-through descriptor");
+        });
+    }
+
+    fn add(alias: &str) -> GraphPatchProcessor {
+        GraphPatchProcessor {
+            alias: alias.to_string(),
+            spec: ProcessorSpec::new(ident(PASS_TYPE), serde_json::json!({})),
+        }
+    }
+
+    fn link(from: &str, to: &str) -> GraphPatchLink {
+        GraphPatchLink {
+            from: OutputLinkPortRef::new(from, "out"),
+            to: InputLinkPortRef::new(to, "in"),
+        }
+    }
+
+    #[test]
+    fn a_patch_stages_every_step_and_resolves_aliases() {
+        ensure_pass_type_registered();
+        let mut graph = Graph::new();
+        let mut staged = StagedPatch::default();
+        staged
+            .stage(
+                &mut graph,
+                GraphPatch {
+                    add_processors: vec![add("a"), add("b")],
+                    connect: vec![link("a", "b")],
+                    ..GraphPatch::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(graph.traversal().v(()).ids().len(), 2);
+        assert_eq!(staged.receipt.links, graph.traversal().e(()).ids());
+        let a = &staged.receipt.processors["a"];
+        let b = &staged.receipt.processors["b"];
+        let wired = graph.traversal().e(()).iter().next().unwrap();
+        assert_eq!(
+            (&wired.source.processor_id, &wired.target.processor_id),
+            (a, b)
+        );
+        // Nothing reaches the compiler until the caller hands it over.
+        assert_eq!(staged.operations.len(), 3);
+        assert_eq!(staged.edits.len(), 3);
+        // Undone as one edit: the link goes first, then both processors.
+        let Some(GraphEdit::Batch { edits }) =
+            GraphEdit::batch(staged.edits.clone()).map(|edit| edit.inverse())
+        else {
+            panic!("a three-step patch records a batch");
+        };
+        assert!(matches!(edits[0], GraphEdit::Disconnect { .. }));
+        assert!(matches!(edits[2], GraphEdit::RemoveProcessor { .. }));
+    }
+
+    #[test]
+    fn a_rejected_step_unwinds_the_staged_ones() {
+        ensure_pass_type_registered();
+        let mut graph = Graph::new();
+        let mut staged = StagedPatch::default();
+        staged
+            .stage(
+                &mut graph,
+                GraphPatch {
+                    add_processors: vec![add("a"), add("b")],
+                    connect: vec![link("a", "b")],
+                    ..GraphPatch::default()
+                },
+            )
+            .unwrap();
+        let a = staged.receipt.processors["a"].clone();
+        let b = staged.receipt.processors["b"].clone();
+        let existing = graph.traversal().e(()).ids();
+
+        // Rewire through a new processor, then close a cycle: the last
+        // step fails and everything before it must be backed out.
+        let mut staged = StagedPatch::default();
+        let error = staged
+            .stage(
+                &mut graph,
+                GraphPatch {
+                    disconnect: existing.clone(),
+                    remove_processors: vec![b.clone()],
+                    add_processors: vec![add("c")],
+                    update_configs: vec![GraphPatchConfig {
+                        processor_id: a.clone(),
+                        config: serde_json::json!({ "gain": 2 }),
+                    }],
+                    connect: vec![link(a.as_str(), "c"), link("c", a.as_str())],
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(error, Error::Topology { .. }), "{error}");
+        staged.unwind(&mut graph);
+
+        let mut ids = graph.traversal().v(()).ids();
+        ids.sort();
+        let mut expected = vec![a.clone(), b.clone()];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(!processor_pending_deletion(&graph, &b));
+        assert_eq!(graph.traversal().e(()).ids(), existing);
+        assert!(!link_pending_deletion(&graph, &existing[0]));
+        let config = graph.traversal().v(&a).first().unwrap().config.clone();
+        assert_eq!(config, Some(serde_json::json!({})));
+    }
+
+    #[test]
+    fn an_unknown_type_is_backed_out_instead_of_left_in_error_state() {
+        let mut graph = Graph::new();
+        let mut staged = StagedPatch::default();
+        let error = staged
+            .stage(
+                &mut graph,
+                GraphPatch {
+                    add_processors: vec![GraphPatchProcessor {
+                        alias: "ghost".into(),
+                        spec: ProcessorSpec::new(
+                            ident("PatchNotRegistered"),
+                            serde_json::json!({}),
+                        ),
+                    }],
+                    ..GraphPatch::default()
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(error, Error::UnknownProcessorType { .. }),
+            "{error}"
+        );
+        staged.unwind(&mut graph);
+        assert!(graph.traversal().v(()).ids().is_empty());
+    }
+}
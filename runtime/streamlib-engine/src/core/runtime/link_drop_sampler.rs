@@ -0,0 +1,71 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Periodic sampler that rolls every link's frame-drop window forward and
+//! publishes [`RuntimeEvent::LinkCongested`] on the rising edge.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::core::compiler::Compiler;
+use crate::core::graph::{GraphEdgeWithComponents, LinkFrameDropComponent};
+use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
+
+use super::RuntimeStatus;
+
+/// How often the sampler reads the link mailbox counters.
+const LINK_DROP_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sample every wired link's [`LinkFrameDropComponent`] at `now` and return a
+/// [`RuntimeEvent::LinkCongested`] for each link that just crossed the
+/// congestion threshold.
+pub(crate) fn sample_link_drop_rates(compiler: &Compiler, now: Instant) -> Vec<RuntimeEvent> {
+    compiler.scope(|graph, _tx| {
+        graph
+            .traversal()
+            .e(())
+            .iter()
+            .filter_map(|link| {
+                let drops = link.get::<LinkFrameDropComponent>()?;
+                let sample = drops.sample(now);
+                sample
+                    .became_congested
+                    .then(|| RuntimeEvent::LinkCongested {
+                        link_id: link.id.to_string(),
+                        from_port: link.from_port().to_string(),
+                        to_port: link.to_port().to_string(),
+                        window_frames_dropped: sample.window_frames_dropped,
+                        window_drop_rate: sample.window_drop_rate,
+                    })
+            })
+            .collect()
+    })
+}
+
+/// Spawn the sampler on the runtime's tokio handle. The task exits on its own
+/// once the runtime leaves the started / paused states; `stop()` aborts it
+/// through the returned handle without waiting for that.
+pub(crate) fn spawn_link_drop_sampler(
+    handle: &tokio::runtime::Handle,
+    compiler: Arc<Compiler>,
+    status: Arc<Mutex<RuntimeStatus>>,
+) -> tokio::task::JoinHandle<()> {
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(LINK_DROP_SAMPLE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match *status.lock() {
+                RuntimeStatus::Started => {}
+                RuntimeStatus::Pausing | RuntimeStatus::Paused => continue,
+                _ => break,
+            }
+            for event in sample_link_drop_rates(&compiler, Instant::now()) {
+                tracing::warn!(event = ?event, "link congested: dropping frames");
+                PUBSUB.publish(topics::RUNTIME_GLOBAL, &Event::RuntimeGlobal(event));
+            }
+        }
+        tracing::debug!("[link_drop_sampler] runtime no longer running, sampler exiting");
+    })
+}
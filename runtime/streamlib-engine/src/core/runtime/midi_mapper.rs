@@ -0,0 +1,250 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The task that drives processor configs from MIDI control changes for
+//! [`Runner::set_midi_mappings`].
+//!
+//! Control changes arrive as custom events on [`MIDI_CONTROL_CHANGE_TOPIC`]
+//! and are coalesced per controller; each tick writes the latest values
+//! into the bound configs through the same path as
+//! [`Runner::update_processor_config`]. Like preset morph ticks, they are
+//! live control rather than edits, so nothing lands in the edit history.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::time::MissedTickBehavior;
+
+use super::runtime::replace_processor_config_impl;
+use super::{Runner, RuntimeStatus};
+use crate::core::Result;
+use crate::core::compiler::Compiler;
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::midi_mapping::{
+    MIDI_CONTROL_CHANGE_TOPIC, MidiControlBinding, MidiControlChange, validate_midi_mappings,
+};
+use crate::core::pubsub::{Event, EventListener, PUBSUB};
+
+/// How often queued control changes are written into configs. A fader
+/// sweep sends hundreds of changes a second; only the latest value per
+/// controller in each tick is applied.
+const MIDI_MAPPER_TICK_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Config updates for one processor: each binding with the controller
+/// value to apply.
+type ProcessorControlUpdates = (ProcessorUniqueId, Vec<(MidiControlBinding, u8)>);
+
+/// The bindings and the control changes waiting for the next tick.
+#[derive(Default)]
+pub(crate) struct MidiMappings {
+    bindings: Vec<MidiControlBinding>,
+    /// Latest change per port / channel / controller, in first-arrival order.
+    pending: Vec<MidiControlChange>,
+}
+
+impl MidiMappings {
+    /// Hold `change` for the next tick, replacing an earlier value from the
+    /// same controller. Changes no binding matches are dropped.
+    fn queue(&mut self, change: MidiControlChange) {
+        if !self.bindings.iter().any(|binding| binding.matches(&change)) {
+            return;
+        }
+        match self.pending.iter_mut().find(|pending| {
+            pending.port == change.port
+                && pending.channel == change.channel
+                && pending.controller == change.controller
+        }) {
+            Some(pending) => pending.value = change.value,
+            None => self.pending.push(change),
+        }
+    }
+
+    /// Drain the queued changes, grouped by the processor each matching
+    /// binding drives.
+    fn take_updates(&mut self) -> Vec<ProcessorControlUpdates> {
+        let mut updates: Vec<ProcessorControlUpdates> = Vec::new();
+        for change in std::mem::take(&mut self.pending) {
+            for binding in self
+                .bindings
+                .iter()
+                .filter(|binding| binding.matches(&change))
+            {
+                let update = (binding.clone(), change.value);
+                match updates
+                    .iter_mut()
+                    .find(|(processor_id, _)| *processor_id == binding.processor_id)
+                {
+                    Some((_, controls)) => controls.push(update),
+                    None => updates.push((binding.processor_id.clone(), vec![update])),
+                }
+            }
+        }
+        updates
+    }
+}
+
+/// Queues control changes from the bus for the mapper task.
+struct MidiControlListener {
+    mappings: Arc<Mutex<MidiMappings>>,
+}
+
+impl EventListener for MidiControlListener {
+    fn on_event(&mut self, event: &Event) -> Result<()> {
+        if let Event::Custom { topic, data } = event
+            && topic == MIDI_CONTROL_CHANGE_TOPIC
+        {
+            match serde_json::from_value::<MidiControlChange>(data.clone()) {
+                Ok(change) => self.mappings.lock().queue(change),
+                Err(e) => tracing::debug!("[midi_mapper] Ignoring malformed control change: {}", e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write each processor's control values into its current config.
+fn apply_control_updates(compiler: &Compiler, updates: Vec<ProcessorControlUpdates>) {
+    for (processor_id, controls) in updates {
+        let config = compiler.scope(|graph, _tx| {
+            graph
+                .traversal()
+                .v(&processor_id)
+                .first()
+                .map(|node| node.config.clone().unwrap_or(Value::Null))
+        });
+        let Some(mut config) = config else {
+            tracing::debug!("[midi_mapper] {} isn't in the graph", processor_id);
+            continue;
+        };
+        let previous = config.clone();
+        for (binding, value) in &controls {
+            binding.apply(&mut config, *value);
+        }
+        if config != previous {
+            replace_processor_config_impl(compiler, &processor_id, config);
+        }
+    }
+}
+
+/// Spawn the mapper on the runtime's tokio handle. It subscribes to
+/// [`MIDI_CONTROL_CHANGE_TOPIC`] for as long as it runs; changes arriving
+/// while the runtime is paused are applied on resume. The task exits once
+/// the runtime leaves the started / paused states, and `stop()` aborts it
+/// through the returned handle — dropping its subscription — so a quick
+/// restart never applies a control change twice.
+pub(crate) fn spawn_midi_mapper(
+    handle: &tokio::runtime::Handle,
+    compiler: Arc<Compiler>,
+    mappings: Arc<Mutex<MidiMappings>>,
+    status: Arc<Mutex<RuntimeStatus>>,
+) -> tokio::task::JoinHandle<()> {
+    handle.spawn(async move {
+        let listener: Arc<Mutex<dyn EventListener>> = Arc::new(Mutex::new(MidiControlListener {
+            mappings: Arc::clone(&mappings),
+        }));
+        PUBSUB.subscribe(MIDI_CONTROL_CHANGE_TOPIC, Arc::clone(&listener));
+
+        let mut interval = tokio::time::interval(MIDI_MAPPER_TICK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            match *status.lock() {
+                RuntimeStatus::Started => {}
+                RuntimeStatus::Pausing | RuntimeStatus::Paused => continue,
+                _ => break,
+            }
+            let updates = mappings.lock().take_updates();
+            apply_control_updates(&compiler, updates);
+        }
+        drop(listener);
+        tracing::debug!("[midi_mapper] runtime no longer running, mapper exiting");
+    })
+}
+
+impl Runner {
+    /// Replace the MIDI control bindings. From then on, control changes
+    /// from `@tatolab/midi/MidiInput` drive the config parameters the
+    /// bindings name, while the runtime is running. Bindings may name
+    /// processors that aren't in the graph yet.
+    pub fn set_midi_mappings(&self, bindings: Vec<MidiControlBinding>) -> Result<()> {
+        validate_midi_mappings(&bindings)?;
+        tracing::info!("[midi_mapper] {} MIDI control binding(s)", bindings.len());
+        let mut mappings = self.midi_mappings.lock();
+        mappings.bindings = bindings;
+        mappings.pending.clear();
+        Ok(())
+    }
+
+    /// The current MIDI control bindings.
+    pub fn midi_mappings(&self) -> Vec<MidiControlBinding> {
+        self.midi_mappings.lock().bindings.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(controller: u8, value: u8) -> MidiControlChange {
+        MidiControlChange {
+            port: None,
+            channel: 1,
+            controller,
+            value,
+        }
+    }
+
+    fn mappings(bindings: Vec<MidiControlBinding>) -> MidiMappings {
+        MidiMappings {
+            bindings,
+            pending: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn queue_keeps_the_latest_value_per_controller_and_drops_unbound_ones() {
+        let mut mappings = mappings(vec![
+            MidiControlBinding::new(7, "mixer", "/gain_db", -60.0, 0.0),
+            MidiControlBinding::new(10, "mixer", "/pan", -1.0, 1.0),
+        ]);
+        mappings.queue(change(7, 10));
+        mappings.queue(change(10, 64));
+        mappings.queue(change(7, 90));
+        mappings.queue(change(11, 127));
+
+        let updates = mappings.take_updates();
+        assert_eq!(updates.len(), 1);
+        let (processor_id, controls) = &updates[0];
+        assert_eq!(*processor_id, ProcessorUniqueId::from("mixer"));
+        let values: Vec<(u8, u8)> = controls
+            .iter()
+            .map(|(binding, value)| (binding.controller, *value))
+            .collect();
+        assert_eq!(values, vec![(7, 90), (10, 64)]);
+        assert!(mappings.take_updates().is_empty());
+    }
+
+    #[test]
+    fn one_controller_can_drive_several_processors() {
+        let mut mappings = mappings(vec![
+            MidiControlBinding::new(1, "blur", "/radius", 0.0, 20.0),
+            MidiControlBinding::new(1, "grade", "/saturation", 1.0, 0.0),
+        ]);
+        mappings.queue(change(1, 127));
+
+        let processors: Vec<ProcessorUniqueId> = mappings
+            .take_updates()
+            .into_iter()
+            .map(|(processor_id, _)| processor_id)
+            .collect();
+        assert_eq!(
+            processors,
+            vec![
+                ProcessorUniqueId::from("blur"),
+                ProcessorUniqueId::from("grade")
+            ]
+        );
+    }
+}
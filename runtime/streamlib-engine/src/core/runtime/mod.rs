@@ -1,17 +1,33 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+mod auto_convert;
+mod chaos_injector;
+#[cfg(target_os = "linux")]
+mod device_monitor;
+mod edit_history;
+mod frame_snapshotter;
+pub(crate) mod federation;
+mod gpu_pool_sampler;
 mod graph_change_listener;
+mod graph_patcher;
 mod install;
+mod link_drop_sampler;
+mod midi_mapper;
 mod module_loader;
 mod operations;
 mod operations_runtime;
+mod port_recorder;
+mod preset_morpher;
+mod processor_scheduler;
 #[allow(clippy::module_inception)]
 mod runtime;
+mod runtime_tasks;
 mod runtime_unique_id;
 mod status;
 mod tap;
 
+pub use auto_convert::AutoConverterRule;
 pub use install::{InstallError, InstallOptions, InstallReport, install};
 pub use streamlib_idents::app_modules::{
     APP_MODULES_DIR_NAME, AddPackageOptions, AddPackageReport, AddPackageSource, AppModulesDir,
@@ -35,6 +51,7 @@ pub use operations::{
     RegisteredProcessorReceipt, ReplaceProcessorFromSource, RuntimeOperations,
     SchemaValidationPosture, SubmittedProcessorSource,
 };
+pub use frame_snapshotter::PortPreview;
 pub use runtime::Runner;
 pub use tap::TapSubscription;
 pub use runtime_unique_id::RuntimeUniqueId;
@@ -415,11 +415,15 @@ pub(super) fn register_manifest_processors(
                     &p.name,
                     p.description.as_deref().unwrap_or(""),
                     p.schema.clone(),
-                    true,
+                    !p.optional,
                 );
-                match &p.delivery_profile {
+                let descriptor = match &p.delivery_profile {
                     Some(profile) => descriptor.with_delivery_profile(profile),
                     None => descriptor,
+                };
+                match &p.format {
+                    Some(format) => descriptor.with_format(format),
+                    None => descriptor,
                 }
             })
             .collect();
@@ -428,12 +432,16 @@ pub(super) fn register_manifest_processors(
             .outputs
             .iter()
             .map(|p| {
-                PortDescriptor::new(
+                let descriptor = PortDescriptor::new(
                     &p.name,
                     p.description.as_deref().unwrap_or(""),
                     p.schema.clone(),
                     true,
-                )
+                );
+                match &p.format {
+                    Some(format) => descriptor.with_format(format),
+                    None => descriptor,
+                }
             })
             .collect();
 
@@ -492,6 +500,7 @@ pub(super) fn register_manifest_processors(
                 }
             }
         };
+        descriptor.execution = execution;
         let execution_config = ExecutionConfig::new(execution);
 
         // Create constructor based on runtime language.
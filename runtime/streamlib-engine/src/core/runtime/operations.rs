@@ -1,12 +1,21 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+use crate::core::chaos::{ChaosFault, ChaosFaultRecord};
 use crate::core::error::Result;
+use crate::core::frame_snapshot::{FrameSnapshot, PreviewOptions};
 use crate::core::graph::{LinkUniqueId, ProcessorUniqueId};
+use crate::core::graph_edit_history::GraphEdit;
+use crate::core::graph_patch::{GraphPatch, GraphPatchReceipt};
+use crate::core::graph_snapshot::GraphSnapshot;
+use crate::core::midi_mapping::MidiControlBinding;
+use crate::core::parameter_automation::ParameterChange;
+use crate::core::preset_morph::PresetMorph;
 use crate::core::processors::ProcessorSpec;
-use crate::core::runtime::TapSubscription;
+use crate::core::runtime::{PortPreview, TapSubscription};
 use crate::core::{InputLinkPortRef, OutputLinkPortRef};
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 use streamlib_idents::ModuleIdent;
 use streamlib_processor_schema::PortSchemaSpec;
@@ -21,11 +30,14 @@ pub use crate::core::schema_agreement::SchemaValidationPosture;
 
 /// Per-wiring-site options for a connect call.
 ///
-/// The default is the engine-wide loose-but-observed schema validation
+/// The default is loose-but-observed schema validation
 /// ([`SchemaValidationPosture::Loose`]): a concrete producer/consumer schema
-/// mismatch warns but the link is still wired. A safety-critical channel selects
-/// [`ConnectOptions::strict`] so the same mismatch instead hard-fails at the
-/// wiring site with [`Error::SchemaIdentMismatch`].
+/// mismatch warns but the link is still wired. [`ConnectOptions::strict`] —
+/// what plain `connect` uses — makes the same mismatch hard-fail at the wiring
+/// site with [`Error::SchemaIdentMismatch`].
+///
+/// Payload encryption is off by default; see
+/// [`with_payload_encryption`](Self::with_payload_encryption).
 ///
 /// [`Error::SchemaIdentMismatch`]: crate::core::error::Error::SchemaIdentMismatch
 #[derive(Debug, Clone, Default)]
@@ -33,14 +45,18 @@ pub use crate::core::schema_agreement::SchemaValidationPosture;
 pub struct ConnectOptions {
     /// Schema-agreement posture applied when the link is wired.
     pub validation: SchemaValidationPosture,
+    /// Seal the link's payloads with ChaCha20-Poly1305 before they enter
+    /// shared memory.
+    pub encrypt_payloads: bool,
 }
 
 impl ConnectOptions {
-    /// Loose-but-observed validation — the engine-wide default; a concrete
-    /// schema mismatch warns, then wires the link anyway.
+    /// Loose-but-observed validation — a concrete schema mismatch warns, then
+    /// wires the link anyway.
     pub fn loose() -> Self {
         Self {
             validation: SchemaValidationPosture::Loose,
+            encrypt_payloads: false,
         }
     }
 
@@ -51,6 +67,7 @@ impl ConnectOptions {
     pub fn strict() -> Self {
         Self {
             validation: SchemaValidationPosture::Strict,
+            encrypt_payloads: false,
         }
     }
 
@@ -60,6 +77,22 @@ impl ConnectOptions {
         self.validation = validation;
         self
     }
+
+    /// Encrypt the link's payloads in shared memory.
+    ///
+    /// Any local process can map an iceoryx2 segment, so a link carrying
+    /// sensitive video opts in here: each frame is sealed with a per-channel
+    /// ChaCha20-Poly1305 key before it is published and opened by the
+    /// subscriber, with the frame header authenticated as associated data.
+    /// Encryption is per channel — the first link out of an output port
+    /// decides it, and an encrypted link onto a channel already opened in
+    /// the clear is rejected. Subprocess endpoints and taps are refused on
+    /// encrypted channels; federated links carry the key over the broker.
+    #[must_use]
+    pub fn with_payload_encryption(mut self, encrypt: bool) -> Self {
+        self.encrypt_payloads = encrypt;
+        self
+    }
 }
 
 /// A processor definition submitted as source text for live registration
@@ -274,6 +307,114 @@ pub trait RuntimeOperations: Send + Sync {
         count: Option<usize>,
     ) -> BoxFuture<'_, Result<TapSubscription>>;
 
+    /// Replace a processor's config. The running instance receives it
+    /// through its config-update path on the next commit. Fails with
+    /// [`Error::ProcessorNotFound`] for an unknown id.
+    ///
+    /// Host-side only, like [`tap_async`](Self::tap_async): implementations
+    /// reachable only across the plugin ABI reject this with
+    /// [`Error::NotSupported`].
+    ///
+    /// [`Error::ProcessorNotFound`]: crate::core::error::Error::ProcessorNotFound
+    /// [`Error::NotSupported`]: crate::core::error::Error::NotSupported
+    fn update_processor_config_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        config: serde_json::Value,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Pause (`paused = true`) or resume one processor without touching the
+    /// rest of the graph. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn set_processor_paused_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        paused: bool,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Save the live graph as a [`GraphSnapshot`] — the same document
+    /// `streamlib-runtime --snapshot` loads. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn save_graph_snapshot_async(&self) -> BoxFuture<'_, Result<GraphSnapshot>>;
+
+    /// Undo the newest graph edit, returning it, or `None` when there is
+    /// nothing to undo. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>>;
+
+    /// Re-apply the most recently undone graph edit, returning it, or `None`
+    /// when there is nothing to redo. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>>;
+
+    /// Apply a [`GraphPatch`] all or nothing: every step is validated and
+    /// staged under one graph lock, and either the whole patch applies —
+    /// publishing a single `GraphDidChange` — or the graph is left as it
+    /// was. Returns the ids minted for the patch's aliases and links.
+    /// Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn apply_graph_patch_async(
+        &self,
+        patch: GraphPatch,
+    ) -> BoxFuture<'_, Result<GraphPatchReceipt>>;
+
+    /// Start morphing processor configs as [`PresetMorph`] describes.
+    /// Resolves once the morph is running, not when it finishes.
+    /// Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn morph_processor_configs_async(&self, morph: PresetMorph) -> BoxFuture<'_, Result<()>>;
+
+    /// Replace the MIDI control bindings. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn set_midi_mappings_async(
+        &self,
+        bindings: Vec<MidiControlBinding>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// The current MIDI control bindings. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>>;
+
+    /// Schedule one parameter change on a running processor. Resolves once
+    /// it is queued, not when it is delivered. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn set_parameter_async(&self, change: ParameterChange) -> BoxFuture<'_, Result<()>>;
+
+    /// Inject a [`ChaosFault`] now, returning its log entry. Fails unless
+    /// the runner is in chaos mode. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn inject_chaos_fault_async(
+        &self,
+        fault: ChaosFault,
+    ) -> BoxFuture<'_, Result<ChaosFaultRecord>>;
+
+    /// The chaos fault log, oldest first; empty outside chaos mode.
+    /// Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn chaos_fault_log_async(&self) -> BoxFuture<'_, Result<Vec<ChaosFaultRecord>>>;
+
+    /// Write the next frame `processor_id` sends on its `port` output to
+    /// the PNG file at `path`. Takes the frame through the channel's tap
+    /// slot, so the port must be wired and untapped. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn snapshot_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        port: String,
+        path: PathBuf,
+    ) -> BoxFuture<'_, Result<FrameSnapshot>>;
+
+    /// Open a thumbnail preview of the frames `processor_id` sends on its
+    /// `port` output. Holds the channel's tap slot while it lives, so the
+    /// port must be wired and untapped. Host-side only; see
+    /// [`update_processor_config_async`](Self::update_processor_config_async).
+    fn preview_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        port: String,
+        options: PreviewOptions,
+    ) -> BoxFuture<'_, Result<PortPreview>>;
+
     // =========================================================================
     // Sync Methods (convenience wrappers - NOT safe from tokio tasks)
     // =========================================================================
@@ -296,6 +437,9 @@ pub trait RuntimeOperations: Send + Sync {
 
     /// Connect two ports. Returns the link ID.
     ///
+    /// Fails with `Error::Topology` on a concrete producer/consumer schema
+    /// mismatch or a link that would close a deadlocking cycle.
+    ///
     /// Note: No `#[must_use]` - callers may intentionally ignore the ID in fire-and-forget scenarios.
     ///
     /// This is a blocking wrapper around [`connect_async`]. Do not call
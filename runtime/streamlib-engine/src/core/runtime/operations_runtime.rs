@@ -1,26 +1,40 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::Runner;
+use super::auto_convert::{
+    AutoConverterRule, PortEnd, find_auto_converter, registered_auto_converters,
+    resolve_node_port_format,
+};
 use super::operations::{
     BoxFuture, ConnectOptions, RegisterProcessorReceipt, ReplaceProcessorFromSource,
     RuntimeOperations, SubmittedProcessorSource,
 };
 use super::runtime::TokioRuntimeVariant;
+use crate::core::chaos::{ChaosFault, ChaosFaultRecord};
 use crate::core::compiler::{Compiler, PendingOperation};
 use crate::core::graph::{
-    GraphEdgeWithComponents, GraphNodeWithComponents, LinkUniqueId, PendingDeletionComponent,
-    ProcessorUniqueId, StateComponent,
+    AutoConverterComponent, Graph, GraphEdgeWithComponents, GraphNodeWithComponents,
+    LinkEncryptionComponent, LinkUniqueId, PendingDeletionComponent, ProcessorUniqueId,
+    StateComponent, TopologyAnalyzer,
 };
 use crate::core::embedded_schemas::resolve_node_port_schema;
+use crate::core::frame_snapshot::{FrameSnapshot, PreviewOptions};
+use crate::core::graph_edit_history::{GraphEdit, GraphEditLink};
+use crate::core::graph_patch::{GraphPatch, GraphPatchReceipt};
+use crate::core::graph_snapshot::GraphSnapshot;
+use crate::core::midi_mapping::MidiControlBinding;
+use crate::core::parameter_automation::ParameterChange;
+use crate::core::preset_morph::PresetMorph;
 use crate::core::processors::{ProcessorSpec, ProcessorState};
 use crate::core::pubsub::{Event, PUBSUB, RuntimeEvent, topics};
-use crate::core::schema_agreement::{
-    ConnectSchemaContext, SchemaValidationPosture, enforce_connect_schema_agreement,
+use crate::core::schema_agreement::{ConnectSchemaContext, enforce_connect_schema_agreement};
+use crate::core::{
+    Error, InputLinkPortRef, OutputLinkPortRef, PortDirection, Result, TopologyDiagnostic,
 };
-use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, PortDirection, Result};
 use streamlib_idents::ChannelName;
 
 // =============================================================================
@@ -37,7 +51,7 @@ use streamlib_idents::ChannelName;
 /// reason while the failed node is still surfaced in the graph for
 /// observability. `None` (type available after the lazy load, or no package
 /// provided it) leaves the existing behavior unchanged.
-async fn add_processor_impl(
+pub(super) async fn add_processor_impl(
     compiler: Arc<Compiler>,
     spec: ProcessorSpec,
     lazy_error: Option<Error>,
@@ -113,7 +127,7 @@ async fn add_processor_impl(
 }
 
 /// Core implementation for remove_processor - takes owned Arcs for 'static lifetime.
-async fn remove_processor_impl(
+pub(super) async fn remove_processor_impl(
     compiler: Arc<Compiler>,
     processor_id: ProcessorUniqueId,
 ) -> Result<()> {
@@ -131,6 +145,8 @@ async fn remove_processor_impl(
         Ok(())
     })?;
 
+    crate::core::graph::clear_reported_components(processor_id.as_str());
+
     PUBSUB.publish(
         topics::RUNTIME_GLOBAL,
         &Event::RuntimeGlobal(RuntimeEvent::RuntimeWillRemoveProcessor {
@@ -155,24 +171,31 @@ async fn remove_processor_impl(
 
 /// Core implementation for connect - takes owned Arcs for 'static lifetime.
 ///
-/// `validation` selects the schema-agreement posture for this wiring site.
-/// [`connect`](Runner::connect) / [`connect_async`](RuntimeOperations::connect_async)
-/// pass [`SchemaValidationPosture::Loose`] (warn-but-wire); the
-/// [`connect_with`](Runner::connect_with) opt-in passes the caller's
-/// [`ConnectOptions`] posture, so a safety-critical channel selects
-/// [`Strict`][SchemaValidationPosture::Strict] to hard-fail a concrete
-/// producer/consumer schema mismatch with [`Error::SchemaIdentMismatch`]
-/// instead of only warning.
+/// `options.validation` selects the schema-agreement posture for this wiring
+/// site. [`connect`](Runner::connect) /
+/// [`connect_async`](RuntimeOperations::connect_async) pass
+/// [`ConnectOptions::strict`] and report the rejection as
+/// [`Error::Topology`]; [`connect_with`](Runner::connect_with) passes the
+/// caller's [`ConnectOptions`], so [`strict`][ConnectOptions::strict]
+/// hard-fails a concrete producer/consumer schema mismatch with
+/// [`Error::SchemaIdentMismatch`] and [`loose`][ConnectOptions::loose] only
+/// warns. `options.encrypt_payloads` tags the new link
+/// with [`LinkEncryptionComponent`] for the compiler to honour.
 #[tracing::instrument(
     name = "runtime.connect",
-    skip(compiler),
-    fields(from = %from, to = %to, validation = ?validation),
+    skip(compiler, options),
+    fields(
+        from = %from,
+        to = %to,
+        validation = ?options.validation,
+        encrypt_payloads = options.encrypt_payloads,
+    ),
 )]
 async fn connect_impl(
     compiler: Arc<Compiler>,
     from: OutputLinkPortRef,
     to: InputLinkPortRef,
-    validation: SchemaValidationPosture,
+    options: ConnectOptions,
 ) -> Result<LinkUniqueId> {
     let from_processor = from.processor_id.clone();
     let from_port = from.port_name.clone();
@@ -189,100 +212,12 @@ async fn connect_impl(
         }),
     );
 
-    let (link_id, channel) = compiler.scope(|graph, tx| -> Result<(LinkUniqueId, ChannelName)> {
-        // Validate endpoints + ports FIRST — before the channel-name
-        // derivation — so a missing processor/port reads as the typed
-        // ProcessorNotFound / ProcessorPortNotFound and never gets masked by an
-        // InvalidLink from the wire-name grammar. The `add_e` call still checks
-        // defensively; this pre-validation is what gets the typed error out.
-        // Validate source processor + output port.
-        {
-            let from_node = graph
-                .traversal()
-                .v(&from.processor_id)
-                .first()
-                .ok_or_else(|| Error::ProcessorNotFound(from.processor_id.to_string()))?;
-            if !from_node.has_output(&from.port_name) {
-                return Err(Error::ProcessorPortNotFound {
-                    processor_id: from.processor_id.to_string(),
-                    port_name: from.port_name.clone(),
-                    direction: PortDirection::Output,
-                });
-            }
-        }
-        // Validate target processor + input port.
-        {
-            let to_node = graph
-                .traversal()
-                .v(&to.processor_id)
-                .first()
-                .ok_or_else(|| Error::ProcessorNotFound(to.processor_id.to_string()))?;
-            if !to_node.has_input(&to.port_name) {
-                return Err(Error::ProcessorPortNotFound {
-                    processor_id: to.processor_id.to_string(),
-                    port_name: to.port_name.clone(),
-                    direction: PortDirection::Input,
-                });
-            }
-        }
-
-        // Schema-agreement check at the wiring site: resolve the producer's
-        // output schema and the consumer's input schema from the registry and
-        // compare. A wildcard (`any`) on either side never mismatches; two
-        // concrete-but-unequal schemas warn (loose) or hard-fail (strict).
-        // Runs before `add_e` so a strict rejection rolls the pending link
-        // back rather than committing a mismatched edge. Endpoints are already
-        // validated to exist above.
-        {
-            let producer_schema = resolve_node_port_schema(
-                graph,
-                &from.processor_id,
-                &from.port_name,
-                PortDirection::Output,
-            );
-            let consumer_schema = resolve_node_port_schema(
-                graph,
-                &to.processor_id,
-                &to.port_name,
-                PortDirection::Input,
-            );
-            enforce_connect_schema_agreement(
-                &producer_schema,
-                &consumer_schema,
-                validation,
-                ConnectSchemaContext {
-                    from_processor: from.processor_id.as_str(),
-                    from_port: &from.port_name,
-                    to_processor: to.processor_id.as_str(),
-                    to_port: &to.port_name,
-                },
-            )?;
-        }
-
-        // The one channel this link's source output port publishes to — keyed
-        // on the SOURCE only (`{src_processor}/{src_output}`), so every link
-        // from this output port shares one channel / one publisher / N
-        // subscribers (D1, #1419). Endpoints are validated above, so a grammar
-        // failure here is a genuinely-illegal source PORT name (author error),
-        // surfaced as InvalidLink. The processor id is lowercased inside
-        // `source_channel_name`; underscore is legal and rides through. Deriving
-        // inside the transaction means an illegal port name rolls the pending
-        // link back rather than committing a half-built edge.
-        let channel = streamlib_idents::source_channel_name(
-            from.processor_id.as_str(),
-            &from.port_name,
-        )
-        .map_err(|source| Error::InvalidLink(source.to_string()))?;
-
-        let link_id = graph
-            .traversal_mut()
-            .add_e(from, to)
-            .inspect(|link| tx.log(PendingOperation::AddLink(link.id.clone())))
-            .first()
-            .map(|link| link.id.clone())
-            .ok_or_else(|| Error::GraphError("failed to create link after validation".into()))?;
-        Ok((link_id, channel))
-    })?;
+    let (link_id, channel) =
+        compiler.scope(|graph, tx| -> Result<(LinkUniqueId, ChannelName)> {
+            let (link_id, channel) = add_validated_link(graph, from, to, &options)?;
+            tx.log(PendingOperation::AddLink(link_id.clone()));
+            Ok((link_id, channel))
+        })?;
 
     tracing::debug!(
         link_id = %link_id,
@@ -307,8 +242,212 @@ async fn connect_impl(
     Ok(link_id)
 }
 
+/// [`RuntimeOperations::connect`]'s strict schema rejection, reported as a
+/// topology diagnostic beside the cycle check.
+fn schema_mismatch_as_topology(err: Error) -> Error {
+    match err {
+        Error::SchemaIdentMismatch {
+            from_processor,
+            from_port,
+            to_processor,
+            to_port,
+            producer_schema,
+            consumer_schema,
+        } => Error::Topology {
+            diagnostics: vec![TopologyDiagnostic::SchemaMismatch {
+                from_processor,
+                from_port,
+                to_processor,
+                to_port,
+                producer_schema,
+                consumer_schema,
+            }],
+        },
+        other => other,
+    }
+}
+
+/// Validate `from -> to` against `graph` and add the link, returning it
+/// and the channel it publishes on. Does not queue the link for the
+/// compiler — the caller logs [`PendingOperation::AddLink`] once the link
+/// should be wired — so a graph patch can back it out untouched.
+pub(super) fn add_validated_link(
+    graph: &mut Graph,
+    from: OutputLinkPortRef,
+    to: InputLinkPortRef,
+    options: &ConnectOptions,
+) -> Result<(LinkUniqueId, ChannelName)> {
+    // Validate endpoints + ports FIRST — before the channel-name
+    // derivation — so a missing processor/port reads as the typed
+    // ProcessorNotFound / ProcessorPortNotFound and never gets masked by an
+    // InvalidLink from the wire-name grammar. The `add_e` call still checks
+    // defensively; this pre-validation is what gets the typed error out.
+    // Validate source processor + output port.
+    {
+        let from_node = graph
+            .traversal()
+            .v(&from.processor_id)
+            .first()
+            .ok_or_else(|| Error::ProcessorNotFound(from.processor_id.to_string()))?;
+        if !from_node.has_output(&from.port_name) {
+            return Err(Error::ProcessorPortNotFound {
+                processor_id: from.processor_id.to_string(),
+                port_name: from.port_name.clone(),
+                direction: PortDirection::Output,
+            });
+        }
+    }
+    // Validate target processor + input port.
+    {
+        let to_node = graph
+            .traversal()
+            .v(&to.processor_id)
+            .first()
+            .ok_or_else(|| Error::ProcessorNotFound(to.processor_id.to_string()))?;
+        if !to_node.has_input(&to.port_name) {
+            return Err(Error::ProcessorPortNotFound {
+                processor_id: to.processor_id.to_string(),
+                port_name: to.port_name.clone(),
+                direction: PortDirection::Input,
+            });
+        }
+    }
+
+    // Schema-agreement check at the wiring site: resolve the producer's
+    // output schema and the consumer's input schema from the registry and
+    // compare. A wildcard (`any`) on either side never mismatches; two
+    // concrete-but-unequal schemas warn (loose) or hard-fail (strict).
+    // Runs before `add_e` so a strict rejection rolls the pending link
+    // back rather than committing a mismatched edge. Endpoints are already
+    // validated to exist above.
+    {
+        let producer_schema = resolve_node_port_schema(
+            graph,
+            &from.processor_id,
+            &from.port_name,
+            PortDirection::Output,
+        );
+        let consumer_schema =
+            resolve_node_port_schema(graph, &to.processor_id, &to.port_name, PortDirection::Input);
+        enforce_connect_schema_agreement(
+            &producer_schema,
+            &consumer_schema,
+            options.validation,
+            ConnectSchemaContext {
+                from_processor: from.processor_id.as_str(),
+                from_port: &from.port_name,
+                to_processor: to.processor_id.as_str(),
+                to_port: &to.port_name,
+            },
+        )?;
+    }
+
+    // Reject a link that would close a deadlocking cycle: Reactive
+    // processors on a loop of required inputs each wait on the other's
+    // output and never run. Feedback through an optional input or a
+    // self-driven processor is allowed. Checked before `add_e` so the
+    // rejection leaves the graph untouched.
+    if let Some(cycle) = TopologyAnalyzer::new(graph).cycle_closed_by(&from, &to) {
+        return Err(Error::Topology {
+            diagnostics: vec![cycle],
+        });
+    }
+
+    // The one channel this link's source output port publishes to — keyed
+    // on the SOURCE only (`{src_processor}/{src_output}`), so every link
+    // from this output port shares one channel / one publisher / N
+    // subscribers (D1, #1419). Endpoints are validated above, so a grammar
+    // failure here is a genuinely-illegal source PORT name (author error),
+    // surfaced as InvalidLink. The processor id is lowercased inside
+    // `source_channel_name`; underscore is legal and rides through. Deriving
+    // inside the transaction means an illegal port name rolls the pending
+    // link back rather than committing a half-built edge.
+    let channel =
+        streamlib_idents::source_channel_name(from.processor_id.as_str(), &from.port_name)
+            .map_err(|source| Error::InvalidLink(source.to_string()))?;
+
+    let link_id = graph
+        .traversal_mut()
+        .add_e(from, to)
+        .first()
+        .map(|link| link.id.clone())
+        .ok_or_else(|| Error::GraphError("failed to create link after validation".into()))?;
+    if options.encrypt_payloads
+        && let Some(link) = graph.traversal_mut().e(&link_id).first_mut()
+    {
+        link.insert(LinkEncryptionComponent);
+    }
+    Ok((link_id, channel))
+}
+
+/// Core implementation for a connect that may go through an auto-inserted
+/// converter - takes owned Arcs for 'static lifetime.
+///
+/// With no `converter` this is [`connect_impl`]. Otherwise the rule's
+/// converter processor is added, tagged with [`AutoConverterComponent`], and
+/// wired `from -> converter -> to`; if either link is rejected the converter
+/// is removed again and the error returned. Returns the link feeding `to`.
+pub(super) async fn connect_through_converter_impl(
+    compiler: Arc<Compiler>,
+    converter: Option<AutoConverterRule>,
+    from: OutputLinkPortRef,
+    to: InputLinkPortRef,
+    options: ConnectOptions,
+) -> Result<LinkUniqueId> {
+    let Some(rule) = converter else {
+        return connect_impl(compiler, from, to, options).await;
+    };
+
+    let converter_id =
+        add_processor_impl(Arc::clone(&compiler), rule.converter_spec(), None).await?;
+    compiler.scope(|graph, _tx| {
+        if let Some(node) = graph.traversal_mut().v(&converter_id).first_mut() {
+            node.insert(AutoConverterComponent {
+                upstream: from.clone(),
+                downstream: to.clone(),
+            });
+        }
+    });
+    tracing::info!(
+        "[auto_convert] Inserted {} ({}) between {} and {}",
+        rule.converter_type,
+        converter_id,
+        from,
+        to
+    );
+
+    let wired = async {
+        connect_impl(
+            Arc::clone(&compiler),
+            from,
+            InputLinkPortRef::new(converter_id.clone(), rule.converter_input.clone()),
+            options.clone(),
+        )
+        .await?;
+        connect_impl(
+            Arc::clone(&compiler),
+            OutputLinkPortRef::new(converter_id.clone(), rule.converter_output.clone()),
+            to,
+            options,
+        )
+        .await
+    }
+    .await;
+
+    if wired.is_err()
+        && let Err(e) = remove_processor_impl(Arc::clone(&compiler), converter_id.clone()).await
+    {
+        tracing::warn!(
+            "[auto_convert] Failed to remove converter {} after wiring failed: {}",
+            converter_id,
+            e
+        );
+    }
+    wired
+}
+
 /// Core implementation for disconnect - takes owned Arcs for 'static lifetime.
-async fn disconnect_impl(compiler: Arc<Compiler>, link_id: LinkUniqueId) -> Result<()> {
+pub(super) async fn disconnect_impl(compiler: Arc<Compiler>, link_id: LinkUniqueId) -> Result<()> {
     let link_info = compiler.scope(|graph, tx| {
         let (from_value, to_value) = graph
             .traversal()
@@ -376,13 +515,27 @@ impl RuntimeOperations for Runner {
             let lazy_error = self
                 .lazily_load_provider_for_processor_type(&spec.name)
                 .await;
-            add_processor_impl(compiler, spec, lazy_error).await
+            let processor_id = add_processor_impl(compiler, spec.clone(), lazy_error).await?;
+            self.record_graph_edit(GraphEdit::AddProcessor {
+                processor_id: processor_id.clone(),
+                spec,
+                links: Vec::new(),
+            });
+            Ok(processor_id)
         })
     }
 
     fn remove_processor_async(&self, processor_id: ProcessorUniqueId) -> BoxFuture<'_, Result<()>> {
         let compiler = Arc::clone(&self.compiler);
-        Box::pin(remove_processor_impl(compiler, processor_id))
+        Box::pin(async move {
+            let edit = self.processor_removal_edit(&processor_id);
+            remove_processor_impl(compiler, processor_id.clone()).await?;
+            self.clear_processor_schedules(&processor_id);
+            if let Some(edit) = edit {
+                self.record_graph_edit(edit);
+            }
+            Ok(())
+        })
     }
 
     fn connect_async(
@@ -390,12 +543,20 @@ impl RuntimeOperations for Runner {
         from: OutputLinkPortRef,
         to: InputLinkPortRef,
     ) -> BoxFuture<'_, Result<LinkUniqueId>> {
-        self.connect_with_async(from, to, ConnectOptions::loose())
+        let connecting = self.connect_with_async(from, to, ConnectOptions::strict());
+        Box::pin(async move { connecting.await.map_err(schema_mismatch_as_topology) })
     }
 
     fn disconnect_async(&self, link_id: LinkUniqueId) -> BoxFuture<'_, Result<()>> {
         let compiler = Arc::clone(&self.compiler);
-        Box::pin(disconnect_impl(compiler, link_id))
+        Box::pin(async move {
+            let edit = self.disconnect_edit(&link_id);
+            disconnect_impl(compiler, link_id).await?;
+            if let Some(edit) = edit {
+                self.record_graph_edit(edit);
+            }
+            Ok(())
+        })
     }
 
     fn to_json_async(&self) -> BoxFuture<'_, Result<serde_json::Value>> {
@@ -431,6 +592,18 @@ impl RuntimeOperations for Runner {
                 let (source_proc_id, source_port) =
                     crate::core::compiler::compiler_ops::find_channel_source_port(graph, &channel)
                         .ok_or_else(|| Error::TapChannelNotFound(channel.clone()))?;
+                if crate::core::compiler::compiler_ops::channel_cipher(
+                    graph,
+                    &source_proc_id,
+                    &source_port,
+                )
+                .is_some()
+                {
+                    return Err(Error::NotSupported(format!(
+                        "channel '{}' is encrypted; taps cannot open its sealed payloads",
+                        channel
+                    )));
+                }
                 let sizing = crate::core::compiler::compiler_ops::resolve_channel_sizing(
                     graph,
                     &source_proc_id,
@@ -465,6 +638,103 @@ impl RuntimeOperations for Runner {
         })
     }
 
+    fn update_processor_config_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        config: serde_json::Value,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            let exists = self
+                .compiler
+                .scope(|graph, _tx| graph.traversal().v(&processor_id).exists());
+            if !exists {
+                return Err(Error::ProcessorNotFound(processor_id.to_string()));
+            }
+            Runner::update_processor_config(self, &processor_id, config)
+        })
+    }
+
+    fn set_processor_paused_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        paused: bool,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            if paused {
+                self.pause_processor(&processor_id)
+            } else {
+                self.resume_processor(&processor_id)
+            }
+        })
+    }
+
+    fn save_graph_snapshot_async(&self) -> BoxFuture<'_, Result<GraphSnapshot>> {
+        Box::pin(async move { self.save_graph_snapshot() })
+    }
+
+    fn undo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+        Box::pin(self.undo_graph_edit())
+    }
+
+    fn redo_graph_edit_async(&self) -> BoxFuture<'_, Result<Option<GraphEdit>>> {
+        Box::pin(self.redo_graph_edit())
+    }
+
+    fn apply_graph_patch_async(
+        &self,
+        patch: GraphPatch,
+    ) -> BoxFuture<'_, Result<GraphPatchReceipt>> {
+        Box::pin(self.apply_graph_patch(patch))
+    }
+
+    fn morph_processor_configs_async(&self, morph: PresetMorph) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { self.morph_processor_configs(morph) })
+    }
+
+    fn set_midi_mappings_async(
+        &self,
+        bindings: Vec<MidiControlBinding>,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { self.set_midi_mappings(bindings) })
+    }
+
+    fn midi_mappings_async(&self) -> BoxFuture<'_, Result<Vec<MidiControlBinding>>> {
+        Box::pin(async move { Ok(self.midi_mappings()) })
+    }
+
+    fn set_parameter_async(&self, change: ParameterChange) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { self.set_parameter(change) })
+    }
+
+    fn inject_chaos_fault_async(
+        &self,
+        fault: ChaosFault,
+    ) -> BoxFuture<'_, Result<ChaosFaultRecord>> {
+        Box::pin(self.inject_chaos_fault(fault))
+    }
+
+    fn chaos_fault_log_async(&self) -> BoxFuture<'_, Result<Vec<ChaosFaultRecord>>> {
+        Box::pin(async move { Ok(self.chaos_fault_log()) })
+    }
+
+    fn snapshot_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        port: String,
+        path: PathBuf,
+    ) -> BoxFuture<'_, Result<FrameSnapshot>> {
+        Box::pin(async move { self.snapshot_port(&processor_id, &port, path).await })
+    }
+
+    fn preview_async(
+        &self,
+        processor_id: ProcessorUniqueId,
+        port: String,
+        options: PreviewOptions,
+    ) -> BoxFuture<'_, Result<crate::core::runtime::PortPreview>> {
+        Box::pin(async move { self.preview_port(&processor_id, &port, options).await })
+    }
+
     // =========================================================================
     // Sync Methods (variant-aware blocking strategy)
     // =========================================================================
@@ -482,13 +752,21 @@ impl RuntimeOperations for Runner {
                 let lazy_error =
                     self.lazily_load_provider_for_processor_type_blocking(&spec.name);
                 let compiler = Arc::clone(&self.compiler);
+                let recorded_spec = spec.clone();
                 let (tx, rx) = std::sync::mpsc::channel();
                 handle.spawn(async move {
                     let result = add_processor_impl(compiler, spec, lazy_error).await;
                     let _ = tx.send(result);
                 });
-                rx.recv()
-                    .map_err(|_| Error::Runtime("Task channel closed".into()))?
+                let processor_id = rx
+                    .recv()
+                    .map_err(|_| Error::Runtime("Task channel closed".into()))??;
+                self.record_graph_edit(GraphEdit::AddProcessor {
+                    processor_id: processor_id.clone(),
+                    spec: recorded_spec,
+                    links: Vec::new(),
+                });
+                Ok(processor_id)
             }
         }
     }
@@ -500,20 +778,27 @@ impl RuntimeOperations for Runner {
             }
             TokioRuntimeVariant::ExternalTokioHandle(handle) => {
                 let compiler = Arc::clone(&self.compiler);
-                let processor_id = processor_id.clone();
+                let edit = self.processor_removal_edit(processor_id);
+                let removed_id = processor_id.clone();
                 let (tx, rx) = std::sync::mpsc::channel();
                 handle.spawn(async move {
-                    let result = remove_processor_impl(compiler, processor_id).await;
+                    let result = remove_processor_impl(compiler, removed_id).await;
                     let _ = tx.send(result);
                 });
                 rx.recv()
-                    .map_err(|_| Error::Runtime("Task channel closed".into()))?
+                    .map_err(|_| Error::Runtime("Task channel closed".into()))??;
+                self.clear_processor_schedules(processor_id);
+                if let Some(edit) = edit {
+                    self.record_graph_edit(edit);
+                }
+                Ok(())
             }
         }
     }
 
     fn connect(&self, from: OutputLinkPortRef, to: InputLinkPortRef) -> Result<LinkUniqueId> {
-        self.connect_with(from, to, ConnectOptions::loose())
+        self.connect_with(from, to, ConnectOptions::strict())
+            .map_err(schema_mismatch_as_topology)
     }
 
     fn disconnect(&self, link_id: &LinkUniqueId) -> Result<()> {
@@ -523,6 +808,7 @@ impl RuntimeOperations for Runner {
             }
             TokioRuntimeVariant::ExternalTokioHandle(handle) => {
                 let compiler = Arc::clone(&self.compiler);
+                let edit = self.disconnect_edit(link_id);
                 let link_id = link_id.clone();
                 let (tx, rx) = std::sync::mpsc::channel();
                 handle.spawn(async move {
@@ -530,7 +816,11 @@ impl RuntimeOperations for Runner {
                     let _ = tx.send(result);
                 });
                 rx.recv()
-                    .map_err(|_| Error::Runtime("Task channel closed".into()))?
+                    .map_err(|_| Error::Runtime("Task channel closed".into()))??;
+                if let Some(edit) = edit {
+                    self.record_graph_edit(edit);
+                }
+                Ok(())
             }
         }
     }
@@ -545,38 +835,47 @@ impl RuntimeOperations for Runner {
 }
 
 impl Runner {
-    /// Connect two ports under explicit [`ConnectOptions`] — the strict
-    /// schema-validation opt-in for a safety-critical wiring site.
+    /// Connect two ports under explicit [`ConnectOptions`].
     ///
-    /// [`connect`](RuntimeOperations::connect) is the loose-but-observed default
-    /// (a concrete producer/consumer schema mismatch warns, then wires the link
-    /// anyway); this threads the caller's posture into the same wiring path, so
-    /// under [`ConnectOptions::strict`] the mismatch instead hard-fails with
-    /// [`Error::SchemaIdentMismatch`] and the link is not wired.
+    /// [`connect`](RuntimeOperations::connect) rejects a concrete
+    /// producer/consumer schema mismatch with [`Error::Topology`]; this threads
+    /// the caller's posture into the same wiring path, so under
+    /// [`ConnectOptions::strict`] the mismatch hard-fails with
+    /// [`Error::SchemaIdentMismatch`] and under [`ConnectOptions::loose`] it
+    /// warns, then wires the link anyway.
     pub fn connect_with(
         &self,
         from: OutputLinkPortRef,
         to: InputLinkPortRef,
         options: ConnectOptions,
     ) -> Result<LinkUniqueId> {
-        match &self.tokio_runtime_variant {
-            TokioRuntimeVariant::OwnedTokioRuntime(rt) => rt.block_on(connect_impl(
-                Arc::clone(&self.compiler),
-                from,
-                to,
-                options.validation,
-            )),
+        let converter = self.auto_converter_for(&from, &to);
+        let (recorded_from, recorded_to) = (from.clone(), to.clone());
+        let link_id = match &self.tokio_runtime_variant {
+            TokioRuntimeVariant::OwnedTokioRuntime(rt) => {
+                rt.block_on(connect_through_converter_impl(
+                    Arc::clone(&self.compiler),
+                    converter,
+                    from,
+                    to,
+                    options,
+                ))
+            }
             TokioRuntimeVariant::ExternalTokioHandle(handle) => {
                 let compiler = Arc::clone(&self.compiler);
                 let (tx, rx) = std::sync::mpsc::channel();
                 handle.spawn(async move {
-                    let result = connect_impl(compiler, from, to, options.validation).await;
+                    let result =
+                        connect_through_converter_impl(compiler, converter, from, to, options)
+                            .await;
                     let _ = tx.send(result);
                 });
                 rx.recv()
                     .map_err(|_| Error::Runtime("Task channel closed".into()))?
             }
-        }
+        }?;
+        self.record_connect(link_id.clone(), recorded_from, recorded_to);
+        Ok(link_id)
     }
 
     /// Async form of [`connect_with`](Self::connect_with) — safe from any
@@ -588,7 +887,69 @@ impl Runner {
         options: ConnectOptions,
     ) -> BoxFuture<'_, Result<LinkUniqueId>> {
         let compiler = Arc::clone(&self.compiler);
-        Box::pin(connect_impl(compiler, from, to, options.validation))
+        let converter = self.auto_converter_for(&from, &to);
+        Box::pin(async move {
+            let link_id = connect_through_converter_impl(
+                compiler,
+                converter,
+                from.clone(),
+                to.clone(),
+                options,
+            )
+            .await?;
+            self.record_connect(link_id.clone(), from, to);
+            Ok(link_id)
+        })
+    }
+
+    /// Record a connect as asked for — `from` to `to`, even when an
+    /// auto-inserted converter sits between them.
+    fn record_connect(&self, link_id: LinkUniqueId, from: OutputLinkPortRef, to: InputLinkPortRef) {
+        self.record_graph_edit(GraphEdit::Connect {
+            link: GraphEditLink { link_id, from, to },
+        });
+    }
+
+    /// The converter [`Self::set_auto_convert`] would insert between `from`
+    /// and `to`, if insertion is on and a registered rule with a registered
+    /// converter covers their port schemas and formats. Unknown endpoints
+    /// resolve to no converter and surface their typed error from the
+    /// connect itself.
+    pub(super) fn auto_converter_for(
+        &self,
+        from: &OutputLinkPortRef,
+        to: &InputLinkPortRef,
+    ) -> Option<AutoConverterRule> {
+        if !self.auto_convert() {
+            return None;
+        }
+        let ((producer_schema, producer_format), (consumer_schema, consumer_format)) =
+            self.compiler.scope(|graph, _tx| {
+                let (out, input) = (PortDirection::Output, PortDirection::Input);
+                (
+                    (
+                        resolve_node_port_schema(graph, &from.processor_id, &from.port_name, out),
+                        resolve_node_port_format(graph, &from.processor_id, &from.port_name, out),
+                    ),
+                    (
+                        resolve_node_port_schema(graph, &to.processor_id, &to.port_name, input),
+                        resolve_node_port_format(graph, &to.processor_id, &to.port_name, input),
+                    ),
+                )
+            });
+        let rules = registered_auto_converters(&self.auto_converters.lock());
+        find_auto_converter(
+            &rules,
+            PortEnd {
+                schema: &producer_schema,
+                format: producer_format.as_deref(),
+            },
+            PortEnd {
+                schema: &consumer_schema,
+                format: consumer_format.as_deref(),
+            },
+        )
+        .cloned()
     }
 }
 
@@ -634,8 +995,8 @@ mod connect_schema_agreement_tests {
     //! on `enforce_connect_schema_agreement` alone do NOT catch that regression:
     //! they never exercise the wiring site.
     //!
-    //! [`Loose`]: SchemaValidationPosture::Loose
-    //! [`Strict`]: SchemaValidationPosture::Strict
+    //! [`Loose`]: crate::core::schema_agreement::SchemaValidationPosture::Loose
+    //! [`Strict`]: crate::core::schema_agreement::SchemaValidationPosture::Strict
 
     use std::sync::{Arc, Mutex, Once};
 
@@ -653,7 +1014,6 @@ mod connect_schema_agreement_tests {
     use crate::core::descriptors::{PortDescriptor, ProcessorDescriptor};
     use crate::core::graph::{InputLinkPortRef, OutputLinkPortRef, ProcessorUniqueId};
     use crate::core::processors::{PROCESSOR_REGISTRY, ProcessorSpec};
-    use crate::core::schema_agreement::SchemaValidationPosture;
     use streamlib_idents::{Org, Package, SchemaIdent, SemVer, TypeName};
     use streamlib_processor_schema::PortSchemaSpec;
 
@@ -774,12 +1134,7 @@ mod connect_schema_agreement_tests {
         let subscriber = tracing_subscriber::registry().with(warnings.clone());
 
         let result = tracing::subscriber::with_default(subscriber, || {
-            block_on(connect_impl(
-                compiler,
-                from,
-                to,
-                SchemaValidationPosture::Loose,
-            ))
+            block_on(connect_impl(compiler, from, to, ConnectOptions::loose()))
         });
 
         result.expect("loose posture must wire the mismatched link, not fail");
@@ -797,13 +1152,8 @@ mod connect_schema_agreement_tests {
     #[test]
     fn strict_connect_rejects_a_mismatched_link() {
         let (compiler, from, to) = compiler_with_mismatched_pair();
-        let err = block_on(connect_impl(
-            compiler,
-            from,
-            to,
-            SchemaValidationPosture::Strict,
-        ))
-        .expect_err("strict posture must reject the mismatched link");
+        let err = block_on(connect_impl(compiler, from, to, ConnectOptions::strict()))
+            .expect_err("strict posture must reject the mismatched link");
         assert!(
             matches!(err, Error::SchemaIdentMismatch { .. }),
             "strict connect over a concrete schema mismatch must surface \
@@ -817,9 +1167,8 @@ mod connect_schema_agreement_tests {
     /// typed [`Error::SchemaIdentMismatch`] and does not wire the link, while
     /// [`ConnectOptions::loose`] over the same pair still wires it.
     ///
-    /// Mentally reverting `connect_with` to drop `options.validation` — routing
-    /// through the loose `connect` — makes the strict half return `Ok` and fails
-    /// here. The `connect_impl`-level tests above never exercise the public
+    /// Mentally reverting `connect_with` to drop `options.validation` makes one
+    /// half fail here. The `connect_impl`-level tests above never exercise the public
     /// surface, so they don't catch that regression.
     #[test]
     #[serial]
@@ -861,6 +1210,68 @@ mod connect_schema_agreement_tests {
             .expect("loose connect_with over the same pair must still wire the link");
     }
 
+    /// Plain `connect` checks schemas strictly and reports the mismatch as an
+    /// [`Error::Topology`] diagnostic naming both ends; the link is not wired.
+    #[test]
+    #[serial]
+    fn connect_rejects_a_mismatched_link_with_a_topology_diagnostic() {
+        use crate::core::TopologyDiagnostic;
+        use crate::core::runtime::RuntimeOperations;
+
+        ensure_mismatch_types_registered();
+        let runtime = Runner::new().expect("runner builds");
+        let producer = runtime
+            .add_processor(ProcessorSpec::new(
+                ident("connectcheck", PRODUCER_TYPE),
+                Value::Null,
+            ))
+            .expect("producer node adds");
+        let consumer = runtime
+            .add_processor(ProcessorSpec::new(
+                ident("connectcheck", CONSUMER_TYPE),
+                Value::Null,
+            ))
+            .expect("consumer node adds");
+
+        let err = runtime
+            .connect(
+                OutputLinkPortRef::new(producer.clone(), "out"),
+                InputLinkPortRef::new(consumer.clone(), "in"),
+            )
+            .expect_err("connect must reject the mismatched link");
+        match &err {
+            Error::Topology { diagnostics } => match &diagnostics[..] {
+                [
+                    TopologyDiagnostic::SchemaMismatch {
+                        from_port,
+                        to_port,
+                        producer_schema,
+                        consumer_schema,
+                        ..
+                    },
+                ] => {
+                    assert_eq!((from_port.as_str(), to_port.as_str()), ("out", "in"));
+                    assert!(producer_schema.contains("VideoFrame"), "{producer_schema}");
+                    assert!(consumer_schema.contains("AudioFrame"), "{consumer_schema}");
+                }
+                other => panic!("expected one schema mismatch, got {other:?}"),
+            },
+            other => panic!("expected Error::Topology, got {other:?}"),
+        }
+
+        let err = block_on(runtime.connect_async(
+            OutputLinkPortRef::new(producer, "out"),
+            InputLinkPortRef::new(consumer, "in"),
+        ))
+        .expect_err("connect_async must reject the mismatched link");
+        assert!(matches!(err, Error::Topology { .. }), "{err:?}");
+
+        let link_count = runtime
+            .compiler
+            .scope(|graph, _tx| graph.traversal().e(()).iter().count());
+        assert_eq!(link_count, 0, "a rejected connect must not wire the link");
+    }
+
     /// Async counterpart to
     /// [`connect_with_strict_rejects_a_mismatched_link_via_the_public_surface`]:
     /// awaiting `Runner::connect_with_async` from inside a tokio task under
@@ -910,4 +1321,166 @@ mod connect_schema_agreement_tests {
         ))
         .expect("loose connect_with_async over the same pair must still wire the link");
     }
+
+    /// With auto-convert on and a rule covering the producer's VideoFrame →
+    /// consumer's AudioFrame mismatch, `connect` wires through a hidden
+    /// converter node instead of linking the mismatched ports directly. The
+    /// saved snapshot collapses the converter back into the one connection
+    /// the caller asked for and keeps the auto-convert flag, so loading it
+    /// re-inserts the converter.
+    #[test]
+    #[serial]
+    fn auto_convert_inserts_registered_converter_on_mismatch() {
+        use crate::core::graph::{AutoConverterComponent, GraphNodeWithComponents};
+        use crate::core::runtime::AutoConverterRule;
+
+        const CONVERTER_TYPE: &str = "SchemaMismatchConverter";
+        ensure_mismatch_types_registered();
+        static REGISTER_CONVERTER: Once = Once::new();
+        REGISTER_CONVERTER.call_once(|| {
+            let mut converter = ProcessorDescriptor::new(
+                ident("connectcheck", CONVERTER_TYPE),
+                "mismatch converter",
+            );
+            converter.inputs.push(PortDescriptor::iceoryx2(
+                "video_in",
+                "input",
+                schema("VideoFrame"),
+            ));
+            converter.outputs.push(PortDescriptor::iceoryx2(
+                "audio_out",
+                "output",
+                schema("AudioFrame"),
+            ));
+            PROCESSOR_REGISTRY
+                .register_descriptor_only(converter)
+                .expect("register mismatch converter descriptor");
+        });
+
+        let runtime = Runner::new().expect("runner builds");
+        runtime.register_auto_converter(AutoConverterRule::new(
+            ident("core", "VideoFrame"),
+            ident("core", "AudioFrame"),
+            ident("connectcheck", CONVERTER_TYPE),
+            "video_in",
+            "audio_out",
+        ));
+        runtime.set_auto_convert(true);
+
+        let producer = runtime
+            .add_processor(ProcessorSpec::new(
+                ident("connectcheck", PRODUCER_TYPE),
+                Value::Null,
+            ))
+            .expect("producer node adds");
+        let consumer = runtime
+            .add_processor(ProcessorSpec::new(
+                ident("connectcheck", CONSUMER_TYPE),
+                Value::Null,
+            ))
+            .expect("consumer node adds");
+
+        runtime
+            .connect_with(
+                OutputLinkPortRef::new(producer.clone(), "out"),
+                InputLinkPortRef::new(consumer.clone(), "in"),
+                ConnectOptions::strict(),
+            )
+            .expect("strict connect must succeed through the inserted converter");
+
+        let converters_and_links = |runtime: &Runner| {
+            runtime.compiler.scope(|graph, _tx| {
+                (
+                    graph
+                        .traversal()
+                        .v(())
+                        .iter()
+                        .filter(|node| node.has::<AutoConverterComponent>())
+                        .count(),
+                    graph.traversal().e(()).iter().count(),
+                )
+            })
+        };
+        let (converter_count, link_count) = converters_and_links(&runtime);
+        assert_eq!(converter_count, 1, "exactly one converter must be inserted");
+        assert_eq!(link_count, 2, "producer -> converter -> consumer");
+
+        let snapshot = runtime.save_graph_snapshot().expect("snapshot saves");
+        assert_eq!(snapshot.processors.len(), 2, "converter is saved away");
+        assert_eq!(snapshot.connections.len(), 1);
+        assert!(snapshot.connections[0].from.ends_with(".out"));
+        assert!(snapshot.connections[0].to.ends_with(".in"));
+        assert!(snapshot.auto_convert, "the flag rides the snapshot");
+
+        let reloaded = Runner::new().expect("runner builds");
+        reloaded.register_auto_converter(AutoConverterRule::new(
+            ident("core", "VideoFrame"),
+            ident("core", "AudioFrame"),
+            ident("connectcheck", CONVERTER_TYPE),
+            "video_in",
+            "audio_out",
+        ));
+        reloaded
+            .load_graph_snapshot(&snapshot)
+            .expect("snapshot reloads");
+        assert!(reloaded.auto_convert());
+        assert_eq!(
+            converters_and_links(&reloaded),
+            (1, 2),
+            "loading re-inserts the converter"
+        );
+    }
+}
+
+#[cfg(test)]
+mod connect_topology_tests {
+    use serial_test::serial;
+
+    use super::Runner;
+    use crate::core::graph::{InputLinkPortRef, OutputLinkPortRef};
+    use crate::core::test_support::{MockReactiveProcessor, ensure_test_mocks_registered};
+    use crate::core::{Error, TopologyDiagnostic};
+
+    /// A loop of Reactive processors is rejected only when it closes onto a
+    /// required input; the same loop closed onto an optional input wires.
+    #[test]
+    #[serial]
+    fn connect_wires_feedback_loop_onto_optional_input() {
+        ensure_test_mocks_registered();
+        let runtime = Runner::new().expect("runner builds");
+        let a = runtime
+            .add_processor(MockReactiveProcessor::Processor::node(Default::default()))
+            .expect("a adds");
+        let b = runtime
+            .add_processor(MockReactiveProcessor::Processor::node(Default::default()))
+            .expect("b adds");
+        runtime
+            .connect(
+                OutputLinkPortRef::new(a.clone(), "out1"),
+                InputLinkPortRef::new(b.clone(), "in1"),
+            )
+            .expect("a -> b wires");
+
+        let err = runtime
+            .connect(
+                OutputLinkPortRef::new(b.clone(), "out1"),
+                InputLinkPortRef::new(a.clone(), "in2"),
+            )
+            .expect_err("closing the loop onto a required input must deadlock");
+        assert!(
+            matches!(
+                &err,
+                Error::Topology { diagnostics }
+                    if matches!(diagnostics[..], [TopologyDiagnostic::Cycle { .. }])
+            ),
+            "expected a cycle diagnostic, got {err:?}"
+        );
+
+        runtime
+            .connect(
+                OutputLinkPortRef::new(b, "out1"),
+                InputLinkPortRef::new(a, "feedback"),
+            )
+            .expect("closing the loop onto an optional input must wire");
+    }
 }
@@ -0,0 +1,187 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Runner side of port recording: resolving the links to record and the
+//! ports to replay into against the live graph.
+//!
+//! One recording runs at a time. Replays are independent of it and of each
+//! other; each returns its own [`PortReplayer`] handle.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Runner;
+use crate::core::graph::{
+    GraphEdgeWithComponents, GraphNodeWithComponents, LinkFrameDropComponent, LinkUniqueId,
+    ProcessorInstanceComponent,
+};
+use crate::core::port_recording::{
+    PortRecorder, PortRecordingReader, PortRecordingSummary, PortReplayOptions, PortReplayer,
+    RecordedLink, ReplayTarget, bound_link_recording_tap,
+};
+use crate::core::{Error, PortDirection, Result};
+
+impl Runner {
+    /// Record every frame the given links deliver to `path` until
+    /// [`Self::stop_port_recording`]. Each link must be wired to an
+    /// in-process destination, where its frames are captured.
+    pub fn start_port_recording(
+        &self,
+        path: impl AsRef<Path>,
+        link_ids: &[LinkUniqueId],
+    ) -> Result<()> {
+        if link_ids.is_empty() {
+            return Err(Error::Configuration(
+                "a port recording needs at least one link".into(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = link_ids.iter().find(|id| !seen.insert(*id)) {
+            return Err(Error::Configuration(format!(
+                "link {duplicate} is listed twice"
+            )));
+        }
+
+        let mut recording = self.port_recording.lock();
+        if recording.is_some() {
+            return Err(Error::Runtime("a port recording is already running".into()));
+        }
+        let links = link_ids
+            .iter()
+            .map(|link_id| {
+                let link = self.recordable_link(link_id)?;
+                let tap = bound_link_recording_tap(link_id.as_str())
+                    .ok_or_else(|| Error::LinkNotWired(link_id.to_string()))?;
+                Ok((link, tap))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        *recording = Some(PortRecorder::start(path, links)?);
+        Ok(())
+    }
+
+    /// Stop the running port recording and report what it captured.
+    pub fn stop_port_recording(&self) -> Result<PortRecordingSummary> {
+        let recorder = self
+            .port_recording
+            .lock()
+            .take()
+            .ok_or_else(|| Error::NotFound("no port recording is running".into()))?;
+        recorder.stop()
+    }
+
+    pub fn is_port_recording(&self) -> bool {
+        self.port_recording.lock().is_some()
+    }
+
+    /// Replay the recording at `path` into the graph as it is now. Each
+    /// recorded link's frames go to the input port it was recorded at
+    /// unless `options` routes them elsewhere; either way the port must
+    /// exist on a running in-process processor.
+    pub fn replay_port_recording(
+        &self,
+        path: impl AsRef<Path>,
+        options: PortReplayOptions,
+    ) -> Result<PortReplayer> {
+        let reader = PortRecordingReader::open_path(path)?;
+        let recorded = &reader.header().links;
+        for link_id in options
+            .routes
+            .iter()
+            .map(|route| &route.link_id)
+            .chain(&options.only_links)
+        {
+            if !recorded.iter().any(|link| &link.link_id == link_id) {
+                return Err(Error::Configuration(format!(
+                    "the recording has no link {link_id}"
+                )));
+            }
+        }
+
+        let mut targets = Vec::with_capacity(recorded.len());
+        for link in recorded {
+            if !options.only_links.is_empty() && !options.only_links.contains(&link.link_id) {
+                targets.push(None);
+                continue;
+            }
+            let (processor_id, port) = options
+                .routes
+                .iter()
+                .find(|route| route.link_id == link.link_id)
+                .map(|route| (route.processor_id.clone(), route.port.clone()))
+                .unwrap_or_else(|| {
+                    (
+                        link.destination_processor_id.clone(),
+                        link.destination_port.clone(),
+                    )
+                });
+            let instance = self.compiler.scope(|graph, _tx| {
+                let node = graph
+                    .traversal()
+                    .v(&processor_id)
+                    .first()
+                    .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+                if !node.has_input(&port) {
+                    return Err(Error::ProcessorPortNotFound {
+                        processor_id: processor_id.to_string(),
+                        port_name: port.clone(),
+                        direction: PortDirection::Input,
+                    });
+                }
+                node.get::<ProcessorInstanceComponent>()
+                    .map(|component| Arc::clone(&component.0))
+                    .ok_or_else(|| {
+                        Error::Runtime(format!(
+                            "processor '{processor_id}' is not running — start the runtime \
+                             before replaying into it"
+                        ))
+                    })
+            })?;
+            // Locked outside the graph scope: the processor's own thread
+            // holds this lock across process().
+            let mailboxes = instance.lock().iceoryx2_input_mailboxes_inner();
+            let mailboxes = mailboxes.ok_or_else(|| {
+                Error::NotSupported(format!(
+                    "processor '{processor_id}' has no in-process input mailboxes to replay into"
+                ))
+            })?;
+            targets.push(Some(ReplayTarget { mailboxes, port }));
+        }
+
+        tracing::info!(
+            "[port_recording] Replaying {} of {} recorded links ({:?} pace)",
+            targets.iter().flatten().count(),
+            targets.len(),
+            options.pace
+        );
+        PortReplayer::start(reader, targets, options.pace)
+    }
+
+    /// How `link_id` is wired, for a recording header. The link must have
+    /// an in-process destination.
+    fn recordable_link(&self, link_id: &LinkUniqueId) -> Result<RecordedLink> {
+        let (link, in_process) = self
+            .compiler
+            .scope(|graph, _tx| {
+                graph.traversal().e(link_id).first().map(|link| {
+                    (
+                        RecordedLink {
+                            link_id: link_id.clone(),
+                            source_processor_id: link.source.processor_id.clone(),
+                            source_port: link.source.port_name.clone(),
+                            destination_processor_id: link.target.processor_id.clone(),
+                            destination_port: link.target.port_name.clone(),
+                        },
+                        link.has::<LinkFrameDropComponent>(),
+                    )
+                })
+            })
+            .ok_or_else(|| Error::LinkNotFound(link_id.to_string()))?;
+        if !in_process {
+            return Err(Error::NotSupported(format!(
+                "link {link_id} has no in-process destination to record at"
+            )));
+        }
+        Ok(link)
+    }
+}
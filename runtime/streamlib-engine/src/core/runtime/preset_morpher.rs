@@ -0,0 +1,226 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The task that steps processors through a [`PresetMorph`] for
+//! [`Runner::morph_processor_configs`].
+//!
+//! Each tick swaps the interpolated configs into the graph through the
+//! same path as [`Runner::update_processor_config`], so processors pick
+//! them up on the next commit like any other config change. Only the
+//! finished morph lands in the edit history — one `UpdateConfig` per
+//! processor, from its config before the morph to the morph's `to`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::time::MissedTickBehavior;
+
+use super::Runner;
+use super::runtime::replace_processor_config_impl;
+use crate::core::compiler::Compiler;
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::graph_edit_history::{GraphEdit, GraphEditHistory};
+use crate::core::preset_morph::{MorphCurve, PresetMorph, interpolate_config};
+use crate::core::{Error, Result};
+
+/// How often a running morph pushes interpolated configs.
+const PRESET_MORPH_TICK_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Which morph currently drives each processor. Starting a morph takes its
+/// processors over from any older morph still running; the older one
+/// carries on with the rest.
+#[derive(Default)]
+pub(crate) struct PresetMorphOwners {
+    next_morph_id: u64,
+    owners: HashMap<ProcessorUniqueId, u64>,
+}
+
+impl PresetMorphOwners {
+    fn claim<'a>(&mut self, processor_ids: impl IntoIterator<Item = &'a ProcessorUniqueId>) -> u64 {
+        self.next_morph_id += 1;
+        for processor_id in processor_ids {
+            self.owners.insert(processor_id.clone(), self.next_morph_id);
+        }
+        self.next_morph_id
+    }
+
+    fn owns(&self, morph_id: u64, processor_id: &ProcessorUniqueId) -> bool {
+        self.owners.get(processor_id) == Some(&morph_id)
+    }
+
+    /// Hand `processor_id` back, returning whether `morph_id` still owned it.
+    fn release(&mut self, morph_id: u64, processor_id: &ProcessorUniqueId) -> bool {
+        if !self.owns(morph_id, processor_id) {
+            return false;
+        }
+        self.owners.remove(processor_id);
+        true
+    }
+}
+
+/// One target, resolved against the graph when the morph starts.
+struct MorphLane {
+    processor_id: ProcessorUniqueId,
+    /// The config before the morph, for the edit history.
+    previous: Value,
+    from: Value,
+    to: Value,
+}
+
+struct PresetMorphTask {
+    morph_id: u64,
+    lanes: Vec<MorphLane>,
+    duration: Duration,
+    curve: MorphCurve,
+    compiler: Arc<Compiler>,
+    owners: Arc<Mutex<PresetMorphOwners>>,
+    graph_edits: Arc<Mutex<GraphEditHistory>>,
+}
+
+impl PresetMorphTask {
+    async fn run(self) {
+        let started = Instant::now();
+        let mut interval = tokio::time::interval(PRESET_MORPH_TICK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let progress = started.elapsed().as_secs_f64() / self.duration.as_secs_f64();
+            if !self.step(progress.min(1.0)) {
+                break;
+            }
+        }
+        tracing::debug!("[preset_morph] Morph {} done", self.morph_id);
+    }
+
+    /// Push the configs for linear `progress`, finishing the morph at `1.0`.
+    /// Returns whether there is anything left to drive.
+    fn step(&self, progress: f64) -> bool {
+        let finished = progress >= 1.0;
+        let t = self.curve.ease(progress);
+        let mut driving = false;
+        for lane in &self.lanes {
+            let owned = {
+                let mut owners = self.owners.lock();
+                if finished {
+                    owners.release(self.morph_id, &lane.processor_id)
+                } else {
+                    owners.owns(self.morph_id, &lane.processor_id)
+                }
+            };
+            if !owned {
+                continue;
+            }
+
+            let config = interpolate_config(&lane.from, &lane.to, t);
+            if replace_processor_config_impl(&self.compiler, &lane.processor_id, config).is_none() {
+                tracing::debug!(
+                    "[preset_morph] {} left the graph mid-morph",
+                    lane.processor_id
+                );
+                self.owners
+                    .lock()
+                    .release(self.morph_id, &lane.processor_id);
+                continue;
+            }
+            driving = true;
+
+            if finished && lane.previous != lane.to {
+                self.graph_edits.lock().record(GraphEdit::UpdateConfig {
+                    processor_id: lane.processor_id.clone(),
+                    previous: lane.previous.clone(),
+                    config: lane.to.clone(),
+                });
+            }
+        }
+        driving && !finished
+    }
+}
+
+impl Runner {
+    /// Morph processor configs between two snapshots over the morph's
+    /// duration, easing along its curve.
+    ///
+    /// Returns once the morph is running; it finishes on the runtime's
+    /// tokio handle. A processor named by a newer morph is taken over by
+    /// it. Plain config updates made mid-morph are overwritten on the next
+    /// tick. A zero duration applies the `to` configs at once.
+    pub fn morph_processor_configs(&self, morph: PresetMorph) -> Result<()> {
+        morph.validate()?;
+        let lanes = self.compiler.scope(|graph, _tx| {
+            morph
+                .targets
+                .iter()
+                .map(|target| {
+                    let node = graph
+                        .traversal()
+                        .v(&target.processor_id)
+                        .first()
+                        .ok_or_else(|| Error::ProcessorNotFound(target.processor_id.to_string()))?;
+                    let previous = node.config.clone().unwrap_or(Value::Null);
+                    Ok(MorphLane {
+                        processor_id: target.processor_id.clone(),
+                        from: target.from.clone().unwrap_or_else(|| previous.clone()),
+                        previous,
+                        to: target.to.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+
+        let morph_id = self
+            .preset_morphs
+            .lock()
+            .claim(lanes.iter().map(|lane| &lane.processor_id));
+        tracing::info!(
+            "[preset_morph] Morph {}: {} processor(s) over {:?} ({:?})",
+            morph_id,
+            lanes.len(),
+            morph.duration(),
+            morph.curve
+        );
+
+        let task = PresetMorphTask {
+            morph_id,
+            lanes,
+            duration: morph.duration(),
+            curve: morph.curve,
+            compiler: Arc::clone(&self.compiler),
+            owners: Arc::clone(&self.preset_morphs),
+            graph_edits: Arc::clone(&self.graph_edits),
+        };
+        if task.duration.is_zero() {
+            task.step(1.0);
+        } else {
+            self.tokio_runtime_variant.handle().spawn(task.run());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_newer_morph_takes_over_shared_processors_only() {
+        let (blur, gain) = (
+            ProcessorUniqueId::from("blur"),
+            ProcessorUniqueId::from("gain"),
+        );
+        let mut owners = PresetMorphOwners::default();
+
+        let first = owners.claim([&blur, &gain]);
+        let second = owners.claim([&gain]);
+
+        assert!(owners.owns(first, &blur));
+        assert!(!owners.owns(first, &gain));
+        assert!(owners.owns(second, &gain));
+
+        assert!(!owners.release(first, &gain));
+        assert!(owners.release(second, &gain));
+        assert!(!owners.owns(second, &gain));
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Minute-resolution evaluator for the cron schedules attached with
+//! [`Runner::add_processor_schedule`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+
+use crate::core::graph::ProcessorUniqueId;
+use crate::core::processor_schedule::{ProcessorSchedule, ProcessorScheduleAction};
+
+use super::{Runner, RuntimeStatus};
+
+/// How often the scheduler checks for a minute boundary.
+const PROCESSOR_SCHEDULER_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest run of missed minutes replayed firing by firing; a longer gap
+/// catches up to each processor's most recent firing instead.
+const MAX_REPLAYED_SCHEDULE_MINUTES: i64 = 60;
+
+/// Actions due in the minute starting at `unix_minute`, in schedule
+/// insertion order.
+pub(crate) fn due_schedule_actions(
+    schedules: &[(ProcessorUniqueId, ProcessorSchedule)],
+    unix_minute: i64,
+) -> Vec<(ProcessorUniqueId, ProcessorScheduleAction)> {
+    schedules
+        .iter()
+        .filter(|(_, schedule)| schedule.fires_at(unix_minute))
+        .map(|(processor_id, schedule)| (processor_id.clone(), schedule.action))
+        .collect()
+}
+
+/// The state each scheduled processor should be in at `unix_minute`: the
+/// action of its most recently fired schedule. Ties go to the schedule
+/// added last.
+pub(crate) fn catch_up_schedule_actions(
+    schedules: &[(ProcessorUniqueId, ProcessorSchedule)],
+    unix_minute: i64,
+) -> Vec<(ProcessorUniqueId, ProcessorScheduleAction)> {
+    let mut latest: HashMap<&ProcessorUniqueId, (i64, ProcessorScheduleAction)> = HashMap::new();
+    let mut order: Vec<&ProcessorUniqueId> = Vec::new();
+    for (processor_id, schedule) in schedules {
+        let Some(fired_at) = schedule.most_recent_firing(unix_minute) else {
+            continue;
+        };
+        match latest.get(processor_id) {
+            Some((previous, _)) if *previous > fired_at => {}
+            Some(_) => {
+                latest.insert(processor_id, (fired_at, schedule.action));
+            }
+            None => {
+                latest.insert(processor_id, (fired_at, schedule.action));
+                order.push(processor_id);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .map(|processor_id| (processor_id.clone(), latest[processor_id].1))
+        .collect()
+}
+
+/// Actions to apply on reaching `unix_minute` when `last_evaluated` was the
+/// last minute evaluated (`None` on the first tick and after a pause).
+/// Every minute since is replayed in order, so a stall, host suspend or
+/// clock jump that skips a minute boundary loses no firing. A gap longer
+/// than [`MAX_REPLAYED_SCHEDULE_MINUTES`], or a clock that went backwards,
+/// catches up instead.
+pub(crate) fn schedule_actions_since(
+    schedules: &[(ProcessorUniqueId, ProcessorSchedule)],
+    last_evaluated: Option<i64>,
+    unix_minute: i64,
+) -> Vec<(ProcessorUniqueId, ProcessorScheduleAction)> {
+    match last_evaluated {
+        Some(last) if last == unix_minute => Vec::new(),
+        Some(last) if last < unix_minute && unix_minute - last <= MAX_REPLAYED_SCHEDULE_MINUTES => {
+            (last + 1..=unix_minute)
+                .flat_map(|minute| due_schedule_actions(schedules, minute))
+                .collect()
+        }
+        _ => catch_up_schedule_actions(schedules, unix_minute),
+    }
+}
+
+fn current_unix_minute() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0);
+    secs.div_euclid(60)
+}
+
+fn apply_schedule_action(
+    runner: &Runner,
+    processor_id: &ProcessorUniqueId,
+    action: ProcessorScheduleAction,
+) {
+    let result = match action {
+        ProcessorScheduleAction::Start => runner
+            .set_processor_bypassed(processor_id, false)
+            .and_then(|()| runner.resume_processor(processor_id)),
+        ProcessorScheduleAction::Stop => runner.pause_processor(processor_id),
+        ProcessorScheduleAction::Bypass => runner
+            .resume_processor(processor_id)
+            .and_then(|()| runner.set_processor_bypassed(processor_id, true)),
+    };
+    match result {
+        Ok(()) => tracing::debug!("[{}] Applied scheduled '{}'", processor_id, action),
+        Err(e) => tracing::warn!(
+            "[{}] Failed to apply scheduled '{}': {}",
+            processor_id,
+            action,
+            e
+        ),
+    }
+}
+
+/// Spawn the scheduler on the runtime's tokio handle. The first tick
+/// brings every scheduled processor into the state its most recent firing
+/// implies (so a runtime started outside business hours comes up with its
+/// business-hours source stopped); later ticks apply firings as each
+/// minute begins. Runtime-level pause suspends evaluation and re-runs the
+/// catch-up on resume. The task exits once the runtime leaves the started
+/// / paused states or is dropped, and `stop()` aborts it through the
+/// returned handle, so only one scheduler ever applies a firing.
+pub(crate) fn spawn_processor_scheduler(
+    handle: &tokio::runtime::Handle,
+    runner: Weak<Runner>,
+    status: Arc<Mutex<RuntimeStatus>>,
+) -> tokio::task::JoinHandle<()> {
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(PROCESSOR_SCHEDULER_TICK_INTERVAL);
+        let mut last_evaluated_minute: Option<i64> = None;
+        loop {
+            interval.tick().await;
+            match *status.lock() {
+                RuntimeStatus::Started => {}
+                RuntimeStatus::Pausing | RuntimeStatus::Paused => {
+                    last_evaluated_minute = None;
+                    continue;
+                }
+                _ => break,
+            }
+            let Some(runner) = runner.upgrade() else {
+                break;
+            };
+            let minute = current_unix_minute();
+            if last_evaluated_minute == Some(minute) {
+                continue;
+            }
+
+            let schedules = runner.processor_schedules.lock().clone();
+            let actions = schedule_actions_since(&schedules, last_evaluated_minute, minute);
+            last_evaluated_minute = Some(minute);
+            for (processor_id, action) in actions {
+                apply_schedule_action(&runner, &processor_id, action);
+            }
+        }
+        tracing::debug!("[processor_scheduler] runtime no longer running, scheduler exiting");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::processor_schedule::CronExpression;
+
+    /// 2025-01-06 00:00 UTC, a Monday.
+    const MONDAY_MIDNIGHT_UTC_MINUTE: i64 = 1_736_121_600 / 60;
+
+    fn business_hours(
+        processor_id: &ProcessorUniqueId,
+    ) -> Vec<(ProcessorUniqueId, ProcessorSchedule)> {
+        vec![
+            (
+                processor_id.clone(),
+                ProcessorSchedule::new(
+                    CronExpression::parse("0 9 * * MON-FRI").unwrap(),
+                    ProcessorScheduleAction::Start,
+                ),
+            ),
+            (
+                processor_id.clone(),
+                ProcessorSchedule::new(
+                    CronExpression::parse("0 17 * * MON-FRI").unwrap(),
+                    ProcessorScheduleAction::Stop,
+                ),
+            ),
+        ]
+    }
+
+    #[test]
+    fn due_actions_fire_only_on_matching_minute() {
+        let camera = ProcessorUniqueId::from("camera");
+        let schedules = business_hours(&camera);
+        let monday_nine = MONDAY_MIDNIGHT_UTC_MINUTE + 9 * 60;
+
+        assert_eq!(
+            due_schedule_actions(&schedules, monday_nine),
+            vec![(camera.clone(), ProcessorScheduleAction::Start)]
+        );
+        assert!(due_schedule_actions(&schedules, monday_nine + 1).is_empty());
+    }
+
+    #[test]
+    fn a_skipped_minute_still_fires_and_a_long_gap_catches_up() {
+        let camera = ProcessorUniqueId::from("camera");
+        let schedules = business_hours(&camera);
+        let monday_nine = MONDAY_MIDNIGHT_UTC_MINUTE + 9 * 60;
+
+        // The tick for 09:00 never ran: last evaluated 08:59, now 09:01.
+        assert_eq!(
+            schedule_actions_since(&schedules, Some(monday_nine - 1), monday_nine + 1),
+            vec![(camera.clone(), ProcessorScheduleAction::Start)]
+        );
+        assert!(schedule_actions_since(&schedules, Some(monday_nine), monday_nine).is_empty());
+
+        // Suspended from 08:00 to 18:00: only the state 18:00 implies.
+        assert_eq!(
+            schedule_actions_since(&schedules, Some(monday_nine - 60), monday_nine + 9 * 60),
+            vec![(camera.clone(), ProcessorScheduleAction::Stop)]
+        );
+        // The clock went back an hour.
+        assert_eq!(
+            schedule_actions_since(&schedules, Some(monday_nine + 60), monday_nine),
+            vec![(camera, ProcessorScheduleAction::Start)]
+        );
+    }
+
+    #[test]
+    fn catch_up_picks_most_recent_firing_per_processor() {
+        let camera = ProcessorUniqueId::from("camera");
+        let schedules = business_hours(&camera);
+
+        // Monday 12:00 → inside business hours.
+        assert_eq!(
+            catch_up_schedule_actions(&schedules, MONDAY_MIDNIGHT_UTC_MINUTE + 12 * 60),
+            vec![(camera.clone(), ProcessorScheduleAction::Start)]
+        );
+        // Monday 08:00 → last firing was Friday 17:00.
+        assert_eq!(
+            catch_up_schedule_actions(&schedules, MONDAY_MIDNIGHT_UTC_MINUTE + 8 * 60),
+            vec![(camera, ProcessorScheduleAction::Stop)]
+        );
+    }
+}
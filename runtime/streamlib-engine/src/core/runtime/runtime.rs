@@ -11,21 +11,36 @@ use serde::Serialize;
 use super::RuntimeOperations;
 use super::RuntimeStatus;
 use super::RuntimeUniqueId;
+use super::auto_convert::{AutoConverterRule, builtin_auto_converters};
+use super::chaos_injector::ChaosState;
+use super::federation::RemoteLinkMonitors;
 use super::graph_change_listener::GraphChangeListener;
+use super::midi_mapper::MidiMappings;
+use super::preset_morpher::PresetMorphOwners;
+use super::runtime_tasks::RuntimeTasks;
+use crate::core::assets::AssetManager;
 use crate::core::compiler::{Compiler, PendingOperation};
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
 use crate::core::context::SoftwareAudioClock;
 use crate::core::context::{
-    AudioClockConfig, GpuContext, RuntimeContext, SharedAudioClock, TimeContext,
+    AudioClockConfig, GpuContext, RuntimeContext, SharedAudioClock, TexturePoolConfig, TimeContext,
 };
+use crate::core::fonts::FontRegistry;
 use crate::core::graph::{
-    GraphNodeWithComponents, GraphState, LinkUniqueId, ProcessorPauseGateComponent,
-    ProcessorUniqueId,
+    AutoConverterComponent, GpuAdapterAffinityComponent, Graph, GraphNodeWithComponents,
+    GraphState, LinkUniqueId, ProcessorBypassGateComponent, ProcessorParameterPortComponent,
+    ProcessorPauseGateComponent, ProcessorUniqueId, TopologyAnalyzer,
 };
+use crate::core::graph_edit_history::GraphEditHistory;
+use crate::core::media_clock::MediaClock;
+use crate::core::parameter_automation::ParameterChange;
+use crate::core::port_recording::PortRecorder;
+use crate::core::processor_schedule::{ProcessorSchedule, ProcessorScheduleAction};
+use crate::core::processors::PROCESSOR_REGISTRY;
 use crate::core::processors::ProcessorSpec;
 use crate::core::processors::ProcessorState;
 use crate::core::pubsub::{Event, EventListener, PUBSUB, ProcessorEvent, RuntimeEvent, topics};
-use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, Result};
+use crate::core::{Error, InputLinkPortRef, OutputLinkPortRef, Result, TopologyDiagnostic};
 use crate::iceoryx2::Iceoryx2Node;
 
 /// Storage variant for tokio runtime in Runner.
@@ -49,6 +64,63 @@ impl TokioRuntimeVariant {
     }
 }
 
+/// [`Runner::replace_processor_config`] for callers holding only the
+/// compiler — the preset morph task.
+pub(super) fn replace_processor_config_impl(
+    compiler: &Compiler,
+    processor_id: &ProcessorUniqueId,
+    config_json: serde_json::Value,
+) -> Option<serde_json::Value> {
+    // Update config in graph and queue operation
+    let previous = compiler.scope(|graph, tx| {
+        let previous = swap_processor_config(graph, processor_id, config_json);
+
+        tx.log(PendingOperation::UpdateProcessorConfig(
+            processor_id.clone(),
+        ));
+        previous
+    });
+
+    // Publish event
+    PUBSUB.publish(
+        topics::RUNTIME_GLOBAL,
+        &Event::RuntimeGlobal(RuntimeEvent::ProcessorConfigDidChange {
+            processor_id: processor_id.clone(),
+        }),
+    );
+
+    // Notify listeners that graph changed (triggers commit via GraphChangeListener)
+    PUBSUB.publish(
+        topics::RUNTIME_GLOBAL,
+        &Event::RuntimeGlobal(RuntimeEvent::GraphDidChange),
+    );
+
+    previous
+}
+
+/// Swap `config_json` into `processor_id`'s node, returning the config it
+/// replaced (`None` for an unknown processor). Queues nothing; the caller
+/// logs the config update.
+pub(super) fn swap_processor_config(
+    graph: &mut Graph,
+    processor_id: &ProcessorUniqueId,
+    config_json: serde_json::Value,
+) -> Option<serde_json::Value> {
+    graph
+        .traversal_mut()
+        .v(processor_id)
+        .first_mut()
+        .map(|processor| {
+            let previous = processor.config.clone().unwrap_or(serde_json::Value::Null);
+            // Scheduled parameter changes build on the new config.
+            if let Some(parameter_port) = processor.get::<ProcessorParameterPortComponent>() {
+                parameter_port.set_config(config_json.clone());
+            }
+            processor.set_config(config_json);
+            previous
+        })
+}
+
 /// The main stream processing runtime.
 ///
 /// # Thread Safety
@@ -157,6 +229,41 @@ pub struct Runner {
     ///
     /// [`Runner::add_module`]: Self::add_module
     pub(crate) resolution_memo: Arc<crate::core::runtime::module_loader::ResolutionMemo>,
+    /// Cron schedules bound to processors, in insertion order. Evaluated
+    /// by the processor scheduler task spawned in [`Self::start`] and
+    /// persisted through [`Self::save_graph_snapshot`].
+    pub(crate) processor_schedules: Arc<Mutex<Vec<(ProcessorUniqueId, ProcessorSchedule)>>>,
+    /// Whether `connect()` inserts a registered converter between ports
+    /// whose concrete schemas disagree. Off by default.
+    pub(crate) auto_convert: Arc<AtomicBool>,
+    /// Converters [`Self::set_auto_convert`] may insert, first match wins;
+    /// starts with the built-in format converters.
+    pub(crate) auto_converters: Arc<Mutex<Vec<AutoConverterRule>>>,
+    /// Links made by [`Self::connect_remote`], each with the thread holding
+    /// its broker connection to the remote runtime.
+    pub(crate) remote_links: RemoteLinkMonitors,
+    /// Undo / redo log of graph edits; see [`Self::undo_graph_edit`].
+    pub(crate) graph_edits: Arc<Mutex<GraphEditHistory>>,
+    /// Which running preset morph drives each processor; see
+    /// [`Self::morph_processor_configs`].
+    pub(crate) preset_morphs: Arc<Mutex<PresetMorphOwners>>,
+    /// MIDI control bindings and the changes queued for them; see
+    /// [`Self::set_midi_mappings`].
+    pub(crate) midi_mappings: Arc<Mutex<MidiMappings>>,
+    /// Chaos mode and its fault log; see [`Self::enable_chaos_mode`].
+    pub(crate) chaos: Arc<Mutex<ChaosState>>,
+    /// The running port recording, if any; see
+    /// [`Self::start_port_recording`].
+    pub(crate) port_recording: Arc<Mutex<Option<PortRecorder>>>,
+    /// Asset cache handed to every processor context; see [`Self::assets`].
+    pub(crate) assets: Arc<AssetManager>,
+    /// Logical font names and shared glyph atlases; see [`Self::fonts`].
+    pub(crate) fonts: Arc<FontRegistry>,
+    /// Sizing, prewarm and eviction of the GPU texture pool each `start()`
+    /// creates; see [`Self::set_texture_pool_config`].
+    pub(crate) texture_pool_config: Arc<Mutex<TexturePoolConfig>>,
+    /// Periodic tasks spawned by [`Self::start`]; aborted by [`Self::stop`].
+    tasks: Arc<RuntimeTasks>,
 }
 
 impl Runner {
@@ -256,15 +363,23 @@ impl Runner {
         // Must happen before any subscribe() calls (GraphChangeListener below).
         PUBSUB.init(&runtime_id, iceoryx2_node.clone());
 
+        // Create Arc-wrapped components
+        let compiler = Arc::new(Compiler::new());
+
         // Bring up the per-runtime surface-sharing service. Each runtime owns
         // a unique Unix socket at $XDG_RUNTIME_DIR/streamlib-<uuid>.sock that
-        // its polyglot subprocesses connect to via STREAMLIB_SURFACE_SOCKET.
-        // No external daemon is required.
+        // its polyglot subprocesses connect to via STREAMLIB_SURFACE_SOCKET,
+        // and that other runtimes federate links through. No external daemon
+        // is required.
         #[cfg(target_os = "linux")]
-        let (surface_service, surface_socket_path) = bring_up_surface_service(&runtime_id)?;
+        let (surface_service, surface_socket_path) = bring_up_surface_service(
+            &runtime_id,
+            Arc::new(super::federation::RuntimeFederationHandler::new(
+                Arc::clone(&compiler),
+                iceoryx2_node.clone(),
+            )),
+        )?;
 
-        // Create Arc-wrapped components
-        let compiler = Arc::new(Compiler::new());
         let runtime_context = Arc::new(Mutex::new(None));
         let status = Arc::new(Mutex::new(RuntimeStatus::Initial));
 
@@ -279,6 +394,8 @@ impl Runner {
         // Subscribe to graph changes
         PUBSUB.subscribe(topics::RUNTIME_GLOBAL, Arc::clone(&listener));
 
+        let assets = Arc::new(AssetManager::from_environment());
+
         Ok(Arc::new(Self {
             runtime_id,
             tokio_runtime_variant,
@@ -298,6 +415,19 @@ impl Runner {
             build_orchestrator: Arc::new(Mutex::new(None)),
             loading_modules: Arc::new(Mutex::new(std::collections::HashMap::new())),
             resolution_memo: Arc::new(crate::core::runtime::module_loader::ResolutionMemo::new()),
+            processor_schedules: Arc::new(Mutex::new(Vec::new())),
+            auto_convert: Arc::new(AtomicBool::new(false)),
+            auto_converters: Arc::new(Mutex::new(builtin_auto_converters())),
+            remote_links: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            graph_edits: Arc::new(Mutex::new(GraphEditHistory::default())),
+            preset_morphs: Arc::new(Mutex::new(PresetMorphOwners::default())),
+            midi_mappings: Arc::new(Mutex::new(MidiMappings::default())),
+            chaos: Arc::new(Mutex::new(ChaosState::default())),
+            port_recording: Arc::new(Mutex::new(None)),
+            fonts: Arc::new(FontRegistry::from_environment(Arc::clone(&assets))),
+            assets,
+            texture_pool_config: Arc::new(Mutex::new(TexturePoolConfig::default())),
+            tasks: Arc::new(RuntimeTasks::default()),
         }))
     }
 
@@ -377,6 +507,19 @@ impl Runner {
         &self.iceoryx2_node
     }
 
+    /// This runtime's asset cache. Embedders toggle offline mode here
+    /// ([`AssetManager::set_offline`]) or pre-warm it before `start()`.
+    pub fn assets(&self) -> &Arc<AssetManager> {
+        &self.assets
+    }
+
+    /// This runtime's font registry. Embedders register project fonts
+    /// ([`FontRegistry::register`]) before `start()` so processor configs
+    /// can name them.
+    pub fn fonts(&self) -> &Arc<FontRegistry> {
+        &self.fonts
+    }
+
     /// Path of the JSONL log file this runtime is writing to, if any.
     /// Returns `None` on platforms where the logging pathway is not
     /// installed, or when the caller opted out of JSONL output.
@@ -394,34 +537,28 @@ impl Runner {
         let config_json =
             serde_json::to_value(&config).map_err(|e| crate::core::Error::Config(e.to_string()))?;
 
-        // Update config in graph and queue operation
-        self.compiler.scope(|graph, tx| {
-            if let Some(processor) = graph.traversal_mut().v(processor_id).first_mut() {
-                processor.set_config(config_json);
-            }
-
-            tx.log(PendingOperation::UpdateProcessorConfig(
-                processor_id.clone(),
-            ));
-        });
-
-        // Publish event
-        PUBSUB.publish(
-            topics::RUNTIME_GLOBAL,
-            &Event::RuntimeGlobal(RuntimeEvent::ProcessorConfigDidChange {
+        if let Some(previous) = self.replace_processor_config(processor_id, config_json.clone()) {
+            self.record_graph_edit(crate::core::graph_edit_history::GraphEdit::UpdateConfig {
                 processor_id: processor_id.clone(),
-            }),
-        );
-
-        // Notify listeners that graph changed (triggers commit via GraphChangeListener)
-        PUBSUB.publish(
-            topics::RUNTIME_GLOBAL,
-            &Event::RuntimeGlobal(RuntimeEvent::GraphDidChange),
-        );
+                previous,
+                config: config_json,
+            });
+        }
 
         Ok(())
     }
 
+    /// Swap `config_json` into the graph and queue the config update,
+    /// returning the config it replaced (`None` for an unknown processor).
+    /// Not recorded in the edit history.
+    pub(crate) fn replace_processor_config(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        config_json: serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        replace_processor_config_impl(&self.compiler, processor_id, config_json)
+    }
+
     // =========================================================================
     // Module Loading — see `core/runtime/module_loader/` for the impl.
     // =========================================================================
@@ -448,6 +585,10 @@ impl Runner {
             );
         }
 
+        // Refuse to run a graph with a required input nothing feeds: its
+        // processor would spin up waiting on data that never arrives.
+        self.validate_topology()?;
+
         *self.status.lock() = RuntimeStatus::Starting;
         tracing::info!("[start] Starting runtime");
         PUBSUB.publish(
@@ -460,7 +601,9 @@ impl Runner {
         // before NSApplication configuration changes thread behavior.
         // Always create fresh context on start - enables tracking per session.
         tracing::info!("[start] Initializing GPU context...");
-        let gpu = GpuContext::init_for_platform_sync()?;
+        let gpu = GpuContext::init_for_platform_with_texture_pool_config(
+            self.texture_pool_config.lock().clone(),
+        )?;
         tracing::info!("[start] GPU context initialized");
 
         // Initialize SurfaceStore for cross-process GPU surface sharing (macOS only)
@@ -587,6 +730,8 @@ impl Runner {
             self.tokio_runtime_variant.handle(),
             iceoryx2_node,
             Arc::clone(&audio_clock),
+            Arc::clone(&self.assets),
+            Arc::clone(&self.fonts),
             #[cfg(target_os = "linux")]
             self.surface_socket_path.clone(),
         ));
@@ -608,12 +753,62 @@ impl Runner {
         // Mark runtime as started so commit will actually compile
         *self.status.lock() = RuntimeStatus::Started;
 
+        // Roll per-link frame-drop windows forward and surface congestion.
+        self.tasks.register(
+            "link_drop_sampler",
+            super::link_drop_sampler::spawn_link_drop_sampler(
+                &self.tokio_runtime_variant.handle(),
+                Arc::clone(&self.compiler),
+                Arc::clone(&self.status),
+            ),
+        );
+
+        // Mirror GPU texture pool occupancy onto the graph and surface pressure.
+        self.tasks.register(
+            "gpu_pool_sampler",
+            super::gpu_pool_sampler::spawn_gpu_pool_sampler(
+                &self.tokio_runtime_variant.handle(),
+                Arc::clone(&self.compiler),
+                runtime_ctx.gpu.clone(),
+                Arc::clone(&self.status),
+            ),
+        );
+
+        // Publish DeviceConnected / DeviceDisconnected as hardware comes and goes.
+        #[cfg(target_os = "linux")]
+        self.tasks.register(
+            "device_monitor",
+            super::device_monitor::spawn_device_monitor(
+                &self.tokio_runtime_variant.handle(),
+                Arc::clone(&self.status),
+            ),
+        );
+
         // Compile any pending changes directly (includes Phase 4: START)
         // This ensures all queued operations are processed before start() returns.
         // After this, GraphChangeListener handles commits asynchronously.
         tracing::info!("[start] Committing pending graph operations");
         self.compiler.commit(&runtime_ctx)?;
 
+        // Apply cron schedules now that every processor has its pause gate.
+        self.tasks.register(
+            "processor_scheduler",
+            super::processor_scheduler::spawn_processor_scheduler(
+                &self.tokio_runtime_variant.handle(),
+                Arc::downgrade(self),
+                Arc::clone(&self.status),
+            ),
+        );
+        self.tasks.register(
+            "midi_mapper",
+            super::midi_mapper::spawn_midi_mapper(
+                &self.tokio_runtime_variant.handle(),
+                Arc::clone(&self.compiler),
+                Arc::clone(&self.midi_mappings),
+                Arc::clone(&self.status),
+            ),
+        );
+
         tracing::info!("[start] Runtime started (platform verified)");
         PUBSUB.publish(
             topics::RUNTIME_GLOBAL,
@@ -633,6 +828,12 @@ impl Runner {
             &Event::RuntimeGlobal(RuntimeEvent::RuntimeStopping),
         );
 
+        self.tasks.abort_all();
+
+        // Release outbound federated links while their processors still exist,
+        // so each remote runtime drops its half before ours goes away.
+        self.disconnect_all_remote_links();
+
         // Queue removal of all processors and commit
         let runtime_ctx = self.runtime_context.lock().clone();
         let processor_count = self.compiler.scope(|graph, tx| {
@@ -798,6 +999,244 @@ impl Runner {
         })
     }
 
+    /// Bypass a reactive processor: while set, its thread forwards each
+    /// input frame unchanged to the output port of the same schema instead
+    /// of calling `process()`. Inputs with no such output are dropped.
+    /// Other execution modes have no per-frame hook to bypass and are
+    /// rejected; clearing the bypass is accepted for any processor.
+    pub fn set_processor_bypassed(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        bypassed: bool,
+    ) -> Result<()> {
+        self.compiler.scope(|graph, _tx| {
+            let node = graph
+                .traversal()
+                .v(processor_id)
+                .first()
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+
+            let reactive = PROCESSOR_REGISTRY
+                .descriptor(node.processor_type())
+                .is_some_and(|descriptor| descriptor.execution.is_reactive());
+            if bypassed && !reactive {
+                return Err(Error::Runtime(format!(
+                    "Processor '{}' is not reactive and cannot be bypassed",
+                    processor_id
+                )));
+            }
+
+            let bypass_gate = node.get::<ProcessorBypassGateComponent>().ok_or_else(|| {
+                Error::Runtime(format!(
+                    "Processor '{}' has no ProcessorBypassGate",
+                    processor_id
+                ))
+            })?;
+
+            if bypass_gate.is_bypassed() == bypassed {
+                return Ok(());
+            }
+            bypass_gate.set_bypassed(bypassed);
+
+            let status = if bypassed {
+                ProcessorEvent::Bypassed
+            } else {
+                ProcessorEvent::BypassCleared
+            };
+            let event = Event::processor(processor_id, status);
+            PUBSUB.publish(&event.topic(), &event);
+
+            if bypassed {
+                tracing::info!("[{}] Processor bypassed", processor_id);
+            } else {
+                tracing::info!("[{}] Processor bypass cleared", processor_id);
+            }
+            Ok(())
+        })
+    }
+
+    /// Check if a specific processor is bypassed.
+    pub fn is_processor_bypassed(&self, processor_id: &ProcessorUniqueId) -> Result<bool> {
+        self.compiler.scope(|graph, _tx| {
+            let node = graph
+                .traversal()
+                .v(processor_id)
+                .first()
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+
+            let bypass_gate = node
+                .get::<ProcessorBypassGateComponent>()
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))?;
+
+            Ok(bypass_gate.is_bypassed())
+        })
+    }
+
+    // =========================================================================
+    // Scheduled Parameter Changes
+    // =========================================================================
+
+    /// Set one config parameter of a running processor at a media-clock
+    /// time, stepping or ramping to it; see
+    /// [`crate::core::parameter_automation`].
+    ///
+    /// The change is delivered by the processor's own thread right before
+    /// its first `process()` at or after `at_ns`, and the graph's copy of
+    /// the config follows. Not recorded in the edit history.
+    pub fn set_parameter(&self, change: ParameterChange) -> Result<()> {
+        change.validate()?;
+        let parameter_port = self.compiler.scope(|graph, _tx| {
+            let node = graph
+                .traversal()
+                .v(&change.processor_id)
+                .first()
+                .ok_or_else(|| Error::ProcessorNotFound(change.processor_id.to_string()))?;
+            node.get::<ProcessorParameterPortComponent>()
+                .cloned()
+                .ok_or_else(|| {
+                    Error::Runtime(format!(
+                        "Processor '{}' is not running",
+                        change.processor_id
+                    ))
+                })
+        })?;
+
+        tracing::debug!(
+            "[{}] Scheduled '{}' = {} at {:?} ({:?})",
+            change.processor_id,
+            change.parameter,
+            change.value,
+            change.at_ns,
+            change.interpolation
+        );
+        parameter_port.schedule(change, MediaClock::now().as_nanos() as i64);
+        Ok(())
+    }
+
+    // =========================================================================
+    // Per-Processor Schedules
+    // =========================================================================
+
+    /// Attach a cron schedule to a processor.
+    ///
+    /// A processor may carry several schedules (typically a `start` and a
+    /// `stop`). Schedules added while the runtime is started take effect
+    /// from the next minute boundary. A `bypass` schedule is only accepted
+    /// on a reactive processor; see [`Self::set_processor_bypassed`].
+    pub fn add_processor_schedule(
+        &self,
+        processor_id: &ProcessorUniqueId,
+        schedule: ProcessorSchedule,
+    ) -> Result<()> {
+        let processor_type = self.compiler.scope(|graph, _tx| {
+            graph
+                .traversal()
+                .v(processor_id)
+                .first()
+                .map(|node| node.processor_type().clone())
+                .ok_or_else(|| Error::ProcessorNotFound(processor_id.to_string()))
+        })?;
+        if schedule.action == ProcessorScheduleAction::Bypass
+            && !PROCESSOR_REGISTRY
+                .descriptor(&processor_type)
+                .is_some_and(|descriptor| descriptor.execution.is_reactive())
+        {
+            return Err(Error::Config(format!(
+                "Processor '{}' is not reactive and cannot be scheduled to bypass",
+                processor_id
+            )));
+        }
+
+        tracing::info!(
+            "[{}] Scheduled '{}' on '{}' ({})",
+            processor_id,
+            schedule.action,
+            schedule.cron,
+            schedule.timezone
+        );
+        self.processor_schedules
+            .lock()
+            .push((processor_id.clone(), schedule));
+        Ok(())
+    }
+
+    /// Remove every schedule attached to a processor.
+    pub fn clear_processor_schedules(&self, processor_id: &ProcessorUniqueId) {
+        self.processor_schedules
+            .lock()
+            .retain(|(id, _)| id != processor_id);
+    }
+
+    /// Schedules attached to a processor, in insertion order.
+    pub fn processor_schedules(&self, processor_id: &ProcessorUniqueId) -> Vec<ProcessorSchedule> {
+        self.processor_schedules
+            .lock()
+            .iter()
+            .filter(|(id, _)| id == processor_id)
+            .map(|(_, schedule)| schedule.clone())
+            .collect()
+    }
+
+    // =========================================================================
+    // Automatic Converter Insertion
+    // =========================================================================
+
+    /// Turn automatic converter insertion on or off.
+    ///
+    /// While on, a `connect()` between a producer and consumer whose port
+    /// schemas are concrete and differ, or whose declared frame formats
+    /// differ, wires through the converter of the first matching
+    /// [`AutoConverterRule`] instead of linking directly. Pairs without a
+    /// rule connect as before. Links wired earlier are not revisited.
+    pub fn set_auto_convert(&self, enabled: bool) {
+        self.auto_convert.store(enabled, Ordering::SeqCst);
+        tracing::info!(
+            "[auto_convert] Automatic converter insertion {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Whether automatic converter insertion is on.
+    pub fn auto_convert(&self) -> bool {
+        self.auto_convert.load(Ordering::SeqCst)
+    }
+
+    /// Register a converter for [`Self::set_auto_convert`] to insert.
+    /// Rules are matched in registration order.
+    pub fn register_auto_converter(&self, rule: AutoConverterRule) {
+        tracing::debug!(
+            "[auto_convert] Registered {} for {} -> {}",
+            rule.converter_type,
+            rule.producer_schema,
+            rule.consumer_schema
+        );
+        self.auto_converters.lock().push(rule);
+    }
+
+    // =========================================================================
+    // GPU Texture Pool
+    // =========================================================================
+
+    /// Configure the GPU texture pool: per-resolution bucket limits,
+    /// textures to prewarm, and how idle buckets are evicted when the
+    /// pipeline changes resolution. Takes effect on the next
+    /// [`Self::start`], which creates a fresh pool.
+    pub fn set_texture_pool_config(&self, config: TexturePoolConfig) {
+        tracing::info!(
+            "[texture_pool] max {} per bucket, {} bucket limit(s), {} prewarm(s), eviction {:?}",
+            config.max_pool_size_per_bucket,
+            config.bucket_limits.len(),
+            config.prewarm.len(),
+            config.eviction_policy
+        );
+        *self.texture_pool_config.lock() = config;
+    }
+
+    /// The texture pool configuration the next [`Self::start`] uses.
+    pub fn texture_pool_config(&self) -> TexturePoolConfig {
+        self.texture_pool_config.lock().clone()
+    }
+
     // =========================================================================
     // Runtime-level Pause/Resume (all processors)
     // =========================================================================
@@ -987,6 +1426,12 @@ impl Runner {
     }
 
     /// Connect two ports.
+    ///
+    /// A concrete producer/consumer schema mismatch that no auto-converter
+    /// bridges fails with [`Error::Topology`], as does a link that would
+    /// close a deadlocking cycle. Pass
+    /// [`ConnectOptions::loose`](super::ConnectOptions::loose) to
+    /// [`Self::connect_with`] to wire a mismatched link anyway.
     pub fn connect(
         &self,
         from: impl Into<OutputLinkPortRef>,
@@ -1012,6 +1457,27 @@ impl Runner {
         })
     }
 
+    /// Structural problems in the current graph that would surface only at
+    /// runtime — today, required input ports with no incoming link.
+    /// Deadlocking cycles are rejected up front by [`Self::connect`].
+    pub fn analyze_topology(&self) -> Vec<TopologyDiagnostic> {
+        self.compiler
+            .scope(|graph, _tx| TopologyAnalyzer::new(graph).unsatisfied_required_inputs())
+    }
+
+    /// [`Self::analyze_topology`] as a hard check: fails with
+    /// [`Error::Topology`] carrying every diagnostic. [`Self::start`] runs
+    /// it before bringing anything up; call it directly to check a graph
+    /// without starting it.
+    pub fn validate_topology(&self) -> Result<()> {
+        let diagnostics = self.analyze_topology();
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Topology { diagnostics })
+        }
+    }
+
     // =========================================================================
     // Graph Snapshot Save / Load
     // =========================================================================
@@ -1022,7 +1488,10 @@ impl Runner {
     /// connections are created by resolving aliases to runtime IDs.
     /// The snapshot's `name` is stashed on the runtime so a subsequent
     /// [`Self::save_graph_snapshot`] re-emits it without caller
-    /// bookkeeping.
+    /// bookkeeping. Its edit history replaces the runtime's, so the load
+    /// itself is not an undoable step. A snapshot saved with automatic
+    /// converter insertion on turns it on before wiring, so its converters
+    /// are re-inserted.
     ///
     /// Assumes every referenced processor type is already registered (it
     /// validates and fails on an unregistered type). For the turnkey case —
@@ -1055,6 +1524,9 @@ impl Runner {
         }
 
         // Phase 2: Create connections, resolving aliases
+        if snapshot.auto_convert {
+            self.set_auto_convert(true);
+        }
         for conn_def in &snapshot.connections {
             let from = conn_def.parse_from()?;
             let to = conn_def.parse_to()?;
@@ -1066,9 +1538,12 @@ impl Runner {
                 Error::GraphError(format!("Unknown processor alias: '{}'", to.alias))
             })?;
 
-            self.connect(
+            // Validation reports a schema mismatch as a warning, so the
+            // load wires it the same way.
+            self.connect_with(
                 OutputLinkPortRef::new(from_id, from.port_name),
                 InputLinkPortRef::new(to_id, to.port_name),
+                super::ConnectOptions::loose(),
             )?;
 
             tracing::info!(
@@ -1080,8 +1555,29 @@ impl Runner {
             );
         }
 
+        // Phase 3: Attach schedules, resolving aliases
+        for schedule_def in &snapshot.schedules {
+            let processor_id = alias_to_id.get(&schedule_def.processor).ok_or_else(|| {
+                Error::GraphError(format!(
+                    "Unknown processor alias: '{}'",
+                    schedule_def.processor
+                ))
+            })?;
+            self.add_processor_schedule(processor_id, schedule_def.to_processor_schedule()?)?;
+        }
+
         *self.pipeline_name.lock() = snapshot.name.clone();
 
+        // Phase 4: Resume the snapshot's edit history in place of the edits
+        // the load itself just recorded, resolving its aliases.
+        {
+            let mut graph_edits = self.graph_edits.lock();
+            let mut edit_history = snapshot.edit_history.clone();
+            edit_history.set_capacity(graph_edits.capacity());
+            edit_history.map_processor_ids(|alias| alias_to_id.get(alias.as_str()).cloned());
+            *graph_edits = edit_history;
+        }
+
         if let Some(name) = &snapshot.name {
             tracing::info!("Loaded pipeline: {}", name);
         }
@@ -1122,55 +1618,67 @@ impl Runner {
         &self,
         snapshot: &crate::core::graph_snapshot::GraphSnapshot,
     ) -> Result<()> {
-        use crate::core::processors::PROCESSOR_REGISTRY;
-        use crate::core::runtime::module_loader::{BuildPolicy, Strategy};
-        use streamlib_idents::{ModuleIdent, SemVerRange};
-
         // NB: do NOT validate() here — validate() rejects unregistered processor
         // types, which is exactly what this pass resolves. Reading the structured
         // processor_type fields needs no validation; load_graph_snapshot below
         // validates (structure + registration) once the modules are loaded.
+        for module in unregistered_snapshot_modules(snapshot) {
+            self.resolve_snapshot_module(module).await?;
+        }
 
-        // The unique packages whose processor types aren't already registered
-        // (e.g. the api-server type is registered in-process at boot). One
-        // add_module per package — a snapshot may reference several
-        // processors from the same package.
-        let mut seen: std::collections::HashSet<streamlib_idents::PackageRef> =
-            std::collections::HashSet::new();
-        let mut to_load: Vec<ModuleIdent> = Vec::new();
-        for proc_def in &snapshot.processors {
-            let ty = &proc_def.processor_type;
-            if PROCESSOR_REGISTRY.port_info(ty).is_some() {
-                continue;
-            }
-            let module = ModuleIdent::new(ty.org.clone(), ty.package.clone(), SemVerRange::Any);
-            if seen.insert(module.package_ref()) {
-                to_load.push(module);
+        self.load_graph_snapshot(snapshot)
+    }
+
+    /// Dry-run counterpart of [`Self::load_graph_snapshot_with_resolving`]:
+    /// resolves and loads the snapshot's unregistered modules the same way,
+    /// then runs [`GraphSnapshot::check`] instead of loading. Every module
+    /// that fails to resolve is reported as an error alongside the check's
+    /// own findings. The graph is left untouched and nothing is started —
+    /// this backs `streamlib validate`.
+    ///
+    /// [`GraphSnapshot::check`]: crate::core::graph_snapshot::GraphSnapshot::check
+    pub async fn check_graph_snapshot_with_resolving(
+        &self,
+        snapshot: &crate::core::graph_snapshot::GraphSnapshot,
+    ) -> crate::core::graph_snapshot::GraphSnapshotCheck {
+        let mut resolution_errors = Vec::new();
+        for module in unregistered_snapshot_modules(snapshot) {
+            if let Err(e) = self.resolve_snapshot_module(module).await {
+                resolution_errors.push(e);
             }
         }
 
-        for module in to_load {
-            tracing::info!(
-                "Snapshot: resolving module '{}' by version (linked checkout if a streamlib link is active, else the configured package source)",
-                module.package_ref()
-            );
-            self.add_module_with(
-                module.clone(),
-                Strategy::ByVersion {
-                    version_req: SemVerRange::Any,
-                    build: BuildPolicy::IfStale,
-                },
-            )
-            .await
-            .map_err(|e| {
-                Error::GraphError(format!(
-                    "snapshot module resolution failed for '{}': {e}",
-                    module.package_ref()
-                ))
-            })?;
-        }
+        let mut check = snapshot.check();
+        resolution_errors.append(&mut check.errors);
+        check.errors = resolution_errors;
+        check
+    }
 
-        self.load_graph_snapshot(snapshot)
+    /// Resolve and load one snapshot-referenced module by version, as
+    /// [`Self::load_graph_snapshot_with_resolving`] does for each package.
+    async fn resolve_snapshot_module(&self, module: streamlib_idents::ModuleIdent) -> Result<()> {
+        use crate::core::runtime::module_loader::{BuildPolicy, Strategy};
+        use streamlib_idents::SemVerRange;
+
+        tracing::info!(
+            "Snapshot: resolving module '{}' by version (linked checkout if a streamlib link is active, else the configured package source)",
+            module.package_ref()
+        );
+        self.add_module_with(
+            module.clone(),
+            Strategy::ByVersion {
+                version_req: SemVerRange::Any,
+                build: BuildPolicy::IfStale,
+            },
+        )
+        .await
+        .map_err(|e| {
+            Error::GraphError(format!(
+                "snapshot module resolution failed for '{}': {e}",
+                module.package_ref()
+            ))
+        })?;
+        Ok(())
     }
 
     /// Path variant of [`Runner::load_graph_snapshot_with_resolving`].
@@ -1208,12 +1716,19 @@ impl Runner {
     /// display name differs from its processor type's PascalCase
     /// short name — i.e. only when a caller explicitly overrode the
     /// default — so the user-intent distinction survives round-trips.
+    ///
+    /// The runtime's graph edit history is included, so an editor session
+    /// saved here resumes with its undo / redo stacks on load.
+    ///
+    /// Auto-inserted converters are left out; their connections are saved
+    /// as asked for, with [`Self::auto_convert`], so loading re-inserts them.
     pub fn save_graph_snapshot(&self) -> Result<crate::core::graph_snapshot::GraphSnapshot> {
         use std::collections::HashMap;
 
         use crate::core::graph_snapshot::{
             ConnectionDefinition, GraphSnapshot, ProcessorDefinition,
         };
+        use crate::core::processor_schedule::ScheduleDefinition;
 
         self.compiler.scope(|graph, _tx| {
             // Deterministic aliasing — camelCase the type's PascalCase
@@ -1223,7 +1738,17 @@ impl Runner {
             let mut id_to_alias: HashMap<String, String> = HashMap::new();
             let mut processors: Vec<ProcessorDefinition> = Vec::new();
 
+            // Auto-inserted converters are saved away: their links collapse
+            // back into the connection the caller asked for, which reloading
+            // re-derives under the saved `auto_convert` flag.
+            let mut converter_upstreams: HashMap<String, OutputLinkPortRef> = HashMap::new();
+
             for node in graph.traversal().v(()).iter() {
+                if let Some(converter) = node.get::<AutoConverterComponent>() {
+                    converter_upstreams.insert(node.id.to_string(), converter.upstream.clone());
+                    continue;
+                }
+
                 let short = node.processor_type.r#type.as_str();
                 let base = pascal_to_camel(short);
 
@@ -1245,19 +1770,27 @@ impl Runner {
                     processor_type: node.processor_type.clone(),
                     config: node.config.clone().unwrap_or(serde_json::Value::Null),
                     display_name,
+                    gpu_adapter: node.get::<GpuAdapterAffinityComponent>().map(|a| a.0),
                 });
             }
 
             let mut connections: Vec<ConnectionDefinition> = Vec::new();
             for link in graph.traversal().e(()).iter() {
-                let from_alias = id_to_alias
+                if converter_upstreams.contains_key(link.target.processor_id.as_str()) {
+                    continue;
+                }
+                let source = converter_upstreams
                     .get(link.source.processor_id.as_str())
-                    .ok_or_else(|| {
-                        Error::GraphError(format!(
-                            "Link source processor '{}' missing from snapshot alias map",
-                            link.source.processor_id
-                        ))
-                    })?;
+                    .unwrap_or(&link.source);
+                let from_alias =
+                    id_to_alias
+                        .get(source.processor_id.as_str())
+                        .ok_or_else(|| {
+                            Error::GraphError(format!(
+                                "Link source processor '{}' missing from snapshot alias map",
+                                source.processor_id
+                            ))
+                        })?;
                 let to_alias = id_to_alias
                     .get(link.target.processor_id.as_str())
                     .ok_or_else(|| {
@@ -1267,15 +1800,41 @@ impl Runner {
                         ))
                     })?;
                 connections.push(ConnectionDefinition {
-                    from: format!("{}.{}", from_alias, link.source.port_name),
+                    from: format!("{}.{}", from_alias, source.port_name),
                     to: format!("{}.{}", to_alias, link.target.port_name),
                 });
             }
 
+            // Schedules whose processor has since been removed are dropped.
+            let schedules: Vec<ScheduleDefinition> = self
+                .processor_schedules
+                .lock()
+                .iter()
+                .filter_map(|(processor_id, schedule)| {
+                    let alias = id_to_alias.get(processor_id.as_str())?;
+                    Some(ScheduleDefinition::from_processor_schedule(
+                        alias.clone(),
+                        schedule,
+                    ))
+                })
+                .collect();
+
+            // Edit history references live processors by alias, like the
+            // rest of the snapshot; removed processors keep their old ids.
+            let mut edit_history = self.graph_edits.lock().clone();
+            edit_history.map_processor_ids(|id| {
+                id_to_alias
+                    .get(id.as_str())
+                    .map(|alias| ProcessorUniqueId::from(alias.as_str()))
+            });
+
             Ok(GraphSnapshot {
                 name: self.pipeline_name.lock().clone(),
                 processors,
                 connections,
+                schedules,
+                auto_convert: self.auto_convert(),
+                edit_history,
             })
         })
     }
@@ -1307,6 +1866,32 @@ impl Runner {
     }
 }
 
+/// The unique packages whose processor types a snapshot references but the
+/// registry doesn't know yet (e.g. the api-server type is registered
+/// in-process at boot). One entry per package — a snapshot may reference
+/// several processors from the same package.
+fn unregistered_snapshot_modules(
+    snapshot: &crate::core::graph_snapshot::GraphSnapshot,
+) -> Vec<streamlib_idents::ModuleIdent> {
+    use crate::core::processors::PROCESSOR_REGISTRY;
+    use streamlib_idents::{ModuleIdent, SemVerRange};
+
+    let mut seen: std::collections::HashSet<streamlib_idents::PackageRef> =
+        std::collections::HashSet::new();
+    let mut to_load: Vec<ModuleIdent> = Vec::new();
+    for proc_def in &snapshot.processors {
+        let ty = &proc_def.processor_type;
+        if PROCESSOR_REGISTRY.port_info(ty).is_some() {
+            continue;
+        }
+        let module = ModuleIdent::new(ty.org.clone(), ty.package.clone(), SemVerRange::Any);
+        if seen.insert(module.package_ref()) {
+            to_load.push(module);
+        }
+    }
+    to_load
+}
+
 /// PascalCase → camelCase for snapshot alias generation.
 ///
 /// `CameraProcessor → cameraProcessor`; `BGRAFileSource → bGRAFileSource`
@@ -1329,23 +1914,14 @@ fn pascal_to_camel(short: &str) -> String {
 #[cfg(target_os = "linux")]
 fn bring_up_surface_service(
     runtime_id: &RuntimeUniqueId,
+    federation_handler: Arc<dyn super::federation::FederationLinkHandler>,
 ) -> Result<(
     Arc<Mutex<Option<crate::linux::surface_share::UnixSocketSurfaceService>>>,
     std::path::PathBuf,
 )> {
     use crate::linux::surface_share::{SurfaceShareState, UnixSocketSurfaceService};
 
-    let xdg_runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").ok_or_else(|| {
-        Error::Runtime(
-            "XDG_RUNTIME_DIR is not set. The runtime needs a writable directory \
-             for its per-runtime surface-sharing socket — typically /run/user/<uid>. \
-             Set XDG_RUNTIME_DIR or run under a session manager that provides it."
-                .to_string(),
-        )
-    })?;
-
-    let socket_path =
-        std::path::PathBuf::from(xdg_runtime_dir).join(format!("streamlib-{}.sock", runtime_id));
+    let socket_path = super::federation::broker_socket_path(runtime_id.as_str())?;
 
     if socket_path.exists() {
         match std::os::unix::net::UnixStream::connect(&socket_path) {
@@ -1375,7 +1951,8 @@ fn bring_up_surface_service(
         }
     }
 
-    let mut service = UnixSocketSurfaceService::new(SurfaceShareState::new(), socket_path.clone());
+    let mut service = UnixSocketSurfaceService::new(SurfaceShareState::new(), socket_path.clone())
+        .with_federation_handler(federation_handler);
     service.start().map_err(|e| {
         Error::Runtime(format!(
             "Failed to start runtime-internal surface-sharing service at {}: {}",
@@ -0,0 +1,76 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! The periodic tasks a started runtime runs on its tokio handle — the
+//! link-drop and GPU-pool samplers, the device monitor, the processor
+//! scheduler and the MIDI mapper.
+//!
+//! Each task also exits on its own once it sees the runtime stopped, but
+//! only on its next tick: a `stop()` → `start()` inside one tick would
+//! leave the old task running beside the new one. [`Runner::start`]
+//! registers every task here and [`Runner::stop`] aborts them all.
+//!
+//! [`Runner::start`]: super::Runner::start
+//! [`Runner::stop`]: super::Runner::stop
+
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+
+/// Handles of the running periodic tasks, by name.
+#[derive(Default)]
+pub(crate) struct RuntimeTasks {
+    tasks: Mutex<Vec<(&'static str, JoinHandle<()>)>>,
+}
+
+impl RuntimeTasks {
+    /// Track `task` as `name`, aborting a task still tracked under that
+    /// name.
+    pub(crate) fn register(&self, name: &'static str, task: JoinHandle<()>) {
+        let mut tasks = self.tasks.lock();
+        if let Some(index) = tasks.iter().position(|(tracked, _)| *tracked == name) {
+            let (_, previous) = tasks.swap_remove(index);
+            previous.abort();
+        }
+        tasks.push((name, task));
+    }
+
+    /// Abort every tracked task.
+    pub(crate) fn abort_all(&self) {
+        for (name, task) in self.tasks.lock().drain(..) {
+            task.abort();
+            tracing::debug!("[runtime_tasks] aborted {}", name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registering_a_name_again_aborts_the_old_task_and_abort_all_stops_the_rest() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let tasks = RuntimeTasks::default();
+            let first = tokio::spawn(std::future::pending::<()>());
+            let first_abort = first.abort_handle();
+            tasks.register("sampler", first);
+            tasks.register("sampler", tokio::spawn(std::future::pending::<()>()));
+            let other = tokio::spawn(std::future::pending::<()>());
+            let other_abort = other.abort_handle();
+            tasks.register("monitor", other);
+            tokio::task::yield_now().await;
+            assert!(first_abort.is_finished());
+            assert!(!other_abort.is_finished());
+            assert_eq!(tasks.tasks.lock().len(), 2);
+
+            tasks.abort_all();
+            tokio::task::yield_now().await;
+            assert!(other_abort.is_finished());
+            assert!(tasks.tasks.lock().is_empty());
+        });
+    }
+}
@@ -110,6 +110,12 @@ impl TapSubscription {
         self.receiver.recv().await
     }
 
+    /// Take the next bag already forwarded, without waiting. `None` when
+    /// none is queued.
+    pub fn try_recv(&mut self) -> Option<Vec<u8>> {
+        self.receiver.try_recv().ok()
+    }
+
     /// Bags this tap dropped because the async consumer fell behind and the
     /// bounded forward channel was full. A read-only tap drops rather than
     /// back-pressuring the source, so a non-zero count means the observer is
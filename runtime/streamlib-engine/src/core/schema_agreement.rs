@@ -18,8 +18,9 @@
 //! schemas are a mismatch. The default posture is [loose-but-observed][Loose] —
 //! a mismatch is a `tracing::warn`, not a hard error — matching the #1345 design
 //! (a graph that ran yesterday must not stop running because a port was
-//! re-typed). A safety-critical wiring site opts into [`Strict`][Strict] to turn
-//! that warn into a typed [`Error::SchemaIdentMismatch`] at the wiring site.
+//! re-typed), so loading a saved graph stays loose. A new link made with
+//! `Runner::connect` is checked [`Strict`][Strict], turning that warn into a
+//! typed error at the wiring site.
 //!
 //! [`SchemaIdentWire`]: crate::iceoryx2::SchemaIdentWire
 //! [`PortSchemaSpec`]: streamlib_processor_schema::PortSchemaSpec
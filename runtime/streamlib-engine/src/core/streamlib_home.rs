@@ -31,8 +31,10 @@ use streamlib_idents::PackageRef;
 /// │                                     #   (installed_package_slot_dir); each
 /// │                                     #   Python slot carries its own `.venv/`
 /// └── .streamlib/                       # generated working tree — get_streamlib_data_dir()
+///     ├── assets/                       # content-addressed asset cache (AssetManager)
 ///     ├── cache/
 ///     │   └── uv/                        # uv PyPI cache      (Python packages only)
+///     ├── fonts/                        # project-pinned fonts, scanned first (FontRegistry)
 ///     ├── logs/<runtime_id>-<ts>.jsonl  # per-runtime JSONL logs
 ///     └── resolver-cache/               # git / URL checkouts (Strategy::Git / Url)
 /// ```
@@ -104,6 +104,29 @@ impl crate::core::ManualProcessor for MockInputOnlyProcessor::Processor {
     }
 }
 
+/// Reactive counterpart of [`MockProcessor`], plus an optional `feedback`
+/// input — the shape topology tests need to tell a deadlocking cycle from a
+/// feedback loop.
+#[crate::processor(
+    "@tatolab/streamlib-engine/TestMockReactiveProcessor",
+    execution = reactive,
+    input("in1", any),
+    input("in2", any),
+    input("feedback", any, optional = true),
+    output("out1", any),
+    output("out2", any),
+)]
+pub(crate) struct MockReactiveProcessor;
+
+impl crate::core::ReactiveProcessor for MockReactiveProcessor::Processor {
+    fn process(
+        &mut self,
+        _ctx: &crate::core::context::RuntimeContextLimitedAccess<'_>,
+    ) -> crate::core::error::Result<()> {
+        Ok(())
+    }
+}
+
 /// Register all engine-internal test mock processors with the global
 /// `PROCESSOR_REGISTRY`. Idempotent — safe to call from every test
 /// fixture that builds a graph against `lookup_registered_ident` or
@@ -114,5 +137,6 @@ pub(crate) fn ensure_test_mocks_registered() {
         PROCESSOR_REGISTRY.register::<MockProcessor::Processor>();
         PROCESSOR_REGISTRY.register::<MockOutputOnlyProcessor::Processor>();
         PROCESSOR_REGISTRY.register::<MockInputOnlyProcessor::Processor>();
+        PROCESSOR_REGISTRY.register::<MockReactiveProcessor::Processor>();
     });
 }
@@ -0,0 +1,385 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Comparing a rendered frame against its reference, with tolerance.
+//!
+//! GPU paths are rarely bit-exact across drivers, so a comparison counts
+//! the pixels whose worst channel moved by more than a threshold and
+//! measures the PSNR over all of them; a [`Tolerance`] bounds both. A
+//! [`FrameDiff`] prints as a summary plus a coarse map of where the
+//! differing pixels are.
+
+use std::fmt;
+
+use super::golden_image::GoldenImage;
+
+/// Map cells across; rows follow the frame's aspect ratio.
+const HEATMAP_COLUMNS: u32 = 48;
+const HEATMAP_MAX_ROWS: u32 = 24;
+
+/// Cell shading by the fraction of its pixels that differ.
+const HEATMAP_SHADES: [(f64, char); 4] = [(0.01, '.'), (0.05, ':'), (0.25, '+'), (1.0, '#')];
+
+/// How far a rendered frame may stray from its reference.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// A pixel differs when any channel moved by more than this.
+    pub max_channel_delta: u8,
+    /// The fraction of pixels, 0–1, allowed to differ.
+    pub max_differing_fraction: f64,
+    /// The lowest PSNR, in dB, allowed over the whole frame.
+    pub min_psnr_db: f64,
+}
+
+impl Tolerance {
+    /// Bit-identical frames only.
+    pub const EXACT: Tolerance = Tolerance {
+        max_channel_delta: 0,
+        max_differing_fraction: 0.0,
+        min_psnr_db: f64::INFINITY,
+    };
+
+    /// Invisible differences: rounding and dithering from a different
+    /// driver or GPU, not a changed result.
+    pub const PERCEPTUAL: Tolerance = Tolerance {
+        max_channel_delta: 2,
+        max_differing_fraction: 0.001,
+        min_psnr_db: 45.0,
+    };
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::PERCEPTUAL
+    }
+}
+
+/// The largest difference found, where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorstPixel {
+    pub x: u32,
+    pub y: u32,
+    pub expected: [u8; 4],
+    pub actual: [u8; 4],
+}
+
+/// How a rendered frame differs from its reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameDiff {
+    pub expected_size: (u32, u32),
+    pub actual_size: (u32, u32),
+    /// Pixels whose worst channel moved by more than the tolerance's
+    /// threshold.
+    pub differing_pixels: u64,
+    pub total_pixels: u64,
+    /// The largest channel change anywhere.
+    pub max_channel_delta: u8,
+    /// Infinite for identical frames.
+    pub psnr_db: f64,
+    pub worst: Option<WorstPixel>,
+    /// Differing pixels per heat map cell over the cell's pixels, row-major.
+    heatmap: Vec<f64>,
+    heatmap_columns: u32,
+    tolerance: Tolerance,
+}
+
+impl FrameDiff {
+    /// Compare `actual` against `expected`.
+    pub fn compute(expected: &GoldenImage, actual: &GoldenImage, tolerance: Tolerance) -> Self {
+        let expected_size = (expected.width(), expected.height());
+        let actual_size = (actual.width(), actual.height());
+        let total_pixels = u64::from(expected.width()) * u64::from(expected.height());
+        if expected_size != actual_size {
+            return Self {
+                expected_size,
+                actual_size,
+                differing_pixels: total_pixels,
+                total_pixels,
+                max_channel_delta: u8::MAX,
+                psnr_db: 0.0,
+                worst: None,
+                heatmap: Vec::new(),
+                heatmap_columns: 0,
+                tolerance,
+            };
+        }
+
+        let (width, height) = expected_size;
+        let columns = HEATMAP_COLUMNS.min(width.max(1));
+        // Terminal cells are about twice as tall as wide.
+        let rows = (height as u64 * columns as u64 / (2 * width.max(1) as u64))
+            .clamp(1, u64::from(HEATMAP_MAX_ROWS.min(height.max(1)))) as u32;
+        let mut cell_differing = vec![0u64; (columns * rows) as usize];
+        let mut cell_total = vec![0u64; (columns * rows) as usize];
+
+        let mut differing_pixels = 0;
+        let mut max_channel_delta = 0u8;
+        let mut squared_error = 0u64;
+        let mut worst: Option<(u8, WorstPixel)> = None;
+        for y in 0..height {
+            let row = (u64::from(y) * u64::from(rows) / u64::from(height)) as u32;
+            for x in 0..width {
+                let a = expected.pixel(x, y);
+                let b = actual.pixel(x, y);
+                let mut pixel_delta = 0u8;
+                for channel in 0..4 {
+                    let delta = a[channel].abs_diff(b[channel]);
+                    pixel_delta = pixel_delta.max(delta);
+                    squared_error += u64::from(delta) * u64::from(delta);
+                }
+                let column = (u64::from(x) * u64::from(columns) / u64::from(width)) as u32;
+                let cell = (row * columns + column) as usize;
+                cell_total[cell] += 1;
+                if pixel_delta > tolerance.max_channel_delta {
+                    differing_pixels += 1;
+                    cell_differing[cell] += 1;
+                }
+                if pixel_delta > max_channel_delta {
+                    max_channel_delta = pixel_delta;
+                }
+                if pixel_delta > 0 && worst.is_none_or(|(d, _)| pixel_delta > d) {
+                    worst = Some((
+                        pixel_delta,
+                        WorstPixel {
+                            x,
+                            y,
+                            expected: a,
+                            actual: b,
+                        },
+                    ));
+                }
+            }
+        }
+
+        let samples = (total_pixels * 4).max(1) as f64;
+        let mse = squared_error as f64 / samples;
+        let psnr_db = if mse == 0.0 {
+            f64::INFINITY
+        } else {
+            10.0 * (255.0f64 * 255.0 / mse).log10()
+        };
+        let heatmap = cell_differing
+            .iter()
+            .zip(&cell_total)
+            .map(|(d, t)| if *t == 0 { 0.0 } else { *d as f64 / *t as f64 })
+            .collect();
+
+        Self {
+            expected_size,
+            actual_size,
+            differing_pixels,
+            total_pixels,
+            max_channel_delta,
+            psnr_db,
+            worst: worst.map(|(_, w)| w),
+            heatmap,
+            heatmap_columns: columns,
+            tolerance,
+        }
+    }
+
+    pub fn size_matches(&self) -> bool {
+        self.expected_size == self.actual_size
+    }
+
+    pub fn differing_fraction(&self) -> f64 {
+        if self.total_pixels == 0 {
+            0.0
+        } else {
+            self.differing_pixels as f64 / self.total_pixels as f64
+        }
+    }
+
+    /// Whether the frame is within the tolerance it was compared with.
+    pub fn passes(&self) -> bool {
+        self.size_matches()
+            && self.differing_fraction() <= self.tolerance.max_differing_fraction
+            && self.psnr_db >= self.tolerance.min_psnr_db
+    }
+
+    /// A picture of the differences: the reference in dim grey, differing
+    /// pixels in red scaled by how far they moved, pixels within the
+    /// threshold but not identical in blue. `None` when the sizes differ.
+    pub fn visualize(&self, expected: &GoldenImage, actual: &GoldenImage) -> Option<GoldenImage> {
+        if !self.size_matches() {
+            return None;
+        }
+        let (width, height) = self.expected_size;
+        let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let a = expected.pixel(x, y);
+                let b = actual.pixel(x, y);
+                let delta = (0..4).map(|c| a[c].abs_diff(b[c])).max().unwrap_or(0);
+                let grey = ((u16::from(a[0]) + u16::from(a[1]) + u16::from(a[2])) / 12) as u8;
+                let pixel = if delta > self.tolerance.max_channel_delta {
+                    [128u8.saturating_add(delta / 2), 0, 0, 255]
+                } else if delta > 0 {
+                    [grey, grey, 160, 255]
+                } else {
+                    [grey, grey, grey, 255]
+                };
+                rgba.extend_from_slice(&pixel);
+            }
+        }
+        GoldenImage::from_rgba(width, height, rgba).ok()
+    }
+}
+
+impl fmt::Display for FrameDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.size_matches() {
+            return write!(
+                f,
+                "frame is {}x{}, reference is {}x{}",
+                self.actual_size.0, self.actual_size.1, self.expected_size.0, self.expected_size.1
+            );
+        }
+        let psnr = if self.psnr_db.is_infinite() {
+            "inf".to_string()
+        } else {
+            format!("{:.1}", self.psnr_db)
+        };
+        writeln!(
+            f,
+            "{} of {} pixels ({:.3}%) off by more than {}; max channel delta {}; PSNR {} dB",
+            self.differing_pixels,
+            self.total_pixels,
+            self.differing_fraction() * 100.0,
+            self.tolerance.max_channel_delta,
+            self.max_channel_delta,
+            psnr
+        )?;
+        write!(
+            f,
+            "allowed: {:.3}% of pixels, PSNR >= {} dB",
+            self.tolerance.max_differing_fraction * 100.0,
+            self.tolerance.min_psnr_db
+        )?;
+        if let Some(worst) = &self.worst {
+            write!(
+                f,
+                "\nworst at ({}, {}): expected rgba{:?}, got rgba{:?}",
+                worst.x, worst.y, worst.expected, worst.actual
+            )?;
+        }
+        if self.differing_pixels == 0 || self.heatmap_columns == 0 {
+            return Ok(());
+        }
+
+        let (width, height) = self.expected_size;
+        let rows = self.heatmap.len() as u32 / self.heatmap_columns;
+        write!(
+            f,
+            "\nwhere ({}x{} px per cell; '.' <1%, ':' <5%, '+' <25%, '#' differing):",
+            width.div_ceil(self.heatmap_columns),
+            height.div_ceil(rows)
+        )?;
+        let border = "-".repeat(self.heatmap_columns as usize);
+        write!(f, "\n+{border}+")?;
+        for row in self.heatmap.chunks(self.heatmap_columns as usize) {
+            let line: String = row
+                .iter()
+                .map(|fraction| {
+                    if *fraction == 0.0 {
+                        ' '
+                    } else {
+                        HEATMAP_SHADES
+                            .iter()
+                            .find(|(limit, _)| fraction < limit)
+                            .map_or('#', |(_, shade)| *shade)
+                    }
+                })
+                .collect();
+            write!(f, "\n|{line}|")?;
+        }
+        write!(f, "\n+{border}+")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(width: u32, height: u32, value: u8) -> GoldenImage {
+        GoldenImage::from_rgba(
+            width,
+            height,
+            vec![value; width as usize * height as usize * 4],
+        )
+        .expect("sized")
+    }
+
+    fn with_block(
+        mut base: Vec<u8>,
+        width: u32,
+        x0: u32,
+        y0: u32,
+        size: u32,
+        value: u8,
+    ) -> Vec<u8> {
+        for y in y0..y0 + size {
+            for x in x0..x0 + size {
+                let offset = ((y * width + x) * 4) as usize;
+                base[offset..offset + 3].fill(value);
+            }
+        }
+        base
+    }
+
+    #[test]
+    fn identical_frames_pass_exactly_with_infinite_psnr() {
+        let diff = FrameDiff::compute(&flat(8, 8, 40), &flat(8, 8, 40), Tolerance::EXACT);
+        assert!(diff.passes());
+        assert_eq!(diff.differing_pixels, 0);
+        assert!(diff.psnr_db.is_infinite());
+        assert_eq!(diff.worst, None);
+    }
+
+    #[test]
+    fn small_drift_passes_perceptually_but_not_exactly() {
+        let drifted = flat(64, 64, 41);
+        let reference = flat(64, 64, 40);
+        assert!(FrameDiff::compute(&reference, &drifted, Tolerance::PERCEPTUAL).passes());
+        assert!(!FrameDiff::compute(&reference, &drifted, Tolerance::EXACT).passes());
+    }
+
+    #[test]
+    fn a_changed_region_fails_and_is_located_in_the_map() {
+        let reference = flat(96, 48, 40);
+        let actual = GoldenImage::from_rgba(
+            96,
+            48,
+            with_block(reference.rgba().to_vec(), 96, 80, 0, 8, 200),
+        )
+        .expect("sized");
+        let diff = FrameDiff::compute(&reference, &actual, Tolerance::PERCEPTUAL);
+
+        assert!(!diff.passes());
+        assert_eq!(diff.differing_pixels, 64);
+        assert_eq!(diff.max_channel_delta, 160);
+        let worst = diff.worst.expect("worst pixel");
+        assert_eq!((worst.x, worst.y), (80, 0));
+
+        let printed = diff.to_string();
+        let map: Vec<&str> = printed.lines().filter(|l| l.starts_with('|')).collect();
+        assert!(!map.is_empty());
+        assert!(map[0].contains('#'), "{printed}");
+        assert!(
+            map.last().expect("row").trim_matches('|').trim().is_empty(),
+            "{printed}"
+        );
+
+        let picture = diff.visualize(&reference, &actual).expect("same size");
+        assert_eq!(picture.pixel(80, 0)[0], 208);
+        assert_eq!(picture.pixel(0, 0), [10, 10, 10, 255]);
+    }
+
+    #[test]
+    fn size_mismatches_fail_with_a_plain_message() {
+        let diff = FrameDiff::compute(&flat(4, 4, 0), &flat(4, 2, 0), Tolerance::PERCEPTUAL);
+        assert!(!diff.passes());
+        assert_eq!(diff.to_string(), "frame is 4x2, reference is 4x4");
+        assert!(diff.visualize(&flat(4, 4, 0), &flat(4, 2, 0)).is_none());
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! CPU-side RGBA8 frames, and their on-disk form.
+//!
+//! References are stored as PAM (`P7`, `TUPLTYPE RGB_ALPHA`): uncompressed,
+//! lossless, a dozen lines to read and write, and opened by most image
+//! viewers — so a failed comparison leaves files a reviewer can look at.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::core::{Error, Result};
+
+/// An 8-bit RGBA frame, rows top to bottom, tightly packed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenImage {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+impl GoldenImage {
+    /// Wrap `rgba`, which must hold exactly `width * height` pixels.
+    pub fn from_rgba(width: u32, height: u32, rgba: Vec<u8>) -> Result<Self> {
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(Error::Configuration(format!(
+                "{width}x{height} RGBA image needs {expected} bytes, got {}",
+                rgba.len()
+            )));
+        }
+        Ok(Self {
+            width,
+            height,
+            rgba,
+        })
+    }
+
+    /// Wrap BGRA pixels, swizzling them to RGBA.
+    pub fn from_bgra(width: u32, height: u32, mut bgra: Vec<u8>) -> Result<Self> {
+        for pixel in bgra.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        Self::from_rgba(width, height, bgra)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// The pixel at (`x`, `y`).
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.rgba[offset],
+            self.rgba[offset + 1],
+            self.rgba[offset + 2],
+            self.rgba[offset + 3],
+        ]
+    }
+
+    /// SHA-256 of the dimensions and pixels, as lowercase hex. Equal hashes
+    /// mean bit-identical frames.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.width.to_le_bytes());
+        hasher.update(self.height.to_le_bytes());
+        hasher.update(&self.rgba);
+        hex(&hasher.finalize())
+    }
+
+    /// Encode as a PAM file.
+    pub fn to_pam(&self) -> Vec<u8> {
+        let header = format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+            self.width, self.height
+        );
+        let mut pam = Vec::with_capacity(header.len() + self.rgba.len());
+        pam.extend_from_slice(header.as_bytes());
+        pam.extend_from_slice(&self.rgba);
+        pam
+    }
+
+    /// Decode a PAM file with 8-bit RGB_ALPHA (or RGB, given opaque alpha)
+    /// tuples.
+    pub fn from_pam(bytes: &[u8]) -> Result<Self> {
+        let malformed = |what: &str| Error::Configuration(format!("malformed PAM file: {what}"));
+        let mut rest = bytes
+            .strip_prefix(b"P7\n")
+            .ok_or_else(|| malformed("missing P7 magic"))?;
+        let (mut width, mut height, mut depth, mut maxval) = (None, None, None, None);
+        loop {
+            let end = rest
+                .iter()
+                .position(|b| *b == b'\n')
+                .ok_or_else(|| malformed("header never ends"))?;
+            let line = std::str::from_utf8(&rest[..end])
+                .map_err(|_| malformed("header is not text"))?
+                .trim();
+            rest = &rest[end + 1..];
+            if line == "ENDHDR" {
+                break;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(key), value) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let number = || {
+                value
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .ok_or_else(|| malformed(&format!("bad {key}")))
+            };
+            match key {
+                "WIDTH" => width = Some(number()?),
+                "HEIGHT" => height = Some(number()?),
+                "DEPTH" => depth = Some(number()?),
+                "MAXVAL" => maxval = Some(number()?),
+                _ => {}
+            }
+        }
+        let (Some(width), Some(height), Some(depth)) = (width, height, depth) else {
+            return Err(malformed("WIDTH, HEIGHT and DEPTH are required"));
+        };
+        if maxval != Some(255) {
+            return Err(malformed("only MAXVAL 255 is supported"));
+        }
+        let pixels = width as usize * height as usize;
+        let rgba = match depth {
+            4 if rest.len() == pixels * 4 => rest.to_vec(),
+            3 if rest.len() == pixels * 3 => rest
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            3 | 4 => return Err(malformed("pixel data does not match the header")),
+            _ => return Err(malformed("only DEPTH 3 or 4 is supported")),
+        };
+        Self::from_rgba(width, height, rgba)
+    }
+
+    pub fn read_pam(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_pam(&std::fs::read(path)?)
+    }
+
+    pub fn write_pam(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_pam())?;
+        Ok(())
+    }
+}
+
+/// SHA-256 of `bytes` as lowercase hex.
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> GoldenImage {
+        let rgba = (0..width * height)
+            .flat_map(|i| [(i % 256) as u8, (i / 256) as u8, 7, 255])
+            .collect();
+        GoldenImage::from_rgba(width, height, rgba).expect("sized")
+    }
+
+    #[test]
+    fn pam_round_trips_and_rgb_pams_read_as_opaque() {
+        let image = gradient(5, 3);
+        assert_eq!(GoldenImage::from_pam(&image.to_pam()).expect("pam"), image);
+
+        let mut rgb =
+            b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 3\nMAXVAL 255\nTUPLTYPE RGB\nENDHDR\n".to_vec();
+        rgb.extend_from_slice(&[10, 20, 30]);
+        assert_eq!(
+            GoldenImage::from_pam(&rgb).expect("pam").pixel(0, 0),
+            [10, 20, 30, 255]
+        );
+    }
+
+    #[test]
+    fn malformed_pams_and_missized_pixels_are_rejected() {
+        assert!(GoldenImage::from_pam(b"P6\n1 1\n255\n\0\0\0").is_err());
+        let mut short = gradient(2, 2).to_pam();
+        short.pop();
+        assert!(GoldenImage::from_pam(&short).is_err());
+        assert!(GoldenImage::from_rgba(2, 2, vec![0; 15]).is_err());
+    }
+
+    #[test]
+    fn hashes_change_with_pixels_and_dimensions_and_bgra_is_swizzled() {
+        let image = gradient(4, 2);
+        assert_eq!(image.content_hash(), gradient(4, 2).content_hash());
+        assert_ne!(image.content_hash(), gradient(2, 4).content_hash());
+
+        let bgra = GoldenImage::from_bgra(1, 1, vec![1, 2, 3, 4]).expect("sized");
+        assert_eq!(bgra.pixel(0, 0), [3, 2, 1, 4]);
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! A directory of reference frames, checked and updated by name.
+//!
+//! Each reference is `<name>.pam` (compared with a [`Tolerance`]) or
+//! `<name>.sha256` (compared exactly). A failed or missing reference leaves
+//! `<name>.actual.pam` — and for a failed image `<name>.diff.pam` — next to
+//! it for review. Setting [`BLESS_ENV_VAR`] (or
+//! [`GoldenStore::blessing`]) writes the rendered output as the new
+//! reference instead of comparing.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::frame_diff::{FrameDiff, Tolerance};
+use super::golden_image::{GoldenImage, content_hash};
+use crate::core::{Error, Result};
+
+/// Set to `1` to write rendered output as the new references.
+pub const BLESS_ENV_VAR: &str = "STREAMLIB_BLESS_GOLDENS";
+
+/// The outcome of checking one frame against its reference.
+#[derive(Debug, Clone)]
+pub enum GoldenCheck {
+    /// Within tolerance of the reference.
+    Matched { name: String },
+    /// Written as the new reference.
+    Blessed { name: String, path: PathBuf },
+    /// No reference exists; the output is saved at `actual`.
+    Missing { name: String, actual: PathBuf },
+    /// Outside tolerance; the output and a picture of the differences are
+    /// saved next to the reference.
+    Differs {
+        name: String,
+        diff: Box<FrameDiff>,
+        actual: PathBuf,
+        visualized: Option<PathBuf>,
+    },
+    /// The hash of the output is not the stored one.
+    HashDiffers {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl GoldenCheck {
+    pub fn passed(&self) -> bool {
+        matches!(self, Self::Matched { .. } | Self::Blessed { .. })
+    }
+}
+
+impl fmt::Display for GoldenCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Matched { name } => write!(f, "{name}: matches"),
+            Self::Blessed { name, path } => write!(f, "{name}: blessed to {}", path.display()),
+            Self::Missing { name, actual } => write!(
+                f,
+                "{name}: no reference — output saved to {}; rerun with {BLESS_ENV_VAR}=1 \
+                 to accept it",
+                actual.display()
+            ),
+            Self::Differs {
+                name,
+                diff,
+                actual,
+                visualized,
+            } => {
+                writeln!(f, "{name}: differs from its reference")?;
+                for line in diff.to_string().lines() {
+                    writeln!(f, "  {line}")?;
+                }
+                write!(f, "  output: {}", actual.display())?;
+                if let Some(visualized) = visualized {
+                    write!(f, "\n  differences: {}", visualized.display())?;
+                }
+                Ok(())
+            }
+            Self::HashDiffers {
+                name,
+                expected,
+                actual,
+            } => write!(f, "{name}: hash {actual} is not the reference {expected}"),
+        }
+    }
+}
+
+/// Reference frames kept under one directory.
+#[derive(Debug, Clone)]
+pub struct GoldenStore {
+    dir: PathBuf,
+    bless: bool,
+}
+
+impl GoldenStore {
+    /// References under `dir`; blessing when [`BLESS_ENV_VAR`] is `1`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            bless: std::env::var(BLESS_ENV_VAR).is_ok_and(|v| v == "1"),
+        }
+    }
+
+    /// Write output as the new references instead of comparing.
+    pub fn blessing(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Compare `image` with the reference `<name>.pam`.
+    pub fn check_image(
+        &self,
+        name: &str,
+        image: &GoldenImage,
+        tolerance: Tolerance,
+    ) -> Result<GoldenCheck> {
+        let reference = self.path(name, "pam")?;
+        let actual_path = self.path(name, "actual.pam")?;
+        let diff_path = self.path(name, "diff.pam")?;
+        if self.bless {
+            image.write_pam(&reference)?;
+            remove_stale(&[&actual_path, &diff_path]);
+            return Ok(GoldenCheck::Blessed {
+                name: name.to_string(),
+                path: reference,
+            });
+        }
+        if !reference.exists() {
+            image.write_pam(&actual_path)?;
+            return Ok(GoldenCheck::Missing {
+                name: name.to_string(),
+                actual: actual_path,
+            });
+        }
+
+        let expected = GoldenImage::read_pam(&reference)?;
+        let diff = FrameDiff::compute(&expected, image, tolerance);
+        if diff.passes() {
+            remove_stale(&[&actual_path, &diff_path]);
+            return Ok(GoldenCheck::Matched {
+                name: name.to_string(),
+            });
+        }
+        image.write_pam(&actual_path)?;
+        let visualized = match diff.visualize(&expected, image) {
+            Some(picture) => {
+                picture.write_pam(&diff_path)?;
+                Some(diff_path)
+            }
+            None => None,
+        };
+        Ok(GoldenCheck::Differs {
+            name: name.to_string(),
+            diff: Box::new(diff),
+            actual: actual_path,
+            visualized,
+        })
+    }
+
+    /// Compare the SHA-256 of `bytes` with the reference `<name>.sha256` —
+    /// for outputs that must match exactly, or that are not images.
+    pub fn check_hash(&self, name: &str, bytes: &[u8]) -> Result<GoldenCheck> {
+        let reference = self.path(name, "sha256")?;
+        let actual = content_hash(bytes);
+        if self.bless {
+            write_file(&reference, format!("{actual}\n").as_bytes())?;
+            return Ok(GoldenCheck::Blessed {
+                name: name.to_string(),
+                path: reference,
+            });
+        }
+        if !reference.exists() {
+            let actual_path = self.path(name, "actual.sha256")?;
+            write_file(&actual_path, format!("{actual}\n").as_bytes())?;
+            return Ok(GoldenCheck::Missing {
+                name: name.to_string(),
+                actual: actual_path,
+            });
+        }
+        let expected = std::fs::read_to_string(&reference)?.trim().to_string();
+        if expected == actual {
+            Ok(GoldenCheck::Matched {
+                name: name.to_string(),
+            })
+        } else {
+            Ok(GoldenCheck::HashDiffers {
+                name: name.to_string(),
+                expected,
+                actual,
+            })
+        }
+    }
+
+    /// Check a sequence of frames as `<prefix>-0000`, `<prefix>-0001`, …
+    pub fn check_frames(
+        &self,
+        prefix: &str,
+        images: &[GoldenImage],
+        tolerance: Tolerance,
+    ) -> Result<Vec<GoldenCheck>> {
+        images
+            .iter()
+            .enumerate()
+            .map(|(index, image)| self.check_image(&frame_name(prefix, index), image, tolerance))
+            .collect()
+    }
+
+    /// [`Self::check_frames`], panicking with every failure printed if any
+    /// frame fails — the form to call from a `#[test]`.
+    pub fn assert_frames(&self, prefix: &str, images: &[GoldenImage], tolerance: Tolerance) {
+        let checks = match self.check_frames(prefix, images, tolerance) {
+            Ok(checks) => checks,
+            Err(e) => panic!("checking golden frames '{prefix}' failed: {e}"),
+        };
+        let failures: Vec<String> = checks
+            .iter()
+            .filter(|check| !check.passed())
+            .map(ToString::to_string)
+            .collect();
+        if !failures.is_empty() {
+            panic!(
+                "{} of {} golden frames failed under {}:\n\n{}",
+                failures.len(),
+                checks.len(),
+                self.dir.display(),
+                failures.join("\n\n")
+            );
+        }
+    }
+
+    fn path(&self, name: &str, extension: &str) -> Result<PathBuf> {
+        if name.is_empty()
+            || name.contains(['/', '\\'])
+            || name.starts_with('.')
+            || name.chars().any(char::is_control)
+        {
+            return Err(Error::Configuration(format!(
+                "golden name '{name}' must be a plain file name"
+            )));
+        }
+        Ok(self.dir.join(format!("{name}.{extension}")))
+    }
+}
+
+/// The reference name of frame `index` of a sequence.
+pub fn frame_name(prefix: &str, index: usize) -> String {
+    format!("{prefix}-{index:04}")
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Drop review files left by an earlier failing run.
+fn remove_stale(paths: &[&Path]) {
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(value: u8) -> GoldenImage {
+        GoldenImage::from_rgba(16, 8, vec![value; 16 * 8 * 4]).expect("sized")
+    }
+
+    #[test]
+    fn missing_references_are_saved_then_blessed_then_matched() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = GoldenStore::new(dir.path()).blessing(false);
+
+        let missing = store
+            .check_image("frame", &flat(10), Tolerance::EXACT)
+            .expect("check");
+        assert!(matches!(missing, GoldenCheck::Missing { .. }));
+        assert!(dir.path().join("frame.actual.pam").exists());
+
+        let blessed = store
+            .clone()
+            .blessing(true)
+            .check_image("frame", &flat(10), Tolerance::EXACT)
+            .expect("bless");
+        assert!(blessed.passed());
+        assert!(!dir.path().join("frame.actual.pam").exists());
+
+        let matched = store
+            .check_image("frame", &flat(10), Tolerance::EXACT)
+            .expect("check");
+        assert!(matches!(matched, GoldenCheck::Matched { .. }));
+    }
+
+    #[test]
+    fn differing_frames_leave_output_and_differences_for_review() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = GoldenStore::new(dir.path()).blessing(false);
+        store
+            .clone()
+            .blessing(true)
+            .check_image("frame", &flat(10), Tolerance::EXACT)
+            .expect("bless");
+
+        let check = store
+            .check_image("frame", &flat(90), Tolerance::PERCEPTUAL)
+            .expect("check");
+        assert!(!check.passed());
+        assert!(check.to_string().contains("differs from its reference"));
+        assert!(dir.path().join("frame.actual.pam").exists());
+        assert!(dir.path().join("frame.diff.pam").exists());
+    }
+
+    #[test]
+    fn hashes_are_checked_exactly() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = GoldenStore::new(dir.path()).blessing(false);
+        store
+            .clone()
+            .blessing(true)
+            .check_hash("payload", b"abc")
+            .expect("bless");
+
+        assert!(store.check_hash("payload", b"abc").expect("check").passed());
+        assert!(matches!(
+            store.check_hash("payload", b"abd").expect("check"),
+            GoldenCheck::HashDiffers { .. }
+        ));
+    }
+
+    #[test]
+    fn names_must_stay_inside_the_store() {
+        let store = GoldenStore::new("/tmp/goldens");
+        for name in ["", "../escape", "a/b", ".hidden"] {
+            assert!(store.check_hash(name, b"x").is_err(), "{name}");
+        }
+        assert_eq!(frame_name("bars", 7), "bars-0007");
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 2 golden frames failed")]
+    fn assert_frames_reports_every_failure() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = GoldenStore::new(dir.path()).blessing(false);
+        store
+            .clone()
+            .blessing(true)
+            .check_frames("seq", &[flat(10), flat(20)], Tolerance::EXACT)
+            .expect("bless");
+        store.assert_frames("seq", &[flat(10), flat(99)], Tolerance::EXACT);
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Golden-frame testing: render frames through a processor, compare them
+//! with stored references, and report differences a reviewer can act on.
+//!
+//! A typical test starts a runner, captures frames with [`OfflineRender`]
+//! (optionally driven by a stepped port-recording replay), converts video
+//! frames with [`video_frame_image`], then calls
+//! [`GoldenStore::assert_frames`]. Run once with
+//! `STREAMLIB_BLESS_GOLDENS=1` to write the references.
+
+mod frame_diff;
+mod golden_image;
+mod golden_store;
+mod offline_render;
+
+pub use frame_diff::{FrameDiff, Tolerance, WorstPixel};
+pub use golden_image::{GoldenImage, content_hash};
+pub use golden_store::{BLESS_ENV_VAR, GoldenCheck, GoldenStore, frame_name};
+pub use offline_render::{OfflineRender, RenderedFrame, video_frame_image};
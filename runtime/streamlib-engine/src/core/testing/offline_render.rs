@@ -0,0 +1,290 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Capture the next N frames a processor publishes on one output port.
+//!
+//! The capture rides the output channel's tap slot, so the port must already
+//! be wired to a destination and the runner started. To make the input
+//! deterministic, drive the processor from a port recording: a
+//! [`ReplayPace::Stepped`](crate::core::port_recording::ReplayPace::Stepped)
+//! replay hands it one recorded frame at a time, so output `i` always comes
+//! from the same input.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use super::golden_image::GoldenImage;
+use crate::core::graph::OutputLinkPortRef;
+use crate::core::port_recording::PortReplayOptions;
+use crate::core::rhi::PixelFormat;
+use crate::core::runtime::{Runner, RuntimeOperations};
+use crate::core::{Error, Result};
+use crate::iceoryx2::{FRAME_HEADER_SIZE, FrameHeader};
+
+/// How long to wait for each frame before giving up.
+const DEFAULT_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One captured frame.
+#[derive(Debug, Clone)]
+pub struct RenderedFrame {
+    /// Position in the capture, from 0.
+    pub index: usize,
+    pub timestamp_ns: i64,
+    /// The schema the frame was published under.
+    pub schema: String,
+    /// The MessagePack payload, as published.
+    pub payload: Vec<u8>,
+}
+
+impl RenderedFrame {
+    fn from_wire(index: usize, frame: &[u8]) -> Result<Self> {
+        if frame.len() < FRAME_HEADER_SIZE {
+            return Err(Error::Runtime(format!(
+                "captured frame {index} is {} bytes, shorter than a frame header",
+                frame.len()
+            )));
+        }
+        let header = FrameHeader::read_from_slice(frame);
+        let payload = frame[FRAME_HEADER_SIZE..]
+            .get(..header.len as usize)
+            .ok_or_else(|| {
+                Error::Runtime(format!(
+                    "captured frame {index} declares {} payload bytes but carries fewer",
+                    header.len
+                ))
+            })?;
+        Ok(Self {
+            index,
+            timestamp_ns: header.timestamp_ns,
+            schema: header.schema().render_joined(),
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// Decode the payload as `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        rmp_serde::from_slice(&self.payload).map_err(|e| {
+            Error::Runtime(format!(
+                "frame {} ({}) does not decode as {}: {e}",
+                self.index,
+                self.schema,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+/// Captures frames from one output port of a running graph.
+pub struct OfflineRender<'a> {
+    runner: &'a Runner,
+    output: OutputLinkPortRef,
+    frames: usize,
+    frame_timeout: Duration,
+    replay: Option<(PathBuf, PortReplayOptions)>,
+}
+
+impl<'a> OfflineRender<'a> {
+    /// Capture from `output`, one frame by default.
+    pub fn new(runner: &'a Runner, output: OutputLinkPortRef) -> Self {
+        Self {
+            runner,
+            output,
+            frames: 1,
+            frame_timeout: DEFAULT_FRAME_TIMEOUT,
+            replay: None,
+        }
+    }
+
+    /// Capture `count` frames.
+    pub fn frames(mut self, count: usize) -> Self {
+        self.frames = count;
+        self
+    }
+
+    /// Give up when no frame arrives within `timeout` of the last.
+    pub fn frame_timeout(mut self, timeout: Duration) -> Self {
+        self.frame_timeout = timeout;
+        self
+    }
+
+    /// Replay the port recording at `path` once the capture is attached,
+    /// and stop it when the capture is complete.
+    pub fn driven_by_replay(
+        mut self,
+        path: impl Into<PathBuf>,
+        options: PortReplayOptions,
+    ) -> Self {
+        self.replay = Some((path.into(), options));
+        self
+    }
+
+    /// Capture the frames. Fails if any frame is late or was lost to a
+    /// slow reader, since a capture with gaps cannot be compared.
+    pub async fn run(self) -> Result<Vec<RenderedFrame>> {
+        if self.frames == 0 {
+            return Err(Error::Configuration(
+                "an offline render needs at least one frame".into(),
+            ));
+        }
+        let channel = streamlib_idents::source_channel_name(
+            self.output.processor_id.as_str(),
+            &self.output.port_name,
+        )
+        .map_err(|e| Error::Configuration(e.to_string()))?
+        .into_string();
+        let mut tap = self
+            .runner
+            .tap_async(channel.clone(), Some(self.frames))
+            .await?;
+
+        let replayer = match &self.replay {
+            Some((path, options)) => {
+                Some(self.runner.replay_port_recording(path, options.clone())?)
+            }
+            None => None,
+        };
+
+        let mut frames = Vec::with_capacity(self.frames);
+        let captured = loop {
+            if frames.len() == self.frames {
+                break Ok(());
+            }
+            let index = frames.len();
+            match tokio::time::timeout(self.frame_timeout, tap.recv()).await {
+                Ok(Some(frame)) => match RenderedFrame::from_wire(index, &frame) {
+                    Ok(frame) => frames.push(frame),
+                    Err(e) => break Err(e),
+                },
+                Ok(None) => {
+                    break Err(Error::Runtime(format!(
+                        "{channel} closed after {index} of {} frames",
+                        self.frames
+                    )));
+                }
+                Err(_) => {
+                    break Err(Error::Runtime(format!(
+                        "no frame {index} on {channel} within {:?}",
+                        self.frame_timeout
+                    )));
+                }
+            }
+        };
+        if let Some(replayer) = replayer {
+            replayer.cancel();
+        }
+        captured?;
+
+        let dropped = tap.dropped_bags();
+        if dropped > 0 {
+            return Err(Error::Runtime(format!(
+                "{dropped} frames on {channel} were dropped before they could be captured"
+            )));
+        }
+        tracing::debug!(
+            "[testing] Captured {} frames from {}",
+            frames.len(),
+            channel
+        );
+        Ok(frames)
+    }
+}
+
+/// The fields of a video frame payload needed to find its pixels.
+#[derive(Deserialize)]
+struct VideoFrameSurface {
+    surface_id: String,
+    width: u32,
+    height: u32,
+}
+
+/// Read back the pixels of a captured video frame. The frame's surface must
+/// still be resolvable, so call this while the runner that produced it is
+/// running; only packed 8-bit RGBA and BGRA surfaces are supported.
+pub fn video_frame_image(runner: &Runner, frame: &RenderedFrame) -> Result<GoldenImage> {
+    let surface: VideoFrameSurface = frame.decode()?;
+    let context = runner
+        .runtime_context
+        .lock()
+        .clone()
+        .ok_or_else(|| Error::Runtime("the runtime is not started".into()))?;
+    let pixel_buffer = context
+        .gpu
+        .resolve_pixel_buffer_by_surface_id(&surface.surface_id)?;
+
+    let format = pixel_buffer.format();
+    if !matches!(format, PixelFormat::Rgba32 | PixelFormat::Bgra32) {
+        return Err(Error::NotSupported(format!(
+            "frame {} has pixel format {format:?}; only RGBA and BGRA frames can be compared",
+            frame.index
+        )));
+    }
+    let (width, height) = (surface.width as usize, surface.height as usize);
+    let raw_ptr = pixel_buffer.plane_base_address(0);
+    let plane_size = pixel_buffer.plane_size(0) as usize;
+    let row_bytes = width * 4;
+    if raw_ptr.is_null() || height == 0 || plane_size < row_bytes * height {
+        return Err(Error::Runtime(format!(
+            "frame {} has no mapped {width}x{height} pixel data",
+            frame.index
+        )));
+    }
+    // SAFETY: `raw_ptr` is the mapped base of plane 0, `plane_size` bytes
+    // long, and `pixel_buffer` keeps the mapping alive for this scope.
+    let plane = unsafe { std::slice::from_raw_parts(raw_ptr, plane_size) };
+    // Rows may be padded; the plane holds `height` rows of equal stride.
+    let stride = plane_size / height;
+    let pixels: Vec<u8> = plane
+        .chunks(stride)
+        .take(height)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect();
+
+    match format {
+        PixelFormat::Bgra32 => GoldenImage::from_bgra(surface.width, surface.height, pixels),
+        _ => GoldenImage::from_rgba(surface.width, surface.height, pixels),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceoryx2::SchemaIdentWire;
+
+    fn wire(timestamp_ns: i64, body: &[u8], declared_len: u32) -> Vec<u8> {
+        let mut buf = vec![0u8; FRAME_HEADER_SIZE + body.len()];
+        FrameHeader::new(
+            "video",
+            SchemaIdentWire::default(),
+            timestamp_ns,
+            declared_len,
+        )
+        .expect("port key fits")
+        .write_to_slice(&mut buf);
+        buf[FRAME_HEADER_SIZE..].copy_from_slice(body);
+        buf
+    }
+
+    #[test]
+    fn captured_frames_decode_their_payload() {
+        let surface = (String::from("surface-7"), 64u32, 32u32);
+        let body = rmp_serde::to_vec(&surface).expect("encode");
+        let frame =
+            RenderedFrame::from_wire(3, &wire(42, &body, body.len() as u32)).expect("whole frame");
+        assert_eq!((frame.index, frame.timestamp_ns), (3, 42));
+        assert_eq!(
+            frame.decode::<(String, u32, u32)>().expect("decode"),
+            surface
+        );
+        assert!(frame.decode::<u64>().is_err());
+    }
+
+    #[test]
+    fn truncated_frames_are_rejected() {
+        assert!(RenderedFrame::from_wire(0, &[0; 8]).is_err());
+        assert!(RenderedFrame::from_wire(0, &wire(0, b"abc", 10)).is_err());
+    }
+}
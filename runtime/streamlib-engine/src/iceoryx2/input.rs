@@ -32,16 +32,23 @@ use std::ffi::c_void;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+#[cfg(target_os = "linux")]
+use std::os::fd::{AsRawFd, OwnedFd};
+
 use iceoryx2::port::listener::Listener;
 use iceoryx2::port::subscriber::Subscriber;
 use iceoryx2::prelude::*;
 use serde::de::DeserializeOwned;
 use streamlib_plugin_abi::InputMailboxesVTable;
 
-use super::mailbox::PortMailbox;
+use super::mailbox::{MailboxFrameCounters, PortMailbox};
 use super::read_mode::ReadMode;
-use super::{FRAME_HEADER_SIZE, FrameHeader, SchemaIdentWire};
+use super::{FRAME_HEADER_SIZE, FrameHeader, PayloadCipher, SchemaIdentWire};
+use crate::core::chaos::{LinkChaos, LinkChaosVerdict, link_chaos, release_link_chaos};
 use crate::core::error::{Error, Result};
+use crate::core::port_recording::{
+    LinkRecordingTap, link_recording_tap, release_link_recording_tap,
+};
 use crate::core::schema_agreement::{SchemaAgreement, classify_wire_schema_agreement};
 
 /// One channel subscriber bound to the local input port it feeds.
@@ -62,6 +69,13 @@ struct PortBoundSubscriber {
     link_id: String,
     local_port: String,
     subscriber: Subscriber<ipc::Service, [u8], ()>,
+    /// Chaos-mode faults applied to the link, checked per frame.
+    chaos: Arc<LinkChaos>,
+    /// Port recording of the link, fed every frame that reaches the mailbox.
+    recording: Arc<LinkRecordingTap>,
+    /// Opens every frame when the channel is encrypted; a frame that fails to
+    /// open is counted as dropped and never reaches the mailbox.
+    cipher: Option<PayloadCipher>,
 }
 
 /// Thread-local set of channel subscribers.
@@ -89,13 +103,19 @@ impl SendableChannelSubscribers {
         link_id: String,
         local_port: String,
         subscriber: Subscriber<ipc::Service, [u8], ()>,
+        cipher: Option<PayloadCipher>,
     ) {
+        let chaos = link_chaos(&link_id);
+        let recording = link_recording_tap(&link_id);
         // SAFETY: Only called from the processor's execution thread during wiring.
         unsafe {
             (*self.0.get()).push(PortBoundSubscriber {
                 link_id,
                 local_port,
                 subscriber,
+                chaos,
+                recording,
+                cipher,
             });
         }
     }
@@ -227,6 +247,18 @@ struct PortConfig {
     schema_mismatch_observed: AtomicBool,
 }
 
+impl PortConfig {
+    fn new(buffer_size: usize, read_mode: ReadMode) -> Self {
+        Self {
+            mailbox: PortMailbox::new(buffer_size),
+            read_mode,
+            staged_oversized: None,
+            expected_schema_ident: SchemaIdentWire::default(),
+            schema_mismatch_observed: AtomicBool::new(false),
+        }
+    }
+}
+
 /// Host-side inner state for input mailboxes. Owns the per-port
 /// mailbox map plus the per-thread subscriber + listener. All
 /// per-frame `receive_pending` + queue-pop work runs here.
@@ -239,6 +271,12 @@ pub struct InputMailboxesInner {
     ports: parking_lot::Mutex<HashMap<String, PortConfig>>,
     subscribers: SendableChannelSubscribers,
     listener: SendableListener,
+    /// Linux-only: eventfd signalled by [`InputMailboxesInner::inject`], so a
+    /// reactive runner waiting on the listener also wakes for frames pushed
+    /// from outside the receive path. `None` if the eventfd could not be
+    /// created; injected frames are then picked up on the next wake.
+    #[cfg(target_os = "linux")]
+    inject_wake: Option<OwnedFd>,
 }
 
 impl InputMailboxesInner {
@@ -248,6 +286,8 @@ impl InputMailboxesInner {
             ports: parking_lot::Mutex::new(HashMap::new()),
             subscribers: SendableChannelSubscribers::new(),
             listener: SendableListener::new(),
+            #[cfg(target_os = "linux")]
+            inject_wake: create_inject_wake_eventfd(),
         }
     }
 
@@ -265,16 +305,9 @@ impl InputMailboxesInner {
             read_mode = ?read_mode,
             "InputMailboxes: add_port"
         );
-        self.ports.lock().insert(
-            port.to_string(),
-            PortConfig {
-                mailbox: PortMailbox::new(buffer_size),
-                read_mode,
-                staged_oversized: None,
-                expected_schema_ident: SchemaIdentWire::default(),
-                schema_mismatch_observed: AtomicBool::new(false),
-            },
-        );
+        self.ports
+            .lock()
+            .insert(port.to_string(), PortConfig::new(buffer_size, read_mode));
     }
 
     /// Record the schema-ident tag this port expects inbound frames to carry.
@@ -304,6 +337,17 @@ impl InputMailboxesInner {
             .unwrap_or(false)
     }
 
+    /// Shared delivered / dropped frame counters for `port`'s mailbox, or
+    /// `None` for unknown ports. The compiler hangs this handle on the link
+    /// edge at wire time so drop accounting reads never take the processor
+    /// instance lock.
+    pub fn port_frame_counters(&self, port: &str) -> Option<Arc<MailboxFrameCounters>> {
+        self.ports
+            .lock()
+            .get(port)
+            .map(|cfg| cfg.mailbox.frame_counters())
+    }
+
     /// Whether any channel subscriber has been configured yet.
     pub fn has_subscribers(&self) -> bool {
         !self.subscribers.is_empty()
@@ -323,8 +367,27 @@ impl InputMailboxesInner {
         link_id: &str,
         subscriber: Subscriber<ipc::Service, [u8], ()>,
     ) {
-        self.subscribers
-            .push(link_id.to_string(), local_port.to_string(), subscriber);
+        self.add_channel_subscriber_with_cipher(local_port, link_id, subscriber, None);
+    }
+
+    /// [`Self::add_channel_subscriber`] for a channel that may be encrypted:
+    /// with a `cipher`, every frame the subscriber delivers is opened under the
+    /// channel key before it reaches the mailbox.
+    ///
+    /// Note: This should only be called from the processor's execution thread.
+    pub fn add_channel_subscriber_with_cipher(
+        &self,
+        local_port: &str,
+        link_id: &str,
+        subscriber: Subscriber<ipc::Service, [u8], ()>,
+        cipher: Option<PayloadCipher>,
+    ) {
+        self.subscribers.push(
+            link_id.to_string(),
+            local_port.to_string(),
+            subscriber,
+            cipher,
+        );
     }
 
     /// Reclaim the destination-side ports for one disconnected `connect()` link.
@@ -341,6 +404,8 @@ impl InputMailboxesInner {
         let Some(local_port) = self.subscribers.remove_by_link(link_id) else {
             return;
         };
+        release_link_chaos(link_id);
+        release_link_recording_tap(link_id);
         if !self.subscribers.port_still_bound(&local_port) {
             self.ports.lock().remove(&local_port);
         }
@@ -382,6 +447,8 @@ impl InputMailboxesInner {
     ///
     /// Call this after `epoll_wait` reports the fd readable, before the next
     /// `epoll_wait`, otherwise the wait returns immediately on the same event.
+    ///
+    /// Also resets the inject-wake eventfd (see [`Self::inject_wake_fd`]).
     pub fn drain_listener(&self) {
         if let Some(listener) = self.listener.get() {
             if let Err(e) = listener.try_wait_all(|_event_id| {}) {
@@ -391,6 +458,71 @@ impl InputMailboxesInner {
                 );
             }
         }
+        #[cfg(target_os = "linux")]
+        if let Some(fd) = self.inject_wake.as_ref() {
+            let mut count = 0u64;
+            // SAFETY: reads 8 bytes into a live u64 from an owned, non-blocking
+            // eventfd; an EAGAIN (nothing injected) is ignored.
+            unsafe {
+                libc::read(
+                    fd.as_raw_fd(),
+                    (&mut count as *mut u64).cast::<c_void>(),
+                    std::mem::size_of::<u64>(),
+                );
+            }
+        }
+    }
+
+    /// Linux-only: the eventfd [`Self::inject`] signals, for registering
+    /// alongside [`Self::listener_fd`] in the reactive runner's epoll set.
+    /// Reset by [`Self::drain_listener`]. Owned by the inner — callers must
+    /// NOT `close()` it.
+    #[cfg(target_os = "linux")]
+    pub fn inject_wake_fd(&self) -> Option<i32> {
+        self.inject_wake.as_ref().map(|fd| fd.as_raw_fd())
+    }
+
+    /// Push a whole wire frame into `port`'s mailbox from outside the
+    /// receive path — a port replay — and wake the processor. A port without
+    /// a mailbox gets one `depth` deep, read in order. Thread-safe: can be
+    /// called from any thread; the lookup and any insert happen under one
+    /// lock, so a concurrent `inject` never replaces a mailbox just filled.
+    pub fn inject(&self, port: &str, frame: Vec<u8>, depth: usize) {
+        self.ports
+            .lock()
+            .entry(port.to_string())
+            .or_insert_with(|| {
+                tracing::debug!(
+                    port = port,
+                    depth = depth,
+                    "InputMailboxes: inject adds port"
+                );
+                PortConfig::new(depth, ReadMode::ReadNextInOrder)
+            })
+            .mailbox
+            .push(frame);
+        #[cfg(target_os = "linux")]
+        if let Some(fd) = self.inject_wake.as_ref() {
+            let one = 1u64;
+            // SAFETY: writes 8 bytes from a live u64 to an owned eventfd.
+            unsafe {
+                libc::write(
+                    fd.as_raw_fd(),
+                    (&one as *const u64).cast::<c_void>(),
+                    std::mem::size_of::<u64>(),
+                );
+            }
+        }
+    }
+
+    /// Whether `port`'s mailbox is empty, without receiving from its
+    /// channels first — unlike [`Self::has_data`], safe to call from any
+    /// thread. `true` for unknown ports.
+    pub fn port_is_drained(&self, port: &str) -> bool {
+        self.ports
+            .lock()
+            .get(port)
+            .is_none_or(|p| p.mailbox.is_empty())
     }
 
     /// Receive all pending payloads from every channel subscriber and route them
@@ -402,6 +534,11 @@ impl InputMailboxesInner {
     /// Note: This should only be called from the thread that owns the subscribers.
     pub fn receive_pending(&self) {
         for bound in self.subscribers.iter() {
+            // A stalled link is left unread; its channel backs up as it
+            // would behind a hung consumer.
+            if bound.chaos.is_stalled() {
+                continue;
+            }
             loop {
                 match bound.subscriber.receive() {
                     Ok(Some(sample)) => {
@@ -416,7 +553,38 @@ impl InputMailboxesInner {
                         }
                         let ports = self.ports.lock();
                         if let Some(port_config) = ports.get(&bound.local_port) {
-                            port_config.mailbox.push(slice.to_vec());
+                            let frame = match bound.chaos.verdict() {
+                                LinkChaosVerdict::Deliver => slice.to_vec(),
+                                LinkChaosVerdict::Drop => {
+                                    port_config.mailbox.record_discarded();
+                                    continue;
+                                }
+                                LinkChaosVerdict::Corrupt => {
+                                    let mut frame = slice.to_vec();
+                                    bound.chaos.corrupt(&mut frame);
+                                    frame
+                                }
+                            };
+                            // Corruption lands on the sealed bytes, as it would
+                            // on the wire, so an encrypted link drops the frame.
+                            let frame = match &bound.cipher {
+                                Some(cipher) => match cipher.open_frame(&frame) {
+                                    Ok(opened) => opened,
+                                    Err(e) => {
+                                        tracing::warn!(
+                                            port = %bound.local_port,
+                                            link_id = %bound.link_id,
+                                            "InputMailboxes: dropping frame: {}",
+                                            e
+                                        );
+                                        port_config.mailbox.record_discarded();
+                                        continue;
+                                    }
+                                },
+                                None => frame,
+                            };
+                            bound.recording.record(&frame);
+                            port_config.mailbox.push(frame);
                         } else {
                             tracing::warn!(
                                 port = %bound.local_port,
@@ -591,6 +759,24 @@ impl Default for InputMailboxesInner {
     }
 }
 
+#[cfg(target_os = "linux")]
+fn create_inject_wake_eventfd() -> Option<OwnedFd> {
+    use std::os::fd::FromRawFd;
+    // SAFETY: eventfd returns -1 on failure; checked below. Non-blocking so
+    // draining an unsignalled fd returns at once.
+    let raw = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+    if raw < 0 {
+        tracing::warn!(
+            "InputMailboxes: inject-wake eventfd failed ({}); injected frames \
+             wait for the next wake",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+    // SAFETY: raw is a fresh, owned fd from a successful eventfd() call.
+    Some(unsafe { OwnedFd::from_raw_fd(raw) })
+}
+
 // =============================================================================
 // InputMailboxes PluginAbiObject
 // =============================================================================
@@ -930,6 +1116,36 @@ mod tests {
         );
     }
 
+    /// Concurrent injects onto a port with no mailbox yet all land in one
+    /// mailbox — none creates a second mailbox that replaces a filled one.
+    #[test]
+    fn concurrent_injects_onto_a_new_port_keep_every_frame() {
+        const THREADS: usize = 8;
+        const FRAMES_PER_THREAD: usize = 16;
+        let mailboxes = InputMailboxesInner::new();
+        let frame = frame_with_schema("replay", SchemaIdentWire::default());
+
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for _ in 0..FRAMES_PER_THREAD {
+                        mailboxes.inject("replay", frame.clone(), THREADS * FRAMES_PER_THREAD);
+                    }
+                });
+            }
+        });
+
+        let mut delivered = 0;
+        while mailboxes
+            .read_raw("replay")
+            .expect("read_raw replay ok")
+            .is_some()
+        {
+            delivered += 1;
+        }
+        assert_eq!(delivered, THREADS * FRAMES_PER_THREAD);
+    }
+
     fn frame_with_schema(port: &str, schema: SchemaIdentWire) -> Vec<u8> {
         let mut buf = vec![0u8; FRAME_HEADER_SIZE + 4];
         let header = FrameHeader::new(port, schema, 0, 4).expect("port fits PortKey");
@@ -1059,6 +1275,59 @@ mod tests {
         );
     }
 
+    /// An encrypted channel's subscriber opens each sealed frame before the
+    /// mailbox sees it, and discards a frame that fails authentication rather
+    /// than delivering ciphertext.
+    ///
+    /// Revert lock: skip `open_frame` in `receive_pending` and the mailbox
+    /// yields the nonce + ciphertext + tag instead of the plaintext.
+    #[test]
+    fn encrypted_subscriber_opens_sealed_frames_and_drops_forged_ones() {
+        use crate::iceoryx2::SEALED_PAYLOAD_OVERHEAD;
+
+        let node = NodeBuilder::new().create::<ipc::Service>().unwrap();
+        let schema =
+            SchemaIdentWire::from_segments("tatolab", "core", "VideoFrame", 1, 0, 0).unwrap();
+        let pubsub = node
+            .service_builder(&ServiceName::new(&unique_suffix("sealed")).unwrap())
+            .publish_subscribe::<[u8]>()
+            .open_or_create()
+            .unwrap();
+        let publisher = pubsub
+            .publisher_builder()
+            .initial_max_slice_len(4096)
+            .create()
+            .unwrap();
+        let subscriber = pubsub.subscriber_builder().create().unwrap();
+        let cipher = PayloadCipher::generate().unwrap();
+
+        let publish_sealed = |data: &[u8], tamper: bool| {
+            let sealed_len = data.len() + SEALED_PAYLOAD_OVERHEAD;
+            let mut frame = vec![0u8; FRAME_HEADER_SIZE + sealed_len];
+            FrameHeader::new("out", schema, 7, sealed_len as u32)
+                .expect("source port fits PortKey")
+                .write_to_slice(&mut frame[..FRAME_HEADER_SIZE]);
+            cipher.seal_frame(&mut frame, data).unwrap();
+            if tamper {
+                *frame.last_mut().unwrap() ^= 1;
+            }
+            let sample = publisher.loan_slice_uninit(frame.len()).unwrap();
+            sample.write_from_slice(&frame).send().unwrap();
+        };
+        publish_sealed(b"forged", true);
+        publish_sealed(b"secret frame", false);
+
+        let mailboxes = InputMailboxesInner::new();
+        mailboxes.add_port("in", 8, ReadMode::ReadNextInOrder);
+        mailboxes.add_channel_subscriber_with_cipher("in", "L-sealed", subscriber, Some(cipher));
+
+        assert_eq!(
+            mailboxes.read_raw("in").unwrap(),
+            Some((b"secret frame".to_vec(), 7)),
+        );
+        assert_eq!(mailboxes.read_raw("in").unwrap(), None);
+    }
+
     /// Per-link destination reclaim (#1549): a destination fanning two inbound
     /// links into ONE local port holds two tagged subscribers plus one shared
     /// listener. Disconnecting one link drops only its subscriber (the port
@@ -3,8 +3,54 @@
 
 //! Per-port mailbox using crossbeam ArrayQueue for thread-safe access.
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use crossbeam_queue::ArrayQueue;
 
+/// Delivered / dropped frame tallies for one [`PortMailbox`].
+///
+/// Shared as `Arc` so the graph can hold a read handle on the link edge
+/// (see `LinkFrameDropComponent`) without locking the destination
+/// processor instance. Counters are monotonic for the mailbox's lifetime;
+/// the queue depth is a gauge refreshed after every push and pop.
+#[derive(Debug, Default)]
+pub struct MailboxFrameCounters {
+    frames_delivered: AtomicU64,
+    frames_dropped: AtomicU64,
+    queue_depth: AtomicU64,
+}
+
+impl MailboxFrameCounters {
+    /// Frames handed to the processor by a pop.
+    pub fn frames_delivered(&self) -> u64 {
+        self.frames_delivered.load(Ordering::Relaxed)
+    }
+
+    /// Frames discarded without reaching the processor — evicted by a push
+    /// into a full mailbox, or skipped by a `SkipToLatest` drain.
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Frames waiting in the mailbox as of its last push or pop.
+    pub fn queue_depth(&self) -> u64 {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    fn record_delivered(&self) {
+        self.frames_delivered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_dropped(&self, count: u64) {
+        self.frames_dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn record_queue_depth(&self, depth: usize) {
+        self.queue_depth.store(depth as u64, Ordering::Relaxed);
+    }
+}
+
 /// Per-port mailbox with configurable history depth.
 ///
 /// Stores raw wire-format `[u8]` slices (header + data) as `Vec<u8>`.
@@ -13,6 +59,7 @@ use crossbeam_queue::ArrayQueue;
 pub struct PortMailbox {
     queue: ArrayQueue<Vec<u8>>,
     capacity: usize,
+    counters: Arc<MailboxFrameCounters>,
 }
 
 impl PortMailbox {
@@ -22,6 +69,7 @@ impl PortMailbox {
         Self {
             queue: ArrayQueue::new(capacity),
             capacity,
+            counters: Arc::new(MailboxFrameCounters::default()),
         }
     }
 
@@ -32,31 +80,58 @@ impl PortMailbox {
     pub fn push(&self, payload: Vec<u8>) {
         // If full, pop oldest to make room
         while self.queue.is_full() {
-            let _ = self.queue.pop();
+            if self.queue.pop().is_some() {
+                self.counters.record_dropped(1);
+            }
         }
         // Push should succeed now (may fail if another thread filled it, retry)
         let mut val = payload;
         while let Err(v) = self.queue.push(val) {
             val = v;
-            let _ = self.queue.pop();
+            if self.queue.pop().is_some() {
+                self.counters.record_dropped(1);
+            }
         }
+        self.counters.record_queue_depth(self.queue.len());
+    }
+
+    /// Count a frame that was discarded before reaching the mailbox — a
+    /// chaos-mode drop — as dropped.
+    pub fn record_discarded(&self) {
+        self.counters.record_dropped(1);
     }
 
     /// Pop the oldest entry from the mailbox (FIFO).
     ///
     /// Thread-safe: can be called from any thread.
     pub fn pop(&self) -> Option<Vec<u8>> {
-        self.queue.pop()
+        let value = self.queue.pop();
+        if value.is_some() {
+            self.counters.record_delivered();
+        }
+        self.counters.record_queue_depth(self.queue.len());
+        value
     }
 
     /// Drain buffer and return only the newest entry.
     ///
+    /// Every entry older than the returned one counts as dropped.
     /// Thread-safe: can be called from any thread.
     pub fn pop_latest(&self) -> Option<Vec<u8>> {
         let mut latest = None;
+        let mut skipped = 0u64;
         while let Some(value) = self.queue.pop() {
-            latest = Some(value);
+            if latest.replace(value).is_some() {
+                skipped += 1;
+            }
+        }
+        if skipped > 0 {
+            self.counters.record_dropped(skipped);
+        }
+        if latest.is_some() {
+            self.counters.record_delivered();
         }
+        self.counters.record_queue_depth(self.queue.len());
         latest
     }
 
@@ -75,10 +150,74 @@ impl PortMailbox {
         self.capacity
     }
 
+    /// Shared handle on this mailbox's delivered / dropped frame counters.
+    pub fn frame_counters(&self) -> Arc<MailboxFrameCounters> {
+        Arc::clone(&self.counters)
+    }
+
     /// Drain all entries from the mailbox.
     ///
     /// Thread-safe: can be called from any thread.
     pub fn drain(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
-        std::iter::from_fn(move || self.queue.pop())
+        std::iter::from_fn(move || {
+            let value = self.queue.pop();
+            self.counters.record_queue_depth(self.queue.len());
+            value
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_into_full_mailbox_counts_evicted_frame_as_dropped() {
+        let mailbox = PortMailbox::new(2);
+        mailbox.push(vec![1]);
+        mailbox.push(vec![2]);
+        mailbox.push(vec![3]);
+
+        let counters = mailbox.frame_counters();
+        assert_eq!(counters.frames_dropped(), 1);
+        assert_eq!(mailbox.pop(), Some(vec![2]));
+        assert_eq!(counters.frames_delivered(), 1);
+    }
+
+    #[test]
+    fn pop_latest_counts_skipped_frames_as_dropped() {
+        let mailbox = PortMailbox::new(4);
+        mailbox.push(vec![1]);
+        mailbox.push(vec![2]);
+        mailbox.push(vec![3]);
+
+        assert_eq!(mailbox.pop_latest(), Some(vec![3]));
+        let counters = mailbox.frame_counters();
+        assert_eq!(counters.frames_dropped(), 2);
+        assert_eq!(counters.frames_delivered(), 1);
+    }
+
+    #[test]
+    fn pop_latest_on_empty_mailbox_records_nothing() {
+        let mailbox = PortMailbox::new(4);
+        assert_eq!(mailbox.pop_latest(), None);
+        let counters = mailbox.frame_counters();
+        assert_eq!(counters.frames_dropped(), 0);
+        assert_eq!(counters.frames_delivered(), 0);
+    }
+
+    #[test]
+    fn queue_depth_tracks_pushes_and_pops() {
+        let mailbox = PortMailbox::new(2);
+        let counters = mailbox.frame_counters();
+        mailbox.push(vec![1]);
+        mailbox.push(vec![2]);
+        mailbox.push(vec![3]);
+        assert_eq!(counters.queue_depth(), 2);
+
+        mailbox.pop();
+        assert_eq!(counters.queue_depth(), 1);
+        assert_eq!(mailbox.drain().count(), 1);
+        assert_eq!(counters.queue_depth(), 0);
     }
 }
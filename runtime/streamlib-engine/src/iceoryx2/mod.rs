@@ -11,6 +11,7 @@ mod node;
 mod output;
 mod overflow;
 mod payload;
+mod payload_cipher;
 mod read_mode;
 
 pub use channel_ceiling::{
@@ -19,7 +20,7 @@ pub use channel_ceiling::{
 };
 pub use delivery_profile::{DeliveryProfile, DeliveryResolution, FlowClass};
 pub use input::{BoundedReadOutcome, InputMailboxes, InputMailboxesInner};
-pub use mailbox::PortMailbox;
+pub use mailbox::{MailboxFrameCounters, PortMailbox};
 pub use node::{
     ChannelTapSubscribeError, Iceoryx2EventService, Iceoryx2Node, Iceoryx2NotifyService,
     Iceoryx2Service,
@@ -35,4 +36,7 @@ pub use payload::{
     TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES, TopicKey,
     UNTRUSTED_SESSION_CHANNEL_PAYLOAD_CEILING_BYTES,
 };
+pub use payload_cipher::{
+    PAYLOAD_KEY_LEN, PayloadCipher, PayloadKey, SEALED_PAYLOAD_OVERHEAD,
+};
 pub use read_mode::ReadMode;
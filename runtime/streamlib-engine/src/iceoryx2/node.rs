@@ -1001,6 +1001,7 @@ mod tests {
                         trust_tier: ChannelTrustTier::Trusted,
                         expected_payload_bytes: 64,
                         ceiling_bytes: TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+                        cipher: None,
                     },
                 );
             }
@@ -44,7 +44,10 @@ use parking_lot::Mutex;
 use serde::Serialize;
 use streamlib_plugin_abi::OutputWriterVTable;
 
-use super::{ChannelTrustTier, FRAME_HEADER_SIZE, FrameHeader, SchemaIdentWire};
+use super::{
+    ChannelTrustTier, FRAME_HEADER_SIZE, FrameHeader, PayloadCipher, SEALED_PAYLOAD_OVERHEAD,
+    SchemaIdentWire,
+};
 use crate::core::error::{ChannelTrustTierLabel, Error, Result};
 use crate::core::media_clock::MediaClock;
 
@@ -95,6 +98,9 @@ struct ChannelEgress {
     current_slot_capacity_bytes: usize,
     /// Count of samples refused for crossing [`Self::ceiling_bytes`].
     refused_over_ceiling_count: u64,
+    /// Seals every payload when the channel is encrypted; fixed for the
+    /// publisher's lifetime.
+    cipher: Option<PayloadCipher>,
 }
 
 /// The channel-egress primitives that prime an output port's channel
@@ -114,6 +120,9 @@ pub struct ChannelEgressConfig {
     pub expected_payload_bytes: usize,
     /// Per-channel payload ceiling in bytes; a frame above it is refused.
     pub ceiling_bytes: usize,
+    /// Channel key when the channel is encrypted: every payload is sealed
+    /// before it reaches shared memory. `None` publishes in the clear.
+    pub cipher: Option<PayloadCipher>,
 }
 
 /// Host-side inner state for an output writer. Owns the per-output-port
@@ -170,6 +179,7 @@ impl OutputWriterInner {
             trust_tier,
             expected_payload_bytes,
             ceiling_bytes,
+            cipher,
         } = egress_config;
         self.channels.lock().insert(
             output_port.to_string(),
@@ -182,6 +192,7 @@ impl OutputWriterInner {
                 ceiling_bytes,
                 current_slot_capacity_bytes: expected_payload_bytes + FRAME_HEADER_SIZE,
                 refused_over_ceiling_count: 0,
+                cipher,
             },
         );
     }
@@ -196,6 +207,16 @@ impl OutputWriterInner {
             .unwrap_or(0)
     }
 
+    /// The cipher an output port's channel seals with — `None` when the channel
+    /// publishes in the clear or has no publisher yet. A later link out of the
+    /// port subscribes with this same key.
+    pub fn channel_cipher(&self, output_port: &str) -> Option<PayloadCipher> {
+        self.channels
+            .lock()
+            .get(output_port)
+            .and_then(|e| e.cipher.clone())
+    }
+
     /// Append a destination notifier to an output port's channel, tagged with
     /// the `link_id` of the `connect()` link it serves.
     ///
@@ -251,7 +272,13 @@ impl OutputWriterInner {
             .get_mut(port)
             .ok_or_else(|| Error::Link(format!("Unknown output port: {}", port)))?;
 
-        let total_len = FRAME_HEADER_SIZE + data.len();
+        // An encrypted channel's body is the sealed payload, so the ceiling and
+        // the header's `len` both count the AEAD overhead.
+        let body_len = match egress.cipher {
+            Some(_) => data.len() + SEALED_PAYLOAD_OVERHEAD,
+            None => data.len(),
+        };
+        let total_len = FRAME_HEADER_SIZE + body_len;
 
         // Per-channel ceiling refusal + PowerOfTwo growth bookkeeping share their
         // authority with the subprocess natives via
@@ -281,10 +308,13 @@ impl OutputWriterInner {
         }
 
         let mut frame = vec![0u8; total_len];
-        FrameHeader::new(port, egress.schema_ident, timestamp_ns, data.len() as u32)
+        FrameHeader::new(port, egress.schema_ident, timestamp_ns, body_len as u32)
             .map_err(|e| Error::Link(format!("output port '{}': {}", port, e)))?
             .write_to_slice(&mut frame[..FRAME_HEADER_SIZE]);
-        frame[FRAME_HEADER_SIZE..].copy_from_slice(data);
+        match &egress.cipher {
+            Some(cipher) => cipher.seal_frame(&mut frame, data)?,
+            None => frame[FRAME_HEADER_SIZE..].copy_from_slice(data),
+        }
 
         let sample = egress
             .publisher
@@ -608,6 +638,7 @@ mod tests {
                 trust_tier: crate::iceoryx2::ChannelTrustTier::Trusted,
                 expected_payload_bytes: 4096,
                 ceiling_bytes: crate::iceoryx2::TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+                cipher: None,
             },
         );
         inner.add_channel_notifier("out", "L-test-notify", notifier);
@@ -683,6 +714,7 @@ mod tests {
                 trust_tier: crate::iceoryx2::ChannelTrustTier::Trusted,
                 expected_payload_bytes: 4096,
                 ceiling_bytes: crate::iceoryx2::TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+                cipher: None,
             },
         );
 
@@ -770,6 +802,7 @@ mod tests {
                 trust_tier: ChannelTrustTier::Trusted,
                 expected_payload_bytes: 4096,
                 ceiling_bytes: crate::iceoryx2::TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+                cipher: None,
             },
         );
 
@@ -893,6 +926,7 @@ mod tests {
                 trust_tier: ChannelTrustTier::UntrustedSession,
                 expected_payload_bytes: 64,
                 ceiling_bytes: ceiling,
+                cipher: None,
             },
         );
 
@@ -0,0 +1,290 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Per-channel payload encryption.
+//!
+//! iceoryx2 data segments are shared memory any local process of the same
+//! user can map, so a channel carrying sensitive frames can opt into
+//! ChaCha20-Poly1305: the publisher seals each frame's payload under the
+//! channel's key and every subscriber opens it before the frame reaches its
+//! mailbox. The [`FrameHeader`] stays in the clear — routing, schema checks and
+//! the per-channel ceiling read it — but is bound to the ciphertext as
+//! associated data, so a rewritten header fails to open like a rewritten
+//! payload.
+//!
+//! A sealed body is `nonce (12) ‖ ciphertext ‖ tag (16)`, and the header's
+//! `len` counts all of it. Nonces are a random per-key prefix plus a counter:
+//! a channel has exactly one publisher and a fresh key per publisher, so the
+//! counter alone never repeats under one key.
+//!
+//! Keys never touch the shared segment. A Rust→Rust link hands the key to
+//! both halves in-process at wiring time; a federated link ships it to the
+//! destination runtime over the broker socket, which only the owning user
+//! can open.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{FRAME_HEADER_SIZE, FrameHeader};
+use crate::core::error::{Error, Result};
+
+/// Key length of the channel AEAD.
+pub const PAYLOAD_KEY_LEN: usize = 32;
+
+const TAG_LEN: usize = 16;
+
+/// Bytes a sealed payload adds to the plaintext: the nonce and the tag.
+pub const SEALED_PAYLOAD_OVERHEAD: usize = NONCE_LEN + TAG_LEN;
+
+/// A channel's raw key. Serializes as lowercase hex for the broker handshake;
+/// `Debug` never prints it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PayloadKey([u8; PAYLOAD_KEY_LEN]);
+
+impl PayloadKey {
+    /// A fresh key from the system CSPRNG.
+    pub fn generate() -> Result<Self> {
+        let mut key = [0u8; PAYLOAD_KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| Error::Runtime("system RNG failed to generate a channel key".into()))?;
+        Ok(Self(key))
+    }
+
+    fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != PAYLOAD_KEY_LEN * 2 {
+            return None;
+        }
+        let mut key = [0u8; PAYLOAD_KEY_LEN];
+        for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+        }
+        Some(Self(key))
+    }
+}
+
+impl std::fmt::Debug for PayloadKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PayloadKey(<redacted>)")
+    }
+}
+
+impl Serialize for PayloadKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for PayloadKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Self::from_hex(&hex).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "channel key must be {} hex characters",
+                PAYLOAD_KEY_LEN * 2
+            ))
+        })
+    }
+}
+
+/// Seals and opens one channel's frames. Clones share the key and the nonce
+/// counter.
+#[derive(Clone)]
+pub struct PayloadCipher {
+    key: PayloadKey,
+    aead: Arc<LessSafeKey>,
+    nonce_prefix: [u8; 4],
+    next_nonce: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadCipher").finish_non_exhaustive()
+    }
+}
+
+impl PayloadCipher {
+    /// A cipher under a fresh key.
+    pub fn generate() -> Result<Self> {
+        Self::new(PayloadKey::generate()?)
+    }
+
+    pub fn new(key: PayloadKey) -> Result<Self> {
+        let unbound = UnboundKey::new(&CHACHA20_POLY1305, &key.0)
+            .map_err(|_| Error::Runtime("invalid channel key".into()))?;
+        let mut nonce_prefix = [0u8; 4];
+        SystemRandom::new()
+            .fill(&mut nonce_prefix)
+            .map_err(|_| Error::Runtime("system RNG failed to generate a nonce prefix".into()))?;
+        Ok(Self {
+            key,
+            aead: Arc::new(LessSafeKey::new(unbound)),
+            nonce_prefix,
+            next_nonce: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// The key, for handing to a subscriber in another runtime.
+    pub fn key(&self) -> &PayloadKey {
+        &self.key
+    }
+
+    /// Seal `plaintext` into `frame`, whose first [`FRAME_HEADER_SIZE`] bytes
+    /// already hold the header and whose body is exactly
+    /// `plaintext.len() + SEALED_PAYLOAD_OVERHEAD` bytes.
+    pub fn seal_frame(&self, frame: &mut [u8], plaintext: &[u8]) -> Result<()> {
+        if frame.len() != FRAME_HEADER_SIZE + plaintext.len() + SEALED_PAYLOAD_OVERHEAD {
+            return Err(Error::Link(format!(
+                "sealed frame buffer is {} bytes, expected {}",
+                frame.len(),
+                FRAME_HEADER_SIZE + plaintext.len() + SEALED_PAYLOAD_OVERHEAD
+            )));
+        }
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..4].copy_from_slice(&self.nonce_prefix);
+        let counter = self.next_nonce.fetch_add(1, Ordering::Relaxed);
+        nonce[4..].copy_from_slice(&counter.to_le_bytes());
+
+        let (header, body) = frame.split_at_mut(FRAME_HEADER_SIZE);
+        let (nonce_bytes, rest) = body.split_at_mut(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at_mut(plaintext.len());
+        nonce_bytes.copy_from_slice(&nonce);
+        ciphertext.copy_from_slice(plaintext);
+        let sealed_tag = self
+            .aead
+            .seal_in_place_separate_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(&*header),
+                ciphertext,
+            )
+            .map_err(|_| Error::Link("failed to seal channel payload".into()))?;
+        tag.copy_from_slice(sealed_tag.as_ref());
+        Ok(())
+    }
+
+    /// Open a sealed `frame`, returning it with the plaintext payload and a
+    /// header whose `len` counts only the plaintext — the shape an unencrypted
+    /// channel delivers. Fails when the frame was not sealed under this key or
+    /// any byte of it, header included, changed.
+    pub fn open_frame(&self, frame: &[u8]) -> Result<Vec<u8>> {
+        if frame.len() < FRAME_HEADER_SIZE + SEALED_PAYLOAD_OVERHEAD {
+            return Err(Error::Link(format!(
+                "sealed frame of {} bytes is shorter than its header and AEAD overhead",
+                frame.len()
+            )));
+        }
+        let mut header = FrameHeader::read_from_slice(frame);
+        let body_end = FRAME_HEADER_SIZE + header.len as usize;
+        if body_end > frame.len() || (header.len as usize) < SEALED_PAYLOAD_OVERHEAD {
+            return Err(Error::Link(format!(
+                "sealed frame header claims {} payload bytes, frame carries {}",
+                header.len,
+                frame.len() - FRAME_HEADER_SIZE
+            )));
+        }
+        let (aad, body) = frame[..body_end].split_at(FRAME_HEADER_SIZE);
+        let (nonce, sealed) = body.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| Error::Link("malformed channel payload nonce".into()))?;
+
+        let mut opened = Vec::with_capacity(FRAME_HEADER_SIZE + sealed.len());
+        opened.extend_from_slice(aad);
+        opened.extend_from_slice(sealed);
+        let plaintext_len = self
+            .aead
+            .open_in_place(nonce, Aad::from(aad), &mut opened[FRAME_HEADER_SIZE..])
+            .map_err(|_| {
+                Error::Link("channel payload failed authentication (wrong key or tampered)".into())
+            })?
+            .len();
+        opened.truncate(FRAME_HEADER_SIZE + plaintext_len);
+        header.len = plaintext_len as u32;
+        header.write_to_slice(&mut opened[..FRAME_HEADER_SIZE]);
+        Ok(opened)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceoryx2::SchemaIdentWire;
+
+    fn sealed(cipher: &PayloadCipher, plaintext: &[u8]) -> Vec<u8> {
+        let body_len = plaintext.len() + SEALED_PAYLOAD_OVERHEAD;
+        let mut frame = vec![0u8; FRAME_HEADER_SIZE + body_len];
+        FrameHeader::new("video_out", SchemaIdentWire::default(), 42, body_len as u32)
+            .unwrap()
+            .write_to_slice(&mut frame[..FRAME_HEADER_SIZE]);
+        cipher.seal_frame(&mut frame, plaintext).unwrap();
+        frame
+    }
+
+    #[test]
+    fn a_sealed_frame_opens_to_the_unencrypted_wire_shape() {
+        let cipher = PayloadCipher::generate().unwrap();
+        let frame = sealed(&cipher, b"sensitive frame");
+        assert!(
+            !frame.windows(9).any(|window| window == b"sensitive"),
+            "the plaintext must not appear in the shared segment"
+        );
+
+        let opened = cipher.open_frame(&frame).unwrap();
+        let header = FrameHeader::read_from_slice(&opened);
+        assert_eq!(header.port(), "video_out");
+        assert_eq!(header.timestamp_ns, 42);
+        assert_eq!(header.len as usize, b"sensitive frame".len());
+        assert_eq!(&opened[FRAME_HEADER_SIZE..], b"sensitive frame");
+
+        // Successive frames never reuse a nonce.
+        let again = sealed(&cipher, b"sensitive frame");
+        let nonce = |frame: &[u8]| frame[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + NONCE_LEN].to_vec();
+        assert_ne!(nonce(&frame), nonce(&again));
+    }
+
+    #[test]
+    fn a_wrong_key_or_any_changed_byte_fails_to_open() {
+        let cipher = PayloadCipher::generate().unwrap();
+        let frame = sealed(&cipher, b"payload");
+
+        let stranger = PayloadCipher::generate().unwrap();
+        assert!(stranger.open_frame(&frame).is_err());
+
+        // Payload, tag and header (here the timestamp) are all authenticated.
+        for index in [
+            FRAME_HEADER_SIZE + NONCE_LEN,
+            frame.len() - 1,
+            FRAME_HEADER_SIZE - 12,
+        ] {
+            let mut tampered = frame.clone();
+            tampered[index] ^= 1;
+            assert!(cipher.open_frame(&tampered).is_err(), "byte {index}");
+        }
+        assert!(cipher.open_frame(&frame[..FRAME_HEADER_SIZE + 4]).is_err());
+    }
+
+    #[test]
+    fn the_key_crosses_the_broker_as_hex_and_never_prints() {
+        let cipher = PayloadCipher::generate().unwrap();
+        let json = serde_json::to_string(cipher.key()).unwrap();
+        assert_eq!(json.len(), PAYLOAD_KEY_LEN * 2 + 2);
+        let key: PayloadKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(&key, cipher.key());
+        assert!(serde_json::from_str::<PayloadKey>("\"abcd\"").is_err());
+        assert_eq!(format!("{:?}", key), "PayloadKey(<redacted>)");
+
+        // A subscriber holding the shipped key opens the publisher's frames.
+        let subscriber = PayloadCipher::new(key).unwrap();
+        let opened = subscriber
+            .open_frame(&sealed(&cipher, b"federated"))
+            .unwrap();
+        assert_eq!(&opened[FRAME_HEADER_SIZE..], b"federated");
+    }
+}
@@ -149,9 +149,11 @@ pub use host_rhi::HostSurfaceStoreExt;
 /// Vulkan Video codec layer — engine-tier H.264/H.265 encode/decode
 /// primitives. Public surface lives at `crate::vulkan::video::*`; this
 /// facade re-exports the codec types at engine root so the SDK's
-/// `sdk::engine::video::*` tier-2 surface can pass them through.
+/// `sdk::engine::video::*` tier-2 surface can pass them through. The
+/// VAAPI fallback and the backend-selecting codec factory ride along.
 #[cfg(target_os = "linux")]
 pub mod video {
+    pub use crate::linux::{codec_factory, vaapi};
     pub use crate::vulkan::video::*;
 }
 
@@ -227,20 +229,29 @@ pub mod platform {
 // path resolution.
 
 pub mod sdk {
+    pub use crate::core::chaos;
     pub use crate::core::context;
     pub use crate::core::descriptors;
     pub use crate::core::display_info;
     pub use crate::core::error;
     pub use crate::core::execution;
+    pub use crate::core::frame_snapshot;
     pub use crate::core::graph;
+    pub use crate::core::graph_edit_history;
+    pub use crate::core::graph_patch;
     pub use crate::core::graph_snapshot;
     pub use crate::core::json_schema;
     pub use crate::core::media_clock;
+    pub use crate::core::midi_mapping;
+    pub use crate::core::parameter_automation;
     pub use crate::core::plugin;
+    pub use crate::core::port_recording;
     pub use crate::core::prelude;
+    pub use crate::core::preset_morph;
     pub use crate::core::rhi;
     pub use crate::core::runtime;
     pub use crate::core::sync;
+    pub use crate::core::testing;
     pub use crate::core::texture;
     pub use crate::core::utils;
 
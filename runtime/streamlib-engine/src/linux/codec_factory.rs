@@ -0,0 +1,373 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hardware video codec backend selection.
+//!
+//! Not every box has Vulkan Video (NVENC / NVDEC class hardware, or a Mesa
+//! new enough to expose it). The factory picks, per session, between the
+//! Vulkan Video [`SimpleEncoder`] / [`SimpleDecoder`] and the VAAPI
+//! fallbacks in [`super::vaapi`], based on what the host device reports
+//! and what [`super::vaapi::probe`] finds at runtime.
+//!
+//! The preference can be pinned via:
+//! 1. Explicit value passed to [`VideoCodecBackendPreference::resolve`]
+//! 2. `STREAMLIB_VIDEO_CODEC_BACKEND` environment variable
+//! 3. `auto` (Vulkan Video first, VAAPI when Vulkan Video is unavailable)
+
+use std::str::FromStr;
+
+use crate::vulkan::rhi::HostVulkanTexture;
+use crate::vulkan::video::decode::{SimpleDecodedFrame, SimpleDecoder};
+use crate::vulkan::video::encode::{Codec, EncodePacket, SimpleEncoder};
+use crate::vulkan::video::{H273ColorVui, VideoError};
+
+use super::vaapi::{VaapiH264Decoder, VaapiH264Encoder};
+
+/// A concrete hardware codec backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodecBackend {
+    /// `VK_KHR_video_encode_queue` / `VK_KHR_video_decode_queue`.
+    VulkanVideo,
+    /// libva on a DRM render node.
+    Vaapi,
+}
+
+impl VideoCodecBackend {
+    /// Get the backend name as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::VulkanVideo => "vulkan",
+            Self::Vaapi => "vaapi",
+        }
+    }
+}
+
+impl std::fmt::Display for VideoCodecBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Which backend sessions should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VideoCodecBackendPreference {
+    /// Vulkan Video when the device supports it, VAAPI otherwise.
+    #[default]
+    Auto,
+    /// Vulkan Video only.
+    VulkanVideo,
+    /// VAAPI only.
+    Vaapi,
+}
+
+impl VideoCodecBackendPreference {
+    /// Environment variable name for backend override.
+    pub const ENV_VAR: &'static str = "STREAMLIB_VIDEO_CODEC_BACKEND";
+
+    /// Resolve the preference to use.
+    ///
+    /// Resolution priority:
+    /// 1. Explicit value (if provided)
+    /// 2. `STREAMLIB_VIDEO_CODEC_BACKEND` environment variable
+    /// 3. [`Self::Auto`]
+    pub fn resolve(explicit: Option<Self>) -> Self {
+        if let Some(preference) = explicit {
+            return preference;
+        }
+        if let Ok(env_value) = std::env::var(Self::ENV_VAR) {
+            match env_value.parse() {
+                Ok(preference) => return preference,
+                Err(e) => tracing::warn!("{}: {e}", Self::ENV_VAR),
+            }
+        }
+        Self::Auto
+    }
+
+    /// Get the preference name as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::VulkanVideo => "vulkan",
+            Self::Vaapi => "vaapi",
+        }
+    }
+}
+
+impl FromStr for VideoCodecBackendPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "vulkan" | "vulkan-video" => Ok(Self::VulkanVideo),
+            "vaapi" | "va" => Ok(Self::Vaapi),
+            _ => Err(format!(
+                "Unknown video codec backend '{}'. Valid values: auto, vulkan, vaapi",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for VideoCodecBackendPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Pick a backend for one session. `vaapi_available` must already account
+/// for the codec (the VAAPI backend is H.264-only). `None` when neither
+/// backend can serve the request.
+pub fn select_backend(
+    preference: VideoCodecBackendPreference,
+    vulkan_video_available: bool,
+    vaapi_available: bool,
+) -> Option<VideoCodecBackend> {
+    match preference {
+        VideoCodecBackendPreference::Auto if vulkan_video_available => {
+            Some(VideoCodecBackend::VulkanVideo)
+        }
+        VideoCodecBackendPreference::Auto if vaapi_available => Some(VideoCodecBackend::Vaapi),
+        VideoCodecBackendPreference::VulkanVideo if vulkan_video_available => {
+            Some(VideoCodecBackend::VulkanVideo)
+        }
+        VideoCodecBackendPreference::Vaapi if vaapi_available => Some(VideoCodecBackend::Vaapi),
+        _ => None,
+    }
+}
+
+/// Whether VAAPI can encode (`encode = true`) or decode `codec` on this
+/// host. Probes libva once per process.
+pub fn vaapi_supports(codec: Codec, encode: bool) -> bool {
+    if codec != Codec::H264 {
+        return false;
+    }
+    let caps = super::vaapi::probe();
+    if encode {
+        caps.h264_encode
+    } else {
+        caps.h264_decode
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Backend-erased sessions
+// ---------------------------------------------------------------------------
+
+/// A hardware encoder on whichever backend the factory chose.
+pub enum VideoEncoderBackend {
+    VulkanVideo(SimpleEncoder),
+    Vaapi(VaapiH264Encoder),
+}
+
+impl VideoEncoderBackend {
+    pub fn backend(&self) -> VideoCodecBackend {
+        match self {
+            Self::VulkanVideo(_) => VideoCodecBackend::VulkanVideo,
+            Self::Vaapi(_) => VideoCodecBackend::Vaapi,
+        }
+    }
+
+    /// SPS/PPS (and VPS for H.265) for this session.
+    pub fn header(&self) -> &[u8] {
+        match self {
+            Self::VulkanVideo(e) => e.header(),
+            Self::Vaapi(e) => e.header(),
+        }
+    }
+
+    /// Force the next frame to be encoded as an IDR keyframe.
+    pub fn force_idr(&mut self) {
+        match self {
+            Self::VulkanVideo(e) => e.force_idr(),
+            Self::Vaapi(e) => e.force_idr(),
+        }
+    }
+
+    /// Minimum source texture size for [`Self::encode_texture`].
+    pub fn aligned_extent(&self) -> (u32, u32) {
+        match self {
+            Self::VulkanVideo(e) => e.aligned_extent(),
+            Self::Vaapi(e) => e.aligned_extent(),
+        }
+    }
+
+    /// Encode one tightly packed NV12 frame.
+    pub fn submit_frame(
+        &mut self,
+        nv12_data: &[u8],
+        timestamp_ns: Option<i64>,
+    ) -> Result<Vec<EncodePacket>, VideoError> {
+        match self {
+            Self::VulkanVideo(e) => e.submit_frame(nv12_data, timestamp_ns),
+            Self::Vaapi(e) => Ok(e.submit_frame(nv12_data, timestamp_ns)?),
+        }
+    }
+
+    /// Encode an RGBA/BGRA texture in `SHADER_READ_ONLY_OPTIMAL`. Vulkan
+    /// Video samples its image view; VAAPI imports its DMA-BUF.
+    pub fn encode_texture(
+        &mut self,
+        texture: &HostVulkanTexture,
+        timestamp_ns: Option<i64>,
+    ) -> Result<Vec<EncodePacket>, VideoError> {
+        match self {
+            Self::VulkanVideo(e) => {
+                let view = texture
+                    .image_view()
+                    .map_err(|err| VideoError::Engine(format!("resolve image view: {err}")))?;
+                e.encode_image(view, timestamp_ns)
+            }
+            Self::Vaapi(e) => Ok(e.encode_texture(texture, timestamp_ns)?),
+        }
+    }
+
+    /// Flush any frames held for reordering.
+    pub fn finish(&mut self) -> Result<Vec<EncodePacket>, VideoError> {
+        match self {
+            Self::VulkanVideo(e) => e.finish(),
+            Self::Vaapi(e) => Ok(e.finish()?),
+        }
+    }
+}
+
+impl From<SimpleEncoder> for VideoEncoderBackend {
+    fn from(encoder: SimpleEncoder) -> Self {
+        Self::VulkanVideo(encoder)
+    }
+}
+
+impl From<VaapiH264Encoder> for VideoEncoderBackend {
+    fn from(encoder: VaapiH264Encoder) -> Self {
+        Self::Vaapi(encoder)
+    }
+}
+
+/// A hardware decoder on whichever backend the factory chose.
+pub enum VideoDecoderBackend {
+    VulkanVideo(SimpleDecoder),
+    Vaapi(VaapiH264Decoder),
+}
+
+impl VideoDecoderBackend {
+    pub fn backend(&self) -> VideoCodecBackend {
+        match self {
+            Self::VulkanVideo(_) => VideoCodecBackend::VulkanVideo,
+            Self::Vaapi(_) => VideoCodecBackend::Vaapi,
+        }
+    }
+
+    /// Feed Annex B data; returns the pictures it completed.
+    pub fn feed(&mut self, data: &[u8]) -> Result<Vec<SimpleDecodedFrame>, VideoError> {
+        match self {
+            Self::VulkanVideo(d) => d.feed(data),
+            Self::Vaapi(d) => d.feed(data),
+        }
+    }
+
+    /// Signal a discontinuity (e.g. seek).
+    pub fn feed_discontinuity(&mut self) {
+        match self {
+            Self::VulkanVideo(d) => d.feed_discontinuity(),
+            Self::Vaapi(d) => d.feed_discontinuity(),
+        }
+    }
+
+    /// Full reset: reconfigure on the next SPS.
+    pub fn reset(&mut self) {
+        match self {
+            Self::VulkanVideo(d) => d.reset(),
+            Self::Vaapi(d) => d.reset(),
+        }
+    }
+
+    /// Number of frames decoded so far.
+    pub fn decode_count(&self) -> u64 {
+        match self {
+            Self::VulkanVideo(d) => d.decode_count(),
+            Self::Vaapi(d) => d.decode_count(),
+        }
+    }
+
+    /// Stream dimensions from the active SPS.
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self {
+            Self::VulkanVideo(d) => d.dimensions(),
+            Self::Vaapi(d) => d.dimensions(),
+        }
+    }
+
+    /// H.273 colour VUI of the active SPS.
+    pub fn current_color_vui(&self) -> Option<H273ColorVui> {
+        match self {
+            Self::VulkanVideo(d) => d.current_color_vui(),
+            Self::Vaapi(d) => d.current_color_vui(),
+        }
+    }
+}
+
+impl From<SimpleDecoder> for VideoDecoderBackend {
+    fn from(decoder: SimpleDecoder) -> Self {
+        Self::VulkanVideo(decoder)
+    }
+}
+
+impl From<VaapiH264Decoder> for VideoDecoderBackend {
+    fn from(decoder: VaapiH264Decoder) -> Self {
+        Self::Vaapi(decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use VideoCodecBackendPreference as Pref;
+
+    #[test]
+    fn auto_prefers_vulkan_video_and_falls_back_to_vaapi() {
+        assert_eq!(
+            select_backend(Pref::Auto, true, true),
+            Some(VideoCodecBackend::VulkanVideo)
+        );
+        assert_eq!(
+            select_backend(Pref::Auto, false, true),
+            Some(VideoCodecBackend::Vaapi)
+        );
+        assert_eq!(select_backend(Pref::Auto, false, false), None);
+    }
+
+    #[test]
+    fn pinned_preference_never_falls_back() {
+        assert_eq!(select_backend(Pref::VulkanVideo, false, true), None);
+        assert_eq!(select_backend(Pref::Vaapi, true, false), None);
+        assert_eq!(
+            select_backend(Pref::Vaapi, true, true),
+            Some(VideoCodecBackend::Vaapi)
+        );
+    }
+
+    #[test]
+    fn preference_parses_case_insensitively() {
+        assert_eq!("VAAPI".parse::<Pref>(), Ok(Pref::Vaapi));
+        assert_eq!("vulkan".parse::<Pref>(), Ok(Pref::VulkanVideo));
+        assert_eq!("Auto".parse::<Pref>(), Ok(Pref::Auto));
+        assert!(
+            "nvenc"
+                .parse::<Pref>()
+                .unwrap_err()
+                .contains("Valid values")
+        );
+    }
+
+    #[test]
+    fn explicit_preference_wins() {
+        assert_eq!(Pref::resolve(Some(Pref::Vaapi)), Pref::Vaapi);
+    }
+
+    #[test]
+    fn vaapi_never_claims_h265() {
+        assert!(!vaapi_supports(Codec::H265, true));
+        assert!(!vaapi_supports(Codec::H265, false));
+    }
+}
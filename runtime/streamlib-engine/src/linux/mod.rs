@@ -4,9 +4,11 @@
 //! Linux-specific implementations.
 
 pub mod audio_clock;
+pub mod codec_factory;
 pub mod rtkit;
 pub mod surface_share;
 pub mod thread_priority;
+pub mod vaapi;
 
 pub use audio_clock::LinuxTimerFdAudioClock;
 
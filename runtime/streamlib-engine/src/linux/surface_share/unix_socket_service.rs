@@ -9,6 +9,11 @@
 //! DMA-BUF fds over `SCM_RIGHTS`. Surfaces may carry up to
 //! [`streamlib_surface_client::MAX_DMA_BUF_PLANES`] plane fds — one per plane
 //! for multi-plane DMA-BUFs (e.g. NV12 with separate Y and UV allocations).
+//!
+//! The same socket is the runtime's federation broker: another runtime sends
+//! `federate_link` to wire one of its output ports into a processor here, then
+//! keeps the connection open and pings it with `federation_ping`. Links
+//! federated over a connection are released when it closes.
 
 use std::io::Read;
 use std::os::unix::io::{AsRawFd, RawFd};
@@ -21,6 +26,8 @@ use streamlib_surface_client::{
     MAX_DMA_BUF_PLANES, MAX_SCM_RIGHTS_FDS, recv_message_with_fds, send_message_with_fds,
 };
 
+use crate::core::runtime::federation::{FederateLinkRequest, FederationLinkHandler};
+
 use super::state::{
     SurfaceRegistration, SurfaceShareState, VK_IMAGE_ALLOCATION_SIZE_DEFAULT,
     VK_IMAGE_ARRAY_LAYERS_DEFAULT, VK_IMAGE_MIP_LEVELS_DEFAULT, VK_IMAGE_SAMPLES_DEFAULT,
@@ -32,6 +39,7 @@ pub struct UnixSocketSurfaceService {
     socket_path: PathBuf,
     listener_thread: Option<thread::JoinHandle<()>>,
     shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+    federation: Option<Arc<dyn FederationLinkHandler>>,
 }
 
 impl UnixSocketSurfaceService {
@@ -41,9 +49,20 @@ impl UnixSocketSurfaceService {
             socket_path,
             listener_thread: None,
             shutdown_flag: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            federation: None,
         }
     }
 
+    /// Accept federated links from other runtimes through `handler`. Without
+    /// one, the federation ops answer with an error.
+    pub(crate) fn with_federation_handler(
+        mut self,
+        handler: Arc<dyn FederationLinkHandler>,
+    ) -> Self {
+        self.federation = Some(handler);
+        self
+    }
+
     pub fn start(&mut self) -> Result<(), String> {
         if self.socket_path.exists() {
             std::fs::remove_file(&self.socket_path)
@@ -68,9 +87,10 @@ impl UnixSocketSurfaceService {
 
         let state = self.state.clone();
         let shutdown_flag = self.shutdown_flag.clone();
+        let federation = self.federation.clone();
 
         let handle = thread::spawn(move || {
-            run_listener(listener, state, shutdown_flag);
+            run_listener(listener, state, federation, shutdown_flag);
         });
 
         self.listener_thread = Some(handle);
@@ -108,6 +128,7 @@ impl Drop for UnixSocketSurfaceService {
 fn run_listener(
     listener: UnixListener,
     state: SurfaceShareState,
+    federation: Option<Arc<dyn FederationLinkHandler>>,
     shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
 ) {
     loop {
@@ -125,13 +146,28 @@ fn run_listener(
                 // on out-of-process subprocess connections.
                 let is_subprocess_peer = is_out_of_process_peer(&stream);
                 let state = state.clone();
+                let federation = federation.clone();
                 thread::spawn(move || {
                     let mut connection_runtime_id: Option<String> = None;
-                    let conn_result =
-                        handle_client_connection(stream, state.clone(), &mut connection_runtime_id);
+                    let mut federated_links: Vec<String> = Vec::new();
+                    let conn_result = handle_client_connection(
+                        stream,
+                        state.clone(),
+                        federation.as_deref(),
+                        &mut connection_runtime_id,
+                        &mut federated_links,
+                    );
                     if let Err(e) = conn_result {
                         tracing::debug!("[Surface share] Client connection ended: {}", e);
                     }
+                    // A federated link lives exactly as long as the connection
+                    // that made it — same-process peers included, since the link
+                    // has no owner once its source stops pinging.
+                    if let Some(federation) = federation.as_deref() {
+                        for link_id in &federated_links {
+                            federation.release_remote_link(link_id);
+                        }
+                    }
                     // EPOLLHUP-equivalent watchdog: when the kernel closes the
                     // socket (typical on subprocess SIGKILL), the per-connection
                     // read loop above exits with `UnexpectedEof`. Release every
@@ -161,7 +197,9 @@ fn run_listener(
 fn handle_client_connection(
     mut stream: UnixStream,
     state: SurfaceShareState,
+    federation: Option<&dyn FederationLinkHandler>,
     observed_runtime_id: &mut Option<String>,
+    federated_links: &mut Vec<String>,
 ) -> Result<(), std::io::Error> {
     stream.set_nonblocking(false)?;
 
@@ -228,6 +266,10 @@ fn handle_client_connection(
             "unregister" | "release" => handle_unregister(&state, &request),
             "check_in" => handle_check_in(&state, &request, &received_fds),
             "update_layout" => handle_update_layout(&state, &request),
+            "federate_link" | "unfederate_link" | "federation_ping" => (
+                handle_federation_op(federation, op, &request, federated_links),
+                Vec::new(),
+            ),
             _ => (
                 serde_json::json!({"error": format!("unknown operation: {}", op)}),
                 Vec::new(),
@@ -916,6 +958,50 @@ fn is_out_of_process_peer(stream: &UnixStream) -> bool {
     ucred.pid != host_pid
 }
 
+/// Federation ops from another runtime. `federate_link` wires the destination
+/// half of a link and remembers its id on this connection; `unfederate_link`
+/// releases it early; `federation_ping` is the source's liveness probe.
+fn handle_federation_op(
+    federation: Option<&dyn FederationLinkHandler>,
+    op: &str,
+    request: &serde_json::Value,
+    federated_links: &mut Vec<String>,
+) -> serde_json::Value {
+    let Some(federation) = federation else {
+        return serde_json::json!({"error": "this runtime does not accept federated links"});
+    };
+
+    match op {
+        "federate_link" => {
+            let link_request: FederateLinkRequest = match serde_json::from_value(request.clone()) {
+                Ok(link_request) => link_request,
+                Err(e) => {
+                    return serde_json::json!({
+                        "error": format!("malformed federate_link request: {}", e)
+                    });
+                }
+            };
+            let link_id = link_request.link_id.to_string();
+            match federation.accept_remote_link(link_request) {
+                Ok(reply) => {
+                    federated_links.push(link_id);
+                    serde_json::to_value(reply).expect("FederateLinkReply must serialize cleanly")
+                }
+                Err(e) => serde_json::json!({"error": e.to_string()}),
+            }
+        }
+        "unfederate_link" => {
+            let Some(link_id) = request.get("link_id").and_then(|v| v.as_str()) else {
+                return serde_json::json!({"error": "missing link_id"});
+            };
+            federated_links.retain(|id| id != link_id);
+            federation.release_remote_link(link_id);
+            serde_json::json!({"success": true})
+        }
+        _ => serde_json::json!({"success": true}),
+    }
+}
+
 /// Release every surface registered by `runtime_id`. Called when a client
 /// connection drops (kernel-side equivalent of EPOLLHUP — typical when a
 /// polyglot subprocess SIGKILLs mid-flight). Idempotent: any surface the
@@ -1943,4 +2029,98 @@ mod tests {
         drop(stream);
         service.stop();
     }
+
+    /// Records what the broker asked of it, accepting every link.
+    #[derive(Default)]
+    struct RecordingFederationHandler {
+        accepted: std::sync::Mutex<Vec<String>>,
+        released: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FederationLinkHandler for RecordingFederationHandler {
+        fn accept_remote_link(
+            &self,
+            request: FederateLinkRequest,
+        ) -> crate::core::Result<crate::core::runtime::federation::FederateLinkReply> {
+            self.accepted
+                .lock()
+                .unwrap()
+                .push(request.link_id.to_string());
+            Ok(crate::core::runtime::federation::FederateLinkReply {
+                notify_service_name: format!("streamlib/{}/notify", request.dest.processor_id),
+                max_notifiers: 1,
+            })
+        }
+
+        fn release_remote_link(&self, link_id: &str) {
+            self.released.lock().unwrap().push(link_id.to_string());
+        }
+    }
+
+    /// A link federated over a connection is released when that connection
+    /// closes, without an explicit `unfederate_link` — the broker half of
+    /// federation liveness. Pings answer while the connection is up.
+    #[test]
+    fn federated_link_is_released_when_its_connection_closes() {
+        let handler = Arc::new(RecordingFederationHandler::default());
+        let socket_path = tmp_socket_path();
+        let mut service =
+            UnixSocketSurfaceService::new(SurfaceShareState::new(), socket_path.clone())
+                .with_federation_handler(handler.clone());
+        service.start().expect("service start");
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let stream = connect_to_surface_share_socket(&socket_path).expect("connect");
+        let federate = serde_json::json!({
+            "op": "federate_link",
+            "link_id": "Lfederated",
+            "source_runtime_id": "Rsource",
+            "source": {"processor_id": "Pcamera", "port_name": "video_out"},
+            "dest": {"processor_id": "Pdisplay", "port_name": "video_in"},
+            "channel": {
+                "channel_service_name": "pcamera/video_out",
+                "max_subscribers": 2,
+                "max_queued_messages": 1,
+                "enable_safe_overflow": true,
+            },
+        });
+        let (reply, _) = send_request_with_fds(&stream, &federate, &[], 0).expect("federate_link");
+        assert_eq!(reply["notify_service_name"], "streamlib/Pdisplay/notify");
+        assert_eq!(reply["max_notifiers"], 1);
+
+        let ping = serde_json::json!({"op": "federation_ping", "link_id": "Lfederated"});
+        let (pong, _) = send_request_with_fds(&stream, &ping, &[], 0).expect("federation_ping");
+        assert_eq!(pong["success"], true);
+        assert!(handler.released.lock().unwrap().is_empty());
+
+        drop(stream);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        while handler.released.lock().unwrap().is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(*handler.accepted.lock().unwrap(), vec!["Lfederated"]);
+        assert_eq!(*handler.released.lock().unwrap(), vec!["Lfederated"]);
+
+        service.stop();
+    }
+
+    /// Without a handler the federation ops answer with an error instead of
+    /// silently accepting a link nothing will wire.
+    #[test]
+    fn federation_ops_are_refused_without_a_handler() {
+        let socket_path = tmp_socket_path();
+        let mut service =
+            UnixSocketSurfaceService::new(SurfaceShareState::new(), socket_path.clone());
+        service.start().expect("service start");
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let stream = connect_to_surface_share_socket(&socket_path).expect("connect");
+        let ping = serde_json::json!({"op": "federation_ping", "link_id": "Lfederated"});
+        let (reply, _) = send_request_with_fds(&stream, &ping, &[], 0).expect("federation_ping");
+        assert!(reply.get("error").is_some(), "reply: {reply}");
+
+        service.stop();
+    }
 }
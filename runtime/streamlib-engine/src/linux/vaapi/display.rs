@@ -0,0 +1,225 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! DRM render node + `VADisplay` lifetime, and the H.264 capability query
+//! behind [`super::probe`].
+
+use std::ffi::{CStr, c_int};
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::VaapiError;
+use super::ffi::{self, VaApi};
+
+/// First and last DRM render minor (`/dev/dri/renderD128..=renderD191`).
+const RENDER_NODE_MINORS: std::ops::RangeInclusive<u32> = 128..=191;
+
+/// Which H.264 / H.265 entrypoints the VAAPI driver exposes.
+///
+/// `h265_*` is reported for diagnostics only — the backend implements
+/// H.264, so the codec factory never routes H.265 sessions here.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VaapiCapabilities {
+    pub h264_decode: bool,
+    pub h264_encode: bool,
+    pub h265_decode: bool,
+    pub h265_encode: bool,
+}
+
+impl VaapiCapabilities {
+    /// `true` when any codec entrypoint was found.
+    pub fn any(&self) -> bool {
+        self.h264_decode || self.h264_encode || self.h265_decode || self.h265_encode
+    }
+}
+
+/// An initialised `VADisplay` on a DRM render node. Terminates the display
+/// before closing the node on drop.
+pub(super) struct VaDisplay {
+    api: Arc<VaApi>,
+    raw: ffi::VADisplay,
+    path: PathBuf,
+    /// Kept open for the display's lifetime; libva does not dup it.
+    _node: File,
+}
+
+// SAFETY: libva serialises calls on a display internally; the handle is
+// only ever used through `&self` methods that forward to libva.
+unsafe impl Send for VaDisplay {}
+unsafe impl Sync for VaDisplay {}
+
+impl VaDisplay {
+    /// Open [`super::VAAPI_DEVICE_ENV_VAR`] if set, otherwise the first
+    /// render node whose driver initialises.
+    pub(super) fn open() -> Result<Arc<Self>, VaapiError> {
+        let api = VaApi::get()?;
+        if let Ok(path) = std::env::var(super::VAAPI_DEVICE_ENV_VAR) {
+            return Self::open_node(&api, Path::new(&path)).map(Arc::new);
+        }
+        let mut last_err = None;
+        for minor in RENDER_NODE_MINORS {
+            let path = PathBuf::from(format!("/dev/dri/renderD{minor}"));
+            if !path.exists() {
+                continue;
+            }
+            match Self::open_node(&api, &path) {
+                Ok(display) => return Ok(Arc::new(display)),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| VaapiError::NoDevice("no /dev/dri/renderD* nodes".into())))
+    }
+
+    fn open_node(api: &Arc<VaApi>, path: &Path) -> Result<Self, VaapiError> {
+        let node = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| VaapiError::NoDevice(format!("{}: {e}", path.display())))?;
+        // SAFETY: `node` is an open DRM fd that outlives the display.
+        let raw = unsafe { (api.vaGetDisplayDRM)(node.as_raw_fd()) };
+        if raw.is_null() {
+            return Err(VaapiError::NoDevice(format!(
+                "{}: vaGetDisplayDRM returned NULL",
+                path.display()
+            )));
+        }
+        let (mut major, mut minor): (c_int, c_int) = (0, 0);
+        // SAFETY: `raw` is the display just returned; out-params are locals.
+        let status = unsafe { (api.vaInitialize)(raw, &mut major, &mut minor) };
+        if let Err(e) = api.check("vaInitialize", status) {
+            // SAFETY: balances vaGetDisplayDRM on the failure path.
+            unsafe { (api.vaTerminate)(raw) };
+            return Err(e);
+        }
+        tracing::debug!(
+            device = %path.display(),
+            "VAAPI {major}.{minor} initialised"
+        );
+        Ok(Self {
+            api: Arc::clone(api),
+            raw,
+            path: path.to_path_buf(),
+            _node: node,
+        })
+    }
+
+    pub(super) fn api(&self) -> &VaApi {
+        &self.api
+    }
+
+    pub(super) fn raw(&self) -> ffi::VADisplay {
+        self.raw
+    }
+
+    pub(super) fn device_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Driver vendor string (e.g. "Intel iHD driver ... 24.1.0").
+    pub(super) fn vendor(&self) -> String {
+        // SAFETY: the display is initialised; libva returns a static string.
+        unsafe {
+            let ptr = (self.api.vaQueryVendorString)(self.raw);
+            if ptr.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(ptr).to_string_lossy().into_owned()
+            }
+        }
+    }
+
+    /// Profiles the driver advertises.
+    pub(super) fn profiles(&self) -> Vec<ffi::VAProfile> {
+        // SAFETY: buffer sized by vaMaxNumProfiles; `num` is written back.
+        unsafe {
+            let max = (self.api.vaMaxNumProfiles)(self.raw).max(0) as usize;
+            let mut profiles = vec![ffi::VAProfileNone; max];
+            let mut num: c_int = 0;
+            let status =
+                (self.api.vaQueryConfigProfiles)(self.raw, profiles.as_mut_ptr(), &mut num);
+            if status != ffi::VA_STATUS_SUCCESS {
+                return Vec::new();
+            }
+            profiles.truncate(num.max(0) as usize);
+            profiles
+        }
+    }
+
+    /// Entrypoints the driver advertises for `profile`.
+    pub(super) fn entrypoints(&self, profile: ffi::VAProfile) -> Vec<ffi::VAEntrypoint> {
+        // SAFETY: buffer sized by vaMaxNumEntrypoints; `num` is written back.
+        unsafe {
+            let max = (self.api.vaMaxNumEntrypoints)(self.raw).max(0) as usize;
+            let mut entrypoints = vec![0; max];
+            let mut num: c_int = 0;
+            let status = (self.api.vaQueryConfigEntrypoints)(
+                self.raw,
+                profile,
+                entrypoints.as_mut_ptr(),
+                &mut num,
+            );
+            if status != ffi::VA_STATUS_SUCCESS {
+                return Vec::new();
+            }
+            entrypoints.truncate(num.max(0) as usize);
+            entrypoints
+        }
+    }
+
+    /// Read one config attribute; `None` when the driver reports it
+    /// unsupported for this profile / entrypoint.
+    pub(super) fn config_attribute(
+        &self,
+        profile: ffi::VAProfile,
+        entrypoint: ffi::VAEntrypoint,
+        attrib_type: c_int,
+    ) -> Option<u32> {
+        let mut attrib = ffi::VAConfigAttrib {
+            type_: attrib_type,
+            value: 0,
+        };
+        // SAFETY: one attribute in, one written back.
+        let status = unsafe {
+            (self.api.vaGetConfigAttributes)(self.raw, profile, entrypoint, &mut attrib, 1)
+        };
+        (status == ffi::VA_STATUS_SUCCESS && attrib.value != ffi::VA_ATTRIB_NOT_SUPPORTED)
+            .then_some(attrib.value)
+    }
+
+    pub(super) fn capabilities(&self) -> VaapiCapabilities {
+        let profiles = self.profiles();
+        let has = |profile: ffi::VAProfile, wanted: &[ffi::VAEntrypoint]| {
+            profiles.contains(&profile)
+                && self
+                    .entrypoints(profile)
+                    .iter()
+                    .any(|ep| wanted.contains(ep))
+        };
+        let h264_profiles = [
+            ffi::VAProfileH264High,
+            ffi::VAProfileH264Main,
+            ffi::VAProfileH264ConstrainedBaseline,
+        ];
+        let encode_entrypoints = [ffi::VAEntrypointEncSlice, ffi::VAEntrypointEncSliceLP];
+        VaapiCapabilities {
+            h264_decode: h264_profiles
+                .iter()
+                .any(|&p| has(p, &[ffi::VAEntrypointVLD])),
+            h264_encode: h264_profiles.iter().any(|&p| has(p, &encode_entrypoints)),
+            h265_decode: has(ffi::VAProfileHEVCMain, &[ffi::VAEntrypointVLD]),
+            h265_encode: has(ffi::VAProfileHEVCMain, &encode_entrypoints),
+        }
+    }
+}
+
+impl Drop for VaDisplay {
+    fn drop(&mut self) {
+        // SAFETY: every config / context / surface created on this display
+        // is owned by a struct holding an `Arc<VaDisplay>`, so they are
+        // gone by the time the last reference drops.
+        unsafe { (self.api.vaTerminate)(self.raw) };
+    }
+}
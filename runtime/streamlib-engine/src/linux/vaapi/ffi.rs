@@ -0,0 +1,847 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hand-written FFI declarations for the subset of `libva.so.2` /
+//! `libva-drm.so.2` the VAAPI codec backend uses. Resolved via
+//! [`libloading`] at runtime — the engine builds and runs on hosts without
+//! libva; a failed `dlopen` surfaces as [`VaapiError::LibraryNotFound`] and
+//! the codec factory falls back to (or stays on) Vulkan Video.
+//!
+//! Struct layouts mirror `va.h`, `va_enc_h264.h`, `va_vpp.h` and
+//! `va_drmcommon.h` from the libva 2.x line (VA-API 1.x). Bitfield unions
+//! are carried as their `uint32_t value` member and packed by hand at the
+//! call sites. Only the members the backend writes are named; every buffer
+//! is zero-initialised before use, which is the documented default for
+//! all of them.
+
+#![allow(non_camel_case_types, non_snake_case, dead_code)]
+
+use std::ffi::{CStr, c_char, c_int, c_uint, c_void};
+use std::sync::{Arc, OnceLock};
+
+use libloading::Library;
+
+use super::VaapiError;
+
+pub type VADisplay = *mut c_void;
+pub type VAStatus = c_int;
+pub type VAGenericID = c_uint;
+pub type VAConfigID = VAGenericID;
+pub type VAContextID = VAGenericID;
+pub type VASurfaceID = VAGenericID;
+pub type VABufferID = VAGenericID;
+pub type VAImageID = VAGenericID;
+pub type VAProfile = c_int;
+pub type VAEntrypoint = c_int;
+pub type VABufferType = c_int;
+
+pub const VA_STATUS_SUCCESS: VAStatus = 0;
+pub const VA_INVALID_ID: VAGenericID = 0xffff_ffff;
+pub const VA_INVALID_SURFACE: VASurfaceID = VA_INVALID_ID;
+
+// VAProfile
+pub const VAProfileNone: VAProfile = -1;
+pub const VAProfileH264Main: VAProfile = 6;
+pub const VAProfileH264High: VAProfile = 7;
+pub const VAProfileH264ConstrainedBaseline: VAProfile = 13;
+pub const VAProfileHEVCMain: VAProfile = 17;
+
+// VAEntrypoint
+pub const VAEntrypointVLD: VAEntrypoint = 1;
+pub const VAEntrypointEncSlice: VAEntrypoint = 6;
+pub const VAEntrypointEncSliceLP: VAEntrypoint = 8;
+pub const VAEntrypointVideoProc: VAEntrypoint = 10;
+
+// VAConfigAttribType
+pub const VAConfigAttribRTFormat: c_int = 0;
+pub const VAConfigAttribRateControl: c_int = 5;
+pub const VAConfigAttribEncPackedHeaders: c_int = 10;
+pub const VA_ATTRIB_NOT_SUPPORTED: c_uint = 0x8000_0000;
+
+pub const VA_RT_FORMAT_YUV420: c_uint = 0x0000_0001;
+pub const VA_RT_FORMAT_RGB32: c_uint = 0x0002_0000;
+
+pub const VA_RC_CBR: c_uint = 0x0000_0002;
+pub const VA_RC_VBR: c_uint = 0x0000_0004;
+pub const VA_RC_CQP: c_uint = 0x0000_0010;
+
+pub const VA_ENC_PACKED_HEADER_SEQUENCE: c_uint = 0x0000_0001;
+pub const VA_ENC_PACKED_HEADER_PICTURE: c_uint = 0x0000_0002;
+
+pub const VA_PROGRESSIVE: c_int = 0x1;
+
+// VABufferType
+pub const VAPictureParameterBufferType: VABufferType = 0;
+pub const VAIQMatrixBufferType: VABufferType = 1;
+pub const VASliceParameterBufferType: VABufferType = 4;
+pub const VASliceDataBufferType: VABufferType = 5;
+pub const VAEncCodedBufferType: VABufferType = 21;
+pub const VAEncSequenceParameterBufferType: VABufferType = 22;
+pub const VAEncPictureParameterBufferType: VABufferType = 23;
+pub const VAEncSliceParameterBufferType: VABufferType = 24;
+pub const VAEncPackedHeaderParameterBufferType: VABufferType = 25;
+pub const VAEncPackedHeaderDataBufferType: VABufferType = 26;
+pub const VAEncMiscParameterBufferType: VABufferType = 27;
+pub const VAProcPipelineParameterBufferType: VABufferType = 41;
+
+// VAEncPackedHeaderType
+pub const VAEncPackedHeaderSequence: c_uint = 1;
+pub const VAEncPackedHeaderPicture: c_uint = 2;
+
+// VAEncMiscParameterType
+pub const VAEncMiscParameterTypeFrameRate: c_uint = 0;
+pub const VAEncMiscParameterTypeRateControl: c_uint = 1;
+pub const VAEncMiscParameterTypeHRD: c_uint = 5;
+
+// VAPictureH264 flags
+pub const VA_PICTURE_H264_INVALID: u32 = 0x0000_0001;
+pub const VA_PICTURE_H264_SHORT_TERM_REFERENCE: u32 = 0x0000_0008;
+pub const VA_PICTURE_H264_LONG_TERM_REFERENCE: u32 = 0x0000_0010;
+
+pub const VA_SLICE_DATA_FLAG_ALL: u32 = 0x00;
+
+// Surface attributes
+pub const VASurfaceAttribPixelFormat: c_int = 1;
+pub const VASurfaceAttribMemoryType: c_int = 6;
+pub const VASurfaceAttribExternalBufferDescriptor: c_int = 7;
+pub const VAGenericValueTypeInteger: c_int = 1;
+pub const VAGenericValueTypePointer: c_int = 3;
+pub const VA_SURFACE_ATTRIB_SETTABLE: u32 = 0x0000_0002;
+pub const VA_SURFACE_ATTRIB_MEM_TYPE_DRM_PRIME_2: u32 = 0x4000_0000;
+
+pub const VA_EXPORT_SURFACE_READ_ONLY: u32 = 0x0001;
+pub const VA_EXPORT_SURFACE_COMPOSED_LAYERS: u32 = 0x0008;
+
+/// `VA_FOURCC(ch0, ch1, ch2, ch3)` — little-endian packed ASCII.
+pub const fn va_fourcc(code: &[u8; 4]) -> u32 {
+    (code[0] as u32) | ((code[1] as u32) << 8) | ((code[2] as u32) << 16) | ((code[3] as u32) << 24)
+}
+
+pub const VA_FOURCC_NV12: u32 = va_fourcc(b"NV12");
+pub const VA_FOURCC_RGBA: u32 = va_fourcc(b"RGBA");
+pub const VA_FOURCC_BGRA: u32 = va_fourcc(b"BGRA");
+pub const VA_LSB_FIRST: u32 = 1;
+
+/// `VAProcColorStandardBT709`.
+pub const VAProcColorStandardBT709: c_int = 2;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAConfigAttrib {
+    pub type_: c_int,
+    pub value: c_uint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union VAGenericValueUnion {
+    pub i: i32,
+    pub f: f32,
+    pub p: *mut c_void,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VAGenericValue {
+    pub type_: c_int,
+    pub value: VAGenericValueUnion,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VASurfaceAttrib {
+    pub type_: c_int,
+    pub flags: u32,
+    pub value: VAGenericValue,
+}
+
+impl VASurfaceAttrib {
+    pub fn integer(type_: c_int, value: i32) -> Self {
+        Self {
+            type_,
+            flags: VA_SURFACE_ATTRIB_SETTABLE,
+            value: VAGenericValue {
+                type_: VAGenericValueTypeInteger,
+                value: VAGenericValueUnion { i: value },
+            },
+        }
+    }
+
+    pub fn pointer(type_: c_int, value: *mut c_void) -> Self {
+        Self {
+            type_,
+            flags: VA_SURFACE_ATTRIB_SETTABLE,
+            value: VAGenericValue {
+                type_: VAGenericValueTypePointer,
+                value: VAGenericValueUnion { p: value },
+            },
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VADRMPRIMEObject {
+    pub fd: c_int,
+    pub size: u32,
+    pub drm_format_modifier: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VADRMPRIMELayer {
+    pub drm_format: u32,
+    pub num_planes: u32,
+    pub object_index: [u32; 4],
+    pub offset: [u32; 4],
+    pub pitch: [u32; 4],
+}
+
+/// `VADRMPRIMESurfaceDescriptor` — the `DRM_PRIME_2` import / export
+/// descriptor.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VADRMPRIMESurfaceDescriptor {
+    pub fourcc: u32,
+    pub width: u32,
+    pub height: u32,
+    pub num_objects: u32,
+    pub objects: [VADRMPRIMEObject; 4],
+    pub num_layers: u32,
+    pub layers: [VADRMPRIMELayer; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAImageFormat {
+    pub fourcc: u32,
+    pub byte_order: u32,
+    pub bits_per_pixel: u32,
+    pub depth: u32,
+    pub red_mask: u32,
+    pub green_mask: u32,
+    pub blue_mask: u32,
+    pub alpha_mask: u32,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAImage {
+    pub image_id: VAImageID,
+    pub format: VAImageFormat,
+    pub buf: VABufferID,
+    pub width: u16,
+    pub height: u16,
+    pub data_size: u32,
+    pub num_planes: u32,
+    pub pitches: [u32; 3],
+    pub offsets: [u32; 3],
+    pub num_palette_entries: i32,
+    pub entry_bytes: i32,
+    pub component_order: [i8; 4],
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VACodedBufferSegment {
+    pub size: u32,
+    pub bit_offset: u32,
+    pub status: u32,
+    pub reserved: u32,
+    pub buf: *mut c_void,
+    pub next: *mut c_void,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VARectangle {
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAProcColorProperties {
+    pub chroma_sample_location: u8,
+    pub color_range: u8,
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+    pub reserved: [u8; 3],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAProcPipelineParameterBuffer {
+    pub surface: VASurfaceID,
+    pub surface_region: *const VARectangle,
+    pub surface_color_standard: c_int,
+    pub output_region: *const VARectangle,
+    pub output_background_color: u32,
+    pub output_color_standard: c_int,
+    pub pipeline_flags: u32,
+    pub filter_flags: u32,
+    pub filters: *mut VABufferID,
+    pub num_filters: u32,
+    pub forward_references: *mut VASurfaceID,
+    pub num_forward_references: u32,
+    pub backward_references: *mut VASurfaceID,
+    pub num_backward_references: u32,
+    pub rotation_state: u32,
+    pub blend_state: *const c_void,
+    pub mirror_state: u32,
+    pub additional_outputs: *mut VASurfaceID,
+    pub num_additional_outputs: u32,
+    pub input_surface_flag: u32,
+    pub output_surface_flag: u32,
+    pub input_color_properties: VAProcColorProperties,
+    pub output_color_properties: VAProcColorProperties,
+    pub processing_mode: u32,
+    pub output_hdr_metadata: *const c_void,
+    pub va_reserved: [u32; 16],
+}
+
+impl Default for VAProcPipelineParameterBuffer {
+    fn default() -> Self {
+        // SAFETY: every member is an integer, a fixed-size integer array or
+        // a raw pointer; all-zero is the documented default.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// H.264 decode (va.h)
+// ---------------------------------------------------------------------------
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAPictureH264 {
+    pub picture_id: VASurfaceID,
+    pub frame_idx: u32,
+    pub flags: u32,
+    pub TopFieldOrderCnt: i32,
+    pub BottomFieldOrderCnt: i32,
+    pub va_reserved: [u32; 4],
+}
+
+impl VAPictureH264 {
+    /// The "no picture" entry for unused reference / list slots.
+    pub const INVALID: Self = Self {
+        picture_id: VA_INVALID_SURFACE,
+        frame_idx: 0,
+        flags: VA_PICTURE_H264_INVALID,
+        TopFieldOrderCnt: 0,
+        BottomFieldOrderCnt: 0,
+        va_reserved: [0; 4],
+    };
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAPictureParameterBufferH264 {
+    pub CurrPic: VAPictureH264,
+    pub ReferenceFrames: [VAPictureH264; 16],
+    pub picture_width_in_mbs_minus1: u16,
+    pub picture_height_in_mbs_minus1: u16,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub num_ref_frames: u8,
+    pub seq_fields: u32,
+    pub num_slice_groups_minus1: u8,
+    pub slice_group_map_type: u8,
+    pub slice_group_change_rate_minus1: u16,
+    pub pic_init_qp_minus26: i8,
+    pub pic_init_qs_minus26: i8,
+    pub chroma_qp_index_offset: i8,
+    pub second_chroma_qp_index_offset: i8,
+    pub pic_fields: u32,
+    pub frame_num: u16,
+    pub va_reserved: [u32; 8],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAIQMatrixBufferH264 {
+    pub ScalingList4x4: [[u8; 16]; 6],
+    pub ScalingList8x8: [[u8; 64]; 2],
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VASliceParameterBufferH264 {
+    pub slice_data_size: u32,
+    pub slice_data_offset: u32,
+    pub slice_data_flag: u32,
+    pub slice_data_bit_offset: u16,
+    pub first_mb_in_slice: u16,
+    pub slice_type: u8,
+    pub direct_spatial_mv_pred_flag: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub cabac_init_idc: u8,
+    pub slice_qp_delta: i8,
+    pub disable_deblocking_filter_idc: u8,
+    pub slice_alpha_c0_offset_div2: i8,
+    pub slice_beta_offset_div2: i8,
+    pub RefPicList0: [VAPictureH264; 32],
+    pub RefPicList1: [VAPictureH264; 32],
+    pub luma_log2_weight_denom: u8,
+    pub chroma_log2_weight_denom: u8,
+    pub luma_weight_l0_flag: u8,
+    pub luma_weight_l0: [i16; 32],
+    pub luma_offset_l0: [i16; 32],
+    pub chroma_weight_l0_flag: u8,
+    pub chroma_weight_l0: [[i16; 2]; 32],
+    pub chroma_offset_l0: [[i16; 2]; 32],
+    pub luma_weight_l1_flag: u8,
+    pub luma_weight_l1: [i16; 32],
+    pub luma_offset_l1: [i16; 32],
+    pub chroma_weight_l1_flag: u8,
+    pub chroma_weight_l1: [[i16; 2]; 32],
+    pub chroma_offset_l1: [[i16; 2]; 32],
+    pub va_reserved: [u32; 4],
+}
+
+// ---------------------------------------------------------------------------
+// H.264 encode (va_enc_h264.h)
+// ---------------------------------------------------------------------------
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAEncSequenceParameterBufferH264 {
+    pub seq_parameter_set_id: u8,
+    pub level_idc: u8,
+    pub intra_period: u32,
+    pub intra_idr_period: u32,
+    pub ip_period: u32,
+    pub bits_per_second: u32,
+    pub max_num_ref_frames: u32,
+    pub picture_width_in_mbs: u16,
+    pub picture_height_in_mbs: u16,
+    pub seq_fields: u32,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub num_ref_frames_in_pic_order_cnt_cycle: u8,
+    pub offset_for_non_ref_pic: i32,
+    pub offset_for_top_to_bottom_field: i32,
+    pub offset_for_ref_frame: [i32; 256],
+    pub frame_cropping_flag: u8,
+    pub frame_crop_left_offset: u32,
+    pub frame_crop_right_offset: u32,
+    pub frame_crop_top_offset: u32,
+    pub frame_crop_bottom_offset: u32,
+    pub vui_parameters_present_flag: u8,
+    pub vui_fields: u32,
+    pub aspect_ratio_idc: u8,
+    pub sar_width: u32,
+    pub sar_height: u32,
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAEncPictureParameterBufferH264 {
+    pub CurrPic: VAPictureH264,
+    pub ReferenceFrames: [VAPictureH264; 16],
+    pub coded_buf: VABufferID,
+    pub pic_parameter_set_id: u8,
+    pub seq_parameter_set_id: u8,
+    pub last_picture: u8,
+    pub frame_num: u16,
+    pub pic_init_qp: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub chroma_qp_index_offset: i8,
+    pub second_chroma_qp_index_offset: i8,
+    pub pic_fields: u32,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAEncSliceParameterBufferH264 {
+    pub macroblock_address: u32,
+    pub num_macroblocks: u32,
+    pub macroblock_info: VABufferID,
+    pub slice_type: u8,
+    pub pic_parameter_set_id: u8,
+    pub idr_pic_id: u16,
+    pub pic_order_cnt_lsb: u16,
+    pub delta_pic_order_cnt_bottom: i32,
+    pub delta_pic_order_cnt: [i32; 2],
+    pub direct_spatial_mv_pred_flag: u8,
+    pub num_ref_idx_active_override_flag: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub RefPicList0: [VAPictureH264; 32],
+    pub RefPicList1: [VAPictureH264; 32],
+    pub luma_log2_weight_denom: u8,
+    pub chroma_log2_weight_denom: u8,
+    pub luma_weight_l0_flag: u8,
+    pub luma_weight_l0: [i16; 32],
+    pub luma_offset_l0: [i16; 32],
+    pub chroma_weight_l0_flag: u8,
+    pub chroma_weight_l0: [[i16; 2]; 32],
+    pub chroma_offset_l0: [[i16; 2]; 32],
+    pub luma_weight_l1_flag: u8,
+    pub luma_weight_l1: [i16; 32],
+    pub luma_offset_l1: [i16; 32],
+    pub chroma_weight_l1_flag: u8,
+    pub chroma_weight_l1: [[i16; 2]; 32],
+    pub chroma_offset_l1: [[i16; 2]; 32],
+    pub cabac_init_idc: u8,
+    pub slice_qp_delta: i8,
+    pub disable_deblocking_filter_idc: u8,
+    pub slice_alpha_c0_offset_div2: i8,
+    pub slice_beta_offset_div2: i8,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAEncPackedHeaderParameterBuffer {
+    pub type_: u32,
+    pub bit_length: u32,
+    pub has_emulation_bytes: u8,
+    pub va_reserved: [u32; 4],
+}
+
+/// `VAEncMiscParameterBuffer` header; the typed payload follows it
+/// directly in the same VA buffer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAEncMiscParameterBuffer {
+    pub type_: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAEncMiscParameterRateControl {
+    pub bits_per_second: u32,
+    pub target_percentage: u32,
+    pub window_size: u32,
+    pub initial_qp: u32,
+    pub min_qp: u32,
+    pub basic_unit_size: u32,
+    pub rc_flags: u32,
+    pub ICQ_quality_factor: u32,
+    pub max_qp: u32,
+    pub quality_factor: u32,
+    pub target_frame_size: u32,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAEncMiscParameterFrameRate {
+    /// Numerator in the low 16 bits, denominator in the high 16 (0 = 1).
+    pub framerate: u32,
+    pub framerate_flags: u32,
+    pub va_reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VAEncMiscParameterHRD {
+    pub initial_buffer_fullness: u32,
+    pub buffer_size: u32,
+    pub va_reserved: [u32; 4],
+}
+
+/// Zero-initialise one of the plain-data VA parameter structs above.
+pub fn zeroed<T: Copy>() -> T {
+    // SAFETY: only instantiated with the `#[repr(C)]` parameter structs in
+    // this module — integers, integer arrays, raw pointers and nested
+    // structs of the same — for which all-zero is a valid value and the
+    // libva-documented default.
+    unsafe { std::mem::zeroed() }
+}
+
+// ---------------------------------------------------------------------------
+// Function table
+// ---------------------------------------------------------------------------
+
+type vaGetDisplayDRMFn = unsafe extern "C" fn(fd: c_int) -> VADisplay;
+type vaInitializeFn =
+    unsafe extern "C" fn(dpy: VADisplay, major: *mut c_int, minor: *mut c_int) -> VAStatus;
+type vaTerminateFn = unsafe extern "C" fn(dpy: VADisplay) -> VAStatus;
+type vaErrorStrFn = unsafe extern "C" fn(status: VAStatus) -> *const c_char;
+type vaQueryVendorStringFn = unsafe extern "C" fn(dpy: VADisplay) -> *const c_char;
+type vaMaxNumFn = unsafe extern "C" fn(dpy: VADisplay) -> c_int;
+type vaQueryConfigProfilesFn =
+    unsafe extern "C" fn(dpy: VADisplay, profiles: *mut VAProfile, num: *mut c_int) -> VAStatus;
+type vaQueryConfigEntrypointsFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    profile: VAProfile,
+    entrypoints: *mut VAEntrypoint,
+    num: *mut c_int,
+) -> VAStatus;
+type vaGetConfigAttributesFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    profile: VAProfile,
+    entrypoint: VAEntrypoint,
+    attribs: *mut VAConfigAttrib,
+    num: c_int,
+) -> VAStatus;
+type vaCreateConfigFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    profile: VAProfile,
+    entrypoint: VAEntrypoint,
+    attribs: *mut VAConfigAttrib,
+    num: c_int,
+    config: *mut VAConfigID,
+) -> VAStatus;
+type vaDestroyConfigFn = unsafe extern "C" fn(dpy: VADisplay, config: VAConfigID) -> VAStatus;
+type vaCreateSurfacesFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    format: c_uint,
+    width: c_uint,
+    height: c_uint,
+    surfaces: *mut VASurfaceID,
+    num_surfaces: c_uint,
+    attribs: *mut VASurfaceAttrib,
+    num_attribs: c_uint,
+) -> VAStatus;
+type vaDestroySurfacesFn =
+    unsafe extern "C" fn(dpy: VADisplay, surfaces: *mut VASurfaceID, num: c_int) -> VAStatus;
+type vaCreateContextFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    config: VAConfigID,
+    picture_width: c_int,
+    picture_height: c_int,
+    flag: c_int,
+    render_targets: *mut VASurfaceID,
+    num_render_targets: c_int,
+    context: *mut VAContextID,
+) -> VAStatus;
+type vaDestroyContextFn = unsafe extern "C" fn(dpy: VADisplay, context: VAContextID) -> VAStatus;
+type vaCreateBufferFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    context: VAContextID,
+    type_: VABufferType,
+    size: c_uint,
+    num_elements: c_uint,
+    data: *mut c_void,
+    buf: *mut VABufferID,
+) -> VAStatus;
+type vaDestroyBufferFn = unsafe extern "C" fn(dpy: VADisplay, buf: VABufferID) -> VAStatus;
+type vaMapBufferFn =
+    unsafe extern "C" fn(dpy: VADisplay, buf: VABufferID, pbuf: *mut *mut c_void) -> VAStatus;
+type vaUnmapBufferFn = unsafe extern "C" fn(dpy: VADisplay, buf: VABufferID) -> VAStatus;
+type vaBeginPictureFn =
+    unsafe extern "C" fn(dpy: VADisplay, context: VAContextID, target: VASurfaceID) -> VAStatus;
+type vaRenderPictureFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    context: VAContextID,
+    buffers: *mut VABufferID,
+    num_buffers: c_int,
+) -> VAStatus;
+type vaEndPictureFn = unsafe extern "C" fn(dpy: VADisplay, context: VAContextID) -> VAStatus;
+type vaSyncSurfaceFn = unsafe extern "C" fn(dpy: VADisplay, surface: VASurfaceID) -> VAStatus;
+type vaCreateImageFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    format: *mut VAImageFormat,
+    width: c_int,
+    height: c_int,
+    image: *mut VAImage,
+) -> VAStatus;
+type vaDestroyImageFn = unsafe extern "C" fn(dpy: VADisplay, image: VAImageID) -> VAStatus;
+type vaGetImageFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    surface: VASurfaceID,
+    x: c_int,
+    y: c_int,
+    width: c_uint,
+    height: c_uint,
+    image: VAImageID,
+) -> VAStatus;
+type vaPutImageFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    surface: VASurfaceID,
+    image: VAImageID,
+    src_x: c_int,
+    src_y: c_int,
+    src_width: c_uint,
+    src_height: c_uint,
+    dest_x: c_int,
+    dest_y: c_int,
+    dest_width: c_uint,
+    dest_height: c_uint,
+) -> VAStatus;
+type vaExportSurfaceHandleFn = unsafe extern "C" fn(
+    dpy: VADisplay,
+    surface: VASurfaceID,
+    mem_type: u32,
+    flags: u32,
+    descriptor: *mut c_void,
+) -> VAStatus;
+
+/// Loaded `libva.so.2` + `libva-drm.so.2` and every symbol the backend
+/// calls, resolved once per process.
+pub struct VaApi {
+    _va: Library,
+    _va_drm: Library,
+    pub vaGetDisplayDRM: vaGetDisplayDRMFn,
+    pub vaInitialize: vaInitializeFn,
+    pub vaTerminate: vaTerminateFn,
+    pub vaErrorStr: vaErrorStrFn,
+    pub vaQueryVendorString: vaQueryVendorStringFn,
+    pub vaMaxNumProfiles: vaMaxNumFn,
+    pub vaMaxNumEntrypoints: vaMaxNumFn,
+    pub vaQueryConfigProfiles: vaQueryConfigProfilesFn,
+    pub vaQueryConfigEntrypoints: vaQueryConfigEntrypointsFn,
+    pub vaGetConfigAttributes: vaGetConfigAttributesFn,
+    pub vaCreateConfig: vaCreateConfigFn,
+    pub vaDestroyConfig: vaDestroyConfigFn,
+    pub vaCreateSurfaces: vaCreateSurfacesFn,
+    pub vaDestroySurfaces: vaDestroySurfacesFn,
+    pub vaCreateContext: vaCreateContextFn,
+    pub vaDestroyContext: vaDestroyContextFn,
+    pub vaCreateBuffer: vaCreateBufferFn,
+    pub vaDestroyBuffer: vaDestroyBufferFn,
+    pub vaMapBuffer: vaMapBufferFn,
+    pub vaUnmapBuffer: vaUnmapBufferFn,
+    pub vaBeginPicture: vaBeginPictureFn,
+    pub vaRenderPicture: vaRenderPictureFn,
+    pub vaEndPicture: vaEndPictureFn,
+    pub vaSyncSurface: vaSyncSurfaceFn,
+    pub vaCreateImage: vaCreateImageFn,
+    pub vaDestroyImage: vaDestroyImageFn,
+    pub vaGetImage: vaGetImageFn,
+    pub vaPutImage: vaPutImageFn,
+    pub vaExportSurfaceHandle: vaExportSurfaceHandleFn,
+}
+
+// SAFETY: the table holds `Library` handles and plain `extern "C"` function
+// pointers; libva's entry points are thread-safe per display.
+unsafe impl Send for VaApi {}
+unsafe impl Sync for VaApi {}
+
+static VA_API: OnceLock<Result<Arc<VaApi>, String>> = OnceLock::new();
+
+impl VaApi {
+    /// The process-wide function table, loading libva on first use.
+    pub fn get() -> Result<Arc<Self>, VaapiError> {
+        VA_API
+            .get_or_init(|| Self::load().map(Arc::new).map_err(|e| e.to_string()))
+            .clone()
+            .map_err(VaapiError::LibraryNotFound)
+    }
+
+    fn load() -> Result<Self, VaapiError> {
+        // SAFETY: loading a library by SONAME runs its initialisers; libva
+        // has no unsound constructors. Failure is an `Err`, not UB.
+        let va = unsafe { Library::new("libva.so.2") }
+            .map_err(|e| VaapiError::LibraryNotFound(format!("libva.so.2: {e}")))?;
+        let va_drm = unsafe { Library::new("libva-drm.so.2") }
+            .map_err(|e| VaapiError::LibraryNotFound(format!("libva-drm.so.2: {e}")))?;
+
+        unsafe fn sym<T: Copy>(lib: &Library, name: &'static [u8]) -> Result<T, VaapiError> {
+            // SAFETY: caller pairs `name` with its `va.h` signature `T`.
+            let symbol: libloading::Symbol<T> = unsafe { lib.get(name) }.map_err(|_| {
+                VaapiError::SymbolMissing(
+                    std::str::from_utf8(&name[..name.len().saturating_sub(1)]).unwrap_or("?"),
+                )
+            })?;
+            Ok(*symbol)
+        }
+
+        // SAFETY: each symbol is resolved with the signature from `va.h` /
+        // `va_drm.h`; the libraries outlive the pointers (held in `Self`).
+        unsafe {
+            Ok(Self {
+                vaGetDisplayDRM: sym(&va_drm, b"vaGetDisplayDRM\0")?,
+                vaInitialize: sym(&va, b"vaInitialize\0")?,
+                vaTerminate: sym(&va, b"vaTerminate\0")?,
+                vaErrorStr: sym(&va, b"vaErrorStr\0")?,
+                vaQueryVendorString: sym(&va, b"vaQueryVendorString\0")?,
+                vaMaxNumProfiles: sym(&va, b"vaMaxNumProfiles\0")?,
+                vaMaxNumEntrypoints: sym(&va, b"vaMaxNumEntrypoints\0")?,
+                vaQueryConfigProfiles: sym(&va, b"vaQueryConfigProfiles\0")?,
+                vaQueryConfigEntrypoints: sym(&va, b"vaQueryConfigEntrypoints\0")?,
+                vaGetConfigAttributes: sym(&va, b"vaGetConfigAttributes\0")?,
+                vaCreateConfig: sym(&va, b"vaCreateConfig\0")?,
+                vaDestroyConfig: sym(&va, b"vaDestroyConfig\0")?,
+                vaCreateSurfaces: sym(&va, b"vaCreateSurfaces\0")?,
+                vaDestroySurfaces: sym(&va, b"vaDestroySurfaces\0")?,
+                vaCreateContext: sym(&va, b"vaCreateContext\0")?,
+                vaDestroyContext: sym(&va, b"vaDestroyContext\0")?,
+                vaCreateBuffer: sym(&va, b"vaCreateBuffer\0")?,
+                vaDestroyBuffer: sym(&va, b"vaDestroyBuffer\0")?,
+                vaMapBuffer: sym(&va, b"vaMapBuffer\0")?,
+                vaUnmapBuffer: sym(&va, b"vaUnmapBuffer\0")?,
+                vaBeginPicture: sym(&va, b"vaBeginPicture\0")?,
+                vaRenderPicture: sym(&va, b"vaRenderPicture\0")?,
+                vaEndPicture: sym(&va, b"vaEndPicture\0")?,
+                vaSyncSurface: sym(&va, b"vaSyncSurface\0")?,
+                vaCreateImage: sym(&va, b"vaCreateImage\0")?,
+                vaDestroyImage: sym(&va, b"vaDestroyImage\0")?,
+                vaGetImage: sym(&va, b"vaGetImage\0")?,
+                vaPutImage: sym(&va, b"vaPutImage\0")?,
+                vaExportSurfaceHandle: sym(&va, b"vaExportSurfaceHandle\0")?,
+                _va: va,
+                _va_drm: va_drm,
+            })
+        }
+    }
+
+    /// Map a non-success `VAStatus` from `call` to a typed error.
+    pub fn check(&self, call: &'static str, status: VAStatus) -> Result<(), VaapiError> {
+        if status == VA_STATUS_SUCCESS {
+            return Ok(());
+        }
+        // SAFETY: `vaErrorStr` returns a static NUL-terminated string for
+        // any status value.
+        let message = unsafe {
+            let ptr = (self.vaErrorStr)(status);
+            if ptr.is_null() {
+                String::from("unknown error")
+            } else {
+                CStr::from_ptr(ptr).to_string_lossy().into_owned()
+            }
+        };
+        Err(VaapiError::Call {
+            call,
+            status,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Layouts the drivers read by `sizeof` — a drifted member shifts every
+    /// field after it, which the driver would read as garbage rather than
+    /// reject.
+    #[test]
+    fn parameter_buffer_sizes_match_libva_headers() {
+        assert_eq!(std::mem::size_of::<VAPictureH264>(), 36);
+        assert_eq!(std::mem::size_of::<VAPictureParameterBufferH264>(), 672);
+        assert_eq!(std::mem::size_of::<VAIQMatrixBufferH264>(), 240);
+        assert_eq!(
+            std::mem::size_of::<VAEncSequenceParameterBufferH264>(),
+            1132
+        );
+        assert_eq!(std::mem::size_of::<VAEncPictureParameterBufferH264>(), 648);
+        assert_eq!(std::mem::size_of::<VAImageFormat>(), 48);
+        assert_eq!(std::mem::size_of::<VASurfaceAttrib>(), 24);
+        assert_eq!(std::mem::size_of::<VADRMPRIMESurfaceDescriptor>(), 312);
+        assert_eq!(std::mem::size_of::<VAProcPipelineParameterBuffer>(), 224);
+    }
+
+    #[test]
+    fn fourcc_packs_little_endian() {
+        assert_eq!(VA_FOURCC_NV12, 0x3231_564E);
+        assert_eq!(VA_FOURCC_RGBA, 0x4142_4752);
+    }
+}
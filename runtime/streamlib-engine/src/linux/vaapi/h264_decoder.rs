@@ -0,0 +1,899 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! [`VaapiH264Decoder`] — H.264 decode through `VAEntrypointVLD`, with the
+//! same Annex B in / [`SimpleDecodedFrame`] out contract as the Vulkan Video
+//! [`crate::vulkan::video::SimpleDecoder`].
+//!
+//! Bitstream parsing, POC, picture numbering, reference-list
+//! initialisation and reference marking all come from the same ported
+//! parser the Vulkan decoder drives; this module only translates its state
+//! into VA parameter buffers. Parser DPB entries map onto a fixed pool of
+//! driver surfaces. Progressive (frame) pictures only.
+
+use std::sync::Arc;
+
+use crate::vulkan::video::decode::{SimpleDecodedFrame, SimpleDecoder, SimpleDecoderConfig};
+use crate::vulkan::video::encode::Codec;
+use crate::vulkan::video::nv_video_parser::nv_vulkan_h264_scaling_list::{
+    ScalingListH264, ScalingListType, set_seq_pic_scaling_lists_h264,
+};
+use crate::vulkan::video::nv_video_parser::vulkan_h26x_decoder::SliceType;
+use crate::vulkan::video::nv_video_parser::vulkan_h264_decoder::{
+    self as h264dec, BitstreamReader, H264LevelIdc, MARKING_LONG, MARKING_SHORT, MAX_DPB_SIZE,
+    MAX_REFS, NvScalingListH264, PicParameterSet, RefPicListReordering, SeqParameterSet,
+    SliceHeader, VulkanH264Decoder, reference_picture_list_initialization_b_frame,
+    reference_picture_list_initialization_p_frame,
+};
+use crate::vulkan::video::{H273ColorVui, VideoError};
+
+use super::VaapiError;
+use super::display::VaDisplay;
+use super::ffi;
+use super::surface::{VaBuffer, VaConfig, VaContext, VaImage, VaSurfaces, VaVideoProcessor};
+
+/// Every parser DPB entry (including the overflow slot) plus one spare, so
+/// the current picture never has to share a surface with a reference.
+const SURFACE_COUNT: usize = MAX_DPB_SIZE + 2;
+
+/// Per-stream session state, rebuilt when the SPS changes the coded size
+/// or profile. Field order is drop order: context before surfaces.
+struct Session {
+    context: VaContext,
+    rgba_image: Option<VaImage>,
+    rgba_surface: Option<VaSurfaces>,
+    nv12_image: VaImage,
+    surfaces: VaSurfaces,
+    profile: ffi::VAProfile,
+    coded_width: u32,
+    coded_height: u32,
+}
+
+/// A picture whose slices are still being collected.
+struct PendingPicture {
+    surface: usize,
+    dpb_idx: usize,
+    poc: i32,
+    first_slice: SliceHeader,
+    sps: SeqParameterSet,
+    /// Picture + IQ matrix buffers, then one (param, data) pair per slice.
+    buffers: Vec<VaBuffer>,
+}
+
+/// H.264 decoder on a VAAPI VLD entrypoint.
+pub struct VaapiH264Decoder {
+    // Field order is drop order: buffers and the session before the
+    // display they were created on.
+    picture: Option<PendingPicture>,
+    vpp: Option<VaVideoProcessor>,
+    session: Option<Session>,
+    display: Arc<VaDisplay>,
+
+    config: SimpleDecoderConfig,
+    parser: VulkanH264Decoder,
+    width: u32,
+    height: u32,
+    /// Surface index per parser DPB entry.
+    dpb_to_surface: [Option<usize>; MAX_DPB_SIZE + 1],
+    surface_in_use: [bool; SURFACE_COUNT],
+    waiting_for_idr: bool,
+    frame_counter: u64,
+}
+
+impl VaapiH264Decoder {
+    /// Open a VAAPI display for decoding. The VA context itself is created
+    /// on the first SPS.
+    ///
+    /// # Errors
+    ///
+    /// H.265 was requested, libva / a render node is unavailable, or the
+    /// driver exposes no H.264 VLD entrypoint.
+    pub fn new(config: SimpleDecoderConfig) -> Result<Self, VaapiError> {
+        if config.codec != Codec::H264 {
+            return Err(VaapiError::Unsupported(
+                "the VAAPI decoder implements H.264 only".into(),
+            ));
+        }
+        let display = VaDisplay::open()?;
+        if !display.capabilities().h264_decode {
+            return Err(VaapiError::Unsupported(format!(
+                "{} exposes no H.264 decode entrypoint",
+                display.device_path().display()
+            )));
+        }
+        tracing::info!(
+            device = %display.device_path().display(),
+            rgba_output = config.rgba_output,
+            "VAAPI H.264 decoder created"
+        );
+        Ok(Self {
+            picture: None,
+            vpp: None,
+            session: None,
+            display,
+            config,
+            parser: VulkanH264Decoder::new(),
+            width: 0,
+            height: 0,
+            dpb_to_surface: [None; MAX_DPB_SIZE + 1],
+            surface_in_use: [false; SURFACE_COUNT],
+            waiting_for_idr: true,
+            frame_counter: 0,
+        })
+    }
+
+    /// Feed Annex B data containing whole NAL units. Returns every picture
+    /// completed by this call, in decode order.
+    pub fn feed(&mut self, data: &[u8]) -> Result<Vec<SimpleDecodedFrame>, VideoError> {
+        let mut frames = Vec::new();
+        for nal in SimpleDecoder::split_nal_units_owned(data) {
+            if nal.is_empty() {
+                continue;
+            }
+            match nal[0] & 0x1F {
+                7 => self.handle_sps(&nal)?,
+                8 => self.handle_pps(&nal)?,
+                1 | 5 => {
+                    if let Some(frame) = self.handle_slice(&nal)? {
+                        frames.push(frame);
+                    }
+                }
+                nal_type => {
+                    tracing::trace!(
+                        "VAAPI NAL: type {} ({} bytes) -- skipped",
+                        nal_type,
+                        nal.len()
+                    )
+                }
+            }
+        }
+        // `feed` carries whole access units, so the last picture is complete.
+        if let Some(frame) = self.finish_picture()? {
+            frames.push(frame);
+        }
+        Ok(frames)
+    }
+
+    /// Signal a discontinuity (e.g. seek): drop references and wait for
+    /// the next IDR.
+    pub fn feed_discontinuity(&mut self) {
+        self.picture = None;
+        self.parser.flush_decoded_picture_buffer();
+        self.release_all_surfaces();
+        self.waiting_for_idr = true;
+        tracing::info!("VAAPI decoder discontinuity: waiting for next IDR");
+    }
+
+    /// Full reset: the session is rebuilt from the next SPS.
+    pub fn reset(&mut self) {
+        self.feed_discontinuity();
+        self.session = None;
+        self.parser = VulkanH264Decoder::new();
+        self.width = 0;
+        self.height = 0;
+        self.frame_counter = 0;
+    }
+
+    /// Number of pictures decoded so far.
+    pub fn decode_count(&self) -> u64 {
+        self.frame_counter
+    }
+
+    /// Stream dimensions from the active SPS (cropped).
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// H.273 colour VUI of the active SPS, with the same per-axis semantics
+    /// as [`SimpleDecoder::current_color_vui`].
+    pub fn current_color_vui(&self) -> Option<H273ColorVui> {
+        crate::vulkan::video::decode::h264_sps_color_vui(self.parser.sps.as_ref()?)
+    }
+
+    // ------------------------------------------------------------------
+    // Parameter sets
+    // ------------------------------------------------------------------
+
+    fn handle_sps(&mut self, nal: &[u8]) -> Result<(), VideoError> {
+        let rbsp = SimpleDecoder::remove_emulation_prevention_bytes(&nal[1..]);
+        let sps_id = self
+            .parser
+            .parse_sps(&mut BitstreamReader::new(&rbsp))
+            .ok_or_else(|| VideoError::BitstreamError("Failed to parse H.264 SPS".into()))?;
+        let sps = self.parser.spss[sps_id as usize]
+            .clone()
+            .ok_or_else(|| VideoError::BitstreamError(format!("SPS {sps_id} missing")))?;
+        self.configure(&sps)?;
+        self.parser.sps = Some(sps);
+        Ok(())
+    }
+
+    fn handle_pps(&mut self, nal: &[u8]) -> Result<(), VideoError> {
+        let rbsp = SimpleDecoder::remove_emulation_prevention_bytes(&nal[1..]);
+        if !self.parser.parse_pps(&mut BitstreamReader::new(&rbsp)) {
+            tracing::warn!("Failed to parse H.264 PPS");
+        }
+        Ok(())
+    }
+
+    /// (Re)create the session when the SPS changes the coded size or
+    /// profile; otherwise keep it (parameter sets repeat on every IDR).
+    fn configure(&mut self, sps: &SeqParameterSet) -> Result<(), VaapiError> {
+        let frame_mbs_only = sps.flags.frame_mbs_only_flag;
+        let coded_width = (sps.pic_width_in_mbs_minus1 + 1) as u32 * 16;
+        let coded_height = (if frame_mbs_only { 1 } else { 2 })
+            * (sps.pic_height_in_map_units_minus1 + 1) as u32
+            * 16;
+        let (mut width, mut height) = (coded_width, coded_height);
+        if sps.flags.frame_cropping_flag {
+            let crop_unit_y: u32 = 2 * if frame_mbs_only { 1 } else { 2 };
+            width -= 2 * (sps.frame_crop_left_offset + sps.frame_crop_right_offset) as u32;
+            height -=
+                crop_unit_y * (sps.frame_crop_top_offset + sps.frame_crop_bottom_offset) as u32;
+        }
+        let profile = match sps.profile_idc {
+            66 => ffi::VAProfileH264ConstrainedBaseline,
+            77 => ffi::VAProfileH264Main,
+            _ => ffi::VAProfileH264High,
+        };
+
+        if let Some(session) = &self.session
+            && session.profile == profile
+            && session.coded_width == coded_width
+            && session.coded_height == coded_height
+            && (self.width, self.height) == (width, height)
+        {
+            return Ok(());
+        }
+
+        // Outstanding work targets the old surfaces.
+        self.picture = None;
+        self.session = None;
+        self.parser.flush_decoded_picture_buffer();
+        self.release_all_surfaces();
+        self.waiting_for_idr = true;
+
+        if !self
+            .display
+            .entrypoints(profile)
+            .contains(&ffi::VAEntrypointVLD)
+        {
+            return Err(VaapiError::Unsupported(format!(
+                "driver cannot decode H.264 profile_idc {}",
+                sps.profile_idc
+            )));
+        }
+        let mut attribs = [ffi::VAConfigAttrib {
+            type_: ffi::VAConfigAttribRTFormat,
+            value: ffi::VA_RT_FORMAT_YUV420,
+        }];
+        let va_config = VaConfig::new(&self.display, profile, ffi::VAEntrypointVLD, &mut attribs)?;
+        let surfaces = VaSurfaces::new(
+            &self.display,
+            ffi::VA_FOURCC_NV12,
+            coded_width,
+            coded_height,
+            SURFACE_COUNT,
+        )?;
+        let context = VaContext::new(va_config, coded_width, coded_height, &surfaces.ids)?;
+        let (rgba_surface, rgba_image) = if self.config.rgba_output {
+            (
+                Some(VaSurfaces::new(
+                    &self.display,
+                    ffi::VA_FOURCC_RGBA,
+                    width,
+                    height,
+                    1,
+                )?),
+                Some(VaImage::new(
+                    &self.display,
+                    ffi::VA_FOURCC_RGBA,
+                    width,
+                    height,
+                )?),
+            )
+        } else {
+            (None, None)
+        };
+        let nv12_image = VaImage::new(&self.display, ffi::VA_FOURCC_NV12, width, height)?;
+
+        tracing::info!(
+            width,
+            height,
+            coded_width,
+            coded_height,
+            profile_idc = sps.profile_idc,
+            "VAAPI H.264 decode session configured"
+        );
+        self.session = Some(Session {
+            context,
+            rgba_image,
+            rgba_surface,
+            nv12_image,
+            surfaces,
+            profile,
+            coded_width,
+            coded_height,
+        });
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Slices
+    // ------------------------------------------------------------------
+
+    fn handle_slice(&mut self, nal: &[u8]) -> Result<Option<SimpleDecodedFrame>, VideoError> {
+        let nal_ref_idc = (nal[0] >> 5) & 0x3;
+        let nal_unit_type = nal[0] & 0x1F;
+        let is_idr = nal_unit_type == 5;
+        if self.session.is_none() {
+            tracing::warn!("Slice NAL received before SPS — skipping");
+            return Ok(None);
+        }
+        if self.waiting_for_idr && !is_idr {
+            return Ok(None);
+        }
+
+        let rbsp = SimpleDecoder::remove_emulation_prevention_bytes(&nal[1..]);
+        let slh = self
+            .parser
+            .parse_slice_header(&mut BitstreamReader::new(&rbsp), nal_ref_idc, nal_unit_type)
+            .ok_or_else(|| {
+                VideoError::BitstreamError("Failed to parse H.264 slice header".into())
+            })?;
+        if slh.field_pic_flag {
+            return Err(VaapiError::Unsupported("interlaced (field) H.264 pictures".into()).into());
+        }
+        let pps = self
+            .parser
+            .ppss
+            .get(slh.pic_parameter_set_id as usize)
+            .and_then(|p| p.clone())
+            .ok_or_else(|| {
+                VideoError::BitstreamError(format!(
+                    "H.264 PPS {} not found",
+                    slh.pic_parameter_set_id
+                ))
+            })?;
+        let sps = self
+            .parser
+            .spss
+            .get(pps.seq_parameter_set_id as usize)
+            .and_then(|s| s.clone())
+            .ok_or_else(|| {
+                VideoError::BitstreamError(format!(
+                    "H.264 SPS {} not found",
+                    pps.seq_parameter_set_id
+                ))
+            })?;
+
+        let mut completed = None;
+        if slh.first_mb_in_slice == 0 || self.picture.is_none() {
+            completed = self.finish_picture()?;
+            self.begin_picture(&sps, &pps, &slh, is_idr)?;
+        }
+        self.add_slice(&sps, &pps, &slh, nal)?;
+        Ok(completed)
+    }
+
+    /// Allocate a DPB entry + surface for a new picture and build its
+    /// picture-level buffers.
+    fn begin_picture(
+        &mut self,
+        sps: &SeqParameterSet,
+        pps: &PicParameterSet,
+        slh: &SliceHeader,
+        is_idr: bool,
+    ) -> Result<(), VaapiError> {
+        if is_idr {
+            self.parser.flush_decoded_picture_buffer();
+            self.release_all_surfaces();
+            self.waiting_for_idr = false;
+        }
+        self.parser.sps = Some(sps.clone());
+        self.parser.pps = Some(pps.clone());
+
+        let dpb_idx = (0..MAX_DPB_SIZE)
+            .find(|&i| self.parser.dpb[i].state == 0)
+            .unwrap_or(MAX_DPB_SIZE);
+        self.parser.i_cur = dpb_idx;
+        self.release_surface_of(dpb_idx);
+        self.parser.dpb[dpb_idx] = h264dec::DpbEntry::default();
+        self.parser.dpb[dpb_idx].state = 3;
+        self.parser.dpb[dpb_idx].frame_num = slh.frame_num;
+        self.parser.picture_order_count(sps, slh);
+        let max_frame_num = 1 << (sps.log2_max_frame_num_minus4 + 4);
+        self.parser.picture_numbers(slh, max_frame_num);
+
+        let surface = (0..SURFACE_COUNT)
+            .find(|&s| !self.surface_in_use[s])
+            .ok_or_else(|| VaapiError::Bitstream("no free VAAPI decode surface".into()))?;
+        self.surface_in_use[surface] = true;
+        self.dpb_to_surface[dpb_idx] = Some(surface);
+
+        let session = self.session.as_ref().expect("configured before slices");
+        let current = &self.parser.dpb[dpb_idx];
+        let poc = current
+            .top_field_order_cnt
+            .min(current.bottom_field_order_cnt);
+
+        let mut picture: ffi::VAPictureParameterBufferH264 = ffi::zeroed();
+        picture.CurrPic = ffi::VAPictureH264 {
+            picture_id: session.surfaces.ids[surface],
+            frame_idx: slh.frame_num as u32,
+            flags: 0,
+            TopFieldOrderCnt: current.top_field_order_cnt,
+            BottomFieldOrderCnt: current.bottom_field_order_cnt,
+            va_reserved: [0; 4],
+        };
+        picture.ReferenceFrames = [ffi::VAPictureH264::INVALID; 16];
+        let references = (0..MAX_DPB_SIZE)
+            .filter(|&i| i != dpb_idx)
+            .filter_map(|i| self.va_picture(i))
+            .take(16);
+        for (slot, reference) in picture.ReferenceFrames.iter_mut().zip(references) {
+            *slot = reference;
+        }
+        picture.picture_width_in_mbs_minus1 = sps.pic_width_in_mbs_minus1 as u16;
+        let mbs_per_map_unit = if sps.flags.frame_mbs_only_flag { 1 } else { 2 };
+        picture.picture_height_in_mbs_minus1 =
+            ((sps.pic_height_in_map_units_minus1 + 1) * mbs_per_map_unit - 1) as u16;
+        picture.bit_depth_luma_minus8 = sps.bit_depth_luma_minus8 as u8;
+        picture.bit_depth_chroma_minus8 = sps.bit_depth_chroma_minus8 as u8;
+        picture.num_ref_frames = sps.max_num_ref_frames as u8;
+        picture.seq_fields = sps.chroma_format_idc as u32 & 0x3
+            | u32::from(sps.flags.gaps_in_frame_num_value_allowed_flag) << 3
+            | u32::from(sps.flags.frame_mbs_only_flag) << 4
+            | u32::from(sps.flags.mb_adaptive_frame_field_flag) << 5
+            | u32::from(sps.flags.direct_8x8_inference_flag) << 6
+            | u32::from(sps.level_idc as u32 >= H264LevelIdc::Level3_1 as u32) << 7 // MinLumaBiPredSize8x8
+            | (sps.log2_max_frame_num_minus4 as u32 & 0xF) << 8
+            | (sps.pic_order_cnt_type as u32 & 0x3) << 12
+            | (sps.log2_max_pic_order_cnt_lsb_minus4 as u32 & 0xF) << 14
+            | u32::from(sps.flags.delta_pic_order_always_zero_flag) << 18;
+        picture.num_slice_groups_minus1 = pps.num_slice_groups_minus1;
+        picture.pic_init_qp_minus26 = pps.pic_init_qp_minus26;
+        picture.pic_init_qs_minus26 = pps.pic_init_qs_minus26;
+        picture.chroma_qp_index_offset = pps.chroma_qp_index_offset;
+        picture.second_chroma_qp_index_offset = pps.second_chroma_qp_index_offset;
+        picture.pic_fields = u32::from(pps.flags.entropy_coding_mode_flag)
+            | u32::from(pps.flags.weighted_pred_flag) << 1
+            | (u32::from(pps.weighted_bipred_idc) & 0x3) << 2
+            | u32::from(pps.flags.transform_8x8_mode_flag) << 4
+            | u32::from(pps.flags.constrained_intra_pred_flag) << 6
+            | u32::from(pps.flags.bottom_field_pic_order_in_frame_present_flag) << 7
+            | u32::from(pps.flags.deblocking_filter_control_present_flag) << 8
+            | u32::from(pps.flags.redundant_pic_cnt_present_flag) << 9
+            | u32::from(slh.nal_ref_idc != 0) << 10;
+        picture.frame_num = slh.frame_num as u16;
+
+        let ctx = session.context.id;
+        let buffers = vec![
+            VaBuffer::new(
+                &self.display,
+                ctx,
+                ffi::VAPictureParameterBufferType,
+                &picture,
+            )?,
+            VaBuffer::new(
+                &self.display,
+                ctx,
+                ffi::VAIQMatrixBufferType,
+                &iq_matrix(sps, pps),
+            )?,
+        ];
+        self.picture = Some(PendingPicture {
+            surface,
+            dpb_idx,
+            poc,
+            first_slice: slh.clone(),
+            sps: sps.clone(),
+            buffers,
+        });
+        Ok(())
+    }
+
+    /// Build reference lists and the slice parameter / data buffers for
+    /// one slice of the pending picture.
+    fn add_slice(
+        &mut self,
+        sps: &SeqParameterSet,
+        pps: &PicParameterSet,
+        slh: &SliceHeader,
+        nal: &[u8],
+    ) -> Result<(), VaapiError> {
+        let picture = self.picture.as_ref().expect("begun above");
+        let dpb = &self.parser.dpb;
+        let max_pic_num = 1 << (sps.log2_max_frame_num_minus4 + 4);
+        let candidates: Vec<RefCandidate> = (0..MAX_DPB_SIZE)
+            .filter(|&i| i != picture.dpb_idx)
+            .filter_map(|i| {
+                let e = &dpb[i];
+                match e.top_field_marking {
+                    MARKING_SHORT => Some(RefCandidate {
+                        dpb_idx: i,
+                        pic_num: e.top_pic_num,
+                        long_term: false,
+                    }),
+                    MARKING_LONG => Some(RefCandidate {
+                        dpb_idx: i,
+                        pic_num: e.top_long_term_pic_num,
+                        long_term: true,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let mut l0 = [0i8; MAX_REFS];
+        let mut l1 = [0i8; MAX_REFS];
+        let (n0, n1) = match slh.slice_type {
+            SliceType::P | SliceType::Sp => (
+                reference_picture_list_initialization_p_frame(dpb, &mut l0),
+                0,
+            ),
+            SliceType::B => {
+                reference_picture_list_initialization_b_frame(dpb, picture.poc, &mut l0, &mut l1)
+            }
+            _ => (0, 0),
+        };
+        let is_inter = !matches!(slh.slice_type, SliceType::I | SliceType::Si);
+        let is_b = slh.slice_type == SliceType::B;
+        let active_l0 = if is_inter {
+            (slh.num_ref_idx_l0_active_minus1 + 1) as usize
+        } else {
+            0
+        };
+        let active_l1 = if is_b {
+            (slh.num_ref_idx_l1_active_minus1 + 1) as usize
+        } else {
+            0
+        };
+        let mut list0: Vec<usize> = l0[..n0].iter().map(|&i| i as usize).collect();
+        let mut list1: Vec<usize> = l1[..n1].iter().map(|&i| i as usize).collect();
+        if slh.ref_pic_list_reordering_flag_l0 {
+            modify_ref_pic_list(
+                &mut list0,
+                &slh.ref_pic_list_reordering_l0,
+                slh.frame_num,
+                max_pic_num,
+                &candidates,
+            );
+        }
+        if slh.ref_pic_list_reordering_flag_l1 {
+            modify_ref_pic_list(
+                &mut list1,
+                &slh.ref_pic_list_reordering_l1,
+                slh.frame_num,
+                max_pic_num,
+                &candidates,
+            );
+        }
+        list0.truncate(active_l0);
+        list1.truncate(active_l1);
+
+        let mut slice: ffi::VASliceParameterBufferH264 = ffi::zeroed();
+        slice.slice_data_size = nal.len() as u32;
+        slice.slice_data_offset = 0;
+        slice.slice_data_flag = ffi::VA_SLICE_DATA_FLAG_ALL;
+        // From the NAL header byte, in emulation-prevention-free bits.
+        slice.slice_data_bit_offset = (8 + slh.header_bits) as u16;
+        slice.first_mb_in_slice = slh.first_mb_in_slice as u16;
+        slice.slice_type = (slh.slice_type_raw % 5) as u8;
+        slice.direct_spatial_mv_pred_flag = u8::from(slh.direct_spatial_mv_pred_flag);
+        slice.num_ref_idx_l0_active_minus1 = active_l0.saturating_sub(1) as u8;
+        slice.num_ref_idx_l1_active_minus1 = active_l1.saturating_sub(1) as u8;
+        slice.cabac_init_idc = slh.cabac_init_idc as u8;
+        slice.slice_qp_delta = slh.slice_qp_delta as i8;
+        slice.disable_deblocking_filter_idc = slh.disable_deblocking_filter_idc as u8;
+        slice.slice_alpha_c0_offset_div2 = slh.slice_alpha_c0_offset_div2 as i8;
+        slice.slice_beta_offset_div2 = slh.slice_beta_offset_div2 as i8;
+        slice.RefPicList0 = [ffi::VAPictureH264::INVALID; 32];
+        slice.RefPicList1 = [ffi::VAPictureH264::INVALID; 32];
+        for (slot, &idx) in slice.RefPicList0.iter_mut().zip(&list0) {
+            *slot = self.va_picture(idx).unwrap_or(ffi::VAPictureH264::INVALID);
+        }
+        for (slot, &idx) in slice.RefPicList1.iter_mut().zip(&list1) {
+            *slot = self.va_picture(idx).unwrap_or(ffi::VAPictureH264::INVALID);
+        }
+
+        let explicit_weights = (pps.flags.weighted_pred_flag
+            && matches!(slh.slice_type, SliceType::P | SliceType::Sp))
+            || (pps.weighted_bipred_idc == 1 && is_b);
+        if explicit_weights {
+            let chroma = u8::from(sps.chroma_format_idc != 0);
+            slice.luma_log2_weight_denom = slh.luma_log2_weight_denom as u8;
+            slice.chroma_log2_weight_denom = slh.chroma_log2_weight_denom as u8;
+            slice.luma_weight_l0_flag = 1;
+            slice.chroma_weight_l0_flag = chroma;
+            slice.luma_weight_l0 = slh.luma_weight[0];
+            slice.luma_offset_l0 = slh.luma_offset[0];
+            slice.chroma_weight_l0 = slh.chroma_weight[0];
+            slice.chroma_offset_l0 = slh.chroma_offset[0];
+            if is_b {
+                slice.luma_weight_l1_flag = 1;
+                slice.chroma_weight_l1_flag = chroma;
+                slice.luma_weight_l1 = slh.luma_weight[1];
+                slice.luma_offset_l1 = slh.luma_offset[1];
+                slice.chroma_weight_l1 = slh.chroma_weight[1];
+                slice.chroma_offset_l1 = slh.chroma_offset[1];
+            }
+        }
+
+        let ctx = self.session.as_ref().expect("configured").context.id;
+        let param = VaBuffer::new(&self.display, ctx, ffi::VASliceParameterBufferType, &slice)?;
+        let data = VaBuffer::new_raw(
+            &self.display,
+            ctx,
+            ffi::VASliceDataBufferType,
+            nal.len(),
+            Some(nal),
+        )?;
+        let picture = self.picture.as_mut().expect("begun above");
+        picture.buffers.push(param);
+        picture.buffers.push(data);
+        Ok(())
+    }
+
+    /// Submit the pending picture, apply reference marking and read it
+    /// back.
+    fn finish_picture(&mut self) -> Result<Option<SimpleDecodedFrame>, VideoError> {
+        let Some(picture) = self.picture.take() else {
+            return Ok(None);
+        };
+        let target = {
+            let session = self.session.as_ref().expect("pictures imply a session");
+            let target = session.surfaces.ids[picture.surface];
+            let buffers: Vec<&VaBuffer> = picture.buffers.iter().collect();
+            session.context.render(target, &buffers)?;
+            target
+        };
+        VaSurfaces::sync(&self.display, target)?;
+
+        self.parser.decoded_reference_picture_marking(
+            &picture.first_slice,
+            picture.sps.max_num_ref_frames,
+        );
+        // Free parser entries (and their surfaces) that are no longer
+        // references; the current picture's surface stays held until the
+        // readback below.
+        for i in 0..MAX_DPB_SIZE {
+            if i == picture.dpb_idx {
+                continue;
+            }
+            let e = &self.parser.dpb[i];
+            if e.top_field_marking == 0 && e.bottom_field_marking == 0 {
+                self.parser.dpb[i].state = 0;
+                self.release_surface_of(i);
+            }
+        }
+
+        let is_rgba = self.config.rgba_output;
+        if is_rgba && self.vpp.is_none() {
+            self.vpp = Some(VaVideoProcessor::new(&self.display)?);
+        }
+        let (width, height) = (self.width as usize, self.height as usize);
+        let session = self.session.as_ref().expect("pictures imply a session");
+        let data = match (&self.vpp, &session.rgba_surface, &session.rgba_image) {
+            (Some(vpp), Some(rgba_surface), Some(rgba_image)) if is_rgba => {
+                vpp.blit(
+                    target,
+                    self.width,
+                    self.height,
+                    rgba_surface.ids[0],
+                    self.width,
+                    self.height,
+                )?;
+                rgba_image.read(rgba_surface.ids[0], width, height)?
+            }
+            _ => session.nv12_image.read(target, width, height)?,
+        };
+
+        let current = &self.parser.dpb[picture.dpb_idx];
+        if current.top_field_marking == 0 && current.bottom_field_marking == 0 {
+            // Non-reference picture: its entry and surface are free again.
+            self.parser.dpb[picture.dpb_idx].state = 0;
+            self.release_surface_of(picture.dpb_idx);
+        }
+
+        let frame = SimpleDecodedFrame {
+            data,
+            width: self.width,
+            height: self.height,
+            decode_order: self.frame_counter,
+            picture_order_count: picture.poc,
+            is_rgba,
+        };
+        self.frame_counter += 1;
+        Ok(Some(frame))
+    }
+
+    /// `VAPictureH264` for a reference-marked parser DPB entry.
+    fn va_picture(&self, dpb_idx: usize) -> Option<ffi::VAPictureH264> {
+        let e = self.parser.dpb.get(dpb_idx)?;
+        let surface = self.dpb_to_surface[dpb_idx]?;
+        let (flags, frame_idx) = match e.top_field_marking {
+            MARKING_SHORT => (ffi::VA_PICTURE_H264_SHORT_TERM_REFERENCE, e.frame_num),
+            MARKING_LONG => (
+                ffi::VA_PICTURE_H264_LONG_TERM_REFERENCE,
+                e.long_term_frame_idx,
+            ),
+            _ => return None,
+        };
+        Some(ffi::VAPictureH264 {
+            picture_id: self.session.as_ref()?.surfaces.ids[surface],
+            frame_idx: frame_idx as u32,
+            flags,
+            TopFieldOrderCnt: e.top_field_order_cnt,
+            BottomFieldOrderCnt: e.bottom_field_order_cnt,
+            va_reserved: [0; 4],
+        })
+    }
+
+    fn release_surface_of(&mut self, dpb_idx: usize) {
+        if let Some(surface) = self.dpb_to_surface[dpb_idx].take() {
+            self.surface_in_use[surface] = false;
+        }
+    }
+
+    fn release_all_surfaces(&mut self) {
+        self.dpb_to_surface = [None; MAX_DPB_SIZE + 1];
+        self.surface_in_use = [false; SURFACE_COUNT];
+    }
+}
+
+/// The IQ matrix for a picture: SPS / PPS scaling lists resolved with the
+/// spec's fall-back rules (flat 16 when neither carries one), flattened to
+/// the raster order VAAPI expects.
+fn iq_matrix(sps: &SeqParameterSet, pps: &PicParameterSet) -> ffi::VAIQMatrixBufferH264 {
+    let convert = |list: &NvScalingListH264| ScalingListH264 {
+        scaling_matrix_present_flag: list.scaling_matrix_present_flag,
+        scaling_list_type: list.scaling_list_type.map(ScalingListType::from),
+        scaling_list_4x4: list.scaling_list_4x4,
+        scaling_list_8x8: list.scaling_list_8x8,
+    };
+    let seq = convert(&sps.seq_scaling_list);
+    let pic = convert(&pps.pic_scaling_list);
+    let mut m4 = [[[16u8; 4]; 4]; 6];
+    let mut m8 = [[[16u8; 8]; 8]; 2];
+    set_seq_pic_scaling_lists_h264(Some(&seq), Some(&pic), &mut m4, &mut m8);
+
+    let mut iq: ffi::VAIQMatrixBufferH264 = ffi::zeroed();
+    for (dst, src) in iq.ScalingList4x4.iter_mut().zip(&m4) {
+        for (chunk, row) in dst.chunks_exact_mut(4).zip(src) {
+            chunk.copy_from_slice(row);
+        }
+    }
+    for (dst, src) in iq.ScalingList8x8.iter_mut().zip(&m8) {
+        for (chunk, row) in dst.chunks_exact_mut(8).zip(src) {
+            chunk.copy_from_slice(row);
+        }
+    }
+    iq
+}
+
+/// A reference-marked DPB entry, as seen by list modification.
+#[derive(Debug, Clone, Copy)]
+struct RefCandidate {
+    dpb_idx: usize,
+    /// `PicNum` for short-term, `LongTermPicNum` for long-term.
+    pic_num: i32,
+    long_term: bool,
+}
+
+/// Apply `ref_pic_list_modification()` (H.264 8.2.4.3) for a frame
+/// picture: each operation moves the named picture to the next index and
+/// removes its later duplicate. Truncation to the active count is left to
+/// the caller.
+fn modify_ref_pic_list(
+    list: &mut Vec<usize>,
+    ops: &[RefPicListReordering],
+    curr_pic_num: i32,
+    max_pic_num: i32,
+    candidates: &[RefCandidate],
+) {
+    let mut pic_num_pred = curr_pic_num;
+    let mut ref_idx = 0;
+    for op in ops {
+        let target = match op.reordering_of_pic_nums_idc {
+            0 | 1 => {
+                let abs_diff = op.pic_num_idx + 1;
+                let mut no_wrap = if op.reordering_of_pic_nums_idc == 0 {
+                    pic_num_pred - abs_diff
+                } else {
+                    pic_num_pred + abs_diff
+                };
+                if no_wrap < 0 {
+                    no_wrap += max_pic_num;
+                } else if no_wrap >= max_pic_num {
+                    no_wrap -= max_pic_num;
+                }
+                pic_num_pred = no_wrap;
+                let pic_num = if no_wrap > curr_pic_num {
+                    no_wrap - max_pic_num
+                } else {
+                    no_wrap
+                };
+                candidates
+                    .iter()
+                    .find(|c| !c.long_term && c.pic_num == pic_num)
+            }
+            2 => candidates
+                .iter()
+                .find(|c| c.long_term && c.pic_num == op.pic_num_idx),
+            _ => break,
+        };
+        let Some(target) = target else {
+            tracing::warn!(?op, "H.264 ref list modification names a missing picture");
+            continue;
+        };
+        list.retain(|&i| i != target.dpb_idx);
+        list.insert(ref_idx.min(list.len()), target.dpb_idx);
+        ref_idx += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(idc: i32, pic_num_idx: i32) -> RefPicListReordering {
+        RefPicListReordering {
+            reordering_of_pic_nums_idc: idc,
+            pic_num_idx,
+        }
+    }
+
+    fn short(dpb_idx: usize, pic_num: i32) -> RefCandidate {
+        RefCandidate {
+            dpb_idx,
+            pic_num,
+            long_term: false,
+        }
+    }
+
+    #[test]
+    fn list_modification_moves_named_picture_to_front() {
+        // Current frame_num 5; refs with PicNum 4, 3, 2 in default order.
+        let candidates = [short(0, 4), short(1, 3), short(2, 2)];
+        let mut list = vec![0, 1, 2];
+        // abs_diff_pic_num_minus1 = 2 → PicNum 5 - 3 = 2.
+        modify_ref_pic_list(&mut list, &[op(0, 2), op(3, 0)], 5, 16, &candidates);
+        assert_eq!(list, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn list_modification_wraps_pic_num_and_chains_prediction() {
+        // frame_num 1 after a wrap: the reference with frame_num 15 has
+        // PicNum -1.
+        let candidates = [short(0, 0), short(1, -1)];
+        let mut list = vec![0, 1];
+        // 1 - 3 = -2 → +16 = 14 > 1 → PicNum -2: absent, skipped.
+        // Then pred 14 + 1 = 15 → PicNum -1 → dpb 1.
+        modify_ref_pic_list(
+            &mut list,
+            &[op(0, 2), op(1, 0), op(3, 0)],
+            1,
+            16,
+            &candidates,
+        );
+        assert_eq!(list, vec![1, 0]);
+    }
+
+    #[test]
+    fn list_modification_selects_long_term_by_long_term_pic_num() {
+        let candidates = [
+            short(0, 3),
+            RefCandidate {
+                dpb_idx: 1,
+                pic_num: 0,
+                long_term: true,
+            },
+        ];
+        let mut list = vec![0, 1];
+        modify_ref_pic_list(&mut list, &[op(2, 0), op(3, 0)], 4, 16, &candidates);
+        assert_eq!(list, vec![1, 0]);
+    }
+}
@@ -0,0 +1,512 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! [`VaapiH264Encoder`] — H.264 encode through `VAEntrypointEncSlice`, with
+//! the same [`SimpleEncoderConfig`] / [`EncodePacket`] contract as the
+//! Vulkan Video [`crate::vulkan::video::SimpleEncoder`].
+//!
+//! IP-only GOP (matching the Vulkan encoder's streaming presets): one
+//! short-term reference, two reconstructed surfaces used ping-pong, POC
+//! type 0 advancing by two per frame. Rate control, GOP and QP come from
+//! [`SimpleEncoderConfig::to_encode_config`] so both backends derive them
+//! identically.
+
+use std::sync::Arc;
+
+use crate::vulkan::rhi::HostVulkanTexture;
+use crate::vulkan::video::encode::{
+    Codec, EncodePacket, FrameType, RateControlMode, SimpleEncoderConfig,
+};
+
+use super::VaapiError;
+use super::display::VaDisplay;
+use super::ffi;
+use super::h264_headers::{
+    self, H264StreamParams, LOG2_MAX_FRAME_NUM_MINUS4, LOG2_MAX_POC_LSB_MINUS4, MAX_NUM_REF_FRAMES,
+};
+use super::surface::{VaBuffer, VaConfig, VaContext, VaImage, VaSurfaces, VaVideoProcessor};
+
+/// Profiles tried in order, with the `profile_idc` each one writes.
+const PROFILE_PREFERENCE: [(ffi::VAProfile, u8); 3] = [
+    (ffi::VAProfileH264High, h264_headers::PROFILE_IDC_HIGH),
+    (ffi::VAProfileH264Main, h264_headers::PROFILE_IDC_MAIN),
+    (
+        ffi::VAProfileH264ConstrainedBaseline,
+        h264_headers::PROFILE_IDC_BASELINE,
+    ),
+];
+
+/// Ping-pong reconstructed surfaces: the current picture and its single
+/// reference.
+const RECON_SURFACES: usize = 2;
+
+/// H.264 encoder on a VAAPI encode entrypoint.
+pub struct VaapiH264Encoder {
+    // Field order is drop order: contexts before the surfaces they target,
+    // everything before the display.
+    context: VaContext,
+    vpp: Option<VaVideoProcessor>,
+    upload_image: Option<VaImage>,
+    input: VaSurfaces,
+    recon: VaSurfaces,
+    display: Arc<VaDisplay>,
+
+    config: SimpleEncoderConfig,
+    params: H264StreamParams,
+    header: Vec<u8>,
+    rate_control: RateControlMode,
+    bits_per_second: u32,
+    max_bits_per_second: u32,
+    qp_intra: i32,
+    qp_inter: i32,
+    gop_size: u32,
+    idr_period: u32,
+    prepend_header: bool,
+
+    /// Frames submitted so far (the packet `pts`).
+    frame_index: u64,
+    /// Frames since the last IDR; drives `frame_num` and POC.
+    frames_since_idr: u32,
+    idr_pic_id: u16,
+    force_idr_flag: bool,
+    /// Recon surface holding the current reference, if any.
+    reference: Option<usize>,
+}
+
+impl VaapiH264Encoder {
+    /// Open a VAAPI display and create an encode session for `config`.
+    ///
+    /// # Errors
+    ///
+    /// - The config is invalid or asks for H.265.
+    /// - libva / a render node is unavailable, or the driver exposes no
+    ///   H.264 encode entrypoint.
+    /// - The driver rejects the requested rate-control mode.
+    pub fn new(config: SimpleEncoderConfig) -> Result<Self, VaapiError> {
+        config.validate().map_err(VaapiError::Bitstream)?;
+        if config.codec != Codec::H264 {
+            return Err(VaapiError::Unsupported(
+                "the VAAPI encoder implements H.264 only".into(),
+            ));
+        }
+        let display = VaDisplay::open()?;
+
+        let profiles = display.profiles();
+        let (profile, profile_idc, entrypoint) = PROFILE_PREFERENCE
+            .iter()
+            .filter(|(p, _)| profiles.contains(p))
+            .find_map(|&(p, idc)| {
+                let entrypoints = display.entrypoints(p);
+                [ffi::VAEntrypointEncSlice, ffi::VAEntrypointEncSliceLP]
+                    .into_iter()
+                    .find(|ep| entrypoints.contains(ep))
+                    .map(|ep| (p, idc, ep))
+            })
+            .ok_or_else(|| {
+                VaapiError::Unsupported(format!(
+                    "{} exposes no H.264 encode entrypoint",
+                    display.device_path().display()
+                ))
+            })?;
+
+        let enc = config.to_encode_config();
+        let va_rc = match enc.rate_control_mode {
+            RateControlMode::Cbr => ffi::VA_RC_CBR,
+            RateControlMode::Vbr => ffi::VA_RC_VBR,
+            RateControlMode::Cqp | RateControlMode::Default => ffi::VA_RC_CQP,
+        };
+        let supported_rc = display
+            .config_attribute(profile, entrypoint, ffi::VAConfigAttribRateControl)
+            .unwrap_or(0);
+        if supported_rc & va_rc == 0 {
+            return Err(VaapiError::Unsupported(format!(
+                "driver does not support rate control {:?} (supported mask 0x{supported_rc:x})",
+                enc.rate_control_mode
+            )));
+        }
+
+        let mut attribs = vec![
+            ffi::VAConfigAttrib {
+                type_: ffi::VAConfigAttribRTFormat,
+                value: ffi::VA_RT_FORMAT_YUV420,
+            },
+            ffi::VAConfigAttrib {
+                type_: ffi::VAConfigAttribRateControl,
+                value: va_rc,
+            },
+        ];
+        // Claiming the sequence / picture headers stops drivers that honour
+        // packed headers from writing their own; the rest are stripped from
+        // the coded output.
+        let wanted_packed = ffi::VA_ENC_PACKED_HEADER_SEQUENCE | ffi::VA_ENC_PACKED_HEADER_PICTURE;
+        if let Some(packed) =
+            display.config_attribute(profile, entrypoint, ffi::VAConfigAttribEncPackedHeaders)
+            && packed & wanted_packed == wanted_packed
+        {
+            attribs.push(ffi::VAConfigAttrib {
+                type_: ffi::VAConfigAttribEncPackedHeaders,
+                value: wanted_packed,
+            });
+        }
+
+        let is_cqp = va_rc == ffi::VA_RC_CQP;
+        let params = H264StreamParams {
+            profile_idc,
+            level_idc: h264_headers::level_idc_for(config.width, config.height, config.fps),
+            width: config.width,
+            height: config.height,
+            fps: config.fps,
+            color_vui: config.color_vui,
+            cabac: profile_idc != h264_headers::PROFILE_IDC_BASELINE,
+            transform_8x8: profile_idc == h264_headers::PROFILE_IDC_HIGH,
+            pic_init_qp: if is_cqp {
+                enc.const_qp_intra.clamp(0, 51) as u8
+            } else {
+                26
+            },
+        };
+        let (aligned_w, aligned_h) = (params.width_in_mbs() * 16, params.height_in_mbs() * 16);
+
+        let va_config = VaConfig::new(&display, profile, entrypoint, &mut attribs)?;
+        let recon = VaSurfaces::new(
+            &display,
+            ffi::VA_FOURCC_NV12,
+            aligned_w,
+            aligned_h,
+            RECON_SURFACES,
+        )?;
+        let input = VaSurfaces::new(&display, ffi::VA_FOURCC_NV12, aligned_w, aligned_h, 1)?;
+        let context = VaContext::new(va_config, aligned_w, aligned_h, &recon.ids)?;
+
+        tracing::info!(
+            device = %display.device_path().display(),
+            profile_idc,
+            level_idc = params.level_idc,
+            rate_control = ?enc.rate_control_mode,
+            low_power = entrypoint == ffi::VAEntrypointEncSliceLP,
+            "VAAPI H.264 encoder created ({}x{} @ {} fps)",
+            config.width,
+            config.height,
+            config.fps
+        );
+
+        Ok(Self {
+            context,
+            vpp: None,
+            upload_image: None,
+            input,
+            recon,
+            display,
+            header: params.header(),
+            params,
+            rate_control: enc.rate_control_mode,
+            bits_per_second: enc.average_bitrate,
+            max_bits_per_second: enc.max_bitrate.max(enc.average_bitrate),
+            qp_intra: enc.const_qp_intra,
+            qp_inter: enc.const_qp_inter_p,
+            gop_size: enc.gop_size,
+            idr_period: enc.idr_period,
+            prepend_header: config.effective_prepend_header(),
+            config,
+            frame_index: 0,
+            frames_since_idr: 0,
+            idr_pic_id: 0,
+            force_idr_flag: false,
+            reference: None,
+        })
+    }
+
+    /// SPS + PPS for this session.
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+
+    /// Force the next frame to be encoded as an IDR keyframe.
+    pub fn force_idr(&mut self) {
+        self.force_idr_flag = true;
+    }
+
+    /// Minimum source texture size for [`Self::encode_texture`]. The VPP
+    /// blit crops the source to the configured size, so no codec alignment
+    /// leaks to callers.
+    pub fn aligned_extent(&self) -> (u32, u32) {
+        (self.config.width, self.config.height)
+    }
+
+    /// Encode one tightly packed NV12 frame (`width * height * 3 / 2`).
+    pub fn submit_frame(
+        &mut self,
+        nv12_data: &[u8],
+        timestamp_ns: Option<i64>,
+    ) -> Result<Vec<EncodePacket>, VaapiError> {
+        let (width, height) = (self.config.width as usize, self.config.height as usize);
+        let expected_size = width * height * 3 / 2;
+        if nv12_data.len() < expected_size {
+            return Err(VaapiError::Bitstream(format!(
+                "NV12 data too small: expected {} bytes, got {}",
+                expected_size,
+                nv12_data.len()
+            )));
+        }
+        if self.upload_image.is_none() {
+            self.upload_image = Some(VaImage::new(
+                &self.display,
+                ffi::VA_FOURCC_NV12,
+                self.config.width,
+                self.config.height,
+            )?);
+        }
+        let image = self.upload_image.as_ref().expect("created above");
+        image.upload_nv12(self.input.ids[0], nv12_data, width, height)?;
+        self.encode_input(timestamp_ns).map(|packet| vec![packet])
+    }
+
+    /// Encode a GPU texture without a CPU copy: its DMA-BUF is imported as
+    /// a VA surface and converted to NV12 on the video-processing engine.
+    /// The texture must be DMA-BUF exportable with a plane layout (the
+    /// render-target DMA-BUF allocations the camera and pools hand out).
+    pub fn encode_texture(
+        &mut self,
+        texture: &HostVulkanTexture,
+        timestamp_ns: Option<i64>,
+    ) -> Result<Vec<EncodePacket>, VaapiError> {
+        if self.vpp.is_none() {
+            self.vpp = Some(VaVideoProcessor::new(&self.display)?);
+        }
+        let source = VaSurfaces::import_texture(&self.display, texture)?;
+        let vpp = self.vpp.as_ref().expect("created above");
+        vpp.blit(
+            source.ids[0],
+            self.config.width,
+            self.config.height,
+            self.input.ids[0],
+            self.config.width,
+            self.config.height,
+        )?;
+        // The blit has completed (synced); the import can go before the
+        // encode so the texture is released as early as possible.
+        drop(source);
+        self.encode_input(timestamp_ns).map(|packet| vec![packet])
+    }
+
+    /// IP-only: nothing is held back, so there is nothing to flush.
+    pub fn finish(&mut self) -> Result<Vec<EncodePacket>, VaapiError> {
+        Ok(Vec::new())
+    }
+
+    fn next_frame_type(&mut self) -> FrameType {
+        let idr_due = self.frame_index == 0
+            || self.force_idr_flag
+            || (self.idr_period > 0 && self.frames_since_idr >= self.idr_period);
+        if idr_due {
+            self.force_idr_flag = false;
+            if self.frame_index > 0 {
+                self.idr_pic_id = self.idr_pic_id.wrapping_add(1);
+            }
+            self.frames_since_idr = 0;
+            FrameType::Idr
+        } else if self.gop_size > 0 && self.frames_since_idr % self.gop_size == 0 {
+            FrameType::I
+        } else {
+            FrameType::P
+        }
+    }
+
+    /// Encode whatever is in the input surface.
+    fn encode_input(&mut self, timestamp_ns: Option<i64>) -> Result<EncodePacket, VaapiError> {
+        let frame_type = self.next_frame_type();
+        let is_idr = frame_type == FrameType::Idr;
+        let reference = if is_idr { None } else { self.reference };
+        let current = self.reference.map_or(0, |r| (r + 1) % RECON_SURFACES);
+        let frame_num = self.frames_since_idr % (1 << (LOG2_MAX_FRAME_NUM_MINUS4 + 4));
+        let poc = (self.frames_since_idr * 2) % (1 << (LOG2_MAX_POC_LSB_MINUS4 + 4));
+
+        let display = Arc::clone(&self.display);
+        let ctx = self.context.id;
+        let mbs = self.params.width_in_mbs() * self.params.height_in_mbs();
+        // Worst case for an intra frame at low QP, plus slack for headers.
+        let coded_size = (mbs as usize * 384 * 3 / 2).max(1 << 20);
+        let coded = VaBuffer::new_raw(&display, ctx, ffi::VAEncCodedBufferType, coded_size, None)?;
+
+        let mut buffers = Vec::with_capacity(8);
+        if is_idr {
+            buffers.push(VaBuffer::new(
+                &display,
+                ctx,
+                ffi::VAEncSequenceParameterBufferType,
+                &self.sequence_params(),
+            )?);
+            buffers.extend(self.misc_params(&display)?);
+        }
+
+        let reference_picture = reference.map(|r| ffi::VAPictureH264 {
+            picture_id: self.recon.ids[r],
+            frame_idx: frame_num.wrapping_sub(1) % (1 << (LOG2_MAX_FRAME_NUM_MINUS4 + 4)),
+            flags: ffi::VA_PICTURE_H264_SHORT_TERM_REFERENCE,
+            TopFieldOrderCnt: poc as i32 - 2,
+            BottomFieldOrderCnt: poc as i32 - 2,
+            va_reserved: [0; 4],
+        });
+
+        let mut picture: ffi::VAEncPictureParameterBufferH264 = ffi::zeroed();
+        picture.CurrPic = ffi::VAPictureH264 {
+            picture_id: self.recon.ids[current],
+            frame_idx: frame_num,
+            flags: 0,
+            TopFieldOrderCnt: poc as i32,
+            BottomFieldOrderCnt: poc as i32,
+            va_reserved: [0; 4],
+        };
+        picture.ReferenceFrames = [ffi::VAPictureH264::INVALID; 16];
+        if let Some(r) = reference_picture {
+            picture.ReferenceFrames[0] = r;
+        }
+        picture.coded_buf = coded.id;
+        picture.frame_num = frame_num as u16;
+        picture.pic_init_qp = self.params.pic_init_qp;
+        picture.pic_fields = u32::from(is_idr)
+            | 1 << 1 // reference_pic_flag: every picture is a reference
+            | u32::from(self.params.cabac) << 3
+            | u32::from(self.params.transform_8x8) << 8
+            | 1 << 9; // deblocking_filter_control_present_flag
+        buffers.push(VaBuffer::new(
+            &display,
+            ctx,
+            ffi::VAEncPictureParameterBufferType,
+            &picture,
+        )?);
+
+        let is_p = frame_type == FrameType::P && reference_picture.is_some();
+        let mut slice: ffi::VAEncSliceParameterBufferH264 = ffi::zeroed();
+        slice.num_macroblocks = mbs;
+        slice.macroblock_info = ffi::VA_INVALID_ID;
+        slice.slice_type = if is_p { 0 } else { 2 };
+        slice.idr_pic_id = self.idr_pic_id;
+        slice.pic_order_cnt_lsb = poc as u16;
+        slice.RefPicList0 = [ffi::VAPictureH264::INVALID; 32];
+        slice.RefPicList1 = [ffi::VAPictureH264::INVALID; 32];
+        if is_p && let Some(r) = reference_picture {
+            slice.RefPicList0[0] = r;
+        }
+        if self.rate_control == RateControlMode::Cqp {
+            let qp = if is_p { self.qp_inter } else { self.qp_intra };
+            slice.slice_qp_delta = (qp.clamp(0, 51) - self.params.pic_init_qp as i32) as i8;
+        }
+        buffers.push(VaBuffer::new(
+            &display,
+            ctx,
+            ffi::VAEncSliceParameterBufferType,
+            &slice,
+        )?);
+
+        let input = self.input.ids[0];
+        let refs: Vec<&VaBuffer> = buffers.iter().collect();
+        self.context.render(input, &refs)?;
+        VaSurfaces::sync(&display, input)?;
+        let bitstream = h264_headers::strip_parameter_sets(&coded.read_coded()?);
+
+        let frame_type = if is_p { FrameType::P } else { frame_type };
+        let pts = self.frame_index;
+        let mut data = Vec::with_capacity(bitstream.len() + self.header.len());
+        if is_idr && (pts == 0 || self.prepend_header) {
+            data.extend_from_slice(&self.header);
+        }
+        data.extend_from_slice(&bitstream);
+
+        self.reference = Some(current);
+        self.frames_since_idr += 1;
+        self.frame_index += 1;
+
+        Ok(EncodePacket {
+            data,
+            frame_type,
+            pts,
+            is_keyframe: is_idr,
+            timestamp_ns,
+        })
+    }
+
+    fn sequence_params(&self) -> ffi::VAEncSequenceParameterBufferH264 {
+        let p = &self.params;
+        let mut seq: ffi::VAEncSequenceParameterBufferH264 = ffi::zeroed();
+        seq.level_idc = p.level_idc;
+        seq.intra_period = self.gop_size;
+        seq.intra_idr_period = self.idr_period;
+        seq.ip_period = 1;
+        seq.bits_per_second = if self.rate_control == RateControlMode::Cqp {
+            0
+        } else {
+            self.max_bits_per_second
+        };
+        seq.max_num_ref_frames = MAX_NUM_REF_FRAMES;
+        seq.picture_width_in_mbs = p.width_in_mbs() as u16;
+        seq.picture_height_in_mbs = p.height_in_mbs() as u16;
+        seq.seq_fields = 1 // chroma_format_idc: 4:2:0
+            | 1 << 2 // frame_mbs_only_flag
+            | 1 << 5 // direct_8x8_inference_flag
+            | LOG2_MAX_FRAME_NUM_MINUS4 << 6
+            | LOG2_MAX_POC_LSB_MINUS4 << 12;
+        let (crop_right, crop_bottom) = p.crop();
+        if crop_right > 0 || crop_bottom > 0 {
+            seq.frame_cropping_flag = 1;
+            seq.frame_crop_right_offset = crop_right;
+            seq.frame_crop_bottom_offset = crop_bottom;
+        }
+        seq.vui_parameters_present_flag = 1;
+        seq.vui_fields = 1 << 1 // timing_info_present_flag
+            | 1 << 2 // bitstream_restriction_flag
+            | 15 << 3 // log2_max_mv_length_horizontal
+            | 15 << 8 // log2_max_mv_length_vertical
+            | 1 << 13 // fixed_frame_rate_flag
+            | 1 << 15; // motion_vectors_over_pic_boundaries_flag
+        seq.num_units_in_tick = 1;
+        seq.time_scale = p.fps * 2;
+        seq
+    }
+
+    /// Frame-rate, rate-control and HRD misc buffers, sent with every IDR.
+    fn misc_params(&self, display: &Arc<VaDisplay>) -> Result<Vec<VaBuffer>, VaapiError> {
+        let ctx = self.context.id;
+        let mut buffers = vec![VaBuffer::new_misc(
+            display,
+            ctx,
+            ffi::VAEncMiscParameterTypeFrameRate,
+            &ffi::VAEncMiscParameterFrameRate {
+                framerate: self.config.fps,
+                ..Default::default()
+            },
+        )?];
+        if self.rate_control == RateControlMode::Cqp {
+            return Ok(buffers);
+        }
+        let max = self.max_bits_per_second;
+        let target_percentage = if self.rate_control == RateControlMode::Cbr || max == 0 {
+            100
+        } else {
+            (u64::from(self.bits_per_second) * 100 / u64::from(max)).clamp(1, 100) as u32
+        };
+        buffers.push(VaBuffer::new_misc(
+            display,
+            ctx,
+            ffi::VAEncMiscParameterTypeRateControl,
+            &ffi::VAEncMiscParameterRateControl {
+                bits_per_second: max,
+                target_percentage,
+                window_size: 1000,
+                ..Default::default()
+            },
+        )?);
+        buffers.push(VaBuffer::new_misc(
+            display,
+            ctx,
+            ffi::VAEncMiscParameterTypeHRD,
+            &ffi::VAEncMiscParameterHRD {
+                // One second of buffering, starting half full.
+                buffer_size: max,
+                initial_buffer_fullness: max / 2,
+                ..Default::default()
+            },
+        )?);
+        Ok(buffers)
+    }
+}
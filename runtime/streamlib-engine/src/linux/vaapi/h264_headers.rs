@@ -0,0 +1,304 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! SPS / PPS generation for the VAAPI H.264 encoder.
+//!
+//! Drivers disagree on whether (and how) they emit parameter sets — iHD and
+//! radeonsi write their own, some older drivers write none. The encoder
+//! writes its own from the same values it hands the driver and strips
+//! whatever the driver inserted, so `header()` and in-band IDR headers are
+//! identical across drivers and match what the Vulkan Video encoder emits
+//! (timing info, colour description, bitstream restriction).
+
+use crate::vulkan::video::H273ColorVui;
+use crate::vulkan::video::SimpleDecoder;
+use crate::vulkan::video::encode::vui_patch::{BitWriter, add_epb};
+
+/// `log2_max_frame_num_minus4`: 8-bit `frame_num`.
+pub(super) const LOG2_MAX_FRAME_NUM_MINUS4: u32 = 4;
+/// `log2_max_pic_order_cnt_lsb_minus4`: 8-bit POC LSB (POC type 0).
+pub(super) const LOG2_MAX_POC_LSB_MINUS4: u32 = 4;
+/// IP-only GOP: one reference frame.
+pub(super) const MAX_NUM_REF_FRAMES: u32 = 1;
+
+pub(super) const PROFILE_IDC_BASELINE: u8 = 66;
+pub(super) const PROFILE_IDC_MAIN: u8 = 77;
+pub(super) const PROFILE_IDC_HIGH: u8 = 100;
+
+const NAL_SPS: u8 = 7;
+const NAL_PPS: u8 = 8;
+
+/// Everything the SPS / PPS depend on. The encoder mirrors the same values
+/// into `VAEncSequenceParameterBufferH264` / `VAEncPictureParameterBufferH264`.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct H264StreamParams {
+    pub profile_idc: u8,
+    pub level_idc: u8,
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub color_vui: Option<H273ColorVui>,
+    pub cabac: bool,
+    pub transform_8x8: bool,
+    pub pic_init_qp: u8,
+}
+
+impl H264StreamParams {
+    pub(super) fn width_in_mbs(&self) -> u32 {
+        self.width.div_ceil(16)
+    }
+
+    pub(super) fn height_in_mbs(&self) -> u32 {
+        self.height.div_ceil(16)
+    }
+
+    /// `(crop_right, crop_bottom)` in 4:2:0 crop units (2 pixels).
+    pub(super) fn crop(&self) -> (u32, u32) {
+        (
+            (self.width_in_mbs() * 16 - self.width) / 2,
+            (self.height_in_mbs() * 16 - self.height) / 2,
+        )
+    }
+
+    /// `constraint_set0..5` flags byte for the profile.
+    fn constraint_flags(&self) -> u8 {
+        match self.profile_idc {
+            // Constrained Baseline = Baseline with constraint_set0 + set1.
+            PROFILE_IDC_BASELINE => 0xc0,
+            _ => 0x00,
+        }
+    }
+
+    /// Annex B SPS NAL (4-byte start code).
+    pub(super) fn sps_nal(&self) -> Vec<u8> {
+        let mut w = BitWriter::new();
+        w.put_bits(self.profile_idc as u32, 8);
+        w.put_bits(self.constraint_flags() as u32, 8);
+        w.put_bits(self.level_idc as u32, 8);
+        w.put_ue(0); // seq_parameter_set_id
+        if self.profile_idc == PROFILE_IDC_HIGH {
+            w.put_ue(1); // chroma_format_idc: 4:2:0
+            w.put_ue(0); // bit_depth_luma_minus8
+            w.put_ue(0); // bit_depth_chroma_minus8
+            w.put_bit(0); // qpprime_y_zero_transform_bypass_flag
+            w.put_bit(0); // seq_scaling_matrix_present_flag
+        }
+        w.put_ue(LOG2_MAX_FRAME_NUM_MINUS4);
+        w.put_ue(0); // pic_order_cnt_type
+        w.put_ue(LOG2_MAX_POC_LSB_MINUS4);
+        w.put_ue(MAX_NUM_REF_FRAMES);
+        w.put_bit(0); // gaps_in_frame_num_value_allowed_flag
+        w.put_ue(self.width_in_mbs() - 1);
+        w.put_ue(self.height_in_mbs() - 1);
+        w.put_bit(1); // frame_mbs_only_flag
+        w.put_bit(1); // direct_8x8_inference_flag
+        let (crop_right, crop_bottom) = self.crop();
+        if crop_right > 0 || crop_bottom > 0 {
+            w.put_bit(1);
+            w.put_ue(0);
+            w.put_ue(crop_right);
+            w.put_ue(0);
+            w.put_ue(crop_bottom);
+        } else {
+            w.put_bit(0);
+        }
+        w.put_bit(1); // vui_parameters_present_flag
+        self.write_vui(&mut w);
+        w.trailing_bits();
+        nal(NAL_SPS, 3, &w.into_bytes())
+    }
+
+    fn write_vui(&self, w: &mut BitWriter) {
+        w.put_bit(0); // aspect_ratio_info_present_flag
+        w.put_bit(0); // overscan_info_present_flag
+        match self.color_vui {
+            Some(vui) if vui.is_video_signal_type_block_needed() => {
+                w.put_bit(1); // video_signal_type_present_flag
+                w.put_bits(5, 3); // video_format: unspecified
+                w.put_bit(vui.full_range_bit());
+                if vui.is_colour_description_block_needed() {
+                    w.put_bit(1);
+                    w.put_bits(vui.primaries_byte() as u32, 8);
+                    w.put_bits(vui.transfer_byte() as u32, 8);
+                    w.put_bits(vui.matrix_byte() as u32, 8);
+                } else {
+                    w.put_bit(0);
+                }
+            }
+            _ => w.put_bit(0),
+        }
+        w.put_bit(0); // chroma_loc_info_present_flag
+        w.put_bit(1); // timing_info_present_flag
+        w.put_bits(1, 32); // num_units_in_tick
+        w.put_bits(self.fps * 2, 32); // time_scale (two ticks per frame)
+        w.put_bit(1); // fixed_frame_rate_flag
+        w.put_bit(0); // nal_hrd_parameters_present_flag
+        w.put_bit(0); // vcl_hrd_parameters_present_flag
+        w.put_bit(0); // pic_struct_present_flag
+        w.put_bit(1); // bitstream_restriction_flag
+        w.put_bit(1); // motion_vectors_over_pic_boundaries_flag
+        w.put_ue(0); // max_bytes_per_pic_denom
+        w.put_ue(0); // max_bits_per_mb_denom
+        w.put_ue(15); // log2_max_mv_length_horizontal
+        w.put_ue(15); // log2_max_mv_length_vertical
+        w.put_ue(0); // max_num_reorder_frames: no B-frames
+        w.put_ue(MAX_NUM_REF_FRAMES); // max_dec_frame_buffering
+    }
+
+    /// Annex B PPS NAL (4-byte start code).
+    pub(super) fn pps_nal(&self) -> Vec<u8> {
+        let mut w = BitWriter::new();
+        w.put_ue(0); // pic_parameter_set_id
+        w.put_ue(0); // seq_parameter_set_id
+        w.put_bit(u32::from(self.cabac));
+        w.put_bit(0); // bottom_field_pic_order_in_frame_present_flag
+        w.put_ue(0); // num_slice_groups_minus1
+        w.put_ue(0); // num_ref_idx_l0_default_active_minus1
+        w.put_ue(0); // num_ref_idx_l1_default_active_minus1
+        w.put_bit(0); // weighted_pred_flag
+        w.put_bits(0, 2); // weighted_bipred_idc
+        w.put_se(self.pic_init_qp as i32 - 26);
+        w.put_se(0); // pic_init_qs_minus26
+        w.put_se(0); // chroma_qp_index_offset
+        w.put_bit(1); // deblocking_filter_control_present_flag
+        w.put_bit(0); // constrained_intra_pred_flag
+        w.put_bit(0); // redundant_pic_cnt_present_flag
+        if self.transform_8x8 {
+            w.put_bit(1); // transform_8x8_mode_flag
+            w.put_bit(0); // pic_scaling_matrix_present_flag
+            w.put_se(0); // second_chroma_qp_index_offset
+        }
+        w.trailing_bits();
+        nal(NAL_PPS, 3, &w.into_bytes())
+    }
+
+    /// SPS + PPS, the encoder's `header()`.
+    pub(super) fn header(&self) -> Vec<u8> {
+        let mut out = self.sps_nal();
+        out.extend_from_slice(&self.pps_nal());
+        out
+    }
+}
+
+/// Annex B NAL: start code, header byte, EPB-escaped RBSP.
+fn nal(nal_unit_type: u8, nal_ref_idc: u8, rbsp: &[u8]) -> Vec<u8> {
+    let mut out = vec![0, 0, 0, 1, (nal_ref_idc << 5) | nal_unit_type];
+    out.extend_from_slice(&add_epb(rbsp));
+    out
+}
+
+/// Lowest level whose frame-size and macroblock-rate limits (Table A-1)
+/// cover `width x height @ fps`.
+pub(super) fn level_idc_for(width: u32, height: u32, fps: u32) -> u8 {
+    // (level_idc, MaxMBPS, MaxFS)
+    const LEVELS: &[(u8, u64, u64)] = &[
+        (10, 1_485, 99),
+        (11, 3_000, 396),
+        (12, 6_000, 396),
+        (13, 11_880, 396),
+        (20, 11_880, 396),
+        (21, 19_800, 792),
+        (22, 20_250, 1_620),
+        (30, 40_500, 1_620),
+        (31, 108_000, 3_600),
+        (32, 216_000, 5_120),
+        (40, 245_760, 8_192),
+        (41, 245_760, 8_192),
+        (42, 522_240, 8_704),
+        (50, 589_824, 22_080),
+        (51, 983_040, 36_864),
+        (52, 2_073_600, 36_864),
+        (60, 4_177_920, 139_264),
+        (61, 8_355_840, 139_264),
+        (62, 16_711_680, 139_264),
+    ];
+    let frame_mbs = width.div_ceil(16) as u64 * height.div_ceil(16) as u64;
+    let mbps = frame_mbs * fps as u64;
+    LEVELS
+        .iter()
+        .find(|&&(_, max_mbps, max_fs)| frame_mbs <= max_fs && mbps <= max_mbps)
+        .map_or(62, |&(level, _, _)| level)
+}
+
+/// Drop SPS / PPS NALs the driver inserted into a coded picture; the
+/// encoder prepends its own.
+pub(super) fn strip_parameter_sets(annex_b: &[u8]) -> Vec<u8> {
+    let nals = SimpleDecoder::split_nal_units_owned(annex_b);
+    let mut out = Vec::with_capacity(annex_b.len());
+    for nal in nals
+        .iter()
+        .filter(|n| !n.is_empty() && !matches!(n[0] & 0x1f, NAL_SPS | NAL_PPS))
+    {
+        out.extend_from_slice(&[0, 0, 0, 1]);
+        out.extend_from_slice(nal);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vulkan::video::nv_video_parser::vulkan_h264_decoder::{
+        BitstreamReader, H264PocType, VulkanH264Decoder,
+    };
+
+    fn params() -> H264StreamParams {
+        H264StreamParams {
+            profile_idc: PROFILE_IDC_HIGH,
+            level_idc: level_idc_for(1920, 1080, 30),
+            width: 1920,
+            height: 1080,
+            fps: 30,
+            color_vui: Some(H273ColorVui {
+                primaries: Some(1),
+                transfer: Some(1),
+                matrix: Some(1),
+                full_range: Some(false),
+            }),
+            cabac: true,
+            transform_8x8: true,
+            pic_init_qp: 20,
+        }
+    }
+
+    #[test]
+    fn sps_round_trips_through_the_decoder_parser() {
+        let sps = params().sps_nal();
+        assert_eq!(&sps[..5], &[0, 0, 0, 1, 0x67]);
+        let rbsp = SimpleDecoder::remove_emulation_prevention_bytes(&sps[5..]);
+        let mut parser = VulkanH264Decoder::new();
+        let id = parser
+            .parse_sps(&mut BitstreamReader::new(&rbsp))
+            .expect("generated SPS parses");
+        let parsed = parser.spss[id as usize].as_ref().unwrap();
+        assert_eq!(parsed.profile_idc, PROFILE_IDC_HIGH);
+        assert_eq!(parsed.pic_width_in_mbs_minus1, 119);
+        assert_eq!(parsed.pic_height_in_map_units_minus1, 67);
+        assert!(parsed.flags.frame_cropping_flag);
+        assert_eq!(parsed.frame_crop_bottom_offset, 4);
+        assert_eq!(parsed.pic_order_cnt_type, H264PocType::Type0);
+        assert_eq!(parsed.max_num_ref_frames, MAX_NUM_REF_FRAMES);
+        assert_eq!(parsed.vui.time_scale, 60);
+        assert_eq!(parsed.vui.colour_primaries, 1);
+        assert!(parsed.vui.bitstream_restriction_flag);
+    }
+
+    #[test]
+    fn level_tracks_frame_size_and_rate() {
+        assert_eq!(level_idc_for(640, 480, 30), 30);
+        assert_eq!(level_idc_for(1280, 720, 30), 31);
+        assert_eq!(level_idc_for(1920, 1080, 30), 40);
+        assert_eq!(level_idc_for(1920, 1080, 60), 42);
+        assert_eq!(level_idc_for(3840, 2160, 30), 51);
+    }
+
+    #[test]
+    fn strip_drops_only_parameter_sets() {
+        let mut stream = params().header();
+        stream.extend_from_slice(&[0, 0, 1, 0x65, 0x88, 0x84]);
+        assert_eq!(
+            strip_parameter_sets(&stream),
+            vec![0, 0, 0, 1, 0x65, 0x88, 0x84]
+        );
+    }
+}
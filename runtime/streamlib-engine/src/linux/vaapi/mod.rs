@@ -0,0 +1,98 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! VAAPI H.264 encode / decode — the fallback codec backend for Linux boxes
+//! without Vulkan Video (Intel / AMD iGPUs on older Mesa, NVIDIA-less hosts).
+//!
+//! `libva` is loaded at runtime ([`ffi`]), so the engine links and runs on
+//! hosts without it. [`probe`] opens a DRM render node once per process and
+//! reports which H.264 entrypoints the driver exposes; the codec factory
+//! (`linux::codec_factory`) consults it to pick between Vulkan Video and
+//! VAAPI per session.
+//!
+//! GPU textures cross into VAAPI as DMA-BUFs: the encoder imports the
+//! source texture's DMA-BUF as a VA surface (`DRM_PRIME_2`) and converts it
+//! to NV12 on the video-processing engine, so camera frames never touch
+//! host memory on the encode path. Decoded pictures come back through
+//! `vaGetImage` (NV12) or a VPP blit into an RGBA surface.
+//!
+//! H.264 only — H.265 reports unsupported and the factory keeps those
+//! sessions on Vulkan Video.
+
+mod display;
+mod ffi;
+mod h264_decoder;
+mod h264_encoder;
+mod h264_headers;
+mod surface;
+
+use std::sync::OnceLock;
+
+use thiserror::Error;
+
+use crate::vulkan::video::VideoError;
+
+pub use display::VaapiCapabilities;
+pub use h264_decoder::VaapiH264Decoder;
+pub use h264_encoder::VaapiH264Encoder;
+
+/// Environment variable naming the DRM render node to open (e.g.
+/// `/dev/dri/renderD129`). Unset = the first render node whose driver
+/// initialises.
+pub const VAAPI_DEVICE_ENV_VAR: &str = "STREAMLIB_VAAPI_DEVICE";
+
+/// Errors from the VAAPI backend.
+#[derive(Debug, Error)]
+pub enum VaapiError {
+    #[error("libva not loadable: {0}")]
+    LibraryNotFound(String),
+    #[error("required libva symbol '{0}' missing")]
+    SymbolMissing(&'static str),
+    #[error("no usable VAAPI render node: {0}")]
+    NoDevice(String),
+    #[error("{call} failed: {message} (VAStatus {status})")]
+    Call {
+        call: &'static str,
+        status: i32,
+        message: String,
+    },
+    #[error("VAAPI: {0}")]
+    Unsupported(String),
+    #[error("VAAPI bitstream error: {0}")]
+    Bitstream(String),
+}
+
+impl From<VaapiError> for VideoError {
+    fn from(e: VaapiError) -> Self {
+        match e {
+            VaapiError::Bitstream(msg) => VideoError::BitstreamError(msg),
+            other => VideoError::Engine(other.to_string()),
+        }
+    }
+}
+
+static CAPABILITIES: OnceLock<VaapiCapabilities> = OnceLock::new();
+
+/// VAAPI codec capabilities of this host, probed once per process.
+///
+/// Never fails: a host without libva, without a render node or whose driver
+/// refuses to initialise reports every capability `false` (the reason is
+/// logged at debug level).
+pub fn probe() -> VaapiCapabilities {
+    *CAPABILITIES.get_or_init(|| match display::VaDisplay::open() {
+        Ok(display) => {
+            let caps = display.capabilities();
+            tracing::info!(
+                device = %display.device_path().display(),
+                vendor = %display.vendor(),
+                ?caps,
+                "VAAPI probe"
+            );
+            caps
+        }
+        Err(e) => {
+            tracing::debug!("VAAPI unavailable: {e}");
+            VaapiCapabilities::default()
+        }
+    })
+}
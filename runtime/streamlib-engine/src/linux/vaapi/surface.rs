@@ -0,0 +1,731 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! RAII wrappers for VA configs, contexts, surfaces and parameter buffers,
+//! plus the pixel paths: NV12 upload / readback through `VAImage`, DMA-BUF
+//! import / export, and the VPP blit used for RGBA <-> NV12.
+
+use std::ffi::{c_int, c_uint, c_void};
+use std::os::fd::RawFd;
+use std::sync::Arc;
+
+use crate::core::rhi::TextureFormat;
+use crate::vulkan::rhi::HostVulkanTexture;
+use crate::vulkan::rhi::drm_modifier_probe::fourcc;
+
+use super::VaapiError;
+use super::display::VaDisplay;
+use super::ffi;
+
+// ---------------------------------------------------------------------------
+// Config / context
+// ---------------------------------------------------------------------------
+
+/// A `VAConfigID`, destroyed on drop.
+pub(super) struct VaConfig {
+    display: Arc<VaDisplay>,
+    pub(super) id: ffi::VAConfigID,
+}
+
+impl VaConfig {
+    pub(super) fn new(
+        display: &Arc<VaDisplay>,
+        profile: ffi::VAProfile,
+        entrypoint: ffi::VAEntrypoint,
+        attribs: &mut [ffi::VAConfigAttrib],
+    ) -> Result<Self, VaapiError> {
+        let api = display.api();
+        let mut id = ffi::VA_INVALID_ID;
+        // SAFETY: attribs slice is valid for its length; `id` is written.
+        let status = unsafe {
+            (api.vaCreateConfig)(
+                display.raw(),
+                profile,
+                entrypoint,
+                attribs.as_mut_ptr(),
+                attribs.len() as c_int,
+                &mut id,
+            )
+        };
+        api.check("vaCreateConfig", status)?;
+        Ok(Self {
+            display: Arc::clone(display),
+            id,
+        })
+    }
+}
+
+impl Drop for VaConfig {
+    fn drop(&mut self) {
+        // SAFETY: `id` was created on this display and is destroyed once.
+        unsafe { (self.display.api().vaDestroyConfig)(self.display.raw(), self.id) };
+    }
+}
+
+/// A `VAContextID`, destroyed on drop. Holds its config so the config
+/// outlives the context.
+pub(super) struct VaContext {
+    display: Arc<VaDisplay>,
+    _config: VaConfig,
+    pub(super) id: ffi::VAContextID,
+}
+
+impl VaContext {
+    pub(super) fn new(
+        config: VaConfig,
+        width: u32,
+        height: u32,
+        render_targets: &[ffi::VASurfaceID],
+    ) -> Result<Self, VaapiError> {
+        let display = Arc::clone(&config.display);
+        let api = display.api();
+        let mut targets = render_targets.to_vec();
+        let mut id = ffi::VA_INVALID_ID;
+        // SAFETY: `targets` is a valid surface list for the call's duration.
+        let status = unsafe {
+            (api.vaCreateContext)(
+                display.raw(),
+                config.id,
+                width as c_int,
+                height as c_int,
+                ffi::VA_PROGRESSIVE,
+                if targets.is_empty() {
+                    std::ptr::null_mut()
+                } else {
+                    targets.as_mut_ptr()
+                },
+                targets.len() as c_int,
+                &mut id,
+            )
+        };
+        api.check("vaCreateContext", status)?;
+        Ok(Self {
+            display,
+            _config: config,
+            id,
+        })
+    }
+
+    /// Submit one picture: `vaBeginPicture` on `target`, render every
+    /// buffer, `vaEndPicture`.
+    pub(super) fn render(
+        &self,
+        target: ffi::VASurfaceID,
+        buffers: &[&VaBuffer],
+    ) -> Result<(), VaapiError> {
+        let api = self.display.api();
+        let dpy = self.display.raw();
+        let mut ids: Vec<ffi::VABufferID> = buffers.iter().map(|b| b.id).collect();
+        // SAFETY: context, target and buffers all belong to this display;
+        // `ids` lives across the calls.
+        unsafe {
+            api.check("vaBeginPicture", (api.vaBeginPicture)(dpy, self.id, target))?;
+            let rendered = api.check(
+                "vaRenderPicture",
+                (api.vaRenderPicture)(dpy, self.id, ids.as_mut_ptr(), ids.len() as c_int),
+            );
+            // vaEndPicture even after a render failure, so the context is
+            // not left mid-picture.
+            let ended = api.check("vaEndPicture", (api.vaEndPicture)(dpy, self.id));
+            rendered.and(ended)
+        }
+    }
+}
+
+impl Drop for VaContext {
+    fn drop(&mut self) {
+        // SAFETY: `id` was created on this display and is destroyed once.
+        unsafe { (self.display.api().vaDestroyContext)(self.display.raw(), self.id) };
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Buffers
+// ---------------------------------------------------------------------------
+
+/// A `VABufferID`, destroyed on drop.
+pub(super) struct VaBuffer {
+    display: Arc<VaDisplay>,
+    pub(super) id: ffi::VABufferID,
+}
+
+impl VaBuffer {
+    /// Create a buffer holding a copy of `bytes`, or an uninitialised
+    /// buffer of `size` bytes when `bytes` is `None` (coded buffers).
+    pub(super) fn new_raw(
+        display: &Arc<VaDisplay>,
+        context: ffi::VAContextID,
+        buffer_type: ffi::VABufferType,
+        size: usize,
+        bytes: Option<&[u8]>,
+    ) -> Result<Self, VaapiError> {
+        let api = display.api();
+        let mut id = ffi::VA_INVALID_ID;
+        let data = bytes.map_or(std::ptr::null_mut(), |b| b.as_ptr() as *mut c_void);
+        // SAFETY: libva copies `size` bytes out of `data` (when non-null)
+        // before returning.
+        let status = unsafe {
+            (api.vaCreateBuffer)(
+                display.raw(),
+                context,
+                buffer_type,
+                size as c_uint,
+                1,
+                data,
+                &mut id,
+            )
+        };
+        api.check("vaCreateBuffer", status)?;
+        Ok(Self {
+            display: Arc::clone(display),
+            id,
+        })
+    }
+
+    /// Create a buffer holding one `#[repr(C)]` parameter struct.
+    pub(super) fn new<T: Copy>(
+        display: &Arc<VaDisplay>,
+        context: ffi::VAContextID,
+        buffer_type: ffi::VABufferType,
+        value: &T,
+    ) -> Result<Self, VaapiError> {
+        // SAFETY: `T` is one of the plain-data VA parameter structs.
+        let bytes =
+            unsafe { std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) };
+        Self::new_raw(display, context, buffer_type, bytes.len(), Some(bytes))
+    }
+
+    /// A `VAEncMiscParameterBuffer`: the type header followed by `payload`.
+    pub(super) fn new_misc<T: Copy>(
+        display: &Arc<VaDisplay>,
+        context: ffi::VAContextID,
+        misc_type: u32,
+        payload: &T,
+    ) -> Result<Self, VaapiError> {
+        let mut bytes = misc_type.to_ne_bytes().to_vec();
+        // SAFETY: as in `new`.
+        bytes.extend_from_slice(unsafe {
+            std::slice::from_raw_parts(payload as *const T as *const u8, size_of::<T>())
+        });
+        Self::new_raw(
+            display,
+            context,
+            ffi::VAEncMiscParameterBufferType,
+            bytes.len(),
+            Some(&bytes),
+        )
+    }
+
+    /// Map the buffer, run `f` on the mapping, unmap.
+    pub(super) fn with_mapped<R>(
+        &self,
+        f: impl FnOnce(*mut c_void) -> Result<R, VaapiError>,
+    ) -> Result<R, VaapiError> {
+        let api = self.display.api();
+        let mut ptr: *mut c_void = std::ptr::null_mut();
+        // SAFETY: buffer belongs to this display; unmapped below.
+        api.check("vaMapBuffer", unsafe {
+            (api.vaMapBuffer)(self.display.raw(), self.id, &mut ptr)
+        })?;
+        let result = f(ptr);
+        // SAFETY: balances the map above.
+        unsafe { (api.vaUnmapBuffer)(self.display.raw(), self.id) };
+        result
+    }
+
+    /// Concatenate every segment of a mapped `VAEncCodedBufferType` buffer.
+    pub(super) fn read_coded(&self) -> Result<Vec<u8>, VaapiError> {
+        self.with_mapped(|ptr| {
+            let mut out = Vec::new();
+            let mut segment = ptr as *const ffi::VACodedBufferSegment;
+            // SAFETY: libva returns a NULL-terminated segment list whose
+            // `buf` pointers are valid while the buffer is mapped.
+            unsafe {
+                while !segment.is_null() {
+                    let seg = &*segment;
+                    if !seg.buf.is_null() && seg.size > 0 {
+                        out.extend_from_slice(std::slice::from_raw_parts(
+                            seg.buf as *const u8,
+                            seg.size as usize,
+                        ));
+                    }
+                    segment = seg.next as *const ffi::VACodedBufferSegment;
+                }
+            }
+            Ok(out)
+        })
+    }
+}
+
+impl Drop for VaBuffer {
+    fn drop(&mut self) {
+        // SAFETY: `id` was created on this display and is destroyed once.
+        unsafe { (self.display.api().vaDestroyBuffer)(self.display.raw(), self.id) };
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Surfaces
+// ---------------------------------------------------------------------------
+
+/// A batch of `VASurfaceID`s, destroyed together on drop.
+pub(super) struct VaSurfaces {
+    display: Arc<VaDisplay>,
+    pub(super) ids: Vec<ffi::VASurfaceID>,
+}
+
+impl VaSurfaces {
+    /// Allocate `count` driver-owned surfaces of `fourcc` (NV12 or RGBA).
+    pub(super) fn new(
+        display: &Arc<VaDisplay>,
+        fourcc: u32,
+        width: u32,
+        height: u32,
+        count: usize,
+    ) -> Result<Self, VaapiError> {
+        let rt_format = if fourcc == ffi::VA_FOURCC_NV12 {
+            ffi::VA_RT_FORMAT_YUV420
+        } else {
+            ffi::VA_RT_FORMAT_RGB32
+        };
+        let mut attribs = [ffi::VASurfaceAttrib::integer(
+            ffi::VASurfaceAttribPixelFormat,
+            fourcc as i32,
+        )];
+        Self::create(display, rt_format, width, height, count, &mut attribs)
+    }
+
+    /// Wrap `texture`'s DMA-BUF as a VA surface without copying. The fd
+    /// stays owned by the texture (its cached export), so the surface must
+    /// not outlive it — callers drop the surface at the end of the frame.
+    pub(super) fn import_texture(
+        display: &Arc<VaDisplay>,
+        texture: &HostVulkanTexture,
+    ) -> Result<Self, VaapiError> {
+        let (va_fourcc, drm_fourcc) = match texture.format() {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+                (ffi::VA_FOURCC_RGBA, fourcc::DRM_FORMAT_ABGR8888)
+            }
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+                (ffi::VA_FOURCC_BGRA, fourcc::DRM_FORMAT_ARGB8888)
+            }
+            other => {
+                return Err(VaapiError::Unsupported(format!(
+                    "cannot import {other:?} textures (RGBA8 / BGRA8 only)"
+                )));
+            }
+        };
+        let fd = texture
+            .export_dma_buf_fd()
+            .map_err(|e| VaapiError::Unsupported(format!("texture DMA-BUF export: {e}")))?;
+        let planes = texture.dma_buf_plane_layout().map_err(|e| {
+            VaapiError::Unsupported(format!(
+                "texture has no DMA-BUF plane layout (allocate it as a DMA-BUF \
+                 render target): {e}"
+            ))
+        })?;
+        let &(offset, pitch) = planes
+            .first()
+            .ok_or_else(|| VaapiError::Unsupported("texture reports no planes".into()))?;
+
+        let mut descriptor = ffi::VADRMPRIMESurfaceDescriptor {
+            fourcc: va_fourcc,
+            width: texture.width(),
+            height: texture.height(),
+            num_objects: 1,
+            num_layers: 1,
+            ..Default::default()
+        };
+        descriptor.objects[0] = ffi::VADRMPRIMEObject {
+            fd,
+            size: dma_buf_size(fd, texture.vma_allocation_size())?,
+            drm_format_modifier: texture.chosen_drm_format_modifier(),
+        };
+        descriptor.layers[0].drm_format = drm_fourcc;
+        descriptor.layers[0].num_planes = 1;
+        descriptor.layers[0].object_index[0] = 0;
+        descriptor.layers[0].offset[0] = offset as u32;
+        descriptor.layers[0].pitch[0] = pitch as u32;
+
+        let mut attribs = [
+            ffi::VASurfaceAttrib::integer(
+                ffi::VASurfaceAttribMemoryType,
+                ffi::VA_SURFACE_ATTRIB_MEM_TYPE_DRM_PRIME_2 as i32,
+            ),
+            ffi::VASurfaceAttrib::pointer(
+                ffi::VASurfaceAttribExternalBufferDescriptor,
+                &mut descriptor as *mut _ as *mut c_void,
+            ),
+        ];
+        Self::create(
+            display,
+            ffi::VA_RT_FORMAT_RGB32,
+            texture.width(),
+            texture.height(),
+            1,
+            &mut attribs,
+        )
+    }
+
+    fn create(
+        display: &Arc<VaDisplay>,
+        rt_format: c_uint,
+        width: u32,
+        height: u32,
+        count: usize,
+        attribs: &mut [ffi::VASurfaceAttrib],
+    ) -> Result<Self, VaapiError> {
+        let api = display.api();
+        let mut ids = vec![ffi::VA_INVALID_SURFACE; count];
+        // SAFETY: `ids` holds `count` slots; attribs (and any descriptor
+        // they point at) outlive the call.
+        let status = unsafe {
+            (api.vaCreateSurfaces)(
+                display.raw(),
+                rt_format,
+                width,
+                height,
+                ids.as_mut_ptr(),
+                count as c_uint,
+                attribs.as_mut_ptr(),
+                attribs.len() as c_uint,
+            )
+        };
+        api.check("vaCreateSurfaces", status)?;
+        Ok(Self {
+            display: Arc::clone(display),
+            ids,
+        })
+    }
+
+    /// Block until all work targeting `surface` has finished.
+    pub(super) fn sync(display: &VaDisplay, surface: ffi::VASurfaceID) -> Result<(), VaapiError> {
+        let api = display.api();
+        // SAFETY: surface belongs to this display.
+        api.check("vaSyncSurface", unsafe {
+            (api.vaSyncSurface)(display.raw(), surface)
+        })
+    }
+}
+
+impl Drop for VaSurfaces {
+    fn drop(&mut self) {
+        // SAFETY: the ids were created on this display and are destroyed
+        // once; contexts using them hold their own references and are
+        // dropped first by field order in the owning structs.
+        unsafe {
+            (self.display.api().vaDestroySurfaces)(
+                self.display.raw(),
+                self.ids.as_mut_ptr(),
+                self.ids.len() as c_int,
+            )
+        };
+    }
+}
+
+/// Size of the DMA-BUF behind `fd` (`lseek(SEEK_END)`), falling back to the
+/// exporter's allocation size when the kernel refuses to seek.
+fn dma_buf_size(fd: RawFd, allocation_size: u64) -> Result<u32, VaapiError> {
+    // SAFETY: `fd` is a valid DMA-BUF fd owned by the texture; seeking a
+    // DMA-BUF has no side effects beyond the file offset.
+    let end = unsafe { libc::lseek(fd, 0, libc::SEEK_END) };
+    let size = if end > 0 {
+        // SAFETY: as above; restore the offset for other importers.
+        unsafe { libc::lseek(fd, 0, libc::SEEK_SET) };
+        end as u64
+    } else {
+        allocation_size
+    };
+    u32::try_from(size)
+        .ok()
+        .filter(|&s| s > 0)
+        .ok_or_else(|| VaapiError::Unsupported(format!("DMA-BUF size {size} unusable")))
+}
+
+/// Export `surface` as a composed-layer DMA-BUF descriptor. The returned
+/// fds are owned by the caller.
+#[allow(dead_code)] // Zero-copy decode output for DMA-BUF consumers
+pub(super) fn export_dma_buf(
+    display: &VaDisplay,
+    surface: ffi::VASurfaceID,
+) -> Result<ffi::VADRMPRIMESurfaceDescriptor, VaapiError> {
+    let api = display.api();
+    let mut descriptor = ffi::VADRMPRIMESurfaceDescriptor::default();
+    // SAFETY: descriptor is the struct libva fills for DRM_PRIME_2.
+    api.check("vaExportSurfaceHandle", unsafe {
+        (api.vaExportSurfaceHandle)(
+            display.raw(),
+            surface,
+            ffi::VA_SURFACE_ATTRIB_MEM_TYPE_DRM_PRIME_2,
+            ffi::VA_EXPORT_SURFACE_READ_ONLY | ffi::VA_EXPORT_SURFACE_COMPOSED_LAYERS,
+            &mut descriptor as *mut _ as *mut c_void,
+        )
+    })?;
+    Ok(descriptor)
+}
+
+// ---------------------------------------------------------------------------
+// CPU pixel paths
+// ---------------------------------------------------------------------------
+
+/// A `VAImage` of one format, created once per session and reused for
+/// every upload / readback.
+pub(super) struct VaImage {
+    display: Arc<VaDisplay>,
+    image: ffi::VAImage,
+}
+
+impl VaImage {
+    pub(super) fn new(
+        display: &Arc<VaDisplay>,
+        fourcc: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, VaapiError> {
+        let api = display.api();
+        let mut format = ffi::VAImageFormat {
+            fourcc,
+            byte_order: ffi::VA_LSB_FIRST,
+            bits_per_pixel: if fourcc == ffi::VA_FOURCC_NV12 {
+                12
+            } else {
+                32
+            },
+            ..Default::default()
+        };
+        if fourcc != ffi::VA_FOURCC_NV12 {
+            format.depth = 32;
+            format.red_mask = 0x0000_00ff;
+            format.green_mask = 0x0000_ff00;
+            format.blue_mask = 0x00ff_0000;
+            format.alpha_mask = 0xff00_0000;
+        }
+        let mut image = ffi::VAImage {
+            image_id: ffi::VA_INVALID_ID,
+            buf: ffi::VA_INVALID_ID,
+            ..Default::default()
+        };
+        // SAFETY: format and image are locals; libva fills `image`.
+        api.check("vaCreateImage", unsafe {
+            (api.vaCreateImage)(
+                display.raw(),
+                &mut format,
+                width as c_int,
+                height as c_int,
+                &mut image,
+            )
+        })?;
+        Ok(Self {
+            display: Arc::clone(display),
+            image,
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.image.width as usize
+    }
+
+    fn height(&self) -> usize {
+        self.image.height as usize
+    }
+
+    /// Map the image's backing buffer for the duration of `f`.
+    fn with_mapped<R>(&self, f: impl FnOnce(*mut u8) -> R) -> Result<R, VaapiError> {
+        let api = self.display.api();
+        let mut ptr: *mut c_void = std::ptr::null_mut();
+        // SAFETY: the image buffer belongs to this display; unmapped below.
+        api.check("vaMapBuffer", unsafe {
+            (api.vaMapBuffer)(self.display.raw(), self.image.buf, &mut ptr)
+        })?;
+        let result = f(ptr as *mut u8);
+        // SAFETY: balances the map above.
+        unsafe { (api.vaUnmapBuffer)(self.display.raw(), self.image.buf) };
+        Ok(result)
+    }
+
+    /// Copy tightly packed NV12 (`width * height * 3 / 2`) into the image
+    /// and blit it onto `surface`.
+    pub(super) fn upload_nv12(
+        &self,
+        surface: ffi::VASurfaceID,
+        nv12: &[u8],
+        width: usize,
+        height: usize,
+    ) -> Result<(), VaapiError> {
+        let (pitches, offsets) = (self.image.pitches, self.image.offsets);
+        self.with_mapped(|base| {
+            for row in 0..height {
+                let src = &nv12[row * width..row * width + width];
+                // SAFETY: row < image height and width <= pitch[0].
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        src.as_ptr(),
+                        base.add(offsets[0] as usize + row * pitches[0] as usize),
+                        width,
+                    )
+                };
+            }
+            let uv = &nv12[width * height..];
+            for row in 0..height / 2 {
+                let src = &uv[row * width..row * width + width];
+                // SAFETY: as above, for the interleaved UV plane.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        src.as_ptr(),
+                        base.add(offsets[1] as usize + row * pitches[1] as usize),
+                        width,
+                    )
+                };
+            }
+        })?;
+        let api = self.display.api();
+        let (w, h) = (width as c_uint, height as c_uint);
+        // SAFETY: image and surface belong to this display.
+        api.check("vaPutImage", unsafe {
+            (api.vaPutImage)(
+                self.display.raw(),
+                surface,
+                self.image.image_id,
+                0,
+                0,
+                w,
+                h,
+                0,
+                0,
+                w,
+                h,
+            )
+        })
+    }
+
+    /// Read `width x height` of `surface` back as tightly packed NV12 (or
+    /// RGBA for an RGBA image).
+    pub(super) fn read(
+        &self,
+        surface: ffi::VASurfaceID,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<u8>, VaapiError> {
+        let api = self.display.api();
+        // SAFETY: image and surface belong to this display.
+        api.check("vaGetImage", unsafe {
+            (api.vaGetImage)(
+                self.display.raw(),
+                surface,
+                0,
+                0,
+                self.width() as c_uint,
+                self.height() as c_uint,
+                self.image.image_id,
+            )
+        })?;
+        let (pitches, offsets) = (self.image.pitches, self.image.offsets);
+        let is_nv12 = self.image.format.fourcc == ffi::VA_FOURCC_NV12;
+        self.with_mapped(|base| {
+            let row_bytes = if is_nv12 { width } else { width * 4 };
+            let mut out = Vec::with_capacity(if is_nv12 {
+                width * height * 3 / 2
+            } else {
+                width * height * 4
+            });
+            let mut copy_plane = |plane: usize, rows: usize| {
+                for row in 0..rows {
+                    // SAFETY: rows / row_bytes stay inside the mapped plane
+                    // (the image is at least `width x height`).
+                    let src = unsafe {
+                        std::slice::from_raw_parts(
+                            base.add(offsets[plane] as usize + row * pitches[plane] as usize),
+                            row_bytes,
+                        )
+                    };
+                    out.extend_from_slice(src);
+                }
+            };
+            copy_plane(0, height);
+            if is_nv12 {
+                copy_plane(1, height / 2);
+            }
+            out
+        })
+    }
+}
+
+impl Drop for VaImage {
+    fn drop(&mut self) {
+        // SAFETY: created on this display; destroyed once.
+        unsafe { (self.display.api().vaDestroyImage)(self.display.raw(), self.image.image_id) };
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Video processing (colour conversion)
+// ---------------------------------------------------------------------------
+
+/// A `VAEntrypointVideoProc` context: converts between RGB and NV12 on the
+/// GPU's video-processing engine.
+pub(super) struct VaVideoProcessor {
+    display: Arc<VaDisplay>,
+    context: VaContext,
+}
+
+impl VaVideoProcessor {
+    pub(super) fn new(display: &Arc<VaDisplay>) -> Result<Self, VaapiError> {
+        let mut attribs = [ffi::VAConfigAttrib {
+            type_: ffi::VAConfigAttribRTFormat,
+            value: ffi::VA_RT_FORMAT_YUV420 | ffi::VA_RT_FORMAT_RGB32,
+        }];
+        let config = VaConfig::new(
+            display,
+            ffi::VAProfileNone,
+            ffi::VAEntrypointVideoProc,
+            &mut attribs,
+        )?;
+        let context = VaContext::new(config, 0, 0, &[])?;
+        Ok(Self {
+            display: Arc::clone(display),
+            context,
+        })
+    }
+
+    /// Scale / convert the `src_width x src_height` region of `src` into
+    /// `dst` (whole surface), BT.709 on both sides.
+    pub(super) fn blit(
+        &self,
+        src: ffi::VASurfaceID,
+        src_width: u32,
+        src_height: u32,
+        dst: ffi::VASurfaceID,
+        dst_width: u32,
+        dst_height: u32,
+    ) -> Result<(), VaapiError> {
+        let src_region = ffi::VARectangle {
+            x: 0,
+            y: 0,
+            width: src_width as u16,
+            height: src_height as u16,
+        };
+        let dst_region = ffi::VARectangle {
+            x: 0,
+            y: 0,
+            width: dst_width as u16,
+            height: dst_height as u16,
+        };
+        let params = ffi::VAProcPipelineParameterBuffer {
+            surface: src,
+            surface_region: &src_region,
+            surface_color_standard: ffi::VAProcColorStandardBT709,
+            output_region: &dst_region,
+            output_background_color: 0xff00_0000,
+            output_color_standard: ffi::VAProcColorStandardBT709,
+            ..Default::default()
+        };
+        let buffer = VaBuffer::new(
+            &self.display,
+            self.context.id,
+            ffi::VAProcPipelineParameterBufferType,
+            &params,
+        )?;
+        self.context.render(dst, &[&buffer])?;
+        VaSurfaces::sync(&self.display, dst)
+    }
+}
@@ -7,7 +7,10 @@ use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
 use objc2_metal::{MTLCommandQueue, MTLCreateSystemDefaultDevice, MTLDevice};
 
-use crate::core::rhi::{TextureDescriptor, TextureFormat, TextureUsages};
+use crate::core::rhi::{
+    GpuAdapterInfo, GpuAdapterKind, GpuAdapterPreference, TextureDescriptor, TextureFormat,
+    TextureUsages,
+};
 use crate::core::{Error, Result};
 
 use super::{MetalCommandQueue, MetalTexture};
@@ -28,6 +31,31 @@ impl MetalDevice {
             )
         })?;
 
+        Self::from_device(device)
+    }
+
+    /// Open the adapter `preference` picks from [`Self::enumerate_adapters`].
+    pub fn new_with_adapter(preference: GpuAdapterPreference) -> Result<Self> {
+        use metal::foreign_types::ForeignType;
+
+        let all = metal::Device::all();
+        let kinds: Vec<GpuAdapterKind> = all.iter().map(|d| metal_adapter_kind(d)).collect();
+        let index = preference.pick(&kinds).ok_or_else(|| {
+            Error::GpuError(format!(
+                "No Metal device matches adapter preference '{}' ({} available)",
+                preference,
+                all.len()
+            ))
+        })?;
+        let raw = all[index].as_ptr() as *mut ProtocolObject<dyn MTLDevice>;
+        // SAFETY: `all` holds a +1 reference for the duration of this call;
+        // `retain` takes our own.
+        let device = unsafe { Retained::retain(raw) }
+            .ok_or_else(|| Error::GpuError("Metal device list returned a null device".into()))?;
+        Self::from_device(device)
+    }
+
+    fn from_device(device: Retained<ProtocolObject<dyn MTLDevice>>) -> Result<Self> {
         let command_queue = device
             .newCommandQueue()
             .ok_or_else(|| Error::GpuError("Failed to create Metal command queue".into()))?;
@@ -38,6 +66,36 @@ impl MetalDevice {
         })
     }
 
+    /// Every Metal device on the system, in `MTLCopyAllDevices` order.
+    /// `uuid` carries the IORegistry ID in its low 8 bytes.
+    pub fn enumerate_adapters() -> Vec<GpuAdapterInfo> {
+        metal::Device::all()
+            .iter()
+            .enumerate()
+            .map(|(index, d)| {
+                let mut uuid = [0u8; 16];
+                uuid[..8].copy_from_slice(&d.registry_id().to_le_bytes());
+                GpuAdapterInfo {
+                    index,
+                    name: d.name().to_string(),
+                    kind: metal_adapter_kind(d),
+                    vendor_id: 0,
+                    device_id: 0,
+                    uuid,
+                }
+            })
+            .collect()
+    }
+
+    /// Position of this device in [`Self::enumerate_adapters`] order.
+    pub fn adapter_index(&self) -> usize {
+        let registry_id = self.device_ref().registry_id();
+        metal::Device::all()
+            .iter()
+            .position(|d| d.registry_id() == registry_id)
+            .unwrap_or(0)
+    }
+
     /// Create a texture on this device.
     pub fn create_texture(&self, desc: &TextureDescriptor) -> Result<MetalTexture> {
         use objc2_metal::MTLTextureDescriptor;
@@ -112,6 +170,16 @@ impl MetalDevice {
     }
 }
 
+/// Low-power devices are the integrated GPU on dual-GPU Macs; Apple
+/// silicon reports a single unified-memory device, also integrated.
+fn metal_adapter_kind(device: &metal::DeviceRef) -> GpuAdapterKind {
+    if device.is_low_power() || device.has_unified_memory() {
+        GpuAdapterKind::Integrated
+    } else {
+        GpuAdapterKind::Discrete
+    }
+}
+
 /// Convert RHI TextureFormat to Metal pixel format.
 fn texture_format_to_metal(format: TextureFormat) -> objc2_metal::MTLPixelFormat {
     use objc2_metal::MTLPixelFormat;
@@ -195,6 +195,12 @@ impl VulkanColorConverter {
                     "/color_convert_yuyv_buffer_to_rgba.spv"
                 ))
             }
+            PixelFormat::Bgra32 => {
+                include_bytes!(concat!(
+                    env!("OUT_DIR"),
+                    "/color_convert_bgra_buffer_to_rgba.spv"
+                ))
+            }
             other => {
                 return Err(Error::NotSupported(format!(
                     "color converter buffer-source path: unsupported src format {:?}",
@@ -267,6 +273,7 @@ mod tests {
             PixelFormat::Nv12VideoRange,
             PixelFormat::Nv12FullRange,
             PixelFormat::Yuyv422,
+            PixelFormat::Bgra32,
         ] {
             let conv = VulkanColorConverter::new(&device, src, PixelFormat::Rgba32)
                 .expect("converter construction must succeed");
@@ -785,4 +792,91 @@ mod tests {
             "bt709→srgb",
         );
     }
+
+    /// Packed BGRx with row padding (the PipeWire screencast shape) →
+    /// Rgba8Unorm must swap B/R and skip the pad bytes exactly.
+    ///
+    /// Locks:
+    /// - Byte order of the `uint` unpack (`B` in the low byte).
+    /// - `SourceLayoutInfo::bgra` stride flowing through push constants
+    ///   — the source rows carry 16 bytes of padding that must never be
+    ///   read as pixels.
+    /// - Identity matrix + zero offset from `ColorSpaceKind::Rgb`
+    ///   defaults reducing `convert_color` to a plain byte → unorm
+    ///   scale (sRGB → sRGB bypasses the transfer path).
+    #[cfg_attr(
+        not(feature = "hardware-tests"),
+        ignore = "hardware integration — set --features streamlib/hardware-tests + run with --test-threads=1. See docs/testing-hardware.md"
+    )]
+    #[test]
+    fn bgrx_with_row_padding_swizzles_to_rgba() {
+        let Some(device) = try_device() else { return };
+        let (width, height) = (48u32, 16u32);
+        let stride = width * 4 + 16;
+        let mut bgrx = vec![0xEEu8; (stride * height) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                let off = (y * stride + x * 4) as usize;
+                bgrx[off] = (x * 5) as u8; // B
+                bgrx[off + 1] = (y * 9) as u8; // G
+                bgrx[off + 2] = (200 - x) as u8; // R
+                bgrx[off + 3] = 0; // x — undefined in BGRx
+            }
+        }
+        let pixel_buf =
+            upload_nv12_pixel_buffer(&device, &bgrx, width, height, PixelFormat::Bgra32);
+        let output_texture = allocate_storage_target_in_general(&device, width, height);
+        let converter =
+            VulkanColorConverter::new(&device, PixelFormat::Bgra32, PixelFormat::Rgba32)
+                .expect("converter construction");
+        let info = ResolvedColorInfo {
+            primaries: PrimariesId::Bt709,
+            transfer: TransferId::Srgb,
+            matrix: MatrixId::Identity,
+            range: RangeId::Full,
+        };
+        let kernel = converter
+            .prepare_buffer_to_image_pixel(
+                &pixel_buf,
+                SourceLayoutInfo::bgra(stride),
+                &output_texture,
+                &info,
+                TransferId::Srgb,
+            )
+            .expect("prepare");
+        kernel
+            .dispatch(
+                width.div_ceil(COLOR_CONVERTER_WORKGROUP_SIZE),
+                height.div_ceil(COLOR_CONVERTER_WORKGROUP_SIZE),
+                1,
+            )
+            .expect("dispatch");
+
+        let readback = VulkanTextureReadback::new(
+            &device,
+            &TextureReadbackDescriptor {
+                label: "color-converter-bgra-test-readback",
+                format: TextureFormat::Rgba8Unorm,
+                width,
+                height,
+            },
+        )
+        .expect("readback handle");
+        let ticket = readback
+            .submit(&output_texture, TextureSourceLayout::General)
+            .expect("readback submit");
+        let gpu = readback
+            .wait_and_read(ticket, u64::MAX)
+            .expect("readback wait");
+
+        for y in 0..height {
+            for x in 0..width {
+                let src = (y * stride + x * 4) as usize;
+                let expected = [bgrx[src + 2], bgrx[src + 1], bgrx[src], 255];
+                let off = ((y * width + x) * 4) as usize;
+                let actual = [gpu[off], gpu[off + 1], gpu[off + 2], gpu[off + 3]];
+                assert_eq!(actual, expected, "pixel ({x},{y})");
+            }
+        }
+    }
 }
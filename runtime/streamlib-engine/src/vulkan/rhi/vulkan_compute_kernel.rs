@@ -390,7 +390,23 @@ impl VulkanComputeKernelInner {
     /// silently ignore the default sampler in favor of the
     /// layout-baked one, and a caller passing a non-default sampler
     /// in `texture` would not get it applied).
+    ///
+    /// A [`ComputeBindingKind::SampledImage`] slot (GLSL `texture2D`,
+    /// WGSL `texture_2d`) takes the texture's view alone — the shader
+    /// reads it by integer coordinate, so no sampler is bound.
     pub fn set_sampled_texture(&self, binding: u32, texture: &Texture) -> Result<()> {
+        if self
+            .bindings
+            .iter()
+            .any(|b| b.binding == binding && b.kind == ComputeBindingKind::SampledImage)
+        {
+            let view = vk_image_view_for(texture)?;
+            self.pending
+                .lock()
+                .bindings
+                .insert(binding, BindingResource::SampledImageOnly { view });
+            return Ok(());
+        }
         self.expect_kind(binding, ComputeBindingKind::SampledTexture)?;
         if self.immutable_sampler_bindings.contains(&binding) {
             return Err(Error::GpuError(format!(
@@ -13,13 +13,24 @@ use vulkanalia::prelude::v1_4::*;
 use vulkanalia::vk::{self, KhrSwapchainExtensionDeviceCommands};
 use vulkanalia_vma as vma;
 
-use crate::core::rhi::TextureDescriptor;
+use crate::core::rhi::{GpuAdapterInfo, GpuAdapterKind, GpuAdapterPreference, TextureDescriptor};
 use crate::core::{Error, Result};
 
 #[cfg(target_os = "linux")]
 use super::drm_modifier_probe::{self, DrmModifierTable};
 use super::{HostMarker, HostVulkanTexture, VulkanCommandQueue, VulkanRhiDevice};
 
+/// Map a Vulkan device type onto the backend-neutral [`GpuAdapterKind`].
+fn adapter_kind(device_type: vk::PhysicalDeviceType) -> GpuAdapterKind {
+    match device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => GpuAdapterKind::Discrete,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => GpuAdapterKind::Integrated,
+        vk::PhysicalDeviceType::VIRTUAL_GPU => GpuAdapterKind::Virtual,
+        vk::PhysicalDeviceType::CPU => GpuAdapterKind::Cpu,
+        _ => GpuAdapterKind::Other,
+    }
+}
+
 /// Best-effort hint about which third-party GPU compute libraries are
 /// **available to integrate against this device**. Probed once at
 /// device construction; each field is `true` when the matching vendor
@@ -256,6 +267,9 @@ pub struct HostVulkanDevice {
     /// CUDA device whose `cudaDeviceProp::uuid` equals this value;
     /// using a mismatched device silently fails on the import.
     physical_device_uuid: [u8; 16],
+    /// Position of the opened device in `vkEnumeratePhysicalDevices`
+    /// order — the index [`GpuAdapterPreference::Index`] refers to.
+    adapter_index: usize,
     /// Render-target-capable DRM modifiers per format from the EGL probe at
     /// device init. Empty when libEGL is unavailable or the probe failed.
     /// Callers consult this before requesting
@@ -400,6 +414,13 @@ impl HostVulkanDevice {
     /// and `nvidia-opaque-fd-after-swapchain.md`); pre-warming here
     /// removes that footgun for every consumer at the engine layer.
     pub fn new() -> Result<Arc<Self>> {
+        Self::new_with_adapter(GpuAdapterPreference::resolve(None))
+    }
+
+    /// [`Self::new`] on the physical device `adapter_preference` picks
+    /// instead of the `STREAMLIB_GPU_ADAPTER` default — how the runtime
+    /// opens the extra devices processors are pinned to.
+    pub fn new_with_adapter(adapter_preference: GpuAdapterPreference) -> Result<Arc<Self>> {
         // 1. Load Vulkan entry points via libloading
         let loader = unsafe { LibloadingLoader::new(LIBRARY) }
             .map_err(|e| Error::GpuError(format!("Failed to load Vulkan library: {e}")))?;
@@ -553,15 +574,35 @@ impl HostVulkanDevice {
             return Err(Error::GpuError("No Vulkan devices found".into()));
         }
 
-        // Prefer discrete GPU, fall back to first available
-        let physical_device = physical_devices
+        // Pick by adapter preference (`STREAMLIB_GPU_ADAPTER` unless the
+        // caller pinned one): by default a discrete GPU, falling back to
+        // the first device; `software` opens a CPU rasterizer (lavapipe /
+        // SwiftShader) for GPU-less CI; `index:N` opens the N-th device.
+        let adapter_kinds: Vec<GpuAdapterKind> = physical_devices
             .iter()
-            .find(|&&pd| {
+            .map(|&pd| {
                 let props = unsafe { instance.get_physical_device_properties(pd) };
-                props.device_type == vk::PhysicalDeviceType::DISCRETE_GPU
+                adapter_kind(props.device_type)
             })
-            .copied()
-            .unwrap_or(physical_devices[0]);
+            .collect();
+        let adapter_index = adapter_preference.pick(&adapter_kinds);
+        let physical_device = adapter_index
+            .map(|index| physical_devices[index])
+            .ok_or_else(|| match adapter_preference {
+                GpuAdapterPreference::Software => Error::GpuError(format!(
+                    "{}=software but no software Vulkan device was found (devices: {:?}). \
+                     Install Mesa's lavapipe (mesa-vulkan-drivers) or SwiftShader; if its ICD \
+                     is outside the loader's search path, point VK_DRIVER_FILES at its JSON",
+                    GpuAdapterPreference::ENV_VAR,
+                    adapter_kinds
+                )),
+                _ => Error::GpuError(format!(
+                    "{}={} but no matching Vulkan device was found (devices: {:?})",
+                    GpuAdapterPreference::ENV_VAR,
+                    adapter_preference,
+                    adapter_kinds
+                )),
+            })?;
 
         let device_props = unsafe { instance.get_physical_device_properties(physical_device) };
         let device_name =
@@ -610,9 +651,10 @@ impl HostVulkanDevice {
             _ => "Other",
         };
         tracing::info!(
-            "Selected Vulkan device: {} (type: {})",
+            "Selected Vulkan device: {} (type: {}, adapter preference: {})",
             device_name,
-            device_type_str
+            device_type_str,
+            adapter_preference
         );
 
         // 6. Find graphics queue family
@@ -1391,6 +1433,7 @@ impl HostVulkanDevice {
             #[cfg(target_os = "linux")]
             _opaque_fd_image_export_info: opaque_fd_image_export_info,
             physical_device_uuid,
+            adapter_index: adapter_index.unwrap_or_default(),
             #[cfg(target_os = "linux")]
             drm_modifier_table,
             live_allocation_count: AtomicUsize::new(0),
@@ -3301,6 +3344,73 @@ impl HostVulkanDevice {
         self.physical_device_uuid
     }
 
+    /// Position of this device in enumeration order (see
+    /// [`Self::enumerate_adapters`]).
+    pub fn adapter_index(&self) -> usize {
+        self.adapter_index
+    }
+
+    /// List the physical devices a [`Self::new_with_adapter`] call can
+    /// open, in enumeration order. Uses a throwaway instance with no
+    /// extensions, so it is cheap enough to call before any device exists.
+    pub fn enumerate_adapters() -> Result<Vec<GpuAdapterInfo>> {
+        let loader = unsafe { LibloadingLoader::new(LIBRARY) }
+            .map_err(|e| Error::GpuError(format!("Failed to load Vulkan library: {e}")))?;
+        let entry = unsafe { vulkanalia::Entry::new(loader) }
+            .map_err(|e| Error::GpuError(format!("Failed to load Vulkan: {e}")))?;
+
+        let app_info = vk::ApplicationInfo::builder()
+            .application_name(b"StreamLib\0")
+            .engine_name(b"StreamLib\0")
+            .api_version(vk::make_version(1, 4, 0))
+            .build();
+        #[allow(unused_mut)]
+        let mut instance_extensions: Vec<*const c_char> = Vec::new();
+        #[allow(unused_mut)]
+        let mut flags = vk::InstanceCreateFlags::empty();
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        {
+            instance_extensions.push(c"VK_KHR_portability_enumeration".as_ptr());
+            flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+        }
+        let instance_info = vk::InstanceCreateInfo::builder()
+            .application_info(&app_info)
+            .enabled_extension_names(&instance_extensions)
+            .flags(flags)
+            .build();
+        let instance = unsafe { entry.create_instance(&instance_info, None) }
+            .map_err(|e| Error::GpuError(format!("Failed to create Vulkan instance: {e}")))?;
+
+        let adapters = unsafe { instance.enumerate_physical_devices() }
+            .map_err(|e| Error::GpuError(format!("Failed to enumerate devices: {e}")))
+            .map(|devices| {
+                devices
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &pd)| {
+                        let props = unsafe { instance.get_physical_device_properties(pd) };
+                        let mut id_props = vk::PhysicalDeviceIDProperties::default();
+                        let mut props2 = vk::PhysicalDeviceProperties2::builder()
+                            .push_next(&mut id_props)
+                            .build();
+                        unsafe { instance.get_physical_device_properties2(pd, &mut props2) };
+                        GpuAdapterInfo {
+                            index,
+                            name: unsafe { CStr::from_ptr(props.device_name.as_ptr()) }
+                                .to_string_lossy()
+                                .into_owned(),
+                            kind: adapter_kind(props.device_type),
+                            vendor_id: props.vendor_id,
+                            device_id: props.device_id,
+                            uuid: id_props.device_uuid.into(),
+                        }
+                    })
+                    .collect()
+            });
+        unsafe { instance.destroy_instance(None) };
+        adapters
+    }
+
     /// Render-target-capable DRM format modifiers, by DRM FOURCC, from the
     /// EGL probe at device init. Empty when the probe failed. Callers
     /// pass [`DrmModifierTable::rt_modifiers`] into
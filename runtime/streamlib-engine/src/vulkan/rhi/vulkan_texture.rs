@@ -1190,6 +1190,14 @@ impl HostVulkanTexture {
         vk_dev.allocator().get_allocation_info(*allocation).size as vk::DeviceSize
     }
 
+    /// The `HostVulkanDevice` this texture was allocated from, or `None`
+    /// for placeholders / IOSurface imports. Mirrors
+    /// `HostVulkanBuffer::vulkan_device`: OPAQUE_FD exporters bind the
+    /// CUDA context to this device's `physical_device_uuid()`.
+    pub(crate) fn vulkan_device(&self) -> Option<&Arc<HostVulkanDevice>> {
+        self.vulkan_device.as_ref()
+    }
+
     /// Export the texture's OPAQUE_FD memory as a file descriptor.
     ///
     /// Only valid for textures created via [`Self::new_opaque_fd_export`];
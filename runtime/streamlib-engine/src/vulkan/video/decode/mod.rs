@@ -551,20 +551,7 @@ impl SimpleDecoder {
         match self.config.codec {
             crate::vulkan::video::encode::Codec::H264 => {
                 let parser = self.h264_parser.as_ref()?;
-                let sps = parser.sps.as_ref()?;
-                if !sps.flags.vui_parameters_present_flag {
-                    return None;
-                }
-                let vui = &sps.vui;
-                let primaries = decoded_byte_to_option(vui.colour_primaries as i32);
-                let transfer = decoded_byte_to_option(vui.transfer_characteristics as i32);
-                let matrix = decoded_byte_to_option(vui.matrix_coefficients as i32);
-                let full_range = if vui.video_signal_type_present_flag {
-                    Some(vui.video_full_range_flag)
-                } else {
-                    None
-                };
-                build_color_vui(primaries, transfer, matrix, full_range)
+                h264_sps_color_vui(parser.sps.as_ref()?)
             }
             crate::vulkan::video::encode::Codec::H265 => {
                 let parser = self.h265_parser.as_ref()?;
@@ -593,7 +580,7 @@ impl SimpleDecoder {
     // ------------------------------------------------------------------
 
     /// Find Annex B start codes and split into NAL units (owned version).
-    fn split_nal_units_owned(data: &[u8]) -> Vec<Vec<u8>> {
+    pub(crate) fn split_nal_units_owned(data: &[u8]) -> Vec<Vec<u8>> {
         let mut nals = Vec::new();
         let mut i = 0;
         let mut start: Option<usize> = None;
@@ -684,7 +671,7 @@ impl SimpleDecoder {
     /// H.264/H.265 NAL units use byte-stuffing: any occurrence of
     /// `00 00 03 XX` in the raw stream means the `03` is an escape byte
     /// and should be removed to recover the original RBSP data.
-    fn remove_emulation_prevention_bytes(data: &[u8]) -> Vec<u8> {
+    pub(crate) fn remove_emulation_prevention_bytes(data: &[u8]) -> Vec<u8> {
         let mut out = Vec::with_capacity(data.len());
         let mut i = 0;
         while i < data.len() {
@@ -939,6 +926,27 @@ impl SimpleDecoder {
 // Helpers — H.273 byte → Option<u8>
 // ---------------------------------------------------------------------------
 
+/// H.273 color VUI carried by an H.264 SPS, with the same per-axis
+/// semantics as [`SimpleDecoder::current_color_vui`]. Shared with the VAAPI
+/// decoder, which drives the same parser.
+pub(crate) fn h264_sps_color_vui(
+    sps: &crate::vulkan::video::nv_video_parser::vulkan_h264_decoder::SeqParameterSet,
+) -> Option<crate::vulkan::video::H273ColorVui> {
+    if !sps.flags.vui_parameters_present_flag {
+        return None;
+    }
+    let vui = &sps.vui;
+    let primaries = decoded_byte_to_option(vui.colour_primaries as i32);
+    let transfer = decoded_byte_to_option(vui.transfer_characteristics as i32);
+    let matrix = decoded_byte_to_option(vui.matrix_coefficients as i32);
+    let full_range = if vui.video_signal_type_present_flag {
+        Some(vui.video_full_range_flag)
+    } else {
+        None
+    };
+    build_color_vui(primaries, transfer, matrix, full_range)
+}
+
 /// Map an H.273 byte parsed from the bitstream to `Some(byte)` if it is a
 /// real value, or `None` if it is the H.273 "Unspecified" enumerant
 /// (value `2`) or out-of-range. Returning `None` for `Unspecified` means
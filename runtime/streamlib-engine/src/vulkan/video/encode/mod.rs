@@ -14,7 +14,7 @@ mod gop;
 mod session;
 mod staging;
 mod submit;
-mod vui_patch;
+pub(crate) mod vui_patch;
 
 #[cfg(test)]
 mod tests;
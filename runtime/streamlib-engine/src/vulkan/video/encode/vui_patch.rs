@@ -78,20 +78,20 @@ impl<'a> BitReader<'a> {
 // Bitstream writer
 // ---------------------------------------------------------------------------
 
-struct BitWriter {
+pub(crate) struct BitWriter {
     buf: Vec<u8>,
     bit_pos: usize,
 }
 
 impl BitWriter {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             buf: Vec::new(),
             bit_pos: 0,
         }
     }
 
-    fn put_bit(&mut self, b: u32) {
+    pub(crate) fn put_bit(&mut self, b: u32) {
         let byte_idx = self.bit_pos / 8;
         let bit_idx = 7 - (self.bit_pos % 8);
         if byte_idx >= self.buf.len() {
@@ -103,13 +103,13 @@ impl BitWriter {
         self.bit_pos += 1;
     }
 
-    fn put_bits(&mut self, val: u32, n: u32) {
+    pub(crate) fn put_bits(&mut self, val: u32, n: u32) {
         for i in (0..n).rev() {
             self.put_bit((val >> i) & 1);
         }
     }
 
-    fn put_ue(&mut self, val: u32) {
+    pub(crate) fn put_ue(&mut self, val: u32) {
         if val == 0 {
             self.put_bit(1);
             return;
@@ -122,14 +122,24 @@ impl BitWriter {
         self.put_bits(code, lz + 1);
     }
 
-    fn trailing_bits(&mut self) {
+    pub(crate) fn put_se(&mut self, val: i32) {
+        // se(v) -> codeNum: positive k maps to 2k-1, non-positive k to -2k.
+        let code = if val > 0 {
+            (val as u32) * 2 - 1
+        } else {
+            val.unsigned_abs() * 2
+        };
+        self.put_ue(code);
+    }
+
+    pub(crate) fn trailing_bits(&mut self) {
         self.put_bit(1); // RBSP stop bit
         while self.bit_pos % 8 != 0 {
             self.put_bit(0);
         }
     }
 
-    fn into_bytes(self) -> Vec<u8> {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
         self.buf
     }
 }
@@ -175,7 +185,7 @@ fn remove_epb(data: &[u8]) -> Vec<u8> {
 
 /// Add emulation prevention bytes to RBSP data.
 /// Inserts 03 before `00 00 00`, `00 00 01`, `00 00 02`, `00 00 03`.
-fn add_epb(data: &[u8]) -> Vec<u8> {
+pub(crate) fn add_epb(data: &[u8]) -> Vec<u8> {
     let mut out = Vec::with_capacity(data.len() + data.len() / 64);
     let mut zero_count = 0u32;
     for &b in data {
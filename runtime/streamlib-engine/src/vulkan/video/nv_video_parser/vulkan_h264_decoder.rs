@@ -24,11 +24,11 @@ use super::vulkan_h26x_decoder::SliceType;
 // ---------------------------------------------------------------------------
 
 /// Unused for reference.
-const MARKING_UNUSED: i32 = 0;
+pub const MARKING_UNUSED: i32 = 0;
 /// Used for short-term reference.
-const MARKING_SHORT: i32 = 1;
+pub const MARKING_SHORT: i32 = 1;
 /// Used for long-term reference.
-const MARKING_LONG: i32 = 2;
+pub const MARKING_LONG: i32 = 2;
 /// Sentinel for "infinity" comparisons.
 const INF_MAX: i32 = 0x7fff_ffff;
 
@@ -653,6 +653,15 @@ pub struct SliceHeader {
     pub luma_offset: [[i16; MAX_REFS]; 2],
     pub chroma_weight: [[[i16; 2]; MAX_REFS]; 2],
     pub chroma_offset: [[[i16; 2]; MAX_REFS]; 2],
+    // slice tail (entropy / QP / deblocking)
+    pub cabac_init_idc: i32,
+    pub slice_qp_delta: i32,
+    pub disable_deblocking_filter_idc: i32,
+    pub slice_alpha_c0_offset_div2: i32,
+    pub slice_beta_offset_div2: i32,
+    /// Bits consumed by the header (RBSP, excluding the NAL header byte);
+    /// `slice_data()` starts here.
+    pub header_bits: usize,
     // access_unit_delimiter
     pub primary_pic_type: i32,
     // pic_timing
@@ -726,6 +735,12 @@ impl Default for SliceHeader {
             luma_offset: [[0i16; MAX_REFS]; 2],
             chroma_weight: [[[0i16; 2]; MAX_REFS]; 2],
             chroma_offset: [[[0i16; 2]; MAX_REFS]; 2],
+            cabac_init_idc: 0,
+            slice_qp_delta: 0,
+            disable_deblocking_filter_idc: 0,
+            slice_alpha_c0_offset_div2: 0,
+            slice_beta_offset_div2: 0,
+            header_bits: 0,
             primary_pic_type: -1,
             sei_pic_struct: -1,
             view_id: 0,
@@ -1719,9 +1734,9 @@ impl VulkanH264Decoder {
             && slh.slice_type != SliceType::I
             && slh.slice_type != SliceType::Si
         {
-            reader.ue(); // cabac_init_idc
+            slh.cabac_init_idc = reader.ue();
         }
-        reader.se(); // slice_qp_delta
+        slh.slice_qp_delta = reader.se();
         if slh.slice_type == SliceType::Sp || slh.slice_type == SliceType::Si {
             if slh.slice_type == SliceType::Sp {
                 reader.u(1); // sp_for_switch_flag
@@ -1729,12 +1744,13 @@ impl VulkanH264Decoder {
             reader.se(); // slice_qs_delta
         }
         if pps.flags.deblocking_filter_control_present_flag {
-            if reader.ue() != 1 {
-                // disable_deblocking_filter_idc
-                reader.se(); // slice_alpha_c0_offset_div2
-                reader.se(); // slice_beta_offset_div2
+            slh.disable_deblocking_filter_idc = reader.ue();
+            if slh.disable_deblocking_filter_idc != 1 {
+                slh.slice_alpha_c0_offset_div2 = reader.se();
+                slh.slice_beta_offset_div2 = reader.se();
             }
         }
+        slh.header_bits = reader.consumed_bits();
 
         Some(slh)
     }
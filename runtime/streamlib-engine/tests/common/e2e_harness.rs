@@ -0,0 +1,227 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Shared harness for the hardware-free end-to-end suite.
+//!
+//! Lives under `tests/common/` so cargo's integration-test harness does
+//! *not* compile it as its own test binary. Each e2e test pulls it in with
+//! `#[path = "common/e2e_harness.rs"]` + a `mod` declaration (the
+//! api-server's e2e test reaches it by relative path from its own crate).
+//!
+//! The graphs are built from the two CPU-only test-fixtures processors:
+//! `ToneSourceTestProcessor` (a wall-clock-paced sine) feeding
+//! `AudioProbeTestProcessor` (a file-marker sink). Neither touches a
+//! capture device, a display, or the GPU, so the only host requirement
+//! beyond the build is the Vulkan device `Runner::start` opens — a
+//! software rasterizer (lavapipe) satisfies it on a bare CI runner.
+
+#![allow(dead_code)] // Each test binary uses only a subset of the helper.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use streamlib::sdk::RunnerAutoBuild;
+use streamlib::sdk::descriptors::SchemaIdent;
+use streamlib::sdk::module_ident_any_version;
+use streamlib::sdk::processors::ProcessorSpec;
+use streamlib::sdk::runtime::{BuildPolicy, Runner, Strategy, host_target_triple};
+use streamlib::sdk::schema_ident;
+
+/// Upper bound on every wait in the suite. Generous for a cold, shared CI
+/// runner; a healthy graph reaches each checkpoint in well under a second.
+pub const E2E_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn tone_source_ident() -> SchemaIdent {
+    schema_ident!(
+        "tatolab",
+        "test-fixtures",
+        "ToneSourceTestProcessor",
+        "1.0.0"
+    )
+}
+
+pub fn audio_probe_ident() -> SchemaIdent {
+    schema_ident!(
+        "tatolab",
+        "test-fixtures",
+        "AudioProbeTestProcessor",
+        "1.0.0"
+    )
+}
+
+pub fn tone_source_spec(amplitude: f32) -> ProcessorSpec {
+    ProcessorSpec::new(
+        tone_source_ident(),
+        json!({ "frequency_hz": 440.0, "amplitude": amplitude }),
+    )
+}
+
+pub fn audio_probe_spec(probe: &ProbeFile) -> ProcessorSpec {
+    ProcessorSpec::new(
+        audio_probe_ident(),
+        json!({
+            "output_path": probe.path().to_string_lossy(),
+            "max_frames": 1_000_000u32,
+        }),
+    )
+}
+
+fn copy_dir_contents(src: &Path, dst: &Path) {
+    std::fs::create_dir_all(dst).unwrap();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let entry = entry.unwrap();
+        let dst_entry = dst.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir_contents(&entry.path(), &dst_entry);
+        } else {
+            std::fs::copy(entry.path(), &dst_entry).unwrap();
+        }
+    }
+}
+
+/// Build the `streamlib-test-fixtures` cdylib and stage it (plus its
+/// `@tatolab/core` schema dep) into a temp package tree the runtime can
+/// `add_module_with` under `BuildPolicy::NeverBuild`. Mirrors the staging
+/// recipe every `load_project_dylib_*` test uses.
+pub fn build_and_stage_test_fixtures() -> (tempfile::TempDir, PathBuf) {
+    // Both callers (`runtime/streamlib-engine`, `packages/api-server`) sit
+    // two levels below the workspace root.
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap();
+
+    let status = std::process::Command::new(env!("CARGO"))
+        .args(["build", "-p", "streamlib-test-fixtures"])
+        .status()
+        .expect("invoking cargo build");
+    assert!(
+        status.success(),
+        "cargo build -p streamlib-test-fixtures must succeed"
+    );
+
+    let dylib_ext = if cfg!(target_os = "macos") {
+        "dylib"
+    } else if cfg!(target_os = "windows") {
+        "dll"
+    } else {
+        "so"
+    };
+    let dylib_name = format!("libstreamlib_test_fixtures.{}", dylib_ext);
+    let built_dylib = workspace_root
+        .join("target")
+        .join("debug")
+        .join(&dylib_name);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let fixtures_src = workspace_root.join("packages/test-fixtures");
+    let core_src = workspace_root.join("packages/core");
+    let fixtures_dst = tmp.path().join("test-fixtures");
+    let core_dst = tmp.path().join("core");
+
+    std::fs::create_dir_all(&fixtures_dst).unwrap();
+    std::fs::copy(
+        fixtures_src.join("streamlib.yaml"),
+        fixtures_dst.join("streamlib.yaml"),
+    )
+    .unwrap();
+    copy_dir_contents(&fixtures_src.join("schemas"), &fixtures_dst.join("schemas"));
+
+    std::fs::create_dir_all(&core_dst).unwrap();
+    std::fs::copy(
+        core_src.join("streamlib.yaml"),
+        core_dst.join("streamlib.yaml"),
+    )
+    .unwrap();
+    copy_dir_contents(&core_src.join("schemas"), &core_dst.join("schemas"));
+
+    let triple_dir = fixtures_dst.join("lib").join(host_target_triple());
+    std::fs::create_dir_all(&triple_dir).unwrap();
+    std::fs::copy(&built_dylib, triple_dir.join(&dylib_name)).unwrap();
+
+    (tmp, fixtures_dst)
+}
+
+/// A fresh runtime with the staged test-fixtures package loaded.
+pub fn runner_with_test_fixtures(fixtures_dst: &Path) -> Arc<Runner> {
+    let runtime = Runner::with_auto_build().unwrap();
+    runtime
+        .add_module_with_blocking(
+            module_ident_any_version!("tatolab", "test-fixtures"),
+            Strategy::Path {
+                path: fixtures_dst.to_path_buf(),
+                build: BuildPolicy::NeverBuild,
+            },
+        )
+        .expect("add_module_with must succeed against a real test-fixtures cdylib");
+    runtime
+}
+
+/// The marker file one `AudioProbeTestProcessor` appends to.
+pub struct ProbeFile {
+    path: PathBuf,
+}
+
+impl ProbeFile {
+    pub fn new(dir: &Path, name: &str) -> Self {
+        Self {
+            path: dir.join(format!("{name}.txt")),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every marker line so far. A missing file reads as empty (the probe
+    /// hasn't run its first hook yet).
+    pub fn lines(&self) -> Vec<String> {
+        std::fs::read_to_string(&self.path)
+            .map(|contents| contents.lines().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn count(&self, marker: &str) -> usize {
+        self.lines().iter().filter(|l| *l == marker).count()
+    }
+
+    /// The `peak=` value of every `FRAME:` line, in arrival order.
+    pub fn peaks(&self) -> Vec<f32> {
+        self.lines()
+            .iter()
+            .filter(|l| l.starts_with("FRAME:"))
+            .filter_map(|l| l.split_once(" peak=")?.1.parse().ok())
+            .collect()
+    }
+
+    pub fn frames(&self) -> usize {
+        self.peaks().len()
+    }
+
+    /// Poll until at least `target` frames have been recorded or
+    /// [`E2E_TIMEOUT`] elapses; returns the final observed count.
+    pub fn wait_for_frames(&self, target: usize) -> usize {
+        self.wait_for(|probe| probe.frames() >= target);
+        self.frames()
+    }
+
+    /// Poll until `done` holds or [`E2E_TIMEOUT`] elapses; returns whether
+    /// it held.
+    pub fn wait_for(&self, done: impl Fn(&Self) -> bool) -> bool {
+        let deadline = Instant::now() + E2E_TIMEOUT;
+        while Instant::now() < deadline {
+            if done(self) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        done(self)
+    }
+
+    pub fn dump(&self) -> String {
+        self.lines().join("\n")
+    }
+}
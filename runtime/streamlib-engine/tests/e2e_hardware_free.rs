@@ -0,0 +1,210 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hardware-free end-to-end suite.
+//!
+//! Drives a real runtime through the graph lifecycle with a graph that
+//! needs no camera, microphone, display or GPU work: the test-fixtures
+//! `ToneSourceTestProcessor` feeding one or more `AudioProbeTestProcessor`
+//! sinks (see `common/e2e_harness.rs`). Every assertion reads the probes'
+//! marker files, which are written by cdylib code on the far side of a
+//! real iceoryx2 link — so a pass means frames actually flowed, not just
+//! that the calls returned `Ok`.
+//!
+//! What it locks:
+//! - Compile + start/stop: a linked graph passes topology validation,
+//!   starts, delivers frames, and tears every processor down on stop.
+//! - Live edits on a started runtime: a config update reaches the very
+//!   next frames, and a disconnect → add → connect rewire moves the
+//!   stream to a newly added sink while the old one goes quiet.
+//! - Checkpoints: a snapshot saved from a running graph restores into a
+//!   fresh runtime with its config and links intact and runs.
+//!
+//! The API-server leg of the suite lives in
+//! `packages/api-server/tests/e2e_runtime_api.rs`.
+//!
+//! Runs wherever `Runner::start` can open a Vulkan device — the rig, or a
+//! CI runner with a software rasterizer (`.github/workflows/e2e.yml`).
+
+#[path = "common/e2e_harness.rs"]
+mod e2e_harness;
+
+use e2e_harness::{
+    ProbeFile, audio_probe_spec, build_and_stage_test_fixtures, runner_with_test_fixtures,
+    tone_source_spec,
+};
+use serial_test::serial;
+use streamlib::sdk::graph::{InputLinkPortRef, OutputLinkPortRef};
+use streamlib::sdk::graph_snapshot::GraphSnapshot;
+
+#[test]
+#[serial]
+fn graph_compiles_starts_delivers_and_stops() {
+    let (tmp, fixtures_dst) = build_and_stage_test_fixtures();
+    let probe = ProbeFile::new(tmp.path(), "probe");
+
+    let runtime = runner_with_test_fixtures(&fixtures_dst);
+    let tone = runtime.add_processor(tone_source_spec(0.5)).unwrap();
+    let sink = runtime.add_processor(audio_probe_spec(&probe)).unwrap();
+    runtime
+        .connect(
+            OutputLinkPortRef::new(&tone, "audio"),
+            InputLinkPortRef::new(&sink, "audio"),
+        )
+        .unwrap();
+    runtime
+        .validate_topology()
+        .expect("a fully linked tone → probe graph has no topology diagnostics");
+
+    runtime.start().expect("runtime.start");
+    let frames = probe.wait_for_frames(10);
+    runtime.stop().expect("runtime.stop");
+
+    assert!(
+        frames >= 10,
+        "probe must receive tone frames; got {frames}:\n{}",
+        probe.dump()
+    );
+    assert!(
+        probe.peaks().iter().all(|p| (p - 0.5).abs() < 0.01),
+        "every frame must carry the configured amplitude:\n{}",
+        probe.dump()
+    );
+    assert_eq!(probe.count("SETUP"), 1, "{}", probe.dump());
+    assert_eq!(probe.count("TEARDOWN"), 1, "{}", probe.dump());
+}
+
+#[test]
+#[serial]
+fn live_config_update_and_rewire_on_a_started_runtime() {
+    let (tmp, fixtures_dst) = build_and_stage_test_fixtures();
+    let first = ProbeFile::new(tmp.path(), "first");
+    let second = ProbeFile::new(tmp.path(), "second");
+
+    let runtime = runner_with_test_fixtures(&fixtures_dst);
+    let tone = runtime.add_processor(tone_source_spec(0.5)).unwrap();
+    let first_sink = runtime.add_processor(audio_probe_spec(&first)).unwrap();
+    let link = runtime
+        .connect(
+            OutputLinkPortRef::new(&tone, "audio"),
+            InputLinkPortRef::new(&first_sink, "audio"),
+        )
+        .unwrap();
+    runtime.start().expect("runtime.start");
+    assert!(first.wait_for_frames(5) >= 5, "{}", first.dump());
+
+    // ---- Live config update --------------------------------------------------
+    runtime
+        .update_processor_config(
+            &tone,
+            serde_json::json!({ "frequency_hz": 440.0, "amplitude": 0.25 }),
+        )
+        .unwrap();
+    let updated = first.wait_for(|probe| {
+        probe
+            .peaks()
+            .last()
+            .is_some_and(|peak| (peak - 0.25).abs() < 0.01)
+    });
+    assert!(
+        updated,
+        "frames after a live config update must carry the new amplitude:\n{}",
+        first.dump()
+    );
+
+    // ---- Live rewire ---------------------------------------------------------
+    runtime.disconnect(&link).unwrap();
+    let second_sink = runtime.add_processor(audio_probe_spec(&second)).unwrap();
+    runtime
+        .connect(
+            OutputLinkPortRef::new(&tone, "audio"),
+            InputLinkPortRef::new(&second_sink, "audio"),
+        )
+        .unwrap();
+    assert!(
+        second.wait_for_frames(5) >= 5,
+        "a sink added and linked live must receive frames:\n{}",
+        second.dump()
+    );
+
+    // Frames in flight at the disconnect may still land; after that the
+    // unlinked sink stays quiet while the new one keeps counting.
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    let first_settled = first.frames();
+    let second_settled = second.frames();
+    assert!(second.wait_for_frames(second_settled + 5) >= second_settled + 5);
+    assert_eq!(
+        first.frames(),
+        first_settled,
+        "the disconnected sink must stop receiving frames:\n{}",
+        first.dump()
+    );
+
+    runtime.stop().expect("runtime.stop");
+}
+
+#[test]
+#[serial]
+fn checkpoint_of_a_running_graph_restores_and_runs() {
+    let (tmp, fixtures_dst) = build_and_stage_test_fixtures();
+    let original = ProbeFile::new(tmp.path(), "original");
+
+    let runtime = runner_with_test_fixtures(&fixtures_dst);
+    runtime.set_pipeline_name(Some("e2e-checkpoint".to_string()));
+    let tone = runtime.add_processor(tone_source_spec(0.5)).unwrap();
+    let sink = runtime.add_processor(audio_probe_spec(&original)).unwrap();
+    runtime
+        .connect(
+            OutputLinkPortRef::new(&tone, "audio"),
+            InputLinkPortRef::new(&sink, "audio"),
+        )
+        .unwrap();
+    runtime.start().expect("runtime.start");
+    assert!(original.wait_for_frames(5) >= 5, "{}", original.dump());
+    runtime
+        .update_processor_config(
+            &tone,
+            serde_json::json!({ "frequency_hz": 440.0, "amplitude": 0.75 }),
+        )
+        .unwrap();
+
+    let checkpoint = runtime
+        .save_graph_snapshot()
+        .expect("a running graph must snapshot");
+    runtime.stop().expect("runtime.stop");
+    let json = checkpoint.to_json_string().unwrap();
+
+    // Point the restored probe at a fresh file so its markers can't be
+    // confused with the original run's.
+    let restored_probe = ProbeFile::new(tmp.path(), "restored");
+    let mut restored = GraphSnapshot::from_json_str(&json).unwrap();
+    assert_eq!(restored.name.as_deref(), Some("e2e-checkpoint"));
+    assert_eq!(restored.connections.len(), 1);
+    for processor in &mut restored.processors {
+        if processor.config.get("output_path").is_some() {
+            processor.config["output_path"] = restored_probe.path().to_string_lossy().into();
+        }
+    }
+
+    let runtime = runner_with_test_fixtures(&fixtures_dst);
+    runtime
+        .load_graph_snapshot(&restored)
+        .expect("the checkpoint must load into a fresh runtime");
+    runtime.start().expect("restored runtime.start");
+    let frames = restored_probe.wait_for_frames(5);
+    runtime.stop().expect("restored runtime.stop");
+
+    assert!(
+        frames >= 5,
+        "the restored graph must run; got {frames}:\n{}",
+        restored_probe.dump()
+    );
+    assert!(
+        restored_probe
+            .peaks()
+            .iter()
+            .all(|p| (p - 0.75).abs() < 0.01),
+        "the restored tone must keep the config live-updated before the checkpoint:\n{}",
+        restored_probe.dump()
+    );
+}
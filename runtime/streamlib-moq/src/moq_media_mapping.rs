@@ -0,0 +1,165 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Media → MoQ object mapping.
+//!
+//! A MoQ group is the unit a subscriber can join at, so it has to start
+//! with something decodable on its own:
+//!
+//! - **Video**: one group per GOP. A keyframe opens a group; the frames
+//!   that follow it are objects in that group. Frames before the first
+//!   keyframe are dropped — no subscriber could decode them.
+//! - **Audio**: every audio frame decodes on its own, so groups are cut
+//!   purely for join latency: a new group every `frames_per_group`
+//!   frames (`1` = one group per frame).
+
+/// Where a frame goes on its track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoqObjectPlacement {
+    /// Open a new group and write the frame as its first object.
+    NewGroup,
+    /// Append the frame to the track's current group.
+    CurrentGroup,
+    /// Don't publish the frame.
+    Drop,
+}
+
+impl MoqObjectPlacement {
+    /// Whether the frame starts a group — the `is_keyframe` argument of
+    /// [`crate::MoqPublishSession::publish_frame`].
+    pub fn starts_group(self) -> bool {
+        matches!(self, Self::NewGroup)
+    }
+}
+
+/// Group boundaries for one video track: a group per GOP.
+#[derive(Debug, Default)]
+pub struct MoqVideoGroupMapper {
+    seen_keyframe: bool,
+}
+
+impl MoqVideoGroupMapper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Placement for the next encoded video frame.
+    pub fn place(&mut self, is_keyframe: bool) -> MoqObjectPlacement {
+        if is_keyframe {
+            self.seen_keyframe = true;
+            MoqObjectPlacement::NewGroup
+        } else if self.seen_keyframe {
+            MoqObjectPlacement::CurrentGroup
+        } else {
+            MoqObjectPlacement::Drop
+        }
+    }
+
+    /// Forget the current GOP — after a failed write, or when the
+    /// session is replaced. Frames are dropped until the next keyframe.
+    pub fn reset(&mut self) {
+        self.seen_keyframe = false;
+    }
+}
+
+/// Group boundaries for one audio track: a group every
+/// `frames_per_group` frames.
+#[derive(Debug)]
+pub struct MoqAudioGroupMapper {
+    frames_per_group: u32,
+    frames_in_group: u32,
+}
+
+impl MoqAudioGroupMapper {
+    /// `frames_per_group` is clamped to at least 1.
+    pub fn new(frames_per_group: u32) -> Self {
+        Self {
+            frames_per_group: frames_per_group.max(1),
+            frames_in_group: 0,
+        }
+    }
+
+    /// Placement for the next encoded audio frame.
+    pub fn place(&mut self) -> MoqObjectPlacement {
+        if self.frames_in_group == 0 || self.frames_in_group >= self.frames_per_group {
+            self.frames_in_group = 1;
+            MoqObjectPlacement::NewGroup
+        } else {
+            self.frames_in_group += 1;
+            MoqObjectPlacement::CurrentGroup
+        }
+    }
+
+    /// Start a fresh group with the next frame.
+    pub fn reset(&mut self) {
+        self.frames_in_group = 0;
+    }
+
+    pub fn frames_per_group(&self) -> u32 {
+        self.frames_per_group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MoqObjectPlacement::*;
+
+    #[test]
+    fn video_groups_follow_gops_and_drop_the_leading_p_frames() {
+        let mut mapper = MoqVideoGroupMapper::new();
+        let placements: Vec<_> = [false, false, true, false, false, true, false]
+            .into_iter()
+            .map(|key| mapper.place(key))
+            .collect();
+        assert_eq!(
+            placements,
+            [
+                Drop,
+                Drop,
+                NewGroup,
+                CurrentGroup,
+                CurrentGroup,
+                NewGroup,
+                CurrentGroup
+            ]
+        );
+    }
+
+    #[test]
+    fn video_reset_waits_for_the_next_keyframe() {
+        let mut mapper = MoqVideoGroupMapper::new();
+        assert_eq!(mapper.place(true), NewGroup);
+        mapper.reset();
+        assert_eq!(mapper.place(false), Drop);
+        assert_eq!(mapper.place(true), NewGroup);
+    }
+
+    #[test]
+    fn audio_groups_are_cut_every_n_frames() {
+        let mut mapper = MoqAudioGroupMapper::new(3);
+        let placements: Vec<_> = (0..7).map(|_| mapper.place()).collect();
+        assert_eq!(
+            placements,
+            [
+                NewGroup,
+                CurrentGroup,
+                CurrentGroup,
+                NewGroup,
+                CurrentGroup,
+                CurrentGroup,
+                NewGroup
+            ]
+        );
+    }
+
+    #[test]
+    fn audio_group_size_is_at_least_one_frame() {
+        let mut mapper = MoqAudioGroupMapper::new(0);
+        assert_eq!(mapper.frames_per_group(), 1);
+        assert_eq!(mapper.place(), NewGroup);
+        assert_eq!(mapper.place(), NewGroup);
+        mapper.reset();
+        assert_eq!(mapper.place(), NewGroup);
+    }
+}
@@ -442,6 +442,32 @@ pub const PLUGIN_ABI_LAYOUT_FINGERPRINT: u64 = {
 pub const PUBSUB_CONTROL_TOPIC_RUNTIME_SHUTDOWN_REQUEST: &str =
     "control:runtime-shutdown-request";
 
+/// Reserved control topic for [`HostServices::pubsub_publish`]: a
+/// plugin driving hardware with a reference input (SDI genlock, word
+/// clock) reports that reference's lock state to the host's clock
+/// subsystem. The payload is a msgpack-named map
+/// `{ source: string, locked: bool, mode: string | nil }` — `source`
+/// names the device (stable across reports), `mode` the detected
+/// reference format when locked. The host keeps the latest report per
+/// source and never re-publishes the bytes on this topic.
+///
+/// Locked by a constant-value test in this crate, like
+/// [`PUBSUB_CONTROL_TOPIC_RUNTIME_SHUTDOWN_REQUEST`].
+pub const PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS: &str = "control:clock-reference-status";
+
+/// Reserved control topic for [`HostServices::pubsub_publish`]: a plugin
+/// processor publishes a named, JSON-shaped component onto its own graph
+/// node, alongside the host's built-in `metrics` / `state` components. The
+/// payload is a msgpack-named map
+/// `{ processor_id: string, key: string, value: any }` — each report
+/// replaces the previous value under `key`, and a key the host already
+/// populates is never overridden. The host drops the node's reported
+/// components when the processor is removed.
+///
+/// Locked by a constant-value test in this crate, like
+/// [`PUBSUB_CONTROL_TOPIC_RUNTIME_SHUTDOWN_REQUEST`].
+pub const PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT: &str = "control:processor-component";
+
 // =============================================================================
 // HostServices — the callback table
 // =============================================================================
@@ -931,9 +957,14 @@ unsafe impl Sync for PluginDeclaration {}
 /// export_plugin!(MyProcessor::Processor);
 /// export_plugin!(ProcessorA::Processor, ProcessorB::Processor);
 /// ```
+///
+/// Every processor after the first may carry `#[cfg(..)]` attributes, so a
+/// platform-only processor joins the one list instead of a second, cfg'd
+/// copy of it. The first processor installs the host services and must be
+/// unconditional.
 #[macro_export]
 macro_rules! export_plugin {
-    ($first:ty $(, $rest:ty)* $(,)?) => {
+    ($first:ty $(, $(#[$rest_attr:meta])* $rest:ty)* $(,)?) => {
         /// Generated by `streamlib_plugin_abi::export_plugin!`.
         ///
         /// # Safety
@@ -971,6 +1002,7 @@ macro_rules! export_plugin {
                 };
                 <$first>::__streamlib_register(&helper);
                 $(
+                    $(#[$rest_attr])*
                     <$rest>::__streamlib_register(&helper);
                 )*
             });
@@ -1057,7 +1089,9 @@ mod layout_tests {
         // v2: shared-Rust-type iceoryx2 slots replaced by
         // `set_iceoryx2_resources` (issue #894).
         assert_eq!(PROCESSOR_VTABLE_LAYOUT_VERSION, 2);
-        assert_eq!(RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION, 1);
+        // v2: appended `assets_fetch`. v3: appended `fonts_resolve`.
+        // v4: appended `gpu_limited_access_for_adapter`.
+        assert_eq!(RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION, 4);
         assert_eq!(AUDIO_CLOCK_VTABLE_LAYOUT_VERSION, 1);
         // v3: added register-from-source slots
         // (`register_processor_source` / `replace_processor`); v2
@@ -1065,7 +1099,8 @@ mod layout_tests {
         // (`clone_handle` / `drop_handle`).
         assert_eq!(RUNTIME_OPS_VTABLE_LAYOUT_VERSION, 3);
         // v15: #1270 removed the v12–v14 video-source-timeline slots.
-        assert_eq!(GPU_CONTEXT_LIMITED_ACCESS_VTABLE_LAYOUT_VERSION, 15);
+        // v16: appended the TextureRegistration producer-sync slots.
+        assert_eq!(GPU_CONTEXT_LIMITED_ACCESS_VTABLE_LAYOUT_VERSION, 16);
         // SurfaceStore stays at v1 for the entire M32 milestone — #1260
         // and #1262 both re-bless existing slots (no new SurfaceStore
         // slot).
@@ -1257,4 +1292,26 @@ mod control_topic_tests {
             "control:runtime-shutdown-request"
         );
     }
+
+    /// Same contract for the reference-status topic the host matches in
+    /// `host_pubsub_publish` and the SDK's `report_reference_signal`
+    /// publishes to.
+    #[test]
+    fn clock_reference_status_control_topic_value_is_locked() {
+        assert_eq!(
+            PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS,
+            "control:clock-reference-status"
+        );
+    }
+
+    /// Same contract for the processor-component topic the host matches
+    /// in `host_pubsub_publish` and the SDK's `report_processor_component`
+    /// publishes to.
+    #[test]
+    fn processor_component_control_topic_value_is_locked() {
+        assert_eq!(
+            PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT,
+            "control:processor-component"
+        );
+    }
 }
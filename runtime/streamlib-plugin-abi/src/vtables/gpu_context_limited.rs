@@ -91,7 +91,19 @@ use core::ffi::c_void;
 ///   `HostVulkanTimelineSemaphore` transit surface on this vtable. No
 ///   engine-free plugin can name the type, and the tail-truncation
 ///   leaves every kept slot at its prior offset. **ABI-breaking**.
-pub const GPU_CONTEXT_LIMITED_ACCESS_VTABLE_LAYOUT_VERSION: u32 = 15;
+/// - v16: adds the producer-sync trio on `TextureRegistration` —
+///   `texture_registration_set_producer_timeline`,
+///   `texture_registration_producer_timeline`,
+///   `texture_registration_wait_producer`. Async-compute producers
+///   publish the timeline value their submit signals so consumers on
+///   another queue wait for the write instead of relying on implicit
+///   driver ordering. The timeline crosses as the exportable-timeline
+///   `(handle, methods)` PluginAbiObject, not a raw
+///   `HostVulkanTimelineSemaphore`, so v15's no-raw-transit rule holds.
+///   Timeline publish/read are Linux-only on the host side;
+///   `wait_producer` also covers macOS hosts, whose registrations carry
+///   an `MTLSharedEvent` published host-side. Append-only.
+pub const GPU_CONTEXT_LIMITED_ACCESS_VTABLE_LAYOUT_VERSION: u32 = 16;
 
 /// Dispatch table for the host's `GpuContextLimitedAccess`. The
 /// cdylib obtains a handle via
@@ -785,6 +797,49 @@ pub struct GpuContextLimitedAccessVTable {
     ///
     /// Calling with a null `texture_handle` returns `-1` (no panic).
     pub texture_native_dma_buf_fd: unsafe extern "C" fn(texture_handle: *const c_void) -> i64,
+
+    // -------------------------------------------------------------------------
+    // TextureRegistration producer sync (v16)
+    // -------------------------------------------------------------------------
+    /// Publish the producer's completion point on a registration:
+    /// `timeline_handle` is the borrowed
+    /// `Arc::into_raw(Arc<HostVulkanTimelineSemaphore>)` handle of an
+    /// exportable-timeline PluginAbiObject and `value` the counter value
+    /// the producer's submit signals. The host clones the Arc into the
+    /// registration; the caller keeps its own reference. A null
+    /// `timeline_handle` clears the published point.
+    ///
+    /// Linux-only behaviour; non-Linux hosts treat this as a no-op.
+    /// Null `handle` is a no-op.
+    pub texture_registration_set_producer_timeline:
+        unsafe extern "C" fn(handle: *const c_void, timeline_handle: *const c_void, value: u64),
+
+    /// Read the producer's published timeline. On hit writes an owned
+    /// exportable-timeline `(handle, methods)` value into
+    /// `*out_timeline` (caller-allocated storage of that shape; the
+    /// caller's Drop releases the strong count) plus the signal value
+    /// into `*out_value`, and returns `1`. Returns `0` and leaves both
+    /// out-params untouched when no timeline is published, on null
+    /// arguments, and unconditionally on non-Linux hosts.
+    pub texture_registration_producer_timeline: unsafe extern "C" fn(
+        handle: *const c_void,
+        out_timeline: *mut c_void,
+        out_value: *mut u64,
+    ) -> i32,
+
+    /// CPU-block until the registration's published producer point is
+    /// reached (`timeout_ns == u64::MAX` waits with no timeout). Returns
+    /// `0` when reached or when no point is published; non-zero with
+    /// `err_buf` populated on timeout / driver failure. Covers Vulkan
+    /// timelines on Linux and `MTLSharedEvent`s on macOS. Null `handle`
+    /// returns `0`.
+    pub texture_registration_wait_producer: unsafe extern "C" fn(
+        handle: *const c_void,
+        timeout_ns: u64,
+        err_buf: *mut u8,
+        err_buf_cap: usize,
+        err_len: *mut usize,
+    ) -> i32,
 }
 
 unsafe impl Send for GpuContextLimitedAccessVTable {}
@@ -797,9 +852,9 @@ mod tests {
 
     #[test]
     fn gpu_context_limited_access_vtable_layout() {
-        // layout_version (u32) + _reserved_padding (u32) + 53 fn
-        // pointers (8 bytes each) = 4 + 4 + 424 = 432 bytes, align = 8.
-        assert_eq!(size_of::<GpuContextLimitedAccessVTable>(), 432);
+        // layout_version (u32) + _reserved_padding (u32) + 56 fn
+        // pointers (8 bytes each) = 4 + 4 + 448 = 456 bytes, align = 8.
+        assert_eq!(size_of::<GpuContextLimitedAccessVTable>(), 456);
         assert_eq!(align_of::<GpuContextLimitedAccessVTable>(), 8);
         assert_eq!(offset_of!(GpuContextLimitedAccessVTable, layout_version), 0);
         assert_eq!(
@@ -1021,11 +1076,33 @@ mod tests {
             408
         );
         assert_eq!(offset_of!(GpuContextLimitedAccessVTable, escalate_end), 416);
-        // Phase F entry (#908 / #957) — now the tail slot after the
-        // v15 removal of the v12–v14 video-source-timeline slots.
+        // Phase F entry (#908 / #957) — kept at its offset by the v15
+        // tail-truncation of the v12–v14 video-source-timeline slots.
         assert_eq!(
             offset_of!(GpuContextLimitedAccessVTable, texture_native_dma_buf_fd),
             424
         );
+        // v16 producer-sync entries.
+        assert_eq!(
+            offset_of!(
+                GpuContextLimitedAccessVTable,
+                texture_registration_set_producer_timeline
+            ),
+            432
+        );
+        assert_eq!(
+            offset_of!(
+                GpuContextLimitedAccessVTable,
+                texture_registration_producer_timeline
+            ),
+            440
+        );
+        assert_eq!(
+            offset_of!(
+                GpuContextLimitedAccessVTable,
+                texture_registration_wait_producer
+            ),
+            448
+        );
     }
 }
@@ -7,7 +7,56 @@ use core::ffi::c_void;
 
 /// Layout version of [`crate::RuntimeContextVTable`]. Pinned at offset 0;
 /// newer fields append to the end and bump this constant.
-pub const RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION: u32 = 1;
+///
+/// - v1: identifier accessors, lifecycle flags, GPU context handles,
+///   audio-clock / runtime-ops handles.
+/// - v2: appended `assets_fetch` — resolve a URL through the host's
+///   content-addressed asset cache, downloading on a miss.
+/// - v3: appended `fonts_resolve` — resolve a logical font name to its
+///   fallback chain of font files through the host's font registry.
+/// - v4: appended `gpu_limited_access_for_adapter` — a limited-access GPU
+///   handle on another adapter, for processors that copy frames between
+///   devices.
+pub const RUNTIME_CONTEXT_VTABLE_LAYOUT_VERSION: u32 = 4;
+
+/// [`AssetFetchCompletionCallback`] status: the payload is the cached
+/// file's path as UTF-8.
+pub const ASSET_FETCH_OK: i32 = 0;
+/// [`AssetFetchCompletionCallback`] status: the download or checksum
+/// check failed; the payload is a UTF-8 error message.
+pub const ASSET_FETCH_FAILED: i32 = 1;
+/// [`AssetFetchCompletionCallback`] status: the asset is not cached and the
+/// host's asset manager is offline; the payload is a UTF-8 error message.
+pub const ASSET_FETCH_OFFLINE: i32 = 2;
+
+/// Completion callback for [`RuntimeContextVTable::assets_fetch`]. Same
+/// shape as [`crate::RuntimeOpCompletionCallback`]; `status` is one of the
+/// `ASSET_FETCH_*` constants. The pointed-at bytes are valid only for the
+/// duration of the callback invocation.
+pub type AssetFetchCompletionCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    status: i32,
+    result_ptr: *const u8,
+    result_len: usize,
+);
+
+/// [`FontResolveCompletionCallback`] status: the payload is the fallback
+/// chain, one `"<face_index>\t<path>"` line per face, UTF-8.
+pub const FONT_RESOLVE_OK: i32 = 0;
+/// [`FontResolveCompletionCallback`] status: nothing in the name's chain
+/// resolved; the payload is a UTF-8 error message.
+pub const FONT_RESOLVE_NOT_FOUND: i32 = 1;
+
+/// Completion callback for [`RuntimeContextVTable::fonts_resolve`]. Same
+/// shape as [`AssetFetchCompletionCallback`]; `status` is one of the
+/// `FONT_RESOLVE_*` constants. The pointed-at bytes are valid only for the
+/// duration of the callback invocation.
+pub type FontResolveCompletionCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    status: i32,
+    result_ptr: *const u8,
+    result_len: usize,
+);
 
 /// Dispatch table the cdylib's `RuntimeContext{Full,Limited}Access`
 /// shim uses to read host-owned runtime context state. Every accessor
@@ -101,6 +150,55 @@ pub struct RuntimeContextVTable {
     /// methods. The handle remains valid for the lifetime of the
     /// runtime.
     pub runtime_ops_handle: unsafe extern "C" fn(ctx: *const c_void) -> *const c_void,
+
+    // -------------------------------------------------------------------------
+    // Assets (v2)
+    // -------------------------------------------------------------------------
+    /// Resolve `url` (UTF-8) to a file in the host's asset cache,
+    /// downloading it on a miss. `sha256_len == 0` fetches unpinned;
+    /// otherwise `sha256` is the expected hex digest. Blocks for the
+    /// download and invokes `completion` exactly once before returning.
+    pub assets_fetch: unsafe extern "C" fn(
+        ctx: *const c_void,
+        url_ptr: *const u8,
+        url_len: usize,
+        sha256_ptr: *const u8,
+        sha256_len: usize,
+        completion: AssetFetchCompletionCallback,
+        user_data: *mut c_void,
+    ),
+
+    // -------------------------------------------------------------------------
+    // Fonts (v3)
+    // -------------------------------------------------------------------------
+    /// Resolve logical font `name` (UTF-8) to its fallback chain through
+    /// the host's font registry; URL sources in the chain may download.
+    /// Invokes `completion` exactly once before returning.
+    pub fonts_resolve: unsafe extern "C" fn(
+        ctx: *const c_void,
+        name_ptr: *const u8,
+        name_len: usize,
+        completion: FontResolveCompletionCallback,
+        user_data: *mut c_void,
+    ),
+
+    // -------------------------------------------------------------------------
+    // Multi-GPU (v4)
+    // -------------------------------------------------------------------------
+    /// Write an owned limited-access GPU handle for the adapter at
+    /// `adapter_index` to `*out_handle`, opening the host's context on that
+    /// adapter if needed. The handle pairs with
+    /// [`crate::HostServices::gpu_context_limited_access_vtable`] and is
+    /// released with its `drop_handle`. Returns `0` on success; non-zero
+    /// writes a UTF-8 message into `err_buf`.
+    pub gpu_limited_access_for_adapter: unsafe extern "C" fn(
+        ctx: *const c_void,
+        adapter_index: u32,
+        out_handle: *mut *const c_void,
+        err_buf: *mut u8,
+        err_buf_cap: usize,
+        err_len: *mut usize,
+    ) -> i32,
 }
 
 // Safety: every field is a primitive or a fn pointer. The vtable's
@@ -116,9 +214,9 @@ mod tests {
 
     #[test]
     fn runtime_context_vtable_layout() {
-        // layout_version (u32) + _reserved_padding (u32) + 8 fn pointers (8 bytes each)
-        // = 4 + 4 + 8*8 = 72 bytes
-        assert_eq!(size_of::<RuntimeContextVTable>(), 72);
+        // layout_version (u32) + _reserved_padding (u32) + 11 fn pointers (8 bytes each)
+        // = 4 + 4 + 11*8 = 96 bytes
+        assert_eq!(size_of::<RuntimeContextVTable>(), 96);
         assert_eq!(align_of::<RuntimeContextVTable>(), 8);
         assert_eq!(offset_of!(RuntimeContextVTable, layout_version), 0);
         assert_eq!(offset_of!(RuntimeContextVTable, _reserved_padding), 4);
@@ -130,5 +228,11 @@ mod tests {
         assert_eq!(offset_of!(RuntimeContextVTable, gpu_limited_access), 48);
         assert_eq!(offset_of!(RuntimeContextVTable, audio_clock_handle), 56);
         assert_eq!(offset_of!(RuntimeContextVTable, runtime_ops_handle), 64);
+        assert_eq!(offset_of!(RuntimeContextVTable, assets_fetch), 72);
+        assert_eq!(offset_of!(RuntimeContextVTable, fonts_resolve), 80);
+        assert_eq!(
+            offset_of!(RuntimeContextVTable, gpu_limited_access_for_adapter),
+            88
+        );
     }
 }
@@ -17,8 +17,10 @@ use std::path::PathBuf;
 use anyhow::Result;
 use clap::Parser;
 use streamlib::sdk::RunnerAutoBuild;
+use streamlib::sdk::chaos::ChaosMode;
 use streamlib::sdk::processor_type_ref;
-use streamlib::sdk::processors::{PROCESSOR_REGISTRY, ProcessorSpec};
+use streamlib::sdk::processors::ProcessorSpec;
+use streamlib::sdk::pubsub::{DEFAULT_EVENT_JOURNAL_CAPACITY, EventJournalConfig, PUBSUB};
 use streamlib::sdk::runtime::Runner;
 
 #[derive(Parser)]
@@ -41,6 +43,32 @@ struct Args {
     /// Pipeline graph snapshot to load (JSON)
     #[arg(long = "snapshot", value_name = "PATH")]
     snapshot: Option<PathBuf>,
+
+    /// Push this runtime's telemetry to a collector: a `TelemetryUplink`
+    /// config (JSON) for an instance added at boot
+    #[arg(long = "telemetry", value_name = "PATH")]
+    telemetry: Option<PathBuf>,
+
+    /// Accept signed commands from a fleet controller: a `ControllerAgent`
+    /// config (JSON) for an instance added at boot
+    #[arg(long = "controller", value_name = "PATH")]
+    controller: Option<PathBuf>,
+
+    /// Run in chaos mode: a `ChaosMode` (JSON) listing the faults to draw
+    /// from and how often. Faults can also be injected through the API
+    #[arg(long = "chaos", value_name = "PATH")]
+    chaos: Option<PathBuf>,
+
+    /// Journal runtime events for replay (`GET /api/events`, `/ws/events`
+    /// with `since_ns`), persisted as JSON lines to this file so replay
+    /// reaches back across restarts
+    #[arg(long = "event-journal", value_name = "PATH")]
+    event_journal: Option<PathBuf>,
+
+    /// Events the journal keeps for replay; on its own, journals in memory
+    /// only
+    #[arg(long = "event-journal-capacity", value_name = "EVENTS")]
+    event_journal_capacity: Option<usize>,
 }
 
 fn main() -> Result<()> {
@@ -67,12 +95,27 @@ async fn run(args: Args) -> Result<()> {
     // processor / schema arrives through the all-dynamic module loader.
     let runtime = Runner::with_auto_build()?;
 
+    // Journal from the start, so a replay covers the graph being built.
+    if args.event_journal.is_some() || args.event_journal_capacity.is_some() {
+        PUBSUB.enable_journal(EventJournalConfig {
+            capacity: args
+                .event_journal_capacity
+                .unwrap_or(DEFAULT_EVENT_JOURNAL_CAPACITY),
+            path: args.event_journal.clone(),
+        })?;
+    }
+
     // Seed the core module set. The API server is the always-present
     // control plane — a host, not a loadable plugin — so it is statically
     // linked into this binary and registered in-process on the shared
     // `PROCESSOR_REGISTRY`. This registers the `ApiServer` processor type;
-    // the instance is added below.
-    PROCESSOR_REGISTRY.register::<streamlib_api_server::ApiServerProcessor::Processor>();
+    // the instance is added below. `WebhookNotifier`, `TelemetryUplink`,
+    // `ControllerAgent`, `OscServer`, `OscSender`, `Timeline` and (with the
+    // `grpc` / `whep` features) `GrpcApi` and `WhepSink` ship in the same
+    // host-side package (they talk to the runtime over pubsub and
+    // `RuntimeOperations`) and are registered alongside it so graphs can add
+    // them by type.
+    streamlib_api_server::register_processors();
 
     let log_path = runtime
         .jsonl_log_path()
@@ -92,6 +135,26 @@ async fn run(args: Args) -> Result<()> {
         serde_json::Value::Object(api_config),
     ))?;
 
+    // Per-runtime push telemetry, for devices nothing can scrape.
+    if let Some(ref path) = args.telemetry {
+        let telemetry_config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        runtime.add_processor(ProcessorSpec::new(
+            processor_type_ref!("tatolab", "api-server", "TelemetryUplink"),
+            telemetry_config,
+        ))?;
+    }
+
+    // Remote management by a fleet controller, over an outbound long poll.
+    if let Some(ref path) = args.controller {
+        let controller_config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        runtime.add_processor(ProcessorSpec::new(
+            processor_type_ref!("tatolab", "api-server", "ControllerAgent"),
+            controller_config,
+        ))?;
+    }
+
     if let Some(ref path) = args.snapshot {
         println!("Loading pipeline: {}", path.display());
         // Resolving variant: pull + build any referenced package from the
@@ -102,6 +165,13 @@ async fn run(args: Args) -> Result<()> {
             .await?;
     }
 
+    // Chaos mode injects faults only while the runtime is started, so it
+    // can be armed before `start`.
+    if let Some(ref path) = args.chaos {
+        let mode: ChaosMode = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        runtime.enable_chaos_mode(mode)?;
+    }
+
     runtime.start()?;
 
     if args.snapshot.is_none() {
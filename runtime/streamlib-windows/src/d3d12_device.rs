@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! D3D12 device bring-up.
+
+use windows::Win32::Graphics::Direct3D::D3D_FEATURE_LEVEL_11_0;
+use windows::Win32::Graphics::Direct3D12::{D3D12CreateDevice, ID3D12Device};
+use windows::Win32::Graphics::Dxgi::{
+    CreateDXGIFactory2, DXGI_ADAPTER_FLAG_SOFTWARE, DXGI_CREATE_FACTORY_FLAGS, IDXGIAdapter1,
+    IDXGIFactory4,
+};
+
+use crate::error::{Result, WindowsBackendError};
+
+/// An `ID3D12Device` on the first hardware adapter that supports feature
+/// level 11.0.
+///
+/// Each process opens its own device; textures move between them as
+/// [`DxgiSharedTexture`](crate::DxgiSharedTexture) NT handles. The adapter
+/// LUID is kept so a consumer can check it opened the same GPU as the
+/// producer — a shared handle only opens on the adapter it was created on.
+pub struct D3d12Device {
+    device: ID3D12Device,
+    adapter_name: String,
+    adapter_luid: i64,
+}
+
+impl D3d12Device {
+    /// Open a device on the first hardware adapter, skipping WARP and other
+    /// software adapters.
+    pub fn new() -> Result<Self> {
+        let factory: IDXGIFactory4 = unsafe { CreateDXGIFactory2(DXGI_CREATE_FACTORY_FLAGS(0)) }
+            .map_err(WindowsBackendError::gpu("CreateDXGIFactory2"))?;
+
+        let mut index = 0;
+        // EnumAdapters1 fails with DXGI_ERROR_NOT_FOUND past the last adapter.
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            if let Some(device) = Self::open_hardware_adapter(&adapter)? {
+                return Ok(device);
+            }
+        }
+
+        Err(WindowsBackendError::Gpu(
+            "no hardware adapter supports D3D12 at feature level 11.0".into(),
+        ))
+    }
+
+    fn open_hardware_adapter(adapter: &IDXGIAdapter1) -> Result<Option<Self>> {
+        let desc = unsafe { adapter.GetDesc1() }.map_err(WindowsBackendError::gpu("GetDesc1"))?;
+        if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+            return Ok(None);
+        }
+
+        let adapter_name = String::from_utf16_lossy(&desc.Description)
+            .trim_end_matches('\0')
+            .to_string();
+        let mut device: Option<ID3D12Device> = None;
+        if let Err(error) =
+            unsafe { D3D12CreateDevice(adapter, D3D_FEATURE_LEVEL_11_0, &mut device) }
+        {
+            tracing::debug!("[d3d12] Skipping adapter '{}': {}", adapter_name, error);
+            return Ok(None);
+        }
+        let Some(device) = device else {
+            return Ok(None);
+        };
+
+        tracing::info!("[d3d12] Opened device on '{}'", adapter_name);
+        Ok(Some(Self {
+            device,
+            adapter_name,
+            adapter_luid: ((desc.AdapterLuid.HighPart as i64) << 32)
+                | desc.AdapterLuid.LowPart as i64,
+        }))
+    }
+
+    pub fn device(&self) -> &ID3D12Device {
+        &self.device
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    /// The adapter's locally unique id, packed high part first.
+    pub fn adapter_luid(&self) -> i64 {
+        self.adapter_luid
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Cross-process D3D12 textures over DXGI shared NT handles — the Windows
+//! counterpart of the DMA-BUF fd and IOSurface paths.
+
+use windows::Win32::Foundation::{
+    CloseHandle, DUPLICATE_SAME_ACCESS, DuplicateHandle, GENERIC_ALL, HANDLE,
+};
+use windows::Win32::Graphics::Direct3D12::{
+    D3D12_HEAP_FLAG_SHARED, D3D12_HEAP_PROPERTIES, D3D12_HEAP_TYPE_DEFAULT, D3D12_RESOURCE_DESC,
+    D3D12_RESOURCE_DIMENSION_TEXTURE2D, D3D12_RESOURCE_FLAG_ALLOW_RENDER_TARGET,
+    D3D12_RESOURCE_FLAG_ALLOW_SIMULTANEOUS_ACCESS, D3D12_RESOURCE_STATE_COMMON,
+    D3D12_TEXTURE_LAYOUT_UNKNOWN, ID3D12Resource,
+};
+use windows::Win32::Graphics::Dxgi::Common::{DXGI_FORMAT, DXGI_SAMPLE_DESC};
+use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcess, PROCESS_DUP_HANDLE};
+use windows::core::PCWSTR;
+
+use crate::D3d12Device;
+use crate::error::{Result, WindowsBackendError};
+
+/// A 2D texture in a shared heap plus the NT handle that names it.
+///
+/// The producer [`create`](Self::create)s the texture and hands the handle
+/// to the consumer process with [`duplicate_handle_into_process`]; the
+/// consumer [`open`](Self::open)s it on its own [`D3d12Device`]. Both sides
+/// own their copy of the handle and close it on drop — the texture lives
+/// until the last handle and resource reference are gone.
+///
+/// Textures are created with simultaneous access, so producer and consumer
+/// don't need resource barriers across the process boundary; ordering is
+/// the caller's job, as it is for the other shared-texture paths.
+pub struct DxgiSharedTexture {
+    resource: ID3D12Resource,
+    handle: HANDLE,
+    width: u32,
+    height: u32,
+    format: DXGI_FORMAT,
+}
+
+// SAFETY: the NT handle is a process-wide kernel object name, usable from
+// any thread; the resource is already Send + Sync.
+unsafe impl Send for DxgiSharedTexture {}
+unsafe impl Sync for DxgiSharedTexture {}
+
+impl DxgiSharedTexture {
+    /// Allocate a render-target-capable `width`×`height` texture and export
+    /// it as an NT handle.
+    pub fn create(
+        device: &D3d12Device,
+        width: u32,
+        height: u32,
+        format: DXGI_FORMAT,
+    ) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(WindowsBackendError::Configuration(format!(
+                "shared texture must be non-empty, got {width}x{height}"
+            )));
+        }
+
+        let heap = D3D12_HEAP_PROPERTIES {
+            Type: D3D12_HEAP_TYPE_DEFAULT,
+            CreationNodeMask: 1,
+            VisibleNodeMask: 1,
+            ..Default::default()
+        };
+        let desc = D3D12_RESOURCE_DESC {
+            Dimension: D3D12_RESOURCE_DIMENSION_TEXTURE2D,
+            Alignment: 0,
+            Width: width as u64,
+            Height: height,
+            DepthOrArraySize: 1,
+            MipLevels: 1,
+            Format: format,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Layout: D3D12_TEXTURE_LAYOUT_UNKNOWN,
+            Flags: D3D12_RESOURCE_FLAG_ALLOW_RENDER_TARGET
+                | D3D12_RESOURCE_FLAG_ALLOW_SIMULTANEOUS_ACCESS,
+        };
+
+        let device = device.device();
+        let mut resource: Option<ID3D12Resource> = None;
+        unsafe {
+            device.CreateCommittedResource(
+                &heap,
+                D3D12_HEAP_FLAG_SHARED,
+                &desc,
+                D3D12_RESOURCE_STATE_COMMON,
+                None,
+                &mut resource,
+            )
+        }
+        .map_err(WindowsBackendError::gpu("CreateCommittedResource"))?;
+        let resource = resource.ok_or_else(|| {
+            WindowsBackendError::Gpu("CreateCommittedResource returned no resource".into())
+        })?;
+
+        let handle =
+            unsafe { device.CreateSharedHandle(&resource, None, GENERIC_ALL.0, PCWSTR::null()) }
+                .map_err(WindowsBackendError::gpu("CreateSharedHandle"))?;
+
+        tracing::debug!(
+            "[dxgi] Created shared texture {}x{} {:?}",
+            width,
+            height,
+            format
+        );
+        Ok(Self {
+            resource,
+            handle,
+            width,
+            height,
+            format,
+        })
+    }
+
+    /// Open a texture another process shared with us. Takes ownership of
+    /// `handle`, which must already be valid in this process.
+    pub fn open(device: &D3d12Device, handle: HANDLE) -> Result<Self> {
+        let mut resource: Option<ID3D12Resource> = None;
+        if let Err(error) = unsafe { device.device().OpenSharedHandle(handle, &mut resource) } {
+            // We own the handle either way; don't leak it on failure.
+            unsafe {
+                let _ = CloseHandle(handle);
+            }
+            return Err(WindowsBackendError::gpu("OpenSharedHandle")(error));
+        }
+        let Some(resource) = resource else {
+            unsafe {
+                let _ = CloseHandle(handle);
+            }
+            return Err(WindowsBackendError::Gpu(
+                "OpenSharedHandle returned no resource".into(),
+            ));
+        };
+
+        let desc = unsafe { resource.GetDesc() };
+        Ok(Self {
+            resource,
+            handle,
+            width: desc.Width as u32,
+            height: desc.Height,
+            format: desc.Format,
+        })
+    }
+
+    pub fn resource(&self) -> &ID3D12Resource {
+        &self.resource
+    }
+
+    /// This process's handle to the texture. Still owned by `self`; use
+    /// [`duplicate_handle_into_process`] to hand it to another process.
+    pub fn handle(&self) -> HANDLE {
+        self.handle
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn format(&self) -> DXGI_FORMAT {
+        self.format
+    }
+}
+
+impl Drop for DxgiSharedTexture {
+    fn drop(&mut self) {
+        if let Err(error) = unsafe { CloseHandle(self.handle) } {
+            tracing::warn!("[dxgi] Failed to close shared texture handle: {}", error);
+        }
+    }
+}
+
+/// Duplicate `handle` into the process `target_pid`, returning the handle
+/// value as seen by that process.
+///
+/// NT handles are per-process, so the raw value is meaningless on the other
+/// side until duplicated. The returned value is owned by the target
+/// process, which passes it to [`DxgiSharedTexture::open`]; it has to reach
+/// that process over some other channel (the broker socket, in practice).
+pub fn duplicate_handle_into_process(handle: HANDLE, target_pid: u32) -> Result<HANDLE> {
+    let target = unsafe { OpenProcess(PROCESS_DUP_HANDLE, false, target_pid) }
+        .map_err(WindowsBackendError::gpu("OpenProcess"))?;
+
+    let mut duplicated = HANDLE::default();
+    let result = unsafe {
+        DuplicateHandle(
+            GetCurrentProcess(),
+            handle,
+            target,
+            &mut duplicated,
+            0,
+            false,
+            DUPLICATE_SAME_ACCESS,
+        )
+    };
+    unsafe {
+        let _ = CloseHandle(target);
+    }
+    result.map_err(WindowsBackendError::gpu("DuplicateHandle"))?;
+    Ok(duplicated)
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Error taxonomy for the Windows backend.
+
+use thiserror::Error;
+
+/// Failures the Windows backend can return.
+///
+/// Each variant names the subsystem that failed; the string carries the API
+/// call and the `HRESULT` it returned.
+#[derive(Debug, Error)]
+pub enum WindowsBackendError {
+    /// A D3D12 / DXGI call failed — no adapter, device creation, resource
+    /// creation, shared-handle export or import.
+    #[error("GPU operation failed: {0}")]
+    Gpu(String),
+
+    /// A Media Foundation capture call failed — device enumeration,
+    /// activation, source-reader configuration, or a read.
+    #[error("camera capture failed: {0}")]
+    Capture(String),
+
+    /// A Media Foundation encoder call failed — MFT lookup, media-type
+    /// negotiation, or a process call.
+    #[error("H.264 encode failed: {0}")]
+    Codec(String),
+
+    /// Caller-supplied input failed validation before any API call.
+    #[error("invalid configuration: {0}")]
+    Configuration(String),
+}
+
+/// Result alias paired with [`WindowsBackendError`].
+pub type Result<T> = std::result::Result<T, WindowsBackendError>;
+
+#[cfg(target_os = "windows")]
+impl WindowsBackendError {
+    pub(crate) fn gpu(call: &'static str) -> impl FnOnce(windows::core::Error) -> Self {
+        move |error| Self::Gpu(format!("{call}: {error}"))
+    }
+
+    pub(crate) fn capture(call: &'static str) -> impl FnOnce(windows::core::Error) -> Self {
+        move |error| Self::Capture(format!("{call}: {error}"))
+    }
+
+    pub(crate) fn codec(call: &'static str) -> impl FnOnce(windows::core::Error) -> Self {
+        move |error| Self::Codec(format!("{call}: {error}"))
+    }
+}
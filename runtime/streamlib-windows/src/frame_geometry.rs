@@ -0,0 +1,102 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Frame-size arithmetic shared by the Media Foundation capture and encode
+//! paths. Platform-independent so it stays testable off Windows.
+
+use crate::error::{Result, WindowsBackendError};
+
+/// Bytes in one tightly packed NV12 frame: a full-resolution Y plane followed
+/// by an interleaved half-resolution CbCr plane.
+///
+/// NV12 subsamples 2×2, so odd dimensions are rejected.
+pub fn nv12_frame_len(width: u32, height: u32) -> Result<usize> {
+    if width == 0 || height == 0 || (width | height) & 1 != 0 {
+        return Err(WindowsBackendError::Configuration(format!(
+            "NV12 needs non-zero even dimensions, got {width}x{height}"
+        )));
+    }
+    let luma = width as usize * height as usize;
+    Ok(luma + luma / 2)
+}
+
+/// Repack an NV12 frame whose rows are `pitch` bytes apart — as
+/// `IMF2DBuffer::Lock2D` hands it out, CbCr plane directly after the last
+/// Y row — into the tightly packed layout of [`nv12_frame_len`].
+pub fn pack_nv12(planes: &[u8], pitch: usize, width: u32, height: u32) -> Result<Vec<u8>> {
+    let len = nv12_frame_len(width, height)?;
+    let (width, rows) = (width as usize, height as usize * 3 / 2);
+    if pitch < width || planes.len() < pitch * (rows - 1) + width {
+        return Err(WindowsBackendError::Capture(format!(
+            "NV12 buffer of {} bytes with pitch {pitch} is too small for {width}x{height}",
+            planes.len()
+        )));
+    }
+    let mut packed = Vec::with_capacity(len);
+    for row in planes.chunks(pitch).take(rows) {
+        packed.extend_from_slice(&row[..width]);
+    }
+    Ok(packed)
+}
+
+/// Pack two `u32`s into the `UINT64` layout Media Foundation uses for
+/// `MF_MT_FRAME_SIZE` (width, height) and `MF_MT_FRAME_RATE` (numerator,
+/// denominator): the first value in the high 32 bits.
+pub fn pack_u32_pair(high: u32, low: u32) -> u64 {
+    ((high as u64) << 32) | low as u64
+}
+
+/// Inverse of [`pack_u32_pair`].
+pub fn unpack_u32_pair(packed: u64) -> (u32, u32) {
+    ((packed >> 32) as u32, packed as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nv12_is_one_and_a_half_bytes_per_pixel() {
+        assert_eq!(nv12_frame_len(1920, 1080).unwrap(), 1920 * 1080 * 3 / 2);
+        assert_eq!(nv12_frame_len(2, 2).unwrap(), 6);
+    }
+
+    #[test]
+    fn nv12_rejects_odd_or_empty_dimensions() {
+        for (width, height) in [(0, 1080), (1920, 0), (1921, 1080), (1920, 1079)] {
+            assert!(matches!(
+                nv12_frame_len(width, height),
+                Err(WindowsBackendError::Configuration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn pitched_nv12_drops_row_padding() {
+        // 4x2: two Y rows and one CbCr row, each padded to a pitch of 6.
+        let planes = [
+            1, 2, 3, 4, 0, 0, //
+            5, 6, 7, 8, 0, 0, //
+            9, 10, 11, 12, 0, 0,
+        ];
+        assert_eq!(
+            pack_nv12(&planes, 6, 4, 2).unwrap(),
+            (1..=12).collect::<Vec<u8>>()
+        );
+        // The last row's padding may be missing.
+        assert!(pack_nv12(&planes[..16], 6, 4, 2).is_ok());
+        assert!(pack_nv12(&planes[..15], 6, 4, 2).is_err());
+        assert!(pack_nv12(&planes, 3, 4, 2).is_err());
+    }
+
+    #[test]
+    fn packed_pairs_put_the_first_value_high() {
+        let packed = pack_u32_pair(1920, 1080);
+        assert_eq!(packed, (1920u64 << 32) | 1080);
+        assert_eq!(unpack_u32_pair(packed), (1920, 1080));
+        assert_eq!(
+            unpack_u32_pair(pack_u32_pair(30_000, 1_001)),
+            (30_000, 1_001)
+        );
+    }
+}
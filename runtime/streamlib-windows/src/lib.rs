@@ -0,0 +1,59 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Windows platform backend for StreamLib.
+//!
+//! `platform::gpu_backend()` reports Direct3D 12 on Windows; this crate is
+//! the platform layer behind that claim, kept out of the engine the same way
+//! `streamlib-consumer-rhi` keeps the Vulkan consumer carve-out out of it.
+//! Everything here compiles only on Windows — elsewhere the crate is just the
+//! error taxonomy and the frame-geometry helpers.
+//!
+//! What lives here:
+//!
+//! - [`D3d12Device`] — adapter selection and `ID3D12Device` bring-up.
+//! - [`DxgiSharedTexture`] — committed D3D12 textures created with
+//!   `D3D12_HEAP_FLAG_SHARED` and exported as NT handles. The handle is the
+//!   Windows analogue of a DMA-BUF FD: [`duplicate_handle_into_process`]
+//!   hands it to a subprocess, which opens it with
+//!   [`DxgiSharedTexture::open`] on its own device.
+//! - [`MediaFoundation`] — per-thread COM + `MFStartup` scope every Media
+//!   Foundation call runs under.
+//! - [`MfCamera`] — Media Foundation camera enumeration and NV12 capture
+//!   through `IMFSourceReader`.
+//! - [`MfH264Encoder`] — NV12 → Annex-B H.264 through the synchronous
+//!   Media Foundation encoder MFT.
+//!
+//! What does NOT live here yet: the camera / h264 / display package arms.
+//! Those processors drive GPU resources through the plugin SDK's
+//! Vulkan-shaped RHI, so their Windows arms land once the plugin SDK grows a
+//! D3D12 surface — the same reason the Apple arms are parked under
+//! `_apple_impl_pending_/`.
+
+mod error;
+mod frame_geometry;
+
+#[cfg(target_os = "windows")]
+mod d3d12_device;
+#[cfg(target_os = "windows")]
+mod dxgi_shared_texture;
+#[cfg(target_os = "windows")]
+mod media_foundation;
+#[cfg(target_os = "windows")]
+mod mf_camera;
+#[cfg(target_os = "windows")]
+mod mf_h264_encoder;
+
+pub use error::{Result, WindowsBackendError};
+pub use frame_geometry::{nv12_frame_len, pack_nv12, pack_u32_pair, unpack_u32_pair};
+
+#[cfg(target_os = "windows")]
+pub use d3d12_device::D3d12Device;
+#[cfg(target_os = "windows")]
+pub use dxgi_shared_texture::{DxgiSharedTexture, duplicate_handle_into_process};
+#[cfg(target_os = "windows")]
+pub use media_foundation::MediaFoundation;
+#[cfg(target_os = "windows")]
+pub use mf_camera::{MfCamera, MfCameraConfig, MfCameraDevice, MfCapturedFrame};
+#[cfg(target_os = "windows")]
+pub use mf_h264_encoder::{MfEncodedFrame, MfH264Encoder, MfH264EncoderConfig};
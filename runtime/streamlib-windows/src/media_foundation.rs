@@ -0,0 +1,46 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! COM + Media Foundation lifetime.
+
+use std::marker::PhantomData;
+
+use windows::Win32::Media::MediaFoundation::{MF_VERSION, MFSTARTUP_FULL, MFShutdown, MFStartup};
+use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx, CoUninitialize};
+
+use crate::error::{Result, WindowsBackendError};
+
+/// Keeps COM (multithreaded apartment) and Media Foundation started on the
+/// current thread for as long as it lives.
+///
+/// Create one on every thread that touches [`MfCamera`](crate::MfCamera) or
+/// [`MfH264Encoder`](crate::MfH264Encoder), before opening them, and drop
+/// it after them. Both calls are reference counted by Windows, so nesting
+/// scopes is fine. The scope is tied to its thread and is not `Send`.
+pub struct MediaFoundation {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl MediaFoundation {
+    pub fn startup() -> Result<Self> {
+        unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }
+            .ok()
+            .map_err(WindowsBackendError::capture("CoInitializeEx"))?;
+        if let Err(error) = unsafe { MFStartup(MF_VERSION, MFSTARTUP_FULL) } {
+            unsafe { CoUninitialize() };
+            return Err(WindowsBackendError::capture("MFStartup")(error));
+        }
+        Ok(Self {
+            _not_send: PhantomData,
+        })
+    }
+}
+
+impl Drop for MediaFoundation {
+    fn drop(&mut self) {
+        if let Err(error) = unsafe { MFShutdown() } {
+            tracing::warn!("[media_foundation] MFShutdown failed: {}", error);
+        }
+        unsafe { CoUninitialize() };
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Media Foundation camera capture.
+
+use windows::Win32::Media::MediaFoundation::{
+    IMF2DBuffer, IMFActivate, IMFAttributes, IMFMediaSource, IMFSourceReader,
+    MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME, MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE,
+    MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID,
+    MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK, MF_MT_FRAME_RATE, MF_MT_FRAME_SIZE,
+    MF_MT_MAJOR_TYPE, MF_MT_SUBTYPE, MF_SOURCE_READER_ALL_STREAMS,
+    MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING, MF_SOURCE_READER_FIRST_VIDEO_STREAM,
+    MF_SOURCE_READERF_ENDOFSTREAM, MF_SOURCE_READERF_ERROR, MFCreateAttributes, MFCreateMediaType,
+    MFCreateSourceReaderFromMediaSource, MFEnumDeviceSources, MFMediaType_Video,
+    MFVideoFormat_NV12,
+};
+use windows::Win32::System::Com::CoTaskMemFree;
+use windows::core::{GUID, Interface, PWSTR};
+
+use crate::error::{Result, WindowsBackendError};
+use crate::frame_geometry::{pack_nv12, pack_u32_pair, unpack_u32_pair};
+
+const VIDEO_STREAM: u32 = MF_SOURCE_READER_FIRST_VIDEO_STREAM.0 as u32;
+
+/// A video capture device as Media Foundation enumerates it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfCameraDevice {
+    pub name: String,
+    /// Stable device path; what [`MfCameraConfig::device`] matches against.
+    pub symbolic_link: String,
+}
+
+/// What to open and the format to ask it for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfCameraConfig {
+    /// Symbolic link of the device to open; `None` opens the first one.
+    pub device: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+}
+
+impl Default for MfCameraConfig {
+    fn default() -> Self {
+        Self {
+            device: None,
+            width: 1280,
+            height: 720,
+            fps: 30,
+        }
+    }
+}
+
+/// One tightly packed NV12 frame.
+#[derive(Debug, Clone)]
+pub struct MfCapturedFrame {
+    pub width: u32,
+    pub height: u32,
+    /// Presentation time in 100 ns units, on the source's clock.
+    pub timestamp_100ns: i64,
+    pub data: Vec<u8>,
+}
+
+/// A camera delivering NV12 frames through a synchronous `IMFSourceReader`.
+///
+/// The reader's video processing is enabled so cameras that only offer
+/// MJPG or YUY2 still hand back NV12. Must be opened and used inside a
+/// [`MediaFoundation`](crate::MediaFoundation) scope.
+pub struct MfCamera {
+    source: IMFMediaSource,
+    reader: IMFSourceReader,
+    width: u32,
+    height: u32,
+}
+
+// SAFETY: the reader and source are created in the multithreaded apartment
+// that `MediaFoundation::startup` joins, so they may be called from any
+// thread in it; `MfCamera` hands out no interior references.
+unsafe impl Send for MfCamera {}
+
+impl MfCamera {
+    /// List the video capture devices currently present.
+    pub fn enumerate() -> Result<Vec<MfCameraDevice>> {
+        device_activates()?
+            .iter()
+            .map(|activate| {
+                Ok(MfCameraDevice {
+                    name: allocated_string(activate, &MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME)?,
+                    symbolic_link: allocated_string(
+                        activate,
+                        &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK,
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn open(config: &MfCameraConfig) -> Result<Self> {
+        let activates = device_activates()?;
+        let activate = match &config.device {
+            None => activates.first(),
+            Some(device) => activates.iter().find(|activate| {
+                allocated_string(
+                    activate,
+                    &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK,
+                )
+                .is_ok_and(|link| link.eq_ignore_ascii_case(device))
+            }),
+        }
+        .ok_or_else(|| {
+            WindowsBackendError::Configuration(match &config.device {
+                None => "no video capture device present".into(),
+                Some(device) => format!("video capture device '{device}' not found"),
+            })
+        })?;
+        let name = allocated_string(activate, &MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME)?;
+
+        let source: IMFMediaSource = unsafe { activate.ActivateObject() }
+            .map_err(WindowsBackendError::capture("ActivateObject"))?;
+        let reader = match Self::configure_reader(&source, config) {
+            Ok(reader) => reader,
+            Err(error) => {
+                let _ = unsafe { source.Shutdown() };
+                return Err(error);
+            }
+        };
+
+        // The device may have settled on a different size than we asked for.
+        let current = unsafe { reader.GetCurrentMediaType(VIDEO_STREAM) }
+            .and_then(|media_type| unsafe { media_type.GetUINT64(&MF_MT_FRAME_SIZE) })
+            .map_err(WindowsBackendError::capture("GetCurrentMediaType"))?;
+        let (width, height) = unpack_u32_pair(current);
+        tracing::info!(
+            "[mf_camera] Opened '{}' at {}x{} NV12 (requested {}x{}@{})",
+            name,
+            width,
+            height,
+            config.width,
+            config.height,
+            config.fps
+        );
+
+        Ok(Self {
+            source,
+            reader,
+            width,
+            height,
+        })
+    }
+
+    fn configure_reader(
+        source: &IMFMediaSource,
+        config: &MfCameraConfig,
+    ) -> Result<IMFSourceReader> {
+        let attributes = create_attributes(1)?;
+        unsafe { attributes.SetUINT32(&MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING, 1) }
+            .map_err(WindowsBackendError::capture("SetUINT32"))?;
+        let reader = unsafe { MFCreateSourceReaderFromMediaSource(source, &attributes) }.map_err(
+            WindowsBackendError::capture("MFCreateSourceReaderFromMediaSource"),
+        )?;
+
+        select_nv12(&reader, config)
+            .map_err(WindowsBackendError::capture("SetCurrentMediaType"))?;
+        Ok(reader)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Block until the next frame. `Ok(None)` for a stream tick — a gap the
+    /// source reports instead of a frame.
+    pub fn read_frame(&mut self) -> Result<Option<MfCapturedFrame>> {
+        let mut flags = 0u32;
+        let mut timestamp_100ns = 0i64;
+        let mut sample = None;
+        unsafe {
+            self.reader.ReadSample(
+                VIDEO_STREAM,
+                0,
+                None,
+                Some(&mut flags),
+                Some(&mut timestamp_100ns),
+                Some(&mut sample),
+            )
+        }
+        .map_err(WindowsBackendError::capture("ReadSample"))?;
+
+        if flags & MF_SOURCE_READERF_ERROR.0 as u32 != 0 {
+            return Err(WindowsBackendError::Capture(
+                "source reader reported a stream error".into(),
+            ));
+        }
+        if flags & MF_SOURCE_READERF_ENDOFSTREAM.0 as u32 != 0 {
+            return Err(WindowsBackendError::Capture("camera stream ended".into()));
+        }
+        let Some(sample) = sample else {
+            return Ok(None);
+        };
+
+        let buffer = unsafe { sample.ConvertToContiguousBuffer() }
+            .map_err(WindowsBackendError::capture("ConvertToContiguousBuffer"))?;
+        let data = match buffer.cast::<IMF2DBuffer>() {
+            Ok(buffer_2d) => {
+                let mut scanline0 = std::ptr::null_mut();
+                let mut pitch = 0i32;
+                unsafe { buffer_2d.Lock2D(&mut scanline0, &mut pitch) }
+                    .map_err(WindowsBackendError::capture("Lock2D"))?;
+                // A negative pitch means a bottom-up image, which NV12 never is.
+                let pitch = pitch.unsigned_abs() as usize;
+                // Only borrow up to the end of the last row's pixels; the
+                // padding after it need not be allocated.
+                let rows = self.height as usize * 3 / 2;
+                let len = pitch * (rows - 1) + self.width as usize;
+                let planes = unsafe { std::slice::from_raw_parts(scanline0, len) };
+                let packed = pack_nv12(planes, pitch, self.width, self.height);
+                let _ = unsafe { buffer_2d.Unlock2D() };
+                packed?
+            }
+            Err(_) => {
+                let mut data = std::ptr::null_mut();
+                let mut len = 0u32;
+                unsafe { buffer.Lock(&mut data, None, Some(&mut len)) }
+                    .map_err(WindowsBackendError::capture("Lock"))?;
+                let bytes = unsafe { std::slice::from_raw_parts(data, len as usize) };
+                let packed = pack_nv12(bytes, self.width as usize, self.width, self.height);
+                let _ = unsafe { buffer.Unlock() };
+                packed?
+            }
+        };
+
+        Ok(Some(MfCapturedFrame {
+            width: self.width,
+            height: self.height,
+            timestamp_100ns,
+            data,
+        }))
+    }
+}
+
+impl Drop for MfCamera {
+    fn drop(&mut self) {
+        if let Err(error) = unsafe { self.source.Shutdown() } {
+            tracing::warn!("[mf_camera] Failed to shut down media source: {}", error);
+        }
+    }
+}
+
+/// Read only the first video stream, as NV12 at the configured size and rate.
+fn select_nv12(reader: &IMFSourceReader, config: &MfCameraConfig) -> windows::core::Result<()> {
+    unsafe {
+        let media_type = MFCreateMediaType()?;
+        media_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+        media_type.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_NV12)?;
+        media_type.SetUINT64(
+            &MF_MT_FRAME_SIZE,
+            pack_u32_pair(config.width, config.height),
+        )?;
+        media_type.SetUINT64(&MF_MT_FRAME_RATE, pack_u32_pair(config.fps, 1))?;
+
+        reader.SetStreamSelection(MF_SOURCE_READER_ALL_STREAMS.0 as u32, false)?;
+        reader.SetStreamSelection(VIDEO_STREAM, true)?;
+        reader.SetCurrentMediaType(VIDEO_STREAM, None, &media_type)
+    }
+}
+
+fn create_attributes(initial_size: u32) -> Result<IMFAttributes> {
+    let mut attributes = None;
+    unsafe { MFCreateAttributes(&mut attributes, initial_size) }
+        .map_err(WindowsBackendError::capture("MFCreateAttributes"))?;
+    attributes
+        .ok_or_else(|| WindowsBackendError::Capture("MFCreateAttributes returned nothing".into()))
+}
+
+/// Activation objects for every video capture device present.
+fn device_activates() -> Result<Vec<IMFActivate>> {
+    let attributes = create_attributes(1)?;
+    unsafe {
+        attributes.SetGUID(
+            &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE,
+            &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID,
+        )
+    }
+    .map_err(WindowsBackendError::capture("SetGUID"))?;
+
+    let mut activates: *mut Option<IMFActivate> = std::ptr::null_mut();
+    let mut count = 0u32;
+    unsafe { MFEnumDeviceSources(&attributes, &mut activates, &mut count) }
+        .map_err(WindowsBackendError::capture("MFEnumDeviceSources"))?;
+    if activates.is_null() {
+        return Ok(Vec::new());
+    }
+
+    // The array is CoTaskMemAlloc'd and owns one reference per entry; take
+    // the references out before freeing it.
+    let devices = unsafe { std::slice::from_raw_parts_mut(activates, count as usize) }
+        .iter_mut()
+        .filter_map(Option::take)
+        .collect();
+    unsafe { CoTaskMemFree(Some(activates as *const _)) };
+    Ok(devices)
+}
+
+fn allocated_string(activate: &IMFActivate, key: &GUID) -> Result<String> {
+    let mut value = PWSTR::null();
+    let mut len = 0u32;
+    unsafe { activate.GetAllocatedString(key, &mut value, &mut len) }
+        .map_err(WindowsBackendError::capture("GetAllocatedString"))?;
+    let string = unsafe { value.to_string() }.unwrap_or_default();
+    unsafe { CoTaskMemFree(Some(value.0 as *const _)) };
+    Ok(string)
+}
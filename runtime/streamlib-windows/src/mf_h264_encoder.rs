@@ -0,0 +1,362 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! NV12 → H.264 through the Media Foundation encoder MFT.
+
+use std::mem::ManuallyDrop;
+
+use windows::Win32::Media::MediaFoundation::{
+    IMFActivate, IMFMediaType, IMFSample, IMFTransform, MF_E_TRANSFORM_NEED_MORE_INPUT,
+    MF_E_TRANSFORM_STREAM_CHANGE, MF_LOW_LATENCY, MF_MT_AVG_BITRATE, MF_MT_FRAME_RATE,
+    MF_MT_FRAME_SIZE, MF_MT_INTERLACE_MODE, MF_MT_MAJOR_TYPE, MF_MT_MPEG2_PROFILE, MF_MT_SUBTYPE,
+    MFCreateMediaType, MFCreateMemoryBuffer, MFCreateSample, MFMediaType_Video,
+    MFSampleExtension_CleanPoint, MFT_CATEGORY_VIDEO_ENCODER, MFT_ENUM_FLAG_LOCALMFT,
+    MFT_ENUM_FLAG_SORTANDFILTER, MFT_ENUM_FLAG_SYNCMFT, MFT_MESSAGE_COMMAND_DRAIN,
+    MFT_MESSAGE_NOTIFY_BEGIN_STREAMING, MFT_MESSAGE_NOTIFY_END_OF_STREAM,
+    MFT_MESSAGE_NOTIFY_END_STREAMING, MFT_MESSAGE_NOTIFY_START_OF_STREAM, MFT_OUTPUT_DATA_BUFFER,
+    MFT_OUTPUT_STREAM_CAN_PROVIDE_SAMPLES, MFT_OUTPUT_STREAM_INFO,
+    MFT_OUTPUT_STREAM_PROVIDES_SAMPLES, MFT_REGISTER_TYPE_INFO, MFTEnumEx, MFVideoFormat_H264,
+    MFVideoFormat_NV12, MFVideoInterlace_Progressive, eAVEncH264VProfile_Main,
+};
+use windows::Win32::System::Com::CoTaskMemFree;
+
+use crate::error::{Result, WindowsBackendError};
+use crate::frame_geometry::{nv12_frame_len, pack_u32_pair};
+
+/// Encoder settings. The output is Main profile, progressive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfH264EncoderConfig {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub bitrate_bps: u32,
+}
+
+impl Default for MfH264EncoderConfig {
+    fn default() -> Self {
+        Self {
+            width: 1280,
+            height: 720,
+            fps: 30,
+            bitrate_bps: 4_000_000,
+        }
+    }
+}
+
+/// One encoded access unit in Annex-B byte-stream format.
+#[derive(Debug, Clone)]
+pub struct MfEncodedFrame {
+    pub data: Vec<u8>,
+    /// Presentation time in 100 ns units, carried over from the input frame.
+    pub timestamp_100ns: i64,
+    pub keyframe: bool,
+}
+
+/// A synchronous H.264 encoder MFT fed NV12 frames one at a time.
+///
+/// Only synchronous MFTs are considered — on most machines that is the
+/// Microsoft software encoder, since the vendor hardware encoders are
+/// asynchronous and event-driven. Must be created and used inside a
+/// [`MediaFoundation`](crate::MediaFoundation) scope.
+pub struct MfH264Encoder {
+    activate: IMFActivate,
+    transform: IMFTransform,
+    config: MfH264EncoderConfig,
+    frame_len: usize,
+    output_info: MFT_OUTPUT_STREAM_INFO,
+}
+
+// SAFETY: see `MfCamera` — the transform lives in the multithreaded
+// apartment and `MfH264Encoder` hands out no interior references.
+unsafe impl Send for MfH264Encoder {}
+
+impl MfH264Encoder {
+    pub fn new(config: MfH264EncoderConfig) -> Result<Self> {
+        let frame_len = nv12_frame_len(config.width, config.height)?;
+        if config.fps == 0 || config.bitrate_bps == 0 {
+            return Err(WindowsBackendError::Configuration(format!(
+                "H.264 encoder needs a non-zero fps and bitrate, got {} fps at {} bps",
+                config.fps, config.bitrate_bps
+            )));
+        }
+
+        let activate = find_encoder()?;
+        let transform: IMFTransform = unsafe { activate.ActivateObject() }
+            .map_err(WindowsBackendError::codec("ActivateObject"))?;
+
+        // Without low-latency mode the encoder may hold frames back for
+        // B-frame reordering; a live pipeline wants each frame out as it
+        // goes in. Not every encoder honours it, so failure is not fatal.
+        if let Err(error) = unsafe { transform.GetAttributes() }
+            .and_then(|attributes| unsafe { attributes.SetUINT32(&MF_LOW_LATENCY, 1) })
+        {
+            tracing::debug!("[mf_h264] Encoder ignored MF_LOW_LATENCY: {}", error);
+        }
+
+        let output_info = start_streaming(&transform, &config)
+            .map_err(WindowsBackendError::codec("encoder media types"))?;
+
+        tracing::info!(
+            "[mf_h264] Encoder ready: {}x{}@{} {} bps",
+            config.width,
+            config.height,
+            config.fps,
+            config.bitrate_bps
+        );
+        Ok(Self {
+            activate,
+            transform,
+            config,
+            frame_len,
+            output_info,
+        })
+    }
+
+    pub fn config(&self) -> &MfH264EncoderConfig {
+        &self.config
+    }
+
+    /// Feed one tightly packed NV12 frame and return whatever access units
+    /// the encoder has ready — usually one, possibly none while it warms up.
+    pub fn encode(&mut self, nv12: &[u8], timestamp_100ns: i64) -> Result<Vec<MfEncodedFrame>> {
+        if nv12.len() != self.frame_len {
+            return Err(WindowsBackendError::Configuration(format!(
+                "expected a {}-byte NV12 frame for {}x{}, got {} bytes",
+                self.frame_len,
+                self.config.width,
+                self.config.height,
+                nv12.len()
+            )));
+        }
+
+        let duration_100ns = 10_000_000 / self.config.fps as i64;
+        let sample = input_sample(nv12, timestamp_100ns, duration_100ns)
+            .map_err(WindowsBackendError::codec("input sample"))?;
+
+        unsafe { self.transform.ProcessInput(0, &sample, 0) }
+            .map_err(WindowsBackendError::codec("ProcessInput"))?;
+        self.drain_output()
+    }
+
+    /// Signal end of stream and collect the access units still buffered.
+    pub fn finish(&mut self) -> Result<Vec<MfEncodedFrame>> {
+        unsafe {
+            self.transform
+                .ProcessMessage(MFT_MESSAGE_NOTIFY_END_OF_STREAM, 0)
+                .and_then(|()| self.transform.ProcessMessage(MFT_MESSAGE_COMMAND_DRAIN, 0))
+        }
+        .map_err(WindowsBackendError::codec("ProcessMessage"))?;
+        self.drain_output()
+    }
+
+    fn drain_output(&mut self) -> Result<Vec<MfEncodedFrame>> {
+        let mut frames = Vec::new();
+        loop {
+            let provides_samples = self.output_info.dwFlags
+                & (MFT_OUTPUT_STREAM_PROVIDES_SAMPLES.0 | MFT_OUTPUT_STREAM_CAN_PROVIDE_SAMPLES.0)
+                    as u32
+                != 0;
+            let sample = if provides_samples {
+                None
+            } else {
+                Some(
+                    new_sample(self.output_info.cbSize)
+                        .map_err(WindowsBackendError::codec("MFCreateSample"))?,
+                )
+            };
+
+            let mut output = [MFT_OUTPUT_DATA_BUFFER {
+                dwStreamID: 0,
+                pSample: ManuallyDrop::new(sample),
+                dwStatus: 0,
+                pEvents: ManuallyDrop::new(None),
+            }];
+            let mut status = 0u32;
+            let result = unsafe { self.transform.ProcessOutput(0, &mut output, &mut status) };
+            let [output] = output;
+            let sample = ManuallyDrop::into_inner(output.pSample);
+            drop(ManuallyDrop::into_inner(output.pEvents));
+
+            match result {
+                Ok(()) => {}
+                Err(error) if error.code() == MF_E_TRANSFORM_NEED_MORE_INPUT => return Ok(frames),
+                Err(error) if error.code() == MF_E_TRANSFORM_STREAM_CHANGE => {
+                    self.renegotiate_output()
+                        .map_err(WindowsBackendError::codec("output renegotiation"))?;
+                    continue;
+                }
+                Err(error) => return Err(WindowsBackendError::codec("ProcessOutput")(error)),
+            }
+            if let Some(sample) = sample {
+                frames.push(
+                    encoded_frame(&sample).map_err(WindowsBackendError::codec("output sample"))?,
+                );
+            }
+        }
+    }
+
+    /// Accept the encoder's new output type after a stream change.
+    fn renegotiate_output(&mut self) -> windows::core::Result<()> {
+        unsafe {
+            let media_type = self.transform.GetOutputAvailableType(0, 0)?;
+            self.transform.SetOutputType(0, &media_type, 0)?;
+            self.output_info = self.transform.GetOutputStreamInfo(0)?;
+        }
+        tracing::debug!("[mf_h264] Encoder output type changed");
+        Ok(())
+    }
+}
+
+impl Drop for MfH264Encoder {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self
+                .transform
+                .ProcessMessage(MFT_MESSAGE_NOTIFY_END_STREAMING, 0);
+            if let Err(error) = self.activate.ShutdownObject() {
+                tracing::warn!("[mf_h264] Failed to shut down encoder: {}", error);
+            }
+        }
+    }
+}
+
+/// Activation object for the best-ranked synchronous NV12 → H.264 encoder.
+fn find_encoder() -> Result<IMFActivate> {
+    let input = MFT_REGISTER_TYPE_INFO {
+        guidMajorType: MFMediaType_Video,
+        guidSubtype: MFVideoFormat_NV12,
+    };
+    let output = MFT_REGISTER_TYPE_INFO {
+        guidMajorType: MFMediaType_Video,
+        guidSubtype: MFVideoFormat_H264,
+    };
+    let mut activates: *mut Option<IMFActivate> = std::ptr::null_mut();
+    let mut count = 0u32;
+    unsafe {
+        MFTEnumEx(
+            MFT_CATEGORY_VIDEO_ENCODER,
+            MFT_ENUM_FLAG_SYNCMFT | MFT_ENUM_FLAG_LOCALMFT | MFT_ENUM_FLAG_SORTANDFILTER,
+            Some(&input),
+            Some(&output),
+            &mut activates,
+            &mut count,
+        )
+    }
+    .map_err(WindowsBackendError::codec("MFTEnumEx"))?;
+    if activates.is_null() {
+        return Err(WindowsBackendError::Codec(
+            "no synchronous H.264 encoder MFT installed".into(),
+        ));
+    }
+
+    // Same ownership dance as device enumeration: take every reference out
+    // of the CoTaskMemAlloc'd array, keep the best-ranked one, free the rest.
+    let mut encoders: Vec<IMFActivate> =
+        unsafe { std::slice::from_raw_parts_mut(activates, count as usize) }
+            .iter_mut()
+            .filter_map(Option::take)
+            .collect();
+    unsafe { CoTaskMemFree(Some(activates as *const _)) };
+    if encoders.is_empty() {
+        return Err(WindowsBackendError::Codec(
+            "no synchronous H.264 encoder MFT installed".into(),
+        ));
+    }
+    Ok(encoders.swap_remove(0))
+}
+
+/// Set the media types and start streaming, returning the output stream's
+/// buffer requirements. The encoder only accepts an input type once the
+/// output type is set.
+fn start_streaming(
+    transform: &IMFTransform,
+    config: &MfH264EncoderConfig,
+) -> windows::core::Result<MFT_OUTPUT_STREAM_INFO> {
+    unsafe {
+        transform.SetOutputType(0, &output_type(config)?, 0)?;
+        transform.SetInputType(0, &input_type(config)?, 0)?;
+        let output_info = transform.GetOutputStreamInfo(0)?;
+        transform.ProcessMessage(MFT_MESSAGE_NOTIFY_BEGIN_STREAMING, 0)?;
+        transform.ProcessMessage(MFT_MESSAGE_NOTIFY_START_OF_STREAM, 0)?;
+        Ok(output_info)
+    }
+}
+
+fn output_type(config: &MfH264EncoderConfig) -> windows::core::Result<IMFMediaType> {
+    unsafe {
+        let media_type = MFCreateMediaType()?;
+        media_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+        media_type.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_H264)?;
+        media_type.SetUINT32(&MF_MT_AVG_BITRATE, config.bitrate_bps)?;
+        media_type.SetUINT64(
+            &MF_MT_FRAME_SIZE,
+            pack_u32_pair(config.width, config.height),
+        )?;
+        media_type.SetUINT64(&MF_MT_FRAME_RATE, pack_u32_pair(config.fps, 1))?;
+        media_type.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+        media_type.SetUINT32(&MF_MT_MPEG2_PROFILE, eAVEncH264VProfile_Main.0 as u32)?;
+        Ok(media_type)
+    }
+}
+
+fn input_type(config: &MfH264EncoderConfig) -> windows::core::Result<IMFMediaType> {
+    unsafe {
+        let media_type = MFCreateMediaType()?;
+        media_type.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+        media_type.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_NV12)?;
+        media_type.SetUINT64(
+            &MF_MT_FRAME_SIZE,
+            pack_u32_pair(config.width, config.height),
+        )?;
+        media_type.SetUINT64(&MF_MT_FRAME_RATE, pack_u32_pair(config.fps, 1))?;
+        media_type.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+        Ok(media_type)
+    }
+}
+
+/// A sample backed by one `len`-byte memory buffer.
+fn new_sample(len: u32) -> windows::core::Result<IMFSample> {
+    unsafe {
+        let sample = MFCreateSample()?;
+        sample.AddBuffer(&MFCreateMemoryBuffer(len)?)?;
+        Ok(sample)
+    }
+}
+
+fn input_sample(
+    nv12: &[u8],
+    timestamp_100ns: i64,
+    duration_100ns: i64,
+) -> windows::core::Result<IMFSample> {
+    let sample = new_sample(nv12.len() as u32)?;
+    unsafe {
+        let buffer = sample.GetBufferByIndex(0)?;
+        let mut data = std::ptr::null_mut();
+        buffer.Lock(&mut data, None, None)?;
+        std::ptr::copy_nonoverlapping(nv12.as_ptr(), data, nv12.len());
+        buffer.Unlock()?;
+        buffer.SetCurrentLength(nv12.len() as u32)?;
+        sample.SetSampleTime(timestamp_100ns)?;
+        sample.SetSampleDuration(duration_100ns)?;
+    }
+    Ok(sample)
+}
+
+fn encoded_frame(sample: &IMFSample) -> windows::core::Result<MfEncodedFrame> {
+    let (data, timestamp_100ns) = unsafe {
+        let buffer = sample.ConvertToContiguousBuffer()?;
+        let mut bytes = std::ptr::null_mut();
+        let mut len = 0u32;
+        buffer.Lock(&mut bytes, None, Some(&mut len))?;
+        let data = std::slice::from_raw_parts(bytes, len as usize).to_vec();
+        buffer.Unlock()?;
+        (data, sample.GetSampleTime()?)
+    };
+    // An absent clean-point attribute means a delta frame.
+    let keyframe = unsafe { sample.GetUINT32(&MFSampleExtension_CleanPoint) }.unwrap_or(0) != 0;
+    Ok(MfEncodedFrame {
+        data,
+        timestamp_100ns,
+        keyframe,
+    })
+}
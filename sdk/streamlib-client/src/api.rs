@@ -0,0 +1,249 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Endpoint table shared by the blocking and async clients: method, path,
+//! body and which statuses count as an answer. The transports only move
+//! bytes; everything the server contract decides lives here.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::{ClientError, Result};
+
+pub(crate) const EVENTS_PATH: &str = "/ws/events";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Endpoint {
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) body: Option<serde_json::Value>,
+    /// Probes answer `503` with the same report body as `200`; a failing
+    /// probe is a result, not an error.
+    pub(crate) probe: bool,
+}
+
+impl Endpoint {
+    fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+            probe: false,
+        }
+    }
+
+    fn with_body(mut self, body: &impl Serialize) -> Self {
+        // The request DTOs are plain structs of strings and JSON values;
+        // serializing them cannot fail.
+        self.body = Some(serde_json::to_value(body).unwrap_or_default());
+        self
+    }
+
+    pub(crate) fn health() -> Self {
+        Self::new(Method::Get, "/health")
+    }
+
+    pub(crate) fn ready() -> Self {
+        Self {
+            probe: true,
+            ..Self::new(Method::Get, "/ready")
+        }
+    }
+
+    pub(crate) fn live() -> Self {
+        Self {
+            probe: true,
+            ..Self::new(Method::Get, "/live")
+        }
+    }
+
+    pub(crate) fn graph() -> Self {
+        Self::new(Method::Get, "/api/graph")
+    }
+
+    pub(crate) fn registry() -> Self {
+        Self::new(Method::Get, "/api/registry")
+    }
+
+    pub(crate) fn schemas() -> Self {
+        Self::new(Method::Get, "/api/schemas")
+    }
+
+    pub(crate) fn create_processor(request: &impl Serialize) -> Self {
+        Self::new(Method::Post, "/api/processor").with_body(request)
+    }
+
+    pub(crate) fn remove_processor(processor_id: &str) -> Self {
+        Self::new(
+            Method::Delete,
+            format!("/api/processors/{}", segment(processor_id)),
+        )
+    }
+
+    pub(crate) fn update_processor_config(processor_id: &str, request: &impl Serialize) -> Self {
+        Self::new(
+            Method::Put,
+            format!("/api/processors/{}/config", segment(processor_id)),
+        )
+        .with_body(request)
+    }
+
+    pub(crate) fn pause_processor(processor_id: &str) -> Self {
+        Self::new(
+            Method::Post,
+            format!("/api/processors/{}/pause", segment(processor_id)),
+        )
+    }
+
+    pub(crate) fn resume_processor(processor_id: &str) -> Self {
+        Self::new(
+            Method::Post,
+            format!("/api/processors/{}/resume", segment(processor_id)),
+        )
+    }
+
+    pub(crate) fn connect(request: &impl Serialize) -> Self {
+        Self::new(Method::Post, "/api/connections").with_body(request)
+    }
+
+    pub(crate) fn disconnect(link_id: &str) -> Self {
+        Self::new(
+            Method::Delete,
+            format!("/api/connections/{}", segment(link_id)),
+        )
+    }
+
+    /// `Ok(())` when `status` is an answer for this endpoint, the server's
+    /// error otherwise.
+    pub(crate) fn check(&self, url: &str, status: u16, body: &str) -> Result<()> {
+        if (200..300).contains(&status) || (self.probe && status == 503) {
+            return Ok(());
+        }
+        Err(ClientError::status_from_body(
+            self.method.as_str(),
+            url.to_string(),
+            status,
+            body,
+        ))
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(&self, url: &str, body: &str) -> Result<T> {
+        serde_json::from_str(body).map_err(|source| ClientError::Decode {
+            method: self.method.as_str(),
+            url: url.to_string(),
+            source,
+        })
+    }
+}
+
+/// A validated `http(s)://host[:port][/prefix]` base, without a trailing
+/// slash.
+#[derive(Debug, Clone)]
+pub(crate) struct BaseUrl(String);
+
+impl BaseUrl {
+    pub(crate) fn parse(base_url: &str) -> Result<Self> {
+        let trimmed = base_url.trim().trim_end_matches('/');
+        let host = trimmed
+            .strip_prefix("http://")
+            .or_else(|| trimmed.strip_prefix("https://"));
+        match host {
+            Some(host) if !host.is_empty() => Ok(Self(trimmed.to_string())),
+            _ => Err(ClientError::InvalidUrl(base_url.to_string())),
+        }
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.0, path)
+    }
+
+    /// The event stream URL: the same host on `ws://` / `wss://`.
+    pub(crate) fn events_url(&self) -> String {
+        let url = self.url(EVENTS_PATH);
+        match url.strip_prefix("https://") {
+            Some(rest) => format!("wss://{rest}"),
+            None => format!("ws://{}", url.trim_start_matches("http://")),
+        }
+    }
+}
+
+/// Percent-encode an id for use as one path segment. Ids the runtime mints
+/// are already URL-safe; user-chosen ones may not be.
+fn segment(id: &str) -> String {
+    let mut encoded = String::with_capacity(id.len());
+    for byte in id.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_urls_are_validated_and_mapped_to_the_event_stream() {
+        let base = BaseUrl::parse("http://127.0.0.1:9000/").unwrap();
+        assert_eq!(base.url("/api/graph"), "http://127.0.0.1:9000/api/graph");
+        assert_eq!(base.events_url(), "ws://127.0.0.1:9000/ws/events");
+
+        let tls = BaseUrl::parse("https://node.example/streamlib").unwrap();
+        assert_eq!(tls.events_url(), "wss://node.example/streamlib/ws/events");
+
+        for invalid in ["127.0.0.1:9000", "ftp://node", "http://"] {
+            assert!(matches!(
+                BaseUrl::parse(invalid),
+                Err(ClientError::InvalidUrl(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn ids_are_escaped_as_single_path_segments() {
+        assert_eq!(
+            Endpoint::pause_processor("camera_1").path,
+            "/api/processors/camera_1/pause"
+        );
+        assert_eq!(
+            Endpoint::disconnect("a/b c").path,
+            "/api/connections/a%2Fb%20c"
+        );
+    }
+
+    #[test]
+    fn probes_accept_503_and_other_endpoints_do_not() {
+        let body = r#"{"ok":false,"checks":[]}"#;
+        assert!(Endpoint::ready().check("u", 503, body).is_ok());
+        assert!(Endpoint::live().check("u", 503, body).is_ok());
+        assert_eq!(
+            Endpoint::graph()
+                .check("u", 503, body)
+                .unwrap_err()
+                .status(),
+            Some(503)
+        );
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use streamlib_processor_schema::runtime_api::{
+    CreateConnectionRequest, CreateProcessorRequest, GraphResponse, IdResponse, ProbeResponse,
+    RegistryResponse, UpdateProcessorConfigRequest,
+};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::DEFAULT_TIMEOUT;
+use crate::api::{BaseUrl, Endpoint, Method};
+use crate::error::{ClientError, Result};
+use crate::events::{EventStreamItem, ReconnectPolicy, RuntimeEventMessage};
+use crate::metrics::GraphMetrics;
+
+/// Client for a runtime's control plane on tokio. Cheap to clone; clones
+/// share one connection pool.
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    base: BaseUrl,
+    http: reqwest::Client,
+    timeout: Duration,
+    bearer_token: Option<String>,
+    reconnect: ReconnectPolicy,
+}
+
+impl AsyncClient {
+    /// `base_url` is the api-server root, e.g. `http://127.0.0.1:9000`.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            base: BaseUrl::parse(base_url)?,
+            http: reqwest::Client::new(),
+            timeout: DEFAULT_TIMEOUT,
+            bearer_token: None,
+            reconnect: ReconnectPolicy::default(),
+        })
+    }
+
+    /// Present `token` on every request; required for the mutating routes
+    /// when the server runs with `require_auth`.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Per-request timeout. Doesn't apply to event streams.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    pub async fn health(&self) -> Result<()> {
+        self.send(&Endpoint::health()).await.map(drop)
+    }
+
+    /// Readiness report; `ok` is false (not an error) while the pipeline
+    /// isn't ready.
+    pub async fn ready(&self) -> Result<ProbeResponse> {
+        self.json(&Endpoint::ready()).await
+    }
+
+    pub async fn live(&self) -> Result<ProbeResponse> {
+        self.json(&Endpoint::live()).await
+    }
+
+    pub async fn graph(&self) -> Result<GraphResponse> {
+        self.json(&Endpoint::graph()).await
+    }
+
+    /// Metrics of every processor and link, from one graph read.
+    pub async fn metrics(&self) -> Result<GraphMetrics> {
+        self.graph().await.map(|graph| GraphMetrics::from(&graph))
+    }
+
+    pub async fn registry(&self) -> Result<RegistryResponse> {
+        self.json(&Endpoint::registry()).await
+    }
+
+    /// Canonical idents of every registered schema.
+    pub async fn schemas(&self) -> Result<Vec<String>> {
+        self.json(&Endpoint::schemas()).await
+    }
+
+    /// Add a processor; returns its id.
+    pub async fn create_processor(&self, request: &CreateProcessorRequest) -> Result<String> {
+        self.json::<IdResponse>(&Endpoint::create_processor(request))
+            .await
+            .map(|response| response.id)
+    }
+
+    pub async fn remove_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::remove_processor(processor_id))
+            .await
+            .map(drop)
+    }
+
+    pub async fn update_processor_config(
+        &self,
+        processor_id: &str,
+        request: &UpdateProcessorConfigRequest,
+    ) -> Result<()> {
+        self.send(&Endpoint::update_processor_config(processor_id, request))
+            .await
+            .map(drop)
+    }
+
+    pub async fn pause_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::pause_processor(processor_id))
+            .await
+            .map(drop)
+    }
+
+    pub async fn resume_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::resume_processor(processor_id))
+            .await
+            .map(drop)
+    }
+
+    /// Link two ports; returns the link id.
+    pub async fn connect(&self, request: &CreateConnectionRequest) -> Result<String> {
+        self.json::<IdResponse>(&Endpoint::connect(request))
+            .await
+            .map(|response| response.id)
+    }
+
+    pub async fn disconnect(&self, link_id: &str) -> Result<()> {
+        self.send(&Endpoint::disconnect(link_id)).await.map(drop)
+    }
+
+    /// Open the event stream. Fails if the first connection can't be made;
+    /// later drops are reconnected per the client's [`ReconnectPolicy`].
+    pub async fn events(&self) -> Result<AsyncEventStream> {
+        let url = self.base.events_url();
+        let socket = open_socket(&url).await?;
+        Ok(AsyncEventStream {
+            url,
+            policy: self.reconnect,
+            socket: Some(socket),
+            ended: false,
+        })
+    }
+
+    async fn json<T: DeserializeOwned>(&self, endpoint: &Endpoint) -> Result<T> {
+        let (url, body) = self.send(endpoint).await?;
+        endpoint.decode(&url, &body)
+    }
+
+    /// Send `endpoint` and return the URL and body of an accepted response.
+    async fn send(&self, endpoint: &Endpoint) -> Result<(String, String)> {
+        let method = endpoint.method.as_str();
+        let url = self.base.url(&endpoint.path);
+        let transport = |error: reqwest::Error| ClientError::Transport {
+            method,
+            url: url.clone(),
+            message: error.to_string(),
+        };
+        let mut request = self
+            .http
+            .request(reqwest_method(endpoint.method), &url)
+            .timeout(self.timeout);
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = &endpoint.body {
+            request = request.json(body);
+        }
+        let response = request.send().await.map_err(transport)?;
+        let status = response.status().as_u16();
+        let body = response.text().await.map_err(transport)?;
+        endpoint.check(&url, status, &body)?;
+        Ok((url, body))
+    }
+}
+
+fn reqwest_method(method: Method) -> reqwest::Method {
+    match method {
+        Method::Get => reqwest::Method::GET,
+        Method::Post => reqwest::Method::POST,
+        Method::Put => reqwest::Method::PUT,
+        Method::Delete => reqwest::Method::DELETE,
+    }
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn open_socket(url: &str) -> Result<Socket> {
+    tokio_tungstenite::connect_async(url)
+        .await
+        .map(|(socket, _)| socket)
+        .map_err(|error| ClientError::WebSocket {
+            url: url.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// The runtime's event stream on tokio. Yields
+/// [`EventStreamItem::Reconnected`] after re-establishing a dropped
+/// connection, and ends after yielding the error that exhausted the
+/// reconnect policy.
+pub struct AsyncEventStream {
+    url: String,
+    policy: ReconnectPolicy,
+    socket: Option<Socket>,
+    ended: bool,
+}
+
+impl AsyncEventStream {
+    /// The next event, or `None` once the stream has ended.
+    pub async fn next(&mut self) -> Option<Result<EventStreamItem>> {
+        loop {
+            if self.ended {
+                return None;
+            }
+            let Some(socket) = &mut self.socket else {
+                let reconnected = self.reconnect().await;
+                self.ended = reconnected.is_err();
+                return Some(reconnected);
+            };
+            match socket.next().await {
+                Some(Ok(Message::Text(text))) => match RuntimeEventMessage::parse(&text) {
+                    Ok(event) => return Some(Ok(EventStreamItem::Event(event))),
+                    Err(error) => {
+                        tracing::warn!(url = %self.url, %error, "skipping undecodable event");
+                    }
+                },
+                Some(Ok(Message::Close(_))) | None => {
+                    tracing::warn!(url = %self.url, "event stream closed by server");
+                    self.socket = None;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(error)) => {
+                    tracing::warn!(url = %self.url, %error, "event stream dropped");
+                    self.socket = None;
+                }
+            }
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<EventStreamItem> {
+        let mut attempt = 1;
+        loop {
+            let Some(delay) = self.policy.delay(attempt) else {
+                return Err(ClientError::WebSocket {
+                    url: self.url.clone(),
+                    message: format!("connection lost; gave up after {} attempts", attempt - 1),
+                });
+            };
+            tokio::time::sleep(delay).await;
+            match open_socket(&self.url).await {
+                Ok(socket) => {
+                    tracing::info!(url = %self.url, attempt, "event stream reconnected");
+                    self.socket = Some(socket);
+                    return Ok(EventStreamItem::Reconnected { attempts: attempt });
+                }
+                Err(error) => {
+                    tracing::debug!(
+                        url = %self.url,
+                        attempt,
+                        %error,
+                        "event stream reconnect failed"
+                    );
+                }
+            }
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::net::TcpStream;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use streamlib_processor_schema::runtime_api::{
+    CreateConnectionRequest, CreateProcessorRequest, GraphResponse, IdResponse, ProbeResponse,
+    RegistryResponse, UpdateProcessorConfigRequest,
+};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::DEFAULT_TIMEOUT;
+use crate::api::{BaseUrl, Endpoint};
+use crate::error::{ClientError, Result};
+use crate::events::{EventStreamItem, ReconnectPolicy, RuntimeEventMessage};
+use crate::metrics::GraphMetrics;
+
+/// Client for a runtime's control plane that blocks the calling thread.
+#[derive(Debug, Clone)]
+pub struct BlockingClient {
+    base: BaseUrl,
+    agent: ureq::Agent,
+    bearer_token: Option<String>,
+    reconnect: ReconnectPolicy,
+}
+
+impl BlockingClient {
+    /// `base_url` is the api-server root, e.g. `http://127.0.0.1:9000`.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            base: BaseUrl::parse(base_url)?,
+            agent: agent(DEFAULT_TIMEOUT),
+            bearer_token: None,
+            reconnect: ReconnectPolicy::default(),
+        })
+    }
+
+    /// Present `token` on every request; required for the mutating routes
+    /// when the server runs with `require_auth`.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Per-request timeout. Doesn't apply to event streams.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = agent(timeout);
+        self
+    }
+
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    pub fn health(&self) -> Result<()> {
+        self.send(&Endpoint::health()).map(drop)
+    }
+
+    /// Readiness report; `ok` is false (not an error) while the pipeline
+    /// isn't ready.
+    pub fn ready(&self) -> Result<ProbeResponse> {
+        self.json(&Endpoint::ready())
+    }
+
+    pub fn live(&self) -> Result<ProbeResponse> {
+        self.json(&Endpoint::live())
+    }
+
+    pub fn graph(&self) -> Result<GraphResponse> {
+        self.json(&Endpoint::graph())
+    }
+
+    /// Metrics of every processor and link, from one graph read.
+    pub fn metrics(&self) -> Result<GraphMetrics> {
+        self.graph().map(|graph| GraphMetrics::from(&graph))
+    }
+
+    pub fn registry(&self) -> Result<RegistryResponse> {
+        self.json(&Endpoint::registry())
+    }
+
+    /// Canonical idents of every registered schema.
+    pub fn schemas(&self) -> Result<Vec<String>> {
+        self.json(&Endpoint::schemas())
+    }
+
+    /// Add a processor; returns its id.
+    pub fn create_processor(&self, request: &CreateProcessorRequest) -> Result<String> {
+        self.json::<IdResponse>(&Endpoint::create_processor(request))
+            .map(|response| response.id)
+    }
+
+    pub fn remove_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::remove_processor(processor_id))
+            .map(drop)
+    }
+
+    pub fn update_processor_config(
+        &self,
+        processor_id: &str,
+        request: &UpdateProcessorConfigRequest,
+    ) -> Result<()> {
+        self.send(&Endpoint::update_processor_config(processor_id, request))
+            .map(drop)
+    }
+
+    pub fn pause_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::pause_processor(processor_id))
+            .map(drop)
+    }
+
+    pub fn resume_processor(&self, processor_id: &str) -> Result<()> {
+        self.send(&Endpoint::resume_processor(processor_id))
+            .map(drop)
+    }
+
+    /// Link two ports; returns the link id.
+    pub fn connect(&self, request: &CreateConnectionRequest) -> Result<String> {
+        self.json::<IdResponse>(&Endpoint::connect(request))
+            .map(|response| response.id)
+    }
+
+    pub fn disconnect(&self, link_id: &str) -> Result<()> {
+        self.send(&Endpoint::disconnect(link_id)).map(drop)
+    }
+
+    /// Open the event stream. Fails if the first connection can't be made;
+    /// later drops are reconnected per the client's [`ReconnectPolicy`].
+    pub fn events(&self) -> Result<BlockingEventStream> {
+        let url = self.base.events_url();
+        let socket = open_socket(&url)?;
+        Ok(BlockingEventStream {
+            url,
+            policy: self.reconnect,
+            socket: Some(socket),
+            ended: false,
+        })
+    }
+
+    fn json<T: DeserializeOwned>(&self, endpoint: &Endpoint) -> Result<T> {
+        let (url, body) = self.send(endpoint)?;
+        endpoint.decode(&url, &body)
+    }
+
+    /// Send `endpoint` and return the URL and body of an accepted response.
+    fn send(&self, endpoint: &Endpoint) -> Result<(String, String)> {
+        let method = endpoint.method.as_str();
+        let url = self.base.url(&endpoint.path);
+        let mut request = self.agent.request(method, &url);
+        if let Some(token) = &self.bearer_token {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+        let response = match &endpoint.body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(transport)) => {
+                return Err(ClientError::Transport {
+                    method,
+                    url,
+                    message: transport.to_string(),
+                });
+            }
+        };
+        let status = response.status();
+        let body = response
+            .into_string()
+            .map_err(|error| ClientError::Transport {
+                method,
+                url: url.clone(),
+                message: error.to_string(),
+            })?;
+        endpoint.check(&url, status, &body)?;
+        Ok((url, body))
+    }
+}
+
+fn agent(timeout: Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(timeout).build()
+}
+
+type Socket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+fn open_socket(url: &str) -> Result<Socket> {
+    tungstenite::connect(url)
+        .map(|(socket, _)| socket)
+        .map_err(|error| ClientError::WebSocket {
+            url: url.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// The runtime's event stream as an iterator. Yields
+/// [`EventStreamItem::Reconnected`] after re-establishing a dropped
+/// connection, and ends after yielding the error that exhausted the
+/// reconnect policy.
+pub struct BlockingEventStream {
+    url: String,
+    policy: ReconnectPolicy,
+    socket: Option<Socket>,
+    ended: bool,
+}
+
+impl BlockingEventStream {
+    fn reconnect(&mut self) -> Result<EventStreamItem> {
+        let mut attempt = 1;
+        loop {
+            let Some(delay) = self.policy.delay(attempt) else {
+                return Err(ClientError::WebSocket {
+                    url: self.url.clone(),
+                    message: format!("connection lost; gave up after {} attempts", attempt - 1),
+                });
+            };
+            std::thread::sleep(delay);
+            match open_socket(&self.url) {
+                Ok(socket) => {
+                    tracing::info!(url = %self.url, attempt, "event stream reconnected");
+                    self.socket = Some(socket);
+                    return Ok(EventStreamItem::Reconnected { attempts: attempt });
+                }
+                Err(error) => {
+                    tracing::debug!(
+                        url = %self.url,
+                        attempt,
+                        %error,
+                        "event stream reconnect failed"
+                    );
+                }
+            }
+            attempt += 1;
+        }
+    }
+}
+
+impl Iterator for BlockingEventStream {
+    type Item = Result<EventStreamItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.ended {
+                return None;
+            }
+            let Some(socket) = &mut self.socket else {
+                let reconnected = self.reconnect();
+                self.ended = reconnected.is_err();
+                return Some(reconnected);
+            };
+            match socket.read() {
+                Ok(Message::Text(text)) => match RuntimeEventMessage::parse(&text) {
+                    Ok(event) => return Some(Ok(EventStreamItem::Event(event))),
+                    Err(error) => {
+                        tracing::warn!(url = %self.url, %error, "skipping undecodable event");
+                    }
+                },
+                Ok(Message::Close(_)) => {
+                    tracing::warn!(url = %self.url, "event stream closed by server");
+                    self.socket = None;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    tracing::warn!(url = %self.url, %error, "event stream dropped");
+                    self.socket = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    use super::*;
+
+    /// Serve one canned `(status line, body)` per connection, returning
+    /// each request's head and body as received.
+    fn serve_http(
+        responses: Vec<(&'static str, &'static str)>,
+    ) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                request.push_str(&String::from_utf8(payload).unwrap());
+                requests.push(request);
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn requests_carry_the_token_and_responses_decode() {
+        let (base_url, server) = serve_http(vec![
+            ("200 OK", r#"{"id":"link_7"}"#),
+            ("204 No Content", ""),
+            (
+                "503 Service Unavailable",
+                r#"{"ok":false,"checks":[{"name":"processor camera","ok":false,"detail":"Pending"}]}"#,
+            ),
+            (
+                "404 Not Found",
+                r#"{"error":"Processor 'ghost' not found"}"#,
+            ),
+        ]);
+        let client = BlockingClient::new(&base_url)
+            .unwrap()
+            .with_bearer_token("secret");
+
+        let link_id = client
+            .connect(&CreateConnectionRequest {
+                from_processor: "camera".to_string(),
+                from_port: "video".to_string(),
+                to_processor: "display".to_string(),
+                to_port: "video".to_string(),
+            })
+            .unwrap();
+        assert_eq!(link_id, "link_7");
+        client.pause_processor("camera").unwrap();
+        let ready = client.ready().unwrap();
+        assert!(!ready.ok);
+        assert_eq!(ready.checks[0].detail.as_deref(), Some("Pending"));
+        let error = client.remove_processor("ghost").unwrap_err();
+        assert_eq!(error.status(), Some(404));
+        assert!(error.to_string().ends_with("Processor 'ghost' not found"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /api/connections HTTP/1.1"));
+        assert!(
+            requests[0]
+                .to_ascii_lowercase()
+                .contains("authorization: bearer secret")
+        );
+        assert!(requests[0].contains(r#""from_processor":"camera""#));
+        assert!(requests[0].contains(r#""to_processor":"display""#));
+        assert!(requests[1].starts_with("POST /api/processors/camera/pause HTTP/1.1"));
+        assert!(requests[2].starts_with("GET /ready HTTP/1.1"));
+        assert!(requests[3].starts_with("DELETE /api/processors/ghost HTTP/1.1"));
+    }
+
+    #[test]
+    fn the_event_stream_reconnects_after_the_server_drops_it() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            for event in [
+                r#"{"RuntimeGlobal":"RuntimeStarted"}"#,
+                r#"{"ProcessorEvent":{"processor_id":"camera","event":"Started"}}"#,
+            ] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut socket = tungstenite::accept(stream).unwrap();
+                socket.send(Message::text("not an event")).unwrap();
+                socket.send(Message::text(event)).unwrap();
+                socket.close(None).unwrap();
+                while socket.read().is_ok() {}
+            }
+        });
+        let client = BlockingClient::new(&base_url)
+            .unwrap()
+            .with_reconnect_policy(ReconnectPolicy {
+                initial_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(10),
+                max_attempts: Some(2),
+            });
+
+        let items: Vec<_> = client.events().unwrap().collect();
+        server.join().unwrap();
+
+        assert_eq!(items.len(), 4, "{items:?}");
+        assert!(matches!(
+            &items[0],
+            Ok(EventStreamItem::Event(event)) if event.kind() == "RuntimeStarted"
+        ));
+        assert!(matches!(
+            items[1],
+            Ok(EventStreamItem::Reconnected { attempts: 1 })
+        ));
+        assert!(matches!(
+            &items[2],
+            Ok(EventStreamItem::Event(RuntimeEventMessage::Processor { processor_id, .. }))
+                if processor_id == "camera"
+        ));
+        assert!(matches!(items[3], Err(ClientError::WebSocket { .. })));
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The server answered with a status the endpoint doesn't succeed on.
+    /// `message` is the server's `{"error": ...}` text when the body carries
+    /// one, the raw body otherwise.
+    #[error("{method} {url} returned {status}: {message}")]
+    Status {
+        method: &'static str,
+        url: String,
+        status: u16,
+        message: String,
+    },
+
+    /// The request never got a response: connect, TLS, or IO failure.
+    #[error("{method} {url} failed: {message}")]
+    Transport {
+        method: &'static str,
+        url: String,
+        message: String,
+    },
+
+    /// The response body didn't match the shape the endpoint documents —
+    /// usually a client built against a different server version.
+    #[error("{method} {url}: could not decode response: {source}")]
+    Decode {
+        method: &'static str,
+        url: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("event stream {url}: {message}")]
+    WebSocket { url: String, message: String },
+
+    #[error("invalid base URL {0:?}: expected http:// or https://")]
+    InvalidUrl(String),
+}
+
+impl ClientError {
+    /// HTTP status of a [`ClientError::Status`]; `None` for every other
+    /// variant.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn status_from_body(
+        method: &'static str,
+        url: String,
+        status: u16,
+        body: &str,
+    ) -> Self {
+        let message = match serde_json::from_str::<
+            streamlib_processor_schema::runtime_api::ErrorResponse,
+        >(body)
+        {
+            Ok(response) => response.error,
+            Err(_) if body.trim().is_empty() => "(empty body)".to_string(),
+            Err(_) => body.trim().to_string(),
+        };
+        Self::Status {
+            method,
+            url,
+            status,
+            message,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+#[cfg(all(test, any(feature = "blocking", feature = "async")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_errors_surface_the_server_message() {
+        let error = ClientError::status_from_body(
+            "POST",
+            "http://localhost:9000/api/connections".to_string(),
+            404,
+            r#"{"error":"Processor 'camera' not found","processor_id":"camera"}"#,
+        );
+        assert_eq!(error.status(), Some(404));
+        assert_eq!(
+            error.to_string(),
+            "POST http://localhost:9000/api/connections returned 404: Processor 'camera' not found"
+        );
+
+        let plain = ClientError::status_from_body("GET", "u".to_string(), 502, "Bad Gateway\n");
+        assert!(plain.to_string().ends_with("502: Bad Gateway"));
+    }
+}
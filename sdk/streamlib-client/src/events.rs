@@ -0,0 +1,220 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `/ws/events` messages and the reconnect policy both event streams share.
+//!
+//! The server forwards every pubsub `Event` as JSON text. Its payload types
+//! live in the engine, so the client types the envelope — which kind of
+//! event, which processor, which topic — and hands the variant body over as
+//! JSON. A client built against an older server keeps decoding new variants.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// One event off the runtime's event stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeEventMessage {
+    /// Runtime-wide lifecycle, graph-change and input events:
+    /// `RuntimeStarted`, `RuntimeDidAddProcessor`, `RuntimeDidConnect`, ...
+    Runtime { kind: String, data: Value },
+    /// Lifecycle and state of one processor: `Started`, `Error`,
+    /// `StateChanged`, ...
+    Processor {
+        processor_id: String,
+        kind: String,
+        data: Value,
+    },
+    /// An event published on an application topic.
+    Custom { topic: String, data: Value },
+}
+
+#[derive(Deserialize)]
+enum WireEvent {
+    RuntimeGlobal(Value),
+    ProcessorEvent { processor_id: String, event: Value },
+    Custom { topic: String, data: Value },
+}
+
+impl RuntimeEventMessage {
+    pub fn parse(text: &str) -> serde_json::Result<Self> {
+        Ok(match serde_json::from_str(text)? {
+            WireEvent::RuntimeGlobal(event) => {
+                let (kind, data) = split_variant(event)?;
+                Self::Runtime { kind, data }
+            }
+            WireEvent::ProcessorEvent {
+                processor_id,
+                event,
+            } => {
+                let (kind, data) = split_variant(event)?;
+                Self::Processor {
+                    processor_id,
+                    kind,
+                    data,
+                }
+            }
+            WireEvent::Custom { topic, data } => Self::Custom { topic, data },
+        })
+    }
+
+    /// The variant name, or the topic of a custom event.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Runtime { kind, .. } | Self::Processor { kind, .. } => kind,
+            Self::Custom { topic, .. } => topic,
+        }
+    }
+
+    /// The variant body; `Null` for unit variants like `RuntimeStarted`.
+    pub fn data(&self) -> &Value {
+        match self {
+            Self::Runtime { data, .. }
+            | Self::Processor { data, .. }
+            | Self::Custom { data, .. } => data,
+        }
+    }
+
+    /// Whether this event changes the graph's shape, i.e. a cached
+    /// [`GraphResponse`](crate::GraphResponse) is stale.
+    pub fn is_graph_change(&self) -> bool {
+        matches!(self, Self::Runtime { kind, .. } if kind.starts_with("RuntimeDid"))
+    }
+}
+
+/// An externally tagged enum value: `"Unit"` or `{"Variant": body}`.
+fn split_variant(value: Value) -> serde_json::Result<(String, Value)> {
+    use serde::de::Error as _;
+    match value {
+        Value::String(kind) => Ok((kind, Value::Null)),
+        Value::Object(map) if map.len() == 1 => Ok(map.into_iter().next().unwrap_or_default()),
+        other => Err(serde_json::Error::custom(format!(
+            "expected an enum variant, got {other}"
+        ))),
+    }
+}
+
+/// What an event stream yields.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventStreamItem {
+    Event(RuntimeEventMessage),
+    /// The connection dropped and was re-established. Events published in
+    /// between are lost; re-read the graph to catch up.
+    Reconnected {
+        attempts: u32,
+    },
+}
+
+/// How an event stream re-establishes a dropped connection: exponential
+/// backoff from `initial_delay`, capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Consecutive failed attempts before the stream gives up and ends;
+    /// `None` retries forever, `Some(0)` never reconnects.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Fail on the first dropped connection instead of reconnecting.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Delay before reconnect attempt `attempt` (1-based), or `None` once
+    /// the policy is exhausted.
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| attempt > max) {
+            return None;
+        }
+        let doublings = attempt.saturating_sub(1).min(16);
+        Some(
+            self.initial_delay
+                .saturating_mul(1 << doublings)
+                .min(self.max_delay),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_event_envelope_parses() {
+        let started = RuntimeEventMessage::parse(r#"{"RuntimeGlobal":"RuntimeStarted"}"#).unwrap();
+        assert_eq!(started.kind(), "RuntimeStarted");
+        assert_eq!(started.data(), &Value::Null);
+        assert!(!started.is_graph_change());
+
+        let added = RuntimeEventMessage::parse(
+            r#"{"RuntimeGlobal":{"RuntimeDidAddProcessor":{"processor_id":"camera"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(added.kind(), "RuntimeDidAddProcessor");
+        assert_eq!(added.data()["processor_id"], "camera");
+        assert!(added.is_graph_change());
+
+        let error = RuntimeEventMessage::parse(
+            r#"{"ProcessorEvent":{"processor_id":"camera","event":{"Error":"device lost"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            error,
+            RuntimeEventMessage::Processor {
+                processor_id: "camera".to_string(),
+                kind: "Error".to_string(),
+                data: Value::from("device lost"),
+            }
+        );
+
+        let custom =
+            RuntimeEventMessage::parse(r#"{"Custom":{"topic":"scores","data":{"home":2}}}"#)
+                .unwrap();
+        assert_eq!(custom.kind(), "scores");
+
+        assert!(RuntimeEventMessage::parse(r#"{"RuntimeGlobal":[1,2]}"#).is_err());
+        assert!(RuntimeEventMessage::parse(r#"{"Unknown":{}}"#).is_err());
+    }
+
+    #[test]
+    fn reconnect_delays_back_off_and_give_up() {
+        let policy = ReconnectPolicy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            max_attempts: Some(4),
+        };
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.delay(attempt)).collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(400)),
+                Some(Duration::from_millis(500)),
+                None,
+            ]
+        );
+
+        assert_eq!(ReconnectPolicy::never().delay(1), None);
+        assert_eq!(
+            ReconnectPolicy::default().delay(1_000),
+            Some(Duration::from_secs(10))
+        );
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Typed client for a streamlib runtime's control plane — the REST and
+//! WebSocket API the api-server serves.
+//!
+//! Request and response types are the server's own, re-exported from
+//! `streamlib-processor-schema`, so a field the server adds or renames is a
+//! compile error here rather than a silent `null`.
+//!
+//! - [`BlockingClient`] (feature `blocking`, default) runs on the calling
+//!   thread; its [`BlockingEventStream`] is an iterator.
+//! - [`AsyncClient`] (feature `async`) runs on tokio; its
+//!   [`AsyncEventStream`] is polled with `next().await`.
+//!
+//! Both event streams reconnect after a dropped connection per a
+//! [`ReconnectPolicy`] and report it with [`EventStreamItem::Reconnected`].
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+mod api;
+mod error;
+mod events;
+mod metrics;
+
+#[cfg(feature = "async")]
+mod async_client;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+#[cfg(feature = "async")]
+pub use async_client::{AsyncClient, AsyncEventStream};
+#[cfg(feature = "blocking")]
+pub use blocking::{BlockingClient, BlockingEventStream};
+pub use error::{ClientError, Result};
+pub use events::{EventStreamItem, ReconnectPolicy, RuntimeEventMessage};
+pub use metrics::GraphMetrics;
+pub use streamlib_processor_schema::runtime_api::*;
+pub use streamlib_processor_schema::{SchemaIdentOutput, SemanticVersionOutput};
+
+/// Per-request timeout unless the client sets its own.
+pub const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
@@ -0,0 +1,134 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::collections::BTreeMap;
+
+use streamlib_processor_schema::runtime_api::{
+    GpuPoolStatsOutput, GraphResponse, LinkFrameDropsOutput, ProcessorMetricsOutput,
+};
+
+/// Per-processor and per-link metrics pulled out of one graph read. Nodes
+/// and links that haven't published metrics yet are absent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMetrics {
+    /// Keyed by processor id.
+    pub processors: BTreeMap<String, ProcessorMetricsOutput>,
+    /// Keyed by link id.
+    pub links: BTreeMap<String, LinkFrameDropsOutput>,
+    /// The runtime's GPU texture pool; `None` until the runtime started.
+    pub gpu_pool: Option<GpuPoolStatsOutput>,
+}
+
+impl GraphMetrics {
+    /// Links whose recent drop rate marks them congested.
+    pub fn congested_links(&self) -> impl Iterator<Item = (&str, &LinkFrameDropsOutput)> {
+        self.links
+            .iter()
+            .filter(|(_, drops)| drops.congested)
+            .map(|(id, drops)| (id.as_str(), drops))
+    }
+}
+
+impl From<&GraphResponse> for GraphMetrics {
+    fn from(graph: &GraphResponse) -> Self {
+        Self {
+            processors: graph
+                .nodes
+                .iter()
+                .filter_map(|node| Some((node.id.clone(), node.metrics()?)))
+                .collect(),
+            links: graph
+                .links
+                .iter()
+                .filter_map(|link| Some((link.id.clone(), link.frame_drops()?)))
+                .collect(),
+            gpu_pool: graph.gpu_pool(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, components: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": {
+                "org": "tatolab",
+                "package": "core",
+                "type": "Node",
+                "version": { "major": 1, "minor": 0, "patch": 0 }
+            },
+            "display_name": id,
+            "config": null,
+            "config_checksum": 0,
+            "ports": { "inputs": [], "outputs": [] },
+            "components": components
+        })
+    }
+
+    #[test]
+    fn metrics_are_collected_from_node_and_link_components() {
+        let graph: GraphResponse = serde_json::from_value(serde_json::json!({
+            "nodes": [
+                node("camera", serde_json::json!({
+                    "metrics": {
+                        "throughput_fps": 30.0,
+                        "latency_p50_ms": 1.5,
+                        "latency_p99_ms": 4.0,
+                        "frames_processed": 900,
+                        "frames_dropped": 2
+                    }
+                })),
+                node("display", serde_json::json!({})),
+            ],
+            "links": [
+                {
+                    "id": "link_0",
+                    "source": { "processor_id": "camera", "port_name": "video" },
+                    "target": { "processor_id": "display", "port_name": "video" },
+                    "capacity": 4,
+                    "state": "wired",
+                    "components": {
+                        "frame_drops": {
+                            "frames_delivered": 898,
+                            "frames_dropped": 2,
+                            "window_secs": 10,
+                            "window_frames_dropped": 2,
+                            "window_drop_rate": 0.01,
+                            "congested": true
+                        }
+                    }
+                }
+            ],
+            "components": {
+                "gpu_pool": {
+                    "total_textures": 12,
+                    "textures_in_use": 10,
+                    "capacity": 16,
+                    "occupancy": 0.625,
+                    "high_water_in_use": 14,
+                    "allocation_failures": 0,
+                    "exhausted_acquires": 0,
+                    "under_pressure": false
+                }
+            }
+        }))
+        .unwrap();
+
+        let metrics = GraphMetrics::from(&graph);
+
+        assert_eq!(metrics.processors.len(), 1);
+        assert_eq!(metrics.processors["camera"].frames_processed, 900);
+        assert_eq!(metrics.links["link_0"].frames_delivered, 898);
+        assert_eq!(
+            metrics
+                .congested_links()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            ["link_0"]
+        );
+        assert_eq!(metrics.gpu_pool.unwrap().high_water_in_use, 14);
+    }
+}
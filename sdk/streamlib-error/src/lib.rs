@@ -60,6 +60,9 @@ pub enum Error {
     #[error("Processor not found: {0}")]
     ProcessorNotFound(String),
 
+    #[error("{}", topology_message(.diagnostics))]
+    Topology { diagnostics: Vec<TopologyDiagnostic> },
+
     #[error("{}", unknown_processor_type_message(.ident))]
     UnknownProcessorType { ident: SchemaIdent },
 
@@ -160,6 +163,12 @@ pub enum Error {
     )]
     TapSlotOccupied(String),
 
+    #[error(
+        "remote runtime '{0}' is not reachable over its broker socket — it must be \
+         running on this host, as the same user, with the same XDG_RUNTIME_DIR"
+    )]
+    RemoteRuntimeUnreachable(String),
+
     #[error("Plugin host services unavailable: {0}")]
     PluginHostUnavailable(String),
 
@@ -227,6 +236,15 @@ pub enum Error {
         tier: ChannelTrustTierLabel,
     },
 
+    #[error("asset '{url}' could not be fetched: {detail}")]
+    AssetFetchFailed { url: String, detail: String },
+
+    #[error("asset '{0}' is not cached and asset fetching is offline")]
+    AssetOffline(String),
+
+    #[error("no font resolves for '{0}'")]
+    FontNotFound(String),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -251,6 +269,16 @@ fn unknown_processor_type_message(ident: &SchemaIdent) -> String {
     }
 }
 
+/// Render the [`Error::Topology`] message — one diagnostic per line.
+fn topology_message(diagnostics: &[TopologyDiagnostic]) -> String {
+    let mut message = format!("Invalid graph topology ({} issue(s)):", diagnostics.len());
+    for diagnostic in diagnostics {
+        message.push_str("\n  - ");
+        message.push_str(&diagnostic.to_string());
+    }
+    message
+}
+
 /// StreamLib result alias.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -273,6 +301,78 @@ impl std::fmt::Display for PortDirection {
     }
 }
 
+/// One structural problem found by the engine's topology analysis, carried
+/// by [`Error::Topology`]. Processor ids are the runtime-assigned ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopologyDiagnostic {
+    /// Wiring `from_processor:from_port -> to_processor:to_port` would close
+    /// a directed cycle. `cycle` lists the processors on the loop in
+    /// dataflow order, starting at `to_processor` and ending at
+    /// `from_processor`. Reactive processors on a cycle each wait on the
+    /// other's output, so the loop never starts.
+    Cycle {
+        from_processor: String,
+        from_port: String,
+        to_processor: String,
+        to_port: String,
+        cycle: Vec<String>,
+    },
+    /// A required input port has no incoming link.
+    UnsatisfiedRequiredInput {
+        processor_id: String,
+        port_name: String,
+    },
+    /// The producer's output schema and the consumer's input schema are both
+    /// concrete and differ, and no auto-converter bridges them.
+    SchemaMismatch {
+        from_processor: String,
+        from_port: String,
+        to_processor: String,
+        to_port: String,
+        producer_schema: String,
+        consumer_schema: String,
+    },
+}
+
+impl std::fmt::Display for TopologyDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle {
+                from_processor,
+                from_port,
+                to_processor,
+                to_port,
+                cycle,
+            } => write!(
+                f,
+                "link {from_processor}:{from_port} -> {to_processor}:{to_port} would \
+                 close a cycle ({} -> {to_processor})",
+                cycle.join(" -> ")
+            ),
+            Self::UnsatisfiedRequiredInput {
+                processor_id,
+                port_name,
+            } => write!(
+                f,
+                "required input port '{port_name}' on processor '{processor_id}' \
+                 has no incoming link"
+            ),
+            Self::SchemaMismatch {
+                from_processor,
+                from_port,
+                to_processor,
+                to_port,
+                producer_schema,
+                consumer_schema,
+            } => write!(
+                f,
+                "link {from_processor}:{from_port} -> {to_processor}:{to_port} carries \
+                 {producer_schema} into an input expecting {consumer_schema}"
+            ),
+        }
+    }
+}
+
 /// Trust tier of the iceoryx2 data channel a payload was refused on, named in
 /// [`Error::PayloadExceedsChannelCeiling`]. Mirrors the engine's
 /// `iceoryx2::ChannelTrustTier` at the error boundary so the ceiling error stays
@@ -358,4 +458,39 @@ mod tests {
         assert!(msg.contains("streamlib_modules/"), "message: {msg}");
         assert!(msg.contains("streamlib add"), "message: {msg}");
     }
+
+    #[test]
+    fn topology_lists_every_diagnostic() {
+        let msg = Error::Topology {
+            diagnostics: vec![
+                TopologyDiagnostic::Cycle {
+                    from_processor: "c".to_string(),
+                    from_port: "out".to_string(),
+                    to_processor: "a".to_string(),
+                    to_port: "in".to_string(),
+                    cycle: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                },
+                TopologyDiagnostic::UnsatisfiedRequiredInput {
+                    processor_id: "sink".to_string(),
+                    port_name: "video".to_string(),
+                },
+                TopologyDiagnostic::SchemaMismatch {
+                    from_processor: "camera".to_string(),
+                    from_port: "video".to_string(),
+                    to_processor: "speaker".to_string(),
+                    to_port: "audio".to_string(),
+                    producer_schema: "@tatolab/core/VideoFrame@1.0.0".to_string(),
+                    consumer_schema: "@tatolab/core/AudioFrame@1.0.0".to_string(),
+                },
+            ],
+        }
+        .to_string();
+        assert!(msg.contains("3 issue(s)"), "message: {msg}");
+        assert!(msg.contains("a -> b -> c -> a"), "message: {msg}");
+        assert!(msg.contains("'video' on processor 'sink'"), "message: {msg}");
+        assert!(
+            msg.contains("VideoFrame@1.0.0 into an input expecting"),
+            "message: {msg}"
+        );
+    }
 }
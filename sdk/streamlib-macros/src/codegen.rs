@@ -56,6 +56,14 @@ fn port_schema_spec_tokens(spec: &PortSchemaSpec) -> TokenStream {
     }
 }
 
+/// Emit the `Option<String>` literal for a port's `format` hint.
+fn port_format_tokens(format: Option<&str>) -> TokenStream {
+    match format {
+        Some(value) => quote! { ::std::option::Option::Some(#value.to_string()) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
 /// Generate a processor module from the attribute-declared [`ProcessorSchema`]
 /// and its fully-qualified [`SchemaIdent`]. Identity, execution, and ports are
 /// authored in the `#[processor(...)]` attribute — nothing here reads a file.
@@ -363,15 +371,20 @@ fn generate_processor_impl_from_schema(
 
     let from_config_body =
         generate_from_config_from_schema(schema, config_field_name, custom_fields);
-    let descriptor_impl =
-        generate_descriptor_from_schema(schema, description, &version, config_schema_id);
+    let descriptor_impl = generate_descriptor_from_schema(
+        schema,
+        description,
+        &version,
+        config_schema_id,
+        &execution_variant,
+    );
     let iceoryx2_accessors = generate_iceoryx2_accessors_from_schema(schema);
 
     let update_config = config_field_name.as_ref().map(|name| {
         quote! {
             fn update_config(&mut self, config: Self::Config) -> __streamlib_sdk::error::Result<()> {
                 self.#name = config;
-                Ok(())
+                <Self as #processor_trait>::on_config_update(self)
             }
         }
     });
@@ -580,6 +593,7 @@ fn generate_descriptor_from_schema(
     description: &str,
     version: &str,
     config_schema_id: Option<&str>,
+    execution_variant: &TokenStream,
 ) -> TokenStream {
     let _name = &schema.name; // PascalCase short name retained for identifier checks elsewhere
     let repository = "https://github.com/tatolab/streamlib";
@@ -596,14 +610,17 @@ fn generate_descriptor_from_schema(
                 Some(value) => quote! { ::std::option::Option::Some(#value.to_string()) },
                 None => quote! { ::std::option::Option::None },
             };
+            let format_tokens = port_format_tokens(p.format.as_deref());
+            let required = !p.optional;
             quote! {
                 .with_input(__streamlib_sdk::descriptors::PortDescriptor {
                     name: #port_name.to_string(),
                     description: #port_desc.to_string(),
                     schema: #port_schema_tokens,
-                    required: true,
+                    required: #required,
                     is_iceoryx2: true,
                     delivery_profile: #delivery_profile_tokens,
+                    format: #format_tokens,
                 })
             }
         })
@@ -617,6 +634,7 @@ fn generate_descriptor_from_schema(
             let port_name = &p.name;
             let port_schema_tokens = port_schema_spec_tokens(&p.schema);
             let port_desc = p.description.as_deref().unwrap_or("");
+            let format_tokens = port_format_tokens(p.format.as_deref());
             quote! {
                 .with_output(__streamlib_sdk::descriptors::PortDescriptor {
                     name: #port_name.to_string(),
@@ -625,6 +643,7 @@ fn generate_descriptor_from_schema(
                     required: true,
                     is_iceoryx2: true,
                     delivery_profile: ::std::option::Option::None,
+                    format: #format_tokens,
                 })
             }
         })
@@ -656,6 +675,7 @@ fn generate_descriptor_from_schema(
                 __streamlib_sdk::descriptors::ProcessorDescriptor::new(Processor::schema_ident(), #description)
                     .with_version(#version)
                     .with_repository(#repository)
+                    .with_execution(#execution_variant)
                     #config_schema
                     #scheduling
                     #(#ipc_input_ports)*
@@ -2047,6 +2047,42 @@ impl<'a> RuntimeContextFullAccess<'a> {
         &self.gpu_limited
     }
 
+    /// Restricted GPU capability on the adapter at `adapter_index` (see
+    /// `GpuContext::enumerate_adapters` on the host), for processors that
+    /// move frames between GPUs. The host opens that adapter on first use;
+    /// asking for this processor's own adapter returns an equivalent
+    /// handle. Routed through
+    /// [`RuntimeContextVTable::gpu_limited_access_for_adapter`].
+    pub fn gpu_limited_access_for_adapter(
+        &self,
+        adapter_index: u32,
+    ) -> Result<GpuContextLimitedAccess> {
+        let mut handle: *const c_void = std::ptr::null();
+        let mut err_buf = [0u8; 512];
+        let mut err_len: usize = 0;
+        // SAFETY: `handle` + `vtable` were paired by the host at
+        // construction; on success the host writes an owning handle the
+        // returned value's `Drop` releases.
+        let status = unsafe {
+            ((*self.vtable).gpu_limited_access_for_adapter)(
+                self.handle,
+                adapter_index,
+                &mut handle,
+                err_buf.as_mut_ptr(),
+                err_buf.len(),
+                &mut err_len as *mut usize,
+            )
+        };
+        if status != 0 || handle.is_null() {
+            let msg = String::from_utf8_lossy(&err_buf[..err_len.min(err_buf.len())]).into_owned();
+            return Err(Error::GpuError(msg));
+        }
+        Ok(GpuContextLimitedAccess {
+            handle,
+            vtable: crate::rhi::host_gpu_context_limited_access_vtable(),
+        })
+    }
+
     /// Runtime unique id as an owned [`String`]. Routed through the
     /// [`RuntimeContextVTable::runtime_id_copy`] slot.
     pub fn runtime_id(&self) -> String {
@@ -2093,6 +2129,212 @@ impl<'a> RuntimeContextFullAccess<'a> {
             .unwrap_or(std::ptr::null());
         AudioClockShim::from_ffi(handle, vtable)
     }
+
+    /// The runtime's asset cache — fetch LUTs, stills, fonts, and models
+    /// by URL. Fetches block for the download, so this lives on the
+    /// setup-time capability only. Routed through
+    /// [`RuntimeContextVTable::assets_fetch`].
+    pub fn assets(&self) -> AssetsShim<'a> {
+        AssetsShim {
+            handle: self.handle,
+            vtable: self.vtable,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The runtime's font registry — resolve the logical font names a
+    /// config carries to fallback chains of font files. URL sources may
+    /// download, so this lives on the setup-time capability only. Routed
+    /// through [`RuntimeContextVTable::fonts_resolve`].
+    pub fn fonts(&self) -> FontsShim<'a> {
+        FontsShim {
+            handle: self.handle,
+            vtable: self.vtable,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// =============================================================================
+// AssetsShim — cdylib arm
+// =============================================================================
+
+/// Borrow-scoped view of the host's asset cache, returned by
+/// [`RuntimeContextFullAccess::assets`]. Cdylib-arm twin of the engine's
+/// `AssetsShim`.
+pub struct AssetsShim<'a> {
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl AssetsShim<'_> {
+    /// Path of the cached copy of `url`, downloading it on first use.
+    /// Blocks for the download.
+    pub fn fetch(&self, url: &str) -> streamlib_error::Result<std::path::PathBuf> {
+        // SAFETY: `handle` + `vtable` were paired by the host at construction.
+        unsafe { vtable_assets_fetch(self.handle, self.vtable, url, None) }
+    }
+
+    /// Like [`Self::fetch`], but the content must hash to `sha256` (hex);
+    /// a cached copy with that digest is returned without a download.
+    pub fn fetch_pinned(
+        &self,
+        url: &str,
+        sha256: &str,
+    ) -> streamlib_error::Result<std::path::PathBuf> {
+        // SAFETY: see [`Self::fetch`].
+        unsafe { vtable_assets_fetch(self.handle, self.vtable, url, Some(sha256)) }
+    }
+}
+
+/// Turn the [`RuntimeContextVTable::assets_fetch`] completion callback into
+/// a `Result`. The host fires the completion synchronously, exactly once.
+///
+/// # Safety
+///
+/// `vtable` must point at a valid [`RuntimeContextVTable`] (layout v2+)
+/// paired with `handle`.
+unsafe fn vtable_assets_fetch(
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    url: &str,
+    sha256: Option<&str>,
+) -> streamlib_error::Result<std::path::PathBuf> {
+    use streamlib_error::Error;
+    use streamlib_plugin_abi::{ASSET_FETCH_OFFLINE, ASSET_FETCH_OK};
+
+    unsafe extern "C" fn record(
+        user_data: *mut c_void,
+        status: i32,
+        result_ptr: *const u8,
+        result_len: usize,
+    ) {
+        // SAFETY: `user_data` is the `Option<(i32, Vec<u8>)>` below; the
+        // payload bytes are valid for the duration of this call.
+        let slot = unsafe { &mut *(user_data as *mut Option<(i32, Vec<u8>)>) };
+        let payload = if result_ptr.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(result_ptr, result_len) }.to_vec()
+        };
+        *slot = Some((status, payload));
+    }
+
+    let (pin_ptr, pin_len) = sha256.map_or((std::ptr::null(), 0), |pin| (pin.as_ptr(), pin.len()));
+    let mut slot: Option<(i32, Vec<u8>)> = None;
+    unsafe {
+        ((*vtable).assets_fetch)(
+            handle,
+            url.as_ptr(),
+            url.len(),
+            pin_ptr,
+            pin_len,
+            record,
+            &mut slot as *mut Option<(i32, Vec<u8>)> as *mut c_void,
+        )
+    };
+    match slot {
+        Some((ASSET_FETCH_OK, payload)) => Ok(std::path::PathBuf::from(
+            String::from_utf8_lossy(&payload).into_owned(),
+        )),
+        Some((ASSET_FETCH_OFFLINE, _)) => Err(Error::AssetOffline(url.to_string())),
+        Some((_, payload)) => Err(Error::AssetFetchFailed {
+            url: url.to_string(),
+            detail: String::from_utf8_lossy(&payload).into_owned(),
+        }),
+        None => Err(Error::AssetFetchFailed {
+            url: url.to_string(),
+            detail: "host did not complete the fetch".to_string(),
+        }),
+    }
+}
+
+// =============================================================================
+// FontsShim — cdylib arm
+// =============================================================================
+
+/// One face of a resolved font fallback chain. Cdylib-arm twin of the
+/// engine's `FontFace`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FontFace {
+    pub path: std::path::PathBuf,
+    /// Face within a `.ttc` / `.otc` collection; 0 otherwise.
+    pub face_index: u32,
+}
+
+/// Borrow-scoped view of the host's font registry, returned by
+/// [`RuntimeContextFullAccess::fonts`]. Cdylib-arm twin of the engine's
+/// `FontsShim`.
+pub struct FontsShim<'a> {
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl FontsShim<'_> {
+    /// The fallback chain for logical font `name`, primary face first.
+    pub fn resolve(&self, name: &str) -> streamlib_error::Result<Vec<FontFace>> {
+        // SAFETY: `handle` + `vtable` were paired by the host at construction.
+        unsafe { vtable_fonts_resolve(self.handle, self.vtable, name) }
+    }
+}
+
+/// Turn the [`RuntimeContextVTable::fonts_resolve`] completion callback
+/// into a `Result`, parsing the `"<face_index>\t<path>"` payload lines.
+///
+/// # Safety
+///
+/// `vtable` must point at a valid [`RuntimeContextVTable`] (layout v3+)
+/// paired with `handle`.
+unsafe fn vtable_fonts_resolve(
+    handle: *const c_void,
+    vtable: *const RuntimeContextVTable,
+    name: &str,
+) -> streamlib_error::Result<Vec<FontFace>> {
+    use streamlib_error::Error;
+    use streamlib_plugin_abi::FONT_RESOLVE_OK;
+
+    unsafe extern "C" fn record(
+        user_data: *mut c_void,
+        status: i32,
+        result_ptr: *const u8,
+        result_len: usize,
+    ) {
+        // SAFETY: `user_data` is the `Option<(i32, Vec<u8>)>` below; the
+        // payload bytes are valid for the duration of this call.
+        let slot = unsafe { &mut *(user_data as *mut Option<(i32, Vec<u8>)>) };
+        let payload = if result_ptr.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(result_ptr, result_len) }.to_vec()
+        };
+        *slot = Some((status, payload));
+    }
+
+    let mut slot: Option<(i32, Vec<u8>)> = None;
+    unsafe {
+        ((*vtable).fonts_resolve)(
+            handle,
+            name.as_ptr(),
+            name.len(),
+            record,
+            &mut slot as *mut Option<(i32, Vec<u8>)> as *mut c_void,
+        )
+    };
+    match slot {
+        Some((FONT_RESOLVE_OK, payload)) => Ok(String::from_utf8_lossy(&payload)
+            .lines()
+            .filter_map(|line| {
+                let (index, path) = line.split_once('\t')?;
+                Some(FontFace {
+                    path: std::path::PathBuf::from(path),
+                    face_index: index.parse().ok()?,
+                })
+            })
+            .collect()),
+        _ => Err(Error::FontNotFound(name.to_string())),
+    }
 }
 
 // =============================================================================
@@ -2332,6 +2574,63 @@ mod runtime_context_id_dispatch_tests {
         std::ptr::null()
     }
 
+    /// Completes offline for a pinned fetch, otherwise echoes the URL back
+    /// as the cached path.
+    unsafe extern "C" fn stub_assets_fetch(
+        _ctx: *const c_void,
+        url_ptr: *const u8,
+        url_len: usize,
+        _sha256_ptr: *const u8,
+        sha256_len: usize,
+        completion: streamlib_plugin_abi::AssetFetchCompletionCallback,
+        user_data: *mut c_void,
+    ) {
+        let status = if sha256_len == 0 {
+            streamlib_plugin_abi::ASSET_FETCH_OK
+        } else {
+            streamlib_plugin_abi::ASSET_FETCH_OFFLINE
+        };
+        unsafe { completion(user_data, status, url_ptr, url_len) };
+    }
+
+    /// Resolves `"title"` to a two-face chain; every other name is not found.
+    unsafe extern "C" fn stub_fonts_resolve(
+        _ctx: *const c_void,
+        name_ptr: *const u8,
+        name_len: usize,
+        completion: streamlib_plugin_abi::FontResolveCompletionCallback,
+        user_data: *mut c_void,
+    ) {
+        let name = unsafe { std::slice::from_raw_parts(name_ptr, name_len) };
+        let (status, payload): (i32, &[u8]) = if name == b"title" {
+            (
+                streamlib_plugin_abi::FONT_RESOLVE_OK,
+                b"0\t/fonts/Title.otf\n2\t/fonts/Fallback.ttc",
+            )
+        } else {
+            (streamlib_plugin_abi::FONT_RESOLVE_NOT_FOUND, b"not found")
+        };
+        unsafe { completion(user_data, status, payload.as_ptr(), payload.len()) };
+    }
+
+    /// Only adapter 0 exists.
+    unsafe extern "C" fn stub_gpu_limited_access_for_adapter(
+        _ctx: *const c_void,
+        adapter_index: u32,
+        _out_handle: *mut *const c_void,
+        err_buf: *mut u8,
+        err_buf_cap: usize,
+        err_len: *mut usize,
+    ) -> i32 {
+        let msg = format!("no GPU adapter at index {adapter_index}");
+        let n = msg.len().min(err_buf_cap);
+        unsafe {
+            std::ptr::copy_nonoverlapping(msg.as_ptr(), err_buf, n);
+            *err_len = n;
+        }
+        1
+    }
+
     fn stub_vtable(
         runtime_id_copy: unsafe extern "C" fn(*const c_void, *mut u8, usize, *mut usize) -> usize,
         processor_id_copy: unsafe extern "C" fn(
@@ -2352,6 +2651,9 @@ mod runtime_context_id_dispatch_tests {
             gpu_limited_access: stub_opaque_handle,
             audio_clock_handle: stub_opaque_handle,
             runtime_ops_handle: stub_opaque_handle,
+            assets_fetch: stub_assets_fetch,
+            fonts_resolve: stub_fonts_resolve,
+            gpu_limited_access_for_adapter: stub_gpu_limited_access_for_adapter,
         }
     }
 
@@ -2392,6 +2694,69 @@ mod runtime_context_id_dispatch_tests {
         assert!(!full.should_process());
     }
 
+    #[test]
+    fn full_access_gpu_for_unknown_adapter_surfaces_host_error() {
+        let vtable = stub_vtable(stub_runtime_id_copy_short, stub_processor_id_copy_none);
+        let full = RuntimeContextFullAccess {
+            handle: std::ptr::null(),
+            vtable: &vtable as *const RuntimeContextVTable,
+            gpu_full: null_gpu_full(),
+            gpu_limited: null_gpu_limited(),
+            _marker: PhantomData,
+        };
+        let err = full.gpu_limited_access_for_adapter(3).err().unwrap();
+        assert!(err.to_string().contains("no GPU adapter at index 3"));
+    }
+
+    #[test]
+    fn full_access_assets_fetch_maps_completion_status() {
+        let vtable = stub_vtable(stub_runtime_id_copy_short, stub_processor_id_copy_none);
+        let full = RuntimeContextFullAccess {
+            handle: std::ptr::null(),
+            vtable: &vtable as *const RuntimeContextVTable,
+            gpu_full: null_gpu_full(),
+            gpu_limited: null_gpu_limited(),
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            full.assets().fetch("/cache/lut.cube").unwrap(),
+            std::path::PathBuf::from("/cache/lut.cube")
+        );
+        assert!(matches!(
+            full.assets().fetch_pinned("/cache/lut.cube", "ab"),
+            Err(streamlib_error::Error::AssetOffline(url)) if url == "/cache/lut.cube"
+        ));
+    }
+
+    #[test]
+    fn full_access_fonts_resolve_parses_the_fallback_chain() {
+        let vtable = stub_vtable(stub_runtime_id_copy_short, stub_processor_id_copy_none);
+        let full = RuntimeContextFullAccess {
+            handle: std::ptr::null(),
+            vtable: &vtable as *const RuntimeContextVTable,
+            gpu_full: null_gpu_full(),
+            gpu_limited: null_gpu_limited(),
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            full.fonts().resolve("title").unwrap(),
+            vec![
+                FontFace {
+                    path: "/fonts/Title.otf".into(),
+                    face_index: 0,
+                },
+                FontFace {
+                    path: "/fonts/Fallback.ttc".into(),
+                    face_index: 2,
+                },
+            ]
+        );
+        assert!(matches!(
+            full.fonts().resolve("caption"),
+            Err(streamlib_error::Error::FontNotFound(name)) if name == "caption"
+        ));
+    }
+
     #[test]
     fn limited_access_runtime_id_and_processor_id_dispatch() {
         let vtable = stub_vtable(stub_runtime_id_copy_long, stub_processor_id_copy_some_long);
@@ -32,10 +32,14 @@ mod context;
 mod iceoryx2;
 mod media_clock;
 mod plugin;
+mod processor_component;
 mod processors;
+mod pubsub;
+mod reference_signal;
 #[cfg(target_os = "linux")]
 mod rhi;
 mod runtime_control;
+mod shader_watch;
 
 /// Public plugin-authoring surface. Packages author against
 /// `streamlib_plugin_sdk::sdk::*`; the `#[processor]` macro and
@@ -90,17 +94,19 @@ pub mod sdk {
     pub mod context {
         pub use crate::audio_clock_shim::{AudioClockShim, AudioTickContext};
         pub use crate::context::{
-            GpuCapabilities, GpuContextFullAccess, GpuContextLimitedAccess,
-            RuntimeContextFullAccess, RuntimeContextLimitedAccess,
+            AssetsShim, FontFace, FontsShim, GpuCapabilities, GpuContextFullAccess,
+            GpuContextLimitedAccess, RuntimeContextFullAccess, RuntimeContextLimitedAccess,
         };
     }
 
     // ---- Monotonic process clock (engine-free) ----
     /// `MediaClock` — the monotonic process clock the output-writer view
     /// stamps frame timestamps with. Engine-free twin of the engine
-    /// facade's `sdk::media_clock`.
+    /// facade's `sdk::media_clock`. `report_reference_signal` feeds the
+    /// host clock subsystem's genlock status.
     pub mod media_clock {
         pub use crate::media_clock::MediaClock;
+        pub use crate::reference_signal::report_reference_signal;
     }
 
     // ---- Cdylib-arm RHI views (the GPU resource surface) ----
@@ -115,7 +121,7 @@ pub mod sdk {
     pub mod rhi {
         pub use crate::rhi::{
             AttachmentFormats, BlendFactor, BlendOp, COLOR_CONVERTER_PUSH_CONSTANT_SIZE,
-            ColorBlendAttachment, ColorBlendState, ColorConverterPushConstants, ColorWriteMask,
+            COMPUTE_OUTPUT_RING_DEPTH, ColorBlendAttachment, ColorBlendState, ColorConverterPushConstants, ColorWriteMask,
             ComputeBindingKind, ComputeBindingSpec, ComputeKernelDescriptor, CullMode,
             DecodedColorVui, DecodedFrame, DecoderSession, DepthCompareOp, DepthFormat,
             DepthStencilState, DrawCall, DrawIndexedCall,
@@ -193,6 +199,32 @@ pub mod sdk {
         pub use crate::runtime_control::request_runtime_shutdown;
     }
 
+    // ---- Shader hot-reload (engine-free) ----
+    /// `ShaderWatcher` — polls a processor's shader files so it can
+    /// recompile between frames — and the `STREAMLIB_WATCH_SHADERS`
+    /// switch that turns watching on for every processor.
+    pub mod shader_watch {
+        pub use crate::shader_watch::{
+            DEFAULT_SHADER_WATCH_INTERVAL, SHADER_WATCH_ENV_VAR, ShaderWatcher,
+            shader_watch_enabled,
+        };
+    }
+
+    // ---- Custom events (engine-free) ----
+    /// `publish_custom_event` — publish a JSON payload on the host bus as
+    /// a custom event, through the cached `pubsub_publish` callback.
+    pub mod pubsub {
+        pub use crate::pubsub::publish_custom_event;
+    }
+
+    // ---- Graph components (engine-free) ----
+    /// `report_processor_component` — publish a JSON value onto the
+    /// calling processor's graph node `components`, through the reserved
+    /// plugin-ABI control topic.
+    pub mod graph {
+        pub use crate::processor_component::report_processor_component;
+    }
+
     // ---- Plugin registration glue (cdylib arm) ----
     /// `install_host_services` + `RegisterHelper` — the symbols
     /// `export_plugin!` resolves into. Re-exports the ABI's `HostServices`
@@ -0,0 +1,116 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Engine-free graph-component reports.
+//!
+//! A processor publishes a JSON value under a key of its own graph node's
+//! `components` (next to the host's `metrics` / `state`) on the reserved
+//! plugin-ABI control topic
+//! ([`streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT`]).
+//! Same transport as [`crate::reference_signal`].
+
+use serde::Serialize;
+use streamlib_error::{Error, Result};
+use streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT;
+
+/// Wire shape of the control-topic payload (msgpack named map).
+#[derive(Serialize)]
+struct ProcessorComponentReport<'a> {
+    processor_id: &'a str,
+    key: &'a str,
+    value: serde_json::Value,
+}
+
+/// Set `components[key]` on processor `processor_id`'s graph node to
+/// `value` (anything serializing to JSON), replacing the previous report.
+/// Keys the host already populates (`metrics`, `state`, ...) are never
+/// overridden. `processor_id` is the calling processor's
+/// `ctx.processor_id()`.
+///
+/// Returns [`Error::PluginHostUnavailable`] outside a streamlib host.
+pub fn report_processor_component(
+    processor_id: &str,
+    key: &str,
+    value: &impl Serialize,
+) -> Result<()> {
+    let Some(callbacks) = crate::plugin::host_callbacks() else {
+        return Err(Error::PluginHostUnavailable(
+            "report_processor_component called in a cdylib whose host \
+             services were never installed (not loaded by a streamlib host)"
+                .into(),
+        ));
+    };
+    let payload = encode_processor_component_report(processor_id, key, value)?;
+
+    // SAFETY: `callbacks.pubsub_publish` and `callbacks.host` were
+    // populated by `install_host_services` and stay valid for the
+    // plugin's process lifetime. The slices outlive the synchronous call.
+    unsafe {
+        (callbacks.pubsub_publish)(
+            callbacks.host,
+            PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT.as_ptr(),
+            PUBSUB_CONTROL_TOPIC_PROCESSOR_COMPONENT.len(),
+            payload.as_ptr(),
+            payload.len(),
+        );
+    }
+    Ok(())
+}
+
+fn encode_processor_component_report(
+    processor_id: &str,
+    key: &str,
+    value: &impl Serialize,
+) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| Error::Runtime(format!("component '{key}' is not JSON-serializable: {e}")))?;
+    rmp_serde::to_vec_named(&ProcessorComponentReport {
+        processor_id,
+        key,
+        value,
+    })
+    .map_err(|e| Error::Runtime(format!("failed to encode processor component report: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_processor_component_without_host_returns_plugin_host_unavailable() {
+        let result = report_processor_component("processor-1", "loudness", &-23.0f32);
+        assert!(
+            matches!(result, Err(Error::PluginHostUnavailable(_))),
+            "expected PluginHostUnavailable, got {result:?}",
+        );
+    }
+
+    /// The host decodes the payload into its own report struct by field
+    /// name, so the report must be a named map, not a positional array.
+    #[test]
+    fn report_is_encoded_as_a_named_map() {
+        let bytes = encode_processor_component_report(
+            "processor-1",
+            "loudness",
+            &serde_json::json!({ "integrated_lufs": -23.5 }),
+        )
+        .unwrap();
+
+        let value: rmpv::Value = rmp_serde::from_slice(&bytes).unwrap();
+        let map = value.as_map().expect("named map");
+        let field = |name: &str| {
+            map.iter()
+                .find(|(k, _)| k.as_str() == Some(name))
+                .map(|(_, v)| v.clone())
+        };
+        assert_eq!(
+            field("processor_id"),
+            Some(rmpv::Value::from("processor-1"))
+        );
+        assert_eq!(field("key"), Some(rmpv::Value::from("loudness")));
+        let component = field("value").expect("value");
+        let component = component.as_map().expect("component map");
+        assert_eq!(component[0].0.as_str(), Some("integrated_lufs"));
+        assert_eq!(component[0].1.as_f64(), Some(-23.5));
+    }
+}
@@ -138,6 +138,14 @@ pub trait ReactiveProcessor {
         Ok(())
     }
 
+    /// Called after a runtime config update has replaced the processor's
+    /// config field. Processors whose running state derives from config
+    /// (device settings, worker threads) re-apply it here; an error is
+    /// reported back to whoever submitted the update.
+    fn on_config_update(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Called when input data arrives. Restricted ctx.
     fn process(&mut self, ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()>;
 }
@@ -164,6 +172,14 @@ pub trait ContinuousProcessor {
         Ok(())
     }
 
+    /// Called after a runtime config update has replaced the processor's
+    /// config field. Processors whose running state derives from config
+    /// (device settings, worker threads) re-apply it here; an error is
+    /// reported back to whoever submitted the update.
+    fn on_config_update(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Called repeatedly by the runtime in a loop. Restricted ctx.
     fn process(&mut self, ctx: &RuntimeContextLimitedAccess<'_>) -> Result<()>;
 }
@@ -190,6 +206,14 @@ pub trait ManualProcessor {
         Ok(())
     }
 
+    /// Called after a runtime config update has replaced the processor's
+    /// config field. Processors whose running state derives from config
+    /// (device settings, worker threads) re-apply it here; an error is
+    /// reported back to whoever submitted the update.
+    fn on_config_update(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Called once to start the processor. Privileged ctx.
     fn start(&mut self, ctx: &RuntimeContextFullAccess<'_>) -> Result<()>;
 
@@ -508,6 +532,10 @@ pub struct ProcessorSpec {
     /// PascalCase short name.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    /// GPU adapter to run on, as an index into the enumerated adapters.
+    /// `None` runs on the runtime's own device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_adapter: Option<usize>,
 }
 
 impl ProcessorSpec {
@@ -517,6 +545,7 @@ impl ProcessorSpec {
             name,
             config,
             display_name: None,
+            gpu_adapter: None,
         }
     }
 
@@ -525,6 +554,12 @@ impl ProcessorSpec {
         self.display_name = Some(display_name.into());
         self
     }
+
+    /// Pin this processor to the GPU adapter at `index`.
+    pub fn with_gpu_adapter(mut self, index: usize) -> Self {
+        self.gpu_adapter = Some(index);
+        self
+    }
 }
 
 // =============================================================================
@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Engine-free custom-event publishing.
+//!
+//! The engine's `Event` enum stays on the host side of the plugin ABI.
+//! A plugin publishes a custom event by encoding [`CustomEventWire`] — a
+//! one-variant twin whose msgpack is the host `Event::Custom` encoding
+//! (`rmp_serde` tags enum variants by name) — and handing it to the
+//! cached `pubsub_publish` callback, which decodes it as an `Event` and
+//! re-publishes it on the host bus.
+
+use serde::Serialize;
+use streamlib_error::{Error, Result};
+
+/// Wire twin of the host's `Event::Custom { topic, data }`.
+#[derive(Serialize)]
+enum CustomEventWire<'a> {
+    Custom {
+        topic: &'a str,
+        data: &'a serde_json::Value,
+    },
+}
+
+/// Publish `data` on the host bus as a custom event on `topic`.
+///
+/// Host-side subscribers (the API server's `/ws/events` stream, host
+/// processors) receive it as `Event::Custom`. `topic` must not start with
+/// the reserved `control:` prefix — the host drops those.
+///
+/// Returns [`Error::PluginHostUnavailable`] when called from a cdylib
+/// whose host services were never installed.
+pub fn publish_custom_event(topic: &str, data: &serde_json::Value) -> Result<()> {
+    let Some(callbacks) = crate::plugin::host_callbacks() else {
+        return Err(Error::PluginHostUnavailable(
+            "publish_custom_event called in a cdylib whose host services \
+             were never installed (not loaded by a streamlib host)"
+                .into(),
+        ));
+    };
+    let event = encode_custom_event(topic, data)?;
+
+    // SAFETY: `callbacks` were populated by `install_host_services` from a
+    // host-provided `HostServices` and stay valid for the plugin's process
+    // lifetime. The topic and payload slices outlive the synchronous call.
+    unsafe {
+        (callbacks.pubsub_publish)(
+            callbacks.host,
+            topic.as_ptr(),
+            topic.len(),
+            event.as_ptr(),
+            event.len(),
+        );
+    }
+    Ok(())
+}
+
+fn encode_custom_event(topic: &str, data: &serde_json::Value) -> Result<Vec<u8>> {
+    rmp_serde::to_vec(&CustomEventWire::Custom { topic, data })
+        .map_err(|e| Error::Runtime(format!("failed to encode custom event: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirror of the host `Event` shape: more variants ahead of `Custom`,
+    /// so a by-index variant encoding would decode as the wrong variant.
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    #[allow(dead_code)] // Only `Custom` is ever decoded.
+    enum HostEventShape {
+        RuntimeGlobal(serde_json::Value),
+        ProcessorEvent {
+            processor_id: String,
+            event: serde_json::Value,
+        },
+        Custom {
+            topic: String,
+            data: serde_json::Value,
+        },
+    }
+
+    #[test]
+    fn custom_event_decodes_as_the_host_event_variant() {
+        let data = serde_json::json!({ "controls": [{ "name": "Gain", "value": 12 }] });
+
+        let bytes = encode_custom_event("camera:controls", &data).unwrap();
+
+        assert_eq!(
+            rmp_serde::from_slice::<HostEventShape>(&bytes).unwrap(),
+            HostEventShape::Custom {
+                topic: "camera:controls".to_string(),
+                data,
+            }
+        );
+    }
+
+    #[test]
+    fn publish_without_host_returns_plugin_host_unavailable() {
+        let result = publish_custom_event("camera:controls", &serde_json::Value::Null);
+
+        assert!(matches!(result, Err(Error::PluginHostUnavailable(_))));
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Engine-free reference-signal (genlock) status reports.
+//!
+//! A processor driving hardware with a reference input reports its lock
+//! state on the reserved plugin-ABI control topic
+//! ([`streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS`]);
+//! the host keeps the latest report per source in its clock subsystem and
+//! announces changes. Same transport as [`crate::runtime_control`].
+
+use serde::Serialize;
+use streamlib_error::{Error, Result};
+use streamlib_plugin_abi::PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS;
+
+/// Wire shape of the control-topic payload (msgpack named map).
+#[derive(Serialize)]
+struct ReferenceSignalReport<'a> {
+    source: &'a str,
+    locked: bool,
+    mode: Option<&'a str>,
+}
+
+/// Report whether `source` (a stable device id, e.g. `"decklink:0"`) is
+/// locked to its reference input, and the detected reference format when
+/// it is. Cheap enough to call on every poll: the host only announces
+/// changes.
+///
+/// Returns [`Error::PluginHostUnavailable`] outside a streamlib host.
+pub fn report_reference_signal(source: &str, locked: bool, mode: Option<&str>) -> Result<()> {
+    let Some(callbacks) = crate::plugin::host_callbacks() else {
+        return Err(Error::PluginHostUnavailable(
+            "report_reference_signal called in a cdylib whose host services \
+             were never installed (not loaded by a streamlib host)"
+                .into(),
+        ));
+    };
+    let payload = encode_reference_signal_report(source, locked, mode)?;
+
+    // SAFETY: `callbacks.pubsub_publish` and `callbacks.host` were
+    // populated by `install_host_services` and stay valid for the
+    // plugin's process lifetime. The slices outlive the synchronous call.
+    unsafe {
+        (callbacks.pubsub_publish)(
+            callbacks.host,
+            PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS.as_ptr(),
+            PUBSUB_CONTROL_TOPIC_CLOCK_REFERENCE_STATUS.len(),
+            payload.as_ptr(),
+            payload.len(),
+        );
+    }
+    Ok(())
+}
+
+fn encode_reference_signal_report(
+    source: &str,
+    locked: bool,
+    mode: Option<&str>,
+) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(&ReferenceSignalReport {
+        source,
+        locked,
+        mode,
+    })
+    .map_err(|e| Error::Runtime(format!("failed to encode reference signal status: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_reference_signal_without_host_returns_plugin_host_unavailable() {
+        let result = report_reference_signal("decklink:0", true, None);
+        assert!(
+            matches!(result, Err(Error::PluginHostUnavailable(_))),
+            "expected PluginHostUnavailable, got {result:?}",
+        );
+    }
+
+    /// The host decodes the payload into its own status struct by field
+    /// name, so the report must be a named map, not a positional array.
+    #[test]
+    fn report_is_encoded_as_a_named_map() {
+        let bytes = encode_reference_signal_report("decklink:0", true, Some("1080p25")).unwrap();
+
+        let value: rmpv::Value = rmp_serde::from_slice(&bytes).unwrap();
+        let map = value.as_map().expect("named map");
+        let field = |name: &str| {
+            map.iter()
+                .find(|(k, _)| k.as_str() == Some(name))
+                .map(|(_, v)| v.clone())
+        };
+        assert_eq!(field("source"), Some(rmpv::Value::from("decklink:0")));
+        assert_eq!(field("locked"), Some(rmpv::Value::from(true)));
+        assert_eq!(field("mode"), Some(rmpv::Value::from("1080p25")));
+    }
+}
@@ -13,6 +13,12 @@
 //! `TextureFormat` / `TextureUsages` / `PixelFormat` / `VulkanLayout` are
 //! already engine-free in [`streamlib_consumer_rhi`]; they're re-exported
 //! here so a plugin reaches the whole RHI surface from one module.
+//!
+//! The module only builds on Linux — the host's Vulkan device is the one
+//! RHI backing with an engine-free twin today. Packages whose processors
+//! record through it gate those processors on `target_os = "linux"` and
+//! keep their CPU-side parameter and layout code portable, so it builds
+//! and tests on every platform.
 
 mod color_converter;
 mod command_recorder;
@@ -61,7 +67,9 @@ pub use surface_store::SurfaceStore;
 pub use texture::{NativeTextureHandle, Texture, TextureDescriptor};
 pub use texture_readback::{ReadbackTicket, TextureReadback, TextureSourceLayout};
 pub use texture_registration::TextureRegistration;
-pub use texture_ring::{TEXTURE_RING_SLOT_SURFACE_ID_MAX_BYTES, TextureRing, TextureRingSlot};
+pub use texture_ring::{
+    COMPUTE_OUTPUT_RING_DEPTH, TEXTURE_RING_SLOT_SURFACE_ID_MAX_BYTES, TextureRing, TextureRingSlot,
+};
 pub use video_decoder_session::{DecodedColorVui, DecodedFrame, DecoderSession};
 pub use video_encoder_session::{EncodedFrameType, EncodedPacket, EncoderSession};
 pub use vulkan_compute_kernel::VulkanComputeKernel;
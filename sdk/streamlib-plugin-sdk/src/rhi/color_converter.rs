@@ -140,6 +140,15 @@ impl SourceLayoutInfo {
     pub fn yuyv_tight(width: u32) -> Self {
         Self::yuyv(width * 2)
     }
+
+    /// Packed BGRA / BGRx layout — single plane, one `u32` per pixel.
+    pub fn bgra(packed_stride_bytes: u32) -> Self {
+        Self {
+            plane0_stride_bytes: packed_stride_bytes,
+            plane1_stride_bytes: 0,
+            plane1_offset_bytes: 0,
+        }
+    }
 }
 
 /// Byte size of the push-constants block sent to the converter kernel.
@@ -34,6 +34,8 @@ use streamlib_plugin_abi::{
     GpuContextFullAccessVTable, ImageCopyRegionRepr, RhiCommandRecorderMethodsVTable,
 };
 
+#[cfg(target_os = "linux")]
+use crate::rhi::TextureRegistration;
 use crate::rhi::{
     DrawCall, DrawIndexedCall, HostTimelineSemaphore, PixelBuffer, StorageBuffer, Texture,
     VulkanAccess, VulkanComputeKernel, VulkanGraphicsKernel, VulkanStage,
@@ -253,6 +255,36 @@ impl RhiCommandRecorder {
         status_to_result(status, &err_buf, err_len)
     }
 
+    /// Close out a compute pass that wrote `output` in `GENERAL` and hand
+    /// it on in `SHADER_READ_ONLY_OPTIMAL`, the layout every in-tree
+    /// consumer samples from: record that transition, submit, wait for the
+    /// GPU, then record the new layout on `output` and on each of
+    /// `sampled` — the inputs the pass moved to `SHADER_READ_ONLY_OPTIMAL`
+    /// to read them. Waiting here means a consumer that receives the frame
+    /// can sample it without further synchronization.
+    #[cfg(target_os = "linux")]
+    pub fn finish_compute_output(
+        &mut self,
+        output: &TextureRegistration,
+        sampled: &[&TextureRegistration],
+    ) -> Result<()> {
+        self.record_image_barrier(
+            output.texture(),
+            VulkanLayout::GENERAL,
+            VulkanLayout::SHADER_READ_ONLY_OPTIMAL,
+            VulkanStage::COMPUTE_SHADER,
+            VulkanStage::ALL_COMMANDS,
+            VulkanAccess::SHADER_WRITE,
+            VulkanAccess::MEMORY_READ,
+        )?;
+        self.submit_and_wait()?;
+        for registration in sampled {
+            registration.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+        }
+        output.update_layout(VulkanLayout::SHADER_READ_ONLY_OPTIMAL);
+        Ok(())
+    }
+
     // -------------------------------------------------------------------------
     // Buffer copy / barrier / timeline-submit wrappers (recorder-v1/v2 slots).
     // The camera producer path drives these per frame:
@@ -7,19 +7,21 @@
 //! `core/context/texture_registration.rs::TextureRegistration`. The host
 //! `TextureRegistrationInner` backing + the `new` / `from_arc_into_raw` /
 //! `host_inner` constructors stay in the engine; this twin carries only the
-//! vtable-dispatched `texture` / `current_layout` / `update_layout` / Clone /
-//! Drop methods a cdylib consumer needs after resolving an incoming
-//! `surface_id` via
+//! vtable-dispatched `texture` / `current_layout` / `update_layout` /
+//! producer-sync / Clone / Drop methods a cdylib consumer needs after
+//! resolving an incoming `surface_id` via
 //! [`crate::context::GpuContextLimitedAccess::resolve_texture_registration_by_surface_id`].
 
 use std::ffi::c_void;
+use std::time::Duration;
 
+use streamlib_error::{Error, Result};
 use streamlib_plugin_abi::GpuContextLimitedAccessVTable;
 
 #[cfg(target_os = "linux")]
 use streamlib_consumer_rhi::VulkanLayout;
 
-use crate::rhi::Texture;
+use crate::rhi::{HostTimelineSemaphore, Texture};
 
 /// Per-surface registration record resolved from a `surface_id` — the
 /// texture plus its last-known Vulkan image layout.
@@ -97,6 +99,107 @@ impl TextureRegistration {
             ((*self.vtable).texture_registration_update_layout)(self.handle, new_layout.0);
         }
     }
+
+    /// Publish the timeline value the producer's latest submit signals,
+    /// so consumers on other queues wait for the write to land. Typically
+    /// paired with
+    /// [`crate::rhi::RhiCommandRecorder::submit_signaling_timeline`] on the
+    /// same `timeline` / `value`.
+    ///
+    /// Dispatches through the vtable's
+    /// [`GpuContextLimitedAccessVTable::texture_registration_set_producer_timeline`]
+    /// callback. No-op on non-Linux hosts.
+    pub fn set_producer_timeline(&self, timeline: &HostTimelineSemaphore, value: u64) {
+        if self.handle.is_null() || self.vtable.is_null() {
+            return;
+        }
+        // SAFETY: vtable + handle were paired at construction;
+        // `timeline.cdylib_handle()` is borrowed — the host takes its own
+        // strong count.
+        unsafe {
+            ((*self.vtable).texture_registration_set_producer_timeline)(
+                self.handle,
+                timeline.cdylib_handle(),
+                value,
+            );
+        }
+    }
+
+    /// Drop the published producer timeline; consumers fall back to
+    /// queue-submission ordering.
+    pub fn clear_producer_timeline(&self) {
+        if self.handle.is_null() || self.vtable.is_null() {
+            return;
+        }
+        // SAFETY: vtable + handle were paired at construction; a null
+        // timeline handle is the documented clear.
+        unsafe {
+            ((*self.vtable).texture_registration_set_producer_timeline)(
+                self.handle,
+                std::ptr::null(),
+                0,
+            );
+        }
+    }
+
+    /// The producer's published timeline and signal value, for consumers
+    /// that wait GPU-side in their own submit. `None` when nothing is
+    /// published (and always on non-Linux hosts).
+    ///
+    /// Dispatches through the vtable's
+    /// [`GpuContextLimitedAccessVTable::texture_registration_producer_timeline`]
+    /// callback.
+    pub fn producer_timeline(&self) -> Option<(HostTimelineSemaphore, u64)> {
+        if self.handle.is_null() || self.vtable.is_null() {
+            return None;
+        }
+        let mut out = std::mem::MaybeUninit::<HostTimelineSemaphore>::uninit();
+        let mut value: u64 = 0;
+        // SAFETY: vtable + handle were paired at construction; on hit the
+        // host writes an owned `(handle, methods)` envelope into `out`.
+        let hit = unsafe {
+            ((*self.vtable).texture_registration_producer_timeline)(
+                self.handle,
+                out.as_mut_ptr() as *mut c_void,
+                &mut value as *mut u64,
+            )
+        };
+        // SAFETY: `hit == 1` means the host initialised `out`.
+        (hit == 1).then(|| (unsafe { out.assume_init() }, value))
+    }
+
+    /// CPU-block until the producer's published point is reached.
+    /// Returns immediately when nothing is published.
+    ///
+    /// Dispatches through the vtable's
+    /// [`GpuContextLimitedAccessVTable::texture_registration_wait_producer`]
+    /// callback.
+    pub fn wait_for_producer(&self, timeout: Duration) -> Result<()> {
+        if self.handle.is_null() || self.vtable.is_null() {
+            return Ok(());
+        }
+        let timeout_ns = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+        let mut err_buf = [0u8; 256];
+        let mut err_len: usize = 0;
+        // SAFETY: vtable + handle were paired at construction; err_buf is
+        // stack storage the host writes at most `len` bytes to.
+        let status = unsafe {
+            ((*self.vtable).texture_registration_wait_producer)(
+                self.handle,
+                timeout_ns,
+                err_buf.as_mut_ptr(),
+                err_buf.len(),
+                &mut err_len as *mut usize,
+            )
+        };
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(Error::GpuError(
+                String::from_utf8_lossy(&err_buf[..err_len.min(err_buf.len())]).into_owned(),
+            ))
+        }
+    }
 }
 
 impl Clone for TextureRegistration {
@@ -30,6 +30,12 @@ use crate::rhi::Texture;
 /// representation without crossing the plugin ABI as a heap `String`.
 pub const TEXTURE_RING_SLOT_SURFACE_ID_MAX_BYTES: usize = 64;
 
+/// Ring depth for a processor that writes one output frame per input:
+/// downstream may still be sampling the previous slot while the next one
+/// is written, so two slots keep producer and consumer apart. Processors
+/// that emit several frames per input need a deeper ring.
+pub const COMPUTE_OUTPUT_RING_DEPTH: usize = 2;
+
 /// A single slot in a [`TextureRing`].
 ///
 /// Layout-stable `#[repr(C)]` PluginAbiObject. `Clone` is structural —
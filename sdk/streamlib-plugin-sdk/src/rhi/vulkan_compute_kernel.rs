@@ -84,8 +84,10 @@ impl VulkanComputeKernel {
     }
 
     /// Bind a sampled texture at `binding`, using the kernel's default
-    /// linear-clamp sampler. Dispatches through the per-type methods
-    /// vtable's `set_sampled_texture` slot.
+    /// linear-clamp sampler — or, for a slot declared
+    /// [`ComputeBindingSpec::sampled_image`](crate::rhi::ComputeBindingSpec::sampled_image),
+    /// the texture alone. Dispatches through the per-type methods vtable's
+    /// `set_sampled_texture` slot.
     pub fn set_sampled_texture(&self, binding: u32, texture: &Texture) -> Result<()> {
         if self.methods_vtable.is_null() {
             return Err(Error::GpuError(
@@ -0,0 +1,169 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Shader hot-reload support for processors that load shaders from disk.
+//!
+//! [`ShaderWatcher`] polls the modification times of a processor's shader
+//! files from its own `process` call, so a change is picked up between
+//! frames on the thread that owns the pipeline — no watcher thread, and
+//! nothing to synchronize with an in-flight dispatch. The processor
+//! recompiles on a reported change and keeps its previous pipeline when
+//! the new source fails to compile.
+//!
+//! Watching is opt-in per processor (a `watch` config field) or for every
+//! processor at once with [`SHADER_WATCH_ENV_VAR`] — the switch to flip
+//! while developing an effect.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Set to `1` to watch every shader file a processor loads from disk.
+pub const SHADER_WATCH_ENV_VAR: &str = "STREAMLIB_WATCH_SHADERS";
+
+/// How often a [`ShaderWatcher`] checks its files by default.
+pub const DEFAULT_SHADER_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Whether to watch shader files.
+///
+/// Resolution priority:
+/// 1. Explicit value (a processor's `watch` config field, if set)
+/// 2. [`SHADER_WATCH_ENV_VAR`] set to `1`
+/// 3. Off
+pub fn shader_watch_enabled(explicit: Option<bool>) -> bool {
+    explicit.unwrap_or_else(|| std::env::var(SHADER_WATCH_ENV_VAR).is_ok_and(|v| v == "1"))
+}
+
+/// Reports which of a set of shader files changed since they were loaded.
+#[derive(Debug, Clone)]
+pub struct ShaderWatcher {
+    files: Vec<WatchedFile>,
+    interval: Duration,
+    last_check: Option<Instant>,
+}
+
+#[derive(Debug, Clone)]
+struct WatchedFile {
+    path: PathBuf,
+    /// Modification time when last seen; `None` if the file was missing.
+    modified: Option<SystemTime>,
+}
+
+impl ShaderWatcher {
+    /// Watch `paths`, taking their current state as loaded.
+    pub fn new<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let path = path.into();
+                    WatchedFile {
+                        modified: modified(&path),
+                        path,
+                    }
+                })
+                .collect(),
+            interval: DEFAULT_SHADER_WATCH_INTERVAL,
+            last_check: None,
+        }
+    }
+
+    /// Check the files at most once per `interval`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|file| file.path.as_path())
+    }
+
+    /// The files whose modification time changed since the last poll (or
+    /// since [`Self::new`]), at most once per interval; empty otherwise.
+    ///
+    /// A change is reported once whether or not the processor manages to
+    /// reload it, so a broken save is not retried every interval. A file
+    /// that has gone missing is not reported — editors that save by
+    /// renaming briefly remove it — and counts as changed when it returns.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self
+            .last_check
+            .is_some_and(|checked| checked.elapsed() < self.interval)
+        {
+            return Vec::new();
+        }
+        self.last_check = Some(Instant::now());
+        self.files
+            .iter_mut()
+            .filter_map(|file| {
+                let current = modified(&file.path)?;
+                if file.modified == Some(current) {
+                    return None;
+                }
+                file.modified = Some(current);
+                Some(file.path.clone())
+            })
+            .collect()
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(path: &Path, seconds: u64) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| {
+                file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            })
+            .expect("set mtime");
+    }
+
+    #[test]
+    fn changed_files_are_reported_once() {
+        let dir = std::env::temp_dir().join(format!("shader-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let (blur, tint) = (dir.join("blur.wgsl"), dir.join("tint.wgsl"));
+        for path in [&blur, &tint] {
+            std::fs::write(path, "// shader").expect("write");
+            touch(path, 1_000);
+        }
+
+        let mut watcher = ShaderWatcher::new([&blur, &tint]).with_interval(Duration::ZERO);
+        assert!(watcher.poll().is_empty());
+
+        touch(&tint, 2_000);
+        assert_eq!(watcher.poll(), vec![tint.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::remove_file(&blur).expect("remove");
+        assert!(watcher.poll().is_empty());
+        std::fs::write(&blur, "// saved").expect("write");
+        touch(&blur, 3_000);
+        assert_eq!(watcher.poll(), vec![blur.clone()]);
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn polls_are_rate_limited() {
+        let mut watcher =
+            ShaderWatcher::new(["/nonexistent/effect.wgsl"]).with_interval(Duration::from_secs(60));
+        assert!(watcher.poll().is_empty());
+        assert!(watcher.last_check.is_some());
+        let checked = watcher.last_check;
+        watcher.poll();
+        assert_eq!(watcher.last_check, checked);
+    }
+
+    #[test]
+    fn explicit_setting_wins_over_the_environment() {
+        assert!(shader_watch_enabled(Some(true)));
+        assert!(!shader_watch_enabled(Some(false)));
+    }
+}
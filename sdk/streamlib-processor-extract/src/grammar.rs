@@ -15,6 +15,7 @@
 //!     unsafe_send,                      // flag — emit `unsafe impl Send`
 //!     config = crate::CameraConfig,     // Rust type path for the typed Config alias
 //!     input("video_in", "@tatolab/core/VideoFrame", delivery_profile = "latest"),
+//!     input("control", "@tatolab/core/EncoderControl", optional = true),
 //!     output("video", "@tatolab/core/VideoFrame"),
 //! )]
 //! ```
@@ -35,7 +36,7 @@ use streamlib_processor_schema::{
 };
 use syn::ext::IdentExt;
 use syn::parse::{ParseStream, Parser};
-use syn::{Ident, LitInt, LitStr, Path, Token, parenthesized};
+use syn::{Ident, LitBool, LitInt, LitStr, Path, Token, parenthesized};
 
 /// Which side of a link a port sits on. `delivery_profile` is a consumer-side
 /// setting only valid on an `input(...)`; the grammar rejects it on an
@@ -61,6 +62,8 @@ pub struct ParsedPort {
     pub schema: PortSchemaSpec,
     pub description: Option<String>,
     pub delivery_profile: Option<String>,
+    pub format: Option<String>,
+    pub optional: bool,
 }
 
 /// The fully-parsed `#[processor(...)]` attribute.
@@ -96,6 +99,8 @@ impl ParsedProcessorAttr {
             schema: p.schema.clone(),
             description: p.description.clone(),
             delivery_profile: p.delivery_profile.clone(),
+            format: p.format.clone(),
+            optional: p.optional,
         };
 
         ProcessorSchema {
@@ -368,13 +373,17 @@ fn parse_execution(input: ParseStream<'_>) -> syn::Result<ProcessorSchemaExecuti
 
 /// Parse an `input(...)` / `output(...)` port body.
 ///
-/// `<name-string>, <schema>, [delivery_profile = "...", description = "..."]` —
+/// `<name-string>, <schema>, [delivery_profile = "...", description = "...",
+/// format = "...", optional = <bool>]` —
 /// where `<schema>` is either the bare identifier `any` or a version-free
 /// `"@org/package/Type"` string.
 ///
 /// `delivery_profile` is a consumer-side setting the destination input port
 /// declares; it is rejected with a spanned error on an `output(...)` rather
-/// than silently dropped.
+/// than silently dropped. `optional` marks an input the processor runs
+/// without when unlinked; outputs are never required, so it is rejected on
+/// them the same way. `format` names the frame format within the schema
+/// (`rgba8`, `48khz`, …) and is valid on either direction.
 fn parse_port(input: ParseStream<'_>, direction: PortDirection) -> syn::Result<ParsedPort> {
     let content;
     parenthesized!(content in input);
@@ -390,6 +399,8 @@ fn parse_port(input: ParseStream<'_>, direction: PortDirection) -> syn::Result<P
 
     let mut description = None;
     let mut delivery_profile = None;
+    let mut format = None;
+    let mut optional = false;
 
     while !content.is_empty() {
         content.parse::<Token![,]>()?;
@@ -409,12 +420,32 @@ fn parse_port(input: ParseStream<'_>, direction: PortDirection) -> syn::Result<P
                 reject_delivery_profile_on_output(direction, &name, key_span)?;
                 delivery_profile = Some(lit.value());
             }
+            "format" => {
+                let lit: LitStr = content.parse()?;
+                if lit.value().is_empty() {
+                    return Err(syn::Error::new(lit.span(), "port format must not be empty"));
+                }
+                format = Some(lit.value());
+            }
+            "optional" => {
+                let lit: LitBool = content.parse()?;
+                if direction == PortDirection::Output {
+                    return Err(syn::Error::new(
+                        key_span,
+                        format!(
+                            "`optional` is only meaningful on an input port — \
+                             `output(\"{name}\", ...)` never needs a link"
+                        ),
+                    ));
+                }
+                optional = lit.value;
+            }
             other => {
                 return Err(syn::Error::new(
                     key.span(),
                     format!(
-                        "unknown port key `{other}` — expected `delivery_profile` or \
-                         `description`"
+                        "unknown port key `{other}` — expected `delivery_profile`, \
+                         `description`, `format`, or `optional`"
                     ),
                 ));
             }
@@ -426,6 +457,8 @@ fn parse_port(input: ParseStream<'_>, direction: PortDirection) -> syn::Result<P
         schema,
         description,
         delivery_profile,
+        format,
+        optional,
     })
 }
 
@@ -756,6 +789,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn optional_input_reaches_the_manifest_port() {
+        let parsed = parse_ok(quote! {
+            "@tatolab/h264/H264Encoder",
+            execution = reactive,
+            input("video_in", "@tatolab/core/VideoFrame"),
+            input("control", "@tatolab/core/EncoderControl", optional = true),
+        });
+        assert!(!parsed.inputs[0].optional);
+        assert!(parsed.inputs[1].optional);
+        let schema = parsed.to_processor_schema();
+        assert!(schema.inputs[1].optional);
+
+        let tokens: proc_macro2::TokenStream =
+            "\"@tatolab/camera/Camera\", execution = manual, \
+             output(\"video\", \"@tatolab/core/VideoFrame\", optional = true)"
+                .parse()
+                .expect("token stream parses");
+        let msg = parse_err(tokens);
+        assert!(msg.contains("only meaningful on an input port"), "got: {msg}");
+    }
+
+    #[test]
+    fn port_format_reaches_the_manifest_port() {
+        let parsed = parse_ok(quote! {
+            "@tatolab/opus/OpusEncoder",
+            execution = reactive,
+            input("audio_in", "@tatolab/core/AudioFrame", format = "48khz"),
+            output("encoded_audio_out", "@tatolab/core/EncodedAudioFrame"),
+        });
+        assert_eq!(parsed.inputs[0].format.as_deref(), Some("48khz"));
+        assert_eq!(parsed.outputs[0].format, None);
+        let schema = parsed.to_processor_schema();
+        assert_eq!(schema.inputs[0].format.as_deref(), Some("48khz"));
+    }
+
     #[test]
     fn unknown_key_is_an_error() {
         let msg = parse_err(quote! {
@@ -11,7 +11,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Org, Package, PortSchemaSpec, ProcessorScheduling, SchemaIdent, SemVer, TypeName};
+use crate::{
+    Org, Package, PortSchemaSpec, ProcessExecution, ProcessorScheduling, SchemaIdent, SemVer,
+    TypeName,
+};
 
 /// Lossless wire-format mirror of [`PortSchemaSpec`] used at the cdylib
 /// plugin ABI msgpack boundary.
@@ -145,6 +148,11 @@ pub struct PortDescriptor {
     /// type's `flow_class` at wire time. Always `None` on output ports.
     #[serde(default)]
     pub delivery_profile: Option<String>,
+    /// Frame format within [`Self::schema`] — `rgba8`, `48khz`, … — for
+    /// automatic converter insertion. `None` when the port takes whatever
+    /// arrives.
+    #[serde(default)]
+    pub format: Option<String>,
 }
 
 impl PortDescriptor {
@@ -161,6 +169,7 @@ impl PortDescriptor {
             required,
             is_iceoryx2: false,
             delivery_profile: None,
+            format: None,
         }
     }
 
@@ -177,6 +186,7 @@ impl PortDescriptor {
             required: true,
             is_iceoryx2: true,
             delivery_profile: None,
+            format: None,
         }
     }
 
@@ -186,6 +196,12 @@ impl PortDescriptor {
         self.delivery_profile = Some(delivery_profile.into());
         self
     }
+
+    /// Builder-style frame format hint, see [`Self::format`].
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
 }
 
 /// Code examples for a processor in different languages.
@@ -257,6 +273,11 @@ pub struct ProcessorDescriptor {
     /// priority + `processor-{id}` thread name.
     #[serde(default)]
     pub scheduling: ProcessorScheduling,
+    /// How the runtime drives the processor. Known before any instance
+    /// exists, so graph analysis can tell a Reactive processor — one that
+    /// waits on its inputs — from a self-driven one. Defaults to `Reactive`.
+    #[serde(default)]
+    pub execution: ProcessExecution,
     pub inputs: Vec<PortDescriptor>,
     pub outputs: Vec<PortDescriptor>,
     pub examples: CodeExamples,
@@ -273,6 +294,7 @@ impl ProcessorDescriptor {
             entrypoint: None,
             config_schema: None,
             scheduling: ProcessorScheduling::default(),
+            execution: ProcessExecution::default(),
             inputs: Vec::new(),
             outputs: Vec::new(),
             examples: CodeExamples::default(),
@@ -309,6 +331,11 @@ impl ProcessorDescriptor {
         self
     }
 
+    pub fn with_execution(mut self, execution: ProcessExecution) -> Self {
+        self.execution = execution;
+        self
+    }
+
     pub fn with_input(mut self, port: PortDescriptor) -> Self {
         self.inputs.push(port);
         self
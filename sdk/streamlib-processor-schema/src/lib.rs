@@ -12,6 +12,7 @@ pub mod descriptors;
 pub mod error;
 pub mod processor_schema;
 pub mod processor_schema_parser;
+pub mod runtime_api;
 pub mod schema_ident_output;
 
 pub use execution_config::ExecutionConfig;
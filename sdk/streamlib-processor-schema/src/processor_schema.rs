@@ -535,6 +535,17 @@ pub struct ProcessorPortSchema {
     /// Always `None` on output ports.
     #[serde(default)]
     pub delivery_profile: Option<String>,
+    /// Frame format the port carries within its schema — e.g. `rgba8` or
+    /// `bgra8` on a `VideoFrame` port, `48khz` on an `AudioFrame` port.
+    /// Consulted when the runtime inserts converters automatically; `None`
+    /// when the port produces or accepts whatever arrives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Input port the processor runs fine without — a side channel such as
+    /// a control input. Leaving it unlinked is not reported as a topology
+    /// problem. Always `false` on output ports.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
 }
 
 /// Config definition within a processor schema.
@@ -0,0 +1,556 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Wire DTOs of the runtime control-plane API — the bodies the api-server
+//! serves and accepts, and the `metrics` / `frame_drops` graph components
+//! inside them.
+//!
+//! They live here, engine-free, so the server and `streamlib-client` share
+//! one definition: the engine re-exports them through
+//! `streamlib::sdk::json_schema` and converts its graph types into them,
+//! and the client deserializes the same structs. The `utoipa` feature adds
+//! the `utoipa::ToSchema` derive for the server's OpenAPI document, as for
+//! [`SchemaIdentOutput`].
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{SchemaIdentOutput, SemanticVersionOutput};
+
+// =============================================================================
+// Graph Response Schema (/api/graph)
+// =============================================================================
+
+/// Response from the `/api/graph` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GraphResponse {
+    /// All processor nodes in the graph.
+    pub nodes: Vec<ProcessorNodeOutput>,
+    /// All links (connections) between processors.
+    pub links: Vec<LinkOutput>,
+    /// Runtime-level components that belong to no single node or link,
+    /// e.g. `gpu_pool` ([`GpuPoolStatsOutput`]).
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub components: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A processor node in the graph.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProcessorNodeOutput {
+    /// Unique identifier for this processor instance.
+    pub id: String,
+    /// Structured processor identity — `@org/package/Type@version`
+    /// rendered as four typed fields on the wire (the structured-everywhere
+    /// rule). The joined `Display` form is render-only.
+    #[serde(rename = "type")]
+    pub processor_type: SchemaIdentOutput,
+    /// Display name for UI. May differ from type for hosted processors.
+    pub display_name: String,
+    /// Processor configuration as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+    /// Checksum of config for change detection.
+    #[serde(default)]
+    pub config_checksum: u64,
+    /// Input and output ports.
+    pub ports: ProcessorNodePortsOutput,
+    /// Runtime components (dynamic, varies based on processor state).
+    pub components: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Container for processor input and output ports.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProcessorNodePortsOutput {
+    /// Input ports that receive data.
+    pub inputs: Vec<PortInfoOutput>,
+    /// Output ports that send data.
+    pub outputs: Vec<PortInfoOutput>,
+}
+
+/// Metadata about a port.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PortInfoOutput {
+    /// Port name (e.g., "video_in", "audio_out").
+    pub name: String,
+    /// Structured schema identifier of the data flowing through this port.
+    ///
+    /// Resolved from the build-time embedded-schema segment table. `None`
+    /// when the port's declared schema doesn't have a structured-segment
+    /// representation (legacy reverse-DNS schemas, or schemas not in the
+    /// resolver dep graph).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<SchemaIdentOutput>,
+    /// Kind of port: data, event, or control.
+    #[serde(default)]
+    pub port_kind: PortKindOutput,
+}
+
+/// The kind of port - determines how data flows.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PortKindOutput {
+    #[default]
+    Data,
+    Event,
+    Control,
+}
+
+/// A link (connection) between two processor ports.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LinkOutput {
+    /// Unique identifier for this link.
+    pub id: String,
+    /// Source endpoint (output port).
+    pub source: LinkPortRefOutput,
+    /// Target endpoint (input port).
+    pub target: LinkPortRefOutput,
+    /// Ring buffer capacity for the channel.
+    #[serde(default)]
+    pub capacity: usize,
+    /// Current state of the link.
+    #[serde(default)]
+    pub state: LinkStateOutput,
+    /// Runtime components (dynamic, varies based on link state).
+    pub components: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Reference to a port on a processor.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LinkPortRefOutput {
+    /// Processor instance ID.
+    pub processor_id: String,
+    /// Port name on that processor.
+    pub port_name: String,
+}
+
+/// State of a link in the graph.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LinkStateOutput {
+    /// Link exists in graph but not yet wired.
+    #[default]
+    Pending,
+    /// Link is actively wired with a ring buffer channel.
+    Wired,
+    /// Link is being disconnected.
+    Disconnecting,
+    /// Link was disconnected.
+    Disconnected,
+    /// Link is in error state.
+    Error,
+}
+
+// =============================================================================
+// Registry Response Schema (/api/registry)
+// =============================================================================
+
+/// Response from the `/api/registry` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegistryResponse {
+    /// Available processor types with their descriptors.
+    pub processors: Vec<ProcessorDescriptorOutput>,
+    /// Available data frame schemas.
+    pub schemas: Vec<SchemaDescriptorOutput>,
+}
+
+/// Runtime environment for a processor.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessorRuntimeOutput {
+    #[default]
+    Rust,
+    Python,
+    TypeScript,
+}
+
+/// Descriptor for a processor type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProcessorDescriptorOutput {
+    /// Structured processor identity — `@org/package/Type@version`
+    /// rendered as four typed fields on the wire.
+    pub name: SchemaIdentOutput,
+    /// Human-readable description.
+    pub description: String,
+    /// Semantic version string.
+    pub version: String,
+    /// Repository URL.
+    pub repository: String,
+    /// Runtime environment.
+    #[serde(default)]
+    pub runtime: ProcessorRuntimeOutput,
+    /// Entrypoint for non-Rust runtimes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+    /// Reference to config schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_schema: Option<String>,
+    /// Input port descriptors.
+    pub inputs: Vec<PortDescriptorOutput>,
+    /// Output port descriptors.
+    pub outputs: Vec<PortDescriptorOutput>,
+    /// Code examples in different languages.
+    pub examples: CodeExamplesOutput,
+}
+
+/// A configuration field for a processor.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConfigFieldOutput {
+    /// Field name.
+    pub name: String,
+    /// Field type as string (e.g., "String", "u32", "Option<PathBuf>").
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Whether the field is required.
+    pub required: bool,
+    /// Human-readable description.
+    pub description: String,
+}
+
+/// Descriptor for a processor port.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PortDescriptorOutput {
+    /// Port name.
+    pub name: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Structured schema identifier for data flowing through this port.
+    ///
+    /// Resolved from the build-time embedded-schema segment table. `None`
+    /// when the port's declared schema doesn't have a structured-segment
+    /// representation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaIdentOutput>,
+    /// Whether the port is required.
+    pub required: bool,
+}
+
+/// Code examples for a processor in different languages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CodeExamplesOutput {
+    /// Rust code example.
+    pub rust: String,
+    /// Python code example.
+    pub python: String,
+    /// TypeScript code example.
+    pub typescript: String,
+}
+
+/// Descriptor for a data schema.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SchemaDescriptorOutput {
+    /// Schema name (e.g., "VideoFrame", "AudioFrame").
+    pub name: String,
+    /// Semantic version.
+    pub version: SemanticVersionOutput,
+    /// Fields in this schema.
+    pub fields: Vec<SchemaFieldOutput>,
+    /// How data is read from the link buffer.
+    pub read_behavior: LinkBufferReadModeOutput,
+    /// Default ring buffer capacity.
+    pub default_capacity: usize,
+}
+
+/// A field in a data schema.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SchemaFieldOutput {
+    /// Field name.
+    pub name: String,
+    /// Human-readable description.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Type name (e.g., "u32", "Arc<wgpu::Texture>").
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Shape for multi-dimensional fields (e.g., [512] for embeddings).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shape: Vec<usize>,
+    /// Whether this is an internal field (not serializable).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub internal: bool,
+}
+
+/// How data is read from the link buffer.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkBufferReadModeOutput {
+    /// Drain buffer and return only the newest frame (optimal for video).
+    #[default]
+    SkipToLatest,
+    /// Read next frame in FIFO order (required for audio).
+    ReadNextInOrder,
+}
+
+// =============================================================================
+// Graph Components
+// =============================================================================
+
+/// `components` key of a processor node's [`ProcessorMetricsOutput`].
+pub const PROCESSOR_METRICS_COMPONENT: &str = "metrics";
+
+/// `components` key of a link's [`LinkFrameDropsOutput`].
+pub const LINK_FRAME_DROPS_COMPONENT: &str = "frame_drops";
+
+/// `components` key of the graph's [`GpuPoolStatsOutput`].
+pub const GPU_POOL_COMPONENT: &str = "gpu_pool";
+
+/// `components` key of a processor node's lifecycle state (`"Running"`,
+/// `"Paused"`, ...).
+pub const PROCESSOR_STATE_COMPONENT: &str = "state";
+
+/// Runtime metrics for a processor (`components.metrics`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProcessorMetricsOutput {
+    /// Frames per second throughput.
+    pub throughput_fps: f64,
+    /// 50th percentile latency in milliseconds.
+    pub latency_p50_ms: f64,
+    /// 99th percentile latency in milliseconds.
+    pub latency_p99_ms: f64,
+    /// Total frames processed.
+    pub frames_processed: u64,
+    /// Total frames dropped.
+    pub frames_dropped: u64,
+}
+
+/// Delivery and drop counters for a link (`components.frame_drops`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LinkFrameDropsOutput {
+    /// Frames read by the destination since the link was wired.
+    pub frames_delivered: u64,
+    /// Frames evicted unread since the link was wired.
+    pub frames_dropped: u64,
+    /// Frames waiting in the destination mailbox when the graph was read.
+    #[serde(default)]
+    pub queue_depth: u64,
+    /// Length of the sliding window the `window_*` fields cover.
+    pub window_secs: u64,
+    /// Frames dropped within the window.
+    pub window_frames_dropped: u64,
+    /// Dropped share of the frames that left the link within the window.
+    pub window_drop_rate: f64,
+    /// Whether the window's drop rate is over the congestion threshold.
+    pub congested: bool,
+}
+
+/// Occupancy of the runtime's GPU texture pool (`components.gpu_pool` on
+/// the graph).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GpuPoolStatsOutput {
+    /// Pooled textures currently allocated.
+    pub total_textures: u64,
+    /// Pooled textures currently held by processors.
+    pub textures_in_use: u64,
+    /// Textures the pool's buckets may hold before acquires hit the
+    /// exhaustion policy.
+    pub capacity: u64,
+    /// `textures_in_use / capacity`.
+    pub occupancy: f64,
+    /// Most textures ever in use at once.
+    pub high_water_in_use: u64,
+    /// Device allocations that failed while growing the pool.
+    pub allocation_failures: u64,
+    /// Acquires that failed because the pool was exhausted.
+    pub exhausted_acquires: u64,
+    /// Whether occupancy is over the pressure threshold or acquires are
+    /// failing.
+    pub under_pressure: bool,
+}
+
+impl GraphResponse {
+    /// The GPU texture pool component; `None` before the runtime started.
+    pub fn gpu_pool(&self) -> Option<GpuPoolStatsOutput> {
+        component(&self.components, GPU_POOL_COMPONENT)
+    }
+}
+
+impl ProcessorNodeOutput {
+    /// The lifecycle state component, e.g. `"Running"`.
+    pub fn state(&self) -> Option<&str> {
+        self.components
+            .get(PROCESSOR_STATE_COMPONENT)
+            .and_then(|state| state.as_str())
+    }
+
+    /// The metrics component; `None` when the node carries none or it does
+    /// not parse.
+    pub fn metrics(&self) -> Option<ProcessorMetricsOutput> {
+        component(&self.components, PROCESSOR_METRICS_COMPONENT)
+    }
+}
+
+impl LinkOutput {
+    /// The frame-drop counters; `None` until the link is wired.
+    pub fn frame_drops(&self) -> Option<LinkFrameDropsOutput> {
+        component(&self.components, LINK_FRAME_DROPS_COMPONENT)
+    }
+}
+
+fn component<T: serde::de::DeserializeOwned>(
+    components: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Option<T> {
+    components
+        .get(key)
+        .and_then(|value| T::deserialize(value).ok())
+}
+
+// =============================================================================
+// Requests and Responses
+// =============================================================================
+
+/// Body of `POST /api/processor`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateProcessorRequest {
+    /// Structured processor identity — the four-field map form of
+    /// `@org/package/Type@version`. The structured-everywhere rule
+    /// applies on the HTTP API too — bare strings like
+    /// `"CameraProcessor"` are rejected at deserialize time.
+    pub processor_type: SchemaIdentOutput,
+    /// Processor-specific configuration as JSON
+    pub config: serde_json::Value,
+}
+
+/// Body of `PUT /api/processors/{id}/config`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateProcessorConfigRequest {
+    /// Replacement processor configuration as JSON. Replaces the whole
+    /// config; callers wanting a partial update read-modify-write it from
+    /// `GET /api/graph`.
+    pub config: serde_json::Value,
+}
+
+/// Body of `POST /api/connections`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateConnectionRequest {
+    /// Source processor ID
+    pub from_processor: String,
+    /// Source output port name
+    pub from_port: String,
+    /// Destination processor ID
+    pub to_processor: String,
+    /// Destination input port name
+    pub to_port: String,
+}
+
+/// Response carrying the id of a created processor or connection.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct IdResponse {
+    /// The created resource ID
+    pub id: String,
+}
+
+/// Body of most error responses. Typed errors (unknown processor type,
+/// processor or port not found) add fields next to `error`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    /// Error message
+    pub error: String,
+}
+
+/// Body of `GET /ready` and `GET /live`, returned with `200` when every
+/// check passed and `503` otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProbeResponse {
+    /// `true` when every check passed.
+    pub ok: bool,
+    pub checks: Vec<ProbeCheck>,
+}
+
+/// One probe criterion and whether it holds.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProbeCheck {
+    /// `processors`, `link:<designation>`, `graph` or `watchdog`.
+    pub name: String,
+    pub ok: bool,
+    /// Why the check failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_components_parse_into_typed_metrics() {
+        let graph: GraphResponse = serde_json::from_value(serde_json::json!({
+            "nodes": [{
+                "id": "proc-1",
+                "type": {
+                    "org": "tatolab",
+                    "package": "camera",
+                    "type": "Camera",
+                    "version": { "major": 1, "minor": 0, "patch": 0 }
+                },
+                "display_name": "Camera",
+                "ports": { "inputs": [], "outputs": [] },
+                "components": {
+                    "state": "Running",
+                    "metrics": {
+                        "throughput_fps": 30.0,
+                        "latency_p50_ms": 1.5,
+                        "latency_p99_ms": 4.0,
+                        "frames_processed": 900,
+                        "frames_dropped": 2
+                    }
+                }
+            }],
+            "links": [{
+                "id": "link-1",
+                "source": { "processor_id": "proc-1", "port_name": "video" },
+                "target": { "processor_id": "proc-2", "port_name": "video_in" },
+                "components": {}
+            }]
+        }))
+        .unwrap();
+
+        let node = &graph.nodes[0];
+        assert_eq!(node.state(), Some("Running"));
+        let metrics = node.metrics().unwrap();
+        assert_eq!(metrics.frames_processed, 900);
+        assert_eq!(metrics.throughput_fps, 30.0);
+        // Unwired links carry no counters yet.
+        assert!(graph.links[0].frame_drops().is_none());
+        assert_eq!(graph.links[0].capacity, 0);
+    }
+
+    #[test]
+    fn probe_detail_is_omitted_when_absent() {
+        let check = ProbeCheck {
+            name: "watchdog".into(),
+            ok: true,
+            detail: None,
+        };
+        let json = serde_json::to_value(&check).unwrap();
+        assert!(json.get("detail").is_none());
+        let back: ProbeCheck = serde_json::from_value(json).unwrap();
+        assert!(back.ok && back.detail.is_none());
+    }
+}
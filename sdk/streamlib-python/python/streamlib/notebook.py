@@ -0,0 +1,654 @@
+# Copyright (c) 2025 Jonathan Fontanez
+# SPDX-License-Identifier: BUSL-1.1
+
+"""Jupyter helpers for prototyping pipelines against a live runtime.
+
+Everything here drives a running `streamlib-runtime` through its control
+plane — the same REST routes `streamlib repl` and the api-server demo use —
+so a notebook needs nothing beyond this module and a reachable node:
+
+    %load_ext streamlib.notebook
+    %streamlib start pipeline.json      # spawn a runtime (or: %streamlib attach)
+    %streamlib graph                    # inline SVG of the live graph
+    %streamlib grab camera.video_out    # one frame, displayed as a JPEG
+    %streamlib stop
+
+The same operations are plain functions for cells that want the values:
+`ControlPlane` wraps the REST routes, `GraphView` renders a graph as SVG,
+and `grab_frame()` returns a `FrameGrab` that Jupyter displays as an image.
+
+Frame grabs attach a temporary `@tatolab/frame-tap/FrameTap` sink to the
+output port, wait for its first JPEG, and remove it again. The tap writes
+on the runtime's filesystem, so grabs need the notebook kernel and the
+runtime on the same machine — which `%streamlib start` always is.
+
+IPython is only imported by `load_ipython_extension`; the rest of the
+module is stdlib-only and works in any Python process.
+"""
+
+from __future__ import annotations
+
+import html
+import json
+import os
+import shlex
+import shutil
+import signal
+import socket
+import subprocess
+import tempfile
+import time
+import urllib.error
+import urllib.request
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+__all__ = [
+    "ControlPlane",
+    "ControlPlaneError",
+    "FrameGrab",
+    "GraphView",
+    "ManagedRuntime",
+    "grab_frame",
+    "live_nodes",
+    "load_ipython_extension",
+    "start_runtime",
+]
+
+# Schema version of the node-registry entries this module understands
+# (`NODE_REGISTRY_SCHEMA_VERSION` in the api-server's `node_registry.rs`).
+_NODE_REGISTRY_SCHEMA_VERSION = 1
+
+# Processor type a frame grab attaches. The version is taken from the
+# runtime's registry, so any loaded frame-tap release works.
+_FRAME_TAP_ORG = "tatolab"
+_FRAME_TAP_PACKAGE = "frame-tap"
+_FRAME_TAP_TYPE = "FrameTap"
+
+
+class ControlPlaneError(RuntimeError):
+    """A control-plane request failed (non-2xx status or transport error)."""
+
+
+# ============================================================================
+# Node discovery
+# ============================================================================
+
+
+def _node_registry_dir() -> Path:
+    """Directory ApiServer-hosting runtimes register themselves in.
+
+    Mirrors `node_registry::registry_dir()`: `$XDG_RUNTIME_DIR/streamlib/nodes`,
+    falling back to the system temp dir when `XDG_RUNTIME_DIR` is unset.
+    """
+    runtime_dir = os.environ.get("XDG_RUNTIME_DIR")
+    base = Path(runtime_dir) if runtime_dir else Path(tempfile.gettempdir())
+    return base / "streamlib" / "nodes"
+
+
+def live_nodes() -> List[Dict[str, Any]]:
+    """Registry entries of runtimes whose host process is still alive.
+
+    Each entry carries `runtime_id`, `control_url`, `pid` and `hint`.
+    Entries with an unknown `schema_version` are skipped.
+    """
+    directory = _node_registry_dir()
+    if not directory.is_dir():
+        return []
+    nodes = []
+    for path in sorted(directory.glob("*.json")):
+        try:
+            entry = json.loads(path.read_text())
+        except (OSError, ValueError):
+            continue
+        if entry.get("schema_version") != _NODE_REGISTRY_SCHEMA_VERSION:
+            continue
+        if not _pid_alive(entry.get("pid")):
+            continue
+        nodes.append(entry)
+    return nodes
+
+
+def _pid_alive(pid: Any) -> bool:
+    if not isinstance(pid, int) or pid <= 0:
+        return False
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    return True
+
+
+# ============================================================================
+# Control-plane client
+# ============================================================================
+
+
+class ControlPlane:
+    """Blocking client for a runtime's REST control plane.
+
+    `STREAMLIB_MCP_TOKEN`, when set, rides as the bearer token — the same
+    token the CLI control verbs present to an auth-enabled runtime.
+    """
+
+    def __init__(self, url: str, token: Optional[str] = None, timeout: float = 30.0):
+        self.url = url.rstrip("/")
+        self.token = token if token is not None else os.environ.get("STREAMLIB_MCP_TOKEN")
+        self.timeout = timeout
+
+    @classmethod
+    def attach(cls, runtime_id: Optional[str] = None) -> "ControlPlane":
+        """Attach to a registered runtime: `runtime_id`'s node, or the sole
+        live node when omitted (an error lists the candidates otherwise)."""
+        nodes = live_nodes()
+        if runtime_id is not None:
+            for node in nodes:
+                if node["runtime_id"] == runtime_id:
+                    return cls(node["control_url"])
+            raise ControlPlaneError(
+                f"no live node with runtime_id {runtime_id!r}{_node_hint(nodes)}"
+            )
+        if len(nodes) == 1:
+            return cls(nodes[0]["control_url"])
+        if not nodes:
+            raise ControlPlaneError(
+                "no live StreamLib nodes found; start one with `%streamlib start` "
+                "or pass a control-plane URL"
+            )
+        raise ControlPlaneError(
+            f"{len(nodes)} live nodes found; pass a runtime_id{_node_hint(nodes)}"
+        )
+
+    def request(self, method: str, path: str, body: Any = None) -> Any:
+        """Issue `method path` with an optional JSON body and return the
+        decoded JSON response (`None` for an empty body, e.g. a 204)."""
+        data = None
+        headers = {}
+        if body is not None:
+            data = json.dumps(body).encode()
+            headers["content-type"] = "application/json"
+        if self.token:
+            headers["authorization"] = f"Bearer {self.token}"
+        request = urllib.request.Request(
+            f"{self.url}{path}", data=data, headers=headers, method=method
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=self.timeout) as response:
+                text = response.read().decode()
+        except urllib.error.HTTPError as error:
+            text = error.read().decode(errors="replace")
+            try:
+                message = json.loads(text).get("error", text)
+            except (ValueError, AttributeError):
+                message = text
+            raise ControlPlaneError(
+                f"{method} {path} failed: HTTP {error.code}: {message}"
+            ) from None
+        except urllib.error.URLError as error:
+            raise ControlPlaneError(f"{method} {self.url}{path} failed: {error.reason}") from None
+        return json.loads(text) if text.strip() else None
+
+    def healthy(self) -> bool:
+        """Whether `GET /health` answers."""
+        try:
+            request = urllib.request.Request(f"{self.url}/health")
+            with urllib.request.urlopen(request, timeout=2.0) as response:
+                return 200 <= response.status < 300
+        except (urllib.error.URLError, OSError):
+            return False
+
+    def graph(self) -> Dict[str, Any]:
+        return self.request("GET", "/api/graph")
+
+    def registry(self) -> Dict[str, Any]:
+        return self.request("GET", "/api/registry")
+
+    def snapshot(self) -> Dict[str, Any]:
+        """The live graph as a snapshot document `streamlib-runtime --snapshot` loads."""
+        return self.request("GET", "/api/snapshot")
+
+    def add_processor(
+        self, processor_type: Dict[str, Any], config: Optional[Dict[str, Any]] = None
+    ) -> str:
+        """Instantiate a registered processor; returns its id. `processor_type`
+        is the structured ident (`org` / `package` / `type` / `version`)."""
+        created = self.request(
+            "POST",
+            "/api/processor",
+            {"processor_type": processor_type, "config": config or {}},
+        )
+        return created["id"]
+
+    def remove_processor(self, processor_id: str) -> None:
+        self.request("DELETE", f"/api/processors/{processor_id}")
+
+    def connect(self, from_processor: str, from_port: str, to_processor: str, to_port: str) -> str:
+        """Connect an output port to an input port; returns the link id."""
+        created = self.request(
+            "POST",
+            "/api/connections",
+            {
+                "from_processor": from_processor,
+                "from_port": from_port,
+                "to_processor": to_processor,
+                "to_port": to_port,
+            },
+        )
+        return created["id"]
+
+    def disconnect(self, link_id: str) -> None:
+        self.request("DELETE", f"/api/connections/{link_id}")
+
+    def update_config(self, processor_id: str, config: Dict[str, Any]) -> None:
+        self.request("PUT", f"/api/processors/{processor_id}/config", {"config": config})
+
+    def pause(self, processor_id: str) -> None:
+        self.request("POST", f"/api/processors/{processor_id}/pause")
+
+    def resume(self, processor_id: str) -> None:
+        self.request("POST", f"/api/processors/{processor_id}/resume")
+
+    def processor_type(self, org: str, package: str, type_name: str) -> Dict[str, Any]:
+        """The registered structured ident for `@org/package/Type`, at
+        whatever version the runtime has loaded."""
+        for descriptor in self.registry().get("processors", []):
+            ident = descriptor.get("name", {})
+            key = (ident.get("org"), ident.get("package"), ident.get("type"))
+            if key == (org, package, type_name):
+                return ident
+        raise ControlPlaneError(
+            f"@{org}/{package}/{type_name} is not registered on this runtime; "
+            f"add the @{org}/{package} package to it first"
+        )
+
+    def view(self) -> "GraphView":
+        """The live graph as an inline-displayable `GraphView`."""
+        return GraphView(self.graph())
+
+    def __repr__(self) -> str:
+        return f"ControlPlane({self.url!r})"
+
+
+def _node_hint(nodes: List[Dict[str, Any]]) -> str:
+    if not nodes:
+        return ""
+    listed = ", ".join(f"{node['runtime_id']} ({node['control_url']})" for node in nodes)
+    return f". Live nodes: {listed}"
+
+
+# ============================================================================
+# Graph visualization
+# ============================================================================
+
+_NODE_WIDTH = 190
+_NODE_HEADER = 26
+_PORT_ROW = 16
+_COLUMN_GAP = 90
+_ROW_GAP = 28
+_MARGIN = 16
+
+
+class GraphView:
+    """A graph (`GET /api/graph` JSON) rendered as SVG for inline display.
+
+    Processors are laid out left to right in dependency order, each box
+    listing its inputs on the left edge and outputs on the right; links are
+    drawn port to port. Jupyter picks up `_repr_svg_` automatically.
+    """
+
+    def __init__(self, graph: Dict[str, Any]):
+        self.graph = graph
+
+    def _layout(self):
+        nodes = self.graph.get("nodes", [])
+        links = self.graph.get("links", [])
+        ids = [node["id"] for node in nodes]
+        upstream = {node_id: set() for node_id in ids}
+        for link in links:
+            source = link["source"]["processor_id"]
+            target = link["target"]["processor_id"]
+            if source in upstream and target in upstream and source != target:
+                upstream[target].add(source)
+
+        # Longest-path layering; a cycle (rejected at connect, but tolerated
+        # here) falls back to column 0 for the nodes it leaves unresolved.
+        column: Dict[str, int] = {}
+        resolving = set()
+
+        def depth(node_id: str) -> int:
+            if node_id in column:
+                return column[node_id]
+            if node_id in resolving:
+                return 0
+            resolving.add(node_id)
+            value = max((depth(parent) + 1 for parent in upstream[node_id]), default=0)
+            resolving.discard(node_id)
+            column[node_id] = value
+            return value
+
+        for node_id in ids:
+            depth(node_id)
+
+        positions = {}
+        next_y: Dict[int, int] = {}
+        for node in nodes:
+            inputs = [port["name"] for port in node.get("ports", {}).get("inputs", [])]
+            outputs = [port["name"] for port in node.get("ports", {}).get("outputs", [])]
+            height = _NODE_HEADER + _PORT_ROW * max(len(inputs), len(outputs), 1) + 6
+            col = column[node["id"]]
+            x = _MARGIN + col * (_NODE_WIDTH + _COLUMN_GAP)
+            y = next_y.get(col, _MARGIN)
+            next_y[col] = y + height + _ROW_GAP
+            positions[node["id"]] = (x, y, height, inputs, outputs, node)
+        columns = max(column.values(), default=0) + 1
+        width = 2 * _MARGIN + columns * (_NODE_WIDTH + _COLUMN_GAP) - _COLUMN_GAP
+        height = max(next_y.values(), default=_MARGIN + _ROW_GAP) - _ROW_GAP + _MARGIN
+        return positions, links, width, height
+
+    def to_svg(self) -> str:
+        positions, links, width, height = self._layout()
+        if not positions:
+            return (
+                '<svg xmlns="http://www.w3.org/2000/svg" width="200" height="40">'
+                '<text x="10" y="25" font-family="sans-serif" font-size="13" fill="#666">'
+                "(empty graph)</text></svg>"
+            )
+
+        def port_anchor(processor_id: str, port_name: str, output: bool):
+            x, y, _, inputs, outputs, _ = positions[processor_id]
+            ports = outputs if output else inputs
+            row = ports.index(port_name) if port_name in ports else 0
+            anchor_y = y + _NODE_HEADER + row * _PORT_ROW + _PORT_ROW // 2
+            return (x + _NODE_WIDTH if output else x, anchor_y)
+
+        parts = [
+            f'<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" '
+            f'font-family="sans-serif" font-size="11">'
+        ]
+        for link in links:
+            source, target = link["source"], link["target"]
+            if source["processor_id"] not in positions or target["processor_id"] not in positions:
+                continue
+            x1, y1 = port_anchor(source["processor_id"], source["port_name"], True)
+            x2, y2 = port_anchor(target["processor_id"], target["port_name"], False)
+            bend = max(abs(x2 - x1) // 2, 30)
+            parts.append(
+                f'<path d="M{x1},{y1} C{x1 + bend},{y1} {x2 - bend},{y2} {x2},{y2}" '
+                f'fill="none" stroke="#4a7bd0" stroke-width="1.5"><title>'
+                f'{html.escape(link.get("id", ""))}</title></path>'
+            )
+        for processor_id, (x, y, box_height, inputs, outputs, node) in positions.items():
+            type_name = node.get("type", {}).get("type", "?")
+            parts.append(
+                f'<rect x="{x}" y="{y}" width="{_NODE_WIDTH}" height="{box_height}" rx="5" '
+                f'fill="#f7f9fc" stroke="#8a9bb5"/>'
+                f'<text x="{x + 8}" y="{y + 16}" font-weight="bold" font-size="12">'
+                f"{html.escape(type_name)}<title>{html.escape(processor_id)}</title></text>"
+            )
+            for row, name in enumerate(inputs):
+                label_y = y + _NODE_HEADER + row * _PORT_ROW + 11
+                parts.append(
+                    f'<text x="{x + 6}" y="{label_y}" fill="#444">{html.escape(name)}</text>'
+                )
+            for row, name in enumerate(outputs):
+                label_y = y + _NODE_HEADER + row * _PORT_ROW + 11
+                parts.append(
+                    f'<text x="{x + _NODE_WIDTH - 6}" y="{label_y}" fill="#444" '
+                    f'text-anchor="end">{html.escape(name)}</text>'
+                )
+        parts.append("</svg>")
+        return "".join(parts)
+
+    def _repr_svg_(self) -> str:
+        return self.to_svg()
+
+    def __repr__(self) -> str:
+        nodes = len(self.graph.get("nodes", []))
+        links = len(self.graph.get("links", []))
+        return f"<GraphView {nodes} processors, {links} links>"
+
+
+# ============================================================================
+# Frame grabs
+# ============================================================================
+
+
+@dataclass(frozen=True)
+class FrameGrab:
+    """One sampled frame as JPEG bytes; Jupyter displays it as an image."""
+
+    source: str
+    jpeg: bytes
+
+    def _repr_jpeg_(self) -> bytes:
+        return self.jpeg
+
+    def save(self, path: os.PathLike) -> None:
+        Path(path).write_bytes(self.jpeg)
+
+
+def _split_port_address(address: str):
+    processor_id, _, port_name = address.rpartition(".")
+    if not processor_id or not port_name:
+        raise ValueError(f"expected '<processor>.<port>', got {address!r}")
+    return processor_id, port_name
+
+
+def grab_frame(
+    control: ControlPlane, source: str, timeout: float = 10.0, jpeg_quality: int = 85
+) -> FrameGrab:
+    """Sample one frame from the video output `source` (`processor.port`).
+
+    Attaches a temporary FrameTap as a fan-out branch of that output — the
+    existing consumers keep receiving every frame — and removes it once the
+    first JPEG lands or `timeout` seconds pass.
+    """
+    processor_id, port_name = _split_port_address(source)
+    tap_type = control.processor_type(_FRAME_TAP_ORG, _FRAME_TAP_PACKAGE, _FRAME_TAP_TYPE)
+    output_dir = Path(tempfile.mkdtemp(prefix="streamlib-grab-"))
+    tap_id = control.add_processor(
+        tap_type,
+        {
+            "strategy": "KeepLastK",
+            "keep_last_k": 1,
+            "output_dir": str(output_dir),
+            "jpeg_quality": jpeg_quality,
+            "filename_prefix": "grab",
+        },
+    )
+    try:
+        control.connect(processor_id, port_name, tap_id, "video_in")
+        deadline = time.monotonic() + timeout
+        while time.monotonic() < deadline:
+            # The tap writes `*.jpg.tmp` and renames, so any `*.jpg` is complete.
+            stills = sorted(output_dir.glob("*.jpg"))
+            if stills:
+                return FrameGrab(source=source, jpeg=stills[-1].read_bytes())
+            time.sleep(0.05)
+        raise TimeoutError(f"no frame from {source} within {timeout:g}s")
+    finally:
+        try:
+            control.remove_processor(tap_id)
+        finally:
+            shutil.rmtree(output_dir, ignore_errors=True)
+
+
+# ============================================================================
+# Managed runtimes
+# ============================================================================
+
+
+def _runtime_binary() -> str:
+    configured = os.environ.get("STREAMLIB_RUNTIME_BIN")
+    if configured:
+        return configured
+    found = shutil.which("streamlib-runtime")
+    if found:
+        return found
+    raise ControlPlaneError(
+        "streamlib-runtime not found; put it on PATH or set STREAMLIB_RUNTIME_BIN"
+    )
+
+
+def _free_port() -> int:
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as probe:
+        probe.bind(("127.0.0.1", 0))
+        return probe.getsockname()[1]
+
+
+class ManagedRuntime:
+    """A `streamlib-runtime` child process owned by the notebook."""
+
+    def __init__(self, process: subprocess.Popen, control: ControlPlane, snapshot: Optional[Path]):
+        self.process = process
+        self.control = control
+        self.snapshot = snapshot
+
+    @property
+    def running(self) -> bool:
+        return self.process.poll() is None
+
+    def stop(self, timeout: float = 10.0) -> None:
+        """Interrupt the runtime (its Ctrl+C shutdown path) and wait for it,
+        killing it if it hasn't exited within `timeout` seconds."""
+        if not self.running:
+            return
+        self.process.send_signal(signal.SIGINT)
+        try:
+            self.process.wait(timeout=timeout)
+        except subprocess.TimeoutExpired:
+            self.process.kill()
+            self.process.wait()
+
+    def __repr__(self) -> str:
+        state = "running" if self.running else f"exited ({self.process.returncode})"
+        return f"<ManagedRuntime pid={self.process.pid} {self.control.url} {state}>"
+
+
+def start_runtime(
+    snapshot: Optional[os.PathLike] = None,
+    port: Optional[int] = None,
+    startup_timeout: float = 90.0,
+) -> ManagedRuntime:
+    """Spawn `streamlib-runtime` on localhost, optionally loading a graph
+    snapshot, and wait until its control plane answers.
+
+    Boot includes GPU init and may build referenced packages from source on
+    first load, hence the generous default `startup_timeout`.
+    """
+    port = port or _free_port()
+    command = [_runtime_binary(), "--host", "127.0.0.1", "--port", str(port)]
+    snapshot_path = Path(snapshot).resolve() if snapshot is not None else None
+    if snapshot_path is not None:
+        command += ["--snapshot", str(snapshot_path)]
+    process = subprocess.Popen(command)
+    runtime = ManagedRuntime(process, ControlPlane(f"http://127.0.0.1:{port}"), snapshot_path)
+
+    deadline = time.monotonic() + startup_timeout
+    while time.monotonic() < deadline:
+        if not runtime.running:
+            raise ControlPlaneError(
+                f"streamlib-runtime exited during startup (code {process.returncode})"
+            )
+        if runtime.control.healthy():
+            return runtime
+        time.sleep(0.25)
+    runtime.stop()
+    raise ControlPlaneError(f"streamlib-runtime did not serve /health within {startup_timeout:g}s")
+
+
+# ============================================================================
+# IPython magics
+# ============================================================================
+
+_MAGIC_USAGE = """\
+%streamlib start [snapshot.json] [--port N]   spawn a runtime and make it current
+%streamlib attach [runtime_id | url]          use an already-running runtime
+%streamlib stop                               stop the runtime started here
+%streamlib graph                              show the current runtime's graph
+%streamlib grab <processor.port> [timeout]    show one frame from a video output
+%streamlib status                             show the current runtime"""
+
+
+class _NotebookSession:
+    """Per-kernel state behind the `%streamlib` magic."""
+
+    def __init__(self):
+        self.control: Optional[ControlPlane] = None
+        self.managed: Optional[ManagedRuntime] = None
+
+    def current(self) -> ControlPlane:
+        if self.control is None:
+            self.control = ControlPlane.attach()
+        return self.control
+
+    def run(self, line: str):
+        words = shlex.split(line)
+        if not words:
+            print(_MAGIC_USAGE)
+            return None
+        verb, args = words[0], words[1:]
+        if verb == "start":
+            return self._start(args)
+        if verb == "attach":
+            target = args[0] if args else None
+            if target and "://" in target:
+                self.control = ControlPlane(target)
+            else:
+                self.control = ControlPlane.attach(target)
+            return self.control
+        if verb == "stop":
+            if self.managed is None:
+                raise ControlPlaneError("no runtime was started by this notebook")
+            self.managed.stop()
+            if self.control is self.managed.control:
+                self.control = None
+            self.managed = None
+            return None
+        if verb == "graph":
+            return self.current().view()
+        if verb == "grab":
+            if not args:
+                raise ValueError("usage: %streamlib grab <processor.port> [timeout]")
+            timeout = float(args[1]) if len(args) > 1 else 10.0
+            return grab_frame(self.current(), args[0], timeout=timeout)
+        if verb == "status":
+            return self.managed if self.managed is not None else self.current()
+        print(_MAGIC_USAGE)
+        return None
+
+    def _start(self, args: List[str]) -> ManagedRuntime:
+        if self.managed is not None and self.managed.running:
+            raise ControlPlaneError(
+                f"a runtime is already running at {self.managed.control.url}; "
+                "`%streamlib stop` it first"
+            )
+        snapshot = None
+        port = None
+        words = iter(args)
+        for word in words:
+            if word == "--port":
+                port = int(next(words, "0")) or None
+            else:
+                snapshot = word
+        self.managed = start_runtime(snapshot, port=port)
+        self.control = self.managed.control
+        return self.managed
+
+
+def load_ipython_extension(ipython) -> None:
+    """`%load_ext streamlib.notebook` entry point: registers `%streamlib`
+    and stops a notebook-started runtime when the kernel shuts down."""
+    session = _NotebookSession()
+    ipython.register_magic_function(session.run, magic_kind="line", magic_name="streamlib")
+
+    import atexit
+
+    def stop_managed_runtime():
+        if session.managed is not None:
+            session.managed.stop()
+
+    atexit.register(stop_managed_runtime)
@@ -0,0 +1,210 @@
+# Copyright (c) 2025 Jonathan Fontanez
+# SPDX-License-Identifier: BUSL-1.1
+
+"""Tests for the Jupyter helpers in `streamlib.notebook`.
+
+The control plane is faked with a stdlib HTTP server on a loopback port, so
+these run without a runtime or IPython.
+"""
+
+import json
+import os
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+
+import pytest
+
+from streamlib import notebook
+
+FRAME_TAP_IDENT = {
+    "org": "tatolab",
+    "package": "frame-tap",
+    "type": "FrameTap",
+    "version": {"major": 1, "minor": 0, "patch": 0},
+}
+
+GRAPH = {
+    "nodes": [
+        {
+            "id": "cam",
+            "type": {"type": "Camera"},
+            "ports": {"inputs": [], "outputs": [{"name": "video_out"}]},
+        },
+        {
+            "id": "enc",
+            "type": {"type": "H264Encoder"},
+            "ports": {"inputs": [{"name": "video_in"}], "outputs": [{"name": "encoded_out"}]},
+        },
+    ],
+    "links": [
+        {
+            "id": "link-1",
+            "source": {"processor_id": "cam", "port_name": "video_out"},
+            "target": {"processor_id": "enc", "port_name": "video_in"},
+        }
+    ],
+}
+
+
+class FakeControlPlane:
+    """Loopback HTTP server answering the REST routes the helpers use and
+    recording every request as `(method, path, headers, body)`."""
+
+    def __init__(self):
+        self.requests = []
+        self.tap_configs = {}
+        fake = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def log_message(self, *args):
+                pass
+
+            def _reply(self, status, body=None):
+                payload = b"" if body is None else json.dumps(body).encode()
+                self.send_response(status)
+                self.send_header("content-length", str(len(payload)))
+                self.end_headers()
+                self.wfile.write(payload)
+
+            def _handle(self):
+                length = int(self.headers.get("content-length") or 0)
+                body = json.loads(self.rfile.read(length)) if length else None
+                fake.requests.append((self.command, self.path, dict(self.headers), body))
+                route = (self.command, self.path)
+                if route == ("GET", "/api/graph"):
+                    return self._reply(200, GRAPH)
+                if route == ("GET", "/api/registry"):
+                    return self._reply(200, {"processors": [{"name": FRAME_TAP_IDENT}]})
+                if route == ("POST", "/api/processor"):
+                    fake.tap_configs["tap-1"] = body["config"]
+                    return self._reply(200, {"id": "tap-1"})
+                if route == ("POST", "/api/connections"):
+                    # Play the FrameTap: land one still in its output dir.
+                    config = fake.tap_configs[body["to_processor"]]
+                    Path(config["output_dir"], "grab_000000.jpg").write_bytes(b"\xff\xd8jpeg")
+                    return self._reply(200, {"id": "link-2"})
+                if self.command == "DELETE":
+                    return self._reply(204)
+                if route == ("POST", "/api/processors/missing/pause"):
+                    return self._reply(
+                        404, {"error": "ProcessorNotFound", "processor_id": "missing"}
+                    )
+                return self._reply(204)
+
+            do_GET = _handle
+            do_POST = _handle
+            do_PUT = _handle
+            do_DELETE = _handle
+
+        self.server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self.server.server_address[1]}"
+        self.thread = threading.Thread(target=self.server.serve_forever, daemon=True)
+        self.thread.start()
+
+    def close(self):
+        self.server.shutdown()
+        self.server.server_close()
+
+
+@pytest.fixture
+def fake_control_plane():
+    fake = FakeControlPlane()
+    yield fake
+    fake.close()
+
+
+def test_requests_carry_the_bearer_token_and_decode_empty_bodies(fake_control_plane):
+    control = notebook.ControlPlane(fake_control_plane.url, token="secret")
+    assert control.update_config("cam", {"fps": 60}) is None
+
+    method, path, headers, body = fake_control_plane.requests[-1]
+    assert (method, path) == ("PUT", "/api/processors/cam/config")
+    assert headers["Authorization"] == "Bearer secret"
+    assert body == {"config": {"fps": 60}}
+
+
+def test_http_errors_raise_with_the_server_message(fake_control_plane):
+    control = notebook.ControlPlane(fake_control_plane.url, token="")
+    with pytest.raises(notebook.ControlPlaneError, match="HTTP 404: ProcessorNotFound"):
+        control.pause("missing")
+
+
+def test_graph_view_lays_out_processors_in_dependency_order():
+    svg = notebook.GraphView(GRAPH).to_svg()
+    assert svg.startswith("<svg")
+    for label in ("Camera", "H264Encoder", "video_out", "video_in", "encoded_out", "link-1"):
+        assert label in svg
+    # The encoder sits in the column after its upstream camera.
+    camera_x = svg.index('<rect x="16"')
+    encoder_x = svg.index(f'<rect x="{16 + notebook._NODE_WIDTH + notebook._COLUMN_GAP}"')
+    assert camera_x < encoder_x
+    assert "(empty graph)" in notebook.GraphView({"nodes": [], "links": []}).to_svg()
+
+
+def test_grab_frame_attaches_a_frame_tap_and_removes_it(fake_control_plane):
+    control = notebook.ControlPlane(fake_control_plane.url, token="")
+    grab = notebook.grab_frame(control, "cam.video_out", timeout=5.0)
+
+    assert grab.jpeg == b"\xff\xd8jpeg"
+    assert grab._repr_jpeg_() == grab.jpeg
+
+    routes = [(method, path) for method, path, _, _ in fake_control_plane.requests]
+    assert ("DELETE", "/api/processors/tap-1") in routes
+    create = next(body for method, path, _, body in fake_control_plane.requests
+                  if (method, path) == ("POST", "/api/processor"))
+    assert create["processor_type"] == FRAME_TAP_IDENT
+    assert create["config"]["strategy"] == "KeepLastK"
+    connect = next(body for method, path, _, body in fake_control_plane.requests
+                   if (method, path) == ("POST", "/api/connections"))
+    assert connect == {
+        "from_processor": "cam",
+        "from_port": "video_out",
+        "to_processor": "tap-1",
+        "to_port": "video_in",
+    }
+    assert not Path(create["config"]["output_dir"]).exists()
+
+
+def test_grab_frame_rejects_a_malformed_port_address(fake_control_plane):
+    control = notebook.ControlPlane(fake_control_plane.url, token="")
+    with pytest.raises(ValueError, match="<processor>.<port>"):
+        notebook.grab_frame(control, "cam")
+
+
+def test_live_nodes_skips_dead_pids_and_unknown_schema_versions(tmp_path, monkeypatch):
+    monkeypatch.setenv("XDG_RUNTIME_DIR", str(tmp_path))
+    nodes_dir = tmp_path / "streamlib" / "nodes"
+    nodes_dir.mkdir(parents=True)
+
+    def write(runtime_id, pid, schema_version=1):
+        entry = {
+            "schema_version": schema_version,
+            "runtime_id": runtime_id,
+            "control_url": f"http://127.0.0.1/{runtime_id}",
+            "pid": pid,
+            "hint": "test",
+        }
+        (nodes_dir / f"{runtime_id}.json").write_text(json.dumps(entry))
+
+    write("Ralive", os.getpid())
+    write("Rfuture", os.getpid(), schema_version=99)
+    write("Rdead", 2**22 + 12345)
+
+    assert [node["runtime_id"] for node in notebook.live_nodes()] == ["Ralive"]
+    assert notebook.ControlPlane.attach().url == "http://127.0.0.1/Ralive"
+    with pytest.raises(notebook.ControlPlaneError, match="Ralive"):
+        notebook.ControlPlane.attach("Rdead")
+
+
+def test_magic_attach_and_graph_use_the_current_control_plane(fake_control_plane):
+    session = notebook._NotebookSession()
+    control = session.run(f"attach {fake_control_plane.url}")
+    assert control.url == fake_control_plane.url
+
+    view = session.run("graph")
+    assert isinstance(view, notebook.GraphView)
+    assert repr(view) == "<GraphView 2 processors, 1 links>"
+
+    with pytest.raises(notebook.ControlPlaneError, match="no runtime was started"):
+        session.run("stop")
@@ -137,6 +137,7 @@ fn fixture_frame_traverses_the_inline_forward_processor() {
             trust_tier: ChannelTrustTier::Trusted,
             expected_payload_bytes: 4096,
             ceiling_bytes: TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+            cipher: None,
         },
     );
     output_writer_inner.add_channel_notifier("video_out", "L-video-forward", notifier);
@@ -76,21 +76,30 @@ pub mod sdk {
     // `pub use streamlib_engine::*` items below and the Tier-3
     // `engine_internal` namespace.
 
+    pub use streamlib_engine::core::chaos;
     pub use streamlib_engine::core::color;
     pub use streamlib_engine::core::context;
     pub use streamlib_engine::core::descriptors;
     pub use streamlib_engine::core::display_info;
     pub use streamlib_engine::core::error;
     pub use streamlib_engine::core::execution;
+    pub use streamlib_engine::core::frame_snapshot;
     pub use streamlib_engine::core::graph;
+    pub use streamlib_engine::core::graph_edit_history;
+    pub use streamlib_engine::core::graph_patch;
     pub use streamlib_engine::core::graph_snapshot;
     pub use streamlib_engine::core::json_schema;
     pub use streamlib_engine::core::media_clock;
+    pub use streamlib_engine::core::midi_mapping;
+    pub use streamlib_engine::core::parameter_automation;
     /// Plugin-loading host-services payload + cdylib install helper
     /// — referenced from `streamlib_plugin_abi::export_plugin!`
     /// macro expansion.
     pub use streamlib_engine::core::plugin;
+    pub use streamlib_engine::core::port_recording;
     pub use streamlib_engine::core::prelude;
+    pub use streamlib_engine::core::preset_morph;
+    pub use streamlib_engine::core::processor_schedule;
     pub use streamlib_engine::core::pubsub;
     pub use streamlib_engine::core::rhi;
     pub use streamlib_engine::core::runtime;
@@ -130,6 +139,7 @@ pub mod sdk {
     }
 
     pub use streamlib_engine::core::sync;
+    pub use streamlib_engine::core::testing;
     pub use streamlib_engine::core::texture;
 
     // ---- App authoring sugar ----
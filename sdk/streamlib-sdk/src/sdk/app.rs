@@ -91,7 +91,8 @@ impl App {
 
     /// Connect an output endpoint to an input endpoint — `((&from, "out"),
     /// (&to, "in"))`. A nonexistent port surfaces the runtime's
-    /// [`Error::ProcessorPortNotFound`] unchanged.
+    /// [`Error::ProcessorPortNotFound`] unchanged, and a concrete schema
+    /// mismatch its [`Error::Topology`].
     pub fn connect(
         &self,
         from: AppPortEndpoint<'_>,
@@ -103,11 +104,10 @@ impl App {
         )
     }
 
-    /// Connect two endpoints under explicit [`ConnectOptions`] — the strict
-    /// schema-validation opt-in for a safety-critical channel. Under
+    /// Connect two endpoints under explicit [`ConnectOptions`]. Under
     /// [`ConnectOptions::strict`] a concrete producer/consumer schema mismatch
-    /// surfaces the runtime's [`Error::SchemaIdentMismatch`] at the wiring site
-    /// instead of only warning.
+    /// surfaces the runtime's [`Error::SchemaIdentMismatch`] at the wiring
+    /// site; under [`ConnectOptions::loose`] it only warns.
     pub fn connect_with(
         &self,
         from: AppPortEndpoint<'_>,
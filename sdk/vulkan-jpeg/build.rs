@@ -3,9 +3,9 @@
 
 #![allow(clippy::disallowed_macros)] // build.rs uses println!/eprintln! for `cargo:` directives
 
-//! Build script: compiles the fused JPEG decode compute shader at
-//! `src/shaders/jpeg_decode.comp` to SPIR-V via `glslc` and stages
-//! the artifact in `OUT_DIR` for `include_bytes!` to consume at compile
+//! Build script: compiles the fused JPEG decode and encode compute
+//! shaders under `src/shaders/` to SPIR-V via `glslc` and stages
+//! the artifacts in `OUT_DIR` for `include_bytes!` to consume at compile
 //! time. Linux-only — the GPU kernel is gated behind `target_os = "linux"`.
 
 fn main() {
@@ -18,8 +18,10 @@ fn compile_shaders() {
     use std::path::{Path, PathBuf};
     use std::process::Command;
 
-    let shaders: &[(&str, &str, &str)] =
-        &[("src/shaders/jpeg_decode.comp", "jpeg_decode.spv", "compute")];
+    let shaders: &[(&str, &str, &str)] = &[
+        ("src/shaders/jpeg_decode.comp", "jpeg_decode.spv", "compute"),
+        ("src/shaders/jpeg_encode.comp", "jpeg_encode.spv", "compute"),
+    ];
 
     let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
 
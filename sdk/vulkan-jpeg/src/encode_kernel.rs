@@ -0,0 +1,158 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Fused JPEG encode compute kernel: RGB -> BT.601 full-range YCbCr,
+//! 4:2:0 chroma downsample, 8x8 forward DCT, quantization. Writes
+//! zig-zag-ordered, MCU-interleaved `i32` coefficients to a HOST_VISIBLE
+//! SSBO for [`crate::write_baseline_420`] to Huffman-code on the host.
+//!
+//! Built through the same cdylib-safe FullAccess surface as
+//! [`crate::kernel::JpegDecodeKernel`] — `create_compute_kernel` +
+//! `acquire_storage_buffer`, no raw `HostVulkanDevice`.
+
+use streamlib_plugin_sdk::sdk::context::GpuContextFullAccess;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{
+    ComputeBindingSpec, ComputeKernelDescriptor, StorageBuffer, Texture, VulkanComputeKernel,
+};
+
+use crate::writer::{MCU_COEFFICIENTS_420, QuantTables, mcu_grid_420};
+
+const BINDINGS: &[ComputeBindingSpec] = &[
+    ComputeBindingSpec::sampled_texture(0), // RGBA / BGRA source
+    ComputeBindingSpec::storage_buffer(1),  // quant tables (u32 zero-extended from u16)
+    ComputeBindingSpec::storage_buffer(2),  // coefficients out (i32)
+];
+
+/// Push constants matching the GLSL `PushConstants` block.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct JpegEncodePushConstants {
+    width: u32,
+    height: u32,
+    mcus_h: u32,
+    mcus_v: u32,
+}
+
+const PUSH_CONSTANT_SIZE: u32 = std::mem::size_of::<JpegEncodePushConstants>() as u32;
+
+/// Byte size for the quant-table HOST_VISIBLE SSBO: luma + chroma, 64
+/// entries each, packed as `u32`.
+pub const ENCODE_QUANT_TABLE_BUFFER_BYTES: u64 = 128 * 4;
+
+/// Byte size of the coefficient SSBO for a `width x height` 4:2:0 encode.
+/// The kernel writes one `i32` per coefficient, [`MCU_COEFFICIENTS_420`]
+/// per 16x16 MCU.
+pub fn coefficient_buffer_bytes_420(width: u32, height: u32) -> u64 {
+    let (mcus_h, mcus_v) = mcu_grid_420(width, height);
+    u64::from(mcus_h) * u64::from(mcus_v) * MCU_COEFFICIENTS_420 as u64 * 4
+}
+
+/// Fused JPEG encode kernel.
+pub struct JpegEncodeKernel {
+    kernel: VulkanComputeKernel,
+}
+
+impl JpegEncodeKernel {
+    /// Build the kernel through the FullAccess `create_compute_kernel`
+    /// primitive. Same construction contract as
+    /// [`crate::kernel::JpegDecodeKernel::new`].
+    pub fn new(full_access: &GpuContextFullAccess) -> Result<Self> {
+        let spv: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/jpeg_encode.spv"));
+        let kernel = full_access.create_compute_kernel(&ComputeKernelDescriptor {
+            label: "jpeg_encode",
+            spv,
+            bindings: BINDINGS,
+            push_constant_size: PUSH_CONSTANT_SIZE,
+        })?;
+        Ok(Self { kernel })
+    }
+
+    /// Transform the top-left `width x height` of `source` into quantized
+    /// coefficients in `coef_buf`, using `tables` (uploaded into
+    /// `qt_buf`). Waits on the kernel's fence before returning, so the
+    /// coefficients are readable through `coef_buf.mapped_ptr()` on
+    /// return.
+    ///
+    /// `source` must be sampleable (`TEXTURE_BINDING`) and already in
+    /// `SHADER_READ_ONLY_OPTIMAL` (caller's responsibility). Both buffers
+    /// must be HOST_VISIBLE storage buffers from
+    /// `GpuContextFullAccess::acquire_storage_buffer`; undersized buffers
+    /// surface as a typed error.
+    pub fn dispatch_pooled(
+        &self,
+        source: &Texture,
+        width: u32,
+        height: u32,
+        tables: &QuantTables,
+        qt_buf: &StorageBuffer,
+        coef_buf: &StorageBuffer,
+    ) -> Result<()> {
+        let needed = coefficient_buffer_bytes_420(width, height);
+        if needed > coef_buf.byte_size() {
+            return Err(Error::GpuError(format!(
+                "jpeg_encode: coefficient buffer too small — need {} bytes, pool sized for {} \
+                 (rebuild SimpleJpegEncoder with larger max_width/max_height)",
+                needed,
+                coef_buf.byte_size(),
+            )));
+        }
+        if ENCODE_QUANT_TABLE_BUFFER_BYTES > qt_buf.byte_size() {
+            return Err(Error::GpuError(format!(
+                "jpeg_encode: quant-table buffer too small — need {} bytes, pool sized for {}",
+                ENCODE_QUANT_TABLE_BUFFER_BYTES,
+                qt_buf.byte_size(),
+            )));
+        }
+
+        let qt_ptr = qt_buf.mapped_ptr();
+        if qt_ptr.is_null() || coef_buf.mapped_ptr().is_null() {
+            return Err(Error::GpuError(
+                "jpeg_encode: storage buffer is not HOST_VISIBLE (null mapped pointer)".into(),
+            ));
+        }
+        let qt_words: Vec<u32> = tables
+            .luma
+            .iter()
+            .chain(tables.chroma.iter())
+            .map(|&q| u32::from(q))
+            .collect();
+        let qt_bytes = bytemuck::cast_slice::<u32, u8>(&qt_words);
+        // SAFETY: size-checked above; mapped pointer non-null (checked)
+        // and valid for the full allocation.
+        unsafe {
+            std::ptr::copy_nonoverlapping(qt_bytes.as_ptr(), qt_ptr, qt_bytes.len());
+        }
+
+        let (mcus_h, mcus_v) = mcu_grid_420(width, height);
+        self.kernel.set_sampled_texture(0, source)?;
+        self.kernel.set_storage_buffer_storage(1, qt_buf)?;
+        self.kernel.set_storage_buffer_storage(2, coef_buf)?;
+        self.kernel
+            .set_push_constants_value(&JpegEncodePushConstants {
+                width,
+                height,
+                mcus_h,
+                mcus_v,
+            })?;
+        // One 8x8 workgroup per 16x16 MCU.
+        self.kernel.dispatch(mcus_h, mcus_v, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constants_match_the_shader_block() {
+        assert_eq!(PUSH_CONSTANT_SIZE, 16);
+    }
+
+    #[test]
+    fn coefficient_buffer_rounds_up_to_whole_mcus() {
+        // 17x1 needs two MCUs across, one down.
+        assert_eq!(coefficient_buffer_bytes_420(17, 1), 2 * 384 * 4);
+        assert_eq!(coefficient_buffer_bytes_420(1920, 1080), 120 * 68 * 384 * 4);
+    }
+}
@@ -3,7 +3,8 @@
 
 use thiserror::Error;
 
-/// Errors surfaced by the JPEG parser + Huffman entropy decoder.
+/// Errors surfaced by the JPEG parser + Huffman entropy decoder and the
+/// bitstream writer.
 #[derive(Debug, Error)]
 pub enum JpegError {
     #[error("unexpected end of input at byte {offset}")]
@@ -52,6 +53,9 @@ pub enum JpegError {
         offset: usize,
     },
 
+    #[error("expected {expected} quantized coefficients for the frame, got {actual}")]
+    CoefficientCountMismatch { expected: usize, actual: usize },
+
     #[error("unsupported feature: {0}")]
     Unsupported(&'static str),
 }
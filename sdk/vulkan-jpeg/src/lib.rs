@@ -1,7 +1,7 @@
 // Copyright (c) 2025 Jonathan Fontanez
 // SPDX-License-Identifier: BUSL-1.1
 
-//! JPEG decode for streamlib.
+//! JPEG decode and encode for streamlib.
 //!
 //! - Baseline-sequential JPEG marker parser (SOI/DQT/DHT/SOF0/SOS/DRI/EOI,
 //!   skips APPn/COM).
@@ -11,6 +11,11 @@
 //!   that dequantizes, runs the 8x8 IDCT, upsamples 4:2:0 chroma, and
 //!   converts BT.601 full-range YCbCr to RGB, writing the result to a
 //!   caller-supplied `rgba8` storage image.
+//! - The reverse direction: on Linux, a fused encode kernel
+//!   ([`encode_kernel::JpegEncodeKernel`]) that converts RGB to YCbCr,
+//!   downsamples chroma to 4:2:0, runs the forward DCT and quantizes,
+//!   followed by a host Huffman coder that writes a baseline JFIF file
+//!   ([`write_baseline_420`]). [`SimpleJpegEncoder`] wraps both.
 
 mod bit_reader;
 pub mod color;
@@ -20,14 +25,19 @@ mod huffman;
 mod marker;
 mod parser;
 mod scan;
+mod writer;
 
 #[cfg(target_os = "linux")]
 pub mod backend;
 #[cfg(target_os = "linux")]
+pub mod encode_kernel;
+#[cfg(target_os = "linux")]
 pub mod kernel;
 #[cfg(target_os = "linux")]
 pub mod simple_decoder;
 #[cfg(target_os = "linux")]
+pub mod simple_encoder;
+#[cfg(target_os = "linux")]
 pub mod vulkan_compute_backend;
 
 pub use color::{AdobeMetadata, AdobeTransform, ExifColorSpace, JfifMetadata, JpegColorInfo};
@@ -40,14 +50,21 @@ pub use header::{
 };
 pub use huffman::HuffmanClass;
 pub use marker::ZIGZAG;
+pub use writer::{
+    MCU_COEFFICIENTS_420, MCU_SIDE_420, QuantTables, mcu_grid_420, write_baseline_420,
+};
 
 #[cfg(target_os = "linux")]
 pub use backend::{JpegBackendKind, JpegDecodeBackend};
 #[cfg(target_os = "linux")]
+pub use encode_kernel::JpegEncodeKernel;
+#[cfg(target_os = "linux")]
 pub use kernel::{JPEG_DECODE_WORKGROUP_SIZE, JpegDecodeKernel};
 #[cfg(target_os = "linux")]
 pub use simple_decoder::{JpegDecodeOutput, MAX_FRAMES_IN_FLIGHT, SimpleJpegDecoder};
 #[cfg(target_os = "linux")]
+pub use simple_encoder::SimpleJpegEncoder;
+#[cfg(target_os = "linux")]
 pub use vulkan_compute_backend::VulkanComputeBackend;
 
 /// Parse and entropy-decode a baseline-sequential JPEG bitstream.
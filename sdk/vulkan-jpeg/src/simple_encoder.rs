@@ -0,0 +1,160 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! High-level JPEG encoder API — the mirror of
+//! [`crate::SimpleJpegDecoder`]. The GPU does the per-pixel work (color
+//! conversion, chroma downsample, forward DCT, quantization) through
+//! [`JpegEncodeKernel`]; the host Huffman-codes the coefficients and
+//! writes the JFIF file through [`crate::write_baseline_420`].
+//!
+//! Construction is privileged (kernel pipeline + SSBOs sized for the
+//! declared maxima). Per-frame [`SimpleJpegEncoder::encode`] reuses them —
+//! no `vkAllocateMemory` in steady state.
+
+use streamlib_plugin_sdk::sdk::context::GpuContextFullAccess;
+use streamlib_plugin_sdk::sdk::error::{Error, Result};
+use streamlib_plugin_sdk::sdk::rhi::{StorageBuffer, Texture};
+
+use crate::encode_kernel::{
+    ENCODE_QUANT_TABLE_BUFFER_BYTES, JpegEncodeKernel, coefficient_buffer_bytes_420,
+};
+use crate::writer::{MCU_COEFFICIENTS_420, QuantTables, mcu_grid_420, write_baseline_420};
+
+/// Largest frame edge a baseline JPEG can describe (16-bit SOF0 fields).
+const MAX_JPEG_DIMENSION: u32 = u16::MAX as u32;
+
+/// High-level GPU JPEG encoder. Produces baseline 4:2:0 JFIF files.
+pub struct SimpleJpegEncoder {
+    kernel: JpegEncodeKernel,
+    qt_buf: StorageBuffer,
+    coef_buf: StorageBuffer,
+    tables: QuantTables,
+    quality: u8,
+    max_width: u32,
+    max_height: u32,
+}
+
+impl std::fmt::Debug for SimpleJpegEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleJpegEncoder")
+            .field("quality", &self.quality)
+            .field("max_width", &self.max_width)
+            .field("max_height", &self.max_height)
+            .finish()
+    }
+}
+
+impl SimpleJpegEncoder {
+    /// Construct an encoder for frames up to `(max_width, max_height)`
+    /// pixels at `quality` (1..=100, IJG scale).
+    ///
+    /// Must be called inside a
+    /// [`streamlib_plugin_sdk::sdk::context::GpuContextFullAccess`] scope,
+    /// same as [`crate::SimpleJpegDecoder::new`].
+    pub fn new(
+        full_access: &GpuContextFullAccess,
+        max_width: u32,
+        max_height: u32,
+        quality: u8,
+    ) -> Result<Self> {
+        if max_width == 0 || max_height == 0 {
+            return Err(Error::GpuError(format!(
+                "SimpleJpegEncoder::new: max dimensions must be non-zero, got {}x{}",
+                max_width, max_height,
+            )));
+        }
+        if max_width > MAX_JPEG_DIMENSION || max_height > MAX_JPEG_DIMENSION {
+            return Err(Error::NotSupported(format!(
+                "SimpleJpegEncoder::new: {}x{} exceeds the baseline JPEG limit of {}",
+                max_width, max_height, MAX_JPEG_DIMENSION,
+            )));
+        }
+
+        let kernel = JpegEncodeKernel::new(full_access)?;
+        let qt_buf = full_access.acquire_storage_buffer(ENCODE_QUANT_TABLE_BUFFER_BYTES)?;
+        let coef_buf = full_access
+            .acquire_storage_buffer(coefficient_buffer_bytes_420(max_width, max_height))?;
+
+        tracing::debug!(
+            target: "vulkan_jpeg::encoder",
+            max_width,
+            max_height,
+            quality,
+            "SimpleJpegEncoder constructed",
+        );
+        Ok(Self {
+            kernel,
+            qt_buf,
+            coef_buf,
+            tables: QuantTables::for_quality(quality),
+            quality: quality.clamp(1, 100),
+            max_width,
+            max_height,
+        })
+    }
+
+    /// Encode the top-left `width x height` of `texture` into `out`
+    /// (cleared first). Blocks until the GPU pass finishes.
+    ///
+    /// `texture` must be an 8-bit RGBA / BGRA texture with
+    /// `TEXTURE_BINDING` usage, already in `SHADER_READ_ONLY_OPTIMAL`
+    /// (caller's responsibility — same contract shape as the decoder's
+    /// `GENERAL` output requirement).
+    pub fn encode(
+        &mut self,
+        texture: &Texture,
+        width: u32,
+        height: u32,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        if width == 0 || height == 0 || width > self.max_width || height > self.max_height {
+            return Err(Error::GpuError(format!(
+                "SimpleJpegEncoder::encode: frame {}x{} outside encoder maxima {}x{} \
+                 (rebuild SimpleJpegEncoder with larger max_width/max_height)",
+                width, height, self.max_width, self.max_height,
+            )));
+        }
+
+        self.kernel.dispatch_pooled(
+            texture,
+            width,
+            height,
+            &self.tables,
+            &self.qt_buf,
+            &self.coef_buf,
+        )?;
+
+        let (mcus_h, mcus_v) = mcu_grid_420(width, height);
+        let count = mcus_h as usize * mcus_v as usize * MCU_COEFFICIENTS_420;
+        // SAFETY: `dispatch_pooled` checked the buffer is HOST_VISIBLE and
+        // at least `count` i32s long, and waited on the kernel fence, so
+        // the GPU writes are complete. `acquire_storage_buffer` memory is
+        // mapped at a 4-byte-aligned offset.
+        let coefficients =
+            unsafe { std::slice::from_raw_parts(self.coef_buf.mapped_ptr() as *const i32, count) };
+
+        write_baseline_420(width as u16, height as u16, &self.tables, coefficients, out)
+            .map_err(|e| Error::GpuError(format!("jpeg bitstream: {e}")))
+    }
+
+    /// Change the quality used from the next [`Self::encode`] on.
+    pub fn set_quality(&mut self, quality: u8) {
+        self.tables = QuantTables::for_quality(quality);
+        self.quality = quality.clamp(1, 100);
+    }
+
+    /// Current quality (1..=100).
+    pub fn quality(&self) -> u8 {
+        self.quality
+    }
+
+    /// Declared maximum width this encoder can handle, in pixels.
+    pub fn max_width(&self) -> u32 {
+        self.max_width
+    }
+
+    /// Declared maximum height this encoder can handle, in pixels.
+    pub fn max_height(&self) -> u32 {
+        self.max_height
+    }
+}
@@ -0,0 +1,438 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Baseline-sequential JPEG bitstream writer — the CPU half of
+//! [`crate::SimpleJpegEncoder`].
+//!
+//! Takes quantized 4:2:0 DCT coefficients (zig-zag order, MCU-interleaved
+//! `Y0 Y1 Y2 Y3 Cb Cr`, exactly what the encode kernel writes) and emits a
+//! JFIF file: SOI / APP0 / DQT / SOF0 / DHT / SOS, Huffman-coded entropy
+//! data, EOI. Huffman tables are the ITU-T T.81 Annex K.3 examples —
+//! every baseline decoder accepts them and they cost no per-frame
+//! statistics pass.
+
+use crate::error::{JpegError, JpegResult};
+use crate::marker::{self, ZIGZAG};
+
+/// Coefficients per 4:2:0 MCU: four Y blocks plus one Cb and one Cr.
+pub const MCU_COEFFICIENTS_420: usize = 6 * 64;
+
+/// 4:2:0 MCU edge in pixels.
+pub const MCU_SIDE_420: u32 = 16;
+
+/// T.81 Annex K Table K.1 — luminance quantization, natural order.
+const K1_LUMINANCE: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+/// T.81 Annex K Table K.2 — chrominance quantization, natural order.
+const K2_CHROMINANCE: [u16; 64] = [
+    17, 18, 24, 47, 99, 99, 99, 99, 18, 21, 26, 66, 99, 99, 99, 99, 24, 26, 56, 99, 99, 99, 99, 99,
+    47, 66, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+];
+
+/// T.81 Annex K.3 Huffman tables as `(BITS, HUFFVAL)`.
+const DC_LUMINANCE_BITS: [u8; 16] = [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0];
+const DC_CHROMINANCE_BITS: [u8; 16] = [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0];
+const DC_VALUES: [u8; 12] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+const AC_LUMINANCE_BITS: [u8; 16] = [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 0x7d];
+const AC_LUMINANCE_VALUES: [u8; 162] = [
+    0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61, 0x07,
+    0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xa1, 0x08, 0x23, 0x42, 0xb1, 0xc1, 0x15, 0x52, 0xd1, 0xf0,
+    0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0a, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x25, 0x26, 0x27, 0x28,
+    0x29, 0x2a, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49,
+    0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69,
+    0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89,
+    0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+    0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3, 0xc4, 0xc5,
+    0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda, 0xe1, 0xe2,
+    0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+const AC_CHROMINANCE_BITS: [u8; 16] = [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 0x77];
+const AC_CHROMINANCE_VALUES: [u8; 162] = [
+    0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61, 0x71,
+    0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xa1, 0xb1, 0xc1, 0x09, 0x23, 0x33, 0x52, 0xf0,
+    0x15, 0x62, 0x72, 0xd1, 0x0a, 0x16, 0x24, 0x34, 0xe1, 0x25, 0xf1, 0x17, 0x18, 0x19, 0x1a, 0x26,
+    0x27, 0x28, 0x29, 0x2a, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48,
+    0x49, 0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68,
+    0x69, 0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+    0x88, 0x89, 0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5,
+    0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3,
+    0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda,
+    0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+/// AC symbol for a run of 16 zeros.
+const ZRL: u8 = 0xf0;
+/// AC symbol closing a block whose remaining coefficients are zero.
+const EOB: u8 = 0x00;
+
+/// Luma + shared chroma quantization tables, zig-zag order (as stored in
+/// DQT and as the encode kernel indexes them).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantTables {
+    pub luma: [u16; 64],
+    pub chroma: [u16; 64],
+}
+
+impl QuantTables {
+    /// Annex K tables scaled to `quality` (1..=100, clamped) with the
+    /// IJG libjpeg curve, so `quality` means what it means in every other
+    /// JPEG tool.
+    pub fn for_quality(quality: u8) -> Self {
+        let quality = u32::from(quality.clamp(1, 100));
+        let scale = if quality < 50 {
+            5000 / quality
+        } else {
+            200 - 2 * quality
+        };
+        let scaled = |natural: &[u16; 64]| {
+            let mut table = [0u16; 64];
+            for (zigzag, &natural_index) in ZIGZAG.iter().enumerate() {
+                let value = (u32::from(natural[natural_index]) * scale + 50) / 100;
+                table[zigzag] = value.clamp(1, 255) as u16;
+            }
+            table
+        };
+        Self {
+            luma: scaled(&K1_LUMINANCE),
+            chroma: scaled(&K2_CHROMINANCE),
+        }
+    }
+}
+
+/// Number of 4:2:0 MCUs covering a `width x height` frame, as
+/// `(horizontal, vertical)`.
+pub fn mcu_grid_420(width: u32, height: u32) -> (u32, u32) {
+    (width.div_ceil(MCU_SIDE_420), height.div_ceil(MCU_SIDE_420))
+}
+
+/// Write a complete baseline 4:2:0 JFIF file for `width x height` into
+/// `out` (cleared first).
+///
+/// `coefficients` holds [`MCU_COEFFICIENTS_420`] quantized values per MCU
+/// in raster MCU order — see the module docs for the per-MCU layout.
+/// Values are clamped to the baseline coefficient range, so a slightly
+/// out-of-range kernel result degrades that block rather than producing
+/// an undecodable stream.
+pub fn write_baseline_420(
+    width: u16,
+    height: u16,
+    tables: &QuantTables,
+    coefficients: &[i32],
+    out: &mut Vec<u8>,
+) -> JpegResult<()> {
+    if width == 0 || height == 0 {
+        return Err(JpegError::Unsupported("zero-sized frame"));
+    }
+    let (mcus_h, mcus_v) = mcu_grid_420(u32::from(width), u32::from(height));
+    let expected = mcus_h as usize * mcus_v as usize * MCU_COEFFICIENTS_420;
+    if coefficients.len() != expected {
+        return Err(JpegError::CoefficientCountMismatch {
+            expected,
+            actual: coefficients.len(),
+        });
+    }
+
+    out.clear();
+    write_headers(width, height, tables, out);
+
+    let dc_luma = HuffmanCodes::build(&DC_LUMINANCE_BITS, &DC_VALUES);
+    let ac_luma = HuffmanCodes::build(&AC_LUMINANCE_BITS, &AC_LUMINANCE_VALUES);
+    let dc_chroma = HuffmanCodes::build(&DC_CHROMINANCE_BITS, &DC_VALUES);
+    let ac_chroma = HuffmanCodes::build(&AC_CHROMINANCE_BITS, &AC_CHROMINANCE_VALUES);
+
+    let mut writer = BitWriter::new(out);
+    // DC predictors for Y, Cb, Cr.
+    let mut predictors = [0i32; 3];
+    for mcu in coefficients.chunks_exact(MCU_COEFFICIENTS_420) {
+        for (block_index, block) in mcu.chunks_exact(64).enumerate() {
+            let (component, dc, ac) = match block_index {
+                0..=3 => (0, &dc_luma, &ac_luma),
+                4 => (1, &dc_chroma, &ac_chroma),
+                _ => (2, &dc_chroma, &ac_chroma),
+            };
+            encode_block(&mut writer, block, &mut predictors[component], dc, ac);
+        }
+    }
+    writer.flush();
+
+    out.extend_from_slice(&[marker::PREFIX, marker::EOI]);
+    Ok(())
+}
+
+fn write_headers(width: u16, height: u16, tables: &QuantTables, out: &mut Vec<u8>) {
+    out.extend_from_slice(&[marker::PREFIX, marker::SOI]);
+
+    // APP0 JFIF 1.01, no density, no thumbnail.
+    write_segment(
+        out,
+        marker::APP0,
+        &[b'J', b'F', b'I', b'F', 0, 1, 1, 0, 0, 1, 0, 1, 0, 0],
+    );
+
+    // DQT: 8-bit precision, table 0 = luma, table 1 = chroma.
+    let mut dqt = Vec::with_capacity(2 * 65);
+    for (id, table) in [(0u8, &tables.luma), (1u8, &tables.chroma)] {
+        dqt.push(id);
+        dqt.extend(table.iter().map(|&q| q as u8));
+    }
+    write_segment(out, marker::DQT, &dqt);
+
+    // SOF0: 8-bit, Y 2x2 on table 0, Cb / Cr 1x1 on table 1.
+    let [height_hi, height_lo] = height.to_be_bytes();
+    let [width_hi, width_lo] = width.to_be_bytes();
+    write_segment(
+        out,
+        marker::SOF0,
+        &[
+            8, height_hi, height_lo, width_hi, width_lo, 3, 1, 0x22, 0, 2, 0x11, 1, 3, 0x11, 1,
+        ],
+    );
+
+    let mut dht = Vec::new();
+    for (class_id, bits, values) in [
+        (0x00u8, &DC_LUMINANCE_BITS, &DC_VALUES[..]),
+        (0x10, &AC_LUMINANCE_BITS, &AC_LUMINANCE_VALUES[..]),
+        (0x01, &DC_CHROMINANCE_BITS, &DC_VALUES[..]),
+        (0x11, &AC_CHROMINANCE_BITS, &AC_CHROMINANCE_VALUES[..]),
+    ] {
+        dht.push(class_id);
+        dht.extend_from_slice(bits);
+        dht.extend_from_slice(values);
+    }
+    write_segment(out, marker::DHT, &dht);
+
+    // SOS: Y on DC0/AC0, Cb / Cr on DC1/AC1, full spectral range.
+    write_segment(out, marker::SOS, &[3, 1, 0x00, 2, 0x11, 3, 0x11, 0, 63, 0]);
+}
+
+fn write_segment(out: &mut Vec<u8>, marker: u8, payload: &[u8]) {
+    out.extend_from_slice(&[marker::PREFIX, marker]);
+    out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(payload);
+}
+
+/// Huffman-code one zig-zag-ordered block (T.81 F.1.2.1 / F.1.2.2).
+fn encode_block(
+    writer: &mut BitWriter<'_>,
+    block: &[i32],
+    predictor: &mut i32,
+    dc: &HuffmanCodes,
+    ac: &HuffmanCodes,
+) {
+    let dc_value = block[0].clamp(-1024, 1023);
+    let diff = dc_value - *predictor;
+    *predictor = dc_value;
+    let category = magnitude_category(diff);
+    dc.emit(writer, category);
+    writer.put_bits(magnitude_bits(diff, category), category);
+
+    let mut run = 0u8;
+    for &coefficient in &block[1..] {
+        let value = coefficient.clamp(-1023, 1023);
+        if value == 0 {
+            run += 1;
+            continue;
+        }
+        while run > 15 {
+            ac.emit(writer, ZRL);
+            run -= 16;
+        }
+        let category = magnitude_category(value);
+        ac.emit(writer, (run << 4) | category);
+        writer.put_bits(magnitude_bits(value, category), category);
+        run = 0;
+    }
+    if run > 0 {
+        ac.emit(writer, EOB);
+    }
+}
+
+/// SSSS: number of bits needed for `|value|`.
+fn magnitude_category(value: i32) -> u8 {
+    (32 - value.unsigned_abs().leading_zeros()) as u8
+}
+
+/// Low `category` bits of `value`, one's-complement for negatives.
+fn magnitude_bits(value: i32, category: u8) -> u32 {
+    if value < 0 {
+        (value + (1 << category) - 1) as u32
+    } else {
+        value as u32
+    }
+}
+
+/// Encoder-side view of a canonical Huffman table (T.81 Annex C):
+/// code and length per symbol.
+struct HuffmanCodes {
+    code: [u16; 256],
+    length: [u8; 256],
+}
+
+impl HuffmanCodes {
+    fn build(bits: &[u8; 16], values: &[u8]) -> Self {
+        let mut codes = Self {
+            code: [0; 256],
+            length: [0; 256],
+        };
+        let mut code: u32 = 0;
+        let mut symbols = values.iter();
+        for (length_minus_one, &count) in bits.iter().enumerate() {
+            for _ in 0..count {
+                if let Some(&symbol) = symbols.next() {
+                    codes.code[usize::from(symbol)] = code as u16;
+                    codes.length[usize::from(symbol)] = length_minus_one as u8 + 1;
+                }
+                code += 1;
+            }
+            code <<= 1;
+        }
+        codes
+    }
+
+    fn emit(&self, writer: &mut BitWriter<'_>, symbol: u8) {
+        let index = usize::from(symbol);
+        writer.put_bits(u32::from(self.code[index]), self.length[index]);
+    }
+}
+
+/// MSB-first entropy-segment writer with `0xFF 0x00` byte stuffing.
+struct BitWriter<'a> {
+    out: &'a mut Vec<u8>,
+    accumulator: u32,
+    bit_count: u8,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(out: &'a mut Vec<u8>) -> Self {
+        Self {
+            out,
+            accumulator: 0,
+            bit_count: 0,
+        }
+    }
+
+    /// Append the low `count` (≤ 16) bits of `value`.
+    fn put_bits(&mut self, value: u32, count: u8) {
+        if count == 0 {
+            return;
+        }
+        let mask = (1u32 << count) - 1;
+        self.accumulator = (self.accumulator << count) | (value & mask);
+        self.bit_count += count;
+        while self.bit_count >= 8 {
+            self.bit_count -= 8;
+            let byte = (self.accumulator >> self.bit_count) as u8;
+            self.out.push(byte);
+            if byte == marker::PREFIX {
+                self.out.push(0x00);
+            }
+        }
+        self.accumulator &= (1u32 << self.bit_count) - 1;
+    }
+
+    /// Pad the final partial byte with 1-bits (T.81 F.1.2.3).
+    fn flush(&mut self) {
+        if self.bit_count > 0 {
+            let pad = 8 - self.bit_count;
+            self.put_bits((1u32 << pad) - 1, pad);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::huffman::{HuffmanClass, HuffmanTable};
+
+    #[test]
+    fn annex_k_huffman_tables_are_well_formed() {
+        for (class, bits, values) in [
+            (HuffmanClass::Dc, &DC_LUMINANCE_BITS, &DC_VALUES[..]),
+            (
+                HuffmanClass::Ac,
+                &AC_LUMINANCE_BITS,
+                &AC_LUMINANCE_VALUES[..],
+            ),
+            (HuffmanClass::Dc, &DC_CHROMINANCE_BITS, &DC_VALUES[..]),
+            (
+                HuffmanClass::Ac,
+                &AC_CHROMINANCE_BITS,
+                &AC_CHROMINANCE_VALUES[..],
+            ),
+        ] {
+            HuffmanTable::build(class, 0, bits, values).unwrap();
+        }
+    }
+
+    #[test]
+    fn quality_50_is_the_unscaled_annex_k_table() {
+        let tables = QuantTables::for_quality(50);
+        assert_eq!(tables.luma[0], 16);
+        // Zig-zag position 2 is natural (row 1, col 0).
+        assert_eq!(tables.luma[2], 12);
+        assert_eq!(tables.chroma[63], 99);
+    }
+
+    #[test]
+    fn quality_100_is_all_ones() {
+        let tables = QuantTables::for_quality(100);
+        assert!(
+            tables
+                .luma
+                .iter()
+                .chain(tables.chroma.iter())
+                .all(|&q| q == 1)
+        );
+    }
+
+    #[test]
+    fn magnitude_coding_matches_t81_table_f1() {
+        assert_eq!(magnitude_category(0), 0);
+        assert_eq!(magnitude_category(1), 1);
+        assert_eq!(magnitude_category(-1), 1);
+        assert_eq!(magnitude_category(-7), 3);
+        assert_eq!(magnitude_category(1023), 10);
+        assert_eq!(magnitude_bits(-1, 1), 0b0);
+        assert_eq!(magnitude_bits(-7, 3), 0b000);
+        assert_eq!(magnitude_bits(5, 3), 0b101);
+    }
+
+    #[test]
+    fn bit_writer_stuffs_ff_bytes() {
+        let mut out = Vec::new();
+        let mut writer = BitWriter::new(&mut out);
+        writer.put_bits(0xff, 8);
+        writer.put_bits(0b1, 1);
+        writer.flush();
+        assert_eq!(out, [0xff, 0x00, 0xff, 0x00]);
+    }
+
+    #[test]
+    fn rejects_wrong_coefficient_count() {
+        let mut out = Vec::new();
+        let err = write_baseline_420(
+            32,
+            16,
+            &QuantTables::for_quality(90),
+            &[0; MCU_COEFFICIENTS_420],
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            JpegError::CoefficientCountMismatch {
+                expected: 768,
+                actual: 384
+            }
+        ));
+    }
+}
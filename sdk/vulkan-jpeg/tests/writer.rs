@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+#![allow(clippy::needless_range_loop)] // natural reading for 8x8 FDCT + per-block pixel iteration
+
+//! Host-side checks for the baseline JPEG writer. A CPU reference of the
+//! encode kernel's front end (YCbCr, 4:2:0 box downsample, FDCT,
+//! quantize — same math as `jpeg_encode.comp`) feeds
+//! `write_baseline_420`; the output must round-trip through both our
+//! parser and zune-jpeg.
+
+use vulkan_jpeg::{
+    MCU_COEFFICIENTS_420, QuantTables, ZIGZAG, decode, mcu_grid_420, write_baseline_420,
+};
+
+/// Quantized coefficients for an RGB8 image, laid out like the kernel's
+/// output SSBO.
+fn reference_coefficients(width: u32, height: u32, rgb: &[u8], tables: &QuantTables) -> Vec<i32> {
+    let (mcus_h, mcus_v) = mcu_grid_420(width, height);
+    let pixel = |x: u32, y: u32| -> [f32; 3] {
+        let x = x.min(width - 1) as usize;
+        let y = y.min(height - 1) as usize;
+        let i = (y * width as usize + x) * 3;
+        [
+            f32::from(rgb[i]),
+            f32::from(rgb[i + 1]),
+            f32::from(rgb[i + 2]),
+        ]
+    };
+
+    let mut out = Vec::with_capacity((mcus_h * mcus_v) as usize * MCU_COEFFICIENTS_420);
+    for mcu_y in 0..mcus_v {
+        for mcu_x in 0..mcus_h {
+            let mut blocks = [[0f32; 64]; 6];
+            for cy in 0..8u32 {
+                for cx in 0..8u32 {
+                    let (mut cb, mut cr) = (0.0, 0.0);
+                    for dy in 0..2u32 {
+                        for dx in 0..2u32 {
+                            let (lx, ly) = (cx * 2 + dx, cy * 2 + dy);
+                            let [r, g, b] = pixel(mcu_x * 16 + lx, mcu_y * 16 + ly);
+                            let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+                            cb += -0.168736 * r - 0.331264 * g + 0.5 * b;
+                            cr += 0.5 * r - 0.418688 * g - 0.081312 * b;
+                            let block = ((ly / 8) * 2 + lx / 8) as usize;
+                            blocks[block][((ly % 8) * 8 + lx % 8) as usize] = luma - 128.0;
+                        }
+                    }
+                    blocks[4][(cy * 8 + cx) as usize] = cb * 0.25;
+                    blocks[5][(cy * 8 + cx) as usize] = cr * 0.25;
+                }
+            }
+            for (index, block) in blocks.iter().enumerate() {
+                let table = if index < 4 {
+                    &tables.luma
+                } else {
+                    &tables.chroma
+                };
+                let mut zigzagged = [0i32; 64];
+                for (zigzag, &natural) in ZIGZAG.iter().enumerate() {
+                    let (u, v) = (natural % 8, natural / 8);
+                    let mut sum = 0.0f32;
+                    for y in 0..8 {
+                        for x in 0..8 {
+                            sum += block[y * 8 + x] * basis(x, u) * basis(y, v);
+                        }
+                    }
+                    let cu = if u == 0 {
+                        std::f32::consts::FRAC_1_SQRT_2
+                    } else {
+                        1.0
+                    };
+                    let cv = if v == 0 {
+                        std::f32::consts::FRAC_1_SQRT_2
+                    } else {
+                        1.0
+                    };
+                    let value = 0.25 * cu * cv * sum / f32::from(table[zigzag]);
+                    zigzagged[zigzag] = value.round() as i32;
+                }
+                out.extend_from_slice(&zigzagged);
+            }
+        }
+    }
+    out
+}
+
+fn basis(i: usize, frequency: usize) -> f32 {
+    ((2 * i + 1) as f32 * frequency as f32 * std::f32::consts::PI / 16.0).cos()
+}
+
+/// Smooth color ramp with edges that don't land on an MCU boundary.
+fn ramp(width: u32, height: u32) -> Vec<u8> {
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            rgb.push((x * 255 / width) as u8);
+            rgb.push((y * 255 / height) as u8);
+            rgb.push(((x + y) * 255 / (width + height)) as u8);
+        }
+    }
+    rgb
+}
+
+#[test]
+fn written_stream_round_trips_through_zune_jpeg() {
+    let (width, height) = (40u32, 24u32);
+    let rgb = ramp(width, height);
+    let tables = QuantTables::for_quality(95);
+    let coefficients = reference_coefficients(width, height, &rgb, &tables);
+
+    let mut bytes = Vec::new();
+    write_baseline_420(
+        width as u16,
+        height as u16,
+        &tables,
+        &coefficients,
+        &mut bytes,
+    )
+    .unwrap();
+
+    let mut zune = zune_jpeg::JpegDecoder::new(&bytes);
+    let pixels = zune.decode().expect("zune-jpeg accepts the written stream");
+    assert_eq!(pixels.len(), rgb.len());
+    let mean_error = pixels
+        .iter()
+        .zip(&rgb)
+        .map(|(&a, &b)| f64::from(a.abs_diff(b)))
+        .sum::<f64>()
+        / rgb.len() as f64;
+    assert!(mean_error < 4.0, "mean absolute error {mean_error}");
+}
+
+#[test]
+fn written_stream_round_trips_through_our_parser() {
+    let (width, height) = (33u32, 17u32);
+    let rgb = ramp(width, height);
+    let tables = QuantTables::for_quality(75);
+    let coefficients = reference_coefficients(width, height, &rgb, &tables);
+
+    let mut bytes = Vec::new();
+    write_baseline_420(
+        width as u16,
+        height as u16,
+        &tables,
+        &coefficients,
+        &mut bytes,
+    )
+    .unwrap();
+
+    let decoded = decode(&bytes).expect("our parser accepts the written stream");
+    assert_eq!(u32::from(decoded.frame.width), width);
+    assert_eq!(u32::from(decoded.frame.height), height);
+    assert_eq!(decoded.quant_tables[0].values, tables.luma);
+    assert_eq!(decoded.quant_tables[1].values, tables.chroma);
+
+    // First MCU's Cb block survives entropy coding exactly.
+    let cb = &decoded.components[1];
+    let written_cb = &coefficients[4 * 64..5 * 64];
+    let parsed_cb: Vec<i32> = cb.coefficients[..64]
+        .iter()
+        .map(|&c| i32::from(c))
+        .collect();
+    assert_eq!(parsed_cb, written_cb);
+}
+
+#[test]
+fn long_zero_runs_and_ff_bytes_stay_decodable() {
+    // Sparse blocks force ZRL symbols; full-range DC swings produce 0xFF
+    // bytes in the entropy segment that must be stuffed.
+    let (mcus_h, mcus_v) = mcu_grid_420(32, 32);
+    let mut coefficients = vec![0i32; (mcus_h * mcus_v) as usize * MCU_COEFFICIENTS_420];
+    for (index, block) in coefficients.chunks_exact_mut(64).enumerate() {
+        block[0] = if index % 2 == 0 { 1023 } else { -1024 };
+        block[40] = -3;
+        block[63] = 1;
+    }
+    let mut bytes = Vec::new();
+    write_baseline_420(
+        32,
+        32,
+        &QuantTables::for_quality(100),
+        &coefficients,
+        &mut bytes,
+    )
+    .unwrap();
+
+    decode(&bytes).expect("our parser accepts the written stream");
+    let mut zune = zune_jpeg::JpegDecoder::new(&bytes);
+    zune.decode().expect("zune-jpeg accepts the written stream");
+}
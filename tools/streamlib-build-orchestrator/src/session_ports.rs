@@ -357,6 +357,8 @@ fn ports_to_yaml(
             } else {
                 None
             },
+            format: None,
+            optional: false,
         };
         seq.push(
             serde_yaml::to_value(&manifest_port)
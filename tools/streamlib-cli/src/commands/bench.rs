@@ -0,0 +1,663 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib bench` — a fixed benchmark matrix for comparing machines and
+//! releases.
+//!
+//! Every case pushes frames through a real iceoryx2 channel on this machine —
+//! an [`OutputWriterInner`] publisher and a subscriber on one service, the
+//! same publish → receive → copy-to-mailbox steps a link performs — and times
+//! each frame. Two kinds of case make up the matrix:
+//!
+//! - payload cases send an opaque buffer of each `--payload-sizes` size, the
+//!   transport cost on its own;
+//! - frame cases synthesise an RGBA frame at each `--resolutions` size and
+//!   encode it with each `--codecs` codec before sending it (`raw` sends the
+//!   pixels as-is; `jpeg` / `webp` use the engine's preview encoder).
+//!
+//! Cases run one after another on the calling thread, so the numbers are
+//! per-frame costs, not contended throughput. Results go to `bench.json`
+//! (the comparable record) and `bench.html` (the same table for a browser)
+//! under `--output-dir`; with `--baseline` every case also shows its fps
+//! change against an earlier `bench.json`.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use streamlib::sdk::frame_snapshot::{PreviewFormat, encode_rgba};
+use streamlib::sdk::iceoryx2::{
+    ChannelEgressConfig, ChannelTrustTier, Iceoryx2Node, OutputWriterInner, SchemaIdentWire,
+    TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+};
+
+/// Version of the `bench.json` layout; bumped when a field changes meaning.
+const BENCH_REPORT_VERSION: u32 = 1;
+
+/// Untimed frames sent before each case, so slot growth and encoder warm-up
+/// stay out of the numbers.
+const WARMUP_FRAMES: u32 = 10;
+
+/// JPEG quality for the `jpeg` codec.
+const JPEG_QUALITY: u8 = 80;
+
+/// A frame codec applied before the frame is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchCodec {
+    /// Uncompressed RGBA.
+    Raw,
+    /// Baseline JPEG at quality 80.
+    Jpeg,
+    /// Lossless WebP.
+    Webp,
+}
+
+impl BenchCodec {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Jpeg => "jpeg",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// A frame size, parsed from `WIDTHxHEIGHT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Parse `WIDTHxHEIGHT`, e.g. `1920x1080`.
+pub fn parse_resolution(text: &str) -> std::result::Result<Resolution, String> {
+    let (width, height) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got `{text}`"))?;
+    let dimension = |value: &str| {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(|| format!("`{value}` in `{text}` is not a positive integer"))
+    };
+    Ok(Resolution {
+        width: dimension(width)?,
+        height: dimension(height)?,
+    })
+}
+
+/// Arguments for the `bench` verb.
+pub struct BenchArgs {
+    pub payload_sizes: Vec<usize>,
+    pub resolutions: Vec<Resolution>,
+    pub codecs: Vec<BenchCodec>,
+    /// Timed frames per case.
+    pub frames: u32,
+    pub output_dir: PathBuf,
+    /// An earlier run's `bench.json` to compare against.
+    pub baseline: Option<PathBuf>,
+}
+
+/// The whole run, as written to `bench.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub version: u32,
+    pub streamlib_version: String,
+    /// RFC 3339 start time.
+    pub started_at: String,
+    pub machine: MachineInfo,
+    pub frames_per_case: u32,
+    pub cases: Vec<BenchCaseResult>,
+}
+
+/// What the numbers were measured on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MachineInfo {
+    pub os: String,
+    pub arch: String,
+    /// Logical CPUs available to the process.
+    pub cpus: usize,
+}
+
+impl MachineInfo {
+    fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, |cpus| cpus.get()),
+        }
+    }
+}
+
+/// Timing summary in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub mean_us: f64,
+    pub p50_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+}
+
+impl LatencyStats {
+    fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut micros: Vec<f64> = samples
+            .iter()
+            .map(|sample| sample.as_secs_f64() * 1e6)
+            .collect();
+        micros.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = (p * micros.len() as f64).ceil() as usize;
+            micros[rank.clamp(1, micros.len()) - 1]
+        };
+        Self {
+            mean_us: micros.iter().sum::<f64>() / micros.len() as f64,
+            p50_us: percentile(0.50),
+            p99_us: percentile(0.99),
+            max_us: micros[micros.len() - 1],
+        }
+    }
+}
+
+/// One case's measurements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchCaseResult {
+    /// Stable case key, e.g. `payload/4096` or `frame/1920x1080/jpeg`; what
+    /// `--baseline` matches cases on.
+    pub id: String,
+    pub codec: BenchCodec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Bytes per frame before encoding.
+    pub source_bytes: usize,
+    /// Mean bytes per frame put on the channel.
+    pub sent_bytes: usize,
+    pub encode: LatencyStats,
+    /// Publish, receive and mailbox copy.
+    pub transport: LatencyStats,
+    /// Frames per second through encode and transport together.
+    pub fps: f64,
+    /// Source bytes per second, in MiB.
+    pub throughput_mib_s: f64,
+}
+
+/// One case to run.
+#[derive(Debug, Clone, PartialEq)]
+enum BenchCase {
+    Payload {
+        bytes: usize,
+    },
+    Frame {
+        resolution: Resolution,
+        codec: BenchCodec,
+    },
+}
+
+impl BenchCase {
+    fn id(&self) -> String {
+        match self {
+            Self::Payload { bytes } => format!("payload/{bytes}"),
+            Self::Frame { resolution, codec } => format!(
+                "frame/{}x{}/{}",
+                resolution.width,
+                resolution.height,
+                codec.name()
+            ),
+        }
+    }
+}
+
+/// The matrix: every payload size, then every resolution × codec.
+fn bench_matrix(args: &BenchArgs) -> Vec<BenchCase> {
+    let payloads = args
+        .payload_sizes
+        .iter()
+        .map(|bytes| BenchCase::Payload { bytes: *bytes });
+    let frames = args.resolutions.iter().flat_map(|resolution| {
+        args.codecs.iter().map(|codec| BenchCase::Frame {
+            resolution: *resolution,
+            codec: *codec,
+        })
+    });
+    payloads.chain(frames).collect()
+}
+
+/// Run the matrix, print the table and write `bench.json` + `bench.html`.
+pub fn run(args: BenchArgs) -> Result<()> {
+    let baseline = args.baseline.as_deref().map(read_report).transpose()?;
+    let node = Iceoryx2Node::new().context("failed to create an iceoryx2 node")?;
+    let cases = bench_matrix(&args);
+    if cases.is_empty() {
+        bail!("nothing to run: give at least one payload size, or a resolution and a codec");
+    }
+
+    let mut report = BenchReport {
+        version: BENCH_REPORT_VERSION,
+        streamlib_version: env!("CARGO_PKG_VERSION").to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        machine: MachineInfo::current(),
+        frames_per_case: args.frames,
+        cases: Vec::with_capacity(cases.len()),
+    };
+    for (index, case) in cases.iter().enumerate() {
+        eprintln!("[{}/{}] {}", index + 1, cases.len(), case.id());
+        let result = run_case(&node, case, args.frames)
+            .with_context(|| format!("bench case {} failed", case.id()))?;
+        report.cases.push(result);
+    }
+
+    print!("{}", render_table(&report, baseline.as_ref()));
+
+    std::fs::create_dir_all(&args.output_dir)
+        .with_context(|| format!("failed to create {}", args.output_dir.display()))?;
+    let json_path = args.output_dir.join("bench.json");
+    std::fs::write(&json_path, serde_json::to_string_pretty(&report)? + "\n")
+        .with_context(|| format!("failed to write {}", json_path.display()))?;
+    let html_path = args.output_dir.join("bench.html");
+    std::fs::write(&html_path, render_html(&report, baseline.as_ref()))
+        .with_context(|| format!("failed to write {}", html_path.display()))?;
+    println!(
+        "\nwrote {} and {}",
+        json_path.display(),
+        html_path.display()
+    );
+    Ok(())
+}
+
+fn read_report(path: &Path) -> Result<BenchReport> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read baseline {}", path.display()))?;
+    let report: BenchReport = serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a bench.json", path.display()))?;
+    if report.version != BENCH_REPORT_VERSION {
+        bail!(
+            "{} is a version {} report; this build compares version {BENCH_REPORT_VERSION}",
+            path.display(),
+            report.version
+        );
+    }
+    Ok(report)
+}
+
+/// Time `frames` frames of `case` through a fresh loopback channel.
+fn run_case(node: &Iceoryx2Node, case: &BenchCase, frames: u32) -> Result<BenchCaseResult> {
+    let (codec, resolution, sources) = match case {
+        BenchCase::Payload { bytes } => (BenchCodec::Raw, None, vec![vec![0xA5; *bytes]]),
+        BenchCase::Frame { resolution, codec } => (
+            *codec,
+            Some(*resolution),
+            (0..2)
+                .map(|seed| synthetic_frame(*resolution, seed))
+                .collect(),
+        ),
+    };
+    let source_bytes = sources[0].len();
+
+    let service_name = format!(
+        "streamlib/bench/{}/{}",
+        std::process::id(),
+        case.id().replace('/', "-")
+    );
+    let service = node.open_or_create_service(&service_name, 1, 2, true)?;
+    let publisher = service.create_publisher(source_bytes)?;
+    let subscriber = service.create_subscriber()?;
+    let writer = OutputWriterInner::new();
+    let schema_ident = SchemaIdentWire::from_segments("tatolab", "bench", "BenchFrame", 1, 0, 0)
+        .map_err(|e| anyhow!("bench schema ident: {e:?}"))?;
+    writer.set_channel_publisher(
+        "out",
+        schema_ident,
+        publisher,
+        ChannelEgressConfig {
+            service_name,
+            trust_tier: ChannelTrustTier::Trusted,
+            expected_payload_bytes: source_bytes,
+            ceiling_bytes: TRUSTED_CHANNEL_PAYLOAD_CEILING_BYTES,
+            cipher: None,
+        },
+    );
+
+    let mut encode_times = Vec::with_capacity(frames as usize);
+    let mut transport_times = Vec::with_capacity(frames as usize);
+    let mut sent_bytes = 0usize;
+    for index in 0..WARMUP_FRAMES + frames {
+        let source = &sources[index as usize % sources.len()];
+
+        let encode_started = Instant::now();
+        let encoded = match (codec, resolution) {
+            (BenchCodec::Raw, _) | (_, None) => None,
+            (BenchCodec::Jpeg, Some(resolution)) => Some(encode_rgba(
+                source,
+                resolution.width,
+                resolution.height,
+                PreviewFormat::Jpeg,
+                JPEG_QUALITY,
+            )?),
+            (BenchCodec::Webp, Some(resolution)) => Some(encode_rgba(
+                source,
+                resolution.width,
+                resolution.height,
+                PreviewFormat::Webp,
+                0,
+            )?),
+        };
+        let encode_time = encode_started.elapsed();
+        let payload = encoded.as_deref().unwrap_or(source);
+
+        let transport_started = Instant::now();
+        writer.write_raw("out", payload, index as i64)?;
+        let sample = subscriber
+            .receive()
+            .map_err(|e| anyhow!("receive failed: {e:?}"))?
+            .context("published frame did not reach the subscriber")?;
+        let mailbox_copy = sample.payload().to_vec();
+        let transport_time = transport_started.elapsed();
+        drop(sample);
+
+        if index >= WARMUP_FRAMES {
+            encode_times.push(encode_time);
+            transport_times.push(transport_time);
+            sent_bytes += payload.len();
+        }
+        std::hint::black_box(mailbox_copy);
+    }
+    let timed: Duration = encode_times.iter().chain(&transport_times).sum();
+
+    let secs = timed.as_secs_f64().max(f64::EPSILON);
+    Ok(BenchCaseResult {
+        id: case.id(),
+        codec,
+        width: resolution.map(|resolution| resolution.width),
+        height: resolution.map(|resolution| resolution.height),
+        source_bytes,
+        sent_bytes: sent_bytes / frames.max(1) as usize,
+        encode: LatencyStats::from_samples(&encode_times),
+        transport: LatencyStats::from_samples(&transport_times),
+        fps: frames as f64 / secs,
+        throughput_mib_s: (source_bytes as f64 * frames as f64) / secs / (1024.0 * 1024.0),
+    })
+}
+
+/// A deterministic RGBA test card: gradients plus hashed noise, so encoders
+/// see detail rather than a flat fill. `seed` varies the noise between
+/// frames.
+fn synthetic_frame(resolution: Resolution, seed: u32) -> Vec<u8> {
+    let (width, height) = (resolution.width, resolution.height);
+    let mut frame = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let noise = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663) ^ seed)
+                .wrapping_mul(2_654_435_761)
+                >> 27;
+            frame.extend_from_slice(&[
+                ((x * 255 / width) as u8).wrapping_add(noise as u8),
+                ((y * 255 / height) as u8).wrapping_add(noise as u8),
+                (((x + y) * 255 / (width + height)) as u8) ^ (noise as u8),
+                0xFF,
+            ]);
+        }
+    }
+    frame
+}
+
+/// Percentage fps change of `case` against the same case in `baseline`.
+fn fps_change_pct(case: &BenchCaseResult, baseline: Option<&BenchReport>) -> Option<f64> {
+    let previous = baseline?
+        .cases
+        .iter()
+        .find(|previous| previous.id == case.id)?;
+    (previous.fps > 0.0).then(|| (case.fps - previous.fps) / previous.fps * 100.0)
+}
+
+/// Render `value` bytes as B / KiB / MiB.
+fn human_bytes(bytes: usize) -> String {
+    match bytes {
+        bytes if bytes >= 1024 * 1024 => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        bytes if bytes >= 1024 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        bytes => format!("{bytes} B"),
+    }
+}
+
+/// The cells every output shows for one case, in column order.
+fn case_cells(case: &BenchCaseResult, baseline: Option<&BenchReport>) -> Vec<String> {
+    let mut cells = vec![
+        case.id.clone(),
+        human_bytes(case.sent_bytes),
+        format!("{:.1}", case.encode.p50_us),
+        format!("{:.1}", case.transport.p50_us),
+        format!("{:.1}", case.transport.p99_us),
+        format!("{:.1}", case.fps),
+        format!("{:.1}", case.throughput_mib_s),
+    ];
+    if baseline.is_some() {
+        cells.push(
+            fps_change_pct(case, baseline)
+                .map_or_else(|| "-".to_string(), |change| format!("{change:+.1}%")),
+        );
+    }
+    cells
+}
+
+fn column_headers(baseline: Option<&BenchReport>) -> Vec<&'static str> {
+    let mut headers = vec![
+        "CASE",
+        "SENT/FRAME",
+        "ENCODE P50 µs",
+        "SEND P50 µs",
+        "SEND P99 µs",
+        "FPS",
+        "MiB/s",
+    ];
+    if baseline.is_some() {
+        headers.push("FPS vs BASELINE");
+    }
+    headers
+}
+
+fn render_table(report: &BenchReport, baseline: Option<&BenchReport>) -> String {
+    let headers = column_headers(baseline);
+    let rows: Vec<Vec<String>> = report
+        .cases
+        .iter()
+        .map(|case| case_cells(case, baseline))
+        .collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut text = format!(
+        "streamlib {} on {}/{} ({} CPUs), {} frames per case\n\n",
+        report.streamlib_version,
+        report.machine.os,
+        report.machine.arch,
+        report.machine.cpus,
+        report.frames_per_case
+    );
+    let line = |cells: Vec<String>| {
+        let mut line = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.push('\n');
+        line
+    };
+    text.push_str(&line(headers.iter().map(|h| h.to_string()).collect()));
+    for row in rows {
+        text.push_str(&line(row));
+    }
+    text
+}
+
+fn render_html(report: &BenchReport, baseline: Option<&BenchReport>) -> String {
+    let mut html = String::from(
+        "<!doctype html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>streamlib bench</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 2rem; }\n\
+         table { border-collapse: collapse; }\n\
+         th, td { padding: 0.3rem 0.8rem; border-bottom: 1px solid #ddd; }\n\
+         td { text-align: right; font-variant-numeric: tabular-nums; }\n\
+         td:first-child, th:first-child { text-align: left; }\n\
+         .faster { color: #1a7f37; } .slower { color: #cf222e; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    html.push_str(&format!(
+        "<h1>streamlib bench</h1>\n<p>streamlib {} on {}/{} ({} CPUs), {} frames per case, \
+         started {}.</p>\n<table>\n<tr>",
+        escape_html(&report.streamlib_version),
+        escape_html(&report.machine.os),
+        escape_html(&report.machine.arch),
+        report.machine.cpus,
+        report.frames_per_case,
+        escape_html(&report.started_at)
+    ));
+    for header in column_headers(baseline) {
+        html.push_str(&format!("<th>{}</th>", escape_html(header)));
+    }
+    html.push_str("</tr>\n");
+    for case in &report.cases {
+        html.push_str("<tr>");
+        let change = fps_change_pct(case, baseline);
+        let cells = case_cells(case, baseline);
+        let last = cells.len() - 1;
+        for (column, cell) in cells.iter().enumerate() {
+            let class = match change {
+                Some(change) if baseline.is_some() && column == last && change >= 0.0 => {
+                    " class=\"faster\""
+                }
+                Some(_) if baseline.is_some() && column == last => " class=\"slower\"",
+                _ => "",
+            };
+            html.push_str(&format!("<td{class}>{}</td>", escape_html(cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(id: &str, fps: f64) -> BenchCaseResult {
+        BenchCaseResult {
+            id: id.to_string(),
+            codec: BenchCodec::Raw,
+            width: None,
+            height: None,
+            source_bytes: 4096,
+            sent_bytes: 4096,
+            encode: LatencyStats::default(),
+            transport: LatencyStats::default(),
+            fps,
+            throughput_mib_s: 0.0,
+        }
+    }
+
+    fn report(cases: Vec<BenchCaseResult>) -> BenchReport {
+        BenchReport {
+            version: BENCH_REPORT_VERSION,
+            streamlib_version: "0.8.0".to_string(),
+            started_at: "2026-01-01T00:00:00+00:00".to_string(),
+            machine: MachineInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                cpus: 8,
+            },
+            frames_per_case: 300,
+            cases,
+        }
+    }
+
+    #[test]
+    fn resolutions_parse_and_reject_nonsense() {
+        assert_eq!(
+            parse_resolution("1920x1080"),
+            Ok(Resolution {
+                width: 1920,
+                height: 1080
+            })
+        );
+        assert!(parse_resolution("1920").is_err());
+        assert!(parse_resolution("0x1080").is_err());
+        assert!(parse_resolution("widexhigh").is_err());
+    }
+
+    #[test]
+    fn the_matrix_is_payloads_then_resolutions_by_codecs() {
+        let args = BenchArgs {
+            payload_sizes: vec![256],
+            resolutions: vec![parse_resolution("640x360").unwrap()],
+            codecs: vec![BenchCodec::Raw, BenchCodec::Jpeg],
+            frames: 1,
+            output_dir: PathBuf::new(),
+            baseline: None,
+        };
+        let ids: Vec<String> = bench_matrix(&args).iter().map(BenchCase::id).collect();
+        assert_eq!(
+            ids,
+            ["payload/256", "frame/640x360/raw", "frame/640x360/jpeg"]
+        );
+    }
+
+    #[test]
+    fn latency_stats_use_nearest_rank_percentiles() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_micros).collect();
+        let stats = LatencyStats::from_samples(&samples);
+        assert_eq!(stats.p50_us, 50.0);
+        assert_eq!(stats.p99_us, 99.0);
+        assert_eq!(stats.max_us, 100.0);
+        assert!((stats.mean_us - 50.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_baseline_adds_the_fps_change_per_matching_case() {
+        let baseline = report(vec![case("payload/4096", 1000.0)]);
+        let current = report(vec![case("payload/4096", 1100.0), case("payload/256", 5.0)]);
+
+        let table = render_table(&current, Some(&baseline));
+        assert!(table.contains("FPS vs BASELINE"), "{table}");
+        assert!(table.contains("+10.0%"), "{table}");
+
+        let html = render_html(&current, Some(&baseline));
+        assert!(html.contains("<td class=\"faster\">+10.0%</td>"), "{html}");
+        assert!(html.contains("<td>-</td>"), "{html}");
+        assert!(!render_html(&current, None).contains("BASELINE"));
+    }
+
+    #[test]
+    fn synthetic_frames_are_rgba_and_vary_by_seed() {
+        let resolution = parse_resolution("32x16").unwrap();
+        let first = synthetic_frame(resolution, 0);
+        assert_eq!(first.len(), 32 * 16 * 4);
+        assert_ne!(first, synthetic_frame(resolution, 1));
+    }
+}
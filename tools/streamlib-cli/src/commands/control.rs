@@ -313,8 +313,7 @@ fn call_tool_to_stdout(url: &str, tool_name: &str, arguments: Value) -> Result<(
 
 /// Marshal `arguments` into a `tools/call` for `tool_name`, POST it, and write
 /// the tool result's text content to `writer`. Generic over the writer so a
-/// test captures the output while the CLI wires process stdout. Covers the four
-/// result channels described in the module docs.
+/// test captures the output while the CLI wires process stdout.
 fn call_tool(
     url: &str,
     bearer_token: Option<&str>,
@@ -322,6 +321,21 @@ fn call_tool(
     arguments: Value,
     writer: &mut impl Write,
 ) -> Result<()> {
+    let text = call_tool_text(url, bearer_token, tool_name, arguments)?;
+    writeln!(writer, "{text}")?;
+    Ok(())
+}
+
+/// Marshal `arguments` into a `tools/call` for `tool_name`, POST it, and return
+/// the tool result's text content. Covers the four result channels described
+/// in the module docs; verbs that post-process a result (`top`) call this
+/// directly.
+pub fn call_tool_text(
+    url: &str,
+    bearer_token: Option<&str>,
+    tool_name: &str,
+    arguments: Value,
+) -> Result<String> {
     let request = json!({
         "jsonrpc": "2.0",
         "id": 1,
@@ -358,8 +372,7 @@ fn call_tool(
         bail!("{tool_name} failed: {text}");
     }
 
-    writeln!(writer, "{text}")?;
-    Ok(())
+    Ok(text.to_string())
 }
 
 /// Resolve a `--source` value to processor source text: `@<path>` or a plain
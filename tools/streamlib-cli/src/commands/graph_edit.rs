@@ -0,0 +1,179 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib graph add-processor | remove-processor | connect | disconnect |
+//! set-config` — one-shot edits of a running node's graph over the same REST
+//! routes the web UI drives (`POST /api/processor`, `DELETE
+//! /api/processors/{id}`, `POST /api/connections`, `DELETE
+//! /api/connections/{id}`, `PUT /api/processors/{id}/config`).
+//!
+//! Each verb is one request through the shell's REST client
+//! ([`super::repl`]), so the bearer token, error rendering and processor-type
+//! resolution match `streamlib repl` exactly. Created ids go to stdout, one
+//! per line, so a script can capture them:
+//!
+//! ```text
+//! cam=$(streamlib graph add-processor Camera)
+//! view=$(streamlib graph add-processor Display --config '{"width": 1280}')
+//! streamlib graph connect "$cam.video" "$view.video"
+//! streamlib graph set-config "$cam" --set device=/dev/video2
+//! ```
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+
+use super::repl::{
+    ControlPlaneClient, PortAddress, parse_param_value, processor_config, resolve_processor_type,
+    set_config_path,
+};
+
+/// Instantiate a registered processor type — `@org/package/Type@version`, or
+/// a bare `Type` exactly one registered processor carries — and print its id.
+pub fn add_processor(url: &str, processor_type: &str, config: Option<&str>) -> Result<()> {
+    let client = ControlPlaneClient::new(url);
+    let config = match config {
+        Some(config) => parse_config_json(config)?,
+        None => json!({}),
+    };
+    let processor_type = resolve_processor_type(&client.registry()?, processor_type)?;
+    let created = client.send(
+        "POST",
+        "/api/processor",
+        Some(&json!({ "processor_type": processor_type, "config": config })),
+    )?;
+    println!("{}", created_id(&created)?);
+    Ok(())
+}
+
+/// Remove a processor and every link touching it.
+pub fn remove_processor(url: &str, processor_id: &str) -> Result<()> {
+    ControlPlaneClient::new(url).send(
+        "DELETE",
+        &format!("/api/processors/{processor_id}"),
+        None,
+    )?;
+    Ok(())
+}
+
+/// Link an output port to an input port, both as `processor.port`, and print
+/// the link id.
+pub fn connect(url: &str, from: &str, to: &str) -> Result<()> {
+    let from = PortAddress::parse(from)?;
+    let to = PortAddress::parse(to)?;
+    let created = ControlPlaneClient::new(url).send(
+        "POST",
+        "/api/connections",
+        Some(&json!({
+            "from_processor": from.processor_id,
+            "from_port": from.port_name,
+            "to_processor": to.processor_id,
+            "to_port": to.port_name,
+        })),
+    )?;
+    println!("{}", created_id(&created)?);
+    Ok(())
+}
+
+/// Remove a link by id.
+pub fn disconnect(url: &str, link_id: &str) -> Result<()> {
+    ControlPlaneClient::new(url).send("DELETE", &format!("/api/connections/{link_id}"), None)?;
+    Ok(())
+}
+
+/// Replace a processor's config with `config`, or — without one — edit its
+/// current config; either way each `key.path=value` in `assignments` is
+/// applied on top before the single `PUT`.
+pub fn set_config(
+    url: &str,
+    processor_id: &str,
+    config: Option<&str>,
+    assignments: &[String],
+) -> Result<()> {
+    if config.is_none() && assignments.is_empty() {
+        bail!("set-config needs a CONFIG, one or more `--set key.path=value`, or both");
+    }
+    let client = ControlPlaneClient::new(url);
+    let base = match config {
+        Some(config) => parse_config_json(config)?,
+        None => processor_config(&client.graph()?, processor_id)?,
+    };
+    let config = edited_config(base, assignments)?;
+    client.send(
+        "PUT",
+        &format!("/api/processors/{processor_id}/config"),
+        Some(&json!({ "config": config })),
+    )?;
+    Ok(())
+}
+
+/// `config` with every `key.path=value` assignment applied in order. Values
+/// parse as JSON when they can and are taken as bare strings otherwise, as in
+/// the shell's `param set`.
+fn edited_config(mut config: Value, assignments: &[String]) -> Result<Value> {
+    for assignment in assignments {
+        let (key_path, value) = assignment
+            .split_once('=')
+            .filter(|(key_path, _)| !key_path.is_empty())
+            .with_context(|| format!("expected `key.path=value`, got `{assignment}`"))?;
+        set_config_path(&mut config, key_path, parse_param_value(value))?;
+    }
+    Ok(config)
+}
+
+/// Parse a config argument that must be a JSON object.
+fn parse_config_json(text: &str) -> Result<Value> {
+    let config: Value =
+        serde_json::from_str(text).with_context(|| format!("config is not valid JSON: {text}"))?;
+    if !config.is_object() {
+        bail!("config must be a JSON object, got `{text}`");
+    }
+    Ok(config)
+}
+
+/// The `id` of a `POST` route's `IdResponse`.
+fn created_id(created: &Value) -> Result<&str> {
+    created
+        .get("id")
+        .and_then(Value::as_str)
+        .context("control plane returned no id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assignments_apply_in_order_over_the_base_config() {
+        let config = edited_config(
+            json!({ "device": "/dev/video0", "size": { "width": 1280 } }),
+            &[
+                "device=/dev/video2".to_string(),
+                "size.height=720".to_string(),
+                "size.width=1920".to_string(),
+                "mirror=true".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "device": "/dev/video2",
+                "size": { "width": 1920, "height": 720 },
+                "mirror": true,
+            })
+        );
+
+        assert!(edited_config(json!({}), &["width".to_string()]).is_err());
+        assert!(edited_config(json!({}), &["=1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn config_arguments_must_be_json_objects() {
+        assert_eq!(
+            parse_config_json(r#"{"fps": 30}"#).unwrap(),
+            json!({ "fps": 30 })
+        );
+        assert!(parse_config_json("[30]").is_err());
+        assert!(parse_config_json("{fps: 30}").is_err());
+    }
+}
@@ -2,14 +2,20 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 pub mod add;
+pub mod bench;
 pub mod build_on_place;
 pub mod control;
 pub mod generate;
+pub mod graph_edit;
 pub mod install;
 pub mod link;
 pub mod logs;
 pub mod mcp;
+pub mod new;
 pub mod nodes;
 pub mod pkg;
+pub mod repl;
 pub mod schema;
 pub mod setup;
+pub mod top;
+pub mod validate;
@@ -0,0 +1,438 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib new` — scaffold a ready-to-run pipeline from a template.
+//!
+//! A template is a set of files (a graph-snapshot pipeline JSON plus whatever
+//! example config it needs) rendered into the target directory with every
+//! `{{pipeline_name}}` in file names and contents replaced by the chosen
+//! pipeline name. The built-in templates ship with the CLI; an installed
+//! package adds its own by carrying a `templates/<template-name>/` folder in
+//! its `streamlib_modules/@org/name/` slot. Built-in names win a collision.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use streamlib::sdk::runtime::{APP_MODULES_DIR_NAME, AppModulesDir};
+
+/// Placeholder substituted with the pipeline name in template file names and
+/// contents.
+pub const PIPELINE_NAME_PLACEHOLDER: &str = "{{pipeline_name}}";
+
+/// Folder inside a package slot holding the package's templates, one
+/// sub-folder per template.
+pub const PACKAGE_TEMPLATES_DIR_NAME: &str = "templates";
+
+/// One file a template renders, with a relative path.
+#[derive(Debug, Clone)]
+pub struct TemplateFile {
+    pub path: String,
+    pub contents: String,
+}
+
+impl TemplateFile {
+    pub fn new(path: impl Into<String>, contents: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+/// Where a template came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateProvider {
+    /// Ships with the CLI.
+    Builtin,
+    /// Ships in an installed package, named `@org/name`.
+    Package(String),
+}
+
+impl std::fmt::Display for TemplateProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Builtin => f.write_str("built-in"),
+            Self::Package(package) => f.write_str(package),
+        }
+    }
+}
+
+/// A named pipeline scaffold.
+#[derive(Debug, Clone)]
+pub struct PipelineTemplate {
+    pub name: String,
+    pub description: String,
+    pub provider: TemplateProvider,
+    pub files: Vec<TemplateFile>,
+}
+
+impl PipelineTemplate {
+    /// The template's files with `pipeline_name` substituted, as
+    /// `(relative path, contents)` pairs.
+    pub fn render(&self, pipeline_name: &str) -> Vec<(PathBuf, String)> {
+        self.files
+            .iter()
+            .map(|file| {
+                (
+                    PathBuf::from(file.path.replace(PIPELINE_NAME_PLACEHOLDER, pipeline_name)),
+                    file.contents
+                        .replace(PIPELINE_NAME_PLACEHOLDER, pipeline_name),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Every template `streamlib new` can render, keyed by name.
+#[derive(Debug, Default)]
+pub struct TemplateRegistry {
+    templates: BTreeMap<String, PipelineTemplate>,
+}
+
+impl TemplateRegistry {
+    /// The templates that ship with the CLI.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for template in builtin_templates() {
+            registry
+                .register(template)
+                .expect("built-in template names are unique");
+        }
+        registry
+    }
+
+    /// Add a template. Fails if one with the same name is already registered.
+    pub fn register(&mut self, template: PipelineTemplate) -> Result<()> {
+        if let Some(existing) = self.templates.get(&template.name) {
+            bail!(
+                "template '{}' from {} is already provided by {}",
+                template.name,
+                template.provider,
+                existing.provider
+            );
+        }
+        self.templates.insert(template.name.clone(), template);
+        Ok(())
+    }
+
+    /// Add the templates shipped by every package in the app's
+    /// `streamlib_modules/`. A template whose name is already taken is
+    /// skipped with a warning.
+    pub fn register_package_templates(&mut self, app: &AppModulesDir) -> Result<()> {
+        for template in discover_package_templates(&app.modules_dir())? {
+            if let Err(e) = self.register(template) {
+                tracing::warn!("Skipping package template: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PipelineTemplate> {
+        self.templates.get(name)
+    }
+
+    /// All templates, ordered by name.
+    pub fn templates(&self) -> impl Iterator<Item = &PipelineTemplate> {
+        self.templates.values()
+    }
+}
+
+fn builtin_templates() -> Vec<PipelineTemplate> {
+    let builtin = |name: &str, description: &str, pipeline: &str, readme: &str| PipelineTemplate {
+        name: name.to_string(),
+        description: description.to_string(),
+        provider: TemplateProvider::Builtin,
+        files: vec![
+            TemplateFile::new(format!("{PIPELINE_NAME_PLACEHOLDER}.json"), pipeline),
+            TemplateFile::new("README.md", readme),
+        ],
+    };
+    vec![
+        builtin(
+            "camera-to-whip",
+            "Camera + microphone encoded to H.264 / Opus and published over WHIP",
+            include_str!("../../templates/camera-to-whip/pipeline.json"),
+            include_str!("../../templates/camera-to-whip/README.md"),
+        ),
+        builtin(
+            "file-transcode",
+            "Raw BGRA file encoded to MP4",
+            include_str!("../../templates/file-transcode/pipeline.json"),
+            include_str!("../../templates/file-transcode/README.md"),
+        ),
+        builtin(
+            "multiview-monitor",
+            "Two cameras composited into a labeled multiview grid on one display",
+            include_str!("../../templates/multiview-monitor/pipeline.json"),
+            include_str!("../../templates/multiview-monitor/README.md"),
+        ),
+    ]
+}
+
+/// Templates under `<modules_dir>/@org/name/templates/<template>/`. Every
+/// regular file directly inside a template folder is one template file.
+fn discover_package_templates(modules_dir: &Path) -> Result<Vec<PipelineTemplate>> {
+    let mut templates = Vec::new();
+    for org_dir in sorted_subdirs(modules_dir)? {
+        let Some(org) = file_name(&org_dir).and_then(|name| name.strip_prefix('@')) else {
+            continue;
+        };
+        for package_dir in sorted_subdirs(&org_dir)? {
+            let Some(package) = file_name(&package_dir) else {
+                continue;
+            };
+            let provider = TemplateProvider::Package(format!("@{org}/{package}"));
+            for template_dir in sorted_subdirs(&package_dir.join(PACKAGE_TEMPLATES_DIR_NAME))? {
+                let Some(name) = file_name(&template_dir) else {
+                    continue;
+                };
+                templates.push(PipelineTemplate {
+                    name: name.to_string(),
+                    description: format!("Provided by {provider}"),
+                    provider: provider.clone(),
+                    files: read_template_files(&template_dir)?,
+                });
+            }
+        }
+    }
+    Ok(templates)
+}
+
+/// Sub-directories of `dir`, sorted; empty when `dir` doesn't exist.
+fn sorted_subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut subdirs = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        }
+    }
+    subdirs.sort();
+    Ok(subdirs)
+}
+
+fn read_template_files(template_dir: &Path) -> Result<Vec<TemplateFile>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(template_dir)
+        .with_context(|| format!("Failed to read {}", template_dir.display()))?
+    {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(name) = file_name(&path) else {
+            continue;
+        };
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read template file {}", path.display()))?;
+        files.push(TemplateFile::new(name, contents));
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|name| name.to_str())
+}
+
+/// Pipeline names end up in file names, so keep them to one path segment of
+/// ASCII letters, digits, `-` and `_`.
+fn validate_pipeline_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("invalid pipeline name '{name}': use ASCII letters, digits, '-' and '_'");
+    }
+    Ok(())
+}
+
+/// Render `template` into `dir`. Refuses to overwrite any existing file
+/// unless `force`; nothing is written when it refuses.
+pub fn render_into(
+    template: &PipelineTemplate,
+    pipeline_name: &str,
+    dir: &Path,
+    force: bool,
+) -> Result<Vec<PathBuf>> {
+    validate_pipeline_name(pipeline_name)?;
+    let rendered = template.render(pipeline_name);
+
+    if !force {
+        let existing: Vec<String> = rendered
+            .iter()
+            .map(|(path, _)| dir.join(path))
+            .filter(|path| path.exists())
+            .map(|path| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            bail!(
+                "refusing to overwrite existing files (pass --force to replace them): {}",
+                existing.join(", ")
+            );
+        }
+    }
+
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut written = Vec::with_capacity(rendered.len());
+    for (path, contents) in rendered {
+        let path = dir.join(path);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The registry for an app at `dir` (default: CWD): built-ins plus the
+/// templates of every package in its `streamlib_modules/`.
+fn registry_for(dir: Option<&Path>) -> Result<TemplateRegistry> {
+    let app = match dir {
+        Some(root) => AppModulesDir::at(root),
+        None => AppModulesDir::from_cwd().map_err(|e| anyhow::anyhow!("{e}"))?,
+    };
+    let mut registry = TemplateRegistry::builtin();
+    registry.register_package_templates(&app)?;
+    Ok(registry)
+}
+
+/// Run `streamlib new --template <name>`.
+pub fn run(template: &str, name: Option<&str>, dir: Option<&Path>, force: bool) -> Result<()> {
+    let registry = registry_for(dir)?;
+    let Some(template) = registry.get(template) else {
+        let available: Vec<&str> = registry.templates().map(|t| t.name.as_str()).collect();
+        bail!(
+            "unknown template '{template}' (available: {}). Packages in {APP_MODULES_DIR_NAME}/ \
+             can add their own under {PACKAGE_TEMPLATES_DIR_NAME}/<name>/",
+            available.join(", ")
+        );
+    };
+
+    let pipeline_name = name.unwrap_or(&template.name);
+    let target_dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir().context("Failed to resolve current directory")?,
+    };
+    let written = render_into(template, pipeline_name, &target_dir, force)?;
+
+    println!(
+        "Created pipeline '{}' from template '{}' ({}):",
+        pipeline_name, template.name, template.provider
+    );
+    for path in &written {
+        println!("  {}", path.display());
+    }
+    println!();
+    println!("Review the generated config, then run:");
+    println!();
+    println!("  streamlib-runtime --snapshot {pipeline_name}.json");
+    Ok(())
+}
+
+/// Run `streamlib new --list`.
+pub fn list(dir: Option<&Path>) -> Result<()> {
+    let registry = registry_for(dir)?;
+    println!("Available templates:\n");
+    for template in registry.templates() {
+        println!(
+            "  {:<20} {} [{}]",
+            template.name, template.description, template.provider
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use streamlib::sdk::graph_snapshot::GraphSnapshot;
+
+    #[test]
+    fn builtin_templates_render_parseable_pipelines() {
+        let registry = TemplateRegistry::builtin();
+        for name in ["camera-to-whip", "file-transcode", "multiview-monitor"] {
+            let template = registry.get(name).expect("built-in template registered");
+            let rendered = template.render("demo");
+            let (_, pipeline) = rendered
+                .iter()
+                .find(|(path, _)| path == Path::new("demo.json"))
+                .expect("template renders <name>.json");
+            assert!(!pipeline.contains(PIPELINE_NAME_PLACEHOLDER));
+
+            let snapshot = GraphSnapshot::from_json_str(pipeline)
+                .unwrap_or_else(|e| panic!("{name} pipeline must parse: {e}"));
+            assert_eq!(snapshot.name.as_deref(), Some("demo"));
+            let aliases: Vec<&str> = snapshot
+                .processors
+                .iter()
+                .map(|p| p.alias.as_str())
+                .collect();
+            for connection in &snapshot.connections {
+                for port in [
+                    connection.parse_from().unwrap(),
+                    connection.parse_to().unwrap(),
+                ] {
+                    assert!(
+                        aliases.contains(&port.alias),
+                        "{name}: connection references unknown alias '{}'",
+                        port.alias
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn package_templates_extend_but_never_shadow_builtins() {
+        let app_root = tempfile::tempdir().unwrap();
+        let templates = app_root
+            .path()
+            .join(APP_MODULES_DIR_NAME)
+            .join("@acme")
+            .join("lidar")
+            .join(PACKAGE_TEMPLATES_DIR_NAME);
+        for name in ["lidar-recorder", "file-transcode"] {
+            std::fs::create_dir_all(templates.join(name)).unwrap();
+            std::fs::write(
+                templates
+                    .join(name)
+                    .join(format!("{PIPELINE_NAME_PLACEHOLDER}.json")),
+                format!("{{\"name\": \"{PIPELINE_NAME_PLACEHOLDER}\"}}"),
+            )
+            .unwrap();
+        }
+
+        let registry = registry_for(Some(app_root.path())).unwrap();
+        let lidar = registry
+            .get("lidar-recorder")
+            .expect("package template registered");
+        assert_eq!(
+            lidar.provider,
+            TemplateProvider::Package("@acme/lidar".into())
+        );
+        assert_eq!(
+            registry.get("file-transcode").unwrap().provider,
+            TemplateProvider::Builtin
+        );
+    }
+
+    #[test]
+    fn render_refuses_to_overwrite_without_force() {
+        let out = tempfile::tempdir().unwrap();
+        let registry = TemplateRegistry::builtin();
+        let template = registry.get("file-transcode").unwrap();
+
+        let written = render_into(template, "transcode", out.path(), false).unwrap();
+        assert!(written.contains(&out.path().join("transcode.json")));
+        assert!(render_into(template, "transcode", out.path(), false).is_err());
+        render_into(template, "transcode", out.path(), true).unwrap();
+        assert!(render_into(template, "../escape", out.path(), true).is_err());
+    }
+}
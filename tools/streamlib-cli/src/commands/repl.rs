@@ -0,0 +1,1033 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib repl` — an interactive shell over a running node's control plane.
+//!
+//! Each line is one verb against the node the `-r/--runtime` (or `--url`) flag
+//! resolved, through the same two surfaces the one-shot verbs use: the MCP
+//! tools over `POST {url}/mcp` ([`super::control`]) for `connect`, `remove`
+//! and `tap`, and the REST routes for what MCP deliberately doesn't expose —
+//! instantiating a registered type, per-processor config / pause / resume, and
+//! the graph snapshot. `STREAMLIB_MCP_TOKEN` rides as the bearer token on both,
+//! matching the one-shot verbs.
+//!
+//! Tab completion is fed by the node itself: after every command the shell
+//! re-reads `GET /api/registry` and `GET /api/graph` and completes verbs,
+//! registered processor types, processor ids, `id.port` addresses, link ids,
+//! config keys and tap channels from what it saw.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result, bail};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::{Map, Value, json};
+
+/// Prompt printed before every line.
+const PROMPT: &str = "streamlib> ";
+
+/// Every verb the shell accepts, in `help` order. Also the first-word
+/// completion candidates.
+const COMMANDS: &[&str] = &[
+    "help",
+    "graph",
+    "registry",
+    "add",
+    "remove",
+    "connect",
+    "disconnect",
+    "start",
+    "stop",
+    "param",
+    "tap",
+    "snapshot",
+    "exit",
+    "quit",
+];
+
+/// `param` sub-verbs.
+const PARAM_COMMANDS: &[&str] = &["set", "get"];
+
+const HELP: &str = "\
+commands:
+  graph                                 list processors and links
+  registry                              list registered processor types
+  add <Type|@org/pkg/Type@ver> [json]   instantiate a registered processor
+  remove <processor>                    remove a processor
+  connect <processor.port> <processor.port>
+                                        connect an output port to an input port
+  disconnect <link>                     remove a link
+  start <processor>                     resume a paused processor
+  stop <processor>                      pause a processor
+  param set <processor> <key.path> <value>
+                                        set one config value (JSON, or a bare string)
+  param get <processor> [key.path]      print a processor's config, or one value
+  tap <channel> [count]                 sample raw bags from a channel
+  snapshot [path]                       print the graph snapshot, or save it to path
+  exit | quit                           leave the shell";
+
+/// Run the shell against the control plane at `url` until `exit`, `quit`,
+/// Ctrl-D or Ctrl-C. A failed command prints its error and keeps the shell
+/// open.
+pub fn run(url: &str) -> Result<()> {
+    let client = ControlPlaneClient::new(url);
+    let mut editor: Editor<ReplHelper, DefaultHistory> =
+        Editor::new().context("failed to initialize the line editor")?;
+    editor.set_helper(Some(ReplHelper {
+        index: client.completion_index(),
+    }));
+
+    println!("Connected to {url}. Type `help` for commands, Tab to complete.");
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(error) => return Err(error).context("failed to read a line"),
+        };
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+
+        let command = match parse_command(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(error) => {
+                eprintln!("error: {error:#}");
+                continue;
+            }
+        };
+        if matches!(command, ReplCommand::Exit) {
+            break;
+        }
+        if let Err(error) = execute(&client, command) {
+            eprintln!("error: {error:#}");
+        }
+
+        if let Some(helper) = editor.helper_mut() {
+            helper.index = client.completion_index();
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// A processor port addressed as `processor_id.port_name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PortAddress {
+    pub(super) processor_id: String,
+    pub(super) port_name: String,
+}
+
+impl PortAddress {
+    /// Split `processor_id.port_name` on its last `.` — port names never
+    /// contain one.
+    pub(super) fn parse(text: &str) -> Result<Self> {
+        match text.rsplit_once('.') {
+            Some((processor_id, port_name))
+                if !processor_id.is_empty() && !port_name.is_empty() =>
+            {
+                Ok(Self {
+                    processor_id: processor_id.to_string(),
+                    port_name: port_name.to_string(),
+                })
+            }
+            _ => bail!("expected `<processor>.<port>`, got `{text}`"),
+        }
+    }
+}
+
+/// One parsed shell line.
+#[derive(Debug, Clone, PartialEq)]
+enum ReplCommand {
+    Help,
+    Graph,
+    Registry,
+    Add {
+        processor_type: String,
+        config: Value,
+    },
+    Remove {
+        processor_id: String,
+    },
+    Connect {
+        from: PortAddress,
+        to: PortAddress,
+    },
+    Disconnect {
+        link_id: String,
+    },
+    Start {
+        processor_id: String,
+    },
+    Stop {
+        processor_id: String,
+    },
+    ParamSet {
+        processor_id: String,
+        key_path: String,
+        value: Value,
+    },
+    ParamGet {
+        processor_id: String,
+        key_path: Option<String>,
+    },
+    Tap {
+        channel: String,
+        count: Option<usize>,
+    },
+    Snapshot {
+        path: Option<PathBuf>,
+    },
+    Exit,
+}
+
+/// Split off the first whitespace-delimited word of `text`, returning it and
+/// the trimmed remainder.
+fn next_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (text, ""),
+    }
+}
+
+/// The next word of `text`, or an error naming what `usage` expects.
+fn required_word<'a>(text: &'a str, usage: &str) -> Result<(&'a str, &'a str)> {
+    let (word, rest) = next_word(text);
+    if word.is_empty() {
+        bail!("usage: {usage}");
+    }
+    Ok((word, rest))
+}
+
+/// Reject trailing words after a verb's last argument.
+fn no_more_words(rest: &str, usage: &str) -> Result<()> {
+    if !rest.is_empty() {
+        bail!("unexpected `{rest}`; usage: {usage}");
+    }
+    Ok(())
+}
+
+/// Parse a `param set` value: JSON when it parses, otherwise the text as a
+/// bare string so `param set cam device /dev/video0` needs no quoting.
+pub(super) fn parse_param_value(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+/// Parse one shell line. `Ok(None)` for a blank line.
+fn parse_command(line: &str) -> Result<Option<ReplCommand>> {
+    let (verb, rest) = next_word(line);
+    let command = match verb {
+        "" => return Ok(None),
+        "help" | "?" => ReplCommand::Help,
+        "graph" => ReplCommand::Graph,
+        "registry" => ReplCommand::Registry,
+        "exit" | "quit" => ReplCommand::Exit,
+        "add" => {
+            let usage = "add <Type|@org/pkg/Type@ver> [json]";
+            let (processor_type, rest) = required_word(rest, usage)?;
+            let config = if rest.is_empty() {
+                json!({})
+            } else {
+                serde_json::from_str(rest)
+                    .with_context(|| format!("config is not valid JSON: {rest}"))?
+            };
+            ReplCommand::Add {
+                processor_type: processor_type.to_string(),
+                config,
+            }
+        }
+        "remove" | "start" | "stop" => {
+            let usage = format!("{verb} <processor>");
+            let (processor_id, rest) = required_word(rest, &usage)?;
+            no_more_words(rest, &usage)?;
+            let processor_id = processor_id.to_string();
+            match verb {
+                "remove" => ReplCommand::Remove { processor_id },
+                "start" => ReplCommand::Start { processor_id },
+                _ => ReplCommand::Stop { processor_id },
+            }
+        }
+        "connect" => {
+            let usage = "connect <processor.port> <processor.port>";
+            let (from, rest) = required_word(rest, usage)?;
+            let (to, rest) = required_word(rest, usage)?;
+            no_more_words(rest, usage)?;
+            ReplCommand::Connect {
+                from: PortAddress::parse(from)?,
+                to: PortAddress::parse(to)?,
+            }
+        }
+        "disconnect" => {
+            let usage = "disconnect <link>";
+            let (link_id, rest) = required_word(rest, usage)?;
+            no_more_words(rest, usage)?;
+            ReplCommand::Disconnect {
+                link_id: link_id.to_string(),
+            }
+        }
+        "param" => {
+            let (sub_verb, rest) = next_word(rest);
+            match sub_verb {
+                "set" => {
+                    let usage = "param set <processor> <key.path> <value>";
+                    let (processor_id, rest) = required_word(rest, usage)?;
+                    let (key_path, rest) = required_word(rest, usage)?;
+                    if rest.is_empty() {
+                        bail!("usage: {usage}");
+                    }
+                    ReplCommand::ParamSet {
+                        processor_id: processor_id.to_string(),
+                        key_path: key_path.to_string(),
+                        value: parse_param_value(rest),
+                    }
+                }
+                "get" => {
+                    let usage = "param get <processor> [key.path]";
+                    let (processor_id, rest) = required_word(rest, usage)?;
+                    let (key_path, rest) = next_word(rest);
+                    no_more_words(rest, usage)?;
+                    ReplCommand::ParamGet {
+                        processor_id: processor_id.to_string(),
+                        key_path: (!key_path.is_empty()).then(|| key_path.to_string()),
+                    }
+                }
+                _ => bail!("usage: param set|get <processor> ..."),
+            }
+        }
+        "tap" => {
+            let usage = "tap <channel> [count]";
+            let (channel, rest) = required_word(rest, usage)?;
+            let (count, rest) = next_word(rest);
+            no_more_words(rest, usage)?;
+            let count = if count.is_empty() {
+                None
+            } else {
+                Some(
+                    count
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .with_context(|| {
+                            format!("count must be a positive integer, got `{count}`")
+                        })?,
+                )
+            };
+            ReplCommand::Tap {
+                channel: channel.to_string(),
+                count,
+            }
+        }
+        "snapshot" => {
+            let usage = "snapshot [path]";
+            let (path, rest) = next_word(rest);
+            no_more_words(rest, usage)?;
+            ReplCommand::Snapshot {
+                path: (!path.is_empty()).then(|| PathBuf::from(path)),
+            }
+        }
+        other => bail!("unknown command `{other}`; type `help` for the command list"),
+    };
+    Ok(Some(command))
+}
+
+/// Run one command against the control plane, printing its result.
+fn execute(client: &ControlPlaneClient, command: ReplCommand) -> Result<()> {
+    match command {
+        ReplCommand::Help => println!("{HELP}"),
+        ReplCommand::Graph => print!("{}", render_graph_summary(&client.graph()?)),
+        ReplCommand::Registry => {
+            for processor_type in registered_processor_types(&client.registry()?) {
+                println!("{}", processor_type.canonical);
+            }
+        }
+        ReplCommand::Add {
+            processor_type,
+            config,
+        } => {
+            let processor_type = resolve_processor_type(&client.registry()?, &processor_type)?;
+            let created = client.send(
+                "POST",
+                "/api/processor",
+                Some(&json!({ "processor_type": processor_type, "config": config })),
+            )?;
+            let id = created
+                .get("id")
+                .and_then(Value::as_str)
+                .context("control plane returned no processor id")?;
+            println!("{id}");
+        }
+        ReplCommand::Remove { processor_id } => {
+            super::control::remove(client.url(), &processor_id)?
+        }
+        ReplCommand::Connect { from, to } => super::control::connect(
+            client.url(),
+            &from.processor_id,
+            &from.port_name,
+            &to.processor_id,
+            &to.port_name,
+        )?,
+        ReplCommand::Disconnect { link_id } => {
+            client.send("DELETE", &format!("/api/connections/{link_id}"), None)?;
+        }
+        ReplCommand::Start { processor_id } => {
+            client.send(
+                "POST",
+                &format!("/api/processors/{processor_id}/resume"),
+                None,
+            )?;
+        }
+        ReplCommand::Stop { processor_id } => {
+            client.send(
+                "POST",
+                &format!("/api/processors/{processor_id}/pause"),
+                None,
+            )?;
+        }
+        ReplCommand::ParamSet {
+            processor_id,
+            key_path,
+            value,
+        } => {
+            let mut config = processor_config(&client.graph()?, &processor_id)?;
+            set_config_path(&mut config, &key_path, value)?;
+            client.send(
+                "PUT",
+                &format!("/api/processors/{processor_id}/config"),
+                Some(&json!({ "config": config })),
+            )?;
+        }
+        ReplCommand::ParamGet {
+            processor_id,
+            key_path,
+        } => {
+            let config = processor_config(&client.graph()?, &processor_id)?;
+            let value = match key_path.as_deref() {
+                None => &config,
+                Some(key_path) => config_path(&config, key_path).with_context(|| {
+                    format!("processor `{processor_id}` has no config key `{key_path}`")
+                })?,
+            };
+            println!("{}", serde_json::to_string_pretty(value)?);
+        }
+        ReplCommand::Tap { channel, count } => super::control::tap(client.url(), &channel, count)?,
+        ReplCommand::Snapshot { path } => {
+            let snapshot = serde_json::to_string_pretty(&client.get("/api/snapshot")?)?;
+            match path {
+                Some(path) => {
+                    std::fs::write(&path, format!("{snapshot}\n"))
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    println!("wrote {}", path.display());
+                }
+                None => println!("{snapshot}"),
+            }
+        }
+        ReplCommand::Exit => {}
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Control-plane REST client
+// ============================================================================
+
+/// Blocking client for the REST routes the shell needs beyond the MCP tools.
+pub(super) struct ControlPlaneClient {
+    url: String,
+    bearer_token: Option<String>,
+}
+
+impl ControlPlaneClient {
+    pub(super) fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            bearer_token: std::env::var("STREAMLIB_MCP_TOKEN").ok(),
+        }
+    }
+
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(super) fn graph(&self) -> Result<Value> {
+        self.get("/api/graph")
+    }
+
+    pub(super) fn registry(&self) -> Result<Value> {
+        self.get("/api/registry")
+    }
+
+    fn get(&self, path: &str) -> Result<Value> {
+        self.send("GET", path, None)
+    }
+
+    /// Issue `method path` with an optional JSON body. A 2xx yields the parsed
+    /// response body (`Value::Null` when empty, e.g. a `204`); a non-2xx is an
+    /// error carrying the server's `error` message when it sent one.
+    pub(super) fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let endpoint = format!("{}{path}", self.url);
+        let mut request = ureq::request(method, &endpoint);
+        if let Some(bearer_token) = &self.bearer_token {
+            request = request.set("authorization", &format!("Bearer {bearer_token}"));
+        }
+        let response = match body {
+            Some(body) => request
+                .set("content-type", "application/json")
+                .send_string(&body.to_string()),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => {
+                let text = response.into_string()?;
+                if text.trim().is_empty() {
+                    return Ok(Value::Null);
+                }
+                serde_json::from_str(&text)
+                    .with_context(|| format!("{method} {endpoint} returned non-JSON: {text}"))
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                let text = response.into_string().unwrap_or_default();
+                let message = serde_json::from_str::<Value>(&text)
+                    .ok()
+                    .and_then(|body| {
+                        body.get("error")
+                            .and_then(Value::as_str)
+                            .map(str::to_string)
+                    })
+                    .unwrap_or(text);
+                bail!("{method} {path} failed: HTTP {code}: {message}");
+            }
+            Err(error) => bail!("{method} {endpoint} transport error: {error}"),
+        }
+    }
+
+    /// Build the completion index from the node's current registry and graph.
+    /// A failed read leaves that half of the index empty rather than failing
+    /// the shell — completion is best-effort.
+    fn completion_index(&self) -> CompletionIndex {
+        let registry = self.registry().unwrap_or_else(|error| {
+            tracing::debug!(%error, "registry read for completion failed");
+            Value::Null
+        });
+        let graph = self.graph().unwrap_or_else(|error| {
+            tracing::debug!(%error, "graph read for completion failed");
+            Value::Null
+        });
+        CompletionIndex::build(&registry, &graph)
+    }
+}
+
+// ============================================================================
+// Registry and graph views
+// ============================================================================
+
+/// A registered processor type: its structured wire ident and its
+/// `@org/package/Type@version` display form.
+struct RegisteredProcessorType {
+    ident: Value,
+    type_name: String,
+    canonical: String,
+}
+
+/// Every processor type in a `GET /api/registry` response, sorted by display
+/// form.
+fn registered_processor_types(registry: &Value) -> Vec<RegisteredProcessorType> {
+    let mut types: Vec<RegisteredProcessorType> = registry
+        .get("processors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|descriptor| {
+            let ident = descriptor.get("name")?;
+            let field = |key: &str| ident.get(key).and_then(Value::as_str);
+            let version = ident.get("version")?;
+            let version_part = |key: &str| version.get(key).and_then(Value::as_u64);
+            let type_name = field("type")?.to_string();
+            let canonical = format!(
+                "@{}/{}/{}@{}.{}.{}",
+                field("org")?,
+                field("package")?,
+                type_name,
+                version_part("major")?,
+                version_part("minor")?,
+                version_part("patch")?,
+            );
+            Some(RegisteredProcessorType {
+                ident: ident.clone(),
+                type_name,
+                canonical,
+            })
+        })
+        .collect();
+    types.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+    types
+}
+
+/// Resolve an `add` argument to the structured ident the create route takes:
+/// an exact `@org/package/Type@version` match, or a bare `Type` that exactly
+/// one registered processor carries.
+pub(super) fn resolve_processor_type(registry: &Value, query: &str) -> Result<Value> {
+    let types = registered_processor_types(registry);
+    if let Some(exact) = types.iter().find(|candidate| candidate.canonical == query) {
+        return Ok(exact.ident.clone());
+    }
+    let matches: Vec<&RegisteredProcessorType> = types
+        .iter()
+        .filter(|candidate| candidate.type_name == query)
+        .collect();
+    match matches.as_slice() {
+        [single] => Ok(single.ident.clone()),
+        [] => bail!("no registered processor type `{query}`; `registry` lists them"),
+        several => bail!(
+            "`{query}` is ambiguous; use one of: {}",
+            several
+                .iter()
+                .map(|candidate| candidate.canonical.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// The graph's `nodes[]` array (empty when absent).
+fn graph_nodes(graph: &Value) -> &[Value] {
+    graph
+        .get("nodes")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// The graph's `links[]` array (empty when absent).
+fn graph_links(graph: &Value) -> &[Value] {
+    graph
+        .get("links")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// A link endpoint (`source` / `target`) as `processor_id.port_name`.
+fn link_endpoint(link: &Value, side: &str) -> Option<String> {
+    let endpoint = link.get(side)?;
+    Some(format!(
+        "{}.{}",
+        endpoint.get("processor_id")?.as_str()?,
+        endpoint.get("port_name")?.as_str()?
+    ))
+}
+
+/// The current config of `processor_id` (`{}` when it has none).
+pub(super) fn processor_config(graph: &Value, processor_id: &str) -> Result<Value> {
+    let node = graph_nodes(graph)
+        .iter()
+        .find(|node| node.get("id").and_then(Value::as_str) == Some(processor_id))
+        .with_context(|| format!("no processor `{processor_id}` in the graph"))?;
+    Ok(node
+        .get("config")
+        .filter(|config| !config.is_null())
+        .cloned()
+        .unwrap_or_else(|| json!({})))
+}
+
+/// One line per processor and per link.
+fn render_graph_summary(graph: &Value) -> String {
+    let mut summary = String::new();
+    for node in graph_nodes(graph) {
+        let id = node.get("id").and_then(Value::as_str).unwrap_or("?");
+        let type_name = node
+            .get("type")
+            .and_then(|ident| ident.get("type"))
+            .and_then(Value::as_str)
+            .unwrap_or("?");
+        summary.push_str(&format!("{id}  {type_name}\n"));
+    }
+    for link in graph_links(graph) {
+        let id = link.get("id").and_then(Value::as_str).unwrap_or("?");
+        let source = link_endpoint(link, "source").unwrap_or_else(|| "?".to_string());
+        let target = link_endpoint(link, "target").unwrap_or_else(|| "?".to_string());
+        summary.push_str(&format!("{source} -> {target}  [{id}]\n"));
+    }
+    if summary.is_empty() {
+        summary.push_str("(empty graph)\n");
+    }
+    summary
+}
+
+// ============================================================================
+// Config paths
+// ============================================================================
+
+/// The value at dotted `key_path` inside `config`.
+fn config_path<'a>(config: &'a Value, key_path: &str) -> Option<&'a Value> {
+    key_path
+        .split('.')
+        .try_fold(config, |value, key| value.as_object()?.get(key))
+}
+
+/// Set the value at dotted `key_path` inside `config`, creating intermediate
+/// objects for missing keys. Fails when the path runs through a non-object.
+pub(super) fn set_config_path(config: &mut Value, key_path: &str, value: Value) -> Result<()> {
+    let keys: Vec<&str> = key_path.split('.').collect();
+    if keys.iter().any(|key| key.is_empty()) {
+        bail!("invalid config key path `{key_path}`");
+    }
+    let not_an_object = |depth: usize| {
+        let parent = if depth == 0 {
+            "config".to_string()
+        } else {
+            keys[..depth].join(".")
+        };
+        anyhow::anyhow!("cannot set `{key_path}`: `{parent}` is not an object")
+    };
+
+    let (last, parents) = keys.split_last().expect("split always yields one key");
+    let mut cursor = config;
+    for (depth, key) in parents.iter().enumerate() {
+        cursor = cursor
+            .as_object_mut()
+            .ok_or_else(|| not_an_object(depth))?
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    cursor
+        .as_object_mut()
+        .ok_or_else(|| not_an_object(parents.len()))?
+        .insert(last.to_string(), value);
+    Ok(())
+}
+
+/// Every dotted key path in `config`, intermediate objects included.
+fn collect_config_paths(config: &Value, prefix: &str, paths: &mut Vec<String>) {
+    let Some(object) = config.as_object() else {
+        return;
+    };
+    for (key, value) in object {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        collect_config_paths(value, &path, paths);
+        paths.push(path);
+    }
+}
+
+// ============================================================================
+// Completion
+// ============================================================================
+
+/// Completion candidates snapshotted from the node's registry and graph.
+#[derive(Debug, Default)]
+struct CompletionIndex {
+    /// Registered processor types, as both `@org/package/Type@version` and
+    /// bare `Type`.
+    processor_types: Vec<String>,
+    processor_ids: Vec<String>,
+    /// `processor_id.port_name` for every output port.
+    output_ports: Vec<String>,
+    /// `processor_id.port_name` for every input port.
+    input_ports: Vec<String>,
+    link_ids: Vec<String>,
+    /// Dotted config key paths per processor id.
+    config_keys: BTreeMap<String, Vec<String>>,
+    /// The channel name of every linked output port.
+    tap_channels: Vec<String>,
+}
+
+impl CompletionIndex {
+    fn build(registry: &Value, graph: &Value) -> Self {
+        let mut processor_types = BTreeSet::new();
+        for processor_type in registered_processor_types(registry) {
+            processor_types.insert(processor_type.type_name);
+            processor_types.insert(processor_type.canonical);
+        }
+
+        let mut index = Self {
+            processor_types: processor_types.into_iter().collect(),
+            ..Self::default()
+        };
+        for node in graph_nodes(graph) {
+            let Some(id) = node.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            index.processor_ids.push(id.to_string());
+            let ports = node.get("ports");
+            for (direction, addresses) in [
+                ("outputs", &mut index.output_ports),
+                ("inputs", &mut index.input_ports),
+            ] {
+                let names = ports
+                    .and_then(|ports| ports.get(direction))
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|port| port.get("name").and_then(Value::as_str));
+                addresses.extend(names.map(|name| format!("{id}.{name}")));
+            }
+            let mut keys = Vec::new();
+            if let Some(config) = node.get("config") {
+                collect_config_paths(config, "", &mut keys);
+            }
+            keys.sort();
+            index.config_keys.insert(id.to_string(), keys);
+        }
+
+        let mut tap_channels = BTreeSet::new();
+        for link in graph_links(graph) {
+            if let Some(id) = link.get("id").and_then(Value::as_str) {
+                index.link_ids.push(id.to_string());
+            }
+            let Some(source) = link.get("source") else {
+                continue;
+            };
+            let processor_id = source.get("processor_id").and_then(Value::as_str);
+            let port_name = source.get("port_name").and_then(Value::as_str);
+            if let (Some(processor_id), Some(port_name)) = (processor_id, port_name)
+                && let Ok(channel) = streamlib_idents::source_channel_name(processor_id, port_name)
+            {
+                tap_channels.insert(channel.as_str().to_string());
+            }
+        }
+        index.tap_channels = tap_channels.into_iter().collect();
+        index
+    }
+
+    /// Candidates for the word under the cursor, given the line up to the
+    /// cursor. Returns the byte offset the completed word starts at and the
+    /// candidates that extend it.
+    fn candidates(&self, line: &str) -> (usize, Vec<String>) {
+        let word_start = line
+            .rfind(char::is_whitespace)
+            .map_or(0, |position| position + 1);
+        let partial = &line[word_start..];
+        let words: Vec<&str> = line[..word_start].split_whitespace().collect();
+
+        let pool: Vec<&str> = match words.as_slice() {
+            [] => COMMANDS.to_vec(),
+            ["add"] => self.processor_types.iter().map(String::as_str).collect(),
+            ["remove" | "start" | "stop"] => {
+                self.processor_ids.iter().map(String::as_str).collect()
+            }
+            ["connect"] => self.output_ports.iter().map(String::as_str).collect(),
+            ["connect", _] => self.input_ports.iter().map(String::as_str).collect(),
+            ["disconnect"] => self.link_ids.iter().map(String::as_str).collect(),
+            ["param"] => PARAM_COMMANDS.to_vec(),
+            ["param", "set" | "get"] => self.processor_ids.iter().map(String::as_str).collect(),
+            ["param", "set" | "get", processor_id] => self
+                .config_keys
+                .get(*processor_id)
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect(),
+            ["tap"] => self.tap_channels.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let matches = pool
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(partial))
+            .map(str::to_string)
+            .collect();
+        (word_start, matches)
+    }
+}
+
+/// rustyline helper: completion from the [`CompletionIndex`], no hints,
+/// highlighting or multi-line validation.
+struct ReplHelper {
+    index: CompletionIndex,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.index.candidates(&line[..pos]))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry_fixture() -> Value {
+        json!({
+            "processors": [
+                { "name": { "org": "tatolab", "package": "camera", "type": "Camera",
+                            "version": { "major": 1, "minor": 0, "patch": 0 } } },
+                { "name": { "org": "tatolab", "package": "display", "type": "Display",
+                            "version": { "major": 1, "minor": 2, "patch": 0 } } },
+                { "name": { "org": "acme", "package": "display", "type": "Display",
+                            "version": { "major": 0, "minor": 1, "patch": 0 } } }
+            ],
+            "schemas": []
+        })
+    }
+
+    fn graph_fixture() -> Value {
+        json!({
+            "nodes": [
+                { "id": "cam", "type": { "type": "Camera" },
+                  "config": { "device": "/dev/video0", "size": { "width": 1280 } },
+                  "ports": { "inputs": [], "outputs": [{ "name": "video" }] } },
+                { "id": "view", "type": { "type": "Display" },
+                  "ports": { "inputs": [{ "name": "video" }], "outputs": [] } }
+            ],
+            "links": [
+                { "id": "link-1",
+                  "source": { "processor_id": "cam", "port_name": "video" },
+                  "target": { "processor_id": "view", "port_name": "video" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn parse_command_covers_every_verb() {
+        assert_eq!(parse_command("   ").unwrap(), None);
+        assert_eq!(
+            parse_command(r#"add Camera {"device": "/dev/video2"}"#).unwrap(),
+            Some(ReplCommand::Add {
+                processor_type: "Camera".to_string(),
+                config: json!({ "device": "/dev/video2" }),
+            })
+        );
+        assert_eq!(
+            parse_command("connect cam.video view.video").unwrap(),
+            Some(ReplCommand::Connect {
+                from: PortAddress::parse("cam.video").unwrap(),
+                to: PortAddress::parse("view.video").unwrap(),
+            })
+        );
+        assert_eq!(
+            parse_command("param set cam size.width 1920").unwrap(),
+            Some(ReplCommand::ParamSet {
+                processor_id: "cam".to_string(),
+                key_path: "size.width".to_string(),
+                value: json!(1920),
+            })
+        );
+        assert_eq!(
+            parse_command("param set cam device /dev/video1").unwrap(),
+            Some(ReplCommand::ParamSet {
+                processor_id: "cam".to_string(),
+                key_path: "device".to_string(),
+                value: json!("/dev/video1"),
+            })
+        );
+        assert_eq!(
+            parse_command("tap cam/video 3").unwrap(),
+            Some(ReplCommand::Tap {
+                channel: "cam/video".to_string(),
+                count: Some(3),
+            })
+        );
+        assert_eq!(
+            parse_command("stop cam").unwrap(),
+            Some(ReplCommand::Stop {
+                processor_id: "cam".to_string()
+            })
+        );
+        assert_eq!(parse_command("quit").unwrap(), Some(ReplCommand::Exit));
+    }
+
+    #[test]
+    fn parse_command_rejects_malformed_lines() {
+        assert!(parse_command("connect cam view.video").is_err());
+        assert!(parse_command("add Camera {not json").is_err());
+        assert!(parse_command("param set cam device").is_err());
+        assert!(parse_command("tap cam/video 0").is_err());
+        assert!(parse_command("start cam extra").is_err());
+        assert!(parse_command("launch cam").is_err());
+    }
+
+    #[test]
+    fn set_config_path_creates_nested_objects_and_rejects_scalars() {
+        let mut config = json!({ "size": { "width": 1280 }, "fps": 30 });
+        set_config_path(&mut config, "size.height", json!(720)).unwrap();
+        set_config_path(&mut config, "encoder.bitrate", json!(4_000_000)).unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "size": { "width": 1280, "height": 720 },
+                "fps": 30,
+                "encoder": { "bitrate": 4_000_000 }
+            })
+        );
+        assert_eq!(config_path(&config, "size.height"), Some(&json!(720)));
+        assert!(set_config_path(&mut config, "fps.max", json!(60)).is_err());
+        assert!(set_config_path(&mut config, "size..width", json!(1)).is_err());
+    }
+
+    #[test]
+    fn resolve_processor_type_matches_canonical_or_unique_bare_names() {
+        let registry = registry_fixture();
+        assert_eq!(
+            resolve_processor_type(&registry, "Camera").unwrap()["package"],
+            "camera"
+        );
+        assert_eq!(
+            resolve_processor_type(&registry, "@acme/display/Display@0.1.0").unwrap()["org"],
+            "acme"
+        );
+        let ambiguous = resolve_processor_type(&registry, "Display").unwrap_err();
+        assert!(ambiguous.to_string().contains("ambiguous"), "{ambiguous}");
+        assert!(resolve_processor_type(&registry, "Microphone").is_err());
+    }
+
+    #[test]
+    fn completion_follows_the_command_being_typed() {
+        let index = CompletionIndex::build(&registry_fixture(), &graph_fixture());
+
+        assert_eq!(index.candidates("con").1, vec!["connect".to_string()]);
+        assert_eq!(index.candidates("add Cam").1, vec!["Camera".to_string()]);
+        assert_eq!(
+            index.candidates("add @tatolab/").1,
+            vec![
+                "@tatolab/camera/Camera@1.0.0".to_string(),
+                "@tatolab/display/Display@1.2.0".to_string(),
+            ]
+        );
+        assert_eq!(
+            index.candidates("connect c"),
+            (8, vec!["cam.video".to_string()])
+        );
+        assert_eq!(
+            index.candidates("connect cam.video "),
+            (18, vec!["view.video".to_string()])
+        );
+        assert_eq!(
+            index.candidates("param set cam si").1,
+            vec!["size".to_string(), "size.width".to_string()]
+        );
+        assert_eq!(
+            index.candidates("disconnect ").1,
+            vec!["link-1".to_string()]
+        );
+        assert_eq!(index.candidates("tap ").1, vec!["cam/video".to_string()]);
+    }
+}
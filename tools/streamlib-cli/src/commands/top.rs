@@ -0,0 +1,398 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib top` — a live per-processor dashboard for a running node,
+//! refreshed in place.
+//!
+//! Each refresh is one `graph` `tools/call` over the node's `POST /mcp` (the
+//! same seam as `streamlib graph`, via [`super::control::call_tool_text`]).
+//! Frame rates and drop rates are counter deltas between two refreshes:
+//!
+//! - fps is the processor's own `metrics.throughput_fps` when it reports one;
+//!   otherwise frames delivered on its input links per second, or — for a
+//!   source with no inputs — frames pushed into its output links per second.
+//! - p50 / p99 are the processor's reported processing latency; a processor
+//!   that reports no metrics shows `-`.
+//! - queue is the frames waiting in its input mailboxes against their
+//!   combined capacity.
+//! - drops are frames evicted unread from its input mailboxes, per second and
+//!   in total, with `!` marking an input link latched as congested.
+//!
+//! The first refresh has nothing to diff against, so its rates read `-`.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use streamlib_processor_schema::runtime_api::{
+    GraphResponse, LINK_FRAME_DROPS_COMPONENT, LinkFrameDropsOutput, PROCESSOR_METRICS_COMPONENT,
+    PROCESSOR_STATE_COMPONENT, ProcessorMetricsOutput,
+};
+
+/// Move the cursor home and clear the screen before each redraw.
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+/// Widest processor id shown before it is truncated.
+const PROCESSOR_ID_WIDTH: usize = 24;
+
+/// Widest processor type shown before it is truncated.
+const PROCESSOR_TYPE_WIDTH: usize = 32;
+
+/// Arguments for the `top` verb.
+pub struct TopArgs {
+    pub url: String,
+    /// Time between refreshes.
+    pub interval: Duration,
+    /// Stop after this many refreshes; `None` runs until interrupted.
+    pub iterations: Option<u64>,
+}
+
+/// Poll the node's graph every `interval` and redraw the dashboard in place.
+pub fn run(args: TopArgs) -> Result<()> {
+    let bearer_token = std::env::var("STREAMLIB_MCP_TOKEN").ok();
+    let stdout = std::io::stdout();
+    let mut previous: Option<GraphSample> = None;
+    let mut refreshes = 0u64;
+    loop {
+        let graph = fetch_graph(&args.url, bearer_token.as_deref())?;
+        let sample = GraphSample::new(Instant::now(), &graph);
+        let rows = processor_rows(&graph, &sample, previous.as_ref());
+
+        let mut out = stdout.lock();
+        write!(
+            out,
+            "{CLEAR_SCREEN}{}",
+            render(&args.url, &graph, &rows, args.interval)
+        )?;
+        out.flush()?;
+
+        refreshes += 1;
+        if args
+            .iterations
+            .is_some_and(|iterations| refreshes >= iterations)
+        {
+            return Ok(());
+        }
+        previous = Some(sample);
+        std::thread::sleep(args.interval);
+    }
+}
+
+fn fetch_graph(url: &str, bearer_token: Option<&str>) -> Result<GraphResponse> {
+    let text = super::control::call_tool_text(url, bearer_token, "graph", json!({}))?;
+    serde_json::from_str(&text).context("control plane returned an unreadable graph")
+}
+
+/// Link counters at one refresh, keyed by link id.
+struct GraphSample {
+    at: Instant,
+    links: HashMap<String, LinkFrameDropsOutput>,
+}
+
+impl GraphSample {
+    fn new(at: Instant, graph: &GraphResponse) -> Self {
+        let links = graph
+            .links
+            .iter()
+            .filter_map(|link| {
+                let drops = link.components.get(LINK_FRAME_DROPS_COMPONENT)?;
+                let drops = serde_json::from_value(drops.clone()).ok()?;
+                Some((link.id.clone(), drops))
+            })
+            .collect();
+        Self { at, links }
+    }
+}
+
+/// One dashboard line.
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessorRow {
+    id: String,
+    processor_type: String,
+    state: String,
+    fps: Option<f64>,
+    latency_p50_ms: Option<f64>,
+    latency_p99_ms: Option<f64>,
+    /// Frames queued and combined capacity across the input mailboxes.
+    queue: Option<(u64, u64)>,
+    drops_per_sec: Option<f64>,
+    frames_dropped: u64,
+    congested: bool,
+}
+
+/// Per-link counter movement between two refreshes.
+#[derive(Debug, Clone, Copy, Default)]
+struct LinkDelta {
+    delivered: u64,
+    dropped: u64,
+}
+
+fn processor_rows(
+    graph: &GraphResponse,
+    current: &GraphSample,
+    previous: Option<&GraphSample>,
+) -> Vec<ProcessorRow> {
+    let elapsed_secs = previous
+        .map(|previous| current.at.duration_since(previous.at).as_secs_f64())
+        .filter(|secs| *secs > 0.0);
+    let link_delta = |link_id: &str| -> Option<LinkDelta> {
+        let now = current.links.get(link_id)?;
+        let before = previous?.links.get(link_id)?;
+        Some(LinkDelta {
+            delivered: now.frames_delivered.saturating_sub(before.frames_delivered),
+            dropped: now.frames_dropped.saturating_sub(before.frames_dropped),
+        })
+    };
+
+    graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let inputs: Vec<_> = graph
+                .links
+                .iter()
+                .filter(|link| link.target.processor_id == node.id)
+                .collect();
+            let outputs: Vec<_> = graph
+                .links
+                .iter()
+                .filter(|link| link.source.processor_id == node.id)
+                .collect();
+            let metrics = node
+                .components
+                .get(PROCESSOR_METRICS_COMPONENT)
+                .and_then(|metrics| {
+                    serde_json::from_value::<ProcessorMetricsOutput>(metrics.clone()).ok()
+                });
+
+            let input_deltas: Vec<_> = inputs
+                .iter()
+                .filter_map(|link| link_delta(&link.id))
+                .collect();
+            let per_sec = |frames: u64| elapsed_secs.map(|secs| frames as f64 / secs);
+            let fps = match &metrics {
+                Some(metrics) => Some(metrics.throughput_fps),
+                None if !input_deltas.is_empty() => {
+                    per_sec(input_deltas.iter().map(|delta| delta.delivered).sum())
+                }
+                None => {
+                    let output_deltas: Vec<_> = outputs
+                        .iter()
+                        .filter_map(|link| link_delta(&link.id))
+                        .collect();
+                    if output_deltas.is_empty() {
+                        None
+                    } else {
+                        per_sec(
+                            output_deltas
+                                .iter()
+                                .map(|delta| delta.delivered + delta.dropped)
+                                .sum(),
+                        )
+                    }
+                }
+            };
+
+            let input_counters: Vec<_> = inputs
+                .iter()
+                .filter_map(|link| current.links.get(&link.id).map(|drops| (link, drops)))
+                .collect();
+            let queue = (!input_counters.is_empty()).then(|| {
+                input_counters
+                    .iter()
+                    .fold((0, 0), |(queued, capacity), (link, drops)| {
+                        (queued + drops.queue_depth, capacity + link.capacity as u64)
+                    })
+            });
+            let drops_per_sec = if input_deltas.is_empty() {
+                None
+            } else {
+                per_sec(input_deltas.iter().map(|delta| delta.dropped).sum())
+            };
+
+            ProcessorRow {
+                id: node.id.clone(),
+                processor_type: format!(
+                    "@{}/{}/{}",
+                    node.processor_type.org,
+                    node.processor_type.package,
+                    node.processor_type.type_name
+                ),
+                state: node
+                    .components
+                    .get(PROCESSOR_STATE_COMPONENT)
+                    .and_then(|state| state.as_str())
+                    .unwrap_or("-")
+                    .to_string(),
+                fps,
+                latency_p50_ms: metrics.as_ref().map(|metrics| metrics.latency_p50_ms),
+                latency_p99_ms: metrics.as_ref().map(|metrics| metrics.latency_p99_ms),
+                queue,
+                drops_per_sec,
+                frames_dropped: input_counters
+                    .iter()
+                    .map(|(_, drops)| drops.frames_dropped)
+                    .sum(),
+                congested: input_counters.iter().any(|(_, drops)| drops.congested),
+            }
+        })
+        .collect()
+}
+
+fn render(url: &str, graph: &GraphResponse, rows: &[ProcessorRow], interval: Duration) -> String {
+    let mut text = format!(
+        "streamlib top — {url}   {} processors, {} links   every {}ms, Ctrl+C to quit\n\n",
+        graph.nodes.len(),
+        graph.links.len(),
+        interval.as_millis()
+    );
+    text.push_str(&format!(
+        "{:<id_width$} {:<type_width$} {:<10} {:>8} {:>8} {:>8} {:>9} {:>8} {:>9}\n",
+        "PROCESSOR",
+        "TYPE",
+        "STATE",
+        "FPS",
+        "P50 MS",
+        "P99 MS",
+        "QUEUE",
+        "DROP/S",
+        "DROPPED",
+        id_width = PROCESSOR_ID_WIDTH,
+        type_width = PROCESSOR_TYPE_WIDTH,
+    ));
+    for row in rows {
+        let queue = row
+            .queue
+            .map(|(queued, capacity)| format!("{queued}/{capacity}"))
+            .unwrap_or_else(|| "-".to_string());
+        let congested = if row.congested { "!" } else { "" };
+        text.push_str(&format!(
+            "{:<id_width$} {:<type_width$} {:<10} {:>8} {:>8} {:>8} {:>9} {:>8} {:>9}\n",
+            truncate(&row.id, PROCESSOR_ID_WIDTH),
+            truncate(&row.processor_type, PROCESSOR_TYPE_WIDTH),
+            row.state,
+            decimal(row.fps, 1),
+            decimal(row.latency_p50_ms, 2),
+            decimal(row.latency_p99_ms, 2),
+            queue,
+            decimal(row.drops_per_sec, 1),
+            format!("{}{congested}", row.frames_dropped),
+            id_width = PROCESSOR_ID_WIDTH,
+            type_width = PROCESSOR_TYPE_WIDTH,
+        ));
+    }
+    text
+}
+
+fn decimal(value: Option<f64>, precision: usize) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{value:.precision$}"))
+}
+
+/// `value` cut to `width` characters, ending in `…` when shortened.
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+    let mut shortened: String = value.chars().take(width - 1).collect();
+    shortened.push('…');
+    shortened
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn node(id: &str, type_name: &str) -> Value {
+        json!({
+            "id": id,
+            "type": {
+                "org": "tatolab",
+                "package": "core",
+                "type": type_name,
+                "version": { "major": 1, "minor": 0, "patch": 0 },
+            },
+            "display_name": type_name,
+            "ports": { "inputs": [], "outputs": [] },
+            "components": { "state": "running" },
+        })
+    }
+
+    fn graph(delivered: u64, dropped: u64, queued: u64) -> GraphResponse {
+        serde_json::from_value(json!({
+            "nodes": [node("camera", "Camera"), node("display", "Display")],
+            "links": [{
+                "id": "camera-display",
+                "source": { "processor_id": "camera", "port_name": "video" },
+                "target": { "processor_id": "display", "port_name": "video" },
+                "capacity": 4,
+                "state": "wired",
+                "components": {
+                    "frame_drops": {
+                        "frames_delivered": delivered,
+                        "frames_dropped": dropped,
+                        "queue_depth": queued,
+                        "window_secs": 5,
+                        "window_frames_dropped": dropped,
+                        "window_drop_rate": 0.0,
+                        "congested": dropped > 0,
+                    },
+                },
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rates_come_from_counter_deltas_between_refreshes() {
+        let start = Instant::now();
+        let before = graph(100, 0, 1);
+        let after = graph(160, 2, 3);
+        let previous = GraphSample::new(start, &before);
+        let current = GraphSample::new(start + Duration::from_secs(2), &after);
+
+        let first = processor_rows(&before, &previous, None);
+        assert_eq!(first[1].fps, None);
+        assert_eq!(first[1].queue, Some((1, 4)));
+
+        let rows = processor_rows(&after, &current, Some(&previous));
+        let (camera, display) = (&rows[0], &rows[1]);
+        assert_eq!(display.fps, Some(30.0));
+        assert_eq!(display.drops_per_sec, Some(1.0));
+        assert_eq!(display.frames_dropped, 2);
+        assert!(display.congested);
+        assert_eq!(display.queue, Some((3, 4)));
+        assert_eq!(display.latency_p50_ms, None);
+
+        // A source's rate is what it pushed into its outputs, kept or not.
+        assert_eq!(camera.fps, Some(31.0));
+        assert_eq!(camera.queue, None);
+        assert_eq!(camera.state, "running");
+    }
+
+    #[test]
+    fn render_shows_unknown_values_as_dashes() {
+        let graph = graph(0, 0, 0);
+        let sample = GraphSample::new(Instant::now(), &graph);
+        let rows = processor_rows(&graph, &sample, None);
+        let text = render("http://node", &graph, &rows, Duration::from_millis(500));
+
+        assert!(text.contains("2 processors, 1 links"), "{text}");
+        let camera = text
+            .lines()
+            .find(|line| line.starts_with("camera "))
+            .unwrap();
+        assert!(camera.contains("@tatolab/core/Camera"), "{camera}");
+        assert_eq!(
+            camera
+                .split_whitespace()
+                .filter(|cell| *cell == "-")
+                .count(),
+            5
+        );
+        assert_eq!(truncate("abcdef", 4), "abc…");
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2025 Jonathan Fontanez
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `streamlib validate graph.json` — check a graph snapshot the way
+//! `streamlib-runtime --snapshot` would load it, without starting anything.
+//!
+//! The host-side api-server processor types are registered as the runtime
+//! registers them, and every other referenced package is resolved and built
+//! the same way — by version, from a linked checkout or the configured
+//! package source. Then [`GraphSnapshot::check`] dry-runs the graph: aliases,
+//! processor types, ports, schema agreement, cycles and unlinked required
+//! inputs. Every problem is listed, not just the first; the command fails
+//! when there are errors, or — with `--strict` — warnings.
+//!
+//! [`GraphSnapshot::check`]: streamlib::sdk::graph_snapshot::GraphSnapshot::check
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use streamlib::sdk::RunnerAutoBuild;
+use streamlib::sdk::graph_snapshot::{GraphSnapshot, GraphSnapshotCheck};
+use streamlib::sdk::runtime::Runner;
+
+/// Validate the snapshot at `path`. `strict` fails on schema-mismatch
+/// warnings too.
+pub async fn run(path: &Path, strict: bool) -> Result<()> {
+    let snapshot = GraphSnapshot::from_json_file(path)?;
+
+    streamlib_api_server::register_processors();
+    let runner = Runner::with_auto_build()?;
+    let check = runner.check_graph_snapshot_with_resolving(&snapshot).await;
+
+    print!("{}", render_check(path, &snapshot, &check));
+    if !check.errors.is_empty() || (strict && !check.warnings.is_empty()) {
+        bail!("{} is not a valid graph", path.display());
+    }
+    Ok(())
+}
+
+/// One line per problem, errors first, then a count line.
+fn render_check(path: &Path, snapshot: &GraphSnapshot, check: &GraphSnapshotCheck) -> String {
+    let mut text = format!(
+        "{}: {} processor(s), {} connection(s)\n",
+        path.display(),
+        snapshot.processors.len(),
+        snapshot.connections.len()
+    );
+    for error in &check.errors {
+        text.push_str(&format!("error: {error}\n"));
+    }
+    for warning in &check.warnings {
+        text.push_str(&format!("warning: {warning}\n"));
+    }
+    if check.is_clean() {
+        text.push_str("ok\n");
+    } else {
+        text.push_str(&format!(
+            "{} error(s), {} warning(s)\n",
+            check.errors.len(),
+            check.warnings.len()
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use streamlib::sdk::error::{Error, PortDirection};
+
+    #[test]
+    fn problems_are_listed_errors_first() {
+        let snapshot = GraphSnapshot::from_json_str(r#"{ "processors": [] }"#).unwrap();
+        let check = GraphSnapshotCheck {
+            errors: vec![Error::ProcessorPortNotFound {
+                processor_id: "camera".to_string(),
+                port_name: "video".to_string(),
+                direction: PortDirection::Output,
+            }],
+            warnings: vec![Error::SchemaIdentMismatch {
+                from_processor: "camera".to_string(),
+                from_port: "video".to_string(),
+                to_processor: "display".to_string(),
+                to_port: "video".to_string(),
+                producer_schema: "@tatolab/core/VideoFrame@1.0.0".to_string(),
+                consumer_schema: "@tatolab/core/AudioFrame@1.0.0".to_string(),
+            }],
+        };
+
+        let text = render_check(Path::new("graph.json"), &snapshot, &check);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "graph.json: 0 processor(s), 0 connection(s)");
+        assert!(lines[1].starts_with("error: "), "{text}");
+        assert!(lines[2].starts_with("warning: "), "{text}");
+        assert_eq!(lines[3], "1 error(s), 1 warning(s)");
+
+        let clean = render_check(
+            Path::new("graph.json"),
+            &snapshot,
+            &GraphSnapshotCheck::default(),
+        );
+        assert!(clean.ends_with("ok\n"), "{clean}");
+    }
+}
@@ -117,21 +117,95 @@ enum Commands {
     Nodes,
 
     /// Export a running node's live graph (processors, links, states, metrics)
-    /// as JSON via its control plane.
+    /// as JSON via its control plane, or edit it with a subcommand.
     ///
     /// Target selection (shared by every control verb): `--url` pins an explicit
     /// endpoint; `--node <runtime_id>` resolves one from the registry; with
     /// neither, the sole live node is used (an error lists candidates when zero
     /// or more than one is live).
     Graph {
+        #[command(subcommand)]
+        action: Option<GraphCommands>,
+
         /// Control-plane base URL of the target node (its `POST /mcp` host).
-        #[arg(long, value_name = "URL")]
+        #[arg(long, value_name = "URL", global = true)]
         url: Option<String>,
 
         /// Registered runtime_id to target instead of `--url` (resolved via the
         /// node registry).
+        #[arg(long, value_name = "RUNTIME_ID", conflicts_with = "url", global = true)]
+        node: Option<String>,
+    },
+
+    /// Live per-processor dashboard for a running node, refreshed in place —
+    /// `htop` for a graph.
+    ///
+    /// Each refresh reads the graph through the control plane and shows every
+    /// processor's state, frame rate, reported p50/p99 processing latency,
+    /// input queue depth against capacity, and frames dropped on its inputs
+    /// (per second and in total, `!` when a link is congested). Rates are
+    /// counter deltas between refreshes, so the first screen shows totals only.
+    Top {
+        /// Control-plane base URL of the target node (its `POST /mcp` host).
+        #[arg(long, value_name = "URL")]
+        url: Option<String>,
+
+        /// Registered runtime_id to target instead of `--url`.
         #[arg(long, value_name = "RUNTIME_ID", conflicts_with = "url")]
         node: Option<String>,
+
+        /// Milliseconds between refreshes.
+        #[arg(long, value_name = "MS", default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+        interval_ms: u64,
+
+        /// Stop after this many refreshes instead of running until Ctrl+C.
+        #[arg(long, value_name = "COUNT", value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: Option<u64>,
+    },
+
+    /// Run the transport and codec benchmark matrix on this machine.
+    ///
+    /// Pushes opaque payloads and encoded synthetic frames through a local
+    /// iceoryx2 channel and writes `bench.json` and `bench.html` for
+    /// comparing machines and releases.
+    Bench {
+        /// Opaque payload sizes in bytes, comma-separated.
+        #[arg(long, value_name = "BYTES", value_delimiter = ',', default_values_t = [256, 4096, 65536, 1048576])]
+        payload_sizes: Vec<usize>,
+
+        /// Frame resolutions as WIDTHxHEIGHT, comma-separated.
+        #[arg(long, value_name = "WxH", value_delimiter = ',', default_values = ["640x360", "1280x720", "1920x1080"], value_parser = commands::bench::parse_resolution)]
+        resolutions: Vec<commands::bench::Resolution>,
+
+        /// Frame codecs, comma-separated.
+        #[arg(long, value_name = "CODEC", value_delimiter = ',', default_values = ["raw", "jpeg"])]
+        codecs: Vec<commands::bench::BenchCodec>,
+
+        /// Timed frames per case.
+        #[arg(long, value_name = "COUNT", default_value_t = 300, value_parser = clap::value_parser!(u32).range(1..))]
+        frames: u32,
+
+        /// Directory `bench.json` and `bench.html` are written to.
+        #[arg(long, value_name = "DIR", default_value = "streamlib-bench")]
+        output_dir: PathBuf,
+
+        /// An earlier run's `bench.json` to compare fps against.
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+    },
+
+    /// Check a graph snapshot file without starting anything.
+    ///
+    /// Resolves the processor types it references the way
+    /// `streamlib-runtime --snapshot` would, then reports every alias, type,
+    /// port, schema, cycle and required-input problem at once.
+    Validate {
+        /// Graph snapshot JSON file.
+        graph: PathBuf,
+
+        /// Fail on schema-mismatch warnings as well as errors.
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Author a processor from source and submit it into a running node's graph.
@@ -260,6 +334,58 @@ enum Commands {
         count: Option<usize>,
     },
 
+    /// Open an interactive shell on a running node: add, connect, start/stop,
+    /// `param set`, tap and snapshot, with tab completion fed by the node's
+    /// registry and live graph.
+    Repl {
+        /// Control-plane base URL of the target node (its `POST /mcp` host).
+        #[arg(long, value_name = "URL")]
+        url: Option<String>,
+
+        /// Registered runtime_id to target instead of `--url` (resolved via the
+        /// node registry).
+        #[arg(
+            short = 'r',
+            long = "runtime",
+            alias = "node",
+            value_name = "RUNTIME_ID",
+            conflicts_with = "url"
+        )]
+        runtime: Option<String>,
+    },
+
+    /// Scaffold a ready-to-run pipeline from a template.
+    ///
+    /// Writes `<name>.json` (a graph snapshot `streamlib-runtime --snapshot`
+    /// loads) plus the template's example config into `--dir`. Built-in
+    /// templates: `camera-to-whip`, `file-transcode`, `multiview-monitor`.
+    /// Installed packages add their own via a `templates/<name>/` folder in
+    /// their `streamlib_modules/@org/name/` slot; `--list` shows every
+    /// available template.
+    New {
+        /// Template to render.
+        #[arg(long, value_name = "TEMPLATE", required_unless_present = "list")]
+        template: Option<String>,
+
+        /// Pipeline name, used for the pipeline file name and its `name`
+        /// (default: the template name).
+        #[arg(long, value_name = "NAME", conflicts_with = "list")]
+        name: Option<String>,
+
+        /// Directory to write into and whose `streamlib_modules/` is searched
+        /// for package templates (default: current working directory).
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Overwrite files that already exist.
+        #[arg(long, conflicts_with = "list")]
+        force: bool,
+
+        /// List available templates instead of rendering one.
+        #[arg(long, conflicts_with = "template")]
+        list: bool,
+    },
+
     /// Setup commands
     Setup {
         #[command(subcommand)]
@@ -457,6 +583,47 @@ enum Commands {
     },
 }
 
+/// Edits of a running node's graph, over the REST routes the web UI uses.
+#[derive(Subcommand)]
+enum GraphCommands {
+    /// Instantiate a registered processor type and print its id.
+    AddProcessor {
+        /// `@org/package/Type@version`, or a bare `Type` exactly one registered
+        /// processor carries.
+        processor_type: String,
+
+        /// Processor config as a JSON object (default `{}`).
+        #[arg(long, value_name = "JSON")]
+        config: Option<String>,
+    },
+    /// Remove a processor and every link touching it.
+    RemoveProcessor { processor_id: String },
+    /// Link an output port to an input port and print the link id.
+    Connect {
+        /// Source output port as `processor.port`.
+        from: String,
+        /// Destination input port as `processor.port`.
+        to: String,
+    },
+    /// Remove a link by id.
+    Disconnect { link_id: String },
+    /// Replace or edit a processor's config.
+    ///
+    /// With CONFIG the whole config is replaced; without it the current config
+    /// is read from the graph. Each `--set key.path=value` is then applied on
+    /// top (values parse as JSON, else as bare strings).
+    SetConfig {
+        processor_id: String,
+
+        /// Replacement config as a JSON object.
+        config: Option<String>,
+
+        /// Set one config value; repeatable.
+        #[arg(long = "set", value_name = "KEY.PATH=VALUE")]
+        assignments: Vec<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum SetupCommands {
     /// Configure shell to add streamlib to PATH
@@ -588,9 +755,71 @@ async fn async_main(cli: Cli) -> Result<()> {
         }
         Some(Commands::Mcp { attach }) => commands::mcp::run(attach).await?,
         Some(Commands::Nodes) => commands::nodes::run()?,
-        Some(Commands::Graph { url, node }) => {
+        Some(Commands::Graph { action, url, node }) => {
+            let url = commands::control::resolve_control_url(url, node)?;
+            match action {
+                None => commands::control::graph(&url)?,
+                Some(GraphCommands::AddProcessor {
+                    processor_type,
+                    config,
+                }) => {
+                    commands::graph_edit::add_processor(&url, &processor_type, config.as_deref())?
+                }
+                Some(GraphCommands::RemoveProcessor { processor_id }) => {
+                    commands::graph_edit::remove_processor(&url, &processor_id)?
+                }
+                Some(GraphCommands::Connect { from, to }) => {
+                    commands::graph_edit::connect(&url, &from, &to)?
+                }
+                Some(GraphCommands::Disconnect { link_id }) => {
+                    commands::graph_edit::disconnect(&url, &link_id)?
+                }
+                Some(GraphCommands::SetConfig {
+                    processor_id,
+                    config,
+                    assignments,
+                }) => commands::graph_edit::set_config(
+                    &url,
+                    &processor_id,
+                    config.as_deref(),
+                    &assignments,
+                )?,
+            }
+        }
+        Some(Commands::Top {
+            url,
+            node,
+            interval_ms,
+            iterations,
+        }) => {
             let url = commands::control::resolve_control_url(url, node)?;
-            commands::control::graph(&url)?
+            let args = commands::top::TopArgs {
+                url,
+                interval: std::time::Duration::from_millis(interval_ms),
+                iterations,
+            };
+            tokio::task::spawn_blocking(move || commands::top::run(args)).await??
+        }
+        Some(Commands::Bench {
+            payload_sizes,
+            resolutions,
+            codecs,
+            frames,
+            output_dir,
+            baseline,
+        }) => {
+            let args = commands::bench::BenchArgs {
+                payload_sizes,
+                resolutions,
+                codecs,
+                frames,
+                output_dir,
+                baseline,
+            };
+            tokio::task::spawn_blocking(move || commands::bench::run(args)).await??
+        }
+        Some(Commands::Validate { graph, strict }) => {
+            commands::validate::run(&graph, strict).await?
         }
         Some(Commands::Submit {
             url,
@@ -652,6 +881,22 @@ async fn async_main(cli: Cli) -> Result<()> {
             let url = commands::control::resolve_control_url(url, node)?;
             commands::control::tap(&url, &channel, count)?
         }
+        Some(Commands::Repl { url, runtime }) => {
+            let url = commands::control::resolve_control_url(url, runtime)?;
+            commands::repl::run(&url)?
+        }
+        Some(Commands::New {
+            template,
+            name,
+            dir,
+            force,
+            list,
+        }) => match template {
+            Some(template) if !list => {
+                commands::new::run(&template, name.as_deref(), dir.as_deref(), force)?
+            }
+            _ => commands::new::list(dir.as_deref())?,
+        },
         Some(Commands::Setup { action }) => match action {
             SetupCommands::Shell { shell } => commands::setup::shell(shell.as_deref())?,
         },
@@ -827,4 +1072,82 @@ mod tests {
         Cli::try_parse_from(["streamlib", "logs", "--node", "Rnode", "--count", "5"])
             .expect("`logs --node ... --count N` must parse");
     }
+
+    /// `new` renders a template or lists them — never neither, never both.
+    #[test]
+    fn new_requires_exactly_one_of_template_or_list() {
+        assert!(Cli::try_parse_from(["streamlib", "new"]).is_err());
+        assert!(
+            Cli::try_parse_from(["streamlib", "new", "--template", "file-transcode", "--list"])
+                .is_err()
+        );
+        Cli::try_parse_from(["streamlib", "new", "--template", "file-transcode"])
+            .expect("`new --template <name>` must parse");
+        Cli::try_parse_from(["streamlib", "new", "--list"]).expect("`new --list` must parse");
+    }
+
+    /// `graph` alone still exports; its edit subcommands take `--url` / `--node`
+    /// on either side of the verb.
+    #[test]
+    fn graph_edits_parse_with_the_target_after_the_verb() {
+        let cli = Cli::try_parse_from(["streamlib", "graph", "--node", "rt-1"])
+            .expect("bare `graph` must parse");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Graph { action: None, .. })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "streamlib",
+            "graph",
+            "set-config",
+            "cam",
+            "--set",
+            "fps=30",
+            "--set",
+            "device=/dev/video2",
+            "--url",
+            "http://x",
+        ])
+        .expect("`graph set-config ... --url` must parse");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Graph {
+                action: Some(GraphCommands::SetConfig { ref assignments, config: None, .. }),
+                url: Some(_),
+                ..
+            }) if assignments.len() == 2
+        ));
+        assert!(
+            Cli::try_parse_from([
+                "streamlib",
+                "graph",
+                "connect",
+                "a.out",
+                "b.in",
+                "--url",
+                "http://x",
+                "--node",
+                "rt-1",
+            ])
+            .is_err()
+        );
+    }
+
+    /// `repl -r <runtime>` targets a registered node; `-r` and `--url` are
+    /// mutually exclusive like every control verb's `--node` / `--url`.
+    #[test]
+    fn repl_targets_a_runtime_or_a_url() {
+        let cli = Cli::try_parse_from(["streamlib", "repl", "-r", "rt-1"])
+            .expect("`repl -r <runtime>` must parse");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Repl { runtime: Some(ref runtime), url: None }) if runtime == "rt-1"
+        ));
+        Cli::try_parse_from(["streamlib", "repl", "--node", "rt-1"])
+            .expect("`repl --node` stays accepted as an alias");
+        assert!(
+            Cli::try_parse_from(["streamlib", "repl", "-r", "rt-1", "--url", "http://x"]).is_err()
+        );
+    }
 }